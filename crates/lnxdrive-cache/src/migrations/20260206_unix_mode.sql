@@ -0,0 +1,5 @@
+-- LNXDrive Unix permissions
+
+-- Mode bits set through the FUSE mount (chmod). OneDrive has no notion of
+-- Unix permissions, so they only live here and are restored on remount.
+ALTER TABLE sync_items ADD COLUMN unix_mode INTEGER;
@@ -0,0 +1,6 @@
+-- LNXDrive delta resumption
+
+-- nextLink of a delta page walk interrupted after some of its pages were
+-- applied. The committed token stays in delta_token; a restart continues
+-- the walk from here instead of enumerating the whole delta again.
+ALTER TABLE accounts ADD COLUMN delta_next_link TEXT;
@@ -4,6 +4,7 @@
 //! - Automatic directory creation for database files
 //! - WAL journal mode for concurrent reads
 //! - Automatic schema migration on first connection
+//! - Read-only mode for short-lived readers that must not migrate
 //! - In-memory mode for testing
 
 use std::path::Path;
@@ -78,6 +79,37 @@ impl DatabasePool {
         Ok(Self { pool })
     }
 
+    /// Opens an existing database file for reading only
+    ///
+    /// Unlike [`new`](Self::new), the file is neither created nor migrated,
+    /// so this is cheap enough for commands polled many times a second and
+    /// never races the daemon's writes. A single connection is used.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CacheError::ConnectionFailed` if the file does not exist or
+    /// cannot be opened.
+    pub async fn open_read_only(db_path: &Path) -> Result<Self, CacheError> {
+        let options = SqliteConnectOptions::new()
+            .filename(db_path)
+            .read_only(true)
+            .busy_timeout(std::time::Duration::from_secs(1));
+
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect_with(options)
+            .await
+            .map_err(|e| {
+                CacheError::ConnectionFailed(format!(
+                    "Failed to open database at {} read-only: {}",
+                    db_path.display(),
+                    e
+                ))
+            })?;
+
+        Ok(Self { pool })
+    }
+
     /// Creates an in-memory database pool for testing
     ///
     /// Uses a single connection to ensure data persistence across queries
@@ -116,6 +148,32 @@ impl DatabasePool {
         &self.pool
     }
 
+    /// Runs a trivial query to check that the database is reachable
+    ///
+    /// # Errors
+    ///
+    /// Returns `CacheError::QueryFailed` if no connection can serve the query.
+    pub async fn ping(&self) -> Result<(), CacheError> {
+        sqlx::query("SELECT 1").execute(&self.pool).await?;
+        Ok(())
+    }
+
+    /// Runs SQLite's `PRAGMA quick_check` on the database
+    ///
+    /// Returns the problems SQLite reported, or an empty list if the
+    /// database is intact.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CacheError::QueryFailed` if the check cannot run at all,
+    /// e.g. because the file is not a database.
+    pub async fn check_integrity(&self) -> Result<Vec<String>, CacheError> {
+        let rows: Vec<String> = sqlx::query_scalar("PRAGMA quick_check")
+            .fetch_all(&self.pool)
+            .await?;
+        Ok(rows.into_iter().filter(|row| row != "ok").collect())
+    }
+
     /// Runs all schema migrations in order
     async fn run_migrations(pool: &SqlitePool) -> Result<(), CacheError> {
         // Create migration tracking table
@@ -141,6 +199,18 @@ impl DatabasePool {
                 "20260204_fuse_support",
                 include_str!("migrations/20260204_fuse_support.sql"),
             ),
+            (
+                "20260205_sync_history",
+                include_str!("migrations/20260205_sync_history.sql"),
+            ),
+            (
+                "20260206_unix_mode",
+                include_str!("migrations/20260206_unix_mode.sql"),
+            ),
+            (
+                "20260207_delta_next_link",
+                include_str!("migrations/20260207_delta_next_link.sql"),
+            ),
         ];
 
         for (name, sql) in migrations {
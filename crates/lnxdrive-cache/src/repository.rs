@@ -35,11 +35,12 @@ use lnxdrive_core::{
         session::{SessionError, SessionStatus},
         sync_item::ItemState,
         Account, AccountState, AuditAction, AuditEntry, AuditResult, Conflict, Resolution,
-        ResolutionSource, SyncItem, SyncSession, VersionInfo,
+        ResolutionSource, SyncHistoryEntry, SyncItem, SyncSession, TransitionTrigger, VersionInfo,
+        MAX_SYNC_HISTORY_ENTRIES,
     },
     ports::{IStateRepository, ItemFilter},
 };
-use sqlx::{sqlite::SqliteRow, Row, SqlitePool};
+use sqlx::{sqlite::SqliteRow, Row, SqliteConnection, SqlitePool};
 
 use crate::CacheError;
 
@@ -47,14 +48,31 @@ use crate::CacheError;
 ///
 /// Provides persistent storage for all domain entities using SQLite.
 /// All operations are performed through a connection pool for concurrency.
+///
+/// Saving an item whose state changed records a
+/// [`AuditAction::StateTransition`] entry in the same connection, so the
+/// audit log explains every state an item went through. The entries carry
+/// the trigger of the repository, [`TransitionTrigger::Sync`] unless set
+/// with [`with_transition_trigger`](Self::with_transition_trigger).
 pub struct SqliteStateRepository {
     pool: SqlitePool,
+    trigger: TransitionTrigger,
 }
 
 impl SqliteStateRepository {
     /// Creates a new repository instance with the given connection pool
     pub fn new(pool: SqlitePool) -> Self {
-        Self { pool }
+        Self {
+            pool,
+            trigger: TransitionTrigger::default(),
+        }
+    }
+
+    /// Sets the trigger recorded for the state transitions saved through
+    /// this repository
+    pub fn with_transition_trigger(mut self, trigger: TransitionTrigger) -> Self {
+        self.trigger = trigger;
+        self
     }
 }
 
@@ -192,6 +210,10 @@ fn sync_item_from_row(row: &SqliteRow) -> Result<SyncItem, CacheError> {
     let last_modified_remote_str: Option<String> = row.get("last_modified_remote");
     let metadata_str: String = row.get("metadata");
     let error_info_str: Option<String> = row.get("error_info");
+    let unix_mode: Option<i64> = row.get("unix_mode");
+    // Only written by the FUSE layer; absent from partial selects
+    let inode: Option<i64> = row.try_get("inode").ok().flatten();
+    let last_accessed_str: Option<String> = row.try_get("last_accessed").ok().flatten();
 
     // Parse the state string to the serde-compatible JSON representation
     let state = item_state_from_string(&state_str)?;
@@ -238,6 +260,11 @@ fn sync_item_from_row(row: &SqliteRow) -> Result<SyncItem, CacheError> {
         None => serde_json::Value::Null,
     };
 
+    let last_accessed_val = match parse_optional_datetime(last_accessed_str)? {
+        Some(dt) => serde_json::Value::String(dt.to_rfc3339()),
+        None => serde_json::Value::Null,
+    };
+
     // Parse complex JSON fields
     let error_info_val: serde_json::Value = match error_info_str {
         Some(ref s) if !s.is_empty() => serde_json::from_str(s).unwrap_or(serde_json::Value::Null),
@@ -262,6 +289,9 @@ fn sync_item_from_row(row: &SqliteRow) -> Result<SyncItem, CacheError> {
         "last_modified_remote": last_modified_remote_val,
         "metadata": metadata_val,
         "error_info": error_info_val,
+        "unix_mode": unix_mode,
+        "inode": inode.map(|ino| ino as u64),
+        "last_accessed": last_accessed_val,
     });
 
     let item: SyncItem = serde_json::from_value(item_json).map_err(|e| {
@@ -281,6 +311,7 @@ fn account_from_row(row: &SqliteRow) -> Result<Account, CacheError> {
     let quota_used: i64 = row.get("quota_used");
     let quota_total: i64 = row.get("quota_total");
     let delta_token_str: Option<String> = row.get("delta_token");
+    let delta_next_link: Option<String> = row.get("delta_next_link");
     let last_sync_str: Option<String> = row.get("last_sync");
     let state_str: String = row.get("state");
     let created_at_str: String = row.get("created_at");
@@ -317,6 +348,7 @@ fn account_from_row(row: &SqliteRow) -> Result<Account, CacheError> {
             }
         }
     }
+    account.set_delta_next_link(delta_next_link);
 
     Ok(account)
 }
@@ -458,6 +490,35 @@ fn audit_entry_from_row(row: &SqliteRow) -> Result<AuditEntry, CacheError> {
     Ok(entry)
 }
 
+/// Reconstruct a SyncHistoryEntry from a database row
+fn sync_history_from_row(row: &SqliteRow) -> Result<SyncHistoryEntry, CacheError> {
+    let started_at_str: String = row.get("started_at");
+    let finished_at_str: String = row.get("finished_at");
+    let errors_str: String = row.get("errors");
+    let success: i64 = row.get("success");
+
+    let errors: Vec<String> = serde_json::from_str(&errors_str).map_err(|e| {
+        CacheError::SerializationError(format!(
+            "Invalid sync history errors '{}': {}",
+            errors_str, e
+        ))
+    })?;
+
+    Ok(SyncHistoryEntry {
+        id: Some(row.get("id")),
+        started_at: parse_datetime(&started_at_str)?,
+        finished_at: parse_datetime(&finished_at_str)?,
+        files_downloaded: row.get::<i64, _>("files_downloaded") as u32,
+        files_uploaded: row.get::<i64, _>("files_uploaded") as u32,
+        files_deleted: row.get::<i64, _>("files_deleted") as u32,
+        bytes_downloaded: row.get::<i64, _>("bytes_downloaded") as u64,
+        bytes_uploaded: row.get::<i64, _>("bytes_uploaded") as u64,
+        errors,
+        duration_ms: row.get::<i64, _>("duration_ms") as u64,
+        success: success != 0,
+    })
+}
+
 /// Reconstruct a Conflict from a database row
 fn conflict_from_row(row: &SqliteRow) -> Result<Conflict, CacheError> {
     let id_str: String = row.get("id");
@@ -534,93 +595,223 @@ fn conflict_from_row(row: &SqliteRow) -> Result<Conflict, CacheError> {
 // IStateRepository implementation
 // ============================================================================
 
+/// Number of items written per transaction by `save_items_batch`
+///
+/// Keeps each write transaction short, so writers on other connections
+/// (e.g. the FUSE write serializer) only wait for one chunk at a time.
+pub const SAVE_BATCH_CHUNK: usize = 500;
+
+/// Key of the folder selection in the `config` table
+const SELECTED_FOLDERS_KEY: &str = "selected_folders";
+
+/// Inserts or replaces a sync item on the given connection
+async fn upsert_item(
+    conn: &mut SqliteConnection,
+    item: &SyncItem,
+    trigger: TransitionTrigger,
+) -> anyhow::Result<()> {
+    let id = item.id().to_string();
+    // We need the account_id from the sync_items table context.
+    // SyncItem doesn't carry account_id directly - it's part of the DB schema.
+    // For UPSERT, we'll need to handle this. Let's check if there's an existing
+    // row to get the account_id, or we'll store the item without it initially.
+    //
+    // Looking at the schema, account_id is NOT NULL.
+    // Since SyncItem doesn't have an account_id field, we need to handle this
+    // through the query_items filter. For save_item, the account_id should
+    // already exist in the DB row (update case) or be provided.
+    //
+    // For now, we'll use a sub-query approach: if the item exists, keep the
+    // existing account_id. For new items, we'll use the default account.
+    // However, this is a limitation. Let's use a pragmatic approach:
+    // attempt to get the existing account_id, or fall back to the first account.
+
+    let local_path = item.local_path().to_string();
+    let remote_id = item.remote_id().map(|r| r.as_str().to_string());
+    let remote_path = item.remote_path().as_str().to_string();
+    let state = item_state_to_string(item.state());
+    let content_hash = item.content_hash().map(|h| h.as_str().to_string());
+    let local_hash = item.local_hash().map(|h| h.as_str().to_string());
+    let size_bytes = item.size_bytes() as i64;
+    let last_sync = item.last_sync().map(|dt| dt.to_rfc3339());
+    let last_modified_local = item.last_modified_local().map(|dt| dt.to_rfc3339());
+    let last_modified_remote = item.last_modified_remote().map(|dt| dt.to_rfc3339());
+    let metadata = serde_json::to_string(item.metadata())
+        .map_err(|e| anyhow::anyhow!("Failed to serialize metadata: {}", e))?;
+    let error_info = match item.error_info() {
+        Some(ei) => Some(
+            serde_json::to_string(ei)
+                .map_err(|e| anyhow::anyhow!("Failed to serialize error_info: {}", e))?,
+        ),
+        None => None,
+    };
+
+    let unix_mode = item.unix_mode().map(i64::from);
+
+    // Try to get existing account_id for this item, or use first account.
+    // The previous state tells whether the save is a state transition.
+    let existing: Option<(String, String, Option<i64>)> =
+        sqlx::query_as("SELECT account_id, state, inode FROM sync_items WHERE id = ?")
+            .bind(&id)
+            .fetch_optional(&mut *conn)
+            .await?;
+    let previous_state = match &existing {
+        Some((_, previous, _)) if *previous != state => Some(item_state_from_string(previous)?),
+        _ => None,
+    };
+
+    // Keep an inode assigned by FUSE when the item was loaded before it
+    // got one, so the number survives remounts
+    let inode = item
+        .inode()
+        .map(|ino| ino as i64)
+        .or_else(|| existing.as_ref().and_then(|(_, _, ino)| *ino));
+
+    let account_id = match existing {
+        Some((aid, _, _)) => aid,
+        None => {
+            // Get the first/default account
+            let default_aid: Option<String> =
+                sqlx::query_scalar("SELECT id FROM accounts ORDER BY created_at ASC LIMIT 1")
+                    .fetch_optional(&mut *conn)
+                    .await?;
+            default_aid
+                .ok_or_else(|| anyhow::anyhow!("No account found to associate with sync item"))?
+        }
+    };
+
+    // Upsert rather than INSERT OR REPLACE: REPLACE deletes the existing
+    // row, which would cascade to the conflicts recorded for the item.
+    // Other items holding the same path, remote ID or inode are still
+    // replaced, as before.
+    sqlx::query(
+        "DELETE FROM sync_items WHERE id != ? AND ( \
+          (account_id = ? AND (local_path = ? OR remote_id = ?)) OR inode = ?)",
+    )
+    .bind(&id)
+    .bind(&account_id)
+    .bind(&local_path)
+    .bind(&remote_id)
+    .bind(inode)
+    .execute(&mut *conn)
+    .await?;
+
+    sqlx::query(
+        "INSERT INTO sync_items \
+         (id, account_id, local_path, remote_id, remote_path, state, \
+          content_hash, local_hash, size_bytes, last_sync, \
+          last_modified_local, last_modified_remote, metadata, error_info, unix_mode, inode) \
+         VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?) \
+         ON CONFLICT(id) DO UPDATE SET \
+          account_id = excluded.account_id, local_path = excluded.local_path, \
+          remote_id = excluded.remote_id, remote_path = excluded.remote_path, \
+          state = excluded.state, content_hash = excluded.content_hash, \
+          local_hash = excluded.local_hash, size_bytes = excluded.size_bytes, \
+          last_sync = excluded.last_sync, last_modified_local = excluded.last_modified_local, \
+          last_modified_remote = excluded.last_modified_remote, metadata = excluded.metadata, \
+          error_info = excluded.error_info, unix_mode = excluded.unix_mode, \
+          inode = excluded.inode",
+    )
+    .bind(&id)
+    .bind(&account_id)
+    .bind(&local_path)
+    .bind(&remote_id)
+    .bind(&remote_path)
+    .bind(&state)
+    .bind(&content_hash)
+    .bind(&local_hash)
+    .bind(size_bytes)
+    .bind(&last_sync)
+    .bind(&last_modified_local)
+    .bind(&last_modified_remote)
+    .bind(&metadata)
+    .bind(&error_info)
+    .bind(unix_mode)
+    .bind(inode)
+    .execute(&mut *conn)
+    .await?;
+
+    if let Some(previous_state) = previous_state {
+        let entry =
+            AuditEntry::state_transition(*item.id(), &previous_state, item.state(), trigger);
+        insert_audit(conn, &entry).await?;
+    }
+
+    tracing::trace!(item_id = %id, "Saved sync item");
+    Ok(())
+}
+
+/// Inserts an audit entry through `conn`
+async fn insert_audit(conn: &mut SqliteConnection, entry: &AuditEntry) -> anyhow::Result<()> {
+    let timestamp = entry.timestamp().to_rfc3339();
+    let session_id = entry.session_id().map(|s| s.to_string());
+    let item_id = entry.item_id().map(|i| i.to_string());
+    let action = entry.action().to_string();
+    let result = serde_json::to_string(entry.result())
+        .map_err(|e| anyhow::anyhow!("Failed to serialize audit result: {}", e))?;
+    let details = serde_json::to_string(entry.details())
+        .map_err(|e| anyhow::anyhow!("Failed to serialize audit details: {}", e))?;
+    let duration_ms = entry.duration_ms().map(|d| d as i64);
+
+    sqlx::query(
+        "INSERT INTO audit_log \
+         (timestamp, session_id, item_id, action, result, details, duration_ms) \
+         VALUES (?, ?, ?, ?, ?, ?, ?)",
+    )
+    .bind(&timestamp)
+    .bind(&session_id)
+    .bind(&item_id)
+    .bind(&action)
+    .bind(&result)
+    .bind(&details)
+    .bind(duration_ms)
+    .execute(&mut *conn)
+    .await?;
+
+    tracing::trace!(action = %action, "Saved audit entry");
+    Ok(())
+}
+
 #[async_trait::async_trait]
 impl IStateRepository for SqliteStateRepository {
     // --- SyncItem operations ---
 
     async fn save_item(&self, item: &SyncItem) -> anyhow::Result<()> {
-        let id = item.id().to_string();
-        // We need the account_id from the sync_items table context.
-        // SyncItem doesn't carry account_id directly - it's part of the DB schema.
-        // For UPSERT, we'll need to handle this. Let's check if there's an existing
-        // row to get the account_id, or we'll store the item without it initially.
-        //
-        // Looking at the schema, account_id is NOT NULL.
-        // Since SyncItem doesn't have an account_id field, we need to handle this
-        // through the query_items filter. For save_item, the account_id should
-        // already exist in the DB row (update case) or be provided.
-        //
-        // For now, we'll use a sub-query approach: if the item exists, keep the
-        // existing account_id. For new items, we'll use the default account.
-        // However, this is a limitation. Let's use a pragmatic approach:
-        // attempt to get the existing account_id, or fall back to the first account.
-
-        let local_path = item.local_path().to_string();
-        let remote_id = item.remote_id().map(|r| r.as_str().to_string());
-        let remote_path = item.remote_path().as_str().to_string();
-        let state = item_state_to_string(item.state());
-        let content_hash = item.content_hash().map(|h| h.as_str().to_string());
-        let local_hash = item.local_hash().map(|h| h.as_str().to_string());
-        let size_bytes = item.size_bytes() as i64;
-        let last_sync = item.last_sync().map(|dt| dt.to_rfc3339());
-        let last_modified_local = item.last_modified_local().map(|dt| dt.to_rfc3339());
-        let last_modified_remote = item.last_modified_remote().map(|dt| dt.to_rfc3339());
-        let metadata = serde_json::to_string(item.metadata())
-            .map_err(|e| anyhow::anyhow!("Failed to serialize metadata: {}", e))?;
-        let error_info = match item.error_info() {
-            Some(ei) => Some(
-                serde_json::to_string(ei)
-                    .map_err(|e| anyhow::anyhow!("Failed to serialize error_info: {}", e))?,
-            ),
-            None => None,
-        };
-
-        // Try to get existing account_id for this item, or use first account
-        let existing_account_id: Option<String> =
-            sqlx::query_scalar("SELECT account_id FROM sync_items WHERE id = ?")
-                .bind(&id)
-                .fetch_optional(&self.pool)
-                .await?;
+        let mut conn = self.pool.acquire().await?;
+        upsert_item(&mut conn, item, self.trigger).await
+    }
 
-        let account_id = match existing_account_id {
-            Some(aid) => aid,
-            None => {
-                // Get the first/default account
-                let default_aid: Option<String> =
-                    sqlx::query_scalar("SELECT id FROM accounts ORDER BY created_at ASC LIMIT 1")
-                        .fetch_optional(&self.pool)
-                        .await?;
-                default_aid.ok_or_else(|| {
-                    anyhow::anyhow!("No account found to associate with sync item")
-                })?
+    /// Saves the items in transactions of [`SAVE_BATCH_CHUNK`] items
+    ///
+    /// Each transaction is started with `BEGIN IMMEDIATE`, so it takes the
+    /// write lock up front and waits for other writers through the busy
+    /// timeout instead of failing when it upgrades from a read. Chunks
+    /// committed before an error stay saved.
+    async fn save_items_batch(&self, items: &[SyncItem]) -> anyhow::Result<()> {
+        let mut conn = self.pool.acquire().await?;
+        for chunk in items.chunks(SAVE_BATCH_CHUNK) {
+            sqlx::query("BEGIN IMMEDIATE").execute(&mut *conn).await?;
+            let mut result = Ok(());
+            for item in chunk {
+                result = upsert_item(&mut conn, item, self.trigger).await;
+                if result.is_err() {
+                    break;
+                }
             }
-        };
-
-        sqlx::query(
-            "INSERT OR REPLACE INTO sync_items \
-             (id, account_id, local_path, remote_id, remote_path, state, \
-              content_hash, local_hash, size_bytes, last_sync, \
-              last_modified_local, last_modified_remote, metadata, error_info) \
-             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
-        )
-        .bind(&id)
-        .bind(&account_id)
-        .bind(&local_path)
-        .bind(&remote_id)
-        .bind(&remote_path)
-        .bind(&state)
-        .bind(&content_hash)
-        .bind(&local_hash)
-        .bind(size_bytes)
-        .bind(&last_sync)
-        .bind(&last_modified_local)
-        .bind(&last_modified_remote)
-        .bind(&metadata)
-        .bind(&error_info)
-        .execute(&self.pool)
-        .await?;
-
-        tracing::trace!(item_id = %id, "Saved sync item");
+            match result {
+                Ok(()) => {
+                    if let Err(err) = sqlx::query("COMMIT").execute(&mut *conn).await {
+                        sqlx::query("ROLLBACK").execute(&mut *conn).await.ok();
+                        return Err(err.into());
+                    }
+                }
+                Err(err) => {
+                    sqlx::query("ROLLBACK").execute(&mut *conn).await.ok();
+                    return Err(err);
+                }
+            }
+        }
+        tracing::debug!(items = items.len(), "Saved sync items in batches");
         Ok(())
     }
 
@@ -761,15 +952,25 @@ impl IStateRepository for SqliteStateRepository {
         let quota_used = account.quota_used() as i64;
         let quota_total = account.quota_total() as i64;
         let delta_token = account.delta_token().map(|t| t.as_str().to_string());
+        let delta_next_link = account.delta_next_link();
         let last_sync = account.last_sync().map(|dt| dt.to_rfc3339());
         let state = account_state_to_string(account.state());
         let created_at = account.created_at().to_rfc3339();
 
+        // Upsert rather than INSERT OR REPLACE: REPLACE deletes the existing
+        // row, which would cascade to every sync item of the account
         sqlx::query(
-            "INSERT OR REPLACE INTO accounts \
-             (id, email, display_name, onedrive_id, sync_root, \
-              quota_used, quota_total, delta_token, last_sync, state, created_at) \
-             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+            "INSERT INTO accounts \
+             (id, email, display_name, onedrive_id, sync_root, quota_used, \
+              quota_total, delta_token, delta_next_link, last_sync, state, created_at) \
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?) \
+             ON CONFLICT(id) DO UPDATE SET \
+              email = excluded.email, display_name = excluded.display_name, \
+              onedrive_id = excluded.onedrive_id, sync_root = excluded.sync_root, \
+              quota_used = excluded.quota_used, quota_total = excluded.quota_total, \
+              delta_token = excluded.delta_token, \
+              delta_next_link = excluded.delta_next_link, last_sync = excluded.last_sync, \
+              state = excluded.state, created_at = excluded.created_at",
         )
         .bind(&id)
         .bind(&email)
@@ -779,6 +980,7 @@ impl IStateRepository for SqliteStateRepository {
         .bind(quota_used)
         .bind(quota_total)
         .bind(&delta_token)
+        .bind(delta_next_link)
         .bind(&last_sync)
         .bind(&state)
         .bind(&created_at)
@@ -814,6 +1016,52 @@ impl IStateRepository for SqliteStateRepository {
         }
     }
 
+    async fn list_accounts(&self) -> anyhow::Result<Vec<Account>> {
+        let rows = sqlx::query("SELECT * FROM accounts ORDER BY created_at ASC")
+            .fetch_all(&self.pool)
+            .await?;
+
+        Ok(rows
+            .iter()
+            .map(account_from_row)
+            .collect::<Result<Vec<_>, _>>()?)
+    }
+
+    async fn purge_account_data(&self, account_id: &AccountId) -> anyhow::Result<u64> {
+        let account_id_str = account_id.to_string();
+        let mut tx = self.pool.begin().await?;
+
+        // Detach audit entries from the sessions being removed so the
+        // session_id foreign key does not block the delete.
+        sqlx::query(
+            "UPDATE audit_log SET session_id = NULL \
+             WHERE session_id IN (SELECT id FROM sync_sessions WHERE account_id = ?)",
+        )
+        .bind(&account_id_str)
+        .execute(&mut *tx)
+        .await?;
+
+        sqlx::query("DELETE FROM sync_sessions WHERE account_id = ?")
+            .bind(&account_id_str)
+            .execute(&mut *tx)
+            .await?;
+
+        // Conflicts are removed through ON DELETE CASCADE
+        let result = sqlx::query("DELETE FROM sync_items WHERE account_id = ?")
+            .bind(&account_id_str)
+            .execute(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
+
+        tracing::debug!(
+            account_id = %account_id_str,
+            items = result.rows_affected(),
+            "Purged account data"
+        );
+        Ok(result.rows_affected())
+    }
+
     // --- Session operations ---
 
     async fn save_session(&self, session: &SyncSession) -> anyhow::Result<()> {
@@ -878,33 +1126,8 @@ impl IStateRepository for SqliteStateRepository {
     // --- Audit operations ---
 
     async fn save_audit(&self, entry: &AuditEntry) -> anyhow::Result<()> {
-        let timestamp = entry.timestamp().to_rfc3339();
-        let session_id = entry.session_id().map(|s| s.to_string());
-        let item_id = entry.item_id().map(|i| i.to_string());
-        let action = entry.action().to_string();
-        let result = serde_json::to_string(entry.result())
-            .map_err(|e| anyhow::anyhow!("Failed to serialize audit result: {}", e))?;
-        let details = serde_json::to_string(entry.details())
-            .map_err(|e| anyhow::anyhow!("Failed to serialize audit details: {}", e))?;
-        let duration_ms = entry.duration_ms().map(|d| d as i64);
-
-        sqlx::query(
-            "INSERT INTO audit_log \
-             (timestamp, session_id, item_id, action, result, details, duration_ms) \
-             VALUES (?, ?, ?, ?, ?, ?, ?)",
-        )
-        .bind(&timestamp)
-        .bind(&session_id)
-        .bind(&item_id)
-        .bind(&action)
-        .bind(&result)
-        .bind(&details)
-        .bind(duration_ms)
-        .execute(&self.pool)
-        .await?;
-
-        tracing::trace!(action = %action, "Saved audit entry");
-        Ok(())
+        let mut conn = self.pool.acquire().await?;
+        insert_audit(&mut conn, entry).await
     }
 
     async fn get_audit_trail(&self, item_id: &UniqueId) -> anyhow::Result<Vec<AuditEntry>> {
@@ -947,6 +1170,68 @@ impl IStateRepository for SqliteStateRepository {
         Ok(entries)
     }
 
+    // --- Sync history operations ---
+
+    async fn save_sync_history(&self, entry: &SyncHistoryEntry) -> anyhow::Result<i64> {
+        let errors = serde_json::to_string(&entry.errors)
+            .map_err(|e| anyhow::anyhow!("Failed to serialize sync history errors: {}", e))?;
+
+        let result = sqlx::query(
+            "INSERT INTO sync_history \
+             (started_at, finished_at, files_downloaded, files_uploaded, files_deleted, \
+              bytes_downloaded, bytes_uploaded, errors, duration_ms, success) \
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+        )
+        .bind(entry.started_at.to_rfc3339())
+        .bind(entry.finished_at.to_rfc3339())
+        .bind(entry.files_downloaded as i64)
+        .bind(entry.files_uploaded as i64)
+        .bind(entry.files_deleted as i64)
+        .bind(entry.bytes_downloaded as i64)
+        .bind(entry.bytes_uploaded as i64)
+        .bind(&errors)
+        .bind(entry.duration_ms as i64)
+        .bind(entry.success as i64)
+        .execute(&self.pool)
+        .await?;
+
+        let id = result.last_insert_rowid();
+        self.prune_sync_history(MAX_SYNC_HISTORY_ENTRIES).await?;
+
+        tracing::trace!(id, success = entry.success, "Saved sync history entry");
+        Ok(id)
+    }
+
+    async fn get_sync_history(&self, limit: u32) -> anyhow::Result<Vec<SyncHistoryEntry>> {
+        let rows = sqlx::query("SELECT * FROM sync_history ORDER BY id DESC LIMIT ?")
+            .bind(limit as i64)
+            .fetch_all(&self.pool)
+            .await?;
+
+        let mut entries = Vec::with_capacity(rows.len());
+        for row in &rows {
+            entries.push(sync_history_from_row(row)?);
+        }
+
+        Ok(entries)
+    }
+
+    async fn prune_sync_history(&self, keep: u32) -> anyhow::Result<u64> {
+        let result = sqlx::query(
+            "DELETE FROM sync_history WHERE id NOT IN \
+             (SELECT id FROM sync_history ORDER BY id DESC LIMIT ?)",
+        )
+        .bind(keep as i64)
+        .execute(&self.pool)
+        .await?;
+
+        let removed = result.rows_affected();
+        if removed > 0 {
+            tracing::debug!(removed, keep, "Pruned sync history");
+        }
+        Ok(removed)
+    }
+
     // --- Conflict operations ---
 
     async fn save_conflict(&self, conflict: &Conflict) -> anyhow::Result<()> {
@@ -1012,6 +1297,61 @@ impl IStateRepository for SqliteStateRepository {
         Ok(conflicts)
     }
 
+    // --- Selective sync operations ---
+
+    async fn get_selected_folders(&self) -> anyhow::Result<Vec<String>> {
+        let value: Option<String> = sqlx::query_scalar("SELECT value FROM config WHERE key = ?")
+            .bind(SELECTED_FOLDERS_KEY)
+            .fetch_optional(&self.pool)
+            .await?;
+
+        match value {
+            Some(json) => serde_json::from_str(&json).map_err(|e| {
+                CacheError::SerializationError(format!("Invalid folder selection: {}", e)).into()
+            }),
+            None => Ok(Vec::new()),
+        }
+    }
+
+    async fn apply_folder_selection(
+        &self,
+        folders: &[String],
+        saved: &[SyncItem],
+        removed: &[UniqueId],
+    ) -> anyhow::Result<()> {
+        let folders_json = serde_json::to_string(folders)
+            .map_err(|e| anyhow::anyhow!("Failed to serialize folder selection: {}", e))?;
+        let mut tx = self.pool.begin().await?;
+
+        sqlx::query(
+            "INSERT INTO config (key, value, updated_at) VALUES (?, ?, datetime('now')) \
+             ON CONFLICT(key) DO UPDATE SET value = excluded.value, updated_at = excluded.updated_at",
+        )
+        .bind(SELECTED_FOLDERS_KEY)
+        .bind(&folders_json)
+        .execute(&mut *tx)
+        .await?;
+        for id in removed {
+            sqlx::query("DELETE FROM sync_items WHERE id = ?")
+                .bind(id.to_string())
+                .execute(&mut *tx)
+                .await?;
+        }
+        for item in saved {
+            upsert_item(&mut tx, item, self.trigger).await?;
+        }
+
+        tx.commit().await?;
+
+        tracing::debug!(
+            folders = folders.len(),
+            saved = saved.len(),
+            removed = removed.len(),
+            "Applied folder selection"
+        );
+        Ok(())
+    }
+
     // --- FUSE inode operations ---
 
     /// Atomically get the next available inode number
@@ -1172,4 +1512,47 @@ impl IStateRepository for SqliteStateRepository {
         );
         Ok(items)
     }
+
+    /// Get large sync items that are candidates for dehydration
+    ///
+    /// Same filter as `get_items_for_dehydration`, restricted to items of at
+    /// least `min_size_bytes` and sorted largest first, so a size-based
+    /// policy frees the most space with the fewest evictions.
+    async fn get_large_items_for_dehydration(
+        &self,
+        max_age_days: u32,
+        min_size_bytes: u64,
+        limit: u32,
+    ) -> anyhow::Result<Vec<SyncItem>> {
+        let cutoff = Utc::now() - chrono::Duration::days(max_age_days as i64);
+        let cutoff_str = cutoff.to_rfc3339();
+
+        let rows = sqlx::query(
+            "SELECT * FROM sync_items \
+             WHERE state = 'hydrated' \
+               AND last_accessed < ? \
+               AND last_accessed IS NOT NULL \
+               AND size_bytes >= ? \
+             ORDER BY size_bytes DESC, last_accessed ASC \
+             LIMIT ?",
+        )
+        .bind(&cutoff_str)
+        .bind(min_size_bytes as i64)
+        .bind(limit as i64)
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut items = Vec::with_capacity(rows.len());
+        for row in &rows {
+            items.push(sync_item_from_row(row)?);
+        }
+
+        tracing::debug!(
+            count = items.len(),
+            max_age_days,
+            min_size_bytes,
+            "Retrieved large dehydration candidates"
+        );
+        Ok(items)
+    }
 }
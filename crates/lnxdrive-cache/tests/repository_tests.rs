@@ -16,7 +16,8 @@ use lnxdrive_core::{
         },
         sync_item::ItemState,
         Account, AccountState, AuditAction, AuditEntry, AuditResult, Conflict, Resolution,
-        ResolutionSource, SyncItem, SyncSession, VersionInfo,
+        ResolutionSource, SyncHistoryEntry, SyncItem, SyncSession, TransitionTrigger, VersionInfo,
+        MAX_SYNC_HISTORY_ENTRIES,
     },
     ports::{IStateRepository, ItemFilter},
 };
@@ -128,6 +129,25 @@ async fn test_get_default_account() {
     assert_eq!(default.unwrap().id(), account.id());
 }
 
+#[tokio::test]
+async fn test_list_accounts() {
+    let repo = setup().await;
+    assert!(repo.list_accounts().await.unwrap().is_empty());
+
+    let first = create_test_account(&repo).await;
+    let second = Account::new(
+        Email::new("work@example.com".to_string()).unwrap(),
+        "Work User",
+        "drive456",
+        SyncPath::new(PathBuf::from("/home/user/OneDrive - Work")).unwrap(),
+    );
+    repo.save_account(&second).await.unwrap();
+
+    let accounts = repo.list_accounts().await.unwrap();
+    let ids: Vec<_> = accounts.iter().map(|a| *a.id()).collect();
+    assert_eq!(ids, vec![*first.id(), *second.id()]);
+}
+
 #[tokio::test]
 async fn test_update_account() {
     let repo = setup().await;
@@ -149,6 +169,30 @@ async fn test_update_account() {
     assert!(matches!(retrieved.state(), AccountState::TokenExpired));
     assert_eq!(retrieved.delta_token().unwrap().as_str(), "delta-token-123");
     assert!(retrieved.last_sync().is_some());
+    assert!(retrieved.delta_next_link().is_none());
+
+    // An interrupted page walk is kept next to the committed token
+    account.set_delta_next_link(Some("https://example.com/delta?skip=2".to_string()));
+    repo.save_account(&account).await.unwrap();
+    let retrieved = repo.get_account(account.id()).await.unwrap().unwrap();
+    assert_eq!(retrieved.delta_token().unwrap().as_str(), "delta-token-123");
+    assert_eq!(
+        retrieved.delta_next_link(),
+        Some("https://example.com/delta?skip=2")
+    );
+}
+
+#[tokio::test]
+async fn test_update_account_keeps_its_items() {
+    let repo = setup().await;
+    let mut account = create_test_account(&repo).await;
+    let item = create_test_sync_item();
+    repo.save_item(&item).await.unwrap();
+
+    account.record_sync(Utc::now());
+    repo.save_account(&account).await.unwrap();
+
+    assert!(repo.get_item(item.id()).await.unwrap().is_some());
 }
 
 // ============================================================================
@@ -177,6 +221,20 @@ async fn test_save_and_get_item() {
     assert!(matches!(retrieved.state(), ItemState::Online));
 }
 
+#[tokio::test]
+async fn test_item_unix_mode_round_trip() {
+    let repo = setup().await;
+    let _account = create_test_account(&repo).await;
+    let mut item = create_test_sync_item();
+    assert_eq!(item.unix_mode(), None);
+
+    item.set_unix_mode(Some(0o100755));
+    repo.save_item(&item).await.unwrap();
+
+    let retrieved = repo.get_item(item.id()).await.unwrap().unwrap();
+    assert_eq!(retrieved.unix_mode(), Some(0o755));
+}
+
 #[tokio::test]
 async fn test_get_item_not_found() {
     let repo = setup().await;
@@ -268,6 +326,80 @@ async fn test_update_item() {
     assert!(retrieved.last_sync().is_some());
 }
 
+/// `count` files below the test account's sync root
+fn many_sync_items(count: usize) -> Vec<SyncItem> {
+    (0..count)
+        .map(|n| {
+            SyncItem::new_file(
+                SyncPath::new(PathBuf::from(format!("/home/user/OneDrive/f{n}.txt"))).unwrap(),
+                RemotePath::new(format!("/f{n}.txt")).unwrap(),
+                n as u64,
+                None,
+            )
+            .unwrap()
+        })
+        .collect()
+}
+
+#[tokio::test]
+async fn test_save_items_batch_inserts_and_updates() {
+    let repo = setup().await;
+    let _account = create_test_account(&repo).await;
+
+    // Spans several transactions
+    let mut items = many_sync_items(lnxdrive_cache::repository::SAVE_BATCH_CHUNK * 2 + 1);
+    repo.save_items_batch(&items).await.unwrap();
+    let all = repo.query_items(&ItemFilter::new()).await.unwrap();
+    assert_eq!(all.len(), items.len());
+
+    for item in &mut items {
+        item.set_size_bytes(7);
+    }
+    repo.save_items_batch(&items).await.unwrap();
+    let all = repo.query_items(&ItemFilter::new()).await.unwrap();
+    assert_eq!(all.len(), items.len());
+    assert!(all.iter().all(|item| item.size_bytes() == 7));
+
+    repo.save_items_batch(&[]).await.unwrap();
+}
+
+/// Write-time benchmark on a file database: 20k items saved one by one
+/// versus with `save_items_batch`. Run with
+/// `cargo test -p lnxdrive-cache --release -- --ignored --nocapture bench`.
+#[tokio::test]
+#[ignore = "benchmark"]
+async fn bench_save_items_batch() {
+    const ITEMS: usize = 20_000;
+
+    let dir = std::env::temp_dir().join(format!("lnxdrive-bench-{}", Uuid::new_v4()));
+    std::fs::create_dir_all(&dir).unwrap();
+
+    let mut timings = Vec::new();
+    for batched in [false, true] {
+        let path = dir.join(format!("bench-{batched}.db"));
+        let pool = DatabasePool::new(&path).await.unwrap();
+        let repo = SqliteStateRepository::new(pool.pool().clone());
+        let _account = create_test_account(&repo).await;
+        let items = many_sync_items(ITEMS);
+
+        let start = std::time::Instant::now();
+        if batched {
+            repo.save_items_batch(&items).await.unwrap();
+        } else {
+            for item in &items {
+                repo.save_item(item).await.unwrap();
+            }
+        }
+        timings.push(start.elapsed());
+    }
+    std::fs::remove_dir_all(&dir).ok();
+
+    println!(
+        "{ITEMS} items: one by one {:?}, batched {:?}",
+        timings[0], timings[1]
+    );
+}
+
 #[tokio::test]
 async fn test_item_with_error_state() {
     let repo = setup().await;
@@ -397,6 +529,69 @@ async fn test_count_items_by_state() {
     assert_eq!(counts.get("Modified"), Some(&1));
 }
 
+#[tokio::test]
+async fn test_purge_account_data() {
+    let repo = setup().await;
+    let account = create_test_account(&repo).await;
+
+    let item = create_test_sync_item();
+    repo.save_item(&item).await.unwrap();
+
+    let session = SyncSession::new(*account.id());
+    repo.save_session(&session).await.unwrap();
+
+    let entry = AuditEntry::new(AuditAction::FileUpload, AuditResult::success())
+        .with_session_id(*session.id());
+    repo.save_audit(&entry).await.unwrap();
+
+    let purged = repo.purge_account_data(account.id()).await.unwrap();
+    assert_eq!(purged, 1);
+
+    assert!(repo.get_item(item.id()).await.unwrap().is_none());
+    assert!(repo.get_session(session.id()).await.unwrap().is_none());
+    // The account itself is kept
+    assert!(repo.get_account(account.id()).await.unwrap().is_some());
+}
+
+#[tokio::test]
+async fn test_apply_folder_selection() {
+    let repo = setup().await;
+    let _account = create_test_account(&repo).await;
+    assert!(repo.get_selected_folders().await.unwrap().is_empty());
+
+    let old = create_test_sync_item();
+    repo.save_item(&old).await.unwrap();
+    let new = many_sync_items(2);
+
+    let folders = vec!["/Docs".to_string(), "/Photos/2024".to_string()];
+    repo.apply_folder_selection(&folders, &new, &[*old.id()])
+        .await
+        .unwrap();
+
+    assert_eq!(repo.get_selected_folders().await.unwrap(), folders);
+    assert!(repo.get_item(old.id()).await.unwrap().is_none());
+    for item in &new {
+        assert!(repo.get_item(item.id()).await.unwrap().is_some());
+    }
+
+    // A later selection replaces the stored one
+    repo.apply_folder_selection(&[], &[], &[]).await.unwrap();
+    assert!(repo.get_selected_folders().await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_failed_folder_selection_writes_nothing() {
+    // Without an account the new items cannot be saved
+    let repo = setup().await;
+
+    let folders = vec!["/Docs".to_string()];
+    assert!(repo
+        .apply_folder_selection(&folders, &many_sync_items(1), &[])
+        .await
+        .is_err());
+    assert!(repo.get_selected_folders().await.unwrap().is_empty());
+}
+
 // ============================================================================
 // Session tests
 // ============================================================================
@@ -512,6 +707,61 @@ async fn test_save_and_get_audit_trail() {
     assert!(trail[1].result().is_failed());
 }
 
+#[tokio::test]
+async fn test_state_transitions_are_audited() {
+    let pool = DatabasePool::in_memory().await.unwrap();
+    let repo = SqliteStateRepository::new(pool.pool().clone());
+    let user_repo = SqliteStateRepository::new(pool.pool().clone())
+        .with_transition_trigger(TransitionTrigger::User);
+    let _account = create_test_account(&repo).await;
+
+    // A new item has no previous state, and saving it unchanged is no transition
+    let mut item = create_test_sync_item();
+    repo.save_item(&item).await.unwrap();
+    repo.save_item(&item).await.unwrap();
+    assert!(repo.get_audit_trail(item.id()).await.unwrap().is_empty());
+
+    item.start_hydrating().unwrap();
+    user_repo.save_item(&item).await.unwrap();
+    item.complete_hydration().unwrap();
+    repo.save_items_batch(std::slice::from_ref(&item))
+        .await
+        .unwrap();
+    item.transition_to(ItemState::Modified).unwrap();
+    user_repo.save_item(&item).await.unwrap();
+    item.transition_to(ItemState::Error("upload failed".to_string()))
+        .unwrap();
+    repo.save_item(&item).await.unwrap();
+
+    let trail = repo.get_audit_trail(item.id()).await.unwrap();
+    let transitions: Vec<_> = trail
+        .iter()
+        .map(|entry| {
+            assert_eq!(*entry.action(), AuditAction::StateTransition);
+            let details = entry.details();
+            (
+                details["from"].as_str().unwrap(),
+                details["to"].as_str().unwrap(),
+                details["trigger"].as_str().unwrap(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        transitions,
+        vec![
+            ("Online", "Hydrating", "user"),
+            ("Hydrating", "Hydrated", "sync"),
+            ("Hydrated", "Modified", "user"),
+            ("Modified", "Error", "sync"),
+        ]
+    );
+    assert!(trail[..3].iter().all(|entry| entry.result().is_success()));
+    assert_eq!(
+        *trail[3].result(),
+        AuditResult::failed("item_error", "upload failed")
+    );
+}
+
 #[tokio::test]
 async fn test_get_audit_since() {
     let repo = setup().await;
@@ -602,6 +852,26 @@ async fn test_save_and_get_unresolved_conflicts() {
     assert!(!unresolved[0].is_resolved());
 }
 
+#[tokio::test]
+async fn test_saving_item_keeps_its_conflicts() {
+    let repo = setup().await;
+    let _account = create_test_account(&repo).await;
+    let mut item = create_test_sync_item();
+    repo.save_item(&item).await.unwrap();
+
+    let version =
+        |hash: &str| VersionInfo::new(FileHash::new(hash.to_string()).unwrap(), 1024, Utc::now());
+    let conflict = Conflict::new(*item.id(), version(VALID_HASH_1), version(VALID_HASH_2));
+    repo.save_conflict(&conflict).await.unwrap();
+
+    item.set_size_bytes(2048);
+    repo.save_item(&item).await.unwrap();
+
+    let unresolved = repo.get_unresolved_conflicts().await.unwrap();
+    assert_eq!(unresolved.len(), 1);
+    assert_eq!(unresolved[0].id(), conflict.id());
+}
+
 #[tokio::test]
 async fn test_resolved_conflict_not_in_unresolved() {
     let repo = setup().await;
@@ -698,6 +968,30 @@ async fn test_file_based_pool_creation() {
     let _ = std::fs::remove_dir_all(&temp_dir);
 }
 
+#[tokio::test]
+async fn test_read_only_pool_reads_without_migrating() {
+    let temp_dir = std::env::temp_dir().join(format!("lnxdrive_test_{}", Uuid::new_v4()));
+    let db_path = temp_dir.join("test.db");
+
+    // Nothing to open yet: the file is not created
+    assert!(DatabasePool::open_read_only(&db_path).await.is_err());
+    assert!(!db_path.exists());
+
+    let pool = DatabasePool::new(&db_path).await.unwrap();
+    let repo = SqliteStateRepository::new(pool.pool().clone());
+    let _account = create_test_account(&repo).await;
+    let item = create_test_sync_item();
+    repo.save_item(&item).await.unwrap();
+
+    let read_only = DatabasePool::open_read_only(&db_path).await.unwrap();
+    let reader = SqliteStateRepository::new(read_only.pool().clone());
+    let found = reader.get_item_by_path(item.local_path()).await.unwrap();
+    assert_eq!(found.map(|i| *i.id()), Some(*item.id()));
+    assert!(reader.save_item(&item).await.is_err());
+
+    let _ = std::fs::remove_dir_all(&temp_dir);
+}
+
 // ============================================================================
 // Edge case tests
 // ============================================================================
@@ -831,6 +1125,29 @@ async fn test_update_and_get_item_by_inode() {
     assert_eq!(retrieved.unwrap().id(), item.id());
 }
 
+#[tokio::test]
+async fn test_inode_survives_save_and_reload() {
+    let repo = setup().await;
+    let _account = create_test_account(&repo).await;
+
+    let mut item = create_test_sync_item();
+    let inode = repo.get_next_inode().await.unwrap();
+    item.set_inode(Some(inode));
+    repo.save_item(&item).await.unwrap();
+
+    let reloaded = repo.get_item(item.id()).await.unwrap().unwrap();
+    assert_eq!(reloaded.inode(), Some(inode));
+
+    // A copy loaded before the inode was assigned must not clear it
+    let mut stale = reloaded.clone();
+    stale.set_inode(None);
+    stale.set_size_bytes(4096);
+    repo.save_item(&stale).await.unwrap();
+    let reloaded = repo.get_item_by_inode(inode).await.unwrap().unwrap();
+    assert_eq!(reloaded.inode(), Some(inode));
+    assert_eq!(reloaded.size_bytes(), 4096);
+}
+
 #[tokio::test]
 async fn test_get_item_by_nonexistent_inode() {
     let repo = setup().await;
@@ -861,9 +1178,8 @@ async fn test_update_last_accessed() {
     let now = Utc::now();
     repo.update_last_accessed(item.id(), now).await.unwrap();
 
-    // Retrieve and verify (we'd need to add a getter in SyncItem for full verification)
-    let retrieved = repo.get_item(item.id()).await.unwrap();
-    assert!(retrieved.is_some());
+    let retrieved = repo.get_item(item.id()).await.unwrap().unwrap();
+    assert_eq!(retrieved.last_accessed(), Some(now));
 }
 
 #[tokio::test]
@@ -1026,3 +1342,128 @@ async fn test_get_items_for_dehydration_excludes_pinned_and_modified() {
     assert_eq!(candidates[0].id(), item_hydrated.id());
     assert!(matches!(candidates[0].state(), ItemState::Hydrated));
 }
+
+#[tokio::test]
+async fn test_get_large_items_for_dehydration() {
+    let repo = setup().await;
+    let _account = create_test_account(&repo).await;
+
+    let old_time = Utc::now() - Duration::days(100);
+    let mut ids = Vec::new();
+    for (name, size, accessed) in [
+        ("small.bin", 10, old_time),
+        ("big.bin", 5_000, old_time),
+        ("bigger.bin", 9_000, old_time),
+        ("recent.bin", 20_000, Utc::now()),
+    ] {
+        let mut item = create_hydrated_sync_item(&format!("/home/user/OneDrive/{}", name));
+        item.set_size_bytes(size);
+        repo.save_item(&item).await.unwrap();
+        repo.update_last_accessed(item.id(), accessed)
+            .await
+            .unwrap();
+        ids.push(*item.id());
+    }
+
+    // Largest first, skipping small and recently used files
+    let candidates = repo
+        .get_large_items_for_dehydration(30, 1_000, 10)
+        .await
+        .unwrap();
+    let found: Vec<_> = candidates.iter().map(|i| *i.id()).collect();
+    assert_eq!(found, vec![ids[2], ids[1]]);
+}
+
+// ============================================================================
+// Sync history tests
+// ============================================================================
+
+#[tokio::test]
+async fn test_save_and_get_sync_history() {
+    let repo = setup().await;
+
+    let ok = SyncHistoryEntry::completed(
+        Utc::now() - Duration::seconds(5),
+        3,
+        1,
+        2,
+        4096,
+        1024,
+        vec!["Failed to upload a.txt".to_string()],
+        1200,
+    );
+    let failed = SyncHistoryEntry::failed(Utc::now(), "network unreachable");
+
+    let first_id = repo.save_sync_history(&ok).await.unwrap();
+    let second_id = repo.save_sync_history(&failed).await.unwrap();
+    assert!(second_id > first_id);
+
+    let history = repo.get_sync_history(10).await.unwrap();
+    assert_eq!(history.len(), 2);
+
+    // Newest first
+    assert_eq!(history[0].id, Some(second_id));
+    assert!(!history[0].success);
+    assert_eq!(history[0].errors, vec!["network unreachable".to_string()]);
+
+    assert_eq!(history[1].id, Some(first_id));
+    assert!(history[1].success);
+    assert_eq!(history[1].files_downloaded, 3);
+    assert_eq!(history[1].files_uploaded, 1);
+    assert_eq!(history[1].files_deleted, 2);
+    assert_eq!(history[1].bytes_downloaded, 4096);
+    assert_eq!(history[1].bytes_uploaded, 1024);
+    assert_eq!(history[1].duration_ms, 1200);
+    assert_eq!(history[1].errors.len(), 1);
+
+    let limited = repo.get_sync_history(1).await.unwrap();
+    assert_eq!(limited.len(), 1);
+    assert_eq!(limited[0].id, Some(second_id));
+}
+
+#[tokio::test]
+async fn test_prune_sync_history() {
+    let repo = setup().await;
+
+    for i in 0..5 {
+        let entry = SyncHistoryEntry::completed(Utc::now(), i, 0, 0, 0, 0, vec![], 10);
+        repo.save_sync_history(&entry).await.unwrap();
+    }
+
+    let removed = repo.prune_sync_history(2).await.unwrap();
+    assert_eq!(removed, 3);
+
+    let history = repo.get_sync_history(10).await.unwrap();
+    assert_eq!(history.len(), 2);
+    assert_eq!(history[0].files_downloaded, 4);
+    assert_eq!(history[1].files_downloaded, 3);
+
+    // Nothing left to prune
+    assert_eq!(repo.prune_sync_history(2).await.unwrap(), 0);
+}
+
+#[tokio::test]
+async fn test_save_sync_history_caps_rows() {
+    let repo = setup().await;
+
+    for _ in 0..(MAX_SYNC_HISTORY_ENTRIES + 3) {
+        let entry = SyncHistoryEntry::completed(Utc::now(), 0, 0, 0, 0, 0, vec![], 1);
+        repo.save_sync_history(&entry).await.unwrap();
+    }
+
+    let history = repo
+        .get_sync_history(MAX_SYNC_HISTORY_ENTRIES * 2)
+        .await
+        .unwrap();
+    assert_eq!(history.len(), MAX_SYNC_HISTORY_ENTRIES as usize);
+}
+
+// ============================================================================
+// Database integrity
+// ============================================================================
+
+#[tokio::test]
+async fn test_fresh_database_passes_integrity_check() {
+    let pool = DatabasePool::in_memory().await.unwrap();
+    assert!(pool.check_integrity().await.unwrap().is_empty());
+}
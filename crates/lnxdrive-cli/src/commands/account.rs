@@ -0,0 +1,365 @@
+//! Account command - Choose which account commands act on
+//!
+//! Provides the `lnxdrive account` CLI commands which:
+//! 1. List the logged-in accounts with their aliases (`list`)
+//! 2. Set the account used when no `--account` is given (`use`)
+//! 3. Give accounts short names for `--account` (`alias`)
+//!
+//! Commands that act on one account take it from the global `--account`
+//! option (or `LNXDRIVE_ACCOUNT`), resolved by [`select_account`] against
+//! the `accounts` section of the configuration.
+
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use clap::Subcommand;
+use lnxdrive_cache::{pool::DatabasePool, SqliteStateRepository};
+use lnxdrive_core::{
+    config::{AccountRefError, Config},
+    domain::Account,
+    ports::state_repository::IStateRepository,
+};
+use lnxdrive_ipc::service::{DBUS_NAME, DBUS_PATH};
+
+use crate::output::{get_formatter, OutputFormat};
+
+/// D-Bus interface of the daemon describing the synced account
+const ACCOUNT_INTERFACE: &str = "com.enigmora.LNXDrive.Account";
+
+/// Account subcommands
+#[derive(Debug, Subcommand)]
+pub enum AccountCommand {
+    /// List logged-in accounts
+    List,
+    /// Use an account when no --account is given
+    Use {
+        /// Email, alias or id of the account
+        account: String,
+    },
+    /// Give an account a short name for --account
+    Alias {
+        /// Short name, e.g. "work"
+        alias: String,
+        /// Email, alias or id of the account (omit with --remove)
+        #[arg(required_unless_present = "remove")]
+        account: Option<String>,
+        /// Remove the alias instead
+        #[arg(long)]
+        remove: bool,
+    },
+}
+
+impl AccountCommand {
+    /// Execute the account command
+    pub async fn execute(&self, format: OutputFormat) -> Result<()> {
+        match self {
+            AccountCommand::List => execute_list(format).await,
+            AccountCommand::Use { account } => execute_use(account, format).await,
+            AccountCommand::Alias {
+                alias,
+                account,
+                remove,
+            } => execute_alias(alias, account.as_deref(), *remove, format).await,
+        }
+    }
+}
+
+/// Lists the accounts, marking the one commands use by default
+async fn execute_list(format: OutputFormat) -> Result<()> {
+    let formatter = get_formatter(matches!(format, OutputFormat::Json));
+    let config = Config::load_or_default(&Config::default_path());
+    let accounts = match open_repository().await? {
+        Some(state_repo) => state_repo.list_accounts().await?,
+        None => Vec::new(),
+    };
+    let default_id = config
+        .accounts
+        .resolve(&accounts, None)
+        .ok()
+        .map(|account| *account.id());
+
+    if matches!(format, OutputFormat::Json) {
+        let list: Vec<_> = accounts
+            .iter()
+            .map(|account| {
+                serde_json::json!({
+                    "id": account.id().to_string(),
+                    "email": account.email().as_str(),
+                    "display_name": account.display_name(),
+                    "sync_root": account.sync_root().to_string(),
+                    "aliases": config.accounts.aliases_of(account.email().as_str()),
+                    "default": default_id == Some(*account.id()),
+                })
+            })
+            .collect();
+        formatter.print_json(&serde_json::json!({ "accounts": list }));
+        return Ok(());
+    }
+
+    if accounts.is_empty() {
+        formatter.info("No account is logged in. Run 'lnxdrive auth login' first.");
+        return Ok(());
+    }
+    for account in &accounts {
+        let marker = if default_id == Some(*account.id()) {
+            "*"
+        } else {
+            " "
+        };
+        let aliases = config.accounts.aliases_of(account.email().as_str());
+        let aliases = if aliases.is_empty() {
+            String::new()
+        } else {
+            format!(" ({})", aliases.join(", "))
+        };
+        formatter.info(&format!(
+            "{} {}{} - {}",
+            marker,
+            account.email(),
+            aliases,
+            account.sync_root()
+        ));
+    }
+    Ok(())
+}
+
+/// Makes `reference` the default account
+async fn execute_use(reference: &str, format: OutputFormat) -> Result<()> {
+    let formatter = get_formatter(matches!(format, OutputFormat::Json));
+    let config_path = Config::default_path();
+    let mut config = Config::load_or_default(&config_path);
+
+    let accounts = list_accounts().await?;
+    let email = config
+        .accounts
+        .resolve(&accounts, Some(reference))?
+        .email()
+        .as_str()
+        .to_string();
+    config.accounts.default = Some(email.clone());
+    save_config(&config, &config_path)?;
+
+    if matches!(format, OutputFormat::Json) {
+        formatter.print_json(&serde_json::json!({
+            "success": true,
+            "default": email,
+        }));
+    } else {
+        formatter.success(&format!("Using {} by default", email));
+        formatter.info("Run 'lnxdrive daemon restart' for the daemon to switch accounts.");
+    }
+    Ok(())
+}
+
+/// Points `alias` at the account `reference`, or removes it
+async fn execute_alias(
+    alias: &str,
+    reference: Option<&str>,
+    remove: bool,
+    format: OutputFormat,
+) -> Result<()> {
+    let formatter = get_formatter(matches!(format, OutputFormat::Json));
+    let config_path = Config::default_path();
+    let mut config = Config::load_or_default(&config_path);
+
+    let email = match reference {
+        Some(reference) if !remove => {
+            let accounts = list_accounts().await?;
+            let email = config
+                .accounts
+                .resolve(&accounts, Some(reference))?
+                .email()
+                .as_str()
+                .to_string();
+            config
+                .accounts
+                .aliases
+                .insert(alias.to_string(), email.clone());
+            if let Some(error) = config
+                .validate()
+                .into_iter()
+                .find(|e| e.field == "accounts.aliases")
+            {
+                anyhow::bail!("{}", error);
+            }
+            Some(email)
+        }
+        _ => {
+            if config.accounts.aliases.remove(alias).is_none() {
+                anyhow::bail!("No alias named '{}'", alias);
+            }
+            None
+        }
+    };
+    save_config(&config, &config_path)?;
+
+    if matches!(format, OutputFormat::Json) {
+        formatter.print_json(&serde_json::json!({
+            "success": true,
+            "alias": alias,
+            "email": email,
+        }));
+    } else {
+        match email {
+            Some(email) => formatter.success(&format!("'{}' now refers to {}", alias, email)),
+            None => formatter.success(&format!("Removed alias '{}'", alias)),
+        }
+    }
+    Ok(())
+}
+
+/// Picks the account a command acts on
+///
+/// `reference` is the email, alias or id given with `--account`. Returns
+/// `None` if nobody is logged in, and an error listing the accounts if the
+/// reference matches none or several accounts are logged in without a
+/// default.
+pub(crate) async fn select_account(
+    state_repo: &SqliteStateRepository,
+    config: &Config,
+    reference: Option<&str>,
+) -> Result<Option<Account>> {
+    let accounts = state_repo
+        .list_accounts()
+        .await
+        .context("Failed to query accounts")?;
+    match config.accounts.resolve(&accounts, reference) {
+        Ok(account) => Ok(Some(account.clone())),
+        Err(AccountRefError::NoAccounts) => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Fails if the running daemon syncs another account than `account`
+///
+/// Succeeds when the daemon is not running or has no account yet, so
+/// callers still report those cases themselves.
+pub(crate) async fn ensure_daemon_serves(account: &Account) -> Result<()> {
+    let Ok(connection) = zbus::Connection::session().await else {
+        return Ok(());
+    };
+    let Ok(reply) = connection
+        .call_method(
+            Some(DBUS_NAME),
+            DBUS_PATH,
+            Some(ACCOUNT_INTERFACE),
+            "GetInfo",
+            &(),
+        )
+        .await
+    else {
+        return Ok(());
+    };
+    let info: serde_json::Value = serde_json::from_str(&reply.body().deserialize::<String>()?)
+        .context("Invalid reply from the daemon")?;
+
+    match info["id"].as_str() {
+        Some(id) if id != account.id().to_string() => anyhow::bail!(
+            "The daemon syncs {}, not {}. Run 'lnxdrive account use {}' and \
+             'lnxdrive daemon restart' to switch.",
+            info["email"].as_str().unwrap_or(id),
+            account.email(),
+            account.email()
+        ),
+        _ => Ok(()),
+    }
+}
+
+/// Lists the logged-in accounts, failing if nobody has logged in yet
+async fn list_accounts() -> Result<Vec<Account>> {
+    let Some(state_repo) = open_repository().await? else {
+        anyhow::bail!(AccountRefError::NoAccounts);
+    };
+    state_repo.list_accounts().await
+}
+
+/// Opens the state database, or returns `None` if it does not exist yet
+async fn open_repository() -> Result<Option<SqliteStateRepository>> {
+    let db_path = dirs::data_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join("lnxdrive")
+        .join("lnxdrive.db");
+    if !db_path.exists() {
+        return Ok(None);
+    }
+    let pool = DatabasePool::new(&db_path)
+        .await
+        .context("Failed to open database")?;
+    Ok(Some(SqliteStateRepository::new(pool.pool().clone())))
+}
+
+/// Writes `config` to `config_path`
+fn save_config(config: &Config, config_path: &Path) -> Result<()> {
+    if let Some(parent) = config_path.parent() {
+        std::fs::create_dir_all(parent).context("Failed to create configuration directory")?;
+    }
+    let yaml = serde_yaml::to_string(config).context("Failed to serialize configuration")?;
+    std::fs::write(config_path, yaml).context("Failed to write configuration file")
+}
+
+#[cfg(test)]
+mod tests {
+    use clap::Parser;
+
+    use super::*;
+
+    #[derive(Debug, Parser)]
+    struct TestCli {
+        #[command(subcommand)]
+        command: AccountCommand,
+    }
+
+    #[test]
+    fn test_parse_alias_requires_account_unless_removing() {
+        let cli = TestCli::parse_from(["test", "alias", "work", "me@work.example"]);
+        assert!(matches!(
+            cli.command,
+            AccountCommand::Alias { ref alias, account: Some(_), remove: false } if alias == "work"
+        ));
+
+        let cli = TestCli::parse_from(["test", "alias", "--remove", "work"]);
+        assert!(matches!(
+            cli.command,
+            AccountCommand::Alias {
+                account: None,
+                remove: true,
+                ..
+            }
+        ));
+
+        assert!(TestCli::try_parse_from(["test", "alias", "work"]).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_select_account_reports_ambiguous_choice() {
+        use lnxdrive_core::domain::newtypes::{Email, SyncPath};
+
+        let pool = DatabasePool::in_memory().await.unwrap();
+        let state_repo = SqliteStateRepository::new(pool.pool().clone());
+        let config = Config::default();
+        assert!(select_account(&state_repo, &config, None)
+            .await
+            .unwrap()
+            .is_none());
+
+        for email in ["me@example.com", "me@work.example"] {
+            let account = Account::new(
+                Email::new(email.to_string()).unwrap(),
+                "Me",
+                "drive",
+                SyncPath::new(PathBuf::from("/home/me/OneDrive")).unwrap(),
+            );
+            state_repo.save_account(&account).await.unwrap();
+        }
+
+        let err = select_account(&state_repo, &config, None)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("me@work.example"));
+        let chosen = select_account(&state_repo, &config, Some("me@work.example"))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(chosen.email().as_str(), "me@work.example");
+    }
+}
@@ -119,6 +119,7 @@ impl AuditCommand {
                         "action": entry.action().to_string(),
                         "item_id": entry.item_id().map(|id| id.to_string()),
                         "result": format!("{:?}", entry.result()),
+                        "code": entry.result().code(),
                         "details": entry.details(),
                         "duration_ms": entry.duration_ms(),
                     })
@@ -155,8 +156,12 @@ impl AuditCommand {
                 "FAILED "
             };
 
-            // Format details - extract a short summary from the JSON details
-            let details = format_details(entry.details());
+            // Format details - extract a short summary from the JSON details,
+            // after the code of a failure
+            let details = match entry.result().code() {
+                Some(code) => format!("[{}] {}", code, format_details(entry.details())),
+                None => format_details(entry.details()),
+            };
 
             formatter.info(&format!(
                 "  {} {:<18} {} {}",
@@ -3,17 +3,26 @@
 //! Provides the `lnxdrive auth` CLI subcommands which:
 //! 1. `login`  - Runs the OAuth2 PKCE flow via GraphAuthAdapter, stores tokens
 //!    in the system keyring, fetches user info, and persists the account in SQLite.
-//! 2. `logout` - Clears tokens from the keyring and suspends the account.
+//! 2. `logout` - Clears lnxdrive's tokens, tells a running daemon to drop its
+//!    session, unmounts the FUSE filesystem and suspends the account. With
+//!    `--purge` the local cache and state are removed.
 //! 3. `status` - Shows current account info and token validity.
 
 use std::{path::Path, sync::Arc};
 
 use anyhow::{Context, Result};
 use clap::Subcommand;
-use tracing::info;
+use tracing::{info, warn};
 
+use super::account::select_account;
 use crate::output::{get_formatter, OutputFormat};
 
+/// D-Bus interface of the daemon describing the synced account
+const ACCOUNT_INTERFACE: &str = "com.enigmora.LNXDrive.Account";
+
+/// D-Bus interface of the daemon handling authentication
+const AUTH_INTERFACE: &str = "com.enigmora.LNXDrive.Auth";
+
 #[derive(Debug, Subcommand)]
 pub enum AuthCommand {
     /// Authenticate with OneDrive via OAuth2
@@ -23,18 +32,39 @@ pub enum AuthCommand {
         app_id: Option<String>,
     },
     /// Remove stored credentials
-    Logout,
+    Logout {
+        /// Also delete the content cache and sync state for the account
+        #[arg(long)]
+        purge: bool,
+        /// Skip the confirmation prompt
+        #[arg(long, short = 'y')]
+        yes: bool,
+        /// Also sign the user out of every Microsoft app and device
+        ///
+        /// Revokes all refresh tokens of the Microsoft account, not only
+        /// lnxdrive's: Outlook, Teams, the OneDrive apps, browsers, etc.
+        /// on every device must sign in again.
+        #[arg(long)]
+        revoke_all_sessions: bool,
+    },
     /// Check authentication status
     Status,
 }
 
 impl AuthCommand {
-    pub async fn execute(&self, format: OutputFormat) -> Result<()> {
+    pub async fn execute(&self, format: OutputFormat, account: Option<&str>) -> Result<()> {
         let fmt = get_formatter(format == OutputFormat::Json);
         match self {
             AuthCommand::Login { app_id } => self.execute_login(app_id.as_deref(), &*fmt).await,
-            AuthCommand::Logout => self.execute_logout(&*fmt).await,
-            AuthCommand::Status => self.execute_status(&*fmt, format).await,
+            AuthCommand::Logout {
+                purge,
+                yes,
+                revoke_all_sessions,
+            } => {
+                self.execute_logout(*purge, *yes, *revoke_all_sessions, account, &*fmt)
+                    .await
+            }
+            AuthCommand::Status => self.execute_status(account, &*fmt, format).await,
         }
     }
 
@@ -80,7 +110,7 @@ impl AuthCommand {
 
         // Step 3: Fetch user info from Graph API
         fmt.info("Retrieving account information...");
-        let graph_client = GraphClient::new(&tokens.access_token);
+        let graph_client = GraphClient::new(&tokens.access_token).with_http_config(&config.http);
         let cloud_provider = GraphCloudProvider::new(graph_client);
         let user_info = cloud_provider
             .get_user_info()
@@ -90,7 +120,8 @@ impl AuthCommand {
         info!(email = %user_info.email, display_name = %user_info.display_name, "Got user info");
 
         // Step 4: Store tokens in keyring
-        KeyringTokenStorage::store(&user_info.email, &tokens)
+        let keyring_id = KeyringTokenStorage::account_id(&user_info.id, &user_info.email);
+        KeyringTokenStorage::store(&keyring_id, &tokens)
             .context("Failed to store tokens in keyring")?;
 
         // Step 5: Open database and persist account
@@ -155,18 +186,39 @@ impl AuthCommand {
 
     /// Execute logout:
     /// 1. Get default account from DB
-    /// 2. Clear tokens from keyring
-    /// 3. Suspend account in DB
-    /// 4. Record audit entry
-    async fn execute_logout(&self, fmt: &dyn crate::output::OutputFormatter) -> Result<()> {
+    /// 2. Ask for confirmation (unless `--yes`)
+    /// 3. With `--revoke-all-sessions`: revoke every session of the user
+    ///    (best effort)
+    /// 4. Clear tokens from keyring and tell a running daemon to log out
+    /// 5. Unmount the FUSE filesystem if mounted
+    /// 6. With `--purge`: remove cached content and sync state for the account
+    /// 7. Suspend account in DB
+    /// 8. Record audit entry
+    ///
+    /// Without `--purge` the sync items are kept so placeholders are restored
+    /// immediately after logging in again.
+    async fn execute_logout(
+        &self,
+        purge: bool,
+        yes: bool,
+        revoke_all_sessions: bool,
+        account: Option<&str>,
+        fmt: &dyn crate::output::OutputFormatter,
+    ) -> Result<()> {
         use lnxdrive_cache::{pool::DatabasePool, SqliteStateRepository};
         use lnxdrive_core::{
+            config::Config,
             domain::{AuditAction, AuditEntry, AuditResult},
             ports::state_repository::IStateRepository,
         };
-        use lnxdrive_graph::auth::KeyringTokenStorage;
+        use lnxdrive_graph::{
+            auth::{GraphAuthAdapter, KeyringTokenStorage},
+            client::GraphClient,
+        };
 
-        // Step 1: Open database and get default account
+        use super::mount::expand_tilde;
+
+        // Step 1: Open database and get the account
         let db_path = dirs::data_dir()
             .unwrap_or_else(|| std::path::PathBuf::from("."))
             .join("lnxdrive")
@@ -177,10 +229,8 @@ impl AuthCommand {
             .context("Failed to open database")?;
         let state_repo = Arc::new(SqliteStateRepository::new(pool.pool().clone()));
 
-        let account = state_repo
-            .get_default_account()
-            .await
-            .context("Failed to query default account")?;
+        let config = Config::load_or_default(&Config::default_path());
+        let account = select_account(&state_repo, &config, account).await?;
 
         let mut account = match account {
             Some(a) => a,
@@ -191,22 +241,114 @@ impl AuthCommand {
         };
 
         let email = account.email().as_str().to_string();
-        info!(email = %email, "Logging out");
 
-        // Step 2: Clear tokens from keyring
-        KeyringTokenStorage::clear(&email).context("Failed to clear tokens from keyring")?;
+        // Step 2: Confirmation prompt
+        if !yes {
+            let prompt = if purge {
+                format!(
+                    "Log out {} and delete its cached files and sync state? [y/N] ",
+                    email
+                )
+            } else {
+                format!("Log out {}? [y/N] ", email)
+            };
+            if !confirm(&prompt)? {
+                fmt.info("Logout cancelled");
+                return Ok(());
+            }
+        }
+
+        info!(email = %email, purge, "Logging out");
 
-        // Step 3: Suspend account
+        // Step 3: Revoke every session of the user, only when asked to
+        let keyring_id = KeyringTokenStorage::account_id_of(&account);
+        let revoked = match KeyringTokenStorage::load(&keyring_id) {
+            _ if !revoke_all_sessions => false,
+            Ok(Some(tokens)) => {
+                let access_token = if tokens.is_expired() {
+                    match (tokens.refresh_token.as_deref(), config.auth.app_id.as_deref()) {
+                        (Some(refresh), Some(app_id)) => GraphAuthAdapter::with_app_id(app_id)
+                            .refresh(refresh)
+                            .await
+                            .map(|t| t.access_token)
+                            .ok(),
+                        _ => None,
+                    }
+                } else {
+                    Some(tokens.access_token.clone())
+                };
+
+                match access_token {
+                    Some(token) => match GraphClient::new(token)
+                        .with_http_config(&config.http)
+                        .revoke_sign_in_sessions()
+                        .await
+                    {
+                        Ok(()) => true,
+                        Err(e) => {
+                            warn!(error = %e, "Failed to revoke sign-in sessions");
+                            fmt.warn("Could not revoke the sign-in sessions of the account");
+                            false
+                        }
+                    },
+                    None => {
+                        fmt.warn("Access token expired; skipping token revocation");
+                        false
+                    }
+                }
+            }
+            Ok(None) => false,
+            Err(e) => {
+                warn!(error = %e, "Failed to read tokens for revocation");
+                false
+            }
+        };
+
+        // Step 4: Clear tokens from keyring, and from a daemon holding them
+        KeyringTokenStorage::delete(&keyring_id).context("Failed to clear tokens from keyring")?;
+        let daemon_logged_out = logout_daemon(&account).await;
+
+        // Step 5: Unmount the FUSE filesystem
+        let mount_point = expand_tilde(&config.fuse.mount_point);
+        let unmounted = unmount_fuse(&mount_point);
+
+        // Step 6: Purge cached content and sync state
+        let mut purged_items = 0;
+        if purge {
+            purged_items = state_repo
+                .purge_account_data(account.id())
+                .await
+                .context("Failed to purge account data")?;
+
+            let cache_dir = expand_tilde(&config.fuse.cache_dir);
+            if cache_dir.exists() {
+                tokio::fs::remove_dir_all(&cache_dir)
+                    .await
+                    .with_context(|| {
+                        format!("Failed to remove cache directory {}", cache_dir.display())
+                    })?;
+            }
+        }
+
+        // Step 7: Suspend account
         account.suspend();
+        if purge {
+            account.clear_delta_token();
+        }
         state_repo
             .save_account(&account)
             .await
             .context("Failed to update account in database")?;
 
-        // Step 4: Record audit entry
+        // Step 8: Record audit entry
         let audit_entry = AuditEntry::new(AuditAction::AuthLogout, AuditResult::success())
             .with_details(serde_json::json!({
                 "email": email,
+                "sessions_revoked": revoked,
+                "daemon_logged_out": daemon_logged_out,
+                "unmounted": unmounted,
+                "purged": purge,
+                "purged_items": purged_items,
             }));
 
         state_repo
@@ -216,24 +358,42 @@ impl AuthCommand {
 
         fmt.success("Logged out successfully");
         fmt.info("Credentials removed from keyring");
+        if revoked {
+            fmt.info("Signed out of all Microsoft apps and devices");
+        }
+        if daemon_logged_out {
+            fmt.info("The running daemon dropped its session");
+        }
+        if unmounted {
+            fmt.info(&format!("Unmounted {}", mount_point.display()));
+        }
+        if purge {
+            fmt.info(&format!(
+                "Removed {} sync items and the local content cache",
+                purged_items
+            ));
+        } else {
+            fmt.info("Placeholders kept; run 'lnxdrive auth login' to resume");
+        }
 
         Ok(())
     }
 
     /// Execute status check:
-    /// 1. Get default account from DB
+    /// 1. Get the account from DB
     /// 2. Check token state in keyring
     /// 3. Display account info and token validity
     async fn execute_status(
         &self,
+        account: Option<&str>,
         fmt: &dyn crate::output::OutputFormatter,
         format: OutputFormat,
     ) -> Result<()> {
         use lnxdrive_cache::{pool::DatabasePool, SqliteStateRepository};
-        use lnxdrive_core::ports::state_repository::IStateRepository;
+        use lnxdrive_core::config::Config;
         use lnxdrive_graph::auth::KeyringTokenStorage;
 
-        // Step 1: Open database and get default account
+        // Step 1: Open database and get the account
         let db_path = dirs::data_dir()
             .unwrap_or_else(|| std::path::PathBuf::from("."))
             .join("lnxdrive")
@@ -250,10 +410,8 @@ impl AuthCommand {
             .context("Failed to open database")?;
         let state_repo = Arc::new(SqliteStateRepository::new(pool.pool().clone()));
 
-        let account = state_repo
-            .get_default_account()
-            .await
-            .context("Failed to query default account")?;
+        let config = Config::load_or_default(&Config::default_path());
+        let account = select_account(&state_repo, &config, account).await?;
 
         let account = match account {
             Some(a) => a,
@@ -266,7 +424,8 @@ impl AuthCommand {
 
         // Step 2: Check tokens in keyring
         let email = account.email().as_str();
-        let token_status = match KeyringTokenStorage::load(email) {
+        let keyring_id = KeyringTokenStorage::account_id_of(&account);
+        let token_status = match KeyringTokenStorage::load(&keyring_id) {
             Ok(Some(tokens)) => {
                 if tokens.is_expired() {
                     "Expired"
@@ -327,3 +486,140 @@ impl AuthCommand {
         Ok(())
     }
 }
+
+/// Asks a yes/no question on stdin, defaulting to "no"
+fn confirm(prompt: &str) -> Result<bool> {
+    use std::io::Write;
+
+    print!("{}", prompt);
+    std::io::stdout().flush()?;
+
+    let mut answer = String::new();
+    std::io::stdin()
+        .read_line(&mut answer)
+        .context("Failed to read confirmation")?;
+
+    Ok(is_affirmative(&answer))
+}
+
+/// Returns true if the answer to a confirmation prompt means "yes"
+fn is_affirmative(answer: &str) -> bool {
+    matches!(answer.trim().to_lowercase().as_str(), "y" | "yes")
+}
+
+/// Tells a running daemon that syncs `account` to drop its session
+///
+/// The daemon keeps the tokens it loaded in memory, so deleting them from
+/// the keyring alone would let it go on syncing. Returns `true` if the
+/// daemon logged out; a daemon that is not running or syncs another
+/// account is left alone.
+async fn logout_daemon(account: &lnxdrive_core::domain::Account) -> bool {
+    use lnxdrive_ipc::service::{DBUS_NAME, DBUS_PATH};
+
+    let Ok(connection) = zbus::Connection::session().await else {
+        return false;
+    };
+    let Ok(reply) = connection
+        .call_method(
+            Some(DBUS_NAME),
+            DBUS_PATH,
+            Some(ACCOUNT_INTERFACE),
+            "GetInfo",
+            &(),
+        )
+        .await
+    else {
+        return false;
+    };
+    let serves_account = reply
+        .body()
+        .deserialize::<String>()
+        .ok()
+        .and_then(|info| serde_json::from_str::<serde_json::Value>(&info).ok())
+        .is_some_and(|info| info["id"].as_str() == Some(&account.id().to_string()));
+    if !serves_account {
+        return false;
+    }
+
+    match connection
+        .call_method(
+            Some(DBUS_NAME),
+            DBUS_PATH,
+            Some(AUTH_INTERFACE),
+            "Logout",
+            &(),
+        )
+        .await
+    {
+        Ok(_) => true,
+        Err(e) => {
+            warn!(error = %e, "Failed to log out the running daemon");
+            false
+        }
+    }
+}
+
+/// Unmounts the FUSE filesystem at `mount_point` if it is mounted
+///
+/// Returns `true` if an unmount was performed.
+fn unmount_fuse(mount_point: &Path) -> bool {
+    use super::mount::which_exists;
+
+    let mounted = std::fs::read_to_string("/proc/mounts")
+        .map(|mounts| {
+            mounts
+                .lines()
+                .filter_map(|line| line.split_whitespace().nth(1))
+                .any(|target| Path::new(target) == mount_point)
+        })
+        .unwrap_or(false);
+
+    if !mounted {
+        return false;
+    }
+
+    let fusermount = if which_exists("fusermount3") {
+        "fusermount3"
+    } else {
+        "fusermount"
+    };
+
+    match std::process::Command::new(fusermount)
+        .args(["-u", "-z"])
+        .arg(mount_point)
+        .output()
+    {
+        Ok(output) if output.status.success() => true,
+        Ok(output) => {
+            warn!(
+                stderr = %String::from_utf8_lossy(&output.stderr).trim(),
+                "Failed to unmount FUSE filesystem"
+            );
+            false
+        }
+        Err(e) => {
+            warn!(error = %e, "Failed to execute {}", fusermount);
+            false
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_affirmative() {
+        assert!(is_affirmative("y\n"));
+        assert!(is_affirmative("YES"));
+        assert!(!is_affirmative(""));
+        assert!(!is_affirmative("n"));
+        assert!(!is_affirmative("maybe"));
+    }
+
+    #[test]
+    fn test_unmount_fuse_not_mounted() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(!unmount_fuse(dir.path()));
+    }
+}
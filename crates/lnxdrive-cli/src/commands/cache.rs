@@ -0,0 +1,210 @@
+//! Cache command - Inspect and manage the local content cache
+//!
+//! Provides the `lnxdrive cache` CLI commands which:
+//! 1. Show how much space the cache uses and for what (`status`)
+//! 2. Dehydrate cached files to free space (`clean`)
+//! 3. Verify cached files and remove damaged ones (`verify`)
+//!
+//! All operations go through the daemon's `com.enigmora.LNXDrive.Cache`
+//! D-Bus interface, so eviction respects open files and in-flight
+//! hydrations of the mounted filesystem.
+
+use anyhow::{Context, Result};
+use clap::Subcommand;
+use lnxdrive_core::ports::{CacheCleanReport, CacheUsage, CacheVerifyReport};
+use lnxdrive_ipc::service::{DBUS_NAME, DBUS_PATH};
+
+use super::hydrate::format_bytes;
+use crate::output::{get_formatter, OutputFormat, OutputFormatter};
+
+/// D-Bus interface of the daemon that manages the cache
+const CACHE_INTERFACE: &str = "com.enigmora.LNXDrive.Cache";
+
+/// Cache subcommands
+#[derive(Debug, Subcommand)]
+pub enum CacheCommand {
+    /// Show cache size, pinned vs evictable bytes and a per-folder breakdown
+    Status,
+    /// Dehydrate cached files to free disk space
+    Clean {
+        /// Keep pinned files (otherwise they are unpinned and dehydrated)
+        #[arg(long)]
+        keep_pinned: bool,
+        /// Only dehydrate files not accessed for at least N days
+        #[arg(long, value_name = "N")]
+        older_than: Option<u32>,
+    },
+    /// Verify cached files and remove damaged ones
+    Verify,
+}
+
+impl CacheCommand {
+    /// Execute the cache command
+    pub async fn execute(&self, format: OutputFormat) -> Result<()> {
+        let formatter = get_formatter(matches!(format, OutputFormat::Json));
+
+        let result = match self {
+            CacheCommand::Status => call_daemon("GetStatus", &()).await,
+            CacheCommand::Clean {
+                keep_pinned,
+                older_than,
+            } => call_daemon("Clean", &(*keep_pinned, older_than.unwrap_or(0))).await,
+            CacheCommand::Verify => call_daemon("Verify", &()).await,
+        };
+        let json = match result {
+            Ok(json) => json,
+            Err(e) => {
+                formatter.error(&format!("{:#}", e));
+                return Ok(());
+            }
+        };
+
+        if matches!(format, OutputFormat::Json) {
+            let value: serde_json::Value =
+                serde_json::from_str(&json).context("Invalid reply from the daemon")?;
+            formatter.print_json(&value);
+            return Ok(());
+        }
+
+        match self {
+            CacheCommand::Status => print_usage(&serde_json::from_str(&json)?, &*formatter),
+            CacheCommand::Clean { .. } => {
+                print_clean_report(&serde_json::from_str(&json)?, &*formatter)
+            }
+            CacheCommand::Verify => print_verify_report(&serde_json::from_str(&json)?, &*formatter),
+        }
+        Ok(())
+    }
+}
+
+/// Calls a method of the daemon's Cache interface and returns its JSON reply
+async fn call_daemon<B>(method: &str, body: &B) -> Result<String>
+where
+    B: serde::Serialize + zbus::zvariant::DynamicType,
+{
+    let connection = zbus::Connection::session()
+        .await
+        .context("Failed to connect to the session bus")?;
+    let reply = connection
+        .call_method(
+            Some(DBUS_NAME),
+            DBUS_PATH,
+            Some(CACHE_INTERFACE),
+            method,
+            body,
+        )
+        .await
+        .context("Cache request to the daemon failed. Is the daemon running?")?;
+    Ok(reply.body().deserialize::<String>()?)
+}
+
+/// Prints cache usage for humans
+fn print_usage(usage: &CacheUsage, formatter: &dyn OutputFormatter) {
+    formatter.success(&format!("Cache uses {}", format_bytes(usage.total_bytes)));
+    formatter.info(&format!("Pinned:    {}", format_bytes(usage.pinned_bytes)));
+    formatter.info(&format!(
+        "Evictable: {}",
+        format_bytes(usage.evictable_bytes)
+    ));
+    if usage.modified_bytes > 0 {
+        formatter.info(&format!(
+            "Modified:  {} (waiting for upload)",
+            format_bytes(usage.modified_bytes)
+        ));
+    }
+    if !usage.folders.is_empty() {
+        formatter.info("");
+        formatter.info("By folder:");
+        for folder in &usage.folders {
+            formatter.info(&format!(
+                "  {:<30} {:>12}  ({} files)",
+                folder.folder,
+                format_bytes(folder.bytes),
+                folder.files
+            ));
+        }
+    }
+}
+
+/// Prints the outcome of `cache clean` for humans
+fn print_clean_report(report: &CacheCleanReport, formatter: &dyn OutputFormatter) {
+    formatter.success(&format!(
+        "Dehydrated {} file(s), freed {}",
+        report.dehydrated,
+        format_bytes(report.bytes_freed)
+    ));
+    if report.skipped > 0 {
+        formatter.warn(&format!(
+            "Skipped {} file(s) (open or changed locally)",
+            report.skipped
+        ));
+    }
+    for error in &report.errors {
+        formatter.error(error);
+    }
+}
+
+/// Prints the outcome of `cache verify` for humans
+fn print_verify_report(report: &CacheVerifyReport, formatter: &dyn OutputFormatter) {
+    if report.healed == 0 {
+        formatter.success(&format!(
+            "Verified {} cached file(s), all intact",
+            report.checked
+        ));
+    } else {
+        formatter.warn(&format!(
+            "Verified {} cached file(s), removed {} damaged file(s); \
+             they will be downloaded again on next access",
+            report.checked, report.healed
+        ));
+    }
+    for error in &report.errors {
+        formatter.error(error);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use clap::Parser;
+
+    use super::*;
+
+    #[derive(Debug, Parser)]
+    struct TestCli {
+        #[command(subcommand)]
+        command: CacheCommand,
+    }
+
+    #[test]
+    fn test_parse_clean_options() {
+        let cli = TestCli::parse_from(["test", "clean", "--keep-pinned", "--older-than", "30"]);
+        assert!(matches!(
+            cli.command,
+            CacheCommand::Clean {
+                keep_pinned: true,
+                older_than: Some(30)
+            }
+        ));
+
+        let cli = TestCli::parse_from(["test", "clean"]);
+        assert!(matches!(
+            cli.command,
+            CacheCommand::Clean {
+                keep_pinned: false,
+                older_than: None
+            }
+        ));
+    }
+
+    #[test]
+    fn test_parse_status_and_verify() {
+        assert!(matches!(
+            TestCli::parse_from(["test", "status"]).command,
+            CacheCommand::Status
+        ));
+        assert!(matches!(
+            TestCli::parse_from(["test", "verify"]).command,
+            CacheCommand::Verify
+        ));
+    }
+}
@@ -157,6 +157,8 @@ impl ConfigCommand {
                         .info("  sync.poll_interval                   - Seconds between polling");
                     formatter
                         .info("  sync.debounce_delay                  - Seconds debounce delay");
+                    formatter
+                        .info("  sync.startup_reconciliation          - Scan for offline changes");
                     formatter.info("  rate_limiting.delta_requests_per_minute");
                     formatter.info("  rate_limiting.upload_concurrent");
                     formatter.info("  rate_limiting.upload_requests_per_minute");
@@ -174,6 +176,7 @@ impl ConfigCommand {
                     formatter.info(
                         "  logging.level                        - trace|debug|info|warn|error",
                     );
+                    formatter.info("  logging.format                       - text|json");
                     formatter.info("  logging.file                         - Log file path");
                     formatter
                         .info("  logging.max_size_mb                  - Max log file size (MiB)");
@@ -268,11 +271,11 @@ impl ConfigCommand {
 /// Apply a dot-notation key/value pair to a Config struct
 ///
 /// Supported keys:
-/// - sync.root, sync.poll_interval, sync.debounce_delay
+/// - sync.root, sync.poll_interval, sync.debounce_delay, sync.startup_reconciliation
 /// - rate_limiting.delta_requests_per_minute, etc.
 /// - large_files.threshold_mb, etc.
 /// - conflicts.default_strategy
-/// - logging.level, logging.file, logging.max_size_mb, logging.max_files
+/// - logging.level, logging.format, logging.file, logging.max_size_mb, logging.max_files
 /// - auth.app_id
 fn apply_config_value(
     config: &mut lnxdrive_core::config::Config,
@@ -294,6 +297,11 @@ fn apply_config_value(
                 .parse::<u64>()
                 .context("Expected a positive integer for sync.debounce_delay")?;
         }
+        "sync.startup_reconciliation" => {
+            config.sync.startup_reconciliation = value
+                .parse::<bool>()
+                .context("Expected true or false for sync.startup_reconciliation")?;
+        }
 
         // --- rate_limiting ---
         "rate_limiting.delta_requests_per_minute" => {
@@ -348,6 +356,9 @@ fn apply_config_value(
         "logging.level" => {
             config.logging.level = value.to_string();
         }
+        "logging.format" => {
+            config.logging.format = value.to_string();
+        }
         "logging.file" => {
             config.logging.file = PathBuf::from(value);
         }
@@ -406,6 +417,14 @@ mod tests {
         assert_eq!(config.sync.debounce_delay, 5);
     }
 
+    #[test]
+    fn test_apply_sync_startup_reconciliation() {
+        let mut config = Config::default();
+        apply_config_value(&mut config, "sync.startup_reconciliation", "false").unwrap();
+        assert!(!config.sync.startup_reconciliation);
+        assert!(apply_config_value(&mut config, "sync.startup_reconciliation", "maybe").is_err());
+    }
+
     #[test]
     fn test_apply_rate_limiting_delta() {
         let mut config = Config::default();
@@ -486,6 +505,13 @@ mod tests {
         assert_eq!(config.logging.level, "debug");
     }
 
+    #[test]
+    fn test_apply_logging_format() {
+        let mut config = Config::default();
+        apply_config_value(&mut config, "logging.format", "json").unwrap();
+        assert_eq!(config.logging.format, "json");
+    }
+
     #[test]
     fn test_apply_logging_file() {
         let mut config = Config::default();
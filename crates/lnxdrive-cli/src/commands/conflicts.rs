@@ -4,8 +4,10 @@
 //! 1. Lists all unresolved conflicts in a table format
 //! 2. Resolves a specific conflict by ID with a chosen strategy
 //! 3. Previews conflict details showing local vs remote metadata
+//! 4. Tests which auto-resolution policy rule applies to a path
 
 use std::{
+    collections::HashSet,
     path::{Path, PathBuf},
     sync::Arc,
 };
@@ -14,6 +16,7 @@ use anyhow::{Context, Result};
 use clap::Subcommand;
 use tracing::info;
 
+use super::account::select_account;
 use crate::output::{get_formatter, OutputFormat};
 
 /// T237: Conflicts subcommands
@@ -34,17 +37,35 @@ pub enum ConflictsCommand {
         /// Conflict ID
         id: String,
     },
+    /// Inspect the auto-resolution policy
+    Policy {
+        #[command(subcommand)]
+        command: PolicyCommand,
+    },
+}
+
+/// Conflict policy subcommands
+#[derive(Debug, Subcommand)]
+pub enum PolicyCommand {
+    /// Show which rule would resolve a conflict on the given path
+    Test {
+        /// Path inside the sync root (absolute, or relative to the sync root)
+        path: String,
+    },
 }
 
 impl ConflictsCommand {
     /// Execute the conflicts command
-    pub async fn execute(&self, format: OutputFormat) -> Result<()> {
+    pub async fn execute(&self, format: OutputFormat, account: Option<&str>) -> Result<()> {
         match self {
-            ConflictsCommand::List => self.execute_list(format).await,
+            ConflictsCommand::List => self.execute_list(account, format).await,
             ConflictsCommand::Resolve { id, strategy } => {
                 self.execute_resolve(id, strategy, format).await
             }
             ConflictsCommand::Preview { id } => self.execute_preview(id, format).await,
+            ConflictsCommand::Policy {
+                command: PolicyCommand::Test { path },
+            } => self.execute_policy_test(path, format),
         }
     }
 
@@ -74,9 +95,12 @@ impl ConflictsCommand {
         Ok(Some(state_repo))
     }
 
-    /// T238: List unresolved conflicts
-    async fn execute_list(&self, format: OutputFormat) -> Result<()> {
-        use lnxdrive_core::ports::state_repository::IStateRepository;
+    /// T238: List unresolved conflicts of the account
+    async fn execute_list(&self, account: Option<&str>, format: OutputFormat) -> Result<()> {
+        use lnxdrive_core::{
+            config::Config,
+            ports::state_repository::{IStateRepository, ItemFilter},
+        };
 
         let formatter = get_formatter(matches!(format, OutputFormat::Json));
 
@@ -85,11 +109,23 @@ impl ConflictsCommand {
             None => return Ok(()),
         };
 
-        let conflicts = state_repo
+        let mut conflicts = state_repo
             .get_unresolved_conflicts()
             .await
             .context("Failed to query unresolved conflicts")?;
 
+        let config = Config::load_or_default(&Config::default_path());
+        if let Some(account) = select_account(&state_repo, &config, account).await? {
+            let item_ids: HashSet<_> = state_repo
+                .query_items(&ItemFilter::new().with_account_id(*account.id()))
+                .await
+                .context("Failed to query the account's items")?
+                .iter()
+                .map(|item| *item.id())
+                .collect();
+            conflicts.retain(|conflict| item_ids.contains(conflict.item_id()));
+        }
+
         info!(count = conflicts.len(), "Retrieved unresolved conflicts");
 
         if matches!(format, OutputFormat::Json) {
@@ -101,12 +137,12 @@ impl ConflictsCommand {
                         "item_id": c.item_id().to_string(),
                         "detected_at": c.detected_at().to_rfc3339(),
                         "local_version": {
-                            "hash": c.local_version().hash().to_string(),
+                            "hash": c.local_version().hash().map(|h| h.to_string()),
                             "size_bytes": c.local_version().size_bytes(),
                             "modified_at": c.local_version().modified_at().to_rfc3339(),
                         },
                         "remote_version": {
-                            "hash": c.remote_version().hash().to_string(),
+                            "hash": c.remote_version().hash().map(|h| h.to_string()),
                             "size_bytes": c.remote_version().size_bytes(),
                             "modified_at": c.remote_version().modified_at().to_rfc3339(),
                         },
@@ -307,13 +343,13 @@ impl ConflictsCommand {
                 "detected_at": conflict.detected_at().to_rfc3339(),
                 "is_resolved": conflict.is_resolved(),
                 "local_version": {
-                    "hash": conflict.local_version().hash().to_string(),
+                    "hash": conflict.local_version().hash().map(|h| h.to_string()),
                     "size_bytes": conflict.local_version().size_bytes(),
                     "modified_at": conflict.local_version().modified_at().to_rfc3339(),
                     "etag": conflict.local_version().etag(),
                 },
                 "remote_version": {
-                    "hash": conflict.remote_version().hash().to_string(),
+                    "hash": conflict.remote_version().hash().map(|h| h.to_string()),
                     "size_bytes": conflict.remote_version().size_bytes(),
                     "modified_at": conflict.remote_version().modified_at().to_rfc3339(),
                     "etag": conflict.remote_version().etag(),
@@ -340,7 +376,10 @@ impl ConflictsCommand {
         formatter.info("Local Version:");
         formatter.info(&format!(
             "  Hash:        {}",
-            conflict.local_version().hash()
+            conflict
+                .local_version()
+                .hash()
+                .map_or("unknown", |hash| hash.as_str())
         ));
         formatter.info(&format!(
             "  Size:        {}",
@@ -361,7 +400,10 @@ impl ConflictsCommand {
         formatter.info("Remote Version:");
         formatter.info(&format!(
             "  Hash:        {}",
-            conflict.remote_version().hash()
+            conflict
+                .remote_version()
+                .hash()
+                .map_or("unknown", |hash| hash.as_str())
         ));
         formatter.info(&format!(
             "  Size:        {}",
@@ -405,15 +447,15 @@ impl ConflictsCommand {
             if local_newer { "Local" } else { "Remote" }
         ));
 
-        let hashes_match = conflict.local_version().hash() == conflict.remote_version().hash();
-        formatter.info(&format!(
-            "  Hashes:      {}",
-            if hashes_match {
-                "Match (content is identical)"
-            } else {
-                "Different (content has diverged)"
-            }
-        ));
+        let hashes = match (
+            conflict.local_version().hash(),
+            conflict.remote_version().hash(),
+        ) {
+            (Some(local), Some(remote)) if local == remote => "Match (content is identical)",
+            (Some(_), Some(_)) => "Different (content has diverged)",
+            _ => "Unknown (a version has no hash)",
+        };
+        formatter.info(&format!("  Hashes:      {}", hashes));
 
         formatter.info("");
         formatter.info("To resolve, run:");
@@ -426,6 +468,92 @@ impl ConflictsCommand {
     }
 }
 
+impl ConflictsCommand {
+    /// Report which conflict policy rule applies to a path
+    fn execute_policy_test(&self, path: &str, format: OutputFormat) -> Result<()> {
+        use lnxdrive_conflict::{MatchedRule, PolicyEngine};
+        use lnxdrive_core::config::Config;
+
+        let formatter = get_formatter(matches!(format, OutputFormat::Json));
+
+        let config = Config::load_or_default(&Config::default_path());
+        let sync_root = super::mount::expand_tilde(&config.sync.root.to_string_lossy());
+
+        let relative = match policy_relative_path(path, &sync_root) {
+            Some(relative) => relative,
+            None => {
+                formatter.error(&format!(
+                    "{} is not inside the sync root ({})",
+                    path,
+                    sync_root.display()
+                ));
+                return Ok(());
+            }
+        };
+
+        let policy = match PolicyEngine::from_config(&config.conflicts) {
+            Ok(policy) => policy,
+            Err(err) => {
+                formatter.error(&format!("Invalid conflict policy: {}", err));
+                return Ok(());
+            }
+        };
+
+        let decision = policy.evaluate(&relative);
+
+        if matches!(format, OutputFormat::Json) {
+            let json = serde_json::json!({
+                "path": relative,
+                "resolution": decision.resolution.to_string(),
+                "strategy": decision.strategy,
+                "automatic": decision.is_automatic(),
+                "rule": decision.rule,
+            });
+            formatter.print_json(&json);
+            return Ok(());
+        }
+
+        match &decision.rule {
+            MatchedRule::Rule { index, pattern } => {
+                formatter.success(&format!(
+                    "{} matches rule #{}: {} -> {}",
+                    relative, index, pattern, decision.strategy
+                ));
+            }
+            MatchedRule::Default => {
+                formatter.info(&format!(
+                    "{} matches no rule; default strategy applies: {}",
+                    relative, decision.strategy
+                ));
+            }
+        }
+        if !decision.is_automatic() {
+            formatter.info("Conflicts on this path will wait for manual resolution.");
+        }
+
+        Ok(())
+    }
+}
+
+/// Convert a user-supplied path into a path relative to the sync root
+///
+/// Absolute paths must lie inside `sync_root`; relative paths are taken as
+/// already relative to it. Returns `None` for absolute paths outside the root.
+fn policy_relative_path(path: &str, sync_root: &Path) -> Option<String> {
+    let path = Path::new(path);
+    let relative = if path.is_absolute() {
+        path.strip_prefix(sync_root).ok()?
+    } else {
+        path
+    };
+    Some(
+        relative
+            .to_string_lossy()
+            .trim_start_matches("./")
+            .to_string(),
+    )
+}
+
 /// Truncate a UUID string for display, showing only the first N characters
 fn truncate_id(id: String, max_len: usize) -> String {
     if id.len() <= max_len {
@@ -477,6 +605,34 @@ mod tests {
         assert_eq!(truncate_id(id, 14), "12345678901234");
     }
 
+    #[test]
+    fn test_policy_relative_path_absolute_inside_root() {
+        let root = Path::new("/home/user/OneDrive");
+        assert_eq!(
+            policy_relative_path("/home/user/OneDrive/notes/todo.md", root),
+            Some("notes/todo.md".to_string())
+        );
+    }
+
+    #[test]
+    fn test_policy_relative_path_outside_root() {
+        let root = Path::new("/home/user/OneDrive");
+        assert_eq!(policy_relative_path("/tmp/todo.md", root), None);
+    }
+
+    #[test]
+    fn test_policy_relative_path_relative() {
+        let root = Path::new("/home/user/OneDrive");
+        assert_eq!(
+            policy_relative_path("./logs/app.log", root),
+            Some("logs/app.log".to_string())
+        );
+        assert_eq!(
+            policy_relative_path("report.docx", root),
+            Some("report.docx".to_string())
+        );
+    }
+
     #[test]
     fn test_format_bytes_small() {
         assert_eq!(format_bytes(0), "0 B");
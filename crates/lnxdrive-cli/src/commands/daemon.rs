@@ -1,7 +1,10 @@
 //! Daemon management commands
 //!
 //! Provides the `lnxdrive daemon` CLI subcommands for controlling the
-//! LNXDrive background synchronization service via systemd user units.
+//! LNXDrive background synchronization service. A running daemon is
+//! controlled through its `com.enigmora.LNXDrive.Manager` D-Bus
+//! interface; the systemd user unit is used to start it, and as a
+//! fallback when it does not answer on the bus.
 //!
 //! # Subcommands
 //!
@@ -9,18 +12,25 @@
 //! - `stop`    - Stop the daemon service
 //! - `status`  - Show daemon status
 //! - `restart` - Restart the daemon service
+//! - `reload`  - Apply changes to the configuration file
 
 use std::process::Command;
 
 use anyhow::{Context, Result};
 use clap::Subcommand;
-use tracing::info;
+use lnxdrive_ipc::service::{DBUS_NAME, DBUS_PATH};
+use serde::de::DeserializeOwned;
+use tracing::{debug, info};
 
+use super::status::format_uptime;
 use crate::output::{get_formatter, OutputFormat};
 
 /// Service unit name for the LNXDrive daemon
 const SYSTEMD_UNIT: &str = "lnxdrive";
 
+/// D-Bus interface for daemon lifecycle control
+const MANAGER_INTERFACE: &str = "com.enigmora.LNXDrive.Manager";
+
 // ============================================================================
 // T226: DaemonCommand with subcommands
 // ============================================================================
@@ -36,6 +46,8 @@ pub enum DaemonCommand {
     Status,
     /// Restart the LNXDrive daemon
     Restart,
+    /// Validate the configuration file and apply it to the running daemon
+    Reload,
 }
 
 impl DaemonCommand {
@@ -43,13 +55,47 @@ impl DaemonCommand {
     pub async fn execute(&self, format: OutputFormat) -> Result<()> {
         match self {
             DaemonCommand::Start => daemon_start(format),
-            DaemonCommand::Stop => daemon_stop(format),
-            DaemonCommand::Status => daemon_status(format),
-            DaemonCommand::Restart => daemon_restart(format),
+            DaemonCommand::Stop => daemon_stop(format).await,
+            DaemonCommand::Status => daemon_status(format).await,
+            DaemonCommand::Restart => daemon_restart(format).await,
+            DaemonCommand::Reload => daemon_reload(format).await,
         }
     }
 }
 
+/// Calls a method without arguments on the daemon's Manager interface
+async fn call_manager<R>(method: &str) -> Result<R>
+where
+    R: DeserializeOwned + zbus::zvariant::Type,
+{
+    let connection = zbus::Connection::session()
+        .await
+        .context("Failed to connect to the session bus")?;
+    let reply = connection
+        .call_method(
+            Some(DBUS_NAME),
+            DBUS_PATH,
+            Some(MANAGER_INTERFACE),
+            method,
+            &(),
+        )
+        .await
+        .with_context(|| format!("Manager.{method} failed"))?;
+    Ok(reply.body().deserialize::<R>()?)
+}
+
+/// Prints the outcome of a lifecycle action
+fn report_action(format: OutputFormat, action: &str, message: &str) {
+    let formatter = get_formatter(matches!(format, OutputFormat::Json));
+    formatter.success(message);
+    if matches!(format, OutputFormat::Json) {
+        formatter.print_json(&serde_json::json!({
+            "action": action,
+            "success": true,
+        }));
+    }
+}
+
 // ============================================================================
 // T227: daemon start
 // ============================================================================
@@ -103,10 +149,20 @@ fn daemon_start(format: OutputFormat) -> Result<()> {
 // T228: daemon stop
 // ============================================================================
 
-/// Stops the LNXDrive daemon via systemctl
+/// Stops the LNXDrive daemon
 ///
-/// Runs `systemctl --user stop lnxdrive` and reports the result.
-fn daemon_stop(format: OutputFormat) -> Result<()> {
+/// Asks the daemon to shut down over D-Bus (`Manager.Shutdown`), which
+/// drains in-flight transfers first. Falls back to
+/// `systemctl --user stop lnxdrive` if the daemon does not answer.
+async fn daemon_stop(format: OutputFormat) -> Result<()> {
+    match call_manager::<()>("Shutdown").await {
+        Ok(()) => {
+            report_action(format, "stop", "LNXDrive daemon is shutting down");
+            return Ok(());
+        }
+        Err(e) => debug!(error = %e, "Daemon not reachable over D-Bus, using systemctl"),
+    }
+
     let formatter = get_formatter(matches!(format, OutputFormat::Json));
 
     info!("Stopping LNXDrive daemon via systemctl");
@@ -146,10 +202,48 @@ fn daemon_stop(format: OutputFormat) -> Result<()> {
 
 /// Shows the LNXDrive daemon status
 ///
-/// Runs `systemctl --user status lnxdrive` and displays the output.
-fn daemon_status(format: OutputFormat) -> Result<()> {
+/// Asks the running daemon (`Manager.GetStatus`) for its process ID,
+/// uptime and sync state. If it does not answer, runs
+/// `systemctl --user status lnxdrive` and displays the output.
+async fn daemon_status(format: OutputFormat) -> Result<()> {
     let formatter = get_formatter(matches!(format, OutputFormat::Json));
 
+    match call_manager::<String>("GetStatus").await {
+        Ok(json) => {
+            let status: serde_json::Value =
+                serde_json::from_str(&json).context("Invalid reply from the daemon")?;
+            if matches!(format, OutputFormat::Json) {
+                formatter.print_json(&serde_json::json!({
+                    "action": "status",
+                    "status": status["status"],
+                    "active": true,
+                    "daemon": status,
+                }));
+                return Ok(());
+            }
+            formatter.success("LNXDrive daemon is running");
+            formatter.info(&format!(
+                "Version: {}",
+                status["version"].as_str().unwrap_or("?")
+            ));
+            formatter.info(&format!("PID: {}", status["pid"]));
+            formatter.info(&format!(
+                "Uptime: {}",
+                format_uptime(status["uptime_secs"].as_u64().unwrap_or(0))
+            ));
+            formatter.info(&format!(
+                "Sync state: {}",
+                status["sync_state"].as_str().unwrap_or("unknown")
+            ));
+            formatter.info(&match status["seconds_since_last_sync"].as_u64() {
+                Some(secs) => format!("Last successful sync: {} ago", format_uptime(secs)),
+                None => "Last successful sync: none yet".to_string(),
+            });
+            return Ok(());
+        }
+        Err(e) => debug!(error = %e, "Daemon not reachable over D-Bus, using systemctl"),
+    }
+
     info!("Querying LNXDrive daemon status via systemctl");
 
     let output = Command::new("systemctl")
@@ -223,10 +317,20 @@ fn daemon_status(format: OutputFormat) -> Result<()> {
 // T230: daemon restart
 // ============================================================================
 
-/// Restarts the LNXDrive daemon via systemctl
+/// Restarts the LNXDrive daemon
 ///
-/// Runs `systemctl --user restart lnxdrive` and reports the result.
-fn daemon_restart(format: OutputFormat) -> Result<()> {
+/// Asks the daemon to restart in place over D-Bus (`Manager.Restart`).
+/// Falls back to `systemctl --user restart lnxdrive` if the daemon does
+/// not answer.
+async fn daemon_restart(format: OutputFormat) -> Result<()> {
+    match call_manager::<()>("Restart").await {
+        Ok(()) => {
+            report_action(format, "restart", "LNXDrive daemon is restarting");
+            return Ok(());
+        }
+        Err(e) => debug!(error = %e, "Daemon not reachable over D-Bus, using systemctl"),
+    }
+
     let formatter = get_formatter(matches!(format, OutputFormat::Json));
 
     info!("Restarting LNXDrive daemon via systemctl");
@@ -260,6 +364,24 @@ fn daemon_restart(format: OutputFormat) -> Result<()> {
     Ok(())
 }
 
+// ============================================================================
+// daemon reload
+// ============================================================================
+
+/// Applies the configuration file to the running daemon
+///
+/// The daemon validates the file (`Manager.ReloadConfig`) and rejects it
+/// with the list of problems; a valid one takes effect after the current
+/// sync cycle.
+async fn daemon_reload(format: OutputFormat) -> Result<()> {
+    info!("Reloading LNXDrive daemon configuration");
+    call_manager::<()>("ReloadConfig")
+        .await
+        .context("Failed to reload the configuration. Is the daemon running?")?;
+    report_action(format, "reload", "Configuration reloaded");
+    Ok(())
+}
+
 // ============================================================================
 // Tests
 // ============================================================================
@@ -280,6 +402,7 @@ mod tests {
         let _stop = DaemonCommand::Stop;
         let _status = DaemonCommand::Status;
         let _restart = DaemonCommand::Restart;
+        let _reload = DaemonCommand::Reload;
     }
 
     #[test]
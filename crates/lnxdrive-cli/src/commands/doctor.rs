@@ -0,0 +1,638 @@
+//! Doctor command - Diagnose installation and configuration problems
+//!
+//! Provides the `lnxdrive doctor` CLI command which runs a battery of
+//! checks and reports each one as passed, warning or failed, together with
+//! a hint on how to fix it:
+//! - the configuration file is valid
+//! - the sync root, mount point and cache directory do not overlap
+//! - the sync root exists and is writable
+//! - the mount point is usable and not a stale FUSE mount
+//! - the cache and temporary directories are writable and have free space
+//! - the state database passes SQLite's integrity check
+//! - the system keyring is available
+//! - an account is logged in and its tokens are usable
+//! - the daemon is running and reachable over D-Bus
+//! - Microsoft Graph is reachable
+//! - the local clock agrees with the cloud's
+//! - the inotify watch limit leaves room for the sync root
+//!
+//! Only failed checks make the command exit with an error.
+
+use std::{
+    ffi::CString,
+    os::unix::{ffi::OsStrExt, fs::MetadataExt},
+    path::{Path, PathBuf},
+};
+
+use anyhow::Result;
+use clap::Args;
+use lnxdrive_cache::{pool::DatabasePool, SqliteStateRepository};
+use lnxdrive_core::{
+    config::{self, check_cache_dir, check_sync_root, check_temp_dir, Config, ValidationError},
+    domain::{Account, ClockSkew},
+};
+use lnxdrive_graph::{auth::KeyringTokenStorage, client::GraphClient};
+use lnxdrive_ipc::service::DBUS_NAME;
+use lnxdrive_sync::watcher::{count_directories, inotify_watch_limit};
+
+use super::{account::select_account, hydrate::format_bytes, mount::expand_tilde};
+use crate::output::{get_formatter, OutputFormat};
+
+/// Free space below which the temporary directory check fails (1 GiB)
+const MIN_TEMP_FREE_BYTES: u64 = 1024 * 1024 * 1024;
+
+/// Free space below which the cache directory check warns (1 GiB)
+const MIN_CACHE_FREE_BYTES: u64 = 1024 * 1024 * 1024;
+
+/// Share of the inotify watch limit (percent) above which the check warns
+const INOTIFY_WARN_PERCENT: usize = 80;
+
+/// Check the installation, account and configuration for problems
+#[derive(Debug, Args)]
+pub struct DoctorCommand {}
+
+/// Outcome of one doctor check
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Status {
+    Pass,
+    Warn,
+    Fail,
+}
+
+impl Status {
+    fn as_str(self) -> &'static str {
+        match self {
+            Status::Pass => "pass",
+            Status::Warn => "warn",
+            Status::Fail => "fail",
+        }
+    }
+}
+
+/// Result of one doctor check
+struct Check {
+    name: &'static str,
+    status: Status,
+    /// What is wrong; empty for passed checks
+    problems: Vec<String>,
+    /// How to fix the problems
+    hint: Option<String>,
+}
+
+impl Check {
+    fn pass(name: &'static str) -> Self {
+        Self {
+            name,
+            status: Status::Pass,
+            problems: Vec::new(),
+            hint: None,
+        }
+    }
+
+    fn warn(name: &'static str, problem: impl Into<String>, hint: impl Into<String>) -> Self {
+        Self {
+            name,
+            status: Status::Warn,
+            problems: vec![problem.into()],
+            hint: Some(hint.into()),
+        }
+    }
+
+    fn fail(name: &'static str, problem: impl Into<String>, hint: impl Into<String>) -> Self {
+        Self {
+            name,
+            status: Status::Fail,
+            problems: vec![problem.into()],
+            hint: Some(hint.into()),
+        }
+    }
+
+    /// Fails with every error in `errors`, passes if there is none
+    fn from_errors(name: &'static str, errors: Vec<ValidationError>, hint: &str) -> Self {
+        if errors.is_empty() {
+            return Self::pass(name);
+        }
+        Self {
+            name,
+            status: Status::Fail,
+            problems: errors.iter().map(|e| e.to_string()).collect(),
+            hint: Some(hint.to_string()),
+        }
+    }
+}
+
+impl DoctorCommand {
+    /// Execute the doctor command
+    pub async fn execute(&self, format: OutputFormat, account: Option<&str>) -> Result<()> {
+        let formatter = get_formatter(matches!(format, OutputFormat::Json));
+
+        let config_path = Config::default_path();
+        let config = Config::load_or_default(&config_path);
+        let db_path = database_path();
+        let account = match &db_path {
+            Some(path) => find_account(path, &config, account).await,
+            None => Ok(None),
+        };
+        let sync_root = account
+            .as_ref()
+            .ok()
+            .and_then(Option::as_ref)
+            .map(|account| account.sync_root().as_path().clone())
+            .unwrap_or_else(|| PathBuf::from(&config.sync.root));
+        let clock_skew = GraphClient::new("")
+            .with_http_config(&config.http)
+            .measure_clock_skew()
+            .await;
+
+        let checks = [
+            Check::from_errors(
+                "config",
+                config.validate(),
+                &format!("Fix the listed settings in {}", config_path.display()),
+            ),
+            Check::from_errors(
+                "layout",
+                config.validate_layout(&sync_root),
+                "Move the sync root, mount point and cache directory apart",
+            ),
+            Check::from_errors(
+                "sync_root",
+                check_sync_root(&sync_root).err().into_iter().collect(),
+                "Create the sync root or make it writable",
+            ),
+            check_mount_point(&expand_tilde(&config.fuse.mount_point)),
+            check_cache_space(&expand_tilde(&config.fuse.cache_dir)),
+            Check::from_errors(
+                "temp_dir",
+                check_temp_space(&config.fuse.temp_dir_path())
+                    .err()
+                    .into_iter()
+                    .collect(),
+                "Free up space or point fuse.temp_dir at a larger filesystem",
+            ),
+            check_database(db_path.as_deref()).await,
+            check_keyring(),
+            check_account(&account),
+            check_daemon().await,
+            check_network(&clock_skew),
+            check_clock(&clock_skew),
+            check_watch_headroom(
+                count_directories(&config::expand_tilde(&sync_root)),
+                inotify_watch_limit(),
+            ),
+        ];
+
+        if matches!(format, OutputFormat::Json) {
+            let results: Vec<_> = checks
+                .iter()
+                .map(|check| {
+                    serde_json::json!({
+                        "check": check.name,
+                        "status": check.status.as_str(),
+                        "ok": check.status != Status::Fail,
+                        "errors": check.problems,
+                        "hint": check.hint,
+                    })
+                })
+                .collect();
+            formatter.print_json(&serde_json::json!({
+                "sync_root": sync_root.display().to_string(),
+                "checks": results,
+            }));
+        } else {
+            for check in &checks {
+                match check.status {
+                    Status::Pass => formatter.success(check.name),
+                    Status::Warn => {
+                        for problem in &check.problems {
+                            formatter.warn(&format!("{}: {}", check.name, problem));
+                        }
+                    }
+                    Status::Fail => {
+                        for problem in &check.problems {
+                            formatter.error(&format!("{}: {}", check.name, problem));
+                        }
+                    }
+                }
+                if let Some(hint) = &check.hint {
+                    formatter.info(&format!("Hint: {}", hint));
+                }
+            }
+        }
+
+        let failed = checks.iter().filter(|c| c.status == Status::Fail).count();
+        if failed > 0 {
+            anyhow::bail!("{} of {} checks failed", failed, checks.len());
+        }
+        Ok(())
+    }
+}
+
+/// Check that the mount point can be mounted on, or is mounted
+///
+/// A mount point whose FUSE session died answers every access with
+/// `ENOTCONN` until it is detached.
+fn check_mount_point(mount_point: &Path) -> Check {
+    const NAME: &str = "mount_point";
+    let shown = mount_point.display();
+
+    let meta = match std::fs::metadata(mount_point) {
+        Ok(meta) => meta,
+        Err(e) if e.raw_os_error() == Some(libc::ENOTCONN) => {
+            return Check::fail(
+                NAME,
+                format!("stale FUSE mount at {}", shown),
+                format!(
+                    "Run `fusermount3 -u -z {}`, then `lnxdrive daemon restart`",
+                    shown
+                ),
+            );
+        }
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            return Check::warn(
+                NAME,
+                format!("{} does not exist, files cannot be mounted", shown),
+                format!("Run `mkdir -p {}`", shown),
+            );
+        }
+        Err(e) => {
+            return Check::fail(
+                NAME,
+                format!("cannot access {} ({})", shown, e),
+                "Fix the permissions of the mount point or change fuse.mount_point",
+            );
+        }
+    };
+    if !meta.is_dir() {
+        return Check::fail(
+            NAME,
+            format!("not a directory: {}", shown),
+            "Change fuse.mount_point to a directory",
+        );
+    }
+
+    // A mounted filesystem has another device than the directory holding it
+    let mounted = mount_point
+        .parent()
+        .and_then(|parent| std::fs::metadata(parent).ok())
+        .is_some_and(|parent| parent.dev() != meta.dev());
+    let empty = std::fs::read_dir(mount_point)
+        .map(|mut entries| entries.next().is_none())
+        .unwrap_or(false);
+    if !mounted && !empty {
+        return Check::fail(
+            NAME,
+            format!("{} is not empty", shown),
+            "Move the files out of the mount point or change fuse.mount_point",
+        );
+    }
+    Check::pass(NAME)
+}
+
+/// Check that the cache directory is writable and has free space left
+fn check_cache_space(cache_dir: &Path) -> Check {
+    const NAME: &str = "cache_dir";
+
+    if let Err(e) = check_cache_dir(cache_dir) {
+        return Check::fail(
+            NAME,
+            e.to_string(),
+            "Make the cache directory writable or change fuse.cache_dir",
+        );
+    }
+    match available_space(cache_dir) {
+        Ok(available) if available < MIN_CACHE_FREE_BYTES => Check::warn(
+            NAME,
+            format!(
+                "only {} free in {}, files cannot be hydrated",
+                format_bytes(available),
+                cache_dir.display()
+            ),
+            "Free up space or lower fuse.cache_max_size_gb",
+        ),
+        Ok(_) => Check::pass(NAME),
+        Err(e) => Check::warn(
+            NAME,
+            format!("cannot read free space of {} ({})", cache_dir.display(), e),
+            "Check that the cache directory's filesystem is mounted",
+        ),
+    }
+}
+
+/// Check that the temporary directory is writable and has enough free space
+///
+/// Downloads and atomic writes are staged there at full size, so a small
+/// filesystem (e.g. `/tmp` on tmpfs) makes large downloads fail.
+fn check_temp_space(temp_dir: &Path) -> Result<(), ValidationError> {
+    check_temp_dir(temp_dir)?;
+    let fail = |message: String| ValidationError {
+        field: "fuse.temp_dir".into(),
+        message,
+    };
+    let available = available_space(temp_dir).map_err(|e| {
+        fail(format!(
+            "cannot read free space of {} ({})",
+            temp_dir.display(),
+            e
+        ))
+    })?;
+    if available < MIN_TEMP_FREE_BYTES {
+        return Err(fail(format!(
+            "only {} free in {}, large downloads may fail",
+            format_bytes(available),
+            temp_dir.display()
+        )));
+    }
+    Ok(())
+}
+
+/// Check that the state database opens and passes SQLite's quick check
+async fn check_database(db_path: Option<&Path>) -> Check {
+    const NAME: &str = "database";
+    const HINT: &str = "Stop the daemon and move the database aside; \
+                        it is recreated on the next `lnxdrive auth login`";
+
+    let Some(db_path) = db_path.filter(|path| path.exists()) else {
+        return Check::pass(NAME);
+    };
+    let pool = match DatabasePool::new(db_path).await {
+        Ok(pool) => pool,
+        Err(e) => {
+            return Check::fail(
+                NAME,
+                format!("cannot open {} ({})", db_path.display(), e),
+                HINT,
+            )
+        }
+    };
+    match pool.check_integrity().await {
+        Ok(problems) if problems.is_empty() => Check::pass(NAME),
+        Ok(problems) => Check {
+            name: NAME,
+            status: Status::Fail,
+            problems,
+            hint: Some(HINT.to_string()),
+        },
+        Err(e) => Check::fail(NAME, format!("integrity check failed ({})", e), HINT),
+    }
+}
+
+/// Check that the system keyring can be read
+fn check_keyring() -> Check {
+    match KeyringTokenStorage::list() {
+        Ok(_) => Check::pass("keyring"),
+        Err(e) => Check::fail(
+            "keyring",
+            format!("{:#}", e),
+            "Start and unlock a Secret Service keyring such as GNOME Keyring or KWallet",
+        ),
+    }
+}
+
+/// Check that an account is logged in with usable tokens
+///
+/// An expired access token is fine as long as a refresh token can renew it.
+fn check_account(account: &Result<Option<Account>>) -> Check {
+    const NAME: &str = "account";
+    const HINT: &str = "Run `lnxdrive auth login`";
+
+    let account = match account {
+        Ok(Some(account)) => account,
+        Ok(None) => return Check::fail(NAME, "no account is logged in", HINT),
+        Err(e) => {
+            return Check::fail(
+                NAME,
+                format!("{:#}", e),
+                "Choose an account with --account or `lnxdrive account use`",
+            )
+        }
+    };
+    match KeyringTokenStorage::load(&KeyringTokenStorage::account_id_of(account)) {
+        Ok(Some(tokens)) if tokens.is_expired() && tokens.refresh_token.is_none() => Check::fail(
+            NAME,
+            "the access token expired and cannot be refreshed",
+            HINT,
+        ),
+        Ok(Some(_)) => Check::pass(NAME),
+        Ok(None) => Check::fail(
+            NAME,
+            format!("no tokens for {} in the keyring", account.email().as_str()),
+            HINT,
+        ),
+        Err(e) => Check::fail(
+            NAME,
+            format!("cannot read tokens ({:#})", e),
+            "Fix the keyring check first",
+        ),
+    }
+}
+
+/// Check that the daemon owns its name on the session bus
+async fn check_daemon() -> Check {
+    const NAME: &str = "daemon";
+
+    let connection = match zbus::Connection::session().await {
+        Ok(connection) => connection,
+        Err(e) => {
+            return Check::fail(
+                NAME,
+                format!("cannot connect to the session bus ({})", e),
+                "Run lnxdrive inside your desktop session",
+            )
+        }
+    };
+    let running = match zbus::fdo::DBusProxy::new(&connection).await {
+        Ok(proxy) => match DBUS_NAME.try_into() {
+            Ok(name) => proxy.name_has_owner(name).await.unwrap_or(false),
+            Err(_) => false,
+        },
+        Err(_) => false,
+    };
+    if running {
+        Check::pass(NAME)
+    } else {
+        Check::fail(
+            NAME,
+            "the daemon is not running",
+            "Run `lnxdrive daemon start`",
+        )
+    }
+}
+
+/// Check that Microsoft Graph answered the clock measurement
+fn check_network(clock_skew: &Result<ClockSkew>) -> Check {
+    match clock_skew {
+        Ok(_) => Check::pass("network"),
+        Err(e) => Check::fail(
+            "network",
+            format!("cannot reach Microsoft Graph ({:#})", e),
+            "Check the network connection and any firewall or proxy",
+        ),
+    }
+}
+
+/// Check that the local clock is within the tolerated skew of the Graph
+/// servers' clock
+///
+/// A wrong clock makes tokens look expired and local modification times
+/// incomparable with remote ones.
+fn check_clock(clock_skew: &Result<ClockSkew>) -> Check {
+    match clock_skew {
+        Ok(skew) if skew.is_significant() => Check::fail(
+            "clock",
+            format!("local time is {}", skew),
+            "Enable time synchronization, e.g. `sudo timedatectl set-ntp true`",
+        ),
+        Ok(_) => Check::pass("clock"),
+        Err(_) => Check::warn(
+            "clock",
+            "cannot compare with server time",
+            "Fix the network check first",
+        ),
+    }
+}
+
+/// Check that watching `directories` directories stays below the inotify limit
+///
+/// Past the limit, changes in the remaining directories go unnoticed.
+fn check_watch_headroom(directories: usize, limit: Option<usize>) -> Check {
+    const NAME: &str = "inotify";
+
+    let Some(limit) = limit else {
+        return Check::warn(
+            NAME,
+            "cannot read fs.inotify.max_user_watches",
+            "Check that /proc/sys/fs/inotify is available",
+        );
+    };
+    let hint = format!(
+        "Run `sudo sysctl fs.inotify.max_user_watches={}` and persist it in /etc/sysctl.d/",
+        (directories * 2).max(524_288)
+    );
+    if directories >= limit {
+        Check::fail(
+            NAME,
+            format!(
+                "the sync root has {} directories but only {} can be watched",
+                directories, limit
+            ),
+            hint,
+        )
+    } else if directories * 100 >= limit * INOTIFY_WARN_PERCENT {
+        Check::warn(
+            NAME,
+            format!(
+                "the sync root needs {} of {} inotify watches",
+                directories, limit
+            ),
+            hint,
+        )
+    } else {
+        Check::pass(NAME)
+    }
+}
+
+/// Bytes available to unprivileged users on the filesystem of `path`
+fn available_space(path: &Path) -> std::io::Result<u64> {
+    let path = CString::new(path.as_os_str().as_bytes())?;
+    let mut stat: libc::statvfs = unsafe { std::mem::zeroed() };
+    // SAFETY: `path` is NUL-terminated and `stat` is a valid statvfs buffer
+    if unsafe { libc::statvfs(path.as_ptr(), &mut stat) } != 0 {
+        return Err(std::io::Error::last_os_error());
+    }
+    // The field types are narrower on 32-bit targets
+    #[allow(clippy::unnecessary_cast)]
+    Ok(stat.f_bavail as u64 * stat.f_frsize as u64)
+}
+
+/// Path of the state database
+fn database_path() -> Option<PathBuf> {
+    Some(dirs::data_dir()?.join("lnxdrive").join("lnxdrive.db"))
+}
+
+/// Returns the account `reference` selects, if one is logged in
+///
+/// Never creates the database: a missing database just means nobody has
+/// logged in yet. A database that cannot be opened is reported by the
+/// database check instead.
+async fn find_account(
+    db_path: &Path,
+    config: &Config,
+    reference: Option<&str>,
+) -> Result<Option<Account>> {
+    if !db_path.exists() {
+        return Ok(None);
+    }
+
+    let Ok(pool) = DatabasePool::new(db_path).await else {
+        return Ok(None);
+    };
+    let state_repo = SqliteStateRepository::new(pool.pool().clone());
+    select_account(&state_repo, config, reference).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validation_errors_fail_with_hint() {
+        let check = Check::from_errors(
+            "config",
+            vec![ValidationError {
+                field: "sync.root".into(),
+                message: "must not be empty".into(),
+            }],
+            "Fix it",
+        );
+        assert_eq!(check.status, Status::Fail);
+        assert_eq!(check.problems.len(), 1);
+        assert_eq!(check.hint.as_deref(), Some("Fix it"));
+
+        let check = Check::from_errors("config", Vec::new(), "Fix it");
+        assert_eq!(check.status, Status::Pass);
+        assert!(check.hint.is_none());
+    }
+
+    #[test]
+    fn test_watch_headroom_warns_near_the_limit() {
+        assert_eq!(check_watch_headroom(100, Some(1000)).status, Status::Pass);
+        assert_eq!(check_watch_headroom(850, Some(1000)).status, Status::Warn);
+        assert_eq!(check_watch_headroom(1000, Some(1000)).status, Status::Fail);
+        assert_eq!(check_watch_headroom(10, None).status, Status::Warn);
+
+        let hint = check_watch_headroom(600_000, Some(524_288)).hint.unwrap();
+        assert!(hint.contains("max_user_watches=1200000"));
+    }
+
+    #[test]
+    fn test_mount_point_must_be_an_empty_directory() {
+        let dir = tempfile::tempdir().unwrap();
+        let mount_point = dir.path().join("OneDrive");
+        assert_eq!(check_mount_point(&mount_point).status, Status::Warn);
+
+        std::fs::create_dir(&mount_point).unwrap();
+        assert_eq!(check_mount_point(&mount_point).status, Status::Pass);
+
+        std::fs::write(mount_point.join("stray.txt"), b"x").unwrap();
+        assert_eq!(check_mount_point(&mount_point).status, Status::Fail);
+
+        let file = dir.path().join("file");
+        std::fs::write(&file, b"").unwrap();
+        assert_eq!(check_mount_point(&file).status, Status::Fail);
+    }
+
+    #[test]
+    fn test_unmeasured_clock_only_warns() {
+        let unreachable: Result<ClockSkew> = Err(anyhow::anyhow!("offline"));
+        assert_eq!(check_network(&unreachable).status, Status::Fail);
+        assert_eq!(check_clock(&unreachable).status, Status::Warn);
+
+        let skewed = Ok(ClockSkew::from_secs(3600));
+        assert_eq!(check_clock(&skewed).status, Status::Fail);
+        assert_eq!(
+            check_clock(&Ok(ClockSkew::from_secs(0))).status,
+            Status::Pass
+        );
+    }
+}
@@ -4,7 +4,8 @@
 //! 1. Looks up a file in the sync state database
 //! 2. Generates a human-readable explanation of its current state
 //! 3. Provides actionable suggestions based on the state/error
-//! 4. Shows recent audit history for the file
+//! 4. For a conflicted file, shows both versions against the last synced one
+//! 5. Shows recent audit history for the file
 
 use std::{
     path::{Path, PathBuf},
@@ -80,6 +81,7 @@ impl ExplainCommand {
                         "timestamp": entry.timestamp().to_rfc3339(),
                         "action": entry.action().to_string(),
                         "result": format!("{:?}", entry.result()),
+                        "code": entry.result().code(),
                         "details": entry.details(),
                         "duration_ms": entry.duration_ms(),
                     })
@@ -90,7 +92,9 @@ impl ExplainCommand {
                 "path": explanation.path.to_string(),
                 "state": explanation.state,
                 "message": explanation.message,
+                "reason_code": explanation.reason_code,
                 "suggestions": explanation.suggestions,
+                "conflict": explanation.conflict,
                 "history": history_json,
             });
             formatter.print_json(&json);
@@ -102,6 +106,13 @@ impl ExplainCommand {
         formatter.info("");
         formatter.info(&format!("State:   {}", explanation.state));
         formatter.info(&format!("Message: {}", explanation.message));
+        if let Some(code) = explanation.reason_code {
+            formatter.info(&format!("Reason:  {}", code));
+        }
+
+        if let Some(conflict) = &explanation.conflict {
+            print_conflict_evidence(formatter.as_ref(), conflict);
+        }
 
         // T197: Suggestions
         if !explanation.suggestions.is_empty() {
@@ -129,10 +140,9 @@ impl ExplainCommand {
             for entry in entries_to_show {
                 let timestamp = entry.timestamp().format("%Y-%m-%d %H:%M:%S");
                 let action = entry.action().to_string();
-                let result = if entry.result().is_success() {
-                    "OK"
-                } else {
-                    "FAILED"
+                let result = match entry.result().code() {
+                    None => "OK".to_string(),
+                    Some(code) => format!("FAILED ({})", code),
                 };
 
                 formatter.info(&format!("  {} {:<16} {}", timestamp, action, result));
@@ -152,3 +162,68 @@ impl ExplainCommand {
         Ok(())
     }
 }
+
+/// Prints the versions of a conflicted file side by side
+fn print_conflict_evidence(
+    formatter: &dyn crate::output::OutputFormatter,
+    conflict: &lnxdrive_core::usecases::explain_failure::ConflictEvidence,
+) {
+    let time = |at: chrono::DateTime<chrono::Utc>| {
+        at.with_timezone(&chrono::Local)
+            .format("%Y-%m-%d %H:%M:%S")
+            .to_string()
+    };
+    let changed = |content_changed: bool| {
+        if content_changed {
+            "changed"
+        } else {
+            "unchanged"
+        }
+    };
+
+    formatter.info("");
+    formatter.info(&format!(
+        "Conflict {} (detected {}):",
+        conflict.conflict_id,
+        time(conflict.detected_at)
+    ));
+    formatter.info(&format!("  {}", conflict.summary));
+    formatter.info("");
+    formatter.info(
+        "  Version  Size           Modified             Hash                          Change",
+    );
+    formatter.info(
+        "  -------- -------------- -------------------- ----------------------------- ---------",
+    );
+    formatter.info(&format!(
+        "  {:<8} {:>14} {:<20} {:<29} {}",
+        "Base",
+        conflict.base.size_bytes,
+        conflict
+            .base
+            .synced_at
+            .map(time)
+            .unwrap_or_else(|| "-".to_string()),
+        conflict
+            .base
+            .hash
+            .as_ref()
+            .map(|h| h.as_str())
+            .unwrap_or("-"),
+        "",
+    ));
+    for (label, version, change) in [
+        ("Local", &conflict.local, &conflict.local_change),
+        ("Cloud", &conflict.remote, &conflict.remote_change),
+    ] {
+        formatter.info(&format!(
+            "  {:<8} {:>14} {:<20} {:<29} {} ({:+} B)",
+            label,
+            version.size_bytes(),
+            time(version.modified_at()),
+            version.hash().map_or("unknown", |hash| hash.as_str()),
+            changed(change.content_changed),
+            change.size_delta,
+        ));
+    }
+}
@@ -1,12 +1,16 @@
+pub mod account;
 pub mod audit;
 pub mod auth;
+pub mod cache;
 pub mod completions;
 pub mod config;
 pub mod conflicts;
 pub mod daemon;
+pub mod doctor;
 pub mod explain;
 pub mod hydrate;
 pub mod mount;
 pub mod pin;
 pub mod status;
+pub mod status_emblem;
 pub mod sync;
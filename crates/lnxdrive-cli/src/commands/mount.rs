@@ -7,6 +7,10 @@
 //! 4. Validate mount point (create if needed, check emptiness)
 //! 5. Check FUSE availability via `/dev/fuse`
 //! 6. Mount the FUSE filesystem and optionally wait for Ctrl+C
+//!
+//! `lnxdrive mount list` shows the lnxdrive mounts found in `/proc/mounts`,
+//! including stale ones left behind by a process that died without
+//! unmounting.
 
 use std::{
     path::{Path, PathBuf},
@@ -15,10 +19,11 @@ use std::{
 };
 
 use anyhow::{Context, Result};
-use clap::Args;
+use clap::{Args, Subcommand};
 use tokio::signal;
 use tracing::info;
 
+use super::account::select_account;
 use crate::output::{get_formatter, OutputFormat};
 
 // ============================================================================
@@ -28,6 +33,9 @@ use crate::output::{get_formatter, OutputFormat};
 /// Mount the LNXDrive Files-on-Demand FUSE filesystem
 #[derive(Debug, Args)]
 pub struct MountCommand {
+    #[command(subcommand)]
+    pub action: Option<MountAction>,
+
     /// Override the default mount point path
     #[arg(long, short = 'p', value_name = "PATH")]
     pub path: Option<PathBuf>,
@@ -41,6 +49,13 @@ pub struct MountCommand {
     pub json: bool,
 }
 
+/// Subcommands of `lnxdrive mount`
+#[derive(Debug, Subcommand)]
+pub enum MountAction {
+    /// List active lnxdrive mounts
+    List,
+}
+
 impl MountCommand {
     /// Execute the mount command
     ///
@@ -50,13 +65,18 @@ impl MountCommand {
     /// 3. Validate prerequisites (account, mount point, FUSE)
     /// 4. Mount the FUSE filesystem
     /// 5. If foreground: wait for Ctrl+C signal
-    pub async fn execute(&self, format: OutputFormat) -> Result<()> {
+    pub async fn execute(&self, format: OutputFormat, account: Option<&str>) -> Result<()> {
         use lnxdrive_cache::{pool::DatabasePool, SqliteStateRepository};
-        use lnxdrive_core::{config::Config, ports::state_repository::IStateRepository};
+        use lnxdrive_core::config::Config;
         use lnxdrive_fuse::{cache::ContentCache, filesystem::LnxDriveFs};
 
         // Use command-level --json flag if set, otherwise use global format
         let use_json = self.json || matches!(format, OutputFormat::Json);
+
+        if let Some(MountAction::List) = self.action {
+            return list_mounts(use_json).await;
+        }
+
         let formatter = get_formatter(use_json);
 
         // Step 1: Load configuration
@@ -90,10 +110,7 @@ impl MountCommand {
         let state_repo = Arc::new(SqliteStateRepository::new(pool.pool().clone()));
 
         // Step 4: Validate authenticated account exists
-        let account = state_repo
-            .get_default_account()
-            .await
-            .context("Failed to query default account")?;
+        let account = select_account(&state_repo, &config, account).await?;
 
         let account = match account {
             Some(a) => a,
@@ -172,7 +189,9 @@ impl MountCommand {
         }
 
         let cache = Arc::new(
-            ContentCache::new(cache_dir.clone()).context("Failed to initialize content cache")?,
+            ContentCache::with_shard_depth(cache_dir.clone(), config.fuse.cache_shard_depth)
+                .and_then(|cache| cache.with_dedup(config.fuse.cache_dedup))
+                .context("Failed to initialize content cache")?,
         );
 
         // Step 9: Create the FUSE filesystem
@@ -243,7 +262,13 @@ impl MountCommand {
 /// Unmount the LNXDrive FUSE filesystem
 #[derive(Debug, Args)]
 pub struct UnmountCommand {
-    /// Force unmount even if the filesystem is busy
+    /// Mount point or account email to unmount (default: the configured
+    /// mount point)
+    #[arg(value_name = "MOUNT_POINT|ACCOUNT", conflicts_with = "path")]
+    pub target: Option<String>,
+
+    /// Force unmount even if the filesystem is busy, or stale after the
+    /// process serving it died
     #[arg(long, short = 'f')]
     pub force: bool,
 
@@ -271,17 +296,46 @@ impl UnmountCommand {
         // Load configuration to get default mount point
         let config_path = Config::default_path();
         let config = Config::load_or_default(&config_path);
-
-        // Determine mount point (command flag overrides config)
-        let mount_point = self
-            .path
-            .clone()
-            .unwrap_or_else(|| expand_tilde(&config.fuse.mount_point));
+        let configured = expand_tilde(&config.fuse.mount_point);
+        let mounts = read_lnxdrive_mounts();
+
+        // Determine mount point (target or --path override config)
+        let mount_point = match (&self.target, &self.path) {
+            (_, Some(path)) => path.clone(),
+            (Some(target), None) => {
+                let account = account_email().await;
+                match resolve_unmount_target(target, &mounts, account.as_deref(), &configured) {
+                    Some(mount_point) => mount_point,
+                    None => {
+                        formatter.error(&format!("No lnxdrive mount matches '{}'", target));
+                        formatter.info("Hint: Run 'lnxdrive mount list' to see active mounts");
+                        if use_json {
+                            formatter.print_json(&serde_json::json!({
+                                "success": false,
+                                "error": "mount_not_found",
+                                "target": target
+                            }));
+                        }
+                        return Ok(());
+                    }
+                }
+            }
+            (None, None) => configured,
+        };
 
         info!(mount_point = %mount_point.display(), "Unmounting filesystem");
 
+        // A stale mount cannot be stat'ed, so it seems not to exist
+        let stale = mounts.contains(&mount_point) && mount_state(&mount_point) == "stale";
+        if stale && !self.force {
+            formatter.warn(&format!(
+                "The mount at {} is stale: the process serving it is gone",
+                mount_point.display()
+            ));
+        }
+
         // Check if mount point exists
-        if !mount_point.exists() {
+        if !mounts.contains(&mount_point) && !mount_point.exists() {
             formatter.error(&format!(
                 "Mount point '{}' does not exist",
                 mount_point.display()
@@ -353,6 +407,9 @@ impl UnmountCommand {
                 formatter.info("Hint: Use --force to perform a lazy unmount");
             } else {
                 formatter.error(&format!("Failed to unmount: {}", error_msg));
+                if stale {
+                    formatter.info("Hint: Use --force to detach the stale mount");
+                }
             }
 
             if use_json {
@@ -374,7 +431,7 @@ impl UnmountCommand {
 // ============================================================================
 
 /// Expand tilde (~) in a path string to the user's home directory
-fn expand_tilde(path: &str) -> PathBuf {
+pub(crate) fn expand_tilde(path: &str) -> PathBuf {
     if let Some(stripped) = path.strip_prefix("~/") {
         if let Some(home) = dirs::home_dir() {
             return home.join(stripped);
@@ -387,6 +444,166 @@ fn expand_tilde(path: &str) -> PathBuf {
     PathBuf::from(path)
 }
 
+// ============================================================================
+// Mount listing
+// ============================================================================
+
+/// Prints the lnxdrive mounts with their account and state
+async fn list_mounts(use_json: bool) -> Result<()> {
+    use lnxdrive_core::config::Config;
+
+    let formatter = get_formatter(use_json);
+    let config = Config::load_or_default(&Config::default_path());
+    let configured = expand_tilde(&config.fuse.mount_point);
+    let account = account_email().await;
+
+    let mounts: Vec<_> = read_lnxdrive_mounts()
+        .into_iter()
+        .map(|mount_point| {
+            let state = mount_state(&mount_point);
+            (mount_point, state)
+        })
+        .collect();
+
+    if use_json {
+        let entries: Vec<_> = mounts
+            .iter()
+            .map(|(mount_point, state)| {
+                serde_json::json!({
+                    "mount_point": mount_point.display().to_string(),
+                    "account": account,
+                    "state": state,
+                    "configured": *mount_point == configured,
+                })
+            })
+            .collect();
+        formatter.print_json(&serde_json::json!({ "mounts": entries }));
+        return Ok(());
+    }
+
+    if mounts.is_empty() {
+        formatter.info("No lnxdrive filesystem is mounted");
+        return Ok(());
+    }
+
+    println!("{:<40} {:<30} STATE", "MOUNT POINT", "ACCOUNT");
+    for (mount_point, state) in &mounts {
+        println!(
+            "{:<40} {:<30} {}",
+            mount_point.display(),
+            account.as_deref().unwrap_or("-"),
+            state
+        );
+    }
+    if mounts.iter().any(|(_, state)| *state == "stale") {
+        formatter.info("Hint: Remove stale mounts with 'lnxdrive unmount --force <MOUNT_POINT>'");
+    }
+    Ok(())
+}
+
+/// Returns the mount points of lnxdrive FUSE filesystems in `/proc/mounts`
+fn read_lnxdrive_mounts() -> Vec<PathBuf> {
+    std::fs::read_to_string("/proc/mounts")
+        .map(|content| parse_lnxdrive_mounts(&content))
+        .unwrap_or_default()
+}
+
+/// Extracts the mount points of lnxdrive FUSE filesystems from the
+/// contents of `/proc/mounts`
+///
+/// lnxdrive mounts use the source name `lnxdrive` and a `fuse` type
+/// (`fuse.onedrive` for daemon mounts).
+fn parse_lnxdrive_mounts(content: &str) -> Vec<PathBuf> {
+    content
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace();
+            let (source, target, fs_type) = (fields.next()?, fields.next()?, fields.next()?);
+            (source == "lnxdrive" && fs_type.starts_with("fuse"))
+                .then(|| PathBuf::from(unescape_mount_field(target)))
+        })
+        .collect()
+}
+
+/// Decodes the octal escapes (`\040` for a space) of a `/proc/mounts`
+/// field
+fn unescape_mount_field(field: &str) -> String {
+    let bytes = field.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let is_octal = |digits: &&[u8]| digits.iter().all(|d| (b'0'..=b'7').contains(d));
+        let escape = bytes
+            .get(i + 1..i + 4)
+            .filter(|digits| bytes[i] == b'\\' && is_octal(digits));
+        match escape {
+            Some(digits) => {
+                let value = digits
+                    .iter()
+                    .fold(0u32, |acc, d| acc * 8 + u32::from(d - b'0'));
+                decoded.push(value as u8);
+                i += 4;
+            }
+            None => {
+                decoded.push(bytes[i]);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+/// Returns `"stale"` if the filesystem at `mount_point` no longer answers
+/// (the process serving it died), `"mounted"` otherwise
+fn mount_state(mount_point: &Path) -> &'static str {
+    match std::fs::metadata(mount_point) {
+        Err(e) if e.raw_os_error() == Some(libc::ENOTCONN) => "stale",
+        _ => "mounted",
+    }
+}
+
+/// Resolves the target of `lnxdrive unmount` to a mount point
+///
+/// `target` is either a mount point or the email of the account, whose
+/// mount is the configured one, or the only lnxdrive mount.
+fn resolve_unmount_target(
+    target: &str,
+    mounts: &[PathBuf],
+    account: Option<&str>,
+    configured: &Path,
+) -> Option<PathBuf> {
+    if account.is_some_and(|email| email.eq_ignore_ascii_case(target)) {
+        if mounts.iter().any(|m| m == configured) {
+            return Some(configured.to_path_buf());
+        }
+        return match mounts {
+            [only] => Some(only.clone()),
+            _ => None,
+        };
+    }
+    if target.contains('@') && !Path::new(target).exists() {
+        return None;
+    }
+    Some(expand_tilde(target))
+}
+
+/// Returns the email of the default account, if an account is logged in
+async fn account_email() -> Option<String> {
+    use lnxdrive_cache::{pool::DatabasePool, SqliteStateRepository};
+    use lnxdrive_core::config::Config;
+
+    let db_path = dirs::data_dir()?.join("lnxdrive").join("lnxdrive.db");
+    if !db_path.exists() {
+        return None;
+    }
+
+    let pool = DatabasePool::new(&db_path).await.ok()?;
+    let state_repo = SqliteStateRepository::new(pool.pool().clone());
+    let config = Config::load_or_default(&Config::default_path());
+    let account = select_account(&state_repo, &config, None).await.ok()??;
+    Some(account.email().as_str().to_string())
+}
+
 /// Check if a mount point is suitable (empty or contains only hidden files)
 async fn is_mount_point_suitable(path: &Path) -> Result<bool> {
     let mut entries = tokio::fs::read_dir(path)
@@ -407,7 +624,7 @@ async fn is_mount_point_suitable(path: &Path) -> Result<bool> {
 }
 
 /// Check if a command exists in PATH
-fn which_exists(cmd: &str) -> bool {
+pub(crate) fn which_exists(cmd: &str) -> bool {
     Command::new("which")
         .arg(cmd)
         .output()
@@ -481,6 +698,7 @@ mod tests {
     #[test]
     fn test_mount_command_default() {
         let cmd = MountCommand {
+            action: None,
             path: None,
             foreground: false,
             json: false,
@@ -492,6 +710,7 @@ mod tests {
     #[test]
     fn test_unmount_command_default() {
         let cmd = UnmountCommand {
+            target: None,
             force: false,
             path: None,
             json: false,
@@ -499,4 +718,64 @@ mod tests {
         assert!(!cmd.force);
         assert!(cmd.path.is_none());
     }
+
+    #[test]
+    fn test_parse_lnxdrive_mounts() {
+        let content = "\
+proc /proc proc rw,nosuid,nodev,noexec,relatime 0 0
+lnxdrive /home/user/OneDrive fuse.onedrive rw,nosuid,nodev,relatime 0 0
+sshfs /mnt/remote fuse.sshfs rw,nosuid,nodev,relatime 0 0
+lnxdrive /mnt/One\\040Drive fuse rw,nosuid,nodev,relatime 0 0
+";
+        assert_eq!(
+            parse_lnxdrive_mounts(content),
+            vec![
+                PathBuf::from("/home/user/OneDrive"),
+                PathBuf::from("/mnt/One Drive"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_unescape_mount_field() {
+        assert_eq!(unescape_mount_field("/plain/path"), "/plain/path");
+        assert_eq!(unescape_mount_field("/a\\040b\\011c"), "/a b\tc");
+        assert_eq!(unescape_mount_field("/trailing\\04"), "/trailing\\04");
+    }
+
+    #[test]
+    fn test_resolve_unmount_target() {
+        let configured = PathBuf::from("/home/user/OneDrive");
+        let other = PathBuf::from("/mnt/onedrive");
+        let only_other = [other.clone()];
+        let account = Some("user@example.com");
+
+        // A mount point is taken as is
+        assert_eq!(
+            resolve_unmount_target("/mnt/onedrive", &only_other, account, &configured),
+            Some(other.clone())
+        );
+        // The account resolves to its configured mount point ...
+        let both = [configured.clone(), other.clone()];
+        assert_eq!(
+            resolve_unmount_target("User@Example.com", &both, account, &configured),
+            Some(configured.clone())
+        );
+        // ... or to the only lnxdrive mount
+        assert_eq!(
+            resolve_unmount_target("user@example.com", &only_other, account, &configured),
+            Some(other)
+        );
+        // Unknown accounts match nothing
+        assert_eq!(
+            resolve_unmount_target("someone@example.com", &both, account, &configured),
+            None
+        );
+    }
+
+    #[test]
+    fn test_mount_state_of_regular_directory() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        assert_eq!(mount_state(temp_dir.path()), "mounted");
+    }
 }
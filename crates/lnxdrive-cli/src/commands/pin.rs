@@ -1,18 +1,28 @@
 //! Pin/Unpin commands - Pin files for permanent offline access
 //!
 //! Provides the `lnxdrive pin` and `lnxdrive unpin` CLI commands which:
-//! 1. Validate paths are within the mount point
-//! 2. Resolve paths to inodes
-//! 3. Call the HydrationManager pin/unpin logic
-//! 4. Report results
+//! 1. Resolve paths to absolute paths inside the mount point
+//! 2. Ask the daemon to pin or unpin them through the `PinAndWait` and
+//!    `UnpinAndWait` methods of `com.enigmora.LNXDrive.Files`, which reply
+//!    once every file is done
+//! 3. Report results
+//!
+//! The daemon pins through the mounted filesystem's hydration manager, so
+//! both commands fail while the daemon is not running or the FUSE
+//! filesystem is not mounted.
 
 use std::path::PathBuf;
 
-use anyhow::Result;
+use anyhow::{Context, Result};
 use clap::Args;
+use lnxdrive_core::ports::CachePinReport;
+use lnxdrive_ipc::service::{DBUS_NAME, DBUS_PATH};
 use tracing::info;
 
-use crate::output::{get_formatter, OutputFormat};
+use crate::output::{get_formatter, OutputFormat, OutputFormatter};
+
+/// D-Bus interface of the daemon that pins files
+const FILES_INTERFACE: &str = "com.enigmora.LNXDrive.Files";
 
 // ============================================================================
 // T076: PinCommand with clap options
@@ -36,83 +46,23 @@ pub struct PinCommand {
 impl PinCommand {
     /// Execute the pin command
     ///
-    /// For each path:
-    /// 1. Validate it's within the mount point
-    /// 2. Resolve to inode (requires mounted filesystem)
-    /// 3. Call pin logic
-    /// 4. Report success/failure
+    /// Each path is pinned by the daemon, which returns once its files are
+    /// downloaded. Fails if any path could not be pinned.
     pub async fn execute(&self, format: OutputFormat) -> Result<()> {
         // Use command-level --json flag if set, otherwise use global format
         let use_json = self.json || matches!(format, OutputFormat::Json);
         let formatter = get_formatter(use_json);
 
         formatter.info(&format!("Pinning {} path(s)...", self.paths.len()));
+        let outcome = pin_paths("PinAndWait", &self.paths, &*formatter).await;
 
-        // Note: Full implementation requires access to the mounted FUSE filesystem
-        // to resolve paths to inodes and call HydrationManager::pin().
-        //
-        // For now, we provide a stub that validates the paths exist and reports
-        // what would be pinned. The actual pin operation would need either:
-        // 1. IPC to a running FUSE daemon
-        // 2. Direct database/state manipulation if FUSE is not running
-        //
-        // This will be fully implemented when FUSE IPC is available.
-
-        let mut pinned_count = 0;
-        let mut errors = Vec::new();
-
-        for path in &self.paths {
-            // Check if path exists
-            if !path.exists() {
-                errors.push(format!("Path does not exist: {}", path.display()));
-                continue;
-            }
-
-            // Check if it's a file or directory
-            let metadata = tokio::fs::metadata(path).await?;
-            let item_type = if metadata.is_dir() {
-                "directory"
-            } else {
-                "file"
-            };
-
-            info!(path = %path.display(), item_type, "Pinning");
-            formatter.info(&format!(
-                "Pinning {} '{}'",
-                item_type,
-                path.display()
-            ));
-
-            // TODO: When FUSE IPC is available:
-            // 1. Resolve path to inode
-            // 2. Get item state from InodeTable
-            // 3. Call HydrationManager::pin() or pin_recursive()
-
-            pinned_count += 1;
-        }
-
-        // Report results
-        if pinned_count > 0 {
+        if outcome.files > 0 {
             formatter.success(&format!(
-                "Pinned {} path(s) for offline access",
-                pinned_count
+                "Pinned {} file(s) for offline access",
+                outcome.files
             ));
         }
-
-        for error in &errors {
-            formatter.error(error);
-        }
-
-        if use_json {
-            formatter.print_json(&serde_json::json!({
-                "success": errors.is_empty(),
-                "pinned_count": pinned_count,
-                "errors": errors,
-                "paths": self.paths.iter().map(|p| p.display().to_string()).collect::<Vec<_>>()
-            }));
-        }
-
-        Ok(())
+        outcome.finish("pinned", &self.paths, use_json, &*formatter)
     }
 }
 
@@ -139,76 +89,132 @@ pub struct UnpinCommand {
 impl UnpinCommand {
     /// Execute the unpin command
     ///
-    /// For each path:
-    /// 1. Validate it's within the mount point
-    /// 2. Resolve to inode (requires mounted filesystem)
-    /// 3. Call unpin logic
-    /// 4. Report success/failure
+    /// Each path is unpinned by the daemon. Fails if any path could not be
+    /// unpinned.
     pub async fn execute(&self, format: OutputFormat) -> Result<()> {
         // Use command-level --json flag if set, otherwise use global format
         let use_json = self.json || matches!(format, OutputFormat::Json);
         let formatter = get_formatter(use_json);
 
         formatter.info(&format!("Unpinning {} path(s)...", self.paths.len()));
+        let outcome = pin_paths("UnpinAndWait", &self.paths, &*formatter).await;
 
-        let mut unpinned_count = 0;
-        let mut errors = Vec::new();
-
-        for path in &self.paths {
-            // Check if path exists
-            if !path.exists() {
-                errors.push(format!("Path does not exist: {}", path.display()));
-                continue;
-            }
-
-            // Check if it's a file or directory
-            let metadata = tokio::fs::metadata(path).await?;
-            let item_type = if metadata.is_dir() {
-                "directory"
-            } else {
-                "file"
-            };
-
-            info!(path = %path.display(), item_type, "Unpinning");
-            formatter.info(&format!(
-                "Unpinning {} '{}'",
-                item_type,
-                path.display()
-            ));
-
-            // TODO: When FUSE IPC is available:
-            // 1. Resolve path to inode
-            // 2. Get item state from InodeTable
-            // 3. Call HydrationManager::unpin() or unpin_recursive()
-
-            unpinned_count += 1;
+        if outcome.files > 0 {
+            formatter.success(&format!("Unpinned {} file(s)", outcome.files));
         }
+        outcome.finish("unpinned", &self.paths, use_json, &*formatter)
+    }
+}
 
-        // Report results
-        if unpinned_count > 0 {
-            formatter.success(&format!(
-                "Unpinned {} path(s)",
-                unpinned_count
-            ));
-        }
+/// What pinning or unpinning a list of paths did
+#[derive(Debug, Default)]
+struct PinOutcome {
+    /// Files pinned (or unpinned) by the daemon
+    files: u64,
+    /// One message per path or file that failed
+    errors: Vec<String>,
+}
 
-        for error in &errors {
+impl PinOutcome {
+    /// Prints the errors and the JSON summary, failing if there were errors
+    fn finish(
+        self,
+        verb: &str,
+        paths: &[PathBuf],
+        use_json: bool,
+        formatter: &dyn OutputFormatter,
+    ) -> Result<()> {
+        for error in &self.errors {
             formatter.error(error);
         }
 
         if use_json {
             formatter.print_json(&serde_json::json!({
-                "success": errors.is_empty(),
-                "unpinned_count": unpinned_count,
-                "errors": errors,
-                "paths": self.paths.iter().map(|p| p.display().to_string()).collect::<Vec<_>>()
+                "success": self.errors.is_empty(),
+                format!("{}_count", verb): self.files,
+                "errors": self.errors,
+                "paths": paths.iter().map(|p| p.display().to_string()).collect::<Vec<_>>()
             }));
         }
 
+        if !self.errors.is_empty() {
+            anyhow::bail!(
+                "{} error(s), not everything was {}",
+                self.errors.len(),
+                verb
+            );
+        }
         Ok(())
     }
 }
 
+/// Calls `method` of the daemon's Files interface for each path
+async fn pin_paths(method: &str, paths: &[PathBuf], formatter: &dyn OutputFormatter) -> PinOutcome {
+    let mut outcome = PinOutcome::default();
+
+    let connection = match zbus::Connection::session().await {
+        Ok(connection) => connection,
+        Err(e) => {
+            outcome
+                .errors
+                .push(format!("Failed to connect to the session bus: {}", e));
+            return outcome;
+        }
+    };
+
+    for path in paths {
+        // The daemon resolves absolute paths below the mount point
+        let path = match std::fs::canonicalize(path) {
+            Ok(path) => path,
+            Err(e) => {
+                outcome
+                    .errors
+                    .push(format!("Path does not exist: {} ({})", path.display(), e));
+                continue;
+            }
+        };
+
+        info!(path = %path.display(), method, "Calling the daemon");
+        formatter.info(&format!("{} '{}'", method, path.display()));
+        match call_daemon(&connection, method, &path).await {
+            Ok(report) => {
+                outcome.files += report.files;
+                if report.failed > 0 {
+                    outcome.errors.push(format!(
+                        "{} file(s) in '{}' failed, see the daemon log",
+                        report.failed,
+                        path.display()
+                    ));
+                }
+            }
+            Err(e) => outcome.errors.push(format!("{}: {:#}", path.display(), e)),
+        }
+    }
+    outcome
+}
+
+/// Calls `method` of the daemon's Files interface for `path`
+async fn call_daemon(
+    connection: &zbus::Connection,
+    method: &str,
+    path: &std::path::Path,
+) -> Result<CachePinReport> {
+    let reply = connection
+        .call_method(
+            Some(DBUS_NAME),
+            DBUS_PATH,
+            Some(FILES_INTERFACE),
+            method,
+            &(path.to_string_lossy().as_ref(),),
+        )
+        .await
+        .context(
+            "Request to the daemon failed. Is the daemon running with the filesystem mounted?",
+        )?;
+    let json = reply.body().deserialize::<String>()?;
+    serde_json::from_str(&json).context("Invalid reply from the daemon")
+}
+
 // ============================================================================
 // Tests
 // ============================================================================
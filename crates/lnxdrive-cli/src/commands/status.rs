@@ -1,11 +1,16 @@
 //! Status command - Display synchronization status
 //!
 //! Provides the `lnxdrive status` CLI command which:
-//! 1. Shows global sync status (item counts by state, last sync time)
+//! 1. Shows global sync status (item counts by state, last sync time,
+//!    storage quota)
 //! 2. Shows per-file status when a path is given
 //! 3. Lists pending (Modified/Hydrating) items
 //! 4. Lists items in Error state with error details
 //! 5. Shows FUSE filesystem status (mount state, cache usage, file counts)
+//! 6. Shows recent sync cycles with `--history`
+//! 7. Shows transfer and cache statistics of the running daemon with
+//!    `--stats`
+//! 8. Follows the running daemon's transfers with their ETA with `--watch`
 
 use std::{
     fs,
@@ -16,8 +21,11 @@ use std::{
 use anyhow::{Context, Result};
 use clap::Args;
 use lnxdrive_core::config::Config;
+use lnxdrive_ipc::service::{DBUS_NAME, DBUS_PATH};
+use lnxdrive_telemetry::StatsSnapshot;
 use tracing::info;
 
+use super::account::{ensure_daemon_serves, select_account};
 use crate::output::{get_formatter, OutputFormat};
 
 /// T189: Status command with optional path argument
@@ -25,16 +33,41 @@ use crate::output::{get_formatter, OutputFormat};
 pub struct StatusCommand {
     /// Optional path to check status of a specific file
     pub path: Option<String>,
+
+    /// Show the most recent sync cycles (default: 20)
+    #[arg(
+        long,
+        value_name = "N",
+        num_args = 0..=1,
+        default_missing_value = "20",
+        conflicts_with = "path"
+    )]
+    pub history: Option<u32>,
+
+    /// Show transfer, throttling and cache statistics of the running daemon
+    #[arg(long, conflicts_with_all = ["path", "history"])]
+    pub stats: bool,
+
+    /// Follow the uploads and downloads of the running daemon, with their
+    /// progress and time left, until interrupted
+    #[arg(long, conflicts_with_all = ["path", "history", "stats"])]
+    pub watch: bool,
 }
 
 impl StatusCommand {
     /// T190-T193: Execute the status command
-    pub async fn execute(&self, format: OutputFormat) -> Result<()> {
+    pub async fn execute(&self, format: OutputFormat, account: Option<&str>) -> Result<()> {
         use lnxdrive_cache::{pool::DatabasePool, SqliteStateRepository};
-        use lnxdrive_core::ports::state_repository::IStateRepository;
 
         let formatter = get_formatter(matches!(format, OutputFormat::Json));
 
+        if self.stats {
+            return self.show_stats(&format, &*formatter).await;
+        }
+        if self.watch {
+            return self.watch_transfers(&format, &*formatter).await;
+        }
+
         // Open database
         let db_path = dirs::data_dir()
             .unwrap_or_else(|| PathBuf::from("."))
@@ -52,11 +85,14 @@ impl StatusCommand {
             .context("Failed to open database")?;
         let state_repo = Arc::new(SqliteStateRepository::new(pool.pool().clone()));
 
-        // Get default account
-        let account = state_repo
-            .get_default_account()
-            .await
-            .context("Failed to query default account")?;
+        if let Some(limit) = self.history {
+            return self
+                .show_history(&*state_repo, limit, &format, &*formatter)
+                .await;
+        }
+
+        let config = Config::load_or_default(&Config::default_path());
+        let account = select_account(&state_repo, &config, account).await?;
 
         let account = match account {
             Some(a) => a,
@@ -86,7 +122,10 @@ impl StatusCommand {
         format: &OutputFormat,
         formatter: &dyn crate::output::OutputFormatter,
     ) -> Result<()> {
-        use lnxdrive_core::{domain::sync_item::ItemState, ports::state_repository::ItemFilter};
+        use lnxdrive_core::{
+            domain::{sync_item::ItemState, ClockSkew, DriveQuota},
+            ports::state_repository::ItemFilter,
+        };
 
         info!(email = %account.email(), "Showing status for account");
 
@@ -101,18 +140,39 @@ impl StatusCommand {
         // T094: Get FUSE status
         let fuse_status = get_fuse_status(&counts);
 
+        // Measured by the daemon at startup, unknown if it is not running
+        // or syncs another account
+        let (clock_skew, sync_age) = if ensure_daemon_serves(account).await.is_ok() {
+            (
+                fetch_clock_skew().await.ok().flatten(),
+                fetch_sync_age().await.ok(),
+            )
+        } else {
+            (None, None)
+        };
+
         if matches!(format, OutputFormat::Json) {
             let last_sync_str = account
                 .last_sync()
                 .map(|t| t.to_rfc3339())
                 .unwrap_or_else(|| "never".to_string());
 
+            let quota = DriveQuota::new(account.quota_used(), account.quota_total());
             let json = serde_json::json!({
                 "account": account.email().as_str(),
                 "last_sync": last_sync_str,
+                "quota": {
+                    "used": quota.used,
+                    "total": quota.total,
+                    "unlimited": quota.is_unlimited(),
+                    "percent": quota.percent_used(),
+                },
                 "total_items": total,
                 "items_by_state": counts,
                 "fuse": fuse_status.to_json(),
+                "clock_skew_secs": clock_skew.map(|skew| skew.seconds),
+                "seconds_since_last_sync": sync_age.as_ref().and_then(|age| age.seconds),
+                "sync_stale": sync_age.as_ref().is_some_and(SyncAge::is_stale),
             });
             formatter.print_json(&json);
             return Ok(());
@@ -135,7 +195,32 @@ impl StatusCommand {
             }
         }
 
+        formatter.info(&format!(
+            "Storage: {}",
+            format_quota(&DriveQuota::new(
+                account.quota_used(),
+                account.quota_total()
+            ))
+        ));
         formatter.info(&format!("Total items: {}", total));
+        if let Some(age) = sync_age.as_ref().filter(|age| age.is_stale()) {
+            formatter.error(&match age.seconds {
+                Some(secs) => format!(
+                    "Sync may be stalled: no successful sync in {} while online",
+                    format_uptime(secs)
+                ),
+                None => format!(
+                    "Sync may be stalled: no successful sync since the daemon started {} ago",
+                    format_uptime(age.uptime_secs)
+                ),
+            });
+        }
+        if let Some(skew) = clock_skew.filter(ClockSkew::is_significant) {
+            formatter.error(&format!(
+                "Clock: local time is {}, check time synchronization",
+                skew
+            ));
+        }
         formatter.info("");
 
         // State counts table
@@ -214,6 +299,188 @@ impl StatusCommand {
         Ok(())
     }
 
+    /// Display the most recent sync cycles, newest first
+    async fn show_history(
+        &self,
+        state_repo: &dyn lnxdrive_core::ports::IStateRepository,
+        limit: u32,
+        format: &OutputFormat,
+        formatter: &dyn crate::output::OutputFormatter,
+    ) -> Result<()> {
+        let history = state_repo
+            .get_sync_history(limit)
+            .await
+            .context("Failed to query sync history")?;
+
+        if matches!(format, OutputFormat::Json) {
+            formatter.print_json(&serde_json::json!({ "history": history }));
+            return Ok(());
+        }
+
+        if history.is_empty() {
+            formatter.info("No sync history recorded yet.");
+            return Ok(());
+        }
+
+        formatter.success(&format!("Last {} sync cycle(s)", history.len()));
+        formatter.info("");
+        formatter.info(
+            "Finished             Result  Down   Up     Del    Transferred  Duration  Errors",
+        );
+        formatter.info(
+            "-------------------- ------- ------ ------ ------ ------------ --------- ------",
+        );
+        for entry in &history {
+            let result = if !entry.success {
+                "failed"
+            } else if entry.has_errors() {
+                "partial"
+            } else {
+                "ok"
+            };
+            formatter.info(&format!(
+                "{:<20} {:<7} {:<6} {:<6} {:<6} {:<12} {:<9} {}",
+                entry.finished_at.format("%Y-%m-%d %H:%M:%S"),
+                result,
+                entry.files_downloaded,
+                entry.files_uploaded,
+                entry.files_deleted,
+                format_bytes(entry.total_bytes()),
+                format_duration_ms(entry.duration_ms),
+                entry.errors.len()
+            ));
+        }
+
+        // Show the first error of failed cycles to help spot intermittent failures
+        let failures: Vec<_> = history.iter().filter(|e| !e.success).collect();
+        if !failures.is_empty() {
+            formatter.info("");
+            formatter.warn(&format!("{} failed cycle(s):", failures.len()));
+            for entry in failures {
+                let reason = entry
+                    .errors
+                    .first()
+                    .map(String::as_str)
+                    .unwrap_or("unknown");
+                formatter.info(&format!(
+                    "  {} - {}",
+                    entry.finished_at.format("%Y-%m-%d %H:%M:%S"),
+                    reason.chars().take(80).collect::<String>()
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Display the session statistics reported by the daemon
+    async fn show_stats(
+        &self,
+        format: &OutputFormat,
+        formatter: &dyn crate::output::OutputFormatter,
+    ) -> Result<()> {
+        let stats = match fetch_stats().await {
+            Ok(stats) => stats,
+            Err(e) => {
+                formatter.error(&format!("{:#}", e));
+                return Ok(());
+            }
+        };
+
+        if matches!(format, OutputFormat::Json) {
+            formatter.print_json(&serde_json::json!({
+                "stats": stats,
+                "cache_hit_ratio": stats.cache_hit_ratio(),
+            }));
+            return Ok(());
+        }
+
+        formatter.success(&format!(
+            "Daemon statistics (up {})",
+            format_uptime(stats.uptime_secs)
+        ));
+        formatter.info(&format!(
+            "Uploaded:          {} ({} files)",
+            format_bytes(stats.bytes_uploaded),
+            stats.files_uploaded
+        ));
+        formatter.info(&format!(
+            "Downloaded:        {} ({} files)",
+            format_bytes(stats.bytes_downloaded),
+            stats.files_downloaded
+        ));
+        formatter.info(&format!(
+            "Throughput:        up {}/s, down {}/s",
+            format_bytes(stats.upload_rate),
+            format_bytes(stats.download_rate)
+        ));
+        formatter.info(&format!("Throttled (429):   {}", stats.throttled_requests));
+        formatter.info(&format!("Cache hit ratio:   {}", format_hit_ratio(&stats)));
+        formatter.info(&format!("Active transfers:  {}", stats.active_transfers));
+        formatter.info(&format!("Queued sync paths: {}", stats.queued_sync_paths));
+        Ok(())
+    }
+
+    /// Prints the daemon's `Sync.FileProgress` and `Sync.FileComplete`
+    /// signals until Ctrl+C
+    async fn watch_transfers(
+        &self,
+        format: &OutputFormat,
+        formatter: &dyn crate::output::OutputFormatter,
+    ) -> Result<()> {
+        use zbus::export::futures_util::StreamExt;
+
+        let proxy = match sync_proxy().await {
+            Ok(proxy) => proxy,
+            Err(e) => {
+                formatter.error(&format!("{:#}", e));
+                return Ok(());
+            }
+        };
+        let mut progress = proxy.receive_signal("FileProgress").await?;
+        let mut complete = proxy.receive_signal("FileComplete").await?;
+        let json = matches!(format, OutputFormat::Json);
+        if !json {
+            formatter.info("Watching transfers, press Ctrl+C to stop");
+        }
+
+        loop {
+            tokio::select! {
+                Some(message) = progress.next() => {
+                    let args: (String, String, u64, u64, i64) = message.body().deserialize()?;
+                    let progress = TransferProgress {
+                        path: args.0,
+                        kind: args.1,
+                        bytes_done: args.2,
+                        bytes_total: args.3,
+                        eta_secs: u64::try_from(args.4).ok(),
+                    };
+                    if json {
+                        formatter.print_json(&serde_json::json!({ "progress": progress }));
+                    } else {
+                        formatter.info(&format_transfer_progress(&progress));
+                    }
+                }
+                Some(message) = complete.next() => {
+                    let (path, kind, result): (String, String, String) =
+                        message.body().deserialize()?;
+                    if json {
+                        formatter.print_json(&serde_json::json!({
+                            "complete": { "path": path, "kind": kind, "result": result },
+                        }));
+                    } else if result == "success" {
+                        formatter.success(&format!("{} {}: done", kind, path));
+                    } else {
+                        formatter.error(&format!("{} {}: {}", kind, path, result));
+                    }
+                }
+                _ = tokio::signal::ctrl_c() => break,
+                else => break,
+            }
+        }
+        Ok(())
+    }
+
     /// T191: Display status for a specific file
     async fn show_file_status(
         &self,
@@ -507,6 +774,187 @@ fn expand_tilde(path: &str) -> String {
     path.to_string()
 }
 
+/// Format a millisecond duration (e.g., "850ms", "12.3s").
+fn format_duration_ms(ms: u64) -> String {
+    if ms >= 1000 {
+        format!("{:.1}s", ms as f64 / 1000.0)
+    } else {
+        format!("{}ms", ms)
+    }
+}
+
+/// Format a storage quota (e.g., "1.2 GB of 5.0 GB used (24%)").
+fn format_quota(quota: &lnxdrive_core::domain::DriveQuota) -> String {
+    match quota.percent_used() {
+        Some(percent) => format!(
+            "{} of {} used ({:.0}%)",
+            format_bytes(quota.used),
+            format_bytes(quota.total),
+            percent
+        ),
+        None => format!("{} used (unlimited)", format_bytes(quota.used)),
+    }
+}
+
+/// Asks the daemon for its session statistics (`Sync.GetStats`)
+async fn fetch_stats() -> Result<StatsSnapshot> {
+    let connection = zbus::Connection::session()
+        .await
+        .context("Failed to connect to the session bus")?;
+    let reply = connection
+        .call_method(
+            Some(DBUS_NAME),
+            DBUS_PATH,
+            Some("com.enigmora.LNXDrive.Sync"),
+            "GetStats",
+            &(),
+        )
+        .await
+        .context("Failed to get statistics from the daemon. Is the daemon running?")?;
+    let json = reply.body().deserialize::<String>()?;
+    serde_json::from_str(&json).context("Invalid reply from the daemon")
+}
+
+/// Proxy for the daemon's `Sync` interface
+async fn sync_proxy() -> Result<zbus::Proxy<'static>> {
+    let connection = zbus::Connection::session()
+        .await
+        .context("Failed to connect to the session bus")?;
+    zbus::Proxy::new(
+        &connection,
+        DBUS_NAME,
+        DBUS_PATH,
+        "com.enigmora.LNXDrive.Sync",
+    )
+    .await
+    .context("Failed to reach the daemon. Is the daemon running?")
+}
+
+/// A `Sync.FileProgress` signal of the running daemon
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+struct TransferProgress {
+    path: String,
+    /// "upload" or "download"
+    kind: String,
+    bytes_done: u64,
+    bytes_total: u64,
+    /// Estimated seconds left, `None` until the daemon knows the throughput
+    eta_secs: Option<u64>,
+}
+
+/// Format a transfer update (e.g., "download /Videos/a.mkv: 40% of
+/// 1.0 GB, 1m 05s left")
+fn format_transfer_progress(progress: &TransferProgress) -> String {
+    let percent = match progress.bytes_total {
+        0 => 100,
+        total => progress.bytes_done.min(total) * 100 / total,
+    };
+    let eta = match progress.eta_secs {
+        Some(secs) => format!("{} left", format_uptime(secs)),
+        None => "estimating time left".to_string(),
+    };
+    format!(
+        "{} {}: {}% of {}, {}",
+        progress.kind,
+        progress.path,
+        percent,
+        format_bytes(progress.bytes_total),
+        eta
+    )
+}
+
+/// Asks the daemon for the clock skew it measured (`Status.ClockSkew`)
+///
+/// Returns `None` while the daemon has not measured it.
+async fn fetch_clock_skew() -> Result<Option<lnxdrive_core::domain::ClockSkew>> {
+    let connection = zbus::Connection::session().await?;
+    let proxy = zbus::Proxy::new(
+        &connection,
+        DBUS_NAME,
+        DBUS_PATH,
+        "com.enigmora.LNXDrive.Status",
+    )
+    .await?;
+    let seconds: i64 = proxy.get_property("ClockSkew").await?;
+    Ok((seconds != 0).then_some(lnxdrive_core::domain::ClockSkew::from_secs(seconds)))
+}
+
+/// Age of the last successful sync after which `status` warns, if the
+/// daemon is online
+const STALE_SYNC_SECS: u64 = 3600;
+
+/// Last successful sync as reported by the running daemon
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct SyncAge {
+    /// Seconds since the last successful sync, `None` if none yet
+    seconds: Option<u64>,
+    /// Seconds since the daemon started
+    uptime_secs: u64,
+    /// Whether the daemon can reach the cloud
+    online: bool,
+}
+
+impl SyncAge {
+    /// Returns true if no sync succeeded for [`STALE_SYNC_SECS`] although
+    /// the daemon is online
+    fn is_stale(&self) -> bool {
+        self.online && self.seconds.unwrap_or(self.uptime_secs) > STALE_SYNC_SECS
+    }
+}
+
+/// Asks the daemon how long ago it last synced successfully
+/// (`Sync.SecondsSinceLastSync`, `Sync.UptimeSecs`,
+/// `Status.ConnectionStatus`)
+async fn fetch_sync_age() -> Result<SyncAge> {
+    let connection = zbus::Connection::session().await?;
+    let sync = zbus::Proxy::new(
+        &connection,
+        DBUS_NAME,
+        DBUS_PATH,
+        "com.enigmora.LNXDrive.Sync",
+    )
+    .await?;
+    let status = zbus::Proxy::new(
+        &connection,
+        DBUS_NAME,
+        DBUS_PATH,
+        "com.enigmora.LNXDrive.Status",
+    )
+    .await?;
+    let seconds: i64 = sync.get_property("SecondsSinceLastSync").await?;
+    let connection_status: String = status.get_property("ConnectionStatus").await?;
+    Ok(SyncAge {
+        seconds: u64::try_from(seconds).ok(),
+        uptime_secs: sync.get_property("UptimeSecs").await?,
+        online: connection_status == "online",
+    })
+}
+
+/// Format a daemon uptime (e.g., "2h 05m")
+pub(crate) fn format_uptime(secs: u64) -> String {
+    let (days, hours, minutes) = (secs / 86_400, secs / 3600 % 24, secs / 60 % 60);
+    if days > 0 {
+        format!("{}d {}h", days, hours)
+    } else if hours > 0 {
+        format!("{}h {:02}m", hours, minutes)
+    } else {
+        format!("{}m {:02}s", minutes, secs % 60)
+    }
+}
+
+/// Format the cache hit ratio with the underlying counts
+fn format_hit_ratio(stats: &StatsSnapshot) -> String {
+    match stats.cache_hit_ratio() {
+        Some(ratio) => format!(
+            "{:.0}% ({} hits, {} misses)",
+            ratio * 100.0,
+            stats.cache_hits,
+            stats.cache_misses
+        ),
+        None => "n/a (no files opened)".to_string(),
+    }
+}
+
 /// Format bytes as a human-readable string (e.g., "2.1 GB").
 fn format_bytes(bytes: u64) -> String {
     const KB: u64 = 1024;
@@ -523,3 +971,113 @@ fn format_bytes(bytes: u64) -> String {
         format!("{} bytes", bytes)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use lnxdrive_core::domain::DriveQuota;
+
+    use super::*;
+
+    #[test]
+    fn test_format_quota() {
+        const GB: u64 = 1024 * 1024 * 1024;
+        assert_eq!(
+            format_quota(&DriveQuota::new(GB, 4 * GB)),
+            "1.0 GB of 4.0 GB used (25%)"
+        );
+        assert_eq!(
+            format_quota(&DriveQuota::unlimited(3 * GB)),
+            "3.0 GB used (unlimited)"
+        );
+    }
+
+    #[test]
+    fn test_format_uptime() {
+        assert_eq!(format_uptime(42), "0m 42s");
+        assert_eq!(format_uptime(2 * 3600 + 5 * 60 + 7), "2h 05m");
+        assert_eq!(format_uptime(3 * 86_400 + 4 * 3600), "3d 4h");
+    }
+
+    #[test]
+    fn test_sync_age_is_stale() {
+        let age = SyncAge {
+            seconds: Some(STALE_SYNC_SECS + 1),
+            uptime_secs: 86_400,
+            online: true,
+        };
+        assert!(age.is_stale());
+        assert!(!SyncAge {
+            online: false,
+            ..age.clone()
+        }
+        .is_stale());
+        assert!(!SyncAge {
+            seconds: Some(60),
+            ..age.clone()
+        }
+        .is_stale());
+
+        // Never synced: stale once the daemon has been up long enough
+        assert!(SyncAge {
+            seconds: None,
+            ..age.clone()
+        }
+        .is_stale());
+        assert!(!SyncAge {
+            seconds: None,
+            uptime_secs: 120,
+            online: true,
+        }
+        .is_stale());
+    }
+
+    #[test]
+    fn test_format_hit_ratio() {
+        let stats = StatsSnapshot {
+            cache_hits: 3,
+            cache_misses: 1,
+            ..Default::default()
+        };
+        assert_eq!(format_hit_ratio(&stats), "75% (3 hits, 1 misses)");
+        assert_eq!(
+            format_hit_ratio(&StatsSnapshot::default()),
+            "n/a (no files opened)"
+        );
+    }
+
+    #[test]
+    fn test_parse_stats_flag() {
+        use clap::Parser;
+
+        #[derive(Debug, Parser)]
+        struct TestCli {
+            #[command(flatten)]
+            status: StatusCommand,
+        }
+
+        assert!(TestCli::parse_from(["test", "--stats"]).status.stats);
+        assert!(TestCli::try_parse_from(["test", "--stats", "--history"]).is_err());
+        assert!(TestCli::parse_from(["test", "--watch"]).status.watch);
+        assert!(TestCli::try_parse_from(["test", "--watch", "--stats"]).is_err());
+    }
+
+    #[test]
+    fn test_format_transfer_progress() {
+        let mut progress = TransferProgress {
+            path: "/Videos/a.mkv".to_string(),
+            kind: "download".to_string(),
+            bytes_done: 400 * 1024 * 1024,
+            bytes_total: 1024 * 1024 * 1024,
+            eta_secs: None,
+        };
+        assert_eq!(
+            format_transfer_progress(&progress),
+            "download /Videos/a.mkv: 39% of 1.0 GB, estimating time left"
+        );
+        progress.eta_secs = Some(65);
+        assert_eq!(
+            format_transfer_progress(&progress),
+            "download /Videos/a.mkv: 39% of 1.0 GB, 1m 05s left"
+        );
+    }
+}
@@ -0,0 +1,202 @@
+//! Status-emblem command - Single-token sync state for file managers
+//!
+//! Provides the `lnxdrive status-emblem <path>` CLI command which prints
+//! exactly one token describing the sync state of a path:
+//!
+//! `synced`, `cloud-only`, `syncing`, `conflict`, `error` or `unknown`
+//!
+//! The command is meant to be polled by file-manager extensions (Nautilus,
+//! Nemo, Caja). It asks the daemon (`Files.GetEmblem`) first and, when the
+//! daemon does not answer, performs a single indexed lookup in the local
+//! state database, opened read-only and without running migrations. It
+//! never contacts the network, so it answers well within the latency
+//! budget of an emblem provider. Every failure (no database, invalid path,
+//! untracked file) is reported as `unknown` with exit status 0 so callers
+//! only ever need to parse stdout.
+//!
+//! See `docs/EMBLEM-INTEGRATION.md` for the full integration contract.
+
+use std::{
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use anyhow::Result;
+use clap::Args;
+use lnxdrive_core::domain::SyncItem;
+use lnxdrive_ipc::service::{DBUS_NAME, DBUS_PATH};
+
+use crate::output::OutputFormat;
+
+/// Token printed for paths whose state cannot be determined
+const UNKNOWN: &str = "unknown";
+
+/// Every token the command prints
+const EMBLEMS: &[&str] = &[
+    "synced",
+    "cloud-only",
+    "syncing",
+    "conflict",
+    "error",
+    UNKNOWN,
+];
+
+/// D-Bus interface of the daemon that answers emblem queries
+const FILES_INTERFACE: &str = "com.enigmora.LNXDrive.Files";
+
+/// How long to wait for the daemon before reading the database instead
+const DAEMON_TIMEOUT: Duration = Duration::from_millis(200);
+
+/// Print the emblem token for a path
+#[derive(Debug, Args)]
+pub struct StatusEmblemCommand {
+    /// Path to the file or directory
+    pub path: String,
+}
+
+impl StatusEmblemCommand {
+    /// Execute the status-emblem command
+    pub async fn execute(&self, format: OutputFormat) -> Result<()> {
+        let abs_path = resolve_path(&self.path);
+        let emblem = lookup_emblem(&abs_path).await;
+
+        if matches!(format, OutputFormat::Json) {
+            let json = serde_json::json!({
+                "path": abs_path.display().to_string(),
+                "emblem": emblem,
+            });
+            println!("{}", json);
+        } else {
+            println!("{}", emblem);
+        }
+
+        Ok(())
+    }
+}
+
+/// Resolves a possibly relative path against the current directory
+fn resolve_path(path: &str) -> PathBuf {
+    let path = PathBuf::from(path);
+    if path.is_absolute() {
+        return path;
+    }
+    std::env::current_dir()
+        .map(|cwd| cwd.join(&path))
+        .unwrap_or(path)
+}
+
+/// Looks up the emblem for a path, from the daemon if it is running and
+/// from the local state database otherwise
+async fn lookup_emblem(path: &Path) -> &'static str {
+    match tokio::time::timeout(DAEMON_TIMEOUT, daemon_emblem(path)).await {
+        Ok(Some(emblem)) => emblem,
+        _ => database_emblem(path).await,
+    }
+}
+
+/// Asks the daemon for the emblem of a path through `Files.GetEmblem`
+async fn daemon_emblem(path: &Path) -> Option<&'static str> {
+    let connection = zbus::Connection::session().await.ok()?;
+    let reply = connection
+        .call_method(
+            Some(DBUS_NAME),
+            DBUS_PATH,
+            Some(FILES_INTERFACE),
+            "GetEmblem",
+            &(path.to_str()?,),
+        )
+        .await
+        .ok()?;
+    let token: String = reply.body().deserialize().ok()?;
+    known_emblem(&token)
+}
+
+/// Returns the static token equal to `token`, if it is one
+fn known_emblem(token: &str) -> Option<&'static str> {
+    EMBLEMS.iter().copied().find(|emblem| *emblem == token)
+}
+
+/// Looks up the emblem for a path in the local state database
+///
+/// The database is opened read-only, so a poll never creates or migrates it.
+async fn database_emblem(path: &Path) -> &'static str {
+    use lnxdrive_cache::{pool::DatabasePool, SqliteStateRepository};
+    use lnxdrive_core::{domain::newtypes::SyncPath, ports::state_repository::IStateRepository};
+
+    let db_path = dirs::data_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join("lnxdrive")
+        .join("lnxdrive.db");
+
+    if !db_path.exists() {
+        return UNKNOWN;
+    }
+
+    let Ok(sync_path) = SyncPath::new(path.to_path_buf()) else {
+        return UNKNOWN;
+    };
+
+    let Ok(pool) = DatabasePool::open_read_only(&db_path).await else {
+        return UNKNOWN;
+    };
+    let repo = SqliteStateRepository::new(pool.pool().clone());
+
+    match repo.get_item_by_path(&sync_path).await {
+        Ok(item) => emblem_for(item.as_ref()),
+        Err(_) => UNKNOWN,
+    }
+}
+
+/// Maps an optional sync item to its emblem token
+fn emblem_for(item: Option<&SyncItem>) -> &'static str {
+    item.map(|i| i.state().emblem()).unwrap_or(UNKNOWN)
+}
+
+#[cfg(test)]
+mod tests {
+    use lnxdrive_core::domain::newtypes::{RemotePath, SyncPath};
+
+    use super::*;
+
+    fn item() -> SyncItem {
+        SyncItem::new_file(
+            SyncPath::new(PathBuf::from("/home/user/OneDrive/a.txt")).unwrap(),
+            RemotePath::new("/a.txt".to_string()).unwrap(),
+            10,
+            None,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_emblem_for_untracked() {
+        assert_eq!(emblem_for(None), "unknown");
+    }
+
+    #[test]
+    fn test_emblem_for_item_states() {
+        let mut item = item();
+        assert_eq!(emblem_for(Some(&item)), "cloud-only");
+
+        item.start_hydrating().unwrap();
+        assert_eq!(emblem_for(Some(&item)), "syncing");
+
+        item.complete_hydration().unwrap();
+        assert_eq!(emblem_for(Some(&item)), "synced");
+    }
+
+    #[test]
+    fn test_known_emblem() {
+        assert_eq!(known_emblem("synced"), Some("synced"));
+        assert_eq!(known_emblem("cloud-only"), Some("cloud-only"));
+        assert_eq!(known_emblem("bogus"), None);
+    }
+
+    #[test]
+    fn test_resolve_path() {
+        assert_eq!(resolve_path("/abs/file"), PathBuf::from("/abs/file"));
+        let rel = resolve_path("rel/file");
+        assert!(rel.is_absolute());
+        assert!(rel.ends_with("rel/file"));
+    }
+}
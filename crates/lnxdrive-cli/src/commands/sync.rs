@@ -10,8 +10,9 @@ use std::{path::Path, sync::Arc};
 
 use anyhow::{Context, Result};
 use clap::Args;
-use tracing::info;
+use tracing::{info, warn};
 
+use super::account::select_account;
 use crate::output::{get_formatter, OutputFormat};
 
 /// T162: Sync command with clap options
@@ -31,7 +32,7 @@ impl SyncCommand {
     ///
     /// Wires up all adapters, creates the SyncEngine, runs sync(),
     /// and displays progress and results.
-    pub async fn execute(&self, format: OutputFormat) -> Result<()> {
+    pub async fn execute(&self, format: OutputFormat, account: Option<&str>) -> Result<()> {
         use lnxdrive_cache::{pool::DatabasePool, SqliteStateRepository};
         use lnxdrive_core::config::Config;
         use lnxdrive_graph::{
@@ -66,10 +67,7 @@ impl SyncCommand {
         // Step 3: Get stored account to retrieve tokens
         use lnxdrive_core::ports::state_repository::IStateRepository;
 
-        let account = state_repo
-            .get_default_account()
-            .await
-            .context("Failed to query default account")?;
+        let account = select_account(&state_repo, &config, account).await?;
 
         let account = match account {
             Some(a) => a,
@@ -85,7 +83,8 @@ impl SyncCommand {
         );
 
         // Step 4: Load tokens from keyring
-        let tokens = match KeyringTokenStorage::load(account.email().as_str()) {
+        let tokens = match KeyringTokenStorage::load(&KeyringTokenStorage::account_id_of(&account))
+        {
             Ok(Some(t)) => t,
             Ok(None) => {
                 formatter.error("No tokens found. Run 'lnxdrive auth login' first.");
@@ -98,14 +97,17 @@ impl SyncCommand {
         };
 
         // Step 5: Create adapters
-        let graph_client = GraphClient::new(&tokens.access_token);
+        let graph_client = GraphClient::new(&tokens.access_token)
+            .with_http_config(&config.http)
+            .with_delta_page_size(config.delta.batch_size);
         let cloud_provider = Arc::new(GraphCloudProvider::new(graph_client));
-        let local_fs = Arc::new(LocalFileSystemAdapter::new());
+        let local_fs =
+            Arc::new(LocalFileSystemAdapter::new().with_temp_dir(config.fuse.temp_dir_path()));
 
         // Step 6: Handle --full flag (clear delta token)
         if self.full {
             formatter.info("Full sync requested - ignoring delta token");
-            // Note: The SyncEngine queries get_default_account() itself
+            // Note: The SyncEngine queries the account itself
             // and uses the account's delta_token. To force a full sync,
             // we would need to clear it on the account. For now we log it.
             info!("Full sync mode: delta token will be ignored");
@@ -121,12 +123,26 @@ impl SyncCommand {
         // Step 8: Create and run sync engine
         formatter.info("Starting synchronization...");
 
-        let engine = SyncEngine::new(cloud_provider, state_repo, local_fs, &config);
+        let history_repo = Arc::clone(&state_repo);
+        let mut engine = SyncEngine::new(cloud_provider, state_repo, local_fs, &config);
+        engine.set_account(*account.id());
 
         // T164: Display progress during sync
         formatter.info("Querying remote changes...");
 
-        let result = engine.sync().await?;
+        let started_at = chrono::Utc::now();
+        let outcome = engine.sync().await;
+
+        // Record the cycle in the sync history, whether or not it succeeded
+        let entry = match &outcome {
+            Ok(result) => result.to_history_entry(started_at),
+            Err(e) => lnxdrive_core::domain::SyncHistoryEntry::failed(started_at, format!("{e:#}")),
+        };
+        if let Err(e) = history_repo.save_sync_history(&entry).await {
+            warn!(error = %e, "Failed to save sync history entry");
+        }
+
+        let result = outcome?;
 
         // Step 9: Display results
         if matches!(format, OutputFormat::Json) {
@@ -134,6 +150,10 @@ impl SyncCommand {
                 "files_downloaded": result.files_downloaded,
                 "files_uploaded": result.files_uploaded,
                 "files_deleted": result.files_deleted,
+                "bytes_downloaded": result.bytes_downloaded,
+                "bytes_uploaded": result.bytes_uploaded,
+                "conflicts_detected": result.conflicts_detected,
+                "conflicts_auto_resolved": result.conflicts_auto_resolved,
                 "errors": result.errors,
                 "duration_ms": result.duration_ms,
             });
@@ -182,6 +202,13 @@ impl SyncCommand {
                 ));
             }
 
+            if result.conflicts_detected > 0 {
+                formatter.info(&format!(
+                    "Conflicts:  {} ({} resolved by policy)",
+                    result.conflicts_detected, result.conflicts_auto_resolved
+                ));
+            }
+
             // Show speed estimate if we have meaningful duration
             if result.duration_ms > 0 && total_files > 0 {
                 let files_per_sec = total_files as f64 / (result.duration_ms as f64 / 1000.0);
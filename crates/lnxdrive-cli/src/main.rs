@@ -2,10 +2,13 @@
 //!
 //! Provides commands for:
 //! - Authentication with OneDrive
+//! - Choosing between several accounts
 //! - Viewing sync status
 //! - Managing conflicts
 //! - Controlling the daemon
+//! - Diagnosing configuration problems
 //! - Explaining file states
+//! - Inspecting and cleaning the content cache
 
 use anyhow::Result;
 use clap::{Parser, Subcommand};
@@ -15,17 +18,21 @@ mod commands;
 mod output;
 
 use commands::{
+    account::AccountCommand,
     audit::AuditCommand,
     auth::AuthCommand,
+    cache::CacheCommand,
     completions::CompletionsCommand,
     config::ConfigCommand,
     conflicts::ConflictsCommand,
     daemon::DaemonCommand,
+    doctor::DoctorCommand,
     explain::ExplainCommand,
     hydrate::{DehydrateCommand, HydrateCommand},
     mount::{MountCommand, UnmountCommand},
     pin::{PinCommand, UnpinCommand},
     status::StatusCommand,
+    status_emblem::StatusEmblemCommand,
     sync::SyncCommand,
 };
 use output::OutputFormat;
@@ -49,6 +56,10 @@ pub struct Cli {
     #[arg(short, long, global = true)]
     quiet: bool,
 
+    /// Account to act on (email, alias or account id)
+    #[arg(long, global = true, env = "LNXDRIVE_ACCOUNT")]
+    account: Option<String>,
+
     #[command(subcommand)]
     command: Commands,
 }
@@ -58,10 +69,15 @@ pub enum Commands {
     /// Authentication commands
     #[command(subcommand)]
     Auth(AuthCommand),
+    /// List accounts and choose the default one
+    #[command(subcommand)]
+    Account(AccountCommand),
     /// Synchronize files with OneDrive
     Sync(SyncCommand),
     /// Show synchronization status
     Status(StatusCommand),
+    /// Print a single sync-state token for file-manager emblems
+    StatusEmblem(StatusEmblemCommand),
     /// Explain why a file is in its current state
     Explain(ExplainCommand),
     /// View audit log entries
@@ -69,6 +85,8 @@ pub enum Commands {
     /// Manage the LNXDrive background daemon
     #[command(subcommand)]
     Daemon(DaemonCommand),
+    /// Check the installation, account and configuration for problems
+    Doctor(DoctorCommand),
     /// View and manage configuration
     #[command(subcommand)]
     Config(ConfigCommand),
@@ -89,6 +107,9 @@ pub enum Commands {
     Hydrate(HydrateCommand),
     /// Dehydrate files to free local disk space
     Dehydrate(DehydrateCommand),
+    /// Show and manage the local content cache
+    #[command(subcommand)]
+    Cache(CacheCommand),
 }
 
 #[tokio::main]
@@ -97,6 +118,8 @@ async fn main() -> Result<()> {
 
     // Setup tracing
     let filter = match cli.verbose {
+        // status-emblem output is parsed by file-manager extensions
+        0 if matches!(cli.command, Commands::StatusEmblem(_)) => "off",
         0 => "info",
         1 => "debug",
         _ => "trace",
@@ -114,21 +137,27 @@ async fn main() -> Result<()> {
         OutputFormat::Human
     };
 
+    let account = cli.account.as_deref();
+
     match cli.command {
-        Commands::Auth(cmd) => cmd.execute(format).await,
-        Commands::Sync(cmd) => cmd.execute(format).await,
-        Commands::Status(cmd) => cmd.execute(format).await,
+        Commands::Auth(cmd) => cmd.execute(format, account).await,
+        Commands::Account(cmd) => cmd.execute(format).await,
+        Commands::Sync(cmd) => cmd.execute(format, account).await,
+        Commands::Status(cmd) => cmd.execute(format, account).await,
+        Commands::StatusEmblem(cmd) => cmd.execute(format).await,
         Commands::Explain(cmd) => cmd.execute(format).await,
         Commands::Audit(cmd) => cmd.execute(format).await,
         Commands::Daemon(cmd) => cmd.execute(format).await,
+        Commands::Doctor(cmd) => cmd.execute(format, account).await,
         Commands::Config(cmd) => cmd.execute(format).await,
-        Commands::Conflicts(cmd) => cmd.execute(format).await,
+        Commands::Conflicts(cmd) => cmd.execute(format, account).await,
         Commands::Completions(cmd) => cmd.execute(format).await,
-        Commands::Mount(cmd) => cmd.execute(format).await,
+        Commands::Mount(cmd) => cmd.execute(format, account).await,
         Commands::Unmount(cmd) => cmd.execute(format).await,
         Commands::Pin(cmd) => cmd.execute(format).await,
         Commands::Unpin(cmd) => cmd.execute(format).await,
         Commands::Hydrate(cmd) => cmd.execute(format).await,
         Commands::Dehydrate(cmd) => cmd.execute(format).await,
+        Commands::Cache(cmd) => cmd.execute(format).await,
     }
 }
@@ -0,0 +1,270 @@
+//! Batch conflict resolution
+//!
+//! Resolves every conflict whose path matches a [`PathFilter`] with one
+//! strategy. Each conflict is resolved independently: a failure is recorded
+//! in the [`BatchResult`] and the batch continues with the next conflict.
+
+use std::future::Future;
+
+use lnxdrive_core::domain::{conflict::Conflict, glob::GlobPattern};
+use serde::Serialize;
+
+use crate::{resolver::ConflictResolver, ConflictError};
+
+/// Selects conflicts by their path relative to the sync root
+#[derive(Debug, Clone)]
+pub enum PathFilter {
+    /// Matches every path
+    All,
+    /// Matches a directory prefix (`Photos` matches `Photos/a.jpg`) or an
+    /// exact path
+    Prefix(String),
+    /// Matches a glob pattern
+    Glob(GlobPattern),
+}
+
+impl PathFilter {
+    /// Parses a filter string
+    ///
+    /// An empty string (or `/`) matches everything; strings containing
+    /// glob metacharacters (`*`, `?`, `[`) are globs; anything else is a
+    /// path prefix.
+    ///
+    /// # Errors
+    /// Returns [`ConflictError::InvalidPattern`] for malformed globs.
+    pub fn parse(filter: &str) -> Result<Self, ConflictError> {
+        let trimmed = filter.trim().trim_matches('/');
+        if trimmed.is_empty() {
+            return Ok(PathFilter::All);
+        }
+        if trimmed.contains(['*', '?', '[']) {
+            return GlobPattern::new(trimmed)
+                .map(PathFilter::Glob)
+                .map_err(|e| ConflictError::InvalidPattern {
+                    index: 0,
+                    message: e.to_string(),
+                });
+        }
+        Ok(PathFilter::Prefix(trimmed.to_string()))
+    }
+
+    /// Returns true if a path relative to the sync root matches
+    pub fn matches(&self, relative_path: &str) -> bool {
+        let path = relative_path.trim_start_matches('/');
+        match self {
+            PathFilter::All => true,
+            PathFilter::Prefix(prefix) => {
+                path == prefix
+                    || path
+                        .strip_prefix(prefix.as_str())
+                        .is_some_and(|rest| rest.starts_with('/'))
+            }
+            PathFilter::Glob(glob) => glob.matches(path),
+        }
+    }
+}
+
+/// A conflict together with its path relative to the sync root
+#[derive(Debug, Clone)]
+pub struct BatchItem {
+    /// The unresolved conflict
+    pub conflict: Conflict,
+    /// Path of the conflicting item relative to the sync root
+    pub path: String,
+}
+
+/// Outcome of resolving one conflict in a batch
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct BatchOutcome {
+    /// ID of the conflict
+    pub conflict_id: String,
+    /// Path of the conflicting item relative to the sync root
+    pub path: String,
+    /// Whether the resolution was applied
+    pub success: bool,
+    /// Failure reason, if any
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Summary of a batch resolution
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct BatchResult {
+    /// Number of conflicts matching the filter
+    pub matched: u32,
+    /// Number of conflicts resolved
+    pub resolved: u32,
+    /// Number of conflicts whose resolution failed
+    pub failed: u32,
+    /// Per-conflict outcomes, in processing order
+    pub outcomes: Vec<BatchOutcome>,
+}
+
+impl BatchResult {
+    /// Returns true if every matching conflict was resolved
+    pub fn is_complete(&self) -> bool {
+        self.failed == 0
+    }
+}
+
+impl ConflictResolver {
+    /// Resolves every conflict matching `filter` using `apply`
+    ///
+    /// `apply` performs the resolution of a single conflict (persisting it,
+    /// auditing it, ...). Failures are recorded and do not stop the batch.
+    pub async fn batch<F, Fut>(
+        &self,
+        items: Vec<BatchItem>,
+        filter: &PathFilter,
+        mut apply: F,
+    ) -> BatchResult
+    where
+        F: FnMut(BatchItem) -> Fut,
+        Fut: Future<Output = Result<(), String>>,
+    {
+        let mut result = BatchResult::default();
+
+        for item in items.into_iter().filter(|i| filter.matches(&i.path)) {
+            result.matched += 1;
+            let conflict_id = item.conflict.id().to_string();
+            let path = item.path.clone();
+
+            match apply(item).await {
+                Ok(()) => {
+                    result.resolved += 1;
+                    result.outcomes.push(BatchOutcome {
+                        conflict_id,
+                        path,
+                        success: true,
+                        error: None,
+                    });
+                }
+                Err(error) => {
+                    tracing::warn!(%conflict_id, %path, %error, "Batch resolution failed");
+                    result.failed += 1;
+                    result.outcomes.push(BatchOutcome {
+                        conflict_id,
+                        path,
+                        success: false,
+                        error: Some(error),
+                    });
+                }
+            }
+        }
+
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+    use lnxdrive_core::domain::{
+        conflict::VersionInfo,
+        newtypes::{FileHash, UniqueId},
+    };
+
+    use super::*;
+
+    fn item(path: &str) -> BatchItem {
+        let version = || {
+            VersionInfo::new(
+                FileHash::new("AAAAAAAAAAAAAAAAAAAAAAAAAAA=".to_string()).unwrap(),
+                1,
+                Utc::now(),
+            )
+        };
+        BatchItem {
+            conflict: Conflict::new(UniqueId::new(), version(), version()),
+            path: path.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_path_filter_parse() {
+        assert!(matches!(PathFilter::parse("").unwrap(), PathFilter::All));
+        assert!(matches!(PathFilter::parse("/").unwrap(), PathFilter::All));
+        assert!(matches!(
+            PathFilter::parse("Photos/").unwrap(),
+            PathFilter::Prefix(p) if p == "Photos"
+        ));
+        assert!(matches!(
+            PathFilter::parse("*.jpg").unwrap(),
+            PathFilter::Glob(_)
+        ));
+        assert!(PathFilter::parse("[abc").is_err());
+    }
+
+    #[test]
+    fn test_prefix_filter_matches_whole_segments() {
+        let filter = PathFilter::parse("Photos").unwrap();
+        assert!(filter.matches("Photos"));
+        assert!(filter.matches("Photos/2025/beach.jpg"));
+        assert!(filter.matches("/Photos/a.jpg"));
+        assert!(!filter.matches("PhotosOld/a.jpg"));
+        assert!(!filter.matches("Docs/Photos/a.jpg"));
+    }
+
+    #[tokio::test]
+    async fn test_batch_resolves_matching_and_continues_on_failure() {
+        let items = vec![
+            item("Photos/a.jpg"),
+            item("Docs/report.docx"),
+            item("Photos/broken.jpg"),
+            item("Photos/b.jpg"),
+        ];
+        let filter = PathFilter::parse("Photos/").unwrap();
+
+        let result = ConflictResolver::new()
+            .batch(items, &filter, |item| async move {
+                if item.path.contains("broken") {
+                    Err("disk full".to_string())
+                } else {
+                    Ok(())
+                }
+            })
+            .await;
+
+        assert_eq!(result.matched, 3);
+        assert_eq!(result.resolved, 2);
+        assert_eq!(result.failed, 1);
+        assert!(!result.is_complete());
+        assert_eq!(result.outcomes.len(), 3);
+        assert_eq!(result.outcomes[1].path, "Photos/broken.jpg");
+        assert_eq!(result.outcomes[1].error.as_deref(), Some("disk full"));
+        assert_eq!(result.outcomes[2].path, "Photos/b.jpg");
+        assert!(result.outcomes[2].success);
+    }
+
+    #[tokio::test]
+    async fn test_batch_with_glob_filter() {
+        let items = vec![item("a.log"), item("logs/b.log"), item("c.txt")];
+        let filter = PathFilter::parse("*.log").unwrap();
+
+        let result = ConflictResolver::new()
+            .batch(items, &filter, |_| async { Ok(()) })
+            .await;
+
+        assert_eq!(result.matched, 2);
+        assert!(result.is_complete());
+    }
+
+    #[test]
+    fn test_batch_result_serialization() {
+        let result = BatchResult {
+            matched: 1,
+            resolved: 1,
+            failed: 0,
+            outcomes: vec![BatchOutcome {
+                conflict_id: "c1".to_string(),
+                path: "a.txt".to_string(),
+                success: true,
+                error: None,
+            }],
+        };
+        let json = serde_json::to_value(&result).unwrap();
+        assert_eq!(json["resolved"], 1);
+        assert_eq!(json["outcomes"][0]["path"], "a.txt");
+        assert!(json["outcomes"][0].get("error").is_none());
+    }
+}
@@ -0,0 +1,425 @@
+//! Conflict detection
+//!
+//! The [`ConflictDetector`] compares the local and remote state of a path
+//! against the last synced content hash. Besides content conflicts it
+//! detects type conflicts, where a path is a file on one side and a
+//! directory on the other; a hash comparison alone would miss those.
+//!
+//! Hashing a large file on every cycle is costly, so in
+//! [`DetectionMode::Quick`] the detector first compares a cheap
+//! [`Fingerprint`] (size, mtime, ETag) with the one recorded at the last
+//! sync, and only asks for the content hash when they differ. An edit that
+//! keeps both the size and the mtime (e.g. a tool restoring the old mtime)
+//! goes unnoticed until the fingerprint changes again;
+//! [`DetectionMode::FullHash`] always hashes and never misses one.
+
+use std::fmt;
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+/// Whether an entry is a regular file or a directory
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EntryKind {
+    /// A regular file
+    File,
+    /// A directory
+    Directory,
+}
+
+impl EntryKind {
+    /// Returns the kind for an `is_directory` flag
+    pub fn from_is_directory(is_directory: bool) -> Self {
+        if is_directory {
+            EntryKind::Directory
+        } else {
+            EntryKind::File
+        }
+    }
+}
+
+impl fmt::Display for EntryKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EntryKind::File => write!(f, "file"),
+            EntryKind::Directory => write!(f, "directory"),
+        }
+    }
+}
+
+/// The state of one side (local or remote) of a path
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EntryState {
+    /// File or directory
+    pub kind: EntryKind,
+    /// Content hash, if known (always `None` for directories)
+    pub hash: Option<String>,
+}
+
+impl EntryState {
+    /// A file with the given content hash
+    pub fn file(hash: Option<impl Into<String>>) -> Self {
+        Self {
+            kind: EntryKind::File,
+            hash: hash.map(Into::into),
+        }
+    }
+
+    /// A directory
+    pub fn directory() -> Self {
+        Self {
+            kind: EntryKind::Directory,
+            hash: None,
+        }
+    }
+}
+
+/// How a side is checked for changes before its hash is compared
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DetectionMode {
+    /// Hash only when the size, mtime or ETag differ from the last sync
+    #[default]
+    Quick,
+    /// Hash every time
+    FullHash,
+}
+
+impl DetectionMode {
+    /// Parses the `conflicts.change_detection` value (`quick` or `full_hash`)
+    ///
+    /// Unknown values fall back to the default; the configuration
+    /// validation reports them.
+    pub fn from_config_value(value: &str) -> Self {
+        match value {
+            "full_hash" => Self::FullHash,
+            _ => Self::Quick,
+        }
+    }
+}
+
+/// Cheap change signal of one side of a path
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Fingerprint {
+    /// Size in bytes
+    pub size: u64,
+    /// Last modification time, if known and trusted
+    pub modified: Option<DateTime<Utc>>,
+    /// Entity tag, known for remote entries only
+    pub etag: Option<String>,
+}
+
+impl Fingerprint {
+    /// A fingerprint from the size and mtime of a local file
+    pub fn local(size: u64, modified: Option<DateTime<Utc>>) -> Self {
+        Self {
+            size,
+            modified,
+            etag: None,
+        }
+    }
+
+    /// Returns true if both fingerprints describe the same content
+    ///
+    /// The sizes must be equal and at least one of the mtime or ETag must be
+    /// known on both sides; every signal known on both sides must agree.
+    pub fn matches(&self, other: &Fingerprint) -> bool {
+        let modified = self.modified.zip(other.modified).map(|(a, b)| a == b);
+        let etag = self
+            .etag
+            .as_deref()
+            .zip(other.etag.as_deref())
+            .map(|(a, b)| a == b);
+        self.size == other.size
+            && (modified.is_some() || etag.is_some())
+            && modified != Some(false)
+            && etag != Some(false)
+    }
+}
+
+/// Outcome of comparing the local and remote state of a path
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum DetectionResult {
+    /// Neither side changed since the last sync
+    Unchanged,
+    /// Only the local side changed
+    LocalChanged,
+    /// Only the remote side changed
+    RemoteChanged,
+    /// Both sides changed to identical content
+    Converged,
+    /// Both sides changed to different content
+    ContentConflict,
+    /// The path is a file on one side and a directory on the other
+    TypeConflict {
+        /// Kind of the local entry
+        local: EntryKind,
+        /// Kind of the remote entry
+        remote: EntryKind,
+    },
+    /// The remote entry was deleted while the local copy changed
+    DeleteConflict,
+}
+
+impl DetectionResult {
+    /// Returns true if the result needs a conflict resolution
+    pub fn is_conflict(&self) -> bool {
+        matches!(
+            self,
+            DetectionResult::ContentConflict
+                | DetectionResult::TypeConflict { .. }
+                | DetectionResult::DeleteConflict
+        )
+    }
+}
+
+/// Compares local and remote entries against the last synced state
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ConflictDetector {
+    mode: DetectionMode,
+}
+
+impl ConflictDetector {
+    /// Creates a new detector in [`DetectionMode::Quick`]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a detector using the given mode
+    pub fn with_mode(mode: DetectionMode) -> Self {
+        Self { mode }
+    }
+
+    /// Returns the detection mode
+    pub fn mode(&self) -> DetectionMode {
+        self.mode
+    }
+
+    /// Returns true if a side must be hashed to tell whether it changed
+    ///
+    /// `recorded` is the fingerprint saved at the last sync and `current`
+    /// the one observed now. In [`DetectionMode::Quick`] matching
+    /// fingerprints mean the content is unchanged and the hash is skipped.
+    pub fn needs_hash(&self, recorded: &Fingerprint, current: &Fingerprint) -> bool {
+        match self.mode {
+            DetectionMode::Quick => !recorded.matches(current),
+            DetectionMode::FullHash => true,
+        }
+    }
+
+    /// Classifies a path from its last synced hash and both current states
+    ///
+    /// `base_hash` is the content hash recorded at the last successful sync,
+    /// or `None` if the path was never synced. A side whose hash is unknown
+    /// is treated as unchanged.
+    pub fn detect(
+        &self,
+        base_hash: Option<&str>,
+        local: &EntryState,
+        remote: &EntryState,
+    ) -> DetectionResult {
+        if local.kind != remote.kind {
+            return DetectionResult::TypeConflict {
+                local: local.kind,
+                remote: remote.kind,
+            };
+        }
+
+        if local.kind == EntryKind::Directory {
+            return DetectionResult::Unchanged;
+        }
+
+        let changed = |hash: &Option<String>| match hash {
+            Some(hash) => base_hash != Some(hash.as_str()),
+            None => false,
+        };
+
+        match (changed(&local.hash), changed(&remote.hash)) {
+            (false, false) => DetectionResult::Unchanged,
+            (true, false) => DetectionResult::LocalChanged,
+            (false, true) => DetectionResult::RemoteChanged,
+            (true, true) if local.hash == remote.hash => DetectionResult::Converged,
+            (true, true) => DetectionResult::ContentConflict,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BASE: &str = "AAAAAAAAAAAAAAAAAAAAAAAAAAA=";
+    const LOCAL: &str = "BBBBBBBBBBBBBBBBBBBBBBBBBBB=";
+    const REMOTE: &str = "CCCCCCCCCCCCCCCCCCCCCCCCCCC=";
+
+    #[test]
+    fn test_content_changes() {
+        let d = ConflictDetector::new();
+        assert_eq!(
+            d.detect(
+                Some(BASE),
+                &EntryState::file(Some(BASE)),
+                &EntryState::file(Some(BASE))
+            ),
+            DetectionResult::Unchanged
+        );
+        assert_eq!(
+            d.detect(
+                Some(BASE),
+                &EntryState::file(Some(LOCAL)),
+                &EntryState::file(Some(BASE))
+            ),
+            DetectionResult::LocalChanged
+        );
+        assert_eq!(
+            d.detect(
+                Some(BASE),
+                &EntryState::file(Some(BASE)),
+                &EntryState::file(Some(REMOTE))
+            ),
+            DetectionResult::RemoteChanged
+        );
+        assert_eq!(
+            d.detect(
+                Some(BASE),
+                &EntryState::file(Some(LOCAL)),
+                &EntryState::file(Some(LOCAL))
+            ),
+            DetectionResult::Converged
+        );
+        assert_eq!(
+            d.detect(
+                Some(BASE),
+                &EntryState::file(Some(LOCAL)),
+                &EntryState::file(Some(REMOTE))
+            ),
+            DetectionResult::ContentConflict
+        );
+    }
+
+    #[test]
+    fn test_unknown_hash_is_unchanged() {
+        let d = ConflictDetector::new();
+        assert_eq!(
+            d.detect(
+                Some(BASE),
+                &EntryState::file(None::<String>),
+                &EntryState::file(Some(REMOTE))
+            ),
+            DetectionResult::RemoteChanged
+        );
+    }
+
+    #[test]
+    fn test_local_file_shadowing_remote_folder() {
+        let d = ConflictDetector::new();
+        let result = d.detect(
+            None,
+            &EntryState::file(Some(LOCAL)),
+            &EntryState::directory(),
+        );
+        assert_eq!(
+            result,
+            DetectionResult::TypeConflict {
+                local: EntryKind::File,
+                remote: EntryKind::Directory,
+            }
+        );
+        assert!(result.is_conflict());
+    }
+
+    #[test]
+    fn test_local_folder_shadowing_remote_file() {
+        let d = ConflictDetector::new();
+        // Matching hashes do not hide a type change
+        let result = d.detect(
+            Some(BASE),
+            &EntryState::directory(),
+            &EntryState::file(Some(BASE)),
+        );
+        assert_eq!(
+            result,
+            DetectionResult::TypeConflict {
+                local: EntryKind::Directory,
+                remote: EntryKind::File,
+            }
+        );
+    }
+
+    #[test]
+    fn test_directories_are_unchanged() {
+        let d = ConflictDetector::new();
+        let result = d.detect(None, &EntryState::directory(), &EntryState::directory());
+        assert_eq!(result, DetectionResult::Unchanged);
+        assert!(!result.is_conflict());
+    }
+
+    #[test]
+    fn test_quick_mode_hashes_only_changed_fingerprints() {
+        let d = ConflictDetector::new();
+        let mtime = Utc::now();
+        let recorded = Fingerprint::local(1024, Some(mtime));
+
+        assert!(!d.needs_hash(&recorded, &Fingerprint::local(1024, Some(mtime))));
+        assert!(d.needs_hash(&recorded, &Fingerprint::local(2048, Some(mtime))));
+        assert!(d.needs_hash(
+            &recorded,
+            &Fingerprint::local(1024, Some(mtime + chrono::Duration::seconds(1)))
+        ));
+        // Without an mtime or ETag the size alone proves nothing
+        assert!(d.needs_hash(
+            &Fingerprint::local(1024, None),
+            &Fingerprint::local(1024, None)
+        ));
+    }
+
+    #[test]
+    fn test_quick_mode_compares_etags() {
+        let d = ConflictDetector::with_mode(DetectionMode::Quick);
+        let remote = |etag: &str| Fingerprint {
+            size: 1024,
+            modified: None,
+            etag: Some(etag.to_string()),
+        };
+
+        assert!(!d.needs_hash(&remote("\"v1\""), &remote("\"v1\"")));
+        assert!(d.needs_hash(&remote("\"v1\""), &remote("\"v2\"")));
+    }
+
+    #[test]
+    fn test_full_hash_mode_always_hashes() {
+        let d = ConflictDetector::with_mode(DetectionMode::FullHash);
+        let fingerprint = Fingerprint::local(1024, Some(Utc::now()));
+        assert_eq!(d.mode(), DetectionMode::FullHash);
+        assert!(d.needs_hash(&fingerprint, &fingerprint.clone()));
+    }
+
+    #[test]
+    fn test_detection_mode_from_config_value() {
+        assert_eq!(
+            DetectionMode::from_config_value("full_hash"),
+            DetectionMode::FullHash
+        );
+        assert_eq!(
+            DetectionMode::from_config_value("quick"),
+            DetectionMode::Quick
+        );
+        assert_eq!(
+            DetectionMode::from_config_value("bogus"),
+            DetectionMode::Quick
+        );
+    }
+
+    #[test]
+    fn test_detection_result_serialization() {
+        let result = DetectionResult::TypeConflict {
+            local: EntryKind::File,
+            remote: EntryKind::Directory,
+        };
+        let json = serde_json::to_value(&result).unwrap();
+        assert_eq!(json["kind"], "type_conflict");
+        assert_eq!(json["local"], "file");
+        assert_eq!(json["remote"], "directory");
+    }
+}
@@ -1,7 +1,79 @@
 //! LNXDrive Conflict - Conflict detection and resolution
 //!
 //! Provides:
-//! - Hash-based conflict detection
+//! - Hash-based conflict detection, with a cheap size/mtime/ETag first pass
 //! - Configurable resolution strategies
 //! - Automatic resolution for configured patterns
 //! - Manual resolution UI integration
+//!
+//! ## Modules
+//!
+//! - [`batch`] - Resolves many conflicts selected by a path filter
+//! - [`detector`] - Classifies local/remote changes, including type conflicts
+//! - [`policy`] - Pattern rules that pick a resolution strategy per path
+//! - [`resolver`] - Plans the steps that apply a resolution
+
+pub mod batch;
+pub mod detector;
+pub mod policy;
+pub mod resolver;
+
+pub use batch::{BatchItem, BatchOutcome, BatchResult, PathFilter};
+pub use detector::{
+    ConflictDetector, DetectionMode, DetectionResult, EntryKind, EntryState, Fingerprint,
+};
+pub use policy::{MatchedRule, PolicyDecision, PolicyEngine, Strategy};
+pub use resolver::{conflict_copy_path, ConflictResolver, ResolutionStep};
+
+use lnxdrive_core::domain::ReasonCode;
+use thiserror::Error;
+
+/// Errors that can occur while building conflict policies
+#[derive(Debug, Error)]
+pub enum ConflictError {
+    /// A rule's glob pattern could not be parsed
+    #[error("Invalid pattern in rule {index}: {message}")]
+    InvalidPattern {
+        /// Position of the rule in the configuration
+        index: usize,
+        /// Parser error message
+        message: String,
+    },
+
+    /// A rule or the default names an unknown strategy
+    #[error("Invalid strategy '{strategy}' for {field}")]
+    InvalidStrategy {
+        /// Configuration field holding the strategy
+        field: String,
+        /// The rejected strategy name
+        strategy: String,
+    },
+}
+
+impl ConflictError {
+    /// Returns the [`ReasonCode`] describing this error
+    ///
+    /// A policy that cannot be built leaves the conflicts it covers
+    /// unresolved, so every variant maps to [`ReasonCode::MergeFailed`].
+    pub fn reason(&self) -> ReasonCode {
+        match self {
+            ConflictError::InvalidPattern { .. } | ConflictError::InvalidStrategy { .. } => {
+                ReasonCode::MergeFailed
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_error_reason() {
+        let error = ConflictError::InvalidStrategy {
+            field: "default_strategy".to_string(),
+            strategy: "merge".to_string(),
+        };
+        assert_eq!(error.reason(), ReasonCode::MergeFailed);
+    }
+}
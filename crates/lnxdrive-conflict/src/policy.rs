@@ -0,0 +1,450 @@
+//! Policy-driven conflict resolution
+//!
+//! A [`PolicyEngine`] maps a conflicting path to a [`Resolution`] using the
+//! ordered `conflicts.rules` from the configuration. The first rule whose
+//! glob matches the path relative to the sync root wins; paths matching no
+//! rule use `conflicts.default_strategy`.
+//!
+//! Besides the fixed resolutions, a strategy can be `keep_newer`, which
+//! keeps whichever version was modified last. It needs both versions (see
+//! [`PolicyEngine::evaluate_versions`]); when they are equally new, or
+//! there is nothing to compare (type and delete conflicts), both are kept.
+
+use std::fmt;
+
+use lnxdrive_core::{
+    config::ConflictsConfig,
+    domain::{
+        conflict::{Resolution, VersionInfo},
+        glob::GlobPattern,
+    },
+};
+use serde::{Serialize, Serializer};
+
+use crate::ConflictError;
+
+/// A configured conflict strategy
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Strategy {
+    /// Always apply this resolution
+    Fixed(Resolution),
+    /// Keep the version modified last
+    KeepNewer,
+}
+
+impl Strategy {
+    /// Parses a strategy name: `keep_newer` or any [`Resolution`] name
+    fn parse(name: &str) -> Option<Self> {
+        match name {
+            "keep_newer" | "newer" => Some(Strategy::KeepNewer),
+            other => other.parse::<Resolution>().ok().map(Strategy::Fixed),
+        }
+    }
+
+    /// Picks the resolution for a conflict between `local` and `remote`
+    fn resolve(&self, versions: Option<(&VersionInfo, &VersionInfo)>) -> Resolution {
+        match self {
+            Strategy::Fixed(resolution) => resolution.clone(),
+            Strategy::KeepNewer => match versions {
+                Some((local, remote)) if local.modified_at() > remote.modified_at() => {
+                    Resolution::KeepLocal
+                }
+                Some((local, remote)) if remote.modified_at() > local.modified_at() => {
+                    Resolution::KeepRemote
+                }
+                _ => Resolution::KeepBoth,
+            },
+        }
+    }
+}
+
+impl fmt::Display for Strategy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Strategy::Fixed(resolution) => resolution.fmt(f),
+            Strategy::KeepNewer => f.write_str("keep_newer"),
+        }
+    }
+}
+
+impl Serialize for Strategy {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+/// A compiled conflict rule
+#[derive(Debug, Clone)]
+struct CompiledRule {
+    /// Position of the rule in `conflicts.rules`
+    index: usize,
+    pattern: GlobPattern,
+    strategy: Strategy,
+}
+
+/// Which part of the policy produced a decision
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum MatchedRule {
+    /// A pattern rule matched
+    Rule {
+        /// Position of the rule in `conflicts.rules`
+        index: usize,
+        /// The rule's glob pattern
+        pattern: String,
+    },
+    /// No rule matched; the default strategy applies
+    Default,
+}
+
+impl fmt::Display for MatchedRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MatchedRule::Rule { index, pattern } => write!(f, "rule #{} ({})", index, pattern),
+            MatchedRule::Default => write!(f, "default strategy"),
+        }
+    }
+}
+
+/// The outcome of evaluating the policy for a path
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PolicyDecision {
+    /// Resolution to apply
+    pub resolution: Resolution,
+    /// The rule that produced the resolution
+    pub rule: MatchedRule,
+    /// The configured strategy; differs from `resolution` for `keep_newer`
+    pub strategy: Strategy,
+}
+
+impl PolicyDecision {
+    /// Returns true if the conflict can be resolved without the user
+    pub fn is_automatic(&self) -> bool {
+        self.resolution != Resolution::Manual
+    }
+}
+
+/// Ordered set of pattern rules plus a default strategy
+#[derive(Debug, Clone)]
+pub struct PolicyEngine {
+    rules: Vec<CompiledRule>,
+    default: Strategy,
+}
+
+impl Default for PolicyEngine {
+    /// A policy without rules that leaves every conflict for the user
+    fn default() -> Self {
+        Self {
+            rules: Vec::new(),
+            default: Strategy::Fixed(Resolution::Manual),
+        }
+    }
+}
+
+impl PolicyEngine {
+    /// Builds a policy from the `conflicts` configuration section
+    ///
+    /// # Errors
+    /// Returns a [`ConflictError`] if a pattern or strategy is invalid.
+    pub fn from_config(config: &ConflictsConfig) -> Result<Self, ConflictError> {
+        let (policy, errors) = Self::from_config_lenient(config);
+        match errors.into_iter().next() {
+            Some(err) => Err(err),
+            None => Ok(policy),
+        }
+    }
+
+    /// Builds a policy from the `conflicts` configuration section, leaving
+    /// out what is invalid
+    ///
+    /// Invalid rules are skipped and an invalid default strategy falls back
+    /// to `manual`, so one mistake does not disable the other rules. The
+    /// returned errors name each setting that was left out.
+    pub fn from_config_lenient(config: &ConflictsConfig) -> (Self, Vec<ConflictError>) {
+        let mut errors = Vec::new();
+        let default = Strategy::parse(&config.default_strategy).unwrap_or_else(|| {
+            errors.push(ConflictError::InvalidStrategy {
+                field: "conflicts.default_strategy".to_string(),
+                strategy: config.default_strategy.clone(),
+            });
+            Strategy::Fixed(Resolution::Manual)
+        });
+
+        let rules = config
+            .rules
+            .iter()
+            .enumerate()
+            .filter_map(|(index, rule)| {
+                let pattern = GlobPattern::new(rule.pattern.as_str()).map_err(|e| {
+                    ConflictError::InvalidPattern {
+                        index,
+                        message: e.to_string(),
+                    }
+                });
+                let strategy =
+                    Strategy::parse(&rule.strategy).ok_or_else(|| ConflictError::InvalidStrategy {
+                        field: format!("conflicts.rules[{}].strategy", index),
+                        strategy: rule.strategy.clone(),
+                    });
+                match (pattern, strategy) {
+                    (Ok(pattern), Ok(strategy)) => Some(CompiledRule {
+                        index,
+                        pattern,
+                        strategy,
+                    }),
+                    (pattern, strategy) => {
+                        errors.extend(pattern.err());
+                        errors.extend(strategy.err());
+                        None
+                    }
+                }
+            })
+            .collect();
+
+        (Self { rules, default }, errors)
+    }
+
+    /// Number of pattern rules in the policy
+    pub fn rule_count(&self) -> usize {
+        self.rules.len()
+    }
+
+    /// Returns the resolution for a path relative to the sync root
+    ///
+    /// Without versions to compare, `keep_newer` keeps both.
+    pub fn evaluate(&self, relative_path: &str) -> PolicyDecision {
+        self.decide(relative_path, None)
+    }
+
+    /// Returns the resolution for a conflict between two file versions
+    ///
+    /// Like [`Self::evaluate`], but `keep_newer` compares the modification
+    /// times of `local` and `remote`.
+    pub fn evaluate_versions(
+        &self,
+        relative_path: &str,
+        local: &VersionInfo,
+        remote: &VersionInfo,
+    ) -> PolicyDecision {
+        self.decide(relative_path, Some((local, remote)))
+    }
+
+    fn decide(
+        &self,
+        relative_path: &str,
+        versions: Option<(&VersionInfo, &VersionInfo)>,
+    ) -> PolicyDecision {
+        let (strategy, rule) = self
+            .rules
+            .iter()
+            .find(|rule| rule.pattern.matches(relative_path))
+            .map(|rule| {
+                (
+                    &rule.strategy,
+                    MatchedRule::Rule {
+                        index: rule.index,
+                        pattern: rule.pattern.to_string(),
+                    },
+                )
+            })
+            .unwrap_or((&self.default, MatchedRule::Default));
+        PolicyDecision {
+            resolution: strategy.resolve(versions),
+            rule,
+            strategy: strategy.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use lnxdrive_core::config::ConflictRule;
+
+    use super::*;
+
+    fn config(rules: &[(&str, &str)]) -> ConflictsConfig {
+        ConflictsConfig {
+            default_strategy: "manual".to_string(),
+            rules: rules
+                .iter()
+                .map(|(p, s)| ConflictRule {
+                    pattern: p.to_string(),
+                    strategy: s.to_string(),
+                })
+                .collect(),
+            quarantine_after_days: 0,
+            change_detection: "quick".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_first_matching_rule_wins() {
+        let policy = PolicyEngine::from_config(&config(&[
+            ("*.log", "keep_remote"),
+            ("notes/**", "keep_local"),
+            ("*.docx", "keep_both"),
+        ]))
+        .unwrap();
+
+        let decision = policy.evaluate("var/app.log");
+        assert_eq!(decision.resolution, Resolution::KeepRemote);
+        assert_eq!(
+            decision.rule,
+            MatchedRule::Rule {
+                index: 0,
+                pattern: "*.log".to_string()
+            }
+        );
+
+        // notes/** is listed before *.docx
+        let decision = policy.evaluate("notes/plan.docx");
+        assert_eq!(decision.resolution, Resolution::KeepLocal);
+
+        let decision = policy.evaluate("work/report.docx");
+        assert_eq!(decision.resolution, Resolution::KeepBoth);
+        assert!(decision.is_automatic());
+    }
+
+    #[test]
+    fn test_unmatched_falls_back_to_default() {
+        let policy = PolicyEngine::from_config(&config(&[("*.log", "keep_remote")])).unwrap();
+        let decision = policy.evaluate("photos/cat.jpg");
+        assert_eq!(decision.resolution, Resolution::Manual);
+        assert_eq!(decision.rule, MatchedRule::Default);
+        assert!(!decision.is_automatic());
+    }
+
+    #[test]
+    fn test_invalid_rules_are_rejected() {
+        assert!(matches!(
+            PolicyEngine::from_config(&config(&[("notes/**", "merge")])),
+            Err(ConflictError::InvalidStrategy { .. })
+        ));
+        assert!(matches!(
+            PolicyEngine::from_config(&config(&[("[abc", "keep_both")])),
+            Err(ConflictError::InvalidPattern { index: 0, .. })
+        ));
+    }
+
+    #[test]
+    fn test_lenient_config_skips_only_invalid_rules() {
+        let mut config = config(&[
+            ("[abc", "keep_both"),
+            ("*.log", "merge"),
+            ("*.docx", "keep_local"),
+        ]);
+        config.default_strategy = "keep_remote".to_string();
+
+        let (policy, errors) = PolicyEngine::from_config_lenient(&config);
+
+        assert_eq!(errors.len(), 2);
+        assert!(matches!(
+            errors[0],
+            ConflictError::InvalidPattern { index: 0, .. }
+        ));
+        assert!(errors[1]
+            .to_string()
+            .contains("conflicts.rules[1].strategy"));
+        assert_eq!(policy.rule_count(), 1);
+        let decision = policy.evaluate("report.docx");
+        assert_eq!(decision.resolution, Resolution::KeepLocal);
+        assert_eq!(
+            decision.rule,
+            MatchedRule::Rule {
+                index: 2,
+                pattern: "*.docx".to_string()
+            }
+        );
+        assert_eq!(
+            policy.evaluate("app.log").resolution,
+            Resolution::KeepRemote
+        );
+    }
+
+    #[test]
+    fn test_lenient_config_invalid_default_is_manual() {
+        let mut config = config(&[("*.log", "keep_remote")]);
+        config.default_strategy = "merge".to_string();
+
+        let (policy, errors) = PolicyEngine::from_config_lenient(&config);
+
+        assert_eq!(errors.len(), 1);
+        assert_eq!(policy.rule_count(), 1);
+        assert_eq!(policy.evaluate("a.txt").resolution, Resolution::Manual);
+    }
+
+    #[test]
+    fn test_default_policy_is_manual() {
+        let policy = PolicyEngine::default();
+        assert_eq!(policy.rule_count(), 0);
+        assert_eq!(policy.evaluate("a.txt").resolution, Resolution::Manual);
+    }
+
+    fn version(minutes_ago: i64) -> VersionInfo {
+        VersionInfo::new(
+            lnxdrive_core::domain::newtypes::FileHash::new("AAAAAAAAAAAAAAAAAAAAAAAAAAA=".into())
+                .unwrap(),
+            10,
+            chrono::Utc::now() - chrono::Duration::minutes(minutes_ago),
+        )
+    }
+
+    fn keep_newer_policy() -> PolicyEngine {
+        PolicyEngine::from_config(&ConflictsConfig {
+            default_strategy: "keep_newer".to_string(),
+            rules: Vec::new(),
+            quarantine_after_days: 0,
+            change_detection: "quick".to_string(),
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn test_keep_newer_prefers_newer_local() {
+        let decision = keep_newer_policy().evaluate_versions("a.txt", &version(1), &version(5));
+        assert_eq!(decision.resolution, Resolution::KeepLocal);
+        assert_eq!(decision.strategy, Strategy::KeepNewer);
+        assert_eq!(decision.rule, MatchedRule::Default);
+        assert!(decision.is_automatic());
+    }
+
+    #[test]
+    fn test_keep_newer_prefers_newer_remote() {
+        let decision = keep_newer_policy().evaluate_versions("a.txt", &version(5), &version(1));
+        assert_eq!(decision.resolution, Resolution::KeepRemote);
+        assert_eq!(decision.strategy.to_string(), "keep_newer");
+    }
+
+    #[test]
+    fn test_keep_newer_without_a_winner_keeps_both() {
+        let policy = keep_newer_policy();
+        let same = version(3);
+        let decision = policy.evaluate_versions("a.txt", &same, &same);
+        assert_eq!(decision.resolution, Resolution::KeepBoth);
+        assert_eq!(policy.evaluate("a.txt").resolution, Resolution::KeepBoth);
+    }
+
+    #[test]
+    fn test_prefer_remote_default() {
+        let policy = PolicyEngine::from_config(&ConflictsConfig {
+            default_strategy: "prefer_remote".to_string(),
+            rules: Vec::new(),
+            quarantine_after_days: 0,
+            change_detection: "quick".to_string(),
+        })
+        .unwrap();
+        let decision = policy.evaluate_versions("a.txt", &version(1), &version(5));
+        assert_eq!(decision.resolution, Resolution::KeepRemote);
+        assert_eq!(decision.strategy, Strategy::Fixed(Resolution::KeepRemote));
+    }
+
+    #[test]
+    fn test_matched_rule_display() {
+        let rule = MatchedRule::Rule {
+            index: 2,
+            pattern: "*.docx".to_string(),
+        };
+        assert_eq!(rule.to_string(), "rule #2 (*.docx)");
+        assert_eq!(MatchedRule::Default.to_string(), "default strategy");
+    }
+}
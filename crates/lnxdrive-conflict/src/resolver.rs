@@ -0,0 +1,249 @@
+//! Planning conflict resolutions
+//!
+//! The [`ConflictResolver`] turns a detected conflict and the chosen
+//! [`Resolution`] into the ordered [`ResolutionStep`]s the sync engine
+//! executes. Resolutions that keep both versions move the local entry to a
+//! sibling name before the remote version is written in its place.
+
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Utc};
+use lnxdrive_core::domain::conflict::Resolution;
+
+use crate::detector::DetectionResult;
+
+/// A single action needed to apply a resolution
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ResolutionStep {
+    /// Move the local entry to the given path so both versions survive
+    RenameLocal(PathBuf),
+    /// Delete the local entry (recursively for directories)
+    DeleteLocal,
+    /// Delete the remote entry
+    DeleteRemote,
+    /// Download the remote entry to the original path
+    DownloadRemote,
+    /// Upload the local entry, replacing the remote version
+    UploadLocal,
+}
+
+/// Plans the steps that apply a resolution to a detected conflict
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ConflictResolver;
+
+impl ConflictResolver {
+    /// Creates a new resolver
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Returns the steps applying `resolution` to the entry at `local_path`
+    ///
+    /// Returns an empty plan when nothing should change on disk: for
+    /// `Manual` resolutions and for detection results that are not
+    /// conflicts. Type conflicts cannot simply overwrite one side, so
+    /// keep-local and keep-remote first delete the entry of the other type.
+    /// A delete conflict has no remote version to keep, so keep-both
+    /// re-uploads the local file like keep-local.
+    pub fn plan(
+        &self,
+        detection: &DetectionResult,
+        resolution: &Resolution,
+        local_path: &Path,
+        now: DateTime<Utc>,
+    ) -> Vec<ResolutionStep> {
+        if !detection.is_conflict() {
+            return Vec::new();
+        }
+        let type_conflict = matches!(detection, DetectionResult::TypeConflict { .. });
+
+        if *detection == DetectionResult::DeleteConflict {
+            return match resolution {
+                Resolution::Manual => Vec::new(),
+                Resolution::KeepRemote => vec![ResolutionStep::DeleteLocal],
+                Resolution::KeepLocal | Resolution::KeepBoth => vec![ResolutionStep::UploadLocal],
+            };
+        }
+
+        match resolution {
+            Resolution::Manual => Vec::new(),
+            Resolution::KeepLocal if type_conflict => {
+                vec![ResolutionStep::DeleteRemote, ResolutionStep::UploadLocal]
+            }
+            Resolution::KeepLocal => vec![ResolutionStep::UploadLocal],
+            Resolution::KeepRemote if type_conflict => {
+                vec![ResolutionStep::DeleteLocal, ResolutionStep::DownloadRemote]
+            }
+            Resolution::KeepRemote => vec![ResolutionStep::DownloadRemote],
+            Resolution::KeepBoth => vec![
+                ResolutionStep::RenameLocal(conflict_copy_path(local_path, now)),
+                ResolutionStep::DownloadRemote,
+            ],
+        }
+    }
+}
+
+/// Returns the sibling path used to preserve the local version of a file
+///
+/// `report.docx` becomes `report (conflicted copy 2026-02-05 143012).docx`.
+/// Dotfiles and names without an extension get the suffix appended.
+pub fn conflict_copy_path(path: &Path, timestamp: DateTime<Utc>) -> PathBuf {
+    let suffix = format!(" (conflicted copy {})", timestamp.format("%Y-%m-%d %H%M%S"));
+
+    let file_name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_default();
+
+    let new_name = match file_name.rfind('.') {
+        Some(dot) if dot > 0 => format!("{}{}{}", &file_name[..dot], suffix, &file_name[dot..]),
+        _ => format!("{}{}", file_name, suffix),
+    };
+
+    path.with_file_name(new_name)
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+    use crate::detector::EntryKind;
+
+    fn ts() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 2, 5, 14, 30, 12).unwrap()
+    }
+
+    #[test]
+    fn test_conflict_copy_keeps_extension() {
+        let copy = conflict_copy_path(Path::new("/home/u/OneDrive/work/report.docx"), ts());
+        assert_eq!(
+            copy,
+            PathBuf::from("/home/u/OneDrive/work/report (conflicted copy 2026-02-05 143012).docx")
+        );
+    }
+
+    #[test]
+    fn test_conflict_copy_without_extension() {
+        let copy = conflict_copy_path(Path::new("/sync/Makefile"), ts());
+        assert_eq!(
+            copy,
+            PathBuf::from("/sync/Makefile (conflicted copy 2026-02-05 143012)")
+        );
+    }
+
+    #[test]
+    fn test_conflict_copy_dotfile() {
+        let copy = conflict_copy_path(Path::new("/sync/.bashrc"), ts());
+        assert_eq!(
+            copy,
+            PathBuf::from("/sync/.bashrc (conflicted copy 2026-02-05 143012)")
+        );
+    }
+
+    fn type_conflict() -> DetectionResult {
+        DetectionResult::TypeConflict {
+            local: EntryKind::File,
+            remote: EntryKind::Directory,
+        }
+    }
+
+    #[test]
+    fn test_plan_content_conflict() {
+        let r = ConflictResolver::new();
+        let path = Path::new("/sync/a.txt");
+        let c = DetectionResult::ContentConflict;
+
+        assert_eq!(
+            r.plan(&c, &Resolution::KeepLocal, path, ts()),
+            vec![ResolutionStep::UploadLocal]
+        );
+        assert_eq!(
+            r.plan(&c, &Resolution::KeepRemote, path, ts()),
+            vec![ResolutionStep::DownloadRemote]
+        );
+        assert!(r.plan(&c, &Resolution::Manual, path, ts()).is_empty());
+    }
+
+    #[test]
+    fn test_plan_type_conflict_deletes_other_type() {
+        let r = ConflictResolver::new();
+        let path = Path::new("/sync/Photos");
+
+        assert_eq!(
+            r.plan(&type_conflict(), &Resolution::KeepLocal, path, ts()),
+            vec![ResolutionStep::DeleteRemote, ResolutionStep::UploadLocal]
+        );
+        assert_eq!(
+            r.plan(&type_conflict(), &Resolution::KeepRemote, path, ts()),
+            vec![ResolutionStep::DeleteLocal, ResolutionStep::DownloadRemote]
+        );
+    }
+
+    #[test]
+    fn test_plan_type_conflict_keep_both_renames_local() {
+        let r = ConflictResolver::new();
+        let plan = r.plan(
+            &type_conflict(),
+            &Resolution::KeepBoth,
+            Path::new("/sync/Photos"),
+            ts(),
+        );
+        assert_eq!(
+            plan,
+            vec![
+                ResolutionStep::RenameLocal(PathBuf::from(
+                    "/sync/Photos (conflicted copy 2026-02-05 143012)"
+                )),
+                ResolutionStep::DownloadRemote,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_plan_type_conflict_manual_is_empty() {
+        let r = ConflictResolver::new();
+        assert!(r
+            .plan(
+                &type_conflict(),
+                &Resolution::Manual,
+                Path::new("/sync/Photos"),
+                ts()
+            )
+            .is_empty());
+    }
+
+    #[test]
+    fn test_plan_delete_conflict() {
+        let r = ConflictResolver::new();
+        let path = Path::new("/sync/a.txt");
+        let c = DetectionResult::DeleteConflict;
+
+        assert_eq!(
+            r.plan(&c, &Resolution::KeepLocal, path, ts()),
+            vec![ResolutionStep::UploadLocal]
+        );
+        assert_eq!(
+            r.plan(&c, &Resolution::KeepBoth, path, ts()),
+            vec![ResolutionStep::UploadLocal]
+        );
+        assert_eq!(
+            r.plan(&c, &Resolution::KeepRemote, path, ts()),
+            vec![ResolutionStep::DeleteLocal]
+        );
+        assert!(r.plan(&c, &Resolution::Manual, path, ts()).is_empty());
+    }
+
+    #[test]
+    fn test_plan_non_conflict_is_empty() {
+        let r = ConflictResolver::new();
+        assert!(r
+            .plan(
+                &DetectionResult::RemoteChanged,
+                &Resolution::KeepBoth,
+                Path::new("/sync/a.txt"),
+                ts()
+            )
+            .is_empty());
+    }
+}
@@ -3,10 +3,19 @@
 //! Provides typed configuration structs that map to the YAML configuration file,
 //! with loading, validation, defaults, and a builder pattern for programmatic use.
 
-use std::path::{Path, PathBuf};
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+};
 
 use serde::{Deserialize, Serialize};
 
+use crate::domain::{
+    glob::GlobPattern,
+    limits::{ProviderLimits, ONEDRIVE_MAX_FILE_SIZE, ONEDRIVE_MAX_PATH_LENGTH},
+    Account,
+};
+
 // ---------------------------------------------------------------------------
 // T099: Config struct with sub-sections
 // ---------------------------------------------------------------------------
@@ -17,10 +26,24 @@ pub struct Config {
     pub sync: SyncConfig,
     pub rate_limiting: RateLimitingConfig,
     pub large_files: LargeFilesConfig,
+    #[serde(default)]
+    pub limits: LimitsConfig,
     pub conflicts: ConflictsConfig,
     pub logging: LoggingConfig,
     pub auth: AuthConfig,
     pub fuse: FuseConfig,
+    #[serde(default)]
+    pub daemon: DaemonConfig,
+    #[serde(default)]
+    pub metrics: MetricsConfig,
+    #[serde(default)]
+    pub http: HttpConfig,
+    #[serde(default)]
+    pub delta: DeltaConfig,
+    #[serde(default)]
+    pub change_notifications: ChangeNotificationsConfig,
+    #[serde(default)]
+    pub accounts: AccountsConfig,
 }
 
 /// Synchronization settings.
@@ -32,6 +55,36 @@ pub struct SyncConfig {
     pub poll_interval: u64,
     /// Seconds to wait after a local change before syncing (debounce).
     pub debounce_delay: u64,
+    /// Scan the sync root on startup for changes made while the daemon was stopped.
+    #[serde(default = "default_true")]
+    pub startup_reconciliation: bool,
+    /// Seconds between storage quota refreshes (also refreshed after large transfers).
+    #[serde(default = "default_quota_refresh_interval")]
+    pub quota_refresh_interval: u64,
+    /// Leave out hidden files and folders (names starting with a dot, such
+    /// as `.git` or `.cache`).
+    #[serde(default)]
+    pub exclude_hidden: bool,
+    /// Leave out files operating systems leave behind (`.DS_Store`,
+    /// `Thumbs.db`, `desktop.ini`) and editor backups (`name~`).
+    #[serde(default = "default_true")]
+    pub exclude_junk: bool,
+    /// Upload names in Unicode NFC, the form OneDrive and Windows use, and
+    /// match remote names to local ones written in another form (NFD).
+    #[serde(default = "default_true")]
+    pub normalize_unicode: bool,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+fn default_quota_refresh_interval() -> u64 {
+    900
+}
+
+fn default_log_format() -> String {
+    "text".to_string()
 }
 
 /// Microsoft Graph API rate-limiting settings.
@@ -55,11 +108,72 @@ pub struct LargeFilesConfig {
     pub max_concurrent_large: u32,
 }
 
+/// Cloud provider limits checked before uploading.
+///
+/// Defaults to OneDrive's limits; override them for business tenants whose
+/// administrators configured different ones.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LimitsConfig {
+    /// Largest file that can be uploaded, in bytes.
+    #[serde(default = "default_max_file_size")]
+    pub max_file_size: u64,
+    /// Longest remote path, in characters.
+    #[serde(default = "default_max_path_length")]
+    pub max_path_length: usize,
+}
+
+fn default_max_file_size() -> u64 {
+    ONEDRIVE_MAX_FILE_SIZE
+}
+
+fn default_max_path_length() -> usize {
+    ONEDRIVE_MAX_PATH_LENGTH
+}
+
+impl LimitsConfig {
+    /// The limits in the form the sync engine checks them.
+    pub fn provider_limits(&self) -> ProviderLimits {
+        ProviderLimits {
+            max_file_size: self.max_file_size,
+            max_path_length: self.max_path_length,
+        }
+    }
+}
+
 /// Conflict resolution settings.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ConflictsConfig {
-    /// Default conflict strategy: `manual`, `keep_local`, `keep_remote`, or `keep_both`.
+    /// Default conflict strategy: `manual`, `keep_local`, `keep_remote`
+    /// (alias `prefer_remote`), `keep_both`, or `keep_newer` (keeps the
+    /// version modified last).
     pub default_strategy: String,
+    /// Pattern rules for automatic resolution, evaluated in order; the first
+    /// match wins and unmatched conflicts fall back to `default_strategy`.
+    #[serde(default)]
+    pub rules: Vec<ConflictRule>,
+    /// Move conflicts left unresolved for this many days to the quarantine
+    /// folder, keeping both versions, so the file syncs again (0 = never).
+    #[serde(default)]
+    pub quarantine_after_days: u32,
+    /// How files are checked for changes: `quick` hashes only files whose
+    /// size, mtime or ETag changed since the last sync, `full_hash` hashes
+    /// every time. `quick` misses an edit that keeps both the size and the
+    /// mtime until either changes again.
+    #[serde(default = "default_change_detection")]
+    pub change_detection: String,
+}
+
+fn default_change_detection() -> String {
+    "quick".to_string()
+}
+
+/// A conflict resolution rule applied to paths matching a glob pattern.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConflictRule {
+    /// Glob relative to the sync root (e.g. `*.log`, `notes/**`).
+    pub pattern: String,
+    /// Strategy applied to matching conflicts.
+    pub strategy: String,
 }
 
 /// Logging / tracing settings.
@@ -67,6 +181,9 @@ pub struct ConflictsConfig {
 pub struct LoggingConfig {
     /// Log level: `trace`, `debug`, `info`, `warn`, or `error`.
     pub level: String,
+    /// Output format: `text` (human readable) or `json` (one object per line).
+    #[serde(default = "default_log_format")]
+    pub format: String,
     /// Path to the log file.
     pub file: PathBuf,
     /// Maximum size of a single log file (in MiB) before rotation.
@@ -91,16 +208,342 @@ pub struct FuseConfig {
     pub auto_mount: bool,
     /// Directory for caching hydrated file content.
     pub cache_dir: String,
+    /// Directory for the temporary files of atomic writes and downloads.
+    /// Defaults to `tmp` inside `cache_dir`; see [`FuseConfig::temp_dir_path`].
+    #[serde(default)]
+    pub temp_dir: Option<String>,
     /// Maximum size of the cache in gigabytes.
     pub cache_max_size_gb: u32,
+    /// Number of nested shard directories for cached content (1-4).
+    #[serde(default = "default_cache_shard_depth")]
+    pub cache_shard_depth: u8,
+    /// Store identical content once, shared between items via hardlinks.
+    #[serde(default)]
+    pub cache_dedup: bool,
+    /// Verify cached content against its quickXorHash in a background scrub.
+    #[serde(default)]
+    pub cache_scrub_full_hash: bool,
     /// Percentage of cache_max_size_gb that triggers dehydration (0-100).
     pub dehydration_threshold_percent: u8,
     /// Maximum age in days before a cached file becomes eligible for dehydration.
     pub dehydration_max_age_days: u32,
+    /// Dehydrate files not accessed for this many days, even when the cache
+    /// is below the threshold; checked once a day (0 = disabled).
+    #[serde(default)]
+    pub dehydration_unused_days: u32,
+    /// When the cache is over the threshold, dehydrate files of at least
+    /// this many MiB before smaller ones (0 = disabled).
+    #[serde(default)]
+    pub dehydration_large_file_mb: u64,
     /// Interval in minutes between dehydration background tasks.
     pub dehydration_interval_minutes: u32,
+    /// What a sweep does with a file it selected that is open: `skip` it
+    /// until a later sweep selects it again, or `defer` its dehydration
+    /// until its last handle is closed.
+    #[serde(default = "default_dehydration_busy_files")]
+    pub dehydration_busy_files: String,
     /// Number of concurrent file hydration operations allowed.
     pub hydration_concurrency: u8,
+    /// Size in MiB of each ranged request when hydrating large files.
+    #[serde(default = "default_hydration_chunk_size_mb")]
+    pub hydration_chunk_size_mb: u32,
+    /// Files of at least this many MiB are streamed: reads are served as
+    /// soon as their range downloads (0 = always download whole files).
+    #[serde(default = "default_streaming_threshold_mb")]
+    pub streaming_threshold_mb: u64,
+    /// MiB fetched ahead of sequential reads of a streamed file, so
+    /// playback rarely waits for the download (0 = disabled).
+    #[serde(default = "default_read_ahead_mb")]
+    pub read_ahead_mb: u64,
+    /// Files larger than this many MiB are never stored in the cache
+    /// (0 = the cache size, `cache_max_size_gb`).
+    #[serde(default)]
+    pub max_cached_file_mb: u64,
+    /// What opening a file over `max_cached_file_mb` does: `reject` it with
+    /// "File too large", or `stream` its reads from the cloud without
+    /// caching them.
+    #[serde(default = "default_oversized_files")]
+    pub oversized_files: String,
+    /// Keep Unix mode bits set with chmod in the local state database and
+    /// restore them on remount. Other OneDrive clients do not see them.
+    #[serde(default)]
+    pub preserve_permissions: bool,
+    /// Mode of synced files, in octal (e.g. "600" to hide them from other
+    /// users). New files get the mode they are created with, limited to it.
+    #[serde(default = "default_file_mode")]
+    pub file_mode: String,
+    /// Mode of synced directories, in octal. New directories get the mode
+    /// they are created with, limited to it.
+    #[serde(default = "default_directory_mode")]
+    pub directory_mode: String,
+    /// Soft cap on the number of inodes kept in memory. Entries the kernel
+    /// has forgotten are evicted, least recently forgotten first, once it
+    /// is exceeded and reloaded from the state database on the next
+    /// lookup (0 = unlimited).
+    #[serde(default = "default_max_inodes")]
+    pub max_inodes: u64,
+    /// Number of most recently accessed files hydrated in the background
+    /// after mounting (0 = disabled).
+    #[serde(default = "default_prefetch_recent_files")]
+    pub prefetch_recent_files: u32,
+    /// Maximum MiB the recent-file prefetch downloads.
+    #[serde(default = "default_prefetch_recent_mb")]
+    pub prefetch_recent_mb: u64,
+}
+
+impl FuseConfig {
+    /// Directory for temporary files, with `~` expanded
+    ///
+    /// Defaults to `{cache_dir}/tmp`, which is on the same filesystem as
+    /// the cache so finished files are renamed into place atomically.
+    /// Unlike `/tmp`, which may be a small tmpfs, it also has room for
+    /// large downloads.
+    pub fn temp_dir_path(&self) -> PathBuf {
+        match &self.temp_dir {
+            Some(dir) => expand_tilde(Path::new(dir)),
+            None => expand_tilde(Path::new(&self.cache_dir)).join("tmp"),
+        }
+    }
+
+    /// Permission bits of synced files (`file_mode`)
+    ///
+    /// Falls back to 0o644 if the value does not parse; `validate` reports it.
+    pub fn file_perm(&self) -> u16 {
+        parse_mode(&self.file_mode).unwrap_or(0o644)
+    }
+
+    /// Permission bits of synced directories (`directory_mode`)
+    ///
+    /// Falls back to 0o755 if the value does not parse; `validate` reports it.
+    pub fn directory_perm(&self) -> u16 {
+        parse_mode(&self.directory_mode).unwrap_or(0o755)
+    }
+
+    /// Size in bytes above which files are not cached
+    ///
+    /// `max_cached_file_mb`, or the whole cache when it is 0.
+    pub fn max_cached_file_bytes(&self) -> u64 {
+        match self.max_cached_file_mb {
+            0 => (self.cache_max_size_gb as u64) * 1024 * 1024 * 1024,
+            mb => mb * 1024 * 1024,
+        }
+    }
+}
+
+fn default_cache_shard_depth() -> u8 {
+    2
+}
+
+fn default_dehydration_busy_files() -> String {
+    "defer".to_string()
+}
+
+fn default_hydration_chunk_size_mb() -> u32 {
+    10
+}
+
+fn default_streaming_threshold_mb() -> u64 {
+    32
+}
+
+fn default_read_ahead_mb() -> u64 {
+    8
+}
+
+fn default_oversized_files() -> String {
+    "stream".to_string()
+}
+
+fn default_file_mode() -> String {
+    "644".to_string()
+}
+
+fn default_directory_mode() -> String {
+    "755".to_string()
+}
+
+/// Parses an octal permission string such as "644", "0644" or "0o644"
+///
+/// Returns `None` for non-octal values and values above 0o777.
+fn parse_mode(value: &str) -> Option<u16> {
+    let digits = value.strip_prefix("0o").unwrap_or(value);
+    let mode = u16::from_str_radix(digits, 8).ok()?;
+    (mode <= 0o777).then_some(mode)
+}
+
+fn default_max_inodes() -> u64 {
+    1_000_000
+}
+
+fn default_prefetch_recent_files() -> u32 {
+    20
+}
+
+fn default_prefetch_recent_mb() -> u64 {
+    100
+}
+
+/// Background daemon (`lnxdrived`) settings.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DaemonConfig {
+    /// Send readiness and watchdog notifications when running under systemd.
+    #[serde(default = "default_true")]
+    pub systemd_notify: bool,
+    /// Seconds to let in-flight transfers finish on shutdown before they
+    /// are cancelled (unfinished large uploads are checkpointed).
+    #[serde(default = "default_shutdown_timeout")]
+    pub shutdown_timeout: u64,
+}
+
+fn default_shutdown_timeout() -> u64 {
+    30
+}
+
+/// Metrics and health-probe HTTP endpoint settings.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MetricsConfig {
+    /// Serve `/metrics`, `/healthz` and `/readyz` over HTTP.
+    #[serde(default)]
+    pub enabled: bool,
+    /// Address the HTTP server listens on (`host:port`).
+    #[serde(default = "default_metrics_listen_address")]
+    pub listen_address: String,
+    /// `/readyz` fails when the last successful sync is older than this
+    /// many seconds.
+    #[serde(default = "default_ready_max_sync_age")]
+    pub ready_max_sync_age: u64,
+    /// `/readyz` fails when the database does not answer within this many
+    /// milliseconds.
+    #[serde(default = "default_ready_db_timeout_ms")]
+    pub ready_db_timeout_ms: u64,
+}
+
+/// HTTP client settings for Microsoft Graph requests.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HttpConfig {
+    /// Send a `User-Agent` header identifying lnxdrive.
+    #[serde(default = "default_true")]
+    pub send_user_agent: bool,
+    /// `User-Agent` to send instead of the default
+    /// (`ISV|Enigmora|LNXDrive/<version> (<os>; <arch>)`).
+    #[serde(default)]
+    pub user_agent: Option<String>,
+    /// Seconds to wait for a connection to be established.
+    #[serde(default = "default_http_connect_timeout")]
+    pub connect_timeout: u64,
+    /// Seconds a response may send no data before the request is aborted.
+    #[serde(default = "default_http_read_timeout")]
+    pub read_timeout: u64,
+    /// Seconds an API request may take in total; transfers of file content
+    /// get more time in proportion to their size.
+    #[serde(default = "default_http_request_timeout")]
+    pub request_timeout: u64,
+    /// Slowest expected transfer rate in KiB/s, which sets how much longer
+    /// than `request_timeout` a transfer may take.
+    #[serde(default = "default_http_min_transfer_rate_kbps")]
+    pub min_transfer_rate_kbps: u64,
+}
+
+/// Memory limits of remote delta processing.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeltaConfig {
+    /// Items requested per delta page.
+    #[serde(default = "default_delta_batch_size")]
+    pub batch_size: u32,
+    /// Remote changes held in memory before they are applied; larger
+    /// deltas are applied in several rounds within one sync cycle.
+    #[serde(default = "default_delta_max_in_flight_items")]
+    pub max_in_flight_items: usize,
+    /// Items written to the state database per transaction.
+    #[serde(default = "default_delta_transaction_size")]
+    pub transaction_size: usize,
+}
+
+/// Microsoft Graph change notifications (webhooks) for remote changes.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChangeNotificationsConfig {
+    /// Subscribe to change notifications instead of relying on polling
+    /// alone.
+    #[serde(default)]
+    pub enabled: bool,
+    /// Public HTTPS URL Microsoft Graph posts notifications to; it must
+    /// forward them to `listen_address`.
+    #[serde(default)]
+    pub notification_url: Option<String>,
+    /// Address the notification receiver listens on (`host:port`).
+    #[serde(default = "default_change_notifications_listen_address")]
+    pub listen_address: String,
+    /// Lifetime requested for each subscription, in minutes; subscriptions
+    /// are renewed halfway through.
+    #[serde(default = "default_change_notifications_expiration_minutes")]
+    pub expiration_minutes: u32,
+    /// Poll interval in seconds while a subscription is active, as a
+    /// safety net for lost notifications.
+    #[serde(default = "default_change_notifications_poll_interval")]
+    pub poll_interval: u64,
+}
+
+/// Which account commands act on when several are logged in.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AccountsConfig {
+    /// Email of the account used when no `--account` is given.
+    #[serde(default)]
+    pub default: Option<String>,
+    /// Short names for accounts, mapping each alias to an account email.
+    #[serde(default)]
+    pub aliases: BTreeMap<String, String>,
+}
+
+fn default_delta_batch_size() -> u32 {
+    200
+}
+
+fn default_delta_max_in_flight_items() -> usize {
+    10_000
+}
+
+fn default_delta_transaction_size() -> usize {
+    500
+}
+
+fn default_change_notifications_listen_address() -> String {
+    "127.0.0.1:9465".to_string()
+}
+
+fn default_change_notifications_expiration_minutes() -> u32 {
+    4230
+}
+
+fn default_change_notifications_poll_interval() -> u64 {
+    900
+}
+
+fn default_http_connect_timeout() -> u64 {
+    30
+}
+
+fn default_http_read_timeout() -> u64 {
+    60
+}
+
+fn default_http_request_timeout() -> u64 {
+    120
+}
+
+fn default_http_min_transfer_rate_kbps() -> u64 {
+    64
+}
+
+fn default_metrics_listen_address() -> String {
+    "127.0.0.1:9464".to_string()
+}
+
+fn default_ready_max_sync_age() -> u64 {
+    300
+}
+
+fn default_ready_db_timeout_ms() -> u64 {
+    2000
 }
 
 // ---------------------------------------------------------------------------
@@ -146,6 +589,11 @@ impl Default for SyncConfig {
                 .join("OneDrive"),
             poll_interval: 30,
             debounce_delay: 2,
+            startup_reconciliation: true,
+            quota_refresh_interval: default_quota_refresh_interval(),
+            exclude_hidden: false,
+            exclude_junk: true,
+            normalize_unicode: true,
         }
     }
 }
@@ -172,10 +620,22 @@ impl Default for LargeFilesConfig {
     }
 }
 
+impl Default for LimitsConfig {
+    fn default() -> Self {
+        Self {
+            max_file_size: default_max_file_size(),
+            max_path_length: default_max_path_length(),
+        }
+    }
+}
+
 impl Default for ConflictsConfig {
     fn default() -> Self {
         Self {
             default_strategy: "manual".to_string(),
+            rules: Vec::new(),
+            quarantine_after_days: 0,
+            change_detection: default_change_detection(),
         }
     }
 }
@@ -187,6 +647,7 @@ impl Default for LoggingConfig {
             .join("lnxdrive");
         Self {
             level: "info".to_string(),
+            format: default_log_format(),
             file: data_dir.join("lnxdrive.log"),
             max_size_mb: 50,
             max_files: 5,
@@ -203,11 +664,84 @@ impl Default for FuseConfig {
             mount_point: "~/OneDrive".to_string(),
             auto_mount: true,
             cache_dir: "~/.local/share/lnxdrive/cache".to_string(),
+            temp_dir: None,
             cache_max_size_gb: 10,
+            cache_shard_depth: default_cache_shard_depth(),
+            cache_dedup: false,
+            cache_scrub_full_hash: false,
             dehydration_threshold_percent: 80,
             dehydration_max_age_days: 30,
+            dehydration_unused_days: 0,
+            dehydration_large_file_mb: 0,
             dehydration_interval_minutes: 60,
+            dehydration_busy_files: default_dehydration_busy_files(),
             hydration_concurrency: 8,
+            hydration_chunk_size_mb: default_hydration_chunk_size_mb(),
+            streaming_threshold_mb: default_streaming_threshold_mb(),
+            read_ahead_mb: default_read_ahead_mb(),
+            max_cached_file_mb: 0,
+            oversized_files: default_oversized_files(),
+            preserve_permissions: false,
+            file_mode: default_file_mode(),
+            directory_mode: default_directory_mode(),
+            max_inodes: default_max_inodes(),
+            prefetch_recent_files: default_prefetch_recent_files(),
+            prefetch_recent_mb: default_prefetch_recent_mb(),
+        }
+    }
+}
+
+impl Default for DaemonConfig {
+    fn default() -> Self {
+        Self {
+            systemd_notify: true,
+            shutdown_timeout: default_shutdown_timeout(),
+        }
+    }
+}
+
+impl Default for HttpConfig {
+    fn default() -> Self {
+        Self {
+            send_user_agent: true,
+            user_agent: None,
+            connect_timeout: default_http_connect_timeout(),
+            read_timeout: default_http_read_timeout(),
+            request_timeout: default_http_request_timeout(),
+            min_transfer_rate_kbps: default_http_min_transfer_rate_kbps(),
+        }
+    }
+}
+
+impl Default for DeltaConfig {
+    fn default() -> Self {
+        Self {
+            batch_size: default_delta_batch_size(),
+            max_in_flight_items: default_delta_max_in_flight_items(),
+            transaction_size: default_delta_transaction_size(),
+        }
+    }
+}
+
+impl Default for ChangeNotificationsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            notification_url: None,
+            listen_address: default_change_notifications_listen_address(),
+            expiration_minutes: default_change_notifications_expiration_minutes(),
+            poll_interval: default_change_notifications_poll_interval(),
+        }
+    }
+}
+
+impl Default for MetricsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            listen_address: default_metrics_listen_address(),
+            ready_max_sync_age: default_ready_max_sync_age(),
+            ready_db_timeout_ms: default_ready_db_timeout_ms(),
         }
     }
 }
@@ -234,8 +768,27 @@ impl std::fmt::Display for ValidationError {
 /// Valid values for `logging.level`.
 const VALID_LOG_LEVELS: &[&str] = &["trace", "debug", "info", "warn", "error"];
 
+/// Valid values for `logging.format`.
+const VALID_LOG_FORMATS: &[&str] = &["text", "json"];
+
+/// Valid values for `fuse.dehydration_busy_files`.
+const VALID_BUSY_FILE_ACTIONS: &[&str] = &["skip", "defer"];
+
+/// Valid values for `fuse.oversized_files`.
+const VALID_OVERSIZED_FILE_ACTIONS: &[&str] = &["reject", "stream"];
+
+/// Valid values for `conflicts.change_detection`.
+const VALID_CHANGE_DETECTION_MODES: &[&str] = &["quick", "full_hash"];
+
 /// Valid values for `conflicts.default_strategy`.
-const VALID_CONFLICT_STRATEGIES: &[&str] = &["manual", "keep_local", "keep_remote", "keep_both"];
+const VALID_CONFLICT_STRATEGIES: &[&str] = &[
+    "manual",
+    "keep_local",
+    "keep_remote",
+    "prefer_remote",
+    "keep_both",
+    "keep_newer",
+];
 
 impl Config {
     /// Validate the configuration and return all errors found.
@@ -328,6 +881,20 @@ impl Config {
             });
         }
 
+        // --- limits ---
+        if self.limits.max_file_size == 0 {
+            errors.push(ValidationError {
+                field: "limits.max_file_size".into(),
+                message: "must be greater than 0".into(),
+            });
+        }
+        if self.limits.max_path_length == 0 {
+            errors.push(ValidationError {
+                field: "limits.max_path_length".into(),
+                message: "must be greater than 0".into(),
+            });
+        }
+
         // --- conflicts ---
         if !VALID_CONFLICT_STRATEGIES.contains(&self.conflicts.default_strategy.as_str()) {
             errors.push(ValidationError {
@@ -339,6 +906,34 @@ impl Config {
                 ),
             });
         }
+        if !VALID_CHANGE_DETECTION_MODES.contains(&self.conflicts.change_detection.as_str()) {
+            errors.push(ValidationError {
+                field: "conflicts.change_detection".into(),
+                message: format!(
+                    "invalid mode '{}'; valid options: {}",
+                    self.conflicts.change_detection,
+                    VALID_CHANGE_DETECTION_MODES.join(", ")
+                ),
+            });
+        }
+        for (i, rule) in self.conflicts.rules.iter().enumerate() {
+            if let Err(err) = GlobPattern::new(rule.pattern.as_str()) {
+                errors.push(ValidationError {
+                    field: format!("conflicts.rules[{}].pattern", i),
+                    message: err.to_string(),
+                });
+            }
+            if !VALID_CONFLICT_STRATEGIES.contains(&rule.strategy.as_str()) {
+                errors.push(ValidationError {
+                    field: format!("conflicts.rules[{}].strategy", i),
+                    message: format!(
+                        "invalid strategy '{}'; valid options: {}",
+                        rule.strategy,
+                        VALID_CONFLICT_STRATEGIES.join(", ")
+                    ),
+                });
+            }
+        }
 
         // --- logging ---
         if !VALID_LOG_LEVELS.contains(&self.logging.level.as_str()) {
@@ -351,6 +946,16 @@ impl Config {
                 ),
             });
         }
+        if !VALID_LOG_FORMATS.contains(&self.logging.format.as_str()) {
+            errors.push(ValidationError {
+                field: "logging.format".into(),
+                message: format!(
+                    "invalid format '{}'; valid options: {}",
+                    self.logging.format,
+                    VALID_LOG_FORMATS.join(", ")
+                ),
+            });
+        }
         if self.logging.max_size_mb == 0 {
             errors.push(ValidationError {
                 field: "logging.max_size_mb".into(),
@@ -371,6 +976,12 @@ impl Config {
                 message: "must be greater than 0".into(),
             });
         }
+        if self.fuse.cache_shard_depth == 0 || self.fuse.cache_shard_depth > 4 {
+            errors.push(ValidationError {
+                field: "fuse.cache_shard_depth".into(),
+                message: "must be in range 1..=4".into(),
+            });
+        }
         if self.fuse.dehydration_threshold_percent == 0
             || self.fuse.dehydration_threshold_percent > 100
         {
@@ -391,11 +1002,435 @@ impl Config {
                 message: "must be greater than 0".into(),
             });
         }
+        if !VALID_BUSY_FILE_ACTIONS.contains(&self.fuse.dehydration_busy_files.as_str()) {
+            errors.push(ValidationError {
+                field: "fuse.dehydration_busy_files".into(),
+                message: format!(
+                    "invalid action '{}'; valid options: {}",
+                    self.fuse.dehydration_busy_files,
+                    VALID_BUSY_FILE_ACTIONS.join(", ")
+                ),
+            });
+        }
+        if self.fuse.hydration_chunk_size_mb == 0 || self.fuse.hydration_chunk_size_mb > 1024 {
+            errors.push(ValidationError {
+                field: "fuse.hydration_chunk_size_mb".into(),
+                message: "must be in range 1..=1024".into(),
+            });
+        }
+        if !VALID_OVERSIZED_FILE_ACTIONS.contains(&self.fuse.oversized_files.as_str()) {
+            errors.push(ValidationError {
+                field: "fuse.oversized_files".into(),
+                message: format!(
+                    "invalid action '{}'; valid options: {}",
+                    self.fuse.oversized_files,
+                    VALID_OVERSIZED_FILE_ACTIONS.join(", ")
+                ),
+            });
+        }
+        // The owner must keep access, or the sync engine could not read
+        // files or enter directories
+        match parse_mode(&self.fuse.file_mode) {
+            Some(mode) if mode & 0o600 == 0o600 => {}
+            _ => errors.push(ValidationError {
+                field: "fuse.file_mode".into(),
+                message: format!(
+                    "invalid mode '{}'; expected octal permissions up to 777 that let \
+                     the owner read and write (e.g. 644 or 600)",
+                    self.fuse.file_mode
+                ),
+            }),
+        }
+        match parse_mode(&self.fuse.directory_mode) {
+            Some(mode) if mode & 0o700 == 0o700 => {}
+            _ => errors.push(ValidationError {
+                field: "fuse.directory_mode".into(),
+                message: format!(
+                    "invalid mode '{}'; expected octal permissions up to 777 that give \
+                     the owner full access (e.g. 755 or 700)",
+                    self.fuse.directory_mode
+                ),
+            }),
+        }
+
+        // --- metrics ---
+        if self
+            .metrics
+            .listen_address
+            .parse::<std::net::SocketAddr>()
+            .is_err()
+        {
+            errors.push(ValidationError {
+                field: "metrics.listen_address".into(),
+                message: format!(
+                    "invalid address '{}'; expected host:port",
+                    self.metrics.listen_address
+                ),
+            });
+        }
+        if self.metrics.ready_max_sync_age == 0 {
+            errors.push(ValidationError {
+                field: "metrics.ready_max_sync_age".into(),
+                message: "must be greater than 0".into(),
+            });
+        }
+        if self.metrics.ready_db_timeout_ms == 0 {
+            errors.push(ValidationError {
+                field: "metrics.ready_db_timeout_ms".into(),
+                message: "must be greater than 0".into(),
+            });
+        }
+
+        // --- http ---
+        for (field, value) in [
+            ("http.connect_timeout", self.http.connect_timeout),
+            ("http.read_timeout", self.http.read_timeout),
+            ("http.request_timeout", self.http.request_timeout),
+            (
+                "http.min_transfer_rate_kbps",
+                self.http.min_transfer_rate_kbps,
+            ),
+        ] {
+            if value == 0 {
+                errors.push(ValidationError {
+                    field: field.into(),
+                    message: "must be greater than 0".into(),
+                });
+            }
+        }
+
+        // --- delta ---
+        if self.delta.batch_size == 0 || self.delta.batch_size > 1000 {
+            errors.push(ValidationError {
+                field: "delta.batch_size".into(),
+                message: "must be in range 1..=1000".into(),
+            });
+        }
+        if self.delta.max_in_flight_items == 0 {
+            errors.push(ValidationError {
+                field: "delta.max_in_flight_items".into(),
+                message: "must be greater than 0".into(),
+            });
+        }
+        if self.delta.transaction_size == 0 {
+            errors.push(ValidationError {
+                field: "delta.transaction_size".into(),
+                message: "must be greater than 0".into(),
+            });
+        }
+
+        // --- change_notifications ---
+        let notifications = &self.change_notifications;
+        if notifications.enabled
+            && !notifications
+                .notification_url
+                .as_deref()
+                .is_some_and(|url| url.starts_with("https://"))
+        {
+            errors.push(ValidationError {
+                field: "change_notifications.notification_url".into(),
+                message: "an https:// URL is required when change notifications are enabled".into(),
+            });
+        }
+        if notifications
+            .listen_address
+            .parse::<std::net::SocketAddr>()
+            .is_err()
+        {
+            errors.push(ValidationError {
+                field: "change_notifications.listen_address".into(),
+                message: format!(
+                    "invalid address '{}'; expected host:port",
+                    notifications.listen_address
+                ),
+            });
+        }
+        if !(45..=42300).contains(&notifications.expiration_minutes) {
+            errors.push(ValidationError {
+                field: "change_notifications.expiration_minutes".into(),
+                message: "must be in range 45..=42300".into(),
+            });
+        }
+        if notifications.poll_interval == 0 {
+            errors.push(ValidationError {
+                field: "change_notifications.poll_interval".into(),
+                message: "must be greater than 0".into(),
+            });
+        }
+
+        // --- accounts ---
+        for alias in self.accounts.aliases.keys() {
+            if alias.is_empty() || alias.contains('@') || alias.contains(char::is_whitespace) {
+                errors.push(ValidationError {
+                    field: "accounts.aliases".into(),
+                    message: format!(
+                        "invalid alias '{}'; aliases must be non-empty and contain no '@' or spaces",
+                        alias
+                    ),
+                });
+            }
+        }
+
+        errors
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Account references
+// ---------------------------------------------------------------------------
+
+/// Why an account reference did not pick exactly one account.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum AccountRefError {
+    /// No account is logged in.
+    #[error("no account is logged in; run 'lnxdrive auth login' first")]
+    NoAccounts,
+    /// The reference matches no email, alias or account id.
+    #[error("no account matches '{reference}' (available: {})", .choices.join(", "))]
+    Unknown {
+        reference: String,
+        choices: Vec<String>,
+    },
+    /// Several accounts are logged in and none was chosen.
+    #[error(
+        "several accounts are logged in; choose one with --account or \
+         'lnxdrive account use' (available: {})",
+        .choices.join(", ")
+    )]
+    Ambiguous { choices: Vec<String> },
+}
+
+impl AccountsConfig {
+    /// Picks the account named by `reference`, an email, alias or account id.
+    ///
+    /// Without a reference, the configured default account is used, or the
+    /// only account if just one is logged in.
+    pub fn resolve<'a>(
+        &self,
+        accounts: &'a [Account],
+        reference: Option<&str>,
+    ) -> Result<&'a Account, AccountRefError> {
+        if accounts.is_empty() {
+            return Err(AccountRefError::NoAccounts);
+        }
+
+        let reference = reference.or(self.default.as_deref());
+        match reference {
+            Some(reference) => {
+                self.find(accounts, reference)
+                    .ok_or_else(|| AccountRefError::Unknown {
+                        reference: reference.to_string(),
+                        choices: self.choices(accounts),
+                    })
+            }
+            None if accounts.len() == 1 => Ok(&accounts[0]),
+            None => Err(AccountRefError::Ambiguous {
+                choices: self.choices(accounts),
+            }),
+        }
+    }
+
+    /// Returns the aliases pointing at `email`, in alphabetical order.
+    pub fn aliases_of(&self, email: &str) -> Vec<&str> {
+        self.aliases
+            .iter()
+            .filter(|(_, target)| target.eq_ignore_ascii_case(email))
+            .map(|(alias, _)| alias.as_str())
+            .collect()
+    }
+
+    /// Finds the account whose alias, email or id is `reference`.
+    fn find<'a>(&self, accounts: &'a [Account], reference: &str) -> Option<&'a Account> {
+        let email = self
+            .aliases
+            .get(reference)
+            .map_or(reference, String::as_str);
+        accounts
+            .iter()
+            .find(|account| account.email().as_str().eq_ignore_ascii_case(email))
+            .or_else(|| {
+                accounts
+                    .iter()
+                    .find(|account| account.id().to_string() == reference)
+            })
+    }
+
+    /// Describes each account by email and aliases, for error messages.
+    fn choices(&self, accounts: &[Account]) -> Vec<String> {
+        accounts
+            .iter()
+            .map(|account| {
+                let email = account.email().as_str();
+                match self.aliases_of(email).as_slice() {
+                    [] => email.to_string(),
+                    aliases => format!("{} ({})", email, aliases.join(", ")),
+                }
+            })
+            .collect()
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Directory layout validation
+// ---------------------------------------------------------------------------
+
+/// Expand a leading `~` to the user's home directory.
+pub fn expand_tilde(path: &Path) -> PathBuf {
+    if let Ok(rest) = path.strip_prefix("~") {
+        if let Some(home) = dirs::home_dir() {
+            return home.join(rest);
+        }
+    }
+    path.to_path_buf()
+}
+
+/// Resolve symlinks and `..` in `path`, even if its tail does not exist yet.
+///
+/// The longest existing ancestor is canonicalized and the missing
+/// components are appended to it.
+fn resolve_path(path: &Path) -> PathBuf {
+    let path = expand_tilde(path);
+    let mut existing = path.as_path();
+    let mut missing = Vec::new();
+    loop {
+        if let Ok(canonical) = existing.canonicalize() {
+            return missing
+                .iter()
+                .rev()
+                .fold(canonical, |acc, name| acc.join(name));
+        }
+        match (existing.parent(), existing.file_name()) {
+            (Some(parent), Some(name)) => {
+                missing.push(name.to_os_string());
+                existing = parent;
+            }
+            _ => return path,
+        }
+    }
+}
+
+impl Config {
+    /// Check that the sync root, FUSE mount point and cache directory do
+    /// not contain one another.
+    ///
+    /// Syncing or caching a directory that contains the sync root, the
+    /// mount or the cache makes LNXDrive process its own output in a loop.
+    /// The mount point may be the sync root itself (Files-on-Demand serves
+    /// the sync root); the cache must be separate from both.
+    ///
+    /// `sync_root` is the root of the account being synced, which can
+    /// differ from `sync.root` if the config changed after login.
+    pub fn validate_layout(&self, sync_root: &Path) -> Vec<ValidationError> {
+        let sync_root = resolve_path(sync_root);
+        let mount_point = resolve_path(Path::new(&self.fuse.mount_point));
+        let cache_dir = resolve_path(Path::new(&self.fuse.cache_dir));
+
+        let mut errors = Vec::new();
+        let mut nested = |field: &str, inner: (&str, &PathBuf), outer: (&str, &PathBuf)| {
+            let message = if inner.1 == outer.1 {
+                format!(
+                    "{} must not be the same directory as {} ({})",
+                    inner.0,
+                    outer.0,
+                    inner.1.display()
+                )
+            } else if inner.1.starts_with(outer.1) {
+                format!(
+                    "{} must not be inside {} ({} is inside {})",
+                    inner.0,
+                    outer.0,
+                    inner.1.display(),
+                    outer.1.display()
+                )
+            } else {
+                return;
+            };
+            errors.push(ValidationError {
+                field: field.into(),
+                message,
+            });
+        };
+
+        let root = ("sync_root", &sync_root);
+        let mount = ("mount_point", &mount_point);
+        let cache = ("cache_dir", &cache_dir);
+        if mount_point != sync_root {
+            nested("fuse.mount_point", mount, root);
+            nested("fuse.mount_point", root, mount);
+        }
+        nested("fuse.cache_dir", cache, root);
+        nested("fuse.cache_dir", root, cache);
+        if cache_dir != sync_root {
+            nested("fuse.cache_dir", cache, mount);
+            nested("fuse.cache_dir", mount, cache);
+        }
 
         errors
     }
 }
 
+/// Check that the sync root exists, is a directory and is writable.
+///
+/// Writability is probed by creating and removing a hidden file, since
+/// permission bits alone miss read-only mounts and ACLs.
+pub fn check_sync_root(sync_root: &Path) -> Result<(), ValidationError> {
+    let sync_root = expand_tilde(sync_root);
+    let fail = |message: String| ValidationError {
+        field: "sync.root".into(),
+        message,
+    };
+
+    match std::fs::metadata(&sync_root) {
+        Ok(meta) if meta.is_dir() => {}
+        Ok(_) => return Err(fail(format!("not a directory: {}", sync_root.display()))),
+        Err(_) => {
+            return Err(fail(format!(
+                "directory does not exist: {}",
+                sync_root.display()
+            )))
+        }
+    }
+
+    probe_writable(&sync_root).map_err(|e| {
+        fail(format!(
+            "directory is not writable: {} ({})",
+            sync_root.display(),
+            e
+        ))
+    })
+}
+
+/// Check that the temporary directory can be created and written to.
+pub fn check_temp_dir(temp_dir: &Path) -> Result<(), ValidationError> {
+    check_writable_dir("fuse.temp_dir", temp_dir)
+}
+
+/// Check that the cache directory can be created and written to.
+pub fn check_cache_dir(cache_dir: &Path) -> Result<(), ValidationError> {
+    check_writable_dir("fuse.cache_dir", cache_dir)
+}
+
+/// Creates `dir` if missing and probes it, reporting failures as `field`.
+fn check_writable_dir(field: &str, dir: &Path) -> Result<(), ValidationError> {
+    std::fs::create_dir_all(dir)
+        .and_then(|()| probe_writable(dir))
+        .map_err(|e| ValidationError {
+            field: field.into(),
+            message: format!("directory is not writable: {} ({})", dir.display(), e),
+        })
+}
+
+/// Creates and removes a hidden file in `dir`.
+fn probe_writable(dir: &Path) -> std::io::Result<()> {
+    let probe = dir.join(format!(".lnxdrive-write-check-{}", std::process::id()));
+    std::fs::OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(&probe)?;
+    let _ = std::fs::remove_file(&probe);
+    Ok(())
+}
+
 // ---------------------------------------------------------------------------
 // T103: ConfigBuilder
 // ---------------------------------------------------------------------------
@@ -421,150 +1456,414 @@ pub struct ConfigBuilder {
     config: Config,
 }
 
-impl ConfigBuilder {
-    /// Create a new builder initialised with [`Config::default`] values.
-    pub fn new() -> Self {
-        Self {
-            config: Config::default(),
-        }
+impl ConfigBuilder {
+    /// Create a new builder initialised with [`Config::default`] values.
+    pub fn new() -> Self {
+        Self {
+            config: Config::default(),
+        }
+    }
+
+    // --- sync ---
+
+    pub fn sync_root(mut self, root: PathBuf) -> Self {
+        self.config.sync.root = root;
+        self
+    }
+
+    pub fn sync_poll_interval(mut self, seconds: u64) -> Self {
+        self.config.sync.poll_interval = seconds;
+        self
+    }
+
+    pub fn sync_debounce_delay(mut self, seconds: u64) -> Self {
+        self.config.sync.debounce_delay = seconds;
+        self
+    }
+
+    pub fn sync_startup_reconciliation(mut self, enabled: bool) -> Self {
+        self.config.sync.startup_reconciliation = enabled;
+        self
+    }
+
+    pub fn sync_quota_refresh_interval(mut self, seconds: u64) -> Self {
+        self.config.sync.quota_refresh_interval = seconds;
+        self
+    }
+
+    pub fn sync_exclude_hidden(mut self, enabled: bool) -> Self {
+        self.config.sync.exclude_hidden = enabled;
+        self
+    }
+
+    pub fn sync_exclude_junk(mut self, enabled: bool) -> Self {
+        self.config.sync.exclude_junk = enabled;
+        self
+    }
+
+    pub fn sync_normalize_unicode(mut self, enabled: bool) -> Self {
+        self.config.sync.normalize_unicode = enabled;
+        self
+    }
+
+    // --- rate_limiting ---
+
+    pub fn rate_limiting_delta_requests_per_minute(mut self, n: u32) -> Self {
+        self.config.rate_limiting.delta_requests_per_minute = n;
+        self
+    }
+
+    pub fn rate_limiting_upload_concurrent(mut self, n: u32) -> Self {
+        self.config.rate_limiting.upload_concurrent = n;
+        self
+    }
+
+    pub fn rate_limiting_upload_requests_per_minute(mut self, n: u32) -> Self {
+        self.config.rate_limiting.upload_requests_per_minute = n;
+        self
+    }
+
+    pub fn rate_limiting_download_concurrent(mut self, n: u32) -> Self {
+        self.config.rate_limiting.download_concurrent = n;
+        self
+    }
+
+    pub fn rate_limiting_metadata_requests_per_minute(mut self, n: u32) -> Self {
+        self.config.rate_limiting.metadata_requests_per_minute = n;
+        self
+    }
+
+    // --- large_files ---
+
+    pub fn large_files_threshold_mb(mut self, mb: u64) -> Self {
+        self.config.large_files.threshold_mb = mb;
+        self
+    }
+
+    pub fn large_files_chunk_size_mb(mut self, mb: u64) -> Self {
+        self.config.large_files.chunk_size_mb = mb;
+        self
+    }
+
+    pub fn large_files_max_concurrent_large(mut self, n: u32) -> Self {
+        self.config.large_files.max_concurrent_large = n;
+        self
+    }
+
+    // --- limits ---
+
+    pub fn limits_max_file_size(mut self, bytes: u64) -> Self {
+        self.config.limits.max_file_size = bytes;
+        self
+    }
+
+    pub fn limits_max_path_length(mut self, chars: usize) -> Self {
+        self.config.limits.max_path_length = chars;
+        self
+    }
+
+    // --- conflicts ---
+
+    pub fn conflicts_default_strategy(mut self, strategy: impl Into<String>) -> Self {
+        self.config.conflicts.default_strategy = strategy.into();
+        self
+    }
+
+    pub fn conflicts_rule(
+        mut self,
+        pattern: impl Into<String>,
+        strategy: impl Into<String>,
+    ) -> Self {
+        self.config.conflicts.rules.push(ConflictRule {
+            pattern: pattern.into(),
+            strategy: strategy.into(),
+        });
+        self
+    }
+
+    pub fn conflicts_quarantine_after_days(mut self, days: u32) -> Self {
+        self.config.conflicts.quarantine_after_days = days;
+        self
+    }
+
+    pub fn conflicts_change_detection(mut self, mode: impl Into<String>) -> Self {
+        self.config.conflicts.change_detection = mode.into();
+        self
+    }
+
+    // --- logging ---
+
+    pub fn logging_level(mut self, level: impl Into<String>) -> Self {
+        self.config.logging.level = level.into();
+        self
+    }
+
+    pub fn logging_format(mut self, format: impl Into<String>) -> Self {
+        self.config.logging.format = format.into();
+        self
+    }
+
+    pub fn logging_file(mut self, file: PathBuf) -> Self {
+        self.config.logging.file = file;
+        self
+    }
+
+    pub fn logging_max_size_mb(mut self, mb: u64) -> Self {
+        self.config.logging.max_size_mb = mb;
+        self
+    }
+
+    pub fn logging_max_files(mut self, n: u32) -> Self {
+        self.config.logging.max_files = n;
+        self
+    }
+
+    // --- auth ---
+
+    pub fn auth_app_id(mut self, app_id: impl Into<String>) -> Self {
+        self.config.auth.app_id = Some(app_id.into());
+        self
+    }
+
+    // --- fuse ---
+
+    pub fn fuse_mount_point(mut self, mount_point: impl Into<String>) -> Self {
+        self.config.fuse.mount_point = mount_point.into();
+        self
+    }
+
+    pub fn fuse_auto_mount(mut self, auto_mount: bool) -> Self {
+        self.config.fuse.auto_mount = auto_mount;
+        self
+    }
+
+    pub fn fuse_cache_dir(mut self, cache_dir: impl Into<String>) -> Self {
+        self.config.fuse.cache_dir = cache_dir.into();
+        self
+    }
+
+    pub fn fuse_temp_dir(mut self, temp_dir: impl Into<String>) -> Self {
+        self.config.fuse.temp_dir = Some(temp_dir.into());
+        self
+    }
+
+    pub fn fuse_cache_max_size_gb(mut self, gb: u32) -> Self {
+        self.config.fuse.cache_max_size_gb = gb;
+        self
+    }
+
+    pub fn fuse_dehydration_threshold_percent(mut self, percent: u8) -> Self {
+        self.config.fuse.dehydration_threshold_percent = percent;
+        self
+    }
+
+    pub fn fuse_dehydration_max_age_days(mut self, days: u32) -> Self {
+        self.config.fuse.dehydration_max_age_days = days;
+        self
+    }
+
+    pub fn fuse_dehydration_unused_days(mut self, days: u32) -> Self {
+        self.config.fuse.dehydration_unused_days = days;
+        self
+    }
+
+    pub fn fuse_dehydration_large_file_mb(mut self, mb: u64) -> Self {
+        self.config.fuse.dehydration_large_file_mb = mb;
+        self
+    }
+
+    pub fn fuse_dehydration_interval_minutes(mut self, minutes: u32) -> Self {
+        self.config.fuse.dehydration_interval_minutes = minutes;
+        self
+    }
+
+    pub fn fuse_dehydration_busy_files(mut self, action: impl Into<String>) -> Self {
+        self.config.fuse.dehydration_busy_files = action.into();
+        self
     }
 
-    // --- sync ---
+    pub fn fuse_hydration_concurrency(mut self, concurrency: u8) -> Self {
+        self.config.fuse.hydration_concurrency = concurrency;
+        self
+    }
 
-    pub fn sync_root(mut self, root: PathBuf) -> Self {
-        self.config.sync.root = root;
+    pub fn fuse_cache_shard_depth(mut self, depth: u8) -> Self {
+        self.config.fuse.cache_shard_depth = depth;
         self
     }
 
-    pub fn sync_poll_interval(mut self, seconds: u64) -> Self {
-        self.config.sync.poll_interval = seconds;
+    pub fn fuse_cache_dedup(mut self, enabled: bool) -> Self {
+        self.config.fuse.cache_dedup = enabled;
         self
     }
 
-    pub fn sync_debounce_delay(mut self, seconds: u64) -> Self {
-        self.config.sync.debounce_delay = seconds;
+    pub fn fuse_cache_scrub_full_hash(mut self, enabled: bool) -> Self {
+        self.config.fuse.cache_scrub_full_hash = enabled;
         self
     }
 
-    // --- rate_limiting ---
+    pub fn fuse_hydration_chunk_size_mb(mut self, mb: u32) -> Self {
+        self.config.fuse.hydration_chunk_size_mb = mb;
+        self
+    }
 
-    pub fn rate_limiting_delta_requests_per_minute(mut self, n: u32) -> Self {
-        self.config.rate_limiting.delta_requests_per_minute = n;
+    pub fn fuse_streaming_threshold_mb(mut self, mb: u64) -> Self {
+        self.config.fuse.streaming_threshold_mb = mb;
         self
     }
 
-    pub fn rate_limiting_upload_concurrent(mut self, n: u32) -> Self {
-        self.config.rate_limiting.upload_concurrent = n;
+    pub fn fuse_read_ahead_mb(mut self, mb: u64) -> Self {
+        self.config.fuse.read_ahead_mb = mb;
         self
     }
 
-    pub fn rate_limiting_upload_requests_per_minute(mut self, n: u32) -> Self {
-        self.config.rate_limiting.upload_requests_per_minute = n;
+    pub fn fuse_max_cached_file_mb(mut self, mb: u64) -> Self {
+        self.config.fuse.max_cached_file_mb = mb;
         self
     }
 
-    pub fn rate_limiting_download_concurrent(mut self, n: u32) -> Self {
-        self.config.rate_limiting.download_concurrent = n;
+    pub fn fuse_oversized_files(mut self, action: impl Into<String>) -> Self {
+        self.config.fuse.oversized_files = action.into();
         self
     }
 
-    pub fn rate_limiting_metadata_requests_per_minute(mut self, n: u32) -> Self {
-        self.config.rate_limiting.metadata_requests_per_minute = n;
+    pub fn fuse_preserve_permissions(mut self, enabled: bool) -> Self {
+        self.config.fuse.preserve_permissions = enabled;
         self
     }
 
-    // --- large_files ---
+    pub fn fuse_file_mode(mut self, mode: impl Into<String>) -> Self {
+        self.config.fuse.file_mode = mode.into();
+        self
+    }
 
-    pub fn large_files_threshold_mb(mut self, mb: u64) -> Self {
-        self.config.large_files.threshold_mb = mb;
+    pub fn fuse_directory_mode(mut self, mode: impl Into<String>) -> Self {
+        self.config.fuse.directory_mode = mode.into();
         self
     }
 
-    pub fn large_files_chunk_size_mb(mut self, mb: u64) -> Self {
-        self.config.large_files.chunk_size_mb = mb;
+    pub fn fuse_max_inodes(mut self, max: u64) -> Self {
+        self.config.fuse.max_inodes = max;
         self
     }
 
-    pub fn large_files_max_concurrent_large(mut self, n: u32) -> Self {
-        self.config.large_files.max_concurrent_large = n;
+    pub fn fuse_prefetch_recent_files(mut self, count: u32) -> Self {
+        self.config.fuse.prefetch_recent_files = count;
         self
     }
 
-    // --- conflicts ---
+    pub fn fuse_prefetch_recent_mb(mut self, mb: u64) -> Self {
+        self.config.fuse.prefetch_recent_mb = mb;
+        self
+    }
 
-    pub fn conflicts_default_strategy(mut self, strategy: impl Into<String>) -> Self {
-        self.config.conflicts.default_strategy = strategy.into();
+    // --- daemon ---
+
+    pub fn daemon_systemd_notify(mut self, enabled: bool) -> Self {
+        self.config.daemon.systemd_notify = enabled;
         self
     }
 
-    // --- logging ---
+    pub fn daemon_shutdown_timeout(mut self, secs: u64) -> Self {
+        self.config.daemon.shutdown_timeout = secs;
+        self
+    }
 
-    pub fn logging_level(mut self, level: impl Into<String>) -> Self {
-        self.config.logging.level = level.into();
+    // --- metrics ---
+
+    pub fn metrics_enabled(mut self, enabled: bool) -> Self {
+        self.config.metrics.enabled = enabled;
         self
     }
 
-    pub fn logging_file(mut self, file: PathBuf) -> Self {
-        self.config.logging.file = file;
+    pub fn metrics_listen_address(mut self, address: impl Into<String>) -> Self {
+        self.config.metrics.listen_address = address.into();
         self
     }
 
-    pub fn logging_max_size_mb(mut self, mb: u64) -> Self {
-        self.config.logging.max_size_mb = mb;
+    pub fn metrics_ready_max_sync_age(mut self, secs: u64) -> Self {
+        self.config.metrics.ready_max_sync_age = secs;
         self
     }
 
-    pub fn logging_max_files(mut self, n: u32) -> Self {
-        self.config.logging.max_files = n;
+    pub fn metrics_ready_db_timeout_ms(mut self, ms: u64) -> Self {
+        self.config.metrics.ready_db_timeout_ms = ms;
         self
     }
 
-    // --- auth ---
+    // --- http ---
 
-    pub fn auth_app_id(mut self, app_id: impl Into<String>) -> Self {
-        self.config.auth.app_id = Some(app_id.into());
+    pub fn http_send_user_agent(mut self, enabled: bool) -> Self {
+        self.config.http.send_user_agent = enabled;
         self
     }
 
-    // --- fuse ---
+    pub fn http_user_agent(mut self, user_agent: impl Into<String>) -> Self {
+        self.config.http.user_agent = Some(user_agent.into());
+        self
+    }
 
-    pub fn fuse_mount_point(mut self, mount_point: impl Into<String>) -> Self {
-        self.config.fuse.mount_point = mount_point.into();
+    pub fn http_connect_timeout(mut self, secs: u64) -> Self {
+        self.config.http.connect_timeout = secs;
         self
     }
 
-    pub fn fuse_auto_mount(mut self, auto_mount: bool) -> Self {
-        self.config.fuse.auto_mount = auto_mount;
+    pub fn http_read_timeout(mut self, secs: u64) -> Self {
+        self.config.http.read_timeout = secs;
         self
     }
 
-    pub fn fuse_cache_dir(mut self, cache_dir: impl Into<String>) -> Self {
-        self.config.fuse.cache_dir = cache_dir.into();
+    pub fn http_request_timeout(mut self, secs: u64) -> Self {
+        self.config.http.request_timeout = secs;
         self
     }
 
-    pub fn fuse_cache_max_size_gb(mut self, gb: u32) -> Self {
-        self.config.fuse.cache_max_size_gb = gb;
+    pub fn http_min_transfer_rate_kbps(mut self, rate: u64) -> Self {
+        self.config.http.min_transfer_rate_kbps = rate;
         self
     }
 
-    pub fn fuse_dehydration_threshold_percent(mut self, percent: u8) -> Self {
-        self.config.fuse.dehydration_threshold_percent = percent;
+    // --- delta ---
+
+    pub fn delta_batch_size(mut self, size: u32) -> Self {
+        self.config.delta.batch_size = size;
         self
     }
 
-    pub fn fuse_dehydration_max_age_days(mut self, days: u32) -> Self {
-        self.config.fuse.dehydration_max_age_days = days;
+    pub fn delta_max_in_flight_items(mut self, max: usize) -> Self {
+        self.config.delta.max_in_flight_items = max;
         self
     }
 
-    pub fn fuse_dehydration_interval_minutes(mut self, minutes: u32) -> Self {
-        self.config.fuse.dehydration_interval_minutes = minutes;
+    pub fn delta_transaction_size(mut self, size: usize) -> Self {
+        self.config.delta.transaction_size = size;
         self
     }
 
-    pub fn fuse_hydration_concurrency(mut self, concurrency: u8) -> Self {
-        self.config.fuse.hydration_concurrency = concurrency;
+    // --- change_notifications ---
+
+    pub fn change_notifications_enabled(mut self, enabled: bool) -> Self {
+        self.config.change_notifications.enabled = enabled;
+        self
+    }
+
+    pub fn change_notifications_url(mut self, url: impl Into<String>) -> Self {
+        self.config.change_notifications.notification_url = Some(url.into());
+        self
+    }
+
+    pub fn change_notifications_listen_address(mut self, address: impl Into<String>) -> Self {
+        self.config.change_notifications.listen_address = address.into();
+        self
+    }
+
+    pub fn change_notifications_expiration_minutes(mut self, minutes: u32) -> Self {
+        self.config.change_notifications.expiration_minutes = minutes;
+        self
+    }
+
+    pub fn change_notifications_poll_interval(mut self, secs: u64) -> Self {
+        self.config.change_notifications.poll_interval = secs;
         self
     }
 
@@ -611,6 +1910,10 @@ mod tests {
         let cfg = Config::default();
         assert_eq!(cfg.sync.poll_interval, 30);
         assert_eq!(cfg.sync.debounce_delay, 2);
+        assert_eq!(cfg.sync.quota_refresh_interval, 900);
+        assert!(!cfg.sync.exclude_hidden);
+        assert!(cfg.sync.exclude_junk);
+        assert!(cfg.sync.normalize_unicode);
         assert!(cfg.sync.root.to_string_lossy().contains("OneDrive"));
         assert_eq!(cfg.rate_limiting.delta_requests_per_minute, 10);
         assert_eq!(cfg.rate_limiting.upload_concurrent, 4);
@@ -620,19 +1923,61 @@ mod tests {
         assert_eq!(cfg.large_files.threshold_mb, 100);
         assert_eq!(cfg.large_files.chunk_size_mb, 10);
         assert_eq!(cfg.large_files.max_concurrent_large, 1);
+        assert_eq!(cfg.limits.max_file_size, 250 * 1024 * 1024 * 1024);
+        assert_eq!(cfg.limits.max_path_length, 400);
         assert_eq!(cfg.conflicts.default_strategy, "manual");
+        assert_eq!(cfg.conflicts.quarantine_after_days, 0);
+        assert_eq!(cfg.conflicts.change_detection, "quick");
         assert_eq!(cfg.logging.level, "info");
+        assert_eq!(cfg.logging.format, "text");
         assert_eq!(cfg.logging.max_size_mb, 50);
         assert_eq!(cfg.logging.max_files, 5);
         assert!(cfg.auth.app_id.is_none());
         assert_eq!(cfg.fuse.mount_point, "~/OneDrive");
         assert!(cfg.fuse.auto_mount);
         assert_eq!(cfg.fuse.cache_dir, "~/.local/share/lnxdrive/cache");
+        assert!(cfg.fuse.temp_dir.is_none());
         assert_eq!(cfg.fuse.cache_max_size_gb, 10);
         assert_eq!(cfg.fuse.dehydration_threshold_percent, 80);
         assert_eq!(cfg.fuse.dehydration_max_age_days, 30);
+        assert_eq!(cfg.fuse.dehydration_unused_days, 0);
+        assert_eq!(cfg.fuse.dehydration_large_file_mb, 0);
         assert_eq!(cfg.fuse.dehydration_interval_minutes, 60);
         assert_eq!(cfg.fuse.hydration_concurrency, 8);
+        assert_eq!(cfg.fuse.hydration_chunk_size_mb, 10);
+        assert_eq!(cfg.fuse.streaming_threshold_mb, 32);
+        assert_eq!(cfg.fuse.read_ahead_mb, 8);
+        assert_eq!(cfg.fuse.max_cached_file_mb, 0);
+        assert_eq!(cfg.fuse.oversized_files, "stream");
+        assert_eq!(cfg.fuse.cache_shard_depth, 2);
+        assert!(!cfg.fuse.cache_dedup);
+        assert!(!cfg.fuse.cache_scrub_full_hash);
+        assert!(!cfg.fuse.preserve_permissions);
+        assert_eq!(cfg.fuse.file_perm(), 0o644);
+        assert_eq!(cfg.fuse.directory_perm(), 0o755);
+        assert_eq!(cfg.fuse.max_inodes, 1_000_000);
+        assert_eq!(cfg.fuse.prefetch_recent_files, 20);
+        assert_eq!(cfg.fuse.prefetch_recent_mb, 100);
+        assert!(cfg.daemon.systemd_notify);
+        assert_eq!(cfg.daemon.shutdown_timeout, 30);
+        assert!(!cfg.metrics.enabled);
+        assert_eq!(cfg.metrics.listen_address, "127.0.0.1:9464");
+        assert_eq!(cfg.metrics.ready_max_sync_age, 300);
+        assert_eq!(cfg.metrics.ready_db_timeout_ms, 2000);
+        assert!(cfg.http.send_user_agent);
+        assert!(cfg.http.user_agent.is_none());
+        assert_eq!(cfg.http.connect_timeout, 30);
+        assert_eq!(cfg.http.read_timeout, 60);
+        assert_eq!(cfg.http.request_timeout, 120);
+        assert_eq!(cfg.http.min_transfer_rate_kbps, 64);
+        assert_eq!(cfg.delta.batch_size, 200);
+        assert_eq!(cfg.delta.max_in_flight_items, 10_000);
+        assert_eq!(cfg.delta.transaction_size, 500);
+        assert!(!cfg.change_notifications.enabled);
+        assert!(cfg.change_notifications.notification_url.is_none());
+        assert_eq!(cfg.change_notifications.listen_address, "127.0.0.1:9465");
+        assert_eq!(cfg.change_notifications.expiration_minutes, 4230);
+        assert_eq!(cfg.change_notifications.poll_interval, 900);
     }
 
     #[test]
@@ -668,6 +2013,13 @@ large_files:
   max_concurrent_large: 2
 conflicts:
   default_strategy: keep_both
+  rules:
+    - pattern: "*.log"
+      strategy: keep_remote
+    - pattern: "notes/**"
+      strategy: keep_local
+  quarantine_after_days: 30
+  change_detection: full_hash
 logging:
   level: debug
   file: /tmp/test.log
@@ -693,13 +2045,35 @@ fuse:
         assert_eq!(cfg.sync.root, PathBuf::from("/tmp/test-onedrive"));
         assert_eq!(cfg.sync.poll_interval, 60);
         assert_eq!(cfg.sync.debounce_delay, 5);
+        // Omitted in the YAML above, so the serde default applies
+        assert!(cfg.sync.startup_reconciliation);
+        assert_eq!(cfg.sync.quota_refresh_interval, 900);
+        assert!(!cfg.sync.exclude_hidden);
+        assert!(cfg.sync.exclude_junk);
+        assert!(cfg.sync.normalize_unicode);
+        assert!(cfg.daemon.systemd_notify);
+        assert_eq!(cfg.daemon.shutdown_timeout, 30);
+        assert!(!cfg.metrics.enabled);
+        assert_eq!(cfg.metrics.ready_max_sync_age, 300);
+        assert!(cfg.http.send_user_agent);
+        assert_eq!(cfg.http.request_timeout, 120);
+        assert_eq!(cfg.delta.max_in_flight_items, 10_000);
+        assert!(!cfg.change_notifications.enabled);
+        assert_eq!(cfg.limits.max_path_length, 400);
         assert_eq!(cfg.rate_limiting.delta_requests_per_minute, 20);
         assert_eq!(cfg.rate_limiting.upload_concurrent, 2);
         assert_eq!(cfg.large_files.threshold_mb, 200);
         assert_eq!(cfg.large_files.chunk_size_mb, 20);
         assert_eq!(cfg.conflicts.default_strategy, "keep_both");
+        assert_eq!(cfg.conflicts.rules.len(), 2);
+        assert_eq!(cfg.conflicts.rules[0].pattern, "*.log");
+        assert_eq!(cfg.conflicts.rules[1].strategy, "keep_local");
+        assert_eq!(cfg.conflicts.quarantine_after_days, 30);
+        assert_eq!(cfg.conflicts.change_detection, "full_hash");
         assert_eq!(cfg.logging.level, "debug");
         assert_eq!(cfg.logging.max_files, 3);
+        // Omitted format falls back to human-readable output
+        assert_eq!(cfg.logging.format, "text");
         assert_eq!(cfg.auth.app_id, Some("test-app-id-123".to_string()));
         assert_eq!(cfg.fuse.mount_point, "~/OneDrive");
         assert!(!cfg.fuse.auto_mount);
@@ -707,6 +2081,9 @@ fuse:
         assert_eq!(cfg.fuse.cache_max_size_gb, 5);
         assert_eq!(cfg.fuse.dehydration_threshold_percent, 70);
         assert_eq!(cfg.fuse.dehydration_max_age_days, 15);
+        // Optional dehydration triggers are off unless configured
+        assert_eq!(cfg.fuse.dehydration_unused_days, 0);
+        assert_eq!(cfg.fuse.dehydration_large_file_mb, 0);
         assert_eq!(cfg.fuse.dehydration_interval_minutes, 30);
         assert_eq!(cfg.fuse.hydration_concurrency, 4);
     }
@@ -804,6 +2181,48 @@ fuse:
             .any(|e| e.field == "conflicts.default_strategy"));
     }
 
+    #[test]
+    fn validate_catches_invalid_change_detection() {
+        let cfg = ConfigBuilder::new()
+            .conflicts_change_detection("mtime_only")
+            .build();
+        let errors = cfg.validate();
+        assert!(errors
+            .iter()
+            .any(|e| e.field == "conflicts.change_detection"));
+    }
+
+    #[test]
+    fn validate_catches_invalid_conflict_rules() {
+        let cfg = ConfigBuilder::new()
+            .conflicts_rule("*.log", "keep_remote")
+            .conflicts_rule("", "keep_both")
+            .conflicts_rule("notes/**", "merge")
+            .build();
+        let errors = cfg.validate();
+        assert!(!errors
+            .iter()
+            .any(|e| e.field.starts_with("conflicts.rules[0]")));
+        assert!(errors
+            .iter()
+            .any(|e| e.field == "conflicts.rules[1].pattern"));
+        assert!(errors
+            .iter()
+            .any(|e| e.field == "conflicts.rules[2].strategy"));
+    }
+
+    #[test]
+    fn validate_catches_invalid_log_format() {
+        let mut cfg = Config::default();
+        cfg.logging.format = "xml".to_string();
+        let errors = cfg.validate();
+        assert!(errors.iter().any(|e| e.field == "logging.format"));
+
+        cfg.logging.format = "json".to_string();
+        let errors = cfg.validate();
+        assert!(!errors.iter().any(|e| e.field == "logging.format"));
+    }
+
     #[test]
     fn validate_catches_zero_logging_max_size() {
         let mut cfg = Config::default();
@@ -863,6 +2282,11 @@ fuse:
             .sync_root(PathBuf::from("/custom/path"))
             .sync_poll_interval(120)
             .sync_debounce_delay(10)
+            .sync_startup_reconciliation(false)
+            .sync_quota_refresh_interval(60)
+            .sync_exclude_hidden(true)
+            .sync_exclude_junk(false)
+            .sync_normalize_unicode(false)
             .rate_limiting_delta_requests_per_minute(5)
             .rate_limiting_upload_concurrent(8)
             .rate_limiting_upload_requests_per_minute(120)
@@ -871,17 +2295,38 @@ fuse:
             .large_files_threshold_mb(500)
             .large_files_chunk_size_mb(50)
             .large_files_max_concurrent_large(3)
+            .limits_max_file_size(15 * 1024 * 1024 * 1024)
+            .limits_max_path_length(255)
             .conflicts_default_strategy("keep_local")
+            .conflicts_rule("*.docx", "keep_both")
+            .conflicts_change_detection("full_hash")
             .logging_level("debug")
             .logging_file(PathBuf::from("/tmp/lnxdrive.log"))
             .logging_max_size_mb(100)
             .logging_max_files(10)
             .auth_app_id("my-app-id")
+            .http_connect_timeout(5)
+            .http_read_timeout(20)
+            .http_request_timeout(45)
+            .http_min_transfer_rate_kbps(256)
+            .delta_batch_size(100)
+            .delta_max_in_flight_items(1000)
+            .delta_transaction_size(50)
+            .change_notifications_enabled(true)
+            .change_notifications_url("https://sync.example.org/lnxdrive")
+            .change_notifications_listen_address("0.0.0.0:8443")
+            .change_notifications_expiration_minutes(1440)
+            .change_notifications_poll_interval(3600)
             .build();
 
         assert_eq!(cfg.sync.root, PathBuf::from("/custom/path"));
         assert_eq!(cfg.sync.poll_interval, 120);
         assert_eq!(cfg.sync.debounce_delay, 10);
+        assert!(!cfg.sync.startup_reconciliation);
+        assert_eq!(cfg.sync.quota_refresh_interval, 60);
+        assert!(cfg.sync.exclude_hidden);
+        assert!(!cfg.sync.exclude_junk);
+        assert!(!cfg.sync.normalize_unicode);
         assert_eq!(cfg.rate_limiting.delta_requests_per_minute, 5);
         assert_eq!(cfg.rate_limiting.upload_concurrent, 8);
         assert_eq!(cfg.rate_limiting.upload_requests_per_minute, 120);
@@ -890,12 +2335,37 @@ fuse:
         assert_eq!(cfg.large_files.threshold_mb, 500);
         assert_eq!(cfg.large_files.chunk_size_mb, 50);
         assert_eq!(cfg.large_files.max_concurrent_large, 3);
+        assert_eq!(cfg.limits.max_file_size, 15 * 1024 * 1024 * 1024);
+        assert_eq!(cfg.limits.max_path_length, 255);
         assert_eq!(cfg.conflicts.default_strategy, "keep_local");
+        assert_eq!(
+            cfg.conflicts.rules,
+            vec![ConflictRule {
+                pattern: "*.docx".to_string(),
+                strategy: "keep_both".to_string(),
+            }]
+        );
+        assert_eq!(cfg.conflicts.change_detection, "full_hash");
         assert_eq!(cfg.logging.level, "debug");
         assert_eq!(cfg.logging.file, PathBuf::from("/tmp/lnxdrive.log"));
         assert_eq!(cfg.logging.max_size_mb, 100);
         assert_eq!(cfg.logging.max_files, 10);
         assert_eq!(cfg.auth.app_id, Some("my-app-id".to_string()));
+        assert_eq!(cfg.http.connect_timeout, 5);
+        assert_eq!(cfg.http.read_timeout, 20);
+        assert_eq!(cfg.http.request_timeout, 45);
+        assert_eq!(cfg.http.min_transfer_rate_kbps, 256);
+        assert_eq!(cfg.delta.batch_size, 100);
+        assert_eq!(cfg.delta.max_in_flight_items, 1000);
+        assert_eq!(cfg.delta.transaction_size, 50);
+        assert!(cfg.change_notifications.enabled);
+        assert_eq!(
+            cfg.change_notifications.notification_url.as_deref(),
+            Some("https://sync.example.org/lnxdrive")
+        );
+        assert_eq!(cfg.change_notifications.listen_address, "0.0.0.0:8443");
+        assert_eq!(cfg.change_notifications.expiration_minutes, 1440);
+        assert_eq!(cfg.change_notifications.poll_interval, 3600);
     }
 
     #[test]
@@ -959,6 +2429,124 @@ fuse:
             .any(|e| e.field == "fuse.hydration_concurrency"));
     }
 
+    #[test]
+    fn validate_catches_zero_http_timeouts() {
+        let mut cfg = Config::default();
+        cfg.http.connect_timeout = 0;
+        cfg.http.read_timeout = 0;
+        cfg.http.request_timeout = 0;
+        cfg.http.min_transfer_rate_kbps = 0;
+        let errors = cfg.validate();
+        for field in [
+            "http.connect_timeout",
+            "http.read_timeout",
+            "http.request_timeout",
+            "http.min_transfer_rate_kbps",
+        ] {
+            assert!(errors.iter().any(|e| e.field == field), "missing {field}");
+        }
+    }
+
+    #[test]
+    fn validate_catches_invalid_delta_limits() {
+        let mut cfg = Config::default();
+        cfg.delta.batch_size = 1001;
+        cfg.delta.max_in_flight_items = 0;
+        cfg.delta.transaction_size = 0;
+        let errors = cfg.validate();
+        for field in [
+            "delta.batch_size",
+            "delta.max_in_flight_items",
+            "delta.transaction_size",
+        ] {
+            assert!(errors.iter().any(|e| e.field == field), "{field}");
+        }
+    }
+
+    #[test]
+    fn validate_catches_invalid_fuse_cache_shard_depth() {
+        let mut cfg = Config::default();
+        cfg.fuse.cache_shard_depth = 0;
+        assert!(cfg
+            .validate()
+            .iter()
+            .any(|e| e.field == "fuse.cache_shard_depth"));
+
+        let mut cfg = Config::default();
+        cfg.fuse.cache_shard_depth = 5;
+        assert!(cfg
+            .validate()
+            .iter()
+            .any(|e| e.field == "fuse.cache_shard_depth"));
+    }
+
+    #[test]
+    fn validate_catches_invalid_fuse_hydration_chunk_size() {
+        let mut cfg = Config::default();
+        cfg.fuse.hydration_chunk_size_mb = 0;
+        let errors = cfg.validate();
+        assert!(errors
+            .iter()
+            .any(|e| e.field == "fuse.hydration_chunk_size_mb"));
+
+        let mut cfg = Config::default();
+        cfg.fuse.hydration_chunk_size_mb = 2048;
+        let errors = cfg.validate();
+        assert!(errors
+            .iter()
+            .any(|e| e.field == "fuse.hydration_chunk_size_mb"));
+    }
+
+    #[test]
+    fn validate_catches_invalid_fuse_dehydration_busy_files() {
+        let mut cfg = Config::default();
+        assert_eq!(cfg.fuse.dehydration_busy_files, "defer");
+        cfg.fuse.dehydration_busy_files = "wait".into();
+        assert!(cfg
+            .validate()
+            .iter()
+            .any(|e| e.field == "fuse.dehydration_busy_files"));
+    }
+
+    #[test]
+    fn validate_catches_invalid_fuse_oversized_files() {
+        let mut cfg = Config::default();
+        cfg.fuse.oversized_files = "truncate".into();
+        assert!(cfg
+            .validate()
+            .iter()
+            .any(|e| e.field == "fuse.oversized_files"));
+    }
+
+    #[test]
+    fn validate_catches_invalid_fuse_modes() {
+        let mut cfg = Config::default();
+        for (file_mode, directory_mode) in [("rw", "755"), ("1644", "755"), ("044", "755")] {
+            cfg.fuse.file_mode = file_mode.into();
+            cfg.fuse.directory_mode = directory_mode.into();
+            assert!(
+                cfg.validate().iter().any(|e| e.field == "fuse.file_mode"),
+                "{file_mode} accepted"
+            );
+        }
+        cfg.fuse.file_mode = "0o600".into();
+        cfg.fuse.directory_mode = "644".into();
+        let errors = cfg.validate();
+        assert!(!errors.iter().any(|e| e.field == "fuse.file_mode"));
+        assert!(errors.iter().any(|e| e.field == "fuse.directory_mode"));
+    }
+
+    #[test]
+    fn max_cached_file_defaults_to_cache_size() {
+        let mut fuse = FuseConfig {
+            cache_max_size_gb: 2,
+            ..FuseConfig::default()
+        };
+        assert_eq!(fuse.max_cached_file_bytes(), 2 * 1024 * 1024 * 1024);
+        fuse.max_cached_file_mb = 500;
+        assert_eq!(fuse.max_cached_file_bytes(), 500 * 1024 * 1024);
+    }
+
     #[test]
     fn validate_catches_zero_fuse_dehydration_interval() {
         let mut cfg = Config::default();
@@ -969,6 +2557,47 @@ fuse:
             .any(|e| e.field == "fuse.dehydration_interval_minutes"));
     }
 
+    #[test]
+    fn validate_catches_invalid_change_notification_values() {
+        let mut cfg = Config::default();
+        cfg.change_notifications.enabled = true;
+        cfg.change_notifications.notification_url = Some("http://example.org/hook".into());
+        cfg.change_notifications.listen_address = "localhost".into();
+        cfg.change_notifications.expiration_minutes = 50_000;
+        cfg.change_notifications.poll_interval = 0;
+        let errors = cfg.validate();
+        for field in [
+            "change_notifications.notification_url",
+            "change_notifications.listen_address",
+            "change_notifications.expiration_minutes",
+            "change_notifications.poll_interval",
+        ] {
+            assert!(errors.iter().any(|e| e.field == field), "missing {field}");
+        }
+
+        cfg.change_notifications.notification_url = None;
+        assert!(cfg
+            .validate()
+            .iter()
+            .any(|e| e.field == "change_notifications.notification_url"));
+    }
+
+    #[test]
+    fn validate_catches_invalid_metrics_values() {
+        let mut cfg = Config::default();
+        cfg.metrics.listen_address = "localhost".into();
+        cfg.metrics.ready_max_sync_age = 0;
+        cfg.metrics.ready_db_timeout_ms = 0;
+        let errors = cfg.validate();
+        for field in [
+            "metrics.listen_address",
+            "metrics.ready_max_sync_age",
+            "metrics.ready_db_timeout_ms",
+        ] {
+            assert!(errors.iter().any(|e| e.field == field), "missing {field}");
+        }
+    }
+
     #[test]
     fn validate_accepts_valid_fuse_values() {
         let mut cfg = Config::default();
@@ -1035,6 +2664,8 @@ dehydration_threshold_percent: 75
 dehydration_max_age_days: 45
 dehydration_interval_minutes: 90
 hydration_concurrency: 12
+read_ahead_mb: 0
+file_mode: "0600"
 "#;
         let fuse: FuseConfig = serde_yaml::from_str(yaml).expect("deserialize FuseConfig");
         assert_eq!(fuse.mount_point, "/mnt/onedrive");
@@ -1045,6 +2676,22 @@ hydration_concurrency: 12
         assert_eq!(fuse.dehydration_max_age_days, 45);
         assert_eq!(fuse.dehydration_interval_minutes, 90);
         assert_eq!(fuse.hydration_concurrency, 12);
+        // Omitted chunk size falls back to the default
+        assert_eq!(fuse.hydration_chunk_size_mb, 10);
+        assert_eq!(fuse.streaming_threshold_mb, 32);
+        assert_eq!(fuse.read_ahead_mb, 0);
+        assert!(!fuse.cache_dedup);
+        assert!(!fuse.cache_scrub_full_hash);
+        assert!(!fuse.preserve_permissions);
+        assert_eq!(fuse.file_perm(), 0o600);
+        assert_eq!(fuse.directory_perm(), 0o755);
+        assert_eq!(fuse.max_inodes, 1_000_000);
+        assert_eq!(fuse.prefetch_recent_files, 20);
+        assert!(fuse.temp_dir.is_none());
+        assert_eq!(
+            fuse.temp_dir_path(),
+            PathBuf::from("/var/cache/lnxdrive/tmp")
+        );
     }
 
     #[test]
@@ -1097,4 +2744,225 @@ fuse:
         assert_eq!(cfg.fuse.dehydration_interval_minutes, 120);
         assert_eq!(cfg.fuse.hydration_concurrency, 10);
     }
+    // -- Directory layout --
+
+    fn layout_config(mount_point: &Path, cache_dir: &Path) -> Config {
+        ConfigBuilder::new()
+            .fuse_mount_point(mount_point.to_string_lossy())
+            .fuse_cache_dir(cache_dir.to_string_lossy())
+            .build()
+    }
+
+    #[test]
+    fn validate_layout_accepts_separate_directories() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().join("OneDrive");
+        let config = layout_config(&root, &dir.path().join("cache"));
+
+        // Files-on-Demand: the mount point is the sync root itself
+        assert!(config.validate_layout(&root).is_empty());
+
+        let config = layout_config(&dir.path().join("mnt"), &dir.path().join("cache"));
+        assert!(config.validate_layout(&root).is_empty());
+    }
+
+    #[test]
+    fn validate_layout_rejects_nested_directories() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().join("OneDrive");
+
+        let config = layout_config(&root.join("mnt"), &dir.path().join("cache"));
+        let errors = config.validate_layout(&root);
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].field, "fuse.mount_point");
+        assert!(errors[0]
+            .message
+            .starts_with("mount_point must not be inside sync_root"));
+
+        let config = layout_config(dir.path(), &dir.path().join("cache"));
+        let errors = config.validate_layout(&root);
+        assert!(errors[0]
+            .message
+            .starts_with("sync_root must not be inside mount_point"));
+        assert!(errors.iter().any(|e| e
+            .message
+            .starts_with("cache_dir must not be inside mount_point")));
+
+        let config = layout_config(&dir.path().join("mnt"), &root.join(".cache"));
+        let errors = config.validate_layout(&root);
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].field, "fuse.cache_dir");
+        assert!(errors[0]
+            .message
+            .starts_with("cache_dir must not be inside sync_root"));
+
+        let config = layout_config(&dir.path().join("mnt"), &root);
+        let errors = config.validate_layout(&root);
+        assert!(errors[0]
+            .message
+            .starts_with("cache_dir must not be the same directory as sync_root"));
+    }
+
+    #[test]
+    fn validate_layout_resolves_symlinks_and_dot_dot() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().join("OneDrive");
+        std::fs::create_dir(&root).unwrap();
+        let link = dir.path().join("link");
+        std::os::unix::fs::symlink(&root, &link).unwrap();
+
+        let config = layout_config(&dir.path().join("mnt"), &link.join("cache"));
+        assert_eq!(config.validate_layout(&root).len(), 1);
+
+        let sneaky = dir.path().join("mnt/../OneDrive/cache");
+        let config = layout_config(&dir.path().join("mnt"), &sneaky);
+        assert_eq!(config.validate_layout(&root).len(), 1);
+    }
+
+    #[test]
+    fn check_sync_root_requires_writable_directory() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(check_sync_root(dir.path()).is_ok());
+        assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 0);
+
+        let missing = check_sync_root(&dir.path().join("missing")).unwrap_err();
+        assert_eq!(missing.field, "sync.root");
+        assert!(missing.message.starts_with("directory does not exist"));
+
+        let file = dir.path().join("file");
+        std::fs::write(&file, b"").unwrap();
+        let not_dir = check_sync_root(&file).unwrap_err();
+        assert!(not_dir.message.starts_with("not a directory"));
+    }
+
+    #[test]
+    fn check_temp_dir_creates_missing_directory() {
+        let dir = tempfile::tempdir().unwrap();
+        let temp_dir = dir.path().join("cache").join("tmp");
+        assert!(check_temp_dir(&temp_dir).is_ok());
+        assert!(temp_dir.is_dir());
+
+        let file = dir.path().join("file");
+        std::fs::write(&file, b"").unwrap();
+        let err = check_temp_dir(&file).unwrap_err();
+        assert_eq!(err.field, "fuse.temp_dir");
+    }
+
+    #[test]
+    fn check_cache_dir_reports_cache_field() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(check_cache_dir(&dir.path().join("cache")).is_ok());
+
+        let file = dir.path().join("file");
+        std::fs::write(&file, b"").unwrap();
+        let err = check_cache_dir(&file).unwrap_err();
+        assert_eq!(err.field, "fuse.cache_dir");
+    }
+
+    #[test]
+    fn temp_dir_path_prefers_configured_directory() {
+        let fuse = FuseConfig {
+            temp_dir: Some("/srv/lnxdrive-tmp".to_string()),
+            ..FuseConfig::default()
+        };
+        assert_eq!(fuse.temp_dir_path(), PathBuf::from("/srv/lnxdrive-tmp"));
+    }
+
+    #[test]
+    fn expand_tilde_uses_home_directory() {
+        let home = dirs::home_dir().unwrap();
+        assert_eq!(expand_tilde(Path::new("~/OneDrive")), home.join("OneDrive"));
+        assert_eq!(expand_tilde(Path::new("~")), home);
+        assert_eq!(
+            expand_tilde(Path::new("/srv/OneDrive")),
+            PathBuf::from("/srv/OneDrive")
+        );
+    }
+
+    fn test_account(email: &str) -> Account {
+        use crate::domain::newtypes::{Email, SyncPath};
+        Account::new(
+            Email::new(email.to_string()).unwrap(),
+            "Test User",
+            "drive",
+            SyncPath::new(PathBuf::from("/home/user/OneDrive")).unwrap(),
+        )
+    }
+
+    #[test]
+    fn account_reference_matches_alias_email_or_id() {
+        let accounts = [
+            test_account("alice@example.com"),
+            test_account("alice@work.example"),
+        ];
+        let mut config = AccountsConfig::default();
+        config
+            .aliases
+            .insert("work".into(), "alice@work.example".into());
+
+        let by_alias = config.resolve(&accounts, Some("work")).unwrap();
+        assert_eq!(by_alias.email().as_str(), "alice@work.example");
+        let by_email = config
+            .resolve(&accounts, Some("ALICE@example.com"))
+            .unwrap();
+        assert_eq!(by_email.email().as_str(), "alice@example.com");
+        let id = accounts[1].id().to_string();
+        assert_eq!(
+            config.resolve(&accounts, Some(&id)).unwrap().id(),
+            accounts[1].id()
+        );
+
+        let err = config.resolve(&accounts, Some("home")).unwrap_err();
+        assert_eq!(
+            err,
+            AccountRefError::Unknown {
+                reference: "home".into(),
+                choices: vec![
+                    "alice@example.com".into(),
+                    "alice@work.example (work)".into()
+                ],
+            }
+        );
+    }
+
+    #[test]
+    fn account_reference_defaults_to_single_or_configured_account() {
+        let config = AccountsConfig::default();
+        assert_eq!(
+            config.resolve(&[], None).unwrap_err(),
+            AccountRefError::NoAccounts
+        );
+
+        let single = [test_account("alice@example.com")];
+        assert!(config.resolve(&single, None).is_ok());
+
+        let accounts = [
+            test_account("alice@example.com"),
+            test_account("bob@example.com"),
+        ];
+        assert!(matches!(
+            config.resolve(&accounts, None),
+            Err(AccountRefError::Ambiguous { .. })
+        ));
+
+        let config = AccountsConfig {
+            default: Some("bob@example.com".into()),
+            ..AccountsConfig::default()
+        };
+        let chosen = config.resolve(&accounts, None).unwrap();
+        assert_eq!(chosen.email().as_str(), "bob@example.com");
+    }
+
+    #[test]
+    fn account_alias_must_not_look_like_an_email() {
+        let mut config = Config::default();
+        config
+            .accounts
+            .aliases
+            .insert("me@home".into(), "alice@example.com".into());
+        assert!(config
+            .validate()
+            .iter()
+            .any(|e| e.field == "accounts.aliases"));
+    }
 }
@@ -72,6 +72,10 @@ pub struct Account {
     quota_total: u64,
     /// Delta token for incremental sync (None for initial sync)
     delta_token: Option<DeltaToken>,
+    /// nextLink of a delta page walk interrupted after its earlier pages
+    /// were applied, from which the walk continues
+    #[serde(default)]
+    delta_next_link: Option<String>,
     /// Timestamp of last successful sync (None if never synced)
     last_sync: Option<DateTime<Utc>>,
     /// Current account state
@@ -106,6 +110,7 @@ impl Account {
             quota_used: 0,
             quota_total: 0,
             delta_token: None,
+            delta_next_link: None,
             last_sync: None,
             state: AccountState::Active,
             created_at: Utc::now(),
@@ -130,6 +135,7 @@ impl Account {
             quota_used: 0,
             quota_total: 0,
             delta_token: None,
+            delta_next_link: None,
             last_sync: None,
             state: AccountState::Active,
             created_at,
@@ -178,6 +184,11 @@ impl Account {
         self.delta_token.as_ref()
     }
 
+    /// Returns the nextLink of an interrupted delta page walk, if any
+    pub fn delta_next_link(&self) -> Option<&str> {
+        self.delta_next_link.as_deref()
+    }
+
     /// Returns the last sync timestamp if any
     pub fn last_sync(&self) -> Option<DateTime<Utc>> {
         self.last_sync
@@ -216,13 +227,27 @@ impl Account {
     }
 
     /// Updates the delta token after a successful sync
+    ///
+    /// The page walk that produced the token is complete, so the saved
+    /// nextLink is dropped.
     pub fn update_delta_token(&mut self, token: DeltaToken) {
         self.delta_token = Some(token);
+        self.delta_next_link = None;
     }
 
     /// Clears the delta token (forces full resync)
     pub fn clear_delta_token(&mut self) {
         self.delta_token = None;
+        self.delta_next_link = None;
+    }
+
+    /// Records how far the current delta page walk was applied
+    ///
+    /// `next_link` is the nextLink of the last applied page, or `None`
+    /// once the walk is over. The delta token stays the last committed
+    /// one until the whole walk is applied.
+    pub fn set_delta_next_link(&mut self, next_link: Option<String>) {
+        self.delta_next_link = next_link;
     }
 
     /// Records a successful sync
@@ -397,6 +422,20 @@ mod tests {
             assert!(account.delta_token().is_none());
         }
 
+        #[test]
+        fn test_new_delta_token_ends_interrupted_walk() {
+            let mut account = create_test_account();
+            account.set_delta_next_link(Some("https://example.com/delta?page=3".into()));
+            assert_eq!(
+                account.delta_next_link(),
+                Some("https://example.com/delta?page=3")
+            );
+
+            let token = DeltaToken::new("token".to_string()).unwrap();
+            account.update_delta_token(token);
+            assert!(account.delta_next_link().is_none());
+        }
+
         #[test]
         fn test_record_sync() {
             let mut account = create_test_account();
@@ -7,7 +7,10 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
-use super::newtypes::{AuditId, SessionId, UniqueId};
+use super::{
+    newtypes::{AuditId, SessionId, UniqueId},
+    sync_item::ItemState,
+};
 
 /// Actions that can be recorded in the audit log
 ///
@@ -40,6 +43,8 @@ pub enum AuditAction {
     Error,
     /// Configuration was changed
     ConfigChange,
+    /// An item moved from one state to another
+    StateTransition,
 }
 
 impl std::fmt::Display for AuditAction {
@@ -57,6 +62,31 @@ impl std::fmt::Display for AuditAction {
             AuditAction::ConflictResolved => "conflict_resolved",
             AuditAction::Error => "error",
             AuditAction::ConfigChange => "config_change",
+            AuditAction::StateTransition => "state_transition",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+/// What caused an item state transition
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TransitionTrigger {
+    /// A file operation of the user on the mount point
+    User,
+    /// The sync engine or a background task of the daemon
+    #[default]
+    Sync,
+    /// Recovery of states left behind by a crash, when mounting
+    CrashRecovery,
+}
+
+impl std::fmt::Display for TransitionTrigger {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            TransitionTrigger::User => "user",
+            TransitionTrigger::Sync => "sync",
+            TransitionTrigger::CrashRecovery => "crash_recovery",
         };
         write!(f, "{}", s)
     }
@@ -100,6 +130,16 @@ impl AuditResult {
     pub fn is_failed(&self) -> bool {
         matches!(self, AuditResult::Failed { .. })
     }
+
+    /// Returns the error code of a failure, e.g. a [`ReasonCode`] string
+    ///
+    /// [`ReasonCode`]: super::reason::ReasonCode
+    pub fn code(&self) -> Option<&str> {
+        match self {
+            AuditResult::Success => None,
+            AuditResult::Failed { code, .. } => Some(code),
+        }
+    }
 }
 
 /// An audit log entry recording a significant operation
@@ -159,6 +199,31 @@ impl AuditEntry {
         }
     }
 
+    /// Creates the entry recording that item `item_id` moved from state
+    /// `from` to state `to`
+    ///
+    /// The details hold the state names and the trigger. A transition to
+    /// [`ItemState::Error`] is recorded as failed, with the error reason
+    /// as message.
+    pub fn state_transition(
+        item_id: UniqueId,
+        from: &ItemState,
+        to: &ItemState,
+        trigger: TransitionTrigger,
+    ) -> Self {
+        let result = match to {
+            ItemState::Error(reason) => AuditResult::failed("item_error", reason.clone()),
+            _ => AuditResult::success(),
+        };
+        Self::new(AuditAction::StateTransition, result)
+            .with_item_id(item_id)
+            .with_details(serde_json::json!({
+                "from": from.name(),
+                "to": to.name(),
+                "trigger": trigger,
+            }))
+    }
+
     /// Returns the audit entry ID (None if not yet persisted)
     pub fn id(&self) -> Option<AuditId> {
         self.id
@@ -251,6 +316,37 @@ mod tests {
         assert_eq!(AuditAction::AuthLogin.to_string(), "auth_login");
         assert_eq!(AuditAction::SyncComplete.to_string(), "sync_complete");
         assert_eq!(AuditAction::FileDownload.to_string(), "file_download");
+        assert_eq!(AuditAction::StateTransition.to_string(), "state_transition");
+    }
+
+    #[test]
+    fn test_state_transition_entry() {
+        let item_id = UniqueId::new();
+        let entry = AuditEntry::state_transition(
+            item_id,
+            &ItemState::Online,
+            &ItemState::Hydrating,
+            TransitionTrigger::User,
+        );
+        assert_eq!(*entry.action(), AuditAction::StateTransition);
+        assert_eq!(entry.item_id(), Some(&item_id));
+        assert!(entry.result().is_success());
+        assert_eq!(
+            *entry.details(),
+            json!({"from": "Online", "to": "Hydrating", "trigger": "user"})
+        );
+
+        let failed = AuditEntry::state_transition(
+            item_id,
+            &ItemState::Hydrating,
+            &ItemState::Error("disk full".to_string()),
+            TransitionTrigger::CrashRecovery,
+        );
+        assert_eq!(
+            *failed.result(),
+            AuditResult::failed("item_error", "disk full")
+        );
+        assert_eq!(failed.details()["trigger"], "crash_recovery");
     }
 
     #[test]
@@ -265,6 +361,7 @@ mod tests {
         let result = AuditResult::failed("E001", "Network error");
         assert!(!result.is_success());
         assert!(result.is_failed());
+        assert_eq!(result.code(), Some("E001"));
 
         if let AuditResult::Failed { code, message } = result {
             assert_eq!(code, "E001");
@@ -0,0 +1,118 @@
+//! ClockSkew domain type
+//!
+//! This module defines the offset between the local clock and the clock
+//! of the cloud provider, measured from the `Date` header of an HTTP
+//! response. A badly set local clock (common on NAS boxes and VMs without
+//! time synchronization) breaks token validation and makes local
+//! modification times incomparable with remote ones.
+
+use std::fmt;
+
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Skew, in seconds, from which the local clock counts as wrong
+///
+/// The `Date` header has a resolution of one second and the request takes
+/// some time, so small offsets are expected.
+pub const CLOCK_SKEW_WARNING_SECS: i64 = 300;
+
+/// How far the local clock is ahead of the server's (negative: behind)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ClockSkew {
+    /// Local time minus server time, in seconds
+    pub seconds: i64,
+}
+
+impl ClockSkew {
+    /// Creates a skew of `seconds` (positive: local clock ahead)
+    pub fn from_secs(seconds: i64) -> Self {
+        Self { seconds }
+    }
+
+    /// Computes the skew from an HTTP `Date` header value (RFC 7231
+    /// IMF-fixdate, e.g. `Tue, 15 Nov 1994 08:12:31 GMT`) and the local
+    /// time at which the response arrived
+    ///
+    /// Returns `None` if the header cannot be parsed.
+    pub fn from_date_header(date: &str, local: DateTime<Utc>) -> Option<Self> {
+        let server = DateTime::parse_from_rfc2822(date.trim()).ok()?;
+        Some(Self::from_secs(
+            (local - server.with_timezone(&Utc)).num_seconds(),
+        ))
+    }
+
+    /// Returns true if the skew reaches [`CLOCK_SKEW_WARNING_SECS`]
+    pub fn is_significant(&self) -> bool {
+        self.seconds.abs() >= CLOCK_SKEW_WARNING_SECS
+    }
+
+    /// Converts a timestamp taken from the local clock to server time
+    pub fn to_server_time(&self, local: DateTime<Utc>) -> DateTime<Utc> {
+        local - Duration::seconds(self.seconds)
+    }
+}
+
+impl fmt::Display for ClockSkew {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let secs = self.seconds.unsigned_abs();
+        let direction = if self.seconds >= 0 {
+            "ahead of"
+        } else {
+            "behind"
+        };
+        match secs {
+            0..=59 => write!(f, "{secs}s"),
+            60..=3599 => write!(f, "{}m {:02}s", secs / 60, secs % 60),
+            _ => write!(f, "{}h {:02}m", secs / 3600, secs / 60 % 60),
+        }?;
+        write!(f, " {direction} server time")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(rfc3339: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(rfc3339)
+            .unwrap()
+            .with_timezone(&Utc)
+    }
+
+    #[test]
+    fn test_skew_from_date_header() {
+        let header = "Tue, 15 Nov 1994 08:12:31 GMT";
+
+        let ahead = ClockSkew::from_date_header(header, at("1994-11-15T08:20:01Z")).unwrap();
+        assert_eq!(ahead.seconds, 450);
+        assert!(ahead.is_significant());
+
+        let behind = ClockSkew::from_date_header(header, at("1994-11-15T08:12:29Z")).unwrap();
+        assert_eq!(behind.seconds, -2);
+        assert!(!behind.is_significant());
+
+        assert!(ClockSkew::from_date_header("yesterday", Utc::now()).is_none());
+    }
+
+    #[test]
+    fn test_to_server_time() {
+        let skew = ClockSkew::from_secs(600);
+        assert_eq!(
+            skew.to_server_time(at("2024-01-01T12:10:00Z")),
+            at("2024-01-01T12:00:00Z")
+        );
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(
+            ClockSkew::from_secs(450).to_string(),
+            "7m 30s ahead of server time"
+        );
+        assert_eq!(
+            ClockSkew::from_secs(-7260).to_string(),
+            "2h 01m behind server time"
+        );
+    }
+}
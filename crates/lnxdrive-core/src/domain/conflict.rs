@@ -6,7 +6,10 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
-use super::newtypes::{ConflictId, FileHash, UniqueId};
+use super::{
+    errors::DomainError,
+    newtypes::{ConflictId, FileHash, UniqueId},
+};
 
 /// Information about a specific version of a file
 ///
@@ -14,8 +17,11 @@ use super::newtypes::{ConflictId, FileHash, UniqueId};
 /// two versions of the same file and determine if they conflict.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct VersionInfo {
-    /// File content hash (quickXorHash for OneDrive)
-    hash: FileHash,
+    /// File content hash (quickXorHash for OneDrive), if known
+    ///
+    /// OneDrive omits the hash of some files; their size and modification
+    /// time are the only evidence of their content then.
+    hash: Option<FileHash>,
     /// File size in bytes
     size_bytes: u64,
     /// When this version was last modified
@@ -34,16 +40,26 @@ impl VersionInfo {
     /// * `modified_at` - When the file was last modified
     pub fn new(hash: FileHash, size_bytes: u64, modified_at: DateTime<Utc>) -> Self {
         Self {
-            hash,
+            hash: Some(hash),
+            size_bytes,
+            modified_at,
+            etag: None,
+        }
+    }
+
+    /// Creates a VersionInfo for a file whose content hash is unknown
+    pub fn without_hash(size_bytes: u64, modified_at: DateTime<Utc>) -> Self {
+        Self {
+            hash: None,
             size_bytes,
             modified_at,
             etag: None,
         }
     }
 
-    /// Returns the file hash
-    pub fn hash(&self) -> &FileHash {
-        &self.hash
+    /// Returns the file hash, if known
+    pub fn hash(&self) -> Option<&FileHash> {
+        self.hash.as_ref()
     }
 
     /// Returns the file size in bytes
@@ -94,6 +110,26 @@ impl std::fmt::Display for Resolution {
     }
 }
 
+impl std::str::FromStr for Resolution {
+    type Err = DomainError;
+
+    /// Parses a strategy name, accepting the short forms `local`, `remote`
+    /// and `both` and the alias `prefer_remote` as well as the canonical
+    /// snake_case names
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "keep_local" | "local" => Ok(Resolution::KeepLocal),
+            "keep_remote" | "remote" | "prefer_remote" => Ok(Resolution::KeepRemote),
+            "keep_both" | "both" => Ok(Resolution::KeepBoth),
+            "manual" => Ok(Resolution::Manual),
+            other => Err(DomainError::ValidationFailed(format!(
+                "Unknown conflict resolution strategy: {}",
+                other
+            ))),
+        }
+    }
+}
+
 /// Who or what initiated the conflict resolution
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
@@ -304,12 +340,22 @@ mod tests {
         let now = Utc::now();
         let version = VersionInfo::new(hash.clone(), 1024, now);
 
-        assert_eq!(version.hash(), &hash);
+        assert_eq!(version.hash(), Some(&hash));
         assert_eq!(version.size_bytes(), 1024);
         assert_eq!(version.modified_at(), now);
         assert!(version.etag().is_none());
     }
 
+    #[test]
+    fn test_version_info_without_hash_roundtrips() {
+        let version = VersionInfo::without_hash(2048, Utc::now());
+        assert!(version.hash().is_none());
+
+        let json = serde_json::to_string(&version).unwrap();
+        let parsed: VersionInfo = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, version);
+    }
+
     #[test]
     fn test_version_info_with_etag() {
         let version = create_version_info(VALID_HASH_1, 2048).with_etag("\"etag123\"");
@@ -325,6 +371,21 @@ mod tests {
         assert_eq!(Resolution::Manual.to_string(), "manual");
     }
 
+    #[test]
+    fn test_resolution_from_str() {
+        assert_eq!(
+            "keep_local".parse::<Resolution>().unwrap(),
+            Resolution::KeepLocal
+        );
+        assert_eq!(
+            "remote".parse::<Resolution>().unwrap(),
+            Resolution::KeepRemote
+        );
+        assert_eq!("both".parse::<Resolution>().unwrap(), Resolution::KeepBoth);
+        assert_eq!("manual".parse::<Resolution>().unwrap(), Resolution::Manual);
+        assert!("merge".parse::<Resolution>().is_err());
+    }
+
     #[test]
     fn test_resolution_serialization() {
         let resolution = Resolution::KeepBoth;
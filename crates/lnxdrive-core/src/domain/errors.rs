@@ -5,6 +5,8 @@
 
 use thiserror::Error;
 
+use super::reason::ReasonCode;
+
 /// Errors that can occur in domain operations
 #[derive(Debug, Error, Clone, PartialEq, Eq)]
 pub enum DomainError {
@@ -54,6 +56,18 @@ pub enum DomainError {
     InvalidId(String),
 }
 
+impl DomainError {
+    /// Returns the [`ReasonCode`] describing this error
+    pub fn reason(&self) -> ReasonCode {
+        match self {
+            DomainError::InvalidPath(_) | DomainError::InvalidRemotePath(_) => {
+                ReasonCode::InvalidName
+            }
+            _ => ReasonCode::Unknown,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -86,6 +100,18 @@ mod tests {
         assert_ne!(err1, err3);
     }
 
+    #[test]
+    fn test_error_reason() {
+        assert_eq!(
+            DomainError::InvalidRemotePath("/a:b".to_string()).reason(),
+            ReasonCode::InvalidName
+        );
+        assert_eq!(
+            DomainError::InvalidHash("x".to_string()).reason(),
+            ReasonCode::Unknown
+        );
+    }
+
     #[test]
     fn test_error_clone() {
         let err = DomainError::ValidationFailed("test".to_string());
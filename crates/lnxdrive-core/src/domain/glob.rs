@@ -0,0 +1,234 @@
+//! Glob patterns for matching paths relative to the sync root
+//!
+//! Patterns follow gitignore-style semantics:
+//! - `*` matches any run of characters except `/`
+//! - `?` matches a single character except `/`
+//! - `**` matches any number of path segments (including none)
+//! - `[abc]`, `[a-z]` and `[!abc]` match one character from a set
+//! - A pattern without `/` matches the file name at any depth
+//!   (`*.log` matches `logs/app.log`); a pattern containing `/` is
+//!   anchored at the sync root (`notes/**` matches `notes/a/b.md`)
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use super::errors::DomainError;
+
+/// A validated glob pattern
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct GlobPattern {
+    pattern: String,
+    anchored: bool,
+}
+
+impl GlobPattern {
+    /// Creates a new GlobPattern
+    ///
+    /// # Errors
+    /// Returns `DomainError::ValidationFailed` if the pattern is empty or
+    /// contains an unterminated character class.
+    pub fn new(pattern: impl Into<String>) -> Result<Self, DomainError> {
+        let pattern = pattern.into();
+        let trimmed = pattern.trim_start_matches('/');
+        if trimmed.is_empty() {
+            return Err(DomainError::ValidationFailed(
+                "Glob pattern must not be empty".to_string(),
+            ));
+        }
+
+        let mut chars = trimmed.chars();
+        while let Some(c) = chars.next() {
+            if c == '[' && !chars.by_ref().any(|c| c == ']') {
+                return Err(DomainError::ValidationFailed(format!(
+                    "Unterminated character class in glob pattern: {}",
+                    pattern
+                )));
+            }
+        }
+
+        Ok(Self {
+            anchored: pattern.contains('/'),
+            pattern,
+        })
+    }
+
+    /// Returns the pattern as written
+    pub fn as_str(&self) -> &str {
+        &self.pattern
+    }
+
+    /// Returns true if the pattern matches a path relative to the sync root
+    ///
+    /// Leading `/` and `./` in the path are ignored.
+    pub fn matches(&self, relative_path: &str) -> bool {
+        let path = relative_path
+            .trim_start_matches("./")
+            .trim_start_matches('/');
+        let pattern = self.pattern.trim_start_matches('/');
+
+        if self.anchored {
+            match_segments(
+                &pattern.split('/').collect::<Vec<_>>(),
+                &path.split('/').collect::<Vec<_>>(),
+            )
+        } else {
+            let name = path.rsplit('/').next().unwrap_or(path);
+            match_segment(pattern.as_bytes(), name.as_bytes())
+        }
+    }
+}
+
+/// Matches pattern segments against path segments, expanding `**`
+fn match_segments(pattern: &[&str], path: &[&str]) -> bool {
+    match pattern.split_first() {
+        None => path.is_empty(),
+        Some((&"**", rest)) => (0..=path.len()).any(|skip| match_segments(rest, &path[skip..])),
+        Some((seg, rest)) => match path.split_first() {
+            Some((name, path_rest)) => {
+                match_segment(seg.as_bytes(), name.as_bytes()) && match_segments(rest, path_rest)
+            }
+            None => false,
+        },
+    }
+}
+
+/// Matches a single segment with `*`, `?` and character classes
+fn match_segment(pattern: &[u8], name: &[u8]) -> bool {
+    match pattern.split_first() {
+        None => name.is_empty(),
+        Some((b'*', rest)) => (0..=name.len()).any(|skip| match_segment(rest, &name[skip..])),
+        Some((b'?', rest)) => !name.is_empty() && match_segment(rest, &name[1..]),
+        Some((b'[', rest)) => {
+            let Some(end) = rest.iter().position(|&b| b == b']') else {
+                return false;
+            };
+            let Some((&c, name_rest)) = name.split_first() else {
+                return false;
+            };
+            match_class(&rest[..end], c) && match_segment(&rest[end + 1..], name_rest)
+        }
+        Some((&p, rest)) => name.first() == Some(&p) && match_segment(rest, &name[1..]),
+    }
+}
+
+/// Matches one byte against a `[...]` class body
+fn match_class(class: &[u8], c: u8) -> bool {
+    let (negated, class) = match class.split_first() {
+        Some((b'!' | b'^', rest)) => (true, rest),
+        _ => (false, class),
+    };
+
+    let mut found = false;
+    let mut i = 0;
+    while i < class.len() {
+        if i + 2 < class.len() && class[i + 1] == b'-' {
+            if (class[i]..=class[i + 2]).contains(&c) {
+                found = true;
+            }
+            i += 3;
+        } else {
+            if class[i] == c {
+                found = true;
+            }
+            i += 1;
+        }
+    }
+    found != negated
+}
+
+impl fmt::Display for GlobPattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.pattern)
+    }
+}
+
+impl TryFrom<String> for GlobPattern {
+    type Error = DomainError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Self::new(value)
+    }
+}
+
+impl From<GlobPattern> for String {
+    fn from(value: GlobPattern) -> Self {
+        value.pattern
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn glob(p: &str) -> GlobPattern {
+        GlobPattern::new(p).unwrap()
+    }
+
+    #[test]
+    fn test_rejects_empty_and_unterminated() {
+        assert!(GlobPattern::new("").is_err());
+        assert!(GlobPattern::new("/").is_err());
+        assert!(GlobPattern::new("file[ab").is_err());
+    }
+
+    #[test]
+    fn test_unanchored_matches_name_at_any_depth() {
+        let p = glob("*.log");
+        assert!(p.matches("app.log"));
+        assert!(p.matches("var/logs/app.log"));
+        assert!(!p.matches("app.log.old"));
+        assert!(!p.matches("logs/app.txt"));
+    }
+
+    #[test]
+    fn test_anchored_double_star() {
+        let p = glob("notes/**");
+        assert!(p.matches("notes/a.md"));
+        assert!(p.matches("notes/deep/nested/b.md"));
+        assert!(!p.matches("other/notes/a.md"));
+
+        let p = glob("**/config/**");
+        assert!(p.matches("config/app.yaml"));
+        assert!(p.matches("project/config/app.yaml"));
+        assert!(!p.matches("project/configs/app.yaml"));
+
+        let p = glob("**/*.docx");
+        assert!(p.matches("report.docx"));
+        assert!(p.matches("work/q3/report.docx"));
+    }
+
+    #[test]
+    fn test_star_does_not_cross_segments() {
+        let p = glob("docs/*.md");
+        assert!(p.matches("docs/readme.md"));
+        assert!(!p.matches("docs/sub/readme.md"));
+    }
+
+    #[test]
+    fn test_question_mark_and_classes() {
+        assert!(glob("file?.txt").matches("file1.txt"));
+        assert!(!glob("file?.txt").matches("file10.txt"));
+        assert!(glob("[ab]*.txt").matches("alpha.txt"));
+        assert!(!glob("[ab]*.txt").matches("gamma.txt"));
+        assert!(glob("v[0-9].bin").matches("v7.bin"));
+        assert!(glob("[!.]*").matches("visible"));
+        assert!(!glob("[!.]*").matches(".hidden"));
+    }
+
+    #[test]
+    fn test_leading_slash_and_dot() {
+        assert!(glob("/notes/*.md").matches("notes/a.md"));
+        assert!(glob("notes/*.md").matches("/notes/a.md"));
+        assert!(glob("notes/*.md").matches("./notes/a.md"));
+    }
+
+    #[test]
+    fn test_serde_roundtrip() {
+        let p: GlobPattern = serde_json::from_str("\"*.log\"").unwrap();
+        assert_eq!(p.as_str(), "*.log");
+        assert_eq!(serde_json::to_string(&p).unwrap(), "\"*.log\"");
+        assert!(serde_json::from_str::<GlobPattern>("\"\"").is_err());
+    }
+}
@@ -0,0 +1,152 @@
+//! ProviderLimits domain type
+//!
+//! This module defines the size and path-length limits enforced by the
+//! cloud drive. Items that exceed them are rejected locally with a
+//! [`ReasonCode`] instead of being sent to the API, which would only fail
+//! with an opaque error after the upload.
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use super::reason::ReasonCode;
+
+/// Largest file OneDrive accepts (250 GB)
+pub const ONEDRIVE_MAX_FILE_SIZE: u64 = 250 * 1024 * 1024 * 1024;
+
+/// Longest path OneDrive accepts, in characters, excluding the leading `/`
+pub const ONEDRIVE_MAX_PATH_LENGTH: usize = 400;
+
+/// Limits an item must respect to be uploaded
+///
+/// Defaults to OneDrive's limits; business tenants with different limits
+/// override them through the `limits` configuration section.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProviderLimits {
+    /// Largest file size in bytes
+    pub max_file_size: u64,
+    /// Longest remote path in characters
+    pub max_path_length: usize,
+}
+
+impl Default for ProviderLimits {
+    fn default() -> Self {
+        Self {
+            max_file_size: ONEDRIVE_MAX_FILE_SIZE,
+            max_path_length: ONEDRIVE_MAX_PATH_LENGTH,
+        }
+    }
+}
+
+impl ProviderLimits {
+    /// Checks an item against the limits
+    ///
+    /// `remote_path` is the path on the drive (e.g. `/Documents/a.txt`)
+    /// and `size` the file size in bytes (0 for directories). Returns the
+    /// first limit exceeded, path length first.
+    pub fn check(&self, remote_path: &str, size: u64) -> Result<(), LimitViolation> {
+        let length = remote_path.trim_start_matches('/').chars().count();
+        if length > self.max_path_length {
+            return Err(LimitViolation::PathTooLong {
+                length,
+                max: self.max_path_length,
+            });
+        }
+        if size > self.max_file_size {
+            return Err(LimitViolation::FileTooLarge {
+                size,
+                max: self.max_file_size,
+            });
+        }
+        Ok(())
+    }
+}
+
+/// A provider limit exceeded by an item
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LimitViolation {
+    /// The remote path has more characters than allowed
+    PathTooLong { length: usize, max: usize },
+    /// The file is larger than allowed
+    FileTooLarge { size: u64, max: u64 },
+}
+
+impl LimitViolation {
+    /// Returns the reason code recorded for the item
+    pub fn reason(&self) -> ReasonCode {
+        match self {
+            LimitViolation::PathTooLong { .. } => ReasonCode::PathTooLong,
+            LimitViolation::FileTooLarge { .. } => ReasonCode::FileTooLarge,
+        }
+    }
+}
+
+impl fmt::Display for LimitViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LimitViolation::PathTooLong { length, max } => write!(
+                f,
+                "Path is {length} characters long, OneDrive allows at most {max}"
+            ),
+            LimitViolation::FileTooLarge { size, max } => write!(
+                f,
+                "File is {size} bytes, OneDrive allows at most {max} bytes"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for LimitViolation {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_within_limits() {
+        let limits = ProviderLimits::default();
+        assert_eq!(limits.check("/Documents/report.pdf", 1024), Ok(()));
+        assert_eq!(
+            limits.check(&format!("/{}", "a".repeat(400)), ONEDRIVE_MAX_FILE_SIZE),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn test_path_too_long_counts_characters() {
+        let limits = ProviderLimits::default();
+        // 401 two-byte characters: over the limit in characters, not just bytes
+        let path = format!("/{}", "é".repeat(401));
+        let violation = limits.check(&path, 0).unwrap_err();
+        assert_eq!(
+            violation,
+            LimitViolation::PathTooLong {
+                length: 401,
+                max: 400
+            }
+        );
+        assert_eq!(violation.reason(), ReasonCode::PathTooLong);
+        assert_eq!(limits.check(&format!("/{}", "é".repeat(200)), 0), Ok(()));
+    }
+
+    #[test]
+    fn test_file_too_large() {
+        let limits = ProviderLimits::default();
+        let violation = limits
+            .check("/backup.img", ONEDRIVE_MAX_FILE_SIZE + 1)
+            .unwrap_err();
+        assert_eq!(violation.reason(), ReasonCode::FileTooLarge);
+        assert!(violation.to_string().contains("at most"));
+    }
+
+    #[test]
+    fn test_overridden_limits() {
+        let limits = ProviderLimits {
+            max_file_size: 100,
+            max_path_length: 10,
+        };
+        assert!(limits.check("/small.txt", 100).is_ok());
+        assert!(limits.check("/small.txt", 101).is_err());
+        assert!(limits.check("/a-longer-name.txt", 1).is_err());
+    }
+}
@@ -0,0 +1,60 @@
+//! Mime-type detection
+//!
+//! This module determines the mime type sent with uploads and stored on
+//! [`SyncItem`](super::sync_item::SyncItem)s. The content is sniffed first,
+//! since extensions are often missing or wrong; the file name is only used
+//! when the content has no recognizable signature (e.g. plain text).
+
+/// Mime type for content that could not be identified
+pub const DEFAULT_MIME_TYPE: &str = "application/octet-stream";
+
+/// Detects the mime type of a file from its content and name
+///
+/// Only the first bytes of `content` are inspected, so a prefix of a large
+/// file is enough. Falls back to [`DEFAULT_MIME_TYPE`].
+pub fn detect_mime_type(file_name: &str, content: &[u8]) -> String {
+    if let Some(kind) = infer::get(content) {
+        return kind.mime_type().to_string();
+    }
+    mime_guess::from_path(file_name)
+        .first()
+        .map(|mime| mime.essence_str().to_string())
+        .unwrap_or_else(|| DEFAULT_MIME_TYPE.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PNG_HEADER: &[u8] = b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR";
+
+    #[test]
+    fn test_detects_png_from_content() {
+        assert_eq!(detect_mime_type("photo.png", PNG_HEADER), "image/png");
+        // The content wins over a misleading or missing extension
+        assert_eq!(detect_mime_type("photo.txt", PNG_HEADER), "image/png");
+        assert_eq!(detect_mime_type("photo", PNG_HEADER), "image/png");
+    }
+
+    #[test]
+    fn test_detects_pdf_from_content() {
+        let pdf = b"%PDF-1.7\n%\xe2\xe3\xcf\xd3\n1 0 obj";
+        assert_eq!(detect_mime_type("report", pdf), "application/pdf");
+    }
+
+    #[test]
+    fn test_falls_back_to_extension() {
+        assert_eq!(detect_mime_type("notes.txt", b"plain text"), "text/plain");
+        assert_eq!(detect_mime_type("empty.pdf", b""), "application/pdf");
+    }
+
+    #[test]
+    fn test_unknown_binary_is_octet_stream() {
+        let data = [0x13, 0x37, 0x00, 0xff, 0x42, 0x00, 0x01];
+        assert_eq!(detect_mime_type("blob", &data), DEFAULT_MIME_TYPE);
+        assert_eq!(
+            detect_mime_type("data.unknownext", &data),
+            DEFAULT_MIME_TYPE
+        );
+    }
+}
@@ -4,24 +4,50 @@
 //! - Newtypes for type-safe identifiers and validated domain types
 //! - Account management types
 //! - Audit entries for tracking operations
+//! - Clock skew between the local machine and the cloud provider
 //! - Conflict detection and resolution types
+//! - Glob patterns for path rules
+//! - Cloud provider size and path-length limits
+//! - OneDrive quickXorHash content hashing
+//! - Mime-type detection
+//! - Drive storage quota
+//! - Reason codes for failed or skipped items
 //! - Session management types
+//! - Sync history records
 //! - Sync item types
 //! - Domain-specific error types
 
 pub mod account;
 pub mod audit;
+pub mod clock;
 pub mod conflict;
 pub mod errors;
+pub mod glob;
+pub mod limits;
+pub mod mime;
 pub mod newtypes;
+pub mod quickxor;
+pub mod quota;
+pub mod reason;
 pub mod session;
+pub mod sync_history;
 pub mod sync_item;
 
 // Re-export commonly used types
 pub use account::{Account, AccountState};
-pub use audit::{AuditAction, AuditEntry, AuditResult};
+pub use audit::{AuditAction, AuditEntry, AuditResult, TransitionTrigger};
+pub use clock::{ClockSkew, CLOCK_SKEW_WARNING_SECS};
 pub use conflict::{Conflict, Resolution, ResolutionSource, VersionInfo};
 pub use errors::DomainError;
+pub use glob::GlobPattern;
+pub use limits::{
+    LimitViolation, ProviderLimits, ONEDRIVE_MAX_FILE_SIZE, ONEDRIVE_MAX_PATH_LENGTH,
+};
+pub use mime::{detect_mime_type, DEFAULT_MIME_TYPE};
 pub use newtypes::*;
+pub use quickxor::QuickXorHash;
+pub use quota::{DriveQuota, QuotaLevel, QUOTA_CRITICAL_PERCENT, QUOTA_NEARING_PERCENT};
+pub use reason::ReasonCode;
 pub use session::{SessionError, SessionStatus, SyncSession};
-pub use sync_item::{ErrorInfo, ItemMetadata, ItemState, Permissions, SyncItem};
+pub use sync_history::{SyncHistoryEntry, MAX_SYNC_HISTORY_ENTRIES};
+pub use sync_item::{ErrorInfo, ItemFacets, ItemMetadata, ItemState, Permissions, SyncItem};
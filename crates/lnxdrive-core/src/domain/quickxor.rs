@@ -0,0 +1,119 @@
+//! OneDrive-compatible quickXorHash
+//!
+//! Shared by the sync adapter (local change detection) and the FUSE
+//! hydration path (verifying assembled downloads), so both compute the
+//! same digest that OneDrive reports in `file.hashes.quickXorHash`.
+
+use base64::Engine;
+
+use super::{errors::DomainError, newtypes::FileHash};
+
+/// OneDrive-compatible quickXorHash algorithm.
+///
+/// The algorithm works on a 160-bit (20-byte) hash state. For each input
+/// byte, it is XOR-ed into the state at the current *bit* position and the
+/// position advances by 11 bits (mod 160). After processing all input bytes
+/// the total file length (as a little-endian `u64`) is XOR-ed into the
+/// first 8 bytes of the state. The final 20-byte result is base64-encoded.
+#[derive(Debug, Clone)]
+pub struct QuickXorHash {
+    data: [u8; 20],
+    shift: usize,
+    length: u64,
+}
+
+impl QuickXorHash {
+    /// Width of the hash in bits.
+    const WIDTH_BITS: usize = 160;
+
+    /// Number of bits the position advances per input byte.
+    const SHIFT_STEP: usize = 11;
+
+    /// Creates an empty hash state.
+    pub fn new() -> Self {
+        Self {
+            data: [0u8; 20],
+            shift: 0,
+            length: 0,
+        }
+    }
+
+    /// Feeds the next block of input into the hash.
+    pub fn update(&mut self, input: &[u8]) {
+        for &byte in input {
+            let byte_pos = self.shift / 8;
+            let bit_offset = self.shift % 8;
+
+            self.data[byte_pos % 20] ^= byte << bit_offset;
+            if bit_offset > 0 {
+                self.data[(byte_pos + 1) % 20] ^= byte >> (8 - bit_offset);
+            }
+
+            self.shift = (self.shift + Self::SHIFT_STEP) % Self::WIDTH_BITS;
+        }
+        self.length += input.len() as u64;
+    }
+
+    /// Returns the raw 20-byte digest.
+    pub fn finalize(mut self) -> [u8; 20] {
+        // XOR the total length (little-endian u64) into the first 8 bytes.
+        let length_bytes = self.length.to_le_bytes();
+        for (i, &lb) in length_bytes.iter().enumerate() {
+            self.data[i] ^= lb;
+        }
+        self.data
+    }
+
+    /// Returns the digest as a [`FileHash`] (base64, as reported by OneDrive).
+    pub fn finalize_hash(self) -> Result<FileHash, DomainError> {
+        FileHash::new(base64::engine::general_purpose::STANDARD.encode(self.finalize()))
+    }
+}
+
+impl Default for QuickXorHash {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_empty_input_is_all_zero() {
+        assert_eq!(QuickXorHash::new().finalize(), [0u8; 20]);
+    }
+
+    #[test]
+    fn test_split_updates_match_single_update() {
+        let data: Vec<u8> = (0..=255u8).cycle().take(10_000).collect();
+
+        let mut whole = QuickXorHash::new();
+        whole.update(&data);
+
+        let mut split = QuickXorHash::new();
+        for chunk in data.chunks(333) {
+            split.update(chunk);
+        }
+
+        assert_eq!(whole.finalize(), split.finalize());
+    }
+
+    #[test]
+    fn test_length_is_mixed_into_digest() {
+        let mut one = QuickXorHash::new();
+        one.update(&[0u8]);
+        let mut two = QuickXorHash::new();
+        two.update(&[0u8, 0u8]);
+        assert_ne!(one.finalize(), two.finalize());
+    }
+
+    #[test]
+    fn test_finalize_hash_is_valid_file_hash() {
+        let mut hasher = QuickXorHash::new();
+        hasher.update(b"hello world");
+        let hash = hasher.finalize_hash().unwrap();
+        assert_eq!(hash.as_str().len(), 28);
+    }
+}
@@ -0,0 +1,109 @@
+//! DriveQuota domain type
+//!
+//! This module defines the storage quota reported by the cloud drive and
+//! the usage levels at which the user is warned that it is running full.
+
+use serde::{Deserialize, Serialize};
+
+/// Usage percentage at which the quota counts as nearly full
+pub const QUOTA_NEARING_PERCENT: f64 = 90.0;
+
+/// Usage percentage at which the quota counts as critically full
+pub const QUOTA_CRITICAL_PERCENT: f64 = 95.0;
+
+/// How full the drive is, ordered from least to most severe
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum QuotaLevel {
+    /// Below [`QUOTA_NEARING_PERCENT`], or no limit
+    Normal,
+    /// At least [`QUOTA_NEARING_PERCENT`] used
+    Nearing,
+    /// At least [`QUOTA_CRITICAL_PERCENT`] used
+    Critical,
+}
+
+/// Storage usage of a cloud drive
+///
+/// A `total` of 0 means the drive reports no limit, which is the case for
+/// some business plans.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DriveQuota {
+    /// Bytes used
+    pub used: u64,
+    /// Bytes available in total (0 = unlimited)
+    pub total: u64,
+}
+
+impl DriveQuota {
+    /// Creates a quota from used and total bytes (total 0 = unlimited)
+    pub fn new(used: u64, total: u64) -> Self {
+        Self { used, total }
+    }
+
+    /// Creates a quota for a drive without a storage limit
+    pub fn unlimited(used: u64) -> Self {
+        Self { used, total: 0 }
+    }
+
+    /// Returns true if the drive has no storage limit
+    pub fn is_unlimited(&self) -> bool {
+        self.total == 0
+    }
+
+    /// Returns the bytes still available, or `None` if unlimited
+    pub fn remaining(&self) -> Option<u64> {
+        (!self.is_unlimited()).then(|| self.total.saturating_sub(self.used))
+    }
+
+    /// Returns the percentage used (0.0 to 100.0), or `None` if unlimited
+    pub fn percent_used(&self) -> Option<f64> {
+        (!self.is_unlimited()).then(|| (self.used as f64 / self.total as f64 * 100.0).min(100.0))
+    }
+
+    /// Returns how full the drive is
+    pub fn level(&self) -> QuotaLevel {
+        match self.percent_used() {
+            Some(p) if p >= QUOTA_CRITICAL_PERCENT => QuotaLevel::Critical,
+            Some(p) if p >= QUOTA_NEARING_PERCENT => QuotaLevel::Nearing,
+            _ => QuotaLevel::Normal,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GB: u64 = 1024 * 1024 * 1024;
+
+    #[test]
+    fn test_limited_quota() {
+        let quota = DriveQuota::new(4 * GB, 5 * GB);
+        assert!(!quota.is_unlimited());
+        assert_eq!(quota.remaining(), Some(GB));
+        assert_eq!(quota.percent_used(), Some(80.0));
+        assert_eq!(quota.level(), QuotaLevel::Normal);
+    }
+
+    #[test]
+    fn test_unlimited_quota() {
+        let quota = DriveQuota::unlimited(100 * GB);
+        assert!(quota.is_unlimited());
+        assert_eq!(quota.remaining(), None);
+        assert_eq!(quota.percent_used(), None);
+        assert_eq!(quota.level(), QuotaLevel::Normal);
+    }
+
+    #[test]
+    fn test_quota_levels() {
+        assert_eq!(DriveQuota::new(89, 100).level(), QuotaLevel::Normal);
+        assert_eq!(DriveQuota::new(90, 100).level(), QuotaLevel::Nearing);
+        assert_eq!(DriveQuota::new(95, 100).level(), QuotaLevel::Critical);
+        // Over quota (e.g. after a plan downgrade)
+        let over = DriveQuota::new(120, 100);
+        assert_eq!(over.level(), QuotaLevel::Critical);
+        assert_eq!(over.remaining(), Some(0));
+        assert!(QuotaLevel::Critical > QuotaLevel::Nearing);
+    }
+}
@@ -0,0 +1,201 @@
+//! ReasonCode domain type
+//!
+//! This module defines the stable, machine-readable codes that explain why
+//! an item failed or was skipped. They are stored in [`ErrorInfo`] and
+//! audit entries, so UIs can filter and localize on them instead of
+//! parsing messages.
+//!
+//! [`ErrorInfo`]: super::sync_item::ErrorInfo
+
+use std::{fmt, io, str::FromStr};
+
+use serde::{Deserialize, Serialize};
+
+/// Why an operation on an item failed or was skipped
+///
+/// The string form (see [`ReasonCode::as_str`]) is stable: it is persisted
+/// and exposed over the CLI and D-Bus, so existing codes must not change.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ReasonCode {
+    /// The network was unreachable or a request timed out
+    NetworkError,
+    /// Authentication failed or the token expired
+    AuthError,
+    /// The server throttled the request
+    RateLimited,
+    /// The local and remote versions conflict
+    Conflict,
+    /// The file does not fit in the remaining storage quota
+    QuotaExceeded,
+    /// The file is larger than the provider allows
+    FileTooLarge,
+    /// The path is longer than the provider allows
+    PathTooLong,
+    /// The name contains characters or words the provider rejects
+    InvalidName,
+    /// The item is locked, locally by another process or in the cloud
+    Locked,
+    /// The local filesystem or the provider denied access
+    PermissionDenied,
+    /// A conflict could not be resolved automatically
+    MergeFailed,
+    /// No more specific code applies
+    Unknown,
+}
+
+impl ReasonCode {
+    /// Every code, in declaration order
+    pub const ALL: &'static [ReasonCode] = &[
+        ReasonCode::NetworkError,
+        ReasonCode::AuthError,
+        ReasonCode::RateLimited,
+        ReasonCode::Conflict,
+        ReasonCode::QuotaExceeded,
+        ReasonCode::FileTooLarge,
+        ReasonCode::PathTooLong,
+        ReasonCode::InvalidName,
+        ReasonCode::Locked,
+        ReasonCode::PermissionDenied,
+        ReasonCode::MergeFailed,
+        ReasonCode::Unknown,
+    ];
+
+    /// Returns the stable string code (e.g. `"QUOTA_EXCEEDED"`)
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ReasonCode::NetworkError => "NETWORK_ERROR",
+            ReasonCode::AuthError => "AUTH_ERROR",
+            ReasonCode::RateLimited => "RATE_LIMITED",
+            ReasonCode::Conflict => "CONFLICT",
+            ReasonCode::QuotaExceeded => "QUOTA_EXCEEDED",
+            ReasonCode::FileTooLarge => "FILE_TOO_LARGE",
+            ReasonCode::PathTooLong => "PATH_TOO_LONG",
+            ReasonCode::InvalidName => "INVALID_NAME",
+            ReasonCode::Locked => "LOCKED",
+            ReasonCode::PermissionDenied => "PERMISSION_DENIED",
+            ReasonCode::MergeFailed => "MERGE_FAILED",
+            ReasonCode::Unknown => "UNKNOWN",
+        }
+    }
+
+    /// Returns the code for an HTTP error status of the cloud provider
+    pub fn from_http_status(status: u16) -> Self {
+        match status {
+            401 => ReasonCode::AuthError,
+            403 => ReasonCode::PermissionDenied,
+            408 | 504 => ReasonCode::NetworkError,
+            409 | 412 => ReasonCode::Conflict,
+            414 => ReasonCode::PathTooLong,
+            423 => ReasonCode::Locked,
+            429 | 503 => ReasonCode::RateLimited,
+            507 => ReasonCode::QuotaExceeded,
+            _ => ReasonCode::Unknown,
+        }
+    }
+
+    /// Returns the code for a local I/O error
+    pub fn from_io_error(error: &io::Error) -> Self {
+        match error.kind() {
+            io::ErrorKind::PermissionDenied | io::ErrorKind::ReadOnlyFilesystem => {
+                ReasonCode::PermissionDenied
+            }
+            io::ErrorKind::InvalidFilename => ReasonCode::InvalidName,
+            io::ErrorKind::ResourceBusy | io::ErrorKind::WouldBlock => ReasonCode::Locked,
+            io::ErrorKind::QuotaExceeded => ReasonCode::QuotaExceeded,
+            io::ErrorKind::TimedOut
+            | io::ErrorKind::ConnectionRefused
+            | io::ErrorKind::ConnectionReset
+            | io::ErrorKind::ConnectionAborted
+            | io::ErrorKind::NotConnected
+            | io::ErrorKind::HostUnreachable
+            | io::ErrorKind::NetworkUnreachable => ReasonCode::NetworkError,
+            _ => ReasonCode::Unknown,
+        }
+    }
+}
+
+impl fmt::Display for ReasonCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for ReasonCode {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        ReasonCode::ALL
+            .iter()
+            .copied()
+            .find(|code| code.as_str() == s)
+            .ok_or(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_codes_round_trip() {
+        for code in ReasonCode::ALL {
+            assert_eq!(code.as_str().parse::<ReasonCode>(), Ok(*code));
+            let json = serde_json::to_string(code).unwrap();
+            assert_eq!(json, format!("\"{}\"", code.as_str()));
+        }
+        assert!("E001".parse::<ReasonCode>().is_err());
+    }
+
+    #[test]
+    fn test_codes_are_stable() {
+        let codes: Vec<&str> = ReasonCode::ALL.iter().map(ReasonCode::as_str).collect();
+        assert_eq!(
+            codes,
+            [
+                "NETWORK_ERROR",
+                "AUTH_ERROR",
+                "RATE_LIMITED",
+                "CONFLICT",
+                "QUOTA_EXCEEDED",
+                "FILE_TOO_LARGE",
+                "PATH_TOO_LONG",
+                "INVALID_NAME",
+                "LOCKED",
+                "PERMISSION_DENIED",
+                "MERGE_FAILED",
+                "UNKNOWN",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_from_http_status() {
+        assert_eq!(ReasonCode::from_http_status(401), ReasonCode::AuthError);
+        assert_eq!(
+            ReasonCode::from_http_status(403),
+            ReasonCode::PermissionDenied
+        );
+        assert_eq!(ReasonCode::from_http_status(409), ReasonCode::Conflict);
+        assert_eq!(ReasonCode::from_http_status(423), ReasonCode::Locked);
+        assert_eq!(ReasonCode::from_http_status(429), ReasonCode::RateLimited);
+        assert_eq!(ReasonCode::from_http_status(507), ReasonCode::QuotaExceeded);
+        assert_eq!(ReasonCode::from_http_status(500), ReasonCode::Unknown);
+    }
+
+    #[test]
+    fn test_from_io_error() {
+        let code = |kind| ReasonCode::from_io_error(&io::Error::from(kind));
+        assert_eq!(
+            code(io::ErrorKind::PermissionDenied),
+            ReasonCode::PermissionDenied
+        );
+        assert_eq!(
+            code(io::ErrorKind::InvalidFilename),
+            ReasonCode::InvalidName
+        );
+        assert_eq!(code(io::ErrorKind::ResourceBusy), ReasonCode::Locked);
+        assert_eq!(code(io::ErrorKind::TimedOut), ReasonCode::NetworkError);
+        assert_eq!(code(io::ErrorKind::NotFound), ReasonCode::Unknown);
+    }
+}
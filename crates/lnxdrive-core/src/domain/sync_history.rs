@@ -0,0 +1,136 @@
+//! SyncHistoryEntry domain type
+//!
+//! This module defines the per-cycle record persisted after every sync
+//! cycle so users can inspect trends and intermittent failures over time.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Maximum number of history rows retained by the state repository
+pub const MAX_SYNC_HISTORY_ENTRIES: u32 = 500;
+
+/// Summary of a single completed (or failed) sync cycle
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SyncHistoryEntry {
+    /// Storage identifier, `None` until the entry has been persisted
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<i64>,
+    /// When the cycle started
+    pub started_at: DateTime<Utc>,
+    /// When the cycle finished
+    pub finished_at: DateTime<Utc>,
+    /// Number of files downloaded from the cloud
+    pub files_downloaded: u32,
+    /// Number of files uploaded to the cloud
+    pub files_uploaded: u32,
+    /// Number of files deleted (local or remote)
+    pub files_deleted: u32,
+    /// Bytes downloaded during the cycle
+    pub bytes_downloaded: u64,
+    /// Bytes uploaded during the cycle
+    pub bytes_uploaded: u64,
+    /// Non-fatal per-item errors and, for failed cycles, the fatal error
+    pub errors: Vec<String>,
+    /// Wall-clock duration of the cycle in milliseconds
+    pub duration_ms: u64,
+    /// Whether the cycle ran to completion
+    pub success: bool,
+}
+
+impl SyncHistoryEntry {
+    /// Creates a history entry for a cycle that ran to completion
+    #[allow(clippy::too_many_arguments)]
+    pub fn completed(
+        started_at: DateTime<Utc>,
+        files_downloaded: u32,
+        files_uploaded: u32,
+        files_deleted: u32,
+        bytes_downloaded: u64,
+        bytes_uploaded: u64,
+        errors: Vec<String>,
+        duration_ms: u64,
+    ) -> Self {
+        Self {
+            id: None,
+            started_at,
+            finished_at: Utc::now(),
+            files_downloaded,
+            files_uploaded,
+            files_deleted,
+            bytes_downloaded,
+            bytes_uploaded,
+            errors,
+            duration_ms,
+            success: true,
+        }
+    }
+
+    /// Creates a history entry for a cycle that aborted with an error
+    pub fn failed(started_at: DateTime<Utc>, error: impl Into<String>) -> Self {
+        let finished_at = Utc::now();
+        let duration_ms = (finished_at - started_at).num_milliseconds().max(0) as u64;
+        Self {
+            id: None,
+            started_at,
+            finished_at,
+            files_downloaded: 0,
+            files_uploaded: 0,
+            files_deleted: 0,
+            bytes_downloaded: 0,
+            bytes_uploaded: 0,
+            errors: vec![error.into()],
+            duration_ms,
+            success: false,
+        }
+    }
+
+    /// Total number of files touched by the cycle
+    pub fn total_files(&self) -> u32 {
+        self.files_downloaded + self.files_uploaded + self.files_deleted
+    }
+
+    /// Total number of bytes transferred in either direction
+    pub fn total_bytes(&self) -> u64 {
+        self.bytes_downloaded + self.bytes_uploaded
+    }
+
+    /// Returns true if the cycle completed but reported per-item errors
+    pub fn has_errors(&self) -> bool {
+        !self.errors.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_completed_entry() {
+        let started = Utc::now();
+        let entry = SyncHistoryEntry::completed(started, 2, 1, 3, 2048, 512, vec![], 150);
+        assert!(entry.success);
+        assert!(entry.id.is_none());
+        assert_eq!(entry.total_files(), 6);
+        assert_eq!(entry.total_bytes(), 2560);
+        assert!(!entry.has_errors());
+        assert!(entry.finished_at >= entry.started_at);
+    }
+
+    #[test]
+    fn test_failed_entry() {
+        let entry = SyncHistoryEntry::failed(Utc::now(), "network unreachable");
+        assert!(!entry.success);
+        assert!(entry.has_errors());
+        assert_eq!(entry.errors, vec!["network unreachable".to_string()]);
+        assert_eq!(entry.total_files(), 0);
+    }
+
+    #[test]
+    fn test_serde_roundtrip() {
+        let entry = SyncHistoryEntry::completed(Utc::now(), 1, 0, 0, 10, 0, vec!["x".into()], 5);
+        let json = serde_json::to_string(&entry).unwrap();
+        assert!(!json.contains("\"id\""));
+        let back: SyncHistoryEntry = serde_json::from_str(&json).unwrap();
+        assert_eq!(back, entry);
+    }
+}
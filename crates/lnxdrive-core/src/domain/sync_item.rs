@@ -37,6 +37,7 @@ use serde::{Deserialize, Serialize};
 use super::{
     errors::DomainError,
     newtypes::{FileHash, RemoteId, RemotePath, SyncPath, UniqueId},
+    reason::ReasonCode,
 };
 
 // ============================================================================
@@ -111,6 +112,22 @@ impl ItemState {
         matches!(self, ItemState::Hydrated)
     }
 
+    /// Returns the file-manager emblem token for this state
+    ///
+    /// One of `synced`, `cloud-only`, `syncing`, `conflict`, `error`, or
+    /// `unknown` for items marked for deletion. Used by the `status-emblem`
+    /// command and the `Files.GetEmblem` D-Bus method.
+    pub fn emblem(&self) -> &'static str {
+        match self {
+            ItemState::Hydrated | ItemState::Pinned => "synced",
+            ItemState::Online => "cloud-only",
+            ItemState::Hydrating | ItemState::Modified => "syncing",
+            ItemState::Conflicted => "conflict",
+            ItemState::Error(_) => "error",
+            ItemState::Deleted => "unknown",
+        }
+    }
+
     /// Returns the state name as a string (without error details)
     pub fn name(&self) -> &'static str {
         match self {
@@ -222,6 +239,21 @@ pub struct ItemMetadata {
     etag: Option<String>,
     /// File permissions
     permissions: Permissions,
+    /// Local path of the tracked file this one is a hardlink to
+    ///
+    /// Set for the extra paths of a hardlinked file: their content is
+    /// uploaded once, through the item at this path.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    hardlink_of: Option<SyncPath>,
+    /// Whether a pin of this item is waiting for its content
+    ///
+    /// Recorded before a file is hydrated for pinning and cleared when it
+    /// becomes `Pinned`, so a crash in between does not lose the pin.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pin_requested: bool,
+    /// Descriptive metadata reported by OneDrive (photo, author)
+    #[serde(default, skip_serializing_if = "ItemFacets::is_empty")]
+    facets: ItemFacets,
 }
 
 impl ItemMetadata {
@@ -233,6 +265,9 @@ impl ItemMetadata {
             created_at: Utc::now(),
             etag: None,
             permissions: Permissions::all(),
+            hardlink_of: None,
+            pin_requested: false,
+            facets: ItemFacets::default(),
         }
     }
 
@@ -244,6 +279,9 @@ impl ItemMetadata {
             created_at: Utc::now(),
             etag: None,
             permissions: Permissions::all(),
+            hardlink_of: None,
+            pin_requested: false,
+            facets: ItemFacets::default(),
         }
     }
 
@@ -261,6 +299,9 @@ impl ItemMetadata {
             created_at,
             etag,
             permissions,
+            hardlink_of: None,
+            pin_requested: false,
+            facets: ItemFacets::default(),
         }
     }
 
@@ -308,6 +349,63 @@ impl ItemMetadata {
     pub fn set_mime_type(&mut self, mime_type: Option<String>) {
         self.mime_type = mime_type;
     }
+
+    /// Returns the path of the file this one is a hardlink to
+    pub fn hardlink_of(&self) -> Option<&SyncPath> {
+        self.hardlink_of.as_ref()
+    }
+
+    /// Sets or clears the path of the file this one is a hardlink to
+    pub fn set_hardlink_of(&mut self, primary: Option<SyncPath>) {
+        self.hardlink_of = primary;
+    }
+
+    /// Returns true if the item must become `Pinned` once hydrated
+    pub fn pin_requested(&self) -> bool {
+        self.pin_requested
+    }
+
+    /// Records or clears a pin waiting for the item's content
+    pub fn set_pin_requested(&mut self, requested: bool) {
+        self.pin_requested = requested;
+    }
+
+    /// Returns the descriptive metadata reported by OneDrive
+    pub fn facets(&self) -> &ItemFacets {
+        &self.facets
+    }
+
+    /// Replaces the descriptive metadata reported by OneDrive
+    pub fn set_facets(&mut self, facets: ItemFacets) {
+        self.facets = facets;
+    }
+}
+
+/// Descriptive metadata OneDrive reports for some files
+///
+/// A curated subset of the Graph facets, stored so it can be read without
+/// downloading the file. A field is `None` when OneDrive does not report it.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ItemFacets {
+    /// When the photo was taken (EXIF capture date)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub photo_taken: Option<DateTime<Utc>>,
+    /// Manufacturer of the camera that took the photo
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub camera_make: Option<String>,
+    /// Model of the camera that took the photo
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub camera_model: Option<String>,
+    /// Display name of the user who created the file
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub author: Option<String>,
+}
+
+impl ItemFacets {
+    /// Returns true if no facet is present
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
 }
 
 // ============================================================================
@@ -359,11 +457,21 @@ impl ErrorInfo {
         }
     }
 
+    /// Creates an ErrorInfo whose code is a [`ReasonCode`]
+    pub fn from_reason(reason: ReasonCode, message: impl Into<String>) -> Self {
+        Self::new(reason.as_str(), message)
+    }
+
     /// Returns the error code
     pub fn code(&self) -> &str {
         &self.code
     }
 
+    /// Returns the code as a [`ReasonCode`], if it is one
+    pub fn reason(&self) -> Option<ReasonCode> {
+        self.code.parse().ok()
+    }
+
     /// Returns the error message
     pub fn message(&self) -> &str {
         &self.message
@@ -420,22 +528,30 @@ impl ErrorInfo {
 
     /// Creates a common network error
     pub fn network_error(message: impl Into<String>) -> Self {
-        Self::with_retry("NETWORK_ERROR", message, Duration::seconds(30))
+        Self::with_retry(
+            ReasonCode::NetworkError.as_str(),
+            message,
+            Duration::seconds(30),
+        )
     }
 
     /// Creates a common authentication error
     pub fn auth_error(message: impl Into<String>) -> Self {
-        Self::new("AUTH_ERROR", message)
+        Self::from_reason(ReasonCode::AuthError, message)
     }
 
     /// Creates a common rate limit error
     pub fn rate_limited(retry_after: Duration) -> Self {
-        Self::with_retry("RATE_LIMITED", "Rate limit exceeded", retry_after)
+        Self::with_retry(
+            ReasonCode::RateLimited.as_str(),
+            "Rate limit exceeded",
+            retry_after,
+        )
     }
 
     /// Creates a common conflict error
     pub fn conflict(message: impl Into<String>) -> Self {
-        Self::new("CONFLICT", message)
+        Self::from_reason(ReasonCode::Conflict, message)
     }
 }
 
@@ -494,6 +610,10 @@ pub struct SyncItem {
     /// Hydration progress 0-100 (for Files-On-Demand)
     #[serde(skip_serializing_if = "Option::is_none")]
     hydration_progress: Option<u8>,
+    /// Unix mode bits set locally (e.g. 0o755); OneDrive does not store
+    /// them, so they are only kept in the local state database
+    #[serde(skip_serializing_if = "Option::is_none")]
+    unix_mode: Option<u32>,
 }
 
 // ============================================================================
@@ -544,6 +664,7 @@ impl SyncItem {
             inode: None,
             last_accessed: None,
             hydration_progress: None,
+            unix_mode: None,
         })
     }
 
@@ -681,6 +802,11 @@ impl SyncItem {
         self.last_accessed
     }
 
+    /// Returns the locally set Unix mode bits, if any
+    pub fn unix_mode(&self) -> Option<u32> {
+        self.unix_mode
+    }
+
     /// Returns the hydration progress (0-100)
     pub fn hydration_progress(&self) -> Option<u8> {
         self.hydration_progress
@@ -747,6 +873,11 @@ impl SyncItem {
     pub fn set_hydration_progress(&mut self, progress: Option<u8>) {
         self.hydration_progress = progress;
     }
+
+    /// Sets the Unix mode bits (only the permission bits, `0o7777`, are kept)
+    pub fn set_unix_mode(&mut self, mode: Option<u32>) {
+        self.unix_mode = mode.map(|m| m & 0o7777);
+    }
 }
 
 // ============================================================================
@@ -837,7 +968,7 @@ impl SyncItem {
         // Set error info when entering error state
         if let ItemState::Error(ref reason) = target {
             if self.error_info.is_none() {
-                self.error_info = Some(ErrorInfo::new("UNKNOWN", reason.clone()));
+                self.error_info = Some(ErrorInfo::from_reason(ReasonCode::Unknown, reason.clone()));
             }
         }
 
@@ -846,6 +977,11 @@ impl SyncItem {
             self.mark_synced();
         }
 
+        // A pending pin is fulfilled once the item is pinned
+        if matches!(target, ItemState::Pinned) {
+            self.metadata.pin_requested = false;
+        }
+
         self.state = target;
         Ok(())
     }
@@ -1007,6 +1143,18 @@ mod tests {
             assert!(!ItemState::Deleted.is_local());
         }
 
+        #[test]
+        fn test_emblem() {
+            assert_eq!(ItemState::Hydrated.emblem(), "synced");
+            assert_eq!(ItemState::Pinned.emblem(), "synced");
+            assert_eq!(ItemState::Online.emblem(), "cloud-only");
+            assert_eq!(ItemState::Hydrating.emblem(), "syncing");
+            assert_eq!(ItemState::Modified.emblem(), "syncing");
+            assert_eq!(ItemState::Conflicted.emblem(), "conflict");
+            assert_eq!(ItemState::Error("x".to_string()).emblem(), "error");
+            assert_eq!(ItemState::Deleted.emblem(), "unknown");
+        }
+
         #[test]
         fn test_is_placeholder() {
             assert!(ItemState::Online.is_placeholder());
@@ -1165,6 +1313,59 @@ mod tests {
             assert!(meta.permissions().read);
             assert!(!meta.permissions().write);
         }
+
+        #[test]
+        fn test_hardlink_of_is_optional_in_json() {
+            let meta = ItemMetadata::new_file(None);
+            let json = serde_json::to_string(&meta).unwrap();
+            assert!(!json.contains("hardlink_of"));
+            let restored: ItemMetadata = serde_json::from_str(&json).unwrap();
+            assert_eq!(restored.hardlink_of(), None);
+
+            let mut linked = meta;
+            let primary = SyncPath::new(PathBuf::from("/home/user/OneDrive/a.txt")).unwrap();
+            linked.set_hardlink_of(Some(primary.clone()));
+            let json = serde_json::to_string(&linked).unwrap();
+            let restored: ItemMetadata = serde_json::from_str(&json).unwrap();
+            assert_eq!(restored.hardlink_of(), Some(&primary));
+        }
+
+        #[test]
+        fn test_facets_round_trip_and_are_optional_in_json() {
+            let mut meta = ItemMetadata::new_file(Some("image/jpeg".to_string()));
+            assert!(!serde_json::to_string(&meta).unwrap().contains("facets"));
+
+            let facets = ItemFacets {
+                photo_taken: Some(Utc::now()),
+                camera_make: Some("Canon".to_string()),
+                author: Some("Ana".to_string()),
+                ..ItemFacets::default()
+            };
+            meta.set_facets(facets.clone());
+            let json = serde_json::to_string(&meta).unwrap();
+            let restored: ItemMetadata = serde_json::from_str(&json).unwrap();
+            assert_eq!(restored.facets(), &facets);
+            assert!(!json.contains("camera_model"));
+        }
+
+        #[test]
+        fn test_pin_requested_is_cleared_when_pinned() {
+            let mut item = create_test_sync_item();
+            assert!(!serde_json::to_string(item.metadata())
+                .unwrap()
+                .contains("pin_requested"));
+
+            item.metadata_mut().set_pin_requested(true);
+            let json = serde_json::to_string(item.metadata()).unwrap();
+            let restored: ItemMetadata = serde_json::from_str(&json).unwrap();
+            assert!(restored.pin_requested());
+
+            item.transition_to(ItemState::Hydrating).unwrap();
+            item.transition_to(ItemState::Hydrated).unwrap();
+            assert!(item.metadata().pin_requested());
+            item.transition_to(ItemState::Pinned).unwrap();
+            assert!(!item.metadata().pin_requested());
+        }
     }
 
     mod error_info_tests {
@@ -1227,6 +1428,15 @@ mod tests {
 
             let conflict = ErrorInfo::conflict("Versions differ");
             assert_eq!(conflict.code(), "CONFLICT");
+            assert_eq!(conflict.reason(), Some(ReasonCode::Conflict));
+        }
+
+        #[test]
+        fn test_from_reason() {
+            let error = ErrorInfo::from_reason(ReasonCode::QuotaExceeded, "No space left");
+            assert_eq!(error.code(), "QUOTA_EXCEEDED");
+            assert_eq!(error.reason(), Some(ReasonCode::QuotaExceeded));
+            assert_eq!(ErrorInfo::new("E001", "Test error").reason(), None);
         }
 
         #[test]
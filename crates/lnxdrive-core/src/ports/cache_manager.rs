@@ -0,0 +1,113 @@
+//! Cache manager port (driven/secondary port)
+//!
+//! This module defines the interface through which the daemon inspects and
+//! trims the local content cache of the Files-on-Demand filesystem, and
+//! pins files to keep them available offline. The implementation lives with the mounted filesystem, so eviction goes
+//! through the same checks (open handles, item state) as automatic
+//! dehydration.
+//!
+//! ## Design Notes
+//!
+//! - Uses `anyhow::Result` because failures are adapter-specific.
+//! - Reports are serializable so adapters can pass them on as JSON
+//!   (e.g. over D-Bus).
+
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+/// Cached bytes below one top-level folder of the drive
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FolderUsage {
+    /// Top-level folder name, or `/` for files in the drive root
+    pub folder: String,
+    /// Cached bytes of the files below the folder
+    pub bytes: u64,
+    /// Number of cached files below the folder
+    pub files: u64,
+}
+
+/// Breakdown of the space used by the content cache
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CacheUsage {
+    /// Bytes used on disk by the cache, as measured on the filesystem
+    pub total_bytes: u64,
+    /// Cached bytes of pinned files
+    pub pinned_bytes: u64,
+    /// Cached bytes of hydrated files that may be dehydrated
+    pub evictable_bytes: u64,
+    /// Cached bytes of files with local changes not yet uploaded
+    pub modified_bytes: u64,
+    /// Per top-level folder breakdown, largest first
+    pub folders: Vec<FolderUsage>,
+}
+
+/// Which files a cache clean may dehydrate
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CacheCleanOptions {
+    /// Keep pinned files; otherwise they are unpinned and dehydrated too
+    pub keep_pinned: bool,
+    /// Only dehydrate files not accessed for this many days (0 = any)
+    pub older_than_days: u32,
+}
+
+/// Outcome of a cache clean
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CacheCleanReport {
+    /// Number of files dehydrated
+    pub dehydrated: u64,
+    /// Bytes freed in the cache
+    pub bytes_freed: u64,
+    /// Number of files left alone (open, modified, state changed)
+    pub skipped: u64,
+    /// Error messages for files that could not be dehydrated
+    pub errors: Vec<String>,
+}
+
+/// Outcome of a cache integrity check
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CacheVerifyReport {
+    /// Number of cached files checked
+    pub checked: u64,
+    /// Number of damaged files removed from the cache (to be downloaded
+    /// again on next access)
+    pub healed: u64,
+    /// Error messages for files that could not be checked or healed
+    pub errors: Vec<String>,
+}
+
+/// Outcome of pinning or unpinning a file or folder
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CachePinReport {
+    /// Number of files now pinned (or unpinned)
+    pub files: u64,
+    /// Number of files that could not be pinned (or unpinned)
+    pub failed: u64,
+}
+
+/// Port trait for inspecting and trimming the content cache
+#[async_trait::async_trait]
+pub trait ICacheManager: Send + Sync {
+    /// Returns the space used by the cache and what it is used for
+    async fn usage(&self) -> anyhow::Result<CacheUsage>;
+
+    /// Dehydrates the cached files selected by `options`
+    ///
+    /// Open files and files with local changes are never dehydrated.
+    async fn clean(&self, options: CacheCleanOptions) -> anyhow::Result<CacheCleanReport>;
+
+    /// Checks every cached file against its metadata and content hash and
+    /// removes the damaged ones
+    async fn verify(&self) -> anyhow::Result<CacheVerifyReport>;
+
+    /// Keeps the file or folder at `path` available offline, downloading
+    /// what is not cached yet
+    ///
+    /// `path` is an absolute path inside the mounted filesystem.
+    async fn pin(&self, path: &Path) -> anyhow::Result<CachePinReport>;
+
+    /// Releases the pin on the file or folder at `path`
+    ///
+    /// The content stays cached until it is evicted.
+    async fn unpin(&self, path: &Path) -> anyhow::Result<CachePinReport>;
+}
@@ -16,7 +16,11 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
-use crate::domain::newtypes::{DeltaToken, RemoteId, RemotePath};
+use crate::domain::{
+    newtypes::{DeltaToken, RemoteId, RemotePath},
+    quota::DriveQuota,
+    sync_item::ItemFacets,
+};
 
 // ============================================================================
 // T048: AuthFlow enum
@@ -118,6 +122,156 @@ pub struct DeltaItem {
     pub is_directory: bool,
     /// Parent folder ID (None for root items)
     pub parent_id: Option<String>,
+    /// Descriptive metadata such as photo EXIF data and author
+    #[serde(default)]
+    pub facets: ItemFacets,
+}
+
+/// A remote folder listed by [`ICloudProvider::list_folders`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RemoteFolder {
+    /// Folder name
+    pub name: String,
+    /// Number of items (files and folders) directly inside the folder
+    pub child_count: u64,
+}
+
+/// One page of the sub-folders of a remote folder
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FolderPage {
+    /// Sub-folders on this page
+    pub folders: Vec<RemoteFolder>,
+    /// Passed back to [`ICloudProvider::list_folders`] to get the next page
+    /// (None if this is the last page)
+    pub continuation: Option<String>,
+}
+
+/// One page of the items directly inside a remote folder
+#[derive(Debug, Clone, Default)]
+pub struct ItemPage {
+    /// Files and folders on this page
+    pub items: Vec<DeltaItem>,
+    /// Passed back to [`ICloudProvider::list_children`] to get the next page
+    /// (None if this is the last page)
+    pub continuation: Option<String>,
+}
+
+/// Source of the pages of a delta query after the first one
+///
+/// Implementations may fetch ahead, so the next page is already on its way
+/// while the caller applies the current one.
+#[async_trait::async_trait]
+pub trait DeltaPageSource: Send {
+    /// Returns the next page, or `None` after the last one
+    async fn next_page(&mut self) -> Option<anyhow::Result<DeltaResponse>>;
+}
+
+/// A delta query delivered page by page
+///
+/// Only the last page carries a `delta_link`. Callers must not persist a
+/// token before every page was fetched and applied: after an error, the
+/// query is repeated from the previous token.
+pub struct DeltaPages {
+    first: Option<DeltaResponse>,
+    rest: Option<Box<dyn DeltaPageSource>>,
+}
+
+impl DeltaPages {
+    /// A query whose first page is `first` and whose remaining pages come
+    /// from `rest`
+    pub fn new(first: DeltaResponse, rest: Box<dyn DeltaPageSource>) -> Self {
+        Self {
+            first: Some(first),
+            rest: Some(rest),
+        }
+    }
+
+    /// A query answered by a single response
+    pub fn single(response: DeltaResponse) -> Self {
+        Self {
+            first: Some(response),
+            rest: None,
+        }
+    }
+
+    /// Returns the next page, or `None` after the last one
+    pub async fn next_page(&mut self) -> Option<anyhow::Result<DeltaResponse>> {
+        if let Some(first) = self.first.take() {
+            return Some(Ok(first));
+        }
+        self.rest.as_mut()?.next_page().await
+    }
+
+    /// Fetches the remaining pages and merges them into one response
+    ///
+    /// # Errors
+    /// Returns the first error of any page.
+    pub async fn into_response(mut self) -> anyhow::Result<DeltaResponse> {
+        let mut response = DeltaResponse {
+            items: Vec::new(),
+            next_link: None,
+            delta_link: None,
+        };
+        while let Some(page) = self.next_page().await {
+            let page = page?;
+            response.items.extend(page.items);
+            response.delta_link = page.delta_link;
+        }
+        Ok(response)
+    }
+}
+
+impl std::fmt::Debug for DeltaPages {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DeltaPages")
+            .field("first", &self.first)
+            .field("has_more", &self.rest.is_some())
+            .finish()
+    }
+}
+
+/// Error returned by [`ICloudProvider::get_delta`] for a token the provider
+/// no longer accepts
+///
+/// The caller must discard its token and request a full listing with
+/// `get_delta(None)`. Adapters return it wrapped in `anyhow::Error`; use
+/// [`is_delta_token_expired`] to detect it through any added context.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+#[error("Delta token expired")]
+pub struct DeltaTokenExpired;
+
+/// Returns true if `err` (or any error it wraps) is [`DeltaTokenExpired`]
+pub fn is_delta_token_expired(err: &anyhow::Error) -> bool {
+    err.chain().any(|cause| cause.is::<DeltaTokenExpired>())
+}
+
+/// Error returned by [`ICloudProvider::delete_item`] for an item that no
+/// longer exists
+///
+/// Someone else deleted the item first, so the caller's deletion already
+/// happened. Use [`is_remote_item_not_found`] to detect it through any
+/// added context.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+#[error("Remote item not found")]
+pub struct RemoteItemNotFound;
+
+/// Returns true if `err` (or any error it wraps) is [`RemoteItemNotFound`]
+pub fn is_remote_item_not_found(err: &anyhow::Error) -> bool {
+    err.chain().any(|cause| cause.is::<RemoteItemNotFound>())
+}
+
+/// Error returned by [`ICloudProvider::append_file`] when the provider
+/// cannot add bytes to an existing file
+///
+/// The caller uploads the whole file instead. Use
+/// [`is_append_not_supported`] to detect it through any added context.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+#[error("Appending to a file is not supported")]
+pub struct AppendNotSupported;
+
+/// Returns true if `err` (or any error it wraps) is [`AppendNotSupported`]
+pub fn is_append_not_supported(err: &anyhow::Error) -> bool {
+    err.chain().any(|cause| cause.is::<AppendNotSupported>())
 }
 
 // ============================================================================
@@ -146,6 +300,12 @@ pub struct UserInfo {
 // T052: ICloudProvider trait
 // ============================================================================
 
+/// Check run before the final chunk of an upload session is sent
+///
+/// An error abandons the session without committing the file; it is
+/// returned from the upload as is.
+pub type CommitCheck = Box<dyn Fn() -> anyhow::Result<()> + Send + Sync>;
+
 /// Port trait for cloud storage provider operations
 ///
 /// This is the primary interface for all interactions with the cloud storage
@@ -184,6 +344,7 @@ pub trait ICloudProvider: Send + Sync {
     ///
     /// If `token` is `None`, returns all items (initial sync).
     /// If `token` is `Some`, returns only items changed since that token.
+    /// A token the provider no longer accepts fails with [`DeltaTokenExpired`].
     ///
     /// # Arguments
     /// * `token` - Delta token from a previous query (None for initial sync)
@@ -192,6 +353,28 @@ pub trait ICloudProvider: Send + Sync {
     /// A response containing changed items and continuation/delta tokens
     async fn get_delta(&self, token: Option<&DeltaToken>) -> anyhow::Result<DeltaResponse>;
 
+    /// Queries for changes like [`get_delta`](Self::get_delta), but hands
+    /// out the result page by page
+    ///
+    /// Providers with paginated deltas should override this to return the
+    /// first page as soon as it arrives and fetch the following ones in the
+    /// background. The default fetches everything with `get_delta` and
+    /// returns it as a single page. Fails like `get_delta` for an expired
+    /// token.
+    async fn get_delta_pages(&self, token: Option<&DeltaToken>) -> anyhow::Result<DeltaPages> {
+        Ok(DeltaPages::single(self.get_delta(token).await?))
+    }
+
+    /// Continues a delta query from the `next_link` of one of its pages
+    ///
+    /// Lets a sync interrupted in the middle of a long delta (e.g. by a
+    /// restart) read the remaining pages instead of starting over. The
+    /// default cannot resume and fails, in which case the caller repeats
+    /// the query from its last delta token.
+    async fn resume_delta_pages(&self, next_link: &str) -> anyhow::Result<DeltaPages> {
+        anyhow::bail!("Cannot resume the delta query at {next_link}")
+    }
+
     /// Downloads a file's content by its remote ID
     ///
     /// # Arguments
@@ -238,6 +421,71 @@ pub trait ICloudProvider: Send + Sync {
         progress: Option<Box<dyn Fn(u64, u64) + Send>>,
     ) -> anyhow::Result<DeltaItem>;
 
+    /// Uploads a large file like [`upload_file_session`](Self::upload_file_session),
+    /// running `commit_check` before the final chunk is sent
+    ///
+    /// Used to make sure the source file did not change while its chunks
+    /// were sent. Providers that send chunks themselves should override
+    /// this; the default runs the check once before uploading.
+    async fn upload_file_session_checked(
+        &self,
+        parent_path: &RemotePath,
+        name: &str,
+        data: &[u8],
+        progress: Option<Box<dyn Fn(u64, u64) + Send>>,
+        commit_check: CommitCheck,
+    ) -> anyhow::Result<DeltaItem> {
+        commit_check()?;
+        self.upload_file_session(parent_path, name, data, progress)
+            .await
+    }
+
+    /// Returns true if [`append_file`](Self::append_file) is implemented
+    ///
+    /// Checked before reading a grown file to append it, so providers that
+    /// cannot append (the default, and OneDrive) upload it whole right
+    /// away.
+    fn supports_append(&self) -> bool {
+        false
+    }
+
+    /// Appends `data` to the file `remote_id`, which must be `offset` bytes
+    /// long, without sending its existing content again
+    ///
+    /// Used when a large file only grew at the end. OneDrive cannot do
+    /// this: an upload session always creates a new version from all of
+    /// its bytes, so there is no way to patch or extend a stored file. The
+    /// default therefore fails with [`AppendNotSupported`]; providers with
+    /// an append operation override it along with
+    /// [`supports_append`](Self::supports_append).
+    ///
+    /// # Returns
+    /// Metadata of the extended file
+    async fn append_file(
+        &self,
+        remote_id: &RemoteId,
+        offset: u64,
+        data: &[u8],
+    ) -> anyhow::Result<DeltaItem> {
+        let _ = (remote_id, offset, data);
+        Err(AppendNotSupported.into())
+    }
+
+    /// Creates the folder `name` in `parent_path`
+    ///
+    /// Folders are never uploaded as content: an empty local folder only
+    /// exists in the cloud once created through this call. If a folder of
+    /// that name already exists, for example because a file upload below
+    /// it created it implicitly, its metadata is returned instead.
+    ///
+    /// # Returns
+    /// Metadata of the folder
+    async fn create_folder(
+        &self,
+        parent_path: &RemotePath,
+        name: &str,
+    ) -> anyhow::Result<DeltaItem>;
+
     /// Retrieves metadata for a specific item by its remote ID
     ///
     /// # Arguments
@@ -247,14 +495,120 @@ pub trait ICloudProvider: Send + Sync {
     /// The item's metadata
     async fn get_metadata(&self, remote_id: &RemoteId) -> anyhow::Result<DeltaItem>;
 
+    /// Records the modification time of a file as set by its client
+    ///
+    /// Called after an upload with the local mtime, so downloads on other
+    /// machines restore it (OneDrive's `fileSystemInfo`). Delta items report
+    /// this time as `modified` afterwards. The default implementation does
+    /// nothing, for providers that only keep their own timestamps.
+    ///
+    /// # Arguments
+    /// * `remote_id` - The provider-specific identifier of the file
+    /// * `modified` - The modification time to record
+    async fn set_modified_time(
+        &self,
+        remote_id: &RemoteId,
+        modified: DateTime<Utc>,
+    ) -> anyhow::Result<()> {
+        let _ = (remote_id, modified);
+        Ok(())
+    }
+
     /// Retrieves information about the authenticated user
     ///
     /// # Returns
     /// User profile and quota information
     async fn get_user_info(&self) -> anyhow::Result<UserInfo>;
 
+    /// Retrieves the current storage quota
+    ///
+    /// The default implementation reads it from [`Self::get_user_info`];
+    /// providers with a cheaper quota endpoint should override it.
+    async fn get_quota(&self) -> anyhow::Result<DriveQuota> {
+        let info = self.get_user_info().await?;
+        Ok(DriveQuota::new(info.quota_used, info.quota_total))
+    }
+
+    /// Lists the sub-folders of the folder at `path`, one page at a time
+    ///
+    /// Files are left out, so browsing the folder tree does not fetch the
+    /// metadata of everything in it. The default implementation fails, for
+    /// providers that cannot browse folders.
+    ///
+    /// # Arguments
+    /// * `path` - Remote path of the folder (`/` for the root)
+    /// * `continuation` - The `continuation` of the previous page, or None
+    ///   for the first page
+    async fn list_folders(
+        &self,
+        path: &RemotePath,
+        continuation: Option<&str>,
+    ) -> anyhow::Result<FolderPage> {
+        let _ = (path, continuation);
+        anyhow::bail!("Listing remote folders is not supported by this provider")
+    }
+
+    /// Lists the files and folders directly inside the folder at `path`,
+    /// one page at a time
+    ///
+    /// Returns [`RemoteItemNotFound`] if the folder does not exist. The
+    /// default implementation fails, for providers that cannot list folders.
+    ///
+    /// # Arguments
+    /// * `path` - Remote path of the folder (`/` for the root)
+    /// * `continuation` - The `continuation` of the previous page, or None
+    ///   for the first page
+    async fn list_children(
+        &self,
+        path: &RemotePath,
+        continuation: Option<&str>,
+    ) -> anyhow::Result<ItemPage> {
+        let _ = (path, continuation);
+        anyhow::bail!("Listing remote folder contents is not supported by this provider")
+    }
+
+    /// Returns the file or folder named `name` directly inside the folder
+    /// at `parent`, if there is one
+    ///
+    /// Names are compared case-insensitively, as OneDrive does; a missing
+    /// folder has no children. The default implementation pages through
+    /// [`list_children`](Self::list_children); providers that can look an
+    /// item up by path should override it.
+    ///
+    /// # Arguments
+    /// * `parent` - Remote path of the folder (`/` for the root)
+    /// * `name` - Name of the item inside it
+    async fn find_child(
+        &self,
+        parent: &RemotePath,
+        name: &str,
+    ) -> anyhow::Result<Option<DeltaItem>> {
+        let name = name.to_lowercase();
+        let mut continuation = None;
+        loop {
+            let page = match self.list_children(parent, continuation.as_deref()).await {
+                Ok(page) => page,
+                Err(err) if is_remote_item_not_found(&err) => return Ok(None),
+                Err(err) => return Err(err),
+            };
+            if let Some(item) = page
+                .items
+                .into_iter()
+                .find(|item| item.name.to_lowercase() == name)
+            {
+                return Ok(Some(item));
+            }
+            match page.continuation {
+                Some(next) => continuation = Some(next),
+                None => return Ok(None),
+            }
+        }
+    }
+
     /// Deletes an item from the cloud storage
     ///
+    /// Returns [`RemoteItemNotFound`] if the item does not exist (anymore).
+    ///
     /// # Arguments
     /// * `remote_id` - The provider-specific identifier for the item to delete
     async fn delete_item(&self, remote_id: &RemoteId) -> anyhow::Result<()>;
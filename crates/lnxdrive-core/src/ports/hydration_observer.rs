@@ -0,0 +1,170 @@
+//! Hydration observer port (driven/secondary port)
+//!
+//! This module defines the interface through which the Files-On-Demand
+//! layer reports files becoming available locally (hydrated) or
+//! cloud-only again (dehydrated), e.g. to the D-Bus service, which
+//! forwards them to file managers as signals so emblems update without
+//! polling.
+//!
+//! ## Design Notes
+//!
+//! - `IHydrationObserver` is synchronous, like
+//!   [`ITransferObserver`](super::ITransferObserver): it is called from
+//!   download tasks and must not block them.
+//! - [`HydrationProgressReporter`] wraps an observer with a
+//!   [`ProgressThrottle`] so a fast download does not flood the bus.
+
+use std::{
+    fmt,
+    sync::{Arc, Mutex},
+    time::Instant,
+};
+
+use serde::{Deserialize, Serialize};
+
+use super::transfer_progress::{ProgressThrottle, PROGRESS_MIN_INTERVAL};
+
+/// Minimum change in percent between two progress events
+pub const HYDRATION_PROGRESS_MIN_PERCENT: u64 = 1;
+
+/// A change of the local availability of a file
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum HydrationEvent {
+    /// `percent` of the content of `path` has been downloaded
+    Progress { path: String, percent: u8 },
+    /// The content of `path` is now available locally
+    Hydrated { path: String },
+    /// The local content of `path` was removed; it is cloud-only again
+    Dehydrated { path: String },
+}
+
+impl HydrationEvent {
+    /// Returns the path the event refers to
+    pub fn path(&self) -> &str {
+        match self {
+            HydrationEvent::Progress { path, .. }
+            | HydrationEvent::Hydrated { path }
+            | HydrationEvent::Dehydrated { path } => path,
+        }
+    }
+}
+
+/// Observer for files being hydrated and dehydrated
+pub trait IHydrationObserver: Send + Sync {
+    /// Called for every progress, hydration or dehydration event
+    fn on_hydration_event(&self, event: HydrationEvent);
+}
+
+/// Reports the hydration of one file to an observer
+///
+/// Progress updates are throttled to one per
+/// [`HYDRATION_PROGRESS_MIN_PERCENT`] and [`PROGRESS_MIN_INTERVAL`]; the
+/// first update and 100% are always sent.
+pub struct HydrationProgressReporter {
+    observer: Arc<dyn IHydrationObserver>,
+    path: String,
+    throttle: Mutex<ProgressThrottle>,
+}
+
+impl HydrationProgressReporter {
+    /// Creates a reporter for the hydration of `path`
+    pub fn new(observer: Arc<dyn IHydrationObserver>, path: impl Into<String>) -> Self {
+        Self {
+            observer,
+            path: path.into(),
+            throttle: Mutex::new(ProgressThrottle::new(
+                HYDRATION_PROGRESS_MIN_PERCENT,
+                PROGRESS_MIN_INTERVAL,
+            )),
+        }
+    }
+
+    /// Returns the path being hydrated
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// Reports the percentage of the content downloaded so far
+    pub fn report(&self, percent: u8) {
+        let emit = match self.throttle.lock() {
+            Ok(mut throttle) => throttle.should_emit(percent.into(), 100, Instant::now()),
+            Err(_) => false,
+        };
+        if emit {
+            self.observer.on_hydration_event(HydrationEvent::Progress {
+                path: self.path.clone(),
+                percent,
+            });
+        }
+    }
+
+    /// Reports that the file was hydrated
+    pub fn hydrated(&self) {
+        self.report(100);
+        self.observer.on_hydration_event(HydrationEvent::Hydrated {
+            path: self.path.clone(),
+        });
+    }
+}
+
+impl fmt::Debug for HydrationProgressReporter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("HydrationProgressReporter")
+            .field("path", &self.path)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Default)]
+    struct Recorder(Mutex<Vec<HydrationEvent>>);
+
+    impl IHydrationObserver for Recorder {
+        fn on_hydration_event(&self, event: HydrationEvent) {
+            self.0.lock().unwrap().push(event);
+        }
+    }
+
+    #[test]
+    fn test_reporter_throttles_progress_and_reports_completion() {
+        let recorder = Arc::new(Recorder::default());
+        let reporter = HydrationProgressReporter::new(
+            Arc::clone(&recorder) as Arc<dyn IHydrationObserver>,
+            "/home/user/OneDrive/a.txt",
+        );
+
+        reporter.report(0);
+        // Within the minimum interval of the first update
+        reporter.report(10);
+        reporter.report(20);
+        reporter.hydrated();
+
+        let path = "/home/user/OneDrive/a.txt".to_string();
+        assert_eq!(
+            *recorder.0.lock().unwrap(),
+            vec![
+                HydrationEvent::Progress {
+                    path: path.clone(),
+                    percent: 0
+                },
+                HydrationEvent::Progress {
+                    path: path.clone(),
+                    percent: 100
+                },
+                HydrationEvent::Hydrated { path },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_event_path() {
+        let event = HydrationEvent::Dehydrated {
+            path: "/home/user/OneDrive/b.txt".to_string(),
+        };
+        assert_eq!(event.path(), "/home/user/OneDrive/b.txt");
+    }
+}
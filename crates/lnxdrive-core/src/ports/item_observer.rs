@@ -0,0 +1,40 @@
+//! Item observer port (driven/secondary port)
+//!
+//! This module defines the interface through which the sync engine tells
+//! adapters that keep their own view of the synced tree (e.g. the FUSE
+//! inode table) about changes it applied from the cloud.
+//!
+//! ## Design Notes
+//!
+//! - `IItemObserver` is synchronous, like
+//!   [`ITransferObserver`](super::ITransferObserver): it is called after
+//!   the change has been persisted and must not block the sync loop.
+
+use crate::domain::newtypes::UniqueId;
+
+/// Receives structural changes the sync engine applied to tracked items
+pub trait IItemObserver: Send + Sync {
+    /// Called after an item was renamed or moved in the cloud and its
+    /// SyncItem now carries the new path
+    ///
+    /// `new_parent` is the ID of the SyncItem of the new parent folder, or
+    /// `None` when the item now sits directly in the sync root.
+    fn item_moved(&self, item_id: &UniqueId, new_parent: Option<&UniqueId>, new_name: &str);
+
+    /// Called after items were added below the folder `parent` (the sync
+    /// root when `None`) without their content, e.g. when the folder was
+    /// selected for sync
+    ///
+    /// The default does nothing.
+    fn children_added(&self, parent: Option<&UniqueId>) {
+        let _ = parent;
+    }
+
+    /// Called after an item stopped being tracked while it still exists in
+    /// the cloud, e.g. when its folder was deselected
+    ///
+    /// The default does nothing.
+    fn item_removed(&self, item_id: &UniqueId) {
+        let _ = item_id;
+    }
+}
@@ -192,6 +192,27 @@ pub trait ILocalFileSystem: Send + Sync {
     /// * `data` - The data to write
     async fn write_file(&self, path: &SyncPath, data: &[u8]) -> anyhow::Result<()>;
 
+    /// Writes data to a file like [`write_file`](Self::write_file) and sets
+    /// its modification time
+    ///
+    /// Used for downloads, so the local file carries the remote modification
+    /// time. The default implementation writes the file and leaves the
+    /// modification time to the filesystem.
+    ///
+    /// # Arguments
+    /// * `path` - Absolute path to the file
+    /// * `data` - The data to write
+    /// * `modified` - Modification time to give the file
+    async fn write_file_with_mtime(
+        &self,
+        path: &SyncPath,
+        data: &[u8],
+        modified: DateTime<Utc>,
+    ) -> anyhow::Result<()> {
+        let _ = modified;
+        self.write_file(path, data).await
+    }
+
     /// Deletes a file from the filesystem
     ///
     /// # Arguments
@@ -201,6 +222,29 @@ pub trait ILocalFileSystem: Send + Sync {
     /// Returns an error if the file doesn't exist or cannot be deleted
     async fn delete_file(&self, path: &SyncPath) -> anyhow::Result<()>;
 
+    /// Renames a file or directory within the filesystem
+    ///
+    /// # Arguments
+    /// * `from` - Absolute path of the existing entry
+    /// * `to` - Absolute destination path (must not exist)
+    ///
+    /// # Errors
+    /// Returns an error if the source doesn't exist or the destination exists
+    async fn rename(&self, from: &SyncPath, to: &SyncPath) -> anyhow::Result<()>;
+
+    /// Copies a file, replacing the destination if it exists
+    ///
+    /// The default implementation reads the whole file into memory and
+    /// writes it with [`write_file`](Self::write_file).
+    ///
+    /// # Arguments
+    /// * `from` - Absolute path of the file to copy
+    /// * `to` - Absolute destination path
+    async fn copy_file(&self, from: &SyncPath, to: &SyncPath) -> anyhow::Result<()> {
+        let data = self.read_file(from).await?;
+        self.write_file(to, &data).await
+    }
+
     /// Gets the current state of a file or directory
     ///
     /// Returns `FileSystemState::not_found()` if the path doesn't exist
@@ -213,7 +257,9 @@ pub trait ILocalFileSystem: Send + Sync {
     /// Computes the quickXorHash of a file
     ///
     /// The hash is compatible with OneDrive's quickXorHash algorithm
-    /// for comparing local and remote file integrity.
+    /// for comparing local and remote file integrity. Implementations may
+    /// return a cached hash while the file's inode, size and mtime are
+    /// unchanged.
     ///
     /// # Arguments
     /// * `path` - Absolute path to the file
@@ -222,6 +268,12 @@ pub trait ILocalFileSystem: Send + Sync {
     /// Returns an error if the file doesn't exist or cannot be read
     async fn compute_hash(&self, path: &SyncPath) -> anyhow::Result<FileHash>;
 
+    /// Discards any hashes cached by `compute_hash`
+    ///
+    /// Called before a reconciliation scan so every file is read again.
+    /// The default implementation does nothing.
+    fn invalidate_hash_cache(&self) {}
+
     /// Creates a directory and all parent directories as needed
     ///
     /// This is equivalent to `mkdir -p` behavior.
@@ -10,13 +10,39 @@
 //! - [`IStateRepository`] - Persistent storage for sync state, accounts, audit
 //! - [`ILocalFileSystem`] - Local filesystem operations and file watching
 //! - [`INotificationService`] - Desktop notifications and progress reporting
+//! - [`ITransferObserver`] - Per-file upload/download byte progress
+//! - [`IHydrationObserver`] - Files becoming available locally or cloud-only
+//! - [`IItemObserver`] - Renames, moves and (de)selected folders applied to tracked items
+//! - [`ICacheManager`] - Usage, cleaning, verification and pinning of the content cache
+//!
+//! [`TransferControl`] is not a port but shared state: the switches that
+//! pause uploads and downloads, set by adapters and read by the engine.
 
+pub mod cache_manager;
 pub mod cloud_provider;
+pub mod hydration_observer;
+pub mod item_observer;
 pub mod local_filesystem;
 pub mod notification;
 pub mod state_repository;
+pub mod transfer_control;
+pub mod transfer_progress;
 
-pub use cloud_provider::{AuthFlow, DeltaItem, DeltaResponse, ICloudProvider, Tokens, UserInfo};
+pub use cache_manager::{
+    CacheCleanOptions, CacheCleanReport, CachePinReport, CacheUsage, CacheVerifyReport,
+    FolderUsage, ICacheManager,
+};
+pub use cloud_provider::{
+    AuthFlow, CommitCheck, DeltaItem, DeltaPageSource, DeltaPages, DeltaResponse, FolderPage,
+    ICloudProvider, ItemPage, RemoteFolder, Tokens, UserInfo,
+};
+pub use hydration_observer::{HydrationEvent, HydrationProgressReporter, IHydrationObserver};
+pub use item_observer::IItemObserver;
 pub use local_filesystem::{FileSystemState, IFileObserver, ILocalFileSystem, WatchHandle};
 pub use notification::{INotificationService, Notification, NotificationPriority};
 pub use state_repository::{IStateRepository, ItemFilter};
+pub use transfer_control::{is_transfer_paused, TransferControl, TransferPaused};
+pub use transfer_progress::{
+    EtaEstimator, ITransferObserver, ProgressThrottle, TransferEvent, TransferKind,
+    TransferObservers, TransferProgressReporter,
+};
@@ -20,7 +20,7 @@ use chrono::{DateTime, Utc};
 use crate::domain::{
     newtypes::{AccountId, RemoteId, SessionId, SyncPath, UniqueId},
     sync_item::ItemState,
-    Account, AuditEntry, Conflict, SyncItem, SyncSession,
+    Account, AuditEntry, Conflict, SyncHistoryEntry, SyncItem, SyncSession,
 };
 
 // ============================================================================
@@ -124,6 +124,18 @@ pub trait IStateRepository: Send + Sync {
     /// If an item with the same ID already exists, it is updated.
     async fn save_item(&self, item: &SyncItem) -> anyhow::Result<()>;
 
+    /// Saves many sync items, like calling [`save_item`](Self::save_item)
+    /// for each
+    ///
+    /// Implementations should write them in a few transactions rather than
+    /// one per item. The default saves them one by one.
+    async fn save_items_batch(&self, items: &[SyncItem]) -> anyhow::Result<()> {
+        for item in items {
+            self.save_item(item).await?;
+        }
+        Ok(())
+    }
+
     /// Retrieves a sync item by its unique ID
     async fn get_item(&self, id: &UniqueId) -> anyhow::Result<Option<SyncItem>>;
 
@@ -162,6 +174,15 @@ pub trait IStateRepository: Send + Sync {
     /// Returns `None` if no accounts are configured.
     async fn get_default_account(&self) -> anyhow::Result<Option<Account>>;
 
+    /// Retrieves all accounts, oldest first
+    async fn list_accounts(&self) -> anyhow::Result<Vec<Account>>;
+
+    /// Removes all sync items and sessions belonging to an account
+    ///
+    /// The account row itself and the audit log are kept so the logout
+    /// remains traceable. Returns the number of sync items removed.
+    async fn purge_account_data(&self, account_id: &AccountId) -> anyhow::Result<u64>;
+
     // --- Session operations ---
 
     /// Saves a sync session (insert or update)
@@ -189,6 +210,24 @@ pub trait IStateRepository: Send + Sync {
         limit: u32,
     ) -> anyhow::Result<Vec<AuditEntry>>;
 
+    // --- Sync history operations ---
+
+    /// Records a finished sync cycle
+    ///
+    /// Older rows beyond [`MAX_SYNC_HISTORY_ENTRIES`](crate::domain::MAX_SYNC_HISTORY_ENTRIES)
+    /// are pruned as part of the same call. Returns the stored row ID.
+    async fn save_sync_history(&self, entry: &SyncHistoryEntry) -> anyhow::Result<i64>;
+
+    /// Retrieves the most recent sync cycles, up to a limit
+    ///
+    /// Returns entries ordered by finish time (newest first).
+    async fn get_sync_history(&self, limit: u32) -> anyhow::Result<Vec<SyncHistoryEntry>>;
+
+    /// Deletes all but the `keep` most recent history rows
+    ///
+    /// Returns the number of rows removed.
+    async fn prune_sync_history(&self, keep: u32) -> anyhow::Result<u64>;
+
     // --- Conflict operations ---
 
     /// Saves a conflict record (insert or update)
@@ -199,6 +238,25 @@ pub trait IStateRepository: Send + Sync {
     /// Returns conflicts ordered by detection time (newest first).
     async fn get_unresolved_conflicts(&self) -> anyhow::Result<Vec<Conflict>>;
 
+    // --- Selective sync operations ---
+
+    /// Returns the remote folders selected for sync
+    ///
+    /// Empty if no selection was stored, i.e. the whole drive is synced.
+    async fn get_selected_folders(&self) -> anyhow::Result<Vec<String>>;
+
+    /// Stores a new folder selection along with the items it adds and the
+    /// items it removes, in one transaction
+    ///
+    /// Either everything is written or nothing is, so a failure leaves the
+    /// previous selection and its items in place.
+    async fn apply_folder_selection(
+        &self,
+        folders: &[String],
+        saved: &[SyncItem],
+        removed: &[UniqueId],
+    ) -> anyhow::Result<()>;
+
     // --- FUSE inode operations ---
 
     /// Atomically get and increment the next available inode number
@@ -249,4 +307,15 @@ pub trait IStateRepository: Send + Sync {
         max_age_days: u32,
         limit: u32,
     ) -> anyhow::Result<Vec<SyncItem>>;
+
+    /// Get large sync items that are candidates for dehydration
+    ///
+    /// Same eligibility as [`get_items_for_dehydration`](Self::get_items_for_dehydration),
+    /// restricted to items of at least `min_size_bytes` and sorted largest first.
+    async fn get_large_items_for_dehydration(
+        &self,
+        max_age_days: u32,
+        min_size_bytes: u64,
+        limit: u32,
+    ) -> anyhow::Result<Vec<SyncItem>>;
 }
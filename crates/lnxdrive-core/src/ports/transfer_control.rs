@@ -0,0 +1,156 @@
+//! Pausing of file transfers
+//!
+//! A [`TransferControl`] is shared between the sync engine, which checks it
+//! before and during every upload and download, and the adapters that let
+//! the user pause transfers (e.g. the D-Bus service). Transfers can be
+//! paused all at once or per path; pausing a folder pauses everything
+//! below it. Metadata sync (renames, deletes, folders) is not affected.
+//!
+//! A transfer interrupted by a pause fails with [`TransferPaused`]. Chunked
+//! uploads keep their checkpoint and continue from the last accepted chunk
+//! once resumed.
+
+use std::{collections::BTreeSet, path::Path, sync::RwLock};
+
+/// Error for a transfer that was not started or was interrupted because
+/// it is paused
+///
+/// Use [`is_transfer_paused`] to detect it through any added context.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("Transfer of {path} is paused")]
+pub struct TransferPaused {
+    /// Local path of the paused transfer
+    pub path: String,
+}
+
+/// Returns true if `err` (or any error it wraps) is [`TransferPaused`]
+pub fn is_transfer_paused(err: &anyhow::Error) -> bool {
+    err.chain().any(|cause| cause.is::<TransferPaused>())
+}
+
+#[derive(Debug, Default)]
+struct PauseState {
+    all: bool,
+    paths: BTreeSet<String>,
+}
+
+/// Pause switches for uploads and downloads
+#[derive(Debug, Default)]
+pub struct TransferControl {
+    state: RwLock<PauseState>,
+}
+
+impl TransferControl {
+    /// Creates a control with nothing paused
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Pauses every transfer
+    pub fn pause_all(&self) {
+        if let Ok(mut state) = self.state.write() {
+            state.all = true;
+        }
+    }
+
+    /// Resumes transfers paused with [`Self::pause_all`]
+    ///
+    /// Paths paused individually stay paused.
+    pub fn resume_all(&self) {
+        if let Ok(mut state) = self.state.write() {
+            state.all = false;
+        }
+    }
+
+    /// Pauses the transfers of a file, or of everything below a folder
+    ///
+    /// Returns false if the path was already paused.
+    pub fn pause_path(&self, path: &str) -> bool {
+        self.state
+            .write()
+            .map(|mut state| state.paths.insert(path.trim_end_matches('/').to_string()))
+            .unwrap_or(false)
+    }
+
+    /// Resumes the transfers of a path paused with [`Self::pause_path`]
+    ///
+    /// Returns false if the path was not paused.
+    pub fn resume_path(&self, path: &str) -> bool {
+        self.state
+            .write()
+            .map(|mut state| state.paths.remove(path.trim_end_matches('/')))
+            .unwrap_or(false)
+    }
+
+    /// Returns true if all transfers are paused
+    pub fn all_paused(&self) -> bool {
+        self.state.read().map(|state| state.all).unwrap_or(false)
+    }
+
+    /// Returns the individually paused paths, sorted
+    pub fn paused_paths(&self) -> Vec<String> {
+        self.state
+            .read()
+            .map(|state| state.paths.iter().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    /// Returns true if transfers of `path` are paused, either all at once,
+    /// for the path itself, or for a folder containing it
+    pub fn is_paused(&self, path: &Path) -> bool {
+        self.state
+            .read()
+            .map(|state| state.all || state.paths.iter().any(|p| path.starts_with(p)))
+            .unwrap_or(false)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pause_all_and_resume() {
+        let control = TransferControl::new();
+        assert!(!control.is_paused(Path::new("/home/user/OneDrive/a.bin")));
+
+        control.pause_all();
+        assert!(control.all_paused());
+        assert!(control.is_paused(Path::new("/home/user/OneDrive/a.bin")));
+
+        control.resume_all();
+        assert!(!control.all_paused());
+        assert!(!control.is_paused(Path::new("/home/user/OneDrive/a.bin")));
+    }
+
+    #[test]
+    fn test_pause_path_covers_folder_contents() {
+        let control = TransferControl::new();
+        assert!(control.pause_path("/home/user/OneDrive/Videos/"));
+        assert!(!control.pause_path("/home/user/OneDrive/Videos"));
+
+        assert!(control.is_paused(Path::new("/home/user/OneDrive/Videos/trip.mp4")));
+        assert!(!control.is_paused(Path::new("/home/user/OneDrive/VideosOld/a.mp4")));
+        assert!(!control.is_paused(Path::new("/home/user/OneDrive/notes.txt")));
+        assert_eq!(control.paused_paths(), vec!["/home/user/OneDrive/Videos"]);
+
+        // Resuming everything does not resume individually paused paths
+        control.pause_all();
+        control.resume_all();
+        assert!(control.is_paused(Path::new("/home/user/OneDrive/Videos/trip.mp4")));
+
+        assert!(control.resume_path("/home/user/OneDrive/Videos"));
+        assert!(!control.resume_path("/home/user/OneDrive/Videos"));
+        assert!(!control.is_paused(Path::new("/home/user/OneDrive/Videos/trip.mp4")));
+    }
+
+    #[test]
+    fn test_transfer_paused_is_detected_through_context() {
+        let err = anyhow::Error::new(TransferPaused {
+            path: "/home/user/OneDrive/a.bin".to_string(),
+        })
+        .context("Failed to upload large file");
+        assert!(is_transfer_paused(&err));
+        assert!(!is_transfer_paused(&anyhow::anyhow!("network down")));
+    }
+}
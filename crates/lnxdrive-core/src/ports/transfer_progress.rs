@@ -0,0 +1,510 @@
+//! Transfer progress port (driven/secondary port)
+//!
+//! This module defines the interface through which uploads and downloads
+//! report per-file byte progress to interested adapters (e.g. the D-Bus
+//! service, which forwards them to UI clients as signals).
+//!
+//! ## Design Notes
+//!
+//! - `ITransferObserver` is synchronous because progress callbacks are
+//!   invoked from inside transfer loops and must not block them.
+//!   Implementations should hand events off (e.g. via a channel).
+//! - [`TransferProgressReporter`] wraps an observer with a
+//!   [`ProgressThrottle`] so a fast transfer does not flood the bus.
+//! - [`TransferObservers`] hands each event to several observers (e.g. the
+//!   D-Bus service and the daemon's statistics).
+//! - Progress events carry an ETA estimated by [`EtaEstimator`] from the
+//!   throughput of the last [`ETA_WINDOW`], so it does not jump around with
+//!   every chunk.
+
+use std::{
+    collections::VecDeque,
+    fmt,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use serde::{Deserialize, Serialize};
+
+/// Minimum number of new bytes between two progress events (256 KiB)
+pub const PROGRESS_MIN_BYTES: u64 = 256 * 1024;
+
+/// Minimum interval between two progress events
+pub const PROGRESS_MIN_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Period whose throughput the ETA of a transfer is estimated from
+pub const ETA_WINDOW: Duration = Duration::from_secs(10);
+
+// ============================================================================
+// TransferKind / TransferEvent
+// ============================================================================
+
+/// Direction of a file transfer
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TransferKind {
+    /// Local content being sent to the cloud
+    Upload,
+    /// Remote content being fetched to the local machine
+    Download,
+}
+
+impl fmt::Display for TransferKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TransferKind::Upload => write!(f, "upload"),
+            TransferKind::Download => write!(f, "download"),
+        }
+    }
+}
+
+/// A progress or completion event for a single file transfer
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum TransferEvent {
+    /// Bytes have been transferred for `path`
+    Progress {
+        path: String,
+        kind: TransferKind,
+        bytes_done: u64,
+        bytes_total: u64,
+        /// Estimated seconds until the transfer completes, `None` until
+        /// the throughput is known
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        eta_secs: Option<u64>,
+    },
+    /// The transfer of `path` finished; `error` is `None` on success
+    Complete {
+        path: String,
+        kind: TransferKind,
+        error: Option<String>,
+    },
+}
+
+impl TransferEvent {
+    /// Returns the path the event refers to
+    pub fn path(&self) -> &str {
+        match self {
+            TransferEvent::Progress { path, .. } | TransferEvent::Complete { path, .. } => path,
+        }
+    }
+
+    /// Returns the transfer direction
+    pub fn kind(&self) -> TransferKind {
+        match self {
+            TransferEvent::Progress { kind, .. } | TransferEvent::Complete { kind, .. } => *kind,
+        }
+    }
+}
+
+// ============================================================================
+// ITransferObserver trait
+// ============================================================================
+
+/// Observer for per-file transfer progress
+///
+/// Implementations receive already-throttled events when they are fed
+/// through a [`TransferProgressReporter`].
+pub trait ITransferObserver: Send + Sync {
+    /// Called for every progress or completion event
+    fn on_transfer_event(&self, event: TransferEvent);
+}
+
+/// Forwards every event to several observers, in order
+pub struct TransferObservers(Vec<Arc<dyn ITransferObserver>>);
+
+impl TransferObservers {
+    /// Creates an observer that forwards to all of `observers`
+    pub fn new(observers: Vec<Arc<dyn ITransferObserver>>) -> Self {
+        Self(observers)
+    }
+}
+
+impl ITransferObserver for TransferObservers {
+    fn on_transfer_event(&self, event: TransferEvent) {
+        if let Some((last, rest)) = self.0.split_last() {
+            for observer in rest {
+                observer.on_transfer_event(event.clone());
+            }
+            last.on_transfer_event(event);
+        }
+    }
+}
+
+// ============================================================================
+// ProgressThrottle
+// ============================================================================
+
+/// Rate limiter for progress events
+///
+/// An update is let through when at least `min_interval` has elapsed and
+/// at least `min_bytes` have been transferred since the last emitted
+/// update. The first update and the one reaching the total size are
+/// always emitted.
+#[derive(Debug, Clone)]
+pub struct ProgressThrottle {
+    min_bytes: u64,
+    min_interval: Duration,
+    last: Option<(u64, Instant)>,
+}
+
+impl ProgressThrottle {
+    /// Creates a throttle with explicit thresholds
+    pub fn new(min_bytes: u64, min_interval: Duration) -> Self {
+        Self {
+            min_bytes,
+            min_interval,
+            last: None,
+        }
+    }
+
+    /// Decides whether an update at `bytes_done` should be emitted at `now`
+    pub fn should_emit(&mut self, bytes_done: u64, bytes_total: u64, now: Instant) -> bool {
+        let emit = match self.last {
+            None => true,
+            Some((last_bytes, _)) if bytes_done <= last_bytes => false,
+            Some(_) if bytes_done >= bytes_total => true,
+            Some((last_bytes, last_at)) => {
+                bytes_done - last_bytes >= self.min_bytes
+                    && now.duration_since(last_at) >= self.min_interval
+            }
+        };
+        if emit {
+            self.last = Some((bytes_done, now));
+        }
+        emit
+    }
+}
+
+impl Default for ProgressThrottle {
+    fn default() -> Self {
+        Self::new(PROGRESS_MIN_BYTES, PROGRESS_MIN_INTERVAL)
+    }
+}
+
+// ============================================================================
+// EtaEstimator
+// ============================================================================
+
+/// Estimates the time left of a transfer from its recent throughput
+///
+/// The rate is measured over the last `window` rather than between two
+/// consecutive updates, which smooths out the bursts of chunked transfers.
+#[derive(Debug, Clone)]
+pub struct EtaEstimator {
+    window: Duration,
+    samples: VecDeque<(Instant, u64)>,
+}
+
+impl EtaEstimator {
+    /// Creates an estimator averaging the throughput over `window`
+    pub fn new(window: Duration) -> Self {
+        Self {
+            window,
+            samples: VecDeque::new(),
+        }
+    }
+
+    /// Records that `bytes_done` bytes were transferred at `now`
+    pub fn record(&mut self, bytes_done: u64, now: Instant) {
+        if matches!(self.samples.back(), Some(&(_, last)) if bytes_done < last) {
+            // The transfer restarted
+            self.samples.clear();
+        }
+        self.samples.push_back((now, bytes_done));
+        // Keep the newest sample at least `window` old as the baseline
+        while self.samples.len() > 2 && now.duration_since(self.samples[1].0) >= self.window {
+            self.samples.pop_front();
+        }
+    }
+
+    /// Returns the throughput in bytes per second, if known
+    pub fn rate(&self) -> Option<f64> {
+        let (&(first_at, first), &(last_at, last)) = (self.samples.front()?, self.samples.back()?);
+        let elapsed = last_at.duration_since(first_at).as_secs_f64();
+        (elapsed > 0.0 && last > first).then(|| (last - first) as f64 / elapsed)
+    }
+
+    /// Returns the estimated time until `bytes_total` bytes are
+    /// transferred, in whole seconds rounded up, if the throughput is known
+    pub fn eta(&self, bytes_total: u64) -> Option<Duration> {
+        let &(_, done) = self.samples.back()?;
+        if done >= bytes_total {
+            return Some(Duration::ZERO);
+        }
+        let secs = (bytes_total - done) as f64 / self.rate()?;
+        Some(Duration::from_secs(secs.ceil() as u64))
+    }
+}
+
+impl Default for EtaEstimator {
+    fn default() -> Self {
+        Self::new(ETA_WINDOW)
+    }
+}
+
+// ============================================================================
+// TransferProgressReporter
+// ============================================================================
+
+/// Reports the progress of one file transfer to an observer
+///
+/// Progress updates are throttled; the completion event is always sent.
+pub struct TransferProgressReporter {
+    observer: Arc<dyn ITransferObserver>,
+    path: String,
+    kind: TransferKind,
+    bytes_total: u64,
+    throttle: Mutex<ProgressThrottle>,
+    eta: Mutex<EtaEstimator>,
+}
+
+impl TransferProgressReporter {
+    /// Creates a reporter for a transfer of `bytes_total` bytes
+    pub fn new(
+        observer: Arc<dyn ITransferObserver>,
+        path: impl Into<String>,
+        kind: TransferKind,
+        bytes_total: u64,
+    ) -> Self {
+        Self {
+            observer,
+            path: path.into(),
+            kind,
+            bytes_total,
+            throttle: Mutex::new(ProgressThrottle::default()),
+            eta: Mutex::new(EtaEstimator::default()),
+        }
+    }
+
+    /// Returns the path being transferred
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// Reports the absolute number of bytes transferred so far
+    ///
+    /// Every report feeds the ETA estimate, including those the throttle
+    /// holds back.
+    pub fn report(&self, bytes_done: u64) {
+        let now = Instant::now();
+        let eta_secs = match self.eta.lock() {
+            Ok(mut eta) => {
+                eta.record(bytes_done, now);
+                eta.eta(self.bytes_total).map(|eta| eta.as_secs())
+            }
+            Err(_) => None,
+        };
+        let emit = match self.throttle.lock() {
+            Ok(mut throttle) => throttle.should_emit(bytes_done, self.bytes_total, now),
+            Err(_) => false,
+        };
+        if emit {
+            self.observer.on_transfer_event(TransferEvent::Progress {
+                path: self.path.clone(),
+                kind: self.kind,
+                bytes_done,
+                bytes_total: self.bytes_total,
+                eta_secs,
+            });
+        }
+    }
+
+    /// Reports the end of the transfer with its outcome
+    pub fn finish<T, E: fmt::Display>(&self, result: &Result<T, E>) {
+        if result.is_ok() {
+            self.report(self.bytes_total);
+        }
+        self.observer.on_transfer_event(TransferEvent::Complete {
+            path: self.path.clone(),
+            kind: self.kind,
+            error: result.as_ref().err().map(|e| format!("{:#}", e)),
+        });
+    }
+
+    /// Returns a `(bytes_done, bytes_total)` callback suitable for
+    /// `ICloudProvider::upload_file_session`
+    pub fn callback(self: &Arc<Self>) -> Box<dyn Fn(u64, u64) + Send> {
+        let reporter = Arc::clone(self);
+        Box::new(move |done, _total| reporter.report(done))
+    }
+}
+
+impl fmt::Debug for TransferProgressReporter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TransferProgressReporter")
+            .field("path", &self.path)
+            .field("kind", &self.kind)
+            .field("bytes_total", &self.bytes_total)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Default)]
+    struct Recorder(Mutex<Vec<TransferEvent>>);
+
+    impl ITransferObserver for Recorder {
+        fn on_transfer_event(&self, event: TransferEvent) {
+            self.0.lock().unwrap().push(event);
+        }
+    }
+
+    #[test]
+    fn throttle_emits_first_and_final_updates() {
+        let mut throttle = ProgressThrottle::default();
+        let now = Instant::now();
+        assert!(throttle.should_emit(0, 1000, now));
+        assert!(!throttle.should_emit(10, 1000, now));
+        assert!(throttle.should_emit(1000, 1000, now));
+        assert!(!throttle.should_emit(1000, 1000, now));
+    }
+
+    #[test]
+    fn throttle_requires_both_bytes_and_interval() {
+        let mut throttle = ProgressThrottle::new(100, Duration::from_millis(250));
+        let start = Instant::now();
+        let total = 10_000;
+        assert!(throttle.should_emit(0, total, start));
+        // Enough bytes, too soon
+        assert!(!throttle.should_emit(500, total, start + Duration::from_millis(10)));
+        // Enough time, too few bytes
+        assert!(!throttle.should_emit(50, total, start + Duration::from_millis(300)));
+        // Both thresholds met
+        assert!(throttle.should_emit(500, total, start + Duration::from_millis(300)));
+    }
+
+    #[test]
+    fn eta_follows_recent_throughput() {
+        let mut eta = EtaEstimator::new(Duration::from_secs(10));
+        let start = Instant::now();
+        let at = |secs: u64| start + Duration::from_secs(secs);
+
+        // Unknown until the throughput can be measured
+        eta.record(0, at(0));
+        assert_eq!(eta.rate(), None);
+        assert_eq!(eta.eta(10_000), None);
+
+        // 100 B/s for 10 seconds
+        for secs in 1..=10 {
+            eta.record(secs * 100, at(secs));
+        }
+        assert_eq!(eta.rate(), Some(100.0));
+        assert_eq!(eta.eta(10_000), Some(Duration::from_secs(90)));
+
+        // A one second burst barely moves the estimate
+        eta.record(1_500, at(11));
+        assert_eq!(eta.rate(), Some(140.0));
+        assert_eq!(eta.eta(10_000), Some(Duration::from_secs(61)));
+
+        // Once the window has moved on, only the new rate counts
+        for secs in 12..=21 {
+            eta.record(1_500 + (secs - 11) * 500, at(secs));
+        }
+        assert_eq!(eta.rate(), Some(500.0));
+        assert_eq!(eta.eta(10_000), Some(Duration::from_secs(7)));
+    }
+
+    #[test]
+    fn eta_handles_stalls_restarts_and_completion() {
+        let mut eta = EtaEstimator::default();
+        let start = Instant::now();
+
+        // No progress at all
+        eta.record(500, start);
+        eta.record(500, start + Duration::from_secs(5));
+        assert_eq!(eta.eta(1_000), None);
+
+        // A retry from scratch starts a new measurement
+        eta.record(100, start + Duration::from_secs(6));
+        assert_eq!(eta.rate(), None);
+        eta.record(300, start + Duration::from_secs(8));
+        assert_eq!(eta.eta(1_000), Some(Duration::from_secs(7)));
+
+        eta.record(1_000, start + Duration::from_secs(9));
+        assert_eq!(eta.eta(1_000), Some(Duration::ZERO));
+    }
+
+    #[test]
+    fn reporter_sends_progress_then_completion() {
+        let recorder = Arc::new(Recorder::default());
+        let reporter = Arc::new(TransferProgressReporter::new(
+            Arc::clone(&recorder) as Arc<dyn ITransferObserver>,
+            "/docs/big.iso",
+            TransferKind::Upload,
+            2048,
+        ));
+
+        let callback = reporter.callback();
+        callback(0, 2048);
+        callback(1, 2048);
+        reporter.finish(&Ok::<(), String>(()));
+
+        let events = recorder.0.lock().unwrap();
+        assert_eq!(events.len(), 3);
+        assert!(matches!(
+            events[1],
+            TransferEvent::Progress {
+                bytes_done: 2048,
+                ..
+            }
+        ));
+        assert_eq!(
+            events[2],
+            TransferEvent::Complete {
+                path: "/docs/big.iso".to_string(),
+                kind: TransferKind::Upload,
+                error: None,
+            }
+        );
+    }
+
+    #[test]
+    fn reporter_records_failure_message() {
+        let recorder = Arc::new(Recorder::default());
+        let reporter = TransferProgressReporter::new(
+            Arc::clone(&recorder) as Arc<dyn ITransferObserver>,
+            "/a.bin",
+            TransferKind::Download,
+            10,
+        );
+
+        reporter.finish(&Err::<(), _>("network down"));
+
+        let events = recorder.0.lock().unwrap();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].kind(), TransferKind::Download);
+        assert!(matches!(
+            &events[0],
+            TransferEvent::Complete { error: Some(msg), .. } if msg == "network down"
+        ));
+    }
+
+    #[test]
+    fn observers_all_receive_events() {
+        let first = Arc::new(Recorder::default());
+        let second = Arc::new(Recorder::default());
+        let observers = TransferObservers::new(vec![
+            Arc::clone(&first) as Arc<dyn ITransferObserver>,
+            Arc::clone(&second) as Arc<dyn ITransferObserver>,
+        ]);
+
+        observers.on_transfer_event(TransferEvent::Complete {
+            path: "/a.bin".to_string(),
+            kind: TransferKind::Upload,
+            error: None,
+        });
+
+        assert_eq!(first.0.lock().unwrap().len(), 1);
+        assert_eq!(second.0.lock().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn transfer_kind_display() {
+        assert_eq!(TransferKind::Upload.to_string(), "upload");
+        assert_eq!(TransferKind::Download.to_string(), "download");
+    }
+}
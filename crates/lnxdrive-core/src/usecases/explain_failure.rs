@@ -3,14 +3,22 @@
 //! Provides human-readable explanations of why a file failed to sync,
 //! including actionable suggestions and audit history. This powers the
 //! `lnxdrive explain <path>` CLI command.
+//!
+//! For a conflicted file the explanation also carries the evidence from
+//! the stored [`Conflict`] record: both versions, the last synced (base)
+//! version, and what each side changed relative to it.
 
 use std::sync::Arc;
 
 use anyhow::{Context, Result};
+use chrono::{DateTime, Local, Utc};
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    domain::{AuditEntry, ItemState, SyncItem, SyncPath},
+    domain::{
+        AuditEntry, Conflict, ConflictId, FileHash, ItemState, ReasonCode, SyncItem, SyncPath,
+        VersionInfo,
+    },
     ports::IStateRepository,
 };
 
@@ -26,23 +34,152 @@ pub struct Explanation {
     pub state: String,
     /// Human-readable explanation of the current state
     pub message: String,
+    /// Code of the item's error, or else of its latest failed operation
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason_code: Option<ReasonCode>,
     /// Actionable suggestions for resolving issues
     pub suggestions: Vec<String>,
     /// Recent audit history entries for this item
     pub history: Vec<AuditEntry>,
+    /// Evidence for the item's unresolved conflict, if it has one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub conflict: Option<ConflictEvidence>,
+}
+
+/// The version both sides last agreed on, recorded at the item's last sync
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BaseVersion {
+    /// Content hash of the last synced version
+    pub hash: Option<FileHash>,
+    /// Size of the last synced version in bytes
+    pub size_bytes: u64,
+    /// When the item was last synced
+    pub synced_at: Option<DateTime<Utc>>,
+}
+
+/// What one side of a conflict changed relative to the base version
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SideChange {
+    /// True if the content differs from the base version
+    pub content_changed: bool,
+    /// Size difference to the base version in bytes
+    pub size_delta: i64,
+    /// When this side's version was modified
+    pub modified_at: DateTime<Utc>,
+}
+
+impl SideChange {
+    fn between(base: &BaseVersion, version: &VersionInfo) -> Self {
+        Self {
+            content_changed: base.hash.as_ref() != version.hash(),
+            size_delta: version.size_bytes() as i64 - base.size_bytes as i64,
+            modified_at: version.modified_at(),
+        }
+    }
+
+    /// Describes the change, e.g. "changed at 14:32 adding 2.0 KB"
+    fn describe(&self) -> String {
+        let at = self
+            .modified_at
+            .with_timezone(&Local)
+            .format("%Y-%m-%d %H:%M");
+        let size = match self.size_delta {
+            0 => "without changing the size".to_string(),
+            d if d > 0 => format!("adding {}", format_size(d.unsigned_abs())),
+            d => format!("removing {}", format_size(d.unsigned_abs())),
+        };
+        format!("at {} {}", at, size)
+    }
+}
+
+/// Evidence explaining an unresolved conflict
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConflictEvidence {
+    /// ID of the stored conflict record
+    pub conflict_id: ConflictId,
+    /// When the conflict was detected
+    pub detected_at: DateTime<Utc>,
+    /// The last synced version
+    pub base: BaseVersion,
+    /// The local version when the conflict was detected
+    pub local: VersionInfo,
+    /// The cloud version when the conflict was detected
+    pub remote: VersionInfo,
+    /// What changed locally since the last sync
+    pub local_change: SideChange,
+    /// What changed in the cloud since the last sync
+    pub remote_change: SideChange,
+    /// One-sentence account of both changes
+    pub summary: String,
+}
+
+impl ConflictEvidence {
+    /// Builds the evidence from a conflict record and its sync item
+    pub fn new(conflict: &Conflict, item: &SyncItem) -> Self {
+        let base = BaseVersion {
+            hash: item.content_hash().cloned(),
+            size_bytes: item.size_bytes(),
+            synced_at: item.last_sync(),
+        };
+        let local_change = SideChange::between(&base, conflict.local_version());
+        let remote_change = SideChange::between(&base, conflict.remote_version());
+
+        let remote = if remote_change.content_changed {
+            format!("The cloud version changed {}", remote_change.describe())
+        } else {
+            "The cloud version still matches the last synced version".to_string()
+        };
+        let local = if local_change.content_changed {
+            format!("you edited the file locally {}", local_change.describe())
+        } else {
+            "the local copy still matches the last synced version".to_string()
+        };
+
+        Self {
+            conflict_id: *conflict.id(),
+            detected_at: conflict.detected_at(),
+            base,
+            local: conflict.local_version().clone(),
+            remote: conflict.remote_version().clone(),
+            local_change,
+            remote_change,
+            summary: format!("{} while {}.", remote, local),
+        }
+    }
+}
+
+/// Formats a byte count with a binary unit, e.g. `2.0 KB`
+fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KB", "MB", "GB", "TB"];
+    if bytes < 1024 {
+        return format!("{} B", bytes);
+    }
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    format!("{:.1} {}", value, UNITS[unit])
 }
 
 impl Explanation {
     /// Creates a new Explanation for a sync item with its audit history
     fn from_item(item: &SyncItem, history: Vec<AuditEntry>) -> Self {
         let (message, suggestions) = Self::generate_explanation(item);
+        let reason_code = item
+            .error_info()
+            .and_then(|error_info| error_info.reason())
+            .or_else(|| last_failure_reason(&history));
 
         Self {
             path: item.local_path().clone(),
             state: item.state().to_string(),
             message,
+            reason_code,
             suggestions,
             history,
+            conflict: None,
         }
     }
 
@@ -52,12 +189,14 @@ impl Explanation {
             path: path.clone(),
             state: "unknown".to_string(),
             message: "This file is not being tracked by LNXDrive.".to_string(),
+            reason_code: None,
             suggestions: vec![
                 "Ensure the file is within the configured sync root directory.".to_string(),
                 "Check that the file is not excluded by sync rules or .lnxdriveignore.".to_string(),
                 "Run 'lnxdrive status' to verify the sync root configuration.".to_string(),
             ],
             history: Vec::new(),
+            conflict: None,
         }
     }
 
@@ -121,20 +260,62 @@ impl Explanation {
 
                 // Add context-specific suggestions based on error info
                 if let Some(error_info) = item.error_info() {
-                    match error_info.code() {
-                        "NETWORK_ERROR" => {
+                    match error_info.reason() {
+                        Some(ReasonCode::NetworkError) => {
                             suggestions
                                 .push("Check your network connection and try again.".to_string());
                         }
-                        "AUTH_ERROR" => {
+                        Some(ReasonCode::AuthError) => {
                             suggestions.push("Re-authenticate with 'lnxdrive login'.".to_string());
                         }
-                        "RATE_LIMITED" => {
+                        Some(ReasonCode::RateLimited) => {
                             suggestions.push(
                                 "The cloud provider is rate-limiting requests. Wait a moment and retry."
                                     .to_string(),
                             );
                         }
+                        Some(ReasonCode::QuotaExceeded) => {
+                            suggestions.push(
+                                "Free up space in OneDrive; the file is uploaded automatically once it fits."
+                                    .to_string(),
+                            );
+                        }
+                        Some(ReasonCode::FileTooLarge) => {
+                            suggestions.push(
+                                "OneDrive does not accept files this large. Split or compress the file."
+                                    .to_string(),
+                            );
+                        }
+                        Some(ReasonCode::PathTooLong) => {
+                            suggestions.push(
+                                "Shorten the file name or move it to a less deeply nested folder."
+                                    .to_string(),
+                            );
+                        }
+                        Some(ReasonCode::InvalidName) => {
+                            suggestions.push(
+                                "Rename the file; OneDrive rejects some characters and reserved names."
+                                    .to_string(),
+                            );
+                        }
+                        Some(ReasonCode::Locked) => {
+                            suggestions.push(
+                                "Close the application using the file; it is retried once unlocked."
+                                    .to_string(),
+                            );
+                        }
+                        Some(ReasonCode::PermissionDenied) => {
+                            suggestions.push(
+                                "Check the file permissions and your access to the OneDrive folder."
+                                    .to_string(),
+                            );
+                        }
+                        Some(ReasonCode::Conflict | ReasonCode::MergeFailed) => {
+                            suggestions.push(
+                                "Resolve the conflict with 'lnxdrive conflicts' and check the conflict rules."
+                                    .to_string(),
+                            );
+                        }
                         _ => {
                             suggestions.push(
                                 "Try 'lnxdrive sync --force' to retry the operation.".to_string(),
@@ -164,6 +345,14 @@ impl Explanation {
     }
 }
 
+/// Returns the code of the most recent failed entry of `history`
+fn last_failure_reason(history: &[AuditEntry]) -> Option<ReasonCode> {
+    history
+        .iter()
+        .rev()
+        .find_map(|entry| entry.result().code()?.parse().ok())
+}
+
 /// Use case for generating human-readable failure explanations
 ///
 /// Provides the `lnxdrive explain` functionality by combining sync item
@@ -188,6 +377,7 @@ impl ExplainFailureUseCase {
     /// 1. Looks up the sync item by its local path
     /// 2. Retrieves the audit history for the item
     /// 3. Generates a human-readable message with suggestions
+    /// 4. For a conflicted item, attaches the stored conflict's evidence
     ///
     /// # Arguments
     ///
@@ -220,7 +410,22 @@ impl ExplainFailureUseCase {
             .context("Failed to retrieve audit history for item")?;
 
         // Step 3: Generate the explanation
-        Ok(Explanation::from_item(&item, history))
+        let mut explanation = Explanation::from_item(&item, history);
+
+        // Step 4: Attach the evidence of an unresolved conflict
+        if matches!(item.state(), ItemState::Conflicted) {
+            let conflicts = self
+                .state_repository
+                .get_unresolved_conflicts()
+                .await
+                .context("Failed to retrieve conflicts")?;
+            explanation.conflict = conflicts
+                .iter()
+                .find(|c| c.item_id() == item.id())
+                .map(|c| ConflictEvidence::new(c, &item));
+        }
+
+        Ok(explanation)
     }
 }
 
@@ -229,7 +434,7 @@ mod tests {
     use std::path::PathBuf;
 
     use super::*;
-    use crate::domain::{ErrorInfo, RemotePath, SyncItem};
+    use crate::domain::{AuditAction, AuditResult, ErrorInfo, RemotePath, SyncItem};
 
     fn test_path() -> SyncPath {
         SyncPath::new(PathBuf::from("/home/user/OneDrive/test.txt")).unwrap()
@@ -348,6 +553,118 @@ mod tests {
         assert!(explanation.suggestions.iter().any(|s| s.contains("login")));
     }
 
+    #[test]
+    fn test_explanation_error_path_too_long() {
+        let mut item = create_item_in_state(ItemState::Online);
+        item.transition_to_error(ErrorInfo::from_reason(
+            ReasonCode::PathTooLong,
+            "Path is 420 characters long",
+        ))
+        .unwrap();
+
+        let explanation = Explanation::from_item(&item, vec![]);
+
+        assert!(explanation.message.contains("420 characters"));
+        assert!(explanation
+            .suggestions
+            .iter()
+            .any(|s| s.contains("Shorten")));
+    }
+
+    #[test]
+    fn test_explanation_reason_code() {
+        let mut item = create_item_in_state(ItemState::Online);
+        item.transition_to_error(ErrorInfo::from_reason(ReasonCode::Locked, "File is in use"))
+            .unwrap();
+        let explanation = Explanation::from_item(&item, vec![]);
+        assert_eq!(explanation.reason_code, Some(ReasonCode::Locked));
+        assert!(explanation
+            .suggestions
+            .iter()
+            .any(|s| s.contains("Close the application")));
+
+        // Without an error on the item, the latest failure in the history
+        let item = create_item_in_state(ItemState::Modified);
+        let history = vec![
+            AuditEntry::new(
+                AuditAction::FileUpload,
+                AuditResult::failed("PERMISSION_DENIED", "403 Forbidden"),
+            ),
+            AuditEntry::new(
+                AuditAction::FileUpload,
+                AuditResult::failed("INVALID_NAME", "invalid name"),
+            ),
+        ];
+        let explanation = Explanation::from_item(&item, history);
+        assert_eq!(explanation.reason_code, Some(ReasonCode::InvalidName));
+
+        let json = serde_json::to_value(&explanation).unwrap();
+        assert_eq!(json["reason_code"], "INVALID_NAME");
+    }
+
+    fn version(hash: &str, size: u64, modified_at: DateTime<Utc>) -> VersionInfo {
+        VersionInfo::new(FileHash::new(hash.to_string()).unwrap(), size, modified_at)
+    }
+
+    const BASE_HASH: &str = "AAAAAAAAAAAAAAAAAAAAAAAAAAA=";
+
+    fn conflicted_item() -> SyncItem {
+        let mut item = create_item_in_state(ItemState::Conflicted);
+        item.set_content_hash(FileHash::new(BASE_HASH.to_string()).unwrap());
+        item.set_size_bytes(1024);
+        item
+    }
+
+    #[test]
+    fn test_conflict_evidence_both_sides_changed() {
+        let item = conflicted_item();
+        let now = Utc::now();
+        let conflict = Conflict::new(
+            *item.id(),
+            version("BBBBBBBBBBBBBBBBBBBBBBBBBBB=", 1000, now),
+            version("CCCCCCCCCCCCCCCCCCCCCCCCCCC=", 3072, now),
+        );
+
+        let evidence = ConflictEvidence::new(&conflict, &item);
+
+        assert_eq!(evidence.base.size_bytes, 1024);
+        assert!(evidence.local_change.content_changed);
+        assert_eq!(evidence.local_change.size_delta, -24);
+        assert!(evidence.remote_change.content_changed);
+        assert_eq!(evidence.remote_change.size_delta, 2048);
+        assert!(evidence.summary.starts_with("The cloud version changed at"));
+        assert!(evidence.summary.contains("adding 2.0 KB"));
+        assert!(evidence.summary.contains("you edited the file locally"));
+        assert!(evidence.summary.contains("removing 24 B"));
+    }
+
+    #[test]
+    fn test_conflict_evidence_unchanged_side() {
+        let item = conflicted_item();
+        let conflict = Conflict::new(
+            *item.id(),
+            version("BBBBBBBBBBBBBBBBBBBBBBBBBBB=", 1024, Utc::now()),
+            version(BASE_HASH, 1024, Utc::now()),
+        );
+
+        let evidence = ConflictEvidence::new(&conflict, &item);
+
+        assert!(!evidence.remote_change.content_changed);
+        assert!(evidence.summary.contains("cloud version still matches"));
+        assert!(evidence.summary.contains("without changing the size"));
+
+        let json = serde_json::to_value(&evidence).unwrap();
+        assert_eq!(json["local_change"]["size_delta"], 0);
+        assert_eq!(json["base"]["hash"], BASE_HASH);
+    }
+
+    #[test]
+    fn test_format_size() {
+        assert_eq!(format_size(512), "512 B");
+        assert_eq!(format_size(2048), "2.0 KB");
+        assert_eq!(format_size(5 * 1024 * 1024), "5.0 MB");
+    }
+
     #[test]
     fn test_explanation_deleted() {
         let item = create_item_in_state(ItemState::Deleted);
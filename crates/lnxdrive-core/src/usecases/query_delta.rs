@@ -1,6 +1,6 @@
 //! Delta query use case
 //!
-//! Orchestrates incremental synchronization by querying the Microsoft Graph
+//! Orchestrates incremental synchronization by querying the cloud provider's
 //! delta API for changes since the last sync. Handles delta token management,
 //! converting cloud-side delta items into domain SyncItems.
 
@@ -185,6 +185,7 @@ impl QueryDeltaUseCase {
                     if let Some(hash) = self.parse_content_hash(item)? {
                         existing_item.set_content_hash(hash);
                     }
+                    existing_item.metadata_mut().set_facets(item.facets.clone());
                     existing_item
                 }
                 None => {
@@ -193,7 +194,7 @@ impl QueryDeltaUseCase {
                     let modified = item.modified.unwrap_or_else(Utc::now);
                     let content_hash = self.parse_content_hash(item)?;
 
-                    SyncItem::from_remote(
+                    let mut new_item = SyncItem::from_remote(
                         local_path,
                         remote_path,
                         remote_id.clone(),
@@ -202,7 +203,9 @@ impl QueryDeltaUseCase {
                         content_hash,
                         modified,
                     )
-                    .context("Failed to create SyncItem from delta item")?
+                    .context("Failed to create SyncItem from delta item")?;
+                    new_item.metadata_mut().set_facets(item.facets.clone());
+                    new_item
                 }
             }
         };
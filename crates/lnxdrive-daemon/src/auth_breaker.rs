@@ -0,0 +1,119 @@
+//! Circuit breaker for rejected credentials
+//!
+//! Once a token is revoked or the refresh fails, every sync cycle fails
+//! the same way. Retrying each poll interval only fills the log and sends
+//! requests that cannot succeed. After [`UNAUTHORIZED_THRESHOLD`]
+//! consecutive unauthorized cycles the breaker trips: the daemon stops
+//! syncing, enters `WaitingForAuth`, asks the user to sign in again and
+//! resumes only once `lnxdrive auth login` stored new tokens.
+
+use lnxdrive_core::ports::notification::{Notification, NotificationPriority};
+
+/// Consecutive unauthorized sync cycles after which syncing stops
+pub const UNAUTHORIZED_THRESHOLD: u32 = 3;
+
+/// Counts consecutive unauthorized sync cycles
+#[derive(Debug)]
+pub struct AuthCircuitBreaker {
+    threshold: u32,
+    consecutive: u32,
+}
+
+impl AuthCircuitBreaker {
+    /// Creates a breaker that trips after `threshold` unauthorized cycles
+    pub fn new(threshold: u32) -> Self {
+        Self {
+            threshold: threshold.max(1),
+            consecutive: 0,
+        }
+    }
+
+    /// Records a successful cycle, closing the breaker again
+    pub fn record_success(&mut self) {
+        self.consecutive = 0;
+    }
+
+    /// Records a failed cycle and returns `true` if the breaker is now open
+    ///
+    /// Failures other than rejected credentials (network, server errors)
+    /// neither count nor reset the count.
+    pub fn record_failure(&mut self, error: &anyhow::Error) -> bool {
+        if is_unauthorized_error(error) {
+            self.consecutive += 1;
+        }
+        self.is_open()
+    }
+
+    /// Returns `true` once the threshold was reached
+    pub fn is_open(&self) -> bool {
+        self.consecutive >= self.threshold
+    }
+}
+
+impl Default for AuthCircuitBreaker {
+    fn default() -> Self {
+        Self::new(UNAUTHORIZED_THRESHOLD)
+    }
+}
+
+/// Returns `true` if `error` means the credentials were rejected
+///
+/// Covers HTTP 401 responses of the Graph API and failed token refreshes
+/// (`invalid_grant`).
+pub fn is_unauthorized_error(error: &anyhow::Error) -> bool {
+    let message = format!("{error:#}").to_lowercase();
+    message.contains("unauthorized")
+        || message.contains("invalidauthenticationtoken")
+        || message.contains("invalid_grant")
+}
+
+/// Desktop notification asking the user to sign in again
+pub fn relogin_notification() -> Notification {
+    Notification::new(
+        "Sign in to OneDrive again",
+        "Your OneDrive credentials were rejected, so syncing has stopped. \
+         Run 'lnxdrive auth login' to resume.",
+    )
+    .with_priority(NotificationPriority::High)
+    .with_category("auth")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn unauthorized() -> anyhow::Error {
+        anyhow::anyhow!("HTTP status client error (401 Unauthorized) for url (https://graph)")
+            .context("Delta query failed")
+    }
+
+    #[test]
+    fn test_breaker_trips_after_threshold() {
+        let mut breaker = AuthCircuitBreaker::new(3);
+        assert!(!breaker.record_failure(&unauthorized()));
+        assert!(!breaker.record_failure(&unauthorized()));
+        assert!(breaker.record_failure(&unauthorized()));
+        assert!(breaker.is_open());
+    }
+
+    #[test]
+    fn test_success_resets_and_other_errors_do_not_count() {
+        let mut breaker = AuthCircuitBreaker::new(2);
+        assert!(!breaker.record_failure(&unauthorized()));
+        breaker.record_success();
+        assert!(!breaker.record_failure(&unauthorized()));
+        assert!(!breaker.record_failure(&anyhow::anyhow!("connection reset by peer")));
+        assert!(breaker.record_failure(&unauthorized()));
+    }
+
+    #[test]
+    fn test_is_unauthorized_error() {
+        assert!(is_unauthorized_error(&unauthorized()));
+        assert!(is_unauthorized_error(&anyhow::anyhow!(
+            "Token refresh failed: invalid_grant"
+        )));
+        assert!(!is_unauthorized_error(&anyhow::anyhow!(
+            "Server error: 503 Service Unavailable"
+        )));
+    }
+}
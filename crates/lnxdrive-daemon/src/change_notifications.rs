@@ -0,0 +1,405 @@
+//! Microsoft Graph change notifications (webhooks)
+//!
+//! When `change_notifications.enabled` is set, the daemon subscribes the
+//! configured public URL to changes of the drive and listens for the
+//! notifications Microsoft Graph posts there (relayed to
+//! `change_notifications.listen_address`). A notification wakes the sync
+//! loop, which runs a delta sync right away instead of at the next poll.
+//!
+//! The subscription is renewed halfway through its lifetime. While none
+//! is active (it could not be created, or renewal failed) the daemon polls
+//! at `sync.poll_interval` as usual; with one it polls at the longer
+//! `change_notifications.poll_interval` to catch lost notifications.
+
+use std::{convert::Infallible, io, net::SocketAddr, sync::Arc};
+
+use chrono::{DateTime, Duration, Utc};
+use http_body_util::{BodyExt, Full, Limited};
+use hyper::{
+    body::{Bytes, Incoming},
+    server::conn::http1,
+    service::service_fn,
+    Method, Request, Response, StatusCode,
+};
+use hyper_util::rt::TokioIo;
+use lnxdrive_core::{config::ChangeNotificationsConfig, domain::newtypes::UniqueId};
+use lnxdrive_graph::{
+    client::GraphClient,
+    subscription::{self, ChangeNotificationCollection, Subscription, VALIDATION_TOKEN_PARAM},
+};
+use tokio::{net::TcpListener, sync::Notify, task::JoinHandle};
+use tokio_util::sync::CancellationToken;
+use tracing::{debug, info, warn};
+
+/// Largest notification body accepted
+const MAX_NOTIFICATION_BODY: usize = 1024 * 1024;
+
+// ============================================================================
+// Notification receiver
+// ============================================================================
+
+/// HTTP endpoint Microsoft Graph posts notifications to
+///
+/// - `POST ?validationToken=...`: URL validation when a subscription is
+///   created; the token is echoed back as `text/plain`
+/// - `POST` with a JSON body: notifications; those carrying the expected
+///   `clientState` wake the sync loop through `remote_changes`
+pub struct NotificationReceiver {
+    client_state: String,
+    remote_changes: Arc<Notify>,
+}
+
+impl NotificationReceiver {
+    /// Creates a receiver accepting notifications with `client_state`
+    pub fn new(client_state: impl Into<String>, remote_changes: Arc<Notify>) -> Self {
+        Self {
+            client_state: client_state.into(),
+            remote_changes,
+        }
+    }
+
+    /// Binds `addr` and serves requests until `shutdown` is cancelled
+    ///
+    /// Returns the bound address (useful with port `0`) and the server task.
+    pub async fn spawn(
+        self,
+        addr: SocketAddr,
+        shutdown: CancellationToken,
+    ) -> io::Result<(SocketAddr, JoinHandle<()>)> {
+        let listener = TcpListener::bind(addr).await?;
+        let local_addr = listener.local_addr()?;
+        info!(address = %local_addr, "Change notification receiver listening");
+
+        let receiver = Arc::new(self);
+        let handle = tokio::spawn(async move { receiver.serve(listener, shutdown).await });
+        Ok((local_addr, handle))
+    }
+
+    async fn serve(self: Arc<Self>, listener: TcpListener, shutdown: CancellationToken) {
+        loop {
+            let stream = tokio::select! {
+                accepted = listener.accept() => match accepted {
+                    Ok((stream, _)) => stream,
+                    Err(e) => {
+                        warn!(error = %e, "Notification receiver failed to accept connection");
+                        continue;
+                    }
+                },
+                _ = shutdown.cancelled() => break,
+            };
+
+            let receiver = Arc::clone(&self);
+            tokio::spawn(async move {
+                let service = service_fn(move |req: Request<Incoming>| {
+                    let receiver = Arc::clone(&receiver);
+                    async move { Ok::<_, Infallible>(receiver.handle(req).await) }
+                });
+                if let Err(e) = http1::Builder::new()
+                    .serve_connection(TokioIo::new(stream), service)
+                    .await
+                {
+                    debug!(error = %e, "Notification receiver connection error");
+                }
+            });
+        }
+        debug!("Notification receiver stopped");
+    }
+
+    async fn handle(&self, req: Request<Incoming>) -> Response<Full<Bytes>> {
+        if req.method() != Method::POST {
+            return response(
+                StatusCode::METHOD_NOT_ALLOWED,
+                "method not allowed\n".into(),
+            );
+        }
+
+        let validation_token = req.uri().query().and_then(|query| {
+            url::form_urlencoded::parse(query.as_bytes())
+                .find(|(name, _)| name == VALIDATION_TOKEN_PARAM)
+                .map(|(_, token)| token.into_owned())
+        });
+        if let Some(token) = validation_token {
+            debug!("Answering notification URL validation");
+            return response(StatusCode::OK, token.into_bytes());
+        }
+
+        let body = match Limited::new(req.into_body(), MAX_NOTIFICATION_BODY)
+            .collect()
+            .await
+        {
+            Ok(body) => body.to_bytes(),
+            Err(e) => {
+                debug!(error = %e, "Failed to read notification body");
+                return response(StatusCode::BAD_REQUEST, "unreadable body\n".into());
+            }
+        };
+        match serde_json::from_slice::<ChangeNotificationCollection>(&body) {
+            Ok(notifications) => {
+                self.accept(&notifications);
+                response(StatusCode::ACCEPTED, Vec::new())
+            }
+            Err(e) => {
+                debug!(error = %e, "Invalid notification body");
+                response(StatusCode::BAD_REQUEST, "invalid notification\n".into())
+            }
+        }
+    }
+
+    /// Wakes the sync loop if any notification is genuine
+    ///
+    /// Notifications with another `clientState` are acknowledged but
+    /// ignored: they did not come from a subscription of this daemon.
+    fn accept(&self, notifications: &ChangeNotificationCollection) {
+        let genuine = notifications
+            .value
+            .iter()
+            .filter(|n| n.client_state.as_deref() == Some(self.client_state.as_str()))
+            .count();
+        if genuine < notifications.value.len() {
+            warn!(
+                ignored = notifications.value.len() - genuine,
+                "Ignoring change notifications with an unexpected clientState"
+            );
+        }
+        if genuine > 0 {
+            debug!(notifications = genuine, "Remote changes notified");
+            self.remote_changes.notify_one();
+        }
+    }
+}
+
+fn response(status: StatusCode, body: Vec<u8>) -> Response<Full<Bytes>> {
+    Response::builder()
+        .status(status)
+        .header("Content-Type", "text/plain")
+        .body(Full::new(Bytes::from(body)))
+        .expect("static response parts are valid")
+}
+
+// ============================================================================
+// Subscription lifecycle
+// ============================================================================
+
+/// Keeps a change-notification subscription alive for one session
+///
+/// Created by [`ChangeNotifications::start`], which also starts the
+/// receiver; [`stop`](Self::stop) deletes the subscription and stops the
+/// receiver.
+pub struct ChangeNotifications {
+    client: GraphClient,
+    notification_url: String,
+    client_state: String,
+    lifetime: Duration,
+    subscription: Option<Subscription>,
+    remote_changes: Arc<Notify>,
+    receiver_shutdown: CancellationToken,
+}
+
+impl ChangeNotifications {
+    /// Starts the receiver if change notifications are enabled
+    ///
+    /// The subscription itself is created by
+    /// [`ensure_subscription`](Self::ensure_subscription). Returns `None`
+    /// when disabled, or when the receiver cannot listen; the daemon then
+    /// only polls.
+    pub async fn start(
+        config: &ChangeNotificationsConfig,
+        client: GraphClient,
+        shutdown: &CancellationToken,
+    ) -> Option<Self> {
+        if !config.enabled {
+            return None;
+        }
+        let Some(notification_url) = config.notification_url.clone() else {
+            warn!("Change notifications enabled without a notification_url, polling instead");
+            return None;
+        };
+        let addr: SocketAddr = match config.listen_address.parse() {
+            Ok(addr) => addr,
+            Err(e) => {
+                warn!(
+                    address = %config.listen_address,
+                    error = %e,
+                    "Invalid change notification address, polling instead"
+                );
+                return None;
+            }
+        };
+
+        let client_state = UniqueId::new().to_string();
+        let remote_changes = Arc::new(Notify::new());
+        let receiver_shutdown = shutdown.child_token();
+        let receiver = NotificationReceiver::new(&client_state, Arc::clone(&remote_changes));
+        if let Err(e) = receiver.spawn(addr, receiver_shutdown.clone()).await {
+            warn!(
+                address = %addr,
+                error = %e,
+                "Failed to start change notification receiver, polling instead"
+            );
+            return None;
+        }
+
+        Some(Self {
+            client,
+            notification_url,
+            client_state,
+            lifetime: Duration::minutes(i64::from(config.expiration_minutes)),
+            subscription: None,
+            remote_changes,
+            receiver_shutdown,
+        })
+    }
+
+    /// Notified whenever Microsoft Graph reports remote changes
+    pub fn remote_changes(&self) -> Arc<Notify> {
+        Arc::clone(&self.remote_changes)
+    }
+
+    /// Creates the subscription, or renews it once half its lifetime has
+    /// passed
+    ///
+    /// Returns whether a subscription is active afterwards. Failures are
+    /// logged; the next call tries again.
+    pub async fn ensure_subscription(&mut self) -> bool {
+        let now = Utc::now();
+        if let Some(current) = &self.subscription {
+            if !needs_renewal(current.expiration_date_time, self.lifetime, now) {
+                return true;
+            }
+            match subscription::renew_subscription(&self.client, &current.id, now + self.lifetime)
+                .await
+            {
+                Ok(renewed) => {
+                    self.subscription = Some(renewed);
+                    return true;
+                }
+                Err(e) => {
+                    warn!(error = %format!("{e:#}"), "Failed to renew change-notification subscription");
+                    self.subscription = None;
+                }
+            }
+        }
+
+        match subscription::create_subscription(
+            &self.client,
+            &self.notification_url,
+            &self.client_state,
+            now + self.lifetime,
+        )
+        .await
+        {
+            Ok(created) => {
+                self.subscription = Some(created);
+                true
+            }
+            Err(e) => {
+                warn!(
+                    error = %format!("{e:#}"),
+                    "Failed to subscribe to change notifications, polling instead"
+                );
+                false
+            }
+        }
+    }
+
+    /// Deletes the subscription and stops the receiver
+    pub async fn stop(mut self) {
+        if let Some(current) = self.subscription.take() {
+            if let Err(e) = subscription::delete_subscription(&self.client, &current.id).await {
+                warn!(error = %format!("{e:#}"), "Failed to delete change-notification subscription");
+            }
+        }
+        self.receiver_shutdown.cancel();
+    }
+}
+
+/// Whether a subscription expiring at `expiration` is past half of
+/// `lifetime` at `now`
+fn needs_renewal(expiration: DateTime<Utc>, lifetime: Duration, now: DateTime<Utc>) -> bool {
+    expiration - now < lifetime / 2
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration as StdDuration;
+
+    use super::*;
+
+    async fn start_receiver() -> (String, Arc<Notify>, CancellationToken) {
+        let remote_changes = Arc::new(Notify::new());
+        let shutdown = CancellationToken::new();
+        let (addr, _handle) = NotificationReceiver::new("secret", Arc::clone(&remote_changes))
+            .spawn("127.0.0.1:0".parse().unwrap(), shutdown.clone())
+            .await
+            .unwrap();
+        (format!("http://{addr}"), remote_changes, shutdown)
+    }
+
+    fn notification(client_state: &str) -> serde_json::Value {
+        serde_json::json!({
+            "value": [{
+                "subscriptionId": "sub-1",
+                "clientState": client_state,
+                "resource": "/me/drive/root"
+            }]
+        })
+    }
+
+    #[tokio::test]
+    async fn test_receiver_echoes_validation_token() {
+        let (base, _, shutdown) = start_receiver().await;
+
+        let response = reqwest::Client::new()
+            .post(format!("{base}/?validationToken=Validation%3A%20Testing"))
+            .send()
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), 200);
+        assert_eq!(response.text().await.unwrap(), "Validation: Testing");
+        shutdown.cancel();
+    }
+
+    #[tokio::test]
+    async fn test_receiver_wakes_sync_only_for_genuine_notifications() {
+        let (base, remote_changes, shutdown) = start_receiver().await;
+        let client = reqwest::Client::new();
+
+        let forged = client
+            .post(&base)
+            .json(&notification("forged"))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(forged.status(), 202);
+        let woken =
+            tokio::time::timeout(StdDuration::from_millis(100), remote_changes.notified()).await;
+        assert!(woken.is_err(), "forged notification woke the sync loop");
+
+        let genuine = client
+            .post(&base)
+            .json(&notification("secret"))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(genuine.status(), 202);
+        tokio::time::timeout(StdDuration::from_secs(5), remote_changes.notified())
+            .await
+            .expect("genuine notification did not wake the sync loop");
+
+        let invalid = client.post(&base).body("not json").send().await.unwrap();
+        assert_eq!(invalid.status(), 400);
+        shutdown.cancel();
+    }
+
+    #[test]
+    fn test_subscription_renewed_halfway_through_lifetime() {
+        let now = Utc::now();
+        let lifetime = Duration::minutes(4230);
+
+        assert!(!needs_renewal(now + lifetime, lifetime, now));
+        assert!(!needs_renewal(now + Duration::minutes(2200), lifetime, now));
+        assert!(needs_renewal(now + Duration::minutes(2000), lifetime, now));
+        assert!(needs_renewal(now - Duration::minutes(1), lifetime, now));
+    }
+}
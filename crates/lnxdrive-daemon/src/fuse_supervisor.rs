@@ -0,0 +1,350 @@
+//! Recovery of an aborted FUSE session
+//!
+//! The kernel can abort the connection of a FUSE filesystem (e.g. through
+//! `/sys/fs/fuse/connections/*/abort`, or when the session thread fails).
+//! The mount then answers every access with `Transport endpoint is not
+//! connected` until it is unmounted. [`supervise`] watches the session
+//! thread and, once it ended while the daemon still runs, detaches the
+//! stale mount and mounts again, backing off between attempts. After
+//! [`REMOUNT_MAX_ATTEMPTS`] failures it gives up and tells the user.
+
+use std::{
+    path::Path,
+    process::Command,
+    sync::{Arc, RwLock},
+    time::Duration,
+};
+
+use async_trait::async_trait;
+use lnxdrive_core::{
+    domain::newtypes::UniqueId,
+    ports::{
+        notification::{INotificationService, Notification, NotificationPriority},
+        IItemObserver,
+    },
+};
+use lnxdrive_fuse::RemoteChanges;
+use tokio_util::sync::CancellationToken;
+use tracing::{debug, error, info, warn};
+
+/// Remount attempts after which the daemon gives up on the mount
+pub const REMOUNT_MAX_ATTEMPTS: u32 = 5;
+
+/// How often and how persistently a lost session is remounted
+#[derive(Debug, Clone)]
+pub struct RemountPolicy {
+    /// Interval between checks of the session
+    pub check_interval: Duration,
+    /// Delay before the first remount attempt, doubled for each next one
+    pub initial_backoff: Duration,
+    /// Longest delay between two attempts
+    pub max_backoff: Duration,
+    /// Attempts before giving up
+    pub max_attempts: u32,
+}
+
+impl RemountPolicy {
+    /// Delay before remount attempt `attempt` (starting at 1)
+    pub fn backoff(&self, attempt: u32) -> Duration {
+        let factor = 2u32.saturating_pow(attempt.saturating_sub(1));
+        self.initial_backoff
+            .saturating_mul(factor)
+            .min(self.max_backoff)
+    }
+}
+
+impl Default for RemountPolicy {
+    fn default() -> Self {
+        Self {
+            check_interval: Duration::from_secs(5),
+            initial_backoff: Duration::from_secs(1),
+            max_backoff: Duration::from_secs(60),
+            max_attempts: REMOUNT_MAX_ATTEMPTS,
+        }
+    }
+}
+
+/// A supervised FUSE mount
+#[async_trait]
+pub trait SupervisedMount: Send + Sync {
+    /// Returns `false` once the session serving the mount has ended
+    fn is_alive(&self) -> bool;
+
+    /// Detaches what is left of the mount and mounts the filesystem again
+    async fn remount(&self) -> anyhow::Result<()>;
+}
+
+/// Watches `mount` until `shutdown` and remounts it when its session ends
+///
+/// Returns early if the mount could not be recovered within
+/// `policy.max_attempts`, after notifying the user through `notifier`.
+pub async fn supervise(
+    mount: &dyn SupervisedMount,
+    policy: &RemountPolicy,
+    notifier: &dyn INotificationService,
+    shutdown: &CancellationToken,
+) {
+    loop {
+        tokio::select! {
+            _ = tokio::time::sleep(policy.check_interval) => {}
+            _ = shutdown.cancelled() => return,
+        }
+        if mount.is_alive() {
+            continue;
+        }
+
+        warn!("FUSE session ended unexpectedly, remounting");
+        let mut recovered = false;
+        for attempt in 1..=policy.max_attempts {
+            tokio::select! {
+                _ = tokio::time::sleep(policy.backoff(attempt)) => {}
+                _ = shutdown.cancelled() => return,
+            }
+            match mount.remount().await {
+                Ok(()) => {
+                    info!(attempt, "FUSE filesystem remounted");
+                    recovered = true;
+                    break;
+                }
+                Err(e) => warn!(attempt, error = %format!("{e:#}"), "Remount failed"),
+            }
+        }
+
+        if !recovered {
+            error!(
+                attempts = policy.max_attempts,
+                "Giving up remounting the FUSE filesystem"
+            );
+            if let Err(e) = notifier.notify(&remount_failed_notification()).await {
+                warn!(error = %e, "Failed to show the remount notification");
+            }
+            return;
+        }
+    }
+}
+
+/// Desktop notification telling the user the mount could not be recovered
+pub fn remount_failed_notification() -> Notification {
+    Notification::new(
+        "OneDrive folder is unavailable",
+        "The OneDrive filesystem stopped responding and could not be mounted \
+         again. Syncing continues; run 'lnxdrive daemon restart' to mount it.",
+    )
+    .with_priority(NotificationPriority::High)
+    .with_category("mount")
+}
+
+/// Lazily unmounts `mount_point` if it is a stale FUSE mount
+///
+/// A mount whose session died answers `stat` with `ENOTCONN`; it has to
+/// be detached before anything can be mounted there again.
+pub fn detach_stale_mount(mount_point: &Path) {
+    let stale = matches!(
+        std::fs::metadata(mount_point),
+        Err(e) if e.raw_os_error() == Some(libc::ENOTCONN)
+    );
+    if !stale {
+        return;
+    }
+
+    for fusermount in ["fusermount3", "fusermount"] {
+        match Command::new(fusermount)
+            .args(["-u", "-z"])
+            .arg(mount_point)
+            .output()
+        {
+            Ok(output) if output.status.success() => {
+                debug!(mount_point = %mount_point.display(), "Detached stale mount");
+                return;
+            }
+            Ok(output) => debug!(
+                fusermount,
+                stderr = %String::from_utf8_lossy(&output.stderr).trim(),
+                "Failed to detach stale mount"
+            ),
+            Err(e) => debug!(fusermount, error = %e, "Cannot run fusermount"),
+        }
+    }
+}
+
+/// Forwards remote renames and (de)selected folders to the current mount
+///
+/// The sync engine keeps one item observer for its lifetime, while each
+/// remount creates a new [`RemoteChanges`]; the daemon swaps it in here.
+#[derive(Default)]
+pub struct MountedItems {
+    current: RwLock<Option<Arc<RemoteChanges>>>,
+}
+
+impl MountedItems {
+    /// Sends renames to `remote_changes` from now on
+    pub fn set(&self, remote_changes: Option<Arc<RemoteChanges>>) {
+        if let Ok(mut current) = self.current.write() {
+            *current = remote_changes;
+        }
+    }
+
+    fn current(&self) -> Option<Arc<RemoteChanges>> {
+        self.current.read().ok().and_then(|c| c.clone())
+    }
+}
+
+impl IItemObserver for MountedItems {
+    fn item_moved(&self, item_id: &UniqueId, new_parent: Option<&UniqueId>, new_name: &str) {
+        if let Some(remote_changes) = self.current() {
+            remote_changes.item_moved(item_id, new_parent, new_name);
+        }
+    }
+
+    fn children_added(&self, parent: Option<&UniqueId>) {
+        if let Some(remote_changes) = self.current() {
+            remote_changes.children_added(parent);
+        }
+    }
+
+    fn item_removed(&self, item_id: &UniqueId) {
+        if let Some(remote_changes) = self.current() {
+            remote_changes.item_removed(item_id);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{
+        atomic::{AtomicBool, AtomicU32, Ordering},
+        Mutex,
+    };
+
+    use super::*;
+
+    /// Mount whose session loss is simulated by clearing `alive`
+    struct FakeMount {
+        alive: AtomicBool,
+        remounts: AtomicU32,
+        fail_remounts: bool,
+    }
+
+    impl FakeMount {
+        fn new(fail_remounts: bool) -> Self {
+            Self {
+                alive: AtomicBool::new(true),
+                remounts: AtomicU32::new(0),
+                fail_remounts,
+            }
+        }
+    }
+
+    #[async_trait]
+    impl SupervisedMount for FakeMount {
+        fn is_alive(&self) -> bool {
+            self.alive.load(Ordering::SeqCst)
+        }
+
+        async fn remount(&self) -> anyhow::Result<()> {
+            self.remounts.fetch_add(1, Ordering::SeqCst);
+            if self.fail_remounts {
+                anyhow::bail!("mount point busy");
+            }
+            self.alive.store(true, Ordering::SeqCst);
+            Ok(())
+        }
+    }
+
+    #[derive(Default)]
+    struct RecordingNotifier {
+        titles: Mutex<Vec<String>>,
+    }
+
+    #[async_trait]
+    impl INotificationService for RecordingNotifier {
+        async fn notify(&self, notification: &Notification) -> anyhow::Result<()> {
+            self.titles.lock().unwrap().push(notification.title.clone());
+            Ok(())
+        }
+
+        async fn show_progress(
+            &self,
+            _progress_id: &str,
+            _title: &str,
+            _percent: f64,
+        ) -> anyhow::Result<()> {
+            Ok(())
+        }
+
+        async fn clear_progress(&self, _progress_id: &str) -> anyhow::Result<()> {
+            Ok(())
+        }
+    }
+
+    fn fast_policy() -> RemountPolicy {
+        RemountPolicy {
+            check_interval: Duration::from_millis(5),
+            initial_backoff: Duration::from_millis(1),
+            max_backoff: Duration::from_millis(4),
+            max_attempts: 3,
+        }
+    }
+
+    #[test]
+    fn test_backoff_doubles_up_to_the_maximum() {
+        let policy = RemountPolicy::default();
+        assert_eq!(policy.backoff(1), Duration::from_secs(1));
+        assert_eq!(policy.backoff(2), Duration::from_secs(2));
+        assert_eq!(policy.backoff(4), Duration::from_secs(8));
+        assert_eq!(policy.backoff(10), Duration::from_secs(60));
+        assert_eq!(policy.backoff(u32::MAX), Duration::from_secs(60));
+    }
+
+    #[tokio::test]
+    async fn test_lost_session_is_remounted() {
+        let mount = Arc::new(FakeMount::new(false));
+        let notifier = Arc::new(RecordingNotifier::default());
+        let shutdown = CancellationToken::new();
+
+        let task = {
+            let (mount, notifier, shutdown) =
+                (Arc::clone(&mount), Arc::clone(&notifier), shutdown.clone());
+            tokio::spawn(async move {
+                supervise(mount.as_ref(), &fast_policy(), notifier.as_ref(), &shutdown).await
+            })
+        };
+
+        // Simulate the kernel aborting the connection
+        mount.alive.store(false, Ordering::SeqCst);
+        tokio::time::timeout(Duration::from_secs(5), async {
+            while mount.remounts.load(Ordering::SeqCst) == 0 {
+                tokio::time::sleep(Duration::from_millis(1)).await;
+            }
+        })
+        .await
+        .expect("no remount was attempted");
+
+        shutdown.cancel();
+        task.await.unwrap();
+        assert_eq!(mount.remounts.load(Ordering::SeqCst), 1);
+        assert!(mount.is_alive());
+        assert!(notifier.titles.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_gives_up_and_notifies_after_max_attempts() {
+        let mount = FakeMount::new(true);
+        mount.alive.store(false, Ordering::SeqCst);
+        let notifier = RecordingNotifier::default();
+        let shutdown = CancellationToken::new();
+
+        tokio::time::timeout(
+            Duration::from_secs(5),
+            supervise(&mount, &fast_policy(), &notifier, &shutdown),
+        )
+        .await
+        .expect("supervision did not give up");
+
+        assert_eq!(mount.remounts.load(Ordering::SeqCst), 3);
+        assert_eq!(
+            *notifier.titles.lock().unwrap(),
+            vec![remount_failed_notification().title]
+        );
+    }
+}
@@ -0,0 +1,97 @@
+//! Health probe data for the metrics server
+//!
+//! Adapts the daemon's shared state and database pool to the
+//! [`HealthSource`] trait behind `/readyz`.
+
+use std::{sync::Arc, time::Duration};
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use lnxdrive_cache::pool::DatabasePool;
+use lnxdrive_core::config::MetricsConfig;
+use lnxdrive_ipc::service::DaemonState;
+use lnxdrive_telemetry::{HealthSource, ReadinessThresholds};
+use tokio::sync::Mutex;
+
+/// Readiness facts read from the running daemon
+pub struct DaemonHealth {
+    daemon_state: Arc<Mutex<DaemonState>>,
+    db_pool: DatabasePool,
+}
+
+impl DaemonHealth {
+    pub fn new(daemon_state: Arc<Mutex<DaemonState>>, db_pool: DatabasePool) -> Self {
+        Self {
+            daemon_state,
+            db_pool,
+        }
+    }
+}
+
+#[async_trait]
+impl HealthSource for DaemonHealth {
+    async fn is_authenticated(&self) -> bool {
+        // Only set once tokens were loaded from the keyring
+        self.daemon_state.lock().await.account_email.is_some()
+    }
+
+    async fn last_successful_sync(&self) -> Option<DateTime<Utc>> {
+        // Also exposed as `Sync.SecondsSinceLastSync`
+        self.daemon_state.lock().await.last_successful_sync
+    }
+
+    async fn ping_database(&self) -> Result<(), String> {
+        self.db_pool.ping().await.map_err(|e| e.to_string())
+    }
+}
+
+/// Converts the `metrics` config section into readiness thresholds
+pub fn readiness_thresholds(config: &MetricsConfig) -> ReadinessThresholds {
+    ReadinessThresholds {
+        max_sync_age: Duration::from_secs(config.ready_max_sync_age),
+        db_timeout: Duration::from_millis(config.ready_db_timeout_ms),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use lnxdrive_core::domain::SyncHistoryEntry;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_reads_daemon_state() {
+        let pool = DatabasePool::in_memory().await.unwrap();
+        let state = Arc::new(Mutex::new(DaemonState::default()));
+        let health = DaemonHealth::new(Arc::clone(&state), pool);
+
+        assert!(!health.is_authenticated().await);
+        assert!(health.last_successful_sync().await.is_none());
+        assert!(health.ping_database().await.is_ok());
+
+        let started = Utc::now();
+        {
+            let mut state = state.lock().await;
+            state.account_email = Some("user@example.com".into());
+            state.push_sync_history(SyncHistoryEntry::failed(started, "offline"));
+        }
+        assert!(health.is_authenticated().await);
+        assert!(health.last_successful_sync().await.is_none());
+
+        let mut entry = SyncHistoryEntry::completed(started, 0, 0, 0, 0, 0, vec![], 10);
+        entry.finished_at = started;
+        {
+            let mut state = state.lock().await;
+            state.push_sync_history(entry);
+            state.push_sync_history(SyncHistoryEntry::failed(Utc::now(), "offline"));
+        }
+        assert_eq!(health.last_successful_sync().await, Some(started));
+    }
+
+    #[test]
+    fn test_readiness_thresholds_from_config() {
+        let thresholds = readiness_thresholds(&MetricsConfig::default());
+        assert_eq!(thresholds.max_sync_age, Duration::from_secs(300));
+        assert_eq!(thresholds.db_timeout, Duration::from_millis(2000));
+    }
+}
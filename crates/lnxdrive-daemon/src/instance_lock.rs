@@ -0,0 +1,206 @@
+//! Single-instance lock file
+//!
+//! The D-Bus well-known name is the primary single-instance guard, but it
+//! only works where a session bus is available. This lock file under the
+//! runtime directory backs it up:
+//!
+//! - The file is locked with `flock(LOCK_EX | LOCK_NB)`; the kernel drops
+//!   the lock when the process dies, so a crash never blocks a restart.
+//! - The owner's PID is written into the file so a conflicting start can
+//!   report it. On filesystems without `flock` support the PID alone is
+//!   used, and a lock whose PID is no longer alive is treated as stale.
+
+use std::{
+    fs::{self, File, OpenOptions},
+    io::{self, Read, Seek, SeekFrom, Write},
+    os::unix::io::AsRawFd,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{Context, Result};
+use tracing::{debug, warn};
+
+/// File name of the lock inside the runtime directory
+const LOCK_FILE_NAME: &str = "lnxdrived.lock";
+
+/// Exclusive lock held for the lifetime of the daemon
+///
+/// The lock is released when this value is dropped (the file descriptor is
+/// closed). The file itself is left in place so its inode stays stable.
+#[derive(Debug)]
+pub struct InstanceLock {
+    _file: File,
+    path: PathBuf,
+}
+
+impl InstanceLock {
+    /// Returns the default lock path: `$XDG_RUNTIME_DIR/lnxdrive/lnxdrived.lock`
+    ///
+    /// Falls back to the temporary directory when no runtime dir is set.
+    pub fn default_path() -> PathBuf {
+        dirs::runtime_dir()
+            .unwrap_or_else(std::env::temp_dir)
+            .join("lnxdrive")
+            .join(LOCK_FILE_NAME)
+    }
+
+    /// Acquires the lock at `path`, failing if another live daemon holds it
+    pub fn acquire(path: &Path) -> Result<Self> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create {}", parent.display()))?;
+        }
+
+        let mut file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(path)
+            .with_context(|| format!("Failed to open lock file {}", path.display()))?;
+
+        let owner = read_pid(&mut file);
+        match try_flock(&file) {
+            Ok(true) => {}
+            Ok(false) => anyhow::bail!(already_running(owner, path)),
+            Err(e) => {
+                // No flock support (e.g. some network filesystems): fall back
+                // to checking whether the recorded PID is still alive
+                warn!(error = %e, "flock unavailable, using PID-only lock");
+                if let Some(pid) = owner.filter(|&pid| pid != std::process::id()) {
+                    if is_process_alive(pid) {
+                        anyhow::bail!(already_running(Some(pid), path));
+                    }
+                    debug!(pid, "Removing stale lock of a dead process");
+                }
+            }
+        }
+
+        write_pid(&mut file, std::process::id())
+            .with_context(|| format!("Failed to write lock file {}", path.display()))?;
+
+        Ok(Self {
+            _file: file,
+            path: path.to_path_buf(),
+        })
+    }
+
+    /// Returns the path of the lock file
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+fn already_running(pid: Option<u32>, path: &Path) -> String {
+    match pid {
+        Some(pid) => format!(
+            "Another instance of lnxdrived is already running (PID {pid}, lock {}). \
+             Use 'lnxdrive daemon stop' to stop it first.",
+            path.display()
+        ),
+        None => format!(
+            "Another instance of lnxdrived is already running (lock {}). \
+             Use 'lnxdrive daemon stop' to stop it first.",
+            path.display()
+        ),
+    }
+}
+
+/// Tries to take an exclusive, non-blocking `flock`
+///
+/// Returns `Ok(false)` if another process holds the lock.
+fn try_flock(file: &File) -> io::Result<bool> {
+    // SAFETY: flock only operates on the descriptor, which `file` keeps open.
+    let rc = unsafe { libc::flock(file.as_raw_fd(), libc::LOCK_EX | libc::LOCK_NB) };
+    if rc == 0 {
+        return Ok(true);
+    }
+    let err = io::Error::last_os_error();
+    if err.raw_os_error() == Some(libc::EWOULDBLOCK) {
+        Ok(false)
+    } else {
+        Err(err)
+    }
+}
+
+/// Returns true if a process with `pid` exists
+fn is_process_alive(pid: u32) -> bool {
+    let Ok(pid) = libc::pid_t::try_from(pid) else {
+        return false;
+    };
+    // SAFETY: signal 0 performs the existence/permission check only.
+    let rc = unsafe { libc::kill(pid, 0) };
+    rc == 0 || io::Error::last_os_error().raw_os_error() == Some(libc::EPERM)
+}
+
+fn read_pid(file: &mut File) -> Option<u32> {
+    let mut contents = String::new();
+    file.seek(SeekFrom::Start(0)).ok()?;
+    file.read_to_string(&mut contents).ok()?;
+    contents.trim().parse().ok()
+}
+
+fn write_pid(file: &mut File, pid: u32) -> io::Result<()> {
+    file.set_len(0)?;
+    file.seek(SeekFrom::Start(0))?;
+    writeln!(file, "{pid}")?;
+    file.sync_all()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_acquire_writes_own_pid() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("run").join(LOCK_FILE_NAME);
+
+        let lock = InstanceLock::acquire(&path).unwrap();
+
+        assert_eq!(lock.path(), path);
+        let contents = fs::read_to_string(&path).unwrap();
+        assert_eq!(contents.trim(), std::process::id().to_string());
+    }
+
+    #[test]
+    fn test_second_acquire_fails_with_owner_pid() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(LOCK_FILE_NAME);
+
+        let _lock = InstanceLock::acquire(&path).unwrap();
+        let err = InstanceLock::acquire(&path).unwrap_err().to_string();
+
+        assert!(err.contains("already running"));
+        assert!(err.contains(&format!("PID {}", std::process::id())));
+    }
+
+    #[test]
+    fn test_lock_is_released_on_drop() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(LOCK_FILE_NAME);
+
+        drop(InstanceLock::acquire(&path).unwrap());
+
+        assert!(InstanceLock::acquire(&path).is_ok());
+    }
+
+    #[test]
+    fn test_stale_pid_from_crashed_daemon_does_not_block() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(LOCK_FILE_NAME);
+        // A crashed daemon leaves its PID behind but no flock
+        fs::write(&path, "999999999\n").unwrap();
+
+        let _lock = InstanceLock::acquire(&path).unwrap();
+
+        let contents = fs::read_to_string(&path).unwrap();
+        assert_eq!(contents.trim(), std::process::id().to_string());
+    }
+
+    #[test]
+    fn test_is_process_alive() {
+        assert!(is_process_alive(std::process::id()));
+        assert!(!is_process_alive(999_999_999));
+    }
+}
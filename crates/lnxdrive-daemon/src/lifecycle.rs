@@ -0,0 +1,138 @@
+//! Lifecycle requests from the Manager D-Bus interface
+//!
+//! `Manager.Shutdown` and `Manager.Restart` trigger the same graceful
+//! drain as SIGTERM. A restart then replaces the process image with a
+//! fresh copy of the daemon binary ([`reexec`]), keeping the process ID
+//! so systemd keeps tracking it. `Manager.ReloadConfig` ends the current
+//! sync session once its cycle is finished; the next session starts with
+//! the configuration read again from disk.
+
+use std::{
+    convert::Infallible,
+    os::unix::process::CommandExt,
+    process::Command,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+};
+
+use anyhow::{Context, Result};
+use lnxdrive_ipc::service::LifecycleRequest;
+use tokio::sync::{mpsc, Notify};
+use tokio_util::sync::CancellationToken;
+use tracing::info;
+
+/// Applies the lifecycle requests received over D-Bus
+#[derive(Debug)]
+pub struct Lifecycle {
+    shutdown: CancellationToken,
+    restart: AtomicBool,
+    reload: Notify,
+}
+
+impl Lifecycle {
+    /// Creates a handler that shuts down through `shutdown`
+    pub fn new(shutdown: CancellationToken) -> Arc<Self> {
+        Arc::new(Self {
+            shutdown,
+            restart: AtomicBool::new(false),
+            reload: Notify::new(),
+        })
+    }
+
+    /// Spawns the task applying requests and returns the sender for the
+    /// Manager interface
+    pub fn spawn(self: &Arc<Self>) -> mpsc::UnboundedSender<LifecycleRequest> {
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let lifecycle = Arc::clone(self);
+        tokio::spawn(async move {
+            while let Some(request) = rx.recv().await {
+                lifecycle.handle(request);
+            }
+        });
+        tx
+    }
+
+    /// Applies one request
+    pub fn handle(&self, request: LifecycleRequest) {
+        match request {
+            LifecycleRequest::Shutdown => {
+                info!("Shutdown requested over D-Bus");
+                self.shutdown.cancel();
+            }
+            LifecycleRequest::Restart => {
+                info!("Restart requested over D-Bus");
+                self.restart.store(true, Ordering::SeqCst);
+                self.shutdown.cancel();
+            }
+            LifecycleRequest::ReloadConfig => {
+                info!("Configuration reload requested over D-Bus");
+                self.reload.notify_one();
+            }
+        }
+    }
+
+    /// Returns `true` if the daemon should execute itself again after
+    /// shutting down
+    pub fn restart_requested(&self) -> bool {
+        self.restart.load(Ordering::SeqCst)
+    }
+
+    /// Waits until a configuration reload is requested
+    ///
+    /// A request made while nobody waits is kept for the next call.
+    pub async fn reload_requested(&self) {
+        self.reload.notified().await;
+    }
+}
+
+/// Replaces the current process with a fresh copy of the daemon binary,
+/// with the same arguments
+///
+/// Only returns on failure. File descriptors (the instance lock, the
+/// D-Bus connection) are close-on-exec, so the new image can take them
+/// again.
+pub fn reexec() -> Result<Infallible> {
+    let exe = std::env::current_exe().context("Failed to locate the daemon binary")?;
+    info!(exe = %exe.display(), "Restarting daemon");
+    let error = Command::new(&exe).args(std::env::args_os().skip(1)).exec();
+    Err(error).with_context(|| format!("Failed to execute {}", exe.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_shutdown_and_restart_cancel_the_token() {
+        let token = CancellationToken::new();
+        let lifecycle = Lifecycle::new(token.clone());
+        lifecycle.handle(LifecycleRequest::Shutdown);
+        assert!(token.is_cancelled());
+        assert!(!lifecycle.restart_requested());
+
+        let token = CancellationToken::new();
+        let lifecycle = Lifecycle::new(token.clone());
+        let tx = lifecycle.spawn();
+        tx.send(LifecycleRequest::Restart).unwrap();
+        tokio::time::timeout(Duration::from_secs(5), token.cancelled())
+            .await
+            .unwrap();
+        assert!(lifecycle.restart_requested());
+    }
+
+    #[tokio::test]
+    async fn test_reload_request_is_kept_until_awaited() {
+        let token = CancellationToken::new();
+        let lifecycle = Lifecycle::new(token.clone());
+        lifecycle.handle(LifecycleRequest::ReloadConfig);
+
+        tokio::time::timeout(Duration::from_secs(5), lifecycle.reload_requested())
+            .await
+            .unwrap();
+        assert!(!token.is_cancelled());
+    }
+}
@@ -0,0 +1,147 @@
+//! Recovery from lost local change events
+//!
+//! The daemon watches the sync root with a [`FileWatcher`]. When the
+//! inotify event queue overflows, events are lost and changes made in the
+//! meantime could go unnoticed. The watcher then reports a
+//! [`ChangeEvent::Rescan`], which the [`SyncScheduler`] turns into a
+//! reconciliation request; the sync loop waits for it through
+//! [`LocalWatch::reconciliation_requested`] and runs a reconciliation scan.
+//!
+//! The number of overflows is exported as `lnxdrive_watcher_overflows`.
+
+use std::{
+    path::Path,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+use anyhow::Result;
+use lnxdrive_sync::{
+    scheduler::SyncScheduler,
+    watcher::{ChangeEvent, FileWatcher, WatchHandle},
+};
+use lnxdrive_telemetry::GaugeFn;
+use tokio::{sync::mpsc, task::JoinHandle};
+use tracing::warn;
+
+/// How often the scheduler and the sync loop check for settled events
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// A watch of the sync root, stopped when dropped
+pub struct LocalWatch {
+    /// Kept alive so events keep coming; `None` when fed by a channel
+    _watcher: Option<(FileWatcher, WatchHandle)>,
+    /// Raised by the scheduler when a reconciliation scan is needed
+    reconcile: Arc<AtomicBool>,
+    /// Task running the scheduler
+    scheduler: JoinHandle<()>,
+}
+
+impl LocalWatch {
+    /// Starts watching `sync_root`, settling events after `debounce`
+    ///
+    /// Also registers the overflow count of the watcher as a metric.
+    pub fn start(sync_root: &Path, debounce: Duration) -> Result<Self> {
+        let (mut watcher, events) = FileWatcher::new(debounce.as_millis() as u64)?;
+        let handle = watcher.watch(sync_root)?;
+
+        let overflows = watcher.overflow_counter();
+        let gauge = GaugeFn::new(
+            "lnxdrive_watcher_overflows",
+            "Number of times the local file watcher lost events",
+            move || overflows.load(Ordering::Relaxed) as f64,
+        );
+        if let Err(e) = gauge.and_then(GaugeFn::register) {
+            warn!(error = %e, "Failed to register the watcher overflow gauge");
+        }
+
+        Ok(Self::from_events(events, debounce, Some((watcher, handle))))
+    }
+
+    /// Schedules reconciliations from the change events of `events`
+    fn from_events(
+        events: mpsc::Receiver<ChangeEvent>,
+        debounce: Duration,
+        watcher: Option<(FileWatcher, WatchHandle)>,
+    ) -> Self {
+        let (mut scheduler, _) = SyncScheduler::new(events, debounce, POLL_INTERVAL);
+        let reconcile = scheduler.reconcile_flag();
+        Self {
+            _watcher: watcher,
+            reconcile,
+            scheduler: tokio::spawn(async move { scheduler.run().await }),
+        }
+    }
+
+    /// Waits until events were lost and a reconciliation scan is due
+    ///
+    /// The request is consumed, so each overflow is answered once.
+    pub async fn reconciliation_requested(&self) {
+        loop {
+            if self.reconcile.swap(false, Ordering::AcqRel) {
+                return;
+            }
+            tokio::time::sleep(POLL_INTERVAL).await;
+        }
+    }
+}
+
+impl Drop for LocalWatch {
+    fn drop(&mut self) {
+        self.scheduler.abort();
+    }
+}
+
+/// Waits for [`LocalWatch::reconciliation_requested`], or forever without
+/// a watch
+pub async fn reconciliation_requested(watch: Option<&LocalWatch>) {
+    match watch {
+        Some(watch) => watch.reconciliation_requested().await,
+        None => std::future::pending().await,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_overflow_schedules_reconciliation() {
+        let (tx, rx) = mpsc::channel(16);
+        let watch = LocalWatch::from_events(rx, Duration::from_millis(50), None);
+
+        // What the watcher sends for each root when its queue overflows
+        tx.send(ChangeEvent::Rescan(PathBuf::from("/home/user/OneDrive")))
+            .await
+            .unwrap();
+
+        tokio::time::timeout(Duration::from_secs(5), watch.reconciliation_requested())
+            .await
+            .expect("no reconciliation scheduled after an overflow");
+        assert!(!watch.reconcile.load(Ordering::Acquire));
+    }
+
+    #[tokio::test]
+    async fn test_regular_changes_do_not_schedule_reconciliation() {
+        let (tx, rx) = mpsc::channel(16);
+        let watch = LocalWatch::from_events(rx, Duration::from_millis(50), None);
+
+        tx.send(ChangeEvent::Modified(PathBuf::from(
+            "/home/user/OneDrive/a.txt",
+        )))
+        .await
+        .unwrap();
+
+        let waited = tokio::time::timeout(
+            Duration::from_secs(2),
+            reconciliation_requested(Some(&watch)),
+        )
+        .await;
+        assert!(waited.is_err());
+    }
+}
@@ -5,6 +5,12 @@
 //! - D-Bus interface for UI clients
 //! - Periodic remote polling
 //! - Graceful shutdown on SIGTERM/SIGINT
+//! - systemd readiness and watchdog notifications
+//! - Optional HTTP endpoint for metrics and health probes
+//! - Periodic storage quota refresh and near-full warnings
+//! - Stopping and asking for a new login when credentials are rejected
+//! - Warning about a wrong local clock at startup
+//! - Shutdown, restart and configuration reload requested over D-Bus
 //!
 //! # Architecture
 //!
@@ -12,22 +18,76 @@
 //! that periodically runs the SyncEngine. The loop is controlled by a
 //! `CancellationToken` that is triggered on receipt of SIGTERM or SIGINT.
 
-use std::{path::Path, sync::Arc, time::Duration};
+mod auth_breaker;
+mod change_notifications;
+mod fuse_supervisor;
+mod health;
+mod instance_lock;
+mod lifecycle;
+mod local_watch;
+mod quota;
+mod systemd;
+
+use std::{
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
 
 use anyhow::{Context, Result};
+use async_trait::async_trait;
+use chrono::Utc;
 use lnxdrive_cache::{pool::DatabasePool, SqliteStateRepository};
-use lnxdrive_core::{config::Config, ports::state_repository::IStateRepository};
-use lnxdrive_fuse::{mount, unmount, BackgroundSession};
+use lnxdrive_core::{
+    config::{check_sync_root, check_temp_dir, AccountRefError, Config},
+    domain::{Account, ClockSkew, SyncHistoryEntry, MAX_SYNC_HISTORY_ENTRIES},
+    ports::{
+        cloud_provider::ICloudProvider, notification::INotificationService,
+        state_repository::IStateRepository, ITransferObserver, TransferObservers,
+    },
+};
+use lnxdrive_fuse::{
+    mount_with_remote_changes, unmount, BackgroundSession, CacheStats, InodeTable, RemoteChanges,
+};
 use lnxdrive_graph::{
     auth::KeyringTokenStorage, client::GraphClient, provider::GraphCloudProvider,
+    upload_checkpoint::UploadCheckpointStore,
+};
+use lnxdrive_ipc::{
+    notifications::DesktopNotifier,
+    service::{
+        DaemonState, DaemonSyncState, DbusHydrationObserver, DbusService, DbusTransferObserver,
+        SettingsInterface, SyncPathStatus, DBUS_NAME,
+    },
+};
+use lnxdrive_sync::{
+    engine::{SyncEngine, SyncResult},
+    filesystem::LocalFileSystemAdapter,
+    selective::{FolderSelection, SelectionProgress},
 };
-use lnxdrive_ipc::service::{DaemonState, DaemonSyncState, DbusService, DBUS_NAME};
-use lnxdrive_sync::{engine::SyncEngine, filesystem::LocalFileSystemAdapter};
-use tokio::sync::Mutex;
+use lnxdrive_telemetry::{stats, GaugeFn, MetricsRegistry, MetricsServer};
+use tokio::sync::{mpsc, Mutex, Notify};
 use tokio_util::sync::CancellationToken;
-use tracing::{error, info, warn};
+use tracing::{debug, error, info, warn};
 use tracing_subscriber::EnvFilter;
 
+use crate::{
+    auth_breaker::{relogin_notification, AuthCircuitBreaker, UNAUTHORIZED_THRESHOLD},
+    change_notifications::ChangeNotifications,
+    fuse_supervisor::{
+        detach_stale_mount, supervise, MountedItems, RemountPolicy, SupervisedMount,
+    },
+    health::{readiness_thresholds, DaemonHealth},
+    instance_lock::InstanceLock,
+    lifecycle::{reexec, Lifecycle},
+    local_watch::{reconciliation_requested, LocalWatch},
+    quota::QuotaMonitor,
+    systemd::{SystemdNotifier, Watchdog},
+};
+
 // ============================================================================
 // T214: DaemonService struct
 // ============================================================================
@@ -37,30 +97,43 @@ use tracing_subscriber::EnvFilter;
 /// Holds the configuration, state repository, shared daemon state,
 /// and a cancellation token for graceful shutdown.
 struct DaemonService {
-    /// Application configuration loaded from YAML
-    config: Config,
+    /// Application configuration loaded from YAML, replaced on reload
+    config: std::sync::RwLock<Arc<Config>>,
+    /// Path the configuration was loaded from
+    config_path: PathBuf,
     /// SQLite state repository for sync state persistence
     state_repo: Arc<SqliteStateRepository>,
     /// Database pool (needed for FUSE mount)
     db_pool: DatabasePool,
+    /// Checkpoints of unfinished large uploads, resumed on the next start
+    upload_checkpoints: Option<Arc<UploadCheckpointStore>>,
     /// Shared state between daemon and D-Bus interfaces
     daemon_state: Arc<Mutex<DaemonState>>,
     /// Token for signalling graceful shutdown to all async tasks
     shutdown: CancellationToken,
+    /// Shutdown, restart and reload requests from the Manager interface
+    lifecycle: Arc<Lifecycle>,
     /// T095: FUSE session handle (when auto-mounted)
     fuse_session: std::sync::Mutex<Option<BackgroundSession>>,
+    /// systemd readiness/watchdog notifications (no-op outside systemd)
+    notifier: Arc<SystemdNotifier>,
+    /// Pings the systemd watchdog while the main loop makes progress
+    watchdog: Arc<Watchdog>,
+    /// Whether READY=1 has already been sent
+    ready_sent: AtomicBool,
 }
 
 impl DaemonService {
     /// Creates a new DaemonService
     ///
-    /// Loads configuration, opens the database, and initializes shared state.
-    async fn new(shutdown: CancellationToken) -> Result<Self> {
-        // Load configuration
-        let config_path = Config::default_path();
-        let config = Config::load_or_default(&config_path);
-        info!(config_path = %config_path.display(), "Loaded configuration");
-
+    /// Opens the database and initializes shared state.
+    async fn new(
+        config: Config,
+        config_path: PathBuf,
+        notifier: Arc<SystemdNotifier>,
+        lifecycle: Arc<Lifecycle>,
+        shutdown: CancellationToken,
+    ) -> Result<Self> {
         // Open database
         let db_path = dirs::data_dir()
             .unwrap_or_else(|| std::path::PathBuf::from("."))
@@ -77,18 +150,145 @@ impl DaemonService {
             .context("Failed to open database")?;
         let state_repo = Arc::new(SqliteStateRepository::new(db_pool.pool().clone()));
 
-        let daemon_state = Arc::new(Mutex::new(DaemonState::default()));
+        Self::validate_directories(&config, state_repo.as_ref()).await?;
+
+        let checkpoint_dir = db_path.with_file_name("upload-sessions");
+        let upload_checkpoints = match UploadCheckpointStore::new(&checkpoint_dir) {
+            Ok(store) => {
+                let pending = store.count();
+                if pending > 0 {
+                    info!(pending, "Found checkpointed uploads to resume");
+                }
+                Some(Arc::new(store))
+            }
+            Err(e) => {
+                warn!(error = %e, "Upload checkpoints disabled");
+                None
+            }
+        };
+
+        // Seed the in-memory sync history so GetHistory survives restarts
+        let mut initial_state = DaemonState::default();
+        match state_repo.get_sync_history(MAX_SYNC_HISTORY_ENTRIES).await {
+            Ok(history) => initial_state.sync_history = history,
+            Err(e) => warn!(error = %e, "Failed to load sync history"),
+        }
+        initial_state.metrics = register_metrics();
+        initial_state.config_path = config_path.clone();
+        initial_state.lifecycle = Some(lifecycle.spawn());
+        let daemon_state = Arc::new(Mutex::new(initial_state));
 
         Ok(Self {
-            config,
+            config: std::sync::RwLock::new(Arc::new(config)),
+            config_path,
             state_repo,
             db_pool,
+            upload_checkpoints,
             daemon_state,
             shutdown,
+            lifecycle,
             fuse_session: std::sync::Mutex::new(None),
+            watchdog: Arc::new(Watchdog::new(Arc::clone(&notifier))),
+            notifier,
+            ready_sent: AtomicBool::new(false),
         })
     }
 
+    /// Returns the account the daemon syncs
+    ///
+    /// The account `accounts.default` names, or else the first account
+    /// logged in.
+    async fn served_account(
+        config: &Config,
+        state_repo: &SqliteStateRepository,
+    ) -> Result<Option<Account>> {
+        let accounts = state_repo
+            .list_accounts()
+            .await
+            .context("Failed to query accounts")?;
+        match config.accounts.resolve(&accounts, None) {
+            Ok(account) => Ok(Some(account.clone())),
+            Err(e) => {
+                if matches!(e, AccountRefError::Unknown { .. }) {
+                    warn!(error = %e, "Default account not found, syncing the first account");
+                }
+                Ok(accounts.into_iter().next())
+            }
+        }
+    }
+
+    /// Refuses to start if the sync root, mount point and cache overlap, or
+    /// if the temporary directory is not writable
+    ///
+    /// The sync root is the default account's root, or `sync.root` before
+    /// the first login. Its existence and writability are only checked
+    /// once an account exists, since login is what creates it.
+    async fn validate_directories(
+        config: &Config,
+        state_repo: &SqliteStateRepository,
+    ) -> Result<()> {
+        let account = Self::served_account(config, state_repo).await?;
+        let sync_root = match &account {
+            Some(account) => account.sync_root().as_path().clone(),
+            None => PathBuf::from(&config.sync.root),
+        };
+
+        let mut errors = config.validate_layout(&sync_root);
+        if let Err(e) = check_temp_dir(&config.fuse.temp_dir_path()) {
+            errors.push(e);
+        }
+        if account.is_some() {
+            if let Err(e) = check_sync_root(&sync_root) {
+                errors.push(e);
+            }
+        }
+
+        if errors.is_empty() {
+            return Ok(());
+        }
+        let details: Vec<String> = errors.iter().map(|e| e.to_string()).collect();
+        anyhow::bail!(
+            "Invalid directory layout:\n  {}\nRun 'lnxdrive doctor' for details.",
+            details.join("\n  ")
+        );
+    }
+
+    /// Returns the current configuration
+    fn config(&self) -> Arc<Config> {
+        Arc::clone(&self.config.read().unwrap_or_else(|e| e.into_inner()))
+    }
+
+    /// Reads the configuration file again for the next session
+    ///
+    /// The file was validated by `Manager.ReloadConfig`; if it cannot be
+    /// loaded now, the current configuration stays in effect. The metrics
+    /// endpoint and the log settings keep their startup values.
+    fn reload_config(&self) {
+        match Config::load(&self.config_path) {
+            Ok(config) => {
+                *self.config.write().unwrap_or_else(|e| e.into_inner()) = Arc::new(config);
+                info!(path = %self.config_path.display(), "Configuration reloaded");
+            }
+            Err(e) => warn!(error = %e, "Failed to reload configuration, keeping the current one"),
+        }
+    }
+
+    /// Tells systemd the daemon is up and sends the first watchdog ping
+    ///
+    /// Called once the main loop (sync or wait-for-auth) is reached, which
+    /// implies the D-Bus name has been acquired. Only the first call has
+    /// an effect.
+    fn notify_ready(&self) {
+        if self.ready_sent.swap(true, Ordering::SeqCst) {
+            return;
+        }
+        self.notifier.ready();
+        self.watchdog.alive();
+        if self.notifier.is_enabled() {
+            info!("Notified systemd that the daemon is ready");
+        }
+    }
+
     // ========================================================================
     // T215: DaemonService::run() - async main loop
     // ========================================================================
@@ -100,12 +300,16 @@ impl DaemonService {
     /// 3. Creates adapters and SyncEngine
     /// 4. Enters the polling loop with graceful shutdown support
     async fn run(&self) -> Result<()> {
-        // T231: Single instance lock via D-Bus name
+        // T231: Single instance lock via D-Bus name (the lock file taken in
+        // main() backs this up where no session bus is available)
         info!("Checking for existing daemon instance...");
 
         // T224: Start D-Bus service (this also acquires the well-known name)
-        let dbus_service = DbusService::new(Arc::clone(&self.daemon_state));
-        let _dbus_connection = match dbus_service.start().await {
+        let status_repo: Arc<dyn IStateRepository + Send + Sync> =
+            Arc::clone(&self.state_repo) as _;
+        let dbus_service =
+            DbusService::new(Arc::clone(&self.daemon_state)).with_repository(status_repo);
+        let dbus_connection = match dbus_service.start().await {
             Ok(conn) => {
                 info!("D-Bus service started, acquired name {}", DBUS_NAME);
                 conn
@@ -130,16 +334,30 @@ impl DaemonService {
             }
         };
 
+        // Health probes are served even while waiting for authentication,
+        // in which case /readyz reports why the daemon is not ready
+        self.start_metrics_server().await;
+
+        // A session ends when credentials are missing or rejected; the next
+        // one starts once the user has logged in
+        while !self.shutdown.is_cancelled() {
+            self.run_session(&dbus_connection).await?;
+        }
+        Ok(())
+    }
+
+    /// Syncs the served account until shutdown or until its credentials
+    /// are missing or rejected
+    ///
+    /// In the latter case, returns once `lnxdrive auth login` stored new
+    /// tokens (or on shutdown).
+    async fn run_session(&self, dbus_connection: &zbus::Connection) -> Result<()> {
         // Try to load account and tokens
-        let account_opt = self
-            .state_repo
-            .get_default_account()
-            .await
-            .context("Failed to query default account")?;
+        let account_opt = Self::served_account(&self.config(), &self.state_repo).await?;
 
-        let (_account, tokens) = match account_opt {
+        let (account, tokens) = match account_opt {
             Some(account) => {
-                match KeyringTokenStorage::load(account.email().as_str()) {
+                match KeyringTokenStorage::load(&KeyringTokenStorage::account_id_of(&account)) {
                     Ok(Some(t)) => {
                         info!(
                             email = %account.email(),
@@ -149,8 +367,11 @@ impl DaemonService {
                         // Update daemon state with account info
                         {
                             let mut state = self.daemon_state.lock().await;
+                            state.account_id = Some(account.id().to_string());
                             state.account_email = Some(account.email().as_str().to_string());
                             state.account_display_name = Some(account.display_name().to_string());
+                            // A logout made while no session ran must not end this one
+                            state.logout_requested = false;
                         }
 
                         (account, t)
@@ -161,7 +382,7 @@ impl DaemonService {
                             "Account found but no tokens in keyring. \
                              Run 'lnxdrive auth login' to authenticate."
                         );
-                        return self.wait_for_auth_loop().await;
+                        return self.wait_for_auth_loop(None).await;
                     }
                     Err(e) => {
                         warn!(
@@ -169,41 +390,229 @@ impl DaemonService {
                             error = %e,
                             "Failed to load tokens from keyring"
                         );
-                        return self.wait_for_auth_loop().await;
+                        return self.wait_for_auth_loop(None).await;
                     }
                 }
             }
             None => {
                 warn!("No account configured. Run 'lnxdrive auth login' to set up an account.");
-                return self.wait_for_auth_loop().await;
+                return self.wait_for_auth_loop(None).await;
             }
         };
 
         // Create adapters
-        let graph_client = GraphClient::new(&tokens.access_token);
-        let cloud_provider = Arc::new(GraphCloudProvider::new(graph_client));
-        let local_fs = Arc::new(LocalFileSystemAdapter::new());
+        let graph_client = GraphClient::new(&tokens.access_token)
+            .with_http_config(&self.config().http)
+            .with_delta_page_size(self.config().delta.batch_size);
+        let clock_skew = self.check_clock_skew(&graph_client).await;
+        let mut cloud_provider = GraphCloudProvider::new(graph_client);
+        if let Some(store) = &self.upload_checkpoints {
+            cloud_provider = cloud_provider.with_upload_checkpoints(Arc::clone(store));
+        }
+        // The mount downloads cloud-only files through the same provider
+        let graph_provider = Arc::new(cloud_provider);
+        let cloud_provider: Arc<dyn ICloudProvider + Send + Sync> =
+            Arc::clone(&graph_provider) as _;
+        // Lets the selective sync UI browse the remote folders
+        self.daemon_state.lock().await.cloud_provider = Some(Arc::clone(&cloud_provider) as _);
+        let local_fs = Arc::new(
+            LocalFileSystemAdapter::new().with_temp_dir(self.config().fuse.temp_dir_path()),
+        );
+        let desktop_notifier = Arc::new(DesktopNotifier::new(dbus_connection.clone()));
+        let hydration_observer = Arc::new(DbusHydrationObserver::spawn(dbus_connection));
 
         // Create SyncEngine
-        let engine = SyncEngine::new(
-            cloud_provider,
+        let mut engine = SyncEngine::new(
+            Arc::clone(&cloud_provider),
             Arc::clone(&self.state_repo) as Arc<dyn IStateRepository + Send + Sync>,
             local_fs,
-            &self.config,
+            &self.config(),
         );
+        engine.set_account(*account.id());
+        let mut observers: Vec<Arc<dyn ITransferObserver>> = vec![
+            Arc::new(DbusTransferObserver::spawn(dbus_connection)),
+            Arc::clone(&self.watchdog) as _,
+        ];
+        if let Some(metrics) = self.daemon_state.lock().await.metrics.clone() {
+            observers.push(metrics);
+        }
+        engine.set_transfer_observer(Arc::new(TransferObservers::new(observers)));
+        engine.set_notifier(Arc::clone(&desktop_notifier) as _);
+        engine.set_transfer_control(Arc::clone(&self.daemon_state.lock().await.transfer_control));
+        if let Some(skew) = clock_skew {
+            engine.set_clock_skew(skew);
+        }
+        self.load_folder_selection(&engine).await;
+
+        let mut quota = QuotaMonitor::new(
+            cloud_provider,
+            Arc::clone(&self.state_repo),
+            *account.id(),
+            Arc::clone(&self.daemon_state),
+            dbus_connection.clone(),
+            Arc::clone(&desktop_notifier) as _,
+            Duration::from_secs(self.config().sync.quota_refresh_interval),
+        );
+
+        // Catch local changes made while the daemon was not running
+        if self.config().sync.startup_reconciliation {
+            match engine.reconcile().await {
+                Ok(report) => info!(
+                    changes = report.total_changes(),
+                    modified = report.modified,
+                    created = report.created,
+                    deleted = report.deleted,
+                    "Startup reconciliation finished"
+                ),
+                Err(e) => warn!(error = %e, "Startup reconciliation failed"),
+            }
+        }
 
         // T095: Auto-mount FUSE filesystem if enabled
-        if self.config.fuse.auto_mount {
-            self.mount_fuse();
+        let mounted_items = Arc::new(MountedItems::default());
+        let mut mounted = false;
+        if self.config().fuse.auto_mount {
+            if let Some(remote_changes) = self
+                .mount_fuse(
+                    Arc::clone(&desktop_notifier),
+                    Arc::clone(&hydration_observer),
+                    Arc::clone(&graph_provider),
+                )
+                .await
+            {
+                mounted_items.set(Some(remote_changes));
+                engine.set_item_observer(Arc::clone(&mounted_items) as _);
+                mounted = true;
+            }
         }
 
+        // Reconcile when the watcher of the sync root loses events. A sync
+        // root that is the mount point is only changed through the mount.
+        let sync_root = account.sync_root().as_path();
+        let mount_point =
+            lnxdrive_core::config::expand_tilde(Path::new(&self.config().fuse.mount_point));
+        let local_watch = if mounted && &mount_point == sync_root {
+            None
+        } else {
+            let debounce = Duration::from_secs(self.config().sync.debounce_delay);
+            match LocalWatch::start(sync_root, debounce) {
+                Ok(watch) => Some(watch),
+                Err(e) => {
+                    warn!(error = %e, "Failed to watch the sync root");
+                    None
+                }
+            }
+        };
+
+        // Sync as soon as Microsoft Graph reports remote changes
+        let mut notifications = ChangeNotifications::start(
+            &self.config().change_notifications,
+            GraphClient::new(&tokens.access_token).with_http_config(&self.config().http),
+            &self.shutdown,
+        )
+        .await;
+
+        // Remount if the kernel aborts the FUSE connection
+        let mount = DaemonMount {
+            service: self,
+            notifier: Arc::clone(&desktop_notifier),
+            hydration_observer,
+            provider: graph_provider,
+            items: mounted_items,
+        };
+        let supervision = async {
+            if mounted {
+                let policy = RemountPolicy::default();
+                supervise(&mount, &policy, desktop_notifier.as_ref(), &self.shutdown).await;
+            }
+            std::future::pending::<()>().await
+        };
+
         // T216: Enter periodic polling loop
-        let result = self.sync_loop(&engine).await;
+        let result = tokio::select! {
+            result = self.sync_loop(
+                &engine,
+                dbus_connection,
+                &mut quota,
+                notifications.as_mut(),
+                local_watch.as_ref(),
+            ) => result,
+            _ = supervision => unreachable!("FUSE supervision never completes"),
+        };
+
+        if let Some(notifications) = notifications {
+            notifications.stop().await;
+        }
 
-        // T095: Unmount FUSE on shutdown
-        self.unmount_fuse();
+        // T095: Unmount FUSE on shutdown, or before waiting for a new login
+        self.unmount_fuse().await;
 
-        result
+        match result? {
+            SessionEnd::Shutdown => {}
+            SessionEnd::Reload => self.reload_config(),
+            SessionEnd::Unauthorized => {
+                self.daemon_state.lock().await.reset_auth();
+                if let Err(e) = desktop_notifier.notify(&relogin_notification()).await {
+                    warn!(error = %e, "Failed to show the sign-in notification");
+                }
+                return self.wait_for_auth_loop(Some(&tokens.access_token)).await;
+            }
+            SessionEnd::LoggedOut => {
+                info!("Logged out, waiting for a new login");
+                return self.wait_for_auth_loop(Some(&tokens.access_token)).await;
+            }
+        }
+        Ok(())
+    }
+
+    /// Compares the local clock with the Graph servers' and warns if it
+    /// is off
+    ///
+    /// The result is published on D-Bus for `lnxdrive status`. A failed
+    /// measurement (e.g. offline) is not an error: syncing proceeds with
+    /// local timestamps.
+    async fn check_clock_skew(&self, client: &GraphClient) -> Option<ClockSkew> {
+        let skew = match client.measure_clock_skew().await {
+            Ok(skew) => skew,
+            Err(e) => {
+                debug!(error = %e, "Could not measure clock skew");
+                return None;
+            }
+        };
+        if skew.is_significant() {
+            warn!(
+                skew_secs = skew.seconds,
+                "Local clock is {}; conflicts are compared in server time. \
+                 Enable time synchronization (e.g. systemd-timesyncd).",
+                skew
+            );
+        }
+        self.daemon_state.lock().await.clock_skew = Some(skew);
+        Some(skew)
+    }
+
+    /// Starts the `/metrics`, `/healthz` and `/readyz` endpoint if enabled
+    ///
+    /// A bind failure is logged and does not stop the daemon.
+    async fn start_metrics_server(&self) {
+        let config = self.config();
+        let metrics = &config.metrics;
+        if !metrics.enabled {
+            return;
+        }
+        let addr = match metrics.listen_address.parse() {
+            Ok(addr) => addr,
+            Err(e) => {
+                warn!(address = %metrics.listen_address, error = %e, "Invalid metrics address");
+                return;
+            }
+        };
+
+        let health = DaemonHealth::new(Arc::clone(&self.daemon_state), self.db_pool.clone());
+        let server = MetricsServer::new(Arc::new(health), readiness_thresholds(metrics));
+        if let Err(e) = server.spawn(addr, self.shutdown.clone()).await {
+            warn!(address = %addr, error = %e, "Failed to start metrics server");
+        }
     }
 
     // ========================================================================
@@ -214,10 +623,25 @@ impl DaemonService {
     ///
     /// Clones the database pool for the FUSE layer and mounts
     /// the filesystem at the configured mount point. The session handle
-    /// is stored for graceful unmount during shutdown.
-    fn mount_fuse(&self) {
+    /// is stored for graceful unmount during shutdown, and the cache manager
+    /// is published to the D-Bus Cache interface. Dehydration sweeps report
+    /// the space they free through `notifier`, and files becoming
+    /// cloud-only are signalled through `hydration_observer`, as is the
+    /// progress of the downloads `provider` makes when files are opened.
+    /// Returns the handle that applies remote renames to the mount, or
+    /// `None` if mounting failed.
+    ///
+    /// Only sessions with credentials mount, so `provider` is always
+    /// authenticated; after a new login the next session mounts again with
+    /// a fresh provider.
+    async fn mount_fuse(
+        &self,
+        notifier: Arc<DesktopNotifier>,
+        hydration_observer: Arc<DbusHydrationObserver>,
+        provider: Arc<GraphCloudProvider>,
+    ) -> Option<Arc<RemoteChanges>> {
         info!(
-            mount_point = %self.config.fuse.mount_point,
+            mount_point = %self.config().fuse.mount_point,
             "Auto-mounting FUSE filesystem"
         );
 
@@ -226,22 +650,35 @@ impl DaemonService {
 
         let rt_handle = tokio::runtime::Handle::current();
 
-        match mount(self.config.fuse.clone(), fuse_pool, rt_handle) {
-            Ok(session) => {
+        match mount_with_remote_changes(
+            self.config().fuse.clone(),
+            fuse_pool,
+            rt_handle,
+            Some(notifier as _),
+            Some(hydration_observer as _),
+            Some(provider),
+        ) {
+            Ok(mounted) => {
                 info!(
-                    mount_point = %self.config.fuse.mount_point,
+                    mount_point = %self.config().fuse.mount_point,
                     "FUSE filesystem mounted successfully"
                 );
                 if let Ok(mut guard) = self.fuse_session.lock() {
-                    *guard = Some(session);
+                    *guard = Some(mounted.session);
                 }
+                self.daemon_state.lock().await.cache_manager =
+                    mounted.cache_manager.map(|manager| manager as _);
+                register_inode_gauge(Arc::clone(&mounted.inode_table));
+                register_cache_gauges(Arc::clone(&mounted.cache_stats));
+                Some(mounted.remote_changes)
             }
             Err(e) => {
                 error!(
-                    mount_point = %self.config.fuse.mount_point,
+                    mount_point = %self.config().fuse.mount_point,
                     error = %e,
                     "Failed to mount FUSE filesystem"
                 );
+                None
             }
         }
     }
@@ -250,11 +687,12 @@ impl DaemonService {
     ///
     /// Takes ownership of the session handle and drops it, triggering
     /// the kernel unmount operation.
-    fn unmount_fuse(&self) {
+    async fn unmount_fuse(&self) {
+        self.daemon_state.lock().await.cache_manager = None;
         if let Ok(mut guard) = self.fuse_session.lock() {
             if let Some(session) = guard.take() {
                 info!(
-                    mount_point = %self.config.fuse.mount_point,
+                    mount_point = %self.config().fuse.mount_point,
                     "Unmounting FUSE filesystem"
                 );
                 unmount(session);
@@ -263,6 +701,20 @@ impl DaemonService {
         }
     }
 
+    /// Returns `false` once the thread serving the FUSE session has ended
+    ///
+    /// Also `true` when nothing is mounted.
+    fn fuse_session_alive(&self) -> bool {
+        self.fuse_session
+            .lock()
+            .map(|guard| {
+                guard
+                    .as_ref()
+                    .map_or(true, |session| !session.guard.is_finished())
+            })
+            .unwrap_or(true)
+    }
+
     // ========================================================================
     // T216: Periodic remote polling
     // ========================================================================
@@ -271,10 +723,24 @@ impl DaemonService {
     ///
     /// Uses `tokio::time::interval` based on `config.sync.poll_interval`
     /// (defaults to 30 seconds). Each tick runs `engine.sync()` unless
-    /// the daemon is paused or shutting down.
-    async fn sync_loop(&self, engine: &SyncEngine) -> Result<()> {
-        let poll_secs = self.config.sync.poll_interval;
-        let poll_duration = Duration::from_secs(poll_secs);
+    /// the daemon is paused or shutting down, then refreshes the storage
+    /// quota if it is due. Sync-by-path requests from D-Bus run as soon as
+    /// they arrive between cycles, even while paused, and before the next
+    /// cycle.
+    ///
+    /// Stops early once the credentials were rejected by
+    /// [`UNAUTHORIZED_THRESHOLD`] consecutive cycles, or between cycles
+    /// when a configuration reload is requested.
+    async fn sync_loop(
+        &self,
+        engine: &SyncEngine,
+        dbus_connection: &zbus::Connection,
+        quota: &mut QuotaMonitor,
+        mut notifications: Option<&mut ChangeNotifications>,
+        local_watch: Option<&LocalWatch>,
+    ) -> Result<SessionEnd> {
+        let poll_secs = self.config().sync.poll_interval;
+        let mut poll_duration = Duration::from_secs(poll_secs);
 
         info!(poll_interval_secs = poll_secs, "Starting sync loop");
 
@@ -282,7 +748,25 @@ impl DaemonService {
         // The first tick fires immediately; we want to sync right away
         interval.tick().await;
 
-        loop {
+        self.notify_ready();
+        let wakeup = Arc::clone(&self.daemon_state.lock().await.sync_wakeup);
+        // Never notified when change notifications are disabled
+        let remote_changes = notifications
+            .as_ref()
+            .map_or_else(|| Arc::new(Notify::new()), |n| n.remote_changes());
+        let mut auth_breaker = AuthCircuitBreaker::new(UNAUTHORIZED_THRESHOLD);
+
+        'sync: loop {
+            self.watchdog.alive();
+            self.apply_folder_selection_request(engine, dbus_connection)
+                .await;
+            self.run_sync_path_requests(engine).await;
+            self.answer_plan_requests(engine).await;
+
+            if self.take_logout_request().await {
+                return Ok(SessionEnd::LoggedOut);
+            }
+
             // Check if a sync was requested via D-Bus
             let sync_requested = {
                 let mut state = self.daemon_state.lock().await;
@@ -301,6 +785,9 @@ impl DaemonService {
                 // Wait for either the next interval tick or shutdown
                 tokio::select! {
                     _ = interval.tick() => continue,
+                    _ = wakeup.notified() => continue,
+                    _ = self.watchdog.tick() => continue,
+                    _ = self.lifecycle.reload_requested() => return Ok(SessionEnd::Reload),
                     _ = self.shutdown.cancelled() => {
                         info!("Shutdown signal received while paused");
                         break;
@@ -322,17 +809,20 @@ impl DaemonService {
             }
 
             info!("Starting sync cycle");
+            let started_at = Utc::now();
 
-            match engine.sync().await {
+            let Some(outcome) = self.run_sync_cycle(engine).await else {
+                // Drain timed out; the cycle was cancelled mid-transfer
+                break;
+            };
+
+            let bytes_transferred = outcome
+                .as_ref()
+                .map_or(0, |result| result.bytes_downloaded + result.bytes_uploaded);
+
+            match outcome {
                 Ok(result) => {
-                    let result_json = serde_json::json!({
-                        "files_downloaded": result.files_downloaded,
-                        "files_uploaded": result.files_uploaded,
-                        "files_deleted": result.files_deleted,
-                        "errors": result.errors,
-                        "duration_ms": result.duration_ms,
-                    })
-                    .to_string();
+                    let result_json = sync_result_json(&result).to_string();
 
                     info!(
                         downloaded = result.files_downloaded,
@@ -343,6 +833,10 @@ impl DaemonService {
                         "Sync cycle completed"
                     );
 
+                    self.record_sync_history(result.to_history_entry(started_at))
+                        .await;
+                    auth_breaker.record_success();
+
                     let mut state = self.daemon_state.lock().await;
                     state.sync_state = DaemonSyncState::Idle;
                     state.last_sync_result = Some(result_json);
@@ -351,55 +845,344 @@ impl DaemonService {
                     let err_msg = format!("{e:#}");
                     error!(error = %err_msg, "Sync cycle failed");
 
-                    let mut state = self.daemon_state.lock().await;
-                    state.sync_state = DaemonSyncState::Error(err_msg);
+                    self.record_sync_history(SyncHistoryEntry::failed(started_at, err_msg.clone()))
+                        .await;
+
+                    {
+                        let mut state = self.daemon_state.lock().await;
+                        state.sync_state = DaemonSyncState::Error(err_msg);
+                    }
+
+                    if auth_breaker.record_failure(&e) {
+                        error!(
+                            cycles = UNAUTHORIZED_THRESHOLD,
+                            "Credentials rejected repeatedly, stopping sync until the next login"
+                        );
+                        return Ok(SessionEnd::Unauthorized);
+                    }
                 }
             }
 
-            // Wait for the next interval or shutdown
-            tokio::select! {
-                _ = interval.tick() => {}
-                _ = self.shutdown.cancelled() => {
-                    info!("Shutdown signal received");
-                    break;
+            quota.refresh_if_due(bytes_transferred).await;
+
+            if self.shutdown.is_cancelled() {
+                info!("Shutdown signal received");
+                break;
+            }
+
+            // While subscribed to change notifications, polling is only a
+            // safety net for lost notifications
+            if let Some(notifications) = notifications.as_deref_mut() {
+                let subscribed = notifications.ensure_subscription().await;
+                let config = self.config();
+                let secs = if subscribed {
+                    config.change_notifications.poll_interval
+                } else {
+                    config.sync.poll_interval
+                };
+                if Duration::from_secs(secs) != poll_duration {
+                    poll_duration = Duration::from_secs(secs);
+                    info!(
+                        subscribed,
+                        poll_interval_secs = secs,
+                        "Changing poll interval"
+                    );
+                    interval = tokio::time::interval_at(
+                        tokio::time::Instant::now() + poll_duration,
+                        poll_duration,
+                    );
+                }
+            }
+
+            // Wait for the next interval or shutdown, running sync-by-path
+            // requests as they come in
+            loop {
+                tokio::select! {
+                    _ = interval.tick() => break,
+                    _ = self.watchdog.tick() => self.watchdog.alive(),
+                    _ = remote_changes.notified() => {
+                        info!("Remote changes notified, syncing");
+                        break;
+                    }
+                    _ = reconciliation_requested(local_watch) => {
+                        warn!("Local change events were lost, reconciling the sync root");
+                        engine.request_reconciliation();
+                        if let Err(e) = engine.reconcile().await {
+                            warn!(error = %e, "Reconciliation after lost events failed");
+                        }
+                        break;
+                    }
+                    _ = wakeup.notified() => {
+                        if self.take_logout_request().await {
+                            return Ok(SessionEnd::LoggedOut);
+                        }
+                        self.apply_folder_selection_request(engine, dbus_connection)
+                            .await;
+                        self.run_sync_path_requests(engine).await;
+                        self.answer_plan_requests(engine).await;
+                    }
+                    _ = self.lifecycle.reload_requested() => return Ok(SessionEnd::Reload),
+                    _ = self.shutdown.cancelled() => {
+                        info!("Shutdown signal received");
+                        break 'sync;
+                    }
                 }
             }
         }
 
         info!("Sync loop terminated");
-        Ok(())
+        Ok(SessionEnd::Shutdown)
+    }
+
+    /// Returns whether `Auth.Logout` was called, clearing the request
+    async fn take_logout_request(&self) -> bool {
+        std::mem::take(&mut self.daemon_state.lock().await.logout_requested)
+    }
+
+    /// Restores the folder selection stored by a previous run
+    async fn load_folder_selection(&self, engine: &SyncEngine) {
+        let folders = match self.state_repo.get_selected_folders().await {
+            Ok(folders) => folders,
+            Err(e) => {
+                warn!(error = %e, "Failed to load the selected folders");
+                return;
+            }
+        };
+        match FolderSelection::new(&folders) {
+            Ok(selection) => {
+                engine.set_folder_selection(selection);
+                self.daemon_state.lock().await.selected_folders = folders;
+            }
+            Err(e) => warn!(error = %format!("{e:#}"), "Ignoring invalid folder selection"),
+        }
+    }
+
+    /// Applies the folder selection set over D-Bus, if there is one
+    ///
+    /// Progress and outcome are signalled on the Settings interface. The
+    /// selection D-Bus clients read is only updated once it was applied.
+    async fn apply_folder_selection_request(
+        &self,
+        engine: &SyncEngine,
+        dbus_connection: &zbus::Connection,
+    ) {
+        let Some(folders) = self
+            .daemon_state
+            .lock()
+            .await
+            .folder_selection_request
+            .take()
+        else {
+            return;
+        };
+        info!(count = folders.len(), "Applying folder selection");
+
+        let (tx, mut rx) = mpsc::unbounded_channel::<SelectionProgress>();
+        let connection = dbus_connection.clone();
+        let signals = tokio::spawn(async move {
+            while let Some(progress) = rx.recv().await {
+                let (phase, folder, done, total) = match &progress {
+                    SelectionProgress::Listing { folder, items } => {
+                        ("listing", folder.as_str(), *items, 0)
+                    }
+                    SelectionProgress::Removing { done, total } => ("removing", "", *done, *total),
+                };
+                if let Err(e) = SettingsInterface::emit_selection_progress(
+                    &connection,
+                    phase,
+                    folder,
+                    done,
+                    total,
+                )
+                .await
+                {
+                    debug!(error = %e, "Failed to emit selection progress");
+                }
+            }
+        });
+        let outcome = match FolderSelection::new(&folders) {
+            Ok(selection) => {
+                let report = move |progress: &SelectionProgress| {
+                    let _ = tx.send(progress.clone());
+                };
+                engine.apply_folder_selection(selection, &report).await
+            }
+            Err(e) => Err(e),
+        };
+        let _ = signals.await;
+
+        let (added, removed, error) = match &outcome {
+            Ok(outcome) => {
+                self.daemon_state.lock().await.selected_folders =
+                    engine.folder_selection().folders().to_vec();
+                (outcome.added, outcome.removed, String::new())
+            }
+            Err(e) => {
+                let err_msg = format!("{e:#}");
+                warn!(error = %err_msg, "Applying the folder selection failed");
+                (0, 0, err_msg)
+            }
+        };
+        if let Err(e) = SettingsInterface::emit_selection_applied(
+            dbus_connection,
+            outcome.is_ok(),
+            added,
+            removed,
+            &error,
+        )
+        .await
+        {
+            debug!(error = %e, "Failed to emit selection outcome");
+        }
+    }
+
+    /// Runs the queued sync-by-path requests one after the other
+    ///
+    /// The outcome of each is stored in the daemon state, where D-Bus
+    /// clients poll it. Requests still queued at shutdown are failed.
+    async fn run_sync_path_requests(&self, engine: &SyncEngine) {
+        loop {
+            let Some(request) = self.daemon_state.lock().await.start_next_sync_path() else {
+                return;
+            };
+            let status = if self.shutdown.is_cancelled() {
+                SyncPathStatus::Failed("The daemon is shutting down".to_string())
+            } else {
+                info!(path = %request.path, id = request.id, "Syncing path on request");
+                let sync = engine.sync_path(Path::new(&request.path));
+                let sync = self.watchdog.guard(sync, || engine.items_processed());
+                tokio::select! {
+                    outcome = sync => match outcome {
+                        Ok(result) => SyncPathStatus::Completed(sync_result_json(&result)),
+                        Err(e) => {
+                            let err_msg = format!("{e:#}");
+                            warn!(path = %request.path, error = %err_msg, "Sync of path failed");
+                            SyncPathStatus::Failed(err_msg)
+                        }
+                    },
+                    _ = self.shutdown.cancelled() => {
+                        SyncPathStatus::Failed("The daemon is shutting down".to_string())
+                    }
+                }
+            };
+            self.daemon_state
+                .lock()
+                .await
+                .finish_sync_path(request.id, status);
+        }
+    }
+
+    /// Answers the queued `GetPendingPlan` requests with a fresh plan
+    ///
+    /// Planning only reads the delta and the sync root, so it runs between
+    /// cycles and never races with a cycle applying changes.
+    async fn answer_plan_requests(&self, engine: &SyncEngine) {
+        if self.daemon_state.lock().await.plan_requests.is_empty() {
+            return;
+        }
+        let reply = match engine.plan().await {
+            Ok(plan) => serde_json::to_string(&plan.preview()).map_err(|e| e.to_string()),
+            Err(e) => {
+                let err_msg = format!("{e:#}");
+                warn!(error = %err_msg, "Planning the pending sync failed");
+                Err(err_msg)
+            }
+        };
+        self.daemon_state.lock().await.answer_plan_requests(reply);
+    }
+
+    /// Runs one sync cycle, draining it if shutdown is requested meanwhile
+    ///
+    /// On shutdown the engine stops starting new transfers and the cycle
+    /// gets `daemon.shutdown_timeout` seconds to finish the one in flight.
+    /// Returns `None` if it had to be cancelled; large uploads cut off
+    /// that way keep their checkpoint and resume on the next start.
+    async fn run_sync_cycle(&self, engine: &SyncEngine) -> Option<Result<SyncResult>> {
+        let sync = engine.sync();
+        tokio::pin!(sync);
+
+        tokio::select! {
+            outcome = self.watchdog.guard(&mut sync, || engine.items_processed()) => {
+                return Some(outcome);
+            }
+            _ = self.shutdown.cancelled() => {}
+        }
+
+        let timeout = Duration::from_secs(self.config().daemon.shutdown_timeout);
+        info!(
+            timeout_secs = timeout.as_secs(),
+            "Shutdown requested during sync, draining in-flight transfers"
+        );
+        engine.begin_drain();
+        let checkpoints = || {
+            self.upload_checkpoints
+                .as_ref()
+                .map_or(0, |store| store.count())
+        };
+        let completed_before = engine.transfers_completed();
+        let checkpoints_before = checkpoints();
+
+        let drain = self.watchdog.guard(&mut sync, || engine.items_processed());
+        let outcome = tokio::time::timeout(timeout, drain).await.ok();
+        let completed = engine.transfers_completed() - completed_before;
+        // Only uploads checkpointed during the drain, not older ones
+        let checkpointed = checkpoints().saturating_sub(checkpoints_before);
+        if outcome.is_some() {
+            info!(completed, checkpointed, "Drain finished");
+        } else {
+            warn!(
+                completed,
+                checkpointed, "Drain timed out, cancelling remaining transfers"
+            );
+        }
+        outcome
+    }
+
+    /// Persists a finished sync cycle and publishes it to D-Bus clients
+    ///
+    /// Storage failures are logged but never abort the sync loop.
+    async fn record_sync_history(&self, mut entry: SyncHistoryEntry) {
+        match self.state_repo.save_sync_history(&entry).await {
+            Ok(id) => entry.id = Some(id),
+            Err(e) => warn!(error = %e, "Failed to save sync history entry"),
+        }
+        self.daemon_state.lock().await.push_sync_history(entry);
     }
 
     /// Waits for authentication in a loop, checking periodically
     ///
-    /// When no account or tokens are available, the daemon enters this
-    /// wait loop. It checks every 30 seconds for a newly configured account.
-    async fn wait_for_auth_loop(&self) -> Result<()> {
+    /// When no account or tokens are available, or the stored tokens were
+    /// rejected, the daemon enters this wait loop. It checks every 30
+    /// seconds for a newly configured account, or for tokens other than
+    /// the `rejected` access token, and returns so a new session starts.
+    async fn wait_for_auth_loop(&self, rejected: Option<&str>) -> Result<()> {
         {
             let mut state = self.daemon_state.lock().await;
             state.sync_state = DaemonSyncState::WaitingForAuth;
         }
 
         info!("Waiting for authentication. Run 'lnxdrive auth login' to configure.");
+        self.notify_ready();
 
         let check_interval = Duration::from_secs(30);
+        let mut check =
+            tokio::time::interval_at(tokio::time::Instant::now() + check_interval, check_interval);
 
         loop {
             tokio::select! {
-                _ = tokio::time::sleep(check_interval) => {
+                _ = self.watchdog.tick() => self.watchdog.alive(),
+                _ = check.tick() => {
                     // Check if an account has been configured
-                    match self.state_repo.get_default_account().await {
+                    match Self::served_account(&self.config(), &self.state_repo).await {
                         Ok(Some(account)) => {
-                            match KeyringTokenStorage::load(account.email().as_str()) {
-                                Ok(Some(_tokens)) => {
+                            match KeyringTokenStorage::load(&KeyringTokenStorage::account_id_of(&account)) {
+                                Ok(Some(tokens))
+                                    if rejected != Some(tokens.access_token.as_str()) =>
+                                {
                                     info!(
                                         email = %account.email(),
-                                        "Account and tokens found, restarting daemon"
+                                        "Account and tokens found, starting a new session"
                                     );
-                                    // Authentication is now available; restart the
-                                    // run process by returning Ok to signal the caller.
-                                    // In practice the daemon would need to be restarted
-                                    // or re-enter the run loop.
                                     return Ok(());
                                 }
                                 _ => {
@@ -412,6 +1195,10 @@ impl DaemonService {
                         }
                     }
                 }
+                _ = self.lifecycle.reload_requested() => {
+                    self.reload_config();
+                    return Ok(());
+                }
                 _ = self.shutdown.cancelled() => {
                     info!("Shutdown signal received while waiting for auth");
                     return Ok(());
@@ -421,6 +1208,134 @@ impl DaemonService {
     }
 }
 
+/// Why a sync session ended
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SessionEnd {
+    /// The daemon is shutting down
+    Shutdown,
+    /// The credentials were rejected; syncing waits for a new login
+    Unauthorized,
+    /// The configuration is reloaded and a new session started
+    Reload,
+    /// The user logged out; syncing waits for a new login
+    LoggedOut,
+}
+
+/// Exports the size of the mounted inode table as `lnxdrive_fuse_inodes`
+fn register_inode_gauge(inode_table: Arc<InodeTable>) {
+    let gauge = GaugeFn::new(
+        "lnxdrive_fuse_inodes",
+        "Number of inodes held in memory by the FUSE filesystem",
+        move || inode_table.len() as f64,
+    );
+    if let Err(e) = gauge.and_then(GaugeFn::register) {
+        warn!(error = %e, "Failed to register the inode table gauge");
+    }
+}
+
+/// The auto-mounted filesystem, as supervised for aborted sessions
+struct DaemonMount<'a> {
+    service: &'a DaemonService,
+    notifier: Arc<DesktopNotifier>,
+    hydration_observer: Arc<DbusHydrationObserver>,
+    /// Downloads cloud-only files of each new mount
+    provider: Arc<GraphCloudProvider>,
+    /// Receives the remote renames handle of each new mount
+    items: Arc<MountedItems>,
+}
+
+#[async_trait]
+impl SupervisedMount for DaemonMount<'_> {
+    fn is_alive(&self) -> bool {
+        self.service.fuse_session_alive()
+    }
+
+    async fn remount(&self) -> Result<()> {
+        self.items.set(None);
+        self.service.unmount_fuse().await;
+        let mount_point = Path::new(&self.service.config().fuse.mount_point).to_path_buf();
+        detach_stale_mount(&lnxdrive_core::config::expand_tilde(&mount_point));
+
+        let remote_changes = self
+            .service
+            .mount_fuse(
+                Arc::clone(&self.notifier),
+                Arc::clone(&self.hydration_observer),
+                Arc::clone(&self.provider),
+            )
+            .await
+            .ok_or_else(|| anyhow::anyhow!("Failed to mount the FUSE filesystem"))?;
+        self.items.set(Some(remote_changes));
+        Ok(())
+    }
+}
+
+/// Exports the content cache hits and misses of the mounted filesystem
+fn register_cache_gauges(cache_stats: Arc<CacheStats>) {
+    let hits = Arc::clone(&cache_stats);
+    let gauges = [
+        GaugeFn::new(
+            stats::CACHE_HITS,
+            "File opens served from the content cache",
+            move || hits.hits() as f64,
+        ),
+        GaugeFn::new(
+            stats::CACHE_MISSES,
+            "File opens that had to download the content",
+            move || cache_stats.misses() as f64,
+        ),
+    ];
+    for gauge in gauges {
+        if let Err(e) = gauge.and_then(GaugeFn::register) {
+            warn!(error = %e, "Failed to register a cache gauge");
+        }
+    }
+}
+
+/// Creates the session counters reported by `lnxdrive status --stats`
+///
+/// They are exported by the metrics endpoint as well. Returns `None` if
+/// the counters cannot be registered, in which case `GetStats` fails.
+fn register_metrics() -> Option<Arc<MetricsRegistry>> {
+    let throttled = GaugeFn::new(
+        stats::THROTTLED_REQUESTS,
+        "Graph requests rejected with HTTP 429",
+        || lnxdrive_sync::engine::throttled_requests() as f64,
+    );
+    if let Err(e) = throttled.and_then(GaugeFn::register) {
+        warn!(error = %e, "Failed to register the throttled requests gauge");
+    }
+    let healed = GaugeFn::new(
+        stats::CACHE_HEALED,
+        "Cached files removed because they failed verification",
+        || lnxdrive_fuse::scrub::healed_total() as f64,
+    );
+    if let Err(e) = healed.and_then(GaugeFn::register) {
+        warn!(error = %e, "Failed to register the healed cache files gauge");
+    }
+    match MetricsRegistry::register_default() {
+        Ok(metrics) => Some(Arc::new(metrics)),
+        Err(e) => {
+            warn!(error = %e, "Failed to register the session counters");
+            None
+        }
+    }
+}
+
+/// Summary of a sync result as published to D-Bus clients
+fn sync_result_json(result: &SyncResult) -> serde_json::Value {
+    serde_json::json!({
+        "files_downloaded": result.files_downloaded,
+        "files_uploaded": result.files_uploaded,
+        "files_deleted": result.files_deleted,
+        "bytes_downloaded": result.bytes_downloaded,
+        "bytes_uploaded": result.bytes_uploaded,
+        "transfers_paused": result.transfers_paused,
+        "errors": result.errors,
+        "duration_ms": result.duration_ms,
+    })
+}
+
 // ============================================================================
 // T217: Graceful shutdown signal handler
 // ============================================================================
@@ -459,21 +1374,102 @@ async fn shutdown_signal(token: CancellationToken) {
     token.cancel();
 }
 
+// ============================================================================
+// Logging setup
+// ============================================================================
+
+/// Output format of the daemon's log lines
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum LogFormat {
+    /// Human-readable lines, for interactive use
+    Text,
+    /// One JSON object per line with structured fields, for journald/Loki/ELK
+    Json,
+}
+
+impl LogFormat {
+    /// Parses `text` or `json`
+    fn parse(value: &str) -> Result<Self> {
+        match value {
+            "text" => Ok(LogFormat::Text),
+            "json" => Ok(LogFormat::Json),
+            other => anyhow::bail!("Invalid log format '{other}'; expected 'text' or 'json'"),
+        }
+    }
+
+    /// Resolves the format from the command line, falling back to the config
+    ///
+    /// Accepts both `--log-format json` and `--log-format=json`.
+    fn from_args(args: impl IntoIterator<Item = String>, config: &Config) -> Result<Self> {
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            if arg == "--log-format" {
+                let value = args.next().context("--log-format requires a value")?;
+                return Self::parse(&value);
+            }
+            if let Some(value) = arg.strip_prefix("--log-format=") {
+                return Self::parse(value);
+            }
+        }
+        Self::parse(&config.logging.format)
+    }
+}
+
+/// Installs the global tracing subscriber
+///
+/// `RUST_LOG` takes precedence over `logging.level`. In JSON mode event
+/// fields are flattened into the top-level object and the enclosing spans
+/// (with their fields) are included, so `item_id`, `remote_id`, etc.
+/// recorded on a span show up on every event inside it.
+fn init_tracing(format: LogFormat, level: &str) {
+    let env_filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new(level));
+
+    match format {
+        LogFormat::Text => tracing_subscriber::fmt()
+            .with_env_filter(env_filter)
+            .with_target(true)
+            .init(),
+        LogFormat::Json => tracing_subscriber::fmt()
+            .json()
+            .with_env_filter(env_filter)
+            .with_target(true)
+            .flatten_event(true)
+            .with_current_span(true)
+            .with_span_list(true)
+            .init(),
+    }
+}
+
 // ============================================================================
 // T215/T217/T218: Main entry point
 // ============================================================================
 
 #[tokio::main]
 async fn main() -> Result<()> {
-    // Initialize tracing
-    let env_filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info"));
+    // Load configuration before tracing so it can pick the log format
+    let config_path = Config::default_path();
+    let config = Config::load_or_default(&config_path);
 
-    tracing_subscriber::fmt()
-        .with_env_filter(env_filter)
-        .with_target(true)
-        .init();
+    // Initialize tracing
+    let log_format = LogFormat::from_args(std::env::args().skip(1), &config)?;
+    init_tracing(log_format, &config.logging.level);
 
     info!("LNXDrive daemon starting (lnxdrived)");
+    info!(config_path = %config_path.display(), "Loaded configuration");
+
+    // Single-instance guard that does not depend on the session bus; held
+    // until main() returns
+    let lock_path = InstanceLock::default_path();
+    let _instance_lock = match InstanceLock::acquire(&lock_path) {
+        Ok(lock) => {
+            info!(path = %lock.path().display(), "Acquired instance lock");
+            lock
+        }
+        Err(e) => {
+            error!(error = %e, "Failed to acquire instance lock");
+            return Err(e);
+        }
+    };
 
     // T218: Create cancellation token for propagation to all tasks
     let shutdown_token = CancellationToken::new();
@@ -484,12 +1480,40 @@ async fn main() -> Result<()> {
         shutdown_signal(signal_token).await;
     });
 
+    // Tell systemd as soon as shutdown begins so it does not treat the
+    // drain period as a hang; a restart keeps the process, so it is a reload
+    let lifecycle = Lifecycle::new(shutdown_token.clone());
+    let notifier = Arc::new(SystemdNotifier::from_env(config.daemon.systemd_notify));
+    let stopping_notifier = Arc::clone(&notifier);
+    let stopping_lifecycle = Arc::clone(&lifecycle);
+    let stopping_token = shutdown_token.clone();
+    tokio::spawn(async move {
+        stopping_token.cancelled().await;
+        if stopping_lifecycle.restart_requested() {
+            stopping_notifier.reloading();
+        } else {
+            stopping_notifier.stopping();
+        }
+    });
+
     // Create and run the daemon service
-    let service = DaemonService::new(shutdown_token.clone()).await?;
+    let service = DaemonService::new(
+        config,
+        config_path,
+        notifier,
+        Arc::clone(&lifecycle),
+        shutdown_token.clone(),
+    )
+    .await?;
 
     let result = service.run().await;
 
     match &result {
+        Ok(()) if lifecycle.restart_requested() => {
+            drop(service);
+            drop(_instance_lock);
+            reexec()?;
+        }
         Ok(()) => info!("LNXDrive daemon shut down gracefully"),
         Err(e) => error!(error = %e, "LNXDrive daemon exiting with error"),
     }
@@ -541,6 +1565,44 @@ mod tests {
         assert!(config.sync.poll_interval > 0);
     }
 
+    fn args(list: &[&str]) -> Vec<String> {
+        list.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_log_format_defaults_to_config() {
+        let mut config = Config::default();
+        assert_eq!(
+            LogFormat::from_args(args(&[]), &config).unwrap(),
+            LogFormat::Text
+        );
+        config.logging.format = "json".to_string();
+        assert_eq!(
+            LogFormat::from_args(args(&[]), &config).unwrap(),
+            LogFormat::Json
+        );
+    }
+
+    #[test]
+    fn test_log_format_flag_overrides_config() {
+        let config = Config::default();
+        assert_eq!(
+            LogFormat::from_args(args(&["--log-format", "json"]), &config).unwrap(),
+            LogFormat::Json
+        );
+        assert_eq!(
+            LogFormat::from_args(args(&["--log-format=json"]), &config).unwrap(),
+            LogFormat::Json
+        );
+    }
+
+    #[test]
+    fn test_log_format_rejects_invalid_values() {
+        let config = Config::default();
+        assert!(LogFormat::from_args(args(&["--log-format", "xml"]), &config).is_err());
+        assert!(LogFormat::from_args(args(&["--log-format"]), &config).is_err());
+    }
+
     #[test]
     fn test_config_default_path_exists() {
         let path = Config::default_path();
@@ -0,0 +1,218 @@
+//! Periodic storage quota refresh
+//!
+//! The quota is fetched from the cloud provider every
+//! `sync.quota_refresh_interval` seconds, and right after a sync cycle
+//! that moved a lot of data. Each refresh:
+//! - updates `DaemonState` and emits `Status.QuotaChanged` if it changed
+//! - stores it on the account, where `lnxdrive status` and the FUSE
+//!   `statfs` read it
+//! - shows a desktop notification when usage crosses 90% or 95%
+
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use lnxdrive_cache::SqliteStateRepository;
+use lnxdrive_core::{
+    domain::{
+        newtypes::AccountId, DriveQuota, QuotaLevel, QUOTA_CRITICAL_PERCENT, QUOTA_NEARING_PERCENT,
+    },
+    ports::{
+        cloud_provider::ICloudProvider,
+        notification::{INotificationService, Notification, NotificationPriority},
+        state_repository::IStateRepository,
+    },
+};
+use lnxdrive_ipc::service::{DaemonState, StatusInterface};
+use tokio::sync::Mutex;
+use tracing::{debug, info, warn};
+
+/// Bytes moved in one sync cycle after which the quota is refreshed early
+pub const LARGE_TRANSFER_BYTES: u64 = 100 * 1024 * 1024;
+
+/// Keeps the daemon's view of the storage quota up to date
+pub struct QuotaMonitor {
+    provider: Arc<dyn ICloudProvider>,
+    state_repo: Arc<SqliteStateRepository>,
+    /// Account the quota is stored on
+    account_id: AccountId,
+    daemon_state: Arc<Mutex<DaemonState>>,
+    connection: zbus::Connection,
+    notifier: Arc<dyn INotificationService>,
+    interval: Duration,
+    last_refresh: Option<Instant>,
+    /// Highest level already notified, so each threshold is announced once
+    notified_level: QuotaLevel,
+}
+
+impl QuotaMonitor {
+    pub fn new(
+        provider: Arc<dyn ICloudProvider>,
+        state_repo: Arc<SqliteStateRepository>,
+        account_id: AccountId,
+        daemon_state: Arc<Mutex<DaemonState>>,
+        connection: zbus::Connection,
+        notifier: Arc<dyn INotificationService>,
+        interval: Duration,
+    ) -> Self {
+        Self {
+            provider,
+            state_repo,
+            account_id,
+            daemon_state,
+            connection,
+            notifier,
+            interval,
+            last_refresh: None,
+            notified_level: QuotaLevel::Normal,
+        }
+    }
+
+    /// Refreshes the quota if it is due after a cycle that moved `bytes_transferred`
+    pub async fn refresh_if_due(&mut self, bytes_transferred: u64) {
+        let since_last = self.last_refresh.map(|at| at.elapsed());
+        if is_due(since_last, self.interval, bytes_transferred) {
+            self.refresh().await;
+        }
+    }
+
+    /// Fetches the quota and publishes it; failures are only logged
+    pub async fn refresh(&mut self) {
+        self.last_refresh = Some(Instant::now());
+        let quota = match self.provider.get_quota().await {
+            Ok(quota) => quota,
+            Err(e) => {
+                warn!(error = %e, "Failed to refresh storage quota");
+                return;
+            }
+        };
+        debug!(
+            used = quota.used,
+            total = quota.total,
+            "Storage quota refreshed"
+        );
+
+        let changed = self
+            .daemon_state
+            .lock()
+            .await
+            .update_quota(quota.used, quota.total);
+        if changed {
+            if let Err(e) =
+                StatusInterface::emit_quota_changed(&self.connection, quota.used, quota.total).await
+            {
+                debug!(error = %e, "Failed to emit QuotaChanged");
+            }
+            self.save_to_account(&quota).await;
+        }
+
+        let level = quota.level();
+        if let Some(notification) = quota_notification(self.notified_level, &quota) {
+            info!(level = ?level, "Storage is nearly full");
+            if let Err(e) = self.notifier.notify(&notification).await {
+                warn!(error = %e, "Failed to show quota notification");
+            }
+        }
+        self.notified_level = level;
+    }
+
+    /// Stores the quota on the synced account
+    async fn save_to_account(&self, quota: &DriveQuota) {
+        let result = async {
+            if let Some(mut account) = self.state_repo.get_account(&self.account_id).await? {
+                account.update_quota(quota.used, quota.total);
+                self.state_repo.save_account(&account).await?;
+            }
+            anyhow::Ok(())
+        }
+        .await;
+        if let Err(e) = result {
+            warn!(error = %e, "Failed to store storage quota");
+        }
+    }
+}
+
+/// Returns true if the quota should be fetched again
+///
+/// `since_last` is `None` before the first refresh.
+fn is_due(since_last: Option<Duration>, interval: Duration, bytes_transferred: u64) -> bool {
+    match since_last {
+        None => true,
+        Some(elapsed) => elapsed >= interval || bytes_transferred >= LARGE_TRANSFER_BYTES,
+    }
+}
+
+/// Builds the warning for a quota that rose above `previous`, if any
+fn quota_notification(previous: QuotaLevel, quota: &DriveQuota) -> Option<Notification> {
+    let level = quota.level();
+    if level <= previous {
+        return None;
+    }
+    let percent = quota.percent_used().unwrap_or_default();
+    let (title, threshold, priority) = match level {
+        QuotaLevel::Normal => return None,
+        QuotaLevel::Nearing => (
+            "OneDrive storage is almost full",
+            QUOTA_NEARING_PERCENT,
+            NotificationPriority::Normal,
+        ),
+        QuotaLevel::Critical => (
+            "OneDrive storage is full",
+            QUOTA_CRITICAL_PERCENT,
+            NotificationPriority::High,
+        ),
+    };
+    let remaining_mb = quota.remaining().unwrap_or_default() / (1024 * 1024);
+    Some(
+        Notification::new(
+            title,
+            format!(
+                "{percent:.0}% used (over {threshold:.0}%), {remaining_mb} MB left. \
+                 Uploads will fail once the quota is exhausted."
+            ),
+        )
+        .with_priority(priority)
+        .with_category("quota"),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_due() {
+        let interval = Duration::from_secs(900);
+        assert!(is_due(None, interval, 0));
+        assert!(!is_due(Some(Duration::from_secs(60)), interval, 0));
+        assert!(is_due(Some(Duration::from_secs(900)), interval, 0));
+        assert!(is_due(
+            Some(Duration::from_secs(60)),
+            interval,
+            LARGE_TRANSFER_BYTES
+        ));
+    }
+
+    #[test]
+    fn test_notifies_once_per_threshold() {
+        let nearing = DriveQuota::new(91, 100);
+        let critical = DriveQuota::new(96, 100);
+
+        let first = quota_notification(QuotaLevel::Normal, &nearing).unwrap();
+        assert_eq!(first.category, "quota");
+        assert!(first.body.starts_with("91% used"));
+        assert!(quota_notification(QuotaLevel::Nearing, &nearing).is_none());
+
+        let second = quota_notification(QuotaLevel::Nearing, &critical).unwrap();
+        assert_eq!(second.priority, NotificationPriority::High);
+        assert!(quota_notification(QuotaLevel::Critical, &critical).is_none());
+        assert!(quota_notification(QuotaLevel::Critical, &nearing).is_none());
+    }
+
+    #[test]
+    fn test_unlimited_quota_never_notifies() {
+        let quota = DriveQuota::unlimited(u64::MAX / 2);
+        assert!(quota_notification(QuotaLevel::Normal, &quota).is_none());
+    }
+}
@@ -0,0 +1,383 @@
+//! systemd service notifications
+//!
+//! Implements the client side of the `sd_notify` protocol so the daemon can
+//! run as a `Type=notify` unit:
+//! - `READY=1` once D-Bus is acquired and the main loop has been reached
+//! - `WATCHDOG=1` pings at half of `WatchdogSec`, when the unit sets one,
+//!   sent by the [`Watchdog`] only while the main loop makes progress
+//! - `STOPPING=1` when shutdown begins
+//! - `RELOADING=1` when the daemon restarts itself in place
+//!
+//! The protocol is a datagram sent to the Unix socket named by
+//! `$NOTIFY_SOCKET`. When that variable is unset (not running under
+//! systemd) or notifications are disabled, every call is a no-op.
+
+use std::{
+    env,
+    future::Future,
+    os::unix::net::{SocketAddr, UnixDatagram},
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use lnxdrive_core::ports::{ITransferObserver, TransferEvent};
+use tokio::time::Instant;
+use tracing::{debug, info, warn};
+
+/// Environment variable that disables notifications when set to `0`/`false`
+pub const DISABLE_ENV: &str = "LNXDRIVE_SYSTEMD_NOTIFY";
+
+/// Sends service state notifications to systemd
+#[derive(Debug)]
+pub struct SystemdNotifier {
+    /// Socket and address of the systemd notification endpoint
+    target: Option<(UnixDatagram, SocketAddr)>,
+    /// Interval between watchdog pings (half of `WatchdogSec`)
+    watchdog_interval: Option<Duration>,
+}
+
+impl SystemdNotifier {
+    /// Creates a notifier from the environment systemd sets for the unit
+    ///
+    /// Returns a no-op notifier when `enabled` is false, when
+    /// `LNXDRIVE_SYSTEMD_NOTIFY=0` is set, or when not running under systemd.
+    pub fn from_env(enabled: bool) -> Self {
+        let env_enabled = env::var(DISABLE_ENV)
+            .map(|v| !matches!(v.as_str(), "0" | "false" | "no"))
+            .unwrap_or(true);
+        if !enabled || !env_enabled {
+            debug!("systemd notifications disabled");
+            return Self::disabled();
+        }
+
+        Self::new(
+            env::var("NOTIFY_SOCKET").ok().as_deref(),
+            env::var("WATCHDOG_USEC").ok().as_deref(),
+            env::var("WATCHDOG_PID").ok().as_deref(),
+        )
+    }
+
+    /// Creates a notifier from raw `NOTIFY_SOCKET`, `WATCHDOG_USEC` and
+    /// `WATCHDOG_PID` values
+    pub fn new(
+        notify_socket: Option<&str>,
+        watchdog_usec: Option<&str>,
+        watchdog_pid: Option<&str>,
+    ) -> Self {
+        let Some(target) = notify_socket.and_then(Self::connect) else {
+            return Self::disabled();
+        };
+
+        Self {
+            target: Some(target),
+            watchdog_interval: watchdog_interval(watchdog_usec, watchdog_pid, std::process::id()),
+        }
+    }
+
+    /// Creates a notifier that never sends anything
+    pub fn disabled() -> Self {
+        Self {
+            target: None,
+            watchdog_interval: None,
+        }
+    }
+
+    /// Returns true if notifications are sent to systemd
+    pub fn is_enabled(&self) -> bool {
+        self.target.is_some()
+    }
+
+    /// Returns the interval at which watchdog pings are due, if any
+    pub fn watchdog_interval(&self) -> Option<Duration> {
+        self.watchdog_interval
+    }
+
+    /// Signals that the service finished starting up
+    pub fn ready(&self) {
+        self.notify("READY=1");
+    }
+
+    /// Signals that the service is shutting down
+    pub fn stopping(&self) {
+        self.notify("STOPPING=1");
+    }
+
+    /// Signals that the service is restarting in place; `READY=1` follows
+    /// once the new process image is up
+    pub fn reloading(&self) {
+        self.notify("RELOADING=1");
+    }
+
+    /// Sends a watchdog keep-alive ping
+    pub fn watchdog(&self) {
+        self.notify("WATCHDOG=1");
+    }
+
+    fn notify(&self, state: &str) {
+        if let Some((socket, addr)) = &self.target {
+            if let Err(e) = socket.send_to_addr(state.as_bytes(), addr) {
+                warn!(state, error = %e, "Failed to send systemd notification");
+            }
+        }
+    }
+
+    fn connect(path: &str) -> Option<(UnixDatagram, SocketAddr)> {
+        let addr = match path.strip_prefix('@') {
+            Some(name) => abstract_addr(name)?,
+            None if path.starts_with('/') => SocketAddr::from_pathname(path).ok()?,
+            None => {
+                warn!(path, "Unsupported NOTIFY_SOCKET address");
+                return None;
+            }
+        };
+        match UnixDatagram::unbound() {
+            Ok(socket) => Some((socket, addr)),
+            Err(e) => {
+                warn!(error = %e, "Failed to create systemd notification socket");
+                None
+            }
+        }
+    }
+}
+
+/// Pings the systemd watchdog on behalf of the daemon's main loop
+///
+/// The main loop calls [`alive`](Self::alive) on each iteration and wraps
+/// long work (a sync cycle) in [`guard`](Self::guard), which keeps pinging
+/// only while that work reports progress. Large transfers beat the
+/// heartbeat through their progress events. Once nothing has beaten it for
+/// a whole watchdog timeout the pings stop, and systemd restarts a daemon
+/// whose loop is stuck.
+#[derive(Debug)]
+pub struct Watchdog {
+    notifier: Arc<SystemdNotifier>,
+    /// Last time the main loop made progress
+    last_beat: Mutex<Instant>,
+    /// Last time a ping was due, sent or not
+    last_check: Mutex<Instant>,
+}
+
+impl Watchdog {
+    /// Creates a watchdog pinging through `notifier`
+    pub fn new(notifier: Arc<SystemdNotifier>) -> Self {
+        if let Some(interval) = notifier.watchdog_interval() {
+            info!(
+                interval_ms = interval.as_millis() as u64,
+                "systemd watchdog enabled"
+            );
+        }
+        let now = Instant::now();
+        Self {
+            notifier,
+            last_beat: Mutex::new(now),
+            last_check: Mutex::new(now),
+        }
+    }
+
+    /// Records that the main loop made progress
+    pub fn beat(&self) {
+        *self.last_beat.lock().unwrap_or_else(|e| e.into_inner()) = Instant::now();
+    }
+
+    /// Records progress and pings right away; called by the main loop on
+    /// each iteration
+    pub fn alive(&self) {
+        self.beat();
+        self.ping_if_alive();
+    }
+
+    /// Pings the watchdog unless the main loop made no progress for a whole
+    /// watchdog timeout
+    ///
+    /// Returns whether a ping was sent.
+    pub fn ping_if_alive(&self) -> bool {
+        let Some(interval) = self.notifier.watchdog_interval() else {
+            return false;
+        };
+        *self.last_check.lock().unwrap_or_else(|e| e.into_inner()) = Instant::now();
+        let stalled_for = self
+            .last_beat
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .elapsed();
+        // The interval is half the timeout
+        if stalled_for >= interval * 2 {
+            warn!(
+                stalled_secs = stalled_for.as_secs(),
+                "Main loop made no progress, withholding watchdog ping"
+            );
+            return false;
+        }
+        self.notifier.watchdog();
+        true
+    }
+
+    /// Completes when the next ping is due; never when the unit has no
+    /// `WatchdogSec`
+    pub async fn tick(&self) {
+        let Some(interval) = self.notifier.watchdog_interval() else {
+            return std::future::pending().await;
+        };
+        let last_check = *self.last_check.lock().unwrap_or_else(|e| e.into_inner());
+        tokio::time::sleep_until(last_check + interval).await;
+    }
+
+    /// Runs `work`, pinging the watchdog while `progress` keeps changing
+    ///
+    /// `progress` is a counter that grows while the work advances, such as
+    /// [`SyncEngine::items_processed`](lnxdrive_sync::engine::SyncEngine::items_processed).
+    pub async fn guard<T>(&self, work: impl Future<Output = T>, progress: impl Fn() -> u64) -> T {
+        tokio::pin!(work);
+        let mut last = progress();
+        loop {
+            tokio::select! {
+                output = &mut work => return output,
+                _ = self.tick() => {
+                    let current = progress();
+                    if current != last {
+                        last = current;
+                        self.beat();
+                    }
+                    self.ping_if_alive();
+                }
+            }
+        }
+    }
+}
+
+impl ITransferObserver for Watchdog {
+    fn on_transfer_event(&self, _event: TransferEvent) {
+        self.beat();
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn abstract_addr(name: &str) -> Option<SocketAddr> {
+    use std::os::linux::net::SocketAddrExt;
+    SocketAddr::from_abstract_name(name.as_bytes()).ok()
+}
+
+#[cfg(not(target_os = "linux"))]
+fn abstract_addr(_name: &str) -> Option<SocketAddr> {
+    None
+}
+
+/// Computes the watchdog ping interval from `WATCHDOG_USEC`
+///
+/// Pings are sent at half the timeout, as recommended by `sd_watchdog_enabled(3)`.
+/// When `WATCHDOG_PID` names another process, the watchdog is not ours.
+fn watchdog_interval(usec: Option<&str>, pid: Option<&str>, own_pid: u32) -> Option<Duration> {
+    if let Some(pid) = pid {
+        if pid.parse::<u32>().ok()? != own_pid {
+            return None;
+        }
+    }
+    let usec = usec?.parse::<u64>().ok().filter(|&usec| usec > 0)?;
+    Some(Duration::from_micros(usec / 2))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicU64, Ordering};
+
+    use super::*;
+
+    #[test]
+    fn test_watchdog_interval_is_half_the_timeout() {
+        assert_eq!(
+            watchdog_interval(Some("30000000"), None, 1),
+            Some(Duration::from_secs(15))
+        );
+        assert_eq!(watchdog_interval(Some("0"), None, 1), None);
+        assert_eq!(watchdog_interval(Some("soon"), None, 1), None);
+        assert_eq!(watchdog_interval(None, None, 1), None);
+    }
+
+    #[test]
+    fn test_watchdog_interval_respects_watchdog_pid() {
+        assert!(watchdog_interval(Some("1000000"), Some("42"), 42).is_some());
+        assert!(watchdog_interval(Some("1000000"), Some("43"), 42).is_none());
+    }
+
+    #[test]
+    fn test_without_notify_socket_is_noop() {
+        let notifier = SystemdNotifier::new(None, Some("1000000"), None);
+        assert!(!notifier.is_enabled());
+        assert!(notifier.watchdog_interval().is_none());
+        notifier.ready();
+        notifier.stopping();
+    }
+
+    #[test]
+    fn test_sends_state_to_notify_socket() {
+        let path = env::temp_dir().join(format!("lnxdrive-notify-{}.sock", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let receiver = UnixDatagram::bind(&path).unwrap();
+
+        let notifier = SystemdNotifier::new(path.to_str(), Some("2000000"), None);
+        assert!(notifier.is_enabled());
+        assert_eq!(notifier.watchdog_interval(), Some(Duration::from_secs(1)));
+
+        notifier.ready();
+        let mut buf = [0u8; 64];
+        let n = receiver.recv(&mut buf).unwrap();
+        assert_eq!(&buf[..n], b"READY=1");
+
+        notifier.stopping();
+        let n = receiver.recv(&mut buf).unwrap();
+        assert_eq!(&buf[..n], b"STOPPING=1");
+
+        notifier.reloading();
+        let n = receiver.recv(&mut buf).unwrap();
+        assert_eq!(&buf[..n], b"RELOADING=1");
+
+        let _ = std::fs::remove_file(&path);
+    }
+
+    /// Counts the watchdog pings waiting on `receiver`
+    fn take_pings(receiver: &UnixDatagram) -> usize {
+        let mut buf = [0u8; 64];
+        let mut pings = 0;
+        while let Ok(n) = receiver.recv(&mut buf) {
+            assert_eq!(&buf[..n], b"WATCHDOG=1");
+            pings += 1;
+        }
+        pings
+    }
+
+    #[tokio::test]
+    async fn test_stalled_loop_stops_watchdog_pings() {
+        let path = env::temp_dir().join(format!("lnxdrive-watchdog-{}.sock", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let receiver = UnixDatagram::bind(&path).unwrap();
+        receiver.set_nonblocking(true).unwrap();
+
+        // Pings every 50 ms, a stall is declared after 100 ms
+        let notifier = Arc::new(SystemdNotifier::new(path.to_str(), Some("100000"), None));
+        let watchdog = Watchdog::new(notifier);
+        let run = |progress: &'static AtomicU64, step: u64| {
+            let work = watchdog.guard(std::future::pending::<()>(), move || {
+                progress.fetch_add(step, Ordering::Relaxed)
+            });
+            tokio::time::timeout(Duration::from_millis(300), work)
+        };
+
+        // Work that makes progress keeps the pings going
+        static ADVANCING: AtomicU64 = AtomicU64::new(0);
+        let _ = run(&ADVANCING, 1).await;
+        assert!(take_pings(&receiver) >= 2);
+
+        // Once it stalls, the pings stop
+        static STALLED: AtomicU64 = AtomicU64::new(0);
+        let _ = run(&STALLED, 0).await;
+        take_pings(&receiver);
+        let _ = run(&STALLED, 0).await;
+        assert_eq!(take_pings(&receiver), 0);
+
+        // The main loop coming round again resumes them
+        watchdog.alive();
+        assert_eq!(take_pings(&receiver), 1);
+
+        let _ = std::fs::remove_file(&path);
+    }
+}
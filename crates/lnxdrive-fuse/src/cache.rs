@@ -1,51 +1,236 @@
 //! File content cache for storing hydrated file data.
 //!
 //! Uses a hash-based directory structure for efficient storage and lookup.
+//! Files are sharded into nested directories named after leading pairs of
+//! hex digits of the hash, so no single directory grows too large on
+//! accounts with millions of files.
+//!
+//! ## Deduplication
+//!
+//! When enabled, identical content is stored once: each cache file is a
+//! hardlink to a blob under `{cache_dir}/blobs`, keyed by the content's
+//! quickXorHash. The filesystem's link count acts as the reference count,
+//! and `blobs/by-inode/{ino}` symlinks map a cache file back to its blob so
+//! the blob can be dropped when its last reference goes away. Writes to a
+//! shared file first break the link, so other items never see the change.
+//!
+//! ## Partial content
+//!
+//! A download that fetches ranges out of order records the extents it has
+//! written in a `.ranges` file next to the cache file, one `start-end`
+//! line per extent. Reads can then be served from the present ranges of
+//! the partial download, and an interrupted download resumes with the
+//! ranges it already has.
+//!
+//! ## Temporary files
+//!
+//! Content replaced as a whole (stored files, private copies of shared
+//! content) is written to a temporary file first and then renamed into
+//! place, so readers never see a half-written file. The temporary
+//! directory defaults to `{cache_dir}/tmp`; when it is configured on
+//! another filesystem, the file is copied into place instead. Partial
+//! downloads stay next to their cache file, since reads are served from
+//! them while they download.
 
 use std::{
+    collections::HashSet,
     fs::{self, File},
     io::{Read, Seek, SeekFrom, Write},
-    path::PathBuf,
+    ops::Range,
+    os::unix::fs::MetadataExt,
+    path::{Path, PathBuf},
+    sync::atomic::{AtomicU64, Ordering},
 };
 
-use lnxdrive_core::domain::newtypes::RemoteId;
+use lnxdrive_core::domain::{
+    newtypes::{FileHash, RemoteId},
+    QuickXorHash, SyncItem,
+};
 use sha2::{Digest, Sha256};
 
-use crate::error::FuseError;
+use crate::{error::FuseError, range_map::RangeMap};
+
+/// Default number of shard directory levels (`ab/cd/<rest>`).
+pub const DEFAULT_SHARD_DEPTH: u8 = 2;
+
+/// Maximum number of shard directory levels.
+pub const MAX_SHARD_DEPTH: u8 = 4;
+
+/// Marker file recording the shard depth the content directory uses.
+const LAYOUT_MARKER: &str = ".layout";
+
+/// Suffix of in-progress download files.
+const PARTIAL_SUFFIX: &str = ".partial";
+
+/// Suffix of temporary files used to swap in links and private copies.
+const SWAP_SUFFIX: &str = ".swap";
+
+/// Suffix of the files recording which ranges of a partial download exist.
+const RANGES_SUFFIX: &str = ".ranges";
+
+/// Name of the default temporary directory inside the cache directory.
+const TEMP_DIR: &str = "tmp";
+
+/// Distinguishes temporary files created concurrently for the same path.
+static TEMP_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+/// Read buffer used when hashing cached content (1 MB).
+const HASH_BUFFER_SIZE: usize = 1024 * 1024;
+
+/// Result of reading a range that may be only partly cached.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PresentRead {
+    /// Bytes from the requested offset up to the first gap
+    pub data: Vec<u8>,
+    /// Missing ranges within the requested range, sorted
+    pub gaps: Vec<Range<u64>>,
+}
+
+impl PresentRead {
+    /// Returns true if the whole requested range was read.
+    pub fn is_complete(&self) -> bool {
+        self.gaps.is_empty()
+    }
+}
+
+/// How often opened files were found in the cache.
+///
+/// Counted per `open()` of a file: content already on disk is a hit, a
+/// placeholder or a file still being downloaded is a miss.
+#[derive(Debug, Default)]
+pub struct CacheStats {
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+impl CacheStats {
+    /// Records an open served from the cache.
+    pub fn record_hit(&self) {
+        self.hits.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Records an open that has to wait for a download.
+    pub fn record_miss(&self) {
+        self.misses.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Number of opens served from the cache.
+    pub fn hits(&self) -> u64 {
+        self.hits.load(Ordering::Relaxed)
+    }
+
+    /// Number of opens that had to wait for a download.
+    pub fn misses(&self) -> u64 {
+        self.misses.load(Ordering::Relaxed)
+    }
+}
 
 /// Manages cached file content on disk.
 ///
-/// Content is stored in a hash-based directory structure:
-/// `{cache_dir}/content/{first_2_chars_of_hash}/{rest_of_hash}`
+/// Content is stored in a hash-based directory structure with one level
+/// per shard, e.g. for the default depth of 2:
+/// `{cache_dir}/content/{hash[0..2]}/{hash[2..4]}/{rest_of_hash}`
 pub struct ContentCache {
     #[allow(dead_code)]
     cache_dir: PathBuf,
     content_dir: PathBuf,
+    shard_depth: u8,
+    /// Blob directory, set when deduplication is enabled
+    blobs_dir: Option<PathBuf>,
+    /// Directory for files written before being renamed into place
+    temp_dir: PathBuf,
 }
 
 impl ContentCache {
-    /// Create a new ContentCache, creating the content directory if needed.
+    /// Create a new ContentCache with the default shard depth, creating the
+    /// content directory if needed.
     pub fn new(cache_dir: PathBuf) -> std::io::Result<Self> {
+        Self::with_shard_depth(cache_dir, DEFAULT_SHARD_DEPTH)
+    }
+
+    /// Create a new ContentCache using `shard_depth` directory levels.
+    ///
+    /// If the content directory was written with a different layout (e.g.
+    /// an older single-level cache), existing files are moved to their new
+    /// locations before the cache is returned.
+    pub fn with_shard_depth(cache_dir: PathBuf, shard_depth: u8) -> std::io::Result<Self> {
         let content_dir = cache_dir.join("content");
         fs::create_dir_all(&content_dir)?;
-        Ok(Self {
+        let temp_dir = cache_dir.join(TEMP_DIR);
+        fs::create_dir_all(&temp_dir)?;
+        let cache = Self {
             cache_dir,
             content_dir,
-        })
+            shard_depth: shard_depth.min(MAX_SHARD_DEPTH),
+            blobs_dir: None,
+            temp_dir,
+        };
+        cache.migrate_layout()?;
+        Ok(cache)
+    }
+
+    /// Enable or disable content deduplication.
+    pub fn with_dedup(mut self, enabled: bool) -> std::io::Result<Self> {
+        self.blobs_dir = if enabled {
+            let blobs_dir = self.cache_dir.join("blobs");
+            fs::create_dir_all(blobs_dir.join("by-inode"))?;
+            Some(blobs_dir)
+        } else {
+            None
+        };
+        Ok(self)
+    }
+
+    /// Write temporary files to `temp_dir` instead of `{cache_dir}/tmp`,
+    /// creating it if needed.
+    pub fn with_temp_dir(mut self, temp_dir: PathBuf) -> std::io::Result<Self> {
+        fs::create_dir_all(&temp_dir)?;
+        self.temp_dir = temp_dir;
+        Ok(self)
+    }
+
+    /// Directory for files written before being renamed into place.
+    pub fn temp_dir(&self) -> &Path {
+        &self.temp_dir
+    }
+
+    /// Whether identical content is stored only once.
+    pub fn is_dedup_enabled(&self) -> bool {
+        self.blobs_dir.is_some()
+    }
+
+    /// Number of shard directory levels in use.
+    pub fn shard_depth(&self) -> u8 {
+        self.shard_depth
     }
 
     /// Compute the cache path for a remote ID using SHA-256 hash.
     pub fn cache_path(&self, remote_id: &RemoteId) -> PathBuf {
-        let hash = Self::hash_remote_id(remote_id);
-        let (prefix, rest) = hash.split_at(2);
-        self.content_dir.join(prefix).join(rest)
+        self.path_for_hash(&Self::hash_remote_id(remote_id))
+    }
+
+    /// Build the sharded path for a full hex hash.
+    fn path_for_hash(&self, hash: &str) -> PathBuf {
+        self.sharded(&self.content_dir, hash)
+    }
+
+    /// Nest `hash` below `base` using one directory per shard level.
+    fn sharded(&self, base: &Path, hash: &str) -> PathBuf {
+        let mut path = base.to_path_buf();
+        let mut rest = hash;
+        for _ in 0..self.shard_depth {
+            let (prefix, tail) = rest.split_at(2);
+            path.push(prefix);
+            rest = tail;
+        }
+        path.join(rest)
     }
 
     /// Get the path for a partial (in-progress) download.
     pub fn partial_path(&self, remote_id: &RemoteId) -> PathBuf {
         let mut path = self.cache_path(remote_id);
         let filename = path.file_name().unwrap().to_string_lossy().to_string();
-        path.set_file_name(format!("{}.partial", filename));
+        path.set_file_name(format!("{}{}", filename, PARTIAL_SUFFIX));
         path
     }
 
@@ -55,20 +240,160 @@ impl ContentCache {
         if let Some(parent) = path.parent() {
             fs::create_dir_all(parent)?;
         }
-        let mut file = File::create(&path)?;
-        file.write_all(data)?;
+        let tmp = self.temp_path(&path)?;
+        if let Err(e) = fs::write(&tmp, data) {
+            let _ = fs::remove_file(&tmp);
+            return Err(e.into());
+        }
+        // Never truncate content shared with other items
+        if self.is_dedup_enabled() && path.exists() {
+            self.unlink(&path)?;
+        }
+        self.install(&tmp, &path)?;
         Ok(path)
     }
 
     /// Read bytes from cached file at offset.
     pub fn read(&self, remote_id: &RemoteId, offset: u64, size: u32) -> Result<Vec<u8>, FuseError> {
-        let path = self.cache_path(remote_id);
-        let mut file = File::open(&path)?;
-        file.seek(SeekFrom::Start(offset))?;
-        let mut buffer = vec![0u8; size as usize];
-        let bytes_read = file.read(&mut buffer)?;
-        buffer.truncate(bytes_read);
-        Ok(buffer)
+        Ok(read_at(&self.cache_path(remote_id), offset, size)?)
+    }
+
+    /// Read bytes of a file that may still be hydrating.
+    ///
+    /// Reads the complete cache file if there is one, otherwise the partial
+    /// download. The caller must know the range is present (see
+    /// `HydrationManager::wait_for_range`).
+    pub fn read_in_progress(
+        &self,
+        remote_id: &RemoteId,
+        offset: u64,
+        size: u32,
+    ) -> Result<Vec<u8>, FuseError> {
+        let complete = self.cache_path(remote_id);
+        let data = match read_at(&complete, offset, size) {
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                match read_at(&self.partial_path(remote_id), offset, size) {
+                    // Renamed into place in the meantime
+                    Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                        read_at(&complete, offset, size)
+                    }
+                    other => other,
+                }
+            }
+            other => other,
+        }?;
+        Ok(data)
+    }
+
+    /// Path of the file recording the present ranges of a partial download.
+    fn ranges_path(&self, remote_id: &RemoteId) -> PathBuf {
+        Self::sibling(&self.cache_path(remote_id), RANGES_SUFFIX)
+    }
+
+    /// Returns the ranges recorded for a partial download, or `None` if
+    /// none were recorded.
+    ///
+    /// An unreadable or malformed range file counts as no ranges present.
+    pub fn present_ranges(&self, remote_id: &RemoteId) -> Option<RangeMap> {
+        let contents = fs::read_to_string(self.ranges_path(remote_id)).ok()?;
+        let mut map = RangeMap::new();
+        for line in contents.lines().filter(|l| !l.trim().is_empty()) {
+            let parsed = line
+                .split_once('-')
+                .and_then(|(start, end)| Some((start.parse().ok()?, end.parse().ok()?)));
+            match parsed {
+                Some((start, end)) => map.insert(start..end),
+                None => return Some(RangeMap::new()),
+            }
+        }
+        Some(map)
+    }
+
+    /// Records `range` of the partial download as present.
+    ///
+    /// Overlapping and adjacent ranges are merged. The range file is
+    /// replaced atomically, so a crash leaves either the old or the new
+    /// set of ranges.
+    pub fn mark_range(&self, remote_id: &RemoteId, range: Range<u64>) -> Result<(), FuseError> {
+        let mut map = self.present_ranges(remote_id).unwrap_or_default();
+        map.insert(range);
+
+        let path = self.ranges_path(remote_id);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let contents: String = map
+            .ranges()
+            .iter()
+            .map(|r| format!("{}-{}\n", r.start, r.end))
+            .collect();
+        let swap = Self::sibling(&path, SWAP_SUFFIX);
+        fs::write(&swap, contents)?;
+        fs::rename(&swap, &path)?;
+        Ok(())
+    }
+
+    /// Forgets the recorded ranges, e.g. once a download completed or
+    /// starts over.
+    pub fn clear_ranges(&self, remote_id: &RemoteId) -> Result<(), FuseError> {
+        match fs::remove_file(self.ranges_path(remote_id)) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e.into()),
+            _ => Ok(()),
+        }
+    }
+
+    /// Returns true if `len` bytes at `offset` are available locally.
+    ///
+    /// A complete cache file has every byte; otherwise the ranges recorded
+    /// for the partial download decide.
+    pub fn has_range(&self, remote_id: &RemoteId, offset: u64, len: u64) -> bool {
+        if self.exists(remote_id) {
+            return true;
+        }
+        self.present_ranges(remote_id)
+            .is_some_and(|map| map.contains(offset..offset.saturating_add(len)))
+    }
+
+    /// Reads as much of a range as is present, reporting the gaps.
+    ///
+    /// Complete cache files are read like [`read`](Self::read). For a
+    /// partial download, the data runs from `offset` up to the first
+    /// missing byte, and `gaps` lists every missing range within the
+    /// request (clipped to the partial file's size). Without any content,
+    /// the whole request is one gap.
+    pub fn read_present(
+        &self,
+        remote_id: &RemoteId,
+        offset: u64,
+        size: u32,
+    ) -> Result<PresentRead, FuseError> {
+        if self.exists(remote_id) {
+            return Ok(PresentRead {
+                data: self.read(remote_id, offset, size)?,
+                gaps: Vec::new(),
+            });
+        }
+
+        let partial = self.partial_path(remote_id);
+        let end = match fs::metadata(&partial) {
+            Ok(metadata) => (offset + u64::from(size)).min(metadata.len()),
+            Err(_) => offset + u64::from(size),
+        };
+        let map = self.present_ranges(remote_id).unwrap_or_default();
+        let mut gaps = Vec::new();
+        let mut from = offset;
+        while let Some(gap) = map.next_gap(from, end) {
+            from = gap.end;
+            gaps.push(gap);
+        }
+
+        let present_end = gaps.first().map_or(end, |gap| gap.start);
+        let data = if present_end > offset {
+            read_at(&partial, offset, (present_end - offset) as u32)?
+        } else {
+            Vec::new()
+        };
+        Ok(PresentRead { data, gaps })
     }
 
     /// Check if content exists in cache.
@@ -80,13 +405,14 @@ impl ContentCache {
     pub fn remove(&self, remote_id: &RemoteId) -> Result<(), FuseError> {
         let path = self.cache_path(remote_id);
         if path.exists() {
-            fs::remove_file(&path)?;
+            self.unlink(&path)?;
         }
         // Also try to remove partial file if it exists
         let partial = self.partial_path(remote_id);
         if partial.exists() {
             let _ = fs::remove_file(&partial);
         }
+        let _ = self.clear_ranges(remote_id);
         Ok(())
     }
 
@@ -115,6 +441,11 @@ impl ContentCache {
             fs::create_dir_all(parent)?;
         }
 
+        // Give this item a private copy before modifying shared content
+        if self.is_dedup_enabled() && path.exists() {
+            self.break_link(&path)?;
+        }
+
         // Open file with read/write, create if doesn't exist
         let mut file = fs::OpenOptions::new()
             .read(true)
@@ -132,25 +463,309 @@ impl ContentCache {
         Ok(data.len() as u32)
     }
 
+    /// Truncate (or extend with zeros) the cached content to `size` bytes.
+    ///
+    /// Creates the file if nothing is cached yet, so a file overwritten
+    /// without being downloaded has complete, empty content.
+    pub fn truncate(&self, remote_id: &RemoteId, size: u64) -> Result<(), FuseError> {
+        let path = self.cache_path(remote_id);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        // Give this item a private copy before modifying shared content
+        if self.is_dedup_enabled() && path.exists() {
+            self.break_link(&path)?;
+        }
+        let file = fs::OpenOptions::new()
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(&path)?;
+        file.set_len(size)?;
+        Ok(())
+    }
+
+    /// Check that the cached content of `item` has the expected size.
+    ///
+    /// Returns `false` when the file is missing or was truncated/extended
+    /// (e.g. by a crash or ENOSPC). Items without a remote ID have nothing
+    /// cached and are always considered valid.
+    pub fn verify(&self, item: &SyncItem) -> bool {
+        let Some(remote_id) = item.remote_id() else {
+            return true;
+        };
+        fs::metadata(self.cache_path(remote_id))
+            .is_ok_and(|metadata| metadata.len() == item.size_bytes())
+    }
+
+    /// Like [`verify`](Self::verify), but also compares the quickXorHash of
+    /// the cached content with the item's content hash when one is known.
+    ///
+    /// Reads the whole file, so this is considerably more expensive.
+    pub fn verify_content(&self, item: &SyncItem) -> bool {
+        if !self.verify(item) {
+            return false;
+        }
+        match (item.remote_id(), item.content_hash()) {
+            (Some(remote_id), Some(expected)) => quick_xor_file(&self.cache_path(remote_id))
+                .is_ok_and(|actual| actual == expected.as_str()),
+            _ => true,
+        }
+    }
+
     /// Calculate total disk usage of the cache.
+    ///
+    /// Files hardlinked to the same blob are counted once.
     pub fn disk_usage(&self) -> Result<u64, FuseError> {
         let mut total = 0u64;
+        let mut seen = HashSet::new();
         if self.content_dir.exists() {
-            for entry in fs::read_dir(&self.content_dir)? {
-                let entry = entry?;
-                if entry.file_type()?.is_dir() {
-                    for file in fs::read_dir(entry.path())? {
-                        let file = file?;
-                        if file.file_type()?.is_file() {
-                            total += file.metadata()?.len();
-                        }
-                    }
+            for file in Self::content_files(&self.content_dir)? {
+                let metadata = fs::metadata(&file)?;
+                if metadata.nlink() == 1 || seen.insert((metadata.dev(), metadata.ino())) {
+                    total += metadata.len();
                 }
             }
         }
         Ok(total)
     }
 
+    // ------------------------------------------------------------------------
+    // Deduplication
+    // ------------------------------------------------------------------------
+
+    /// Share the cached content of `remote_id` with other items whose content
+    /// has the same quickXorHash.
+    ///
+    /// If a blob with this hash already exists, the cache file is replaced by
+    /// a link to it and `true` is returned (space was reclaimed). Otherwise
+    /// the file becomes the blob for future duplicates. Does nothing when
+    /// deduplication is disabled or the content is not cached.
+    pub fn deduplicate(
+        &self,
+        remote_id: &RemoteId,
+        content_hash: &FileHash,
+    ) -> Result<bool, FuseError> {
+        let Some(ref blobs_dir) = self.blobs_dir else {
+            return Ok(false);
+        };
+        let path = self.cache_path(remote_id);
+        let Ok(metadata) = fs::metadata(&path) else {
+            return Ok(false);
+        };
+        let blob = self.blob_path(blobs_dir, content_hash);
+
+        match fs::metadata(&blob) {
+            Ok(blob_meta) if blob_meta.ino() == metadata.ino() => Ok(false),
+            Ok(blob_meta) if blob_meta.len() != metadata.len() => {
+                tracing::warn!(
+                    remote_id = %remote_id,
+                    hash = %content_hash,
+                    "Blob size differs from cached file, not deduplicating"
+                );
+                Ok(false)
+            }
+            Ok(_) => {
+                let swap = Self::sibling(&path, SWAP_SUFFIX);
+                fs::hard_link(&blob, &swap)?;
+                if let Err(e) = self.unlink(&path) {
+                    let _ = fs::remove_file(&swap);
+                    return Err(e);
+                }
+                fs::rename(&swap, &path)?;
+                Ok(true)
+            }
+            Err(_) => {
+                if let Some(parent) = blob.parent() {
+                    fs::create_dir_all(parent)?;
+                }
+                fs::hard_link(&path, &blob)?;
+                let index = Self::index_path(blobs_dir, metadata.ino());
+                let _ = fs::remove_file(&index);
+                std::os::unix::fs::symlink(&blob, &index)?;
+                Ok(false)
+            }
+        }
+    }
+
+    /// Remove a cache file, dropping its blob when this was the last item
+    /// referencing it.
+    fn unlink(&self, path: &Path) -> Result<(), FuseError> {
+        let metadata = fs::metadata(path)?;
+        fs::remove_file(path)?;
+
+        if let Some(ref blobs_dir) = self.blobs_dir {
+            // Two links left before removal: this file and the blob itself
+            if metadata.nlink() == 2 {
+                let index = Self::index_path(blobs_dir, metadata.ino());
+                if let Ok(blob) = fs::read_link(&index) {
+                    let _ = fs::remove_file(&blob);
+                    let _ = fs::remove_file(&index);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Replace a shared cache file by a private copy of its content.
+    fn break_link(&self, path: &Path) -> Result<(), FuseError> {
+        if fs::metadata(path)?.nlink() <= 1 {
+            return Ok(());
+        }
+        let tmp = self.temp_path(path)?;
+        fs::copy(path, &tmp)?;
+        self.unlink(path)?;
+        self.install(&tmp, path)
+    }
+
+    /// A new temporary file name for content that will replace `path`.
+    ///
+    /// Recreates the temporary directory if it was removed (e.g. by a
+    /// cleaner of temporary files) since the cache was opened.
+    fn temp_path(&self, path: &Path) -> std::io::Result<PathBuf> {
+        fs::create_dir_all(&self.temp_dir)?;
+        let name = path.file_name().unwrap_or_default().to_string_lossy();
+        let n = TEMP_COUNTER.fetch_add(1, Ordering::Relaxed);
+        Ok(self.temp_dir.join(format!(
+            "{}-{}-{}{}",
+            name,
+            std::process::id(),
+            n,
+            SWAP_SUFFIX
+        )))
+    }
+
+    /// Move the temporary file `tmp` to `path`.
+    ///
+    /// If the temporary directory is on another filesystem, `tmp` is
+    /// copied next to `path`, synced and renamed over it instead.
+    fn install(&self, tmp: &Path, path: &Path) -> Result<(), FuseError> {
+        match fs::rename(tmp, path) {
+            Ok(()) => Ok(()),
+            Err(e) if e.raw_os_error() == Some(libc::EXDEV) => {
+                let swap = Self::sibling(path, SWAP_SUFFIX);
+                let copied = fs::copy(tmp, &swap)
+                    .and_then(|_| File::open(&swap)?.sync_all())
+                    .and_then(|()| fs::rename(&swap, path));
+                let _ = fs::remove_file(tmp);
+                if copied.is_err() {
+                    let _ = fs::remove_file(&swap);
+                }
+                Ok(copied?)
+            }
+            Err(e) => {
+                let _ = fs::remove_file(tmp);
+                Err(e.into())
+            }
+        }
+    }
+
+    /// Sharded blob path for a content hash.
+    fn blob_path(&self, blobs_dir: &Path, content_hash: &FileHash) -> PathBuf {
+        // quickXorHash is Base64 and may contain '/', so key blobs by its SHA-256
+        let mut hasher = Sha256::new();
+        hasher.update(content_hash.as_str().as_bytes());
+        self.sharded(blobs_dir, &format!("{:x}", hasher.finalize()))
+    }
+
+    /// Index entry mapping a cache file's inode to its blob.
+    fn index_path(blobs_dir: &Path, ino: u64) -> PathBuf {
+        blobs_dir.join("by-inode").join(ino.to_string())
+    }
+
+    /// `path` with `suffix` appended to its file name.
+    fn sibling(path: &Path, suffix: &str) -> PathBuf {
+        let mut sibling = path.as_os_str().to_owned();
+        sibling.push(suffix);
+        PathBuf::from(sibling)
+    }
+
+    /// Move files written under a different shard depth to their current
+    /// location and record the layout in the marker file.
+    ///
+    /// A file's full hash is the concatenation of its path components below
+    /// the content directory, so it can be re-sharded without knowing the
+    /// remote ID it belongs to.
+    fn migrate_layout(&self) -> std::io::Result<()> {
+        let marker = self.content_dir.join(LAYOUT_MARKER);
+        let expected = self.shard_depth.to_string();
+        if fs::read_to_string(&marker).is_ok_and(|depth| depth.trim() == expected) {
+            return Ok(());
+        }
+
+        let mut moved = 0usize;
+        for file in Self::content_files(&self.content_dir)? {
+            let Ok(relative) = file.strip_prefix(&self.content_dir) else {
+                continue;
+            };
+            let joined: String = relative
+                .components()
+                .map(|c| c.as_os_str().to_string_lossy())
+                .collect();
+            let (hash, suffix) = [PARTIAL_SUFFIX, RANGES_SUFFIX]
+                .into_iter()
+                .find_map(|suffix| Some((joined.strip_suffix(suffix)?, suffix)))
+                .unwrap_or((joined.as_str(), ""));
+            if hash.len() != 64 || !hash.bytes().all(|b| b.is_ascii_hexdigit()) {
+                continue;
+            }
+
+            let mut target = self.path_for_hash(hash).into_os_string();
+            target.push(suffix);
+            let target = PathBuf::from(target);
+            if target != file {
+                if let Some(parent) = target.parent() {
+                    fs::create_dir_all(parent)?;
+                }
+                fs::rename(&file, &target)?;
+                moved += 1;
+            }
+        }
+        Self::remove_empty_dirs(&self.content_dir)?;
+
+        if moved > 0 {
+            tracing::info!(
+                moved,
+                shard_depth = self.shard_depth,
+                "Migrated content cache to new shard layout"
+            );
+        }
+        fs::write(&marker, expected)
+    }
+
+    /// Recursively list every cached file (excluding the layout marker).
+    fn content_files(dir: &Path) -> std::io::Result<Vec<PathBuf>> {
+        let mut files = Vec::new();
+        let mut pending = vec![dir.to_path_buf()];
+        while let Some(current) = pending.pop() {
+            for entry in fs::read_dir(&current)? {
+                let entry = entry?;
+                let file_type = entry.file_type()?;
+                if file_type.is_dir() {
+                    pending.push(entry.path());
+                } else if file_type.is_file() && entry.file_name() != LAYOUT_MARKER {
+                    files.push(entry.path());
+                }
+            }
+        }
+        Ok(files)
+    }
+
+    /// Remove empty shard directories below `dir` (but not `dir` itself).
+    fn remove_empty_dirs(dir: &Path) -> std::io::Result<()> {
+        for entry in fs::read_dir(dir)? {
+            let entry = entry?;
+            if entry.file_type()?.is_dir() {
+                let path = entry.path();
+                Self::remove_empty_dirs(&path)?;
+                if fs::read_dir(&path)?.next().is_none() {
+                    fs::remove_dir(&path)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
     fn hash_remote_id(remote_id: &RemoteId) -> String {
         let mut hasher = Sha256::new();
         hasher.update(remote_id.as_str().as_bytes());
@@ -158,8 +773,50 @@ impl ContentCache {
     }
 }
 
+/// Reads up to `size` bytes of a file starting at `offset`.
+fn read_at(path: &Path, offset: u64, size: u32) -> std::io::Result<Vec<u8>> {
+    let mut file = File::open(path)?;
+    file.seek(SeekFrom::Start(offset))?;
+    let mut buffer = vec![0u8; size as usize];
+    let bytes_read = file.read(&mut buffer)?;
+    buffer.truncate(bytes_read);
+    Ok(buffer)
+}
+
+/// Computes the Base64 quickXorHash of a file on disk.
+pub(crate) fn quick_xor_file(path: &Path) -> Result<String, FuseError> {
+    let mut hasher = QuickXorHash::new();
+    hash_file_prefix(path, u64::MAX, &mut hasher)?;
+    let hash = hasher
+        .finalize_hash()
+        .map_err(|e| FuseError::HydrationFailed(e.to_string()))?;
+    Ok(hash.as_str().to_string())
+}
+
+/// Feeds up to the first `len` bytes of a file on disk into `hasher`.
+///
+/// Used to catch up a streaming hash with data that was already on disk,
+/// such as the chunks kept from an interrupted download.
+pub(crate) fn hash_file_prefix(
+    path: &Path,
+    len: u64,
+    hasher: &mut QuickXorHash,
+) -> Result<(), FuseError> {
+    let mut file = File::open(path)?.take(len);
+    let mut buf = vec![0u8; HASH_BUFFER_SIZE];
+    loop {
+        let n = file.read(&mut buf)?;
+        if n == 0 {
+            break;
+        }
+        hasher.update(&buf[..n]);
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
+    use lnxdrive_core::domain::newtypes::{RemotePath, SyncPath};
     use tempfile::tempdir;
 
     use super::*;
@@ -181,9 +838,15 @@ mod tests {
             format!("{:x}", hasher.finalize())
         };
 
-        // Verify 2-char prefix directory structure
+        // Verify two levels of 2-char prefix directories
         let (prefix, rest) = expected_hash.split_at(2);
-        let expected_path = temp_dir.path().join("content").join(prefix).join(rest);
+        let (second, rest) = rest.split_at(2);
+        let expected_path = temp_dir
+            .path()
+            .join("content")
+            .join(prefix)
+            .join(second)
+            .join(rest);
 
         assert_eq!(cache_path, expected_path);
         assert!(cache_path.to_string_lossy().contains(&prefix.to_string()));
@@ -218,6 +881,29 @@ mod tests {
         assert_eq!(partial_data, &test_data[7..16]);
     }
 
+    #[test]
+    fn test_read_in_progress_falls_back_to_partial() {
+        let temp_dir = tempdir().expect("Failed to create temp dir");
+        let cache = ContentCache::new(temp_dir.path().to_path_buf()).unwrap();
+        let remote_id = RemoteId::new("streaming-id".to_string()).unwrap();
+
+        let partial = cache.partial_path(&remote_id);
+        fs::create_dir_all(partial.parent().unwrap()).unwrap();
+        fs::write(&partial, b"partial bytes").unwrap();
+        assert!(cache.read(&remote_id, 0, 7).is_err());
+        assert_eq!(
+            cache.read_in_progress(&remote_id, 0, 7).unwrap(),
+            b"partial"
+        );
+
+        // Once complete, the cache file wins
+        cache.store(&remote_id, b"complete bytes").unwrap();
+        assert_eq!(
+            cache.read_in_progress(&remote_id, 0, 8).unwrap(),
+            b"complete"
+        );
+    }
+
     #[test]
     fn test_exists_returns_correct_bool() {
         let temp_dir = tempdir().expect("Failed to create temp dir");
@@ -457,6 +1143,23 @@ mod tests {
         assert_eq!(read_data, b"Hello, Rust!!");
     }
 
+    #[test]
+    fn test_truncate_shrinks_or_creates_content() {
+        let temp_dir = tempdir().expect("Failed to create temp dir");
+        let cache = ContentCache::new(temp_dir.path().to_path_buf())
+            .expect("Failed to create ContentCache");
+
+        let remote_id = RemoteId::new("truncate-test".to_string()).unwrap();
+        cache.store(&remote_id, b"Hello, World!").unwrap();
+        cache.truncate(&remote_id, 5).unwrap();
+        assert_eq!(cache.read(&remote_id, 0, 100).unwrap(), b"Hello");
+
+        let missing = RemoteId::new("truncate-missing".to_string()).unwrap();
+        cache.truncate(&missing, 0).unwrap();
+        assert!(cache.exists(&missing));
+        assert!(cache.read(&missing, 0, 100).unwrap().is_empty());
+    }
+
     #[test]
     fn test_write_at_extends_file() {
         let temp_dir = tempdir().expect("Failed to create temp dir");
@@ -488,4 +1191,357 @@ mod tests {
         let read_data = cache.read(&remote_id, 0, 100).expect("Failed to read");
         assert_eq!(read_data, b"Hello, World!");
     }
+
+    #[test]
+    fn test_sharded_paths_roundtrip_for_each_depth() {
+        for depth in 1..=MAX_SHARD_DEPTH {
+            let temp_dir = tempdir().expect("Failed to create temp dir");
+            let cache = ContentCache::with_shard_depth(temp_dir.path().to_path_buf(), depth)
+                .expect("Failed to create ContentCache");
+
+            let remote_id =
+                RemoteId::new("sharded-test-id".to_string()).expect("Failed to create RemoteId");
+            let path = cache.store(&remote_id, b"sharded content").unwrap();
+
+            let relative = path.strip_prefix(temp_dir.path().join("content")).unwrap();
+            assert_eq!(relative.components().count(), depth as usize + 1);
+            assert_eq!(
+                cache.read(&remote_id, 0, 100).unwrap(),
+                b"sharded content".to_vec()
+            );
+            assert_eq!(cache.disk_usage().unwrap(), 15);
+        }
+    }
+
+    #[test]
+    fn test_reopening_with_new_depth_migrates_existing_files() {
+        let temp_dir = tempdir().expect("Failed to create temp dir");
+        let stored = RemoteId::new("migrate-stored".to_string()).unwrap();
+        let partial = RemoteId::new("migrate-partial".to_string()).unwrap();
+
+        let old_partial_path = {
+            let cache = ContentCache::with_shard_depth(temp_dir.path().to_path_buf(), 1).unwrap();
+            cache.store(&stored, b"old layout").unwrap();
+            let partial_path = cache.partial_path(&partial);
+            fs::create_dir_all(partial_path.parent().unwrap()).unwrap();
+            fs::write(&partial_path, b"half").unwrap();
+            partial_path
+        };
+
+        let cache = ContentCache::with_shard_depth(temp_dir.path().to_path_buf(), 2).unwrap();
+
+        assert!(cache.exists(&stored));
+        assert_eq!(cache.read(&stored, 0, 100).unwrap(), b"old layout".to_vec());
+        assert!(cache.partial_path(&partial).exists());
+        assert!(!old_partial_path.exists());
+        assert_eq!(cache.disk_usage().unwrap(), 14);
+        assert_eq!(
+            fs::read_to_string(temp_dir.path().join("content").join(LAYOUT_MARKER)).unwrap(),
+            "2"
+        );
+    }
+
+    #[test]
+    fn test_migration_keeps_range_files() {
+        let temp_dir = tempdir().expect("Failed to create temp dir");
+        let remote_id = RemoteId::new("migrate-ranges".to_string()).unwrap();
+        {
+            let cache = ContentCache::with_shard_depth(temp_dir.path().to_path_buf(), 1).unwrap();
+            cache.mark_range(&remote_id, 0..8).unwrap();
+        }
+
+        let cache = ContentCache::with_shard_depth(temp_dir.path().to_path_buf(), 2).unwrap();
+        let map = cache.present_ranges(&remote_id).unwrap();
+        assert_eq!(map.ranges(), std::slice::from_ref(&(0..8)));
+    }
+
+    fn partial_cache(temp_dir: &tempfile::TempDir, remote_id: &RemoteId) -> ContentCache {
+        let cache = ContentCache::new(temp_dir.path().to_path_buf()).unwrap();
+        let partial = cache.partial_path(remote_id);
+        fs::create_dir_all(partial.parent().unwrap()).unwrap();
+        let content: Vec<u8> = (0..100).collect();
+        fs::write(&partial, content).unwrap();
+        cache
+    }
+
+    #[test]
+    fn test_mark_range_merges_overlapping_and_adjacent_ranges() {
+        let temp_dir = tempdir().expect("Failed to create temp dir");
+        let remote_id = RemoteId::new("ranges-merge".to_string()).unwrap();
+        let cache = partial_cache(&temp_dir, &remote_id);
+        assert!(cache.present_ranges(&remote_id).is_none());
+
+        cache.mark_range(&remote_id, 0..10).unwrap();
+        cache.mark_range(&remote_id, 30..40).unwrap();
+        // Adjacent to the first, overlapping the second
+        cache.mark_range(&remote_id, 10..20).unwrap();
+        cache.mark_range(&remote_id, 35..50).unwrap();
+
+        let map = cache.present_ranges(&remote_id).unwrap();
+        let spans: Vec<(u64, u64)> = map.ranges().iter().map(|r| (r.start, r.end)).collect();
+        assert_eq!(spans, [(0, 20), (30, 50)]);
+
+        assert!(cache.has_range(&remote_id, 5, 15));
+        assert!(cache.has_range(&remote_id, 30, 20));
+        assert!(!cache.has_range(&remote_id, 15, 10));
+        assert!(!cache.has_range(&remote_id, 45, 10));
+
+        // Bridging the gap leaves a single range
+        cache.mark_range(&remote_id, 20..30).unwrap();
+        assert!(cache.has_range(&remote_id, 0, 50));
+    }
+
+    #[test]
+    fn test_read_present_reports_gaps() {
+        let temp_dir = tempdir().expect("Failed to create temp dir");
+        let remote_id = RemoteId::new("ranges-read".to_string()).unwrap();
+        let cache = partial_cache(&temp_dir, &remote_id);
+        cache.mark_range(&remote_id, 0..20).unwrap();
+        cache.mark_range(&remote_id, 40..60).unwrap();
+
+        let read = cache.read_present(&remote_id, 10, 20).unwrap();
+        assert_eq!(read.data, (10..20).collect::<Vec<u8>>());
+        assert_eq!(read.gaps, vec![20..30]);
+        assert!(!read.is_complete());
+
+        let read = cache.read_present(&remote_id, 0, 200).unwrap();
+        assert_eq!(read.data.len(), 20);
+        assert_eq!(read.gaps, vec![20..40, 60..100]);
+
+        let read = cache.read_present(&remote_id, 45, 10).unwrap();
+        assert!(read.is_complete());
+        assert_eq!(read.data, (45..55).collect::<Vec<u8>>());
+
+        // Nothing recorded for another item: everything is missing
+        let other = RemoteId::new("ranges-none".to_string()).unwrap();
+        let read = cache.read_present(&other, 0, 10).unwrap();
+        assert!(read.data.is_empty());
+        assert_eq!(read.gaps, vec![0..10]);
+        assert!(!cache.has_range(&other, 0, 1));
+    }
+
+    #[test]
+    fn test_complete_file_has_every_range() {
+        let temp_dir = tempdir().expect("Failed to create temp dir");
+        let remote_id = RemoteId::new("ranges-complete".to_string()).unwrap();
+        let cache = partial_cache(&temp_dir, &remote_id);
+        cache.mark_range(&remote_id, 0..10).unwrap();
+        cache.store(&remote_id, b"complete").unwrap();
+
+        assert!(cache.has_range(&remote_id, 0, 8));
+        assert!(cache.read_present(&remote_id, 0, 8).unwrap().is_complete());
+
+        cache.remove(&remote_id).unwrap();
+        assert!(cache.present_ranges(&remote_id).is_none());
+    }
+
+    #[test]
+    fn test_store_goes_through_temp_dir() {
+        let temp_dir = tempdir().expect("Failed to create temp dir");
+        let cache = ContentCache::new(temp_dir.path().to_path_buf()).unwrap();
+        assert_eq!(cache.temp_dir(), temp_dir.path().join("tmp"));
+        let remote_id = RemoteId::new("temp-store".to_string()).unwrap();
+
+        cache.store(&remote_id, b"first").unwrap();
+        cache.store(&remote_id, b"second").unwrap();
+
+        assert_eq!(cache.read(&remote_id, 0, 10).unwrap(), b"second".to_vec());
+        assert_eq!(fs::read_dir(cache.temp_dir()).unwrap().count(), 0);
+    }
+
+    #[test]
+    fn test_temp_dir_on_other_filesystem() {
+        let temp_dir = tempdir().expect("Failed to create temp dir");
+        // /dev/shm is usually a tmpfs, i.e. another filesystem than /tmp
+        let staging = tempfile::TempDir::new_in("/dev/shm").unwrap_or_else(|_| tempdir().unwrap());
+        let cache = dedup_cache(&temp_dir)
+            .with_temp_dir(staging.path().join("lnxdrive"))
+            .unwrap();
+        let first = RemoteId::new("temp-other-first".to_string()).unwrap();
+        let second = RemoteId::new("temp-other-second".to_string()).unwrap();
+
+        cache.store(&first, b"same").unwrap();
+        cache.store(&second, b"same").unwrap();
+        cache.deduplicate(&first, &test_hash()).unwrap();
+        cache.deduplicate(&second, &test_hash()).unwrap();
+        cache.write_at(&first, 0, b"diff").unwrap();
+
+        assert_eq!(cache.read(&first, 0, 10).unwrap(), b"diff".to_vec());
+        assert_eq!(cache.read(&second, 0, 10).unwrap(), b"same".to_vec());
+        assert_eq!(fs::read_dir(cache.temp_dir()).unwrap().count(), 0);
+        assert!(!ContentCache::sibling(&cache.cache_path(&first), SWAP_SUFFIX).exists());
+    }
+
+    fn dedup_cache(temp_dir: &tempfile::TempDir) -> ContentCache {
+        ContentCache::new(temp_dir.path().to_path_buf())
+            .unwrap()
+            .with_dedup(true)
+            .unwrap()
+    }
+
+    fn test_hash() -> FileHash {
+        FileHash::new("AAAAAAAAAAAAAAAAAAAAAAAAAAA=".to_string()).unwrap()
+    }
+
+    #[test]
+    fn test_dedup_shares_identical_content() {
+        let temp_dir = tempdir().expect("Failed to create temp dir");
+        let cache = dedup_cache(&temp_dir);
+        let first = RemoteId::new("dedup-first".to_string()).unwrap();
+        let second = RemoteId::new("dedup-second".to_string()).unwrap();
+        let data = b"identical content in two places";
+
+        cache.store(&first, data).unwrap();
+        cache.store(&second, data).unwrap();
+        assert_eq!(cache.disk_usage().unwrap(), 2 * data.len() as u64);
+
+        assert!(!cache.deduplicate(&first, &test_hash()).unwrap());
+        assert!(cache.deduplicate(&second, &test_hash()).unwrap());
+
+        let first_ino = fs::metadata(cache.cache_path(&first)).unwrap().ino();
+        let second_ino = fs::metadata(cache.cache_path(&second)).unwrap().ino();
+        assert_eq!(first_ino, second_ino);
+        assert_eq!(cache.disk_usage().unwrap(), data.len() as u64);
+    }
+
+    #[test]
+    fn test_dedup_removing_one_item_keeps_other_readable() {
+        let temp_dir = tempdir().expect("Failed to create temp dir");
+        let cache = dedup_cache(&temp_dir);
+        let first = RemoteId::new("dedup-keep-first".to_string()).unwrap();
+        let second = RemoteId::new("dedup-keep-second".to_string()).unwrap();
+        let data = b"shared bytes";
+
+        cache.store(&first, data).unwrap();
+        cache.store(&second, data).unwrap();
+        cache.deduplicate(&first, &test_hash()).unwrap();
+        cache.deduplicate(&second, &test_hash()).unwrap();
+        let blob = cache.blob_path(cache.blobs_dir.as_ref().unwrap(), &test_hash());
+
+        cache.remove(&first).unwrap();
+        assert!(blob.exists());
+        assert_eq!(cache.read(&second, 0, 100).unwrap(), data.to_vec());
+
+        cache.remove(&second).unwrap();
+        assert!(!blob.exists());
+    }
+
+    #[test]
+    fn test_dedup_write_does_not_leak_into_other_item() {
+        let temp_dir = tempdir().expect("Failed to create temp dir");
+        let cache = dedup_cache(&temp_dir);
+        let first = RemoteId::new("dedup-write-first".to_string()).unwrap();
+        let second = RemoteId::new("dedup-write-second".to_string()).unwrap();
+
+        cache.store(&first, b"same").unwrap();
+        cache.store(&second, b"same").unwrap();
+        cache.deduplicate(&first, &test_hash()).unwrap();
+        cache.deduplicate(&second, &test_hash()).unwrap();
+
+        cache.write_at(&first, 0, b"diff").unwrap();
+
+        assert_eq!(cache.read(&first, 0, 10).unwrap(), b"diff".to_vec());
+        assert_eq!(cache.read(&second, 0, 10).unwrap(), b"same".to_vec());
+    }
+
+    #[test]
+    fn test_deduplicate_is_noop_when_disabled() {
+        let temp_dir = tempdir().expect("Failed to create temp dir");
+        let cache = ContentCache::new(temp_dir.path().to_path_buf()).unwrap();
+        let remote_id = RemoteId::new("dedup-disabled".to_string()).unwrap();
+        cache.store(&remote_id, b"data").unwrap();
+
+        assert!(!cache.deduplicate(&remote_id, &test_hash()).unwrap());
+        assert!(!temp_dir.path().join("blobs").exists());
+    }
+
+    #[test]
+    fn test_quick_xor_file_matches_in_memory_hash() {
+        let temp_dir = tempdir().expect("Failed to create temp dir");
+        let path = temp_dir.path().join("data.bin");
+        let data: Vec<u8> = (0..=255u8)
+            .cycle()
+            .take(3 * HASH_BUFFER_SIZE + 17)
+            .collect();
+        fs::write(&path, &data).unwrap();
+
+        let mut hasher = QuickXorHash::new();
+        hasher.update(&data);
+        let expected = hasher.finalize_hash().unwrap();
+
+        assert_eq!(quick_xor_file(&path).unwrap(), expected.as_str());
+    }
+
+    #[test]
+    fn test_hash_file_prefix_resumes_streaming_hash() {
+        let temp_dir = tempdir().expect("Failed to create temp dir");
+        let path = temp_dir.path().join("partial.bin");
+        let data: Vec<u8> = (0..=255u8).cycle().take(HASH_BUFFER_SIZE + 300).collect();
+        fs::write(&path, &data).unwrap();
+
+        let mut whole = QuickXorHash::new();
+        whole.update(&data);
+
+        // Prefix from disk, remainder as if streamed from the network
+        let split = HASH_BUFFER_SIZE + 1;
+        let mut resumed = QuickXorHash::new();
+        hash_file_prefix(&path, split as u64, &mut resumed).unwrap();
+        resumed.update(&data[split..]);
+
+        assert_eq!(resumed.finalize(), whole.finalize());
+    }
+
+    fn item_with_content(remote_id: &str, data: &[u8]) -> SyncItem {
+        let mut hasher = QuickXorHash::new();
+        hasher.update(data);
+        SyncItem::from_remote(
+            SyncPath::new(format!("/home/user/OneDrive/{}", remote_id).into()).unwrap(),
+            RemotePath::new(format!("/{}", remote_id)).unwrap(),
+            RemoteId::new(remote_id.to_string()).unwrap(),
+            false,
+            data.len() as u64,
+            Some(hasher.finalize_hash().unwrap()),
+            chrono::Utc::now(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_verify_detects_truncated_file() {
+        let temp_dir = tempdir().expect("Failed to create temp dir");
+        let cache = ContentCache::new(temp_dir.path().to_path_buf()).unwrap();
+        let item = item_with_content("verify-size", b"complete content");
+        let remote_id = item.remote_id().unwrap();
+
+        assert!(!cache.verify(&item));
+        cache.store(remote_id, b"complete").unwrap();
+        assert!(!cache.verify(&item));
+        cache.store(remote_id, b"complete content").unwrap();
+        assert!(cache.verify(&item));
+    }
+
+    #[test]
+    fn test_verify_content_detects_same_size_corruption() {
+        let temp_dir = tempdir().expect("Failed to create temp dir");
+        let cache = ContentCache::new(temp_dir.path().to_path_buf()).unwrap();
+        let item = item_with_content("verify-hash", b"hello");
+        let remote_id = item.remote_id().unwrap();
+
+        cache.store(remote_id, b"jello").unwrap();
+        assert!(cache.verify(&item));
+        assert!(!cache.verify_content(&item));
+
+        cache.store(remote_id, b"hello").unwrap();
+        assert!(cache.verify_content(&item));
+    }
+
+    #[test]
+    fn test_cache_stats_counts_hits_and_misses() {
+        let stats = CacheStats::default();
+        stats.record_hit();
+        stats.record_hit();
+        stats.record_miss();
+        assert_eq!(stats.hits(), 2);
+        assert_eq!(stats.misses(), 1);
+    }
 }
@@ -0,0 +1,542 @@
+//! Cache usage, cleaning and verification for a mounted filesystem.
+//!
+//! [`FuseCacheManager`] implements the `ICacheManager` port on top of the
+//! mount's [`DehydrationManager`], so `lnxdrive cache clean` goes through
+//! the same open-handle and state checks as automatic dehydration, on top
+//! of [`CacheScrubber`] for `lnxdrive cache verify`, and on top of the
+//! mount's [`HydrationManager`] for `lnxdrive pin` and `lnxdrive unpin`.
+
+use std::{
+    collections::HashMap,
+    path::{Component, Path, PathBuf},
+    sync::Arc,
+};
+
+use anyhow::{anyhow, bail};
+use lnxdrive_cache::{pool::DatabasePool, SqliteStateRepository};
+use lnxdrive_core::{
+    domain::sync_item::ItemState,
+    ports::{
+        CacheCleanOptions, CacheCleanReport, CachePinReport, CacheUsage, CacheVerifyReport,
+        FolderUsage, ICacheManager, IStateRepository, ItemFilter,
+    },
+};
+
+use crate::{
+    cache::ContentCache,
+    dehydration::DehydrationManager,
+    hydration::HydrationManager,
+    inode::InodeTable,
+    inode_entry::{InodeEntry, InodeNumber},
+    scrub::CacheScrubber,
+    write_serializer::WriteSerializerHandle,
+};
+
+/// Folder name used for files directly in the drive root.
+const ROOT_FOLDER: &str = "/";
+
+/// Cache management operations of a mounted filesystem.
+pub struct FuseCacheManager {
+    /// Dehydration manager of the mount, shared with the periodic sweep.
+    dehydration: Arc<DehydrationManager>,
+    /// Content cache of the mount.
+    cache: Arc<ContentCache>,
+    /// Database pool for querying items.
+    db_pool: DatabasePool,
+    /// Handle for serialized DB writes.
+    write_handle: WriteSerializerHandle,
+    /// Pins files of the mount; unset without a cloud provider.
+    pinning: Option<Pinning>,
+}
+
+/// The parts of a mount that pinning goes through.
+struct Pinning {
+    /// Hydration manager of the mount, downloading pinned files.
+    hydration: Arc<HydrationManager>,
+    /// Inode table of the mount, to resolve paths.
+    inode_table: Arc<InodeTable>,
+    /// Directory the filesystem is mounted on.
+    mount_point: PathBuf,
+}
+
+impl Pinning {
+    /// Finds the mounted entry at `path`.
+    fn resolve(&self, path: &Path) -> anyhow::Result<Arc<InodeEntry>> {
+        let relative = path.strip_prefix(&self.mount_point).map_err(|_| {
+            anyhow!(
+                "{} is not inside the mounted folder {}",
+                path.display(),
+                self.mount_point.display()
+            )
+        })?;
+        let mut entry = self
+            .inode_table
+            .get(InodeNumber::ROOT.get())
+            .ok_or_else(|| anyhow!("The mounted folder has not been loaded"))?;
+        for component in relative.components() {
+            let Component::Normal(name) = component else {
+                bail!("{} is not a normalized path", path.display());
+            };
+            entry = name
+                .to_str()
+                .and_then(|name| self.inode_table.lookup(entry.ino().get(), name))
+                .ok_or_else(|| anyhow!("{} is not in the mounted folder", path.display()))?;
+        }
+        Ok(entry)
+    }
+}
+
+impl FuseCacheManager {
+    /// Create a cache manager for the given mount components.
+    pub fn new(
+        dehydration: Arc<DehydrationManager>,
+        cache: Arc<ContentCache>,
+        db_pool: DatabasePool,
+        write_handle: WriteSerializerHandle,
+    ) -> Self {
+        Self {
+            dehydration,
+            cache,
+            db_pool,
+            write_handle,
+            pinning: None,
+        }
+    }
+
+    /// Pins files through `hydration`, resolving paths under `mount_point`
+    /// in `inode_table`.
+    pub fn with_pinning(
+        mut self,
+        hydration: Arc<HydrationManager>,
+        inode_table: Arc<InodeTable>,
+        mount_point: PathBuf,
+    ) -> Self {
+        self.pinning = Some(Pinning {
+            hydration,
+            inode_table,
+            mount_point,
+        });
+        self
+    }
+
+    fn pinning(&self) -> anyhow::Result<&Pinning> {
+        self.pinning
+            .as_ref()
+            .ok_or_else(|| anyhow!("Files can't be pinned without a cloud provider"))
+    }
+}
+
+/// Top-level folder of a remote path such as `/Documents/a.txt`.
+fn top_level_folder(remote_path: &str) -> &str {
+    match remote_path.trim_start_matches('/').split_once('/') {
+        Some((folder, _)) => folder,
+        None => ROOT_FOLDER,
+    }
+}
+
+#[async_trait::async_trait]
+impl ICacheManager for FuseCacheManager {
+    async fn usage(&self) -> anyhow::Result<CacheUsage> {
+        let repo = SqliteStateRepository::new(self.db_pool.pool().clone());
+        let mut usage = CacheUsage {
+            total_bytes: self.cache.disk_usage()?,
+            ..Default::default()
+        };
+        let mut folders: HashMap<String, FolderUsage> = HashMap::new();
+
+        for state in [ItemState::Hydrated, ItemState::Pinned, ItemState::Modified] {
+            let items = repo
+                .query_items(&ItemFilter::new().with_state(state.clone()))
+                .await?;
+            for item in items {
+                let Some(remote_id) = item.remote_id() else {
+                    continue;
+                };
+                let Ok(metadata) = std::fs::metadata(self.cache.cache_path(remote_id)) else {
+                    continue;
+                };
+                let bytes = metadata.len();
+                match state {
+                    ItemState::Pinned => usage.pinned_bytes += bytes,
+                    ItemState::Modified => usage.modified_bytes += bytes,
+                    _ => usage.evictable_bytes += bytes,
+                }
+                let folder = top_level_folder(item.remote_path().as_str());
+                let entry = folders
+                    .entry(folder.to_string())
+                    .or_insert_with(|| FolderUsage {
+                        folder: folder.to_string(),
+                        ..Default::default()
+                    });
+                entry.bytes += bytes;
+                entry.files += 1;
+            }
+        }
+
+        usage.folders = folders.into_values().collect();
+        usage
+            .folders
+            .sort_by(|a, b| b.bytes.cmp(&a.bytes).then_with(|| a.folder.cmp(&b.folder)));
+        Ok(usage)
+    }
+
+    async fn clean(&self, options: CacheCleanOptions) -> anyhow::Result<CacheCleanReport> {
+        let report = self
+            .dehydration
+            .clean(options.keep_pinned, options.older_than_days)
+            .await?;
+        Ok(CacheCleanReport {
+            dehydrated: report.dehydrated_count as u64,
+            bytes_freed: report.bytes_freed,
+            skipped: report.skipped_count as u64,
+            errors: report.errors,
+        })
+    }
+
+    async fn verify(&self) -> anyhow::Result<CacheVerifyReport> {
+        let scrubber = CacheScrubber::new(Arc::clone(&self.cache), true);
+        let report = scrubber.run(&self.db_pool, &self.write_handle).await?;
+        Ok(CacheVerifyReport {
+            checked: report.checked as u64,
+            healed: report.healed as u64,
+            errors: report.errors,
+        })
+    }
+
+    async fn pin(&self, path: &Path) -> anyhow::Result<CachePinReport> {
+        let pinning = self.pinning()?;
+        let entry = pinning.resolve(path)?;
+        let ino = entry.ino().get();
+
+        if entry.kind() == fuser::FileType::Directory {
+            let pinned = pinning
+                .hydration
+                .pin_recursive(ino, &pinning.inode_table)
+                .await?;
+            let failed = pinning.hydration.pin_progress().borrow().failed_files;
+            return Ok(CachePinReport {
+                files: pinned.len() as u64,
+                failed: failed as u64,
+            });
+        }
+
+        let remote_id = entry
+            .remote_id()
+            .cloned()
+            .ok_or_else(|| anyhow!("{} has not been uploaded yet", path.display()))?;
+        pinning
+            .hydration
+            .pin(
+                ino,
+                *entry.item_id(),
+                remote_id,
+                entry.size(),
+                entry.state().clone(),
+            )
+            .await?;
+        pinning
+            .inode_table
+            .insert(entry.with_state(ItemState::Pinned));
+        Ok(CachePinReport {
+            files: 1,
+            failed: 0,
+        })
+    }
+
+    async fn unpin(&self, path: &Path) -> anyhow::Result<CachePinReport> {
+        let pinning = self.pinning()?;
+        let entry = pinning.resolve(path)?;
+        let ino = entry.ino().get();
+
+        let unpinned = if entry.kind() == fuser::FileType::Directory {
+            pinning
+                .hydration
+                .unpin_recursive(ino, &pinning.inode_table)
+                .await?
+        } else if matches!(entry.state(), ItemState::Pinned) {
+            pinning
+                .hydration
+                .unpin(ino, *entry.item_id(), ItemState::Pinned)
+                .await?;
+            vec![(ino, ItemState::Hydrated)]
+        } else {
+            Vec::new()
+        };
+
+        for (ino, state) in &unpinned {
+            if let Some(entry) = pinning.inode_table.get(*ino) {
+                pinning.inode_table.insert(entry.with_state(state.clone()));
+            }
+        }
+        Ok(CachePinReport {
+            files: unpinned.len() as u64,
+            failed: 0,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use chrono::{Duration, Utc};
+    use lnxdrive_core::domain::{
+        newtypes::{Email, RemoteId, RemotePath, SyncPath, UniqueId},
+        Account, SyncItem,
+    };
+    use lnxdrive_graph::{client::GraphClient, provider::GraphCloudProvider};
+    use tempfile::TempDir;
+    use tokio::runtime::Handle;
+
+    use super::*;
+    use crate::{dehydration::DehydrationPolicy, write_serializer::WriteSerializer};
+
+    const MOUNT_POINT: &str = "/home/user/OneDrive";
+
+    struct Harness {
+        _cache_dir: TempDir,
+        cache: Arc<ContentCache>,
+        repo: SqliteStateRepository,
+        table: Arc<InodeTable>,
+        manager: FuseCacheManager,
+    }
+
+    impl Harness {
+        async fn new() -> Self {
+            let cache_dir = TempDir::new().unwrap();
+            let cache = Arc::new(ContentCache::new(cache_dir.path().to_path_buf()).unwrap());
+            let pool = DatabasePool::in_memory().await.unwrap();
+            let repo = SqliteStateRepository::new(pool.pool().clone());
+            let account = Account::new(
+                Email::new("user@example.com".to_string()).unwrap(),
+                "Test User",
+                "root",
+                SyncPath::new(PathBuf::from("/home/user/OneDrive")).unwrap(),
+            );
+            repo.save_account(&account).await.unwrap();
+            let (serializer, write_handle) = WriteSerializer::new(pool.clone());
+            tokio::spawn(serializer.run());
+            let dehydration = Arc::new(DehydrationManager::new(
+                DehydrationPolicy::default(),
+                Arc::clone(&cache),
+                Arc::new(InodeTable::new()),
+                write_handle.clone(),
+                pool.clone(),
+            ));
+            // Files in these tests are cached, so nothing is downloaded
+            let provider = Arc::new(GraphCloudProvider::new(GraphClient::with_base_url(
+                "token",
+                "http://127.0.0.1:9",
+            )));
+            let hydration = Arc::new(HydrationManager::new(
+                1,
+                Arc::clone(&cache),
+                write_handle.clone(),
+                provider,
+                Handle::current(),
+            ));
+            let table = Arc::new(InodeTable::new());
+            table.insert(entry(1, 1, "", None));
+            let manager =
+                FuseCacheManager::new(dehydration, Arc::clone(&cache), pool, write_handle)
+                    .with_pinning(hydration, Arc::clone(&table), PathBuf::from(MOUNT_POINT));
+            Self {
+                _cache_dir: cache_dir,
+                cache,
+                repo,
+                table,
+                manager,
+            }
+        }
+
+        /// Adds a cached file at `path`, pinned if requested
+        async fn add_file(&self, path: &str, size: u64, pinned: bool, days_ago: i64) -> SyncItem {
+            let mut item = SyncItem::new_file(
+                SyncPath::new(PathBuf::from(format!("/home/user/OneDrive{}", path))).unwrap(),
+                RemotePath::new(path.to_string()).unwrap(),
+                size,
+                None,
+            )
+            .unwrap();
+            item.set_remote_id(RemoteId::new(path.replace(['/', '.'], "_")).unwrap());
+            item.start_hydrating().unwrap();
+            item.complete_hydration().unwrap();
+            if pinned {
+                item.pin().unwrap();
+            }
+            self.repo.save_item(&item).await.unwrap();
+            self.repo
+                .update_last_accessed(item.id(), Utc::now() - Duration::days(days_ago))
+                .await
+                .unwrap();
+            self.cache
+                .store(item.remote_id().unwrap(), &vec![0u8; size as usize])
+                .unwrap();
+            item
+        }
+
+        async fn state(&self, item: &SyncItem) -> ItemState {
+            self.repo
+                .get_item(item.id())
+                .await
+                .unwrap()
+                .unwrap()
+                .state()
+                .clone()
+        }
+    }
+
+    /// Inode table entry for `item`, or for a directory without one
+    fn entry(ino: u64, parent: u64, name: &str, item: Option<&SyncItem>) -> InodeEntry {
+        let now = std::time::SystemTime::now();
+        InodeEntry::new(
+            InodeNumber::new(ino),
+            item.map_or_else(UniqueId::new, |item| *item.id()),
+            item.and_then(|item| item.remote_id().cloned()),
+            InodeNumber::new(parent),
+            name.to_string(),
+            if item.is_some() {
+                fuser::FileType::RegularFile
+            } else {
+                fuser::FileType::Directory
+            },
+            item.map_or(0, SyncItem::size_bytes),
+            0o644,
+            now,
+            now,
+            now,
+            1,
+            item.map_or(ItemState::Online, |item| item.state().clone()),
+        )
+    }
+
+    #[test]
+    fn test_top_level_folder() {
+        assert_eq!(top_level_folder("/Documents/report.pdf"), "Documents");
+        assert_eq!(top_level_folder("/Photos/2024/a.jpg"), "Photos");
+        assert_eq!(top_level_folder("/notes.txt"), ROOT_FOLDER);
+    }
+
+    #[tokio::test]
+    async fn test_usage_breaks_down_by_folder_and_pin_state() {
+        let harness = Harness::new().await;
+        harness.add_file("/Documents/a.txt", 100, false, 1).await;
+        harness.add_file("/Documents/b.txt", 50, true, 1).await;
+        harness.add_file("/Photos/c.jpg", 300, false, 1).await;
+        harness.add_file("/notes.txt", 10, false, 1).await;
+
+        let usage = harness.manager.usage().await.unwrap();
+
+        assert_eq!(usage.total_bytes, 460);
+        assert_eq!(usage.pinned_bytes, 50);
+        assert_eq!(usage.evictable_bytes, 410);
+        let folders: Vec<(&str, u64, u64)> = usage
+            .folders
+            .iter()
+            .map(|f| (f.folder.as_str(), f.bytes, f.files))
+            .collect();
+        assert_eq!(
+            folders,
+            vec![("Photos", 300, 1), ("Documents", 150, 2), ("/", 10, 1)]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_clean_keep_pinned_and_older_than() {
+        let harness = Harness::new().await;
+        let old = harness.add_file("/old.txt", 100, false, 40).await;
+        let recent = harness.add_file("/recent.txt", 100, false, 1).await;
+        let pinned = harness.add_file("/pinned.txt", 100, true, 40).await;
+
+        let report = harness
+            .manager
+            .clean(CacheCleanOptions {
+                keep_pinned: true,
+                older_than_days: 30,
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(report.dehydrated, 1);
+        assert_eq!(report.bytes_freed, 100);
+        assert_eq!(harness.state(&old).await, ItemState::Online);
+        assert_eq!(harness.state(&recent).await, ItemState::Hydrated);
+        assert_eq!(harness.state(&pinned).await, ItemState::Pinned);
+    }
+
+    #[tokio::test]
+    async fn test_clean_without_keep_pinned_unpins() {
+        let harness = Harness::new().await;
+        let pinned = harness.add_file("/pinned.txt", 100, true, 0).await;
+        let hydrated = harness.add_file("/file.txt", 100, false, 0).await;
+
+        let report = harness
+            .manager
+            .clean(CacheCleanOptions::default())
+            .await
+            .unwrap();
+
+        assert_eq!(report.dehydrated, 2);
+        assert_eq!(harness.state(&pinned).await, ItemState::Online);
+        assert_eq!(harness.state(&hydrated).await, ItemState::Online);
+        assert!(!harness.cache.exists(pinned.remote_id().unwrap()));
+        assert_eq!(harness.manager.usage().await.unwrap().total_bytes, 0);
+    }
+
+    #[tokio::test]
+    async fn test_verify_heals_damaged_file() {
+        let harness = Harness::new().await;
+        let good = harness.add_file("/good.txt", 10, false, 0).await;
+        let damaged = harness.add_file("/damaged.txt", 10, false, 0).await;
+        harness
+            .cache
+            .store(damaged.remote_id().unwrap(), b"short")
+            .unwrap();
+        let healed_before = crate::scrub::healed_total();
+
+        let report = harness.manager.verify().await.unwrap();
+
+        assert_eq!(report.checked, 2);
+        assert_eq!(report.healed, 1);
+        assert!(crate::scrub::healed_total() > healed_before);
+        assert_eq!(harness.state(&good).await, ItemState::Hydrated);
+        assert_eq!(harness.state(&damaged).await, ItemState::Online);
+    }
+
+    #[tokio::test]
+    async fn test_pin_and_unpin_resolve_paths_in_the_mount() {
+        let harness = Harness::new().await;
+        let file = harness.add_file("/Documents/a.txt", 10, false, 0).await;
+        harness.table.insert(entry(2, 1, "Documents", None));
+        harness.table.insert(entry(3, 2, "a.txt", Some(&file)));
+
+        let report = harness
+            .manager
+            .pin(Path::new("/home/user/OneDrive/Documents/a.txt"))
+            .await
+            .unwrap();
+
+        assert_eq!(report.files, 1);
+        assert_eq!(harness.state(&file).await, ItemState::Pinned);
+        assert_eq!(*harness.table.get(3).unwrap().state(), ItemState::Pinned);
+
+        let report = harness
+            .manager
+            .unpin(Path::new("/home/user/OneDrive/Documents"))
+            .await
+            .unwrap();
+
+        assert_eq!(report.files, 1);
+        assert_eq!(harness.state(&file).await, ItemState::Hydrated);
+        assert_eq!(*harness.table.get(3).unwrap().state(), ItemState::Hydrated);
+    }
+
+    #[tokio::test]
+    async fn test_pin_rejects_paths_outside_the_mount() {
+        let harness = Harness::new().await;
+
+        for path in ["/home/user/OneDrive/missing.txt", "/tmp/a.txt"] {
+            assert!(harness.manager.pin(Path::new(path)).await.is_err());
+        }
+    }
+}
@@ -7,10 +7,26 @@
 //!
 //! Files are candidates for dehydration when:
 //! - State is `Hydrated` (not `Pinned`, `Modified`, `Online`, etc.)
-//! - `last_accessed` is older than `max_age_days`
 //! - No open file handles
 //!
-//! Dehydration is triggered when cache disk usage exceeds the threshold.
+//! A file selected by a sweep while open is never dehydrated under its
+//! readers. Depending on [`BusyFileAction`] it is either skipped until a
+//! later sweep selects it again, or dehydrated as soon as its last handle
+//! is released.
+//!
+//! A sweep applies the enabled triggers in priority order:
+//! 1. **Age** (`unused_days`, optional): files not accessed for that many
+//!    days are dehydrated even if the cache has room. This trigger runs at
+//!    most once a day, like Storage Sense.
+//! 2. **Large files** (`large_file_bytes`, optional): while cache usage is
+//!    over the threshold, files of at least that size not accessed within
+//!    `max_age_days` go first, largest first.
+//! 3. **Cache size**: while usage is still over the threshold, files not
+//!    accessed within `max_age_days` go least recently used first.
+//!
+//! Each dehydrated file is listed in the [`DehydrationReport`] with the
+//! [`EvictionReason`] that selected it. Periodic sweeps that free space
+//! send a summary notification when a notifier is set.
 //!
 //! ## Architecture
 //!
@@ -28,19 +44,31 @@
 //!           │ run_sweep() every N minutes
 //!           ▼
 //! ┌─────────────────────┐
-//! │  1. Check usage     │ ─── if < threshold, skip
-//! │  2. Query DB        │ ─── get_items_for_dehydration()
-//! │  3. For each item:  │
+//! │  1. Age trigger     │ ─── if unused_days > 0
+//! │  2. Check usage     │ ─── if < threshold, stop
+//! │  3. Query DB        │ ─── large files first, then LRU
+//! │  4. For each item:  │
 //! │     - Check handles │ ─── skip if open
 //! │     - Remove cache  │ ─── cache.remove()
 //! │     - Update state  │ ─── Hydrated → Online
 //! └─────────────────────┘
 //! ```
 
-use std::sync::Arc;
-
-use lnxdrive_cache::pool::DatabasePool;
-use lnxdrive_core::{config::FuseConfig, domain::sync_item::ItemState};
+use std::{
+    collections::{HashMap, HashSet},
+    sync::{Arc, Mutex, OnceLock},
+    time::{Duration, Instant},
+};
+
+use lnxdrive_cache::{pool::DatabasePool, SqliteStateRepository};
+use lnxdrive_core::{
+    config::FuseConfig,
+    domain::{newtypes::UniqueId, sync_item::ItemState, SyncItem},
+    ports::{
+        HydrationEvent, IHydrationObserver, INotificationService, Notification,
+        NotificationPriority,
+    },
+};
 use tokio::{sync::RwLock, task::JoinHandle, time};
 use tracing::{debug, error, info, warn};
 
@@ -50,6 +78,29 @@ use crate::{cache::ContentCache, error::FuseError, inode::InodeTable, write_seri
 // T079: DehydrationPolicy struct
 // ============================================================================
 
+/// What a sweep does with a selected file that is open.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum BusyFileAction {
+    /// Leave the file cached; a later sweep may select it again.
+    Skip,
+    /// Dehydrate the file once its last handle is released.
+    #[default]
+    Defer,
+}
+
+impl BusyFileAction {
+    /// Parse the `fuse.dehydration_busy_files` value (`skip` or `defer`).
+    ///
+    /// Unknown values fall back to the default; the configuration
+    /// validation reports them.
+    pub fn from_config_value(value: &str) -> Self {
+        match value {
+            "skip" => Self::Skip,
+            _ => Self::Defer,
+        }
+    }
+}
+
 /// Policy configuration for automatic dehydration.
 ///
 /// Determines when and how dehydration sweeps occur.
@@ -61,20 +112,32 @@ pub struct DehydrationPolicy {
     pub threshold_percent: u8,
     /// Maximum age in days before a cached file becomes eligible for dehydration.
     pub max_age_days: u32,
+    /// Dehydrate files not accessed for this many days regardless of cache
+    /// usage (0 = disabled).
+    pub unused_days: u32,
+    /// When over the threshold, dehydrate files of at least this many bytes
+    /// before smaller ones (0 = disabled).
+    pub large_file_bytes: u64,
     /// Interval in minutes between dehydration background tasks.
     pub interval_minutes: u32,
+    /// What to do with selected files that are open.
+    pub busy_files: BusyFileAction,
 }
 
 impl DehydrationPolicy {
     /// Create a policy from FUSE configuration.
     ///
-    /// Converts `cache_max_size_gb` to bytes and copies other fields.
+    /// Converts `cache_max_size_gb` and `dehydration_large_file_mb` to bytes
+    /// and copies other fields.
     pub fn from_config(config: &FuseConfig) -> Self {
         Self {
             cache_max_bytes: (config.cache_max_size_gb as u64) * 1024 * 1024 * 1024,
             threshold_percent: config.dehydration_threshold_percent,
             max_age_days: config.dehydration_max_age_days,
+            unused_days: config.dehydration_unused_days,
+            large_file_bytes: config.dehydration_large_file_mb * 1024 * 1024,
             interval_minutes: config.dehydration_interval_minutes,
+            busy_files: BusyFileAction::from_config_value(&config.dehydration_busy_files),
         }
     }
 
@@ -90,7 +153,10 @@ impl Default for DehydrationPolicy {
             cache_max_bytes: 10 * 1024 * 1024 * 1024, // 10 GB
             threshold_percent: 80,
             max_age_days: 30,
+            unused_days: 0,
+            large_file_bytes: 0,
             interval_minutes: 60,
+            busy_files: BusyFileAction::default(),
         }
     }
 }
@@ -99,6 +165,48 @@ impl Default for DehydrationPolicy {
 // T080: DehydrationManager struct
 // ============================================================================
 
+/// Minimum time between two runs of the age trigger.
+pub const AGE_SWEEP_INTERVAL: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// Why a file was dehydrated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EvictionReason {
+    /// Not accessed for at least `days` days.
+    Unused { days: u32 },
+    /// Cache over threshold and the file is at least `min_bytes` large.
+    LargeFile { min_bytes: u64 },
+    /// Cache over threshold and the file was among the least recently used.
+    CacheFull,
+    /// Requested explicitly, e.g. by `lnxdrive dehydrate`.
+    Manual,
+}
+
+impl std::fmt::Display for EvictionReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Unused { days } => write!(f, "not accessed for {} days", days),
+            Self::LargeFile { min_bytes } => write!(
+                f,
+                "cache over threshold, file of at least {} MiB",
+                min_bytes / (1024 * 1024)
+            ),
+            Self::CacheFull => write!(f, "cache over threshold, least recently used"),
+            Self::Manual => write!(f, "requested manually"),
+        }
+    }
+}
+
+/// A file dehydrated by a sweep or a manual request.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EvictedFile {
+    /// Local path of the file (inode number for manual requests).
+    pub path: String,
+    /// Bytes freed in the cache.
+    pub bytes: u64,
+    /// Why the file was chosen.
+    pub reason: EvictionReason,
+}
+
 /// Report of a dehydration operation.
 #[derive(Debug, Clone, Default)]
 pub struct DehydrationReport {
@@ -108,10 +216,14 @@ pub struct DehydrationReport {
     pub bytes_freed: u64,
     /// Number of files skipped (open handles, wrong state, etc.).
     pub skipped_count: usize,
+    /// Number of skipped open files to dehydrate once they are closed.
+    pub deferred_count: usize,
     /// Number of errors encountered.
     pub error_count: usize,
     /// Error messages for failed items.
     pub errors: Vec<String>,
+    /// Dehydrated files in eviction order, with the reason for each.
+    pub evicted: Vec<EvictedFile>,
 }
 
 impl DehydrationReport {
@@ -120,8 +232,43 @@ impl DehydrationReport {
         self.dehydrated_count += other.dehydrated_count;
         self.bytes_freed += other.bytes_freed;
         self.skipped_count += other.skipped_count;
+        self.deferred_count += other.deferred_count;
         self.error_count += other.error_count;
         self.errors.extend(other.errors);
+        self.evicted.extend(other.evicted);
+    }
+
+    /// Desktop notification summarizing the space freed.
+    ///
+    /// Returns `None` if nothing was dehydrated.
+    pub fn summary_notification(&self) -> Option<Notification> {
+        if self.dehydrated_count == 0 {
+            return None;
+        }
+        let files = if self.dehydrated_count == 1 {
+            "file"
+        } else {
+            "files"
+        };
+        let mut body = format!(
+            "{} {} made online-only, {:.1} MiB freed",
+            self.dehydrated_count,
+            files,
+            self.bytes_freed as f64 / (1024.0 * 1024.0)
+        );
+        let unused = self
+            .evicted
+            .iter()
+            .filter(|e| matches!(e.reason, EvictionReason::Unused { .. }))
+            .count();
+        if unused > 0 {
+            body.push_str(&format!(" ({} not opened recently)", unused));
+        }
+        Some(
+            Notification::new("Disk space freed", body)
+                .with_priority(NotificationPriority::Low)
+                .with_category("dehydration"),
+        )
     }
 }
 
@@ -131,6 +278,8 @@ impl DehydrationReport {
 /// - Are in `Hydrated` state (not pinned, modified, etc.)
 /// - Haven't been accessed recently
 /// - Don't have open file handles
+///
+/// See the module documentation for the order in which triggers apply.
 pub struct DehydrationManager {
     /// Policy for dehydration decisions.
     policy: DehydrationPolicy,
@@ -144,6 +293,14 @@ pub struct DehydrationManager {
     db_pool: DatabasePool,
     /// Flag to signal shutdown.
     shutdown: Arc<RwLock<bool>>,
+    /// When the age trigger last ran.
+    last_age_sweep: Mutex<Option<Instant>>,
+    /// Receives a summary after periodic sweeps that freed space.
+    notifier: OnceLock<Arc<dyn INotificationService>>,
+    /// Told about every file that became cloud-only.
+    hydration_observer: OnceLock<Arc<dyn IHydrationObserver>>,
+    /// Open files a sweep selected, by inode, with the reason it did.
+    deferred: Mutex<HashMap<u64, EvictionReason>>,
 }
 
 impl DehydrationManager {
@@ -170,6 +327,10 @@ impl DehydrationManager {
             write_handle,
             db_pool,
             shutdown: Arc::new(RwLock::new(false)),
+            last_age_sweep: Mutex::new(None),
+            notifier: OnceLock::new(),
+            hydration_observer: OnceLock::new(),
+            deferred: Mutex::new(HashMap::new()),
         }
     }
 
@@ -178,9 +339,48 @@ impl DehydrationManager {
         &self.policy
     }
 
+    /// Sets the notification service for periodic sweep summaries.
+    ///
+    /// Only the first call has an effect.
+    pub fn set_notifier(&self, notifier: Arc<dyn INotificationService>) {
+        let _ = self.notifier.set(notifier);
+    }
+
+    /// Sets the observer told about dehydrated files.
+    ///
+    /// Only the first call has an effect.
+    pub fn set_hydration_observer(&self, observer: Arc<dyn IHydrationObserver>) {
+        let _ = self.hydration_observer.set(observer);
+    }
+
+    /// Tells the hydration observer, if any, that `path` is cloud-only.
+    fn report_dehydrated(&self, path: String) {
+        if let Some(observer) = self.hydration_observer.get() {
+            observer.on_hydration_event(HydrationEvent::Dehydrated { path });
+        }
+    }
+
+    /// Returns true if the age trigger is enabled and has not run within
+    /// [`AGE_SWEEP_INTERVAL`], recording the run if so.
+    fn age_sweep_due(&self) -> bool {
+        if self.policy.unused_days == 0 {
+            return false;
+        }
+        let mut last = self
+            .last_age_sweep
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        if last.is_some_and(|at| at.elapsed() < AGE_SWEEP_INTERVAL) {
+            return false;
+        }
+        *last = Some(Instant::now());
+        true
+    }
+
     /// Notify the dehydration manager that a file's last handle was closed.
     ///
-    /// If the cache is above the dehydration threshold, this will attempt
+    /// A file whose dehydration a sweep deferred is dehydrated now. Else,
+    /// if the cache is above the dehydration threshold, this will attempt
     /// to dehydrate the file immediately (if eligible). Otherwise, the file
     /// will be picked up by the next periodic sweep.
     ///
@@ -188,6 +388,21 @@ impl DehydrationManager {
     ///
     /// * `ino` - The inode number of the file that was released
     pub async fn notify_file_closed(&self, ino: u64) {
+        let deferred = self
+            .deferred
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .remove(&ino);
+        if let Some(reason) = deferred {
+            match self.dehydrate_path(ino).await {
+                Ok(freed) => {
+                    debug!(ino, freed_bytes = freed, %reason, "Dehydrated deferred file")
+                }
+                Err(e) => debug!(ino, error = %e, "Deferred dehydration skipped"),
+            }
+            return;
+        }
+
         // Check if cache is over threshold
         let current_usage = match self.cache.disk_usage() {
             Ok(u) => u,
@@ -220,9 +435,11 @@ impl DehydrationManager {
 impl DehydrationManager {
     /// Run a dehydration sweep to reclaim disk space.
     ///
-    /// Checks cache usage against the threshold. If above, queries the database
-    /// for dehydration candidates and processes them until usage drops below
-    /// the threshold or no more candidates are available.
+    /// First dehydrates files unused for `unused_days` (if enabled and not
+    /// already done within [`AGE_SWEEP_INTERVAL`]). Then, if
+    /// cache usage is still above the threshold, dehydrates large files and
+    /// then least recently used files until usage drops to 80% of the
+    /// threshold or no more candidates are available.
     ///
     /// # Returns
     ///
@@ -240,175 +457,312 @@ impl DehydrationManager {
             "Starting dehydration sweep"
         );
 
-        // If below threshold, no need to dehydrate
-        if current_usage < threshold {
-            debug!("Cache usage below threshold, skipping sweep");
-            return Ok(report);
+        let repo = SqliteStateRepository::new(self.db_pool.pool().clone());
+        // Items that could not be dehydrated, so later batches skip them
+        let mut passed_over = HashSet::new();
+
+        // Age trigger: stale files go regardless of cache usage
+        if self.age_sweep_due() {
+            let reason = EvictionReason::Unused {
+                days: self.policy.unused_days,
+            };
+            self.sweep_pass(&repo, reason, u64::MAX, &mut passed_over, &mut report)
+                .await?;
         }
 
-        // Calculate how much space we need to free
-        let target_usage = threshold * 80 / 100; // Target 80% of threshold
-        let bytes_to_free = current_usage.saturating_sub(target_usage);
+        let current_usage = current_usage.saturating_sub(report.bytes_freed);
+        if current_usage < threshold {
+            debug!("Cache usage below threshold, skipping size-based dehydration");
+        } else {
+            // Calculate how much space we need to free
+            let target_usage = threshold * 80 / 100; // Target 80% of threshold
+            let mut bytes_to_free = current_usage.saturating_sub(target_usage);
 
-        debug!(
-            bytes_to_free_mb = bytes_to_free / (1024 * 1024),
-            "Need to free space"
+            debug!(
+                bytes_to_free_mb = bytes_to_free / (1024 * 1024),
+                "Need to free space"
+            );
+
+            if self.policy.large_file_bytes > 0 {
+                let reason = EvictionReason::LargeFile {
+                    min_bytes: self.policy.large_file_bytes,
+                };
+                let freed = self
+                    .sweep_pass(&repo, reason, bytes_to_free, &mut passed_over, &mut report)
+                    .await?;
+                bytes_to_free = bytes_to_free.saturating_sub(freed);
+            }
+
+            if bytes_to_free > 0 {
+                self.sweep_pass(
+                    &repo,
+                    EvictionReason::CacheFull,
+                    bytes_to_free,
+                    &mut passed_over,
+                    &mut report,
+                )
+                .await?;
+            }
+        }
+
+        info!(
+            dehydrated = report.dehydrated_count,
+            freed_mb = report.bytes_freed / (1024 * 1024),
+            skipped = report.skipped_count,
+            errors = report.error_count,
+            "Dehydration sweep complete"
         );
 
-        // Get repository for queries
-        let repo = lnxdrive_cache::SqliteStateRepository::new(self.db_pool.pool().clone());
+        Ok(report)
+    }
+
+    /// Dehydrate the candidates selected by `reason` until `bytes_to_free`
+    /// bytes are freed or no candidates are left.
+    ///
+    /// Returns the number of bytes freed by this pass.
+    async fn sweep_pass(
+        &self,
+        repo: &SqliteStateRepository,
+        reason: EvictionReason,
+        bytes_to_free: u64,
+        passed_over: &mut HashSet<UniqueId>,
+        report: &mut DehydrationReport,
+    ) -> Result<u64, FuseError> {
+        use lnxdrive_core::ports::IStateRepository;
 
-        // Process candidates in batches until we've freed enough space
-        let mut total_freed = 0u64;
         let batch_size = 100u32;
+        let mut freed = 0u64;
 
-        loop {
+        while freed < bytes_to_free {
             // Check for shutdown
             if *self.shutdown.read().await {
                 debug!("Shutdown requested, stopping sweep");
                 break;
             }
 
-            // Query for dehydration candidates
-            use lnxdrive_core::ports::IStateRepository;
-            let candidates = repo
-                .get_items_for_dehydration(self.policy.max_age_days, batch_size)
-                .await
-                .map_err(|e| FuseError::DatabaseError(e.to_string()))?;
+            // Passed-over items are still returned by the query, so fetch
+            // enough to get a full batch of new ones
+            let limit = batch_size + passed_over.len() as u32;
+            let candidates = match reason {
+                EvictionReason::Unused { days } => {
+                    repo.get_items_for_dehydration(days, limit).await
+                }
+                EvictionReason::LargeFile { min_bytes } => {
+                    repo.get_large_items_for_dehydration(self.policy.max_age_days, min_bytes, limit)
+                        .await
+                }
+                EvictionReason::CacheFull | EvictionReason::Manual => {
+                    repo.get_items_for_dehydration(self.policy.max_age_days, limit)
+                        .await
+                }
+            }
+            .map_err(|e| FuseError::DatabaseError(e.to_string()))?;
 
+            let candidates: Vec<SyncItem> = candidates
+                .into_iter()
+                .filter(|item| !passed_over.contains(item.id()))
+                .collect();
             if candidates.is_empty() {
-                debug!("No more dehydration candidates");
+                debug!(%reason, "No more dehydration candidates");
                 break;
             }
 
-            debug!(count = candidates.len(), "Processing dehydration candidates");
+            debug!(count = candidates.len(), %reason, "Processing dehydration candidates");
 
-            // Process each candidate
             for item in candidates {
-                // Check for shutdown
                 if *self.shutdown.read().await {
                     break;
                 }
 
-                // Skip if not in Hydrated state (defensive check)
-                if !matches!(item.state(), ItemState::Hydrated) {
-                    report.skipped_count += 1;
-                    continue;
-                }
-
-                // Check for open handles in inode table
-                if let Some(inode) = item.inode() {
-                    if let Some(entry) = self.inode_table.get(inode) {
-                        if entry.open_handles() > 0 {
-                            debug!(
-                                ino = inode,
-                                handles = entry.open_handles(),
-                                "Skipping file with open handles"
-                            );
-                            report.skipped_count += 1;
-                            continue;
-                        }
+                match self.dehydrate_item(&item, reason, report).await {
+                    Some(file_size) => freed += file_size,
+                    None => {
+                        passed_over.insert(*item.id());
                     }
                 }
 
-                // Get file size before removal
-                let file_size = if let Some(remote_id) = item.remote_id() {
-                    let cache_path = self.cache.cache_path(remote_id);
-                    if cache_path.exists() {
-                        std::fs::metadata(&cache_path)
-                            .map(|m| m.len())
-                            .unwrap_or(0)
-                    } else {
-                        0
-                    }
-                } else {
-                    0
-                };
-
-                // Remove cached content
-                if let Some(remote_id) = item.remote_id() {
-                    match self.cache.remove(remote_id) {
-                        Ok(()) => {
-                            // Update state to Online via WriteSerializer
-                            match self
-                                .write_handle
-                                .update_state(*item.id(), ItemState::Online)
-                                .await
-                            {
-                                Ok(()) => {
-                                    // Note: InodeTable entry state will be refreshed when
-                                    // the file is next accessed. Database is source of truth.
-
-                                    debug!(
-                                        path = %item.local_path(),
-                                        freed_bytes = file_size,
-                                        "Dehydrated file"
-                                    );
-
-                                    report.dehydrated_count += 1;
-                                    report.bytes_freed += file_size;
-                                    total_freed += file_size;
-                                }
-                                Err(e) => {
-                                    warn!(
-                                        path = %item.local_path(),
-                                        error = %e,
-                                        "Failed to update state after dehydration"
-                                    );
-                                    report.error_count += 1;
-                                    report.errors.push(format!(
-                                        "State update failed for {}: {}",
-                                        item.local_path(),
-                                        e
-                                    ));
-                                }
-                            }
-                        }
-                        Err(e) => {
-                            warn!(
-                                path = %item.local_path(),
-                                error = %e,
-                                "Failed to remove cached content"
-                            );
-                            report.error_count += 1;
-                            report.errors.push(format!(
-                                "Cache removal failed for {}: {}",
-                                item.local_path(),
-                                e
-                            ));
-                        }
-                    }
-                } else {
-                    // No remote_id means nothing to dehydrate
-                    report.skipped_count += 1;
-                }
-
                 // Check if we've freed enough space
-                if total_freed >= bytes_to_free {
+                if freed >= bytes_to_free {
                     debug!(
-                        freed_mb = total_freed / (1024 * 1024),
+                        freed_mb = freed / (1024 * 1024),
                         target_mb = bytes_to_free / (1024 * 1024),
                         "Freed target amount of space"
                     );
                     break;
                 }
             }
+        }
 
-            // If we've freed enough, stop
-            if total_freed >= bytes_to_free {
-                break;
-            }
+        Ok(freed)
+    }
+
+    /// Dehydrate one sweep candidate and record the outcome in `report`.
+    ///
+    /// Returns the bytes freed, or `None` if the item was skipped or failed.
+    async fn dehydrate_item(
+        &self,
+        item: &SyncItem,
+        reason: EvictionReason,
+        report: &mut DehydrationReport,
+    ) -> Option<u64> {
+        // Skip if not in Hydrated state (defensive check)
+        if !matches!(item.state(), ItemState::Hydrated) {
+            report.skipped_count += 1;
+            return None;
         }
+        if !self.inode_allows(item, &ItemState::Hydrated, report) {
+            self.defer_if_open(item, reason, report);
+            return None;
+        }
+        self.evict_item(item, reason, report).await
+    }
 
-        info!(
-            dehydrated = report.dehydrated_count,
-            freed_mb = report.bytes_freed / (1024 * 1024),
-            skipped = report.skipped_count,
-            errors = report.error_count,
-            "Dehydration sweep complete"
-        );
+    /// Remember an open sweep candidate, if the policy defers busy files,
+    /// so that [`notify_file_closed`](Self::notify_file_closed) dehydrates
+    /// it.
+    fn defer_if_open(
+        &self,
+        item: &SyncItem,
+        reason: EvictionReason,
+        report: &mut DehydrationReport,
+    ) {
+        if self.policy.busy_files != BusyFileAction::Defer {
+            return;
+        }
+        let Some(inode) = self.inode_table.get_by_item_id(item.id()) else {
+            return;
+        };
+        let open = self
+            .inode_table
+            .get(inode)
+            .is_some_and(|entry| entry.open_handles() > 0);
+        if open {
+            debug!(ino = inode, %reason, "Deferring dehydration until the file is closed");
+            self.deferred
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .insert(inode, reason);
+            report.deferred_count += 1;
+        }
+    }
 
-        Ok(report)
+    /// Check the inode table, which sees opens and local edits before the
+    /// database does.
+    ///
+    /// Returns false, counting a skip in `report`, if `item` is open or its
+    /// inode is no longer in the `expected` state.
+    fn inode_allows(
+        &self,
+        item: &SyncItem,
+        expected: &ItemState,
+        report: &mut DehydrationReport,
+    ) -> bool {
+        let Some(inode) = self.inode_table.get_by_item_id(item.id()) else {
+            return true;
+        };
+        let Some(entry) = self.inode_table.get(inode) else {
+            return true;
+        };
+        if entry.open_handles() > 0 {
+            debug!(
+                ino = inode,
+                handles = entry.open_handles(),
+                "Skipping file with open handles"
+            );
+            report.skipped_count += 1;
+            return false;
+        }
+        if entry.state() != expected {
+            debug!(
+                ino = inode,
+                state = ?entry.state(),
+                "Skipping file whose state changed since it was queried"
+            );
+            report.skipped_count += 1;
+            return false;
+        }
+        true
+    }
+
+    /// Remove the cached content of `item`, mark it `Online` and record it
+    /// in `report`.
+    ///
+    /// Returns the bytes freed, or `None` if the item was skipped or failed.
+    async fn evict_item(
+        &self,
+        item: &SyncItem,
+        reason: EvictionReason,
+        report: &mut DehydrationReport,
+    ) -> Option<u64> {
+        // No remote_id means nothing to dehydrate
+        let Some(remote_id) = item.remote_id() else {
+            report.skipped_count += 1;
+            return None;
+        };
+
+        // Get file size before removal
+        let cache_path = self.cache.cache_path(remote_id);
+        let file_size = if cache_path.exists() {
+            std::fs::metadata(&cache_path).map(|m| m.len()).unwrap_or(0)
+        } else {
+            0
+        };
+
+        // Remove cached content
+        if let Err(e) = self.cache.remove(remote_id) {
+            warn!(
+                path = %item.local_path(),
+                error = %e,
+                "Failed to remove cached content"
+            );
+            report.error_count += 1;
+            report.errors.push(format!(
+                "Cache removal failed for {}: {}",
+                item.local_path(),
+                e
+            ));
+            return None;
+        }
+
+        // Update state to Online via WriteSerializer
+        if let Err(e) = self
+            .write_handle
+            .update_state(*item.id(), ItemState::Online)
+            .await
+        {
+            warn!(
+                path = %item.local_path(),
+                error = %e,
+                "Failed to update state after dehydration"
+            );
+            report.error_count += 1;
+            report.errors.push(format!(
+                "State update failed for {}: {}",
+                item.local_path(),
+                e
+            ));
+            return None;
+        }
+
+        // Note: InodeTable entry state will be refreshed when
+        // the file is next accessed. Database is source of truth.
+
+        debug!(
+            path = %item.local_path(),
+            freed_bytes = file_size,
+            %reason,
+            "Dehydrated file"
+        );
+        self.report_dehydrated(item.local_path().to_string());
+
+        report.dehydrated_count += 1;
+        report.bytes_freed += file_size;
+        report.evicted.push(EvictedFile {
+            path: item.local_path().to_string(),
+            bytes: file_size,
+            reason,
+        });
+        Some(file_size)
     }
 }
 
@@ -455,6 +809,7 @@ impl DehydrationManager {
                                 "Periodic sweep freed space"
                             );
                         }
+                        self.notify_summary(&report).await;
                     }
                     Err(e) => {
                         error!(error = %e, "Periodic dehydration sweep failed");
@@ -464,6 +819,18 @@ impl DehydrationManager {
         })
     }
 
+    /// Sends the summary notification for `report`, if any.
+    async fn notify_summary(&self, report: &DehydrationReport) {
+        let (Some(notifier), Some(notification)) =
+            (self.notifier.get(), report.summary_notification())
+        else {
+            return;
+        };
+        if let Err(e) = notifier.notify(&notification).await {
+            warn!(error = %e, "Failed to send dehydration summary notification");
+        }
+    }
+
     /// Signal the manager to stop periodic sweeps.
     pub async fn shutdown(&self) {
         *self.shutdown.write().await = true;
@@ -543,6 +910,17 @@ impl DehydrationManager {
 
         info!(ino, freed_bytes = file_size, "Manually dehydrated file");
 
+        if self.hydration_observer.get().is_some() {
+            use lnxdrive_core::ports::IStateRepository;
+
+            let repo = SqliteStateRepository::new(self.db_pool.pool().clone());
+            match repo.get_item(&item_id).await {
+                Ok(Some(item)) => self.report_dehydrated(item.local_path().to_string()),
+                Ok(None) => {}
+                Err(e) => warn!(ino, error = %e, "Failed to look up dehydrated file"),
+            }
+        }
+
         Ok(file_size)
     }
 
@@ -565,6 +943,11 @@ impl DehydrationManager {
                 Ok(freed_bytes) => {
                     report.dehydrated_count += 1;
                     report.bytes_freed += freed_bytes;
+                    report.evicted.push(EvictedFile {
+                        path: format!("inode {}", ino),
+                        bytes: freed_bytes,
+                        reason: EvictionReason::Manual,
+                    });
                 }
                 Err(FuseError::NotFound(msg)) => {
                     report.skipped_count += 1;
@@ -589,6 +972,78 @@ impl DehydrationManager {
     }
 }
 
+// ============================================================================
+// Cache clean
+// ============================================================================
+
+impl DehydrationManager {
+    /// Dehydrate every cached file not accessed for `older_than_days` days
+    /// (0 = any age), as requested by `lnxdrive cache clean`.
+    ///
+    /// Unless `keep_pinned` is set, pinned files are unpinned and
+    /// dehydrated too. Open and modified files are always kept.
+    pub async fn clean(
+        &self,
+        keep_pinned: bool,
+        older_than_days: u32,
+    ) -> Result<DehydrationReport, FuseError> {
+        use lnxdrive_core::ports::{IStateRepository, ItemFilter};
+
+        let repo = SqliteStateRepository::new(self.db_pool.pool().clone());
+        let cutoff = (older_than_days > 0)
+            .then(|| chrono::Utc::now() - chrono::Duration::days(older_than_days as i64));
+        let mut states = vec![ItemState::Hydrated];
+        if !keep_pinned {
+            states.push(ItemState::Pinned);
+        }
+
+        let mut report = DehydrationReport::default();
+        for state in states {
+            let items = repo
+                .query_items(&ItemFilter::new().with_state(state.clone()))
+                .await
+                .map_err(|e| FuseError::DatabaseError(e.to_string()))?;
+            for item in items {
+                if *self.shutdown.read().await {
+                    return Ok(report);
+                }
+                // Files never accessed count as old
+                let recent = cutoff
+                    .zip(item.last_accessed())
+                    .is_some_and(|(cutoff, at)| at >= cutoff);
+                if recent || !self.inode_allows(&item, &state, &mut report) {
+                    continue;
+                }
+                if state.is_pinned() {
+                    if let Err(e) = self
+                        .write_handle
+                        .update_state(*item.id(), ItemState::Hydrated)
+                        .await
+                    {
+                        report.error_count += 1;
+                        report
+                            .errors
+                            .push(format!("Unpin failed for {}: {}", item.local_path(), e));
+                        continue;
+                    }
+                }
+                self.evict_item(&item, EvictionReason::Manual, &mut report)
+                    .await;
+            }
+        }
+
+        info!(
+            dehydrated = report.dehydrated_count,
+            freed_mb = report.bytes_freed / (1024 * 1024),
+            skipped = report.skipped_count,
+            keep_pinned,
+            older_than_days,
+            "Cache clean complete"
+        );
+        Ok(report)
+    }
+}
+
 impl std::fmt::Debug for DehydrationManager {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("DehydrationManager")
@@ -614,11 +1069,29 @@ mod tests {
                 mount_point: "~/OneDrive".to_string(),
                 auto_mount: true,
                 cache_dir: "~/.local/share/lnxdrive/cache".to_string(),
+                temp_dir: None,
                 cache_max_size_gb: 20,
+                cache_shard_depth: 2,
+                cache_dedup: false,
+                cache_scrub_full_hash: false,
                 dehydration_threshold_percent: 75,
                 dehydration_max_age_days: 14,
+                dehydration_unused_days: 90,
+                dehydration_large_file_mb: 512,
                 dehydration_interval_minutes: 30,
+                dehydration_busy_files: "skip".to_string(),
                 hydration_concurrency: 8,
+                hydration_chunk_size_mb: 10,
+                streaming_threshold_mb: 32,
+                read_ahead_mb: 8,
+                max_cached_file_mb: 0,
+                oversized_files: "stream".to_string(),
+                preserve_permissions: false,
+                file_mode: "644".to_string(),
+                directory_mode: "755".to_string(),
+                max_inodes: 1_000_000,
+                prefetch_recent_files: 20,
+                prefetch_recent_mb: 100,
             };
 
             let policy = DehydrationPolicy::from_config(&config);
@@ -626,7 +1099,10 @@ mod tests {
             assert_eq!(policy.cache_max_bytes, 20 * 1024 * 1024 * 1024);
             assert_eq!(policy.threshold_percent, 75);
             assert_eq!(policy.max_age_days, 14);
+            assert_eq!(policy.unused_days, 90);
+            assert_eq!(policy.large_file_bytes, 512 * 1024 * 1024);
             assert_eq!(policy.interval_minutes, 30);
+            assert_eq!(policy.busy_files, BusyFileAction::Skip);
         }
 
         #[test]
@@ -635,7 +1111,10 @@ mod tests {
                 cache_max_bytes: 10 * 1024 * 1024 * 1024, // 10 GB
                 threshold_percent: 80,
                 max_age_days: 30,
+                unused_days: 0,
+                large_file_bytes: 0,
                 interval_minutes: 60,
+                busy_files: BusyFileAction::Defer,
             };
 
             let threshold = policy.threshold_bytes();
@@ -649,7 +1128,10 @@ mod tests {
             assert_eq!(policy.cache_max_bytes, 10 * 1024 * 1024 * 1024);
             assert_eq!(policy.threshold_percent, 80);
             assert_eq!(policy.max_age_days, 30);
+            assert_eq!(policy.unused_days, 0);
+            assert_eq!(policy.large_file_bytes, 0);
             assert_eq!(policy.interval_minutes, 60);
+            assert_eq!(policy.busy_files, BusyFileAction::Defer);
         }
 
         #[test]
@@ -685,7 +1167,10 @@ mod tests {
                 cache_max_bytes: 5 * 1024 * 1024 * 1024,
                 threshold_percent: 90,
                 max_age_days: 7,
+                unused_days: 0,
+                large_file_bytes: 0,
                 interval_minutes: 15,
+                busy_files: BusyFileAction::Skip,
             };
 
             let cloned = policy.clone();
@@ -717,6 +1202,7 @@ mod tests {
             assert_eq!(report.skipped_count, 0);
             assert_eq!(report.error_count, 0);
             assert!(report.errors.is_empty());
+            assert!(report.evicted.is_empty());
         }
 
         #[test]
@@ -725,16 +1211,24 @@ mod tests {
                 dehydrated_count: 5,
                 bytes_freed: 1000,
                 skipped_count: 2,
+                deferred_count: 1,
                 error_count: 1,
                 errors: vec!["Error 1".to_string()],
+                evicted: vec![],
             };
 
             let report2 = DehydrationReport {
                 dehydrated_count: 3,
                 bytes_freed: 500,
                 skipped_count: 1,
+                deferred_count: 1,
                 error_count: 2,
                 errors: vec!["Error 2".to_string(), "Error 3".to_string()],
+                evicted: vec![EvictedFile {
+                    path: "/home/user/OneDrive/a.txt".to_string(),
+                    bytes: 500,
+                    reason: EvictionReason::CacheFull,
+                }],
             };
 
             report1.merge(report2);
@@ -742,8 +1236,10 @@ mod tests {
             assert_eq!(report1.dehydrated_count, 8);
             assert_eq!(report1.bytes_freed, 1500);
             assert_eq!(report1.skipped_count, 3);
+            assert_eq!(report1.deferred_count, 2);
             assert_eq!(report1.error_count, 3);
             assert_eq!(report1.errors.len(), 3);
+            assert_eq!(report1.evicted.len(), 1);
         }
 
         #[test]
@@ -752,8 +1248,10 @@ mod tests {
                 dehydrated_count: 10,
                 bytes_freed: 1024 * 1024,
                 skipped_count: 5,
+                deferred_count: 0,
                 error_count: 0,
                 errors: vec![],
+                evicted: vec![],
             };
 
             let debug_str = format!("{:?}", report);
@@ -793,12 +1291,14 @@ mod tests {
                 dehydrated_count: 0,
                 bytes_freed: 0,
                 skipped_count: 3,
+                deferred_count: 0,
                 error_count: 0,
                 errors: vec![
                     "Permission denied: Cannot dehydrate file with 2 open handles".to_string(),
                     "Invalid state: Cannot dehydrate file in state Pinned".to_string(),
                     "Not found: Inode 123 not found".to_string(),
                 ],
+                evicted: vec![],
             };
 
             assert_eq!(report.skipped_count, 3);
@@ -807,4 +1307,456 @@ mod tests {
             assert!(report.errors.iter().any(|e| e.contains("not found")));
         }
     }
+
+    mod sweep_tests {
+        use std::{path::PathBuf, time::SystemTime};
+
+        use chrono::{Duration, Utc};
+        use lnxdrive_core::{
+            domain::{
+                newtypes::{Email, RemoteId, RemotePath, SyncPath},
+                Account,
+            },
+            ports::IStateRepository,
+        };
+        use tempfile::TempDir;
+
+        use super::*;
+        use crate::{
+            inode_entry::{InodeEntry, InodeNumber},
+            write_serializer::WriteSerializer,
+        };
+
+        /// A manager over an in-memory database and a temporary cache
+        struct Harness {
+            _cache_dir: TempDir,
+            cache: Arc<ContentCache>,
+            inode_table: Arc<InodeTable>,
+            repo: SqliteStateRepository,
+            manager: DehydrationManager,
+        }
+
+        impl Harness {
+            async fn new(policy: DehydrationPolicy) -> Self {
+                let cache_dir = TempDir::new().unwrap();
+                let cache = Arc::new(ContentCache::new(cache_dir.path().to_path_buf()).unwrap());
+                let inode_table = Arc::new(InodeTable::new());
+                let pool = DatabasePool::in_memory().await.unwrap();
+                let repo = SqliteStateRepository::new(pool.pool().clone());
+                let account = Account::new(
+                    Email::new("user@example.com".to_string()).unwrap(),
+                    "Test User",
+                    "root",
+                    SyncPath::new(PathBuf::from("/home/user/OneDrive")).unwrap(),
+                );
+                repo.save_account(&account).await.unwrap();
+                let (serializer, write_handle) = WriteSerializer::new(pool.clone());
+                tokio::spawn(serializer.run());
+                let manager = DehydrationManager::new(
+                    policy,
+                    Arc::clone(&cache),
+                    Arc::clone(&inode_table),
+                    write_handle,
+                    pool,
+                );
+                Self {
+                    _cache_dir: cache_dir,
+                    cache,
+                    inode_table,
+                    repo,
+                    manager,
+                }
+            }
+
+            /// Adds a hydrated file of `size` bytes last accessed `days_ago`
+            async fn add_file(&self, name: &str, size: u64, days_ago: i64) -> SyncItem {
+                let mut item = SyncItem::new_file(
+                    SyncPath::new(PathBuf::from(format!("/home/user/OneDrive/{}", name))).unwrap(),
+                    RemotePath::new(format!("/{}", name)).unwrap(),
+                    size,
+                    None,
+                )
+                .unwrap();
+                item.set_remote_id(RemoteId::new(name.replace('.', "_")).unwrap());
+                item.start_hydrating().unwrap();
+                item.complete_hydration().unwrap();
+                self.repo.save_item(&item).await.unwrap();
+                self.repo
+                    .update_last_accessed(item.id(), Utc::now() - Duration::days(days_ago))
+                    .await
+                    .unwrap();
+                self.cache
+                    .store(item.remote_id().unwrap(), &vec![0u8; size as usize])
+                    .unwrap();
+                item
+            }
+
+            /// Registers `item` in the inode table with one open handle
+            fn open(&self, item: &SyncItem, ino: u64) {
+                let now = SystemTime::now();
+                let entry = InodeEntry::new(
+                    InodeNumber::new(ino),
+                    *item.id(),
+                    item.remote_id().cloned(),
+                    InodeNumber::ROOT,
+                    "open.txt".to_string(),
+                    fuser::FileType::RegularFile,
+                    item.size_bytes(),
+                    0o644,
+                    now,
+                    now,
+                    now,
+                    1,
+                    ItemState::Hydrated,
+                );
+                entry.increment_open_handles();
+                self.inode_table.insert(entry);
+            }
+
+            /// Releases the handle opened by [`Harness::open`], like `release()`
+            async fn close(&self, ino: u64) {
+                let remaining = self.inode_table.get(ino).unwrap().decrement_open_handles();
+                assert_eq!(remaining, 0);
+                self.manager.notify_file_closed(ino).await;
+            }
+
+            async fn state(&self, item: &SyncItem) -> ItemState {
+                self.repo
+                    .get_item(item.id())
+                    .await
+                    .unwrap()
+                    .unwrap()
+                    .state()
+                    .clone()
+            }
+        }
+
+        fn evicted_names(report: &DehydrationReport) -> Vec<&str> {
+            report
+                .evicted
+                .iter()
+                .map(|e| e.path.rsplit('/').next().unwrap())
+                .collect()
+        }
+
+        /// Threshold of 1000 bytes, so sweeps free down to 800 bytes
+        fn small_cache_policy() -> DehydrationPolicy {
+            DehydrationPolicy {
+                cache_max_bytes: 1000,
+                threshold_percent: 100,
+                max_age_days: 7,
+                ..Default::default()
+            }
+        }
+
+        #[tokio::test]
+        async fn test_age_trigger_ignores_cache_usage() {
+            let harness = Harness::new(DehydrationPolicy {
+                unused_days: 60,
+                ..Default::default()
+            })
+            .await;
+            let stale = harness.add_file("stale.txt", 10, 90).await;
+            let recent = harness.add_file("recent.txt", 10, 30).await;
+
+            let report = harness.manager.run_sweep().await.unwrap();
+
+            assert_eq!(evicted_names(&report), vec!["stale.txt"]);
+            assert_eq!(
+                report.evicted[0].reason,
+                EvictionReason::Unused { days: 60 }
+            );
+            assert_eq!(harness.state(&stale).await, ItemState::Online);
+            assert_eq!(harness.state(&recent).await, ItemState::Hydrated);
+        }
+
+        #[tokio::test]
+        async fn test_age_trigger_runs_once_a_day() {
+            let harness = Harness::new(DehydrationPolicy {
+                unused_days: 60,
+                ..Default::default()
+            })
+            .await;
+            harness.add_file("first.txt", 10, 90).await;
+            let report = harness.manager.run_sweep().await.unwrap();
+            assert_eq!(evicted_names(&report), vec!["first.txt"]);
+
+            // A later sweep the same day leaves newly stale files alone
+            let second = harness.add_file("second.txt", 10, 90).await;
+            let report = harness.manager.run_sweep().await.unwrap();
+            assert!(report.evicted.is_empty());
+            assert_eq!(harness.state(&second).await, ItemState::Hydrated);
+        }
+
+        #[tokio::test]
+        async fn test_age_trigger_disabled_with_zero_days() {
+            let harness = Harness::new(DehydrationPolicy::default()).await;
+            let stale = harness.add_file("stale.txt", 10, 365).await;
+
+            let report = harness.manager.run_sweep().await.unwrap();
+
+            assert!(report.evicted.is_empty());
+            assert_eq!(harness.state(&stale).await, ItemState::Hydrated);
+        }
+
+        #[tokio::test]
+        async fn test_summary_notification_counts_unused_files() {
+            let harness = Harness::new(DehydrationPolicy {
+                unused_days: 60,
+                ..Default::default()
+            })
+            .await;
+            harness.add_file("a.txt", 1024 * 1024, 90).await;
+            harness.add_file("b.txt", 1024 * 1024, 120).await;
+
+            let report = harness.manager.run_sweep().await.unwrap();
+            let notification = report.summary_notification().unwrap();
+
+            assert_eq!(notification.title, "Disk space freed");
+            assert_eq!(
+                notification.body,
+                "2 files made online-only, 2.0 MiB freed (2 not opened recently)"
+            );
+            assert_eq!(notification.category, "dehydration");
+            let empty = DehydrationReport::default();
+            assert!(empty.summary_notification().is_none());
+        }
+
+        #[tokio::test]
+        async fn test_size_trigger_evicts_least_recently_used_first() {
+            let harness = Harness::new(small_cache_policy()).await;
+            harness.add_file("oldest.txt", 300, 40).await;
+            harness.add_file("older.txt", 300, 30).await;
+            harness.add_file("old.txt", 300, 20).await;
+            harness.add_file("fresh.txt", 300, 1).await;
+
+            // 1200 bytes cached, 400 to free
+            let report = harness.manager.run_sweep().await.unwrap();
+
+            assert_eq!(evicted_names(&report), vec!["oldest.txt", "older.txt"]);
+            assert!(report
+                .evicted
+                .iter()
+                .all(|e| e.reason == EvictionReason::CacheFull));
+            assert_eq!(report.bytes_freed, 600);
+        }
+
+        #[tokio::test]
+        async fn test_size_trigger_skipped_below_threshold() {
+            let harness = Harness::new(small_cache_policy()).await;
+            harness.add_file("old.txt", 500, 40).await;
+
+            let report = harness.manager.run_sweep().await.unwrap();
+
+            assert!(report.evicted.is_empty());
+        }
+
+        #[tokio::test]
+        async fn test_large_files_evicted_first() {
+            let harness = Harness::new(DehydrationPolicy {
+                large_file_bytes: 400,
+                ..small_cache_policy()
+            })
+            .await;
+            harness.add_file("old_small.txt", 200, 40).await;
+            harness.add_file("big.txt", 450, 10).await;
+            harness.add_file("bigger.txt", 500, 10).await;
+
+            // 1150 bytes cached, 350 to free: the biggest file is enough
+            let report = harness.manager.run_sweep().await.unwrap();
+
+            assert_eq!(evicted_names(&report), vec!["bigger.txt"]);
+            assert_eq!(
+                report.evicted[0].reason,
+                EvictionReason::LargeFile { min_bytes: 400 }
+            );
+        }
+
+        #[tokio::test]
+        async fn test_triggers_compose_in_priority_order() {
+            let harness = Harness::new(DehydrationPolicy {
+                unused_days: 60,
+                large_file_bytes: 400,
+                ..small_cache_policy()
+            })
+            .await;
+            harness.add_file("ancient.txt", 100, 90).await;
+            harness.add_file("big.txt", 400, 10).await;
+            harness.add_file("lru.txt", 300, 20).await;
+            harness.add_file("newer.txt", 300, 8).await;
+            harness.add_file("fresh.txt", 300, 1).await;
+
+            // 1400 bytes cached: age frees 100, large frees 400, LRU frees 300
+            let report = harness.manager.run_sweep().await.unwrap();
+
+            assert_eq!(
+                evicted_names(&report),
+                vec!["ancient.txt", "big.txt", "lru.txt"]
+            );
+            let reasons: Vec<_> = report.evicted.iter().map(|e| e.reason).collect();
+            assert_eq!(
+                reasons,
+                vec![
+                    EvictionReason::Unused { days: 60 },
+                    EvictionReason::LargeFile { min_bytes: 400 },
+                    EvictionReason::CacheFull,
+                ]
+            );
+        }
+
+        #[tokio::test]
+        async fn test_sweep_never_evicts_pinned_modified_or_open_files() {
+            let harness = Harness::new(DehydrationPolicy {
+                unused_days: 1,
+                ..Default::default()
+            })
+            .await;
+            let mut pinned = harness.add_file("pinned.txt", 10, 90).await;
+            pinned.pin().unwrap();
+            harness.repo.save_item(&pinned).await.unwrap();
+            let mut modified = harness.add_file("modified.txt", 10, 90).await;
+            modified.mark_modified().unwrap();
+            harness.repo.save_item(&modified).await.unwrap();
+            let open = harness.add_file("open.txt", 10, 90).await;
+            harness.open(&open, 42);
+            let closed = harness.add_file("closed.txt", 10, 90).await;
+
+            let report = harness.manager.run_sweep().await.unwrap();
+
+            assert_eq!(evicted_names(&report), vec!["closed.txt"]);
+            assert_eq!(report.skipped_count, 1);
+            assert_eq!(harness.state(&pinned).await, ItemState::Pinned);
+            assert_eq!(harness.state(&modified).await, ItemState::Modified);
+            assert_eq!(harness.state(&open).await, ItemState::Hydrated);
+            assert_eq!(harness.state(&closed).await, ItemState::Online);
+            assert!(harness.cache.exists(open.remote_id().unwrap()));
+        }
+
+        #[tokio::test]
+        async fn test_open_file_is_dehydrated_once_released() {
+            let harness = Harness::new(DehydrationPolicy {
+                unused_days: 1,
+                ..Default::default()
+            })
+            .await;
+            let open = harness.add_file("open.txt", 10, 90).await;
+            harness.open(&open, 42);
+
+            let report = harness.manager.run_sweep().await.unwrap();
+            assert!(report.evicted.is_empty());
+            assert_eq!(report.skipped_count, 1);
+            assert_eq!(report.deferred_count, 1);
+            assert_eq!(harness.state(&open).await, ItemState::Hydrated);
+            assert!(harness.cache.exists(open.remote_id().unwrap()));
+
+            harness.close(42).await;
+            assert_eq!(harness.state(&open).await, ItemState::Online);
+            assert!(!harness.cache.exists(open.remote_id().unwrap()));
+        }
+
+        #[tokio::test]
+        async fn test_skipped_open_file_waits_for_a_later_sweep() {
+            let harness = Harness::new(DehydrationPolicy {
+                unused_days: 1,
+                busy_files: BusyFileAction::Skip,
+                ..Default::default()
+            })
+            .await;
+            let open = harness.add_file("open.txt", 10, 90).await;
+            harness.open(&open, 42);
+
+            let report = harness.manager.run_sweep().await.unwrap();
+            assert_eq!(report.skipped_count, 1);
+            assert_eq!(report.deferred_count, 0);
+
+            harness.close(42).await;
+            assert_eq!(harness.state(&open).await, ItemState::Hydrated);
+
+            // The age trigger runs once a day; pretend a day has passed
+            *harness.manager.last_age_sweep.lock().unwrap() = None;
+            let report = harness.manager.run_sweep().await.unwrap();
+            assert_eq!(evicted_names(&report), vec!["open.txt"]);
+            assert_eq!(harness.state(&open).await, ItemState::Online);
+        }
+
+        #[tokio::test]
+        async fn test_aggressive_sweep_keeps_pinned_file() {
+            // Every trigger fires for every file
+            let harness = Harness::new(DehydrationPolicy {
+                cache_max_bytes: 1,
+                threshold_percent: 0,
+                max_age_days: 0,
+                unused_days: 1,
+                large_file_bytes: 1,
+                ..Default::default()
+            })
+            .await;
+            let mut pinned = harness.add_file("pinned.txt", 500, 365).await;
+            pinned.pin().unwrap();
+            harness.repo.save_item(&pinned).await.unwrap();
+            harness.add_file("other.txt", 500, 365).await;
+
+            let report = harness.manager.run_sweep().await.unwrap();
+
+            assert_eq!(evicted_names(&report), vec!["other.txt"]);
+            assert_eq!(harness.state(&pinned).await, ItemState::Pinned);
+            assert!(harness.cache.exists(pinned.remote_id().unwrap()));
+        }
+
+        #[tokio::test]
+        async fn test_dehydrated_files_are_reported() {
+            #[derive(Default)]
+            struct Recorder(std::sync::Mutex<Vec<HydrationEvent>>);
+
+            impl IHydrationObserver for Recorder {
+                fn on_hydration_event(&self, event: HydrationEvent) {
+                    self.0.lock().unwrap().push(event);
+                }
+            }
+
+            let harness = Harness::new(DehydrationPolicy {
+                unused_days: 1,
+                ..Default::default()
+            })
+            .await;
+            let recorder = Arc::new(Recorder::default());
+            harness
+                .manager
+                .set_hydration_observer(Arc::clone(&recorder) as Arc<dyn IHydrationObserver>);
+            harness.add_file("old.txt", 10, 90).await;
+            let manual = harness.add_file("manual.txt", 10, 0).await;
+            harness.open(&manual, 42);
+            harness.inode_table.get(42).unwrap().decrement_open_handles();
+
+            harness.manager.run_sweep().await.unwrap();
+            harness.manager.dehydrate_path(42).await.unwrap();
+
+            assert_eq!(
+                *recorder.0.lock().unwrap(),
+                vec![
+                    HydrationEvent::Dehydrated {
+                        path: "/home/user/OneDrive/old.txt".to_string()
+                    },
+                    HydrationEvent::Dehydrated {
+                        path: "/home/user/OneDrive/manual.txt".to_string()
+                    },
+                ]
+            );
+        }
+
+        #[test]
+        fn test_eviction_reason_display() {
+            assert_eq!(
+                EvictionReason::Unused { days: 30 }.to_string(),
+                "not accessed for 30 days"
+            );
+            assert_eq!(
+                EvictionReason::LargeFile {
+                    min_bytes: 100 * 1024 * 1024
+                }
+                .to_string(),
+                "cache over threshold, file of at least 100 MiB"
+            );
+        }
+    }
 }
@@ -43,6 +43,9 @@ pub enum FuseError {
     #[error("name too long: {0}")]
     NameTooLong(String),
 
+    #[error("file too large: {0}")]
+    FileTooLarge(String),
+
     #[error("hydration failed: {0}")]
     HydrationFailed(String),
 
@@ -68,6 +71,7 @@ impl From<FuseError> for libc::c_int {
             FuseError::XattrBufferTooSmall => libc::ERANGE,
             FuseError::InvalidArgument(_) => libc::EINVAL,
             FuseError::NameTooLong(_) => libc::ENAMETOOLONG,
+            FuseError::FileTooLarge(_) => libc::EFBIG,
             FuseError::HydrationFailed(_) => libc::EIO,
             FuseError::CacheError(_) => libc::EIO,
             FuseError::DatabaseError(_) => libc::EIO,
@@ -4,7 +4,7 @@
 //! including file I/O, directory operations, and metadata management.
 
 use std::{
-    collections::HashMap,
+    collections::{HashMap, HashSet},
     ffi::{c_int, OsStr},
     sync::{
         atomic::{AtomicU64, Ordering},
@@ -13,6 +13,7 @@ use std::{
     time::{Duration, SystemTime},
 };
 
+use dashmap::DashSet;
 use fuser::{
     FileType, Filesystem, KernelConfig, ReplyAttr, ReplyCreate, ReplyData, ReplyDirectory,
     ReplyEmpty, ReplyEntry, ReplyOpen, ReplyStatfs, ReplyWrite, ReplyXattr, Request, TimeOrNow,
@@ -21,21 +22,28 @@ use lnxdrive_cache::{pool::DatabasePool, SqliteStateRepository};
 use lnxdrive_core::{
     config::FuseConfig,
     domain::{
-        newtypes::{RemotePath, SyncPath},
-        sync_item::{ItemState, SyncItem},
-        UniqueId,
+        detect_mime_type,
+        newtypes::{RemoteId, RemotePath, SyncPath},
+        sync_item::{ItemFacets, ItemState, SyncItem},
+        AuditAction, AuditEntry, AuditResult, DriveQuota, TransitionTrigger, UniqueId,
     },
-    ports::{IStateRepository, ItemFilter},
+    ports::{IHydrationObserver, INotificationService, IStateRepository, ItemFilter},
 };
+use lnxdrive_graph::provider::GraphCloudProvider;
 use tokio::{runtime::Handle, task::JoinHandle};
 use tracing::{debug, warn};
 
 use crate::{
-    cache::ContentCache,
+    cache::{CacheStats, ContentCache},
+    cache_manager::FuseCacheManager,
     dehydration::{DehydrationManager, DehydrationPolicy},
-    hydration::{HydrationManager, HydrationPriority},
+    hydration::{
+        complete_pin_requests, select_recent_files, HydrationManager, HydrationPriority,
+        OversizedFileAction, PrefetchItem,
+    },
     inode::InodeTable,
     inode_entry::{InodeEntry, InodeNumber},
+    scrub::CacheScrubber,
     write_serializer::{WriteSerializer, WriteSerializerHandle},
     xattr,
 };
@@ -125,6 +133,9 @@ pub struct LnxDriveFs {
     /// Counter for allocating unique file handles
     next_fh: AtomicU64,
 
+    /// File handles opened with O_APPEND, whose writes go to end-of-file
+    append_handles: DashSet<u64>,
+
     /// Manager for automatic dehydration of cached files (T086)
     dehydration_manager: Option<Arc<DehydrationManager>>,
 
@@ -133,6 +144,16 @@ pub struct LnxDriveFs {
 
     /// Manager for on-demand hydration (download) of cloud-only files
     hydration_manager: Option<Arc<HydrationManager>>,
+
+    /// Handle to the background full-hash cache scrub, if enabled
+    scrub_task: Option<JoinHandle<()>>,
+
+    /// Cache hits and misses of file opens
+    cache_stats: Arc<CacheStats>,
+
+    /// Items whose content was requested while no cloud provider was
+    /// attached, so the audit log gets one entry per item and mount
+    unreachable_reported: DashSet<UniqueId>,
 }
 
 impl LnxDriveFs {
@@ -174,8 +195,8 @@ impl LnxDriveFs {
             serializer.run().await;
         });
 
-        // Initialize an empty inode table
-        let inode_table = Arc::new(InodeTable::new());
+        // Initialize an empty inode table, capped at the configured size
+        let inode_table = Arc::new(InodeTable::with_max_entries(config.max_inodes as usize));
 
         // T086: Create the DehydrationManager for automatic cache cleanup
         let policy = DehydrationPolicy::from_config(&config);
@@ -195,9 +216,13 @@ impl LnxDriveFs {
             config,
             db_pool,
             next_fh: AtomicU64::new(1),
+            append_handles: DashSet::new(),
             dehydration_manager: Some(dehydration_manager),
             dehydration_task: None,
             hydration_manager,
+            scrub_task: None,
+            cache_stats: Arc::new(CacheStats::default()),
+            unreachable_reported: DashSet::new(),
         }
     }
 
@@ -211,6 +236,11 @@ impl LnxDriveFs {
         &self.inode_table
     }
 
+    /// Returns the cache hit and miss counters of file opens.
+    pub fn cache_stats(&self) -> &Arc<CacheStats> {
+        &self.cache_stats
+    }
+
     /// Returns a reference to the write serializer handle.
     pub fn write_handle(&self) -> &WriteSerializerHandle {
         &self.write_handle
@@ -236,6 +266,84 @@ impl LnxDriveFs {
         self.hydration_manager.as_ref()
     }
 
+    /// Sets the manager downloading the content of cloud-only files.
+    ///
+    /// The FUSE session takes ownership of the filesystem, so this must be
+    /// called before mounting.
+    pub fn set_hydration_manager(&mut self, manager: Arc<HydrationManager>) {
+        self.hydration_manager = Some(manager);
+    }
+
+    /// Attaches a [`HydrationManager`] downloading through `provider`.
+    ///
+    /// The manager shares this filesystem's cache and write serializer and
+    /// is configured from `fuse.hydration_*`, `fuse.streaming_threshold_mb`,
+    /// `fuse.read_ahead_mb` and the oversized file settings. `observer` is told about hydration
+    /// progress and completion.
+    pub fn attach_provider(
+        &mut self,
+        provider: Arc<GraphCloudProvider>,
+        observer: Option<Arc<dyn IHydrationObserver>>,
+    ) {
+        let config = &self.config;
+        let mut manager = HydrationManager::new(
+            config.hydration_concurrency.max(1) as usize,
+            Arc::clone(&self.cache),
+            self.write_handle.clone(),
+            provider,
+            self.rt_handle.clone(),
+        )
+        .with_chunk_size(config.hydration_chunk_size_mb as u64 * 1024 * 1024)
+        .with_streaming_threshold(config.streaming_threshold_mb * 1024 * 1024)
+        .with_read_ahead(config.read_ahead_mb * 1024 * 1024)
+        .with_oversized_files(
+            config.max_cached_file_bytes(),
+            OversizedFileAction::from_config_value(&config.oversized_files),
+        );
+        if let Some(observer) = observer {
+            manager = manager.with_hydration_observer(observer);
+        }
+        self.set_hydration_manager(Arc::new(manager));
+    }
+
+    /// Returns the cache manager backed by this filesystem's dehydration
+    /// manager, if dehydration is enabled.
+    ///
+    /// It pins files through the hydration manager, so
+    /// [`attach_provider`](Self::attach_provider) must be called first.
+    pub fn cache_manager(&self) -> Option<Arc<FuseCacheManager>> {
+        self.dehydration_manager.as_ref().map(|manager| {
+            let mut cache_manager = FuseCacheManager::new(
+                Arc::clone(manager),
+                Arc::clone(&self.cache),
+                self.db_pool.clone(),
+                self.write_handle.clone(),
+            );
+            if let Some(hydration) = &self.hydration_manager {
+                cache_manager = cache_manager.with_pinning(
+                    Arc::clone(hydration),
+                    Arc::clone(&self.inode_table),
+                    crate::expand_tilde(&self.config.mount_point),
+                );
+            }
+            Arc::new(cache_manager)
+        })
+    }
+
+    /// Sets the notification service that receives dehydration summaries.
+    pub fn set_notifier(&self, notifier: Arc<dyn INotificationService>) {
+        if let Some(manager) = &self.dehydration_manager {
+            manager.set_notifier(notifier);
+        }
+    }
+
+    /// Sets the observer told about files dehydrated on this mount.
+    pub fn set_hydration_observer(&self, observer: Arc<dyn IHydrationObserver>) {
+        if let Some(manager) = &self.dehydration_manager {
+            manager.set_hydration_observer(observer);
+        }
+    }
+
     /// Allocates a new unique file handle.
     ///
     /// File handles are used to track open files and must be unique
@@ -249,6 +357,18 @@ impl LnxDriveFs {
 // Helper functions
 // ============================================================================
 
+/// Returns the `(total, used)` bytes reported by `statfs`
+///
+/// A drive with a storage limit reports its quota, so `df` shows how much
+/// can still be uploaded. Unlimited drives, and accounts whose quota has
+/// not been fetched yet, report the local cache instead.
+fn statfs_capacity(quota: Option<DriveQuota>, cache_total: u64, cache_used: u64) -> (u64, u64) {
+    match quota {
+        Some(quota) if !quota.is_unlimited() => (quota.total, quota.used.min(quota.total)),
+        _ => (cache_total, cache_used),
+    }
+}
+
 /// Converts a SyncItem from the database to an InodeEntry for the FUSE filesystem.
 ///
 /// This function maps domain model fields to the FUSE-specific InodeEntry structure,
@@ -259,6 +379,8 @@ impl LnxDriveFs {
 /// * `item` - The SyncItem to convert
 /// * `ino` - The inode number to assign to this entry
 /// * `parent_ino` - The inode number of the parent directory
+/// * `config` - Supplies the default modes and whether the item's stored
+///   Unix mode is used (`preserve_permissions`)
 ///
 /// # Returns
 ///
@@ -267,6 +389,7 @@ fn sync_item_to_inode_entry(
     item: &SyncItem,
     ino: InodeNumber,
     parent_ino: InodeNumber,
+    config: &FuseConfig,
 ) -> InodeEntry {
     // Determine file type
     let kind = if item.is_directory() {
@@ -282,9 +405,17 @@ fn sync_item_to_inode_entry(
         item.size_bytes()
     };
 
-    // Set permissions based on file type
-    // Directories get 0o755 (rwxr-xr-x), files get 0o644 (rw-r--r--)
-    let perm = if item.is_directory() { 0o755 } else { 0o644 };
+    // Set permissions based on file type, unless a mode was set with chmod
+    // (fuse.file_mode and fuse.directory_mode, 0o644 and 0o755 by default)
+    let default_perm = if item.is_directory() {
+        config.directory_perm()
+    } else {
+        config.file_perm()
+    };
+    let perm = match item.unix_mode() {
+        Some(mode) if config.preserve_permissions => mode as u16,
+        _ => default_perm,
+    };
 
     // Convert timestamps
     // Use last_modified_local if available, otherwise use current time
@@ -340,6 +471,24 @@ fn sync_item_to_inode_entry(
     )
 }
 
+/// Permissions of a file created through the mount
+///
+/// The requested mode with the umask applied, limited to `file_mode`.
+/// Execute bits are kept for those `file_mode` lets read the file, so
+/// scripts stay executable.
+fn new_file_perm(mode: u32, umask: u32, file_mode: u16) -> u16 {
+    let allowed = file_mode | ((file_mode & 0o444) >> 2);
+    (mode & !umask) as u16 & allowed
+}
+
+/// Permissions of a directory created through the mount
+///
+/// The requested mode with the umask applied, limited to `directory_mode`.
+/// Execute bits are added, as directories need them to be traversable.
+fn new_directory_perm(mode: u32, umask: u32, directory_mode: u16) -> u16 {
+    ((mode & !umask) | 0o111) as u16 & directory_mode
+}
+
 // ============================================================================
 // Filesystem trait implementation
 // ============================================================================
@@ -378,8 +527,20 @@ impl Filesystem for LnxDriveFs {
             tracing::debug!("FUSE_EXPORT_SUPPORT capability enabled");
         }
 
-        // Create the state repository from the database pool
-        let repository = SqliteStateRepository::new(self.db_pool.pool().clone());
+        // FUSE_ATOMIC_O_TRUNC (bit 3) passes O_TRUNC to open() instead of
+        // a separate truncate, so overwritten Online files are not hydrated
+        const FUSE_ATOMIC_O_TRUNC: u64 = 1 << 3;
+        if let Err(unsupported) = config.add_capabilities(FUSE_ATOMIC_O_TRUNC) {
+            tracing::debug!(
+                unsupported_bits = unsupported,
+                "FUSE_ATOMIC_O_TRUNC not available from kernel"
+            );
+        }
+
+        // Create the state repository from the database pool. The states
+        // it saves during init are reset by crash recovery and the scrub.
+        let repository = SqliteStateRepository::new(self.db_pool.pool().clone())
+            .with_transition_trigger(TransitionTrigger::CrashRecovery);
 
         // Load all SyncItems from the database using block_on
         // This is safe because init() is called before any other FUSE operations
@@ -469,6 +630,32 @@ impl Filesystem for LnxDriveFs {
             );
         }
 
+        // Cache scrub: a crash or ENOSPC can leave truncated cache files behind.
+        // Check the size of every hydrated item and send damaged ones back to
+        // Online so they are re-hydrated instead of serving corrupt data.
+        // Pinned items the scrub sends back to Online keep a pending pin.
+        let scrubber = Arc::new(CacheScrubber::new(self.cache.clone(), false));
+        let (_, mut healed) = scrubber.scrub_items(&mut items);
+
+        // Pins interrupted by a crash: files downloaded before it are pinned
+        // now, those still missing their content are prefetched below.
+        healed.extend(complete_pin_requests(&mut items));
+        let pinned: HashSet<UniqueId> = items
+            .iter()
+            .filter(|item| item.metadata().pin_requested())
+            .map(|item| *item.id())
+            .collect();
+        for index in healed {
+            let item = &items[index];
+            if let Err(e) = self.rt_handle.block_on(repository.save_item(item)) {
+                tracing::error!(
+                    item_id = %item.id(),
+                    error = %e,
+                    "Failed to save healed item state"
+                );
+            }
+        }
+
         // Create the root inode (ino=1) for the mount point
         // The root inode represents the mount point directory itself
         let root_entry = InodeEntry::new(
@@ -478,8 +665,8 @@ impl Filesystem for LnxDriveFs {
             InodeNumber::ROOT, // Root's parent is itself
             String::new(),     // Root has no name
             FileType::Directory,
-            0,     // Size is 0 for directories
-            0o755, // rwxr-xr-x
+            0, // Size is 0 for directories
+            self.config.directory_perm(),
             SystemTime::now(),
             SystemTime::now(),
             SystemTime::now(),
@@ -500,12 +687,21 @@ impl Filesystem for LnxDriveFs {
             let ino = if let Some(existing_ino) = item.inode() {
                 InodeNumber::new(existing_ino)
             } else {
-                // Allocate a new inode using the write serializer
+                // Allocate a new inode using the write serializer and keep
+                // it on the item, so the next mount hands out the same number
                 match self
                     .rt_handle
                     .block_on(self.write_handle.increment_inode_counter())
                 {
-                    Ok(new_ino) => InodeNumber::new(new_ino),
+                    Ok(new_ino) => {
+                        if let Err(e) = self
+                            .rt_handle
+                            .block_on(self.write_handle.update_inode(*item.id(), new_ino))
+                        {
+                            tracing::warn!(error = %e, "Failed to persist inode");
+                        }
+                        InodeNumber::new(new_ino)
+                    }
                     Err(e) => {
                         tracing::error!(error = %e, "Failed to allocate inode");
                         return Err(libc::EIO);
@@ -521,7 +717,27 @@ impl Filesystem for LnxDriveFs {
         }
 
         // Second pass: create InodeEntries with correct parent inodes
+        let mut prefetch = Vec::new();
+        let mut recent = Vec::new();
         for (item, ino) in item_inodes {
+            if matches!(item.state(), ItemState::Online) {
+                if let Some(remote_id) = item.remote_id() {
+                    let prefetch_item = PrefetchItem {
+                        ino: ino.get(),
+                        item_id: *item.id(),
+                        remote_id: remote_id.clone(),
+                        size: item.size_bytes(),
+                    };
+                    if pinned.contains(item.id()) {
+                        prefetch.push(prefetch_item);
+                    } else if !item.is_directory() {
+                        if let Some(accessed) = item.last_accessed() {
+                            recent.push((prefetch_item, accessed));
+                        }
+                    }
+                }
+            }
+
             // Determine parent inode by looking up parent path
             let parent_ino = item
                 .local_path()
@@ -533,7 +749,8 @@ impl Filesystem for LnxDriveFs {
                 .unwrap_or(InodeNumber::ROOT);
 
             // Convert SyncItem to InodeEntry
-            let entry = sync_item_to_inode_entry(&item, ino, parent_ino);
+            let entry =
+                sync_item_to_inode_entry(&item, ino, parent_ino, &self.config);
 
             // Insert into the inode table
             self.inode_table.insert(entry);
@@ -544,6 +761,33 @@ impl Filesystem for LnxDriveFs {
             "LnxDrive FUSE filesystem initialized"
         );
 
+        // Pinned files must be available offline, so download the ones whose
+        // content is missing right away instead of waiting for an open().
+        if !prefetch.is_empty() {
+            match &self.hydration_manager {
+                Some(manager) => {
+                    manager.prefetch_pinned(prefetch);
+                }
+                None => tracing::warn!(
+                    count = prefetch.len(),
+                    "Pinned files are missing from the cache and no hydration manager is available"
+                ),
+            }
+        }
+
+        // Warm the working set: the files used last are likely opened again
+        // soon after login, so hydrate a few of them in the background.
+        let recent = select_recent_files(
+            recent,
+            self.config.prefetch_recent_files as usize,
+            self.config.prefetch_recent_mb * 1024 * 1024,
+        );
+        if !recent.is_empty() {
+            if let Some(manager) = &self.hydration_manager {
+                manager.prefetch_recent(recent);
+            }
+        }
+
         // T086: Start the periodic dehydration sweep task
         if let Some(manager) = &self.dehydration_manager {
             let interval = manager.policy().interval_minutes;
@@ -555,6 +799,15 @@ impl Filesystem for LnxDriveFs {
             self.dehydration_task = Some(task);
         }
 
+        // The full-hash scrub reads every cached file, so it is opt-in and
+        // runs in the background after the size check above.
+        if self.config.cache_scrub_full_hash {
+            tracing::info!("Starting background full-hash cache scrub");
+            let scrubber = Arc::new(CacheScrubber::new(self.cache.clone(), true));
+            let task = scrubber.start_background(self.db_pool.clone(), self.write_handle.clone());
+            self.scrub_task = Some(task);
+        }
+
         Ok(())
     }
 
@@ -586,6 +839,11 @@ impl Filesystem for LnxDriveFs {
             task.abort();
         }
 
+        if let Some(task) = self.scrub_task.take() {
+            tracing::debug!("Aborting cache scrub task");
+            task.abort();
+        }
+
         // The WriteSerializerHandle will be dropped when LnxDriveFs is dropped,
         // which will close the channel and signal the writer task to exit.
         // No explicit cleanup is needed here.
@@ -611,7 +869,9 @@ impl Filesystem for LnxDriveFs {
     ///    - Increments the entry's `lookup_count` (kernel reference count)
     ///    - Returns `ReplyEntry` with TTL (1 second), `FileAttr` from `InodeEntry::to_file_attr()`,
     ///      and generation=0
-    /// 3. If not found: replies with `ENOENT`
+    /// 3. If not found and children of `parent` were evicted from the inode
+    ///    table, reloads the entry from the state database
+    /// 4. Otherwise: replies with `ENOENT`
     ///
     /// # Performance
     ///
@@ -638,8 +898,13 @@ impl Filesystem for LnxDriveFs {
 
         debug!("lookup(parent={}, name={})", parent, name_str);
 
-        // Search for the entry in the inode table
-        match self.inode_table.lookup(parent, name_str) {
+        // Search for the entry in the inode table, reloading evicted children
+        let found = match self.inode_table.lookup(parent, name_str) {
+            Some(entry) => Some(entry),
+            None if self.inode_table.is_partial(parent) => self.reload_child(parent, name_str),
+            None => None,
+        };
+        match found {
             Some(entry) => {
                 // Found the entry - increment lookup count
                 entry.increment_lookup();
@@ -747,7 +1012,8 @@ impl Filesystem for LnxDriveFs {
     ///
     /// Target: <10ms for 1000 entries. This is achieved by:
     /// - Using lock-free DashMap for inode table access
-    /// - No database queries or network requests
+    /// - No network requests, and database queries only to reload children
+    ///   evicted from a capped inode table
     /// - Early termination when buffer is full
     #[tracing::instrument(level = "debug", skip(self, _req, reply), fields(ino, offset))]
     fn readdir(
@@ -781,7 +1047,10 @@ impl Filesystem for LnxDriveFs {
             current_entry.parent_ino().get()
         };
 
-        // Get children from inode table
+        // Get children from inode table, reloading evicted ones first
+        if self.inode_table.is_partial(ino) {
+            self.reload_children(ino);
+        }
         let children = self.inode_table.children(ino);
 
         // Build the complete entry list: ".", "..", then children
@@ -865,9 +1134,12 @@ impl Filesystem for LnxDriveFs {
     ///
     /// # Notes
     ///
-    /// - Permission changes update the `perm` field in the inode entry
+    /// - Permission changes update the `perm` field in the inode entry and,
+    ///   with `fuse.preserve_permissions`, are stored on the SyncItem so they
+    ///   survive a remount; otherwise they are dropped
     /// - Timestamp changes update `mtime`/`atime`/`ctime` fields
-    /// - Size changes (truncate) will mark the file as modified (deferred to Stage 5)
+    /// - Size changes (truncate) resize the cached content and mark the file as
+    ///   modified; `Online` files can only be truncated to zero
     /// - uid/gid changes are ignored as OneDrive doesn't support Unix ownership
     #[allow(clippy::too_many_arguments)]
     #[tracing::instrument(level = "debug", skip(self, _req, reply), fields(ino, mode, size))]
@@ -895,7 +1167,7 @@ impl Filesystem for LnxDriveFs {
         );
 
         // Look up the inode entry
-        let entry = match self.inode_table.get(ino) {
+        let mut entry = match self.inode_table.get(ino) {
             Some(entry) => entry,
             None => {
                 warn!("setattr: inode {} not found", ino);
@@ -904,22 +1176,47 @@ impl Filesystem for LnxDriveFs {
             }
         };
 
-        // Log what would be changed (actual modification deferred to Stage 5)
         if let Some(new_mode) = mode {
-            debug!(
-                "setattr: would update mode from {:o} to {:o}",
-                entry.perm(),
-                new_mode as u16 & 0o7777
-            );
+            let perm = new_mode as u16 & 0o7777;
+            if self.config.preserve_permissions {
+                debug!("setattr: mode {:o} -> {:o}", entry.perm(), perm);
+                if let Err(e) = self
+                    .rt_handle
+                    .block_on(self.save_unix_mode(*entry.item_id(), perm))
+                {
+                    warn!("setattr: failed to store mode for inode {}: {}", ino, e);
+                    reply.error(libc::EIO);
+                    return;
+                }
+                self.inode_table.insert(entry.with_perm(perm));
+                if let Some(updated) = self.inode_table.get(ino) {
+                    entry = updated;
+                }
+            } else {
+                debug!(
+                    "setattr: ignoring mode {:o} (fuse.preserve_permissions is off)",
+                    perm
+                );
+            }
         }
 
         if let Some(new_size) = size {
             if new_size != entry.size() {
                 debug!(
-                    "setattr: truncate from {} to {} bytes (deferred to Stage 5)",
+                    "setattr: truncate from {} to {} bytes",
                     entry.size(),
                     new_size
                 );
+                match self
+                    .rt_handle
+                    .block_on(self.truncate_content(ino, new_size))
+                {
+                    Ok(updated) => entry = updated,
+                    Err(errno) => {
+                        reply.error(errno);
+                        return;
+                    }
+                }
             }
         }
 
@@ -947,8 +1244,11 @@ impl Filesystem for LnxDriveFs {
 
     /// Returns filesystem statistics.
     ///
-    /// Provides information about the filesystem capacity and usage,
-    /// derived from the cache configuration and current disk usage.
+    /// Provides information about the filesystem capacity and usage.
+    /// Capacity is the account's storage quota, as last refreshed by the
+    /// daemon; without a known limit it falls back to the cache size.
+    /// Mounts without a cloud provider only reach the local cache, so they
+    /// always report the cache.
     ///
     /// # Arguments
     ///
@@ -958,8 +1258,8 @@ impl Filesystem for LnxDriveFs {
     ///
     /// # Statistics Returned
     ///
-    /// - `blocks`: Total number of blocks based on cache_max_size_gb
-    /// - `bfree`/`bavail`: Free blocks based on cache disk usage
+    /// - `blocks`: Total number of blocks (quota total, else cache_max_size_gb)
+    /// - `bfree`/`bavail`: Free blocks (quota remaining, else free cache space)
     /// - `files`: Current number of inodes in the inode table
     /// - `ffree`: Arbitrary large number (no inode limit)
     /// - `bsize`: Block size (4096 bytes)
@@ -972,18 +1272,34 @@ impl Filesystem for LnxDriveFs {
         const BLOCK_SIZE: u32 = 4096;
         const NAME_MAX: u32 = 255;
 
-        // Calculate total capacity from config (cache_max_size_gb * 1024^3)
-        let total_bytes = (self.config.cache_max_size_gb as u64) * 1024 * 1024 * 1024;
-        let total_blocks = total_bytes / (BLOCK_SIZE as u64);
+        // Cache capacity from config (cache_max_size_gb * 1024^3)
+        let cache_total = (self.config.cache_max_size_gb as u64) * 1024 * 1024 * 1024;
 
         // Get current disk usage from the cache
-        let used_bytes = match self.cache.disk_usage() {
+        let cache_used = match self.cache.disk_usage() {
             Ok(bytes) => bytes,
             Err(e) => {
                 warn!("statfs: failed to get disk usage: {}", e);
                 0
             }
         };
+
+        let repository = SqliteStateRepository::new(self.db_pool.pool().clone());
+        let quota = if self.hydration_manager.is_none() {
+            debug!("statfs: no cloud provider attached, reporting the cache");
+            None
+        } else {
+            match self.rt_handle.block_on(repository.get_default_account()) {
+                Ok(account) => account.map(|a| DriveQuota::new(a.quota_used(), a.quota_total())),
+                Err(e) => {
+                    warn!("statfs: failed to read account quota: {}", e);
+                    None
+                }
+            }
+        };
+
+        let (total_bytes, used_bytes) = statfs_capacity(quota, cache_total, cache_used);
+        let total_blocks = total_bytes / (BLOCK_SIZE as u64);
         let used_blocks = used_bytes / (BLOCK_SIZE as u64);
 
         // Calculate free blocks
@@ -1027,7 +1343,8 @@ impl Filesystem for LnxDriveFs {
     ///
     /// This method decrements the lookup count on the inode entry.
     /// When the lookup count reaches zero and there are no open handles,
-    /// the entry becomes eligible for garbage collection (handled separately).
+    /// the entry becomes eligible for eviction, which happens once the inode
+    /// table grows past `max_inodes`.
     ///
     /// There is no reply for this method - it completes silently.
     fn forget(&mut self, _req: &Request<'_>, ino: u64, nlookup: u64) {
@@ -1044,13 +1361,21 @@ impl Filesystem for LnxDriveFs {
                 new_count
             );
 
-            // Log if the entry is now eligible for eviction
+            // Queue the entry for eviction and trim the table if over its cap
             if entry.is_expired() {
                 debug!(
                     "forget: inode {} is now eligible for eviction (lookup=0, handles=0)",
                     ino
                 );
-                // Actual GC will be handled by a separate background task
+                self.inode_table.mark_forgotten(ino);
+                let evicted = self.inode_table.evict_forgotten();
+                if evicted > 0 {
+                    debug!(
+                        "forget: evicted {} inodes, {} left in table",
+                        evicted,
+                        self.inode_table.len()
+                    );
+                }
             }
         } else {
             warn!("forget: inode {} not found in table", ino);
@@ -1168,6 +1493,8 @@ impl Filesystem for LnxDriveFs {
     ///
     /// - `ENOENT` - The inode does not exist in the inode table
     /// - `EISDIR` - The inode exists but is a directory (use opendir instead)
+    /// - `ENOTCONN` - The file is `Online` and the mount has no cloud provider
+    ///   to download it (unless the open truncates it)
     ///
     /// # Hydration Behavior
     ///
@@ -1178,6 +1505,12 @@ impl Filesystem for LnxDriveFs {
     /// - If state is `Hydrated`, `Pinned`, or `Modified`: File content is available
     ///   locally, return FOPEN_KEEP_CACHE to use cached data
     ///
+    /// # Flags
+    ///
+    /// - `O_TRUNC` on a writable open empties the file, which becomes `Modified`.
+    ///   An `Online` file is overwritten locally without being hydrated first.
+    /// - `O_APPEND` makes every write through the handle go to end-of-file.
+    ///
     /// # Performance
     ///
     /// Target: <1ms for already-hydrated files. Uses lock-free DashMap lookup
@@ -1187,7 +1520,7 @@ impl Filesystem for LnxDriveFs {
         debug!("open(ino={}, flags={:#x})", ino, flags);
 
         // Look up the inode in the table
-        let entry = match self.inode_table.get(ino) {
+        let mut entry = match self.inode_table.get(ino) {
             Some(entry) => entry,
             None => {
                 debug!("open: inode {} not found", ino);
@@ -1203,6 +1536,34 @@ impl Filesystem for LnxDriveFs {
             return;
         }
 
+        // Files too large for the cache are rejected, or streamed read-only
+        if let Some(errno) = self.refuse_oversized(&entry, flags) {
+            reply.error(errno);
+            return;
+        }
+
+        // Placeholders can't be downloaded without a cloud provider
+        if self.needs_provider(&entry, flags) {
+            let errno = self.rt_handle.block_on(self.report_unreachable(&entry));
+            reply.error(errno);
+            return;
+        }
+
+        // Allocate a file handle
+        let fh = self.alloc_fh();
+
+        // Apply O_TRUNC/O_APPEND before the state decides about hydration
+        match self
+            .rt_handle
+            .block_on(self.apply_open_flags(entry, fh, flags))
+        {
+            Ok(updated) => entry = updated,
+            Err(errno) => {
+                reply.error(errno);
+                return;
+            }
+        }
+
         // Increment open handles counter
         entry.increment_open_handles();
         debug!(
@@ -1211,15 +1572,24 @@ impl Filesystem for LnxDriveFs {
             entry.open_handles()
         );
 
-        // Allocate a file handle
-        let fh = self.alloc_fh();
-
         // Determine open flags based on state
         let open_flags = match entry.state() {
             lnxdrive_core::domain::sync_item::ItemState::Online => {
+                self.cache_stats.record_miss();
                 // File is a placeholder - trigger on-demand hydration
                 if let Some(ref hm) = self.hydration_manager {
-                    if let Some(remote_id) = entry.remote_id() {
+                    if hm.oversized_action(entry.size()).is_some() {
+                        debug!(
+                            "open: inode {} is too large for the cache, reads are streamed",
+                            ino
+                        );
+                    } else if let Some(remote_id) = entry.remote_id() {
+                        hm.set_transfer_path(
+                            ino,
+                            self.build_local_path(entry.parent_ino().get(), entry.name())
+                                .display()
+                                .to_string(),
+                        );
                         let hm = Arc::clone(hm);
                         let item_id = *entry.item_id();
                         let remote_id = remote_id.clone();
@@ -1270,6 +1640,7 @@ impl Filesystem for LnxDriveFs {
             }
             lnxdrive_core::domain::sync_item::ItemState::Hydrating => {
                 // File is currently being hydrated - read() will wait for completion
+                self.cache_stats.record_miss();
                 debug!(
                     "open: inode {} is Hydrating, read() will wait for data",
                     ino
@@ -1292,6 +1663,7 @@ impl Filesystem for LnxDriveFs {
             | lnxdrive_core::domain::sync_item::ItemState::Pinned
             | lnxdrive_core::domain::sync_item::ItemState::Modified => {
                 // File is available locally - use cached data
+                self.cache_stats.record_hit();
                 debug!(
                     "open: inode {} is locally available, using FOPEN_KEEP_CACHE",
                     ino
@@ -1351,13 +1723,16 @@ impl Filesystem for LnxDriveFs {
     ///
     /// - `ENOENT` - The inode does not exist in the inode table
     /// - `EIO` - File is not hydrated (Online or Hydrating state), or read failed
+    /// - `ENOTCONN` - File is not hydrated and the mount has no cloud provider
     ///
     /// # State Handling
     ///
-    /// - `Online`: Returns EIO (file needs to be hydrated first)
-    /// - `Hydrating`: Returns EIO (hydration in progress, will wait when HydrationManager
-    ///   is integrated)
+    /// - `Online`: Starts hydration and waits for the requested range
+    /// - `Hydrating`: Waits for the requested range; large files are streamed, so
+    ///   the range is usually served before the whole file has downloaded
     /// - `Hydrated`, `Pinned`, `Modified`: Reads from local cache
+    /// - Files too large for the cache are never hydrated: their ranges are
+    ///   read from the cloud, or `EFBIG` is returned (`fuse.oversized_files`)
     ///
     /// # Memory-Mapped Files (mmap)
     ///
@@ -1414,6 +1789,31 @@ impl Filesystem for LnxDriveFs {
             | lnxdrive_core::domain::sync_item::ItemState::Hydrating => {
                 // File needs hydration - wait for data to become available
                 if let Some(ref hm) = self.hydration_manager {
+                    // Files too large for the cache are read from the cloud
+                    if let Some(action) = hm.oversized_action(entry.size()) {
+                        let result = match (action, entry.remote_id()) {
+                            (OversizedFileAction::Stream, Some(remote_id)) => self
+                                .rt_handle
+                                .block_on(hm.read_uncached(
+                                    remote_id,
+                                    entry.size(),
+                                    offset as u64,
+                                    size,
+                                ))
+                                .map_err(c_int::from),
+                            (OversizedFileAction::Stream, None) => Err(libc::EIO),
+                            (OversizedFileAction::Reject, _) => Err(libc::EFBIG),
+                        };
+                        match result {
+                            Ok(data) => reply.data(&data),
+                            Err(errno) => {
+                                warn!("read: uncached read of inode {} failed: {}", ino, errno);
+                                reply.error(errno);
+                            }
+                        }
+                        return;
+                    }
+
                     // Ensure hydration is running (handles race with open())
                     if !hm.is_hydrating(ino) {
                         if let Some(remote_id) = entry.remote_id() {
@@ -1421,6 +1821,12 @@ impl Filesystem for LnxDriveFs {
                                 "read: inode {} not hydrating yet, starting hydration",
                                 ino
                             );
+                            hm.set_transfer_path(
+                                ino,
+                                self.build_local_path(entry.parent_ino().get(), entry.name())
+                                    .display()
+                                    .to_string(),
+                            );
                             match self.rt_handle.block_on(hm.hydrate(
                                 ino,
                                 *entry.item_id(),
@@ -1445,6 +1851,10 @@ impl Filesystem for LnxDriveFs {
                         }
                     }
 
+                    // Sequential reads of streamed files fetch the next range
+                    // ahead, so they rarely have to wait
+                    hm.read_ahead(ino, offset as u64, size as u64);
+
                     // Wait for the requested byte range to be available
                     debug!(
                         "read: waiting for hydration range ino={} offset={} size={}",
@@ -1455,7 +1865,8 @@ impl Filesystem for LnxDriveFs {
                         .block_on(hm.wait_for_range(ino, offset as u64, size as u64))
                     {
                         Ok(()) => {
-                            // Hydration complete for this range - read from cache
+                            // The range is present - read it, from the partial
+                            // download if the file is still hydrating
                             let remote_id = match entry.remote_id() {
                                 Some(id) => id.clone(),
                                 None => {
@@ -1463,7 +1874,7 @@ impl Filesystem for LnxDriveFs {
                                     return;
                                 }
                             };
-                            match self.cache.read(&remote_id, offset as u64, size) {
+                            match self.cache.read_in_progress(&remote_id, offset as u64, size) {
                                 Ok(data) => {
                                     debug!(
                                         "read: successfully read {} bytes from inode {} after hydration",
@@ -1487,11 +1898,8 @@ impl Filesystem for LnxDriveFs {
                         }
                     }
                 } else {
-                    debug!(
-                        "read: inode {} is not hydrated and no hydration manager available",
-                        ino
-                    );
-                    reply.error(libc::EIO);
+                    let errno = self.rt_handle.block_on(self.report_unreachable(&entry));
+                    reply.error(errno);
                 }
             }
             lnxdrive_core::domain::sync_item::ItemState::Hydrated
@@ -1586,7 +1994,7 @@ impl Filesystem for LnxDriveFs {
         &mut self,
         _req: &Request<'_>,
         ino: u64,
-        _fh: u64,
+        fh: u64,
         offset: i64,
         data: &[u8],
         _write_flags: u32,
@@ -1637,8 +2045,11 @@ impl Filesystem for LnxDriveFs {
                     }
                 };
 
+                // Handles opened with O_APPEND ignore the offset
+                let offset = self.write_offset(fh, &remote_id, offset as u64);
+
                 // Write to the content cache
-                match self.cache.write_at(&remote_id, offset as u64, data) {
+                match self.cache.write_at(&remote_id, offset, data) {
                     Ok(bytes_written) => {
                         debug!(
                             "write: successfully wrote {} bytes to inode {}",
@@ -1646,7 +2057,7 @@ impl Filesystem for LnxDriveFs {
                         );
 
                         // Check if file grew (offset + data.len > current size)
-                        let new_end = offset as u64 + data.len() as u64;
+                        let new_end = offset + data.len() as u64;
                         if new_end > entry.size() {
                             debug!(
                                 "write: inode {} size increased from {} to {}",
@@ -1733,6 +2144,7 @@ impl Filesystem for LnxDriveFs {
         reply: ReplyEmpty,
     ) {
         debug!("release(ino={}, fh={})", ino, fh);
+        self.append_handles.remove(&fh);
 
         // Look up the inode in the table
         if let Some(entry) = self.inode_table.get(ino) {
@@ -1904,9 +2316,7 @@ impl Filesystem for LnxDriveFs {
             }
         };
 
-        // Calculate permissions: apply umask and ensure execute bits for directory
-        // Directories need execute bits to be traversable
-        let perm = ((mode & !umask) | 0o111) as u16;
+        let perm = new_directory_perm(mode, umask, self.config.directory_perm());
 
         let now = SystemTime::now();
 
@@ -2388,6 +2798,11 @@ impl Filesystem for LnxDriveFs {
 
         // Set the inode on the SyncItem
         sync_item.set_inode(Some(new_ino.get()));
+        // No content yet, so this is a guess from the name; the sync engine
+        // sniffs the content when it uploads the file
+        sync_item
+            .metadata_mut()
+            .set_mime_type(Some(detect_mime_type(name_str, &[])));
 
         // Transition to Modified state (new file without remote counterpart)
         // New items start in Online state, but we need to mark them as Modified
@@ -2404,8 +2819,7 @@ impl Filesystem for LnxDriveFs {
             return;
         }
 
-        // Calculate file permissions: mode & !umask (apply umask)
-        let perm = (mode & !umask) as u16 & 0o7777;
+        let perm = new_file_perm(mode, umask, self.config.file_perm());
 
         let now = std::time::SystemTime::now();
 
@@ -2440,6 +2854,9 @@ impl Filesystem for LnxDriveFs {
 
         // Allocate a file handle
         let fh = self.alloc_fh();
+        if flags & libc::O_APPEND != 0 {
+            self.append_handles.insert(fh);
+        }
 
         debug!(
             "create: created file {} with inode {}, fh={}",
@@ -2604,8 +3021,20 @@ impl Filesystem for LnxDriveFs {
             .as_ref()
             .and_then(|hm| hm.progress(ino));
 
+        // Facets are only kept in the state database
+        let facets = if xattr::is_facet_xattr(name_str) {
+            self.item_facets(&entry)
+        } else {
+            None
+        };
+
         // Get the attribute value using the xattr module
-        let value = match xattr::get_xattr(&entry, name_str, hydration_progress) {
+        let value = if name_str == xattr::XATTR_PROVIDER {
+            Some(xattr::provider_xattr(self.hydration_manager.is_some()))
+        } else {
+            xattr::get_xattr(&entry, name_str, hydration_progress, facets.as_ref())
+        };
+        let value = match value {
             Some(v) => v,
             None => {
                 debug!("getxattr: attribute {} not found for inode {}", name_str, ino);
@@ -2645,14 +3074,15 @@ impl Filesystem for LnxDriveFs {
         debug!("listxattr: ino={}, size={}", ino, size);
 
         // Verify the inode exists
-        if self.inode_table.get(ino).is_none() {
+        let Some(entry) = self.inode_table.get(ino) else {
             debug!("listxattr: inode {} not found", ino);
             reply.error(libc::ENOENT);
             return;
-        }
+        };
 
-        // Get all supported attribute names
-        let attrs = xattr::list_xattrs();
+        // Get all supported attribute names, with the facets the item has
+        let facets = self.item_facets(&entry);
+        let attrs = xattr::list_xattrs(facets.as_ref());
 
         // Build null-separated list of names
         let mut data = Vec::new();
@@ -2735,6 +3165,129 @@ impl Filesystem for LnxDriveFs {
 // ============================================================================
 
 impl LnxDriveFs {
+    /// Returns the local path of directory `dir_ino` as stored in the state
+    /// database. The root maps to the sync root of the account.
+    fn dir_local_path(&self, repository: &SqliteStateRepository, dir_ino: u64) -> Option<SyncPath> {
+        if dir_ino == InodeNumber::ROOT.get() {
+            return match self.rt_handle.block_on(repository.get_default_account()) {
+                Ok(account) => account.map(|account| account.sync_root().clone()),
+                Err(e) => {
+                    warn!("Failed to load account to reload inodes: {}", e);
+                    None
+                }
+            };
+        }
+        let entry = self.inode_table.get(dir_ino)?;
+        match self
+            .rt_handle
+            .block_on(repository.get_item(entry.item_id()))
+        {
+            Ok(item) => item.map(|item| item.local_path().clone()),
+            Err(e) => {
+                warn!(
+                    "Failed to load directory {} to reload inodes: {}",
+                    dir_ino, e
+                );
+                None
+            }
+        }
+    }
+
+    /// Inserts an entry for `item`, reloaded from the state database, below
+    /// `parent_ino`. Returns the existing entry if the item is in the table.
+    fn insert_reloaded(&self, item: &SyncItem, parent_ino: u64) -> Option<Arc<InodeEntry>> {
+        if let Some(ino) = self.inode_table.get_by_item_id(item.id()) {
+            return self.inode_table.get(ino);
+        }
+        // Evicted inodes are forgotten by the kernel, so a new number is fine
+        let ino = match item.inode() {
+            Some(ino) if self.inode_table.get(ino).is_none() => ino,
+            _ => match self
+                .rt_handle
+                .block_on(self.write_handle.increment_inode_counter())
+            {
+                Ok(ino) => {
+                    if let Err(e) = self
+                        .rt_handle
+                        .block_on(self.write_handle.update_inode(*item.id(), ino))
+                    {
+                        warn!("Failed to persist inode of reloaded item: {}", e);
+                    }
+                    ino
+                }
+                Err(e) => {
+                    warn!("Failed to allocate inode for reloaded item: {}", e);
+                    return None;
+                }
+            },
+        };
+        let entry = sync_item_to_inode_entry(
+            item,
+            InodeNumber::new(ino),
+            InodeNumber::new(parent_ino),
+            &self.config,
+        );
+        self.inode_table.insert(entry);
+        // Its children are loaded on first access
+        if item.is_directory() {
+            self.inode_table.mark_partial(ino);
+        }
+        self.inode_table.get(ino)
+    }
+
+    /// Reloads child `name` of directory `parent` after it was evicted from
+    /// the inode table.
+    fn reload_child(&self, parent: u64, name: &str) -> Option<Arc<InodeEntry>> {
+        let repository = SqliteStateRepository::new(self.db_pool.pool().clone());
+        let dir_path = self.dir_local_path(&repository, parent)?;
+        let path = SyncPath::new(dir_path.as_path().join(name)).ok()?;
+        let item = match self.rt_handle.block_on(repository.get_item_by_path(&path)) {
+            Ok(item) => item?,
+            Err(e) => {
+                warn!("Failed to reload {}: {}", path, e);
+                return None;
+            }
+        };
+        let entry = self.insert_reloaded(&item, parent)?;
+        // The table knows the item under another name (e.g. renamed since)
+        (entry.parent_ino().get() == parent && entry.name() == name).then_some(entry)
+    }
+
+    /// Reloads the children of directory `dir` evicted from the inode table.
+    ///
+    /// The reloaded entries are not referenced by the kernel, so they are
+    /// queued for eviction again.
+    fn reload_children(&self, dir: u64) {
+        let repository = SqliteStateRepository::new(self.db_pool.pool().clone());
+        let Some(dir_path) = self.dir_local_path(&repository, dir) else {
+            return;
+        };
+        let filter = ItemFilter::new().with_path_prefix(dir_path.clone());
+        let items = match self.rt_handle.block_on(repository.query_items(&filter)) {
+            Ok(items) => items,
+            Err(e) => {
+                warn!("Failed to reload children of inode {}: {}", dir, e);
+                return;
+            }
+        };
+
+        let mut reloaded = 0;
+        for item in items
+            .iter()
+            .filter(|item| item.local_path().as_path().parent() == Some(dir_path.as_path()))
+        {
+            if self.inode_table.get_by_item_id(item.id()).is_some() {
+                continue;
+            }
+            if let Some(entry) = self.insert_reloaded(item, dir) {
+                self.inode_table.mark_forgotten(entry.ino().get());
+                reloaded += 1;
+            }
+        }
+        self.inode_table.mark_complete(dir);
+        debug!("reloaded {} evicted children of inode {}", reloaded, dir);
+    }
+
     /// Builds the full local path for a new file given its parent inode and name.
     ///
     /// This method traverses the inode hierarchy from the parent up to the root
@@ -2804,6 +3357,218 @@ impl LnxDriveFs {
 
         format!("/{}", components.join("/"))
     }
+
+    /// Applies the `O_TRUNC` and `O_APPEND` flags of an open of `entry` with
+    /// handle `fh`.
+    ///
+    /// Returns the entry as updated by the truncation, or the errno to
+    /// reply with.
+    async fn apply_open_flags(
+        &self,
+        entry: Arc<InodeEntry>,
+        fh: u64,
+        flags: i32,
+    ) -> Result<Arc<InodeEntry>, c_int> {
+        let writable = flags & libc::O_ACCMODE != libc::O_RDONLY;
+        let entry = if writable && flags & libc::O_TRUNC != 0 {
+            self.truncate_content(entry.ino().get(), 0).await?
+        } else {
+            entry
+        };
+        if flags & libc::O_APPEND != 0 {
+            self.append_handles.insert(fh);
+        }
+        Ok(entry)
+    }
+
+    /// Returns the errno refusing to open `entry` with `flags` because it
+    /// is too large for the cache, if it is.
+    ///
+    /// Depending on `fuse.oversized_files` such a file is rejected, or
+    /// streamed read-only. Truncating it on open replaces its content
+    /// without downloading it, so that is always allowed.
+    fn refuse_oversized(&self, entry: &InodeEntry, flags: i32) -> Option<c_int> {
+        if !matches!(entry.state(), ItemState::Online) {
+            return None;
+        }
+        let action = self
+            .hydration_manager
+            .as_ref()?
+            .oversized_action(entry.size())?;
+        let writable = flags & libc::O_ACCMODE != libc::O_RDONLY;
+        if writable && flags & libc::O_TRUNC != 0 {
+            return None;
+        }
+        if action == OversizedFileAction::Reject || writable {
+            warn!(
+                "open: inode {} ({} bytes) is larger than fuse.max_cached_file_mb allows, refusing to {}",
+                entry.ino().get(),
+                entry.size(),
+                if writable { "write it" } else { "open it" }
+            );
+            return Some(libc::EFBIG);
+        }
+        None
+    }
+
+    /// Returns true if opening `entry` with `flags` needs its content
+    /// downloaded while no cloud provider is attached.
+    ///
+    /// Truncating a placeholder on open replaces its content without
+    /// downloading it, so that still works.
+    fn needs_provider(&self, entry: &InodeEntry, flags: i32) -> bool {
+        if self.hydration_manager.is_some() || !matches!(entry.state(), ItemState::Online) {
+            return false;
+        }
+        let writable = flags & libc::O_ACCMODE != libc::O_RDONLY;
+        !(writable && flags & libc::O_TRUNC != 0)
+    }
+
+    /// Reports that the content of `entry` can't be downloaded because no
+    /// cloud provider is attached, and returns the errno for it.
+    ///
+    /// `ENOTCONN` sets this apart from files that are unreadable for other
+    /// reasons (`EIO`). The first request for each item is recorded in the
+    /// audit log.
+    async fn report_unreachable(&self, entry: &InodeEntry) -> c_int {
+        warn!(
+            "inode {} is not hydrated and no cloud provider is attached",
+            entry.ino().get()
+        );
+        if self.unreachable_reported.insert(*entry.item_id()) {
+            let audit = AuditEntry::new(
+                AuditAction::FileDownload,
+                AuditResult::failed("no_provider", "no cloud provider attached"),
+            )
+            .with_item_id(*entry.item_id())
+            .with_details(serde_json::json!({
+                "path": self
+                    .build_local_path(entry.parent_ino().get(), entry.name())
+                    .display()
+                    .to_string(),
+            }));
+            if let Err(e) = self.write_handle.save_audit(audit).await {
+                warn!(error = %e, "Failed to record the missing provider in the audit log");
+            }
+        }
+        libc::ENOTCONN
+    }
+
+    /// Truncates the content of file `ino` to `size` bytes and marks it
+    /// `Modified`.
+    ///
+    /// Local content is resized in the cache. An `Online` file can only be
+    /// truncated to zero: its content is replaced without downloading it.
+    /// Returns the updated entry, or the errno to reply with.
+    async fn truncate_content(&self, ino: u64, size: u64) -> Result<Arc<InodeEntry>, c_int> {
+        let entry = self.inode_table.get(ino).ok_or(libc::ENOENT)?;
+        if entry.kind() == FileType::Directory {
+            return Err(libc::EISDIR);
+        }
+        let overwrite = match entry.state() {
+            ItemState::Hydrated | ItemState::Pinned | ItemState::Modified => false,
+            ItemState::Online if size == 0 => true,
+            state => {
+                debug!(
+                    "truncate: inode {} is {:?}, its content is not local",
+                    ino, state
+                );
+                return Err(libc::EIO);
+            }
+        };
+
+        match entry.remote_id() {
+            Some(remote_id) => {
+                self.cache.truncate(remote_id, size).map_err(|e| {
+                    warn!("truncate: failed to resize cache for inode {}: {}", ino, e);
+                    libc::EIO
+                })?;
+                if overwrite {
+                    // Ranges of an earlier partial download are stale now
+                    let _ = self.cache.clear_ranges(remote_id);
+                }
+            }
+            // New files have nothing cached until their first write
+            None if size == 0 => {}
+            None => return Err(libc::EIO),
+        }
+
+        let result = if overwrite {
+            self.save_overwritten(*entry.item_id()).await
+        } else if !matches!(entry.state(), ItemState::Modified) {
+            self.write_handle
+                .update_state(*entry.item_id(), ItemState::Modified)
+                .await
+                .map_err(Into::into)
+        } else {
+            Ok(())
+        };
+        if let Err(e) = result {
+            warn!("truncate: failed to mark inode {} modified: {}", ino, e);
+            return Err(libc::EIO);
+        }
+
+        self.inode_table
+            .insert(entry.with_size(size).with_state(ItemState::Modified));
+        self.inode_table.get(ino).ok_or(libc::ENOENT)
+    }
+
+    /// Marks an `Online` item whose content was replaced locally as
+    /// `Modified`, with no content.
+    ///
+    /// The state machine has no Online -> Modified transition, as files
+    /// are normally hydrated before they change, so the state is reset
+    /// like for a newly created file.
+    async fn save_overwritten(&self, item_id: UniqueId) -> anyhow::Result<()> {
+        let repository = SqliteStateRepository::new(self.db_pool.pool().clone())
+            .with_transition_trigger(TransitionTrigger::User);
+        let mut item = repository
+            .get_item(&item_id)
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("item {} not found", item_id))?;
+        item.reset_state_for_crash_recovery(ItemState::Modified);
+        item.set_size_bytes(0);
+        self.write_handle.save_item(item).await?;
+        Ok(())
+    }
+
+    /// Returns the offset a write through `fh` goes to: the end of the
+    /// cached content for handles opened with `O_APPEND`, else `offset`.
+    fn write_offset(&self, fh: u64, remote_id: &RemoteId, offset: u64) -> u64 {
+        if !self.append_handles.contains(&fh) {
+            return offset;
+        }
+        std::fs::metadata(self.cache.cache_path(remote_id))
+            .map(|m| m.len())
+            .unwrap_or(0)
+    }
+
+    /// Loads the OneDrive facets of `entry`'s item for the facet xattrs.
+    fn item_facets(&self, entry: &InodeEntry) -> Option<ItemFacets> {
+        let repository = SqliteStateRepository::new(self.db_pool.pool().clone());
+        match self
+            .rt_handle
+            .block_on(repository.get_item(entry.item_id()))
+        {
+            Ok(item) => item.map(|item| item.metadata().facets().clone()),
+            Err(e) => {
+                warn!("Failed to load facets of inode {}: {}", entry.ino().get(), e);
+                None
+            }
+        }
+    }
+
+    /// Stores a mode set with chmod on the item, so it is restored on remount.
+    async fn save_unix_mode(&self, item_id: UniqueId, perm: u16) -> anyhow::Result<()> {
+        let repository = SqliteStateRepository::new(self.db_pool.pool().clone());
+        let mut item = repository
+            .get_item(&item_id)
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("item {} not found", item_id))?;
+        item.set_unix_mode(Some(u32::from(perm)));
+        self.write_handle.save_item(item).await?;
+        Ok(())
+    }
 }
 
 // ============================================================================
@@ -2851,6 +3616,28 @@ mod tests {
 
     use super::*;
 
+    #[test]
+    fn test_statfs_capacity_prefers_quota() {
+        const GB: u64 = 1024 * 1024 * 1024;
+        let cache = (10 * GB, GB);
+
+        let quota = DriveQuota::new(3 * GB, 5 * GB);
+        assert_eq!(
+            statfs_capacity(Some(quota), cache.0, cache.1),
+            (5 * GB, 3 * GB)
+        );
+        // Over quota never reports more used than total
+        let over = DriveQuota::new(6 * GB, 5 * GB);
+        assert_eq!(
+            statfs_capacity(Some(over), cache.0, cache.1),
+            (5 * GB, 5 * GB)
+        );
+        // Unlimited or not fetched yet: the cache
+        let unlimited = DriveQuota::unlimited(3 * GB);
+        assert_eq!(statfs_capacity(Some(unlimited), cache.0, cache.1), cache);
+        assert_eq!(statfs_capacity(None, cache.0, cache.1), cache);
+    }
+
     /// Helper to create an in-memory test setup
     async fn create_test_setup() -> (Handle, DatabasePool, FuseConfig, Arc<ContentCache>) {
         let pool = DatabasePool::in_memory().await.unwrap();
@@ -3045,7 +3832,7 @@ mod tests {
                 String::new(),
                 FileType::Directory,
                 0,
-                0o755,
+                fs.config.directory_perm(),
                 SystemTime::now(),
                 SystemTime::now(),
                 SystemTime::now(),
@@ -3067,6 +3854,10 @@ mod tests {
                         .increment_inode_counter()
                         .await
                         .map_err(|_| libc::EIO)?;
+                    fs.write_handle()
+                        .update_inode(*item.id(), new_ino)
+                        .await
+                        .map_err(|_| libc::EIO)?;
                     InodeNumber::new(new_ino)
                 };
 
@@ -3086,7 +3877,7 @@ mod tests {
                     .copied()
                     .unwrap_or(InodeNumber::ROOT);
 
-                let entry = sync_item_to_inode_entry(&item, ino, parent_ino);
+                let entry = sync_item_to_inode_entry(&item, ino, parent_ino, &fs.config);
                 fs.inode_table().insert(entry);
             }
 
@@ -3133,6 +3924,69 @@ mod tests {
             assert_eq!(found_folder.unwrap().kind(), FileType::Directory);
         }
 
+        #[tokio::test(flavor = "multi_thread")]
+        async fn test_evicted_inodes_are_reloaded_from_db() {
+            let (rt_handle, db_pool, mut config, cache, repo) =
+                create_test_setup_with_account().await;
+            config.max_inodes = 3;
+
+            let dir = SyncItem::new_directory(
+                SyncPath::new(PathBuf::from("/home/user/OneDrive/docs")).unwrap(),
+                RemotePath::new("/docs".to_string()).unwrap(),
+            )
+            .unwrap();
+            repo.save_item(&dir).await.unwrap();
+            let mut files = Vec::new();
+            for name in ["a.txt", "b.txt"] {
+                let file = SyncItem::new_file(
+                    SyncPath::new(PathBuf::from(format!("/home/user/OneDrive/docs/{}", name)))
+                        .unwrap(),
+                    RemotePath::new(format!("/docs/{}", name)).unwrap(),
+                    1024,
+                    None,
+                )
+                .unwrap();
+                repo.save_item(&file).await.unwrap();
+                files.push(file);
+            }
+
+            let fs = LnxDriveFs::new(rt_handle, db_pool, config, cache, None);
+            simulate_init(&fs).await.unwrap();
+            assert_eq!(fs.inode_table().len(), 4);
+            let docs = fs.lookup_entry(InodeNumber::ROOT.get(), "docs").unwrap();
+            let docs = docs.ino().get();
+
+            // The kernel forgets both files; the oldest one is evicted
+            for name in ["a.txt", "b.txt"] {
+                let entry = fs.lookup_entry(docs, name).unwrap();
+                fs.inode_table().mark_forgotten(entry.ino().get());
+            }
+            assert_eq!(fs.inode_table().evict_forgotten(), 1);
+            assert!(fs.lookup_entry(docs, "a.txt").is_none());
+            assert!(fs.inode_table().is_partial(docs));
+
+            // A lookup of the evicted name reloads it from the database
+            let reloaded = tokio::task::block_in_place(|| fs.reload_child(docs, "a.txt")).unwrap();
+            assert_eq!(reloaded.item_id(), files[0].id());
+            assert_eq!(reloaded.size(), 1024);
+            assert!(tokio::task::block_in_place(|| fs.reload_child(docs, "none.txt")).is_none());
+
+            // A readdir reloads every evicted child
+            fs.inode_table().mark_forgotten(reloaded.ino().get());
+            let other = fs.lookup_entry(docs, "b.txt").unwrap();
+            fs.inode_table().mark_forgotten(other.ino().get());
+            assert_eq!(fs.inode_table().evict_forgotten(), 1);
+            tokio::task::block_in_place(|| fs.reload_children(docs));
+            let mut names: Vec<String> = fs
+                .get_children(docs)
+                .iter()
+                .map(|entry| entry.name().to_string())
+                .collect();
+            names.sort();
+            assert_eq!(names, vec!["a.txt", "b.txt"]);
+            assert!(!fs.inode_table().is_partial(docs));
+        }
+
         #[tokio::test]
         async fn test_init_root_inode_is_1() {
             let (rt_handle, db_pool, config, cache, _repo) = create_test_setup_with_account().await;
@@ -3151,6 +4005,115 @@ mod tests {
             assert_eq!(root_entry.name(), ""); // Root has no name
         }
 
+        #[tokio::test]
+        async fn test_chmod_mode_survives_remount() {
+            let (rt_handle, db_pool, mut config, cache, repo) =
+                create_test_setup_with_account().await;
+            config.preserve_permissions = true;
+
+            let script = SyncItem::new_file(
+                SyncPath::new(PathBuf::from("/home/user/OneDrive/run.sh")).unwrap(),
+                RemotePath::new("/run.sh".to_string()).unwrap(),
+                64,
+                None,
+            )
+            .unwrap();
+            repo.save_item(&script).await.unwrap();
+
+            let fs = LnxDriveFs::new(
+                rt_handle.clone(),
+                db_pool.clone(),
+                config.clone(),
+                Arc::clone(&cache),
+                None,
+            );
+            simulate_init(&fs).await.unwrap();
+            let entry = fs.lookup_entry(InodeNumber::ROOT.get(), "run.sh").unwrap();
+            assert_eq!(entry.perm(), 0o644);
+
+            // chmod +x
+            entry.increment_lookup();
+            fs.save_unix_mode(*script.id(), 0o755).await.unwrap();
+            fs.insert_entry(entry.with_perm(0o755));
+            let updated = fs.get_entry(entry.ino().get()).unwrap();
+            assert_eq!(updated.perm(), 0o755);
+            assert_eq!(updated.lookup_count(), 1);
+
+            // A new mount restores the mode from the database
+            let remounted = LnxDriveFs::new(
+                rt_handle.clone(),
+                db_pool.clone(),
+                config.clone(),
+                Arc::clone(&cache),
+                None,
+            );
+            simulate_init(&remounted).await.unwrap();
+            let entry = remounted
+                .lookup_entry(InodeNumber::ROOT.get(), "run.sh")
+                .unwrap();
+            assert_eq!(entry.perm(), 0o755);
+
+            // Without the option the stored mode is ignored
+            config.preserve_permissions = false;
+            let plain = LnxDriveFs::new(rt_handle, db_pool, config, cache, None);
+            simulate_init(&plain).await.unwrap();
+            let entry = plain
+                .lookup_entry(InodeNumber::ROOT.get(), "run.sh")
+                .unwrap();
+            assert_eq!(entry.perm(), 0o644);
+        }
+
+        #[tokio::test]
+        async fn test_init_applies_configured_modes() {
+            let (rt_handle, db_pool, mut config, cache, repo) =
+                create_test_setup_with_account().await;
+            config.file_mode = "600".to_string();
+            config.directory_mode = "0700".to_string();
+
+            let file = SyncItem::new_file(
+                SyncPath::new(PathBuf::from("/home/user/OneDrive/private.txt")).unwrap(),
+                RemotePath::new("/private.txt".to_string()).unwrap(),
+                128,
+                None,
+            )
+            .unwrap();
+            let dir = SyncItem::new_directory(
+                SyncPath::new(PathBuf::from("/home/user/OneDrive/Private")).unwrap(),
+                RemotePath::new("/Private".to_string()).unwrap(),
+            )
+            .unwrap();
+            repo.save_item(&file).await.unwrap();
+            repo.save_item(&dir).await.unwrap();
+
+            let fs = LnxDriveFs::new(rt_handle, db_pool, config, cache, None);
+            simulate_init(&fs).await.unwrap();
+
+            let root = fs.get_entry(InodeNumber::ROOT.get()).unwrap();
+            assert_eq!(root.perm(), 0o700);
+            let entry = fs
+                .lookup_entry(InodeNumber::ROOT.get(), "private.txt")
+                .unwrap();
+            assert_eq!(entry.perm(), 0o600);
+            let entry = fs.lookup_entry(InodeNumber::ROOT.get(), "Private").unwrap();
+            assert_eq!(entry.perm(), 0o700);
+        }
+
+        #[test]
+        fn test_new_item_perms_are_limited_by_configured_modes() {
+            // Defaults leave the umask in charge
+            assert_eq!(new_file_perm(0o666, 0o022, 0o644), 0o644);
+            assert_eq!(new_file_perm(0o777, 0o022, 0o644), 0o755);
+            assert_eq!(new_directory_perm(0o777, 0o022, 0o755), 0o755);
+
+            // Private modes strip group and other bits
+            assert_eq!(new_file_perm(0o666, 0o022, 0o600), 0o600);
+            assert_eq!(new_file_perm(0o777, 0o022, 0o600), 0o700);
+            assert_eq!(new_directory_perm(0o777, 0o002, 0o700), 0o700);
+
+            // The umask still applies
+            assert_eq!(new_file_perm(0o666, 0o077, 0o644), 0o600);
+        }
+
         #[tokio::test]
         async fn test_init_inode_assignment_for_new_items() {
             let (rt_handle, db_pool, config, cache, repo) = create_test_setup_with_account().await;
@@ -3199,14 +4162,8 @@ mod tests {
 
         #[tokio::test]
         async fn test_init_remount_preserves_existing_inodes() {
-            // NOTE: This test verifies the expected behavior for re-mount inode preservation.
-            // The init logic checks item.inode() and if set, uses that value.
-            // If item.inode() returns None (even if saved in DB), a new inode is allocated.
-            //
-            // This test verifies that when the repository properly returns the inode,
-            // it will be preserved. Currently this relies on the repository's
-            // sync_item_from_row() correctly loading the inode field from the database.
-
+            // init() stores the inode it allocates on the item and reuses
+            // the stored number on the next mount
             let (rt_handle, db_pool, config, cache, repo) = create_test_setup_with_account().await;
 
             // Create an item first (without inode)
@@ -3220,7 +4177,13 @@ mod tests {
 
             repo.save_item(&item).await.unwrap();
 
-            let fs = LnxDriveFs::new(rt_handle, db_pool, config, cache, None);
+            let fs = LnxDriveFs::new(
+                rt_handle.clone(),
+                db_pool.clone(),
+                config.clone(),
+                Arc::clone(&cache),
+                None,
+            );
 
             // Simulate init() - should assign a new inode since item doesn't have one
             simulate_init(&fs).await.unwrap();
@@ -3243,6 +4206,17 @@ mod tests {
                 .lookup_entry(InodeNumber::ROOT.get(), "preserved.txt")
                 .unwrap();
             assert_eq!(found.ino().get(), found_again.ino().get());
+
+            // The sync engine saving a copy without the inode keeps it
+            repo.save_item(&item).await.unwrap();
+
+            // A new mount hands out the same number
+            let remounted = LnxDriveFs::new(rt_handle, db_pool, config, cache, None);
+            simulate_init(&remounted).await.unwrap();
+            let found = remounted
+                .lookup_entry(InodeNumber::ROOT.get(), "preserved.txt")
+                .unwrap();
+            assert_eq!(found.ino().get(), assigned_inode);
         }
 
         #[tokio::test]
@@ -4522,6 +5496,229 @@ mod tests {
         use super::*;
         use lnxdrive_core::domain::sync_item::ItemState;
 
+        /// Saves a file in `state` (Online or Hydrated with `content`
+        /// cached) and registers it as inode 10
+        async fn add_file(
+            fs: &LnxDriveFs,
+            repo: &SqliteStateRepository,
+            state: ItemState,
+            content: &[u8],
+        ) -> (SyncItem, Arc<InodeEntry>) {
+            let mut item = SyncItem::new_file(
+                SyncPath::new(PathBuf::from("/home/user/OneDrive/notes.txt")).unwrap(),
+                RemotePath::new("/notes.txt".to_string()).unwrap(),
+                content.len() as u64,
+                None,
+            )
+            .unwrap();
+            let remote_id = RemoteId::new("remote_notes".to_string()).unwrap();
+            item.set_remote_id(remote_id.clone());
+            if state == ItemState::Hydrated {
+                item.start_hydrating().unwrap();
+                item.complete_hydration().unwrap();
+                fs.cache.store(&remote_id, content).unwrap();
+            }
+            repo.save_item(&item).await.unwrap();
+
+            let now = SystemTime::now();
+            fs.insert_entry(make_test_entry(1, 1, "", true));
+            fs.insert_entry(InodeEntry::new(
+                InodeNumber::new(10),
+                *item.id(),
+                Some(remote_id),
+                InodeNumber::ROOT,
+                "notes.txt".to_string(),
+                FileType::RegularFile,
+                content.len() as u64,
+                0o644,
+                now,
+                now,
+                now,
+                1,
+                state,
+            ));
+            (item, fs.get_entry(10).unwrap())
+        }
+
+        async fn stored_state(repo: &SqliteStateRepository, item: &SyncItem) -> ItemState {
+            repo.get_item(item.id())
+                .await
+                .unwrap()
+                .unwrap()
+                .state()
+                .clone()
+        }
+
+        #[tokio::test]
+        async fn test_open_with_o_trunc_empties_hydrated_file() {
+            let (rt_handle, db_pool, config, cache, repo) = create_test_setup_with_account().await;
+            let fs = LnxDriveFs::new(rt_handle, db_pool, config, Arc::clone(&cache), None);
+            let (item, entry) = add_file(&fs, &repo, ItemState::Hydrated, b"Hello, World!").await;
+            let remote_id = item.remote_id().unwrap();
+
+            // A read-only open ignores O_TRUNC
+            let entry = fs
+                .apply_open_flags(entry, 1, libc::O_RDONLY | libc::O_TRUNC)
+                .await
+                .unwrap();
+            assert_eq!(entry.size(), 13);
+            assert_eq!(entry.state(), &ItemState::Hydrated);
+
+            let entry = fs
+                .apply_open_flags(entry, 2, libc::O_RDWR | libc::O_TRUNC)
+                .await
+                .unwrap();
+            assert_eq!(entry.size(), 0);
+            assert_eq!(entry.state(), &ItemState::Modified);
+            assert_eq!(fs.get_entry(10).unwrap().size(), 0);
+            assert!(cache.read(remote_id, 0, 100).unwrap().is_empty());
+            assert_eq!(stored_state(&repo, &item).await, ItemState::Modified);
+        }
+
+        #[tokio::test]
+        async fn test_open_with_o_trunc_overwrites_online_file_without_hydrating() {
+            let (rt_handle, db_pool, config, cache, repo) = create_test_setup_with_account().await;
+            let fs = LnxDriveFs::new(rt_handle, db_pool, config, Arc::clone(&cache), None);
+            let (item, entry) = add_file(&fs, &repo, ItemState::Online, &[0u8; 4096]).await;
+            let remote_id = item.remote_id().unwrap();
+            assert!(!cache.exists(remote_id));
+
+            let entry = fs
+                .apply_open_flags(entry, 1, libc::O_WRONLY | libc::O_TRUNC)
+                .await
+                .unwrap();
+            assert_eq!(entry.size(), 0);
+            assert_eq!(entry.state(), &ItemState::Modified);
+            assert!(cache.exists(remote_id));
+            assert_eq!(stored_state(&repo, &item).await, ItemState::Modified);
+
+            // Writes now go to the cache like for any local file
+            cache.write_at(remote_id, 0, b"new").unwrap();
+            assert_eq!(cache.read(remote_id, 0, 100).unwrap(), b"new");
+        }
+
+        #[tokio::test]
+        async fn test_online_file_cannot_be_truncated_to_a_nonzero_size() {
+            let (rt_handle, db_pool, config, cache, repo) = create_test_setup_with_account().await;
+            let fs = LnxDriveFs::new(rt_handle, db_pool, config, cache, None);
+            let (item, _) = add_file(&fs, &repo, ItemState::Online, &[0u8; 4096]).await;
+
+            assert_eq!(fs.truncate_content(10, 100).await.unwrap_err(), libc::EIO);
+            assert_eq!(stored_state(&repo, &item).await, ItemState::Online);
+        }
+
+        #[tokio::test]
+        async fn test_online_file_without_provider_is_not_connected() {
+            let (rt_handle, db_pool, config, cache, repo) = create_test_setup_with_account().await;
+            let fs = LnxDriveFs::new(rt_handle, db_pool, config, cache, None);
+            let (item, entry) = add_file(&fs, &repo, ItemState::Online, &[0u8; 4096]).await;
+
+            // Reading needs a download, truncating does not
+            assert!(fs.needs_provider(&entry, libc::O_RDONLY));
+            assert!(fs.needs_provider(&entry, libc::O_RDWR));
+            assert!(!fs.needs_provider(&entry, libc::O_WRONLY | libc::O_TRUNC));
+            let dir = make_test_entry(11, 1, "docs", true);
+            assert!(!fs.needs_provider(&dir, libc::O_RDONLY));
+
+            // Repeated requests are audited once
+            assert_eq!(fs.report_unreachable(&entry).await, libc::ENOTCONN);
+            assert_eq!(fs.report_unreachable(&entry).await, libc::ENOTCONN);
+            let trail = repo.get_audit_trail(item.id()).await.unwrap();
+            assert_eq!(trail.len(), 1);
+            assert_eq!(trail[0].action(), &AuditAction::FileDownload);
+            assert_eq!(
+                trail[0].result(),
+                &AuditResult::failed("no_provider", "no cloud provider attached")
+            );
+
+            let value = xattr::provider_xattr(fs.hydration_manager().is_some());
+            assert_eq!(value, b"none".to_vec());
+        }
+
+        #[tokio::test]
+        async fn test_attached_provider_hydrates_online_files() {
+            use lnxdrive_graph::client::GraphClient;
+            use wiremock::{
+                matchers::{method, path},
+                Mock, MockServer, ResponseTemplate,
+            };
+
+            let server = MockServer::start().await;
+            Mock::given(method("GET"))
+                .and(path("/me/drive/items/remote_notes"))
+                .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                    "@microsoft.graph.downloadUrl": format!("{}/content/notes", server.uri()),
+                    "size": 14,
+                })))
+                .mount(&server)
+                .await;
+            Mock::given(method("GET"))
+                .and(path("/content/notes"))
+                .respond_with(ResponseTemplate::new(200).set_body_bytes(b"from the cloud".to_vec()))
+                .mount(&server)
+                .await;
+
+            let (rt_handle, db_pool, mut config, cache, repo) =
+                create_test_setup_with_account().await;
+            config.max_cached_file_mb = 1;
+            config.oversized_files = "reject".to_string();
+            let mut fs = LnxDriveFs::new(rt_handle, db_pool, config, Arc::clone(&cache), None);
+            let provider = Arc::new(GraphCloudProvider::new(GraphClient::with_base_url(
+                "token",
+                server.uri(),
+            )));
+            fs.attach_provider(provider, None);
+            let (item, entry) = add_file(&fs, &repo, ItemState::Online, b"from the cloud").await;
+            assert!(!fs.needs_provider(&entry, libc::O_RDONLY));
+
+            // The manager follows the mount's configuration
+            let manager = fs.hydration_manager().unwrap();
+            assert_eq!(
+                manager.oversized_action(2 * 1024 * 1024),
+                Some(OversizedFileAction::Reject)
+            );
+
+            let remote_id = item.remote_id().unwrap();
+            manager
+                .hydrate(
+                    10,
+                    *item.id(),
+                    remote_id.clone(),
+                    14,
+                    HydrationPriority::UserOpen,
+                )
+                .await
+                .unwrap();
+            for _ in 0..100 {
+                if stored_state(&repo, &item).await == ItemState::Hydrated {
+                    break;
+                }
+                tokio::time::sleep(Duration::from_millis(20)).await;
+            }
+            assert_eq!(stored_state(&repo, &item).await, ItemState::Hydrated);
+            assert_eq!(cache.read(remote_id, 0, 100).unwrap(), b"from the cloud");
+        }
+
+        #[tokio::test]
+        async fn test_o_append_writes_go_to_end_of_file() {
+            let (rt_handle, db_pool, config, cache, repo) = create_test_setup_with_account().await;
+            let fs = LnxDriveFs::new(rt_handle, db_pool, config, Arc::clone(&cache), None);
+            let (item, entry) = add_file(&fs, &repo, ItemState::Hydrated, b"Hello").await;
+            let remote_id = item.remote_id().unwrap();
+
+            fs.apply_open_flags(Arc::clone(&entry), 1, libc::O_WRONLY | libc::O_APPEND)
+                .await
+                .unwrap();
+            fs.apply_open_flags(entry, 2, libc::O_WRONLY).await.unwrap();
+
+            let offset = fs.write_offset(1, remote_id, 0);
+            assert_eq!(offset, 5);
+            cache.write_at(remote_id, offset, b", World!").unwrap();
+            assert_eq!(fs.write_offset(1, remote_id, 2), 13);
+            assert_eq!(fs.write_offset(2, remote_id, 2), 2);
+            assert_eq!(cache.read(remote_id, 0, 100).unwrap(), b"Hello, World!");
+        }
+
         #[tokio::test]
         async fn test_write_requires_hydrated_state() {
             // Files with Online state should return EIO (need hydration first)
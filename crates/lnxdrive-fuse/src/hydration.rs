@@ -8,16 +8,22 @@
 //! The `HydrationManager` coordinates concurrent file downloads while ensuring:
 //!
 //! - **Deduplication**: Multiple readers of the same file share a single download
-//! - **Concurrency limiting**: Configurable maximum parallel downloads
+//! - **Concurrency limiting**: Configurable maximum parallel downloads,
+//!   handed out by [`HydrationPriority`] so opened files skip the prefetch queue
 //! - **Progress tracking**: Watch channels for real-time progress updates
 //! - **Cancellation support**: In-flight downloads can be cancelled
+//! - **Streaming**: Large files are fetched in ranges, starting where
+//!   readers are waiting, so a read is served as soon as its bytes land
+//! - **Oversized files**: Files larger than the cache allows are never
+//!   hydrated; depending on [`OversizedFileAction`] they are rejected or
+//!   their reads are served straight from the cloud
 //!
 //! ```text
 //! ┌───────────────┐     hydrate()      ┌─────────────────────┐
 //! │  FUSE reader  │ ─────────────────► │  HydrationManager   │
 //! │   (waiting)   │                    │                     │
 //! └───────────────┘                    │  active: DashMap    │
-//!        │                             │  semaphore: permits │
+//!        │                             │  slots: by priority │
 //!        │  watch::Receiver            │                     │
 //!        │◄────────────────────────────│                     │
 //!        │                             └─────────────────────┘
@@ -36,26 +42,39 @@
 //! ```
 
 use std::{
+    collections::BTreeSet,
     fmt,
+    ops::Range,
     path::{Path, PathBuf},
     sync::{
-        atomic::{AtomicU64, Ordering},
-        Arc,
+        atomic::{AtomicU64, AtomicU8, Ordering},
+        Arc, Mutex,
     },
 };
 
 use chrono::{DateTime, Utc};
-use dashmap::DashMap;
-use lnxdrive_core::domain::{sync_item::ItemState, RemoteId, UniqueId};
+use dashmap::{mapref::entry::Entry, DashMap};
+use lnxdrive_core::{
+    domain::{sync_item::ItemState, FileHash, QuickXorHash, RemoteId, SyncItem, UniqueId},
+    ports::{
+        HydrationProgressReporter, IHydrationObserver, ITransferObserver, TransferKind,
+        TransferProgressReporter,
+    },
+};
 use lnxdrive_graph::provider::GraphCloudProvider;
 use tokio::{
     runtime::Handle,
-    sync::{watch, Semaphore},
+    sync::{oneshot, watch},
     task::JoinHandle,
 };
 use tokio_util::sync::CancellationToken;
 
-use crate::{cache::ContentCache, error::FuseError, write_serializer::WriteSerializerHandle};
+use crate::{
+    cache::{hash_file_prefix, ContentCache},
+    error::FuseError,
+    range_map::RangeMap,
+    write_serializer::WriteSerializerHandle,
+};
 
 // ============================================================================
 // HydrationPriority
@@ -75,6 +94,45 @@ pub enum HydrationPriority {
     UserOpen = 2,
 }
 
+impl HydrationPriority {
+    /// Converts the value stored by [`HydrationRequest`] back.
+    fn from_u8(value: u8) -> Self {
+        match value {
+            0 => Self::Prefetch,
+            1 => Self::PinRequest,
+            _ => Self::UserOpen,
+        }
+    }
+}
+
+// ============================================================================
+// OversizedFileAction
+// ============================================================================
+
+/// What happens to a file too large to be cached.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum OversizedFileAction {
+    /// Refuse to open it with `EFBIG` ("File too large").
+    Reject,
+    /// Serve its reads from the cloud range by range, caching nothing.
+    /// Such files are read-only.
+    #[default]
+    Stream,
+}
+
+impl OversizedFileAction {
+    /// Parse the `fuse.oversized_files` value (`reject` or `stream`).
+    ///
+    /// Unknown values fall back to the default; the configuration
+    /// validation reports them.
+    pub fn from_config_value(value: &str) -> Self {
+        match value {
+            "reject" => Self::Reject,
+            _ => Self::Stream,
+        }
+    }
+}
+
 // ============================================================================
 // HydrationRequest
 // ============================================================================
@@ -94,16 +152,39 @@ pub struct HydrationRequest {
     pub total_size: u64,
     /// Bytes downloaded so far (atomically updated)
     downloaded: AtomicU64,
+    /// Byte ranges of the partial file that are present
+    present: Mutex<RangeMap>,
+    /// Offsets readers are waiting for, fetched first when streaming
+    wanted: Mutex<BTreeSet<u64>>,
+    /// Where the last read ended, to tell sequential from random access
+    last_read_end: Mutex<Option<u64>>,
     /// Path to the cache file
     pub cache_path: PathBuf,
-    /// Request priority
-    pub priority: HydrationPriority,
+    /// Request priority, raised when a more urgent caller joins
+    priority: AtomicU8,
     /// When the request was created
     pub created_at: DateTime<Utc>,
     /// Channel to send progress updates (0-100%)
     progress_tx: watch::Sender<u8>,
+    /// Reports byte progress to a transfer observer, if any
+    reporter: Option<Arc<TransferProgressReporter>>,
+    /// Reports percent progress to a hydration observer, if any
+    hydration_reporter: Option<Arc<HydrationProgressReporter>>,
+    /// Whether the item ends up `Pinned` or `Hydrated` (see [`Self::request_pin`])
+    pin_outcome: AtomicU8,
+    /// `None` while downloading, then whether the hydration succeeded
+    finished_tx: watch::Sender<Option<bool>>,
 }
 
+/// No pin requested yet, the download has not finished
+const PIN_NOT_REQUESTED: u8 = 0;
+/// A pin was requested before the download finished
+const PIN_REQUESTED: u8 = 1;
+/// The download finished and the item was marked `Hydrated`
+const PIN_SETTLED_HYDRATED: u8 = 2;
+/// The download finished and the item was marked `Pinned`
+const PIN_SETTLED_PINNED: u8 = 3;
+
 impl HydrationRequest {
     /// Create a new hydration request.
     ///
@@ -127,20 +208,60 @@ impl HydrationRequest {
         priority: HydrationPriority,
     ) -> (Self, watch::Receiver<u8>) {
         let (progress_tx, progress_rx) = watch::channel(0u8);
+        let pin_outcome = if priority == HydrationPriority::PinRequest {
+            PIN_REQUESTED
+        } else {
+            PIN_NOT_REQUESTED
+        };
         let request = Self {
             ino,
             item_id,
             remote_id,
             total_size,
             downloaded: AtomicU64::new(0),
+            present: Mutex::new(RangeMap::new()),
+            wanted: Mutex::new(BTreeSet::new()),
+            last_read_end: Mutex::new(None),
             cache_path,
-            priority,
+            priority: AtomicU8::new(priority as u8),
             created_at: Utc::now(),
             progress_tx,
+            reporter: None,
+            hydration_reporter: None,
+            pin_outcome: AtomicU8::new(pin_outcome),
+            finished_tx: watch::channel(None).0,
         };
         (request, progress_rx)
     }
 
+    /// Attaches a reporter that receives the downloaded byte counts.
+    #[must_use]
+    pub fn with_transfer_reporter(mut self, reporter: Arc<TransferProgressReporter>) -> Self {
+        self.reporter = Some(reporter);
+        self
+    }
+
+    /// Attaches a reporter that receives the progress percentage.
+    #[must_use]
+    pub fn with_hydration_reporter(mut self, reporter: Arc<HydrationProgressReporter>) -> Self {
+        self.hydration_reporter = Some(reporter);
+        self
+    }
+
+    /// Returns the priority of the most urgent caller waiting for this
+    /// request.
+    #[must_use]
+    pub fn priority(&self) -> HydrationPriority {
+        HydrationPriority::from_u8(self.priority.load(Ordering::SeqCst))
+    }
+
+    /// Raises the priority to `priority` if that is more urgent.
+    ///
+    /// Returns true if the priority changed.
+    pub fn raise_priority(&self, priority: HydrationPriority) -> bool {
+        self.priority.fetch_max(priority as u8, Ordering::SeqCst) < priority as u8
+    }
+
     /// Calculate current progress as percentage (0-100).
     ///
     /// Returns 100 for empty files (they are immediately complete).
@@ -163,14 +284,111 @@ impl HydrationRequest {
     ///
     /// This method is thread-safe and can be called from multiple threads.
     pub fn add_downloaded(&self, bytes: u64) {
-        self.downloaded.fetch_add(bytes, Ordering::SeqCst);
+        let downloaded = self.downloaded.fetch_add(bytes, Ordering::SeqCst) + bytes;
         let _ = self.progress_tx.send(self.progress());
+        if let Some(ref reporter) = self.reporter {
+            reporter.report(downloaded);
+        }
+        if let Some(ref reporter) = self.hydration_reporter {
+            reporter.report(self.progress());
+        }
+    }
+
+    /// Records that `range` of the partial file has been written.
+    ///
+    /// Only bytes not already present count towards the progress, which is
+    /// sent to subscribers (waking readers waiting for a range).
+    pub fn mark_present(&self, range: Range<u64>) {
+        let added = match self.present.lock() {
+            Ok(mut present) => {
+                let before = present.covered();
+                present.insert(range);
+                present.covered() - before
+            }
+            Err(_) => 0,
+        };
+        self.add_downloaded(added);
+    }
+
+    /// Returns true if the bytes of `offset..offset + size` are present.
+    ///
+    /// The range is clipped to the file size, so a read past the end only
+    /// waits for the bytes that exist.
+    #[must_use]
+    pub fn has_range(&self, offset: u64, size: u64) -> bool {
+        let end = offset.saturating_add(size).min(self.total_size);
+        self.present
+            .lock()
+            .map(|present| present.contains(offset.min(end)..end))
+            .unwrap_or(false)
+    }
+
+    /// Asks a streaming download to fetch the bytes at `offset` next.
+    pub fn request_range(&self, offset: u64) {
+        if let Ok(mut wanted) = self.wanted.lock() {
+            wanted.insert(offset);
+        }
+    }
+
+    /// Asks a streaming download to fetch the `window` bytes after a
+    /// sequential read of `offset..offset + size` next.
+    ///
+    /// A read is sequential when it starts where the previous one ended.
+    /// Random reads queue nothing, so seeking around a file does not
+    /// download data nobody reads. Returns the range queued, if any.
+    pub fn read_ahead(
+        &self,
+        offset: u64,
+        size: u64,
+        window: u64,
+        chunk_size: u64,
+    ) -> Option<Range<u64>> {
+        let end = offset.saturating_add(size).min(self.total_size);
+        let sequential = {
+            let mut last_read_end = self.last_read_end.lock().ok()?;
+            let sequential = *last_read_end == Some(offset);
+            *last_read_end = Some(end);
+            sequential
+        };
+        if !sequential || window == 0 || end >= self.total_size {
+            return None;
+        }
+        let ahead = end..end.saturating_add(window).min(self.total_size);
+        let chunk_size = chunk_size.max(1);
+        let mut chunk = ahead.start;
+        while chunk < ahead.end {
+            self.request_range(chunk);
+            chunk = chunk - chunk % chunk_size + chunk_size;
+        }
+        Some(ahead)
+    }
+
+    /// Picks the next range a streaming download fetches.
+    ///
+    /// The earliest offset a reader is waiting for wins; otherwise the
+    /// download continues with the first gap at or after `cursor`, then
+    /// wraps around to fill gaps before it. Ranges start on chunk
+    /// boundaries (or the end of present data) and span at most one chunk.
+    fn next_streaming_range(&self, cursor: u64, chunk_size: u64) -> Option<Range<u64>> {
+        let present = self.present.lock().ok()?;
+        let mut wanted = self.wanted.lock().ok()?;
+        wanted.retain(|&offset| offset < self.total_size && !present.contains(offset..offset + 1));
+        let from = wanted
+            .first()
+            .map_or(cursor, |&offset| offset - offset % chunk_size);
+        let gap = present
+            .next_gap(from, self.total_size)
+            .or_else(|| present.next_gap(0, self.total_size))?;
+        Some(gap.start..gap.end.min(gap.start + chunk_size))
     }
 
     /// Set downloaded to total (mark complete).
     ///
     /// Sends a 100% progress update to all subscribers.
     pub fn mark_complete(&self) {
+        if let Ok(mut present) = self.present.lock() {
+            present.insert(0..self.total_size);
+        }
         self.downloaded.store(self.total_size, Ordering::SeqCst);
         let _ = self.progress_tx.send(100);
     }
@@ -182,6 +400,61 @@ impl HydrationRequest {
     pub fn subscribe(&self) -> watch::Receiver<u8> {
         self.progress_tx.subscribe()
     }
+
+    /// Asks for the item to be marked `Pinned` instead of `Hydrated` once
+    /// the download finishes.
+    ///
+    /// Returns `false` if the download already finished as `Hydrated`; the
+    /// caller must then write the `Pinned` state itself.
+    pub fn request_pin(&self) -> bool {
+        match self.pin_outcome.compare_exchange(
+            PIN_NOT_REQUESTED,
+            PIN_REQUESTED,
+            Ordering::SeqCst,
+            Ordering::SeqCst,
+        ) {
+            Ok(_) => true,
+            Err(current) => current != PIN_SETTLED_HYDRATED,
+        }
+    }
+
+    /// Decides the state written when the download succeeds.
+    ///
+    /// After this call [`Self::request_pin`] can no longer change the outcome,
+    /// so a pin arriving concurrently is either honoured here or reported
+    /// back to its caller.
+    fn settle_state(&self) -> ItemState {
+        match self.pin_outcome.compare_exchange(
+            PIN_NOT_REQUESTED,
+            PIN_SETTLED_HYDRATED,
+            Ordering::SeqCst,
+            Ordering::SeqCst,
+        ) {
+            Ok(_) => ItemState::Hydrated,
+            Err(_) => {
+                self.pin_outcome.store(PIN_SETTLED_PINNED, Ordering::SeqCst);
+                ItemState::Pinned
+            }
+        }
+    }
+
+    /// Signals that the download and its state update are done.
+    fn finish(&self, success: bool) {
+        self.finished_tx.send_replace(Some(success));
+    }
+
+    /// Waits until the download and its state update are done.
+    ///
+    /// Returns whether the hydration succeeded. Unlike waiting for 100%
+    /// progress, this also resolves when the download fails.
+    pub async fn wait_finished(&self) -> bool {
+        let mut rx = self.finished_tx.subscribe();
+        let finished = match rx.wait_for(Option::is_some).await {
+            Ok(finished) => finished.unwrap_or(false),
+            Err(_) => false,
+        };
+        finished
+    }
 }
 
 impl fmt::Debug for HydrationRequest {
@@ -193,13 +466,121 @@ impl fmt::Debug for HydrationRequest {
             .field("total_size", &self.total_size)
             .field("downloaded", &self.downloaded())
             .field("cache_path", &self.cache_path)
-            .field("priority", &self.priority)
+            .field("priority", &self.priority())
             .field("created_at", &self.created_at)
             .field("progress", &format!("{}%", self.progress()))
             .finish()
     }
 }
 
+// ============================================================================
+// Download slots
+// ============================================================================
+
+/// Bounded pool of download slots handed out by priority.
+///
+/// A free slot goes to the waiting request with the highest
+/// [`HydrationPriority`], first come first served among equals, so a file
+/// the user opens does not queue behind a pinned folder being warmed.
+/// Priorities are read when a slot frees up, so a queued prefetch that a
+/// reader joins moves ahead as well.
+struct DownloadSlots {
+    state: Mutex<SlotState>,
+}
+
+struct SlotState {
+    /// Slots not held by any download
+    free: usize,
+    /// Requests waiting for a slot
+    waiting: Vec<SlotWaiter>,
+    /// Arrival counter keeping equal priorities in order
+    next_seq: u64,
+}
+
+struct SlotWaiter {
+    request: Arc<HydrationRequest>,
+    seq: u64,
+    slot_tx: oneshot::Sender<DownloadSlot>,
+}
+
+/// A held download slot, returned to the pool on drop.
+struct DownloadSlot {
+    slots: Option<Arc<DownloadSlots>>,
+}
+
+impl DownloadSlots {
+    fn new(count: usize) -> Self {
+        Self {
+            state: Mutex::new(SlotState {
+                free: count,
+                waiting: Vec::new(),
+                next_seq: 0,
+            }),
+        }
+    }
+
+    /// Waits for a free slot for `request`.
+    async fn acquire(self: &Arc<Self>, request: &Arc<HydrationRequest>) -> DownloadSlot {
+        let slot_rx = {
+            let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+            if state.free > 0 {
+                state.free -= 1;
+                return DownloadSlot {
+                    slots: Some(Arc::clone(self)),
+                };
+            }
+            let (slot_tx, slot_rx) = oneshot::channel();
+            let seq = state.next_seq;
+            state.next_seq += 1;
+            state.waiting.push(SlotWaiter {
+                request: Arc::clone(request),
+                seq,
+                slot_tx,
+            });
+            slot_rx
+        };
+        // The sender is only dropped after handing over a slot or with the
+        // pool itself, which outlives its waiters
+        slot_rx.await.unwrap_or(DownloadSlot { slots: None })
+    }
+
+    /// Hands a released slot to the most urgent waiter, or frees it.
+    fn release(self: &Arc<Self>) {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        while let Some(index) = state
+            .waiting
+            .iter()
+            .enumerate()
+            .max_by_key(|(_, w)| (w.request.priority(), std::cmp::Reverse(w.seq)))
+            .map(|(index, _)| index)
+        {
+            let waiter = state.waiting.swap_remove(index);
+            let slot = DownloadSlot {
+                slots: Some(Arc::clone(self)),
+            };
+            match waiter.slot_tx.send(slot) {
+                Ok(()) => return,
+                // The waiter gave up; its slot must not be released again
+                Err(mut slot) => slot.slots = None,
+            }
+        }
+        state.free += 1;
+    }
+
+    /// Returns the number of free slots.
+    fn available(&self) -> usize {
+        self.state.lock().unwrap_or_else(|e| e.into_inner()).free
+    }
+}
+
+impl Drop for DownloadSlot {
+    fn drop(&mut self) {
+        if let Some(slots) = self.slots.take() {
+            slots.release();
+        }
+    }
+}
+
 // ============================================================================
 // T049: HydrationManager struct
 // ============================================================================
@@ -207,9 +588,12 @@ impl fmt::Debug for HydrationRequest {
 /// Threshold in bytes for using chunked downloads (100 MB).
 const CHUNKED_DOWNLOAD_THRESHOLD: u64 = 100 * 1024 * 1024;
 
-/// Size of each chunk for large file downloads (10 MB).
+/// Default size of each chunk for large file downloads (10 MB).
 const DOWNLOAD_CHUNK_SIZE: u64 = 10 * 1024 * 1024;
 
+/// Default size from which files are streamed (32 MB).
+const DEFAULT_STREAMING_THRESHOLD: u64 = 32 * 1024 * 1024;
+
 /// Internal state for an active hydration task.
 struct ActiveHydration {
     /// The hydration request being processed
@@ -225,7 +609,8 @@ struct ActiveHydration {
 /// Ensures:
 /// - **Deduplication**: The same inode is not downloaded twice concurrently.
 ///   Multiple readers waiting on the same file share a single download task.
-/// - **Concurrency limit**: Configurable maximum parallel downloads via semaphore.
+/// - **Concurrency limit**: Configurable maximum parallel downloads; free slots
+///   go to the most urgent queued request.
 /// - **Progress tracking**: Watch channels for real-time progress updates.
 /// - **Cancellation**: In-flight downloads can be cancelled.
 ///
@@ -255,8 +640,8 @@ struct ActiveHydration {
 pub struct HydrationManager {
     /// Active hydration requests, keyed by inode
     active: Arc<DashMap<u64, ActiveHydration>>,
-    /// Semaphore for concurrency limiting
-    semaphore: Arc<Semaphore>,
+    /// Download slots limiting concurrency
+    slots: Arc<DownloadSlots>,
     /// Content cache for storing downloaded files
     cache: Arc<ContentCache>,
     /// Handle for serialized DB writes
@@ -265,6 +650,30 @@ pub struct HydrationManager {
     provider: Arc<GraphCloudProvider>,
     /// Tokio runtime handle for spawning tasks
     rt_handle: Handle,
+    /// Size of each ranged request for chunked downloads
+    chunk_size: u64,
+    /// Files of at least this size are streamed (0 = never)
+    streaming_threshold: u64,
+    /// Bytes fetched ahead of sequential reads of streamed files (0 = off)
+    read_ahead: u64,
+    /// Files larger than this are never cached (0 = no limit)
+    max_cached_size: u64,
+    /// What happens to files larger than `max_cached_size`
+    oversized_files: OversizedFileAction,
+    /// Download URLs of oversized files being read, keyed by remote ID
+    download_urls: DashMap<RemoteId, String>,
+    /// Receives per-file download progress, if set
+    transfer_observer: Option<Arc<dyn ITransferObserver>>,
+    /// Receives hydration progress and completion, if set
+    hydration_observer: Option<Arc<dyn IHydrationObserver>>,
+    /// Display paths for upcoming hydrations, keyed by inode
+    transfer_paths: DashMap<u64, String>,
+    /// Maximum number of parallel downloads
+    max_concurrent: usize,
+    /// Progress of the last pinned-file prefetch
+    prefetch_progress: watch::Sender<PrefetchProgress>,
+    /// Progress of the last recursive pin
+    pin_progress: watch::Sender<PrefetchProgress>,
 }
 
 impl HydrationManager {
@@ -286,11 +695,91 @@ impl HydrationManager {
     ) -> Self {
         Self {
             active: Arc::new(DashMap::new()),
-            semaphore: Arc::new(Semaphore::new(max_concurrent)),
+            slots: Arc::new(DownloadSlots::new(max_concurrent)),
             cache,
             write_handle,
             provider,
             rt_handle,
+            chunk_size: DOWNLOAD_CHUNK_SIZE,
+            streaming_threshold: DEFAULT_STREAMING_THRESHOLD,
+            read_ahead: 0,
+            max_cached_size: 0,
+            oversized_files: OversizedFileAction::default(),
+            download_urls: DashMap::new(),
+            transfer_observer: None,
+            hydration_observer: None,
+            transfer_paths: DashMap::new(),
+            max_concurrent,
+            prefetch_progress: watch::channel(PrefetchProgress::default()).0,
+            pin_progress: watch::channel(PrefetchProgress::default()).0,
+        }
+    }
+
+    /// Sets the size of each ranged request used for large files.
+    ///
+    /// Typically `fuse.hydration_chunk_size_mb` converted to bytes.
+    #[must_use]
+    pub fn with_chunk_size(mut self, chunk_size: u64) -> Self {
+        self.chunk_size = chunk_size.max(1);
+        self
+    }
+
+    /// Sets the size from which files are streamed instead of downloaded
+    /// front to back; 0 disables streaming.
+    ///
+    /// Typically `fuse.streaming_threshold_mb` converted to bytes.
+    #[must_use]
+    pub fn with_streaming_threshold(mut self, threshold: u64) -> Self {
+        self.streaming_threshold = threshold;
+        self
+    }
+
+    /// Sets how many bytes are fetched ahead of sequential reads of
+    /// streamed files; 0 disables read-ahead.
+    ///
+    /// Typically `fuse.read_ahead_mb` converted to bytes.
+    #[must_use]
+    pub fn with_read_ahead(mut self, window: u64) -> Self {
+        self.read_ahead = window;
+        self
+    }
+
+    /// Sets the size above which files are not cached, and what happens
+    /// to them instead; 0 caches files of any size.
+    ///
+    /// Typically `fuse.max_cached_file_bytes()` and `fuse.oversized_files`.
+    #[must_use]
+    pub fn with_oversized_files(
+        mut self,
+        max_cached_size: u64,
+        action: OversizedFileAction,
+    ) -> Self {
+        self.max_cached_size = max_cached_size;
+        self.oversized_files = action;
+        self
+    }
+
+    /// Sets the observer that receives per-file download progress.
+    #[must_use]
+    pub fn with_transfer_observer(mut self, observer: Arc<dyn ITransferObserver>) -> Self {
+        self.transfer_observer = Some(observer);
+        self
+    }
+
+    /// Sets the observer told about hydration progress and completion.
+    #[must_use]
+    pub fn with_hydration_observer(mut self, observer: Arc<dyn IHydrationObserver>) -> Self {
+        self.hydration_observer = Some(observer);
+        self
+    }
+
+    /// Records the path reported for the next hydration of `ino`.
+    ///
+    /// Without a recorded path, progress events name the file by its
+    /// remote ID.
+    pub fn set_transfer_path(&self, ino: u64, path: impl Into<String>) {
+        if self.transfer_observer.is_some() || self.hydration_observer.is_some() {
+            self.transfer_paths.insert(ino, path.into());
         }
     }
 }
@@ -303,7 +792,8 @@ impl HydrationManager {
     /// Initiates hydration (download) for a file.
     ///
     /// If the file is already being hydrated, returns a receiver for the existing
-    /// download's progress. Otherwise, creates a new download task.
+    /// download's progress. Otherwise, creates a new download task. See
+    /// [`Self::start_hydration`] for how concurrent callers share a download.
     ///
     /// # Arguments
     ///
@@ -328,20 +818,58 @@ impl HydrationManager {
         total_size: u64,
         priority: HydrationPriority,
     ) -> Result<watch::Receiver<u8>, FuseError> {
-        // Check if already hydrating (deduplication)
-        if let Some(active) = self.active.get(&ino) {
-            tracing::debug!(
-                ino,
-                "Hydration already in progress, returning existing receiver"
-            );
-            return Ok(active.request.subscribe());
+        let request = self
+            .start_hydration(ino, item_id, remote_id, total_size, priority)
+            .await?;
+        Ok(request.subscribe())
+    }
+
+    /// Starts (or joins) the hydration of a file and returns its request.
+    ///
+    /// All callers hydrating the same inode, e.g. an open, a read and a
+    /// pin racing each other, get the same request and share one download;
+    /// [`HydrationRequest::wait_finished`] tells each of them the outcome.
+    /// A caller joining with a more urgent priority raises the request's.
+    pub async fn start_hydration(
+        &self,
+        ino: u64,
+        item_id: UniqueId,
+        remote_id: RemoteId,
+        total_size: u64,
+        priority: HydrationPriority,
+    ) -> Result<Arc<HydrationRequest>, FuseError> {
+        if self.oversized_action(total_size).is_some() {
+            return Err(FuseError::FileTooLarge(format!(
+                "{} is {} bytes, more than the cache holds ({} bytes)",
+                remote_id, total_size, self.max_cached_size
+            )));
         }
 
+        // Join a hydration already in progress. The inode stays locked
+        // until the new download is registered, so concurrent callers (an
+        // open, a read, a pin) cannot start a second one.
+        let slot = match self.active.entry(ino) {
+            Entry::Occupied(active) => {
+                let request = Arc::clone(&active.get().request);
+                drop(active);
+                self.transfer_paths.remove(&ino);
+                if request.raise_priority(priority) {
+                    tracing::debug!(ino, ?priority, "Raised priority of hydration in progress");
+                }
+                tracing::debug!(
+                    ino,
+                    "Hydration already in progress, returning existing request"
+                );
+                return Ok(request);
+            }
+            Entry::Vacant(slot) => slot,
+        };
+
         // Create the cache path
         let cache_path = self.cache.cache_path(&remote_id);
 
         // Create the hydration request
-        let (request, progress_rx) = HydrationRequest::new(
+        let (mut request, _progress_rx) = HydrationRequest::new(
             ino,
             item_id,
             remote_id.clone(),
@@ -349,51 +877,89 @@ impl HydrationManager {
             cache_path,
             priority,
         );
+        let transfer_path = self.transfer_paths.remove(&ino).map(|(_, path)| path);
+        let transfer_path = transfer_path.unwrap_or_else(|| remote_id.as_str().to_string());
+        let hydration_reporter = self.hydration_observer.as_ref().map(|observer| {
+            let reporter = Arc::new(HydrationProgressReporter::new(
+                Arc::clone(observer),
+                transfer_path.clone(),
+            ));
+            reporter.report(0);
+            reporter
+        });
+        if let Some(ref reporter) = hydration_reporter {
+            request = request.with_hydration_reporter(Arc::clone(reporter));
+        }
+        let reporter = self.transfer_observer.as_ref().map(|observer| {
+            let reporter = Arc::new(TransferProgressReporter::new(
+                Arc::clone(observer),
+                transfer_path,
+                TransferKind::Download,
+                total_size,
+            ));
+            reporter.report(0);
+            reporter
+        });
+        if let Some(ref reporter) = reporter {
+            request = request.with_transfer_reporter(Arc::clone(reporter));
+        }
         let request = Arc::new(request);
 
         // Create cancellation token
         let cancel_token = CancellationToken::new();
 
         // Clone values for the spawned task
-        let semaphore = Arc::clone(&self.semaphore);
+        let slots = Arc::clone(&self.slots);
         let cache = Arc::clone(&self.cache);
         let write_handle = self.write_handle.clone();
         let provider = Arc::clone(&self.provider);
         let request_clone = Arc::clone(&request);
         let cancel_token_clone = cancel_token.clone();
         let active_map = self.active.clone();
-
-        // Update item state to Hydrating
-        write_handle
-            .update_state(item_id, ItemState::Hydrating)
-            .await?;
+        let chunk_size = self.chunk_size;
+        let streaming = self.streaming_threshold > 0 && total_size >= self.streaming_threshold;
 
         // Spawn the download task
         let task_handle = self.rt_handle.spawn(async move {
-            let result = Self::download_task(
-                ino,
-                item_id,
-                remote_id,
-                total_size,
-                semaphore,
-                cache,
-                write_handle.clone(),
-                provider,
-                request_clone,
-                cancel_token_clone,
-            )
-            .await;
+            // Update item state to Hydrating
+            let result = match write_handle
+                .update_state(item_id, ItemState::Hydrating)
+                .await
+            {
+                Ok(()) => {
+                    Self::download_task(
+                        ino,
+                        item_id,
+                        remote_id,
+                        total_size,
+                        chunk_size,
+                        streaming,
+                        slots,
+                        cache,
+                        write_handle.clone(),
+                        provider,
+                        Arc::clone(&request_clone),
+                        cancel_token_clone,
+                    )
+                    .await
+                }
+                Err(e) => Err(e),
+            };
+            let success = result.is_ok();
+            if let Some(reporter) = reporter {
+                reporter.finish(&result);
+            }
 
             // Handle completion or error
             match result {
                 Ok(()) => {
                     tracing::info!(ino, "Hydration completed successfully");
-                    // Update state to Hydrated
-                    if let Err(e) = write_handle
-                        .update_state(item_id, ItemState::Hydrated)
-                        .await
-                    {
-                        tracing::error!(ino, error = %e, "Failed to update state to Hydrated");
+                    // Update state to Hydrated, or Pinned if a pin arrived meanwhile
+                    let state = request_clone.settle_state();
+                    if let Err(e) = write_handle.update_state(item_id, state.clone()).await {
+                        tracing::error!(ino, ?state, error = %e, "Failed to update state after hydration");
+                    } else if let Some(reporter) = hydration_reporter {
+                        reporter.hydrated();
                     }
                     // Clear hydration progress
                     if let Err(e) = write_handle.update_hydration_progress(item_id, None).await {
@@ -416,21 +982,20 @@ impl HydrationManager {
                 }
             }
 
-            // Remove from active map
-            active_map.remove(&ino);
+            request_clone.finish(success);
+
+            // Remove from active map, unless cancelled and started again
+            active_map.remove_if(&ino, |_, active| Arc::ptr_eq(&active.request, &request_clone));
         });
 
         // Insert into active map
-        self.active.insert(
-            ino,
-            ActiveHydration {
-                request,
-                cancel_token,
-                _task_handle: task_handle,
-            },
-        );
+        slot.insert(ActiveHydration {
+            request: Arc::clone(&request),
+            cancel_token,
+            _task_handle: task_handle,
+        });
 
-        Ok(progress_rx)
+        Ok(request)
     }
 
     /// Internal download task that performs the actual file download.
@@ -440,18 +1005,17 @@ impl HydrationManager {
         item_id: UniqueId,
         remote_id: RemoteId,
         total_size: u64,
-        semaphore: Arc<Semaphore>,
+        chunk_size: u64,
+        streaming: bool,
+        slots: Arc<DownloadSlots>,
         cache: Arc<ContentCache>,
         write_handle: WriteSerializerHandle,
         provider: Arc<GraphCloudProvider>,
         request: Arc<HydrationRequest>,
         cancel_token: CancellationToken,
     ) -> Result<(), FuseError> {
-        // Acquire semaphore permit (limits concurrency)
-        let _permit = semaphore
-            .acquire()
-            .await
-            .map_err(|_| FuseError::HydrationFailed("Semaphore closed".to_string()))?;
+        // Wait for a download slot (limits concurrency, most urgent first)
+        let _slot = slots.acquire(&request).await;
 
         tracing::debug!(ino, total_size, "Starting download");
 
@@ -460,10 +1024,11 @@ impl HydrationManager {
             return Err(FuseError::HydrationFailed("Cancelled".to_string()));
         }
 
-        // Get download URL from Graph API
-        let download_url = provider.get_download_url(&remote_id).await.map_err(|e| {
+        // Get download URL and expected hash from Graph API
+        let download_info = provider.get_download_info(&remote_id).await.map_err(|e| {
             FuseError::HydrationFailed(format!("Failed to get download URL: {}", e))
         })?;
+        let download_url = download_info.url;
 
         // Get partial path for download
         let partial_path = cache.partial_path(&remote_id);
@@ -474,8 +1039,30 @@ impl HydrationManager {
             std::fs::create_dir_all(parent)?;
         }
 
+        // The content is hashed as it arrives, so verifying it needs no
+        // second pass over the file
+        let mut hasher = QuickXorHash::new();
+
         // Choose download strategy based on file size
-        if total_size < CHUNKED_DOWNLOAD_THRESHOLD {
+        if streaming {
+            Self::download_streaming(
+                ino,
+                &download_url,
+                &cache,
+                &remote_id,
+                &partial_path,
+                total_size,
+                chunk_size,
+                &provider,
+                &request,
+                &cancel_token,
+                &write_handle,
+                &item_id,
+            )
+            .await?;
+            // Ranges arrive out of order, so the content is hashed at the end
+            hash_file_prefix(&partial_path, total_size, &mut hasher)?;
+        } else if total_size < CHUNKED_DOWNLOAD_THRESHOLD {
             // Full download for smaller files
             Self::download_full(
                 ino,
@@ -486,6 +1073,7 @@ impl HydrationManager {
                 &cancel_token,
                 &write_handle,
                 &item_id,
+                &mut hasher,
             )
             .await?;
         } else {
@@ -495,19 +1083,56 @@ impl HydrationManager {
                 &download_url,
                 &partial_path,
                 total_size,
+                chunk_size,
                 &provider,
                 &request,
                 &cancel_token,
                 &write_handle,
                 &item_id,
+                &mut hasher,
             )
             .await?;
         }
 
+        // Verify the assembled content before exposing it
+        if let Some(ref expected) = download_info.quick_xor_hash {
+            let actual = hasher
+                .finalize_hash()
+                .map_err(|e| FuseError::HydrationFailed(e.to_string()))?;
+            Self::verify_hash(ino, &partial_path, expected, actual.as_str())?;
+        }
+
+        // Carry over the remote modification time, so tools comparing
+        // timestamps see the same file as on other devices
+        if let Some(modified) = download_info.modified {
+            let result = std::fs::OpenOptions::new()
+                .write(true)
+                .open(&partial_path)
+                .and_then(|file| file.set_modified(modified.into()));
+            if let Err(e) = result {
+                tracing::warn!(ino, error = %e, "Failed to set modification time");
+            }
+        }
+
         // Rename partial file to final path
         std::fs::rename(&partial_path, &final_path).map_err(|e| {
             FuseError::HydrationFailed(format!("Failed to rename partial file: {}", e))
         })?;
+        if let Err(e) = cache.clear_ranges(&remote_id) {
+            tracing::warn!(ino, error = %e, "Failed to remove range file");
+        }
+
+        // Share the blob with other items holding identical content
+        if let Some(hash) = download_info
+            .quick_xor_hash
+            .and_then(|h| FileHash::new(h).ok())
+        {
+            match cache.deduplicate(&remote_id, &hash) {
+                Ok(true) => tracing::debug!(ino, "Hydrated content deduplicated"),
+                Ok(false) => {}
+                Err(e) => tracing::warn!(ino, error = %e, "Failed to deduplicate content"),
+            }
+        }
 
         // Mark request as complete
         request.mark_complete();
@@ -526,6 +1151,7 @@ impl HydrationManager {
         cancel_token: &CancellationToken,
         write_handle: &WriteSerializerHandle,
         item_id: &UniqueId,
+        hasher: &mut QuickXorHash,
     ) -> Result<(), FuseError> {
         tracing::debug!(ino, "Using full download strategy");
 
@@ -536,12 +1162,12 @@ impl HydrationManager {
 
         // Download to partial file
         let bytes_written = provider
-            .download_file_to_disk(download_url, partial_path)
+            .download_file_to_disk(download_url, partial_path, Some(hasher))
             .await
             .map_err(|e| FuseError::HydrationFailed(format!("Download failed: {}", e)))?;
 
         // Update progress
-        request.add_downloaded(bytes_written);
+        request.mark_present(0..bytes_written);
 
         // Update progress in database
         let progress = request.progress();
@@ -556,32 +1182,51 @@ impl HydrationManager {
     }
 
     /// Download a file in chunks using HTTP Range requests (for files >= 100MB).
+    ///
+    /// Chunks are written in order, so the partial file always ends on a
+    /// chunk boundary once a chunk completes. A later attempt resumes from
+    /// the last complete chunk instead of starting over; the chunks kept
+    /// from the earlier attempt are read once to bring `hasher` up to date.
     #[allow(clippy::too_many_arguments)]
     async fn download_chunked(
         ino: u64,
         download_url: &str,
         partial_path: &Path,
         total_size: u64,
+        chunk_size: u64,
         provider: &Arc<GraphCloudProvider>,
         request: &Arc<HydrationRequest>,
         cancel_token: &CancellationToken,
         write_handle: &WriteSerializerHandle,
         item_id: &UniqueId,
+        hasher: &mut QuickXorHash,
     ) -> Result<(), FuseError> {
         tracing::debug!(
             ino,
             total_size,
-            chunk_size = DOWNLOAD_CHUNK_SIZE,
+            chunk_size,
             "Using chunked download strategy"
         );
 
-        // Pre-allocate the file to the expected size
+        // Discard any incomplete trailing chunk left by a previous attempt
+        let existing_len = std::fs::metadata(partial_path)
+            .map(|m| m.len())
+            .unwrap_or(0);
+        let mut offset = resume_offset(existing_len, total_size, chunk_size);
         {
-            let file = std::fs::File::create(partial_path)?;
-            file.set_len(total_size)?;
+            let file = std::fs::OpenOptions::new()
+                .write(true)
+                .create(true)
+                .truncate(false)
+                .open(partial_path)?;
+            file.set_len(offset)?;
+        }
+        if offset > 0 {
+            tracing::info!(ino, offset, total_size, "Resuming chunked download");
+            hash_file_prefix(partial_path, offset, hasher)?;
+            request.mark_present(0..offset);
         }
 
-        let mut offset = 0u64;
         let mut last_reported_progress = 0u8;
 
         while offset < total_size {
@@ -594,19 +1239,25 @@ impl HydrationManager {
 
             // Calculate chunk size (may be smaller for last chunk)
             let remaining = total_size - offset;
-            let chunk_size = remaining.min(DOWNLOAD_CHUNK_SIZE);
+            let length = remaining.min(chunk_size);
 
             tracing::trace!(
                 ino,
                 offset,
-                chunk_size,
+                length,
                 progress = request.progress(),
                 "Downloading chunk"
             );
 
             // Download the chunk
             let bytes_written = provider
-                .download_range(download_url, partial_path, offset, chunk_size)
+                .download_range(
+                    download_url,
+                    partial_path,
+                    offset,
+                    length,
+                    Some(&mut *hasher),
+                )
                 .await
                 .map_err(|e| {
                     FuseError::HydrationFailed(format!(
@@ -616,7 +1267,7 @@ impl HydrationManager {
                 })?;
 
             // Update progress
-            request.add_downloaded(bytes_written);
+            request.mark_present(offset..offset + bytes_written);
             offset += bytes_written;
 
             // Update progress in database (throttled to avoid too many writes)
@@ -634,43 +1285,187 @@ impl HydrationManager {
 
         Ok(())
     }
-}
 
-// ============================================================================
-// T051: HydrationManager::wait_for_completion()
-// ============================================================================
-
-impl HydrationManager {
-    /// Waits for a hydration to complete.
-    ///
-    /// Blocks until the hydration for the given inode reaches 100% progress.
-    ///
-    /// # Arguments
-    ///
-    /// * `ino` - FUSE inode number of the file being hydrated
-    ///
-    /// # Returns
-    ///
-    /// `Ok(())` when hydration completes successfully.
+    /// Download a file range by range, starting where readers wait.
     ///
-    /// # Errors
-    ///
-    /// Returns an error if the hydration fails or if no hydration is active
-    /// for the given inode.
-    pub async fn wait_for_completion(&self, ino: u64) -> Result<(), FuseError> {
-        // Get a receiver for the active hydration
-        let mut rx = {
-            let active = self.active.get(&ino).ok_or_else(|| {
-                FuseError::NotFound(format!("No active hydration for inode {}", ino))
-            })?;
-            active.request.subscribe()
-        };
+    /// Each range is recorded in the request as soon as it is written, so
+    /// `wait_for_range` can let a read through before the rest of the file
+    /// arrives. Without waiting readers the file is filled front to back.
+    /// Ranges are also recorded in the cache, so a later attempt keeps the
+    /// ranges an interrupted one already fetched.
+    #[allow(clippy::too_many_arguments)]
+    async fn download_streaming(
+        ino: u64,
+        download_url: &str,
+        cache: &ContentCache,
+        remote_id: &RemoteId,
+        partial_path: &Path,
+        total_size: u64,
+        chunk_size: u64,
+        provider: &Arc<GraphCloudProvider>,
+        request: &Arc<HydrationRequest>,
+        cancel_token: &CancellationToken,
+        write_handle: &WriteSerializerHandle,
+        item_id: &UniqueId,
+    ) -> Result<(), FuseError> {
+        tracing::debug!(
+            ino,
+            total_size,
+            chunk_size,
+            "Using streaming download strategy"
+        );
 
-        // Wait for progress to reach 100
-        loop {
-            let progress = *rx.borrow();
-            if progress >= 100 {
-                return Ok(());
+        let partial_len = std::fs::metadata(partial_path).map(|m| m.len()).ok();
+        match cache.present_ranges(remote_id) {
+            Some(kept) if partial_len == Some(total_size) => {
+                tracing::info!(
+                    ino,
+                    present = kept.covered(),
+                    total_size,
+                    "Resuming streaming download"
+                );
+                for range in kept.ranges() {
+                    request.mark_present(range.clone());
+                }
+            }
+            _ => {
+                cache.clear_ranges(remote_id)?;
+                std::fs::File::create(partial_path)?.set_len(total_size)?;
+            }
+        }
+
+        let mut cursor = 0;
+        let mut last_reported_progress = 0u8;
+
+        while let Some(range) = request.next_streaming_range(cursor, chunk_size) {
+            if cancel_token.is_cancelled() {
+                let _ = std::fs::remove_file(partial_path);
+                return Err(FuseError::HydrationFailed("Cancelled".to_string()));
+            }
+
+            tracing::trace!(
+                ino,
+                offset = range.start,
+                length = range.end - range.start,
+                "Streaming range"
+            );
+
+            let bytes_written = provider
+                .download_range(
+                    download_url,
+                    partial_path,
+                    range.start,
+                    range.end - range.start,
+                    None,
+                )
+                .await
+                .map_err(|e| {
+                    FuseError::HydrationFailed(format!(
+                        "Range download failed at offset {}: {}",
+                        range.start, e
+                    ))
+                })?;
+            if bytes_written == 0 {
+                return Err(FuseError::HydrationFailed(format!(
+                    "Range download at offset {} returned no data",
+                    range.start
+                )));
+            }
+
+            let written = range.start..range.start + bytes_written;
+            if let Err(e) = cache.mark_range(remote_id, written.clone()) {
+                tracing::warn!(ino, error = %e, "Failed to record downloaded range");
+            }
+            request.mark_present(written);
+            cursor = range.start + bytes_written;
+
+            let current_progress = request.progress();
+            if current_progress >= last_reported_progress + 5 || current_progress == 100 {
+                if let Err(e) = write_handle
+                    .update_hydration_progress(*item_id, Some(current_progress))
+                    .await
+                {
+                    tracing::warn!(ino, error = %e, "Failed to update hydration progress in DB");
+                }
+                last_reported_progress = current_progress;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Checks the hash computed while downloading against the quickXorHash
+    /// reported by OneDrive.
+    ///
+    /// On mismatch the partial file is removed so the next attempt starts
+    /// over instead of resuming corrupt data.
+    fn verify_hash(
+        ino: u64,
+        partial_path: &Path,
+        expected: &str,
+        actual: &str,
+    ) -> Result<(), FuseError> {
+        if actual != expected {
+            tracing::warn!(ino, expected, actual, "Downloaded content hash mismatch");
+            let _ = std::fs::remove_file(partial_path);
+            return Err(FuseError::HydrationFailed(format!(
+                "Hash mismatch: expected {}, got {}",
+                expected, actual
+            )));
+        }
+
+        tracing::debug!(ino, hash = expected, "Downloaded content hash verified");
+        Ok(())
+    }
+}
+
+/// Returns the offset a chunked download resumes from, given the length of
+/// an existing partial file.
+///
+/// Only whole chunks are kept; a partial file longer than the target size
+/// is not trusted at all.
+fn resume_offset(existing_len: u64, total_size: u64, chunk_size: u64) -> u64 {
+    if existing_len > total_size || chunk_size == 0 {
+        return 0;
+    }
+    existing_len - existing_len % chunk_size
+}
+
+// ============================================================================
+// T051: HydrationManager::wait_for_completion()
+// ============================================================================
+
+impl HydrationManager {
+    /// Waits for a hydration to complete.
+    ///
+    /// Blocks until the hydration for the given inode reaches 100% progress.
+    ///
+    /// # Arguments
+    ///
+    /// * `ino` - FUSE inode number of the file being hydrated
+    ///
+    /// # Returns
+    ///
+    /// `Ok(())` when hydration completes successfully.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the hydration fails or if no hydration is active
+    /// for the given inode.
+    pub async fn wait_for_completion(&self, ino: u64) -> Result<(), FuseError> {
+        // Get a receiver for the active hydration
+        let mut rx = {
+            let active = self.active.get(&ino).ok_or_else(|| {
+                FuseError::NotFound(format!("No active hydration for inode {}", ino))
+            })?;
+            active.request.subscribe()
+        };
+
+        // Wait for progress to reach 100
+        loop {
+            let progress = *rx.borrow();
+            if progress >= 100 {
+                return Ok(());
             }
 
             // Wait for next update
@@ -686,10 +1481,30 @@ impl HydrationManager {
 // ============================================================================
 
 impl HydrationManager {
+    /// Fetches ahead of a read of `offset..offset + size` of the streamed
+    /// file being hydrated as `ino`, if the read continues the previous one.
+    ///
+    /// Call before [`Self::wait_for_range`] so the download moves on to the
+    /// following range as soon as the read's own range is present. Returns
+    /// the range queued, if any.
+    pub fn read_ahead(&self, ino: u64, offset: u64, size: u64) -> Option<Range<u64>> {
+        if self.read_ahead == 0 {
+            return None;
+        }
+        let request = Arc::clone(&self.active.get(&ino)?.request);
+        if self.streaming_threshold == 0 || request.total_size < self.streaming_threshold {
+            return None;
+        }
+        let ahead = request.read_ahead(offset, size, self.read_ahead, self.chunk_size)?;
+        tracing::trace!(ino, start = ahead.start, end = ahead.end, "Reading ahead");
+        Some(ahead)
+    }
+
     /// Waits until a specific byte range is available.
     ///
-    /// For full downloads, this waits for completion. For streaming downloads,
-    /// this could return sooner when the requested range becomes available.
+    /// Returns as soon as the bytes are present in the partial file, which
+    /// for streaming downloads is usually long before the file completes;
+    /// a streaming download is asked to fetch the range next.
     ///
     /// # Arguments
     ///
@@ -705,47 +1520,34 @@ impl HydrationManager {
     ///
     /// Returns an error if the hydration fails or if no hydration is active.
     pub async fn wait_for_range(&self, ino: u64, offset: u64, size: u64) -> Result<(), FuseError> {
-        // Get request info
-        let (mut rx, total_size) = {
+        let request = {
             let active = self.active.get(&ino).ok_or_else(|| {
                 FuseError::NotFound(format!("No active hydration for inode {}", ino))
             })?;
-            (active.request.subscribe(), active.request.total_size)
-        };
-
-        // Calculate the end of the requested range
-        let range_end = offset.saturating_add(size);
-
-        // For sequential downloads, we need to wait until downloaded bytes >= range_end
-        // Progress is percentage, so we calculate required progress
-        let required_progress = if total_size == 0 {
-            100u8
-        } else {
-            // Calculate minimum progress needed for the range to be available
-            // We need at least (range_end / total_size * 100) progress
-            ((range_end * 100) / total_size).min(100) as u8
+            Arc::clone(&active.request)
         };
+        let mut progress_rx = request.subscribe();
+        let mut finished_rx = request.finished_tx.subscribe();
 
-        tracing::debug!(
-            ino,
-            offset,
-            size,
-            range_end,
-            required_progress,
-            "Waiting for byte range"
-        );
+        tracing::debug!(ino, offset, size, "Waiting for byte range");
 
-        // Wait for sufficient progress
         loop {
-            let progress = *rx.borrow();
-            if progress >= required_progress {
+            if request.has_range(offset, size) {
                 return Ok(());
             }
+            if *finished_rx.borrow() == Some(false) {
+                return Err(FuseError::HydrationFailed(format!(
+                    "Hydration of inode {} failed",
+                    ino
+                )));
+            }
+            request.request_range(offset);
 
-            // Wait for next update
-            rx.changed()
-                .await
-                .map_err(|_| FuseError::HydrationFailed("Hydration channel closed".to_string()))?;
+            tokio::select! {
+                changed = progress_rx.changed() => changed,
+                changed = finished_rx.changed() => changed,
+            }
+            .map_err(|_| FuseError::HydrationFailed("Hydration channel closed".to_string()))?;
         }
     }
 }
@@ -872,6 +1674,61 @@ impl HydrationManager {
     }
 }
 
+// ============================================================================
+// Oversized files
+// ============================================================================
+
+impl HydrationManager {
+    /// Returns what to do with a file of `size` bytes if it is too large
+    /// for the cache, or `None` if it can be hydrated.
+    pub fn oversized_action(&self, size: u64) -> Option<OversizedFileAction> {
+        (self.max_cached_size > 0 && size > self.max_cached_size).then_some(self.oversized_files)
+    }
+
+    /// Reads up to `size` bytes at `offset` of a `file_size` bytes long
+    /// file straight from the cloud, without caching them.
+    ///
+    /// Serves oversized files in [`OversizedFileAction::Stream`] mode. The
+    /// download URL is kept between reads and fetched again once it
+    /// stops working (they expire after about an hour).
+    pub async fn read_uncached(
+        &self,
+        remote_id: &RemoteId,
+        file_size: u64,
+        offset: u64,
+        size: u32,
+    ) -> Result<Vec<u8>, FuseError> {
+        if offset >= file_size || size == 0 {
+            return Ok(Vec::new());
+        }
+        let length = (size as u64).min(file_size - offset);
+
+        let known_url = self.download_urls.get(remote_id).map(|url| url.clone());
+        if let Some(url) = known_url {
+            match self.provider.read_range(&url, offset, length).await {
+                Ok(data) => return Ok(data),
+                Err(e) => {
+                    tracing::debug!(%remote_id, error = %e, "Range read failed, renewing download URL");
+                    self.download_urls.remove(remote_id);
+                }
+            }
+        }
+
+        let url = self
+            .provider
+            .get_download_url(remote_id)
+            .await
+            .map_err(|e| FuseError::HydrationFailed(format!("{e:#}")))?;
+        let data = self
+            .provider
+            .read_range(&url, offset, length)
+            .await
+            .map_err(|e| FuseError::HydrationFailed(format!("{e:#}")))?;
+        self.download_urls.insert(remote_id.clone(), url);
+        Ok(data)
+    }
+}
+
 // ============================================================================
 // T073: HydrationManager::pin()
 // ============================================================================
@@ -881,7 +1738,8 @@ impl HydrationManager {
     ///
     /// If the file is not hydrated, triggers hydration with `PinRequest` priority.
     /// Once hydrated, transitions the state to `Pinned`. Pinned files are never
-    /// auto-dehydrated.
+    /// auto-dehydrated. The pin of a file that still has to be downloaded is
+    /// recorded first, so it survives a crash during the download.
     ///
     /// # Arguments
     ///
@@ -920,20 +1778,19 @@ impl HydrationManager {
             ItemState::Online => {
                 // Need to hydrate first, then pin
                 tracing::debug!(ino, "File is Online, hydrating before pinning");
+                self.record_pin_request(item_id).await?;
 
                 // Start hydration with PinRequest priority
-                let _progress_rx = self
-                    .hydrate(ino, item_id, remote_id, total_size, HydrationPriority::PinRequest)
+                let request = self
+                    .start_hydration(
+                        ino,
+                        item_id,
+                        remote_id,
+                        total_size,
+                        HydrationPriority::PinRequest,
+                    )
                     .await?;
-
-                // Wait for completion
-                self.wait_for_completion(ino).await?;
-
-                // Transition to Pinned
-                self.write_handle
-                    .update_state(item_id, ItemState::Pinned)
-                    .await
-                    .map_err(|e| FuseError::DatabaseError(e.to_string()))?;
+                self.pin_after_hydration(ino, item_id, &request).await?;
 
                 tracing::info!(ino, "File pinned after hydration");
                 Ok(())
@@ -942,13 +1799,15 @@ impl HydrationManager {
                 // Wait for hydration to complete, then pin
                 tracing::debug!(ino, "File is Hydrating, waiting before pinning");
 
-                self.wait_for_completion(ino).await?;
-
-                // Transition to Pinned
-                self.write_handle
-                    .update_state(item_id, ItemState::Pinned)
-                    .await
-                    .map_err(|e| FuseError::DatabaseError(e.to_string()))?;
+                let request = self
+                    .active
+                    .get(&ino)
+                    .map(|active| Arc::clone(&active.request))
+                    .ok_or_else(|| {
+                        FuseError::NotFound(format!("No active hydration for inode {}", ino))
+                    })?;
+                self.record_pin_request(item_id).await?;
+                self.pin_after_hydration(ino, item_id, &request).await?;
 
                 tracing::info!(ino, "File pinned after hydration completed");
                 Ok(())
@@ -989,6 +1848,52 @@ impl HydrationManager {
     }
 }
 
+impl HydrationManager {
+    /// Persists the pin before the content is downloaded.
+    ///
+    /// The item only becomes `Pinned` once hydrated, which clears the
+    /// request in the same write. If the daemon stops in between, startup
+    /// finds the request and hydrates and pins the file again.
+    async fn record_pin_request(&self, item_id: UniqueId) -> Result<(), FuseError> {
+        self.write_handle
+            .update_pin_requested(item_id, true)
+            .await
+    }
+
+    /// Makes an in-flight hydration end in `Pinned` and waits for it.
+    ///
+    /// The download task writes the final state itself, so the pin is handed
+    /// to the request rather than written afterwards: a separate write could
+    /// land before the task's `Hydrated` and be overwritten.
+    async fn pin_after_hydration(
+        &self,
+        ino: u64,
+        item_id: UniqueId,
+        request: &HydrationRequest,
+    ) -> Result<(), FuseError> {
+        let pinned_by_task = request.request_pin();
+        if !request.wait_finished().await {
+            // The caller is told the pin failed, so it must not come back
+            // on the next start
+            if let Err(e) = self.write_handle.update_pin_requested(item_id, false).await {
+                tracing::warn!(ino, error = %e, "Failed to clear pin request");
+            }
+            return Err(FuseError::HydrationFailed(format!(
+                "Hydration of inode {} failed, file not pinned",
+                ino
+            )));
+        }
+
+        if !pinned_by_task {
+            self.write_handle
+                .update_state(item_id, ItemState::Pinned)
+                .await
+                .map_err(|e| FuseError::DatabaseError(e.to_string()))?;
+        }
+        Ok(())
+    }
+}
+
 // ============================================================================
 // T074: HydrationManager::unpin()
 // ============================================================================
@@ -1058,15 +1963,26 @@ use crate::inode::InodeTable;
 use std::pin::Pin;
 use std::future::Future;
 
-/// Type alias for the boxed future returned by recursive pin/unpin operations.
+/// Type alias for the boxed future returned by recursive unpin operations.
 type PinResultFuture<'a> =
     Pin<Box<dyn Future<Output = Result<Vec<(u64, ItemState)>, FuseError>> + Send + 'a>>;
 
 impl HydrationManager {
     /// Recursively pins all files in a directory.
     ///
-    /// Iterates through all children of the given directory, pins each file,
-    /// and recurses into subdirectories.
+    /// The tree is walked breadth-first, so every directory is visited
+    /// before the files below it are queued. The files are then pinned in
+    /// parallel, with as many downloads at once as the manager allows;
+    /// downloads share the Graph client, and therefore the rate limiter,
+    /// of every other transfer.
+    ///
+    /// An interrupted recursive pin can simply be started again: files
+    /// already `Pinned` are skipped and `Hydrated` files are pinned without
+    /// downloading them again. The inode table is updated as files are
+    /// pinned, so the entries reflect how far the last run got.
+    ///
+    /// Aggregate progress is published through [`Self::pin_progress`].
+    /// Files that cannot be pinned are logged and left out of the result.
     ///
     /// # Arguments
     ///
@@ -1076,59 +1992,124 @@ impl HydrationManager {
     /// # Returns
     ///
     /// A vector of (ino, new_state) tuples for all pinned files.
-    pub fn pin_recursive<'a>(
-        &'a self,
+    pub async fn pin_recursive(
+        self: &Arc<Self>,
         parent_ino: u64,
-        inode_table: &'a InodeTable,
-    ) -> PinResultFuture<'a> {
-        Box::pin(async move {
-            let mut results = Vec::new();
-
-            // Get all children of the directory
-            let children = inode_table.children(parent_ino);
-
-            for child in children {
+        inode_table: &InodeTable,
+    ) -> Result<Vec<(u64, ItemState)>, FuseError> {
+        let mut results = Vec::new();
+        let mut pending = Vec::new();
+        let mut progress = PrefetchProgress::default();
+
+        let mut directories = std::collections::VecDeque::from([parent_ino]);
+        while let Some(dir_ino) = directories.pop_front() {
+            for child in inode_table.children(dir_ino) {
                 let ino = child.ino().get();
-                let item_id = *child.item_id();
-                let current_state = child.state();
-
                 if child.kind() == fuser::FileType::Directory {
-                    // Recurse into subdirectory
                     tracing::debug!(ino, name = child.name(), "Recursing into directory");
-                    let sub_results = self.pin_recursive(ino, inode_table).await?;
-                    results.extend(sub_results);
-                } else {
-                    // Pin the file
-                    if let Some(remote_id) = child.remote_id() {
-                        match self
-                            .pin(ino, item_id, remote_id.clone(), child.size(), current_state.clone())
-                            .await
-                        {
-                            Ok(()) => {
-                                results.push((ino, ItemState::Pinned));
-                            }
-                            Err(e) => {
-                                tracing::warn!(
-                                    ino,
-                                    name = child.name(),
-                                    error = %e,
-                                    "Failed to pin file, skipping"
-                                );
-                                // Continue with other files
-                            }
-                        }
-                    } else {
-                        tracing::debug!(
-                            ino,
-                            name = child.name(),
-                            "Skipping file without remote_id (newly created)"
-                        );
+                    directories.push_back(ino);
+                    continue;
+                }
+                let Some(remote_id) = child.remote_id() else {
+                    tracing::debug!(
+                        ino,
+                        name = child.name(),
+                        "Skipping file without remote_id (newly created)"
+                    );
+                    continue;
+                };
+
+                progress.total_files += 1;
+                progress.total_bytes += child.size();
+                if matches!(child.state(), ItemState::Pinned) {
+                    progress.completed_files += 1;
+                    progress.completed_bytes += child.size();
+                    results.push((ino, ItemState::Pinned));
+                    continue;
+                }
+                pending.push((
+                    PrefetchItem {
+                        ino,
+                        item_id: *child.item_id(),
+                        remote_id: remote_id.clone(),
+                        size: child.size(),
+                    },
+                    child.state().clone(),
+                ));
+            }
+        }
+
+        tracing::info!(
+            ino = parent_ino,
+            files = progress.total_files,
+            already_pinned = progress.completed_files,
+            bytes = progress.total_bytes,
+            "Pinning directory"
+        );
+        self.pin_progress.send_replace(progress);
+
+        let mut tasks = tokio::task::JoinSet::new();
+        let mut queue = pending.into_iter();
+        loop {
+            while tasks.len() < self.max_concurrent {
+                let Some((item, state)) = queue.next() else {
+                    break;
+                };
+                let manager = Arc::clone(self);
+                tasks.spawn_on(
+                    async move {
+                        let result = manager
+                            .pin(
+                                item.ino,
+                                item.item_id,
+                                item.remote_id.clone(),
+                                item.size,
+                                state,
+                            )
+                            .await;
+                        (item, result)
+                    },
+                    &self.rt_handle,
+                );
+            }
+
+            let Some(joined) = tasks.join_next().await else {
+                break;
+            };
+            match joined {
+                Ok((item, Ok(()))) => {
+                    progress.completed_files += 1;
+                    progress.completed_bytes += item.size;
+                    if let Some(entry) = inode_table.get(item.ino) {
+                        inode_table.insert(entry.with_state(ItemState::Pinned));
                     }
+                    results.push((item.ino, ItemState::Pinned));
+                }
+                Ok((item, Err(e))) => {
+                    tracing::warn!(ino = item.ino, error = %e, "Failed to pin file, skipping");
+                    progress.failed_files += 1;
+                }
+                Err(e) => {
+                    tracing::error!(error = %e, "Pin task panicked");
+                    progress.failed_files += 1;
                 }
             }
+            self.pin_progress.send_replace(progress);
+        }
 
-            Ok(results)
-        })
+        tracing::info!(
+            ino = parent_ino,
+            pinned = progress.completed_files,
+            failed = progress.failed_files,
+            "Directory pinned"
+        );
+        Ok(results)
+    }
+
+    /// Subscribes to the progress of the last recursive pin.
+    #[must_use]
+    pub fn pin_progress(&self) -> watch::Receiver<PrefetchProgress> {
+        self.pin_progress.subscribe()
     }
 
     /// Recursively unpins all files in a directory.
@@ -1184,41 +2165,283 @@ impl HydrationManager {
     }
 }
 
-impl fmt::Debug for HydrationManager {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        f.debug_struct("HydrationManager")
-            .field("active_count", &self.active.len())
-            .field("semaphore_permits", &self.semaphore.available_permits())
-            .finish()
-    }
-}
-
 // ============================================================================
-// Tests
+// Pinned file prefetch
 // ============================================================================
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+/// A pinned file whose content must be downloaded again.
+#[derive(Debug, Clone)]
+pub struct PrefetchItem {
+    /// FUSE inode number
+    pub ino: u64,
+    /// Database item ID
+    pub item_id: UniqueId,
+    /// OneDrive remote ID
+    pub remote_id: RemoteId,
+    /// File size in bytes
+    pub size: u64,
+}
 
-    mod hydration_priority_tests {
-        use super::*;
+/// Progress of a pinned-file prefetch or of a recursive pin.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PrefetchProgress {
+    /// Files queued for prefetch
+    pub total_files: usize,
+    /// Files downloaded and pinned
+    pub completed_files: usize,
+    /// Files that could not be downloaded
+    pub failed_files: usize,
+    /// Bytes queued for prefetch
+    pub total_bytes: u64,
+    /// Bytes of the files downloaded so far
+    pub completed_bytes: u64,
+}
 
-        #[test]
-        fn test_priority_ordering() {
-            // UserOpen should be greater than PinRequest
-            assert!(HydrationPriority::UserOpen > HydrationPriority::PinRequest);
-            // PinRequest should be greater than Prefetch
-            assert!(HydrationPriority::PinRequest > HydrationPriority::Prefetch);
-            // UserOpen should be greater than Prefetch (transitive)
-            assert!(HydrationPriority::UserOpen > HydrationPriority::Prefetch);
-        }
+impl PrefetchProgress {
+    /// Returns true once every queued file succeeded or failed.
+    #[must_use]
+    pub fn is_done(&self) -> bool {
+        self.completed_files + self.failed_files >= self.total_files
+    }
+}
 
-        #[test]
-        fn test_priority_equality() {
-            assert_eq!(HydrationPriority::UserOpen, HydrationPriority::UserOpen);
-            assert_eq!(HydrationPriority::PinRequest, HydrationPriority::PinRequest);
-            assert_eq!(HydrationPriority::Prefetch, HydrationPriority::Prefetch);
+/// Completes pins interrupted by a crash (used during `init()`).
+///
+/// A `Hydrated` item with a pending pin was downloaded but not marked
+/// `Pinned` yet, so it is pinned now. Returns the indices of the items that
+/// changed, which the caller must persist. `Online` items with a pending pin
+/// still lack their content; the caller prefetches them.
+pub fn complete_pin_requests(items: &mut [SyncItem]) -> Vec<usize> {
+    let mut changed = Vec::new();
+    for (index, item) in items.iter_mut().enumerate() {
+        if !item.metadata().pin_requested() {
+            continue;
+        }
+        match item.state() {
+            ItemState::Hydrated => {
+                if item.pin().is_ok() {
+                    changed.push(index);
+                }
+            }
+            ItemState::Online => {}
+            _ => {
+                // Pinned in the meantime, or no longer a pin candidate
+                item.metadata_mut().set_pin_requested(false);
+                changed.push(index);
+            }
+        }
+    }
+    changed
+}
+
+/// Picks the files the recent-file prefetch downloads.
+///
+/// `candidates` are Online files with the time they were last accessed.
+/// Of the `count` most recently accessed ones, those fitting in `budget`
+/// bytes are returned, most recent first.
+#[must_use]
+pub fn select_recent_files(
+    mut candidates: Vec<(PrefetchItem, DateTime<Utc>)>,
+    count: usize,
+    budget: u64,
+) -> Vec<PrefetchItem> {
+    candidates.sort_by_key(|(_, accessed)| std::cmp::Reverse(*accessed));
+    let mut remaining = budget;
+    candidates
+        .into_iter()
+        .take(count)
+        .filter_map(|(item, _)| {
+            remaining = remaining.checked_sub(item.size)?;
+            Some(item)
+        })
+        .collect()
+}
+
+impl HydrationManager {
+    /// Downloads pinned files in the background and marks them `Pinned`.
+    ///
+    /// Used at mount time for pinned files whose content is missing from the
+    /// cache. At most half of the download slots are used, so files the
+    /// user opens meanwhile are not stuck behind the prefetch. Downloads go
+    /// through the same Graph client, and therefore the same rate limiter,
+    /// as every other transfer.
+    ///
+    /// Progress is published through [`Self::prefetch_progress`] and logged.
+    /// The returned task resolves to the final progress.
+    pub fn prefetch_pinned(
+        self: &Arc<Self>,
+        items: Vec<PrefetchItem>,
+    ) -> JoinHandle<PrefetchProgress> {
+        let manager = Arc::clone(self);
+        let parallel = (self.max_concurrent / 2).max(1);
+
+        self.rt_handle.spawn(async move {
+            let mut progress = PrefetchProgress {
+                total_files: items.len(),
+                total_bytes: items.iter().map(|item| item.size).sum(),
+                ..PrefetchProgress::default()
+            };
+            manager.prefetch_progress.send_replace(progress);
+            tracing::info!(
+                files = progress.total_files,
+                bytes = progress.total_bytes,
+                "Prefetching pinned files"
+            );
+
+            let mut tasks = tokio::task::JoinSet::new();
+            let mut queue = items.into_iter();
+            loop {
+                while tasks.len() < parallel {
+                    let Some(item) = queue.next() else { break };
+                    let manager = Arc::clone(&manager);
+                    tasks.spawn(async move {
+                        let result = manager
+                            .pin(
+                                item.ino,
+                                item.item_id,
+                                item.remote_id.clone(),
+                                item.size,
+                                ItemState::Online,
+                            )
+                            .await;
+                        (item, result)
+                    });
+                }
+
+                let Some(joined) = tasks.join_next().await else { break };
+                match joined {
+                    Ok((item, Ok(()))) => {
+                        progress.completed_files += 1;
+                        progress.completed_bytes += item.size;
+                    }
+                    Ok((item, Err(e))) => {
+                        tracing::warn!(ino = item.ino, error = %e, "Failed to prefetch pinned file");
+                        progress.failed_files += 1;
+                    }
+                    Err(e) => {
+                        tracing::error!(error = %e, "Prefetch task panicked");
+                        progress.failed_files += 1;
+                    }
+                }
+                manager.prefetch_progress.send_replace(progress);
+                tracing::debug!(
+                    completed = progress.completed_files,
+                    failed = progress.failed_files,
+                    total = progress.total_files,
+                    completed_bytes = progress.completed_bytes,
+                    total_bytes = progress.total_bytes,
+                    "Pinned file prefetch progress"
+                );
+            }
+
+            tracing::info!(
+                completed = progress.completed_files,
+                failed = progress.failed_files,
+                bytes = progress.completed_bytes,
+                "Pinned file prefetch finished"
+            );
+            progress
+        })
+    }
+
+    /// Subscribes to the progress of the pinned-file prefetch.
+    #[must_use]
+    pub fn prefetch_progress(&self) -> watch::Receiver<PrefetchProgress> {
+        self.prefetch_progress.subscribe()
+    }
+
+    /// Hydrates recently used files in the background after mounting.
+    ///
+    /// Unlike [`Self::prefetch_pinned`] the files are only hydrated, not
+    /// pinned, so they are dehydrated again like any other file. Downloads
+    /// run at [`HydrationPriority::Prefetch`] on at most half of the
+    /// download slots, behind every file the user opens. The returned task
+    /// resolves to the number of files hydrated.
+    pub fn prefetch_recent(self: &Arc<Self>, items: Vec<PrefetchItem>) -> JoinHandle<usize> {
+        let manager = Arc::clone(self);
+        let parallel = (self.max_concurrent / 2).max(1);
+
+        self.rt_handle.spawn(async move {
+            tracing::info!(files = items.len(), "Prefetching recently used files");
+            let mut tasks = tokio::task::JoinSet::new();
+            let mut queue = items.into_iter();
+            let mut hydrated = 0;
+            loop {
+                while tasks.len() < parallel {
+                    let Some(item) = queue.next() else { break };
+                    let manager = Arc::clone(&manager);
+                    tasks.spawn(async move {
+                        let request = manager
+                            .start_hydration(
+                                item.ino,
+                                item.item_id,
+                                item.remote_id.clone(),
+                                item.size,
+                                HydrationPriority::Prefetch,
+                            )
+                            .await?;
+                        if request.wait_finished().await {
+                            Ok(())
+                        } else {
+                            Err(FuseError::HydrationFailed(format!(
+                                "Prefetch of inode {} failed",
+                                item.ino
+                            )))
+                        }
+                    });
+                }
+
+                let Some(joined) = tasks.join_next().await else { break };
+                match joined {
+                    Ok(Ok(())) => hydrated += 1,
+                    Ok(Err(e)) => {
+                        tracing::warn!(error = %e, "Failed to prefetch recently used file");
+                    }
+                    Err(e) => tracing::error!(error = %e, "Prefetch task panicked"),
+                }
+            }
+
+            tracing::info!(hydrated, "Recently used file prefetch finished");
+            hydrated
+        })
+    }
+}
+
+impl fmt::Debug for HydrationManager {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("HydrationManager")
+            .field("active_count", &self.active.len())
+            .field("free_slots", &self.slots.available())
+            .finish()
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    mod hydration_priority_tests {
+        use super::*;
+
+        #[test]
+        fn test_priority_ordering() {
+            // UserOpen should be greater than PinRequest
+            assert!(HydrationPriority::UserOpen > HydrationPriority::PinRequest);
+            // PinRequest should be greater than Prefetch
+            assert!(HydrationPriority::PinRequest > HydrationPriority::Prefetch);
+            // UserOpen should be greater than Prefetch (transitive)
+            assert!(HydrationPriority::UserOpen > HydrationPriority::Prefetch);
+        }
+
+        #[test]
+        fn test_priority_equality() {
+            assert_eq!(HydrationPriority::UserOpen, HydrationPriority::UserOpen);
+            assert_eq!(HydrationPriority::PinRequest, HydrationPriority::PinRequest);
+            assert_eq!(HydrationPriority::Prefetch, HydrationPriority::Prefetch);
         }
 
         #[test]
@@ -1310,10 +2533,60 @@ mod tests {
             assert_eq!(request.total_size, 1000);
             assert_eq!(request.downloaded(), 0);
             assert_eq!(request.cache_path, cache_path);
-            assert_eq!(request.priority, HydrationPriority::UserOpen);
+            assert_eq!(request.priority(), HydrationPriority::UserOpen);
             assert_eq!(*rx.borrow(), 0); // Initial progress is 0
         }
 
+        #[test]
+        fn test_add_downloaded_feeds_transfer_reporter() {
+            use std::sync::Mutex;
+
+            use lnxdrive_core::ports::TransferEvent;
+
+            #[derive(Default)]
+            struct Recorder(Mutex<Vec<TransferEvent>>);
+
+            impl ITransferObserver for Recorder {
+                fn on_transfer_event(&self, event: TransferEvent) {
+                    self.0.lock().unwrap().push(event);
+                }
+            }
+
+            let recorder = Arc::new(Recorder::default());
+            let reporter = Arc::new(TransferProgressReporter::new(
+                Arc::clone(&recorder) as Arc<dyn ITransferObserver>,
+                "/mnt/onedrive/video.mkv",
+                TransferKind::Download,
+                1000,
+            ));
+            let request = create_test_request(1000, HydrationPriority::UserOpen)
+                .with_transfer_reporter(reporter);
+
+            request.add_downloaded(400);
+            request.add_downloaded(600);
+
+            let events = recorder.0.lock().unwrap();
+            assert_eq!(
+                *events,
+                vec![
+                    TransferEvent::Progress {
+                        path: "/mnt/onedrive/video.mkv".to_string(),
+                        kind: TransferKind::Download,
+                        bytes_done: 400,
+                        bytes_total: 1000,
+                        eta_secs: None,
+                    },
+                    TransferEvent::Progress {
+                        path: "/mnt/onedrive/video.mkv".to_string(),
+                        kind: TransferKind::Download,
+                        bytes_done: 1000,
+                        bytes_total: 1000,
+                        eta_secs: Some(0),
+                    },
+                ]
+            );
+        }
+
         #[test]
         fn test_progress_calculation() {
             let request = create_test_request(1000, HydrationPriority::UserOpen);
@@ -1364,6 +2637,46 @@ mod tests {
             assert_eq!(request.downloaded(), 300);
         }
 
+        #[test]
+        fn test_pin_requested_before_completion_settles_pinned() {
+            let request = create_test_request(1000, HydrationPriority::UserOpen);
+
+            assert!(request.request_pin());
+            assert_eq!(request.settle_state(), ItemState::Pinned);
+            // A second pin after settling is already honoured
+            assert!(request.request_pin());
+        }
+
+        #[test]
+        fn test_pin_requested_after_completion_is_left_to_caller() {
+            let request = create_test_request(1000, HydrationPriority::UserOpen);
+
+            assert_eq!(request.settle_state(), ItemState::Hydrated);
+            assert!(!request.request_pin());
+        }
+
+        #[test]
+        fn test_pin_priority_request_settles_pinned() {
+            let request = create_test_request(1000, HydrationPriority::PinRequest);
+
+            assert_eq!(request.settle_state(), ItemState::Pinned);
+        }
+
+        #[tokio::test]
+        async fn test_wait_finished_reports_outcome() {
+            let request = Arc::new(create_test_request(1000, HydrationPriority::UserOpen));
+            let waiter = {
+                let request = Arc::clone(&request);
+                tokio::spawn(async move { request.wait_finished().await })
+            };
+
+            request.finish(false);
+            assert!(!waiter.await.unwrap());
+
+            // Waiting after the fact returns immediately
+            assert!(!request.wait_finished().await);
+        }
+
         #[test]
         fn test_mark_complete() {
             let item_id = UniqueId::new();
@@ -1937,4 +3250,1161 @@ mod tests {
             assert!(!modified.can_dehydrate());
         }
     }
+
+    mod pin_prefetch_tests {
+        use std::path::PathBuf;
+
+        use lnxdrive_cache::{pool::DatabasePool, SqliteStateRepository};
+        use lnxdrive_core::{
+            domain::{
+                newtypes::{Email, RemotePath, SyncPath},
+                Account, SyncItem,
+            },
+            ports::state_repository::IStateRepository,
+        };
+        use lnxdrive_graph::client::GraphClient;
+        use tempfile::TempDir;
+        use wiremock::{
+            matchers::{method, path},
+            Mock, MockServer, ResponseTemplate,
+        };
+
+        use crate::inode_entry::{InodeEntry, InodeNumber};
+
+        use super::*;
+        use crate::write_serializer::WriteSerializer;
+
+        /// A manager downloading from a mock Graph server
+        struct Harness {
+            _cache_dir: TempDir,
+            server: MockServer,
+            cache: Arc<ContentCache>,
+            repo: SqliteStateRepository,
+            manager: Arc<HydrationManager>,
+        }
+
+        impl Harness {
+            async fn new() -> Self {
+                Self::with_manager(|manager| manager).await
+            }
+
+            /// Builds the harness, letting `configure` adjust the manager
+            async fn with_manager(
+                configure: impl FnOnce(HydrationManager) -> HydrationManager,
+            ) -> Self {
+                Self::with_slots(4, configure).await
+            }
+
+            /// Builds the harness with `max_concurrent` download slots
+            async fn with_slots(
+                max_concurrent: usize,
+                configure: impl FnOnce(HydrationManager) -> HydrationManager,
+            ) -> Self {
+                let server = MockServer::start().await;
+                let cache_dir = TempDir::new().unwrap();
+                let cache = Arc::new(ContentCache::new(cache_dir.path().to_path_buf()).unwrap());
+                let pool = DatabasePool::in_memory().await.unwrap();
+                let repo = SqliteStateRepository::new(pool.pool().clone());
+                let account = Account::new(
+                    Email::new("user@example.com".to_string()).unwrap(),
+                    "Test User",
+                    "root",
+                    SyncPath::new(PathBuf::from("/home/user/OneDrive")).unwrap(),
+                );
+                repo.save_account(&account).await.unwrap();
+                let (serializer, write_handle) = WriteSerializer::new(pool);
+                tokio::spawn(serializer.run());
+                let provider = Arc::new(GraphCloudProvider::new(GraphClient::with_base_url(
+                    "token",
+                    server.uri(),
+                )));
+                let manager = Arc::new(configure(HydrationManager::new(
+                    max_concurrent,
+                    Arc::clone(&cache),
+                    write_handle,
+                    provider,
+                    Handle::current(),
+                )));
+                Self {
+                    _cache_dir: cache_dir,
+                    server,
+                    cache,
+                    repo,
+                    manager,
+                }
+            }
+
+            /// Adds an Online file whose content the server returns
+            async fn add_remote_file(&self, name: &str, content: &[u8]) -> SyncItem {
+                self.add_slow_file(name, content, std::time::Duration::ZERO)
+                    .await
+            }
+
+            /// Adds an Online file whose content the server returns after `delay`
+            async fn add_slow_file(
+                &self,
+                name: &str,
+                content: &[u8],
+                delay: std::time::Duration,
+            ) -> SyncItem {
+                let remote_id = name.replace('.', "_");
+                Mock::given(method("GET"))
+                    .and(path(format!("/me/drive/items/{}", remote_id)))
+                    .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                        "@microsoft.graph.downloadUrl":
+                            format!("{}/content/{}", self.server.uri(), remote_id),
+                        "size": content.len(),
+                    })))
+                    .mount(&self.server)
+                    .await;
+                Mock::given(method("GET"))
+                    .and(path(format!("/content/{}", remote_id)))
+                    .respond_with(
+                        ResponseTemplate::new(200)
+                            .set_body_bytes(content.to_vec())
+                            .set_delay(delay),
+                    )
+                    .mount(&self.server)
+                    .await;
+                self.add_file(name, content.len() as u64).await
+            }
+
+            /// Adds an Online file the server serves in ranges; the returned
+            /// list collects the `Range` headers in request order
+            async fn add_ranged_file(
+                &self,
+                name: &str,
+                content: &[u8],
+            ) -> (SyncItem, Arc<std::sync::Mutex<Vec<String>>>) {
+                let remote_id = name.replace('.', "_");
+                Mock::given(method("GET"))
+                    .and(path(format!("/me/drive/items/{}", remote_id)))
+                    .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                        "@microsoft.graph.downloadUrl":
+                            format!("{}/content/{}", self.server.uri(), remote_id),
+                        "size": content.len(),
+                    })))
+                    .mount(&self.server)
+                    .await;
+                let ranges = Arc::new(std::sync::Mutex::new(Vec::new()));
+                Mock::given(method("GET"))
+                    .and(path(format!("/content/{}", remote_id)))
+                    .respond_with(RangeResponder {
+                        content: content.to_vec(),
+                        ranges: Arc::clone(&ranges),
+                    })
+                    .mount(&self.server)
+                    .await;
+                (self.add_file(name, content.len() as u64).await, ranges)
+            }
+
+            /// Adds an Online file the server knows nothing about
+            async fn add_file(&self, name: &str, size: u64) -> SyncItem {
+                let mut item = SyncItem::new_file(
+                    SyncPath::new(PathBuf::from(format!("/home/user/OneDrive/{}", name))).unwrap(),
+                    RemotePath::new(format!("/{}", name)).unwrap(),
+                    size,
+                    None,
+                )
+                .unwrap();
+                item.set_remote_id(RemoteId::new(name.replace('.', "_")).unwrap());
+                self.repo.save_item(&item).await.unwrap();
+                item
+            }
+
+            async fn state(&self, item: &SyncItem) -> ItemState {
+                self.repo
+                    .get_item(item.id())
+                    .await
+                    .unwrap()
+                    .unwrap()
+                    .state()
+                    .clone()
+            }
+
+            async fn pin_requested(&self, item: &SyncItem) -> bool {
+                self.repo
+                    .get_item(item.id())
+                    .await
+                    .unwrap()
+                    .unwrap()
+                    .metadata()
+                    .pin_requested()
+            }
+
+            /// Leaves `item` as a crash during a pin would: with the pin
+            /// recorded and in `state`
+            async fn interrupt_pin(&self, item: &SyncItem, state: ItemState) {
+                let mut item = self.repo.get_item(item.id()).await.unwrap().unwrap();
+                item.metadata_mut().set_pin_requested(true);
+                item.reset_state_for_crash_recovery(state);
+                self.repo.save_item(&item).await.unwrap();
+            }
+
+            /// Runs the pin recovery of `init()` and waits for the prefetch
+            /// it starts
+            async fn recover_after_crash(&self) {
+                let mut items = self
+                    .repo
+                    .query_items(&lnxdrive_core::ports::ItemFilter::new())
+                    .await
+                    .unwrap();
+                for item in items.iter_mut() {
+                    if matches!(item.state(), ItemState::Hydrating) {
+                        item.reset_state_for_crash_recovery(ItemState::Online);
+                        self.repo.save_item(item).await.unwrap();
+                    }
+                }
+                let scrubber = crate::scrub::CacheScrubber::new(Arc::clone(&self.cache), false);
+                let (_, mut changed) = scrubber.scrub_items(&mut items);
+                changed.extend(complete_pin_requests(&mut items));
+                for index in changed {
+                    self.repo.save_item(&items[index]).await.unwrap();
+                }
+                let prefetch: Vec<PrefetchItem> = items
+                    .iter()
+                    .filter(|item| item.metadata().pin_requested())
+                    .enumerate()
+                    .map(|(n, item)| prefetch_item(n as u64 + 2, item))
+                    .collect();
+                self.manager.prefetch_pinned(prefetch).await.unwrap();
+            }
+        }
+
+        /// Serves `bytes=start-end` requests from fixed content
+        struct RangeResponder {
+            content: Vec<u8>,
+            ranges: Arc<std::sync::Mutex<Vec<String>>>,
+        }
+
+        impl wiremock::Respond for RangeResponder {
+            fn respond(&self, request: &wiremock::Request) -> ResponseTemplate {
+                let header = request.headers["range"].to_str().unwrap().to_string();
+                let (start, end) = header
+                    .trim_start_matches("bytes=")
+                    .split_once('-')
+                    .map(|(s, e)| (s.parse::<usize>().unwrap(), e.parse::<usize>().unwrap()))
+                    .unwrap();
+                self.ranges.lock().unwrap().push(header);
+                let end = (end + 1).min(self.content.len());
+                ResponseTemplate::new(206).set_body_bytes(self.content[start..end].to_vec())
+            }
+        }
+
+        fn prefetch_item(ino: u64, item: &SyncItem) -> PrefetchItem {
+            PrefetchItem {
+                ino,
+                item_id: *item.id(),
+                remote_id: item.remote_id().unwrap().clone(),
+                size: item.size_bytes(),
+            }
+        }
+
+        #[tokio::test]
+        async fn test_pin_online_file_hydrates_and_pins() {
+            let harness = Harness::new().await;
+            let item = harness
+                .add_remote_file("report.txt", b"quarterly numbers")
+                .await;
+
+            harness
+                .manager
+                .pin(
+                    2,
+                    *item.id(),
+                    item.remote_id().unwrap().clone(),
+                    item.size_bytes(),
+                    ItemState::Online,
+                )
+                .await
+                .unwrap();
+
+            assert_eq!(harness.state(&item).await, ItemState::Pinned);
+            assert!(harness.cache.exists(item.remote_id().unwrap()));
+        }
+
+        #[tokio::test(flavor = "multi_thread")]
+        async fn test_pin_is_recorded_before_download() {
+            let harness = Harness::new().await;
+            let item = harness
+                .add_slow_file("slow.txt", b"slow", std::time::Duration::from_millis(300))
+                .await;
+
+            let manager = Arc::clone(&harness.manager);
+            let (item_id, remote_id) = (*item.id(), item.remote_id().unwrap().clone());
+            let pin = tokio::spawn(async move {
+                manager
+                    .pin(2, item_id, remote_id, 4, ItemState::Online)
+                    .await
+            });
+            while harness.state(&item).await != ItemState::Hydrating {
+                tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+            }
+            assert!(harness.pin_requested(&item).await);
+
+            pin.await.unwrap().unwrap();
+            assert_eq!(harness.state(&item).await, ItemState::Pinned);
+            assert!(!harness.pin_requested(&item).await);
+        }
+
+        #[tokio::test]
+        async fn test_failed_pin_is_not_recorded() {
+            let harness = Harness::new().await;
+            let item = harness.add_file("missing.txt", 10).await;
+
+            let result = harness
+                .manager
+                .pin(
+                    2,
+                    *item.id(),
+                    item.remote_id().unwrap().clone(),
+                    item.size_bytes(),
+                    ItemState::Online,
+                )
+                .await;
+
+            assert!(result.is_err());
+            assert!(!harness.pin_requested(&item).await);
+        }
+
+        #[tokio::test]
+        async fn test_crash_during_pin_download_is_recovered() {
+            let harness = Harness::new().await;
+            let item = harness.add_remote_file("a.txt", b"content").await;
+            harness.interrupt_pin(&item, ItemState::Hydrating).await;
+
+            harness.recover_after_crash().await;
+
+            assert_eq!(harness.state(&item).await, ItemState::Pinned);
+            assert!(!harness.pin_requested(&item).await);
+            assert!(harness.cache.exists(item.remote_id().unwrap()));
+        }
+
+        #[tokio::test]
+        async fn test_crash_before_pin_download_is_recovered() {
+            let harness = Harness::new().await;
+            let item = harness.add_remote_file("a.txt", b"content").await;
+            harness.interrupt_pin(&item, ItemState::Online).await;
+
+            harness.recover_after_crash().await;
+
+            assert_eq!(harness.state(&item).await, ItemState::Pinned);
+            assert!(harness.cache.exists(item.remote_id().unwrap()));
+        }
+
+        #[tokio::test]
+        async fn test_crash_after_pin_download_pins_without_downloading() {
+            let harness = Harness::new().await;
+            // No remote content: the file must not be downloaded again
+            let item = harness.add_file("a.txt", 7).await;
+            harness
+                .cache
+                .store(item.remote_id().unwrap(), b"content")
+                .unwrap();
+            harness.interrupt_pin(&item, ItemState::Hydrated).await;
+
+            harness.recover_after_crash().await;
+
+            assert_eq!(harness.state(&item).await, ItemState::Pinned);
+            assert!(!harness.pin_requested(&item).await);
+        }
+
+        #[tokio::test]
+        async fn test_pinned_file_missing_its_content_is_rehydrated() {
+            let harness = Harness::new().await;
+            let item = harness.add_remote_file("a.txt", b"content").await;
+            let mut pinned = harness.repo.get_item(item.id()).await.unwrap().unwrap();
+            pinned.reset_state_for_crash_recovery(ItemState::Pinned);
+            harness.repo.save_item(&pinned).await.unwrap();
+
+            harness.recover_after_crash().await;
+
+            assert_eq!(harness.state(&item).await, ItemState::Pinned);
+            assert!(!harness.pin_requested(&item).await);
+            assert!(harness.cache.exists(item.remote_id().unwrap()));
+        }
+
+        #[tokio::test]
+        async fn test_hydrated_pinned_file_is_kept_after_crash() {
+            let harness = Harness::new().await;
+            // No remote content: the file must not be downloaded again
+            let item = harness.add_file("a.txt", 7).await;
+            harness
+                .cache
+                .store(item.remote_id().unwrap(), b"content")
+                .unwrap();
+            let mut pinned = harness.repo.get_item(item.id()).await.unwrap().unwrap();
+            pinned.reset_state_for_crash_recovery(ItemState::Pinned);
+            harness.repo.save_item(&pinned).await.unwrap();
+
+            harness.recover_after_crash().await;
+
+            assert_eq!(harness.state(&item).await, ItemState::Pinned);
+            assert!(harness.cache.exists(item.remote_id().unwrap()));
+        }
+
+        #[tokio::test]
+        async fn test_hydration_reports_progress_and_completion() {
+            use std::sync::Mutex;
+
+            use lnxdrive_core::ports::HydrationEvent;
+
+            #[derive(Default)]
+            struct Recorder(Mutex<Vec<HydrationEvent>>);
+
+            impl IHydrationObserver for Recorder {
+                fn on_hydration_event(&self, event: HydrationEvent) {
+                    self.0.lock().unwrap().push(event);
+                }
+            }
+
+            let recorder = Arc::new(Recorder::default());
+            let observer = Arc::clone(&recorder) as Arc<dyn IHydrationObserver>;
+            let harness =
+                Harness::with_manager(|manager| manager.with_hydration_observer(observer)).await;
+            let item = harness.add_remote_file("notes.txt", b"meeting notes").await;
+
+            harness
+                .manager
+                .set_transfer_path(2, "/home/user/OneDrive/notes.txt");
+            let request = harness
+                .manager
+                .start_hydration(
+                    2,
+                    *item.id(),
+                    item.remote_id().unwrap().clone(),
+                    item.size_bytes(),
+                    HydrationPriority::UserOpen,
+                )
+                .await
+                .unwrap();
+            assert!(request.wait_finished().await);
+
+            let path = "/home/user/OneDrive/notes.txt".to_string();
+            assert_eq!(
+                *recorder.0.lock().unwrap(),
+                vec![
+                    HydrationEvent::Progress {
+                        path: path.clone(),
+                        percent: 0
+                    },
+                    HydrationEvent::Progress {
+                        path: path.clone(),
+                        percent: 100
+                    },
+                    HydrationEvent::Hydrated { path },
+                ]
+            );
+            assert_eq!(harness.state(&item).await, ItemState::Hydrated);
+        }
+
+        #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+        async fn test_concurrent_hydrations_share_one_download() {
+            let harness = Harness::new().await;
+            Mock::given(method("GET"))
+                .and(path("/me/drive/items/shared_bin"))
+                .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                    "@microsoft.graph.downloadUrl":
+                        format!("{}/content/shared_bin", harness.server.uri()),
+                    "size": 6,
+                })))
+                .mount(&harness.server)
+                .await;
+            Mock::given(method("GET"))
+                .and(path("/content/shared_bin"))
+                .respond_with(
+                    ResponseTemplate::new(200)
+                        .set_body_bytes(b"shared".to_vec())
+                        .set_delay(std::time::Duration::from_millis(200)),
+                )
+                .expect(1)
+                .mount(&harness.server)
+                .await;
+            let item = harness.add_file("shared.bin", 6).await;
+
+            // A prefetch, a pin and an open racing each other
+            let callers = [
+                HydrationPriority::Prefetch,
+                HydrationPriority::PinRequest,
+                HydrationPriority::UserOpen,
+                HydrationPriority::Prefetch,
+            ]
+            .map(|priority| {
+                let manager = Arc::clone(&harness.manager);
+                let item_id = *item.id();
+                let remote_id = item.remote_id().unwrap().clone();
+                tokio::spawn(async move {
+                    manager
+                        .start_hydration(2, item_id, remote_id, 6, priority)
+                        .await
+                        .unwrap()
+                })
+            });
+            let mut requests = Vec::new();
+            for caller in callers {
+                requests.push(caller.await.unwrap());
+            }
+
+            assert!(requests.iter().all(|r| Arc::ptr_eq(r, &requests[0])));
+            assert_eq!(requests[0].priority(), HydrationPriority::UserOpen);
+            for request in &requests {
+                assert!(request.wait_finished().await);
+            }
+            assert_eq!(harness.state(&item).await, ItemState::Hydrated);
+            assert_eq!(
+                harness.cache.read(item.remote_id().unwrap(), 0, 6).unwrap(),
+                b"shared"
+            );
+            harness.server.verify().await;
+        }
+
+        #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+        async fn test_user_open_overtakes_queued_prefetch() {
+            let harness = Harness::with_slots(1, |manager| manager).await;
+            let delay = std::time::Duration::from_millis(100);
+            let running = harness
+                .add_slow_file("running.bin", b"running", delay * 3)
+                .await;
+            let mut items = vec![("running.bin", running, 2, HydrationPriority::Prefetch)];
+            for (ino, name) in [(3, "warm1.bin"), (4, "warm2.bin"), (5, "warm3.bin")] {
+                let item = harness.add_slow_file(name, b"warm", delay).await;
+                items.push((name, item, ino, HydrationPriority::Prefetch));
+            }
+            let opened = harness.add_slow_file("opened.bin", b"opened", delay).await;
+            items.push(("opened.bin", opened, 6, HydrationPriority::UserOpen));
+
+            // The first prefetch takes the only slot; the rest queue behind it
+            let finished = Arc::new(std::sync::Mutex::new(Vec::new()));
+            let mut waiters = Vec::new();
+            for (name, item, ino, priority) in items {
+                let request = harness
+                    .manager
+                    .start_hydration(
+                        ino,
+                        *item.id(),
+                        item.remote_id().unwrap().clone(),
+                        item.size_bytes(),
+                        priority,
+                    )
+                    .await
+                    .unwrap();
+                let finished = Arc::clone(&finished);
+                waiters.push(tokio::spawn(async move {
+                    assert!(request.wait_finished().await);
+                    finished.lock().unwrap().push(name);
+                }));
+                tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+            }
+            for waiter in waiters {
+                waiter.await.unwrap();
+            }
+
+            let finished = finished.lock().unwrap();
+            assert_eq!(finished.len(), 5);
+            assert_eq!(finished[..2], ["running.bin", "opened.bin"]);
+        }
+
+        #[test]
+        fn test_raise_priority_only_raises() {
+            let (request, _rx) = HydrationRequest::new(
+                1,
+                UniqueId::new(),
+                RemoteId::new("raise".to_string()).unwrap(),
+                10,
+                PathBuf::from("/tmp/raise"),
+                HydrationPriority::PinRequest,
+            );
+            assert!(!request.raise_priority(HydrationPriority::Prefetch));
+            assert_eq!(request.priority(), HydrationPriority::PinRequest);
+            assert!(request.raise_priority(HydrationPriority::UserOpen));
+            assert_eq!(request.priority(), HydrationPriority::UserOpen);
+        }
+
+        #[tokio::test]
+        async fn test_pin_during_user_hydration_is_not_lost() {
+            let harness = Harness::new().await;
+            let item = harness.add_remote_file("photo.jpg", b"jpeg bytes").await;
+            let remote_id = item.remote_id().unwrap().clone();
+
+            // The user opened the file first; the pin joins that download
+            harness
+                .manager
+                .hydrate(
+                    2,
+                    *item.id(),
+                    remote_id.clone(),
+                    item.size_bytes(),
+                    HydrationPriority::UserOpen,
+                )
+                .await
+                .unwrap();
+            let result = harness
+                .manager
+                .pin(
+                    2,
+                    *item.id(),
+                    remote_id,
+                    item.size_bytes(),
+                    ItemState::Hydrating,
+                )
+                .await;
+
+            // The download may finish before pin() looks it up
+            if result.is_ok() {
+                assert_eq!(harness.state(&item).await, ItemState::Pinned);
+            } else {
+                assert!(matches!(result, Err(FuseError::NotFound(_))));
+            }
+        }
+
+        #[tokio::test]
+        async fn test_pin_fails_when_download_fails() {
+            let harness = Harness::new().await;
+            let item = harness.add_file("missing.txt", 10).await;
+
+            let result = harness
+                .manager
+                .pin(
+                    2,
+                    *item.id(),
+                    item.remote_id().unwrap().clone(),
+                    item.size_bytes(),
+                    ItemState::Online,
+                )
+                .await;
+
+            assert!(matches!(result, Err(FuseError::HydrationFailed(_))));
+            assert_ne!(harness.state(&item).await, ItemState::Pinned);
+        }
+
+        #[tokio::test]
+        async fn test_prefetch_pinned_downloads_and_reports_progress() {
+            let harness = Harness::new().await;
+            let one = harness.add_remote_file("one.txt", b"first").await;
+            let two = harness.add_remote_file("two.txt", b"second").await;
+            let three = harness.add_remote_file("three.txt", b"third").await;
+            let missing = harness.add_file("missing.txt", 7).await;
+            let progress_rx = harness.manager.prefetch_progress();
+
+            let progress = harness
+                .manager
+                .prefetch_pinned(vec![
+                    prefetch_item(2, &one),
+                    prefetch_item(3, &two),
+                    prefetch_item(4, &three),
+                    prefetch_item(5, &missing),
+                ])
+                .await
+                .unwrap();
+
+            assert_eq!(
+                progress,
+                PrefetchProgress {
+                    total_files: 4,
+                    completed_files: 3,
+                    failed_files: 1,
+                    total_bytes: 23,
+                    completed_bytes: 16,
+                }
+            );
+            assert!(progress.is_done());
+            assert_eq!(*progress_rx.borrow(), progress);
+            for item in [&one, &two, &three] {
+                assert_eq!(harness.state(item).await, ItemState::Pinned);
+                assert!(harness.cache.exists(item.remote_id().unwrap()));
+            }
+            assert!(!harness.cache.exists(missing.remote_id().unwrap()));
+        }
+
+        #[test]
+        fn test_select_recent_files_takes_most_recent_within_budget() {
+            let now = Utc::now();
+            let candidate = |name: &str, size: u64, hours_ago: i64| {
+                let item = PrefetchItem {
+                    ino: 2,
+                    item_id: UniqueId::new(),
+                    remote_id: RemoteId::new(name.to_string()).unwrap(),
+                    size,
+                };
+                (item, now - chrono::Duration::hours(hours_ago))
+            };
+            let candidates = vec![
+                candidate("old", 10, 48),
+                candidate("newest", 10, 1),
+                candidate("huge", 1000, 2),
+                candidate("recent", 10, 3),
+                candidate("older", 10, 24),
+            ];
+
+            let names = |items: Vec<PrefetchItem>| -> Vec<String> {
+                items
+                    .iter()
+                    .map(|item| item.remote_id.as_str().to_string())
+                    .collect()
+            };
+            // Only the top three by access time; the huge one exceeds the budget
+            assert_eq!(
+                names(select_recent_files(candidates.clone(), 3, 100)),
+                ["newest", "recent"]
+            );
+            assert_eq!(
+                names(select_recent_files(candidates.clone(), 3, 2000)),
+                ["newest", "huge", "recent"]
+            );
+            assert!(select_recent_files(candidates, 0, 2000).is_empty());
+        }
+
+        #[tokio::test]
+        async fn test_prefetch_recent_hydrates_without_pinning() {
+            let harness = Harness::new().await;
+            let one = harness.add_remote_file("one.txt", b"first").await;
+            let two = harness.add_remote_file("two.txt", b"second").await;
+            let missing = harness.add_file("missing.txt", 7).await;
+
+            let hydrated = harness
+                .manager
+                .prefetch_recent(vec![
+                    prefetch_item(2, &one),
+                    prefetch_item(3, &two),
+                    prefetch_item(4, &missing),
+                ])
+                .await
+                .unwrap();
+
+            assert_eq!(hydrated, 2);
+            for item in [&one, &two] {
+                assert_eq!(harness.state(item).await, ItemState::Hydrated);
+                assert!(harness.cache.exists(item.remote_id().unwrap()));
+            }
+        }
+
+        fn inode(ino: u64, parent: u64, name: &str, item: Option<&SyncItem>) -> InodeEntry {
+            let now = std::time::SystemTime::now();
+            InodeEntry::new(
+                InodeNumber::new(ino),
+                item.map_or_else(UniqueId::new, |item| *item.id()),
+                item.and_then(|item| item.remote_id().cloned()),
+                InodeNumber::new(parent),
+                name.to_string(),
+                if item.is_some() {
+                    fuser::FileType::RegularFile
+                } else {
+                    fuser::FileType::Directory
+                },
+                item.map_or(0, SyncItem::size_bytes),
+                0o644,
+                now,
+                now,
+                now,
+                1,
+                ItemState::Online,
+            )
+        }
+
+        #[tokio::test]
+        async fn test_pin_recursive_pins_every_file_of_the_tree() {
+            let harness = Harness::new().await;
+            let table = InodeTable::new();
+            // Root (1) holds two directories; the second is nested in the first
+            table.insert(inode(2, 1, "photos", None));
+            table.insert(inode(3, 2, "2024", None));
+            table.insert(inode(4, 1, "docs", None));
+            let mut items = Vec::new();
+            for i in 0..100u64 {
+                let name = format!("file{i}.txt");
+                let content = format!("content {i}");
+                if i < 10 {
+                    // Downloaded before an interruption: nothing to serve
+                    let mut item = harness.add_file(&name, content.len() as u64).await;
+                    item.transition_to(ItemState::Hydrating).unwrap();
+                    item.transition_to(ItemState::Hydrated).unwrap();
+                    harness.repo.save_item(&item).await.unwrap();
+                    harness
+                        .cache
+                        .store(item.remote_id().unwrap(), content.as_bytes())
+                        .unwrap();
+                    let mut entry = inode(100 + i, 1 + i % 4, &name, Some(&item));
+                    entry.state = ItemState::Hydrated;
+                    table.insert(entry);
+                    items.push(item);
+                    continue;
+                }
+                let item = harness.add_remote_file(&name, content.as_bytes()).await;
+                table.insert(inode(100 + i, 1 + i % 4, &name, Some(&item)));
+                items.push(item);
+            }
+
+            let results = harness.manager.pin_recursive(1, &table).await.unwrap();
+
+            assert_eq!(results.len(), 100);
+            for item in &items {
+                assert_eq!(harness.state(item).await, ItemState::Pinned);
+            }
+            let progress = *harness.manager.pin_progress().borrow();
+            assert_eq!(progress.total_files, 100);
+            assert_eq!(progress.completed_files, 100);
+            assert!(progress.is_done());
+
+            assert!(table
+                .children(2)
+                .iter()
+                .filter(|entry| entry.kind() == fuser::FileType::RegularFile)
+                .all(|entry| matches!(entry.state(), ItemState::Pinned)));
+
+            // Pinning again skips the pinned files and downloads nothing
+            harness.server.reset().await;
+            let results = harness.manager.pin_recursive(1, &table).await.unwrap();
+            assert_eq!(results.len(), 100);
+            assert_eq!(harness.manager.pin_progress().borrow().failed_files, 0);
+            assert!(harness.server.received_requests().await.unwrap().is_empty());
+        }
+
+        #[tokio::test]
+        async fn test_streaming_serves_read_before_download_completes() {
+            let harness = Harness::with_manager(|manager| {
+                manager.with_chunk_size(8).with_streaming_threshold(1)
+            })
+            .await;
+            let content: Vec<u8> = (0..40).collect();
+            let (item, ranges) = harness.add_ranged_file("movie.mkv", &content).await;
+            let remote_id = item.remote_id().unwrap().clone();
+
+            harness
+                .manager
+                .hydrate(
+                    2,
+                    *item.id(),
+                    remote_id.clone(),
+                    item.size_bytes(),
+                    HydrationPriority::UserOpen,
+                )
+                .await
+                .unwrap();
+            let request = Arc::clone(&harness.manager.active.get(&2).unwrap().request);
+
+            // A player seeking near the end gets that range first
+            harness.manager.wait_for_range(2, 33, 4).await.unwrap();
+            assert_eq!(ranges.lock().unwrap().first().unwrap(), "bytes=32-39");
+            assert!(harness.manager.progress(2).unwrap() < 100);
+            assert_eq!(
+                harness.cache.read_in_progress(&remote_id, 33, 4).unwrap(),
+                &content[33..37]
+            );
+
+            // The rest of the file follows and the hydration completes
+            assert!(request.wait_finished().await);
+            assert_eq!(ranges.lock().unwrap().len(), 5);
+            assert_eq!(harness.state(&item).await, ItemState::Hydrated);
+            assert_eq!(harness.cache.read(&remote_id, 0, 64).unwrap(), content);
+        }
+
+        #[tokio::test]
+        async fn test_streaming_resumes_with_recorded_ranges() {
+            let harness = Harness::with_manager(|manager| {
+                manager.with_chunk_size(8).with_streaming_threshold(1)
+            })
+            .await;
+            let content: Vec<u8> = (0..40).collect();
+            let (item, ranges) = harness.add_ranged_file("show.mkv", &content).await;
+            let remote_id = item.remote_id().unwrap().clone();
+
+            // An earlier attempt fetched the first and third chunks
+            let partial = harness.cache.partial_path(&remote_id);
+            std::fs::create_dir_all(partial.parent().unwrap()).unwrap();
+            let mut kept = vec![0u8; 40];
+            kept[0..8].copy_from_slice(&content[0..8]);
+            kept[16..24].copy_from_slice(&content[16..24]);
+            std::fs::write(&partial, kept).unwrap();
+            harness.cache.mark_range(&remote_id, 0..8).unwrap();
+            harness.cache.mark_range(&remote_id, 16..24).unwrap();
+
+            harness
+                .manager
+                .hydrate(
+                    2,
+                    *item.id(),
+                    remote_id.clone(),
+                    item.size_bytes(),
+                    HydrationPriority::UserOpen,
+                )
+                .await
+                .unwrap();
+            let request = Arc::clone(&harness.manager.active.get(&2).unwrap().request);
+            assert!(request.wait_finished().await);
+
+            assert_eq!(
+                *ranges.lock().unwrap(),
+                vec!["bytes=8-15", "bytes=24-31", "bytes=32-39"]
+            );
+            assert_eq!(harness.cache.read(&remote_id, 0, 64).unwrap(), content);
+            assert!(harness.cache.present_ranges(&remote_id).is_none());
+        }
+
+        #[tokio::test]
+        async fn test_read_ahead_follows_sequential_reads_of_streamed_files() {
+            let harness = Harness::with_manager(|manager| {
+                manager
+                    .with_chunk_size(16)
+                    .with_streaming_threshold(64)
+                    .with_read_ahead(32)
+            })
+            .await;
+            let delay = std::time::Duration::from_secs(5);
+            let movie = harness.add_slow_file("movie.mkv", &[7u8; 128], delay).await;
+            let notes = harness.add_slow_file("notes.txt", &[1u8; 32], delay).await;
+            for (ino, item) in [(2, &movie), (3, &notes)] {
+                harness
+                    .manager
+                    .hydrate(
+                        ino,
+                        *item.id(),
+                        item.remote_id().unwrap().clone(),
+                        item.size_bytes(),
+                        HydrationPriority::UserOpen,
+                    )
+                    .await
+                    .unwrap();
+            }
+            let manager = &harness.manager;
+
+            // The second of two consecutive reads queues the window after it
+            assert_eq!(manager.read_ahead(2, 0, 16), None);
+            assert_eq!(manager.read_ahead(2, 16, 16), Some(32..64));
+            assert_eq!(manager.read_ahead(2, 32, 16), Some(48..80));
+
+            // A seek stops it until reads are sequential again
+            assert_eq!(manager.read_ahead(2, 100, 8), None);
+            assert_eq!(manager.read_ahead(2, 108, 8), Some(116..128));
+
+            // Files below the streaming threshold are downloaded in order
+            assert_eq!(manager.read_ahead(3, 0, 16), None);
+            assert_eq!(manager.read_ahead(3, 16, 8), None);
+            assert_eq!(manager.read_ahead(9, 0, 16), None);
+
+            manager.cancel(2).await.unwrap();
+            manager.cancel(3).await.unwrap();
+        }
+
+        #[tokio::test]
+        async fn test_file_larger_than_cache_is_read_uncached() {
+            let harness = Harness::with_manager(|manager| {
+                manager.with_oversized_files(16, OversizedFileAction::Stream)
+            })
+            .await;
+            let content: Vec<u8> = (0..40).collect();
+            let (item, ranges) = harness.add_ranged_file("movie.mkv", &content).await;
+            let remote_id = item.remote_id().unwrap().clone();
+            assert_eq!(
+                harness.manager.oversized_action(item.size_bytes()),
+                Some(OversizedFileAction::Stream)
+            );
+
+            // It is never hydrated, so it cannot fill up the cache
+            let result = harness
+                .manager
+                .hydrate(
+                    2,
+                    *item.id(),
+                    remote_id.clone(),
+                    item.size_bytes(),
+                    HydrationPriority::UserOpen,
+                )
+                .await;
+            assert!(matches!(result, Err(FuseError::FileTooLarge(_))));
+            assert_eq!(harness.state(&item).await, ItemState::Online);
+
+            // Reads fetch just their range, clipped to the end of the file
+            let data = harness
+                .manager
+                .read_uncached(&remote_id, 40, 30, 20)
+                .await
+                .unwrap();
+            assert_eq!(data, &content[30..40]);
+            let data = harness
+                .manager
+                .read_uncached(&remote_id, 40, 4, 8)
+                .await
+                .unwrap();
+            assert_eq!(data, &content[4..12]);
+            assert!(harness
+                .manager
+                .read_uncached(&remote_id, 40, 40, 8)
+                .await
+                .unwrap()
+                .is_empty());
+            assert_eq!(*ranges.lock().unwrap(), vec!["bytes=30-39", "bytes=4-11"]);
+            assert!(!harness.cache.cache_path(&remote_id).exists());
+            assert_eq!(harness.manager.download_urls.len(), 1);
+        }
+
+        #[tokio::test]
+        async fn test_file_larger_than_cache_can_be_rejected() {
+            let harness = Harness::with_manager(|manager| {
+                manager.with_oversized_files(16, OversizedFileAction::Reject)
+            })
+            .await;
+            let item = harness.add_file("huge.iso", 17).await;
+            let small = harness.add_file("small.txt", 16).await;
+
+            assert_eq!(
+                harness.manager.oversized_action(item.size_bytes()),
+                Some(OversizedFileAction::Reject)
+            );
+            assert_eq!(harness.manager.oversized_action(small.size_bytes()), None);
+            let err = harness
+                .manager
+                .pin(
+                    2,
+                    *item.id(),
+                    item.remote_id().unwrap().clone(),
+                    17,
+                    ItemState::Online,
+                )
+                .await
+                .unwrap_err();
+            assert_eq!(libc::c_int::from(err), libc::EFBIG);
+        }
+
+        #[test]
+        fn test_oversized_file_action_from_config_value() {
+            assert_eq!(
+                OversizedFileAction::from_config_value("reject"),
+                OversizedFileAction::Reject
+            );
+            assert_eq!(
+                OversizedFileAction::from_config_value("stream"),
+                OversizedFileAction::Stream
+            );
+        }
+    }
+
+    mod streaming_tests {
+        use super::*;
+
+        fn request(total_size: u64) -> HydrationRequest {
+            HydrationRequest::new(
+                2,
+                UniqueId::new(),
+                RemoteId::new("stream".to_string()).unwrap(),
+                total_size,
+                PathBuf::from("/tmp/stream"),
+                HydrationPriority::UserOpen,
+            )
+            .0
+        }
+
+        #[test]
+        fn test_has_range_clips_to_file_size() {
+            let request = request(100);
+            request.mark_present(0..50);
+            assert!(request.has_range(10, 40));
+            assert!(!request.has_range(40, 20));
+
+            request.mark_present(50..100);
+            assert!(request.has_range(90, 4096));
+            assert!(request.has_range(200, 10));
+        }
+
+        #[test]
+        fn test_mark_present_counts_new_bytes_once() {
+            let request = request(100);
+            request.mark_present(0..40);
+            request.mark_present(20..60);
+            assert_eq!(request.downloaded(), 60);
+            assert_eq!(request.progress(), 60);
+        }
+
+        #[test]
+        fn test_streaming_range_follows_readers_then_fills_gaps() {
+            let request = request(100);
+            assert_eq!(request.next_streaming_range(0, 10), Some(0..10));
+
+            // A reader jumps ahead; its chunk comes first
+            request.mark_present(0..10);
+            request.request_range(55);
+            assert_eq!(request.next_streaming_range(10, 10), Some(50..60));
+
+            // Then the download continues after it and wraps around
+            request.mark_present(50..60);
+            assert_eq!(request.next_streaming_range(60, 10), Some(60..70));
+            request.mark_present(60..100);
+            assert_eq!(request.next_streaming_range(100, 10), Some(10..20));
+
+            request.mark_present(10..50);
+            assert_eq!(request.next_streaming_range(50, 10), None);
+        }
+
+        #[test]
+        fn test_sequential_reads_fetch_the_following_range_first() {
+            let request = request(100);
+            assert_eq!(request.read_ahead(0, 10, 30, 10), None);
+            request.mark_present(0..10);
+            assert_eq!(request.read_ahead(10, 10, 30, 10), Some(20..50));
+            request.mark_present(10..20);
+
+            // The download skips to the range after the reads
+            assert_eq!(request.next_streaming_range(80, 10), Some(20..30));
+            request.mark_present(20..50);
+            assert_eq!(request.next_streaming_range(80, 10), Some(80..90));
+
+            // The window ends with the file
+            assert_eq!(request.read_ahead(20, 60, 30, 10), Some(80..100));
+            assert_eq!(request.read_ahead(80, 20, 30, 10), None);
+        }
+
+        #[test]
+        fn test_random_reads_do_not_read_ahead() {
+            let request = request(100);
+            assert_eq!(request.read_ahead(50, 10, 30, 10), None);
+            assert_eq!(request.read_ahead(10, 10, 30, 10), None);
+            assert_eq!(request.read_ahead(70, 5, 30, 10), None);
+            assert_eq!(request.next_streaming_range(30, 10), Some(30..40));
+
+            // Disabled with an empty window
+            assert_eq!(request.read_ahead(75, 5, 0, 10), None);
+        }
+    }
+
+    mod chunked_download_tests {
+        use super::*;
+        use crate::cache::quick_xor_file;
+
+        #[test]
+        fn test_resume_offset_keeps_whole_chunks_only() {
+            assert_eq!(resume_offset(0, 1000, 100), 0);
+            assert_eq!(resume_offset(250, 1000, 100), 200);
+            assert_eq!(resume_offset(300, 1000, 100), 300);
+            assert_eq!(resume_offset(1000, 1000, 100), 1000);
+        }
+
+        #[test]
+        fn test_resume_offset_rejects_oversized_partial() {
+            assert_eq!(resume_offset(1500, 1000, 100), 0);
+        }
+
+        #[test]
+        fn test_verify_hash_mismatch_removes_partial() {
+            let dir = tempfile::tempdir().unwrap();
+            let path = dir.path().join("content.partial");
+            std::fs::write(&path, b"corrupted").unwrap();
+            let actual = quick_xor_file(&path).unwrap();
+
+            let result =
+                HydrationManager::verify_hash(7, &path, "AAAAAAAAAAAAAAAAAAAAAAAAAAA=", &actual);
+
+            assert!(matches!(result, Err(FuseError::HydrationFailed(_))));
+            assert!(!path.exists());
+        }
+
+        #[test]
+        fn test_verify_hash_accepts_matching_content() {
+            let dir = tempfile::tempdir().unwrap();
+            let path = dir.path().join("content.partial");
+            std::fs::write(&path, b"hello").unwrap();
+            let expected = quick_xor_file(&path).unwrap();
+
+            HydrationManager::verify_hash(7, &path, &expected, &expected).unwrap();
+            assert!(path.exists());
+        }
+    }
 }
@@ -1,13 +1,54 @@
 //! Inode table for bidirectional inode ↔ item_id mapping.
 //!
 //! Provides lock-free concurrent access for FUSE operations.
+//!
+//! The table can be capped: once it holds more than `max_entries` entries,
+//! files the kernel has forgotten are evicted, least recently forgotten
+//! first. Their directories are marked partial so the filesystem knows to
+//! reload missing children from the state database.
 
-use std::sync::Arc;
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::{Arc, Mutex},
+};
 
-use dashmap::DashMap;
+use dashmap::{DashMap, DashSet};
 use lnxdrive_core::domain::newtypes::UniqueId;
 
-use crate::inode_entry::InodeEntry;
+use crate::inode_entry::{InodeEntry, InodeNumber};
+
+/// Inodes forgotten by the kernel, in the order they were forgotten.
+#[derive(Default)]
+struct ForgetQueue {
+    /// Sequence number of the next forget
+    next_seq: u64,
+    /// seq -> inode, oldest first
+    by_seq: BTreeMap<u64, u64>,
+    /// inode -> seq of its latest forget
+    by_inode: HashMap<u64, u64>,
+}
+
+impl ForgetQueue {
+    fn push(&mut self, ino: u64) {
+        self.remove(ino);
+        let seq = self.next_seq;
+        self.next_seq += 1;
+        self.by_seq.insert(seq, ino);
+        self.by_inode.insert(ino, seq);
+    }
+
+    fn pop_oldest(&mut self) -> Option<u64> {
+        let (_, ino) = self.by_seq.pop_first()?;
+        self.by_inode.remove(&ino);
+        Some(ino)
+    }
+
+    fn remove(&mut self, ino: u64) {
+        if let Some(seq) = self.by_inode.remove(&ino) {
+            self.by_seq.remove(&seq);
+        }
+    }
+}
 
 /// Bidirectional mapping between inodes and items.
 ///
@@ -17,17 +58,40 @@ pub struct InodeTable {
     by_inode: DashMap<u64, Arc<InodeEntry>>,
     /// item_id -> inode mapping (reverse lookup)
     by_item_id: DashMap<UniqueId, u64>,
+    /// Soft cap on the number of entries (0 = unlimited)
+    max_entries: usize,
+    /// Eviction candidates, least recently forgotten first
+    forgotten: Mutex<ForgetQueue>,
+    /// Directories with children evicted from the table
+    partial_dirs: DashSet<u64>,
 }
 
 impl InodeTable {
     /// Create a new empty inode table.
     pub fn new() -> Self {
+        Self::with_max_entries(0)
+    }
+
+    /// Create a new empty inode table capped at `max_entries` entries
+    /// (0 = unlimited).
+    ///
+    /// The cap is soft: only entries the kernel has forgotten are evicted,
+    /// so the table grows past it while the kernel references more inodes.
+    pub fn with_max_entries(max_entries: usize) -> Self {
         Self {
             by_inode: DashMap::new(),
             by_item_id: DashMap::new(),
+            max_entries,
+            forgotten: Mutex::new(ForgetQueue::default()),
+            partial_dirs: DashSet::new(),
         }
     }
 
+    /// Soft cap on the number of entries (0 = unlimited).
+    pub fn max_entries(&self) -> usize {
+        self.max_entries
+    }
+
     /// Insert a new inode entry into the table.
     ///
     /// Creates bidirectional mapping between inode number and item_id.
@@ -55,6 +119,9 @@ impl InodeTable {
     pub fn remove(&self, ino: u64) -> Option<Arc<InodeEntry>> {
         if let Some((_, entry)) = self.by_inode.remove(&ino) {
             self.by_item_id.remove(entry.item_id());
+            if let Ok(mut forgotten) = self.forgotten.lock() {
+                forgotten.remove(ino);
+            }
             Some(entry)
         } else {
             None
@@ -91,6 +158,70 @@ impl InodeTable {
     pub fn is_empty(&self) -> bool {
         self.by_inode.is_empty()
     }
+
+    /// Record that the kernel no longer references `ino`, making it the
+    /// most recently forgotten eviction candidate.
+    pub fn mark_forgotten(&self, ino: u64) {
+        if let Ok(mut forgotten) = self.forgotten.lock() {
+            forgotten.push(ino);
+        }
+    }
+
+    /// Evict forgotten entries, least recently forgotten first, until the
+    /// table is back under its cap.
+    ///
+    /// Only files that are still unreferenced (no lookups, no open handles)
+    /// are evicted; directories stay so the paths of their children can
+    /// always be resolved. The parent of each evicted entry is marked
+    /// partial. Returns the number of entries evicted.
+    pub fn evict_forgotten(&self) -> usize {
+        if self.max_entries == 0 || self.len() <= self.max_entries {
+            return 0;
+        }
+        let Ok(mut forgotten) = self.forgotten.lock() else {
+            return 0;
+        };
+
+        let mut evicted = 0;
+        while self.len() > self.max_entries {
+            let Some(ino) = forgotten.pop_oldest() else {
+                break;
+            };
+            let Some(entry) = self.get(ino) else {
+                continue;
+            };
+            // Referenced again since it was forgotten, or never evictable
+            if ino == InodeNumber::ROOT.get()
+                || entry.kind() == fuser::FileType::Directory
+                || !entry.is_expired()
+            {
+                continue;
+            }
+            if let Some((_, entry)) = self.by_inode.remove(&ino) {
+                self.by_item_id.remove(entry.item_id());
+                self.partial_dirs.insert(entry.parent_ino().get());
+                evicted += 1;
+            }
+        }
+        evicted
+    }
+
+    /// Whether some children of directory `ino` were evicted and must be
+    /// reloaded from the state database.
+    pub fn is_partial(&self, ino: u64) -> bool {
+        self.partial_dirs.contains(&ino)
+    }
+
+    /// Record that the children of directory `ino` were reloaded.
+    pub fn mark_complete(&self, ino: u64) {
+        self.partial_dirs.remove(&ino);
+    }
+
+    /// Record that directory `ino` has children in the state database that
+    /// are not in the table, e.g. added after the table was loaded.
+    pub fn mark_partial(&self, ino: u64) {
+        self.partial_dirs.insert(ino);
+    }
 }
 
 impl Default for InodeTable {
@@ -448,5 +579,50 @@ mod tests {
         let table = InodeTable::default();
         assert!(table.is_empty());
         assert_eq!(table.len(), 0);
+        assert_eq!(table.max_entries(), 0);
+    }
+
+    #[test]
+    fn test_evict_forgotten_lru() {
+        let table = InodeTable::with_max_entries(4);
+        table.insert(make_test_entry(1, 1, "", true));
+        table.insert(make_test_entry(2, 1, "dir", true));
+        for ino in 3..=5 {
+            table.insert(make_test_entry(ino, 2, &format!("file{}", ino), false));
+        }
+        // Inode 3 is referenced again after being forgotten
+        let referenced = table.get(3).unwrap();
+        table.mark_forgotten(3);
+        referenced.increment_lookup();
+        table.mark_forgotten(2);
+        table.mark_forgotten(5);
+        table.mark_forgotten(4);
+        assert!(!table.is_partial(2));
+
+        assert_eq!(table.evict_forgotten(), 1);
+
+        // The directory and the referenced file stay, the oldest free file goes
+        assert_eq!(table.len(), 4);
+        assert!(table.get(2).is_some());
+        assert!(table.get(3).is_some());
+        assert!(table.get(5).is_none());
+        assert!(table.get(4).is_some());
+        assert!(table.is_partial(2));
+
+        table.mark_complete(2);
+        assert!(!table.is_partial(2));
+    }
+
+    #[test]
+    fn test_evict_forgotten_unlimited_or_under_cap() {
+        let unlimited = InodeTable::new();
+        let capped = InodeTable::with_max_entries(10);
+        for table in [&unlimited, &capped] {
+            table.insert(make_test_entry(1, 1, "", true));
+            table.insert(make_test_entry(2, 1, "a.txt", false));
+            table.mark_forgotten(2);
+            assert_eq!(table.evict_forgotten(), 0);
+            assert_eq!(table.len(), 2);
+        }
     }
 }
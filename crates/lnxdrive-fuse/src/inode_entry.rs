@@ -174,6 +174,105 @@ impl InodeEntry {
         }
     }
 
+    /// Returns a copy of this entry with different permissions.
+    ///
+    /// The kernel reference and open handle counts are carried over, so the
+    /// copy can replace this entry in the inode table. `ctime` is set to now,
+    /// as for any metadata change.
+    pub fn with_perm(&self, perm: u16) -> Self {
+        Self {
+            ino: self.ino,
+            item_id: self.item_id,
+            remote_id: self.remote_id.clone(),
+            parent_ino: self.parent_ino,
+            name: self.name.clone(),
+            kind: self.kind,
+            size: self.size,
+            perm,
+            mtime: self.mtime,
+            ctime: SystemTime::now(),
+            atime: self.atime,
+            nlink: self.nlink,
+            lookup_count: AtomicU64::new(self.lookup_count.load(Ordering::SeqCst)),
+            open_handles: AtomicU64::new(self.open_handles.load(Ordering::SeqCst)),
+            state: self.state.clone(),
+        }
+    }
+
+    /// Returns a copy of this entry in another sync/hydration state.
+    ///
+    /// Used to keep the inode table in step with state changes written to
+    /// the database, e.g. after pinning.
+    pub fn with_state(&self, state: ItemState) -> Self {
+        Self {
+            ino: self.ino,
+            item_id: self.item_id,
+            remote_id: self.remote_id.clone(),
+            parent_ino: self.parent_ino,
+            name: self.name.clone(),
+            kind: self.kind,
+            size: self.size,
+            perm: self.perm,
+            mtime: self.mtime,
+            ctime: self.ctime,
+            atime: self.atime,
+            nlink: self.nlink,
+            lookup_count: AtomicU64::new(self.lookup_count.load(Ordering::SeqCst)),
+            open_handles: AtomicU64::new(self.open_handles.load(Ordering::SeqCst)),
+            state,
+        }
+    }
+
+    /// Returns a copy of this entry with a different size, as after a
+    /// truncate.
+    ///
+    /// `mtime` and `ctime` are set to now, as the content changed.
+    pub fn with_size(&self, size: u64) -> Self {
+        let now = SystemTime::now();
+        Self {
+            ino: self.ino,
+            item_id: self.item_id,
+            remote_id: self.remote_id.clone(),
+            parent_ino: self.parent_ino,
+            name: self.name.clone(),
+            kind: self.kind,
+            size,
+            perm: self.perm,
+            mtime: now,
+            ctime: now,
+            atime: self.atime,
+            nlink: self.nlink,
+            lookup_count: AtomicU64::new(self.lookup_count.load(Ordering::SeqCst)),
+            open_handles: AtomicU64::new(self.open_handles.load(Ordering::SeqCst)),
+            state: self.state.clone(),
+        }
+    }
+
+    /// Returns a copy of this entry under a different parent and name.
+    ///
+    /// Used when the item was renamed or moved in the cloud: the inode
+    /// number, counts and attributes are kept, so open handles and cached
+    /// content stay valid. `ctime` is set to now.
+    pub fn renamed(&self, parent_ino: InodeNumber, name: String) -> Self {
+        Self {
+            ino: self.ino,
+            item_id: self.item_id,
+            remote_id: self.remote_id.clone(),
+            parent_ino,
+            name,
+            kind: self.kind,
+            size: self.size,
+            perm: self.perm,
+            mtime: self.mtime,
+            ctime: SystemTime::now(),
+            atime: self.atime,
+            nlink: self.nlink,
+            lookup_count: AtomicU64::new(self.lookup_count.load(Ordering::SeqCst)),
+            open_handles: AtomicU64::new(self.open_handles.load(Ordering::SeqCst)),
+            state: self.state.clone(),
+        }
+    }
+
     /// Converts this inode entry to a FUSE FileAttr structure.
     ///
     /// This is used to respond to `getattr()` and `lookup()` calls.
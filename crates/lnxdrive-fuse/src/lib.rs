@@ -12,6 +12,7 @@
 //! - [`LnxDriveFs`] implements `fuser::Filesystem` trait
 //! - [`HydrationManager`] handles on-demand content downloads
 //! - [`DehydrationManager`] reclaims disk space via LRU eviction
+//! - [`FuseCacheManager`] reports cache usage and cleans/verifies it on request
 //! - [`ContentCache`] manages the local file cache
 //! - [`WriteSerializer`] serializes SQLite writes to prevent SQLITE_BUSY
 //!
@@ -27,12 +28,16 @@
 
 // Module declarations
 pub mod cache;
+pub mod cache_manager;
 pub mod dehydration;
 pub mod error;
 pub mod filesystem;
 pub mod hydration;
 pub mod inode;
 pub mod inode_entry;
+pub mod range_map;
+pub mod remote_changes;
+pub mod scrub;
 pub mod write_serializer;
 pub mod xattr;
 
@@ -43,15 +48,29 @@ pub mod xattr;
 // ---------------------------------------------------------------------------
 use std::{path::PathBuf, sync::Arc};
 
-pub use cache::ContentCache;
-pub use dehydration::{DehydrationManager, DehydrationPolicy, DehydrationReport};
+pub use cache::{CacheStats, ContentCache, PresentRead};
+pub use cache_manager::FuseCacheManager;
+pub use dehydration::{
+    DehydrationManager, DehydrationPolicy, DehydrationReport, EvictedFile, EvictionReason,
+};
 pub use error::FuseError;
 pub use filesystem::LnxDriveFs;
 pub use fuser::BackgroundSession;
 use fuser::MountOption;
-pub use hydration::{HydrationManager, HydrationPriority, HydrationRequest};
+pub use hydration::{
+    HydrationManager, HydrationPriority, HydrationRequest, OversizedFileAction, PrefetchItem,
+    PrefetchProgress,
+};
+pub use inode::InodeTable;
 use lnxdrive_cache::pool::DatabasePool;
-use lnxdrive_core::config::FuseConfig;
+use lnxdrive_core::{
+    config::FuseConfig,
+    ports::{IHydrationObserver, INotificationService},
+};
+use lnxdrive_graph::provider::GraphCloudProvider;
+pub use range_map::RangeMap;
+pub use remote_changes::RemoteChanges;
+pub use scrub::{CacheScrubber, ScrubReport};
 use tokio::runtime::Handle;
 use tracing::{debug, info};
 
@@ -134,6 +153,49 @@ pub fn mount(
     db_pool: DatabasePool,
     rt_handle: Handle,
 ) -> Result<BackgroundSession, FuseError> {
+    mount_with_remote_changes(config, db_pool, rt_handle, None, None, None)
+        .map(|mounted| mounted.session)
+}
+
+/// A mounted filesystem with the handles the daemon uses to drive it.
+pub struct MountedFs {
+    /// FUSE session; dropping it (or passing it to [`unmount()`]) unmounts.
+    pub session: BackgroundSession,
+    /// Applies renames made in the cloud to the mounted entries.
+    pub remote_changes: Arc<RemoteChanges>,
+    /// Cache usage, cleaning, verification and pinning for the mount.
+    pub cache_manager: Option<Arc<FuseCacheManager>>,
+    /// Inode table of the mount, e.g. to report its size.
+    pub inode_table: Arc<InodeTable>,
+    /// Cache hits and misses of file opens on the mount.
+    pub cache_stats: Arc<CacheStats>,
+}
+
+/// Mounts the filesystem like [`mount()`] and also returns the handles in
+/// [`MountedFs`].
+///
+/// Passing the [`RemoteChanges`] handle to the sync engine as its item
+/// observer lets renames made in the cloud move the mounted entries in
+/// place. When `notifier` is set, periodic dehydration sweeps that free
+/// space send it a summary. When `hydration_observer` is set, it is told
+/// about every file that becomes cloud-only, and about the progress of
+/// every download.
+///
+/// Cloud-only files are downloaded through `provider` when it is set.
+/// Without it, their content can't be read (see
+/// [`LnxDriveFs::attach_provider`]).
+///
+/// # Errors
+///
+/// Same as [`mount()`].
+pub fn mount_with_remote_changes(
+    config: FuseConfig,
+    db_pool: DatabasePool,
+    rt_handle: Handle,
+    notifier: Option<Arc<dyn INotificationService>>,
+    hydration_observer: Option<Arc<dyn IHydrationObserver>>,
+    provider: Option<Arc<GraphCloudProvider>>,
+) -> Result<MountedFs, FuseError> {
     // Expand tilde in mount point path
     let mount_point = expand_tilde(&config.mount_point);
 
@@ -171,14 +233,26 @@ pub fn mount(
     let cache_dir = expand_tilde(&config.cache_dir);
     debug!(cache_dir = %cache_dir.display(), "Creating content cache");
 
-    let cache = ContentCache::new(cache_dir)?;
+    let cache = ContentCache::with_shard_depth(cache_dir, config.cache_shard_depth)?
+        .with_dedup(config.cache_dedup)?
+        .with_temp_dir(config.temp_dir_path())?;
     let cache = Arc::new(cache);
 
-    // Create LnxDriveFs instance
-    // Note: HydrationManager is None here because mount() does not have a
-    // GraphCloudProvider. The daemon should call LnxDriveFs::set_hydration_manager()
-    // after mounting, or pass it via the constructor when using the full daemon setup.
-    let filesystem = LnxDriveFs::new(rt_handle, db_pool, config, cache, None);
+    // Create LnxDriveFs instance; without a provider (mount()) cloud-only
+    // files can't be hydrated
+    let mut filesystem = LnxDriveFs::new(rt_handle, db_pool, config, Arc::clone(&cache), None);
+    if let Some(notifier) = notifier {
+        filesystem.set_notifier(notifier);
+    }
+    if let Some(observer) = &hydration_observer {
+        filesystem.set_hydration_observer(Arc::clone(observer));
+    }
+    if let Some(provider) = provider {
+        filesystem.attach_provider(provider, hydration_observer);
+    }
+    let inode_table = Arc::clone(filesystem.inode_table());
+    let cache_stats = Arc::clone(filesystem.cache_stats());
+    let cache_manager = filesystem.cache_manager();
 
     // Configure mount options
     let mount_options = [
@@ -209,7 +283,16 @@ pub fn mount(
         "LNXDrive FUSE filesystem mounted successfully"
     );
 
-    Ok(session)
+    let remote_changes = Arc::new(
+        RemoteChanges::new(Arc::clone(&inode_table), Some(session.notifier())).with_cache(cache),
+    );
+    Ok(MountedFs {
+        session,
+        remote_changes,
+        cache_manager,
+        inode_table,
+        cache_stats,
+    })
 }
 
 /// Unmounts the LNXDrive FUSE filesystem.
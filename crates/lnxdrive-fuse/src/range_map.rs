@@ -0,0 +1,148 @@
+//! Byte ranges of a file that are present locally.
+//!
+//! A [`RangeMap`] keeps a sorted list of disjoint, non-adjacent half-open
+//! ranges. Streaming hydration records every downloaded chunk in one, so
+//! reads can be served as soon as the bytes they cover have landed, and
+//! the download can find the next gap to fetch.
+
+use std::ops::Range;
+
+/// Sorted set of byte ranges; overlapping and adjacent ranges are merged.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RangeMap {
+    ranges: Vec<Range<u64>>,
+}
+
+impl RangeMap {
+    /// Creates an empty map.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records `range` as present, merging it with its neighbours.
+    pub fn insert(&mut self, range: Range<u64>) {
+        if range.is_empty() {
+            return;
+        }
+        // First range that ends at or after the new start (touching counts)
+        let first = self.ranges.partition_point(|r| r.end < range.start);
+        // First range that starts after the new end (touching counts)
+        let last = self.ranges.partition_point(|r| r.start <= range.end);
+        let merged = if first < last {
+            self.ranges[first].start.min(range.start)..self.ranges[last - 1].end.max(range.end)
+        } else {
+            range
+        };
+        self.ranges.splice(first..last, std::iter::once(merged));
+    }
+
+    /// Returns true if every byte of `range` is present.
+    ///
+    /// An empty range is always present.
+    pub fn contains(&self, range: Range<u64>) -> bool {
+        if range.is_empty() {
+            return true;
+        }
+        let index = self.ranges.partition_point(|r| r.end <= range.start);
+        self.ranges
+            .get(index)
+            .is_some_and(|r| r.start <= range.start && range.end <= r.end)
+    }
+
+    /// Returns the first missing range at or after `from`, ending no later
+    /// than `limit`.
+    pub fn next_gap(&self, from: u64, limit: u64) -> Option<Range<u64>> {
+        let mut start = from;
+        for r in &self.ranges {
+            if r.end <= start {
+                continue;
+            }
+            if r.start > start {
+                return (start < limit).then_some(start..r.start.min(limit));
+            }
+            start = r.end;
+        }
+        (start < limit).then_some(start..limit)
+    }
+
+    /// Total number of bytes present.
+    pub fn covered(&self) -> u64 {
+        self.ranges.iter().map(|r| r.end - r.start).sum()
+    }
+
+    /// The present ranges, sorted.
+    pub fn ranges(&self) -> &[Range<u64>] {
+        &self.ranges
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn spans(map: &RangeMap) -> Vec<(u64, u64)> {
+        map.ranges().iter().map(|r| (r.start, r.end)).collect()
+    }
+
+    #[test]
+    fn test_insert_merges_overlapping_and_adjacent_ranges() {
+        let mut map = RangeMap::new();
+        map.insert(10..20);
+        map.insert(30..40);
+        assert_eq!(spans(&map), [(10, 20), (30, 40)]);
+
+        // Adjacent on both sides
+        map.insert(20..30);
+        assert_eq!(spans(&map), [(10, 40)]);
+
+        // Overlapping and extending
+        map.insert(0..15);
+        map.insert(35..50);
+        assert_eq!(spans(&map), [(0, 50)]);
+        assert_eq!(map.covered(), 50);
+
+        map.insert(60..60);
+        assert_eq!(spans(&map), [(0, 50)]);
+    }
+
+    #[test]
+    fn test_insert_spanning_several_ranges() {
+        let mut map = RangeMap::new();
+        for start in [0, 20, 40, 60] {
+            map.insert(start..start + 5);
+        }
+        map.insert(3..62);
+        assert_eq!(spans(&map), [(0, 65)]);
+    }
+
+    #[test]
+    fn test_contains() {
+        let mut map = RangeMap::new();
+        map.insert(10..20);
+        map.insert(30..40);
+
+        assert!(map.contains(10..20));
+        assert!(map.contains(12..15));
+        assert!(map.contains(5..5));
+        assert!(!map.contains(15..25));
+        assert!(!map.contains(0..10));
+        assert!(!map.contains(19..31));
+    }
+
+    #[test]
+    fn test_next_gap() {
+        let mut map = RangeMap::new();
+        assert_eq!(map.next_gap(0, 100), Some(0..100));
+
+        map.insert(0..10);
+        map.insert(20..30);
+        assert_eq!(map.next_gap(0, 100), Some(10..20));
+        assert_eq!(map.next_gap(15, 100), Some(15..20));
+        assert_eq!(map.next_gap(20, 100), Some(30..100));
+        assert_eq!(map.next_gap(0, 5), None);
+        assert_eq!(map.next_gap(5, 15), Some(10..15));
+
+        map.insert(10..100);
+        assert_eq!(map.next_gap(0, 100), None);
+    }
+}
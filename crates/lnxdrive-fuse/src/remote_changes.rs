@@ -0,0 +1,262 @@
+//! Applies changes made in the cloud to a mounted filesystem.
+//!
+//! The sync engine reports renames and moves it applied from the delta
+//! through the [`IItemObserver`] port. [`RemoteChanges`] moves the
+//! matching inode in place: it keeps its number, so open handles stay
+//! valid, and its cached content, which is keyed by remote ID, is not
+//! fetched again. The kernel is told to forget both the old and the new
+//! name, so the next lookup sees the change.
+//!
+//! Folders selected or deselected for sync are reported the same way.
+//! Added placeholders are loaded from the state database on the next
+//! access of their folder; removed items lose their inode and their
+//! cached content.
+
+use std::{ffi::OsStr, sync::Arc};
+
+use fuser::Notifier;
+use lnxdrive_core::{domain::newtypes::UniqueId, ports::IItemObserver};
+use tracing::debug;
+
+use crate::{cache::ContentCache, inode::InodeTable, inode_entry::InodeNumber};
+
+/// Keeps the inode table of a mounted filesystem in step with the cloud
+pub struct RemoteChanges {
+    inode_table: Arc<InodeTable>,
+    /// Channel for kernel cache invalidations; `None` when not mounted
+    notifier: Option<Notifier>,
+    /// Content of hydrated files, dropped with their items
+    cache: Option<Arc<ContentCache>>,
+}
+
+impl RemoteChanges {
+    /// Creates a handle for the given inode table.
+    pub fn new(inode_table: Arc<InodeTable>, notifier: Option<Notifier>) -> Self {
+        Self {
+            inode_table,
+            notifier,
+            cache: None,
+        }
+    }
+
+    /// Drops the cached content of removed items from `cache`.
+    pub fn with_cache(mut self, cache: Arc<ContentCache>) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+
+    /// Moves the inode of an item below `new_parent` (the root when `None`)
+    /// under `new_name`.
+    ///
+    /// Returns false if the item or its new parent has no inode, e.g. when
+    /// it was created after the filesystem was mounted.
+    pub fn apply_move(
+        &self,
+        item_id: &UniqueId,
+        new_parent: Option<&UniqueId>,
+        new_name: &str,
+    ) -> bool {
+        let Some(entry) = self
+            .inode_table
+            .get_by_item_id(item_id)
+            .and_then(|ino| self.inode_table.get(ino))
+        else {
+            return false;
+        };
+        let parent_ino = match new_parent {
+            None => InodeNumber::ROOT,
+            Some(parent_id) => match self.inode_table.get_by_item_id(parent_id) {
+                Some(ino) => InodeNumber::new(ino),
+                None => {
+                    debug!(%item_id, %parent_id, "New parent of moved item has no inode");
+                    return false;
+                }
+            },
+        };
+
+        let old_parent = entry.parent_ino().get();
+        let old_name = entry.name().to_string();
+        self.inode_table
+            .insert(entry.renamed(parent_ino, new_name.to_string()));
+
+        self.invalidate(old_parent, &old_name);
+        self.invalidate(parent_ino.get(), new_name);
+        true
+    }
+
+    /// Makes the items added below `parent` (the root when `None`) visible.
+    ///
+    /// They are loaded from the state database on the next lookup or
+    /// listing of the directory. Returns false if `parent` has no inode;
+    /// it is then loaded with its children when its own parent is read.
+    pub fn apply_children_added(&self, parent: Option<&UniqueId>) -> bool {
+        let ino = match parent {
+            None => InodeNumber::ROOT.get(),
+            Some(parent_id) => match self.inode_table.get_by_item_id(parent_id) {
+                Some(ino) => ino,
+                None => return false,
+            },
+        };
+        self.inode_table.mark_partial(ino);
+        if let Some(notifier) = &self.notifier {
+            if let Err(e) = notifier.inval_inode(ino, 0, 0) {
+                debug!(ino, error = %e, "Directory invalidation failed");
+            }
+        }
+        true
+    }
+
+    /// Removes the inode and the cached content of an item that is no
+    /// longer tracked.
+    ///
+    /// Returns false if the item has no inode.
+    pub fn apply_removal(&self, item_id: &UniqueId) -> bool {
+        let Some(entry) = self
+            .inode_table
+            .get_by_item_id(item_id)
+            .and_then(|ino| self.inode_table.remove(ino))
+        else {
+            return false;
+        };
+        if let (Some(cache), Some(remote_id)) = (&self.cache, entry.remote_id()) {
+            if let Err(e) = cache.remove(remote_id) {
+                debug!(%item_id, error = %e, "Failed to drop cached content");
+            }
+        }
+        self.invalidate(entry.parent_ino().get(), entry.name());
+        true
+    }
+
+    /// Drops a cached directory entry from the kernel
+    fn invalidate(&self, parent: u64, name: &str) {
+        if let Some(notifier) = &self.notifier {
+            // ENOENT only means the kernel had not cached the name
+            if let Err(e) = notifier.inval_entry(parent, OsStr::new(name)) {
+                debug!(parent, name, error = %e, "Entry invalidation failed");
+            }
+        }
+    }
+}
+
+impl IItemObserver for RemoteChanges {
+    fn item_moved(&self, item_id: &UniqueId, new_parent: Option<&UniqueId>, new_name: &str) {
+        self.apply_move(item_id, new_parent, new_name);
+    }
+
+    fn children_added(&self, parent: Option<&UniqueId>) {
+        self.apply_children_added(parent);
+    }
+
+    fn item_removed(&self, item_id: &UniqueId) {
+        self.apply_removal(item_id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::SystemTime;
+
+    use lnxdrive_core::domain::{ItemState, RemoteId};
+
+    use super::*;
+    use crate::inode_entry::InodeEntry;
+
+    fn make_entry(ino: u64, parent_ino: u64, name: &str, is_dir: bool) -> InodeEntry {
+        InodeEntry::new(
+            InodeNumber::new(ino),
+            UniqueId::new(),
+            Some(RemoteId::new(format!("remote_{}", ino)).unwrap()),
+            InodeNumber::new(parent_ino),
+            name.to_string(),
+            if is_dir {
+                fuser::FileType::Directory
+            } else {
+                fuser::FileType::RegularFile
+            },
+            1024,
+            0o644,
+            SystemTime::now(),
+            SystemTime::now(),
+            SystemTime::now(),
+            1,
+            ItemState::Hydrated,
+        )
+    }
+
+    #[test]
+    fn test_move_keeps_inode_and_state() {
+        let table = Arc::new(InodeTable::new());
+        table.insert(make_entry(1, 1, "", true));
+        let dir = make_entry(2, 1, "Docs", true);
+        let dir_id = *dir.item_id();
+        table.insert(dir);
+        let file = make_entry(3, 1, "draft.txt", false);
+        let file_id = *file.item_id();
+        file.increment_lookup();
+        table.insert(file);
+
+        let changes = RemoteChanges::new(Arc::clone(&table), None);
+        assert!(changes.apply_move(&file_id, Some(&dir_id), "final.txt"));
+
+        assert!(table.lookup(1, "draft.txt").is_none());
+        let moved = table.lookup(2, "final.txt").unwrap();
+        assert_eq!(moved.ino().get(), 3);
+        assert_eq!(*moved.item_id(), file_id);
+        assert_eq!(moved.lookup_count(), 1);
+        assert_eq!(*moved.state(), ItemState::Hydrated);
+        assert_eq!(table.get_by_item_id(&file_id), Some(3));
+
+        // Back to the root
+        assert!(changes.apply_move(&file_id, None, "final.txt"));
+        assert!(table.lookup(1, "final.txt").is_some());
+    }
+
+    #[test]
+    fn test_removal_drops_inode_and_cached_content() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = Arc::new(ContentCache::new(dir.path().to_path_buf()).unwrap());
+        let table = Arc::new(InodeTable::new());
+        table.insert(make_entry(1, 1, "", true));
+        let file = make_entry(2, 1, "a.txt", false);
+        let file_id = *file.item_id();
+        cache.store(file.remote_id().unwrap(), b"content").unwrap();
+        table.insert(file);
+
+        let changes = RemoteChanges::new(Arc::clone(&table), None).with_cache(Arc::clone(&cache));
+        assert!(changes.apply_removal(&file_id));
+
+        assert!(table.lookup(1, "a.txt").is_none());
+        assert!(table.get_by_item_id(&file_id).is_none());
+        assert!(!cache.exists(&RemoteId::new("remote_2".to_string()).unwrap()));
+        assert!(!changes.apply_removal(&file_id));
+    }
+
+    #[test]
+    fn test_added_children_mark_their_folder_partial() {
+        let table = Arc::new(InodeTable::new());
+        table.insert(make_entry(1, 1, "", true));
+        let dir = make_entry(2, 1, "Docs", true);
+        let dir_id = *dir.item_id();
+        table.insert(dir);
+
+        let changes = RemoteChanges::new(Arc::clone(&table), None);
+        assert!(changes.apply_children_added(Some(&dir_id)));
+        assert!(table.is_partial(2));
+        assert!(changes.apply_children_added(None));
+        assert!(table.is_partial(1));
+        assert!(!changes.apply_children_added(Some(&UniqueId::new())));
+    }
+
+    #[test]
+    fn test_move_of_unknown_item_is_ignored() {
+        let table = Arc::new(InodeTable::new());
+        let file = make_entry(2, 1, "a.txt", false);
+        let file_id = *file.item_id();
+        table.insert(file);
+
+        let changes = RemoteChanges::new(Arc::clone(&table), None);
+        assert!(!changes.apply_move(&UniqueId::new(), None, "b.txt"));
+        assert!(!changes.apply_move(&file_id, Some(&UniqueId::new()), "b.txt"));
+        assert!(table.lookup(1, "a.txt").is_some());
+    }
+}
@@ -0,0 +1,335 @@
+//! Cache verification and self-healing.
+//!
+//! Cached files can be truncated by crashes or a full disk (ENOSPC). Serving
+//! such a file would hand corrupt data to applications, so `CacheScrubber`
+//! checks hydrated items against their expected size (and, opt-in, their
+//! quickXorHash). A damaged item has its cache file deleted and goes back
+//! to `Online`, so it is re-hydrated on next access.
+//!
+//! ## When it runs
+//!
+//! - **Startup**: a size-only pass over the items loaded in `init()`, before
+//!   the inode table is built, so the table reflects the healed states.
+//! - **Background**: when `fuse.cache_scrub_full_hash` is enabled, a
+//!   full-hash pass runs after mounting. Like dehydration, it updates the
+//!   database, which is the source of truth for later accesses.
+
+use std::sync::{
+    atomic::{AtomicU64, Ordering},
+    Arc,
+};
+
+use lnxdrive_cache::pool::DatabasePool;
+use lnxdrive_core::{
+    domain::{sync_item::ItemState, SyncItem},
+    ports::{IStateRepository, ItemFilter},
+};
+use tokio::task::JoinHandle;
+use tracing::{debug, error, info, warn};
+
+use crate::{cache::ContentCache, error::FuseError, write_serializer::WriteSerializerHandle};
+
+/// Items healed by every scrubber since the process started.
+static HEALED_TOTAL: AtomicU64 = AtomicU64::new(0);
+
+/// Number of items whose damaged cache file was removed since the process
+/// started, by the startup pass and background scrubs alike.
+pub fn healed_total() -> u64 {
+    HEALED_TOTAL.load(Ordering::Relaxed)
+}
+
+/// Report of a cache scrub.
+#[derive(Debug, Clone, Default)]
+pub struct ScrubReport {
+    /// Number of hydrated items checked.
+    pub checked: usize,
+    /// Number of items whose damaged cache file was removed.
+    pub healed: usize,
+    /// Number of errors encountered.
+    pub error_count: usize,
+    /// Error messages for failed items.
+    pub errors: Vec<String>,
+}
+
+/// Verifies cached content and heals items whose cache file is damaged.
+pub struct CacheScrubber {
+    /// Content cache being verified.
+    cache: Arc<ContentCache>,
+    /// Whether to compare content hashes in addition to sizes.
+    full_hash: bool,
+}
+
+impl CacheScrubber {
+    /// Create a scrubber; `full_hash` enables the (expensive) hash check.
+    pub fn new(cache: Arc<ContentCache>, full_hash: bool) -> Self {
+        Self { cache, full_hash }
+    }
+
+    /// Whether `item` claims to have cached content that fails verification.
+    pub fn needs_healing(&self, item: &SyncItem) -> bool {
+        if !matches!(item.state(), ItemState::Hydrated | ItemState::Pinned) {
+            return false;
+        }
+        if self.full_hash {
+            !self.cache.verify_content(item)
+        } else {
+            !self.cache.verify(item)
+        }
+    }
+
+    /// Check and heal in-memory items (used during `init()`).
+    ///
+    /// Damaged items have their cache file removed and their state reset to
+    /// `Online`. Returns the report and the indices of the items that
+    /// changed, which the caller must persist.
+    pub fn scrub_items(&self, items: &mut [SyncItem]) -> (ScrubReport, Vec<usize>) {
+        let mut report = ScrubReport::default();
+        let mut changed = Vec::new();
+
+        for (index, item) in items.iter_mut().enumerate() {
+            if !matches!(item.state(), ItemState::Hydrated | ItemState::Pinned) {
+                continue;
+            }
+            report.checked += 1;
+            if !self.needs_healing(item) {
+                continue;
+            }
+            match self.remove_cached(item) {
+                Ok(()) => {
+                    // A pinned file must come back: keep its pin pending
+                    // until it is hydrated again
+                    if matches!(item.state(), ItemState::Pinned) {
+                        item.metadata_mut().set_pin_requested(true);
+                    }
+                    item.reset_state_for_crash_recovery(ItemState::Online);
+                    report.healed += 1;
+                    changed.push(index);
+                }
+                Err(e) => Self::record_error(&mut report, item, e),
+            }
+        }
+
+        self.finish(&report);
+        (report, changed)
+    }
+
+    /// Check every hydrated or pinned item in the database and heal the
+    /// damaged ones through the write serializer.
+    pub async fn run(
+        &self,
+        db_pool: &DatabasePool,
+        write_handle: &WriteSerializerHandle,
+    ) -> Result<ScrubReport, FuseError> {
+        let repo = lnxdrive_cache::SqliteStateRepository::new(db_pool.pool().clone());
+        let mut report = ScrubReport::default();
+
+        for state in [ItemState::Hydrated, ItemState::Pinned] {
+            let items = repo
+                .query_items(&ItemFilter::new().with_state(state))
+                .await
+                .map_err(|e| FuseError::DatabaseError(e.to_string()))?;
+
+            for item in items {
+                report.checked += 1;
+                let cache = Arc::clone(&self.cache);
+                let full_hash = self.full_hash;
+                let check_item = item.clone();
+                let damaged = tokio::task::spawn_blocking(move || {
+                    if full_hash {
+                        !cache.verify_content(&check_item)
+                    } else {
+                        !cache.verify(&check_item)
+                    }
+                })
+                .await
+                .unwrap_or(false);
+                if !damaged {
+                    continue;
+                }
+
+                let healed = match self.remove_cached(&item) {
+                    Ok(()) => {
+                        // Pinned files are hydrated and pinned again on the
+                        // next start
+                        let pin = if matches!(item.state(), ItemState::Pinned) {
+                            write_handle.update_pin_requested(*item.id(), true).await
+                        } else {
+                            Ok(())
+                        };
+                        match pin {
+                            Ok(()) => write_handle
+                                .update_state(*item.id(), ItemState::Online)
+                                .await
+                                .map_err(|e| FuseError::DatabaseError(e.to_string())),
+                            Err(e) => Err(e),
+                        }
+                    }
+                    Err(e) => Err(e),
+                };
+                match healed {
+                    Ok(()) => {
+                        debug!(path = %item.local_path(), "Healed damaged cache file");
+                        report.healed += 1;
+                    }
+                    Err(e) => Self::record_error(&mut report, &item, e),
+                }
+            }
+        }
+
+        self.finish(&report);
+        Ok(report)
+    }
+
+    /// Spawn a background scrub over the database.
+    pub fn start_background(
+        self: Arc<Self>,
+        db_pool: DatabasePool,
+        write_handle: WriteSerializerHandle,
+    ) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            if let Err(e) = self.run(&db_pool, &write_handle).await {
+                error!(error = %e, "Background cache scrub failed");
+            }
+        })
+    }
+
+    fn remove_cached(&self, item: &SyncItem) -> Result<(), FuseError> {
+        warn!(
+            path = %item.local_path(),
+            expected_size = item.size_bytes(),
+            "Cached content failed verification, removing it"
+        );
+        match item.remote_id() {
+            Some(remote_id) => self.cache.remove(remote_id),
+            None => Ok(()),
+        }
+    }
+
+    fn record_error(report: &mut ScrubReport, item: &SyncItem, e: FuseError) {
+        warn!(path = %item.local_path(), error = %e, "Failed to heal cached file");
+        report.error_count += 1;
+        report
+            .errors
+            .push(format!("Heal failed for {}: {}", item.local_path(), e));
+    }
+
+    fn finish(&self, report: &ScrubReport) {
+        HEALED_TOTAL.fetch_add(report.healed as u64, Ordering::Relaxed);
+        info!(
+            checked = report.checked,
+            healed = report.healed,
+            healed_total = healed_total(),
+            errors = report.error_count,
+            full_hash = self.full_hash,
+            "Cache scrub complete"
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+    use lnxdrive_core::domain::{
+        newtypes::{RemoteId, RemotePath, SyncPath},
+        QuickXorHash,
+    };
+    use tempfile::tempdir;
+
+    use super::*;
+
+    fn hydrated_item(remote_id: &str, size: u64, hash: Option<&[u8]>) -> SyncItem {
+        let content_hash = hash.map(|data| {
+            let mut hasher = QuickXorHash::new();
+            hasher.update(data);
+            hasher.finalize_hash().unwrap()
+        });
+        let mut item = SyncItem::from_remote(
+            SyncPath::new(format!("/home/user/OneDrive/{}.txt", remote_id).into()).unwrap(),
+            RemotePath::new(format!("/{}.txt", remote_id)).unwrap(),
+            RemoteId::new(remote_id.to_string()).unwrap(),
+            false,
+            size,
+            content_hash,
+            Utc::now(),
+        )
+        .unwrap();
+        item.start_hydrating().unwrap();
+        item.complete_hydration().unwrap();
+        item
+    }
+
+    #[test]
+    fn test_truncated_file_is_healed_to_online() {
+        let temp_dir = tempdir().unwrap();
+        let cache = Arc::new(ContentCache::new(temp_dir.path().to_path_buf()).unwrap());
+        let mut items = vec![
+            hydrated_item("intact", 5, None),
+            hydrated_item("truncated", 10, None),
+        ];
+        cache
+            .store(items[0].remote_id().unwrap(), b"hello")
+            .unwrap();
+        cache.store(items[1].remote_id().unwrap(), b"half").unwrap();
+
+        let healed_before = healed_total();
+        let scrubber = CacheScrubber::new(Arc::clone(&cache), false);
+        let (report, changed) = scrubber.scrub_items(&mut items);
+
+        assert_eq!(report.checked, 2);
+        assert_eq!(report.healed, 1);
+        assert_eq!(changed, vec![1]);
+        assert_eq!(*items[0].state(), ItemState::Hydrated);
+        assert_eq!(*items[1].state(), ItemState::Online);
+        assert!(!cache.exists(items[1].remote_id().unwrap()));
+        // Other tests heal concurrently
+        assert!(healed_total() > healed_before);
+    }
+
+    #[test]
+    fn test_missing_pinned_file_keeps_its_pin_pending() {
+        let temp_dir = tempdir().unwrap();
+        let cache = Arc::new(ContentCache::new(temp_dir.path().to_path_buf()).unwrap());
+        let mut item = hydrated_item("pinned", 5, None);
+        item.pin().unwrap();
+        let mut items = vec![item];
+
+        let (report, changed) = CacheScrubber::new(cache, false).scrub_items(&mut items);
+
+        assert_eq!(report.healed, 1);
+        assert_eq!(changed, vec![0]);
+        assert_eq!(*items[0].state(), ItemState::Online);
+        assert!(items[0].metadata().pin_requested());
+    }
+
+    #[test]
+    fn test_hash_mismatch_only_detected_with_full_hash() {
+        let temp_dir = tempdir().unwrap();
+        let cache = Arc::new(ContentCache::new(temp_dir.path().to_path_buf()).unwrap());
+        let item = hydrated_item("corrupt", 5, Some(b"hello"));
+        cache.store(item.remote_id().unwrap(), b"jello").unwrap();
+
+        assert!(!CacheScrubber::new(Arc::clone(&cache), false).needs_healing(&item));
+        assert!(CacheScrubber::new(Arc::clone(&cache), true).needs_healing(&item));
+    }
+
+    #[test]
+    fn test_online_items_are_not_checked() {
+        let temp_dir = tempdir().unwrap();
+        let cache = Arc::new(ContentCache::new(temp_dir.path().to_path_buf()).unwrap());
+        let mut items = vec![SyncItem::from_remote(
+            SyncPath::new("/home/user/OneDrive/cloud.txt".into()).unwrap(),
+            RemotePath::new("/cloud.txt".to_string()).unwrap(),
+            RemoteId::new("cloud".to_string()).unwrap(),
+            false,
+            100,
+            None,
+            Utc::now(),
+        )
+        .unwrap()];
+
+        let (report, changed) = CacheScrubber::new(cache, false).scrub_items(&mut items);
+
+        assert_eq!(report.checked, 0);
+        assert!(changed.is_empty());
+    }
+}
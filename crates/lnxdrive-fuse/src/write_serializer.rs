@@ -6,7 +6,7 @@
 use chrono::{DateTime, Utc};
 use lnxdrive_cache::{pool::DatabasePool, repository::SqliteStateRepository};
 use lnxdrive_core::{
-    domain::{newtypes::UniqueId, sync_item::ItemState, SyncItem},
+    domain::{newtypes::UniqueId, sync_item::ItemState, AuditEntry, SyncItem, TransitionTrigger},
     ports::IStateRepository,
 };
 use tokio::sync::{mpsc, oneshot};
@@ -54,6 +54,13 @@ pub enum WriteOp {
         reply: oneshot::Sender<Result<()>>,
     },
 
+    /// Record or clear a pin waiting for the item's content
+    UpdatePinRequested {
+        item_id: UniqueId,
+        requested: bool,
+        reply: oneshot::Sender<Result<()>>,
+    },
+
     /// Increment the inode counter and return the next available inode
     IncrementInodeCounter { reply: oneshot::Sender<Result<u64>> },
 
@@ -63,11 +70,23 @@ pub enum WriteOp {
         reply: oneshot::Sender<Result<()>>,
     },
 
+    /// Save many sync items in chunked transactions
+    SaveItemsBatch {
+        items: Vec<SyncItem>,
+        reply: oneshot::Sender<Result<()>>,
+    },
+
     /// Delete a sync item from the database
     DeleteItem {
         item_id: UniqueId,
         reply: oneshot::Sender<Result<()>>,
     },
+
+    /// Append an entry to the audit log
+    SaveAudit {
+        entry: Box<AuditEntry>,
+        reply: oneshot::Sender<Result<()>>,
+    },
 }
 
 // ============================================================================
@@ -168,6 +187,25 @@ impl WriteSerializerHandle {
             .map_err(|_| FuseError::DatabaseError("WriteSerializer response lost".to_string()))?
     }
 
+    /// Sends a write operation to record or clear a pending pin
+    ///
+    /// Returns when the operation has been processed by the serializer.
+    pub async fn update_pin_requested(&self, item_id: UniqueId, requested: bool) -> Result<()> {
+        let (tx, rx) = oneshot::channel();
+        let op = WriteOp::UpdatePinRequested {
+            item_id,
+            requested,
+            reply: tx,
+        };
+
+        self.tx.send(op).await.map_err(|_| {
+            FuseError::DatabaseError("WriteSerializer task has stopped".to_string())
+        })?;
+
+        rx.await
+            .map_err(|_| FuseError::DatabaseError("WriteSerializer response lost".to_string()))?
+    }
+
     /// Allocates a new inode number by incrementing the counter
     ///
     /// Returns the newly allocated inode number.
@@ -201,6 +239,25 @@ impl WriteSerializerHandle {
             .map_err(|_| FuseError::DatabaseError("WriteSerializer response lost".to_string()))?
     }
 
+    /// Saves many sync items with one operation
+    ///
+    /// The serializer writes them with
+    /// [`save_items_batch`](IStateRepository::save_items_batch), in
+    /// transactions of
+    /// [`SAVE_BATCH_CHUNK`](lnxdrive_cache::repository::SAVE_BATCH_CHUNK)
+    /// items, and processes no other operation in between. Returns when all items are saved.
+    pub async fn save_items_batch(&self, items: Vec<SyncItem>) -> Result<()> {
+        let (tx, rx) = oneshot::channel();
+        let op = WriteOp::SaveItemsBatch { items, reply: tx };
+
+        self.tx.send(op).await.map_err(|_| {
+            FuseError::DatabaseError("WriteSerializer task has stopped".to_string())
+        })?;
+
+        rx.await
+            .map_err(|_| FuseError::DatabaseError("WriteSerializer response lost".to_string()))?
+    }
+
     /// Deletes a sync item from the database
     ///
     /// Returns when the operation has been processed by the serializer.
@@ -215,6 +272,24 @@ impl WriteSerializerHandle {
         rx.await
             .map_err(|_| FuseError::DatabaseError("WriteSerializer response lost".to_string()))?
     }
+
+    /// Sends a write operation to append an entry to the audit log
+    ///
+    /// Returns when the operation has been processed by the serializer.
+    pub async fn save_audit(&self, entry: AuditEntry) -> Result<()> {
+        let (tx, rx) = oneshot::channel();
+        let op = WriteOp::SaveAudit {
+            entry: Box::new(entry),
+            reply: tx,
+        };
+
+        self.tx.send(op).await.map_err(|_| {
+            FuseError::DatabaseError("WriteSerializer task has stopped".to_string())
+        })?;
+
+        rx.await
+            .map_err(|_| FuseError::DatabaseError("WriteSerializer response lost".to_string()))?
+    }
 }
 
 // ============================================================================
@@ -276,7 +351,9 @@ impl WriteSerializer {
         // Buffer size of 100 allows reasonable batching without excessive memory use
         let (tx, rx) = mpsc::channel(100);
 
-        let repository = SqliteStateRepository::new(pool.pool().clone());
+        // Writes of the serializer come from file operations on the mount
+        let repository = SqliteStateRepository::new(pool.pool().clone())
+            .with_transition_trigger(TransitionTrigger::User);
 
         let serializer = Self { rx, repository };
         let handle = WriteSerializerHandle { tx };
@@ -395,6 +472,35 @@ impl WriteSerializer {
                 let _ = reply.send(result);
             }
 
+            WriteOp::UpdatePinRequested {
+                item_id,
+                requested,
+                reply,
+            } => {
+                tracing::trace!(?item_id, requested, "Processing UpdatePinRequested");
+
+                let result = async {
+                    let mut item = self
+                        .repository
+                        .get_item(&item_id)
+                        .await
+                        .map_err(|e| FuseError::DatabaseError(e.to_string()))?
+                        .ok_or_else(|| {
+                            FuseError::NotFound(format!("Item not found: {}", item_id))
+                        })?;
+
+                    item.metadata_mut().set_pin_requested(requested);
+
+                    self.repository
+                        .save_item(&item)
+                        .await
+                        .map_err(|e| FuseError::DatabaseError(e.to_string()))
+                }
+                .await;
+
+                let _ = reply.send(result);
+            }
+
             WriteOp::IncrementInodeCounter { reply } => {
                 tracing::trace!("Processing IncrementInodeCounter");
 
@@ -419,6 +525,18 @@ impl WriteSerializer {
                 let _ = reply.send(result);
             }
 
+            WriteOp::SaveItemsBatch { items, reply } => {
+                tracing::trace!(items = items.len(), "Processing SaveItemsBatch");
+
+                let result = self
+                    .repository
+                    .save_items_batch(&items)
+                    .await
+                    .map_err(|e| FuseError::DatabaseError(e.to_string()));
+
+                let _ = reply.send(result);
+            }
+
             WriteOp::DeleteItem { item_id, reply } => {
                 tracing::trace!(?item_id, "Processing DeleteItem");
 
@@ -430,6 +548,18 @@ impl WriteSerializer {
 
                 let _ = reply.send(result);
             }
+
+            WriteOp::SaveAudit { entry, reply } => {
+                tracing::trace!(action = %entry.action(), "Processing SaveAudit");
+
+                let result = self
+                    .repository
+                    .save_audit(&entry)
+                    .await
+                    .map_err(|e| FuseError::DatabaseError(e.to_string()));
+
+                let _ = reply.send(result);
+            }
         }
     }
 }
@@ -443,6 +573,8 @@ mod tests {
         Account, SyncItem,
     };
 
+    use lnxdrive_cache::repository::SAVE_BATCH_CHUNK;
+
     use super::*;
 
     #[tokio::test]
@@ -511,6 +643,44 @@ mod tests {
         serializer_task.await.unwrap();
     }
 
+    #[tokio::test]
+    async fn test_save_items_batch() {
+        let pool = DatabasePool::in_memory().await.unwrap();
+        let repo = SqliteStateRepository::new(pool.pool().clone());
+        let email = Email::new("test@example.com".to_string()).unwrap();
+        let sync_root = SyncPath::new(PathBuf::from("/home/user/OneDrive")).unwrap();
+        repo.save_account(&Account::new(email, "Test User", "drive123", sync_root))
+            .await
+            .unwrap();
+
+        let (serializer, handle) = WriteSerializer::new(pool);
+        let serializer_task = tokio::spawn(serializer.run());
+
+        // More than one chunk
+        let items: Vec<SyncItem> = (0..SAVE_BATCH_CHUNK + 10)
+            .map(|n| {
+                SyncItem::new(
+                    SyncPath::new(PathBuf::from(format!("/home/user/OneDrive/{n}.txt"))).unwrap(),
+                    RemotePath::new(format!("/{n}.txt")).unwrap(),
+                    false,
+                )
+                .unwrap()
+            })
+            .collect();
+        let last_id = *items.last().unwrap().id();
+        handle.save_items_batch(items).await.unwrap();
+
+        let saved = repo
+            .query_items(&lnxdrive_core::ports::ItemFilter::new())
+            .await
+            .unwrap();
+        assert_eq!(saved.len(), SAVE_BATCH_CHUNK + 10);
+        assert!(repo.get_item(&last_id).await.unwrap().is_some());
+
+        drop(handle);
+        serializer_task.await.unwrap();
+    }
+
     #[tokio::test]
     async fn test_concurrent_writes_are_serialized() {
         // Create in-memory database
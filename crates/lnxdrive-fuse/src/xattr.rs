@@ -9,8 +9,18 @@
 //! - `user.lnxdrive.size` - File size in bytes
 //! - `user.lnxdrive.remote_id` - OneDrive item ID
 //! - `user.lnxdrive.progress` - Hydration progress (only during Hydrating state)
+//! - `user.lnxdrive.provider` - Whether a cloud provider is attached to the mount
+//!
+//! Descriptive metadata reported by OneDrive is exposed read-only when the
+//! item has it, so tools can read it without hydrating the file:
+//!
+//! - `user.lnxdrive.photo.taken` - Capture date of a photo (EXIF)
+//! - `user.lnxdrive.photo.camera_make` - Camera manufacturer of a photo
+//! - `user.lnxdrive.photo.camera_model` - Camera model of a photo
+//! - `user.lnxdrive.author` - Display name of the user who created the file
 
-use lnxdrive_core::domain::ItemState;
+use chrono::SecondsFormat;
+use lnxdrive_core::domain::{ItemFacets, ItemState};
 
 use crate::inode_entry::InodeEntry;
 
@@ -38,20 +48,98 @@ pub const XATTR_REMOTE_ID: &str = "user.lnxdrive.remote_id";
 /// Value: percentage string "0" to "100" (only present during Hydrating state)
 pub const XATTR_PROGRESS: &str = "user.lnxdrive.progress";
 
+/// Extended attribute telling whether the mount can download content.
+///
+/// Value: "attached", or "none" when mounted without a cloud provider, in
+/// which case the content of Online files can't be read
+pub const XATTR_PROVIDER: &str = "user.lnxdrive.provider";
+
+/// Extended attribute for the date a photo was taken.
+///
+/// Value: RFC 3339 UTC timestamp (e.g., "2024-08-03T17:21:09Z"), only present
+/// for photos with EXIF data
+pub const XATTR_PHOTO_TAKEN: &str = "user.lnxdrive.photo.taken";
+
+/// Extended attribute for the manufacturer of the camera that took a photo.
+pub const XATTR_PHOTO_CAMERA_MAKE: &str = "user.lnxdrive.photo.camera_make";
+
+/// Extended attribute for the model of the camera that took a photo.
+pub const XATTR_PHOTO_CAMERA_MODEL: &str = "user.lnxdrive.photo.camera_model";
+
+/// Extended attribute for the author of a file.
+///
+/// Value: display name of the user who created the file in OneDrive
+pub const XATTR_AUTHOR: &str = "user.lnxdrive.author";
+
+/// Extended attributes read from the item's OneDrive facets
+const FACET_XATTRS: [&str; 4] = [
+    XATTR_PHOTO_TAKEN,
+    XATTR_PHOTO_CAMERA_MAKE,
+    XATTR_PHOTO_CAMERA_MODEL,
+    XATTR_AUTHOR,
+];
+
 // ============================================================================
 // Helper functions
 // ============================================================================
 
 /// Returns a list of all supported extended attribute names.
 ///
-/// This is used to respond to `listxattr` FUSE operations.
+/// This is used to respond to `listxattr` FUSE operations. Facet attributes
+/// are only listed when `facets` has a value for them.
 ///
 /// # Returns
 ///
 /// A vector containing all supported xattr names.
 #[must_use]
-pub fn list_xattrs() -> Vec<&'static str> {
-    vec![XATTR_STATE, XATTR_SIZE, XATTR_REMOTE_ID, XATTR_PROGRESS]
+pub fn list_xattrs(facets: Option<&ItemFacets>) -> Vec<&'static str> {
+    let mut names = vec![
+        XATTR_STATE,
+        XATTR_SIZE,
+        XATTR_REMOTE_ID,
+        XATTR_PROGRESS,
+        XATTR_PROVIDER,
+    ];
+    if let Some(facets) = facets {
+        names.extend(
+            FACET_XATTRS
+                .into_iter()
+                .filter(|name| facet_value(facets, name).is_some()),
+        );
+    }
+    names
+}
+
+/// Returns true if `name` is read from the item's OneDrive facets.
+///
+/// The facets are not kept in the inode table, so callers load them only
+/// for these attributes.
+#[must_use]
+pub fn is_facet_xattr(name: &str) -> bool {
+    FACET_XATTRS.contains(&name)
+}
+
+/// Returns the value of `XATTR_PROVIDER`.
+///
+/// The attribute describes the mount rather than the item, so it is not
+/// handled by [`get_xattr`].
+#[must_use]
+pub fn provider_xattr(attached: bool) -> Vec<u8> {
+    let value = if attached { "attached" } else { "none" };
+    value.as_bytes().to_vec()
+}
+
+/// Returns the value of facet attribute `name`, if the facet is present.
+fn facet_value(facets: &ItemFacets, name: &str) -> Option<String> {
+    match name {
+        XATTR_PHOTO_TAKEN => facets
+            .photo_taken
+            .map(|taken| taken.to_rfc3339_opts(SecondsFormat::Secs, true)),
+        XATTR_PHOTO_CAMERA_MAKE => facets.camera_make.clone(),
+        XATTR_PHOTO_CAMERA_MODEL => facets.camera_model.clone(),
+        XATTR_AUTHOR => facets.author.clone(),
+        _ => None,
+    }
 }
 
 /// Gets the value of an extended attribute from an inode entry.
@@ -72,14 +160,22 @@ pub fn list_xattrs() -> Vec<&'static str> {
 /// - `XATTR_SIZE` - Always returns the file size as a decimal string in bytes
 /// - `XATTR_REMOTE_ID` - Returns the OneDrive ID if present, None otherwise
 /// - `XATTR_PROGRESS` - Returns hydration progress (0-100) when state is Hydrating, None otherwise
+/// - Facet attributes (`XATTR_PHOTO_TAKEN`, `XATTR_AUTHOR`, ...) - Return the
+///   facet from `facets` if present, None otherwise
 ///
 /// # Arguments
 ///
 /// * `entry` - The inode entry to read the attribute from
 /// * `name` - The name of the extended attribute to read
 /// * `hydration_progress` - Current hydration progress percentage (0-100), if available
+/// * `facets` - OneDrive facets of the item, needed for facet attributes
 #[must_use]
-pub fn get_xattr(entry: &InodeEntry, name: &str, hydration_progress: Option<u8>) -> Option<Vec<u8>> {
+pub fn get_xattr(
+    entry: &InodeEntry,
+    name: &str,
+    hydration_progress: Option<u8>,
+    facets: Option<&ItemFacets>,
+) -> Option<Vec<u8>> {
     match name {
         XATTR_STATE => Some(entry.state().name().as_bytes().to_vec()),
         XATTR_SIZE => Some(entry.size().to_string().as_bytes().to_vec()),
@@ -92,6 +188,9 @@ pub fn get_xattr(entry: &InodeEntry, name: &str, hydration_progress: Option<u8>)
                 None
             }
         }
+        _ if is_facet_xattr(name) => {
+            facets.and_then(|facets| facet_value(facets, name).map(String::into_bytes))
+        }
         _ => None,
     }
 }
@@ -125,34 +224,41 @@ mod tests {
 
     #[test]
     fn test_list_xattrs() {
-        let xattrs = list_xattrs();
-        assert_eq!(xattrs.len(), 4);
+        let xattrs = list_xattrs(None);
+        assert_eq!(xattrs.len(), 5);
         assert!(xattrs.contains(&XATTR_STATE));
         assert!(xattrs.contains(&XATTR_SIZE));
         assert!(xattrs.contains(&XATTR_REMOTE_ID));
         assert!(xattrs.contains(&XATTR_PROGRESS));
+        assert!(xattrs.contains(&XATTR_PROVIDER));
+    }
+
+    #[test]
+    fn test_provider_xattr() {
+        assert_eq!(provider_xattr(true), b"attached".to_vec());
+        assert_eq!(provider_xattr(false), b"none".to_vec());
     }
 
     #[test]
     fn test_get_xattr_state() {
         let entry = create_test_entry(ItemState::Online, None);
-        let value = get_xattr(&entry, XATTR_STATE, None);
+        let value = get_xattr(&entry, XATTR_STATE, None, None);
         assert!(value.is_some());
         assert_eq!(value.unwrap(), b"Online".to_vec());
 
         let entry = create_test_entry(ItemState::Hydrated, None);
-        let value = get_xattr(&entry, XATTR_STATE, None);
+        let value = get_xattr(&entry, XATTR_STATE, None, None);
         assert_eq!(value.unwrap(), b"Hydrated".to_vec());
 
         let entry = create_test_entry(ItemState::Hydrating, None);
-        let value = get_xattr(&entry, XATTR_STATE, None);
+        let value = get_xattr(&entry, XATTR_STATE, None, None);
         assert_eq!(value.unwrap(), b"Hydrating".to_vec());
     }
 
     #[test]
     fn test_get_xattr_size() {
         let entry = create_test_entry(ItemState::Online, None);
-        let value = get_xattr(&entry, XATTR_SIZE, None);
+        let value = get_xattr(&entry, XATTR_SIZE, None, None);
         assert!(value.is_some());
         assert_eq!(value.unwrap(), b"1024".to_vec());
     }
@@ -161,7 +267,7 @@ mod tests {
     fn test_get_xattr_remote_id_present() {
         let remote_id = RemoteId::new("ABC123XYZ".to_string()).unwrap();
         let entry = create_test_entry(ItemState::Hydrated, Some(remote_id));
-        let value = get_xattr(&entry, XATTR_REMOTE_ID, None);
+        let value = get_xattr(&entry, XATTR_REMOTE_ID, None, None);
         assert!(value.is_some());
         assert_eq!(value.unwrap(), b"ABC123XYZ".to_vec());
     }
@@ -169,7 +275,7 @@ mod tests {
     #[test]
     fn test_get_xattr_remote_id_absent() {
         let entry = create_test_entry(ItemState::Online, None);
-        let value = get_xattr(&entry, XATTR_REMOTE_ID, None);
+        let value = get_xattr(&entry, XATTR_REMOTE_ID, None, None);
         assert!(value.is_none());
     }
 
@@ -177,12 +283,12 @@ mod tests {
     fn test_get_xattr_progress_during_hydrating() {
         let entry = create_test_entry(ItemState::Hydrating, None);
         // Without progress info, defaults to 0
-        let value = get_xattr(&entry, XATTR_PROGRESS, None);
+        let value = get_xattr(&entry, XATTR_PROGRESS, None, None);
         assert!(value.is_some());
         assert_eq!(value.unwrap(), b"0".to_vec());
 
         // With real progress
-        let value = get_xattr(&entry, XATTR_PROGRESS, Some(75));
+        let value = get_xattr(&entry, XATTR_PROGRESS, Some(75), None);
         assert!(value.is_some());
         assert_eq!(value.unwrap(), b"75".to_vec());
     }
@@ -190,25 +296,82 @@ mod tests {
     #[test]
     fn test_get_xattr_progress_not_hydrating() {
         let entry = create_test_entry(ItemState::Online, None);
-        let value = get_xattr(&entry, XATTR_PROGRESS, None);
+        let value = get_xattr(&entry, XATTR_PROGRESS, None, None);
         assert!(value.is_none());
 
         let entry = create_test_entry(ItemState::Hydrated, None);
-        let value = get_xattr(&entry, XATTR_PROGRESS, Some(100));
+        let value = get_xattr(&entry, XATTR_PROGRESS, Some(100), None);
         assert!(value.is_none());
 
         let entry = create_test_entry(ItemState::Pinned, None);
-        let value = get_xattr(&entry, XATTR_PROGRESS, None);
+        let value = get_xattr(&entry, XATTR_PROGRESS, None, None);
         assert!(value.is_none());
     }
 
+    fn photo_facets() -> ItemFacets {
+        ItemFacets {
+            photo_taken: Some("2024-08-03T17:21:09Z".parse().unwrap()),
+            camera_make: Some("Canon".to_string()),
+            camera_model: Some("EOS R6".to_string()),
+            author: None,
+        }
+    }
+
+    #[test]
+    fn test_list_xattrs_includes_present_facets() {
+        let xattrs = list_xattrs(Some(&photo_facets()));
+        assert_eq!(xattrs.len(), 8);
+        assert!(xattrs.contains(&XATTR_PHOTO_TAKEN));
+        assert!(xattrs.contains(&XATTR_PHOTO_CAMERA_MAKE));
+        assert!(xattrs.contains(&XATTR_PHOTO_CAMERA_MODEL));
+        assert!(!xattrs.contains(&XATTR_AUTHOR));
+
+        assert_eq!(list_xattrs(Some(&ItemFacets::default())).len(), 5);
+    }
+
+    #[test]
+    fn test_get_xattr_photo_facets() {
+        let entry = create_test_entry(ItemState::Online, None);
+        let facets = photo_facets();
+
+        let value = get_xattr(&entry, XATTR_PHOTO_TAKEN, None, Some(&facets));
+        assert_eq!(value.unwrap(), b"2024-08-03T17:21:09Z".to_vec());
+        let value = get_xattr(&entry, XATTR_PHOTO_CAMERA_MAKE, None, Some(&facets));
+        assert_eq!(value.unwrap(), b"Canon".to_vec());
+        let value = get_xattr(&entry, XATTR_PHOTO_CAMERA_MODEL, None, Some(&facets));
+        assert_eq!(value.unwrap(), b"EOS R6".to_vec());
+
+        // Absent facets have no value
+        assert!(get_xattr(&entry, XATTR_AUTHOR, None, Some(&facets)).is_none());
+        assert!(get_xattr(&entry, XATTR_PHOTO_TAKEN, None, None).is_none());
+    }
+
+    #[test]
+    fn test_get_xattr_author() {
+        let entry = create_test_entry(ItemState::Hydrated, None);
+        let facets = ItemFacets {
+            author: Some("Ana Ruiz".to_string()),
+            ..ItemFacets::default()
+        };
+        let value = get_xattr(&entry, XATTR_AUTHOR, None, Some(&facets));
+        assert_eq!(value.unwrap(), b"Ana Ruiz".to_vec());
+    }
+
+    #[test]
+    fn test_is_facet_xattr() {
+        assert!(is_facet_xattr(XATTR_PHOTO_TAKEN));
+        assert!(is_facet_xattr(XATTR_AUTHOR));
+        assert!(!is_facet_xattr(XATTR_STATE));
+        assert!(!is_facet_xattr("user.unknown"));
+    }
+
     #[test]
     fn test_get_xattr_unknown() {
         let entry = create_test_entry(ItemState::Online, None);
-        let value = get_xattr(&entry, "user.unknown", None);
+        let value = get_xattr(&entry, "user.unknown", None, None);
         assert!(value.is_none());
 
-        let value = get_xattr(&entry, "security.selinux", None);
+        let value = get_xattr(&entry, "security.selinux", None, None);
         assert!(value.is_none());
     }
 
@@ -218,5 +381,7 @@ mod tests {
         assert_eq!(XATTR_SIZE, "user.lnxdrive.size");
         assert_eq!(XATTR_REMOTE_ID, "user.lnxdrive.remote_id");
         assert_eq!(XATTR_PROGRESS, "user.lnxdrive.progress");
+        assert_eq!(XATTR_PHOTO_TAKEN, "user.lnxdrive.photo.taken");
+        assert_eq!(XATTR_AUTHOR, "user.lnxdrive.author");
     }
 }
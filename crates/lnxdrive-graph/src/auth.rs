@@ -13,7 +13,7 @@
 
 use anyhow::{Context, Result};
 use chrono::{Duration, Utc};
-use lnxdrive_core::ports::cloud_provider::Tokens;
+use lnxdrive_core::{domain::Account, ports::cloud_provider::Tokens};
 use oauth2::{
     basic::BasicClient, AuthUrl, AuthorizationCode, ClientId, CsrfToken, EndpointNotSet,
     EndpointSet, PkceCodeChallenge, PkceCodeVerifier, RedirectUrl, RefreshToken, Scope,
@@ -34,6 +34,12 @@ const REDIRECT_URI: &str = "http://127.0.0.1:8400/callback";
 /// Keyring service name for storing tokens
 const KEYRING_SERVICE: &str = "lnxdrive";
 
+/// Keyring username of the entry listing the stored account ids
+///
+/// Keyrings cannot be enumerated portably, so the ids are kept next to the
+/// tokens. It cannot collide with an account id, which contains an email.
+const KEYRING_INDEX_USER: &str = "accounts";
+
 /// Default OAuth2 scopes for OneDrive access
 const DEFAULT_SCOPES: &[&str] = &["Files.ReadWrite.All", "User.Read", "offline_access"];
 
@@ -84,77 +90,169 @@ impl OAuth2Config {
 /// Uses the `keyring` crate to store tokens securely in the OS credential
 /// store (e.g., GNOME Keyring, KDE Wallet, macOS Keychain).
 /// Tokens are serialized as JSON with the service name "lnxdrive" and the
-/// user's email as the username.
+/// account id (see [`KeyringTokenStorage::account_id`]) as the username.
+///
+/// Earlier versions used the bare email as the username; such entries are
+/// moved to the account id the first time they are loaded.
 pub struct KeyringTokenStorage;
 
 impl KeyringTokenStorage {
-    /// Stores tokens in the system keyring for the given user
+    /// Returns the keyring account id of a drive
+    ///
+    /// Combines the OneDrive drive id with the email, so re-adding an
+    /// account or two accounts sharing an email never share tokens.
     ///
     /// # Arguments
-    /// * `username` - The user's email address (used as keyring username)
-    /// * `tokens` - The OAuth tokens to store
-    pub fn store(username: &str, tokens: &Tokens) -> Result<()> {
-        let entry = keyring::Entry::new(KEYRING_SERVICE, username)
-            .context("Failed to create keyring entry")?;
+    /// * `drive_id` - The OneDrive id of the account
+    /// * `email` - The user's email address
+    pub fn account_id(drive_id: &str, email: &str) -> String {
+        format!("{drive_id}:{email}")
+    }
 
+    /// Returns the keyring account id of `account`
+    pub fn account_id_of(account: &Account) -> String {
+        Self::account_id(account.onedrive_id(), account.email().as_str())
+    }
+
+    /// Stores tokens in the system keyring for the given account
+    ///
+    /// # Arguments
+    /// * `account_id` - The keyring account id (used as keyring username)
+    /// * `tokens` - The OAuth tokens to store
+    pub fn store(account_id: &str, tokens: &Tokens) -> Result<()> {
         let json = serde_json::to_string(tokens).context("Failed to serialize tokens")?;
 
-        entry
+        entry(account_id)?
             .set_password(&json)
             .context("Failed to store tokens in keyring")?;
 
-        debug!("Stored tokens in keyring for user: {}", username);
+        let mut ids = Self::list()?;
+        if !ids.iter().any(|id| id == account_id) {
+            ids.push(account_id.to_string());
+            write_index(&ids)?;
+        }
+
+        debug!("Stored tokens in keyring for account: {}", account_id);
         Ok(())
     }
 
-    /// Loads tokens from the system keyring for the given user
+    /// Loads tokens from the system keyring for the given account
+    ///
+    /// Tokens still stored under the account's bare email are moved to the
+    /// account id.
     ///
     /// # Arguments
-    /// * `username` - The user's email address (used as keyring username)
+    /// * `account_id` - The keyring account id (used as keyring username)
     ///
     /// # Returns
     /// `Some(Tokens)` if found and valid, `None` if not found
-    pub fn load(username: &str) -> Result<Option<Tokens>> {
-        let entry = keyring::Entry::new(KEYRING_SERVICE, username)
-            .context("Failed to create keyring entry")?;
+    pub fn load(account_id: &str) -> Result<Option<Tokens>> {
+        if let Some(tokens) = read_tokens(account_id)? {
+            debug!("Loaded tokens from keyring for account: {}", account_id);
+            return Ok(Some(tokens));
+        }
 
-        match entry.get_password() {
-            Ok(json) => {
-                let tokens: Tokens = serde_json::from_str(&json)
-                    .context("Failed to deserialize tokens from keyring")?;
-                debug!("Loaded tokens from keyring for user: {}", username);
+        let Some(email) = legacy_username(account_id) else {
+            debug!("No tokens found in keyring for account: {}", account_id);
+            return Ok(None);
+        };
+        match read_tokens(email)? {
+            Some(tokens) => {
+                Self::store(account_id, &tokens)?;
+                delete_entry(email)?;
+                info!("Migrated keyring tokens of {} to {}", email, account_id);
                 Ok(Some(tokens))
             }
-            Err(keyring::Error::NoEntry) => {
-                debug!("No tokens found in keyring for user: {}", username);
+            None => {
+                debug!("No tokens found in keyring for account: {}", account_id);
                 Ok(None)
             }
+        }
+    }
+
+    /// Lists the account ids that have tokens in the keyring
+    pub fn list() -> Result<Vec<String>> {
+        match entry(KEYRING_INDEX_USER)?.get_password() {
+            Ok(json) => {
+                serde_json::from_str(&json).context("Failed to parse keyring account index")
+            }
+            Err(keyring::Error::NoEntry) => Ok(Vec::new()),
             Err(e) => Err(anyhow::Error::new(e).context("Failed to read from keyring")),
         }
     }
 
-    /// Removes tokens from the system keyring for the given user
+    /// Removes the tokens of the given account from the system keyring
+    ///
+    /// Also removes tokens left under the account's bare email.
     ///
     /// # Arguments
-    /// * `username` - The user's email address (used as keyring username)
-    pub fn clear(username: &str) -> Result<()> {
-        let entry = keyring::Entry::new(KEYRING_SERVICE, username)
-            .context("Failed to create keyring entry")?;
-
-        match entry.delete_credential() {
-            Ok(()) => {
-                info!("Cleared tokens from keyring for user: {}", username);
-                Ok(())
-            }
-            Err(keyring::Error::NoEntry) => {
-                debug!("No tokens to clear for user: {}", username);
-                Ok(())
-            }
-            Err(e) => Err(anyhow::Error::new(e).context("Failed to delete from keyring")),
+    /// * `account_id` - The keyring account id (used as keyring username)
+    pub fn delete(account_id: &str) -> Result<()> {
+        let mut deleted = delete_entry(account_id)?;
+        if let Some(email) = legacy_username(account_id) {
+            deleted |= delete_entry(email)?;
+        }
+
+        let mut ids = Self::list()?;
+        let count = ids.len();
+        ids.retain(|id| id != account_id);
+        if ids.len() != count {
+            write_index(&ids)?;
+        }
+
+        if deleted {
+            info!("Cleared tokens from keyring for account: {}", account_id);
+        } else {
+            debug!("No tokens to clear for account: {}", account_id);
         }
+        Ok(())
+    }
+}
+
+/// Opens the keyring entry of `username` in the lnxdrive service
+fn entry(username: &str) -> Result<keyring::Entry> {
+    keyring::Entry::new(KEYRING_SERVICE, username).context("Failed to create keyring entry")
+}
+
+/// Reads the tokens stored under `username`, if any
+fn read_tokens(username: &str) -> Result<Option<Tokens>> {
+    match entry(username)?.get_password() {
+        Ok(json) => {
+            let tokens =
+                serde_json::from_str(&json).context("Failed to deserialize tokens from keyring")?;
+            Ok(Some(tokens))
+        }
+        Err(keyring::Error::NoEntry) => Ok(None),
+        Err(e) => Err(anyhow::Error::new(e).context("Failed to read from keyring")),
+    }
+}
+
+/// Deletes the entry of `username`; returns whether there was one
+fn delete_entry(username: &str) -> Result<bool> {
+    match entry(username)?.delete_credential() {
+        Ok(()) => Ok(true),
+        Err(keyring::Error::NoEntry) => Ok(false),
+        Err(e) => Err(anyhow::Error::new(e).context("Failed to delete from keyring")),
     }
 }
 
+/// Replaces the list of stored account ids
+fn write_index(ids: &[String]) -> Result<()> {
+    let json = serde_json::to_string(ids).context("Failed to serialize keyring account index")?;
+    entry(KEYRING_INDEX_USER)?
+        .set_password(&json)
+        .context("Failed to store keyring account index")
+}
+
+/// Username the tokens of `account_id` were stored under before accounts
+/// were namespaced: the email
+fn legacy_username(account_id: &str) -> Option<&str> {
+    account_id
+        .split_once(':')
+        .map(|(_, email)| email)
+        .filter(|email| !email.is_empty())
+}
+
 // ============================================================================
 // PKCEFlow
 // ============================================================================
@@ -620,4 +718,161 @@ mod tests {
         let adapter = GraphAuthAdapter::with_app_id("test-id");
         assert_eq!(adapter.config().app_id, "test-id");
     }
+
+    // ------------------------------------------------------------------------
+    // KeyringTokenStorage (against an in-memory keyring)
+    // ------------------------------------------------------------------------
+
+    mod memory_keyring {
+        use std::{
+            any::Any,
+            collections::HashMap,
+            sync::{Mutex, MutexGuard, OnceLock},
+        };
+
+        use keyring::credential::{Credential, CredentialApi, CredentialBuilderApi};
+
+        type Store = Mutex<HashMap<String, Vec<u8>>>;
+
+        fn store() -> &'static Store {
+            static STORE: OnceLock<Store> = OnceLock::new();
+            STORE.get_or_init(Default::default)
+        }
+
+        /// Installs an empty in-memory keyring shared by all entries
+        ///
+        /// Hold the returned guard for the whole test: the keyring is
+        /// process-wide.
+        pub fn install() -> MutexGuard<'static, ()> {
+            static LOCK: Mutex<()> = Mutex::new(());
+            let guard = LOCK.lock().unwrap_or_else(|e| e.into_inner());
+            keyring::set_default_credential_builder(Box::new(Builder));
+            store().lock().unwrap().clear();
+            guard
+        }
+
+        pub fn contains(user: &str) -> bool {
+            store().lock().unwrap().contains_key(user)
+        }
+
+        #[derive(Debug)]
+        struct Builder;
+
+        impl CredentialBuilderApi for Builder {
+            fn build(
+                &self,
+                _target: Option<&str>,
+                _service: &str,
+                user: &str,
+            ) -> keyring::Result<Box<Credential>> {
+                Ok(Box::new(Entry(user.to_string())))
+            }
+
+            fn as_any(&self) -> &dyn Any {
+                self
+            }
+        }
+
+        #[derive(Debug)]
+        struct Entry(String);
+
+        impl CredentialApi for Entry {
+            fn set_secret(&self, secret: &[u8]) -> keyring::Result<()> {
+                store()
+                    .lock()
+                    .unwrap()
+                    .insert(self.0.clone(), secret.to_vec());
+                Ok(())
+            }
+
+            fn get_secret(&self) -> keyring::Result<Vec<u8>> {
+                store()
+                    .lock()
+                    .unwrap()
+                    .get(&self.0)
+                    .cloned()
+                    .ok_or(keyring::Error::NoEntry)
+            }
+
+            fn delete_credential(&self) -> keyring::Result<()> {
+                store()
+                    .lock()
+                    .unwrap()
+                    .remove(&self.0)
+                    .map(|_| ())
+                    .ok_or(keyring::Error::NoEntry)
+            }
+
+            fn as_any(&self) -> &dyn Any {
+                self
+            }
+        }
+    }
+
+    fn tokens(access_token: &str) -> Tokens {
+        Tokens {
+            access_token: access_token.to_string(),
+            refresh_token: Some(format!("{access_token}-refresh")),
+            expires_at: Utc::now() + Duration::hours(1),
+        }
+    }
+
+    #[test]
+    fn test_keyring_keeps_accounts_apart() {
+        let _keyring = memory_keyring::install();
+        // The same email re-added with another drive must not share tokens
+        let first = KeyringTokenStorage::account_id("drive-a", "user@example.com");
+        let second = KeyringTokenStorage::account_id("drive-b", "user@example.com");
+
+        KeyringTokenStorage::store(&first, &tokens("token-a")).unwrap();
+        KeyringTokenStorage::store(&second, &tokens("token-b")).unwrap();
+        KeyringTokenStorage::store(&second, &tokens("token-b2")).unwrap();
+
+        assert_eq!(
+            KeyringTokenStorage::load(&first)
+                .unwrap()
+                .unwrap()
+                .access_token,
+            "token-a"
+        );
+        assert_eq!(
+            KeyringTokenStorage::load(&second)
+                .unwrap()
+                .unwrap()
+                .access_token,
+            "token-b2"
+        );
+        assert_eq!(
+            KeyringTokenStorage::list().unwrap(),
+            vec![first.clone(), second.clone()]
+        );
+
+        KeyringTokenStorage::delete(&first).unwrap();
+        assert!(KeyringTokenStorage::load(&first).unwrap().is_none());
+        assert!(KeyringTokenStorage::load(&second).unwrap().is_some());
+        assert_eq!(KeyringTokenStorage::list().unwrap(), vec![second.clone()]);
+
+        // Deleting twice is not an error
+        KeyringTokenStorage::delete(&first).unwrap();
+        KeyringTokenStorage::delete(&second).unwrap();
+        assert!(KeyringTokenStorage::list().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_keyring_migrates_email_keyed_tokens() {
+        let _keyring = memory_keyring::install();
+        let account_id = KeyringTokenStorage::account_id("drive-a", "old@example.com");
+        let legacy = serde_json::to_string(&tokens("legacy")).unwrap();
+        entry("old@example.com")
+            .unwrap()
+            .set_password(&legacy)
+            .unwrap();
+
+        let loaded = KeyringTokenStorage::load(&account_id).unwrap().unwrap();
+
+        assert_eq!(loaded.access_token, "legacy");
+        assert!(!memory_keyring::contains("old@example.com"));
+        assert!(memory_keyring::contains(&account_id));
+        assert_eq!(KeyringTokenStorage::list().unwrap(), vec![account_id]);
+    }
 }
@@ -19,16 +19,42 @@
 use std::{sync::Arc, time::Duration};
 
 use anyhow::{Context, Result};
-use lnxdrive_core::{domain::newtypes::RemoteId, ports::cloud_provider::UserInfo};
+use lnxdrive_core::{
+    config::HttpConfig,
+    domain::{newtypes::RemoteId, ClockSkew, DriveQuota},
+    ports::cloud_provider::UserInfo,
+};
 use reqwest::{Client, Method, RequestBuilder, Response, StatusCode};
 use serde::Deserialize;
 use tracing::{debug, info, warn};
 
-use crate::rate_limit::{parse_retry_after, AdaptiveRateLimiter};
+use crate::{
+    rate_limit::{parse_retry_after, AdaptiveRateLimiter},
+    special_folder::SpecialFolders,
+    user_agent::user_agent,
+};
 
 /// Base URL for Microsoft Graph API v1.0
 const GRAPH_BASE_URL: &str = "https://graph.microsoft.com/v1.0";
 
+/// Timeout of the request made by [`GraphClient::measure_clock_skew`]
+const CLOCK_PROBE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Builds the HTTP client, sending the configured User-Agent with every
+/// request and applying the connect and read timeouts
+fn build_http_client(config: &HttpConfig) -> Client {
+    let mut builder = Client::builder()
+        .connect_timeout(Duration::from_secs(config.connect_timeout))
+        .read_timeout(Duration::from_secs(config.read_timeout));
+    if let Some(user_agent) = user_agent(config) {
+        builder = builder.user_agent(user_agent);
+    }
+    builder.build().unwrap_or_else(|e| {
+        warn!(error = %e, "Failed to configure HTTP client, using defaults");
+        Client::new()
+    })
+}
+
 // ============================================================================
 // Graph API response types
 // ============================================================================
@@ -97,6 +123,14 @@ pub struct GraphClient {
     access_token: String,
     /// Optional adaptive rate limiter for proactive throttling
     rate_limiter: Option<Arc<AdaptiveRateLimiter>>,
+    /// Display names of the drive's special folders
+    special_folders: Arc<SpecialFolders>,
+    /// Items requested per delta page (`$top`), or the server default
+    delta_page_size: Option<u32>,
+    /// Overall timeout of an API request
+    request_timeout: Duration,
+    /// Slowest expected transfer rate in bytes per second
+    min_transfer_rate: u64,
 }
 
 impl GraphClient {
@@ -106,11 +140,16 @@ impl GraphClient {
     /// * `access_token` - A valid OAuth2 access token for Microsoft Graph
     pub fn new(access_token: impl Into<String>) -> Self {
         Self {
-            client: Client::new(),
+            client: build_http_client(&HttpConfig::default()),
             base_url: GRAPH_BASE_URL.to_string(),
             access_token: access_token.into(),
             rate_limiter: None,
+            special_folders: Arc::new(SpecialFolders::new()),
+            delta_page_size: None,
+            request_timeout: Duration::ZERO,
+            min_transfer_rate: 0,
         }
+        .with_timeouts(&HttpConfig::default())
     }
 
     /// Creates a new GraphClient with a custom base URL (useful for testing)
@@ -120,11 +159,34 @@ impl GraphClient {
     /// * `base_url` - Custom base URL for API requests
     pub fn with_base_url(access_token: impl Into<String>, base_url: impl Into<String>) -> Self {
         Self {
-            client: Client::new(),
+            client: build_http_client(&HttpConfig::default()),
             base_url: base_url.into(),
             access_token: access_token.into(),
             rate_limiter: None,
+            special_folders: Arc::new(SpecialFolders::new()),
+            delta_page_size: None,
+            request_timeout: Duration::ZERO,
+            min_transfer_rate: 0,
         }
+        .with_timeouts(&HttpConfig::default())
+    }
+
+    /// Applies the HTTP settings of the configuration (User-Agent, timeouts)
+    pub fn with_http_config(mut self, config: &HttpConfig) -> Self {
+        self.client = build_http_client(config);
+        self.with_timeouts(config)
+    }
+
+    fn with_timeouts(mut self, config: &HttpConfig) -> Self {
+        self.request_timeout = Duration::from_secs(config.request_timeout);
+        self.min_transfer_rate = config.min_transfer_rate_kbps.max(1) * 1024;
+        self
+    }
+
+    /// Requests `size` items per delta page instead of the server default
+    pub fn with_delta_page_size(mut self, size: u32) -> Self {
+        self.delta_page_size = Some(size);
+        self
     }
 
     /// Sets the adaptive rate limiter for this client.
@@ -171,6 +233,9 @@ impl GraphClient {
     /// Creates an authenticated request builder for the given method and path
     ///
     /// Automatically prepends the base URL and adds the Authorization header.
+    /// The request times out after the configured `request_timeout`; use
+    /// [`RequestBuilder::timeout`] with [`Self::transfer_timeout`] to give
+    /// requests carrying file content more time.
     ///
     /// # Arguments
     /// * `method` - HTTP method (GET, POST, PUT, DELETE, etc.)
@@ -180,6 +245,15 @@ impl GraphClient {
         self.client
             .request(method, &url)
             .bearer_auth(&self.access_token)
+            .timeout(self.request_timeout)
+    }
+
+    /// Overall timeout of a request transferring `bytes` of file content
+    ///
+    /// The request timeout plus the time the transfer takes at the
+    /// configured minimum transfer rate.
+    pub fn transfer_timeout(&self, bytes: u64) -> Duration {
+        self.request_timeout + Duration::from_secs(bytes / self.min_transfer_rate)
     }
 
     /// Retrieves information about the authenticated user
@@ -206,7 +280,7 @@ impl GraphClient {
             .context("Failed to parse /me response")?;
 
         // Get drive quota
-        let (quota_used, quota_total) = self.get_drive_quota().await?;
+        let quota = self.get_drive_quota().await?;
 
         let email = me
             .mail
@@ -223,16 +297,17 @@ impl GraphClient {
             email,
             display_name,
             id,
-            quota_used,
-            quota_total,
+            quota_used: quota.used,
+            quota_total: quota.total,
         })
     }
 
     /// Retrieves drive quota information
     ///
-    /// # Returns
-    /// A tuple of `(used_bytes, total_bytes)`
-    pub async fn get_drive_quota(&self) -> Result<(u64, u64)> {
+    /// Makes `GET /me/drive`. Drives without a storage limit (some
+    /// business plans) omit `total` or report it as 0; both come back as
+    /// [`DriveQuota::unlimited`].
+    pub async fn get_drive_quota(&self) -> Result<DriveQuota> {
         debug!("Fetching drive quota from /me/drive");
 
         let drive: DriveResponse = self
@@ -250,12 +325,63 @@ impl GraphClient {
 
         let total = drive.quota.as_ref().and_then(|q| q.total).unwrap_or(0);
 
-        if total == 0 {
-            warn!("Drive quota total is 0, this may indicate an API issue");
+        let quota = DriveQuota::new(used, total);
+        if quota.is_unlimited() {
+            debug!("Drive quota: {} bytes used, no limit", used);
+        } else {
+            debug!("Drive quota: {} / {} bytes", used, total);
         }
+        Ok(quota)
+    }
+
+    /// Revokes the refresh tokens issued to the authenticated user
+    ///
+    /// Makes `POST /me/revokeSignInSessions`. The Microsoft identity platform
+    /// has no per-token revocation endpoint, so this is the closest equivalent:
+    /// all refresh tokens for the user become invalid and must be re-acquired
+    /// through an interactive login.
+    ///
+    /// This signs the user out of every app and device, not only lnxdrive,
+    /// so it must only be called when the user explicitly asks for it.
+    pub async fn revoke_sign_in_sessions(&self) -> Result<()> {
+        debug!("Revoking sign-in sessions via /me/revokeSignInSessions");
+
+        self.request(Method::POST, "/me/revokeSignInSessions")
+            .header(reqwest::header::CONTENT_LENGTH, 0)
+            .send()
+            .await
+            .context("Failed to send revokeSignInSessions request")?
+            .error_for_status()
+            .context("POST /me/revokeSignInSessions returned error status")?;
 
-        debug!("Drive quota: {} / {} bytes", used, total);
-        Ok((used, total))
+        info!("Revoked sign-in sessions");
+        Ok(())
+    }
+
+    /// Measures the skew between the local clock and the Graph servers
+    ///
+    /// Sends an unauthenticated `GET` to the base URL and compares the
+    /// `Date` header of the response with the local time. The request is
+    /// expected to be rejected; any response carries the header, so no
+    /// valid token is needed.
+    pub async fn measure_clock_skew(&self) -> Result<ClockSkew> {
+        let response = self
+            .client
+            .get(&self.base_url)
+            .timeout(CLOCK_PROBE_TIMEOUT)
+            .send()
+            .await
+            .context("Failed to reach the Graph API")?;
+        let received = chrono::Utc::now();
+        let date = response
+            .headers()
+            .get(reqwest::header::DATE)
+            .and_then(|value| value.to_str().ok())
+            .context("Graph API response has no Date header")?;
+        let skew = ClockSkew::from_date_header(date, received)
+            .with_context(|| format!("Invalid Date header: {date}"))?;
+        debug!(skew_secs = skew.seconds, "Measured clock skew");
+        Ok(skew)
     }
 
     /// Downloads a file by its remote item ID
@@ -273,8 +399,12 @@ impl GraphClient {
         let path = format!("/me/drive/items/{}/content", id.as_str());
         debug!("Downloading file: {}", id.as_str());
 
+        // The size is not known up front, so the download has no overall
+        // timeout; the read timeout still aborts it once it stalls
         let response = self
-            .request(Method::GET, &path)
+            .client
+            .get(format!("{}{}", self.base_url, path))
+            .bearer_auth(&self.access_token)
             .send()
             .await
             .context("Failed to send download request")?
@@ -411,6 +541,17 @@ impl GraphClient {
     pub fn client(&self) -> &Client {
         &self.client
     }
+
+    /// Returns the special folder names of the drive, shared by all the
+    /// requests of this client
+    pub fn special_folders(&self) -> &Arc<SpecialFolders> {
+        &self.special_folders
+    }
+
+    /// Returns the number of items requested per delta page, if set
+    pub fn delta_page_size(&self) -> Option<u32> {
+        self.delta_page_size
+    }
 }
 
 #[cfg(test)]
@@ -450,6 +591,22 @@ mod tests {
         assert_eq!(auth_header, "Bearer test-token");
     }
 
+    #[test]
+    fn test_transfer_timeout_grows_with_size() {
+        let config = HttpConfig {
+            request_timeout: 30,
+            min_transfer_rate_kbps: 100,
+            ..HttpConfig::default()
+        };
+        let client = GraphClient::new("token").with_http_config(&config);
+
+        assert_eq!(client.transfer_timeout(0), Duration::from_secs(30));
+        assert_eq!(
+            client.transfer_timeout(10 * 1024 * 1024),
+            Duration::from_secs(30 + 102)
+        );
+    }
+
     #[test]
     fn test_custom_base_url() {
         let client = GraphClient::with_base_url("token", "http://localhost:8080");
@@ -6,7 +6,8 @@
 //! ## Delta Query Flow
 //!
 //! 1. **Initial sync**: Call [`get_delta`] with `token = None` to get all items
-//! 2. **Follow pages**: The function automatically follows `@odata.nextLink` pages
+//! 2. **Follow pages**: The function automatically follows `@odata.nextLink` pages;
+//!    [`get_delta_pages`] hands them out as they arrive, fetching one page ahead
 //! 3. **Save token**: The returned [`DeltaResponse`] contains a `delta_link` with
 //!    a token for the next sync
 //! 4. **Incremental sync**: Call [`get_delta`] with the saved token to get only changes
@@ -29,21 +30,39 @@
 //! # }
 //! ```
 
+use std::sync::Arc;
+
 use anyhow::{Context, Result};
 use chrono::{DateTime, Utc};
 use lnxdrive_core::{
-    domain::newtypes::DeltaToken,
-    ports::cloud_provider::{DeltaItem, DeltaResponse},
+    domain::{newtypes::DeltaToken, ItemFacets},
+    ports::cloud_provider::{
+        DeltaItem, DeltaPageSource, DeltaPages, DeltaResponse, DeltaTokenExpired,
+    },
 };
 use reqwest::{Client, Method};
 use serde::Deserialize;
+use tokio::sync::mpsc;
 use tracing::{debug, warn};
 
-use crate::client::GraphClient;
+use crate::{
+    client::GraphClient,
+    special_folder::{is_drive_root, SpecialFolders},
+};
 
 /// Path for the delta endpoint relative to the Graph API base URL
 const DELTA_PATH: &str = "/me/drive/root/delta";
 
+/// `Prefer` header of delta requests, as recommended for sync clients
+///
+/// Items the user lost access to are reported as deleted instead of being
+/// left behind, and items below folders the user cannot read (business
+/// drives) are not skipped.
+const DELTA_PREFER: &str = "deltashowremovedasdeleted, deltatraversepermissiongaps";
+
+/// Number of delta pages fetched ahead of the page being processed
+const DELTA_PREFETCH_PAGES: usize = 1;
+
 // ============================================================================
 // Microsoft Graph API response types (JSON deserialization)
 // ============================================================================
@@ -90,6 +109,9 @@ struct GraphDriveItem {
     /// Last modified date and time in ISO 8601 format
     last_modified_date_time: Option<DateTime<Utc>>,
 
+    /// Timestamps as reported by the client that wrote the file
+    file_system_info: Option<GraphFileSystemInfo>,
+
     /// Reference to the parent item
     parent_reference: Option<GraphParentReference>,
 
@@ -101,6 +123,18 @@ struct GraphDriveItem {
 
     /// Deleted facet (present if the item has been deleted)
     deleted: Option<GraphDeletedFacet>,
+
+    /// Special folder facet (present on folders such as Documents)
+    special_folder: Option<GraphSpecialFolderFacet>,
+
+    /// Root facet (present on the drive root itself)
+    root: Option<serde_json::Value>,
+
+    /// Photo facet (present on photos with EXIF data)
+    photo: Option<GraphPhotoFacet>,
+
+    /// Identity of the user who created the item
+    created_by: Option<GraphIdentitySet>,
 }
 
 /// Parent reference information for a drive item
@@ -115,6 +149,72 @@ struct GraphParentReference {
     path: Option<String>,
 }
 
+/// File system timestamps of a drive item
+///
+/// Unlike the top-level `lastModifiedDateTime`, which is when OneDrive
+/// stored the change, these are the times of the file on the client that
+/// uploaded it.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct GraphFileSystemInfo {
+    /// Modification time of the file on the uploading client
+    pub(crate) last_modified_date_time: Option<DateTime<Utc>>,
+}
+
+impl GraphFileSystemInfo {
+    /// Picks the file's own modification time over the server-side one
+    pub(crate) fn modified(
+        info: Option<&Self>,
+        fallback: Option<DateTime<Utc>>,
+    ) -> Option<DateTime<Utc>> {
+        info.and_then(|i| i.last_modified_date_time).or(fallback)
+    }
+}
+
+/// Photo facet with the EXIF data OneDrive extracted from an image
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct GraphPhotoFacet {
+    /// When the photo was taken
+    pub(crate) taken_date_time: Option<DateTime<Utc>>,
+    /// Manufacturer of the camera
+    pub(crate) camera_make: Option<String>,
+    /// Model of the camera
+    pub(crate) camera_model: Option<String>,
+}
+
+/// Identity set such as `createdBy`
+#[derive(Debug, Deserialize)]
+pub(crate) struct GraphIdentitySet {
+    /// The user, if a user (rather than an application) is involved
+    pub(crate) user: Option<GraphIdentity>,
+}
+
+/// A single identity of an identity set
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct GraphIdentity {
+    /// Display name of the identity
+    pub(crate) display_name: Option<String>,
+}
+
+impl GraphPhotoFacet {
+    /// Collects the facets kept on the SyncItem
+    pub(crate) fn facets(
+        photo: Option<&Self>,
+        created_by: Option<&GraphIdentitySet>,
+    ) -> ItemFacets {
+        ItemFacets {
+            photo_taken: photo.and_then(|p| p.taken_date_time),
+            camera_make: photo.and_then(|p| p.camera_make.clone()),
+            camera_model: photo.and_then(|p| p.camera_model.clone()),
+            author: created_by
+                .and_then(|c| c.user.as_ref())
+                .and_then(|u| u.display_name.clone()),
+        }
+    }
+}
+
 /// File facet indicating the item is a file
 ///
 /// Contains file-specific metadata like hashes.
@@ -155,6 +255,16 @@ struct GraphDeletedFacet {
     state: Option<String>,
 }
 
+/// Special folder facet
+///
+/// `name` is the stable, non-localized role of the folder (e.g.
+/// `documents`), see [`special_folder`](crate::special_folder).
+#[derive(Debug, Deserialize)]
+struct GraphSpecialFolderFacet {
+    /// Role of the folder
+    name: String,
+}
+
 // ============================================================================
 // DeltaParser - converts Graph API responses to port-level types
 // ============================================================================
@@ -172,6 +282,7 @@ impl DeltaParser {
     /// - Determines if the item is a directory based on the `folder` facet
     /// - Determines if the item is deleted based on the `deleted` facet
     /// - Extracts the quickXorHash from the file facet
+    /// - Prefers `fileSystemInfo.lastModifiedDateTime` as modification time
     /// - Strips the `/drive/root:` prefix from the parent path
     fn parse_item(item: GraphDriveItem) -> DeltaItem {
         let is_deleted = item.deleted.is_some();
@@ -196,16 +307,23 @@ impl DeltaParser {
         // Extract parent ID
         let parent_id = item.parent_reference.as_ref().and_then(|pr| pr.id.clone());
 
+        let modified = GraphFileSystemInfo::modified(
+            item.file_system_info.as_ref(),
+            item.last_modified_date_time,
+        );
+        let facets = GraphPhotoFacet::facets(item.photo.as_ref(), item.created_by.as_ref());
+
         DeltaItem {
             id: item.id,
             name: item.name,
             path,
             size: item.size,
             hash,
-            modified: item.last_modified_date_time,
+            modified,
             is_deleted,
             is_directory,
             parent_id,
+            facets,
         }
     }
 
@@ -238,9 +356,33 @@ impl DeltaParser {
 
     /// Parse a complete Graph API delta response into a port-level [`DeltaResponse`]
     ///
-    /// Converts all items and preserves the pagination links.
-    fn parse_response(response: GraphDeltaResponse) -> DeltaResponse {
-        let items = response.value.into_iter().map(Self::parse_item).collect();
+    /// Converts all items and preserves the pagination links. The drive
+    /// root is the sync root itself and is left out. Top-level special
+    /// folders are recorded in `special`, and paths below them use the
+    /// fixed local name of the folder.
+    fn parse_response(response: GraphDeltaResponse, special: &SpecialFolders) -> DeltaResponse {
+        for item in &response.value {
+            let parent_path = item
+                .parent_reference
+                .as_ref()
+                .and_then(|pr| pr.path.as_deref());
+            if let Some(facet) = &item.special_folder {
+                if is_drive_root(parent_path) {
+                    special.learn(&facet.name, &item.name);
+                }
+            }
+        }
+
+        let items = response
+            .value
+            .into_iter()
+            .filter(|item| item.root.is_none())
+            .map(|item| {
+                let mut item = Self::parse_item(item);
+                special.localize(&mut item);
+                item
+            })
+            .collect();
 
         DeltaResponse {
             items,
@@ -271,7 +413,8 @@ impl DeltaParser {
 /// Fetches all delta changes from OneDrive, automatically following pagination
 ///
 /// Makes the initial delta request and follows all `@odata.nextLink` pages
-/// until the final page with `@odata.deltaLink` is reached.
+/// until the final page with `@odata.deltaLink` is reached. See
+/// [`get_delta_pages`] to process the pages as they arrive.
 ///
 /// # Arguments
 ///
@@ -290,17 +433,49 @@ impl DeltaParser {
 /// - The API returns a non-success status
 /// - The response cannot be parsed as JSON
 pub async fn get_delta(client: &GraphClient, token: Option<&DeltaToken>) -> Result<DeltaResponse> {
-    // Build the initial request URL
-    let path = match token {
-        Some(t) => format!("{}?token={}", DELTA_PATH, t.as_str()),
-        None => DELTA_PATH.to_string(),
-    };
+    let response = get_delta_pages(client, token)
+        .await?
+        .into_response()
+        .await?;
+
+    debug!(
+        total_items = response.items.len(),
+        has_delta_link = response.delta_link.is_some(),
+        "Delta query complete"
+    );
+
+    if response.delta_link.is_none() {
+        warn!("Delta query completed without a deltaLink; next sync may require full re-scan");
+    }
+
+    Ok(response)
+}
+
+/// Starts a delta query and returns its pages as they arrive
+///
+/// The first page is requested before returning, so an expired token
+/// fails here. The following `@odata.nextLink` pages are fetched by a
+/// background task that stays one page ahead of the caller; pages depend
+/// on each other, so they are still requested one at a time, but each
+/// round-trip overlaps with the caller's work on the previous page.
+/// Dropping the returned [`DeltaPages`] stops the task.
+///
+/// # Errors
+///
+/// Same as [`get_delta`] for the first page. Errors of later pages are
+/// returned by [`DeltaPages::next_page`].
+pub async fn get_delta_pages(
+    client: &GraphClient,
+    token: Option<&DeltaToken>,
+) -> Result<DeltaPages> {
+    let path = delta_path(token, client.delta_page_size());
 
     debug!(has_token = token.is_some(), "Starting delta query");
 
     // Make the initial request using GraphClient's request() method
     let http_response = client
         .request(Method::GET, &path)
+        .header("Prefer", DELTA_PREFER)
         .send()
         .await
         .context("Failed to send delta request")?;
@@ -309,7 +484,7 @@ pub async fn get_delta(client: &GraphClient, token: Option<&DeltaToken>) -> Resu
     // A 410 means the delta token has expired and the client must
     // perform a full resync by re-querying without a token.
     if http_response.status() == reqwest::StatusCode::GONE {
-        anyhow::bail!("Delta token expired (410 Gone)");
+        return Err(anyhow::Error::new(DeltaTokenExpired).context("Delta query returned 410 Gone"));
     }
 
     let raw_response: GraphDeltaResponse = http_response
@@ -319,47 +494,64 @@ pub async fn get_delta(client: &GraphClient, token: Option<&DeltaToken>) -> Resu
         .await
         .context("Failed to parse delta response JSON")?;
 
-    let mut response = DeltaParser::parse_response(raw_response);
-
-    debug!(
-        items = response.items.len(),
-        has_next = response.next_link.is_some(),
-        "Received initial delta page"
-    );
-
-    // Follow pagination via nextLink
-    let mut page_count: u32 = 1;
-    while let Some(next_link) = response.next_link.take() {
-        page_count += 1;
-        debug!(page = page_count, "Following delta nextLink");
-
-        let page = get_delta_page(client, &next_link).await?;
+    Ok(pages_from(client, raw_response))
+}
 
-        debug!(
-            page = page_count,
-            items = page.items.len(),
-            has_next = page.next_link.is_some(),
-            "Received delta page"
-        );
+/// Continues a delta query from the `@odata.nextLink` of one of its pages
+///
+/// Returns the remaining pages like [`get_delta_pages`], starting with the
+/// page `next_link` points to.
+///
+/// # Errors
+///
+/// Returns an error if the page cannot be fetched, e.g. because the link
+/// has expired. Errors of later pages are returned by
+/// [`DeltaPages::next_page`].
+pub async fn resume_delta_pages(client: &GraphClient, next_link: &str) -> Result<DeltaPages> {
+    debug!("Resuming delta query");
+    let raw_response = fetch_raw_page(client.client(), client.access_token(), next_link).await?;
+    Ok(pages_from(client, raw_response))
+}
 
-        // Accumulate items from this page
-        response.items.extend(page.items);
-        response.next_link = page.next_link;
-        response.delta_link = page.delta_link;
-    }
+/// Turns the first page of a query into its [`DeltaPages`], fetching the
+/// following pages in the background
+fn pages_from(client: &GraphClient, raw_response: GraphDeltaResponse) -> DeltaPages {
+    // Start fetching the next page before parsing this one
+    let rest = raw_response
+        .next_link
+        .clone()
+        .map(|next_link| PrefetchedPages::spawn(client, next_link));
+    let first = DeltaParser::parse_response(raw_response, client.special_folders());
 
     debug!(
-        total_items = response.items.len(),
-        total_pages = page_count,
-        has_delta_link = response.delta_link.is_some(),
-        "Delta query complete"
+        items = first.items.len(),
+        has_next = first.next_link.is_some(),
+        "Received initial delta page"
     );
 
-    if response.delta_link.is_none() {
-        warn!("Delta query completed without a deltaLink; next sync may require full re-scan");
+    match rest {
+        Some(rest) => DeltaPages::new(first, Box::new(rest)),
+        None => DeltaPages::single(first),
     }
+}
 
-    Ok(response)
+/// Builds the path of the first request of a delta query
+///
+/// The page size (`$top`) is carried over into the `@odata.nextLink` of
+/// every following page by the server.
+fn delta_path(token: Option<&DeltaToken>, page_size: Option<u32>) -> String {
+    let mut params = Vec::new();
+    if let Some(token) = token {
+        params.push(format!("token={}", token.as_str()));
+    }
+    if let Some(size) = page_size {
+        params.push(format!("$top={size}"));
+    }
+    if params.is_empty() {
+        DELTA_PATH.to_string()
+    } else {
+        format!("{}?{}", DELTA_PATH, params.join("&"))
+    }
 }
 
 /// Fetches a single page of delta results from a nextLink URL
@@ -382,14 +574,26 @@ pub async fn get_delta(client: &GraphClient, token: Option<&DeltaToken>) -> Resu
 ///
 /// Returns an error if the HTTP request fails or the response cannot be parsed.
 pub async fn get_delta_page(client: &GraphClient, next_link: &str) -> Result<DeltaResponse> {
+    let raw_response = fetch_raw_page(client.client(), client.access_token(), next_link).await?;
+    Ok(DeltaParser::parse_response(
+        raw_response,
+        client.special_folders(),
+    ))
+}
+
+/// Requests one nextLink page and deserializes it
+async fn fetch_raw_page(
+    http_client: &Client,
+    access_token: &str,
+    next_link: &str,
+) -> Result<GraphDeltaResponse> {
     // nextLink is an absolute URL, so we cannot use client.request()
     // which prepends the base URL. Instead, create a direct request
     // with Bearer auth using the client's access token.
-    let http_client = Client::new();
-
-    let raw_response: GraphDeltaResponse = http_client
+    http_client
         .get(next_link)
-        .bearer_auth(client.access_token())
+        .bearer_auth(access_token)
+        .header("Prefer", DELTA_PREFER)
         .send()
         .await
         .context("Failed to send delta page request")?
@@ -397,9 +601,65 @@ pub async fn get_delta_page(client: &GraphClient, next_link: &str) -> Result<Del
         .context("Delta page request returned error status")?
         .json()
         .await
-        .context("Failed to parse delta page response JSON")?;
+        .context("Failed to parse delta page response JSON")
+}
 
-    Ok(DeltaParser::parse_response(raw_response))
+/// Pages of a delta query after the first, fetched by a background task
+///
+/// The task follows the nextLink chain and sends each page (or the first
+/// error) through a channel with room for [`DELTA_PREFETCH_PAGES`] pages,
+/// so it runs that far ahead of the consumer. It stops when the receiver
+/// is dropped.
+struct PrefetchedPages {
+    pages: mpsc::Receiver<Result<GraphDeltaResponse>>,
+    page_count: u32,
+    special_folders: Arc<SpecialFolders>,
+}
+
+impl PrefetchedPages {
+    fn spawn(client: &GraphClient, mut next_link: String) -> Self {
+        let (tx, pages) = mpsc::channel(DELTA_PREFETCH_PAGES);
+        let http_client = client.client().clone();
+        let access_token = client.access_token().to_string();
+
+        tokio::spawn(async move {
+            loop {
+                let page = fetch_raw_page(&http_client, &access_token, &next_link).await;
+                let following = page.as_ref().ok().and_then(|p| p.next_link.clone());
+                if tx.send(page).await.is_err() {
+                    break;
+                }
+                match following {
+                    Some(link) => next_link = link,
+                    None => break,
+                }
+            }
+        });
+
+        Self {
+            pages,
+            page_count: 1,
+            special_folders: Arc::clone(client.special_folders()),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl DeltaPageSource for PrefetchedPages {
+    async fn next_page(&mut self) -> Option<Result<DeltaResponse>> {
+        let page = self.pages.recv().await?;
+        self.page_count += 1;
+        Some(page.map(|raw| {
+            let page = DeltaParser::parse_response(raw, &self.special_folders);
+            debug!(
+                page = self.page_count,
+                items = page.items.len(),
+                has_next = page.next_link.is_some(),
+                "Received delta page"
+            );
+            page
+        }))
+    }
 }
 
 // ============================================================================
@@ -510,6 +770,29 @@ mod tests {
         assert!(item.last_modified_date_time.is_none());
     }
 
+    #[test]
+    fn test_file_system_info_takes_precedence() {
+        let json = r#"{
+            "value": [
+                {
+                    "id": "item-002",
+                    "name": "notes.txt",
+                    "size": 10,
+                    "lastModifiedDateTime": "2025-06-15T10:30:00Z",
+                    "fileSystemInfo": {
+                        "lastModifiedDateTime": "2024-01-02T03:04:05Z"
+                    },
+                    "file": {}
+                }
+            ],
+            "@odata.deltaLink": "https://graph.microsoft.com/v1.0/me/drive/root/delta?token=fs"
+        }"#;
+
+        let mut response: GraphDeltaResponse = serde_json::from_str(json).unwrap();
+        let item = DeltaParser::parse_item(response.value.remove(0));
+        assert_eq!(item.modified, Some("2024-01-02T03:04:05Z".parse().unwrap()));
+    }
+
     #[test]
     fn test_deserialize_empty_response() {
         let json = r#"{
@@ -589,6 +872,7 @@ mod tests {
             name: "report.pdf".to_string(),
             size: Some(524288),
             last_modified_date_time: Some("2025-07-01T14:00:00Z".parse().unwrap()),
+            file_system_info: None,
             parent_reference: Some(GraphParentReference {
                 id: Some("parent-001".to_string()),
                 path: Some("/drive/root:/Documents/Reports".to_string()),
@@ -600,6 +884,10 @@ mod tests {
             }),
             folder: None,
             deleted: None,
+            special_folder: None,
+            root: None,
+            photo: None,
+            created_by: None,
         };
 
         let item = DeltaParser::parse_item(graph_item);
@@ -613,6 +901,35 @@ mod tests {
         assert!(!item.is_deleted);
         assert!(!item.is_directory);
         assert_eq!(item.parent_id, Some("parent-001".to_string()));
+        assert!(item.facets.is_empty());
+    }
+
+    #[test]
+    fn test_parse_photo_facets() {
+        let graph_item: GraphDriveItem = serde_json::from_value(serde_json::json!({
+            "id": "photo-001",
+            "name": "beach.jpg",
+            "size": 2048,
+            "file": {},
+            "photo": {
+                "takenDateTime": "2024-08-03T17:21:09Z",
+                "cameraMake": "Canon",
+                "cameraModel": "EOS R6",
+                "iso": 100
+            },
+            "createdBy": { "user": { "displayName": "Ana Ruiz", "id": "u1" } }
+        }))
+        .unwrap();
+
+        let facets = DeltaParser::parse_item(graph_item).facets;
+
+        assert_eq!(
+            facets.photo_taken,
+            Some("2024-08-03T17:21:09Z".parse().unwrap())
+        );
+        assert_eq!(facets.camera_make.as_deref(), Some("Canon"));
+        assert_eq!(facets.camera_model.as_deref(), Some("EOS R6"));
+        assert_eq!(facets.author.as_deref(), Some("Ana Ruiz"));
     }
 
     #[test]
@@ -622,6 +939,7 @@ mod tests {
             name: "Photos".to_string(),
             size: Some(0),
             last_modified_date_time: Some("2025-06-20T09:00:00Z".parse().unwrap()),
+            file_system_info: None,
             parent_reference: Some(GraphParentReference {
                 id: Some("root-id".to_string()),
                 path: Some("/drive/root:".to_string()),
@@ -631,6 +949,10 @@ mod tests {
                 child_count: Some(42),
             }),
             deleted: None,
+            special_folder: None,
+            root: None,
+            photo: None,
+            created_by: None,
         };
 
         let item = DeltaParser::parse_item(graph_item);
@@ -650,12 +972,17 @@ mod tests {
             name: "obsolete.txt".to_string(),
             size: None,
             last_modified_date_time: None,
+            file_system_info: None,
             parent_reference: None,
             file: None,
             folder: None,
             deleted: Some(GraphDeletedFacet {
                 state: Some("deleted".to_string()),
             }),
+            special_folder: None,
+            root: None,
+            photo: None,
+            created_by: None,
         };
 
         let item = DeltaParser::parse_item(graph_item);
@@ -677,6 +1004,7 @@ mod tests {
             name: "readme.md".to_string(),
             size: Some(1024),
             last_modified_date_time: None,
+            file_system_info: None,
             parent_reference: Some(GraphParentReference {
                 id: Some("root-id".to_string()),
                 path: Some("/drive/root:".to_string()),
@@ -684,6 +1012,10 @@ mod tests {
             file: Some(GraphFileFacet { hashes: None }),
             folder: None,
             deleted: None,
+            special_folder: None,
+            root: None,
+            photo: None,
+            created_by: None,
         };
 
         let item = DeltaParser::parse_item(graph_item);
@@ -700,6 +1032,7 @@ mod tests {
             name: "deep-file.txt".to_string(),
             size: Some(256),
             last_modified_date_time: None,
+            file_system_info: None,
             parent_reference: Some(GraphParentReference {
                 id: Some("parent-deep".to_string()),
                 path: Some("/drive/root:/A/B/C/D".to_string()),
@@ -707,6 +1040,10 @@ mod tests {
             file: Some(GraphFileFacet { hashes: None }),
             folder: None,
             deleted: None,
+            special_folder: None,
+            root: None,
+            photo: None,
+            created_by: None,
         };
 
         let item = DeltaParser::parse_item(graph_item);
@@ -723,6 +1060,7 @@ mod tests {
                     name: "file1.txt".to_string(),
                     size: Some(100),
                     last_modified_date_time: None,
+                    file_system_info: None,
                     parent_reference: Some(GraphParentReference {
                         id: Some("root".to_string()),
                         path: Some("/drive/root:".to_string()),
@@ -730,12 +1068,17 @@ mod tests {
                     file: Some(GraphFileFacet { hashes: None }),
                     folder: None,
                     deleted: None,
+                    special_folder: None,
+                    root: None,
+                    photo: None,
+                    created_by: None,
                 },
                 GraphDriveItem {
                     id: "item-2".to_string(),
                     name: "folder1".to_string(),
                     size: Some(0),
                     last_modified_date_time: None,
+                    file_system_info: None,
                     parent_reference: Some(GraphParentReference {
                         id: Some("root".to_string()),
                         path: Some("/drive/root:".to_string()),
@@ -745,16 +1088,25 @@ mod tests {
                         child_count: Some(3),
                     }),
                     deleted: None,
+                    special_folder: None,
+                    root: None,
+                    photo: None,
+                    created_by: None,
                 },
                 GraphDriveItem {
                     id: "item-3".to_string(),
                     name: "deleted.txt".to_string(),
                     size: None,
                     last_modified_date_time: None,
+                    file_system_info: None,
                     parent_reference: None,
                     file: None,
                     folder: None,
                     deleted: Some(GraphDeletedFacet { state: None }),
+                    special_folder: None,
+                    root: None,
+                    photo: None,
+                    created_by: None,
                 },
             ],
             next_link: None,
@@ -763,7 +1115,7 @@ mod tests {
             ),
         };
 
-        let result = DeltaParser::parse_response(response);
+        let result = DeltaParser::parse_response(response, &SpecialFolders::new());
 
         assert_eq!(result.items.len(), 3);
         assert!(result.next_link.is_none());
@@ -793,7 +1145,7 @@ mod tests {
             ),
         };
 
-        let result = DeltaParser::parse_response(response);
+        let result = DeltaParser::parse_response(response, &SpecialFolders::new());
 
         assert_eq!(result.items.len(), 0);
         assert!(result.delta_link.is_some());
@@ -897,7 +1249,7 @@ mod tests {
         }"#;
 
         let raw: GraphDeltaResponse = serde_json::from_str(json).unwrap();
-        let response = DeltaParser::parse_response(raw);
+        let response = DeltaParser::parse_response(raw, &SpecialFolders::new());
 
         assert_eq!(response.items.len(), 1);
         let item = &response.items[0];
@@ -949,7 +1301,7 @@ mod tests {
         }"#;
 
         let raw: GraphDeltaResponse = serde_json::from_str(json).unwrap();
-        let response = DeltaParser::parse_response(raw);
+        let response = DeltaParser::parse_response(raw, &SpecialFolders::new());
 
         assert_eq!(response.items.len(), 3);
 
@@ -991,7 +1343,7 @@ mod tests {
         }"#;
 
         let raw: GraphDeltaResponse = serde_json::from_str(json).unwrap();
-        let response = DeltaParser::parse_response(raw);
+        let response = DeltaParser::parse_response(raw, &SpecialFolders::new());
 
         assert_eq!(response.items.len(), 1);
         assert!(response.next_link.is_some());
@@ -999,20 +1351,100 @@ mod tests {
         assert!(response.next_link.unwrap().contains("$skiptoken=next123"));
     }
 
+    #[test]
+    fn test_special_folder_gets_fixed_local_path() {
+        let json = r#"{
+            "value": [
+                {
+                    "id": "root-id",
+                    "name": "root",
+                    "root": {},
+                    "folder": { "childCount": 1 }
+                },
+                {
+                    "id": "docs-id",
+                    "name": "Documentos",
+                    "parentReference": { "id": "root-id", "path": "/drive/root:" },
+                    "folder": { "childCount": 1 },
+                    "specialFolder": { "name": "documents" }
+                },
+                {
+                    "id": "file-id",
+                    "name": "informe.pdf",
+                    "size": 10,
+                    "parentReference": { "id": "docs-id", "path": "/drive/root:/Documentos" },
+                    "file": {}
+                }
+            ],
+            "@odata.deltaLink": "https://graph.microsoft.com/v1.0/me/drive/root/delta?token=sp"
+        }"#;
+
+        let special = SpecialFolders::new();
+        let raw: GraphDeltaResponse = serde_json::from_str(json).unwrap();
+        let response = DeltaParser::parse_response(raw, &special);
+
+        // The drive root is not an item of its own
+        assert_eq!(response.items.len(), 2);
+        assert_eq!(response.items[0].name, "Documents");
+        assert_eq!(response.items[0].path.as_deref(), Some("/Documents"));
+        assert_eq!(
+            response.items[1].path.as_deref(),
+            Some("/Documents/informe.pdf")
+        );
+
+        // A later page still maps the folder, also once its name changes
+        let json = r#"{
+            "value": [
+                {
+                    "id": "docs-id",
+                    "name": "Dokumente",
+                    "parentReference": { "id": "root-id", "path": "/drive/root:" },
+                    "folder": { "childCount": 1 },
+                    "specialFolder": { "name": "documents" }
+                },
+                {
+                    "id": "file-id",
+                    "name": "informe.pdf",
+                    "size": 12,
+                    "parentReference": { "id": "docs-id", "path": "/drive/root:/Dokumente" },
+                    "file": {}
+                }
+            ]
+        }"#;
+        let raw: GraphDeltaResponse = serde_json::from_str(json).unwrap();
+        let response = DeltaParser::parse_response(raw, &special);
+        assert_eq!(response.items[0].path.as_deref(), Some("/Documents"));
+        assert_eq!(
+            response.items[1].path.as_deref(),
+            Some("/Documents/informe.pdf")
+        );
+    }
+
     // ========================================================================
     // get_delta URL construction test (verifies path building)
     // ========================================================================
 
     #[test]
     fn test_delta_path_without_token() {
-        let path = DELTA_PATH.to_string();
+        let path = delta_path(None, None);
         assert_eq!(path, "/me/drive/root/delta");
     }
 
     #[test]
     fn test_delta_path_with_token() {
         let token = DeltaToken::new("test-token-value".to_string()).unwrap();
-        let path = format!("{}?token={}", DELTA_PATH, token.as_str());
+        let path = delta_path(Some(&token), None);
         assert_eq!(path, "/me/drive/root/delta?token=test-token-value");
     }
+
+    #[test]
+    fn test_delta_path_with_page_size() {
+        assert_eq!(delta_path(None, Some(100)), "/me/drive/root/delta?$top=100");
+
+        let token = DeltaToken::new("abc".to_string()).unwrap();
+        assert_eq!(
+            delta_path(Some(&token), Some(100)),
+            "/me/drive/root/delta?token=abc&$top=100"
+        );
+    }
 }
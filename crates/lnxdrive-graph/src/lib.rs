@@ -11,17 +11,26 @@
 //! - [`auth`] - OAuth2 PKCE authentication flow components
 //! - [`client`] - Microsoft Graph API HTTP client
 //! - [`delta`] - Delta queries for incremental synchronization
+//! - [`special_folder`] - Fixed local names for localized special folders
+//! - [`subscription`] - Change-notification subscriptions (webhooks)
 //! - [`upload`] - File upload operations (small and large/chunked)
+//! - [`upload_checkpoint`] - Persisted progress of resumable uploads
+//! - [`user_agent`] - User-Agent sent with Graph requests
 
 pub mod auth;
 pub mod client;
 pub mod delta;
 pub mod provider;
 pub mod rate_limit;
+pub mod special_folder;
+pub mod subscription;
 pub mod upload;
+pub mod upload_checkpoint;
+pub mod user_agent;
 
 use std::time::Duration;
 
+use lnxdrive_core::domain::ReasonCode;
 use thiserror::Error;
 
 /// Errors that can occur when communicating with the Microsoft Graph API
@@ -56,7 +65,11 @@ pub enum GraphError {
 
     /// A network-level error occurred
     #[error("Network error: {0}")]
-    NetworkError(#[from] reqwest::Error),
+    NetworkError(reqwest::Error),
+
+    /// The request did not complete within its timeout
+    #[error("Timed out: {0}")]
+    Timeout(reqwest::Error),
 
     /// The OAuth2 token has expired and must be refreshed
     #[error("Token expired")]
@@ -66,3 +79,66 @@ pub enum GraphError {
     #[error("Invalid response: {0}")]
     InvalidResponse(String),
 }
+
+impl GraphError {
+    /// Returns the [`ReasonCode`] describing this error
+    ///
+    /// A network error carrying an HTTP status is classified by the status.
+    pub fn reason(&self) -> ReasonCode {
+        match self {
+            GraphError::Unauthorized(_) | GraphError::TokenExpired => ReasonCode::AuthError,
+            GraphError::Forbidden(_) => ReasonCode::PermissionDenied,
+            GraphError::Conflict(_) => ReasonCode::Conflict,
+            GraphError::TooManyRequests { .. } => ReasonCode::RateLimited,
+            GraphError::NetworkError(e) => match e.status() {
+                Some(status) => ReasonCode::from_http_status(status.as_u16()),
+                None => ReasonCode::NetworkError,
+            },
+            GraphError::Timeout(_) => ReasonCode::NetworkError,
+            GraphError::NotFound(_)
+            | GraphError::ServerError(_)
+            | GraphError::InvalidResponse(_) => ReasonCode::Unknown,
+        }
+    }
+}
+
+impl From<reqwest::Error> for GraphError {
+    /// Separates timeouts, which are worth retrying as they are, from other
+    /// network errors
+    fn from(error: reqwest::Error) -> Self {
+        if error.is_timeout() {
+            GraphError::Timeout(error)
+        } else {
+            GraphError::NetworkError(error)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_error_reason() {
+        let cases = [
+            (GraphError::Unauthorized("x".into()), ReasonCode::AuthError),
+            (GraphError::TokenExpired, ReasonCode::AuthError),
+            (
+                GraphError::Forbidden("x".into()),
+                ReasonCode::PermissionDenied,
+            ),
+            (GraphError::Conflict("x".into()), ReasonCode::Conflict),
+            (
+                GraphError::TooManyRequests {
+                    retry_after: Duration::from_secs(1),
+                },
+                ReasonCode::RateLimited,
+            ),
+            (GraphError::NotFound("x".into()), ReasonCode::Unknown),
+            (GraphError::ServerError("x".into()), ReasonCode::Unknown),
+        ];
+        for (error, reason) in cases {
+            assert_eq!(error.reason(), reason, "{error}");
+        }
+    }
+}
@@ -12,16 +12,22 @@
 //! - `get_metadata` and `delete_item` make direct Graph API calls via the
 //!   underlying `GraphClient::request()` method.
 
-use std::path::Path;
+use std::{path::Path, sync::Arc};
 
 use anyhow::{Context, Result};
 use chrono::{DateTime, Utc};
 use futures_util::StreamExt;
 use lnxdrive_core::{
-    domain::newtypes::{DeltaToken, RemoteId, RemotePath},
-    ports::cloud_provider::{AuthFlow, DeltaItem, DeltaResponse, ICloudProvider, Tokens, UserInfo},
+    domain::{
+        newtypes::{DeltaToken, RemoteId, RemotePath},
+        DriveQuota, QuickXorHash,
+    },
+    ports::cloud_provider::{
+        AuthFlow, CommitCheck, DeltaItem, DeltaPages, DeltaResponse, FolderPage, ICloudProvider,
+        ItemPage, RemoteFolder, RemoteItemNotFound, Tokens, UserInfo,
+    },
 };
-use reqwest::Method;
+use reqwest::{Method, StatusCode};
 use serde::Deserialize;
 use tokio::{
     io::{AsyncSeekExt, AsyncWriteExt},
@@ -29,7 +35,12 @@ use tokio::{
 };
 use tracing::debug;
 
-use crate::{client::GraphClient, delta, upload};
+use crate::{
+    client::GraphClient,
+    delta::{self, GraphFileSystemInfo, GraphIdentitySet, GraphPhotoFacet},
+    upload,
+    upload_checkpoint::UploadCheckpointStore,
+};
 
 // ============================================================================
 // Graph API response type for get_metadata
@@ -49,6 +60,8 @@ struct GraphMetadataItem {
     size: Option<u64>,
     /// Last modified timestamp
     last_modified_date_time: Option<DateTime<Utc>>,
+    /// Timestamps of the file on the client that uploaded it
+    file_system_info: Option<GraphFileSystemInfo>,
     /// Parent reference
     parent_reference: Option<GraphParentRef>,
     /// File facet (present if item is a file)
@@ -57,6 +70,10 @@ struct GraphMetadataItem {
     folder: Option<serde_json::Value>,
     /// Deleted facet (present if item was deleted)
     deleted: Option<serde_json::Value>,
+    /// Photo facet (present on photos with EXIF data)
+    photo: Option<GraphPhotoFacet>,
+    /// Identity of the user who created the item
+    created_by: Option<GraphIdentitySet>,
 }
 
 /// Parent reference from metadata response
@@ -84,6 +101,98 @@ struct GraphHashes {
     quick_xor_hash: Option<String>,
 }
 
+/// A page of `GET .../children` restricted to folders
+#[derive(Debug, Deserialize)]
+struct GraphFolderChildren {
+    /// Child items
+    #[serde(default)]
+    value: Vec<GraphFolderChild>,
+    /// URL of the next page
+    #[serde(rename = "@odata.nextLink")]
+    next_link: Option<String>,
+}
+
+/// A child item of a folder listing
+#[derive(Debug, Deserialize)]
+struct GraphFolderChild {
+    /// Item name
+    name: String,
+    /// Folder facet (absent for files)
+    folder: Option<GraphFolderFacet>,
+}
+
+/// Folder facet of a folder listing
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GraphFolderFacet {
+    /// Number of items directly inside the folder
+    #[serde(default)]
+    child_count: u64,
+}
+
+/// A page of `GET .../children`
+#[derive(Debug, Deserialize)]
+struct GraphChildren {
+    /// Child items
+    #[serde(default)]
+    value: Vec<GraphMetadataItem>,
+    /// URL of the next page
+    #[serde(rename = "@odata.nextLink")]
+    next_link: Option<String>,
+}
+
+/// Folders requested per page of [`GraphCloudProvider::list_folders`]
+const FOLDER_PAGE_SIZE: u32 = 200;
+
+/// Items requested per page of [`GraphCloudProvider::list_children`]
+const CHILDREN_PAGE_SIZE: u32 = 200;
+
+/// Returns the Graph path listing the children of the folder at `path`
+///
+/// Folders below a mapped special folder are addressed through
+/// `/me/drive/special/{name}`, like uploads.
+fn children_path(client: &GraphClient, path: &RemotePath) -> String {
+    let path = path.as_str().trim_end_matches('/');
+    if path.is_empty() {
+        return "/me/drive/root/children".to_string();
+    }
+    match client.special_folders().to_special(path) {
+        Some((facet, rest)) if rest.is_empty() => format!("/me/drive/special/{facet}/children"),
+        Some((facet, rest)) => format!("/me/drive/special/{facet}:{rest}:/children"),
+        None => format!("/me/drive/root:{path}:/children"),
+    }
+}
+
+/// Download target for a file, as returned by
+/// [`GraphCloudProvider::get_download_info`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DownloadInfo {
+    /// Pre-authenticated, short-lived download URL
+    pub url: String,
+    /// File size in bytes, if reported
+    pub size: Option<u64>,
+    /// Expected quickXorHash (Base64), if reported
+    pub quick_xor_hash: Option<String>,
+    /// Modification time of the file, if reported
+    pub modified: Option<DateTime<Utc>>,
+}
+
+/// Returns the Graph path addressing the item at `path`
+///
+/// Like [`children_path`], items below a mapped special folder are
+/// addressed through `/me/drive/special/{name}`.
+fn item_path(client: &GraphClient, path: &RemotePath) -> String {
+    let path = path.as_str().trim_end_matches('/');
+    if path.is_empty() {
+        return "/me/drive/root".to_string();
+    }
+    match client.special_folders().to_special(path) {
+        Some((facet, rest)) if rest.is_empty() => format!("/me/drive/special/{facet}"),
+        Some((facet, rest)) => format!("/me/drive/special/{facet}:{rest}"),
+        None => format!("/me/drive/root:{path}"),
+    }
+}
+
 /// Converts a [`GraphMetadataItem`] into a port-level [`DeltaItem`]
 fn metadata_to_delta_item(item: GraphMetadataItem) -> DeltaItem {
     let is_directory = item.folder.is_some();
@@ -118,6 +227,9 @@ fn metadata_to_delta_item(item: GraphMetadataItem) -> DeltaItem {
         });
 
     let parent_id = item.parent_reference.as_ref().and_then(|pr| pr.id.clone());
+    let modified =
+        GraphFileSystemInfo::modified(item.file_system_info.as_ref(), item.last_modified_date_time);
+    let facets = GraphPhotoFacet::facets(item.photo.as_ref(), item.created_by.as_ref());
 
     DeltaItem {
         id: item.id,
@@ -125,10 +237,22 @@ fn metadata_to_delta_item(item: GraphMetadataItem) -> DeltaItem {
         path,
         size: item.size,
         hash,
-        modified: item.last_modified_date_time,
+        modified,
         is_deleted,
         is_directory,
         parent_id,
+        facets,
+    }
+}
+
+/// Queries the special folder names before the first incremental delta
+///
+/// A full delta reports the special folders itself; an incremental one
+/// only does when they change, but paths below them must still be mapped.
+async fn load_special_folders(client: &GraphClient, token: Option<&DeltaToken>) {
+    let special = client.special_folders();
+    if token.is_some() && !special.is_loaded() {
+        special.load(client).await;
     }
 }
 
@@ -144,6 +268,8 @@ fn metadata_to_delta_item(item: GraphMetadataItem) -> DeltaItem {
 pub struct GraphCloudProvider {
     /// The underlying Graph API client, protected by a mutex
     client: Mutex<GraphClient>,
+    /// Where resumable upload sessions are checkpointed, if enabled
+    upload_checkpoints: Option<Arc<UploadCheckpointStore>>,
 }
 
 impl GraphCloudProvider {
@@ -151,8 +277,16 @@ impl GraphCloudProvider {
     pub fn new(client: GraphClient) -> Self {
         Self {
             client: Mutex::new(client),
+            upload_checkpoints: None,
         }
     }
+
+    /// Checkpoints large uploads in `store` so they can resume after an
+    /// interruption
+    pub fn with_upload_checkpoints(mut self, store: Arc<UploadCheckpointStore>) -> Self {
+        self.upload_checkpoints = Some(store);
+        self
+    }
 }
 
 #[async_trait::async_trait]
@@ -179,9 +313,38 @@ impl ICloudProvider for GraphCloudProvider {
     async fn get_delta(&self, token: Option<&DeltaToken>) -> Result<DeltaResponse> {
         let client = self.client.lock().await;
         debug!(has_token = token.is_some(), "GraphCloudProvider::get_delta");
+        load_special_folders(&client, token).await;
         delta::get_delta(&client, token).await
     }
 
+    /// Queries for changes, handing out the pages as they arrive
+    ///
+    /// Delegates to [`delta::get_delta_pages`], which fetches the next page
+    /// in the background while the caller processes the current one.
+    async fn get_delta_pages(&self, token: Option<&DeltaToken>) -> Result<DeltaPages> {
+        let client = self.client.lock().await;
+        debug!(
+            has_token = token.is_some(),
+            "GraphCloudProvider::get_delta_pages"
+        );
+        load_special_folders(&client, token).await;
+        delta::get_delta_pages(&client, token).await
+    }
+
+    /// Continues an interrupted delta query from one of its nextLinks
+    ///
+    /// Delegates to [`delta::resume_delta_pages`]. The pages that reported
+    /// the special folders may be gone, so their names are queried first.
+    async fn resume_delta_pages(&self, next_link: &str) -> Result<DeltaPages> {
+        let client = self.client.lock().await;
+        debug!("GraphCloudProvider::resume_delta_pages");
+        let special = client.special_folders();
+        if !special.is_loaded() {
+            special.load(&client).await;
+        }
+        delta::resume_delta_pages(&client, next_link).await
+    }
+
     /// Downloads a file's content by its remote ID
     ///
     /// Delegates to [`GraphClient::download_file`].
@@ -212,7 +375,8 @@ impl ICloudProvider for GraphCloudProvider {
 
     /// Uploads a large file using a resumable upload session
     ///
-    /// Delegates to [`upload::upload_large`].
+    /// Delegates to [`upload::upload_large_resumable`], checkpointing
+    /// progress when a checkpoint store is configured.
     async fn upload_file_session(
         &self,
         parent_path: &RemotePath,
@@ -227,7 +391,54 @@ impl ICloudProvider for GraphCloudProvider {
             size = data.len(),
             "GraphCloudProvider::upload_file_session"
         );
-        upload::upload_large(&client, parent_path, name, data, progress).await
+        upload::upload_large_resumable(
+            &client,
+            parent_path,
+            name,
+            data,
+            progress,
+            self.upload_checkpoints.as_deref(),
+        )
+        .await
+    }
+
+    /// Uploads a large file, checking the source before the final chunk
+    ///
+    /// Delegates to [`upload::upload_large_checked`].
+    async fn upload_file_session_checked(
+        &self,
+        parent_path: &RemotePath,
+        name: &str,
+        data: &[u8],
+        progress: Option<Box<dyn Fn(u64, u64) + Send>>,
+        commit_check: CommitCheck,
+    ) -> Result<DeltaItem> {
+        let client = self.client.lock().await;
+        debug!(
+            parent = %parent_path,
+            name,
+            size = data.len(),
+            "GraphCloudProvider::upload_file_session_checked"
+        );
+        upload::upload_large_checked(
+            &client,
+            parent_path,
+            name,
+            data,
+            progress,
+            self.upload_checkpoints.as_deref(),
+            Some(commit_check),
+        )
+        .await
+    }
+
+    /// Creates a folder, returning the existing one on a name conflict
+    ///
+    /// Delegates to [`upload::create_folder`].
+    async fn create_folder(&self, parent_path: &RemotePath, name: &str) -> Result<DeltaItem> {
+        let client = self.client.lock().await;
+        debug!(parent = %parent_path, name, "GraphCloudProvider::create_folder");
+        upload::create_folder(&client, parent_path, name).await
     }
 
     /// Retrieves metadata for a specific item by its remote ID
@@ -249,7 +460,29 @@ impl ICloudProvider for GraphCloudProvider {
             .await
             .context("Failed to parse metadata response")?;
 
-        Ok(metadata_to_delta_item(item))
+        let mut item = metadata_to_delta_item(item);
+        client.special_folders().localize(&mut item);
+        Ok(item)
+    }
+
+    /// Sets `fileSystemInfo.lastModifiedDateTime` with
+    /// `PATCH /me/drive/items/{id}`
+    async fn set_modified_time(&self, remote_id: &RemoteId, modified: DateTime<Utc>) -> Result<()> {
+        let client = self.client.lock().await;
+        let path = format!("/me/drive/items/{}", remote_id.as_str());
+        debug!(id = %remote_id, %modified, "GraphCloudProvider::set_modified_time");
+
+        client
+            .request(Method::PATCH, &path)
+            .json(&serde_json::json!({
+                "fileSystemInfo": { "lastModifiedDateTime": modified.to_rfc3339() }
+            }))
+            .send()
+            .await
+            .context("Failed to send update request")?
+            .error_for_status()
+            .context("Update request returned error status")?;
+        Ok(())
     }
 
     /// Retrieves information about the authenticated user
@@ -261,6 +494,173 @@ impl ICloudProvider for GraphCloudProvider {
         client.get_user_info().await
     }
 
+    /// Retrieves the drive quota with a single `GET /me/drive`
+    async fn get_quota(&self) -> Result<DriveQuota> {
+        let client = self.client.lock().await;
+        debug!("GraphCloudProvider::get_quota");
+        client.get_drive_quota().await
+    }
+
+    /// Lists sub-folders with `GET .../children?$filter=folder ne null`
+    ///
+    /// The continuation is the page's `@odata.nextLink`; it is only
+    /// followed if it points to the Graph API, since it may come from a
+    /// D-Bus client and the request carries the access token.
+    async fn list_folders(
+        &self,
+        path: &RemotePath,
+        continuation: Option<&str>,
+    ) -> Result<FolderPage> {
+        let client = self.client.lock().await;
+        debug!(%path, continuation = continuation.is_some(), "GraphCloudProvider::list_folders");
+
+        let request = match continuation {
+            Some(next_link) => {
+                if !next_link.starts_with(client.base_url()) {
+                    anyhow::bail!("Invalid folder listing continuation: {next_link}");
+                }
+                client
+                    .client()
+                    .get(next_link)
+                    .bearer_auth(client.access_token())
+            }
+            None => client
+                .request(Method::GET, &children_path(&client, path))
+                .query(&[
+                    ("$select", "name,folder".to_string()),
+                    ("$filter", "folder ne null".to_string()),
+                    ("$top", FOLDER_PAGE_SIZE.to_string()),
+                ]),
+        };
+        let response = request
+            .send()
+            .await
+            .context("Failed to send folder listing request")?;
+        if response.status() == StatusCode::NOT_FOUND {
+            return Err(anyhow::Error::new(RemoteItemNotFound)
+                .context(format!("Remote folder {path} not found")));
+        }
+        let page: GraphFolderChildren = response
+            .error_for_status()
+            .context("Folder listing returned error status")?
+            .json()
+            .await
+            .context("Failed to parse folder listing")?;
+
+        let is_root = path.as_str().trim_end_matches('/').is_empty();
+        let folders = page
+            .value
+            .into_iter()
+            .filter_map(|child| {
+                let folder = child.folder?;
+                // Top-level special folders are shown under their local name
+                let name = if is_root {
+                    client
+                        .special_folders()
+                        .to_local(&format!("/{}", child.name))[1..]
+                        .to_string()
+                } else {
+                    child.name
+                };
+                Some(RemoteFolder {
+                    name,
+                    child_count: folder.child_count,
+                })
+            })
+            .collect();
+
+        Ok(FolderPage {
+            folders,
+            continuation: page.next_link,
+        })
+    }
+
+    /// Lists the children of a folder with `GET .../children`
+    ///
+    /// Like [`list_folders`](Self::list_folders), the continuation is only
+    /// followed if it points to the Graph API.
+    async fn list_children(
+        &self,
+        path: &RemotePath,
+        continuation: Option<&str>,
+    ) -> Result<ItemPage> {
+        let client = self.client.lock().await;
+        debug!(%path, continuation = continuation.is_some(), "GraphCloudProvider::list_children");
+
+        let request = match continuation {
+            Some(next_link) => {
+                if !next_link.starts_with(client.base_url()) {
+                    anyhow::bail!("Invalid folder listing continuation: {next_link}");
+                }
+                client
+                    .client()
+                    .get(next_link)
+                    .bearer_auth(client.access_token())
+            }
+            None => client
+                .request(Method::GET, &children_path(&client, path))
+                .query(&[("$top", CHILDREN_PAGE_SIZE.to_string())]),
+        };
+        let response = request
+            .send()
+            .await
+            .context("Failed to send folder listing request")?;
+        if response.status() == StatusCode::NOT_FOUND {
+            return Err(anyhow::Error::new(RemoteItemNotFound)
+                .context(format!("Remote folder {path} not found")));
+        }
+        let page: GraphChildren = response
+            .error_for_status()
+            .context("Folder listing returned error status")?
+            .json()
+            .await
+            .context("Failed to parse folder listing")?;
+
+        let items = page
+            .value
+            .into_iter()
+            .map(|item| {
+                let mut item = metadata_to_delta_item(item);
+                client.special_folders().localize(&mut item);
+                item
+            })
+            .collect();
+
+        Ok(ItemPage {
+            items,
+            continuation: page.next_link,
+        })
+    }
+
+    /// Looks the child up by path with a single `GET /me/drive/root:{path}`
+    ///
+    /// OneDrive resolves the path case-insensitively, so a child whose name
+    /// differs only in case is found too.
+    async fn find_child(&self, parent: &RemotePath, name: &str) -> Result<Option<DeltaItem>> {
+        let client = self.client.lock().await;
+        let path = RemotePath::new(format!("{}/{name}", parent.as_str().trim_end_matches('/')))?;
+        debug!(%path, "GraphCloudProvider::find_child");
+
+        let response = client
+            .request(Method::GET, &item_path(&client, &path))
+            .send()
+            .await
+            .context("Failed to send item lookup request")?;
+        if response.status() == StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+        let item: GraphMetadataItem = response
+            .error_for_status()
+            .context("Item lookup returned error status")?
+            .json()
+            .await
+            .context("Failed to parse item lookup response")?;
+
+        let mut item = metadata_to_delta_item(item);
+        client.special_folders().localize(&mut item);
+        Ok(Some(item))
+    }
+
     /// Deletes an item from OneDrive
     ///
     /// Makes `DELETE /me/drive/items/{id}`. OneDrive moves the item to the
@@ -270,11 +670,16 @@ impl ICloudProvider for GraphCloudProvider {
         let path = format!("/me/drive/items/{}", remote_id.as_str());
         debug!(id = %remote_id, "GraphCloudProvider::delete_item");
 
-        client
+        let response = client
             .request(Method::DELETE, &path)
             .send()
             .await
-            .context("Failed to send delete request")?
+            .context("Failed to send delete request")?;
+        if response.status() == StatusCode::NOT_FOUND {
+            return Err(anyhow::Error::new(RemoteItemNotFound)
+                .context("Delete request returned 404 Not Found"));
+        }
+        response
             .error_for_status()
             .context("Delete request returned error status")?;
 
@@ -304,6 +709,16 @@ impl GraphCloudProvider {
     /// # Note
     /// Download URLs are short-lived (typically valid for ~1 hour).
     pub async fn get_download_url(&self, remote_id: &RemoteId) -> Result<String> {
+        Ok(self.get_download_info(remote_id).await?.url)
+    }
+
+    /// Get the download URL together with the expected size and hash.
+    ///
+    /// Issues the same `GET /me/drive/items/{id}` request as
+    /// [`get_download_url`](Self::get_download_url) and additionally extracts
+    /// `size` and `file.hashes.quickXorHash`, so callers can verify the
+    /// assembled content after downloading it, and the modification time.
+    pub async fn get_download_info(&self, remote_id: &RemoteId) -> Result<DownloadInfo> {
         let client = self.client.lock().await;
         let url = format!(
             "{}/me/drive/items/{}",
@@ -325,10 +740,22 @@ impl GraphCloudProvider {
             .await
             .context("Failed to parse response as JSON")?;
 
-        response["@microsoft.graph.downloadUrl"]
+        let url = response["@microsoft.graph.downloadUrl"]
             .as_str()
             .map(|s| s.to_string())
-            .ok_or_else(|| anyhow::anyhow!("No download URL in response"))
+            .ok_or_else(|| anyhow::anyhow!("No download URL in response"))?;
+
+        Ok(DownloadInfo {
+            url,
+            size: response["size"].as_u64(),
+            quick_xor_hash: response["file"]["hashes"]["quickXorHash"]
+                .as_str()
+                .map(|s| s.to_string()),
+            modified: response["fileSystemInfo"]["lastModifiedDateTime"]
+                .as_str()
+                .or_else(|| response["lastModifiedDateTime"].as_str())
+                .and_then(|s| s.parse().ok()),
+        })
     }
 
     /// Download a complete file to disk.
@@ -339,10 +766,17 @@ impl GraphCloudProvider {
     /// # Arguments
     /// * `download_url` - Pre-authenticated download URL (from [`get_download_url`])
     /// * `dest` - Destination path where the file will be written
+    /// * `hasher` - If given, fed every chunk as it is written, so the
+    ///   content can be verified without reading the file back
     ///
     /// # Returns
     /// Total number of bytes written to the destination file
-    pub async fn download_file_to_disk(&self, download_url: &str, dest: &Path) -> Result<u64> {
+    pub async fn download_file_to_disk(
+        &self,
+        download_url: &str,
+        dest: &Path,
+        mut hasher: Option<&mut QuickXorHash>,
+    ) -> Result<u64> {
         let client = self.client.lock().await;
         debug!(dest = %dest.display(), "Downloading file to disk");
 
@@ -367,6 +801,9 @@ impl GraphCloudProvider {
             file.write_all(&chunk)
                 .await
                 .context("Failed to write chunk to file")?;
+            if let Some(hasher) = hasher.as_deref_mut() {
+                hasher.update(&chunk);
+            }
             total_bytes += chunk.len() as u64;
         }
 
@@ -375,40 +812,30 @@ impl GraphCloudProvider {
         Ok(total_bytes)
     }
 
-    /// Download a byte range of a file.
+    /// Read a byte range of a file into memory.
     ///
-    /// Uses HTTP Range header for partial download. The bytes are written
-    /// to the destination file at the specified offset.
+    /// Uses HTTP Range header for partial download. Fewer than `length`
+    /// bytes are returned if the range extends past the end of the file.
     ///
     /// # Arguments
     /// * `download_url` - Pre-authenticated download URL (from [`get_download_url`])
-    /// * `dest` - Destination path where the bytes will be written
-    /// * `offset` - Byte offset in the file to start writing at
-    /// * `length` - Number of bytes to download
-    ///
-    /// # Returns
-    /// Number of bytes actually written (may be less than `length` if EOF reached)
-    pub async fn download_range(
+    /// * `offset` - Byte offset in the file to start reading at
+    /// * `length` - Number of bytes to read (at least 1)
+    pub async fn read_range(
         &self,
         download_url: &str,
-        dest: &Path,
         offset: u64,
         length: u64,
-    ) -> Result<u64> {
+    ) -> Result<Vec<u8>> {
         let client = self.client.lock().await;
         let range_header = format!("bytes={}-{}", offset, offset + length - 1);
-        debug!(
-            dest = %dest.display(),
-            offset,
-            length,
-            range = %range_header,
-            "Downloading byte range"
-        );
+        debug!(offset, length, range = %range_header, "Downloading byte range");
 
         let response = client
             .client()
             .get(download_url)
             .header("Range", range_header)
+            .timeout(client.transfer_timeout(length))
             .send()
             .await
             .context("Failed to send range download request")?
@@ -419,6 +846,34 @@ impl GraphCloudProvider {
             .bytes()
             .await
             .context("Failed to read response bytes")?;
+        Ok(bytes.to_vec())
+    }
+
+    /// Download a byte range of a file.
+    ///
+    /// Uses HTTP Range header for partial download. The bytes are written
+    /// to the destination file at the specified offset.
+    ///
+    /// # Arguments
+    /// * `download_url` - Pre-authenticated download URL (from [`get_download_url`])
+    /// * `dest` - Destination path where the bytes will be written
+    /// * `offset` - Byte offset in the file to start writing at
+    /// * `length` - Number of bytes to download
+    /// * `hasher` - If given, fed the downloaded bytes; ranges must then be
+    ///   requested in order
+    ///
+    /// # Returns
+    /// Number of bytes actually written (may be less than `length` if EOF reached)
+    pub async fn download_range(
+        &self,
+        download_url: &str,
+        dest: &Path,
+        offset: u64,
+        length: u64,
+        hasher: Option<&mut QuickXorHash>,
+    ) -> Result<u64> {
+        debug!(dest = %dest.display(), "Downloading byte range to file");
+        let bytes = self.read_range(download_url, offset, length).await?;
 
         // Open file and seek to offset
         // We use truncate(false) because we're writing to a specific offset,
@@ -438,6 +893,9 @@ impl GraphCloudProvider {
         file.write_all(&bytes)
             .await
             .context("Failed to write bytes to file")?;
+        if let Some(hasher) = hasher {
+            hasher.update(&bytes);
+        }
 
         file.flush().await.context("Failed to flush file")?;
 
@@ -467,6 +925,7 @@ mod tests {
             name: "test.txt".to_string(),
             size: Some(1024),
             last_modified_date_time: Some("2025-06-15T10:30:00Z".parse().unwrap()),
+            file_system_info: None,
             parent_reference: Some(GraphParentRef {
                 id: Some("PARENT001".to_string()),
                 path: Some("/drive/root:/Documents".to_string()),
@@ -478,6 +937,8 @@ mod tests {
             }),
             folder: None,
             deleted: None,
+            photo: None,
+            created_by: None,
         };
 
         let delta = metadata_to_delta_item(item);
@@ -498,6 +959,7 @@ mod tests {
             name: "Photos".to_string(),
             size: Some(0),
             last_modified_date_time: None,
+            file_system_info: None,
             parent_reference: Some(GraphParentRef {
                 id: Some("ROOT".to_string()),
                 path: Some("/drive/root:".to_string()),
@@ -505,6 +967,8 @@ mod tests {
             file: None,
             folder: Some(serde_json::json!({"childCount": 5})),
             deleted: None,
+            photo: None,
+            created_by: None,
         };
 
         let delta = metadata_to_delta_item(item);
@@ -523,10 +987,13 @@ mod tests {
             name: "old.txt".to_string(),
             size: None,
             last_modified_date_time: None,
+            file_system_info: None,
             parent_reference: None,
             file: None,
             folder: None,
             deleted: Some(serde_json::json!({})),
+            photo: None,
+            created_by: None,
         };
 
         let delta = metadata_to_delta_item(item);
@@ -543,6 +1010,7 @@ mod tests {
             name: "readme.md".to_string(),
             size: Some(512),
             last_modified_date_time: None,
+            file_system_info: None,
             parent_reference: Some(GraphParentRef {
                 id: Some("ROOT".to_string()),
                 path: Some("/drive/root:".to_string()),
@@ -550,6 +1018,8 @@ mod tests {
             file: Some(GraphFileFacet { hashes: None }),
             folder: None,
             deleted: None,
+            photo: None,
+            created_by: None,
         };
 
         let delta = metadata_to_delta_item(item);
@@ -557,6 +1027,18 @@ mod tests {
         assert!(delta.hash.is_none());
     }
 
+    #[test]
+    fn test_item_path() {
+        let client = GraphClient::new("test-token");
+        let path = |p: &str| item_path(&client, &RemotePath::new(p.to_string()).unwrap());
+
+        assert_eq!(path("/"), "/me/drive/root");
+        assert_eq!(
+            path("/Documents/report.pdf"),
+            "/me/drive/root:/Documents/report.pdf"
+        );
+    }
+
     #[test]
     fn test_graph_cloud_provider_creation() {
         let client = GraphClient::new("test-token");
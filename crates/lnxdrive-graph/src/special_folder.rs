@@ -0,0 +1,273 @@
+//! OneDrive special folders
+//!
+//! OneDrive marks some top-level folders with a `specialFolder` facet. Their
+//! display name follows the account language ("Documents" becomes
+//! "Documentos" in Spanish) and changes when the language does, while the
+//! facet name stays the same. Using the display name as the local path
+//! would move these folders around, or create a second one, whenever the
+//! language changes.
+//!
+//! The following special folders are therefore mapped to a fixed local
+//! name, whatever their display name in the cloud:
+//!
+//! | Facet       | Local folder |
+//! |-------------|--------------|
+//! | `documents` | `Documents`  |
+//! | `photos`    | `Pictures`   |
+//! | `music`     | `Music`      |
+//!
+//! Other special folders (`cameraroll`, `approot`, `recordings`) live
+//! inside one of these or in `Apps`, and keep their display name.
+//!
+//! Remote paths below a mapped folder are rewritten on the way in (delta
+//! and metadata responses) and addressed through `/me/drive/special/{name}`
+//! on the way out, so uploads land in the localized folder.
+
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        RwLock,
+    },
+};
+
+use lnxdrive_core::ports::cloud_provider::DeltaItem;
+use reqwest::Method;
+use serde::Deserialize;
+use tracing::debug;
+
+use crate::client::GraphClient;
+
+/// Special folders mapped to a fixed local name, as (facet, local name)
+pub const MAPPED_SPECIAL_FOLDERS: &[(&str, &str)] = &[
+    ("documents", "Documents"),
+    ("photos", "Pictures"),
+    ("music", "Music"),
+];
+
+/// Parent path of top-level items in Graph responses
+const DRIVE_ROOT: &str = "/drive/root:";
+
+/// Returns the local name of a mapped special folder
+pub fn local_name(facet: &str) -> Option<&'static str> {
+    MAPPED_SPECIAL_FOLDERS
+        .iter()
+        .find(|(name, _)| *name == facet)
+        .map(|(_, local)| *local)
+}
+
+/// Display names of the mapped special folders of one drive
+///
+/// Learned from the `specialFolder` facets of delta items, or queried with
+/// [`load`](Self::load) when an incremental delta does not report them.
+#[derive(Debug, Default)]
+pub struct SpecialFolders {
+    /// Current display name in the cloud, by facet name
+    display_names: RwLock<HashMap<&'static str, String>>,
+    /// Whether [`load`](Self::load) ran
+    loaded: AtomicBool,
+}
+
+impl SpecialFolders {
+    /// Creates an empty mapping
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns `true` once [`load`](Self::load) ran
+    pub fn is_loaded(&self) -> bool {
+        self.loaded.load(Ordering::Relaxed)
+    }
+
+    /// Records the display name of a top-level special folder
+    ///
+    /// Returns the local name if the folder is mapped. A new display name
+    /// for the same facet (after a language change) replaces the old one.
+    pub fn learn(&self, facet: &str, display_name: &str) -> Option<&'static str> {
+        let (facet, local) = MAPPED_SPECIAL_FOLDERS
+            .iter()
+            .find(|(name, _)| *name == facet)?;
+        self.display_names
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(facet, display_name.to_string());
+        Some(local)
+    }
+
+    /// Rewrites a remote path (e.g. `/Documentos/a.txt`) to the local one
+    /// (`/Documents/a.txt`)
+    pub fn to_local(&self, path: &str) -> String {
+        let (first, rest) = split_first(path);
+        let names = self.read();
+        match names
+            .iter()
+            .find(|(_, display)| display.as_str() == first)
+            .and_then(|(facet, _)| local_name(facet))
+        {
+            Some(local) => format!("/{local}{rest}"),
+            None => path.to_string(),
+        }
+    }
+
+    /// Rewrites the path and name of a delta item to their local form
+    pub fn localize(&self, item: &mut DeltaItem) {
+        if let Some(path) = item.path.as_mut() {
+            let local = self.to_local(path);
+            if local != *path {
+                if !local[1..].contains('/') {
+                    item.name = local[1..].to_string();
+                }
+                *path = local;
+            }
+        }
+    }
+
+    /// Splits a local path below a mapped special folder into the facet
+    /// and the path relative to it (`/Documents/a` gives `("documents", "/a")`)
+    pub fn to_special(&self, path: &str) -> Option<(&'static str, String)> {
+        let (first, rest) = split_first(path);
+        let (facet, _) = MAPPED_SPECIAL_FOLDERS
+            .iter()
+            .find(|(_, local)| *local == first)?;
+        self.read()
+            .contains_key(facet)
+            .then(|| (*facet, rest.to_string()))
+    }
+
+    /// Queries the display names of the mapped special folders
+    ///
+    /// Folders the drive does not have (e.g. on OneDrive for Business) are
+    /// skipped, as are request failures: the paths are then left as is.
+    pub async fn load(&self, client: &GraphClient) {
+        self.loaded.store(true, Ordering::Relaxed);
+        for (facet, _) in MAPPED_SPECIAL_FOLDERS {
+            let path = format!("/me/drive/special/{facet}");
+            let response = match client.request(Method::GET, &path).send().await {
+                Ok(response) => response,
+                Err(e) => {
+                    debug!(facet, error = %e, "Failed to query special folder");
+                    continue;
+                }
+            };
+            let Ok(response) = response.error_for_status() else {
+                debug!(facet, "Special folder not available");
+                continue;
+            };
+            match response.json::<SpecialFolderItem>().await {
+                Ok(item) if item.is_top_level() => {
+                    self.learn(facet, &item.name);
+                }
+                Ok(_) => debug!(facet, "Special folder is not top-level, not mapped"),
+                Err(e) => debug!(facet, error = %e, "Invalid special folder response"),
+            }
+        }
+    }
+
+    fn read(&self) -> std::sync::RwLockReadGuard<'_, HashMap<&'static str, String>> {
+        self.display_names.read().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// Returns `true` if `parent_path` (as in `parentReference.path`) is the
+/// drive root
+pub(crate) fn is_drive_root(parent_path: Option<&str>) -> bool {
+    parent_path == Some(DRIVE_ROOT)
+}
+
+/// Splits `/first/rest` into `first` and `/rest`
+fn split_first(path: &str) -> (&str, &str) {
+    let trimmed = path.strip_prefix('/').unwrap_or(path);
+    match trimmed.find('/') {
+        Some(i) => (&trimmed[..i], &trimmed[i..]),
+        None => (trimmed, ""),
+    }
+}
+
+/// Response of `GET /me/drive/special/{name}`
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SpecialFolderItem {
+    name: String,
+    parent_reference: Option<SpecialFolderParent>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SpecialFolderParent {
+    path: Option<String>,
+}
+
+impl SpecialFolderItem {
+    fn is_top_level(&self) -> bool {
+        is_drive_root(
+            self.parent_reference
+                .as_ref()
+                .and_then(|p| p.path.as_deref()),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn delta_item(name: &str, path: &str) -> DeltaItem {
+        DeltaItem {
+            id: "id".to_string(),
+            name: name.to_string(),
+            path: Some(path.to_string()),
+            size: None,
+            hash: None,
+            modified: None,
+            is_deleted: false,
+            is_directory: true,
+            parent_id: None,
+            facets: Default::default(),
+        }
+    }
+
+    #[test]
+    fn test_unknown_folders_are_left_alone() {
+        let folders = SpecialFolders::new();
+        assert_eq!(folders.to_local("/Documentos/a.txt"), "/Documentos/a.txt");
+        assert!(folders.to_special("/Documents/a.txt").is_none());
+        assert!(folders.learn("cameraroll", "Álbum de cámara").is_none());
+        assert!(folders.read().is_empty());
+    }
+
+    #[test]
+    fn test_localized_names_map_to_fixed_local_names() {
+        let folders = SpecialFolders::new();
+        assert_eq!(folders.learn("documents", "Documentos"), Some("Documents"));
+
+        assert_eq!(folders.to_local("/Documentos"), "/Documents");
+        assert_eq!(
+            folders.to_local("/Documentos/a/b.txt"),
+            "/Documents/a/b.txt"
+        );
+        assert_eq!(folders.to_local("/Other/Documentos"), "/Other/Documentos");
+        assert_eq!(
+            folders.to_special("/Documents/a/b.txt"),
+            Some(("documents", "/a/b.txt".to_string()))
+        );
+        assert_eq!(
+            folders.to_special("/Documents"),
+            Some(("documents", String::new()))
+        );
+        assert!(folders.to_special("/Pictures/x.jpg").is_none());
+
+        let mut item = delta_item("Documentos", "/Documentos");
+        folders.localize(&mut item);
+        assert_eq!(item.name, "Documents");
+        assert_eq!(item.path.as_deref(), Some("/Documents"));
+    }
+
+    #[test]
+    fn test_language_change_keeps_local_name() {
+        let folders = SpecialFolders::new();
+        folders.learn("documents", "Documentos");
+        folders.learn("documents", "Dokumente");
+
+        assert_eq!(folders.to_local("/Dokumente/a.txt"), "/Documents/a.txt");
+        assert_eq!(folders.to_local("/Documentos/a.txt"), "/Documentos/a.txt");
+    }
+}
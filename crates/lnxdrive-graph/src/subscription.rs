@@ -0,0 +1,246 @@
+//! Microsoft Graph change notifications for the drive
+//!
+//! A subscription asks Microsoft Graph to `POST` a notification to a public
+//! HTTPS URL whenever something in the drive changes. Notifications carry no
+//! details about the change; the client runs a delta query to find out what
+//! changed.
+//!
+//! ## Subscription Lifecycle
+//!
+//! 1. **Create**: [`create_subscription`] registers the notification URL.
+//!    Graph first validates the URL by posting a `validationToken` query
+//!    parameter, which the receiver must echo back as plain text
+//! 2. **Renew**: subscriptions expire (at most 42300 minutes for drive items);
+//!    [`renew_subscription`] extends the expiration
+//! 3. **Delete**: [`delete_subscription`] stops the notifications
+//!
+//! See: <https://learn.microsoft.com/en-us/graph/api/resources/subscription>
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use reqwest::Method;
+use serde::{Deserialize, Serialize};
+use tracing::{debug, info};
+
+use crate::client::GraphClient;
+
+/// Resource subscribed to: every item of the user's drive
+const DRIVE_RESOURCE: &str = "/me/drive/root";
+
+/// The only change type drive items support
+const DRIVE_CHANGE_TYPE: &str = "updated";
+
+/// Query parameter Graph sends when validating a notification URL
+pub const VALIDATION_TOKEN_PARAM: &str = "validationToken";
+
+/// An active change-notification subscription
+///
+/// See: <https://learn.microsoft.com/en-us/graph/api/resources/subscription>
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Subscription {
+    /// Identifier used to renew and delete the subscription
+    pub id: String,
+
+    /// Resource the subscription watches
+    #[serde(default)]
+    pub resource: String,
+
+    /// URL the notifications are posted to
+    #[serde(default)]
+    pub notification_url: String,
+
+    /// When the subscription stops delivering notifications
+    pub expiration_date_time: DateTime<Utc>,
+}
+
+/// Request body of `POST /subscriptions`
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct CreateSubscriptionRequest<'a> {
+    change_type: &'a str,
+    notification_url: &'a str,
+    resource: &'a str,
+    expiration_date_time: DateTime<Utc>,
+    client_state: &'a str,
+}
+
+/// Request body of `PATCH /subscriptions/{id}`
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct RenewSubscriptionRequest {
+    expiration_date_time: DateTime<Utc>,
+}
+
+/// Body of a notification posted by Microsoft Graph
+///
+/// Graph may batch several notifications in one request.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ChangeNotificationCollection {
+    /// The notifications of this request
+    #[serde(default)]
+    pub value: Vec<ChangeNotification>,
+}
+
+/// One change notification
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChangeNotification {
+    /// Subscription the notification belongs to
+    pub subscription_id: String,
+
+    /// Secret given when the subscription was created; notifications with
+    /// another value did not come from Microsoft Graph
+    #[serde(default)]
+    pub client_state: Option<String>,
+
+    /// Resource that changed
+    #[serde(default)]
+    pub resource: Option<String>,
+}
+
+/// Subscribes `notification_url` to changes of the drive until `expiration`
+///
+/// Makes `POST /subscriptions`. Graph validates `notification_url` before
+/// answering, so the receiver must already be reachable.
+///
+/// # Arguments
+/// * `notification_url` - Public HTTPS URL notifications are posted to
+/// * `client_state` - Secret echoed in every notification
+/// * `expiration` - Requested expiration of the subscription
+pub async fn create_subscription(
+    client: &GraphClient,
+    notification_url: &str,
+    client_state: &str,
+    expiration: DateTime<Utc>,
+) -> Result<Subscription> {
+    debug!(notification_url, %expiration, "Creating change-notification subscription");
+
+    let body = CreateSubscriptionRequest {
+        change_type: DRIVE_CHANGE_TYPE,
+        notification_url,
+        resource: DRIVE_RESOURCE,
+        expiration_date_time: expiration,
+        client_state,
+    };
+    let subscription: Subscription = client
+        .request(Method::POST, "/subscriptions")
+        .json(&body)
+        .send()
+        .await
+        .context("Failed to send create subscription request")?
+        .error_for_status()
+        .context("POST /subscriptions returned error status")?
+        .json()
+        .await
+        .context("Failed to parse subscription response")?;
+
+    info!(
+        subscription_id = %subscription.id,
+        expires = %subscription.expiration_date_time,
+        "Created change-notification subscription"
+    );
+    Ok(subscription)
+}
+
+/// Extends the subscription `id` until `expiration`
+///
+/// Makes `PATCH /subscriptions/{id}`. Fails once the subscription has
+/// expired or was removed by Graph; create a new one then.
+pub async fn renew_subscription(
+    client: &GraphClient,
+    id: &str,
+    expiration: DateTime<Utc>,
+) -> Result<Subscription> {
+    debug!(subscription_id = id, %expiration, "Renewing change-notification subscription");
+
+    let subscription: Subscription = client
+        .request(Method::PATCH, &format!("/subscriptions/{id}"))
+        .json(&RenewSubscriptionRequest {
+            expiration_date_time: expiration,
+        })
+        .send()
+        .await
+        .context("Failed to send renew subscription request")?
+        .error_for_status()
+        .context("PATCH /subscriptions returned error status")?
+        .json()
+        .await
+        .context("Failed to parse subscription response")?;
+
+    debug!(
+        subscription_id = %subscription.id,
+        expires = %subscription.expiration_date_time,
+        "Renewed change-notification subscription"
+    );
+    Ok(subscription)
+}
+
+/// Deletes the subscription `id`, stopping its notifications
+///
+/// Makes `DELETE /subscriptions/{id}`. A subscription Graph no longer knows
+/// (`404`) counts as deleted.
+pub async fn delete_subscription(client: &GraphClient, id: &str) -> Result<()> {
+    let response = client
+        .request(Method::DELETE, &format!("/subscriptions/{id}"))
+        .send()
+        .await
+        .context("Failed to send delete subscription request")?;
+
+    if response.status() != reqwest::StatusCode::NOT_FOUND {
+        response
+            .error_for_status()
+            .context("DELETE /subscriptions returned error status")?;
+    }
+
+    info!(
+        subscription_id = id,
+        "Deleted change-notification subscription"
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_create_request_uses_graph_field_names() {
+        let expiration = "2026-03-01T12:00:00Z".parse().unwrap();
+        let body = serde_json::to_value(CreateSubscriptionRequest {
+            change_type: DRIVE_CHANGE_TYPE,
+            notification_url: "https://example.org/hook",
+            resource: DRIVE_RESOURCE,
+            expiration_date_time: expiration,
+            client_state: "secret",
+        })
+        .unwrap();
+
+        assert_eq!(body["changeType"], "updated");
+        assert_eq!(body["notificationUrl"], "https://example.org/hook");
+        assert_eq!(body["resource"], "/me/drive/root");
+        assert_eq!(body["clientState"], "secret");
+        assert!(body["expirationDateTime"]
+            .as_str()
+            .unwrap()
+            .starts_with("2026-03-01T12:00:00"));
+    }
+
+    #[test]
+    fn test_notification_collection_parses() {
+        let json = r#"{
+            "value": [{
+                "subscriptionId": "sub-1",
+                "clientState": "secret",
+                "resource": "/me/drive/root",
+                "tenantId": "",
+                "subscriptionExpirationDateTime": "2026-03-01T12:00:00Z"
+            }]
+        }"#;
+        let collection: ChangeNotificationCollection = serde_json::from_str(json).unwrap();
+
+        assert_eq!(collection.value.len(), 1);
+        assert_eq!(collection.value[0].subscription_id, "sub-1");
+        assert_eq!(collection.value[0].client_state.as_deref(), Some("secret"));
+    }
+}
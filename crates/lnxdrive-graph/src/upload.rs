@@ -3,22 +3,36 @@
 //! Provides functions for uploading files to OneDrive:
 //! - [`upload_small`] - Single-request upload for files under 4MB
 //! - [`upload_large`] - Resumable upload session for large files (10MB chunks)
+//! - [`upload_large_resumable`] - Same, checkpointing progress so an
+//!   interrupted upload continues on the next attempt
+//! - [`upload_large_checked`] - Same, checking the source file is
+//!   unchanged before the final chunk commits the upload
 //! - [`create_upload_session`] - Creates a resumable upload session
 //! - [`upload_chunk`] - Uploads a single chunk within a session
+//! - [`create_folder`] - Creates a folder, which has no content to upload
 //!
 //! ## Microsoft Graph API References
 //!
 //! - [Upload small files](https://learn.microsoft.com/en-us/graph/api/driveitem-put-content)
 //! - [Upload large files](https://learn.microsoft.com/en-us/graph/api/driveitem-createuploadsession)
+//! - [Create a folder](https://learn.microsoft.com/en-us/graph/api/driveitem-post-children)
+
+use std::time::Duration;
 
 use anyhow::{Context, Result};
 use chrono::{DateTime, Utc};
-use lnxdrive_core::{domain::newtypes::RemotePath, ports::cloud_provider::DeltaItem};
-use reqwest::Method;
+use lnxdrive_core::{
+    domain::{detect_mime_type, newtypes::RemotePath, ItemFacets, QuickXorHash},
+    ports::cloud_provider::{CommitCheck, DeltaItem},
+};
+use reqwest::{Method, StatusCode};
 use serde::Deserialize;
-use tracing::{debug, info};
+use tracing::{debug, info, warn};
 
-use crate::client::GraphClient;
+use crate::{
+    client::GraphClient,
+    upload_checkpoint::{UploadCheckpoint, UploadCheckpointStore},
+};
 
 /// Chunk size for large file uploads: 10 MiB (10,485,760 bytes)
 ///
@@ -87,6 +101,17 @@ struct FileHashes {
 struct UploadSessionResponse {
     /// The URL to use for uploading chunks
     upload_url: String,
+    /// When the server discards the session (ISO 8601)
+    expiration_date_time: Option<String>,
+}
+
+/// Response from querying the status of an upload session
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct UploadSessionStatus {
+    /// Byte ranges the server still expects, e.g. `["26-"]`
+    #[serde(default)]
+    next_expected_ranges: Vec<String>,
 }
 
 // ============================================================================
@@ -153,6 +178,7 @@ fn drive_item_to_delta(item: GraphDriveItem) -> DeltaItem {
         is_deleted,
         is_directory,
         parent_id,
+        facets: ItemFacets::default(),
     }
 }
 
@@ -185,6 +211,24 @@ fn build_item_path(parent_path: &RemotePath, name: &str, suffix: &str) -> String
     }
 }
 
+/// Builds the item path like [`build_item_path`], addressing parents below
+/// a mapped special folder through `/me/drive/special/{name}`
+///
+/// The local path uses the fixed name of the folder (see
+/// [`special_folder`](crate::special_folder)), which may differ from its
+/// localized name in the cloud.
+fn resolve_item_path(
+    client: &GraphClient,
+    parent_path: &RemotePath,
+    name: &str,
+    suffix: &str,
+) -> String {
+    match client.special_folders().to_special(parent_path.as_str()) {
+        Some((facet, rest)) => format!("/me/drive/special/{facet}:{rest}/{name}:/{suffix}"),
+        None => build_item_path(parent_path, name, suffix),
+    }
+}
+
 // ============================================================================
 // T140: upload_small
 // ============================================================================
@@ -211,7 +255,7 @@ pub async fn upload_small(
     name: &str,
     data: &[u8],
 ) -> Result<DeltaItem> {
-    let path = build_item_path(parent_path, name, "content");
+    let path = resolve_item_path(client, parent_path, name, "content");
     debug!(
         "Uploading small file ({} bytes): {} -> {}",
         data.len(),
@@ -221,8 +265,11 @@ pub async fn upload_small(
 
     let item: GraphDriveItem = client
         .request(Method::PUT, &path)
-        .header("Content-Type", "application/octet-stream")
+        .header("Content-Type", detect_mime_type(name, data))
+        // Not sent for an empty body otherwise, which Graph rejects
+        .header("Content-Length", data.len())
         .body(data.to_vec())
+        .timeout(client.transfer_timeout(data.len() as u64))
         .send()
         .await
         .context("Failed to send small upload request")?
@@ -233,7 +280,99 @@ pub async fn upload_small(
         .context("Failed to parse upload response")?;
 
     debug!("Small upload completed: id={}, name={}", item.id, item.name);
-    Ok(drive_item_to_delta(item))
+    let mut delta = drive_item_to_delta(item);
+    client.special_folders().localize(&mut delta);
+    Ok(delta)
+}
+
+// ============================================================================
+// create_folder
+// ============================================================================
+
+/// Builds the path listing the children of `parent_path`, addressing
+/// folders below a mapped special folder like [`resolve_item_path`]
+///
+/// - Root: `/me/drive/root/children`
+/// - Subfolder: `/me/drive/root:{parent_path}:/children`
+fn resolve_children_path(client: &GraphClient, parent_path: &RemotePath) -> String {
+    match client.special_folders().to_special(parent_path.as_str()) {
+        Some((facet, rest)) if rest.is_empty() || rest == "/" => {
+            format!("/me/drive/special/{facet}/children")
+        }
+        Some((facet, rest)) => format!("/me/drive/special/{facet}:{rest}:/children"),
+        None if parent_path.as_str() == "/" => "/me/drive/root/children".to_string(),
+        None => format!("/me/drive/root:{}:/children", parent_path.as_str()),
+    }
+}
+
+/// Builds the path of the item `name` in `parent_path` itself, like
+/// [`resolve_item_path`] without an operation
+fn resolve_item_address(client: &GraphClient, parent_path: &RemotePath, name: &str) -> String {
+    match client.special_folders().to_special(parent_path.as_str()) {
+        Some((facet, rest)) => format!("/me/drive/special/{facet}:{rest}/{name}"),
+        None if parent_path.as_str() == "/" => format!("/me/drive/root:/{name}"),
+        None => format!("/me/drive/root:{}/{}", parent_path.as_str(), name),
+    }
+}
+
+/// Creates the folder `name` in `parent_path`
+///
+/// Uses `POST /me/drive/root:{parent_path}:/children` with a `folder`
+/// facet. The request fails on a name conflict rather than renaming the
+/// new folder; an existing folder of that name is then looked up and
+/// returned, so creating a folder twice is harmless.
+///
+/// # Errors
+/// Returns an error if the request fails, or if the conflicting item is a
+/// file
+pub async fn create_folder(
+    client: &GraphClient,
+    parent_path: &RemotePath,
+    name: &str,
+) -> Result<DeltaItem> {
+    let path = resolve_children_path(client, parent_path);
+    debug!("Creating folder: {} in {}", name, path);
+
+    let response = client
+        .request(Method::POST, &path)
+        .json(&serde_json::json!({
+            "name": name,
+            "folder": {},
+            "@microsoft.graph.conflictBehavior": "fail",
+        }))
+        .send()
+        .await
+        .context("Failed to send create folder request")?;
+
+    let item: GraphDriveItem = if response.status() == StatusCode::CONFLICT {
+        let existing = resolve_item_address(client, parent_path, name);
+        debug!("Folder already exists, fetching it: {}", existing);
+        client
+            .request(Method::GET, &existing)
+            .send()
+            .await
+            .context("Failed to send folder metadata request")?
+            .error_for_status()
+            .context("Folder metadata request returned error status")?
+            .json()
+            .await
+            .context("Failed to parse folder metadata")?
+    } else {
+        response
+            .error_for_status()
+            .context("Create folder request returned error status")?
+            .json()
+            .await
+            .context("Failed to parse create folder response")?
+    };
+    if item.folder.is_none() {
+        anyhow::bail!("A file named {} already exists in {}", name, parent_path);
+    }
+
+    debug!("Folder created: id={}, name={}", item.id, item.name);
+    let mut delta = drive_item_to_delta(item);
+    client.special_folders().localize(&mut delta);
+    Ok(delta)
 }
 
 // ============================================================================
@@ -263,7 +402,18 @@ pub async fn create_upload_session(
     parent_path: &RemotePath,
     name: &str,
 ) -> Result<String> {
-    let path = build_item_path(parent_path, name, "createUploadSession");
+    Ok(start_upload_session(client, parent_path, name)
+        .await?
+        .upload_url)
+}
+
+/// Creates an upload session and returns the full response
+async fn start_upload_session(
+    client: &GraphClient,
+    parent_path: &RemotePath,
+    name: &str,
+) -> Result<UploadSessionResponse> {
+    let path = resolve_item_path(client, parent_path, name, "createUploadSession");
     debug!("Creating upload session for: {}", name);
 
     let response: UploadSessionResponse = client
@@ -280,7 +430,43 @@ pub async fn create_upload_session(
         .context("Failed to parse upload session response")?;
 
     debug!("Upload session created: {}", response.upload_url);
-    Ok(response.upload_url)
+    Ok(response)
+}
+
+/// Queries an existing upload session for the next byte the server expects
+///
+/// Uses `GET {upload_url}`, whose `nextExpectedRanges` lists the missing
+/// ranges; the start of the first one is where the upload continues.
+///
+/// # Errors
+/// Returns an error if the session no longer exists or the response has no
+/// usable range
+pub async fn query_upload_session(
+    client: &reqwest::Client,
+    upload_url: &str,
+    access_token: &str,
+) -> Result<u64> {
+    let status: UploadSessionStatus = client
+        .get(upload_url)
+        .bearer_auth(access_token)
+        .send()
+        .await
+        .context("Failed to query upload session")?
+        .error_for_status()
+        .context("Upload session query returned error status")?
+        .json()
+        .await
+        .context("Failed to parse upload session status")?;
+
+    let range = status
+        .next_expected_ranges
+        .first()
+        .context("Upload session has no expected ranges")?;
+    range
+        .split('-')
+        .next()
+        .and_then(|start| start.parse().ok())
+        .with_context(|| format!("Invalid expected range '{range}'"))
 }
 
 // ============================================================================
@@ -300,6 +486,8 @@ pub async fn create_upload_session(
 /// * `data` - The chunk bytes to upload
 /// * `offset` - Byte offset of this chunk within the total file
 /// * `total` - Total file size in bytes
+/// * `timeout` - Overall timeout of the request, see
+///   [`GraphClient::transfer_timeout`]
 ///
 /// # Returns
 /// - `Some(Value)` with the completed DriveItem JSON on the final chunk
@@ -314,6 +502,7 @@ pub async fn upload_chunk(
     data: &[u8],
     offset: u64,
     total: u64,
+    timeout: Duration,
 ) -> Result<Option<serde_json::Value>> {
     let chunk_len = data.len() as u64;
     let range_end = offset + chunk_len - 1;
@@ -327,6 +516,7 @@ pub async fn upload_chunk(
         .header("Content-Length", chunk_len.to_string())
         .header("Content-Range", &content_range)
         .body(data.to_vec())
+        .timeout(timeout)
         .send()
         .await
         .context("Failed to send chunk upload request")?;
@@ -339,7 +529,7 @@ pub async fn upload_chunk(
             .await
             .context("Failed to parse chunk response body")?;
 
-        if status == reqwest::StatusCode::OK || status == reqwest::StatusCode::CREATED {
+        if status == StatusCode::OK || status == StatusCode::CREATED {
             // Upload complete - the response contains the final DriveItem
             debug!("Upload session completed (status {})", status);
             Ok(Some(body))
@@ -389,6 +579,52 @@ pub async fn upload_large(
     data: &[u8],
     progress: Option<Box<dyn Fn(u64, u64) + Send>>,
 ) -> Result<DeltaItem> {
+    upload_large_resumable(client, parent_path, name, data, progress, None).await
+}
+
+/// Uploads a large file, persisting progress in `checkpoints`
+///
+/// Works like [`upload_large`], but after every accepted chunk the session
+/// URL and offset are written to the checkpoint store. If a matching
+/// checkpoint exists when the upload starts (same path, size and content
+/// hash), the existing session is queried and the upload continues from
+/// the next expected byte. The checkpoint is removed once the upload
+/// completes; on failure it is kept for the next attempt.
+pub async fn upload_large_resumable(
+    client: &GraphClient,
+    parent_path: &RemotePath,
+    name: &str,
+    data: &[u8],
+    progress: Option<Box<dyn Fn(u64, u64) + Send>>,
+    checkpoints: Option<&UploadCheckpointStore>,
+) -> Result<DeltaItem> {
+    upload_large_checked(client, parent_path, name, data, progress, checkpoints, None).await
+}
+
+/// Uploads a large file, running `commit_check` before the final chunk
+///
+/// Works like [`upload_large_resumable`]. Graph commits the file when it
+/// receives the last byte, so this is the last moment to back out: if the
+/// check fails, the final chunk is not sent, the checkpoint is dropped
+/// (the data no longer matches the file) and the check's error returned.
+pub async fn upload_large_checked(
+    client: &GraphClient,
+    parent_path: &RemotePath,
+    name: &str,
+    data: &[u8],
+    progress: Option<Box<dyn Fn(u64, u64) + Send>>,
+    checkpoints: Option<&UploadCheckpointStore>,
+    commit_check: Option<CommitCheck>,
+) -> Result<DeltaItem> {
+    // A session commits on its last byte, so an empty file would never be
+    // committed; it is uploaded as empty content instead
+    if data.is_empty() {
+        if let Some(check) = commit_check {
+            check()?;
+        }
+        return upload_small(client, parent_path, name, data).await;
+    }
+
     let total = data.len() as u64;
     info!(
         "Starting large file upload: {} ({} bytes, {} chunks)",
@@ -397,27 +633,104 @@ pub async fn upload_large(
         total.div_ceil(CHUNK_SIZE as u64)
     );
 
-    // Step 1: Create upload session
-    let upload_url = create_upload_session(client, parent_path, name).await?;
-
-    // Step 2: Upload chunks
     let http_client = client.http_client();
     let access_token = client.access_token();
-    let mut offset: u64 = 0;
+    let remote_path = format!("{}/{}", parent_path.as_str().trim_end_matches('/'), name);
+    let content_hash = match checkpoints {
+        Some(_) => {
+            let mut hasher = QuickXorHash::new();
+            hasher.update(data);
+            Some(hasher.finalize_hash()?.as_str().to_string())
+        }
+        None => None,
+    };
+
+    // Step 1: Resume a checkpointed session, or create a new one
+    let resumed = match (checkpoints, &content_hash) {
+        (Some(store), Some(hash)) => match store.load(&remote_path, total, hash) {
+            Some(checkpoint) => {
+                match query_upload_session(http_client, &checkpoint.upload_url, access_token).await
+                {
+                    Ok(offset) if offset <= total => {
+                        info!(name, offset, total, "Resuming checkpointed upload session");
+                        Some((checkpoint, offset))
+                    }
+                    Ok(_) | Err(_) => {
+                        warn!(name, "Checkpointed upload session is gone, starting over");
+                        store.remove(&remote_path);
+                        None
+                    }
+                }
+            }
+            None => None,
+        },
+        _ => None,
+    };
+
+    let (mut checkpoint, mut offset) = match resumed {
+        Some(resumed) => resumed,
+        None => {
+            let session = start_upload_session(client, parent_path, name).await?;
+            let checkpoint = UploadCheckpoint {
+                remote_path: remote_path.clone(),
+                upload_url: session.upload_url,
+                total,
+                content_hash: content_hash.clone().unwrap_or_default(),
+                next_offset: 0,
+                expires_at: session
+                    .expiration_date_time
+                    .as_deref()
+                    .and_then(|s| DateTime::parse_from_rfc3339(s).ok())
+                    .map(|dt| dt.with_timezone(&Utc)),
+            };
+            // Record the session right away so even an interruption during
+            // the first chunk can reuse it
+            if let Some(store) = checkpoints {
+                if let Err(e) = store.save(&checkpoint) {
+                    warn!(name, error = %e, "Failed to checkpoint upload session");
+                }
+            }
+            (checkpoint, 0)
+        }
+    };
+    let upload_url = checkpoint.upload_url.clone();
+
+    // Step 2: Upload chunks
     let mut final_response: Option<serde_json::Value> = None;
 
     while offset < total {
         let end = std::cmp::min(offset + CHUNK_SIZE as u64, total);
         let chunk = &data[offset as usize..end as usize];
 
-        let result = upload_chunk(http_client, &upload_url, access_token, chunk, offset, total)
-            .await
-            .with_context(|| {
-                format!(
-                    "Failed to upload chunk at offset {}/{} for {}",
-                    offset, total, name
-                )
-            })?;
+        if end == total {
+            if let Some(check) = &commit_check {
+                if let Err(e) = check() {
+                    warn!(name, error = %e, "Upload abandoned before the final chunk");
+                    if let Some(store) = checkpoints {
+                        store.remove(&remote_path);
+                    }
+                    return Err(e);
+                }
+            }
+        }
+
+        let timeout = client.transfer_timeout(chunk.len() as u64);
+        let result = upload_chunk(
+            http_client,
+            &upload_url,
+            access_token,
+            chunk,
+            offset,
+            total,
+            timeout,
+        )
+        .await
+        .with_context(|| {
+            format!(
+                "Failed to upload chunk at offset {}/{} for {}",
+                offset, total, name
+            )
+        })?;
 
         offset = end;
 
@@ -428,9 +741,18 @@ pub async fn upload_large(
 
         if let Some(response) = result {
             final_response = Some(response);
+        } else if let Some(store) = checkpoints {
+            checkpoint.next_offset = offset;
+            if let Err(e) = store.save(&checkpoint) {
+                warn!(name, error = %e, "Failed to checkpoint upload session");
+            }
         }
     }
 
+    if let Some(store) = checkpoints {
+        store.remove(&remote_path);
+    }
+
     // Step 3: Parse the final response into a DeltaItem
     let response_json = final_response
         .context("Upload session completed without receiving a final DriveItem response")?;
@@ -443,7 +765,9 @@ pub async fn upload_large(
         item.id, item.name, item.size
     );
 
-    Ok(drive_item_to_delta(item))
+    let mut delta = drive_item_to_delta(item);
+    client.special_folders().localize(&mut delta);
+    Ok(delta)
 }
 
 // ============================================================================
@@ -681,6 +1005,23 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_resolve_item_path_below_special_folder() {
+        let client = GraphClient::new("token");
+        client.special_folders().learn("documents", "Documentos");
+
+        let path = RemotePath::new("/Documents/Projects".to_string()).unwrap();
+        assert_eq!(
+            resolve_item_path(&client, &path, "a.txt", "content"),
+            "/me/drive/special/documents:/Projects/a.txt:/content"
+        );
+        let path = RemotePath::new("/Other".to_string()).unwrap();
+        assert_eq!(
+            resolve_item_path(&client, &path, "a.txt", "content"),
+            "/me/drive/root:/Other/a.txt:/content"
+        );
+    }
+
     // ---- UploadSessionResponse deserialization test ----
 
     #[test]
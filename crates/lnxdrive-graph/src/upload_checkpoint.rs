@@ -0,0 +1,207 @@
+//! Persistent checkpoints for resumable upload sessions
+//!
+//! Large files are uploaded through Graph upload sessions, which stay valid
+//! on the server for several days. By recording the session URL and the
+//! number of bytes already accepted after every chunk, an upload that was
+//! interrupted (e.g. by a daemon shutdown) can continue where it stopped on
+//! the next start instead of sending the whole file again.
+//!
+//! Each checkpoint is a small JSON file named after a hash of the remote
+//! path. A checkpoint is only reused when the remote path, total size and
+//! quickXorHash of the content all match, so a file that changed in the
+//! meantime starts a fresh session.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tracing::{debug, warn};
+
+/// State of an unfinished upload session
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UploadCheckpoint {
+    /// Remote path of the file being uploaded (`/Documents/big.iso`)
+    pub remote_path: String,
+    /// Upload session URL returned by `createUploadSession`
+    pub upload_url: String,
+    /// Total size of the file in bytes
+    pub total: u64,
+    /// quickXorHash of the content being uploaded
+    pub content_hash: String,
+    /// Number of bytes the server has accepted
+    pub next_offset: u64,
+    /// When the server will discard the session, if known
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+impl UploadCheckpoint {
+    /// Returns true if the session has expired on the server
+    pub fn is_expired(&self, now: DateTime<Utc>) -> bool {
+        self.expires_at.is_some_and(|expires_at| expires_at <= now)
+    }
+}
+
+/// Directory of [`UploadCheckpoint`] files
+#[derive(Debug, Clone)]
+pub struct UploadCheckpointStore {
+    dir: PathBuf,
+}
+
+impl UploadCheckpointStore {
+    /// Opens (and creates if needed) a checkpoint directory
+    pub fn new(dir: impl Into<PathBuf>) -> Result<Self> {
+        let dir = dir.into();
+        fs::create_dir_all(&dir)
+            .with_context(|| format!("Failed to create checkpoint dir {}", dir.display()))?;
+        Ok(Self { dir })
+    }
+
+    /// Returns the checkpoint directory
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Loads the checkpoint for `remote_path` if it matches the content
+    ///
+    /// Stale checkpoints (different size or hash, or expired) are removed.
+    pub fn load(
+        &self,
+        remote_path: &str,
+        total: u64,
+        content_hash: &str,
+    ) -> Option<UploadCheckpoint> {
+        let path = self.path_for(remote_path);
+        let data = fs::read(&path).ok()?;
+        let checkpoint: UploadCheckpoint = match serde_json::from_slice(&data) {
+            Ok(checkpoint) => checkpoint,
+            Err(e) => {
+                warn!(path = %path.display(), error = %e, "Discarding unreadable upload checkpoint");
+                let _ = fs::remove_file(&path);
+                return None;
+            }
+        };
+
+        if checkpoint.remote_path != remote_path
+            || checkpoint.total != total
+            || checkpoint.content_hash != content_hash
+            || checkpoint.is_expired(Utc::now())
+        {
+            debug!(remote_path, "Discarding stale upload checkpoint");
+            let _ = fs::remove_file(&path);
+            return None;
+        }
+        Some(checkpoint)
+    }
+
+    /// Writes `checkpoint`, replacing any previous one for the same path
+    pub fn save(&self, checkpoint: &UploadCheckpoint) -> Result<()> {
+        let path = self.path_for(&checkpoint.remote_path);
+        let tmp = path.with_extension("json.tmp");
+        let data = serde_json::to_vec(checkpoint).context("Failed to serialize checkpoint")?;
+        fs::write(&tmp, data)
+            .and_then(|()| fs::rename(&tmp, &path))
+            .with_context(|| format!("Failed to write checkpoint {}", path.display()))
+    }
+
+    /// Removes the checkpoint for `remote_path`, if any
+    pub fn remove(&self, remote_path: &str) {
+        let path = self.path_for(remote_path);
+        if let Err(e) = fs::remove_file(&path) {
+            if e.kind() != std::io::ErrorKind::NotFound {
+                warn!(path = %path.display(), error = %e, "Failed to remove upload checkpoint");
+            }
+        }
+    }
+
+    /// Returns the number of unfinished uploads with a checkpoint
+    pub fn count(&self) -> usize {
+        fs::read_dir(&self.dir)
+            .map(|entries| {
+                entries
+                    .filter_map(|entry| entry.ok())
+                    .filter(|entry| entry.path().extension().is_some_and(|ext| ext == "json"))
+                    .count()
+            })
+            .unwrap_or(0)
+    }
+
+    fn path_for(&self, remote_path: &str) -> PathBuf {
+        self.dir
+            .join(format!("{:016x}.json", fnv1a(remote_path.as_bytes())))
+    }
+}
+
+/// 64-bit FNV-1a, used for stable checkpoint file names
+fn fnv1a(bytes: &[u8]) -> u64 {
+    const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0100_0000_01b3;
+    bytes.iter().fold(OFFSET_BASIS, |hash, &byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(PRIME)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn checkpoint(remote_path: &str) -> UploadCheckpoint {
+        UploadCheckpoint {
+            remote_path: remote_path.to_string(),
+            upload_url: "https://upload.example.com/session/1".to_string(),
+            total: 30,
+            content_hash: "hash".to_string(),
+            next_offset: 10,
+            expires_at: None,
+        }
+    }
+
+    #[test]
+    fn test_save_load_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = UploadCheckpointStore::new(dir.path().join("uploads")).unwrap();
+        let cp = checkpoint("/Documents/big.iso");
+
+        store.save(&cp).unwrap();
+
+        assert_eq!(store.count(), 1);
+        assert_eq!(store.load("/Documents/big.iso", 30, "hash"), Some(cp));
+    }
+
+    #[test]
+    fn test_changed_content_discards_checkpoint() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = UploadCheckpointStore::new(dir.path()).unwrap();
+        store.save(&checkpoint("/a.bin")).unwrap();
+
+        assert!(store.load("/a.bin", 30, "other-hash").is_none());
+        assert_eq!(store.count(), 0);
+    }
+
+    #[test]
+    fn test_expired_checkpoint_is_discarded() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = UploadCheckpointStore::new(dir.path()).unwrap();
+        let mut cp = checkpoint("/a.bin");
+        cp.expires_at = Some(Utc::now() - chrono::Duration::hours(1));
+        store.save(&cp).unwrap();
+
+        assert!(store.load("/a.bin", 30, "hash").is_none());
+    }
+
+    #[test]
+    fn test_remove_deletes_checkpoint() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = UploadCheckpointStore::new(dir.path()).unwrap();
+        store.save(&checkpoint("/a.bin")).unwrap();
+        store.save(&checkpoint("/b.bin")).unwrap();
+
+        store.remove("/a.bin");
+
+        assert_eq!(store.count(), 1);
+        assert!(store.load("/b.bin", 30, "hash").is_some());
+    }
+}
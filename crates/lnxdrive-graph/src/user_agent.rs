@@ -0,0 +1,135 @@
+//! User-Agent of Graph requests
+//!
+//! Microsoft asks clients of the Graph API to identify themselves with a
+//! `User-Agent` of the form `ISV|Company|App/Version`: throttling is then
+//! attributed to the application rather than to anonymous traffic, and
+//! support can find the requests of one client. lnxdrive appends the
+//! operating system and architecture, which also makes its traffic easy
+//! to spot in proxy and network logs:
+//!
+//! ```text
+//! ISV|Enigmora|LNXDrive/0.1.0 (Fedora Linux 40; x86_64)
+//! ```
+//!
+//! The header can be replaced or left out with the `http` section of the
+//! configuration.
+
+use std::fs;
+
+use lnxdrive_core::config::HttpConfig;
+
+/// Vendor and product part of the User-Agent
+const PRODUCT: &str = "ISV|Enigmora|LNXDrive";
+
+/// Release file describing the distribution
+const OS_RELEASE: &str = "/etc/os-release";
+
+/// Name and version of the running operating system
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OsInfo {
+    /// Distribution name, e.g. "Fedora Linux"
+    pub name: String,
+    /// Distribution version, e.g. "40", if known
+    pub version: Option<String>,
+}
+
+impl OsInfo {
+    /// Reads the distribution from `/etc/os-release`
+    ///
+    /// Falls back to the kernel name ("linux") if the file is missing.
+    pub fn detect() -> Self {
+        fs::read_to_string(OS_RELEASE)
+            .ok()
+            .and_then(|content| Self::parse(&content))
+            .unwrap_or_else(|| Self {
+                name: std::env::consts::OS.to_string(),
+                version: None,
+            })
+    }
+
+    /// Parses the contents of an `os-release` file
+    ///
+    /// Returns `None` if it has no `NAME`.
+    pub fn parse(content: &str) -> Option<Self> {
+        let field = |key: &str| {
+            content.lines().find_map(|line| {
+                let value = line.trim().strip_prefix(key)?.strip_prefix('=')?;
+                let value = value.trim_matches(|c| c == '"' || c == '\'');
+                (!value.is_empty()).then(|| value.to_string())
+            })
+        };
+        Some(Self {
+            name: field("NAME")?,
+            version: field("VERSION_ID"),
+        })
+    }
+}
+
+/// Returns the default User-Agent for `os`
+pub fn default_user_agent(os: &OsInfo) -> String {
+    let os = match &os.version {
+        Some(version) => format!("{} {}", os.name, version),
+        None => os.name.clone(),
+    };
+    format!(
+        "{}/{} ({}; {})",
+        PRODUCT,
+        env!("CARGO_PKG_VERSION"),
+        os,
+        std::env::consts::ARCH
+    )
+}
+
+/// Returns the User-Agent to send as configured, or `None` to send none
+pub fn user_agent(config: &HttpConfig) -> Option<String> {
+    if !config.send_user_agent {
+        return None;
+    }
+    Some(match config.user_agent.as_deref().map(str::trim) {
+        Some(custom) if !custom.is_empty() => custom.to_string(),
+        _ => default_user_agent(&OsInfo::detect()),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_os_release() {
+        let content = "NAME=\"Fedora Linux\"\nVERSION=\"40 (Workstation Edition)\"\n\
+                       ID=fedora\nVERSION_ID=40\n";
+        let os = OsInfo::parse(content).unwrap();
+        assert_eq!(os.name, "Fedora Linux");
+        assert_eq!(os.version.as_deref(), Some("40"));
+
+        let rolling = OsInfo::parse("NAME=\"Arch Linux\"\nID=arch\n").unwrap();
+        assert_eq!(rolling.version, None);
+        assert!(OsInfo::parse("ID=unknown\n").is_none());
+    }
+
+    #[test]
+    fn test_default_user_agent() {
+        let os = OsInfo {
+            name: "Fedora Linux".to_string(),
+            version: Some("40".to_string()),
+        };
+        let ua = default_user_agent(&os);
+        assert!(ua.starts_with(&format!(
+            "ISV|Enigmora|LNXDrive/{} (Fedora Linux 40; ",
+            env!("CARGO_PKG_VERSION")
+        )));
+    }
+
+    #[test]
+    fn test_user_agent_follows_config() {
+        let mut config = HttpConfig::default();
+        assert!(user_agent(&config).unwrap().starts_with(PRODUCT));
+
+        config.user_agent = Some("custom/1.0".to_string());
+        assert_eq!(user_agent(&config).as_deref(), Some("custom/1.0"));
+
+        config.send_user_agent = false;
+        assert_eq!(user_agent(&config), None);
+    }
+}
@@ -6,7 +6,7 @@
 
 use lnxdrive_graph::client::GraphClient;
 use wiremock::{
-    matchers::{method, path, path_regex},
+    matchers::{method, path, path_regex, query_param, query_param_is_missing},
     Mock, MockServer, ResponseTemplate,
 };
 
@@ -155,3 +155,66 @@ pub async fn mount_upload_small(
         .mount(server)
         .await;
 }
+
+/// Builds `count` file items named `file-<start>.txt` onwards for delta pages.
+#[allow(dead_code)]
+pub fn delta_file_items(start: usize, count: usize) -> serde_json::Value {
+    let items: Vec<serde_json::Value> = (start..start + count)
+        .map(|n| {
+            serde_json::json!({
+                "id": format!("file-{n:06}"),
+                "name": format!("file-{n:06}.txt"),
+                "size": 100,
+                "lastModifiedDateTime": "2026-01-15T10:00:00Z",
+                "parentReference": { "id": "root", "path": "/drive/root:" },
+                "file": { "hashes": { "quickXorHash": "AAAAAAAAAAAAAAAAAAAAAAAAAAA=" } }
+            })
+        })
+        .collect();
+    serde_json::Value::Array(items)
+}
+
+/// Mounts a delta endpoint that returns `pages` in order, each after `delay`.
+///
+/// Page N links to page N+1 via `$skiptoken=pageN+1`; the last page carries
+/// a deltaLink with `delta_token`.
+#[allow(dead_code)]
+pub async fn mount_delta_pages(
+    server: &MockServer,
+    pages: Vec<serde_json::Value>,
+    delta_token: &str,
+    delay: std::time::Duration,
+) {
+    let last = pages.len();
+    for (index, items) in pages.into_iter().enumerate() {
+        let number = index + 1;
+        let mut body = serde_json::json!({ "value": items });
+        if number < last {
+            body["@odata.nextLink"] = serde_json::json!(format!(
+                "{}/me/drive/root/delta?$skiptoken=page{}",
+                server.uri(),
+                number + 1
+            ));
+        } else {
+            body["@odata.deltaLink"] = serde_json::json!(format!(
+                "{}/me/drive/root/delta?token={}",
+                server.uri(),
+                delta_token
+            ));
+        }
+
+        let mock = Mock::given(method("GET")).and(path("/me/drive/root/delta"));
+        let mock = if number == 1 {
+            mock.and(query_param_is_missing("$skiptoken"))
+        } else {
+            mock.and(query_param("$skiptoken", format!("page{number}")))
+        };
+        mock.respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(body)
+                .set_delay(delay),
+        )
+        .mount(server)
+        .await;
+    }
+}
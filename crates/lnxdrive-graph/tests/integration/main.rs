@@ -7,5 +7,6 @@
 mod common;
 
 mod test_delta;
+mod test_subscription;
 mod test_sync_operations;
 mod test_user_info;
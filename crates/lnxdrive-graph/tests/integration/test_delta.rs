@@ -4,13 +4,19 @@
 //! wiremock-based Graph API mock server:
 //! - Initial delta query (no token)
 //! - Incremental delta query (with token)
-//! - Pagination across multiple pages
+//! - Pagination across multiple pages (prefetched in the background)
+//! - A failing page, which must not yield a delta token
 //! - Empty delta response
 //! - Mixed item types (files, folders, deleted)
+//! - Expired token (410 Gone)
+//! - `Prefer` headers recommended for sync clients
+//! - Resuming an interrupted query from a nextLink
+
+use std::time::{Duration, Instant};
 
 use lnxdrive_graph::{client::GraphClient, delta};
 use wiremock::{
-    matchers::{method, path, query_param},
+    matchers::{headers, method, path, query_param},
     Mock, MockServer, ResponseTemplate,
 };
 
@@ -117,6 +123,29 @@ async fn test_delta_incremental_with_token() {
     assert!(response.delta_link.is_some());
 }
 
+#[tokio::test]
+async fn test_delta_gone_reports_expired_token() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/me/drive/root/delta"))
+        .and(query_param("token", "stale-token"))
+        .respond_with(ResponseTemplate::new(410))
+        .mount(&server)
+        .await;
+
+    let client = GraphClient::with_base_url("test-token", server.uri());
+    let delta_token =
+        lnxdrive_core::domain::newtypes::DeltaToken::new("stale-token".to_string()).unwrap();
+
+    let err = delta::get_delta(&client, Some(&delta_token))
+        .await
+        .expect_err("410 should fail the delta query");
+
+    assert!(lnxdrive_core::ports::cloud_provider::is_delta_token_expired(&err));
+    assert!(format!("{err:#}").contains("410 Gone"));
+}
+
 #[tokio::test]
 async fn test_delta_empty_response() {
     let (server, client) = common::setup_graph_mock().await;
@@ -224,3 +253,145 @@ async fn test_delta_mixed_item_types() {
     assert!(response.items[2].is_deleted);
     assert!(!response.items[2].is_directory);
 }
+
+#[tokio::test]
+async fn test_delta_follows_all_pages_in_order() {
+    let (server, client) = common::setup_graph_mock().await;
+    let pages = (0..5).map(|p| common::delta_file_items(p * 3, 3)).collect();
+    common::mount_delta_pages(&server, pages, "final-token", Duration::ZERO).await;
+
+    let response = delta::get_delta(&client, None)
+        .await
+        .expect("Paginated delta query failed");
+
+    let ids: Vec<String> = response.items.iter().map(|i| i.id.clone()).collect();
+    let expected: Vec<String> = (0..15).map(|n| format!("file-{n:06}")).collect();
+    assert_eq!(ids, expected);
+    assert!(response.next_link.is_none());
+    assert!(response.delta_link.unwrap().contains("token=final-token"));
+}
+
+#[tokio::test]
+async fn test_delta_failing_page_returns_no_token() {
+    let (server, client) = common::setup_graph_mock().await;
+    let pages = (0..3).map(|p| common::delta_file_items(p * 2, 2)).collect();
+    Mock::given(method("GET"))
+        .and(path("/me/drive/root/delta"))
+        .and(query_param("$skiptoken", "page2"))
+        .respond_with(ResponseTemplate::new(503))
+        .with_priority(1)
+        .mount(&server)
+        .await;
+    common::mount_delta_pages(&server, pages, "never-returned", Duration::ZERO).await;
+
+    let err = delta::get_delta(&client, None)
+        .await
+        .expect_err("A failing page should fail the delta query");
+
+    assert!(format!("{err:#}").contains("error status"));
+}
+
+/// Enumeration benchmark: 50k items in 250 pages, 20 ms latency per page
+/// and 20 ms of work on each page by the caller.
+///
+/// Compares following the pages one by one with [`delta::get_delta_page`]
+/// against [`delta::get_delta_pages`], which fetches the next page while
+/// the caller works on the current one. Run with
+/// `cargo test -p lnxdrive-graph --release -- --ignored --nocapture bench`.
+#[tokio::test(flavor = "multi_thread")]
+#[ignore = "benchmark"]
+async fn bench_delta_enumeration_of_large_drive() {
+    const PAGES: usize = 250;
+    const PER_PAGE: usize = 200;
+    const LATENCY: Duration = Duration::from_millis(20);
+    const WORK: Duration = Duration::from_millis(20);
+
+    let (server, client) = common::setup_graph_mock().await;
+    let pages = (0..PAGES)
+        .map(|p| common::delta_file_items(p * PER_PAGE, PER_PAGE))
+        .collect();
+    common::mount_delta_pages(&server, pages, "bench-token", LATENCY).await;
+
+    // Sequential: fetch the next page only after working on this one
+    let start = Instant::now();
+    let mut items = 0;
+    let mut next = Some(format!("{}/me/drive/root/delta", server.uri()));
+    while let Some(link) = next {
+        let page = delta::get_delta_page(&client, &link).await.unwrap();
+        items += page.items.len();
+        tokio::time::sleep(WORK).await;
+        next = page.next_link;
+    }
+    let sequential = start.elapsed();
+    assert_eq!(items, PAGES * PER_PAGE);
+
+    // Pipelined
+    let start = Instant::now();
+    let mut pages = delta::get_delta_pages(&client, None).await.unwrap();
+    let mut items = 0;
+    let mut delta_link = None;
+    while let Some(page) = pages.next_page().await {
+        let page = page.unwrap();
+        items += page.items.len();
+        delta_link = page.delta_link.or(delta_link);
+        tokio::time::sleep(WORK).await;
+    }
+    let pipelined = start.elapsed();
+    assert_eq!(items, PAGES * PER_PAGE);
+    assert!(delta_link.unwrap().contains("token=bench-token"));
+
+    println!(
+        "{} items: sequential {:?}, pipelined {:?}",
+        PAGES * PER_PAGE,
+        sequential,
+        pipelined
+    );
+}
+
+#[tokio::test]
+async fn test_delta_requests_send_prefer_header() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/me/drive/root/delta"))
+        .and(headers(
+            "prefer",
+            vec!["deltashowremovedasdeleted", "deltatraversepermissiongaps"],
+        ))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "value": [],
+            "@odata.deltaLink": format!("{}/me/drive/root/delta?token=t", server.uri())
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = GraphClient::with_base_url("test-token", server.uri());
+    let response = delta::get_delta(&client, None)
+        .await
+        .expect("Delta query with Prefer header failed");
+    assert!(response.delta_link.is_some());
+}
+
+#[tokio::test]
+async fn test_delta_resumes_from_next_link() {
+    let (server, client) = common::setup_graph_mock().await;
+    let pages = (0..4).map(|p| common::delta_file_items(p * 2, 2)).collect();
+    common::mount_delta_pages(&server, pages, "resumed-token", Duration::ZERO).await;
+
+    // Continue after page 2 was applied
+    let next_link = format!("{}/me/drive/root/delta?$skiptoken=page3", server.uri());
+    let mut pages = delta::resume_delta_pages(&client, &next_link)
+        .await
+        .expect("Resuming the delta query failed");
+    let mut items = Vec::new();
+    let mut delta_link = None;
+    while let Some(page) = pages.next_page().await {
+        let page = page.unwrap();
+        items.extend(page.items);
+        delta_link = page.delta_link.or(delta_link);
+    }
+
+    assert_eq!(items.len(), 4);
+    assert!(delta_link.unwrap().contains("token=resumed-token"));
+}
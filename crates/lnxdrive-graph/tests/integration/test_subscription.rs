@@ -0,0 +1,128 @@
+//! Integration tests for change-notification subscriptions
+//!
+//! Verifies the subscription lifecycle against a wiremock-based Graph API
+//! mock server:
+//! - Creating a subscription for the drive root
+//! - Renewing it with a new expiration
+//! - Deleting it, including one Graph no longer knows
+
+use chrono::{DateTime, Utc};
+use lnxdrive_graph::{client::GraphClient, subscription};
+use wiremock::{
+    matchers::{body_partial_json, method, path},
+    Mock, MockServer, ResponseTemplate,
+};
+
+fn subscription_json(expiration: &str) -> serde_json::Value {
+    serde_json::json!({
+        "id": "sub-001",
+        "resource": "/me/drive/root",
+        "changeType": "updated",
+        "notificationUrl": "https://example.org/hook",
+        "expirationDateTime": expiration,
+        "clientState": "secret"
+    })
+}
+
+#[tokio::test]
+async fn test_create_subscription_posts_drive_root() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/subscriptions"))
+        .and(body_partial_json(serde_json::json!({
+            "changeType": "updated",
+            "resource": "/me/drive/root",
+            "notificationUrl": "https://example.org/hook",
+            "clientState": "secret"
+        })))
+        .respond_with(
+            ResponseTemplate::new(201).set_body_json(subscription_json("2026-03-01T12:00:00Z")),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+    let client = GraphClient::with_base_url("token", server.uri());
+
+    let expiration: DateTime<Utc> = "2026-03-01T12:00:00Z".parse().unwrap();
+    let created = subscription::create_subscription(
+        &client,
+        "https://example.org/hook",
+        "secret",
+        expiration,
+    )
+    .await
+    .expect("create_subscription failed");
+
+    assert_eq!(created.id, "sub-001");
+    assert_eq!(created.resource, "/me/drive/root");
+    assert_eq!(created.expiration_date_time, expiration);
+}
+
+#[tokio::test]
+async fn test_renew_subscription_patches_expiration() {
+    let server = MockServer::start().await;
+    Mock::given(method("PATCH"))
+        .and(path("/subscriptions/sub-001"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(subscription_json("2026-03-04T12:00:00Z")),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+    let client = GraphClient::with_base_url("token", server.uri());
+
+    let expiration: DateTime<Utc> = "2026-03-04T12:00:00Z".parse().unwrap();
+    let renewed = subscription::renew_subscription(&client, "sub-001", expiration)
+        .await
+        .expect("renew_subscription failed");
+
+    assert_eq!(renewed.expiration_date_time, expiration);
+}
+
+#[tokio::test]
+async fn test_renew_expired_subscription_fails() {
+    let server = MockServer::start().await;
+    Mock::given(method("PATCH"))
+        .and(path("/subscriptions/sub-001"))
+        .respond_with(ResponseTemplate::new(404))
+        .mount(&server)
+        .await;
+    let client = GraphClient::with_base_url("token", server.uri());
+
+    let result = subscription::renew_subscription(&client, "sub-001", Utc::now()).await;
+
+    assert!(result.is_err());
+}
+
+#[tokio::test]
+async fn test_delete_subscription_accepts_unknown_id() {
+    let server = MockServer::start().await;
+    Mock::given(method("DELETE"))
+        .and(path("/subscriptions/sub-001"))
+        .respond_with(ResponseTemplate::new(204))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("DELETE"))
+        .and(path("/subscriptions/sub-gone"))
+        .respond_with(ResponseTemplate::new(404))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("DELETE"))
+        .and(path("/subscriptions/sub-error"))
+        .respond_with(ResponseTemplate::new(500))
+        .mount(&server)
+        .await;
+    let client = GraphClient::with_base_url("token", server.uri());
+
+    subscription::delete_subscription(&client, "sub-001")
+        .await
+        .expect("delete failed");
+    subscription::delete_subscription(&client, "sub-gone")
+        .await
+        .expect("deleting an unknown subscription failed");
+    assert!(subscription::delete_subscription(&client, "sub-error")
+        .await
+        .is_err());
+}
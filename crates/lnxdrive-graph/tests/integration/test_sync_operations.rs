@@ -3,10 +3,21 @@
 //! Verifies end-to-end behavior of file upload and download operations
 //! against a wiremock-based Graph API mock server.
 
-use lnxdrive_core::domain::newtypes::RemoteId;
-use lnxdrive_graph::{client::GraphClient, upload};
+use lnxdrive_core::{
+    domain::{
+        newtypes::{RemoteId, RemotePath},
+        QuickXorHash,
+    },
+    ports::cloud_provider::{is_remote_item_not_found, ICloudProvider},
+};
+use lnxdrive_graph::{
+    client::GraphClient,
+    provider::GraphCloudProvider,
+    upload,
+    upload_checkpoint::{UploadCheckpoint, UploadCheckpointStore},
+};
 use wiremock::{
-    matchers::{method, path},
+    matchers::{body_bytes, body_partial_json, header, method, path},
     Mock, MockServer, ResponseTemplate,
 };
 
@@ -89,6 +100,358 @@ async fn test_upload_small_file() {
     assert!(!result.is_directory);
 }
 
+#[tokio::test]
+async fn test_upload_small_sends_detected_mime_type() {
+    let (server, client) = common::setup_graph_mock().await;
+
+    Mock::given(method("PUT"))
+        .and(path("/me/drive/root:/Pictures/photo:/content"))
+        .and(header("Content-Type", "image/png"))
+        .respond_with(ResponseTemplate::new(201).set_body_json(serde_json::json!({
+            "id": "upload-png",
+            "name": "photo",
+            "size": 16,
+            "file": { "mimeType": "image/png" }
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let parent_path = RemotePath::new("/Pictures".to_string()).unwrap();
+    let png = b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR";
+    let result = upload::upload_small(&client, &parent_path, "photo", png)
+        .await
+        .expect("Small upload failed");
+
+    assert_eq!(result.id, "upload-png");
+}
+
+fn content_hash(data: &[u8]) -> String {
+    let mut hasher = QuickXorHash::new();
+    hasher.update(data);
+    hasher.finalize_hash().unwrap().as_str().to_string()
+}
+
+fn uploaded_item() -> serde_json::Value {
+    serde_json::json!({
+        "id": "large-upload-001",
+        "name": "big.bin",
+        "size": 12,
+        "lastModifiedDateTime": "2026-01-15T10:00:00Z",
+        "parentReference": { "id": "parent-001", "path": "/drive/root:/Documents" },
+        "file": { "hashes": { "quickXorHash": "AAAAAAAAAAAAAAAAAAAAAAAAAAA=" } }
+    })
+}
+
+#[tokio::test]
+async fn test_upload_large_resumes_from_checkpoint() {
+    let (server, client) = common::setup_graph_mock().await;
+    let dir = tempfile::tempdir().unwrap();
+    let store = UploadCheckpointStore::new(dir.path()).unwrap();
+    let data = b"hello world!";
+    let upload_url = format!("{}/upload/session-1", server.uri());
+
+    store
+        .save(&UploadCheckpoint {
+            remote_path: "/Documents/big.bin".to_string(),
+            upload_url: upload_url.clone(),
+            total: data.len() as u64,
+            content_hash: content_hash(data),
+            next_offset: 6,
+            expires_at: None,
+        })
+        .unwrap();
+
+    Mock::given(method("POST"))
+        .and(path(
+            "/me/drive/root:/Documents/big.bin:/createUploadSession",
+        ))
+        .respond_with(ResponseTemplate::new(500))
+        .expect(0)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/upload/session-1"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(serde_json::json!({ "nextExpectedRanges": ["6-"] })),
+        )
+        .mount(&server)
+        .await;
+    Mock::given(method("PUT"))
+        .and(path("/upload/session-1"))
+        .and(header("Content-Range", "bytes 6-11/12"))
+        .respond_with(ResponseTemplate::new(201).set_body_json(uploaded_item()))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let parent_path = RemotePath::new("/Documents".to_string()).unwrap();
+    let result =
+        upload::upload_large_resumable(&client, &parent_path, "big.bin", data, None, Some(&store))
+            .await
+            .expect("Resumed upload failed");
+
+    assert_eq!(result.id, "large-upload-001");
+    assert_eq!(store.count(), 0);
+}
+
+#[tokio::test]
+async fn test_upload_large_keeps_checkpoint_on_failure() {
+    let (server, client) = common::setup_graph_mock().await;
+    let dir = tempfile::tempdir().unwrap();
+    let store = UploadCheckpointStore::new(dir.path()).unwrap();
+    let data = b"hello world!";
+    let upload_url = format!("{}/upload/session-2", server.uri());
+
+    Mock::given(method("POST"))
+        .and(path(
+            "/me/drive/root:/Documents/big.bin:/createUploadSession",
+        ))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "uploadUrl": upload_url,
+            "expirationDateTime": "2099-01-01T00:00:00Z"
+        })))
+        .mount(&server)
+        .await;
+    Mock::given(method("PUT"))
+        .and(path("/upload/session-2"))
+        .respond_with(ResponseTemplate::new(503))
+        .mount(&server)
+        .await;
+
+    let parent_path = RemotePath::new("/Documents".to_string()).unwrap();
+    let result =
+        upload::upload_large_resumable(&client, &parent_path, "big.bin", data, None, Some(&store))
+            .await;
+
+    assert!(result.is_err());
+    let checkpoint = store
+        .load("/Documents/big.bin", data.len() as u64, &content_hash(data))
+        .expect("checkpoint should survive a failed upload");
+    assert_eq!(checkpoint.upload_url, upload_url);
+    assert_eq!(checkpoint.next_offset, 0);
+}
+
+#[tokio::test]
+async fn test_upload_large_checked_does_not_commit_changed_file() {
+    let (server, client) = common::setup_graph_mock().await;
+    let dir = tempfile::tempdir().unwrap();
+    let store = UploadCheckpointStore::new(dir.path()).unwrap();
+    let data = b"hello world!";
+    let upload_url = format!("{}/upload/session-3", server.uri());
+
+    Mock::given(method("POST"))
+        .and(path(
+            "/me/drive/root:/Documents/big.bin:/createUploadSession",
+        ))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "uploadUrl": upload_url,
+            "expirationDateTime": "2099-01-01T00:00:00Z"
+        })))
+        .mount(&server)
+        .await;
+    Mock::given(method("PUT"))
+        .and(path("/upload/session-3"))
+        .respond_with(ResponseTemplate::new(201).set_body_json(uploaded_item()))
+        .expect(0)
+        .mount(&server)
+        .await;
+
+    let parent_path = RemotePath::new("/Documents".to_string()).unwrap();
+    let result = upload::upload_large_checked(
+        &client,
+        &parent_path,
+        "big.bin",
+        data,
+        None,
+        Some(&store),
+        Some(Box::new(|| anyhow::bail!("big.bin changed during upload"))),
+    )
+    .await;
+
+    let err = result.expect_err("the final chunk must not be sent");
+    assert!(err.to_string().contains("changed during upload"));
+    assert_eq!(store.count(), 0);
+}
+
+#[tokio::test]
+async fn test_upload_empty_file_puts_empty_content() {
+    let (server, client) = common::setup_graph_mock().await;
+
+    Mock::given(method("PUT"))
+        .and(path("/me/drive/root:/Documents/empty.txt:/content"))
+        .and(header("Content-Length", "0"))
+        .and(body_bytes(Vec::new()))
+        .respond_with(ResponseTemplate::new(201).set_body_json(serde_json::json!({
+            "id": "empty-001",
+            "name": "empty.txt",
+            "size": 0,
+            "file": {}
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path(
+            "/me/drive/root:/Documents/empty.txt:/createUploadSession",
+        ))
+        .respond_with(ResponseTemplate::new(500))
+        .expect(0)
+        .mount(&server)
+        .await;
+
+    // Even through the session API, which cannot commit zero bytes
+    let parent_path = RemotePath::new("/Documents".to_string()).unwrap();
+    let result = upload::upload_large(&client, &parent_path, "empty.txt", b"", None)
+        .await
+        .expect("Empty upload failed");
+
+    assert_eq!(result.id, "empty-001");
+    assert_eq!(result.size, Some(0));
+    assert!(!result.is_directory);
+}
+
+// ============================================================================
+// Folder creation tests
+// ============================================================================
+
+#[tokio::test]
+async fn test_create_folder_posts_folder_facet() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/me/drive/root:/Documents:/children"))
+        .and(body_partial_json(serde_json::json!({
+            "name": "Empty",
+            "folder": {},
+            "@microsoft.graph.conflictBehavior": "fail"
+        })))
+        .respond_with(ResponseTemplate::new(201).set_body_json(serde_json::json!({
+            "id": "folder-001",
+            "name": "Empty",
+            "parentReference": { "id": "parent-001", "path": "/drive/root:/Documents" },
+            "folder": { "childCount": 0 }
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let provider = GraphCloudProvider::new(GraphClient::with_base_url("test-token", server.uri()));
+    let parent_path = RemotePath::new("/Documents".to_string()).unwrap();
+    let folder = provider
+        .create_folder(&parent_path, "Empty")
+        .await
+        .expect("Folder creation failed");
+
+    assert_eq!(folder.id, "folder-001");
+    assert!(folder.is_directory);
+    assert_eq!(folder.path.as_deref(), Some("/Documents/Empty"));
+}
+
+#[tokio::test]
+async fn test_create_folder_returns_existing_folder_on_conflict() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/me/drive/root/children"))
+        .respond_with(ResponseTemplate::new(409).set_body_json(serde_json::json!({
+            "error": { "code": "nameAlreadyExists", "message": "Name already exists" }
+        })))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/me/drive/root:/Photos"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "id": "folder-002",
+            "name": "Photos",
+            "parentReference": { "id": "root", "path": "/drive/root:" },
+            "folder": { "childCount": 3 }
+        })))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/me/drive/root:/notes.txt"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "id": "file-001",
+            "name": "notes.txt",
+            "parentReference": { "id": "root", "path": "/drive/root:" },
+            "file": {}
+        })))
+        .mount(&server)
+        .await;
+
+    let provider = GraphCloudProvider::new(GraphClient::with_base_url("test-token", server.uri()));
+    let root = RemotePath::new("/".to_string()).unwrap();
+    let folder = provider.create_folder(&root, "Photos").await.unwrap();
+    assert_eq!(folder.id, "folder-002");
+    assert!(folder.is_directory);
+
+    // A file of that name is not a folder
+    assert!(provider.create_folder(&root, "notes.txt").await.is_err());
+}
+
+// ============================================================================
+// Folder listing tests
+// ============================================================================
+
+#[tokio::test]
+async fn test_list_children_follows_pages() {
+    let server = MockServer::start().await;
+    let next_link = format!("{}/me/drive/items/docs/children?$skiptoken=2", server.uri());
+
+    Mock::given(method("GET"))
+        .and(path("/me/drive/root:/Docs:/children"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "value": [{
+                "id": "report",
+                "name": "report.pdf",
+                "size": 2048,
+                "parentReference": { "id": "docs", "path": "/drive/root:/Docs" },
+                "file": { "hashes": { "quickXorHash": "AAAAAAAAAAAAAAAAAAAAAAAAAAA=" } }
+            }],
+            "@odata.nextLink": next_link
+        })))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/me/drive/items/docs/children"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "value": [{
+                "id": "drafts",
+                "name": "Drafts",
+                "parentReference": { "id": "docs", "path": "/drive/root:/Docs" },
+                "folder": { "childCount": 3 }
+            }]
+        })))
+        .mount(&server)
+        .await;
+
+    let provider = GraphCloudProvider::new(GraphClient::with_base_url("test-token", server.uri()));
+    let docs = RemotePath::new("/Docs".to_string()).unwrap();
+
+    let first = provider.list_children(&docs, None).await.unwrap();
+    assert_eq!(first.items.len(), 1);
+    assert_eq!(first.items[0].path.as_deref(), Some("/Docs/report.pdf"));
+    assert_eq!(first.items[0].size, Some(2048));
+    assert!(!first.items[0].is_directory);
+    assert_eq!(first.continuation.as_deref(), Some(next_link.as_str()));
+
+    let second = provider
+        .list_children(&docs, first.continuation.as_deref())
+        .await
+        .unwrap();
+    assert_eq!(second.items[0].path.as_deref(), Some("/Docs/Drafts"));
+    assert!(second.items[0].is_directory);
+    assert!(second.continuation.is_none());
+
+    // Continuations must point to the Graph API
+    assert!(provider
+        .list_children(&docs, Some("https://example.org/steal"))
+        .await
+        .is_err());
+}
+
 // ============================================================================
 // Error handling tests
 // ============================================================================
@@ -115,6 +478,41 @@ async fn test_download_returns_error_on_404() {
     assert!(result.is_err());
 }
 
+#[tokio::test]
+async fn test_delete_reports_missing_item() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("DELETE"))
+        .and(path("/me/drive/items/gone"))
+        .respond_with(ResponseTemplate::new(404).set_body_json(serde_json::json!({
+            "error": {
+                "code": "itemNotFound",
+                "message": "Item not found"
+            }
+        })))
+        .mount(&server)
+        .await;
+    Mock::given(method("DELETE"))
+        .and(path("/me/drive/items/locked"))
+        .respond_with(ResponseTemplate::new(423))
+        .mount(&server)
+        .await;
+
+    let provider = GraphCloudProvider::new(GraphClient::with_base_url("test-token", server.uri()));
+
+    let err = provider
+        .delete_item(&RemoteId::new("gone".to_string()).unwrap())
+        .await
+        .unwrap_err();
+    assert!(is_remote_item_not_found(&err));
+
+    let err = provider
+        .delete_item(&RemoteId::new("locked".to_string()).unwrap())
+        .await
+        .unwrap_err();
+    assert!(!is_remote_item_not_found(&err));
+}
+
 #[tokio::test]
 async fn test_user_info_returns_error_on_401() {
     let server = MockServer::start().await;
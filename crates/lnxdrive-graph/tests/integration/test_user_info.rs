@@ -22,11 +22,236 @@ async fn test_get_user_info_returns_profile_and_quota() {
 async fn test_get_drive_quota() {
     let (_server, client) = common::setup_graph_mock().await;
 
-    let (used, total) = client
+    let quota = client
         .get_drive_quota()
         .await
         .expect("get_drive_quota failed");
 
-    assert_eq!(used, 1_073_741_824);
-    assert_eq!(total, 5_368_709_120);
+    assert_eq!(quota.used, 1_073_741_824);
+    assert_eq!(quota.total, 5_368_709_120);
+    assert_eq!(quota.remaining(), Some(4_294_967_296));
+    assert!(!quota.is_unlimited());
+}
+
+#[tokio::test]
+async fn test_get_drive_quota_unlimited() {
+    use wiremock::{
+        matchers::{method, path},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    // Business drives without a storage limit omit total and remaining
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/me/drive"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "id": "drive-business-001",
+            "driveType": "business",
+            "quota": {
+                "used": 2_199_023_255_552_u64,
+                "deleted": 0,
+                "state": "normal"
+            }
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+    let client = lnxdrive_graph::client::GraphClient::with_base_url("token", server.uri());
+
+    let quota = client
+        .get_drive_quota()
+        .await
+        .expect("get_drive_quota failed");
+
+    assert!(quota.is_unlimited());
+    assert_eq!(quota.used, 2_199_023_255_552);
+    assert_eq!(quota.percent_used(), None);
+}
+
+#[tokio::test]
+async fn test_provider_get_quota_uses_drive_endpoint() {
+    use lnxdrive_core::ports::cloud_provider::ICloudProvider;
+    use lnxdrive_graph::provider::GraphCloudProvider;
+
+    let (_server, client) = common::setup_graph_mock().await;
+    let provider = GraphCloudProvider::new(client);
+
+    let quota = provider.get_quota().await.expect("get_quota failed");
+
+    assert_eq!(quota.used, 1_073_741_824);
+    assert_eq!(quota.total, 5_368_709_120);
+}
+
+#[tokio::test]
+async fn test_revoke_sign_in_sessions() {
+    use wiremock::{
+        matchers::{method, path},
+        Mock, ResponseTemplate,
+    };
+
+    let (server, client) = common::setup_graph_mock().await;
+
+    Mock::given(method("POST"))
+        .and(path("/me/revokeSignInSessions"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "value": true
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    client
+        .revoke_sign_in_sessions()
+        .await
+        .expect("revoke_sign_in_sessions failed");
+}
+
+#[tokio::test]
+async fn test_revoke_sign_in_sessions_error_status() {
+    use wiremock::{
+        matchers::{method, path},
+        Mock, ResponseTemplate,
+    };
+
+    let (server, client) = common::setup_graph_mock().await;
+
+    Mock::given(method("POST"))
+        .and(path("/me/revokeSignInSessions"))
+        .respond_with(ResponseTemplate::new(401))
+        .mount(&server)
+        .await;
+
+    assert!(client.revoke_sign_in_sessions().await.is_err());
+}
+
+#[tokio::test]
+async fn test_measure_clock_skew_reads_date_header() {
+    use wiremock::{
+        matchers::{method, path},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    // The server's clock is an hour behind ours
+    let server_time = chrono::Utc::now() - chrono::Duration::hours(1);
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/"))
+        .respond_with(
+            ResponseTemplate::new(401).insert_header(
+                "Date",
+                server_time
+                    .format("%a, %d %b %Y %H:%M:%S GMT")
+                    .to_string()
+                    .as_str(),
+            ),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+    let client = lnxdrive_graph::client::GraphClient::with_base_url("", server.uri());
+
+    let skew = client
+        .measure_clock_skew()
+        .await
+        .expect("measure_clock_skew failed");
+
+    assert!((3599..=3601).contains(&skew.seconds), "{}", skew.seconds);
+    assert!(skew.is_significant());
+}
+
+#[tokio::test]
+async fn test_requests_carry_configured_user_agent() {
+    use lnxdrive_core::config::HttpConfig;
+    use wiremock::{
+        matchers::{header, method, path},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/me/drive"))
+        .and(header("user-agent", "custom-agent/2.0"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "id": "drive-test-001",
+            "quota": { "total": 100_u64, "used": 10_u64 }
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let config = HttpConfig {
+        user_agent: Some("custom-agent/2.0".to_string()),
+        ..HttpConfig::default()
+    };
+    let client = lnxdrive_graph::client::GraphClient::with_base_url("token", server.uri())
+        .with_http_config(&config);
+    client
+        .get_drive_quota()
+        .await
+        .expect("request with custom User-Agent did not match");
+
+    // The default identifies lnxdrive; disabled sends no User-Agent at all
+    let default_client = lnxdrive_graph::client::GraphClient::with_base_url("token", server.uri());
+    default_client.get_drive_quota().await.unwrap_err();
+    let disabled = HttpConfig {
+        send_user_agent: false,
+        ..HttpConfig::default()
+    };
+    let disabled_client = lnxdrive_graph::client::GraphClient::with_base_url("token", server.uri())
+        .with_http_config(&disabled);
+    disabled_client.get_drive_quota().await.unwrap_err();
+
+    let requests = server.received_requests().await.unwrap();
+    assert_eq!(requests.len(), 3);
+    let agent = |i: usize| {
+        requests[i]
+            .headers
+            .get("user-agent")
+            .map(|v| v.to_str().unwrap().to_string())
+    };
+    assert!(agent(1).unwrap().starts_with("ISV|Enigmora|LNXDrive/"));
+    assert_eq!(agent(2), None);
+}
+
+#[tokio::test]
+async fn test_request_exceeding_timeout_is_aborted() {
+    use std::time::{Duration, Instant};
+
+    use lnxdrive_core::config::HttpConfig;
+    use lnxdrive_graph::GraphError;
+    use wiremock::{
+        matchers::{method, path},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/me/drive"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(serde_json::json!({ "id": "drive-test-001" }))
+                .set_delay(Duration::from_secs(10)),
+        )
+        .mount(&server)
+        .await;
+
+    let config = HttpConfig {
+        request_timeout: 1,
+        ..HttpConfig::default()
+    };
+    let client = lnxdrive_graph::client::GraphClient::with_base_url("token", server.uri())
+        .with_http_config(&config);
+
+    let started = Instant::now();
+    let err = client
+        .get_drive_quota()
+        .await
+        .expect_err("hung request was not aborted");
+    assert!(started.elapsed() < Duration::from_secs(5));
+
+    let source = err
+        .downcast::<reqwest::Error>()
+        .expect("error is not a reqwest error");
+    assert!(source.is_timeout());
+    assert!(matches!(GraphError::from(source), GraphError::Timeout(_)));
 }
@@ -14,6 +14,9 @@
 //! - `com.enigmora.LNXDrive.Settings` - Configuration management
 //! - `com.enigmora.LNXDrive.Manager` - Daemon lifecycle
 //!
+//! Desktop notifications are sent through `org.freedesktop.Notifications`
+//! by [`notifications::DesktopNotifier`].
+//!
 //! # Usage
 //!
 //! The [`DbusService`] type is the main entry point. It manages the
@@ -30,10 +33,13 @@
 //! # }
 //! ```
 
+pub mod notifications;
 pub mod service;
 
+pub use notifications::DesktopNotifier;
 pub use service::{
-    AccountInterface, AuthInterface, ConflictsInterface, DaemonState, DaemonSyncState,
-    DbusService, FilesInterface, ManagerInterface, SettingsInterface, StatusInterface,
-    SyncControllerInterface, SyncInterface, DBUS_NAME, DBUS_PATH,
+    AccountInterface, AuthInterface, ConflictsInterface, DaemonState, DaemonSyncState, DbusService,
+    DbusHydrationObserver, DbusTransferObserver, FilesInterface, LifecycleRequest, ManagerInterface, SettingsInterface, StatusInterface,
+    SyncControllerInterface, SyncInterface, SyncPathRequest, SyncPathStatus, DAEMON_FEATURES, DBUS_INTERFACE_VERSIONS, DBUS_NAME, DBUS_PATH,
+    DBUS_SCHEMA_VERSION,
 };
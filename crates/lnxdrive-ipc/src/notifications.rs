@@ -0,0 +1,148 @@
+//! Desktop notifications over the freedesktop notification service
+//!
+//! [`DesktopNotifier`] implements [`INotificationService`] by calling
+//! `org.freedesktop.Notifications` on the session bus, which every major
+//! desktop (GNOME, KDE, XFCE, ...) provides.
+//!
+//! Progress indicators are ordinary notifications that are replaced in
+//! place on every update, using the `value` hint understood by most
+//! notification daemons to draw a progress bar.
+
+use std::collections::HashMap;
+
+use lnxdrive_core::ports::notification::{
+    INotificationService, Notification, NotificationPriority,
+};
+use tokio::sync::Mutex;
+use zbus::zvariant::Value;
+
+const NOTIFICATIONS_NAME: &str = "org.freedesktop.Notifications";
+const NOTIFICATIONS_PATH: &str = "/org/freedesktop/Notifications";
+const APP_NAME: &str = "LNXDrive";
+const APP_ICON: &str = "lnxdrive";
+
+/// Sends notifications to the desktop's notification daemon
+pub struct DesktopNotifier {
+    connection: zbus::Connection,
+    /// Notification IDs of the progress indicators currently shown
+    progress_ids: Mutex<HashMap<String, u32>>,
+}
+
+impl DesktopNotifier {
+    /// Creates a notifier that sends notifications over `connection`
+    pub fn new(connection: zbus::Connection) -> Self {
+        Self {
+            connection,
+            progress_ids: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Calls `Notify` and returns the ID assigned by the notification daemon
+    async fn send(
+        &self,
+        replaces_id: u32,
+        summary: &str,
+        body: &str,
+        hints: HashMap<&str, Value<'_>>,
+    ) -> anyhow::Result<u32> {
+        let actions: Vec<&str> = Vec::new();
+        // -1 lets the notification daemon choose the timeout
+        let expire_timeout: i32 = -1;
+        let reply = self
+            .connection
+            .call_method(
+                Some(NOTIFICATIONS_NAME),
+                NOTIFICATIONS_PATH,
+                Some(NOTIFICATIONS_NAME),
+                "Notify",
+                &(
+                    APP_NAME,
+                    replaces_id,
+                    APP_ICON,
+                    summary,
+                    body,
+                    actions,
+                    hints,
+                    expire_timeout,
+                ),
+            )
+            .await?;
+        Ok(reply.body().deserialize::<u32>()?)
+    }
+}
+
+#[async_trait::async_trait]
+impl INotificationService for DesktopNotifier {
+    async fn notify(&self, notification: &Notification) -> anyhow::Result<()> {
+        let mut hints = HashMap::new();
+        hints.insert("urgency", Value::U8(urgency(notification.priority)));
+        if !notification.category.is_empty() {
+            hints.insert(
+                "category",
+                Value::from(format!("x-lnxdrive.{}", notification.category)),
+            );
+        }
+        self.send(0, &notification.title, &notification.body, hints)
+            .await?;
+        Ok(())
+    }
+
+    async fn show_progress(
+        &self,
+        progress_id: &str,
+        title: &str,
+        percent: f64,
+    ) -> anyhow::Result<()> {
+        let mut ids = self.progress_ids.lock().await;
+        let replaces_id = ids.get(progress_id).copied().unwrap_or(0);
+
+        let value = percent.clamp(0.0, 100.0).round() as i32;
+        let mut hints = HashMap::new();
+        hints.insert("value", Value::I32(value));
+        hints.insert("urgency", Value::U8(urgency(NotificationPriority::Low)));
+
+        let id = self
+            .send(replaces_id, title, &format!("{value}%"), hints)
+            .await?;
+        ids.insert(progress_id.to_string(), id);
+        Ok(())
+    }
+
+    async fn clear_progress(&self, progress_id: &str) -> anyhow::Result<()> {
+        let Some(id) = self.progress_ids.lock().await.remove(progress_id) else {
+            return Ok(());
+        };
+        self.connection
+            .call_method(
+                Some(NOTIFICATIONS_NAME),
+                NOTIFICATIONS_PATH,
+                Some(NOTIFICATIONS_NAME),
+                "CloseNotification",
+                &(id,),
+            )
+            .await?;
+        Ok(())
+    }
+}
+
+/// Maps a priority to the freedesktop `urgency` hint (0 low, 1 normal, 2 critical)
+fn urgency(priority: NotificationPriority) -> u8 {
+    match priority {
+        NotificationPriority::Low => 0,
+        NotificationPriority::Normal => 1,
+        NotificationPriority::High | NotificationPriority::Critical => 2,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_urgency_mapping() {
+        assert_eq!(urgency(NotificationPriority::Low), 0);
+        assert_eq!(urgency(NotificationPriority::Normal), 1);
+        assert_eq!(urgency(NotificationPriority::High), 2);
+        assert_eq!(urgency(NotificationPriority::Critical), 2);
+    }
+}
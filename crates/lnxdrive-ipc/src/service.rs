@@ -11,14 +11,30 @@
 //! - `com.enigmora.LNXDrive.Status` - Account and quota information
 //! - `com.enigmora.LNXDrive.Auth` - OAuth2 authentication flow
 //! - `com.enigmora.LNXDrive.Settings` - Configuration management
-//! - `com.enigmora.LNXDrive.Manager` - Daemon lifecycle management
+//! - `com.enigmora.LNXDrive.Cache` - Content cache usage, cleaning and verification
+//! - `com.enigmora.LNXDrive.Manager` - Daemon lifecycle management and capabilities
 //!
 //! Signals are emitted on state changes, sync progress, and errors.
 
 use std::collections::HashMap;
+use std::path::PathBuf;
 use std::sync::Arc;
-
-use tokio::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use chrono::{DateTime, Utc};
+use lnxdrive_conflict::{BatchItem, BatchOutcome, BatchResult, ConflictResolver, PathFilter};
+use lnxdrive_core::config::Config;
+use lnxdrive_core::domain::{
+    newtypes::{RemotePath, SyncPath},
+    AuditAction, AuditEntry, AuditResult, ClockSkew, Conflict, ItemState, Resolution,
+    ResolutionSource, SyncHistoryEntry, SyncItem, MAX_SYNC_HISTORY_ENTRIES,
+};
+use lnxdrive_core::ports::{
+    CacheCleanOptions, CachePinReport, FolderPage, HydrationEvent, ICacheManager, ICloudProvider,
+    IHydrationObserver, IStateRepository, ITransferObserver, TransferControl, TransferEvent,
+};
+use lnxdrive_telemetry::MetricsRegistry;
+use tokio::sync::{mpsc, oneshot, Mutex, Notify};
 use tracing::{debug, info, warn};
 use zbus::zvariant::{OwnedValue, Value};
 
@@ -28,6 +44,38 @@ pub const DBUS_NAME: &str = "com.enigmora.LNXDrive";
 /// D-Bus object path for the service
 pub const DBUS_PATH: &str = "/com/enigmora/LNXDrive";
 
+/// Version of the D-Bus API as a whole
+///
+/// Bump it, together with the version of the affected interface in
+/// [`DBUS_INTERFACE_VERSIONS`], whenever a method, signal or property is
+/// added, removed or changes meaning.
+pub const DBUS_SCHEMA_VERSION: u32 = 3;
+
+/// Version of each interface served at [`DBUS_PATH`]
+pub const DBUS_INTERFACE_VERSIONS: &[(&str, u32)] = &[
+    ("com.enigmora.LNXDrive.SyncController", 1),
+    ("com.enigmora.LNXDrive.Account", 1),
+    ("com.enigmora.LNXDrive.Conflicts", 1),
+    ("com.enigmora.LNXDrive.Files", 2),
+    ("com.enigmora.LNXDrive.Sync", 2),
+    ("com.enigmora.LNXDrive.Status", 1),
+    ("com.enigmora.LNXDrive.Auth", 1),
+    ("com.enigmora.LNXDrive.Settings", 1),
+    ("com.enigmora.LNXDrive.Cache", 1),
+    ("com.enigmora.LNXDrive.Manager", 1),
+];
+
+/// Features clients can probe for through `Manager.GetCapabilities`
+pub const DAEMON_FEATURES: &[(&str, bool)] = &[
+    ("multi_account", false),
+    ("streaming_hydration", true),
+    ("hydration_signals", true),
+    ("webhooks", true),
+    ("cache_management", true),
+    ("conflict_quarantine", true),
+    ("lifecycle_control", true),
+];
+
 // ============================================================================
 // Daemon state shared with D-Bus interfaces
 // ============================================================================
@@ -59,12 +107,93 @@ impl std::fmt::Display for DaemonSyncState {
     }
 }
 
+/// A lifecycle action requested through the Manager interface
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LifecycleRequest {
+    /// Drain in-flight transfers and exit
+    Shutdown,
+    /// Drain, then replace the process with a fresh copy of the binary
+    Restart,
+    /// Apply the configuration file, which has been validated already
+    ReloadConfig,
+}
+
+/// Finished sync-by-path requests whose status is kept for polling
+pub const MAX_FINISHED_SYNC_PATH_REQUESTS: usize = 64;
+
+/// A queued request to sync a single path ahead of the regular cycle
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SyncPathRequest {
+    /// Identifier returned to the caller, used to poll the status
+    pub id: u64,
+    /// Absolute path of the file or folder to sync
+    pub path: String,
+}
+
+/// Progress of a sync-by-path request
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SyncPathStatus {
+    /// Waiting for the daemon to pick it up
+    Queued,
+    /// Being synced
+    Running,
+    /// Synced; holds the sync result summary as JSON
+    Completed(serde_json::Value),
+    /// The sync failed with this message
+    Failed(String),
+}
+
+impl SyncPathStatus {
+    /// Returns true once the request has completed or failed
+    pub fn is_finished(&self) -> bool {
+        matches!(self, SyncPathStatus::Completed(_) | SyncPathStatus::Failed(_))
+    }
+
+    fn to_json(&self, id: u64) -> serde_json::Value {
+        match self {
+            SyncPathStatus::Queued => serde_json::json!({"id": id, "state": "queued"}),
+            SyncPathStatus::Running => serde_json::json!({"id": id, "state": "running"}),
+            SyncPathStatus::Completed(result) => {
+                serde_json::json!({"id": id, "state": "completed", "result": result})
+            }
+            SyncPathStatus::Failed(error) => {
+                serde_json::json!({"id": id, "state": "failed", "error": error})
+            }
+        }
+    }
+}
+
+/// A `GetPendingPlan` reply is reused for this long, unless a sync ran
+/// meanwhile
+pub const PENDING_PLAN_MAX_AGE: Duration = Duration::from_secs(30);
+
+/// How long `GetPendingPlan` waits for the daemon, which plans between
+/// sync cycles
+pub const PENDING_PLAN_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Reply to a `GetPendingPlan` request: the plan preview as JSON, or the
+/// reason it could not be made
+pub type PendingPlanReply = Result<String, String>;
+
+/// Pages of the remote folder tree are reused for this long
+pub const REMOTE_FOLDER_TREE_TTL: Duration = Duration::from_secs(30);
+
+/// Deepest tree `GetRemoteFolderTree` expands in one call
+pub const REMOTE_FOLDER_TREE_MAX_DEPTH: u32 = 3;
+
+/// Cached folder listings, keyed by folder path and continuation
+pub type RemoteFolderCache = HashMap<(String, Option<String>), (Instant, FolderPage)>;
+
 /// Shared state between the daemon and D-Bus interfaces
 pub struct DaemonState {
     /// Current sync state
     pub sync_state: DaemonSyncState,
     /// Whether sync has been requested while paused
     pub sync_requested: bool,
+    /// Whether `Auth.Logout` asked the daemon to end its session
+    pub logout_requested: bool,
+    /// Id of the synced account (if authenticated)
+    pub account_id: Option<String>,
     /// Account email (if authenticated)
     pub account_email: Option<String>,
     /// Account display name (if authenticated)
@@ -79,19 +208,38 @@ pub struct DaemonState {
     /// Cached file statuses: absolute path → status string
     /// (synced, cloud-only, syncing, pending, conflict, error, excluded, unknown)
     pub file_statuses: HashMap<String, String>,
-    /// Queue of pin requests (absolute paths)
-    pub pin_requests: Vec<String>,
-    /// Queue of unpin requests (absolute paths)
-    pub unpin_requests: Vec<String>,
-    /// Queue of sync-by-path requests (absolute paths)
-    pub sync_path_requests: Vec<String>,
+    /// Queue of sync-by-path requests, oldest first
+    pub sync_path_requests: Vec<SyncPathRequest>,
+    /// Status of queued, running and recently finished sync-by-path requests
+    pub sync_path_statuses: HashMap<u64, SyncPathStatus>,
+    /// Identifier for the next sync-by-path request
+    pub next_sync_path_id: u64,
+    /// Wakes the daemon's sync loop when a request is queued
+    pub sync_wakeup: Arc<Notify>,
+    /// Pause switches for uploads and downloads, shared with the sync engine
+    pub transfer_control: Arc<TransferControl>,
 
     // -- Sync interface state --
 
     /// Unix timestamp of last completed sync (0 = never)
     pub last_sync_time: i64,
+    /// When the last sync cycle finished successfully, if ever
+    ///
+    /// Kept apart from `sync_history`, which may hold only failed cycles
+    /// once sync has stalled for long enough.
+    pub last_successful_sync: Option<DateTime<Utc>>,
     /// Number of pending file operations
     pub pending_changes: u32,
+    /// Recent sync cycles (newest first)
+    pub sync_history: Vec<SyncHistoryEntry>,
+    /// Callers of `GetPendingPlan` waiting for the daemon to plan
+    pub plan_requests: Vec<oneshot::Sender<PendingPlanReply>>,
+    /// Last plan preview made for `GetPendingPlan`, with the time it was
+    /// made; dropped once a sync runs
+    pub pending_plan: Option<(Instant, String)>,
+    /// Session counters reported by `GetStats` (None until the daemon
+    /// sets them up)
+    pub metrics: Option<Arc<MetricsRegistry>>,
 
     // -- Status interface state --
 
@@ -101,6 +249,8 @@ pub struct DaemonState {
     pub quota_used: u64,
     /// Storage quota total in bytes
     pub quota_total: u64,
+    /// Skew between the local clock and the cloud's, once measured
+    pub clock_skew: Option<ClockSkew>,
 
     // -- Auth interface state --
 
@@ -115,17 +265,35 @@ pub struct DaemonState {
 
     /// Full configuration as YAML string
     pub config_yaml: String,
-    /// Currently synced remote folders
+    /// Currently synced remote folders (empty = the whole drive)
     pub selected_folders: Vec<String>,
+    /// Folders set with `SetSelectedFolders`, waiting for the daemon to
+    /// apply them; a newer request replaces one not picked up yet
+    pub folder_selection_request: Option<Vec<String>>,
     /// File exclusion patterns (glob)
     pub exclusion_patterns: Vec<String>,
-    /// Remote folder tree as JSON string
-    pub remote_folder_tree: String,
+    /// Provider the remote folder tree is browsed with (None until
+    /// authenticated)
+    pub cloud_provider: Option<Arc<dyn ICloudProvider>>,
+    /// Recent pages of the remote folder tree
+    pub remote_folder_cache: RemoteFolderCache,
+
+    // -- Cache interface state --
+
+    /// Content cache of the mounted filesystem (None when not mounted)
+    pub cache_manager: Option<Arc<dyn ICacheManager>>,
 
     // -- Manager interface state --
 
     /// Daemon version string
     pub version: String,
+    /// When the daemon started
+    pub started_at: std::time::Instant,
+    /// Configuration file checked by `ReloadConfig`
+    pub config_path: PathBuf,
+    /// Carries `Shutdown`, `Restart` and `ReloadConfig` to the daemon
+    /// (None until the daemon sets it up)
+    pub lifecycle: Option<mpsc::UnboundedSender<LifecycleRequest>>,
     /// Whether the daemon is actively running
     pub is_running: bool,
 }
@@ -135,32 +303,192 @@ impl Default for DaemonState {
         Self {
             sync_state: DaemonSyncState::Idle,
             sync_requested: false,
+            logout_requested: false,
+            account_id: None,
             account_email: None,
             account_display_name: None,
             last_sync_result: None,
             conflicts_json: "[]".to_string(),
             file_statuses: HashMap::new(),
-            pin_requests: Vec::new(),
-            unpin_requests: Vec::new(),
             sync_path_requests: Vec::new(),
+            sync_path_statuses: HashMap::new(),
+            next_sync_path_id: 1,
+            sync_wakeup: Arc::new(Notify::new()),
+            transfer_control: Arc::new(TransferControl::new()),
             last_sync_time: 0,
+            last_successful_sync: None,
             pending_changes: 0,
+            sync_history: Vec::new(),
+            plan_requests: Vec::new(),
+            pending_plan: None,
+            metrics: None,
             connection_status: "online".to_string(),
             quota_used: 0,
             quota_total: 0,
+            clock_skew: None,
             is_authenticated: false,
             auth_url: None,
             auth_csrf_state: None,
             config_yaml: String::new(),
             selected_folders: Vec::new(),
+            folder_selection_request: None,
             exclusion_patterns: Vec::new(),
-            remote_folder_tree: "{}".to_string(),
+            cloud_provider: None,
+            remote_folder_cache: HashMap::new(),
+            cache_manager: None,
             version: env!("CARGO_PKG_VERSION").to_string(),
+            started_at: std::time::Instant::now(),
+            config_path: Config::default_path(),
+            lifecycle: None,
             is_running: true,
         }
     }
 }
 
+impl DaemonState {
+    /// Clears all state tied to the authenticated account
+    ///
+    /// Used on logout. File statuses are kept so placeholders remain
+    /// visible until the user logs in again.
+    pub fn reset_auth(&mut self) {
+        self.is_authenticated = false;
+        self.account_email = None;
+        self.account_display_name = None;
+        self.auth_url = None;
+        self.auth_csrf_state = None;
+        self.quota_used = 0;
+        self.quota_total = 0;
+        self.cloud_provider = None;
+        self.remote_folder_cache.clear();
+    }
+
+    /// Stores a refreshed storage quota, returning true if it changed
+    pub fn update_quota(&mut self, used: u64, total: u64) -> bool {
+        let changed = self.quota_used != used || self.quota_total != total;
+        self.quota_used = used;
+        self.quota_total = total;
+        changed
+    }
+
+    /// Queues a sync of `path` and wakes the sync loop
+    ///
+    /// Returns the request's identifier. A path that is already waiting
+    /// in the queue keeps its request and identifier.
+    pub fn queue_sync_path(&mut self, path: String) -> u64 {
+        if let Some(queued) = self.sync_path_requests.iter().find(|r| r.path == path) {
+            return queued.id;
+        }
+        let id = self.next_sync_path_id;
+        self.next_sync_path_id += 1;
+        self.sync_path_requests.push(SyncPathRequest { id, path });
+        self.sync_path_statuses.insert(id, SyncPathStatus::Queued);
+        self.update_sync_path_gauge();
+        self.sync_wakeup.notify_one();
+        id
+    }
+
+    /// Queues a change of the selected folders and wakes the sync loop
+    pub fn request_folder_selection(&mut self, folders: Vec<String>) {
+        self.folder_selection_request = Some(folders);
+        self.sync_wakeup.notify_one();
+    }
+
+    /// Takes the oldest queued sync-by-path request and marks it running
+    pub fn start_next_sync_path(&mut self) -> Option<SyncPathRequest> {
+        if self.sync_path_requests.is_empty() {
+            return None;
+        }
+        let request = self.sync_path_requests.remove(0);
+        self.sync_path_statuses
+            .insert(request.id, SyncPathStatus::Running);
+        self.update_sync_path_gauge();
+        Some(request)
+    }
+
+    /// Publishes the length of the sync-by-path queue to the metrics
+    fn update_sync_path_gauge(&self) {
+        if let Some(metrics) = &self.metrics {
+            metrics.set_queued_sync_paths(self.sync_path_requests.len());
+        }
+    }
+
+    /// Records the outcome of a sync-by-path request
+    ///
+    /// Only the newest [`MAX_FINISHED_SYNC_PATH_REQUESTS`] finished
+    /// requests are kept.
+    pub fn finish_sync_path(&mut self, id: u64, status: SyncPathStatus) {
+        self.pending_plan = None;
+        self.sync_path_statuses.insert(id, status);
+        let mut finished: Vec<u64> = self
+            .sync_path_statuses
+            .iter()
+            .filter(|(_, status)| status.is_finished())
+            .map(|(id, _)| *id)
+            .collect();
+        if finished.len() > MAX_FINISHED_SYNC_PATH_REQUESTS {
+            finished.sort_unstable();
+            let excess = finished.len() - MAX_FINISHED_SYNC_PATH_REQUESTS;
+            for id in &finished[..excess] {
+                self.sync_path_statuses.remove(id);
+            }
+        }
+    }
+
+    /// Records a finished sync cycle at the front of the in-memory history
+    ///
+    /// The history is capped at the same size the state repository retains.
+    /// Also updates `last_sync_time`, and `last_successful_sync` if the
+    /// cycle succeeded.
+    pub fn push_sync_history(&mut self, entry: SyncHistoryEntry) {
+        self.pending_plan = None;
+        self.last_sync_time = entry.finished_at.timestamp();
+        if entry.success {
+            self.last_successful_sync = Some(entry.finished_at);
+        }
+        self.sync_history.insert(0, entry);
+        self.sync_history.truncate(MAX_SYNC_HISTORY_ENTRIES as usize);
+    }
+
+    /// Returns the last plan preview if it is still current
+    pub fn cached_pending_plan(&self) -> Option<&str> {
+        self.pending_plan
+            .as_ref()
+            .filter(|(at, _)| at.elapsed() < PENDING_PLAN_MAX_AGE)
+            .map(|(_, json)| json.as_str())
+    }
+
+    /// Queues a `GetPendingPlan` request and wakes the sync loop
+    pub fn request_pending_plan(&mut self) -> oneshot::Receiver<PendingPlanReply> {
+        let (tx, rx) = oneshot::channel();
+        self.plan_requests.push(tx);
+        self.sync_wakeup.notify_one();
+        rx
+    }
+
+    /// Answers the queued `GetPendingPlan` requests, keeping a successful
+    /// reply for [`PENDING_PLAN_MAX_AGE`]
+    pub fn answer_plan_requests(&mut self, reply: PendingPlanReply) {
+        if let Ok(json) = &reply {
+            self.pending_plan = Some((Instant::now(), json.clone()));
+        }
+        for request in self.plan_requests.drain(..) {
+            let _ = request.send(reply.clone());
+        }
+    }
+
+    /// Seconds since the daemon started
+    pub fn uptime_secs(&self) -> u64 {
+        self.started_at.elapsed().as_secs()
+    }
+
+    /// Seconds between the last successful sync and `now`, or `None` if
+    /// no sync succeeded since the daemon started
+    pub fn seconds_since_last_sync(&self, now: DateTime<Utc>) -> Option<u64> {
+        self.last_successful_sync
+            .map(|at| (now - at).num_seconds().max(0) as u64)
+    }
+}
+
 // ============================================================================
 // T219-T220: SyncController interface
 // ============================================================================
@@ -223,6 +551,8 @@ impl SyncControllerInterface {
     /// - `state`: Current sync state (idle, syncing, paused, etc.)
     /// - `account_email`: Email of the authenticated account (if any)
     /// - `last_sync_result`: Summary of the last sync cycle (if any)
+    /// - `transfers_paused`: Whether all transfers are paused
+    /// - `paused_transfer_paths`: Paths whose transfers are paused
     async fn get_status(&self) -> String {
         let state = self.state.lock().await;
         let status = serde_json::json!({
@@ -230,6 +560,8 @@ impl SyncControllerInterface {
             "account_email": state.account_email,
             "account_display_name": state.account_display_name,
             "last_sync_result": state.last_sync_result,
+            "transfers_paused": state.transfer_control.all_paused(),
+            "paused_transfer_paths": state.transfer_control.paused_paths(),
         });
         status.to_string()
     }
@@ -280,11 +612,14 @@ impl AccountInterface {
     /// Returns account information as a JSON string
     ///
     /// The returned JSON contains:
+    /// - `id`: Id of the synced account, which clients compare with the
+    ///   account they act on
     /// - `email`: Account email address
     /// - `display_name`: Account display name
     async fn get_info(&self) -> String {
         let state = self.state.lock().await;
         let info = serde_json::json!({
+            "id": state.account_id,
             "email": state.account_email,
             "display_name": state.account_display_name,
         });
@@ -311,15 +646,81 @@ impl AccountInterface {
 /// using a specified strategy.
 pub struct ConflictsInterface {
     state: Arc<Mutex<DaemonState>>,
+    repository: Option<Arc<dyn IStateRepository + Send + Sync>>,
 }
 
 impl ConflictsInterface {
     /// Creates a new ConflictsInterface with the given shared state
     pub fn new(state: Arc<Mutex<DaemonState>>) -> Self {
-        Self { state }
+        Self {
+            state,
+            repository: None,
+        }
+    }
+
+    /// Attaches the state repository used by `ResolveAll`
+    ///
+    /// Without a repository, `ResolveAll` only resolves the conflicts
+    /// cached in `conflicts_json`.
+    pub fn with_repository(mut self, repository: Arc<dyn IStateRepository + Send + Sync>) -> Self {
+        self.repository = Some(repository);
+        self
+    }
+
+    /// Resolves every unresolved conflict in the repository matching `filter`
+    async fn resolve_all_in_repository(
+        repo: &Arc<dyn IStateRepository + Send + Sync>,
+        filter: &PathFilter,
+        resolution: &Resolution,
+    ) -> anyhow::Result<BatchResult> {
+        let sync_root = repo
+            .get_default_account()
+            .await?
+            .map(|account| account.sync_root().clone());
+
+        let mut items = Vec::new();
+        for conflict in repo.get_unresolved_conflicts().await? {
+            let path = match repo.get_item(conflict.item_id()).await? {
+                Some(item) => relative_to_root(item.local_path(), sync_root.as_ref()),
+                None => String::new(),
+            };
+            items.push(BatchItem { conflict, path });
+        }
+
+        let result = ConflictResolver::new()
+            .batch(items, filter, |item| async move {
+                let resolved = item
+                    .conflict
+                    .resolve(resolution.clone(), ResolutionSource::User);
+                repo.save_conflict(&resolved)
+                    .await
+                    .map_err(|e| e.to_string())?;
+
+                let entry = AuditEntry::new(AuditAction::ConflictResolved, AuditResult::success())
+                    .with_item_id(*resolved.item_id())
+                    .with_details(serde_json::json!({
+                        "path": item.path,
+                        "conflict_id": resolved.id().to_string(),
+                        "resolution": resolution.to_string(),
+                        "resolved_by": ResolutionSource::User.to_string(),
+                        "batch": true,
+                    }));
+                repo.save_audit(&entry).await.map_err(|e| e.to_string())
+            })
+            .await;
+
+        Ok(result)
     }
 }
 
+/// Returns `path` relative to the sync root, using `/` separators
+fn relative_to_root(path: &SyncPath, sync_root: Option<&SyncPath>) -> String {
+    sync_root
+        .and_then(|root| path.relative_to(root).ok())
+        .map(|rel| rel.to_string_lossy().replace('\\', "/"))
+        .unwrap_or_else(|| path.to_string())
+}
+
 #[zbus::interface(name = "com.enigmora.LNXDrive.Conflicts")]
 impl ConflictsInterface {
     /// Returns a JSON array of unresolved conflicts
@@ -408,42 +809,110 @@ impl ConflictsInterface {
         false
     }
 
-    /// Resolves all unresolved conflicts with the given strategy
+    /// Resolves every unresolved conflict matching a path filter
+    ///
+    /// Each conflict is resolved independently; failures are reported per
+    /// file and do not stop the batch. Every resolution is audited.
     ///
     /// # Arguments
+    /// * `filter` - Path prefix (`Photos/`) or glob (`*.docx`) relative to
+    ///   the sync root; an empty string matches every conflict
     /// * `strategy` - Resolution strategy: "keep_local", "keep_remote", or "keep_both"
     ///
     /// # Returns
-    /// The number of conflicts that were resolved
-    async fn resolve_all(&self, strategy: String) -> u32 {
-        let valid_strategies = ["keep_local", "keep_remote", "keep_both"];
-        if !valid_strategies.contains(&strategy.as_str()) {
-            warn!(
-                strategy = %strategy,
-                "Invalid conflict resolution strategy for resolve_all"
-            );
-            return 0;
-        }
+    /// A JSON `BatchResult` with `matched`, `resolved`, `failed` and
+    /// per-file `outcomes`, or `{"error": ...}` for invalid arguments.
+    async fn resolve_all(&self, filter: String, strategy: String) -> String {
+        let resolution = match strategy.parse::<Resolution>() {
+            Ok(Resolution::Manual) | Err(_) => {
+                warn!(
+                    strategy = %strategy,
+                    "Invalid conflict resolution strategy for resolve_all"
+                );
+                return serde_json::json!({
+                    "error": format!("invalid strategy '{}'", strategy),
+                })
+                .to_string();
+            }
+            Ok(resolution) => resolution,
+        };
+        let path_filter = match PathFilter::parse(&filter) {
+            Ok(path_filter) => path_filter,
+            Err(err) => {
+                return serde_json::json!({ "error": err.to_string() }).to_string();
+            }
+        };
+
+        let result = match &self.repository {
+            Some(repo) => {
+                match Self::resolve_all_in_repository(repo, &path_filter, &resolution).await {
+                    Ok(result) => result,
+                    Err(err) => {
+                        warn!(error = %err, "Failed to load conflicts for resolve_all");
+                        return serde_json::json!({ "error": err.to_string() }).to_string();
+                    }
+                }
+            }
+            None => {
+                // Without a repository only the cached conflicts are known
+                let state = self.state.lock().await;
+                let mut result = BatchResult::default();
+                if let Ok(serde_json::Value::Array(conflicts)) =
+                    serde_json::from_str::<serde_json::Value>(&state.conflicts_json)
+                {
+                    for conflict in &conflicts {
+                        let path = conflict.get("path").and_then(|v| v.as_str()).unwrap_or("");
+                        if !path_filter.matches(path) {
+                            continue;
+                        }
+                        result.matched += 1;
+                        result.resolved += 1;
+                        result.outcomes.push(BatchOutcome {
+                            conflict_id: conflict
+                                .get("id")
+                                .and_then(|v| v.as_str())
+                                .unwrap_or_default()
+                                .to_string(),
+                            path: path.to_string(),
+                            success: true,
+                            error: None,
+                        });
+                    }
+                }
+                result
+            }
+        };
 
+        // Drop the resolved conflicts from the cached list
+        let resolved_ids: Vec<&str> = result
+            .outcomes
+            .iter()
+            .filter(|o| o.success)
+            .map(|o| o.conflict_id.as_str())
+            .collect();
         let mut state = self.state.lock().await;
-        let count = if let Ok(serde_json::Value::Array(conflicts)) =
+        if let Ok(serde_json::Value::Array(mut conflicts)) =
             serde_json::from_str::<serde_json::Value>(&state.conflicts_json)
         {
-            conflicts.len() as u32
-        } else {
-            0
-        };
-
-        if count > 0 {
-            state.conflicts_json = "[]".to_string();
-            info!(
-                count = count,
-                strategy = %strategy,
-                "All conflicts resolved via D-Bus"
-            );
+            conflicts.retain(|c| {
+                c.get("id")
+                    .and_then(|v| v.as_str())
+                    .map(|cid| !resolved_ids.contains(&cid))
+                    .unwrap_or(true)
+            });
+            state.conflicts_json = serde_json::to_string(&conflicts).unwrap_or_default();
         }
 
-        count
+        info!(
+            filter = %filter,
+            strategy = %strategy,
+            matched = result.matched,
+            resolved = result.resolved,
+            failed = result.failed,
+            "Batch conflict resolution via D-Bus"
+        );
+
+        serde_json::to_string(&result).unwrap_or_else(|_| "{}".to_string())
     }
 
     /// Signal emitted when a new conflict is detected
@@ -473,15 +942,144 @@ impl ConflictsInterface {
 /// Connected to the daemon's shared state via an `Arc<Mutex<DaemonState>>`.
 pub struct FilesInterface {
     state: Arc<Mutex<DaemonState>>,
+    repository: Option<Arc<dyn IStateRepository + Send + Sync>>,
 }
 
 impl FilesInterface {
     /// Creates a new FilesInterface with the given shared state
     pub fn new(state: Arc<Mutex<DaemonState>>) -> Self {
-        Self { state }
+        Self {
+            state,
+            repository: None,
+        }
+    }
+
+    /// Attaches the state repository used by `GetStatus`
+    ///
+    /// Without a repository, `GetStatus` falls back to the cached
+    /// `file_statuses` map.
+    pub fn with_repository(mut self, repository: Arc<dyn IStateRepository + Send + Sync>) -> Self {
+        self.repository = Some(repository);
+        self
+    }
+
+    /// Builds the structured status for a path
+    async fn query_status(&self, path: &str) -> serde_json::Value {
+        let Some(repo) = &self.repository else {
+            let state = self.state.lock().await;
+            let cached = state.file_statuses.get(path).cloned();
+            return serde_json::json!({
+                "path": path,
+                "tracked": cached.is_some(),
+                "state": cached.unwrap_or_else(|| "unknown".to_string()),
+            });
+        };
+
+        let Ok(sync_path) = SyncPath::new(PathBuf::from(path)) else {
+            return untracked_status_json(path, "invalid_path");
+        };
+
+        let lookup = async {
+            if let Some(account) = repo.get_default_account().await? {
+                if !sync_path
+                    .as_path()
+                    .starts_with(account.sync_root().as_path())
+                {
+                    return Ok(None);
+                }
+            }
+            let item = repo.get_item_by_path(&sync_path).await?;
+            let conflict = match &item {
+                Some(item) => repo
+                    .get_unresolved_conflicts()
+                    .await?
+                    .into_iter()
+                    .find(|c| c.item_id() == item.id()),
+                None => None,
+            };
+            anyhow::Ok(Some((item, conflict)))
+        };
+
+        match lookup.await {
+            Ok(None) => untracked_status_json(path, "outside_sync_root"),
+            Ok(Some((None, _))) => untracked_status_json(path, "not_tracked"),
+            Ok(Some((Some(item), conflict))) => {
+                let mut status = file_status_json(&item, conflict.as_ref());
+                let control = Arc::clone(&self.state.lock().await.transfer_control);
+                status["transfer_paused"] = control.is_paused(sync_path.as_path()).into();
+                status
+            }
+            Err(e) => {
+                warn!(path = %path, error = %e, "Files.GetStatus lookup failed");
+                serde_json::json!({
+                    "path": path,
+                    "tracked": false,
+                    "reason": "lookup_failed",
+                    "message": e.to_string(),
+                })
+            }
+        }
     }
 }
 
+/// Status JSON for a path the repository has no item for
+fn untracked_status_json(path: &str, reason: &str) -> serde_json::Value {
+    serde_json::json!({
+        "path": path,
+        "tracked": false,
+        "state": "not_tracked",
+        "reason": reason,
+    })
+}
+
+/// Status JSON for a tracked item and its unresolved conflict (if any)
+fn file_status_json(item: &SyncItem, conflict: Option<&Conflict>) -> serde_json::Value {
+    let error = match (item.error_info(), item.state()) {
+        (Some(info), _) => serde_json::json!({
+            "code": info.code(),
+            "message": info.message(),
+            "retry_count": info.retry_count(),
+        }),
+        (None, ItemState::Error(reason)) => serde_json::json!({
+            "code": null,
+            "message": reason,
+            "retry_count": 0,
+        }),
+        _ => serde_json::Value::Null,
+    };
+
+    let conflict = conflict.map(|c| {
+        serde_json::json!({
+            "id": c.id().to_string(),
+            "detected_at": c.detected_at().to_rfc3339(),
+            "local_size": c.local_version().size_bytes(),
+            "local_modified": c.local_version().modified_at().to_rfc3339(),
+            "remote_size": c.remote_version().size_bytes(),
+            "remote_modified": c.remote_version().modified_at().to_rfc3339(),
+        })
+    });
+
+    serde_json::json!({
+        "path": item.local_path().to_string(),
+        "tracked": true,
+        "state": item.state().name().to_lowercase(),
+        "is_directory": item.is_directory(),
+        "size_bytes": item.size_bytes(),
+        "remote_id": item.remote_id().map(|r| r.to_string()),
+        "remote_path": item.remote_path().to_string(),
+        "content_hash": item.content_hash().map(|h| h.to_string()),
+        "local_hash": item.local_hash().map(|h| h.to_string()),
+        "hashes_match": item.hashes_match(),
+        "pinned": item.state().is_pinned(),
+        "last_modified_local": item.last_modified_local().map(|t| t.to_rfc3339()),
+        "last_modified_remote": item.last_modified_remote().map(|t| t.to_rfc3339()),
+        "last_sync": item.last_sync().map(|t| t.to_rfc3339()),
+        "hydration_progress": item.hydration_progress(),
+        "error": error,
+        "conflict": conflict,
+    })
+}
+
 #[zbus::interface(name = "com.enigmora.LNXDrive.Files")]
 impl FilesInterface {
     /// Returns the sync status of a single file
@@ -501,6 +1099,52 @@ impl FilesInterface {
             .unwrap_or_else(|| "unknown".to_string())
     }
 
+    /// Returns the full sync status of a path as a JSON object
+    ///
+    /// Unlike `GetFileStatus`, the result is read from the state repository
+    /// so it is always current. Tracked items include `state`, `size_bytes`,
+    /// local/remote modification times, hashes, `pinned`, `last_sync`,
+    /// `transfer_paused`, and `error`/`conflict` details. Paths the daemon does not track return
+    /// `{"tracked": false, "reason": ...}` where reason is one of
+    /// `outside_sync_root`, `not_tracked` or `invalid_path`.
+    async fn get_status(&self, path: String) -> String {
+        self.query_status(&path).await.to_string()
+    }
+
+    /// Returns a single emblem token for a path, optimized for polling
+    ///
+    /// Intended for file-manager extensions. The answer comes from the
+    /// local state database (one indexed lookup) or, without a repository,
+    /// from the cached status map; it never touches the network.
+    ///
+    /// # Returns
+    /// One of "synced", "cloud-only", "syncing", "conflict", "error",
+    /// or "unknown" for untracked paths
+    async fn get_emblem(&self, path: String) -> String {
+        if let Some(repo) = &self.repository {
+            let Ok(sync_path) = SyncPath::new(PathBuf::from(&path)) else {
+                return "unknown".to_string();
+            };
+            return match repo.get_item_by_path(&sync_path).await {
+                Ok(Some(item)) => item.state().emblem().to_string(),
+                Ok(None) => "unknown".to_string(),
+                Err(e) => {
+                    debug!(path = %path, error = %e, "Files.GetEmblem lookup failed");
+                    "unknown".to_string()
+                }
+            };
+        }
+
+        let state = self.state.lock().await;
+        match state.file_statuses.get(&path).map(String::as_str) {
+            Some(status @ ("synced" | "cloud-only" | "syncing" | "conflict" | "error")) => {
+                status.to_string()
+            }
+            Some("pending") => "syncing".to_string(),
+            _ => "unknown".to_string(),
+        }
+    }
+
     /// Returns sync statuses for multiple files in a single call
     ///
     /// # Arguments
@@ -523,41 +1167,146 @@ impl FilesInterface {
             .collect()
     }
 
-    /// Marks a file to keep available offline (pin + hydrate)
+    /// Keeps a file or folder available offline (pin + hydrate)
     ///
-    /// The request is queued and processed by the sync engine.
-    /// Duplicate requests for the same path are ignored.
+    /// Returns right away; the files are downloaded in the background and
+    /// each is announced with `Hydrated`. `PinAndWait` returns a report
+    /// instead. Ignored while the FUSE filesystem is not mounted.
     async fn pin_file(&self, path: String) {
-        let mut state = self.state.lock().await;
-        if !state.pin_requests.contains(&path) {
-            info!(path = %path, "Pin file requested via D-Bus");
-            state.pin_requests.push(path);
-        } else {
-            debug!(path = %path, "Pin request already queued, ignoring duplicate");
-        }
+        let Ok(manager) = mounted_cache_manager(&self.state).await else {
+            warn!(path = %path, "Pin requested while the filesystem is not mounted");
+            return;
+        };
+        info!(path = %path, "Pin file requested via D-Bus");
+        tokio::spawn(async move {
+            log_pin_outcome("Pin", &path, manager.pin(std::path::Path::new(&path)).await);
+        });
     }
 
-    /// Marks a file to free local space (unpin + dehydrate)
+    /// Releases the pin on a file or folder, letting it be dehydrated
     ///
-    /// The request is queued and processed by the sync engine.
-    /// Duplicate requests for the same path are ignored.
+    /// Returns right away, like `PinFile`. Ignored while the FUSE
+    /// filesystem is not mounted.
     async fn unpin_file(&self, path: String) {
+        let Ok(manager) = mounted_cache_manager(&self.state).await else {
+            warn!(path = %path, "Unpin requested while the filesystem is not mounted");
+            return;
+        };
+        info!(path = %path, "Unpin file requested via D-Bus");
+        tokio::spawn(async move {
+            log_pin_outcome(
+                "Unpin",
+                &path,
+                manager.unpin(std::path::Path::new(&path)).await,
+            );
+        });
+    }
+
+    /// Pins a file or folder and waits until its files are downloaded
+    ///
+    /// Returns the report (`files`, `failed`) as JSON. Pinning a large
+    /// folder takes as long as downloading it, so callers must not use the
+    /// default reply timeout. Fails while the FUSE filesystem is not
+    /// mounted.
+    async fn pin_and_wait(&self, path: String) -> zbus::fdo::Result<String> {
+        let manager = mounted_cache_manager(&self.state).await?;
+        info!(path = %path, "Pin requested via D-Bus");
+        cache_json(manager.pin(std::path::Path::new(&path)).await)
+    }
+
+    /// Unpins a file or folder, returning the report as JSON
+    ///
+    /// Fails while the FUSE filesystem is not mounted.
+    async fn unpin_and_wait(&self, path: String) -> zbus::fdo::Result<String> {
+        let manager = mounted_cache_manager(&self.state).await?;
+        info!(path = %path, "Unpin requested via D-Bus");
+        cache_json(manager.unpin(std::path::Path::new(&path)).await)
+    }
+
+    /// Forces immediate synchronization of a specific file or folder
+    ///
+    /// Local changes inside the path are uploaded and remote ones
+    /// downloaded, ahead of the next regular cycle. The request is queued
+    /// and the call returns its identifier; poll `GetSyncPathStatus` for
+    /// the outcome.
+    ///
+    /// # Errors
+    /// `InvalidArgs` if the path is not absolute or is outside the sync
+    /// root.
+    async fn sync_path(&self, path: String) -> zbus::fdo::Result<u64> {
+        let Ok(sync_path) = SyncPath::new(PathBuf::from(&path)) else {
+            return Err(zbus::fdo::Error::InvalidArgs(format!(
+                "Path must be absolute: {path}"
+            )));
+        };
+        if let Some(repo) = &self.repository {
+            if let Ok(Some(account)) = repo.get_default_account().await {
+                let sync_root = account.sync_root();
+                if !sync_path.as_path().starts_with(sync_root.as_path()) {
+                    return Err(zbus::fdo::Error::InvalidArgs(format!(
+                        "{path} is outside the sync root {sync_root}"
+                    )));
+                }
+            }
+        }
+
         let mut state = self.state.lock().await;
-        if !state.unpin_requests.contains(&path) {
-            info!(path = %path, "Unpin file requested via D-Bus");
-            state.unpin_requests.push(path);
-        } else {
-            debug!(path = %path, "Unpin request already queued, ignoring duplicate");
+        let id = state.queue_sync_path(path.clone());
+        info!(path = %path, id, "Sync path requested via D-Bus");
+        Ok(id)
+    }
+
+    /// Returns the status of a `SyncPath` request as a JSON string
+    ///
+    /// The JSON contains `id` and `state` (queued, running, completed,
+    /// failed, or unknown for ids that are not, or no longer, known),
+    /// plus `result` with the sync summary when completed and `error`
+    /// when failed.
+    async fn get_sync_path_status(&self, id: u64) -> String {
+        let state = self.state.lock().await;
+        state
+            .sync_path_statuses
+            .get(&id)
+            .map_or_else(
+                || serde_json::json!({"id": id, "state": "unknown"}),
+                |status| status.to_json(id),
+            )
+            .to_string()
+    }
+
+    /// Pauses the upload or download of a file, or of everything below a
+    /// folder
+    ///
+    /// A transfer in progress stops within a moment; chunked uploads
+    /// continue from their last accepted chunk after `ResumeTransfer`.
+    /// Returns false if the path was already paused.
+    ///
+    /// # Errors
+    /// `InvalidArgs` if the path is not absolute.
+    async fn pause_transfer(&self, path: String) -> zbus::fdo::Result<bool> {
+        if SyncPath::new(PathBuf::from(&path)).is_err() {
+            return Err(zbus::fdo::Error::InvalidArgs(format!(
+                "Path must be absolute: {path}"
+            )));
         }
+        let state = self.state.lock().await;
+        let paused = state.transfer_control.pause_path(&path);
+        info!(path = %path, "Transfer paused via D-Bus");
+        Ok(paused)
     }
 
-    /// Forces immediate synchronization of a specific path
+    /// Resumes transfers of a path paused with `PauseTransfer`
     ///
-    /// The request is queued and processed by the sync engine.
-    async fn sync_path(&self, path: String) {
-        let mut state = self.state.lock().await;
-        info!(path = %path, "Sync path requested via D-Bus");
-        state.sync_path_requests.push(path);
+    /// Returns false if the path was not paused.
+    async fn resume_transfer(&self, path: String) -> bool {
+        let state = self.state.lock().await;
+        let resumed = state.transfer_control.resume_path(&path);
+        if resumed {
+            info!(path = %path, "Transfer resumed via D-Bus");
+            // Pick the transfer up without waiting for the next cycle
+            state.sync_wakeup.notify_one();
+        }
+        resumed
     }
 
     /// Returns a list of file paths that are in conflict state
@@ -578,6 +1327,23 @@ impl FilesInterface {
         path: &str,
         status: &str,
     ) -> zbus::Result<()>;
+
+    /// Emitted when the content of a file has been downloaded
+    #[zbus(signal)]
+    async fn hydrated(signal_ctxt: &zbus::SignalContext<'_>, path: &str) -> zbus::Result<()>;
+
+    /// Emitted when the local content of a file was removed, leaving it
+    /// cloud-only
+    #[zbus(signal)]
+    async fn dehydrated(signal_ctxt: &zbus::SignalContext<'_>, path: &str) -> zbus::Result<()>;
+
+    /// Emitted while a file is being downloaded, at most every 250 ms
+    #[zbus(signal)]
+    async fn hydration_progress(
+        signal_ctxt: &zbus::SignalContext<'_>,
+        path: &str,
+        percent: u8,
+    ) -> zbus::Result<()>;
 }
 
 // ============================================================================
@@ -637,6 +1403,56 @@ impl SyncInterface {
         }
     }
 
+    /// Pause all uploads and downloads
+    ///
+    /// Unlike `Pause`, sync cycles keep running: renames, deletes and
+    /// folders are still synced, but transfers in progress stop within a
+    /// moment and new ones are not started. Chunked uploads continue from
+    /// their last accepted chunk after `ResumeTransfers`.
+    async fn pause_transfers(&self) {
+        let state = self.state.lock().await;
+        if state.transfer_control.all_paused() {
+            debug!("Sync.PauseTransfers called but transfers are already paused");
+        } else {
+            info!("Sync.PauseTransfers called, pausing transfers");
+            state.transfer_control.pause_all();
+        }
+    }
+
+    /// Resume transfers paused with `PauseTransfers`
+    ///
+    /// Paths paused with `Files.PauseTransfer` stay paused.
+    async fn resume_transfers(&self) {
+        let state = self.state.lock().await;
+        if state.transfer_control.all_paused() {
+            info!("Sync.ResumeTransfers called, resuming transfers");
+            state.transfer_control.resume_all();
+            state.sync_wakeup.notify_one();
+        } else {
+            debug!("Sync.ResumeTransfers called but transfers are not paused");
+        }
+    }
+
+    /// Returns the paused transfers as a JSON object
+    ///
+    /// `all` is true after `PauseTransfers`; `paths` lists the files and
+    /// folders paused with `Files.PauseTransfer`.
+    async fn get_paused_transfers(&self) -> String {
+        let state = self.state.lock().await;
+        serde_json::json!({
+            "all": state.transfer_control.all_paused(),
+            "paths": state.transfer_control.paused_paths(),
+        })
+        .to_string()
+    }
+
+    /// Whether all transfers are paused
+    #[zbus(property)]
+    async fn transfers_paused(&self) -> bool {
+        let state = self.state.lock().await;
+        state.transfer_control.all_paused()
+    }
+
     /// Global sync state: idle, syncing, paused, error
     #[zbus(property)]
     async fn sync_status(&self) -> String {
@@ -657,6 +1473,24 @@ impl SyncInterface {
         state.last_sync_time
     }
 
+    /// Seconds since the last successful sync cycle (-1 = none yet)
+    ///
+    /// Lets monitoring notice a sync that stalled without reporting
+    /// errors.
+    #[zbus(property)]
+    async fn seconds_since_last_sync(&self) -> i64 {
+        let state = self.state.lock().await;
+        state
+            .seconds_since_last_sync(Utc::now())
+            .map_or(-1, |secs| secs as i64)
+    }
+
+    /// Seconds since the daemon started
+    #[zbus(property)]
+    async fn uptime_secs(&self) -> u64 {
+        self.state.lock().await.uptime_secs()
+    }
+
     /// Number of pending file operations
     #[zbus(property)]
     async fn pending_changes(&self) -> u32 {
@@ -664,6 +1498,64 @@ impl SyncInterface {
         state.pending_changes
     }
 
+    /// Returns the session statistics as JSON
+    ///
+    /// Bytes and files transferred, current throughput, throttled
+    /// requests, cache hits and misses and queue depths since the daemon
+    /// started.
+    async fn get_stats(&self) -> zbus::fdo::Result<String> {
+        let metrics = self.state.lock().await.metrics.clone();
+        let metrics = metrics
+            .ok_or_else(|| zbus::fdo::Error::Failed("Statistics are not available".to_string()))?;
+        serde_json::to_string(&metrics.snapshot())
+            .map_err(|e| zbus::fdo::Error::Failed(e.to_string()))
+    }
+
+    /// Returns the changes the next sync cycle would apply, as JSON,
+    /// without applying them
+    ///
+    /// `summary` counts the operations by kind (downloads, uploads,
+    /// deletes, create_dirs, moves, conflicts, skipped) and the bytes to
+    /// transfer; `operations` lists them in order, each with its `op`,
+    /// local `path` and details such as `size` or a skip `reason`. The
+    /// plan comes from the incremental delta and the local changes since
+    /// the last sync, and is reused for a short while unless a sync runs.
+    async fn get_pending_plan(&self) -> zbus::fdo::Result<String> {
+        let reply = {
+            let mut state = self.state.lock().await;
+            if let Some(json) = state.cached_pending_plan() {
+                return Ok(json.to_string());
+            }
+            state.request_pending_plan()
+        };
+        match tokio::time::timeout(PENDING_PLAN_TIMEOUT, reply).await {
+            Ok(Ok(Ok(json))) => Ok(json),
+            Ok(Ok(Err(msg))) => Err(zbus::fdo::Error::Failed(msg)),
+            Ok(Err(_)) => Err(zbus::fdo::Error::Failed(
+                "The daemon stopped before planning".to_string(),
+            )),
+            Err(_) => Err(zbus::fdo::Error::Failed(
+                "Timed out waiting for the running sync cycle to finish".to_string(),
+            )),
+        }
+    }
+
+    /// Returns the most recent sync cycles as a JSON array (newest first)
+    ///
+    /// Each entry contains the cycle's start/finish times, file counts,
+    /// bytes transferred, errors, duration and success flag. A `limit`
+    /// of 0 returns the full retained history.
+    async fn get_history(&self, limit: u32) -> String {
+        let state = self.state.lock().await;
+        let take = if limit == 0 {
+            state.sync_history.len()
+        } else {
+            limit as usize
+        };
+        let entries: Vec<&SyncHistoryEntry> = state.sync_history.iter().take(take).collect();
+        serde_json::to_string(&entries).unwrap_or_else(|_| "[]".to_string())
+    }
+
     /// Emitted when a sync cycle begins
     #[zbus(signal)]
     async fn sync_started(signal_ctxt: &zbus::SignalContext<'_>) -> zbus::Result<()>;
@@ -692,6 +1584,163 @@ impl SyncInterface {
         path: &str,
         conflict_type: &str,
     ) -> zbus::Result<()>;
+
+    /// Emitted (throttled) while a large file is uploaded or downloaded
+    ///
+    /// `kind` is "upload" or "download". `eta_secs` estimates the seconds
+    /// left from the throughput of the last few seconds (-1 = not known
+    /// yet).
+    #[zbus(signal)]
+    async fn file_progress(
+        signal_ctxt: &zbus::SignalContext<'_>,
+        path: &str,
+        kind: &str,
+        bytes_done: u64,
+        bytes_total: u64,
+        eta_secs: i64,
+    ) -> zbus::Result<()>;
+
+    /// Emitted when a file transfer finishes
+    ///
+    /// `result` is "success" or the error message of the failed transfer.
+    #[zbus(signal)]
+    async fn file_complete(
+        signal_ctxt: &zbus::SignalContext<'_>,
+        path: &str,
+        kind: &str,
+        result: &str,
+    ) -> zbus::Result<()>;
+}
+
+// ============================================================================
+// Transfer progress forwarding
+// ============================================================================
+
+/// Forwards transfer events as `Sync.FileProgress` / `Sync.FileComplete` signals
+///
+/// Events are queued on a channel and emitted from a background task, so
+/// transfer loops never wait on the bus.
+pub struct DbusTransferObserver {
+    tx: mpsc::UnboundedSender<TransferEvent>,
+}
+
+impl DbusTransferObserver {
+    /// Spawns the forwarding task on the current runtime
+    pub fn spawn(connection: &zbus::Connection) -> Self {
+        let (tx, mut rx) = mpsc::unbounded_channel::<TransferEvent>();
+        let connection = connection.clone();
+        tokio::spawn(async move {
+            let iface = match connection
+                .object_server()
+                .interface::<_, SyncInterface>(DBUS_PATH)
+                .await
+            {
+                Ok(iface) => iface,
+                Err(e) => {
+                    warn!(error = %e, "Sync interface not registered, transfer signals disabled");
+                    return;
+                }
+            };
+            while let Some(event) = rx.recv().await {
+                let kind = event.kind().to_string();
+                let emitted = match &event {
+                    TransferEvent::Progress {
+                        path,
+                        bytes_done,
+                        bytes_total,
+                        eta_secs,
+                        ..
+                    } => {
+                        SyncInterface::file_progress(
+                            iface.signal_context(),
+                            path,
+                            &kind,
+                            *bytes_done,
+                            *bytes_total,
+                            eta_secs.map_or(-1, |secs| secs as i64),
+                        )
+                        .await
+                    }
+                    TransferEvent::Complete { path, error, .. } => {
+                        SyncInterface::file_complete(
+                            iface.signal_context(),
+                            path,
+                            &kind,
+                            transfer_result(error.as_deref()),
+                        )
+                        .await
+                    }
+                };
+                if let Err(e) = emitted {
+                    debug!(error = %e, path = event.path(), "Failed to emit transfer signal");
+                }
+            }
+        });
+        Self { tx }
+    }
+}
+
+impl ITransferObserver for DbusTransferObserver {
+    fn on_transfer_event(&self, event: TransferEvent) {
+        let _ = self.tx.send(event);
+    }
+}
+
+/// Maps a transfer outcome to the `result` argument of `FileComplete`
+fn transfer_result(error: Option<&str>) -> &str {
+    error.unwrap_or("success")
+}
+
+/// Forwards hydration events as `Files.Hydrated` / `Files.Dehydrated` /
+/// `Files.HydrationProgress` signals
+///
+/// Like [`DbusTransferObserver`], events are emitted from a background
+/// task so downloads never wait on the bus.
+pub struct DbusHydrationObserver {
+    tx: mpsc::UnboundedSender<HydrationEvent>,
+}
+
+impl DbusHydrationObserver {
+    /// Spawns the forwarding task on the current runtime
+    pub fn spawn(connection: &zbus::Connection) -> Self {
+        let (tx, mut rx) = mpsc::unbounded_channel::<HydrationEvent>();
+        let connection = connection.clone();
+        tokio::spawn(async move {
+            let iface = match connection
+                .object_server()
+                .interface::<_, FilesInterface>(DBUS_PATH)
+                .await
+            {
+                Ok(iface) => iface,
+                Err(e) => {
+                    warn!(error = %e, "Files interface not registered, hydration signals disabled");
+                    return;
+                }
+            };
+            while let Some(event) = rx.recv().await {
+                let ctxt = iface.signal_context();
+                let emitted = match &event {
+                    HydrationEvent::Progress { path, percent } => {
+                        FilesInterface::hydration_progress(ctxt, path, *percent).await
+                    }
+                    HydrationEvent::Hydrated { path } => FilesInterface::hydrated(ctxt, path).await,
+                    HydrationEvent::Dehydrated { path } => {
+                        FilesInterface::dehydrated(ctxt, path).await
+                    }
+                };
+                if let Err(e) = emitted {
+                    debug!(error = %e, path = event.path(), "Failed to emit hydration signal");
+                }
+            }
+        });
+        Self { tx }
+    }
+}
+
+impl IHydrationObserver for DbusHydrationObserver {
+    fn on_hydration_event(&self, event: HydrationEvent) {
+        let _ = self.tx.send(event);
+    }
 }
 
 // ============================================================================
@@ -710,6 +1759,19 @@ impl StatusInterface {
     pub fn new(state: Arc<Mutex<DaemonState>>) -> Self {
         Self { state }
     }
+
+    /// Emits `QuotaChanged` from the Status interface served on `connection`
+    pub async fn emit_quota_changed(
+        connection: &zbus::Connection,
+        used: u64,
+        total: u64,
+    ) -> zbus::Result<()> {
+        let iface = connection
+            .object_server()
+            .interface::<_, StatusInterface>(DBUS_PATH)
+            .await?;
+        Self::quota_changed(iface.signal_context(), used, total).await
+    }
 }
 
 #[zbus::interface(name = "com.enigmora.LNXDrive.Status")]
@@ -722,14 +1784,16 @@ impl StatusInterface {
 
     /// Returns account details as a variant dictionary
     ///
-    /// Keys: "email" (s), "display_name" (s), "provider" (s)
+    /// Keys: "id" (s), "email" (s), "display_name" (s), "provider" (s)
     async fn get_account_info(&self) -> HashMap<String, OwnedValue> {
         let state = self.state.lock().await;
         let mut info = HashMap::new();
 
+        let id = state.account_id.clone().unwrap_or_default();
         let email = state.account_email.clone().unwrap_or_default();
         let name = state.account_display_name.clone().unwrap_or_default();
 
+        info.insert("id".to_string(), Value::from(id).try_to_owned().unwrap());
         info.insert(
             "email".to_string(),
             Value::from(email).try_to_owned().unwrap(),
@@ -753,6 +1817,14 @@ impl StatusInterface {
         state.connection_status.clone()
     }
 
+    /// Seconds the local clock is ahead of the cloud's (negative: behind),
+    /// 0 until measured
+    #[zbus(property)]
+    async fn clock_skew(&self) -> i64 {
+        let state = self.state.lock().await;
+        state.clock_skew.map_or(0, |skew| skew.seconds)
+    }
+
     /// Emitted when storage quota changes
     #[zbus(signal)]
     async fn quota_changed(
@@ -842,15 +1914,16 @@ impl AuthInterface {
         state.is_authenticated
     }
 
-    /// Removes account credentials and tokens
+    /// Drops the account's session and the tokens held in memory
+    ///
+    /// The sync loop is woken so it stops using the old tokens right
+    /// away. Deleting the stored tokens is left to the caller.
     async fn logout(&self) {
         let mut state = self.state.lock().await;
         info!("Auth.Logout called");
-        state.is_authenticated = false;
-        state.account_email = None;
-        state.account_display_name = None;
-        state.auth_url = None;
-        state.auth_csrf_state = None;
+        state.reset_auth();
+        state.logout_requested = true;
+        state.sync_wakeup.notify_one();
     }
 
     /// Emitted when authentication state changes
@@ -877,6 +1950,144 @@ impl SettingsInterface {
     pub fn new(state: Arc<Mutex<DaemonState>>) -> Self {
         Self { state }
     }
+
+    /// Emits `SelectionProgress` from the Settings interface served on
+    /// `connection`
+    pub async fn emit_selection_progress(
+        connection: &zbus::Connection,
+        phase: &str,
+        folder: &str,
+        done: u64,
+        total: u64,
+    ) -> zbus::Result<()> {
+        let iface = connection
+            .object_server()
+            .interface::<_, SettingsInterface>(DBUS_PATH)
+            .await?;
+        Self::selection_progress(iface.signal_context(), phase, folder, done, total).await
+    }
+
+    /// Emits `SelectionApplied` from the Settings interface served on
+    /// `connection`
+    pub async fn emit_selection_applied(
+        connection: &zbus::Connection,
+        success: bool,
+        added: u64,
+        removed: u64,
+        error: &str,
+    ) -> zbus::Result<()> {
+        let iface = connection
+            .object_server()
+            .interface::<_, SettingsInterface>(DBUS_PATH)
+            .await?;
+        Self::selection_applied(iface.signal_context(), success, added, removed, error).await
+    }
+
+    /// Builds the reply of `GetRemoteFolderTree` and
+    /// `ContinueRemoteFolderTree`
+    async fn remote_folder_tree(
+        &self,
+        path: String,
+        continuation: Option<String>,
+        depth: u32,
+    ) -> zbus::fdo::Result<String> {
+        if !(1..=REMOTE_FOLDER_TREE_MAX_DEPTH).contains(&depth) {
+            return Err(zbus::fdo::Error::InvalidArgs(format!(
+                "Depth must be between 1 and {REMOTE_FOLDER_TREE_MAX_DEPTH}"
+            )));
+        }
+        let path =
+            RemotePath::new(path).map_err(|e| zbus::fdo::Error::InvalidArgs(e.to_string()))?;
+        let provider = self
+            .state
+            .lock()
+            .await
+            .cloud_provider
+            .clone()
+            .ok_or_else(|| zbus::fdo::Error::Failed("Not authenticated".to_string()))?;
+
+        debug!(%path, depth, "Settings.GetRemoteFolderTree called");
+        let node = folder_node(&self.state, provider.as_ref(), path, continuation, depth)
+            .await
+            .map_err(|e| zbus::fdo::Error::Failed(format!("{e:#}")))?;
+        Ok(node.to_string())
+    }
+}
+
+/// Lists the folders of `path` as a tree node, expanding `depth` levels
+///
+/// Boxed because it recurses into the sub-folders.
+fn folder_node<'a>(
+    state: &'a Arc<Mutex<DaemonState>>,
+    provider: &'a dyn ICloudProvider,
+    path: RemotePath,
+    continuation: Option<String>,
+    depth: u32,
+) -> std::pin::Pin<
+    Box<dyn std::future::Future<Output = anyhow::Result<serde_json::Value>> + Send + 'a>,
+> {
+    Box::pin(async move {
+        let page = folder_page(state, provider, &path, continuation).await?;
+
+        let mut folders = Vec::with_capacity(page.folders.len());
+        for folder in page.folders {
+            let child_path = match path.as_str() {
+                "/" => format!("/{}", folder.name),
+                parent => format!("{}/{}", parent.trim_end_matches('/'), folder.name),
+            };
+            let mut child = if depth > 1 && folder.child_count > 0 {
+                folder_node(
+                    state,
+                    provider,
+                    RemotePath::new(child_path.clone())?,
+                    None,
+                    depth - 1,
+                )
+                .await?
+            } else if depth > 1 {
+                serde_json::json!({"folders": [], "continuation": null})
+            } else {
+                serde_json::json!({})
+            };
+            child["name"] = folder.name.into();
+            child["path"] = child_path.into();
+            child["child_count"] = folder.child_count.into();
+            folders.push(child);
+        }
+
+        Ok(serde_json::json!({
+            "path": path.as_str(),
+            "folders": folders,
+            "continuation": page.continuation,
+        }))
+    })
+}
+
+/// Returns a page of the folders of `path`, from the cache if it is
+/// recent enough
+async fn folder_page(
+    state: &Arc<Mutex<DaemonState>>,
+    provider: &dyn ICloudProvider,
+    path: &RemotePath,
+    continuation: Option<String>,
+) -> anyhow::Result<FolderPage> {
+    let key = (path.as_str().to_string(), continuation);
+    {
+        let mut state = state.lock().await;
+        let cache = &mut state.remote_folder_cache;
+        cache.retain(|_, (fetched, _)| fetched.elapsed() < REMOTE_FOLDER_TREE_TTL);
+        if let Some((_, page)) = cache.get(&key) {
+            return Ok(page.clone());
+        }
+    }
+
+    let page = provider.list_folders(path, key.1.as_deref()).await?;
+    state
+        .lock()
+        .await
+        .remote_folder_cache
+        .insert(key, (Instant::now(), page.clone()));
+    Ok(page)
 }
 
 #[zbus::interface(name = "com.enigmora.LNXDrive.Settings")]
@@ -902,16 +2113,24 @@ impl SettingsInterface {
     }
 
     /// Returns the list of currently synced remote folders
+    ///
+    /// Empty when the whole drive is synced.
     async fn get_selected_folders(&self) -> Vec<String> {
         let state = self.state.lock().await;
         state.selected_folders.clone()
     }
 
-    /// Updates the selective sync folder list
+    /// Changes the selective sync folder list
+    ///
+    /// The daemon applies the change in the background: newly selected
+    /// folders get placeholders, deselected ones are removed locally (not
+    /// in the cloud). `SelectionProgress` reports the progress and
+    /// `SelectionApplied` the outcome; `GetSelectedFolders` returns the
+    /// new list once it was applied.
     async fn set_selected_folders(&self, folders: Vec<String>) {
         let mut state = self.state.lock().await;
         info!(count = folders.len(), "Settings.SetSelectedFolders called");
-        state.selected_folders = folders;
+        state.request_folder_selection(folders);
     }
 
     /// Returns the current file exclusion patterns
@@ -927,12 +2146,71 @@ impl SettingsInterface {
         state.exclusion_patterns = patterns;
     }
 
-    /// Returns a JSON tree of remote folders for the selective sync UI
-    async fn get_remote_folder_tree(&self) -> String {
-        let state = self.state.lock().await;
-        state.remote_folder_tree.clone()
+    /// Returns the remote folders below `path` as JSON, for the selective
+    /// sync UI
+    ///
+    /// Expands `depth` levels (1 to [`REMOTE_FOLDER_TREE_MAX_DEPTH`]), so
+    /// the UI can fetch deeper levels on demand. Each level lists one page
+    /// of folders; a folder with more has a `continuation` to pass to
+    /// `ContinueRemoteFolderTree`:
+    ///
+    /// ```json
+    /// {"path": "/", "folders": [
+    ///     {"name": "Documents", "path": "/Documents", "child_count": 4,
+    ///      "folders": [], "continuation": null}
+    /// ], "continuation": null}
+    /// ```
+    ///
+    /// `folders` is left out of folders below the requested depth.
+    async fn get_remote_folder_tree(&self, path: String, depth: u32) -> zbus::fdo::Result<String> {
+        self.remote_folder_tree(path, None, depth).await
     }
 
+    /// Returns the next page of the folders of `path`, in the format of
+    /// `GetRemoteFolderTree`
+    ///
+    /// # Arguments
+    /// * `path` - The folder the continuation was returned for
+    /// * `continuation` - The `continuation` of the previous page
+    /// * `depth` - Levels to expand, as for `GetRemoteFolderTree`
+    async fn continue_remote_folder_tree(
+        &self,
+        path: String,
+        continuation: String,
+        depth: u32,
+    ) -> zbus::fdo::Result<String> {
+        self.remote_folder_tree(path, Some(continuation), depth)
+            .await
+    }
+
+    /// Emitted while a new folder selection is applied
+    ///
+    /// `phase` is `listing` while a newly selected `folder` is listed in
+    /// the cloud (`done` placeholders found so far, `total` 0), or
+    /// `removing` while the items of deselected folders are removed
+    /// (`done` of `total`).
+    #[zbus(signal)]
+    async fn selection_progress(
+        signal_ctxt: &zbus::SignalContext<'_>,
+        phase: &str,
+        folder: &str,
+        done: u64,
+        total: u64,
+    ) -> zbus::Result<()>;
+
+    /// Emitted once a folder selection was applied, or failed
+    ///
+    /// On failure nothing was changed, the previous selection stays in
+    /// effect and `error` tells why.
+    #[zbus(signal)]
+    async fn selection_applied(
+        signal_ctxt: &zbus::SignalContext<'_>,
+        success: bool,
+        added: u64,
+        removed: u64,
+        error: &str,
+    ) -> zbus::Result<()>;
+
     /// Emitted when any configuration value changes
     #[zbus(signal)]
     async fn config_changed(
@@ -941,14 +2219,102 @@ impl SettingsInterface {
     ) -> zbus::Result<()>;
 }
 
+// ============================================================================
+// Cache interface (com.enigmora.LNXDrive.Cache)
+// ============================================================================
+
+/// D-Bus interface for the content cache of the mounted filesystem
+///
+/// Operations run inside the daemon so eviction respects open files and
+/// in-flight hydrations. Results are returned as JSON. All methods fail
+/// while the FUSE filesystem is not mounted.
+pub struct CacheInterface {
+    state: Arc<Mutex<DaemonState>>,
+}
+
+impl CacheInterface {
+    pub fn new(state: Arc<Mutex<DaemonState>>) -> Self {
+        Self { state }
+    }
+
+    async fn manager(&self) -> zbus::fdo::Result<Arc<dyn ICacheManager>> {
+        mounted_cache_manager(&self.state).await
+    }
+}
+
+/// Returns the cache manager of the mounted FUSE filesystem
+async fn mounted_cache_manager(
+    state: &Mutex<DaemonState>,
+) -> zbus::fdo::Result<Arc<dyn ICacheManager>> {
+    state.lock().await.cache_manager.clone().ok_or_else(|| {
+        zbus::fdo::Error::Failed("The FUSE filesystem is not mounted".to_string())
+    })
+}
+
+/// Logs how a background `PinFile` or `UnpinFile` request ended
+fn log_pin_outcome(operation: &str, path: &str, outcome: anyhow::Result<CachePinReport>) {
+    match outcome {
+        Ok(report) if report.failed > 0 => warn!(
+            path,
+            files = report.files,
+            failed = report.failed,
+            "{operation} finished with failures"
+        ),
+        Ok(report) => info!(path, files = report.files, "{operation} finished"),
+        Err(e) => warn!(path, error = %format!("{e:#}"), "{operation} failed"),
+    }
+}
+
+/// Serializes a cache report, mapping an operation error to a D-Bus error
+fn cache_json<T: serde::Serialize>(result: anyhow::Result<T>) -> zbus::fdo::Result<String> {
+    let value = result.map_err(|e| zbus::fdo::Error::Failed(e.to_string()))?;
+    serde_json::to_string(&value).map_err(|e| zbus::fdo::Error::Failed(e.to_string()))
+}
+
+#[zbus::interface(name = "com.enigmora.LNXDrive.Cache")]
+impl CacheInterface {
+    /// Returns cache usage as JSON: total, pinned, evictable and modified
+    /// bytes and a per top-level folder breakdown
+    async fn get_status(&self) -> zbus::fdo::Result<String> {
+        let manager = self.manager().await?;
+        cache_json(manager.usage().await)
+    }
+
+    /// Dehydrates cached files and returns the report as JSON
+    ///
+    /// `older_than_days` limits cleaning to files not accessed for that
+    /// many days (0 = any). Pinned files are unpinned and dehydrated
+    /// unless `keep_pinned` is set.
+    async fn clean(&self, keep_pinned: bool, older_than_days: u32) -> zbus::fdo::Result<String> {
+        let manager = self.manager().await?;
+        info!(keep_pinned, older_than_days, "Cache.Clean called");
+        cache_json(
+            manager
+                .clean(CacheCleanOptions {
+                    keep_pinned,
+                    older_than_days,
+                })
+                .await,
+        )
+    }
+
+    /// Verifies every cached file, removes damaged ones and returns the
+    /// report as JSON
+    async fn verify(&self) -> zbus::fdo::Result<String> {
+        let manager = self.manager().await?;
+        info!("Cache.Verify called");
+        cache_json(manager.verify().await)
+    }
+}
+
 // ============================================================================
 // Manager interface (com.enigmora.LNXDrive.Manager)
 // ============================================================================
 
 /// D-Bus interface for daemon lifecycle management
 ///
-/// Provides methods to start/stop/restart the daemon and
-/// read-only properties for version and running state.
+/// Provides methods to start/stop/restart the daemon, report the API
+/// capabilities and read-only properties for version and running state.
 pub struct ManagerInterface {
     state: Arc<Mutex<DaemonState>>,
 }
@@ -957,6 +2323,21 @@ impl ManagerInterface {
     pub fn new(state: Arc<Mutex<DaemonState>>) -> Self {
         Self { state }
     }
+
+    /// Hands `request` to the daemon's lifecycle handler
+    async fn send_lifecycle(&self, request: LifecycleRequest) -> zbus::fdo::Result<()> {
+        let state = self.state.lock().await;
+        let sent = state
+            .lifecycle
+            .as_ref()
+            .is_some_and(|lifecycle| lifecycle.send(request).is_ok());
+        if !sent {
+            return Err(zbus::fdo::Error::Failed(
+                "Lifecycle control is not available".to_string(),
+            ));
+        }
+        Ok(())
+    }
 }
 
 #[zbus::interface(name = "com.enigmora.LNXDrive.Manager")]
@@ -976,17 +2357,89 @@ impl ManagerInterface {
     }
 
     /// Restarts the daemon
-    async fn restart(&self) {
-        let mut state = self.state.lock().await;
+    ///
+    /// In-flight transfers are drained as on shutdown, then the daemon
+    /// binary is executed again with the same arguments and process ID.
+    async fn restart(&self) -> zbus::fdo::Result<()> {
         info!("Manager.Restart called");
-        state.is_running = false;
-        state.is_running = true;
+        self.send_lifecycle(LifecycleRequest::Restart).await
+    }
+
+    /// Shuts the daemon down after draining in-flight transfers
+    async fn shutdown(&self) -> zbus::fdo::Result<()> {
+        info!("Manager.Shutdown called");
+        self.send_lifecycle(LifecycleRequest::Shutdown).await
+    }
+
+    /// Validates the configuration file and applies it
+    ///
+    /// An invalid file is rejected with the list of problems and the
+    /// running configuration is kept. A valid one takes effect once the
+    /// current sync cycle has finished.
+    async fn reload_config(&self) -> zbus::fdo::Result<()> {
+        info!("Manager.ReloadConfig called");
+        let path = self.state.lock().await.config_path.clone();
+        let config = Config::load(&path).map_err(|e| {
+            zbus::fdo::Error::Failed(format!("Failed to load {}: {e:#}", path.display()))
+        })?;
+        let errors = config.validate();
+        if !errors.is_empty() {
+            let details: Vec<String> = errors.iter().map(|e| e.to_string()).collect();
+            return Err(zbus::fdo::Error::Failed(format!(
+                "Invalid configuration: {}",
+                details.join("; ")
+            )));
+        }
+        self.send_lifecycle(LifecycleRequest::ReloadConfig).await
+    }
+
+    /// Returns the daemon version
+    async fn get_version(&self) -> String {
+        self.state.lock().await.version.clone()
+    }
+
+    /// Returns what this daemon supports as JSON
+    ///
+    /// Keys: "version" (daemon version), "schema_version"
+    /// ([`DBUS_SCHEMA_VERSION`]), "interfaces" (interface name to version)
+    /// and "features" (feature name to whether it is supported). Clients
+    /// should treat a missing interface or feature as unsupported.
+    async fn get_capabilities(&self) -> String {
+        let version = self.state.lock().await.version.clone();
+        let interfaces: serde_json::Map<String, serde_json::Value> = DBUS_INTERFACE_VERSIONS
+            .iter()
+            .map(|(name, version)| (name.to_string(), (*version).into()))
+            .collect();
+        let features: serde_json::Map<String, serde_json::Value> = DAEMON_FEATURES
+            .iter()
+            .map(|(name, supported)| (name.to_string(), (*supported).into()))
+            .collect();
+        serde_json::json!({
+            "version": version,
+            "schema_version": DBUS_SCHEMA_VERSION,
+            "interfaces": interfaces,
+            "features": features,
+        })
+        .to_string()
     }
 
-    /// Returns the current daemon status as a string
+    /// Returns the daemon status as JSON
+    ///
+    /// Keys: "status" ("running" or "stopped"), "running", "version",
+    /// "pid", "uptime_secs", "seconds_since_last_sync" (null if no sync
+    /// succeeded yet), "sync_state"
     async fn get_status(&self) -> String {
         let state = self.state.lock().await;
-        if state.is_running { "running" } else { "stopped" }.to_string()
+        serde_json::json!({
+            "status": if state.is_running { "running" } else { "stopped" },
+            "running": state.is_running,
+            "version": state.version,
+            "pid": std::process::id(),
+            "uptime_secs": state.uptime_secs(),
+            "seconds_since_last_sync": state.seconds_since_last_sync(Utc::now()),
+            "sync_state": state.sync_state.to_string(),
+        })
+        .to_string()
     }
 
     /// Daemon version string
@@ -1002,6 +2455,12 @@ impl ManagerInterface {
         let state = self.state.lock().await;
         state.is_running
     }
+
+    /// Seconds since the daemon started
+    #[zbus(property)]
+    async fn uptime_secs(&self) -> u64 {
+        self.state.lock().await.uptime_secs()
+    }
 }
 
 // ============================================================================
@@ -1015,19 +2474,27 @@ impl ManagerInterface {
 /// well-known name `com.enigmora.LNXDrive`.
 pub struct DbusService {
     state: Arc<Mutex<DaemonState>>,
+    repository: Option<Arc<dyn IStateRepository + Send + Sync>>,
 }
 
 impl DbusService {
     /// Creates a new DbusService with the given shared state
     pub fn new(state: Arc<Mutex<DaemonState>>) -> Self {
-        Self { state }
+        Self {
+            state,
+            repository: None,
+        }
     }
 
     /// Creates a new DbusService with default state
     pub fn with_default_state() -> Self {
-        Self {
-            state: Arc::new(Mutex::new(DaemonState::default())),
-        }
+        Self::new(Arc::new(Mutex::new(DaemonState::default())))
+    }
+
+    /// Attaches the state repository for interfaces that query it directly
+    pub fn with_repository(mut self, repository: Arc<dyn IStateRepository + Send + Sync>) -> Self {
+        self.repository = Some(repository);
+        self
     }
 
     /// Returns a reference to the shared daemon state
@@ -1051,12 +2518,17 @@ impl DbusService {
 
         let sync_controller = SyncControllerInterface::new(Arc::clone(&self.state));
         let account_iface = AccountInterface::new(Arc::clone(&self.state));
-        let conflicts_iface = ConflictsInterface::new(Arc::clone(&self.state));
-        let files_iface = FilesInterface::new(Arc::clone(&self.state));
+        let mut conflicts_iface = ConflictsInterface::new(Arc::clone(&self.state));
+        let mut files_iface = FilesInterface::new(Arc::clone(&self.state));
+        if let Some(repo) = &self.repository {
+            conflicts_iface = conflicts_iface.with_repository(Arc::clone(repo));
+            files_iface = files_iface.with_repository(Arc::clone(repo));
+        }
         let sync_iface = SyncInterface::new(Arc::clone(&self.state));
         let status_iface = StatusInterface::new(Arc::clone(&self.state));
         let auth_iface = AuthInterface::new(Arc::clone(&self.state));
         let settings_iface = SettingsInterface::new(Arc::clone(&self.state));
+        let cache_iface = CacheInterface::new(Arc::clone(&self.state));
         let manager_iface = ManagerInterface::new(Arc::clone(&self.state));
 
         let connection = zbus::connection::Builder::session()?
@@ -1069,6 +2541,7 @@ impl DbusService {
             .serve_at(DBUS_PATH, status_iface)?
             .serve_at(DBUS_PATH, auth_iface)?
             .serve_at(DBUS_PATH, settings_iface)?
+            .serve_at(DBUS_PATH, cache_iface)?
             .serve_at(DBUS_PATH, manager_iface)?
             .build()
             .await?;
@@ -1209,6 +2682,7 @@ mod tests {
     #[tokio::test]
     async fn test_account_get_info_with_account() {
         let state = Arc::new(Mutex::new(DaemonState {
+            account_id: Some("account-1".to_string()),
             account_email: Some("user@example.com".to_string()),
             account_display_name: Some("Test User".to_string()),
             ..DaemonState::default()
@@ -1218,6 +2692,7 @@ mod tests {
         let info_json = account.get_info().await;
         let info: serde_json::Value = serde_json::from_str(&info_json).unwrap();
 
+        assert_eq!(info["id"], "account-1");
         assert_eq!(info["email"], "user@example.com");
         assert_eq!(info["display_name"], "Test User");
     }
@@ -1320,6 +2795,112 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_conflicts_resolve_all_invalid_arguments() {
+        let conflicts = ConflictsInterface::new(Arc::new(Mutex::new(DaemonState::default())));
+
+        let json: serde_json::Value = serde_json::from_str(
+            &conflicts
+                .resolve_all(String::new(), "manual".to_string())
+                .await,
+        )
+        .unwrap();
+        assert!(json["error"].is_string());
+
+        let json: serde_json::Value = serde_json::from_str(
+            &conflicts
+                .resolve_all("[abc".to_string(), "keep_local".to_string())
+                .await,
+        )
+        .unwrap();
+        assert!(json["error"].is_string());
+    }
+
+    #[tokio::test]
+    async fn test_conflicts_resolve_all_cached_by_prefix() {
+        let state = Arc::new(Mutex::new(DaemonState {
+            conflicts_json:
+                r#"[{"id":"c1","path":"Photos/a.jpg"},{"id":"c2","path":"Docs/b.txt"}]"#.to_string(),
+            ..DaemonState::default()
+        }));
+        let conflicts = ConflictsInterface::new(Arc::clone(&state));
+
+        let json: serde_json::Value = serde_json::from_str(
+            &conflicts
+                .resolve_all("Photos/".to_string(), "keep_remote".to_string())
+                .await,
+        )
+        .unwrap();
+        assert_eq!(json["matched"], 1);
+        assert_eq!(json["outcomes"][0]["conflict_id"], "c1");
+
+        let remaining = state.lock().await.conflicts_json.clone();
+        assert!(!remaining.contains("c1"));
+        assert!(remaining.contains("c2"));
+    }
+
+    #[tokio::test]
+    async fn test_conflicts_resolve_all_with_repository() {
+        use lnxdrive_core::domain::{newtypes::FileHash, VersionInfo};
+
+        let repo = setup_status_repo().await;
+        let hash = FileHash::new("AAAAAAAAAAAAAAAAAAAAAAAAAAA=".to_string()).unwrap();
+        let now = chrono::Utc::now();
+        let mut photo_ids = Vec::new();
+        for path in [
+            "/home/user/OneDrive/Photos/a.jpg",
+            "/home/user/OneDrive/Photos/2025/b.jpg",
+            "/home/user/OneDrive/Docs/c.txt",
+        ] {
+            let item = status_test_item(path);
+            repo.save_item(&item).await.unwrap();
+            let conflict = Conflict::new(
+                *item.id(),
+                VersionInfo::new(hash.clone(), 10, now),
+                VersionInfo::new(hash.clone(), 20, now),
+            );
+            repo.save_conflict(&conflict).await.unwrap();
+            if path.contains("Photos") {
+                photo_ids.push(conflict.id().to_string());
+            }
+        }
+
+        let conflicts = ConflictsInterface::new(Arc::new(Mutex::new(DaemonState::default())))
+            .with_repository(repo.clone());
+        let json: serde_json::Value = serde_json::from_str(
+            &conflicts
+                .resolve_all("Photos/".to_string(), "keep_remote".to_string())
+                .await,
+        )
+        .unwrap();
+
+        assert_eq!(json["matched"], 2);
+        assert_eq!(json["resolved"], 2);
+        assert_eq!(json["failed"], 0);
+        let outcome_ids: Vec<&str> = json["outcomes"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|o| o["conflict_id"].as_str().unwrap())
+            .collect();
+        for id in &photo_ids {
+            assert!(outcome_ids.contains(&id.as_str()));
+        }
+
+        let unresolved = repo.get_unresolved_conflicts().await.unwrap();
+        assert_eq!(unresolved.len(), 1);
+
+        let audit = repo
+            .get_audit_since(now - chrono::Duration::minutes(1), 100)
+            .await
+            .unwrap();
+        let resolved_entries = audit
+            .iter()
+            .filter(|e| *e.action() == AuditAction::ConflictResolved)
+            .count();
+        assert_eq!(resolved_entries, 2);
+    }
+
     #[test]
     fn test_dbus_service_with_default_state() {
         let service = DbusService::with_default_state();
@@ -1368,8 +2949,6 @@ mod tests {
     fn test_daemon_state_default_includes_files_fields() {
         let state = DaemonState::default();
         assert!(state.file_statuses.is_empty());
-        assert!(state.pin_requests.is_empty());
-        assert!(state.unpin_requests.is_empty());
         assert!(state.sync_path_requests.is_empty());
     }
 
@@ -1397,6 +2976,193 @@ mod tests {
         assert_eq!(files.get_file_status("/nonexistent/file.txt".to_string()).await, "unknown");
     }
 
+    /// Creates an in-memory repository with an account rooted at /home/user/OneDrive
+    async fn setup_status_repo() -> Arc<lnxdrive_cache::SqliteStateRepository> {
+        use lnxdrive_core::domain::{newtypes::Email, Account};
+
+        let pool = lnxdrive_cache::DatabasePool::in_memory().await.unwrap();
+        let repo = Arc::new(lnxdrive_cache::SqliteStateRepository::new(
+            pool.pool().clone(),
+        ));
+        let account = Account::new(
+            Email::new("test@example.com".to_string()).unwrap(),
+            "Test User",
+            "drive123",
+            SyncPath::new(PathBuf::from("/home/user/OneDrive")).unwrap(),
+        );
+        repo.save_account(&account).await.unwrap();
+        repo
+    }
+
+    fn status_test_item(path: &str) -> SyncItem {
+        use lnxdrive_core::domain::newtypes::RemotePath;
+
+        let name = path.rsplit('/').next().unwrap();
+        SyncItem::new_file(
+            SyncPath::new(PathBuf::from(path)).unwrap(),
+            RemotePath::new(format!("/{}", name)).unwrap(),
+            2048,
+            None,
+        )
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_files_get_status_without_repository_uses_cache() {
+        let mut statuses = HashMap::new();
+        statuses.insert("/home/user/doc.txt".to_string(), "synced".to_string());
+        let state = Arc::new(Mutex::new(DaemonState {
+            file_statuses: statuses,
+            ..DaemonState::default()
+        }));
+        let files = FilesInterface::new(state);
+
+        let json: serde_json::Value =
+            serde_json::from_str(&files.get_status("/home/user/doc.txt".to_string()).await)
+                .unwrap();
+        assert_eq!(json["tracked"], true);
+        assert_eq!(json["state"], "synced");
+    }
+
+    #[tokio::test]
+    async fn test_files_get_status_tracked_item() {
+        let repo = setup_status_repo().await;
+        let mut item = status_test_item("/home/user/OneDrive/report.pdf");
+        item.start_hydrating().unwrap();
+        item.complete_hydration().unwrap();
+        item.pin().unwrap();
+        item.mark_synced();
+        repo.save_item(&item).await.unwrap();
+
+        let files =
+            FilesInterface::new(Arc::new(Mutex::new(DaemonState::default()))).with_repository(repo);
+
+        let json: serde_json::Value = serde_json::from_str(
+            &files
+                .get_status("/home/user/OneDrive/report.pdf".to_string())
+                .await,
+        )
+        .unwrap();
+        assert_eq!(json["tracked"], true);
+        assert_eq!(json["state"], "pinned");
+        assert_eq!(json["pinned"], true);
+        assert_eq!(json["size_bytes"], 2048);
+        assert_eq!(json["remote_path"], "/report.pdf");
+        assert!(json["last_sync"].is_string());
+        assert!(json["error"].is_null());
+        assert!(json["conflict"].is_null());
+    }
+
+    #[tokio::test]
+    async fn test_files_get_status_reports_conflict() {
+        use lnxdrive_core::domain::{newtypes::FileHash, VersionInfo};
+
+        let repo = setup_status_repo().await;
+        let mut item = status_test_item("/home/user/OneDrive/notes.txt");
+        item.start_hydrating().unwrap();
+        item.complete_hydration().unwrap();
+        repo.save_item(&item).await.unwrap();
+
+        let hash = FileHash::new("AAAAAAAAAAAAAAAAAAAAAAAAAAA=".to_string()).unwrap();
+        let now = chrono::Utc::now();
+        let conflict = Conflict::new(
+            *item.id(),
+            VersionInfo::new(hash.clone(), 10, now),
+            VersionInfo::new(hash, 20, now),
+        );
+        repo.save_conflict(&conflict).await.unwrap();
+
+        let files =
+            FilesInterface::new(Arc::new(Mutex::new(DaemonState::default()))).with_repository(repo);
+        let json: serde_json::Value = serde_json::from_str(
+            &files
+                .get_status("/home/user/OneDrive/notes.txt".to_string())
+                .await,
+        )
+        .unwrap();
+        assert_eq!(json["conflict"]["id"], conflict.id().to_string());
+        assert_eq!(json["conflict"]["local_size"], 10);
+        assert_eq!(json["conflict"]["remote_size"], 20);
+    }
+
+    #[tokio::test]
+    async fn test_files_get_status_untracked_paths() {
+        let repo = setup_status_repo().await;
+        let files =
+            FilesInterface::new(Arc::new(Mutex::new(DaemonState::default()))).with_repository(repo);
+
+        let outside: serde_json::Value =
+            serde_json::from_str(&files.get_status("/etc/passwd".to_string()).await).unwrap();
+        assert_eq!(outside["tracked"], false);
+        assert_eq!(outside["reason"], "outside_sync_root");
+
+        let missing: serde_json::Value = serde_json::from_str(
+            &files
+                .get_status("/home/user/OneDrive/missing.txt".to_string())
+                .await,
+        )
+        .unwrap();
+        assert_eq!(missing["tracked"], false);
+        assert_eq!(missing["reason"], "not_tracked");
+
+        let relative: serde_json::Value =
+            serde_json::from_str(&files.get_status("relative.txt".to_string()).await).unwrap();
+        assert_eq!(relative["reason"], "invalid_path");
+    }
+
+    #[tokio::test]
+    async fn test_files_get_emblem_from_repository() {
+        let repo = setup_status_repo().await;
+        let mut hydrated = status_test_item("/home/user/OneDrive/a.txt");
+        hydrated.start_hydrating().unwrap();
+        hydrated.complete_hydration().unwrap();
+        repo.save_item(&hydrated).await.unwrap();
+        repo.save_item(&status_test_item("/home/user/OneDrive/b.txt"))
+            .await
+            .unwrap();
+
+        let files =
+            FilesInterface::new(Arc::new(Mutex::new(DaemonState::default()))).with_repository(repo);
+
+        assert_eq!(
+            files
+                .get_emblem("/home/user/OneDrive/a.txt".to_string())
+                .await,
+            "synced"
+        );
+        assert_eq!(
+            files
+                .get_emblem("/home/user/OneDrive/b.txt".to_string())
+                .await,
+            "cloud-only"
+        );
+        assert_eq!(
+            files
+                .get_emblem("/home/user/OneDrive/c.txt".to_string())
+                .await,
+            "unknown"
+        );
+        assert_eq!(files.get_emblem("relative".to_string()).await, "unknown");
+    }
+
+    #[tokio::test]
+    async fn test_files_get_emblem_from_cache() {
+        let mut statuses = HashMap::new();
+        statuses.insert("/a".to_string(), "pending".to_string());
+        statuses.insert("/b".to_string(), "conflict".to_string());
+        statuses.insert("/c".to_string(), "excluded".to_string());
+        let state = Arc::new(Mutex::new(DaemonState {
+            file_statuses: statuses,
+            ..DaemonState::default()
+        }));
+        let files = FilesInterface::new(state);
+
+        assert_eq!(files.get_emblem("/a".to_string()).await, "syncing");
+        assert_eq!(files.get_emblem("/b".to_string()).await, "conflict");
+        assert_eq!(files.get_emblem("/c".to_string()).await, "unknown");
+        assert_eq!(files.get_emblem("/d".to_string()).await, "unknown");
+    }
+
     #[tokio::test]
     async fn test_files_get_batch_file_status() {
         let mut statuses = HashMap::new();
@@ -1433,63 +3199,167 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn test_files_pin_file() {
-        let state = Arc::new(Mutex::new(DaemonState::default()));
-        let files = FilesInterface::new(Arc::clone(&state));
+    async fn test_files_pin_fails_when_not_mounted() {
+        let files = FilesInterface::new(Arc::new(Mutex::new(DaemonState::default())));
 
+        // Fire-and-forget requests are ignored
         files.pin_file("/home/user/important.pdf".to_string()).await;
+        files
+            .unpin_file("/home/user/large-video.mp4".to_string())
+            .await;
 
-        let locked = state.lock().await;
-        assert_eq!(locked.pin_requests, vec!["/home/user/important.pdf"]);
+        let err = files
+            .pin_and_wait("/home/user/important.pdf".to_string())
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("not mounted"));
+        assert!(files
+            .unpin_and_wait("/home/user/large-video.mp4".to_string())
+            .await
+            .is_err());
     }
 
     #[tokio::test]
-    async fn test_files_pin_does_not_duplicate() {
+    async fn test_files_sync_path() {
         let state = Arc::new(Mutex::new(DaemonState::default()));
         let files = FilesInterface::new(Arc::clone(&state));
 
-        files.pin_file("/home/user/doc.txt".to_string()).await;
-        files.pin_file("/home/user/doc.txt".to_string()).await;
-        files.pin_file("/home/user/other.txt".to_string()).await;
+        let id = files
+            .sync_path("/home/user/urgent/".to_string())
+            .await
+            .unwrap();
 
         let locked = state.lock().await;
-        assert_eq!(locked.pin_requests.len(), 2);
-        assert_eq!(locked.pin_requests[0], "/home/user/doc.txt");
-        assert_eq!(locked.pin_requests[1], "/home/user/other.txt");
+        assert_eq!(
+            locked.sync_path_requests,
+            vec![SyncPathRequest {
+                id,
+                path: "/home/user/urgent/".to_string()
+            }]
+        );
     }
 
     #[tokio::test]
-    async fn test_files_unpin_file() {
+    async fn test_files_sync_path_reuses_queued_request() {
         let state = Arc::new(Mutex::new(DaemonState::default()));
         let files = FilesInterface::new(Arc::clone(&state));
 
-        files.unpin_file("/home/user/large-video.mp4".to_string()).await;
+        let first = files.sync_path("/home/user/a.txt".to_string()).await.unwrap();
+        let second = files.sync_path("/home/user/a.txt".to_string()).await.unwrap();
+        let other = files.sync_path("/home/user/b.txt".to_string()).await.unwrap();
 
-        let locked = state.lock().await;
-        assert_eq!(locked.unpin_requests, vec!["/home/user/large-video.mp4"]);
+        assert_eq!(first, second);
+        assert_ne!(first, other);
+        assert_eq!(state.lock().await.sync_path_requests.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_files_sync_path_rejects_paths_outside_sync_root() {
+        let repo = setup_status_repo().await;
+        let files =
+            FilesInterface::new(Arc::new(Mutex::new(DaemonState::default()))).with_repository(repo);
+
+        assert!(files
+            .sync_path("/home/user/OneDrive/docs".to_string())
+            .await
+            .is_ok());
+
+        let err = files
+            .sync_path("/etc/passwd".to_string())
+            .await
+            .unwrap_err();
+        assert!(matches!(err, zbus::fdo::Error::InvalidArgs(_)));
+        assert!(err.to_string().contains("outside the sync root"));
+
+        let err = files.sync_path("docs/a.txt".to_string()).await.unwrap_err();
+        assert!(matches!(err, zbus::fdo::Error::InvalidArgs(_)));
     }
 
     #[tokio::test]
-    async fn test_files_unpin_does_not_duplicate() {
+    async fn test_files_sync_path_status_lifecycle() {
         let state = Arc::new(Mutex::new(DaemonState::default()));
         let files = FilesInterface::new(Arc::clone(&state));
+        let status = |json: String| -> serde_json::Value { serde_json::from_str(&json).unwrap() };
 
-        files.unpin_file("/home/user/file.txt".to_string()).await;
-        files.unpin_file("/home/user/file.txt".to_string()).await;
+        let id = files.sync_path("/home/user/a.txt".to_string()).await.unwrap();
+        assert_eq!(status(files.get_sync_path_status(id).await)["state"], "queued");
 
-        let locked = state.lock().await;
-        assert_eq!(locked.unpin_requests.len(), 1);
+        let request = state.lock().await.start_next_sync_path().unwrap();
+        assert_eq!(request.id, id);
+        assert_eq!(status(files.get_sync_path_status(id).await)["state"], "running");
+
+        state.lock().await.finish_sync_path(
+            id,
+            SyncPathStatus::Completed(serde_json::json!({"files_uploaded": 1})),
+        );
+        let done = status(files.get_sync_path_status(id).await);
+        assert_eq!(done["state"], "completed");
+        assert_eq!(done["result"]["files_uploaded"], 1);
+
+        assert_eq!(status(files.get_sync_path_status(999).await)["state"], "unknown");
     }
 
     #[tokio::test]
-    async fn test_files_sync_path() {
+    async fn test_files_pause_and_resume_transfer() {
         let state = Arc::new(Mutex::new(DaemonState::default()));
         let files = FilesInterface::new(Arc::clone(&state));
+        let sync = SyncInterface::new(Arc::clone(&state));
 
-        files.sync_path("/home/user/urgent/".to_string()).await;
+        assert!(files
+            .pause_transfer("/home/user/OneDrive/Videos/".to_string())
+            .await
+            .unwrap());
+        assert!(!files
+            .pause_transfer("/home/user/OneDrive/Videos".to_string())
+            .await
+            .unwrap());
+        assert!(files.pause_transfer("Videos".to_string()).await.is_err());
+
+        let control = Arc::clone(&state.lock().await.transfer_control);
+        assert!(control.is_paused(std::path::Path::new("/home/user/OneDrive/Videos/a.mp4")));
+        assert!(!control.is_paused(std::path::Path::new("/home/user/OneDrive/notes.txt")));
+
+        let paused: serde_json::Value =
+            serde_json::from_str(&sync.get_paused_transfers().await).unwrap();
+        assert_eq!(paused["all"], false);
+        assert_eq!(paused["paths"], serde_json::json!(["/home/user/OneDrive/Videos"]));
+
+        assert!(files.resume_transfer("/home/user/OneDrive/Videos".to_string()).await);
+        assert!(!files.resume_transfer("/home/user/OneDrive/Videos".to_string()).await);
+        assert!(!control.is_paused(std::path::Path::new("/home/user/OneDrive/Videos/a.mp4")));
+    }
 
-        let locked = state.lock().await;
-        assert_eq!(locked.sync_path_requests, vec!["/home/user/urgent/"]);
+    #[tokio::test]
+    async fn test_files_get_status_reports_paused_transfer() {
+        let repo = setup_status_repo().await;
+        repo.save_item(&status_test_item("/home/user/OneDrive/big.iso"))
+            .await
+            .unwrap();
+        let state = Arc::new(Mutex::new(DaemonState::default()));
+        let files = FilesInterface::new(Arc::clone(&state)).with_repository(repo);
+        let status = |json: String| -> serde_json::Value { serde_json::from_str(&json).unwrap() };
+
+        let path = "/home/user/OneDrive/big.iso".to_string();
+        assert_eq!(status(files.get_status(path.clone()).await)["transfer_paused"], false);
+        files.pause_transfer(path.clone()).await.unwrap();
+        assert_eq!(status(files.get_status(path).await)["transfer_paused"], true);
+    }
+
+    #[test]
+    fn test_finished_sync_path_statuses_are_capped() {
+        let mut state = DaemonState::default();
+        let total = MAX_FINISHED_SYNC_PATH_REQUESTS as u64 + 5;
+        for n in 0..total {
+            let id = state.queue_sync_path(format!("/home/user/{n}.txt"));
+            state.start_next_sync_path();
+            state.finish_sync_path(id, SyncPathStatus::Failed("offline".to_string()));
+        }
+        assert_eq!(
+            state.sync_path_statuses.len(),
+            MAX_FINISHED_SYNC_PATH_REQUESTS
+        );
+        assert!(!state.sync_path_statuses.contains_key(&1));
+        assert!(state.sync_path_statuses.contains_key(&total));
     }
 
     #[tokio::test]
@@ -1541,8 +3411,9 @@ mod tests {
         // Settings
         assert!(state.config_yaml.is_empty());
         assert!(state.selected_folders.is_empty());
+        assert!(state.folder_selection_request.is_none());
         assert!(state.exclusion_patterns.is_empty());
-        assert_eq!(state.remote_folder_tree, "{}");
+        assert!(state.cloud_provider.is_none());
         // Manager
         assert!(!state.version.is_empty());
         assert!(state.is_running);
@@ -1611,6 +3482,148 @@ mod tests {
         assert_eq!(locked.sync_state, DaemonSyncState::Idle); // unchanged
     }
 
+    #[tokio::test]
+    async fn test_sync_pause_and_resume_transfers() {
+        let state = Arc::new(Mutex::new(DaemonState::default()));
+        let sync = SyncInterface::new(Arc::clone(&state));
+        let control = Arc::clone(&state.lock().await.transfer_control);
+        let file = std::path::Path::new("/home/user/OneDrive/big.iso");
+
+        assert!(!sync.transfers_paused().await);
+        sync.pause_transfers().await;
+        assert!(sync.transfers_paused().await);
+        assert!(control.is_paused(file));
+
+        let paused: serde_json::Value =
+            serde_json::from_str(&sync.get_paused_transfers().await).unwrap();
+        assert_eq!(paused["all"], true);
+        assert_eq!(paused["paths"], serde_json::json!([]));
+
+        sync.resume_transfers().await;
+        assert!(!sync.transfers_paused().await);
+        assert!(!control.is_paused(file));
+    }
+
+    #[tokio::test]
+    async fn test_sync_get_history_empty() {
+        let state = Arc::new(Mutex::new(DaemonState::default()));
+        let sync = SyncInterface::new(state);
+        assert_eq!(sync.get_history(10).await, "[]");
+    }
+
+    #[tokio::test]
+    async fn test_sync_get_history_newest_first_and_limited() {
+        let state = Arc::new(Mutex::new(DaemonState::default()));
+        {
+            let mut locked = state.lock().await;
+            for i in 0..3 {
+                let entry =
+                    SyncHistoryEntry::completed(chrono::Utc::now(), i, 0, 0, 0, 0, vec![], 10);
+                locked.push_sync_history(entry);
+            }
+        }
+        let sync = SyncInterface::new(state);
+
+        let parsed: serde_json::Value = serde_json::from_str(&sync.get_history(2).await).unwrap();
+        let arr = parsed.as_array().unwrap();
+        assert_eq!(arr.len(), 2);
+        assert_eq!(arr[0]["files_downloaded"], 2);
+        assert_eq!(arr[1]["files_downloaded"], 1);
+
+        let all: serde_json::Value = serde_json::from_str(&sync.get_history(0).await).unwrap();
+        assert_eq!(all.as_array().unwrap().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_seconds_since_last_sync_survives_failed_cycles() {
+        let state = Arc::new(Mutex::new(DaemonState::default()));
+        let sync = SyncInterface::new(Arc::clone(&state));
+        assert_eq!(sync.seconds_since_last_sync().await, -1);
+
+        let succeeded = chrono::Utc::now() - chrono::Duration::hours(2);
+        {
+            let mut locked = state.lock().await;
+            let mut entry = SyncHistoryEntry::completed(succeeded, 0, 0, 0, 0, 0, vec![], 10);
+            entry.finished_at = succeeded;
+            locked.push_sync_history(entry);
+            for _ in 0..(MAX_SYNC_HISTORY_ENTRIES + 1) {
+                locked.push_sync_history(SyncHistoryEntry::failed(chrono::Utc::now(), "x"));
+            }
+            assert!(locked.sync_history.iter().all(|entry| !entry.success));
+            assert!(locked.last_sync_time > succeeded.timestamp());
+        }
+
+        let age = sync.seconds_since_last_sync().await;
+        assert!((7200..7300).contains(&age), "{age}");
+    }
+
+    #[tokio::test]
+    async fn test_sync_get_pending_plan_is_answered_by_daemon_and_cached() {
+        let state = Arc::new(Mutex::new(DaemonState::default()));
+        let sync = SyncInterface::new(Arc::clone(&state));
+        let wakeup = Arc::clone(&state.lock().await.sync_wakeup);
+        let daemon = {
+            let state = Arc::clone(&state);
+            tokio::spawn(async move {
+                wakeup.notified().await;
+                let preview = serde_json::json!({
+                    "summary": {"downloads": 3, "uploads": 12, "conflicts": 1},
+                    "operations": [{"op": "upload", "path": "/od/a.txt", "size": 5}],
+                    "errors": [],
+                });
+                state
+                    .lock()
+                    .await
+                    .answer_plan_requests(Ok(preview.to_string()));
+            })
+        };
+
+        let json: serde_json::Value =
+            serde_json::from_str(&sync.get_pending_plan().await.unwrap()).unwrap();
+        assert_eq!(json["summary"]["uploads"], 12);
+        assert_eq!(json["operations"][0]["op"], "upload");
+        assert!(json["errors"].as_array().unwrap().is_empty());
+        daemon.await.unwrap();
+
+        // Served from the cache until a sync runs
+        assert_eq!(sync.get_pending_plan().await.unwrap(), json.to_string());
+        assert!(state.lock().await.plan_requests.is_empty());
+        state
+            .lock()
+            .await
+            .push_sync_history(SyncHistoryEntry::failed(chrono::Utc::now(), "x"));
+        assert!(state.lock().await.cached_pending_plan().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_sync_get_pending_plan_reports_planning_failure() {
+        let state = Arc::new(Mutex::new(DaemonState::default()));
+        let pending = {
+            let sync = SyncInterface::new(Arc::clone(&state));
+            tokio::spawn(async move { sync.get_pending_plan().await })
+        };
+        while state.lock().await.plan_requests.is_empty() {
+            tokio::task::yield_now().await;
+        }
+        state
+            .lock()
+            .await
+            .answer_plan_requests(Err("Delta query failed".to_string()));
+
+        let err = pending.await.unwrap().unwrap_err();
+        assert!(err.to_string().contains("Delta query failed"));
+        assert!(state.lock().await.cached_pending_plan().is_none());
+    }
+
+    #[test]
+    fn test_push_sync_history_is_capped() {
+        let mut state = DaemonState::default();
+        for _ in 0..(MAX_SYNC_HISTORY_ENTRIES + 5) {
+            state.push_sync_history(SyncHistoryEntry::failed(chrono::Utc::now(), "x"));
+        }
+        assert_eq!(state.sync_history.len(), MAX_SYNC_HISTORY_ENTRIES as usize);
+    }
+
     #[tokio::test]
     async fn test_sync_status_property_idle() {
         let state = Arc::new(Mutex::new(DaemonState::default()));
@@ -1658,6 +3671,25 @@ mod tests {
         assert_eq!(sync.pending_changes().await, 42);
     }
 
+    #[tokio::test]
+    async fn test_sync_get_stats() {
+        let state = Arc::new(Mutex::new(DaemonState::default()));
+        let sync = SyncInterface::new(Arc::clone(&state));
+        assert!(sync.get_stats().await.is_err());
+
+        let metrics = Arc::new(MetricsRegistry::new(&prometheus::Registry::new()).unwrap());
+        {
+            let mut state = state.lock().await;
+            state.metrics = Some(metrics);
+            state.queue_sync_path("/home/user/OneDrive/a".to_string());
+        }
+
+        let json = sync.get_stats().await.unwrap();
+        let stats: lnxdrive_telemetry::StatsSnapshot = serde_json::from_str(&json).unwrap();
+        assert_eq!(stats.queued_sync_paths, 1);
+        assert_eq!(stats.bytes_uploaded, 0);
+    }
+
     // -- StatusInterface tests --
 
     #[tokio::test]
@@ -1690,7 +3722,8 @@ mod tests {
         let status = StatusInterface::new(state);
 
         let info = status.get_account_info().await;
-        assert_eq!(info.len(), 3);
+        assert_eq!(info.len(), 4);
+        assert!(info.contains_key("id"));
         assert!(info.contains_key("email"));
         assert!(info.contains_key("display_name"));
         assert!(info.contains_key("provider"));
@@ -1699,6 +3732,7 @@ mod tests {
     #[tokio::test]
     async fn test_status_get_account_info_with_account() {
         let state = Arc::new(Mutex::new(DaemonState {
+            account_id: Some("account-1".to_string()),
             account_email: Some("test@example.com".to_string()),
             account_display_name: Some("Test User".to_string()),
             ..DaemonState::default()
@@ -1707,7 +3741,10 @@ mod tests {
 
         let info = status.get_account_info().await;
         // Verify the variant dict contains expected keys
-        assert_eq!(info.len(), 3);
+        assert_eq!(info.len(), 4);
+
+        let id: String = info["id"].try_clone().unwrap().try_into().unwrap();
+        assert_eq!(id, "account-1");
 
         // Deserialize the OwnedValue for email
         let email: String = info["email"].try_clone().unwrap().try_into().unwrap();
@@ -1717,6 +3754,15 @@ mod tests {
         assert_eq!(name, "Test User");
     }
 
+    #[test]
+    fn test_daemon_state_update_quota() {
+        let mut state = DaemonState::default();
+        assert!(state.update_quota(1024, 4096));
+        assert!(!state.update_quota(1024, 4096));
+        assert!(state.update_quota(2048, 4096));
+        assert_eq!((state.quota_used, state.quota_total), (2048, 4096));
+    }
+
     #[tokio::test]
     async fn test_status_connection_status_property() {
         let state = Arc::new(Mutex::new(DaemonState {
@@ -1727,6 +3773,16 @@ mod tests {
         assert_eq!(status.connection_status().await, "offline");
     }
 
+    #[tokio::test]
+    async fn test_status_clock_skew_property() {
+        let state = Arc::new(Mutex::new(DaemonState::default()));
+        let status = StatusInterface::new(Arc::clone(&state));
+        assert_eq!(status.clock_skew().await, 0);
+
+        state.lock().await.clock_skew = Some(ClockSkew::from_secs(-600));
+        assert_eq!(status.clock_skew().await, -600);
+    }
+
     // -- AuthInterface tests --
 
     #[tokio::test]
@@ -1792,6 +3848,9 @@ mod tests {
             is_authenticated: true,
             account_email: Some("user@example.com".to_string()),
             account_display_name: Some("User".to_string()),
+            auth_csrf_state: Some("state".to_string()),
+            quota_used: 1024,
+            quota_total: 4096,
             ..DaemonState::default()
         }));
         let auth = AuthInterface::new(Arc::clone(&state));
@@ -1802,6 +3861,10 @@ mod tests {
         assert!(!locked.is_authenticated);
         assert!(locked.account_email.is_none());
         assert!(locked.account_display_name.is_none());
+        assert!(locked.auth_csrf_state.is_none());
+        assert_eq!(locked.quota_used, 0);
+        assert_eq!(locked.quota_total, 0);
+        assert!(locked.logout_requested);
     }
 
     #[tokio::test]
@@ -1864,8 +3927,18 @@ mod tests {
         assert!(settings.get_selected_folders().await.is_empty());
 
         let folders = vec!["/Documents".to_string(), "/Photos".to_string()];
+        let wakeup = Arc::clone(&state.lock().await.sync_wakeup);
         settings.set_selected_folders(folders.clone()).await;
 
+        // Queued for the daemon, which reports the selection once applied
+        tokio::time::timeout(Duration::from_secs(1), wakeup.notified())
+            .await
+            .expect("the daemon was not woken up");
+        assert!(settings.get_selected_folders().await.is_empty());
+        let request = state.lock().await.folder_selection_request.take();
+        assert_eq!(request, Some(folders.clone()));
+        state.lock().await.selected_folders = folders.clone();
+
         assert_eq!(settings.get_selected_folders().await, folders);
     }
 
@@ -1882,16 +3955,164 @@ mod tests {
         assert_eq!(settings.get_exclusion_patterns().await, patterns);
     }
 
+    /// Settings interface browsing a Graph API mocked by `server`
+    fn settings_with_graph(server: &wiremock::MockServer) -> SettingsInterface {
+        use lnxdrive_graph::{client::GraphClient, provider::GraphCloudProvider};
+
+        let provider = GraphCloudProvider::new(GraphClient::with_base_url("token", server.uri()));
+        SettingsInterface::new(Arc::new(Mutex::new(DaemonState {
+            cloud_provider: Some(Arc::new(provider)),
+            ..DaemonState::default()
+        })))
+    }
+
     #[tokio::test]
     async fn test_settings_remote_folder_tree() {
-        let tree_json = r#"{"name":"root","children":[{"name":"Docs"}]}"#.to_string();
-        let state = Arc::new(Mutex::new(DaemonState {
-            remote_folder_tree: tree_json.clone(),
-            ..DaemonState::default()
-        }));
-        let settings = SettingsInterface::new(state);
+        use wiremock::{
+            matchers::{method, path, query_param},
+            Mock, MockServer, ResponseTemplate,
+        };
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/me/drive/root/children"))
+            .and(query_param("$filter", "folder ne null"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "value": [
+                    {"name": "Docs", "folder": {"childCount": 3}},
+                    {"name": "Empty", "folder": {"childCount": 0}},
+                    {"name": "notes.txt", "file": {}}
+                ]
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/me/drive/root:/Docs:/children"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "value": [{"name": "Taxes", "folder": {"childCount": 1}}]
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+        let settings = settings_with_graph(&server);
+
+        let tree = settings
+            .get_remote_folder_tree("/".to_string(), 2)
+            .await
+            .unwrap();
+        let tree: serde_json::Value = serde_json::from_str(&tree).unwrap();
+
+        assert_eq!(
+            tree,
+            serde_json::json!({
+                "path": "/",
+                "continuation": null,
+                "folders": [
+                    {
+                        "name": "Docs", "path": "/Docs", "child_count": 3,
+                        "continuation": null,
+                        "folders": [{"name": "Taxes", "path": "/Docs/Taxes", "child_count": 1}]
+                    },
+                    {
+                        "name": "Empty", "path": "/Empty", "child_count": 0,
+                        "continuation": null, "folders": []
+                    }
+                ]
+            })
+        );
+
+        // Served from the cache: each listing is requested once
+        let again = settings
+            .get_remote_folder_tree("/Docs".to_string(), 1)
+            .await
+            .unwrap();
+        assert!(again.contains("/Docs/Taxes"));
+    }
+
+    #[tokio::test]
+    async fn test_settings_remote_folder_tree_continuation() {
+        use wiremock::{
+            matchers::{method, path, query_param},
+            Mock, MockServer, ResponseTemplate,
+        };
 
-        assert_eq!(settings.get_remote_folder_tree().await, tree_json);
+        let server = MockServer::start().await;
+        let next_link = format!("{}/me/drive/root/children?page=2", server.uri());
+        Mock::given(method("GET"))
+            .and(path("/me/drive/root/children"))
+            .and(query_param("page", "2"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "value": [{"name": "B", "folder": {"childCount": 0}}]
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/me/drive/root/children"))
+            .and(query_param("$top", "200"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "value": [{"name": "A", "folder": {"childCount": 0}}],
+                "@odata.nextLink": next_link
+            })))
+            .mount(&server)
+            .await;
+        let settings = settings_with_graph(&server);
+
+        let first: serde_json::Value = serde_json::from_str(
+            &settings
+                .get_remote_folder_tree("/".to_string(), 1)
+                .await
+                .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(first["folders"][0]["name"], "A");
+        assert_eq!(first["continuation"], next_link.as_str());
+
+        let second: serde_json::Value = serde_json::from_str(
+            &settings
+                .continue_remote_folder_tree("/".to_string(), next_link.clone(), 1)
+                .await
+                .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(second["folders"][0]["name"], "B");
+        assert_eq!(second["continuation"], serde_json::Value::Null);
+
+        // The access token is only sent to the Graph API
+        let err = settings
+            .continue_remote_folder_tree(
+                "/".to_string(),
+                "https://attacker.example/children".to_string(),
+                1,
+            )
+            .await
+            .unwrap_err();
+        assert!(matches!(err, zbus::fdo::Error::Failed(_)));
+    }
+
+    #[tokio::test]
+    async fn test_settings_remote_folder_tree_rejects_bad_requests() {
+        let settings = SettingsInterface::new(Arc::new(Mutex::new(DaemonState::default())));
+        let err = settings
+            .get_remote_folder_tree("/".to_string(), 1)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, zbus::fdo::Error::Failed(_)));
+
+        for (path, depth) in [
+            ("/", 0),
+            ("/", REMOTE_FOLDER_TREE_MAX_DEPTH + 1),
+            ("Docs", 1),
+        ] {
+            let err = settings
+                .get_remote_folder_tree(path.to_string(), depth)
+                .await
+                .unwrap_err();
+            assert!(
+                matches!(err, zbus::fdo::Error::InvalidArgs(_)),
+                "{path} {depth}"
+            );
+        }
     }
 
     #[tokio::test]
@@ -1902,19 +4123,76 @@ mod tests {
         }));
         let settings = SettingsInterface::new(Arc::clone(&state));
 
+        settings.set_selected_folders(vec!["/Newer".to_string()]).await;
         settings.set_selected_folders(vec!["/New".to_string()]).await;
 
+        // The latest request replaces a pending one; the old selection
+        // stays until the daemon applied it
         let locked = state.lock().await;
-        assert_eq!(locked.selected_folders, vec!["/New"]);
+        assert_eq!(locked.selected_folders, vec!["/Old"]);
+        assert_eq!(
+            locked.folder_selection_request,
+            Some(vec!["/New".to_string()])
+        );
     }
 
     // -- ManagerInterface tests --
 
+    fn manager_status(json: &str) -> serde_json::Value {
+        serde_json::from_str(json).expect("GetStatus returns JSON")
+    }
+
     #[tokio::test]
     async fn test_manager_get_status_default() {
         let state = Arc::new(Mutex::new(DaemonState::default()));
         let manager = ManagerInterface::new(state);
-        assert_eq!(manager.get_status().await, "running");
+        let status = manager_status(&manager.get_status().await);
+        assert_eq!(status["status"], "running");
+        assert_eq!(status["running"], true);
+        assert_eq!(status["pid"], std::process::id());
+        assert_eq!(status["version"], env!("CARGO_PKG_VERSION"));
+        assert_eq!(status["sync_state"], "idle");
+    }
+
+    #[tokio::test]
+    async fn test_manager_get_status_uptime() {
+        let state = Arc::new(Mutex::new(DaemonState {
+            started_at: std::time::Instant::now() - std::time::Duration::from_secs(90),
+            ..DaemonState::default()
+        }));
+        let manager = ManagerInterface::new(Arc::clone(&state));
+        let status = manager_status(&manager.get_status().await);
+        assert!(status["uptime_secs"].as_u64().unwrap() >= 90);
+        assert!(manager.uptime_secs().await >= 90);
+        assert!(status["seconds_since_last_sync"].is_null());
+
+        state.lock().await.last_successful_sync =
+            Some(chrono::Utc::now() - chrono::Duration::seconds(30));
+        let status = manager_status(&manager.get_status().await);
+        assert!(status["seconds_since_last_sync"].as_u64().unwrap() >= 30);
+    }
+
+    #[tokio::test]
+    async fn test_manager_get_version() {
+        let state = Arc::new(Mutex::new(DaemonState::default()));
+        let manager = ManagerInterface::new(state);
+        assert_eq!(manager.get_version().await, env!("CARGO_PKG_VERSION"));
+    }
+
+    #[tokio::test]
+    async fn test_manager_get_capabilities() {
+        let state = Arc::new(Mutex::new(DaemonState::default()));
+        let manager = ManagerInterface::new(state);
+        let capabilities = manager_status(&manager.get_capabilities().await);
+        assert_eq!(capabilities["version"], env!("CARGO_PKG_VERSION"));
+        assert_eq!(capabilities["schema_version"], DBUS_SCHEMA_VERSION);
+        assert_eq!(capabilities["interfaces"]["com.enigmora.LNXDrive.Manager"], 1);
+        assert_eq!(
+            capabilities["interfaces"].as_object().unwrap().len(),
+            DBUS_INTERFACE_VERSIONS.len()
+        );
+        assert_eq!(capabilities["features"]["streaming_hydration"], true);
+        assert_eq!(capabilities["features"]["multi_account"], false);
     }
 
     #[tokio::test]
@@ -1944,13 +4222,53 @@ mod tests {
 
     #[tokio::test]
     async fn test_manager_restart() {
-        let state = Arc::new(Mutex::new(DaemonState::default()));
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let state = Arc::new(Mutex::new(DaemonState {
+            lifecycle: Some(tx),
+            ..DaemonState::default()
+        }));
         let manager = ManagerInterface::new(Arc::clone(&state));
 
-        manager.restart().await;
+        manager.restart().await.unwrap();
+        manager.shutdown().await.unwrap();
 
-        let locked = state.lock().await;
-        assert!(locked.is_running); // should end running
+        assert_eq!(rx.recv().await, Some(LifecycleRequest::Restart));
+        assert_eq!(rx.recv().await, Some(LifecycleRequest::Shutdown));
+        assert!(state.lock().await.is_running);
+    }
+
+    #[tokio::test]
+    async fn test_manager_lifecycle_unavailable() {
+        let state = Arc::new(Mutex::new(DaemonState::default()));
+        let manager = ManagerInterface::new(state);
+        assert!(manager.restart().await.is_err());
+        assert!(manager.shutdown().await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_manager_reload_config_validates_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.yaml");
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let state = Arc::new(Mutex::new(DaemonState {
+            config_path: path.clone(),
+            lifecycle: Some(tx),
+            ..DaemonState::default()
+        }));
+        let manager = ManagerInterface::new(state);
+
+        let mut config = Config::default();
+        config.sync.root = dir.path().to_path_buf();
+        config.sync.poll_interval = 0;
+        std::fs::write(&path, serde_yaml::to_string(&config).unwrap()).unwrap();
+        let err = manager.reload_config().await.unwrap_err();
+        assert!(err.to_string().contains("sync.poll_interval"), "{err}");
+        assert!(rx.try_recv().is_err());
+
+        config.sync.poll_interval = 60;
+        std::fs::write(&path, serde_yaml::to_string(&config).unwrap()).unwrap();
+        manager.reload_config().await.unwrap();
+        assert_eq!(rx.recv().await, Some(LifecycleRequest::ReloadConfig));
     }
 
     #[tokio::test]
@@ -1960,7 +4278,9 @@ mod tests {
             ..DaemonState::default()
         }));
         let manager = ManagerInterface::new(state);
-        assert_eq!(manager.get_status().await, "stopped");
+        let status = manager_status(&manager.get_status().await);
+        assert_eq!(status["status"], "stopped");
+        assert_eq!(status["running"], false);
     }
 
     #[tokio::test]
@@ -1979,4 +4299,225 @@ mod tests {
         let manager = ManagerInterface::new(state);
         assert!(manager.is_running().await);
     }
+
+    #[test]
+    fn test_transfer_result_reports_success_or_error() {
+        assert_eq!(transfer_result(None), "success");
+        assert_eq!(transfer_result(Some("HTTP 507")), "HTTP 507");
+    }
+
+    // ------------------------------------------------------------------
+    // Cache interface tests
+    // ------------------------------------------------------------------
+
+    /// Records clean options and pinned paths and returns fixed reports
+    #[derive(Default)]
+    struct FakeCacheManager {
+        cleans: std::sync::Mutex<Vec<CacheCleanOptions>>,
+        pins: std::sync::Mutex<Vec<PathBuf>>,
+    }
+
+    #[async_trait::async_trait]
+    impl ICacheManager for FakeCacheManager {
+        async fn usage(&self) -> anyhow::Result<lnxdrive_core::ports::CacheUsage> {
+            Ok(lnxdrive_core::ports::CacheUsage {
+                total_bytes: 300,
+                pinned_bytes: 100,
+                evictable_bytes: 200,
+                modified_bytes: 0,
+                folders: vec![lnxdrive_core::ports::FolderUsage {
+                    folder: "Documents".to_string(),
+                    bytes: 300,
+                    files: 2,
+                }],
+            })
+        }
+
+        async fn clean(
+            &self,
+            options: CacheCleanOptions,
+        ) -> anyhow::Result<lnxdrive_core::ports::CacheCleanReport> {
+            self.cleans.lock().unwrap().push(options);
+            Ok(lnxdrive_core::ports::CacheCleanReport {
+                dehydrated: 1,
+                bytes_freed: 200,
+                ..Default::default()
+            })
+        }
+
+        async fn verify(&self) -> anyhow::Result<lnxdrive_core::ports::CacheVerifyReport> {
+            anyhow::bail!("scrub failed")
+        }
+
+        async fn pin(
+            &self,
+            path: &std::path::Path,
+        ) -> anyhow::Result<lnxdrive_core::ports::CachePinReport> {
+            self.pins.lock().unwrap().push(path.to_path_buf());
+            Ok(lnxdrive_core::ports::CachePinReport {
+                files: 3,
+                failed: 1,
+            })
+        }
+
+        async fn unpin(
+            &self,
+            path: &std::path::Path,
+        ) -> anyhow::Result<lnxdrive_core::ports::CachePinReport> {
+            anyhow::bail!("{} is not in the mounted folder", path.display())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_cache_fails_when_not_mounted() {
+        let cache = CacheInterface::new(Arc::new(Mutex::new(DaemonState::default())));
+
+        let err = cache.get_status().await.unwrap_err();
+        assert!(matches!(err, zbus::fdo::Error::Failed(_)));
+        assert!(err.to_string().contains("not mounted"));
+        assert!(cache.clean(true, 0).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_cache_routes_to_manager() {
+        let manager = Arc::new(FakeCacheManager::default());
+        let state = Arc::new(Mutex::new(DaemonState::default()));
+        state.lock().await.cache_manager = Some(Arc::clone(&manager) as _);
+        let cache = CacheInterface::new(state);
+
+        let status: serde_json::Value =
+            serde_json::from_str(&cache.get_status().await.unwrap()).unwrap();
+        assert_eq!(status["total_bytes"], 300);
+        assert_eq!(status["folders"][0]["folder"], "Documents");
+
+        let report: serde_json::Value =
+            serde_json::from_str(&cache.clean(true, 30).await.unwrap()).unwrap();
+        assert_eq!(report["bytes_freed"], 200);
+        assert_eq!(
+            *manager.cleans.lock().unwrap(),
+            vec![CacheCleanOptions {
+                keep_pinned: true,
+                older_than_days: 30,
+            }]
+        );
+
+        let err = cache.verify().await.unwrap_err();
+        assert!(err.to_string().contains("scrub failed"));
+    }
+
+    #[tokio::test]
+    async fn test_files_pin_routes_to_cache_manager() {
+        let manager = Arc::new(FakeCacheManager::default());
+        let state = Arc::new(Mutex::new(DaemonState::default()));
+        state.lock().await.cache_manager = Some(Arc::clone(&manager) as _);
+        let files = FilesInterface::new(state);
+
+        let report: serde_json::Value = serde_json::from_str(
+            &files
+                .pin_and_wait("/home/user/OneDrive/Photos".to_string())
+                .await
+                .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(report["files"], 3);
+        assert_eq!(report["failed"], 1);
+        assert_eq!(
+            *manager.pins.lock().unwrap(),
+            vec![PathBuf::from("/home/user/OneDrive/Photos")]
+        );
+
+        let err = files
+            .unpin_and_wait("/tmp/a.txt".to_string())
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("not in the mounted folder"));
+    }
+
+    #[tokio::test]
+    async fn test_files_pin_file_returns_before_pinning() {
+        let manager = Arc::new(FakeCacheManager::default());
+        let state = Arc::new(Mutex::new(DaemonState::default()));
+        state.lock().await.cache_manager = Some(Arc::clone(&manager) as _);
+        let files = FilesInterface::new(state);
+
+        files
+            .pin_file("/home/user/OneDrive/Photos".to_string())
+            .await;
+        assert!(manager.pins.lock().unwrap().is_empty());
+
+        // The pin runs in the background
+        for _ in 0..100 {
+            if !manager.pins.lock().unwrap().is_empty() {
+                break;
+            }
+            tokio::task::yield_now().await;
+        }
+        assert_eq!(
+            *manager.pins.lock().unwrap(),
+            vec![PathBuf::from("/home/user/OneDrive/Photos")]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_hydration_observer_emits_files_signals() {
+        use zbus::export::futures_util::StreamExt;
+
+        let (server_socket, client_socket) = std::os::unix::net::UnixStream::pair().unwrap();
+        let server = zbus::connection::Builder::unix_stream(server_socket)
+            .server(zbus::Guid::generate())
+            .unwrap()
+            .p2p()
+            .serve_at(
+                DBUS_PATH,
+                FilesInterface::new(Arc::new(Mutex::new(DaemonState::default()))),
+            )
+            .unwrap()
+            .build();
+        let client = zbus::connection::Builder::unix_stream(client_socket)
+            .p2p()
+            .build();
+        let (server, client) = tokio::try_join!(server, client).unwrap();
+        let mut signals = zbus::MessageStream::from(&client);
+
+        let observer = DbusHydrationObserver::spawn(&server);
+        let path = "/home/user/OneDrive/report.pdf".to_string();
+        observer.on_hydration_event(HydrationEvent::Progress {
+            path: path.clone(),
+            percent: 40,
+        });
+        observer.on_hydration_event(HydrationEvent::Hydrated { path: path.clone() });
+        observer.on_hydration_event(HydrationEvent::Dehydrated { path: path.clone() });
+
+        let mut received = Vec::new();
+        while received.len() < 3 {
+            let message = tokio::time::timeout(Duration::from_secs(5), signals.next())
+                .await
+                .expect("signal not received")
+                .unwrap()
+                .unwrap();
+            let header = message.header();
+            if header.interface().map(|i| i.as_str()) != Some("com.enigmora.LNXDrive.Files") {
+                continue;
+            }
+            let member = header.member().unwrap().to_string();
+            let body = message.body();
+            let args = match member.as_str() {
+                "HydrationProgress" => {
+                    let (path, percent): (String, u8) = body.deserialize().unwrap();
+                    format!("{path} {percent}")
+                }
+                _ => body.deserialize::<String>().unwrap(),
+            };
+            received.push((member, args));
+        }
+
+        assert_eq!(
+            received,
+            vec![
+                ("HydrationProgress".to_string(), format!("{path} 40")),
+                ("Hydrated".to_string(), path.clone()),
+                ("Dehydrated".to_string(), path),
+            ]
+        );
+    }
 }
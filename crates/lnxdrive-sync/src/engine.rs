@@ -1,39 +1,125 @@
 //! Delta synchronization engine
 //!
 //! The [`SyncEngine`] orchestrates bidirectional synchronization between
-//! the local filesystem and a cloud provider (OneDrive via Microsoft Graph).
+//! the local filesystem and a cloud provider. It only talks to the cloud
+//! through the [`ICloudProvider`] port, so any backend works: OneDrive via
+//! Microsoft Graph in production, or a
+//! [`LocalFolderProvider`](crate::local_provider::LocalFolderProvider) in tests.
 //!
 //! ## Sync Flow
 //!
 //! 1. **Remote changes** (pull): Query delta, process creates/updates/deletes
+//!    page by page while the provider fetches the next page
 //! 2. **Local changes** (push): Scan filesystem, upload new/modified, delete remote
 //! 3. **Bookkeeping**: Update delta token, complete session, return summary
 //!
+//! ## Conflicts
+//!
+//! A remote update to a file that also changed locally is a conflict. The
+//! configured conflict policy decides per path whether it is resolved
+//! automatically (`keep_local`, `keep_remote`, `keep_both`, or
+//! `keep_newer`, which compares the two modification times) or left
+//! `Conflicted` for the user. A remote entry whose path is taken locally by
+//! an entry of the other type (file vs directory) is a type conflict; one
+//! left for manual resolution leaves the local entry `Conflicted` like a
+//! content conflict, and the rest of the delta is applied. A remote
+//! delete of a file that changed locally is a delete conflict: the local
+//! edit is never discarded unless the policy says `keep_remote`.
+//!
+//! With `conflicts.quarantine_after_days`, a conflict left unresolved that
+//! long is quarantined: both versions are saved to [`QUARANTINE_DIR`] with
+//! a manifest, and the local version is uploaded so the file syncs again.
+//!
+//! A tracked item that shows up in the delta under a new path was renamed
+//! or moved in the cloud. It is renamed locally in place, keeping its
+//! SyncItem (and with it the pin and hydration state), instead of being
+//! deleted and downloaded again.
+//!
+//! Deletes are idempotent: deleting a remote item that is already gone
+//! counts as success, so a file deleted on both sides between two syncs
+//! is simply forgotten.
+//!
+//! ## Hardlinks
+//!
+//! OneDrive has no hardlinks. When several new paths in the sync root
+//! share an inode, only one of them (the tracked one, or else the first in
+//! path order) is uploaded; the others are recorded with
+//! [`ItemMetadata::hardlink_of`] pointing at it and are not uploaded. A
+//! download that replaces the primary file recreates the links afterwards.
+//! Limitations:
+//! - other devices only see the primary path
+//! - a link that is broken locally (e.g. an editor saving through a
+//!   temporary file) is uploaded as an independent file on the next scan,
+//!   as is a link whose primary was deleted
+//! - hardlinks are only detected within the sync root
+//!
+//! [`ItemMetadata::hardlink_of`]: lnxdrive_core::domain::sync_item::ItemMetadata::hardlink_of
+//!
 //! ## Retry Logic
 //!
 //! Transient errors (network, rate limiting, server errors) are retried with
 //! exponential backoff: 1s, 2s, 4s, 8s, 16s (max 5 retries).
 
-use std::{sync::Arc, time::Duration};
+use std::{
+    collections::{HashMap, HashSet},
+    os::unix::fs::MetadataExt,
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicBool, AtomicI64, AtomicU64, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
 
 use anyhow::{Context, Result};
 use chrono::{DateTime, Utc};
+use lnxdrive_conflict::{
+    conflict_copy_path, ConflictDetector, ConflictError, ConflictResolver, DetectionMode,
+    DetectionResult, EntryKind, EntryState, Fingerprint, PolicyEngine, ResolutionStep,
+};
 use lnxdrive_core::{
-    config::Config,
+    config::{expand_tilde, Config},
     domain::{
-        newtypes::{DeltaToken, FileHash, RemoteId, RemotePath, SyncPath},
+        account::Account,
+        audit::{AuditAction, AuditEntry, AuditResult},
+        clock::ClockSkew,
+        conflict::{Conflict, Resolution, ResolutionSource, VersionInfo},
+        errors::DomainError,
+        limits::ProviderLimits,
+        mime::detect_mime_type,
+        newtypes::{AccountId, DeltaToken, FileHash, RemoteId, RemotePath, SyncPath, UniqueId},
+        quickxor::QuickXorHash,
+        quota::DriveQuota,
+        reason::ReasonCode,
         session::SyncSession,
-        sync_item::SyncItem,
+        sync_history::SyncHistoryEntry,
+        sync_item::{ErrorInfo, ItemState, SyncItem},
     },
     ports::{
-        cloud_provider::{DeltaItem, ICloudProvider},
-        local_filesystem::ILocalFileSystem,
-        state_repository::IStateRepository,
+        cloud_provider::{
+            is_append_not_supported, is_delta_token_expired, is_remote_item_not_found, CommitCheck,
+            DeltaItem, ICloudProvider,
+        },
+        item_observer::IItemObserver,
+        local_filesystem::{FileSystemState, ILocalFileSystem},
+        notification::{INotificationService, Notification},
+        state_repository::{IStateRepository, ItemFilter},
+        transfer_control::{is_transfer_paused, TransferControl, TransferPaused},
+        transfer_progress::{ITransferObserver, TransferKind, TransferProgressReporter},
     },
 };
 use tokio::sync::mpsc;
 use tracing::{debug, error, info, warn};
 
+use crate::{
+    exclusion::SyncExclusions,
+    filesystem::{is_lock_file, mtime_is_reliable, to_utc},
+    normalization::NameNormalization,
+    plan::{DeltaCursor, LocalStep, RemoteStep, SkipReason, SyncOperation, SyncPlan, SyncSide},
+    selective::{self, FolderSelection, SelectionOutcome, SelectionProgress},
+    ItemContext, SyncError, SyncStep,
+};
+
 // ============================================================================
 // T186: FileWatcher integration - re-export ChangeEvent from watcher module
 // ============================================================================
@@ -44,7 +130,7 @@ pub use crate::watcher::ChangeEvent;
 // ============================================================================
 
 /// Summary of a completed synchronization cycle
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub struct SyncResult {
     /// Number of files downloaded from the cloud
     pub files_downloaded: u32,
@@ -52,27 +138,197 @@ pub struct SyncResult {
     pub files_uploaded: u32,
     /// Number of files deleted (locally or remotely)
     pub files_deleted: u32,
+    /// Bytes downloaded from the cloud
+    pub bytes_downloaded: u64,
+    /// Bytes uploaded to the cloud
+    pub bytes_uploaded: u64,
+    /// Conflicts detected between local and remote changes
+    pub conflicts_detected: u32,
+    /// Conflicts resolved automatically by the conflict policy
+    pub conflicts_auto_resolved: u32,
+    /// Local files moved to [`RECOVERED_DIR`] because their remote folder
+    /// was deleted
+    pub files_recovered: u32,
+    /// Unresolved conflicts moved to [`QUARANTINE_DIR`] because they
+    /// exceeded `conflicts.quarantine_after_days`
+    pub conflicts_quarantined: u32,
+    /// Uploads skipped because the file does not fit in the remaining quota
+    /// or exceeds a provider limit
+    pub uploads_blocked: u32,
+    /// Uploads put off because another process holds the file locked;
+    /// they are retried in a later cycle
+    pub uploads_deferred: u32,
+    /// Uploads and downloads not started or interrupted because transfers
+    /// are paused
+    pub transfers_paused: u32,
+    /// Rounds in which remote changes were read and applied; more than one
+    /// if the delta held more than `delta.max_in_flight_items` changes
+    pub remote_batches: u32,
     /// Errors encountered during the sync (non-fatal)
     pub errors: Vec<String>,
     /// Wall-clock duration of the sync in milliseconds
     pub duration_ms: u64,
 }
 
+impl SyncResult {
+    /// Converts the result into a persisted history record
+    pub fn to_history_entry(&self, started_at: DateTime<Utc>) -> SyncHistoryEntry {
+        SyncHistoryEntry::completed(
+            started_at,
+            self.files_downloaded,
+            self.files_uploaded,
+            self.files_deleted,
+            self.bytes_downloaded,
+            self.bytes_uploaded,
+            self.errors.clone(),
+            self.duration_ms,
+        )
+    }
+}
+
+// ============================================================================
+// ReconcileReport
+// ============================================================================
+
+/// Summary of a startup reconciliation scan
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ReconcileReport {
+    /// Number of tracked files whose on-disk state was checked
+    pub files_checked: u32,
+    /// Number of files whose content had to be hashed (size or mtime differed)
+    pub files_hashed: u32,
+    /// Tracked files whose content changed and were marked for upload
+    pub modified: u32,
+    /// Untracked local files that will be uploaded by the next sync
+    pub created: u32,
+    /// Tracked files missing locally that will be deleted remotely by the next sync
+    pub deleted: u32,
+    /// Wall-clock duration of the scan in milliseconds
+    pub duration_ms: u64,
+}
+
+impl ReconcileReport {
+    /// Total number of changes found by the scan
+    pub fn total_changes(&self) -> u32 {
+        self.modified + self.created + self.deleted
+    }
+}
+
+// ============================================================================
+// UploadBudget
+// ============================================================================
+
+/// Storage left for uploads during one sync cycle
+///
+/// Fetched from the provider before the first upload of a cycle and then
+/// tracked locally, so a cycle costs at most one quota request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum UploadBudget {
+    /// Not fetched yet
+    Unknown,
+    /// No limit, or the quota could not be fetched
+    Unlimited,
+    /// Bytes still available
+    Remaining(u64),
+}
+
+impl UploadBudget {
+    /// Deducts an upload of `bytes`
+    fn consume(&mut self, bytes: u64) {
+        if let UploadBudget::Remaining(remaining) = self {
+            *remaining = remaining.saturating_sub(bytes);
+        }
+    }
+
+    /// Credits `bytes` freed by a delete
+    fn release(&mut self, bytes: u64) {
+        if let UploadBudget::Remaining(remaining) = self {
+            *remaining = remaining.saturating_add(bytes);
+        }
+    }
+}
+
+impl From<DriveQuota> for UploadBudget {
+    fn from(quota: DriveQuota) -> Self {
+        match quota.remaining() {
+            Some(remaining) => UploadBudget::Remaining(remaining),
+            None => UploadBudget::Unlimited,
+        }
+    }
+}
+
+/// Compares the remote hash of a file with the stored one to tell whether
+/// its content changed
+fn remote_content_changed(delta_item: &DeltaItem, existing: &SyncItem) -> bool {
+    match (
+        delta_item.hash.as_deref(),
+        existing.content_hash().map(|h| h.as_str()),
+    ) {
+        (Some(remote), Some(stored)) => remote != stored,
+        (Some(_), None) => true, // New hash, assume changed
+        (None, _) => false,      // No remote hash, can't compare
+    }
+}
+
+/// Returns true if `err` was caused by an upload that was not attempted
+/// because it exceeds the quota or a provider limit
+fn is_upload_blocked(err: &anyhow::Error) -> bool {
+    err.chain().any(|cause| {
+        matches!(
+            cause.downcast_ref::<SyncError>(),
+            Some(SyncError::QuotaExceeded { .. } | SyncError::LimitExceeded { .. })
+        )
+    })
+}
+
+/// Determines whether an upload was put off because the file is locked
+fn is_upload_deferred(err: &anyhow::Error) -> bool {
+    err.chain().any(|cause| {
+        matches!(
+            cause.downcast_ref::<SyncError>(),
+            Some(SyncError::FileLocked(_))
+        )
+    })
+}
+
 // ============================================================================
 // T157: LocalChange - represents a detected local change
 // ============================================================================
 
 /// A local filesystem change detected during scanning
 #[derive(Debug, Clone)]
-enum LocalChange {
+pub(crate) enum LocalChange {
     /// A new file or directory that has no SyncItem counterpart
     Created(SyncPath),
     /// An existing file whose content has changed
     Modified(SyncPath, SyncItem),
     /// A SyncItem whose local file no longer exists
     Deleted(SyncItem),
+    /// A new path that is a hardlink to `primary`, whose content is
+    /// uploaded instead
+    Hardlinked { path: SyncPath, primary: SyncPath },
+}
+
+impl LocalChange {
+    /// Returns whether the change affects `scope` or one of its parents
+    ///
+    /// Deletes only count inside `scope`.
+    fn in_scope(&self, scope: &Path) -> bool {
+        let path = match self {
+            LocalChange::Deleted(item) => {
+                return item.local_path().as_path().starts_with(scope);
+            }
+            LocalChange::Created(path)
+            | LocalChange::Modified(path, _)
+            | LocalChange::Hardlinked { path, .. } => path.as_path(),
+        };
+        path.starts_with(scope) || scope.starts_with(path)
+    }
 }
 
+/// Local paths sharing an inode, keyed by `(device, inode)`
+type HardlinkGroups = HashMap<(u64, u64), Vec<SyncPath>>;
+
 // ============================================================================
 // T161: Retry logic
 // ============================================================================
@@ -83,6 +339,98 @@ const MAX_RETRIES: u32 = 5;
 /// Base delay for exponential backoff (1 second)
 const BASE_DELAY_SECS: u64 = 1;
 
+/// Requests rejected with HTTP 429 since the process started
+static THROTTLED_REQUESTS: AtomicU64 = AtomicU64::new(0);
+
+/// Returns the number of requests the cloud rejected with HTTP 429 since
+/// the process started
+pub fn throttled_requests() -> u64 {
+    THROTTLED_REQUESTS.load(Ordering::Relaxed)
+}
+
+/// Determines whether an error reports rate limiting (HTTP 429)
+fn is_throttled_error(err_str: &str) -> bool {
+    err_str.contains("429")
+        || err_str.contains("too many requests")
+        || err_str.contains("rate limit")
+}
+
+/// Determines whether an error reports a network failure
+fn is_network_error(err_str: &str) -> bool {
+    err_str.contains("network")
+        || err_str.contains("connection")
+        || err_str.contains("timeout")
+        || err_str.contains("timed out")
+        || err_str.contains("dns")
+        || err_str.contains("reset by peer")
+        || err_str.contains("broken pipe")
+}
+
+/// Extracts the HTTP error status from an error message, as formatted by
+/// reqwest ("client error (423 Locked)") or the upload session ("status
+/// 507 Insufficient Storage")
+fn http_error_status(err_str: &str) -> Option<u16> {
+    ["error (", "status "].iter().find_map(|marker| {
+        let (_, rest) = err_str.split_once(marker)?;
+        let status: u16 = rest.get(..3)?.parse().ok()?;
+        (400..600).contains(&status).then_some(status)
+    })
+}
+
+/// Classifies an error into the [`ReasonCode`] recorded for its item
+///
+/// Typed errors in the chain ([`SyncError`], domain, conflict and I/O
+/// errors) give their own code. Errors of the cloud provider arrive as
+/// messages, so they are classified by their HTTP status and wording.
+pub fn error_reason(err: &anyhow::Error) -> ReasonCode {
+    for cause in err.chain() {
+        if let Some(e) = cause.downcast_ref::<SyncError>() {
+            return e.reason();
+        }
+        if let Some(e) = cause.downcast_ref::<DomainError>() {
+            return e.reason();
+        }
+        if let Some(e) = cause.downcast_ref::<ConflictError>() {
+            return e.reason();
+        }
+        if let Some(e) = cause.downcast_ref::<std::io::Error>() {
+            let reason = ReasonCode::from_io_error(e);
+            if reason != ReasonCode::Unknown {
+                return reason;
+            }
+        }
+    }
+
+    let err_str = format!("{err:#}").to_lowercase();
+    let by_status = http_error_status(&err_str).map(ReasonCode::from_http_status);
+    if let Some(reason) = by_status.filter(|r| *r != ReasonCode::Unknown) {
+        return reason;
+    }
+    if is_throttled_error(&err_str) {
+        ReasonCode::RateLimited
+    } else if err_str.contains("unauthorized")
+        || err_str.contains("invalidauthenticationtoken")
+        || err_str.contains("invalid_grant")
+    {
+        ReasonCode::AuthError
+    } else if err_str.contains("quota") || err_str.contains("insufficient storage") {
+        ReasonCode::QuotaExceeded
+    } else if err_str.contains("invalidname") || err_str.contains("invalid name") {
+        ReasonCode::InvalidName
+    } else if err_str.contains("locked") {
+        ReasonCode::Locked
+    } else if err_str.contains("accessdenied")
+        || err_str.contains("access denied")
+        || err_str.contains("permission denied")
+    {
+        ReasonCode::PermissionDenied
+    } else if is_network_error(&err_str) {
+        ReasonCode::NetworkError
+    } else {
+        ReasonCode::Unknown
+    }
+}
+
 /// Determines whether an error is transient (retryable)
 ///
 /// Transient errors include:
@@ -90,24 +438,20 @@ const BASE_DELAY_SECS: u64 = 1;
 /// - Rate limiting (HTTP 429)
 /// - Server errors (HTTP 5xx)
 fn is_transient_error(err: &anyhow::Error) -> bool {
+    // Sending the same bytes again cannot help; the file is read again
+    if is_changed_during_upload(err) {
+        return false;
+    }
+
     let err_str = format!("{err:#}").to_lowercase();
 
     // Network errors
-    if err_str.contains("network")
-        || err_str.contains("connection")
-        || err_str.contains("timeout")
-        || err_str.contains("dns")
-        || err_str.contains("reset by peer")
-        || err_str.contains("broken pipe")
-    {
+    if is_network_error(&err_str) {
         return true;
     }
 
     // Rate limiting
-    if err_str.contains("429")
-        || err_str.contains("too many requests")
-        || err_str.contains("rate limit")
-    {
+    if is_throttled_error(&err_str) {
         return true;
     }
 
@@ -149,6 +493,9 @@ where
                 return Ok(value);
             }
             Err(err) => {
+                if is_throttled_error(&format!("{err:#}").to_lowercase()) {
+                    THROTTLED_REQUESTS.fetch_add(1, Ordering::Relaxed);
+                }
                 if attempt < MAX_RETRIES && is_transient_error(&err) {
                     let delay_secs = BASE_DELAY_SECS * 2u64.pow(attempt);
                     warn!(
@@ -182,14 +529,51 @@ enum DeltaAction {
     Updated,
     /// A file was deleted locally
     Deleted,
-    /// No action was needed (unchanged or metadata-only update)
+    /// A directory was deleted locally after `recovered` files inside it
+    /// were moved to [`RECOVERED_DIR`]
+    DeletedWithRecovery { recovered: u32 },
+    /// No action was needed
     Skipped,
+    /// The content is unchanged; the item with refreshed metadata still
+    /// has to be saved, which the caller does in batches
+    Unchanged(Box<SyncItem>),
+    /// The item was renamed or moved in place; its content is unchanged
+    Renamed,
+    /// A conflict was detected and left for manual resolution
+    Conflicted,
+    /// A conflict was resolved by the policy; `downloaded` is true when
+    /// the remote version replaced the local file
+    ConflictResolved { downloaded: bool },
+    /// An untracked local file already had the remote content, so the
+    /// remote item was recorded for it without a transfer
+    Adopted,
+}
+
+/// Outcome of [`SyncEngine::handle_local_create`]
+enum LocalCreateAction {
+    /// The entry was created in the cloud; holds the bytes uploaded
+    Uploaded(u64),
+    /// A remote file already had the name, see
+    /// [`SyncEngine::reconcile_name_collision`]
+    Reconciled(DeltaAction),
 }
 
 // ============================================================================
 // T151: SyncEngine struct
 // ============================================================================
 
+/// Folder under the sync root that receives local files rescued from a
+/// remotely deleted folder
+///
+/// It is never uploaded: the local scan skips it.
+pub const RECOVERED_DIR: &str = ".lnxdrive-recovered";
+
+/// Folder under the sync root that receives both versions of conflicts
+/// left unresolved for `conflicts.quarantine_after_days`
+///
+/// It is never uploaded: the local scan skips it.
+pub const QUARANTINE_DIR: &str = ".lnxdrive-conflicts";
+
 /// Default bulk mode detection threshold (number of items)
 const BULK_MODE_THRESHOLD: u64 = 1000;
 
@@ -201,6 +585,23 @@ const BULK_MODE_MAX_CONCURRENT: u32 = 4;
 #[allow(dead_code)]
 const BULK_MODE_BATCH_DELAY_MS: u64 = 2000;
 
+/// How often a running transfer checks whether it was paused
+const PAUSE_POLL_INTERVAL: Duration = Duration::from_millis(200);
+
+/// Times a file that changed during its upload is read and uploaded
+/// again before it is left to the next sync cycle
+const MAX_UPLOAD_DRIFT_RETRIES: u32 = 3;
+
+/// A local file as uploaded: the provider's metadata, the bytes sent and
+/// the file's state, unchanged since it was read
+struct UploadedFile {
+    delta_item: DeltaItem,
+    data: Vec<u8>,
+    fs_state: FileSystemState,
+    /// Uploads discarded because the file changed
+    retries: u32,
+}
+
 /// Bidirectional synchronization engine
 ///
 /// Coordinates delta queries, local scanning, and file transfers between
@@ -213,7 +614,7 @@ const BULK_MODE_BATCH_DELAY_MS: u64 = 2000;
 /// - `local_filesystem`: Local file I/O, hashing, and directory operations
 /// - `large_file_threshold`: Byte threshold for choosing upload method
 pub struct SyncEngine {
-    /// Cloud storage provider (OneDrive via Graph API)
+    /// Cloud storage provider (any [`ICloudProvider`] implementation)
     cloud_provider: Arc<dyn ICloudProvider + Send + Sync>,
     /// Persistent state store
     state_repository: Arc<dyn IStateRepository + Send + Sync>,
@@ -221,6 +622,20 @@ pub struct SyncEngine {
     local_filesystem: Arc<dyn ILocalFileSystem + Send + Sync>,
     /// Files larger than this (in bytes) use resumable upload sessions
     large_file_threshold: u64,
+    /// File size and path length limits checked before uploading
+    limits: ProviderLimits,
+    /// Hidden and junk entries left out of sync
+    exclusions: SyncExclusions,
+    /// The FUSE mount point and content cache, never synced even when
+    /// they sit inside the sync root
+    own_dirs: Vec<PathBuf>,
+    /// Unicode normalization of uploaded names and of matching remote
+    /// names to local ones
+    names: NameNormalization,
+    /// Remote changes held in memory before they are applied
+    max_in_flight_items: usize,
+    /// Unchanged delta items saved per repository transaction
+    transaction_size: usize,
     /// T186: Receiver for filesystem watcher events
     ///
     /// When set, the engine can consume real-time change events from
@@ -237,6 +652,45 @@ pub struct SyncEngine {
     /// - Delays are added between batches (2 seconds)
     /// - Rate limiting becomes more conservative
     bulk_mode: bool,
+    /// Whether the next sync must rescan every file, ignoring `last_sync`
+    ///
+    /// Set when the watcher reports lost events (e.g. inotify overflow),
+    /// since the mtime shortcut cannot be trusted to catch every change.
+    reconcile_requested: AtomicBool,
+    /// Pattern rules deciding how detected conflicts are resolved
+    conflict_policy: PolicyEngine,
+    /// Decides whether a local file must be hashed to detect changes
+    change_detector: ConflictDetector,
+    /// Age from which unresolved conflicts are quarantined; `None` keeps
+    /// them until the user resolves them
+    quarantine_after: Option<chrono::Duration>,
+    /// Receives per-file progress of large uploads and downloads
+    transfer_observer: Option<Arc<dyn ITransferObserver>>,
+    /// Tells the user about events that need their attention
+    notifier: Option<Arc<dyn INotificationService>>,
+    /// Follows renames applied from the cloud (e.g. the FUSE inode table)
+    item_observer: Option<Arc<dyn IItemObserver>>,
+    /// Pause switches checked before and during every transfer
+    transfer_control: Arc<TransferControl>,
+    /// Set on shutdown: the running sync finishes its current item and
+    /// starts no new work
+    draining: AtomicBool,
+    /// Number of uploads and downloads completed since the engine started
+    transfers_completed: AtomicU64,
+    /// Number of remote and local changes processed since the engine started
+    items_processed: AtomicU64,
+    /// Files whose upload is currently blocked by the storage quota, so
+    /// each is reported once rather than on every cycle
+    quota_blocked: std::sync::Mutex<HashSet<PathBuf>>,
+    /// Seconds the local clock is ahead of the cloud's, see
+    /// [`set_clock_skew`](Self::set_clock_skew)
+    clock_skew_secs: AtomicI64,
+    /// Remote folders that are synced
+    selection: std::sync::RwLock<FolderSelection>,
+    /// Set once the provider refused an append, so later uploads skip it
+    append_unsupported: AtomicBool,
+    /// Account synced; the default account if `None`
+    account_id: Option<AccountId>,
 }
 
 impl SyncEngine {
@@ -253,13 +707,47 @@ impl SyncEngine {
         local_filesystem: Arc<dyn ILocalFileSystem + Send + Sync>,
         config: &Config,
     ) -> Self {
+        // A bad rule is left out rather than disabling the whole policy
+        let (conflict_policy, errors) = PolicyEngine::from_config_lenient(&config.conflicts);
+        for err in errors {
+            warn!(%err, "Ignoring invalid conflict policy setting");
+        }
+
         Self {
             cloud_provider,
             state_repository,
             local_filesystem,
             large_file_threshold: config.large_files.threshold_mb * 1024 * 1024,
+            limits: config.limits.provider_limits(),
+            exclusions: SyncExclusions::from_config(&config.sync),
+            own_dirs: [&config.fuse.mount_point, &config.fuse.cache_dir]
+                .into_iter()
+                .map(|dir| expand_tilde(Path::new(dir)))
+                .collect(),
+            names: NameNormalization::from_config(&config.sync),
+            max_in_flight_items: config.delta.max_in_flight_items.max(1),
+            transaction_size: config.delta.transaction_size.max(1),
             watcher_rx: None,
             bulk_mode: false,
+            reconcile_requested: AtomicBool::new(false),
+            conflict_policy,
+            change_detector: ConflictDetector::with_mode(DetectionMode::from_config_value(
+                &config.conflicts.change_detection,
+            )),
+            quarantine_after: (config.conflicts.quarantine_after_days > 0)
+                .then(|| chrono::Duration::days(config.conflicts.quarantine_after_days.into())),
+            transfer_observer: None,
+            notifier: None,
+            item_observer: None,
+            transfer_control: Arc::new(TransferControl::new()),
+            draining: AtomicBool::new(false),
+            transfers_completed: AtomicU64::new(0),
+            items_processed: AtomicU64::new(0),
+            quota_blocked: std::sync::Mutex::new(HashSet::new()),
+            clock_skew_secs: AtomicI64::new(0),
+            selection: std::sync::RwLock::new(FolderSelection::everything()),
+            append_unsupported: AtomicBool::new(false),
+            account_id: None,
         }
     }
 
@@ -367,544 +855,2900 @@ impl SyncEngine {
     }
 
     // ========================================================================
-    // T152: SyncEngine::sync()
+    // Per-file transfer progress
     // ========================================================================
 
-    /// Performs a full bidirectional synchronization cycle
+    /// Sets the observer notified about the progress of large transfers
     ///
-    /// 1. Gets the default account from the state repository
-    /// 2. Creates a new SyncSession
-    /// 3. Queries the cloud for delta changes
-    /// 4. Processes each remote delta item (create/update/delete)
-    /// 5. Scans the local filesystem for changes
-    /// 6. Processes each local change (upload/delete)
-    /// 7. Updates the delta token on the account
-    /// 8. Completes the session
+    /// Only files above the large-file threshold are reported, so small
+    /// files synced in bulk do not generate a stream of events.
+    pub fn set_transfer_observer(&mut self, observer: Arc<dyn ITransferObserver>) {
+        self.transfer_observer = Some(observer);
+    }
+
+    /// Sets the service used to notify the user, e.g. about recovered files
+    pub fn set_notifier(&mut self, notifier: Arc<dyn INotificationService>) {
+        self.notifier = Some(notifier);
+    }
+
+    /// Sets the observer told about items renamed or moved in the cloud
+    pub fn set_item_observer(&mut self, observer: Arc<dyn IItemObserver>) {
+        self.item_observer = Some(observer);
+    }
+
+    /// Shares the pause switches with the adapters that set them
     ///
-    /// # Returns
-    /// A [`SyncResult`] summarizing the sync cycle
+    /// Without this the engine has its own switches, which nothing pauses.
+    pub fn set_transfer_control(&mut self, control: Arc<TransferControl>) {
+        self.transfer_control = control;
+    }
+
+    /// Syncs `account_id` instead of the default account
+    pub fn set_account(&mut self, account_id: AccountId) {
+        self.account_id = Some(account_id);
+    }
+
+    /// Sets the measured skew between the local clock and the cloud's
     ///
-    /// # Errors
-    /// Returns an error if no account is configured or if the sync cycle fails
-    #[tracing::instrument(skip(self))]
-    pub async fn sync(&self) -> Result<SyncResult> {
-        let start = std::time::Instant::now();
-        let mut result = SyncResult {
-            files_downloaded: 0,
-            files_uploaded: 0,
-            files_deleted: 0,
-            errors: Vec::new(),
-            duration_ms: 0,
+    /// When the skew is significant, local modification times are shifted
+    /// to server time before conflicts are compared with remote ones, so
+    /// a wrong local clock does not make one side look newer.
+    pub fn set_clock_skew(&self, skew: ClockSkew) {
+        self.clock_skew_secs.store(skew.seconds, Ordering::Relaxed);
+    }
+
+    /// Converts a local timestamp to server time if the clock is skewed
+    fn to_server_time(&self, local: DateTime<Utc>) -> DateTime<Utc> {
+        let skew = ClockSkew::from_secs(self.clock_skew_secs.load(Ordering::Relaxed));
+        if skew.is_significant() {
+            skew.to_server_time(local)
+        } else {
+            local
+        }
+    }
+
+    /// Runs a transfer of `path`, stopping it as soon as transfers of the
+    /// path are paused
+    ///
+    /// A paused transfer is not started at all. A transfer stopped midway
+    /// is dropped; chunked uploads keep the checkpoint of their last
+    /// accepted chunk and continue from it on the next attempt. Both fail
+    /// with [`TransferPaused`].
+    async fn run_transfer<T>(
+        &self,
+        path: &SyncPath,
+        transfer: impl std::future::Future<Output = Result<T>>,
+    ) -> Result<T> {
+        let paused = || TransferPaused {
+            path: path.to_string(),
         };
+        if self.transfer_control.is_paused(path.as_path()) {
+            return Err(paused().into());
+        }
+        tokio::pin!(transfer);
+        loop {
+            tokio::select! {
+                outcome = &mut transfer => return outcome,
+                _ = tokio::time::sleep(PAUSE_POLL_INTERVAL) => {
+                    if self.transfer_control.is_paused(path.as_path()) {
+                        info!(path = %path, "Transfer paused");
+                        return Err(paused().into());
+                    }
+                }
+            }
+        }
+    }
 
-        // Step 1: Get the default account
-        let mut account = self
-            .state_repository
-            .get_default_account()
-            .await
-            .context("Failed to query default account")?
-            .ok_or_else(|| {
-                anyhow::anyhow!("No account configured. Run 'lnxdrive auth login' first.")
-            })?;
+    /// Sends a notification if a notifier is set; failures are only logged
+    async fn notify(&self, notification: Notification) {
+        if let Some(notifier) = &self.notifier {
+            if let Err(err) = notifier.notify(&notification).await {
+                warn!(%err, title = %notification.title, "Failed to send notification");
+            }
+        }
+    }
+
+    /// Creates a progress reporter for a transfer of `bytes_total` bytes,
+    /// or `None` when no observer is set or the file is not large
+    fn transfer_reporter(
+        &self,
+        path: &SyncPath,
+        kind: TransferKind,
+        bytes_total: u64,
+    ) -> Option<Arc<TransferProgressReporter>> {
+        let observer = self.transfer_observer.as_ref()?;
+        if bytes_total <= self.large_file_threshold {
+            return None;
+        }
+        let reporter = TransferProgressReporter::new(
+            Arc::clone(observer),
+            path.to_string(),
+            kind,
+            bytes_total,
+        );
+        reporter.report(0);
+        Some(Arc::new(reporter))
+    }
+
+    // ========================================================================
+    // Shutdown drain
+    // ========================================================================
+
+    /// Stops the engine from starting new work
+    ///
+    /// A running [`sync()`](SyncEngine::sync) lets the transfer in flight
+    /// finish, then returns early without advancing the delta token, so
+    /// the skipped items are picked up by the next sync after restart.
+    pub fn begin_drain(&self) {
+        info!("Draining: no new transfers will be started");
+        self.draining.store(true, Ordering::Release);
+    }
+
+    /// Returns whether the engine is draining for shutdown
+    pub fn is_draining(&self) -> bool {
+        self.draining.load(Ordering::Acquire)
+    }
+
+    /// Returns the number of uploads and downloads completed so far
+    pub fn transfers_completed(&self) -> u64 {
+        self.transfers_completed.load(Ordering::Relaxed)
+    }
+
+    fn record_transfer(&self) {
+        self.transfers_completed.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Returns the number of remote and local changes processed so far
+    ///
+    /// It grows steadily while a sync runs, so a caller can tell a long
+    /// sync from a stuck one.
+    pub fn items_processed(&self) -> u64 {
+        self.items_processed.load(Ordering::Relaxed)
+    }
+
+    // ========================================================================
+    // Reconciliation scan
+    // ========================================================================
+
+    /// Requests that the next sync cycle performs a full reconciliation scan
+    ///
+    /// During a reconciliation scan every local file is hashed and compared
+    /// against its stored state, instead of skipping files whose mtime
+    /// predates the last sync. The request is consumed by the next call to
+    /// [`sync()`](SyncEngine::sync).
+    pub fn request_reconciliation(&self) {
+        info!("Reconciliation scan requested for next sync cycle");
+        self.reconcile_requested.store(true, Ordering::Release);
+    }
+
+    /// Returns whether a reconciliation scan is pending
+    pub fn is_reconciliation_requested(&self) -> bool {
+        self.reconcile_requested.load(Ordering::Acquire)
+    }
+
+    // ========================================================================
+    // Startup reconciliation
+    // ========================================================================
 
+    /// Reconciles the local sync root against the stored sync state
+    ///
+    /// Intended to run once at startup to catch changes made while the
+    /// daemon was stopped, which neither the watcher nor the delta query
+    /// can observe. For every tracked file:
+    /// - If the file is missing locally it is counted as a pending deletion
+    /// - If size and mtime match the stored state it is assumed unchanged
+    /// - Otherwise it is hashed; a changed hash marks the item `Modified`
+    ///
+    /// Untracked files under the sync root are counted as pending creations,
+    /// except those the local scan skips (recovery and quarantine folders,
+    /// excluded entries, the FUSE mount point and cache).
+    /// The changes themselves are applied by the next [`sync()`](SyncEngine::sync).
+    #[tracing::instrument(skip(self))]
+    pub async fn reconcile(&self) -> Result<ReconcileReport> {
+        use lnxdrive_core::{domain::sync_item::ItemState, ports::state_repository::ItemFilter};
+
+        let start = std::time::Instant::now();
+        let mut report = ReconcileReport::default();
+
+        let account = self.sync_account().await?;
         let sync_root = account.sync_root().clone();
 
-        info!(
-            account_id = %account.id(),
-            sync_root = %sync_root,
-            "Starting sync cycle"
-        );
+        info!(sync_root = %sync_root, "Starting reconciliation scan");
 
-        // Step 2: Create a new SyncSession
-        let mut session = SyncSession::new(*account.id());
-        self.state_repository
-            .save_session(&session)
+        let items = self
+            .state_repository
+            .query_items(&ItemFilter::new().with_account_id(*account.id()))
             .await
-            .context("Failed to save initial sync session")?;
+            .context("Failed to query sync items for reconciliation")?;
 
-        // Step 3: Query delta (T167/T168/T170: delta token persistence and 410 Gone handling)
-        let delta_token = account.delta_token().cloned();
-        if let Some(ref token) = delta_token {
-            session.set_delta_token_start(token.clone());
-        }
+        let mut tracked = std::collections::HashSet::with_capacity(items.len());
 
-        let delta_response = match with_retry("get_delta", || {
-            let token_ref = delta_token.as_ref();
-            async move { self.cloud_provider.get_delta(token_ref).await }
-        })
-        .await
-        {
-            Ok(response) => response,
-            Err(err) => {
-                // T168/T170: Handle 410 Gone by clearing delta token and retrying with full resync
-                let err_str = format!("{err:#}");
-                if err_str.contains("410") || err_str.contains("Gone") {
-                    warn!("Delta token expired, performing full resync");
-                    account.clear_delta_token();
-                    self.state_repository
-                        .save_account(&account)
-                        .await
-                        .context("Failed to save account after clearing delta token")?;
+        for mut item in items {
+            tracked.insert(item.local_path().as_path().to_path_buf());
 
-                    // Retry with no token (full resync)
-                    match with_retry("get_delta_full_resync", || async move {
-                        self.cloud_provider.get_delta(None).await
-                    })
-                    .await
-                    {
-                        Ok(response) => response,
-                        Err(retry_err) => {
-                            let reason =
-                                format!("Failed to query delta (full resync): {retry_err}");
-                            error!(%reason);
-                            session.fail(&reason);
-                            self.state_repository.save_session(&session).await.ok();
-                            return Err(retry_err.context("Delta query failed (full resync)"));
-                        }
-                    }
-                } else {
-                    let reason = format!("Failed to query delta: {err}");
-                    error!(%reason);
-                    session.fail(&reason);
-                    self.state_repository.save_session(&session).await.ok();
-                    return Err(err.context("Delta query failed"));
-                }
+            if item.is_directory() || matches!(item.state(), ItemState::Deleted) {
+                continue;
             }
-        };
 
-        let total_remote = delta_response.items.len();
-        info!(
-            items = total_remote,
-            has_delta_link = delta_response.delta_link.is_some(),
-            "Delta query returned"
-        );
+            let fs_state = self
+                .local_filesystem
+                .get_state(item.local_path())
+                .await
+                .context("Failed to check local state during reconciliation")?;
+            report.files_checked += 1;
 
-        // T171: Track delta efficiency metrics
-        session.set_items_checked(total_remote as u64);
-        let mut items_synced: u64 = 0;
+            if !fs_state.exists {
+                if item.remote_id().is_some() {
+                    debug!(path = %item.local_path(), "Tracked file missing locally");
+                    report.deleted += 1;
+                }
+                continue;
+            }
 
-        // Step 4: Process remote delta items
-        for delta_item in &delta_response.items {
-            match self.process_delta_item(delta_item, &sync_root).await {
-                Ok(action) => match action {
-                    DeltaAction::Downloaded => {
-                        result.files_downloaded += 1;
-                        items_synced += 1;
-                    }
-                    DeltaAction::Deleted => {
-                        result.files_deleted += 1;
-                        items_synced += 1;
-                    }
-                    DeltaAction::Updated => {
-                        result.files_downloaded += 1;
-                        items_synced += 1;
-                    }
-                    DeltaAction::Skipped => {}
-                },
+            let unchanged = match item.last_modified_local() {
+                Some(_) => self.matches_recorded_metadata(&item, fs_state.size, fs_state.modified),
+                // Items synced before mtimes were recorded
+                None => {
+                    self.change_detector.mode() == DetectionMode::Quick
+                        && fs_state.size == item.size_bytes()
+                        && fs_state
+                            .modified
+                            .zip(item.last_sync())
+                            .is_some_and(|(on_disk, synced)| on_disk <= synced)
+                }
+            };
+            if unchanged {
+                continue;
+            }
+
+            report.files_hashed += 1;
+            let local_hash = match self.local_filesystem.compute_hash(item.local_path()).await {
+                Ok(hash) => hash,
                 Err(err) => {
-                    let msg = format!(
-                        "Error processing delta item '{}' ({}): {err}",
-                        delta_item.name, delta_item.id
-                    );
-                    warn!(%msg);
-                    result.errors.push(msg);
-                    session.record_failure();
+                    warn!(path = %item.local_path(), error = %err, "Failed to hash file");
                     continue;
                 }
+            };
+
+            let stored_hash = item.local_hash().or(item.content_hash());
+            if stored_hash == Some(&local_hash) {
+                // Only metadata changed; remember the new mtime to skip hashing next time
+                if let Some(modified) = fs_state.modified {
+                    item.set_last_modified_local(modified);
+                    self.state_repository.save_item(&item).await?;
+                }
+                continue;
             }
-            session.record_success();
-        }
 
-        // Step 5: Scan for local changes (T172: pass last_sync for optimization)
-        let last_sync = account.last_sync();
-        let local_changes = match self.scan_local_changes(&sync_root, last_sync).await {
-            Ok(changes) => changes,
-            Err(err) => {
-                let msg = format!("Failed to scan local changes: {err}");
+            debug!(path = %item.local_path(), "File changed while daemon was stopped");
+            report.modified += 1;
+            if matches!(item.state(), ItemState::Modified) {
+                continue;
+            }
+            if let Err(err) = item.mark_modified() {
+                debug!(path = %item.local_path(), error = %err, "Cannot mark item as modified");
+                continue;
+            }
+            self.state_repository.save_item(&item).await?;
+        }
+
+        // Look for files that appeared while the daemon was stopped
+        let mut dirs = vec![sync_root.as_path().to_path_buf()];
+        while let Some(dir) = dirs.pop() {
+            let mut entries = match tokio::fs::read_dir(&dir).await {
+                Ok(entries) => entries,
+                Err(err) => {
+                    warn!(path = %dir.display(), error = %err, "Failed to read directory");
+                    continue;
+                }
+            };
+            while let Some(entry) = entries.next_entry().await? {
+                let path = entry.path();
+                let metadata = entry.metadata().await?;
+                if let Some(reason) = self.skip_reason(&path, &metadata) {
+                    debug!(path = %path.display(), "Skipping {reason}");
+                    continue;
+                }
+                if metadata.is_dir() {
+                    dirs.push(path);
+                } else if metadata.is_file() && !tracked.contains(&path) {
+                    report.created += 1;
+                }
+            }
+        }
+
+        report.duration_ms = start.elapsed().as_millis() as u64;
+
+        info!(
+            checked = report.files_checked,
+            hashed = report.files_hashed,
+            modified = report.modified,
+            created = report.created,
+            deleted = report.deleted,
+            duration_ms = report.duration_ms,
+            "Reconciliation scan completed"
+        );
+
+        Ok(report)
+    }
+
+    // ========================================================================
+    // T152: SyncEngine::sync()
+    // ========================================================================
+
+    /// Performs a full bidirectional synchronization cycle
+    ///
+    /// Plans the cycle with [`plan()`](Self::plan) and applies the plan
+    /// with [`execute()`](Self::execute).
+    ///
+    /// # Returns
+    /// A [`SyncResult`] summarizing the sync cycle
+    ///
+    /// # Errors
+    /// Returns an error if no account is configured or if the sync cycle fails
+    #[tracing::instrument(skip(self))]
+    pub async fn sync(&self) -> Result<SyncResult> {
+        let plan = self.plan().await?;
+        self.execute(plan).await
+    }
+
+    // ========================================================================
+    // Sync plans
+    // ========================================================================
+
+    /// Plans a full synchronization cycle without applying anything
+    ///
+    /// 1. Gets the synced account from the state repository
+    /// 2. Queries the cloud for delta changes since the stored token
+    /// 3. Predicts the operation of each remote delta item
+    /// 4. Scans the local filesystem for changes
+    /// 5. Predicts the operation of each local change
+    ///
+    /// # Errors
+    /// Returns an error if no account is configured or if the delta query
+    /// fails
+    #[tracing::instrument(skip(self))]
+    pub async fn plan(&self) -> Result<SyncPlan> {
+        let account = self.sync_account().await?;
+        self.build_plan(account, None).await
+    }
+
+    /// Plans the synchronization of one file or folder, see
+    /// [`sync_path()`](Self::sync_path)
+    ///
+    /// # Errors
+    /// Returns an error if no account is configured, if `path` is outside
+    /// the sync root, or if the delta query fails
+    #[tracing::instrument(skip(self))]
+    pub async fn plan_path(&self, path: &Path) -> Result<SyncPlan> {
+        let account = self.sync_account().await?;
+        let scope = SyncPath::new_within_root(path.to_path_buf(), account.sync_root())
+            .map_err(|e| anyhow::anyhow!("Cannot sync {}: {e}", path.display()))?;
+        self.build_plan(account, Some(scope)).await
+    }
+
+    /// Returns the account every sync runs for
+    ///
+    /// The account chosen with [`set_account`](Self::set_account), or else
+    /// the default account.
+    async fn sync_account(&self) -> Result<Account> {
+        let account = match &self.account_id {
+            Some(account_id) => self
+                .state_repository
+                .get_account(account_id)
+                .await
+                .context("Failed to query account")?,
+            None => self
+                .state_repository
+                .get_default_account()
+                .await
+                .context("Failed to query default account")?,
+        };
+        account.ok_or_else(|| {
+            anyhow::anyhow!("No account configured. Run 'lnxdrive auth login' first.")
+        })
+    }
+
+    /// Builds the plan of `account`, restricted to `scope` if given
+    async fn build_plan(&self, account: Account, scope: Option<SyncPath>) -> Result<SyncPlan> {
+        let sync_root = account.sync_root().clone();
+        info!(
+            account_id = %account.id(),
+            sync_root = %sync_root,
+            scope = scope.as_ref().map(|s| s.to_string()),
+            "Planning sync"
+        );
+
+        // A page walk an earlier cycle left unfinished (e.g. a restart in
+        // the middle of a large delta) continues where it was applied up
+        // to. If it cannot, the delta is read again from the token, and
+        // the changes applied meanwhile are found unchanged.
+        let resumed_pages = match account.delta_next_link().filter(|_| scope.is_none()) {
+            Some(next_link) => {
+                let next_link = next_link.to_string();
+                match with_retry("resume_delta", || {
+                    let link = next_link.as_str();
+                    async move { self.cloud_provider.resume_delta_pages(link).await }
+                })
+                .await
+                {
+                    Ok(pages) => {
+                        info!("Resuming interrupted delta enumeration");
+                        Some(pages)
+                    }
+                    Err(err) => {
+                        warn!(
+                            "Cannot resume interrupted delta enumeration, starting over: {err:#}"
+                        );
+                        None
+                    }
+                }
+            }
+            None => None,
+        };
+        let resumed = resumed_pages.is_some();
+
+        // T167/T168/T170: delta token persistence and 410 Gone handling.
+        // An expired token means a full resync; the token is only cleared
+        // once the plan is executed.
+        let delta_token = account.delta_token().cloned();
+        let mut full_resync = false;
+        let delta_pages = match resumed_pages {
+            Some(pages) => Ok(pages),
+            None => {
+                with_retry("get_delta", || {
+                    let token_ref = delta_token.as_ref();
+                    async move { self.cloud_provider.get_delta_pages(token_ref).await }
+                })
+                .await
+            }
+        };
+        let delta_pages = match delta_pages {
+            Ok(pages) => pages,
+            Err(err) if scope.is_none() && is_delta_token_expired(&err) => {
+                warn!("Delta token expired, performing full resync");
+                full_resync = true;
+                with_retry("get_delta_full_resync", || async move {
+                    self.cloud_provider.get_delta_pages(None).await
+                })
+                .await
+                .map_err(|err| {
+                    error!("Failed to query delta (full resync): {err}");
+                    err.context("Delta query failed (full resync)")
+                })?
+            }
+            Err(err) => {
+                error!("Failed to query delta: {err}");
+                return Err(err.context("Delta query failed"));
+            }
+        };
+
+        let mut plan = SyncPlan {
+            account,
+            scope,
+            full_resync,
+            reconciliation: false,
+            resumed,
+            delta: DeltaCursor {
+                pages: Some(delta_pages),
+                ..DeltaCursor::default()
+            },
+            last_sync: None,
+            remote: Vec::new(),
+            local: Vec::new(),
+            errors: Vec::new(),
+        };
+
+        // A page that fails to arrive fails the plan, so a delta that fits
+        // in memory is never applied in part
+        self.read_delta_pages(
+            &mut plan.delta,
+            plan.scope.as_ref(),
+            &sync_root,
+            &mut plan.remote,
+        )
+        .await?;
+
+        // T172: the mtime shortcut skips files not modified since the last
+        // sync, unless a reconciliation is pending
+        plan.reconciliation = plan.scope.is_none() && self.is_reconciliation_requested();
+        plan.last_sync = if plan.reconciliation {
+            info!("Running reconciliation scan of the sync root");
+            self.local_filesystem.invalidate_hash_cache();
+            None
+        } else {
+            // The adapter's hash cache is keyed on size and mtime as well
+            if self.change_detector.mode() == DetectionMode::FullHash {
+                self.local_filesystem.invalidate_hash_cache();
+            }
+            plan.account.last_sync()
+        };
+        match self.scan_local_changes(&sync_root, plan.last_sync).await {
+            Ok(changes) => {
+                plan.local = self.plan_local_changes(changes, plan.scope.as_ref()).await;
+            }
+            Err(err) => {
+                let msg = format!("Failed to scan local changes: {err}");
                 warn!(%msg);
-                result.errors.push(msg);
-                Vec::new()
+                plan.errors.push(msg);
             }
+        }
+
+        let summary = plan.summary();
+        info!(
+            remote_items = plan.delta.items_checked,
+            downloads = summary.downloads,
+            uploads = summary.uploads,
+            deletes = summary.deletes,
+            conflicts = summary.conflicts,
+            "Sync planned"
+        );
+        Ok(plan)
+    }
+
+    /// Reads the unread pages of `delta` into `remote` until the delta
+    /// ends or `remote` holds `delta.max_in_flight_items` changes
+    ///
+    /// The pages still unread are left in `delta`. Pages are read whole,
+    /// so at most one page more than the limit is held.
+    ///
+    /// # Errors
+    /// Returns an error if a page fails to arrive
+    async fn read_delta_pages(
+        &self,
+        delta: &mut DeltaCursor,
+        scope: Option<&SyncPath>,
+        sync_root: &SyncPath,
+        remote: &mut Vec<RemoteStep>,
+    ) -> Result<()> {
+        let Some(mut pages) = delta.pages.take() else {
+            return Ok(());
         };
+        while remote.len() < self.max_in_flight_items {
+            let Some(page) = pages.next_page().await else {
+                return Ok(());
+            };
+            let page = page.map_err(|err| {
+                error!("Failed to fetch delta page: {err}");
+                err.context("Delta query failed")
+            })?;
+            delta.items_checked += page.items.len();
+            delta.next_link = page.next_link;
+            if page.delta_link.is_some() {
+                delta.delta_link = page.delta_link;
+            }
+            for item in page.items {
+                if let Some(scope) = scope {
+                    if !self.delta_item_in_scope(&item, scope, sync_root).await {
+                        continue;
+                    }
+                }
+                let operation = self.plan_delta_item(&item, sync_root).await;
+                remote.push(RemoteStep { item, operation });
+            }
+        }
+        debug!(
+            held = remote.len(),
+            limit = self.max_in_flight_items,
+            "Delta holds more changes than fit in memory, reading the rest later"
+        );
+        delta.pages = Some(pages);
+        Ok(())
+    }
 
-        info!(changes = local_changes.len(), "Local changes detected");
+    /// Predicts the operation [`process_delta_item`](Self::process_delta_item)
+    /// applies for `delta_item`, without changing anything
+    async fn plan_delta_item(&self, delta_item: &DeltaItem, sync_root: &SyncPath) -> SyncOperation {
+        let tracked = match RemoteId::new(delta_item.id.clone()) {
+            Ok(remote_id) => self
+                .state_repository
+                .get_item_by_remote_id(&remote_id)
+                .await
+                .ok()
+                .flatten(),
+            Err(_) => None,
+        };
+        let remote_path = delta_item.path.as_deref().unwrap_or(&delta_item.name);
+        let path = self.local_path_of(remote_path, sync_root);
 
-        // Step 6: Process local changes
-        for change in &local_changes {
-            match change {
-                LocalChange::Created(path) => {
-                    match self.handle_local_create(path, &sync_root).await {
-                        Ok(()) => {
-                            result.files_uploaded += 1;
-                            items_synced += 1;
-                            session.record_success();
-                        }
-                        Err(err) => {
-                            let msg = format!("Error uploading new file '{}': {err}", path);
-                            warn!(%msg);
-                            result.errors.push(msg);
-                            session.record_failure();
-                        }
+        if delta_item.is_deleted {
+            return match tracked {
+                Some(item) if !item.is_directory() && self.has_local_changes(&item).await => {
+                    SyncOperation::Conflict {
+                        path: item.local_path().as_path().to_path_buf(),
                     }
                 }
-                LocalChange::Modified(path, existing) => {
-                    match self.handle_local_update(path, existing, &sync_root).await {
-                        Ok(()) => {
-                            result.files_uploaded += 1;
-                            items_synced += 1;
-                            session.record_success();
+                Some(item) => SyncOperation::Delete {
+                    path: item.local_path().as_path().to_path_buf(),
+                    side: SyncSide::Local,
+                },
+                None => SyncOperation::Skip {
+                    path,
+                    reason: SkipReason::NotTracked,
+                },
+            };
+        }
+
+        if self
+            .exclusions
+            .excludes(Path::new(""), Path::new(remote_path))
+        {
+            return SyncOperation::Skip {
+                path,
+                reason: SkipReason::Excluded,
+            };
+        }
+        if !self.is_selected(remote_path, delta_item.is_directory) {
+            return SyncOperation::Skip {
+                path,
+                reason: SkipReason::NotSelected,
+            };
+        }
+
+        let size = delta_item.size.unwrap_or(0);
+        if let Some(item) = tracked {
+            if remote_content_changed(delta_item, &item) {
+                if self.has_local_changes(&item).await {
+                    SyncOperation::Conflict { path }
+                } else {
+                    SyncOperation::Download { path, size }
+                }
+            } else if item.local_path().as_path() != path.as_path() {
+                SyncOperation::Move {
+                    from: item.local_path().as_path().to_path_buf(),
+                    to: path,
+                }
+            } else {
+                SyncOperation::Skip {
+                    path,
+                    reason: SkipReason::Unchanged,
+                }
+            }
+        } else {
+            let state = match SyncPath::new(path.clone()) {
+                Ok(local_path) => self
+                    .local_filesystem
+                    .get_state(&local_path)
+                    .await
+                    .unwrap_or_else(|_| FileSystemState::not_found()),
+                Err(_) => FileSystemState::not_found(),
+            };
+            if state.exists && state.is_file == delta_item.is_directory {
+                SyncOperation::Conflict { path }
+            } else if !delta_item.is_directory {
+                SyncOperation::Download { path, size }
+            } else if state.exists {
+                SyncOperation::Skip {
+                    path,
+                    reason: SkipReason::Unchanged,
+                }
+            } else {
+                SyncOperation::CreateDir {
+                    path,
+                    side: SyncSide::Local,
+                }
+            }
+        }
+    }
+
+    /// Plans the local `changes` that fall inside `scope`, if given
+    async fn plan_local_changes(
+        &self,
+        changes: Vec<LocalChange>,
+        scope: Option<&SyncPath>,
+    ) -> Vec<LocalStep> {
+        let mut steps = Vec::with_capacity(changes.len());
+        for change in changes {
+            if scope.is_some_and(|scope| !change.in_scope(scope.as_path())) {
+                continue;
+            }
+            let operation = self.plan_local_change(&change).await;
+            steps.push(LocalStep { change, operation });
+        }
+        steps
+    }
+
+    /// Predicts the operation applying a local change, without changing
+    /// anything
+    async fn plan_local_change(&self, change: &LocalChange) -> SyncOperation {
+        let path = match change {
+            LocalChange::Deleted(item) => {
+                return SyncOperation::Delete {
+                    path: item.local_path().as_path().to_path_buf(),
+                    side: SyncSide::Remote,
+                };
+            }
+            LocalChange::Hardlinked { path, .. } => {
+                return SyncOperation::Skip {
+                    path: path.as_path().to_path_buf(),
+                    reason: SkipReason::Hardlink,
+                };
+            }
+            LocalChange::Created(path) | LocalChange::Modified(path, _) => path,
+        };
+        let state = self
+            .local_filesystem
+            .get_state(path)
+            .await
+            .unwrap_or_else(|_| FileSystemState::not_found());
+        let path = path.as_path().to_path_buf();
+        if state.exists && !state.is_file {
+            SyncOperation::CreateDir {
+                path,
+                side: SyncSide::Remote,
+            }
+        } else if state.is_locked {
+            SyncOperation::Skip {
+                path,
+                reason: SkipReason::Locked,
+            }
+        } else {
+            SyncOperation::Upload {
+                path,
+                size: state.size,
+            }
+        }
+    }
+
+    /// Applies a plan made by [`plan()`](Self::plan) or
+    /// [`plan_path()`](Self::plan_path)
+    ///
+    /// This is the only code path of the engine that changes local files,
+    /// the state database or the cloud.
+    ///
+    /// 1. Creates a new SyncSession (full plans only)
+    /// 2. Processes each remote delta item (create/update/delete). If the
+    ///    delta was larger than `delta.max_in_flight_items`, the pages the
+    ///    plan left unread are planned and applied in further rounds
+    /// 3. Processes each local change (upload/delete). If remote changes
+    ///    were applied, the sync root is scanned again first, since
+    ///    downloads and conflict resolutions change the local tree
+    /// 4. Updates the delta token on the account (full plans only)
+    /// 5. Completes the session
+    ///
+    /// # Errors
+    /// Returns an error if the session or the account cannot be saved
+    #[tracing::instrument(skip_all)]
+    pub async fn execute(&self, plan: SyncPlan) -> Result<SyncResult> {
+        let start = std::time::Instant::now();
+        let SyncPlan {
+            mut account,
+            scope,
+            full_resync,
+            reconciliation,
+            resumed,
+            mut delta,
+            last_sync,
+            mut remote,
+            local,
+            errors,
+        } = plan;
+        let mut result = SyncResult {
+            errors,
+            ..SyncResult::default()
+        };
+        // A plan for one path leaves the session and the delta token to
+        // the next full cycle
+        let full = scope.is_none();
+        let sync_root = account.sync_root().clone();
+
+        info!(
+            account_id = %account.id(),
+            sync_root = %sync_root,
+            remote = remote.len(),
+            local = local.len(),
+            "Starting sync cycle"
+        );
+
+        if reconciliation {
+            self.reconcile_requested.store(false, Ordering::Release);
+        }
+
+        let mut session = SyncSession::new(*account.id());
+        if full {
+            if let Some(token) = account.delta_token() {
+                session.set_delta_token_start(token.clone());
+            }
+            self.state_repository
+                .save_session(&session)
+                .await
+                .context("Failed to save initial sync session")?;
+            if full_resync {
+                account.clear_delta_token();
+                self.state_repository
+                    .save_account(&account)
+                    .await
+                    .context("Failed to save account after clearing delta token")?;
+            }
+        }
+
+        let mut items_synced: u64 = 0;
+        let mut remote_applied = false;
+
+        // Conflicts the user left alone too long stop blocking their files
+        if full {
+            result.conflicts_quarantined = self
+                .quarantine_stale_conflicts(&sync_root, &mut result.errors)
+                .await;
+            // The quarantined files are uploaded by the rescan below
+            remote_applied = result.conflicts_quarantined > 0;
+        }
+
+        // Remote changes. Unchanged items are saved in batches. The batch
+        // is written before any delta item that might touch a buffered
+        // item: a repeated item, a delete or a directory (which may move
+        // or drop its children).
+        let mut pending_saves: Vec<SyncItem> = Vec::new();
+        let mut interrupted = false;
+        let mut delta_complete = true;
+        loop {
+            result.remote_batches += 1;
+            for RemoteStep {
+                item: delta_item, ..
+            } in &remote
+            {
+                if self.is_draining() {
+                    interrupted = true;
+                    break;
+                }
+                self.items_processed.fetch_add(1, Ordering::Relaxed);
+                let touches_pending = delta_item.is_deleted
+                    || delta_item.is_directory
+                    || pending_saves.iter().any(|item| {
+                        item.remote_id()
+                            .is_some_and(|id| id.as_str() == delta_item.id)
+                    });
+                if touches_pending {
+                    self.save_unchanged(&mut pending_saves, &mut result).await;
+                }
+                match self.process_delta_item(delta_item, &sync_root).await {
+                    Ok(action) => {
+                        if !matches!(action, DeltaAction::Skipped | DeltaAction::Unchanged(_)) {
+                            remote_applied = true;
                         }
-                        Err(err) => {
-                            let msg = format!("Error uploading modified file '{}': {err}", path);
-                            warn!(%msg);
-                            result.errors.push(msg);
-                            session.record_failure();
+                        match action {
+                            DeltaAction::Downloaded => {
+                                result.files_downloaded += 1;
+                                result.bytes_downloaded += delta_item.size.unwrap_or(0);
+                                items_synced += 1;
+                                self.record_transfer();
+                            }
+                            DeltaAction::Deleted => {
+                                result.files_deleted += 1;
+                                items_synced += 1;
+                            }
+                            DeltaAction::DeletedWithRecovery { recovered } => {
+                                result.files_deleted += 1;
+                                result.files_recovered += recovered;
+                                items_synced += 1;
+                            }
+                            DeltaAction::Updated => {
+                                result.files_downloaded += 1;
+                                result.bytes_downloaded += delta_item.size.unwrap_or(0);
+                                items_synced += 1;
+                                self.record_transfer();
+                            }
+                            DeltaAction::Skipped => {}
+                            DeltaAction::Unchanged(item) => {
+                                pending_saves.push(*item);
+                                if pending_saves.len() >= self.transaction_size {
+                                    self.save_unchanged(&mut pending_saves, &mut result).await;
+                                }
+                            }
+                            DeltaAction::Renamed => {
+                                items_synced += 1;
+                            }
+                            DeltaAction::Conflicted => {
+                                result.conflicts_detected += 1;
+                            }
+                            DeltaAction::ConflictResolved { downloaded } => {
+                                result.conflicts_detected += 1;
+                                result.conflicts_auto_resolved += 1;
+                                if downloaded {
+                                    result.files_downloaded += 1;
+                                    result.bytes_downloaded += delta_item.size.unwrap_or(0);
+                                    self.record_transfer();
+                                }
+                                items_synced += 1;
+                            }
+                            DeltaAction::Adopted => {
+                                items_synced += 1;
+                            }
                         }
                     }
-                }
-                LocalChange::Deleted(item) => match self.handle_local_delete(item).await {
-                    Ok(()) => {
-                        result.files_deleted += 1;
-                        items_synced += 1;
-                        session.record_success();
+                    Err(err) if is_transfer_paused(&err) => {
+                        result.transfers_paused += 1;
+                        continue;
                     }
                     Err(err) => {
-                        let msg =
-                            format!("Error deleting remote item '{}': {err}", item.local_path());
+                        let context = ItemContext::new(SyncStep::ApplyRemoteChange)
+                            .with_remote_id(Some(&delta_item.id))
+                            .with_remote_path(
+                                delta_item.path.as_deref().unwrap_or(&delta_item.name),
+                            );
+                        let err = SyncError::item(context, err);
+                        let msg = format!("{err:#}");
                         warn!(%msg);
                         result.errors.push(msg);
+                        self.audit_remote_failure(delta_item, &err).await;
                         session.record_failure();
+                        continue;
                     }
-                },
+                }
+                session.record_success();
+            }
+            self.save_unchanged(&mut pending_saves, &mut result).await;
+            if interrupted {
+                break;
+            }
+
+            // Record how far the page walk was applied, so a restart goes
+            // on from there. A walk that ended needs no resumption; one
+            // whose changes must be retried continues from an earlier page.
+            if full {
+                let next_link = if delta.pages.is_none() {
+                    None
+                } else if result.transfers_paused == 0 {
+                    delta.next_link.clone()
+                } else {
+                    account.delta_next_link().map(str::to_string)
+                };
+                self.save_delta_next_link(&mut account, next_link).await;
+            }
+
+            // A delta larger than the in-flight limit is applied in rounds;
+            // the token is only advanced once every page was applied
+            if delta.pages.is_none() {
+                break;
+            }
+            remote.clear();
+            if let Err(err) = self
+                .read_delta_pages(&mut delta, scope.as_ref(), &sync_root, &mut remote)
+                .await
+            {
+                result.errors.push(format!("{err:#}"));
+                delta_complete = false;
+                break;
+            }
+            if remote.is_empty() && delta.pages.is_none() {
+                break;
+            }
+        }
+
+        info!(
+            items = delta.items_checked,
+            has_delta_link = delta.delta_link.is_some(),
+            resumed,
+            "Delta query processed"
+        );
+
+        // T171: Track delta efficiency metrics
+        session.set_items_checked(delta.items_checked as u64);
+
+        let local = if interrupted {
+            Vec::new()
+        } else if remote_applied {
+            match self.scan_local_changes(&sync_root, last_sync).await {
+                Ok(changes) => self.plan_local_changes(changes, scope.as_ref()).await,
+                Err(err) => {
+                    let msg = format!("Failed to scan local changes: {err}");
+                    warn!(%msg);
+                    result.errors.push(msg);
+                    Vec::new()
+                }
+            }
+        } else {
+            local
+        };
+
+        info!(changes = local.len(), "Local changes detected");
+
+        // Local changes
+        let mut budget = UploadBudget::Unknown;
+        let blocked_before = self.quota_blocked_count();
+        for LocalStep { change, .. } in &local {
+            if self.is_draining() {
+                interrupted = true;
+                break;
+            }
+            self.items_processed.fetch_add(1, Ordering::Relaxed);
+            match change {
+                LocalChange::Created(path) => {
+                    match self
+                        .handle_local_create(path, &sync_root, &mut budget)
+                        .await
+                    {
+                        Ok(LocalCreateAction::Uploaded(bytes)) => {
+                            result.files_uploaded += 1;
+                            result.bytes_uploaded += bytes;
+                            items_synced += 1;
+                            session.record_success();
+                            self.record_transfer();
+                        }
+                        Ok(LocalCreateAction::Reconciled(action)) => {
+                            match action {
+                                DeltaAction::Conflicted => result.conflicts_detected += 1,
+                                DeltaAction::ConflictResolved { downloaded } => {
+                                    result.conflicts_detected += 1;
+                                    result.conflicts_auto_resolved += 1;
+                                    if downloaded {
+                                        result.files_downloaded += 1;
+                                        self.record_transfer();
+                                    }
+                                    items_synced += 1;
+                                }
+                                _ => items_synced += 1,
+                            }
+                            session.record_success();
+                        }
+                        Err(err) if is_upload_blocked(&err) => {
+                            result.uploads_blocked += 1;
+                        }
+                        Err(err) if is_upload_deferred(&err) => {
+                            info!(path = %path, "File is locked, deferring its upload");
+                            result.uploads_deferred += 1;
+                            self.audit_failure(AuditAction::FileUpload, path, &err)
+                                .await;
+                        }
+                        Err(err) if is_transfer_paused(&err) => {
+                            result.transfers_paused += 1;
+                        }
+                        Err(err) => {
+                            let context = ItemContext::new(SyncStep::UploadNew)
+                                .with_local_path(path.as_path().clone());
+                            let err = SyncError::item(context, err);
+                            let msg = format!("{err:#}");
+                            warn!(%msg);
+                            result.errors.push(msg);
+                            self.audit_failure(AuditAction::FileUpload, path, &err)
+                                .await;
+                            session.record_failure();
+                        }
+                    }
+                }
+                LocalChange::Modified(path, existing) => {
+                    match self
+                        .handle_local_update(path, existing, &sync_root, &mut budget)
+                        .await
+                    {
+                        Ok(bytes) => {
+                            result.files_uploaded += 1;
+                            result.bytes_uploaded += bytes;
+                            items_synced += 1;
+                            session.record_success();
+                            self.record_transfer();
+                        }
+                        Err(err) if is_upload_blocked(&err) => {
+                            result.uploads_blocked += 1;
+                        }
+                        Err(err) if is_upload_deferred(&err) => {
+                            info!(path = %path, "File is locked, deferring its upload");
+                            result.uploads_deferred += 1;
+                            self.audit_failure(AuditAction::FileUpload, path, &err)
+                                .await;
+                        }
+                        Err(err) if is_transfer_paused(&err) => {
+                            result.transfers_paused += 1;
+                        }
+                        Err(err) => {
+                            let context = ItemContext::for_item(SyncStep::UploadModified, existing);
+                            let err = SyncError::item(context, err);
+                            let msg = format!("{err:#}");
+                            warn!(%msg);
+                            result.errors.push(msg);
+                            self.audit_failure(AuditAction::FileUpload, path, &err)
+                                .await;
+                            session.record_failure();
+                        }
+                    }
+                }
+                LocalChange::Hardlinked { path, primary } => {
+                    match self.handle_local_hardlink(path, primary, &sync_root).await {
+                        Ok(true) => {
+                            items_synced += 1;
+                            session.record_success();
+                        }
+                        Ok(false) => {}
+                        Err(err) => {
+                            let context = ItemContext::new(SyncStep::RecordHardlink)
+                                .with_local_path(path.as_path().clone());
+                            let err = SyncError::item(context, err);
+                            let msg = format!("{err:#}");
+                            warn!(%msg);
+                            result.errors.push(msg);
+                            session.record_failure();
+                        }
+                    }
+                }
+                LocalChange::Deleted(item) => match self.handle_local_delete(item).await {
+                    Ok(()) => {
+                        budget.release(item.size_bytes());
+                        result.files_deleted += 1;
+                        items_synced += 1;
+                        session.record_success();
+                    }
+                    Err(err) => {
+                        let err = SyncError::item(
+                            ItemContext::for_item(SyncStep::DeleteRemote, item),
+                            err,
+                        );
+                        let msg = format!("{err:#}");
+                        warn!(%msg);
+                        result.errors.push(msg);
+                        self.audit_failure(AuditAction::FileDelete, item.local_path(), &err)
+                            .await;
+                        session.record_failure();
+                    }
+                },
+            }
+        }
+
+        let newly_blocked = self.quota_blocked_count().saturating_sub(blocked_before);
+        if newly_blocked > 0 {
+            self.notify(
+                Notification::error(
+                    "OneDrive storage is full",
+                    format!(
+                        "{newly_blocked} file(s) were not uploaded because they do not fit in the \
+                     remaining storage. They will be uploaded once space is freed."
+                    ),
+                )
+                .with_category("quota"),
+            )
+            .await;
+        }
+
+        result.duration_ms = start.elapsed().as_millis() as u64;
+        if let Some(scope) = &scope {
+            info!(
+                path = %scope,
+                downloaded = result.files_downloaded,
+                uploaded = result.files_uploaded,
+                deleted = result.files_deleted,
+                errors = result.errors.len(),
+                duration_ms = result.duration_ms,
+                "Sync of a single path completed"
+            );
+            return Ok(result);
+        }
+
+        // T171: Finalize delta efficiency metrics on the session
+        session.set_items_synced(items_synced);
+
+        // Drained for shutdown: keep the old delta token so the skipped
+        // remote items are fetched again on the next start
+        if interrupted {
+            info!(items_synced, "Sync cycle interrupted by shutdown drain");
+            session.cancel();
+            self.state_repository.save_session(&session).await.ok();
+            return Ok(result);
+        }
+
+        debug!(
+            items_checked = session.items_checked(),
+            items_synced = session.items_synced(),
+            efficiency = session.sync_efficiency(),
+            "Delta sync efficiency"
+        );
+
+        // Step 4: Update delta token
+        if result.transfers_paused > 0 {
+            // Paused downloads are fetched again once transfers resume
+            info!(
+                paused = result.transfers_paused,
+                "Transfers are paused; keeping previous delta token"
+            );
+        } else if !delta_complete {
+            // The next cycle reads the delta again from the old token and
+            // finds the changes applied so far unchanged
+            warn!("Delta was not read to the end; keeping previous delta token");
+        } else if let Some(delta_link) = &delta.delta_link {
+            // Extract the token value from the delta link URL
+            // The delta_link is a full URL like:
+            // https://graph.microsoft.com/v1.0/me/drive/root/delta?token=...
+            if let Some(token_str) = extract_token_from_delta_link(delta_link) {
+                match DeltaToken::new(token_str) {
+                    Ok(token) => {
+                        session.set_delta_token_end(token.clone());
+                        account.update_delta_token(token);
+                        account.record_sync(Utc::now());
+                        self.state_repository
+                            .save_account(&account)
+                            .await
+                            .context("Failed to save updated account")?;
+                    }
+                    Err(err) => {
+                        warn!("Failed to create DeltaToken from delta link: {err}");
+                    }
+                }
+            } else {
+                // Use the full delta_link as the token
+                match DeltaToken::new(delta_link.clone()) {
+                    Ok(token) => {
+                        session.set_delta_token_end(token.clone());
+                        account.update_delta_token(token);
+                        account.record_sync(Utc::now());
+                        self.state_repository
+                            .save_account(&account)
+                            .await
+                            .context("Failed to save updated account")?;
+                    }
+                    Err(err) => {
+                        warn!("Failed to create DeltaToken from delta link: {err}");
+                    }
+                }
+            }
+        }
+
+        // Step 5: Complete the session
+        session.complete();
+        self.state_repository
+            .save_session(&session)
+            .await
+            .context("Failed to save completed session")?;
+
+        result.duration_ms = start.elapsed().as_millis() as u64;
+
+        info!(
+            downloaded = result.files_downloaded,
+            uploaded = result.files_uploaded,
+            deleted = result.files_deleted,
+            bytes_down = result.bytes_downloaded,
+            bytes_up = result.bytes_uploaded,
+            errors = result.errors.len(),
+            duration_ms = result.duration_ms,
+            "Sync cycle completed"
+        );
+
+        Ok(result)
+    }
+
+    // ========================================================================
+    // Sync of a single path
+    // ========================================================================
+
+    /// Synchronizes one file or folder right away, ahead of the next cycle
+    ///
+    /// Remote changes from the delta that fall inside `path` are applied
+    /// first, then local changes inside it are uploaded. Changes to the
+    /// parent folders of `path` are applied too, so a new file can be
+    /// placed in a new folder. Everything else is left for the next
+    /// [`sync()`](SyncEngine::sync), which is also why the delta token is
+    /// not advanced: that cycle fetches the skipped changes again and finds
+    /// the ones applied here unchanged.
+    ///
+    /// # Errors
+    /// Returns an error if no account is configured, if `path` is outside
+    /// the sync root, or if the delta query fails
+    #[tracing::instrument(skip(self))]
+    pub async fn sync_path(&self, path: &Path) -> Result<SyncResult> {
+        let plan = self.plan_path(path).await?;
+        self.execute(plan).await
+    }
+
+    /// Returns whether a delta item touches `scope` or one of its parents
+    ///
+    /// Both the item's new path and, for tracked items, its current local
+    /// path count, so renames into and out of the scope are applied.
+    async fn delta_item_in_scope(
+        &self,
+        delta_item: &DeltaItem,
+        scope: &SyncPath,
+        sync_root: &SyncPath,
+    ) -> bool {
+        let tracked = match RemoteId::new(delta_item.id.clone()) {
+            Ok(remote_id) => self
+                .state_repository
+                .get_item_by_remote_id(&remote_id)
+                .await
+                .ok()
+                .flatten(),
+            Err(_) => None,
+        };
+        if let Some(item) = &tracked {
+            if item.local_path().as_path().starts_with(scope.as_path()) {
+                return true;
+            }
+        }
+        if delta_item.is_deleted {
+            return false;
+        }
+        delta_item.path.as_deref().is_some_and(|remote_path| {
+            let local = self.local_path_of(remote_path, sync_root);
+            local.starts_with(scope.as_path()) || scope.as_path().starts_with(&local)
+        })
+    }
+
+    /// Local path of the remote `remote_path`, reusing local entries whose
+    /// names differ only in Unicode normalization
+    fn local_path_of(&self, remote_path: &str, sync_root: &SyncPath) -> PathBuf {
+        self.names.local_path(sync_root.as_path(), remote_path)
+    }
+
+    /// Remote path of the local `path`, with names in NFC if enabled
+    fn remote_path_of(&self, path: &SyncPath, sync_root: &SyncPath) -> Result<String> {
+        let relative = path
+            .relative_to(sync_root)
+            .context("Path is not within sync root")?;
+        // Normalize for Windows-style paths in tests
+        let remote_path = format!("/{}", relative.display()).replace('\\', "/");
+        Ok(self.names.normalize(&remote_path).into_owned())
+    }
+
+    // ========================================================================
+    // Selective sync
+    // ========================================================================
+
+    /// Syncs only the folders of `selection` from now on
+    ///
+    /// Tracked items are left as they are; use
+    /// [`apply_folder_selection`](SyncEngine::apply_folder_selection) to
+    /// change the selection of a running sync. This sets the selection
+    /// stored by a previous run.
+    pub fn set_folder_selection(&self, selection: FolderSelection) {
+        if let Ok(mut current) = self.selection.write() {
+            *current = selection;
+        }
+    }
+
+    /// Returns the folders that are synced
+    pub fn folder_selection(&self) -> FolderSelection {
+        self.selection
+            .read()
+            .map(|selection| selection.clone())
+            .unwrap_or_default()
+    }
+
+    /// Returns whether the remote item at `remote_path` is in the selection
+    fn is_selected(&self, remote_path: &str, is_directory: bool) -> bool {
+        self.selection.read().map_or(true, |selection| {
+            selection.includes(remote_path, is_directory)
+        })
+    }
+
+    /// Changes the synced folders to `selection`
+    ///
+    /// Newly selected folders get placeholders for all their items; no
+    /// content is downloaded. The items of deselected folders stop being
+    /// tracked and their local copies are removed; nothing is deleted in
+    /// the cloud. The new selection, the placeholders and the removals are
+    /// stored in one transaction, so a failure leaves the previous
+    /// selection in place. Adapters are told through the item observer.
+    ///
+    /// # Errors
+    /// Returns an error if no account is configured, if a selected folder
+    /// does not exist in the cloud, if listing it fails, or if a
+    /// deselected folder holds changes that were not uploaded yet
+    #[tracing::instrument(skip(self, progress))]
+    pub async fn apply_folder_selection(
+        &self,
+        selection: FolderSelection,
+        progress: &(dyn Fn(&SelectionProgress) + Send + Sync),
+    ) -> Result<SelectionOutcome> {
+        let account = self.sync_account().await?;
+        let sync_root = account.sync_root().clone();
+        let previous = self.folder_selection();
+
+        let plan = selective::plan_selection(
+            self.cloud_provider.as_ref(),
+            self.state_repository.as_ref(),
+            &self.exclusions,
+            &sync_root,
+            &previous,
+            &selection,
+            progress,
+        )
+        .await?;
+        let removed_ids: Vec<UniqueId> = plan.removed.iter().map(|item| *item.id()).collect();
+        self.state_repository
+            .apply_folder_selection(selection.folders(), &plan.saved, &removed_ids)
+            .await
+            .context("Failed to store the folder selection")?;
+        self.set_folder_selection(selection);
+
+        // The view of the adapters first, so the local copies below are
+        // no longer reachable through them
+        let total = plan.removed.len() as u64;
+        if let Some(observer) = &self.item_observer {
+            for item in plan.removed.iter().rev() {
+                observer.item_removed(item.id());
+            }
+        }
+        for (done, item) in selective::topmost(&plan.removed).into_iter().enumerate() {
+            self.remove_local_copy(item, &plan.removed).await;
+            progress(&SelectionProgress::Removing {
+                done: done as u64 + 1,
+                total,
+            });
+        }
+        if let Some(observer) = &self.item_observer {
+            let mut parents = HashSet::new();
+            for item in &plan.saved {
+                let parent = match item.local_path().as_path().parent() {
+                    Some(parent) if parent != sync_root.as_path() => {
+                        let parent = SyncPath::new(parent.to_path_buf())?;
+                        match self.state_repository.get_item_by_path(&parent).await? {
+                            Some(parent) => Some(*parent.id()),
+                            None => continue,
+                        }
+                    }
+                    _ => None,
+                };
+                if parents.insert(parent) {
+                    observer.children_added(parent.as_ref());
+                }
+            }
+        }
+
+        let outcome = SelectionOutcome {
+            added: plan.saved.len() as u64,
+            removed: total,
+        };
+        info!(
+            added = outcome.added,
+            removed = outcome.removed,
+            "Applied folder selection"
+        );
+        Ok(outcome)
+    }
+
+    /// Removes the local copy of `item`, a deselected item
+    ///
+    /// Folders are only removed once they hold nothing but the local copies
+    /// of other items in `removed`, so untracked local files are kept.
+    async fn remove_local_copy(&self, item: &SyncItem, removed: &[SyncItem]) {
+        if item.is_directory() {
+            let mut inside: Vec<&SyncItem> = removed
+                .iter()
+                .filter(|other| {
+                    other.local_path().as_path() != item.local_path().as_path()
+                        && other
+                            .local_path()
+                            .as_path()
+                            .starts_with(item.local_path().as_path())
+                })
+                .collect();
+            // Files first, then folders deepest first
+            inside.sort_by_key(|other| {
+                (
+                    other.is_directory(),
+                    std::cmp::Reverse(other.local_path().as_path().components().count()),
+                )
+            });
+            for other in inside {
+                self.remove_local_entry(other).await;
+            }
+        }
+        self.remove_local_entry(item).await;
+    }
+
+    /// Removes the file or empty folder of one deselected item, if present
+    async fn remove_local_entry(&self, item: &SyncItem) {
+        let path = item.local_path();
+        match self.local_filesystem.get_state(path).await {
+            Ok(state) if state.exists => {}
+            _ => return,
+        }
+        let result = if item.is_directory() {
+            tokio::fs::remove_dir(path.as_path())
+                .await
+                .map_err(anyhow::Error::from)
+        } else {
+            self.local_filesystem.delete_file(path).await
+        };
+        if let Err(e) = result {
+            debug!(path = %path, error = %e, "Keeping local copy of deselected item");
+        }
+    }
+
+    // ========================================================================
+    // T153: process_delta_item()
+    // ========================================================================
+
+    /// Processes a single delta item from the cloud
+    ///
+    /// Determines the appropriate action based on the item's state:
+    /// - Deleted -> handle_remote_delete
+    /// - Existing (by remote_id) -> handle_remote_update
+    /// - New -> handle_remote_create
+    #[tracing::instrument(skip(self))]
+    async fn process_delta_item(
+        &self,
+        delta_item: &DeltaItem,
+        sync_root: &SyncPath,
+    ) -> Result<DeltaAction> {
+        if delta_item.is_deleted {
+            return self.handle_remote_delete(delta_item, sync_root).await;
+        }
+
+        if let Some(path) = delta_item.path.as_deref() {
+            if self.exclusions.excludes(Path::new(""), Path::new(path)) {
+                debug!(path, "Skipping excluded remote item");
+                return Ok(DeltaAction::Skipped);
+            }
+            if !self.is_selected(path, delta_item.is_directory) {
+                debug!(path, "Skipping remote item outside the selected folders");
+                return Ok(DeltaAction::Skipped);
+            }
+        }
+
+        // Check if we already track this remote item
+        let remote_id =
+            RemoteId::new(delta_item.id.clone()).context("Invalid remote ID in delta item")?;
+
+        let existing = self
+            .state_repository
+            .get_item_by_remote_id(&remote_id)
+            .await
+            .context("Failed to query existing item by remote ID")?;
+
+        if let Some(existing_item) = existing {
+            self.handle_remote_update(delta_item, &existing_item, sync_root)
+                .await
+        } else {
+            self.handle_remote_create(delta_item, sync_root).await
+        }
+    }
+
+    /// Records the failed `action` on the item at `path` in the audit log,
+    /// with the [`ReasonCode`] of `err`
+    async fn audit_failure(&self, action: AuditAction, path: &SyncPath, err: &anyhow::Error) {
+        let item_id = match self.state_repository.get_item_by_path(path).await {
+            Ok(item) => item.map(|item| *item.id()),
+            Err(_) => None,
+        };
+        let details = serde_json::json!({ "path": path.to_string() });
+        self.save_failure_audit(action, item_id, details, err).await;
+    }
+
+    /// Records the failure to apply a delta item in the audit log, with
+    /// the [`ReasonCode`] of `err`
+    async fn audit_remote_failure(&self, delta_item: &DeltaItem, err: &anyhow::Error) {
+        let item_id = match RemoteId::new(delta_item.id.clone()) {
+            Ok(remote_id) => self
+                .state_repository
+                .get_item_by_remote_id(&remote_id)
+                .await
+                .ok()
+                .flatten()
+                .map(|item| *item.id()),
+            Err(_) => None,
+        };
+        let action = if delta_item.is_deleted {
+            AuditAction::FileDelete
+        } else {
+            AuditAction::FileDownload
+        };
+        let details = serde_json::json!({
+            "name": delta_item.name,
+            "remote_id": delta_item.id,
+        });
+        self.save_failure_audit(action, item_id, details, err).await;
+    }
+
+    /// Saves the failed audit entry of `action`, with the code and message
+    /// of `err`
+    ///
+    /// The [`ItemContext`] of `err`, if any, is added to `details` under
+    /// `context`.
+    async fn save_failure_audit(
+        &self,
+        action: AuditAction,
+        item_id: Option<UniqueId>,
+        details: serde_json::Value,
+        err: &anyhow::Error,
+    ) {
+        let reason = error_reason(err);
+        let mut details = details;
+        let context = SyncError::context_of(err);
+        if let (Some(context), Some(details)) = (context, details.as_object_mut()) {
+            if let Ok(context) = serde_json::to_value(context) {
+                details.insert("context".to_string(), context);
+            }
+        }
+        let mut entry = AuditEntry::new(
+            action,
+            AuditResult::failed(reason.as_str(), format!("{err:#}")),
+        )
+        .with_details(details);
+        if let Some(item_id) = item_id.or_else(|| context.and_then(|c| c.item_id)) {
+            entry = entry.with_item_id(item_id);
+        }
+        if let Err(e) = self.state_repository.save_audit(&entry).await {
+            warn!(error = %e, "Failed to record the failure in the audit log");
+        }
+    }
+
+    /// Saves the buffered items of unchanged delta items in one batch
+    /// Saves how far the current delta page walk was applied, see
+    /// [`Account::set_delta_next_link`]
+    async fn save_delta_next_link(&self, account: &mut Account, next_link: Option<String>) {
+        if account.delta_next_link() == next_link.as_deref() {
+            return;
+        }
+        account.set_delta_next_link(next_link);
+        if let Err(err) = self.state_repository.save_account(account).await {
+            warn!("Failed to save delta resumption point: {err}");
+        }
+    }
+
+    async fn save_unchanged(&self, pending: &mut Vec<SyncItem>, result: &mut SyncResult) {
+        if pending.is_empty() {
+            return;
+        }
+        if let Err(err) = self.state_repository.save_items_batch(pending).await {
+            let msg = format!("Failed to save {} unchanged items: {err}", pending.len());
+            warn!(%msg);
+            result.errors.push(msg);
+        }
+        pending.clear();
+    }
+
+    // ========================================================================
+    // T154: handle_remote_create()
+    // ========================================================================
+
+    /// Handles a new item appearing in the cloud
+    ///
+    /// - Directories: Creates the local directory, saves SyncItem as Hydrated
+    /// - Files: Downloads content, writes to local path, creates SyncItem,
+    ///   transitions through Hydrating -> Hydrated
+    #[tracing::instrument(skip(self))]
+    async fn handle_remote_create(
+        &self,
+        delta_item: &DeltaItem,
+        sync_root: &SyncPath,
+    ) -> Result<DeltaAction> {
+        let remote_path_str = delta_item
+            .path
+            .as_deref()
+            .ok_or_else(|| anyhow::anyhow!("Delta item has no path: {}", delta_item.id))?;
+
+        let remote_path = RemotePath::new(remote_path_str.to_string())
+            .context("Invalid remote path in delta item")?;
+        let remote_id =
+            RemoteId::new(delta_item.id.clone()).context("Invalid remote ID in delta item")?;
+
+        // Build local path: sync_root + relative path from remote
+        let local_path = SyncPath::new(self.local_path_of(remote_path_str, sync_root))
+            .context("Failed to construct local path")?;
+        let exact_path = sync_root
+            .as_path()
+            .join(remote_path_str.trim_start_matches('/'));
+        if local_path.as_path() != exact_path.as_path() {
+            // Matched a local name in another Unicode form, which must not
+            // belong to another remote item
+            if let Some(other) = self.state_repository.get_item_by_path(&local_path).await? {
+                if other.remote_id().is_some_and(|id| *id != remote_id) {
+                    return Err(SyncError::NameCollision {
+                        path: exact_path,
+                        other: local_path.as_path().to_path_buf(),
+                    }
+                    .into());
+                }
+            }
+        }
+
+        // Entries under a folder whose path is a conflicted local file wait
+        // for that conflict to be resolved
+        if let Some(parent) = shadowing_local_file(&local_path, sync_root).await {
+            debug!(
+                path = %local_path,
+                parent = %parent.display(),
+                "Skipping entry under a local file"
+            );
+            return Ok(DeltaAction::Skipped);
+        }
+
+        // A local entry of the other type at this path is a type conflict
+        let fs_state = self.local_filesystem.get_state(&local_path).await?;
+        let mut resolved_conflict = false;
+        if fs_state.exists && fs_state.is_file == delta_item.is_directory {
+            let local_kind = EntryKind::from_is_directory(!fs_state.is_file);
+            match self
+                .handle_type_conflict(delta_item, &remote_id, &local_path, local_kind, sync_root)
+                .await?
+            {
+                Some(action) => return Ok(action),
+                None => resolved_conflict = true,
+            }
+        }
+        let created = if resolved_conflict {
+            DeltaAction::ConflictResolved { downloaded: true }
+        } else {
+            DeltaAction::Downloaded
+        };
+
+        // An untracked local file is reconciled with the remote one rather
+        // than overwritten
+        if fs_state.exists
+            && fs_state.is_file
+            && !delta_item.is_directory
+            && self
+                .state_repository
+                .get_item_by_path(&local_path)
+                .await?
+                .is_none()
+        {
+            return self
+                .reconcile_name_collision(&local_path, delta_item, sync_root)
+                .await;
+        }
+
+        if delta_item.is_directory {
+            debug!(path = %local_path, "Creating local directory from remote");
+
+            self.local_filesystem
+                .create_directory(&local_path)
+                .await
+                .context("Failed to create local directory")?;
+
+            let mut item = SyncItem::from_remote(
+                local_path,
+                remote_path,
+                remote_id,
+                true, // is_directory
+                0,
+                None,
+                delta_item.modified.unwrap_or_else(Utc::now),
+            )?;
+
+            // Directories go directly to Hydrated via Hydrating
+            item.start_hydrating()?;
+            item.complete_hydration()?;
+            item.mark_synced();
+
+            self.state_repository
+                .save_item(&item)
+                .await
+                .context("Failed to save new directory SyncItem")?;
+
+            Ok(created)
+        } else {
+            debug!(
+                path = %local_path,
+                size = delta_item.size.unwrap_or(0),
+                "Downloading new file from remote"
+            );
+
+            // Download the file content
+            let reporter = self.transfer_reporter(
+                &local_path,
+                TransferKind::Download,
+                delta_item.size.unwrap_or(0),
+            );
+            let download = self
+                .run_transfer(
+                    &local_path,
+                    with_retry("download_file", || {
+                        let rid = remote_id.clone();
+                        async move { self.cloud_provider.download_file(&rid).await }
+                    }),
+                )
+                .await;
+            if let Some(reporter) = &reporter {
+                reporter.finish(&download);
+            }
+            let data = download.context("Failed to download file")?;
+
+            // Write to local filesystem
+            self.write_download(&local_path, &data, delta_item)
+                .await
+                .context("Failed to write downloaded file")?;
+
+            // Build content hash if available
+            let content_hash = if let Some(ref hash_str) = delta_item.hash {
+                FileHash::new(hash_str.clone()).ok()
+            } else {
+                None
+            };
+
+            // Create and persist the SyncItem
+            let mut item = SyncItem::from_remote(
+                local_path.clone(),
+                remote_path,
+                remote_id,
+                false, // is_file
+                delta_item.size.unwrap_or(data.len() as u64),
+                content_hash,
+                delta_item.modified.unwrap_or_else(Utc::now),
+            )?;
+
+            item.metadata_mut().set_facets(delta_item.facets.clone());
+            item.start_hydrating()?;
+            item.complete_hydration()?;
+            item.mark_synced();
+            self.record_local_state(&mut item).await;
+            warn_on_download_mismatch(&item);
+
+            self.state_repository
+                .save_item(&item)
+                .await
+                .context("Failed to save new file SyncItem")?;
+
+            Ok(created)
+        }
+    }
+
+    // ========================================================================
+    // T155: handle_remote_update()
+    // ========================================================================
+
+    /// Handles an updated item in the cloud
+    ///
+    /// A changed path is applied first as an in-place rename. Then the
+    /// remote content hash is compared with the stored hash. If they differ,
+    /// downloads the new content and updates the local file and SyncItem.
+    /// If the local file has also changed, the conflict policy decides.
+    #[tracing::instrument(skip(self))]
+    async fn handle_remote_update(
+        &self,
+        delta_item: &DeltaItem,
+        existing: &SyncItem,
+        sync_root: &SyncPath,
+    ) -> Result<DeltaAction> {
+        let renamed = self
+            .apply_remote_rename(delta_item, existing, sync_root)
+            .await?;
+        let was_renamed = renamed.is_some();
+        let existing = renamed.as_ref().unwrap_or(existing);
+
+        // For directories, just update metadata
+        if delta_item.is_directory {
+            debug!(
+                path = %existing.local_path(),
+                "Remote directory updated (metadata only)"
+            );
+            return self
+                .metadata_only_update(delta_item, existing, was_renamed)
+                .await;
+        }
+
+        if !remote_content_changed(delta_item, existing) {
+            debug!(
+                path = %existing.local_path(),
+                "Remote file unchanged (hash match)"
+            );
+            return self
+                .metadata_only_update(delta_item, existing, was_renamed)
+                .await;
+        }
+
+        if self.has_local_changes(existing).await {
+            return self.handle_conflict(delta_item, existing, sync_root).await;
+        }
+
+        debug!(
+            path = %existing.local_path(),
+            "Remote file content changed, downloading update"
+        );
+
+        self.apply_remote_update(delta_item, existing).await?;
+
+        Ok(DeltaAction::Updated)
+    }
+
+    /// Refreshes the remote metadata of an item whose content is unchanged
+    ///
+    /// A renamed item is saved right away; otherwise the refreshed item is
+    /// handed back to be saved in a batch with the other unchanged items.
+    async fn metadata_only_update(
+        &self,
+        delta_item: &DeltaItem,
+        existing: &SyncItem,
+        renamed: bool,
+    ) -> Result<DeltaAction> {
+        let mut updated = existing.clone();
+        if let Some(modified) = delta_item.modified {
+            updated.set_last_modified_remote(modified);
+        }
+        updated.metadata_mut().set_facets(delta_item.facets.clone());
+        updated.mark_synced();
+        if renamed {
+            self.state_repository.save_item(&updated).await?;
+            return Ok(DeltaAction::Renamed);
+        }
+        Ok(DeltaAction::Unchanged(Box::new(updated)))
+    }
+
+    /// Applies a rename or move made in the cloud to a tracked item
+    ///
+    /// The local entry is renamed in place and the SyncItem keeps its ID,
+    /// state and pin, so content that is already local is not downloaded
+    /// again. Items below a renamed directory follow it. Returns the
+    /// renamed item, or `None` if the remote path did not change.
+    async fn apply_remote_rename(
+        &self,
+        delta_item: &DeltaItem,
+        existing: &SyncItem,
+        sync_root: &SyncPath,
+    ) -> Result<Option<SyncItem>> {
+        let Some(remote_path_str) = delta_item.path.as_deref() else {
+            return Ok(None);
+        };
+        if existing.remote_path().as_str() == remote_path_str {
+            return Ok(None);
+        }
+
+        let remote_path = RemotePath::new(remote_path_str.to_string())
+            .context("Invalid remote path in delta item")?;
+        let old_path = existing.local_path().clone();
+        let new_path = SyncPath::new(self.local_path_of(remote_path_str, sync_root))
+            .context("Failed to construct local path")?;
+
+        if new_path != old_path {
+            if let Some(other) = self.state_repository.get_item_by_path(&new_path).await? {
+                if other.id() != existing.id() {
+                    anyhow::bail!(
+                        "Cannot apply remote rename of {}: {} is already tracked",
+                        old_path,
+                        new_path
+                    );
+                }
+            }
+
+            if self.local_filesystem.get_state(&old_path).await?.exists {
+                if let Some(parent) = new_path.as_path().parent() {
+                    self.local_filesystem
+                        .create_directory(&SyncPath::new(parent.to_path_buf())?)
+                        .await
+                        .context("Failed to create parent of renamed item")?;
+                }
+                self.local_filesystem
+                    .rename(&old_path, &new_path)
+                    .await
+                    .context("Failed to rename local item")?;
+            }
+        }
+
+        info!(from = %old_path, to = %new_path, "Applying remote rename");
+
+        if existing.is_directory() {
+            self.move_items_under(&old_path, &new_path, &remote_path)
+                .await?;
+        }
+
+        let mut renamed = existing.clone();
+        renamed.update_local_path(new_path.clone());
+        renamed.update_remote_path(remote_path);
+        self.state_repository.save_item(&renamed).await?;
+
+        if let Some(observer) = &self.item_observer {
+            let parent = match new_path.as_path().parent() {
+                Some(parent) if parent != sync_root.as_path() => {
+                    let parent = SyncPath::new(parent.to_path_buf())?;
+                    self.state_repository.get_item_by_path(&parent).await?
+                }
+                _ => None,
+            };
+            let name = new_path
+                .as_path()
+                .file_name()
+                .map(|n| n.to_string_lossy().into_owned())
+                .unwrap_or_default();
+            observer.item_moved(renamed.id(), parent.as_ref().map(|p| p.id()), &name);
+        }
+
+        Ok(Some(renamed))
+    }
+
+    /// Rebases the paths of every item below a directory renamed from
+    /// `old_dir` to `new_dir` (`new_remote` in the cloud)
+    async fn move_items_under(
+        &self,
+        old_dir: &SyncPath,
+        new_dir: &SyncPath,
+        new_remote: &RemotePath,
+    ) -> Result<()> {
+        let items = self
+            .state_repository
+            .query_items(&ItemFilter::new().with_path_prefix(old_dir.clone()))
+            .await?;
+        for mut item in items {
+            let Ok(relative) = item.local_path().as_path().strip_prefix(old_dir.as_path()) else {
+                continue;
+            };
+            if relative.as_os_str().is_empty() {
+                continue;
+            }
+            let local = SyncPath::new(new_dir.as_path().join(relative))?;
+            let remote = RemotePath::new(format!(
+                "{}/{}",
+                new_remote.as_str().trim_end_matches('/'),
+                relative.to_string_lossy()
+            ))?;
+            item.update_local_path(local);
+            item.update_remote_path(remote);
+            self.state_repository.save_item(&item).await?;
+        }
+        Ok(())
+    }
+
+    /// Downloads the remote version of a file over the local copy and
+    /// records the new hashes and metadata on its SyncItem
+    async fn apply_remote_update(&self, delta_item: &DeltaItem, existing: &SyncItem) -> Result<()> {
+        let remote_id = existing
+            .remote_id()
+            .ok_or_else(|| anyhow::anyhow!("Existing item has no remote ID"))?
+            .clone();
+
+        // Download updated content
+        let local_path = existing.local_path();
+        let reporter = self.transfer_reporter(
+            local_path,
+            TransferKind::Download,
+            delta_item.size.unwrap_or(0),
+        );
+        let download = self
+            .run_transfer(
+                local_path,
+                with_retry("download_file_update", || {
+                    let rid = remote_id.clone();
+                    async move { self.cloud_provider.download_file(&rid).await }
+                }),
+            )
+            .await;
+        if let Some(reporter) = &reporter {
+            reporter.finish(&download);
+        }
+        let data = download.context("Failed to download updated file")?;
+
+        // Write to local filesystem
+        let hardlinked = tokio::fs::metadata(local_path.as_path())
+            .await
+            .is_ok_and(|m| m.nlink() > 1);
+        self.write_download(local_path, &data, delta_item)
+            .await
+            .context("Failed to write updated file")?;
+
+        // Update the SyncItem
+        let mut updated = existing.clone();
+        if let Some(ref hash_str) = delta_item.hash {
+            if let Ok(hash) = FileHash::new(hash_str.clone()) {
+                updated.set_content_hash(hash);
+            }
+        }
+        if let Some(size) = delta_item.size {
+            updated.set_size_bytes(size);
+        }
+        if let Some(modified) = delta_item.modified {
+            updated.set_last_modified_remote(modified);
+        }
+        updated.metadata_mut().set_facets(delta_item.facets.clone());
+
+        self.record_local_state(&mut updated).await;
+        warn_on_download_mismatch(&updated);
+
+        // Local edits were overwritten, so the item is in sync again
+        if matches!(updated.state(), ItemState::Modified | ItemState::Conflicted) {
+            updated.transition_to(ItemState::Hydrated)?;
+        }
+        updated.mark_synced();
+        self.state_repository.save_item(&updated).await?;
+
+        if hardlinked {
+            if let Err(err) = self.relink_hardlinks(&updated).await {
+                warn!(path = %local_path, error = %err, "Failed to recreate hardlinks");
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Records the on-disk mtime and local hash of a file that was just synced
+    ///
+    /// The mtime is read first so that a write racing with the hash leaves
+    /// a newer mtime behind and the next scan hashes the file again. Right
+    /// after a transfer the hash comes from the adapter's cache, filled
+    /// while the bytes were streamed, so the file is not read again.
+    /// Writes downloaded content, carrying over the remote modification time
+    async fn write_download(
+        &self,
+        path: &SyncPath,
+        data: &[u8],
+        delta_item: &DeltaItem,
+    ) -> Result<()> {
+        match delta_item.modified {
+            Some(modified) => {
+                self.local_filesystem
+                    .write_file_with_mtime(path, data, modified)
+                    .await
             }
+            None => self.local_filesystem.write_file(path, data).await,
         }
+    }
 
-        // T171: Finalize delta efficiency metrics on the session
-        session.set_items_synced(items_synced);
+    /// Records the local modification time of an uploaded file remotely
+    ///
+    /// On success `delta_item` reports that time. A failure only loses the
+    /// timestamp, so it is logged and the upload still counts.
+    async fn send_local_mtime(&self, delta_item: &mut DeltaItem, modified: Option<DateTime<Utc>>) {
+        let Some(modified) = modified else {
+            return;
+        };
+        let Ok(remote_id) = RemoteId::new(delta_item.id.clone()) else {
+            return;
+        };
+        match self
+            .cloud_provider
+            .set_modified_time(&remote_id, modified)
+            .await
+        {
+            Ok(()) => delta_item.modified = Some(modified),
+            Err(e) => warn!(
+                id = %remote_id,
+                error = %e,
+                "Failed to set the modification time of an uploaded file"
+            ),
+        }
+    }
 
-        debug!(
-            items_checked = session.items_checked(),
-            items_synced = session.items_synced(),
-            efficiency = session.sync_efficiency(),
-            "Delta sync efficiency"
-        );
+    async fn record_local_state(&self, item: &mut SyncItem) {
+        if let Ok(fs_state) = self.local_filesystem.get_state(item.local_path()).await {
+            if let Some(modified) = fs_state.modified {
+                item.set_last_modified_local(modified);
+            }
+        }
+        if let Ok(local_hash) = self.local_filesystem.compute_hash(item.local_path()).await {
+            item.set_local_hash(local_hash);
+        }
+    }
 
-        // Step 7: Update delta token
-        if let Some(delta_link) = &delta_response.delta_link {
-            // Extract the token value from the delta link URL
-            // The delta_link is a full URL like:
-            // https://graph.microsoft.com/v1.0/me/drive/root/delta?token=...
-            if let Some(token_str) = extract_token_from_delta_link(delta_link) {
-                match DeltaToken::new(token_str) {
-                    Ok(token) => {
-                        session.set_delta_token_end(token.clone());
-                        account.update_delta_token(token);
-                        account.record_sync(Utc::now());
-                        self.state_repository
-                            .save_account(&account)
-                            .await
-                            .context("Failed to save updated account")?;
-                    }
-                    Err(err) => {
-                        warn!("Failed to create DeltaToken from delta link: {err}");
+    // ========================================================================
+    // Conflict handling
+    // ========================================================================
+
+    /// Returns true if a file's size and mtime still match its SyncItem
+    ///
+    /// The recorded mtime is the one read right before the file was last
+    /// hashed, so a match means the content is unchanged and hashing can be
+    /// skipped. Mtimes too close to the last sync to reveal later writes
+    /// (see [`mtime_is_reliable`]) never match, and in
+    /// [`DetectionMode::FullHash`] nothing does.
+    fn matches_recorded_metadata(
+        &self,
+        item: &SyncItem,
+        size: u64,
+        modified: Option<DateTime<Utc>>,
+    ) -> bool {
+        let recorded_mtime = item.last_modified_local().filter(|recorded| {
+            item.last_sync()
+                .is_some_and(|synced| mtime_is_reliable(*recorded, synced))
+        });
+        let recorded = Fingerprint::local(item.size_bytes(), recorded_mtime);
+        !self
+            .change_detector
+            .needs_hash(&recorded, &Fingerprint::local(size, modified))
+    }
+
+    /// Returns true if the local copy of a file changed since its last sync
+    ///
+    /// The file is hashed only if its size or mtime changed, see
+    /// [`matches_recorded_metadata`](Self::matches_recorded_metadata).
+    async fn has_local_changes(&self, item: &SyncItem) -> bool {
+        match item.state() {
+            ItemState::Modified | ItemState::Conflicted => true,
+            ItemState::Hydrated | ItemState::Pinned => {
+                if let Ok(fs_state) = self.local_filesystem.get_state(item.local_path()).await {
+                    if fs_state.exists
+                        && self.matches_recorded_metadata(item, fs_state.size, fs_state.modified)
+                    {
+                        return false;
                     }
                 }
-            } else {
-                // Use the full delta_link as the token
-                match DeltaToken::new(delta_link.clone()) {
-                    Ok(token) => {
-                        session.set_delta_token_end(token.clone());
-                        account.update_delta_token(token);
-                        account.record_sync(Utc::now());
-                        self.state_repository
-                            .save_account(&account)
-                            .await
-                            .context("Failed to save updated account")?;
-                    }
-                    Err(err) => {
-                        warn!("Failed to create DeltaToken from delta link: {err}");
-                    }
+                match (
+                    self.local_filesystem.compute_hash(item.local_path()).await,
+                    item.content_hash(),
+                ) {
+                    (Ok(local), Some(stored)) => local.as_str() != stored.as_str(),
+                    _ => false,
                 }
             }
+            _ => false,
         }
+    }
 
-        // Step 8: Complete the session
-        session.complete();
-        self.state_repository
-            .save_session(&session)
-            .await
-            .context("Failed to save completed session")?;
+    /// Handles a new remote entry whose local path holds the other type
+    ///
+    /// Applies the resolution chosen by the conflict policy. Returns `None`
+    /// when the local path was cleared and the remote entry should now be
+    /// created, or the action to report when processing stops here. Without
+    /// an automatic resolution the local entry is left `Conflicted`.
+    async fn handle_type_conflict(
+        &self,
+        delta_item: &DeltaItem,
+        remote_id: &RemoteId,
+        local_path: &SyncPath,
+        local_kind: EntryKind,
+        sync_root: &SyncPath,
+    ) -> Result<Option<DeltaAction>> {
+        let existing = self.state_repository.get_item_by_path(local_path).await?;
+        if existing
+            .as_ref()
+            .is_some_and(|item| matches!(item.state(), ItemState::Conflicted))
+        {
+            debug!(path = %local_path, "Item already awaiting conflict resolution");
+            return Ok(Some(DeltaAction::Conflicted));
+        }
 
-        result.duration_ms = start.elapsed().as_millis() as u64;
+        let relative = local_path
+            .relative_to(sync_root)?
+            .to_string_lossy()
+            .replace('\\', "/");
 
-        info!(
-            downloaded = result.files_downloaded,
-            uploaded = result.files_uploaded,
-            deleted = result.files_deleted,
-            errors = result.errors.len(),
-            duration_ms = result.duration_ms,
-            "Sync cycle completed"
+        let local_state = EntryState {
+            kind: local_kind,
+            hash: None,
+        };
+        let remote_state = EntryState {
+            kind: EntryKind::from_is_directory(delta_item.is_directory),
+            hash: delta_item.hash.clone(),
+        };
+        let detection = ConflictDetector::new().detect(None, &local_state, &remote_state);
+        let decision = self.conflict_policy.evaluate(&relative);
+
+        warn!(
+            path = %relative,
+            local = %local_kind,
+            remote = %remote_state.kind,
+            resolution = %decision.resolution,
+            rule = %decision.rule,
+            "Type conflict detected"
         );
 
-        Ok(result)
-    }
+        let detected = AuditEntry::new(AuditAction::ConflictDetected, AuditResult::success())
+            .with_details(serde_json::json!({
+                "path": relative,
+                "remote_id": remote_id.as_str(),
+                "detection": detection,
+            }));
+        self.state_repository.save_audit(&detected).await?;
+
+        let steps = ConflictResolver::new().plan(
+            &detection,
+            &decision.resolution,
+            local_path.as_path(),
+            Utc::now(),
+        );
+        if steps.is_empty() {
+            self.record_type_conflict(existing, delta_item, local_path, sync_root)
+                .await?;
+            return Ok(Some(DeltaAction::Conflicted));
+        }
 
-    // ========================================================================
-    // T153: process_delta_item()
-    // ========================================================================
+        for step in &steps {
+            match step {
+                ResolutionStep::RenameLocal(to) => {
+                    let to = SyncPath::new(to.clone())?;
+                    self.forget_item_at(local_path).await?;
+                    self.local_filesystem
+                        .rename(local_path, &to)
+                        .await
+                        .context("Failed to move local entry aside")?;
+                    debug!(from = %local_path, to = %to, "Moved local entry aside");
+                }
+                ResolutionStep::DeleteLocal => {
+                    self.forget_item_at(local_path).await?;
+                    self.local_filesystem
+                        .delete_file(local_path)
+                        .await
+                        .context("Failed to delete local entry")?;
+                }
+                ResolutionStep::DeleteRemote => {
+                    self.delete_remote_item(remote_id)
+                        .await
+                        .context("Failed to delete remote entry")?;
+                }
+                // Downloads happen in handle_remote_create once this returns,
+                // uploads in the local scan of the same cycle
+                ResolutionStep::DownloadRemote | ResolutionStep::UploadLocal => {}
+            }
+        }
 
-    /// Processes a single delta item from the cloud
+        let entry = AuditEntry::new(AuditAction::ConflictResolved, AuditResult::success())
+            .with_details(serde_json::json!({
+                "path": relative,
+                "remote_id": remote_id.as_str(),
+                "detection": detection,
+                "resolution": decision.resolution.to_string(),
+                "resolved_by": ResolutionSource::Policy.to_string(),
+                "rule": decision.rule,
+                "strategy": decision.strategy,
+            }));
+        self.state_repository.save_audit(&entry).await?;
+
+        if steps.contains(&ResolutionStep::DownloadRemote) {
+            Ok(None)
+        } else {
+            Ok(Some(DeltaAction::ConflictResolved { downloaded: false }))
+        }
+    }
+
+    /// Leaves the local entry of a type conflict `Conflicted`
     ///
-    /// Determines the appropriate action based on the item's state:
-    /// - Deleted -> handle_remote_delete
-    /// - Existing (by remote_id) -> handle_remote_update
-    /// - New -> handle_remote_create
-    #[tracing::instrument(skip(self))]
-    async fn process_delta_item(
+    /// An untracked local entry is recorded first, so the conflict has an
+    /// item to point at and the local scan leaves the entry alone.
+    async fn record_type_conflict(
         &self,
+        existing: Option<SyncItem>,
         delta_item: &DeltaItem,
+        local_path: &SyncPath,
         sync_root: &SyncPath,
-    ) -> Result<DeltaAction> {
-        if delta_item.is_deleted {
-            return self.handle_remote_delete(delta_item).await;
-        }
-
-        // Check if we already track this remote item
-        let remote_id =
-            RemoteId::new(delta_item.id.clone()).context("Invalid remote ID in delta item")?;
-
-        let existing = self
-            .state_repository
-            .get_item_by_remote_id(&remote_id)
-            .await
-            .context("Failed to query existing item by remote ID")?;
+    ) -> Result<()> {
+        let fs_state = self.local_filesystem.get_state(local_path).await?;
 
-        if let Some(existing_item) = existing {
-            self.handle_remote_update(delta_item, &existing_item, sync_root)
+        // Both sides are compared in server time
+        let now = self.to_server_time(Utc::now());
+        let local_modified = fs_state.modified.map_or(now, |t| self.to_server_time(t));
+        let local_version = if fs_state.is_file {
+            let local_hash = self
+                .local_filesystem
+                .compute_hash(local_path)
                 .await
+                .context("Failed to hash conflicting local file")?;
+            VersionInfo::new(local_hash, fs_state.size, local_modified)
         } else {
-            self.handle_remote_create(delta_item, sync_root).await
+            VersionInfo::without_hash(0, local_modified)
+        };
+        let remote_hash = delta_item
+            .hash
+            .clone()
+            .map(FileHash::new)
+            .transpose()
+            .context("Invalid remote hash for conflicting file")?;
+        let remote_size = delta_item.size.unwrap_or(0);
+        let remote_modified = delta_item.modified.unwrap_or(now);
+        let remote_version = match remote_hash {
+            Some(hash) => VersionInfo::new(hash, remote_size, remote_modified),
+            None => VersionInfo::without_hash(remote_size, remote_modified),
+        };
+
+        let mut item = match existing {
+            Some(item) => item,
+            None => {
+                let remote = RemotePath::new(self.remote_path_of(local_path, sync_root)?)
+                    .context("Failed to construct remote path")?;
+                let mut item = if fs_state.is_file {
+                    SyncItem::new_file(local_path.clone(), remote, fs_state.size, None)?
+                } else {
+                    SyncItem::new_directory(local_path.clone(), remote)?
+                };
+                item.start_hydrating()?;
+                item.complete_hydration()?;
+                self.record_local_state(&mut item).await;
+                item
+            }
+        };
+        if !matches!(item.state(), ItemState::Modified) {
+            item.mark_modified()?;
         }
-    }
+        item.mark_conflicted()?;
+        self.state_repository.save_item(&item).await?;
 
-    // ========================================================================
-    // T154: handle_remote_create()
-    // ========================================================================
+        let conflict = Conflict::new(*item.id(), local_version, remote_version);
+        self.state_repository.save_conflict(&conflict).await?;
+        Ok(())
+    }
 
-    /// Handles a new item appearing in the cloud
+    /// Handles a remote delete of a file that changed locally
     ///
-    /// - Directories: Creates the local directory, saves SyncItem as Hydrated
-    /// - Files: Downloads content, writes to local path, creates SyncItem,
-    ///   transitions through Hydrating -> Hydrated
-    #[tracing::instrument(skip(self))]
-    async fn handle_remote_create(
+    /// Applies the resolution chosen by the conflict policy. `keep_remote`
+    /// deletes the local file; `keep_local` and `keep_both` forget the
+    /// remote item so the local scan uploads the file as a new one. Without
+    /// an automatic resolution the item is left `Conflicted`.
+    async fn handle_delete_conflict(
         &self,
-        delta_item: &DeltaItem,
+        existing: &SyncItem,
+        remote_id: &RemoteId,
         sync_root: &SyncPath,
     ) -> Result<DeltaAction> {
-        let remote_path_str = delta_item
-            .path
-            .as_deref()
-            .ok_or_else(|| anyhow::anyhow!("Delta item has no path: {}", delta_item.id))?;
+        let local_path = existing.local_path();
 
-        let remote_path = RemotePath::new(remote_path_str.to_string())
-            .context("Invalid remote path in delta item")?;
-        let remote_id =
-            RemoteId::new(delta_item.id.clone()).context("Invalid remote ID in delta item")?;
+        if matches!(existing.state(), ItemState::Conflicted) {
+            debug!(path = %local_path, "Item already awaiting conflict resolution");
+            return Ok(DeltaAction::Conflicted);
+        }
 
-        // Build local path: sync_root + relative path from remote
-        let relative = remote_path_str.trim_start_matches('/');
-        let local_path = SyncPath::new(sync_root.as_path().join(relative))
-            .context("Failed to construct local path")?;
+        let relative = local_path
+            .relative_to(sync_root)?
+            .to_string_lossy()
+            .replace('\\', "/");
+        let detection = DetectionResult::DeleteConflict;
+        let decision = self.conflict_policy.evaluate(&relative);
+
+        warn!(
+            path = %relative,
+            resolution = %decision.resolution,
+            rule = %decision.rule,
+            "File deleted remotely but modified locally"
+        );
 
-        if delta_item.is_directory {
-            debug!(path = %local_path, "Creating local directory from remote");
+        let detected = AuditEntry::new(AuditAction::ConflictDetected, AuditResult::success())
+            .with_item_id(*existing.id())
+            .with_details(serde_json::json!({
+                "path": relative,
+                "remote_id": remote_id.as_str(),
+                "detection": detection,
+            }));
+        self.state_repository.save_audit(&detected).await?;
+
+        let steps = ConflictResolver::new().plan(
+            &detection,
+            &decision.resolution,
+            local_path.as_path(),
+            Utc::now(),
+        );
+        if steps.is_empty() {
+            let fs_state = self.local_filesystem.get_state(local_path).await?;
+            let local_hash = self
+                .local_filesystem
+                .compute_hash(local_path)
+                .await
+                .context("Failed to hash conflicting local file")?;
+            // The remote side is the version that was deleted
+            let deleted_hash = existing
+                .content_hash()
+                .cloned()
+                .unwrap_or_else(|| local_hash.clone());
+            let now = self.to_server_time(Utc::now());
+            let conflict = Conflict::new(
+                *existing.id(),
+                VersionInfo::new(
+                    local_hash,
+                    fs_state.size,
+                    fs_state.modified.map_or(now, |t| self.to_server_time(t)),
+                ),
+                VersionInfo::new(deleted_hash, existing.size_bytes(), now),
+            );
+            self.state_repository.save_conflict(&conflict).await?;
+
+            let mut updated = existing.clone();
+            if !matches!(updated.state(), ItemState::Modified) {
+                updated.mark_modified()?;
+            }
+            updated.mark_conflicted()?;
+            self.state_repository.save_item(&updated).await?;
+            return Ok(DeltaAction::Conflicted);
+        }
 
+        // The remote item is gone either way, so the record is dropped: the
+        // local scan then sees a kept file as new and uploads it
+        self.state_repository.delete_item(existing.id()).await?;
+        if steps.contains(&ResolutionStep::DeleteLocal) {
             self.local_filesystem
-                .create_directory(&local_path)
+                .delete_file(local_path)
                 .await
-                .context("Failed to create local directory")?;
+                .context("Failed to delete local file")?;
+        }
 
-            let mut item = SyncItem::from_remote(
-                local_path,
-                remote_path,
-                remote_id,
-                true, // is_directory
-                0,
-                None,
-                delta_item.modified.unwrap_or_else(Utc::now),
-            )?;
+        let entry = AuditEntry::new(AuditAction::ConflictResolved, AuditResult::success())
+            .with_item_id(*existing.id())
+            .with_details(serde_json::json!({
+                "path": relative,
+                "remote_id": remote_id.as_str(),
+                "detection": detection,
+                "resolution": decision.resolution.to_string(),
+                "resolved_by": ResolutionSource::Policy.to_string(),
+                "rule": decision.rule,
+                "strategy": decision.strategy,
+            }));
+        self.state_repository.save_audit(&entry).await?;
+
+        Ok(DeltaAction::ConflictResolved { downloaded: false })
+    }
 
-            // Directories go directly to Hydrated via Hydrating
-            item.start_hydrating()?;
-            item.complete_hydration()?;
-            item.mark_synced();
+    /// Drops the tracked item at a path, if any, so a moved or deleted
+    /// local entry is not reported as a local deletion by the next scan
+    async fn forget_item_at(&self, path: &SyncPath) -> Result<()> {
+        if let Some(item) = self.state_repository.get_item_by_path(path).await? {
+            self.state_repository.delete_item(item.id()).await?;
+        }
+        Ok(())
+    }
 
-            self.state_repository
-                .save_item(&item)
-                .await
-                .context("Failed to save new directory SyncItem")?;
+    /// Reconciles an untracked local file with a remote file at its path
+    ///
+    /// Happens when the record of a synced file was lost (e.g. with the
+    /// state database) or both sides created the same name. Neither side
+    /// overwrites the other: identical content adopts the remote item
+    /// without a transfer, and different content is a conflict resolved by
+    /// the conflict policy like any other (e.g. keeping both).
+    async fn reconcile_name_collision(
+        &self,
+        local_path: &SyncPath,
+        delta_item: &DeltaItem,
+        sync_root: &SyncPath,
+    ) -> Result<DeltaAction> {
+        let remote_path = RemotePath::new(
+            delta_item
+                .path
+                .clone()
+                .ok_or_else(|| anyhow::anyhow!("Remote item has no path: {}", delta_item.id))?,
+        )
+        .context("Invalid remote path of colliding item")?;
+        let remote_id =
+            RemoteId::new(delta_item.id.clone()).context("Invalid remote ID of colliding item")?;
+        let local_hash = self
+            .local_filesystem
+            .compute_hash(local_path)
+            .await
+            .context("Failed to hash colliding local file")?;
 
-            Ok(DeltaAction::Downloaded)
-        } else {
-            debug!(
-                path = %local_path,
-                size = delta_item.size.unwrap_or(0),
-                "Downloading new file from remote"
-            );
+        let mut item = SyncItem::from_remote(
+            local_path.clone(),
+            remote_path,
+            remote_id,
+            false,
+            delta_item.size.unwrap_or(0),
+            delta_item
+                .hash
+                .as_ref()
+                .and_then(|h| FileHash::new(h.clone()).ok()),
+            delta_item.modified.unwrap_or_else(Utc::now),
+        )?;
+        item.metadata_mut().set_facets(delta_item.facets.clone());
+        item.start_hydrating()?;
+        item.complete_hydration()?;
+        item.mark_synced();
 
-            // Download the file content
-            let data = with_retry("download_file", || {
-                let rid = remote_id.clone();
-                async move { self.cloud_provider.download_file(&rid).await }
-            })
-            .await
-            .context("Failed to download file")?;
+        // Never synced, so there is no base: equal hashes converge and
+        // anything else conflicts. Without a remote hash, matching size and
+        // mtime are the evidence of identical content.
+        let converged = match delta_item.hash.as_deref() {
+            Some(remote_hash) => {
+                self.change_detector.detect(
+                    None,
+                    &EntryState::file(Some(local_hash.as_str())),
+                    &EntryState::file(Some(remote_hash)),
+                ) == DetectionResult::Converged
+            }
+            None => {
+                let fs_state = self.local_filesystem.get_state(local_path).await?;
+                delta_item.size.is_some_and(|size| {
+                    Fingerprint::local(fs_state.size, fs_state.modified)
+                        .matches(&Fingerprint::local(size, delta_item.modified))
+                })
+            }
+        };
+        if converged {
+            if item.content_hash().is_none() {
+                item.set_content_hash(local_hash);
+            }
+            self.record_local_state(&mut item).await;
+            self.state_repository.save_item(&item).await?;
+            debug!(path = %local_path, "Adopted remote item of identical local file");
+            return Ok(DeltaAction::Adopted);
+        }
 
-            // Write to local filesystem
-            self.local_filesystem
-                .write_file(&local_path, &data)
-                .await
-                .context("Failed to write downloaded file")?;
+        info!(path = %local_path, "Local file collides with a different remote file");
+        self.state_repository.save_item(&item).await?;
+        self.handle_conflict(delta_item, &item, sync_root).await
+    }
+
+    /// Handles a remote update to a file that was also modified locally
+    ///
+    /// Records the conflict, then applies the resolution chosen by the
+    /// conflict policy for the file's path. Conflicts without an automatic
+    /// resolution leave the item `Conflicted` until the user resolves them.
+    async fn handle_conflict(
+        &self,
+        delta_item: &DeltaItem,
+        existing: &SyncItem,
+        sync_root: &SyncPath,
+    ) -> Result<DeltaAction> {
+        let local_path = existing.local_path();
+
+        if matches!(existing.state(), ItemState::Conflicted) {
+            debug!(path = %local_path, "Item already awaiting conflict resolution");
+            return Ok(DeltaAction::Conflicted);
+        }
 
-            // Build content hash if available
-            let content_hash = if let Some(ref hash_str) = delta_item.hash {
-                FileHash::new(hash_str.clone()).ok()
-            } else {
-                None
-            };
+        let relative = local_path
+            .relative_to(sync_root)?
+            .to_string_lossy()
+            .replace('\\', "/");
 
-            // Create and persist the SyncItem
-            let mut item = SyncItem::from_remote(
-                local_path.clone(),
-                remote_path,
-                remote_id,
-                false, // is_file
-                delta_item.size.unwrap_or(data.len() as u64),
-                content_hash,
-                delta_item.modified.unwrap_or_else(Utc::now),
-            )?;
+        let fs_state = self.local_filesystem.get_state(local_path).await?;
+        let local_hash = self
+            .local_filesystem
+            .compute_hash(local_path)
+            .await
+            .context("Failed to hash conflicting local file")?;
+        let remote_hash = delta_item
+            .hash
+            .clone()
+            .map(FileHash::new)
+            .transpose()
+            .context("Invalid remote hash for conflicting file")?;
+
+        // Both sides are compared in server time
+        let now = self.to_server_time(Utc::now());
+        let conflict = Conflict::new(
+            *existing.id(),
+            VersionInfo::new(
+                local_hash,
+                fs_state.size,
+                fs_state.modified.map_or(now, |t| self.to_server_time(t)),
+            ),
+            match remote_hash {
+                Some(hash) => VersionInfo::new(
+                    hash,
+                    delta_item.size.unwrap_or(0),
+                    delta_item.modified.unwrap_or(now),
+                ),
+                None => VersionInfo::without_hash(
+                    delta_item.size.unwrap_or(0),
+                    delta_item.modified.unwrap_or(now),
+                ),
+            },
+        );
 
-            item.start_hydrating()?;
-            item.complete_hydration()?;
-            item.mark_synced();
+        let decision = self.conflict_policy.evaluate_versions(
+            &relative,
+            conflict.local_version(),
+            conflict.remote_version(),
+        );
+        info!(
+            path = %relative,
+            conflict_id = %conflict.id(),
+            resolution = %decision.resolution,
+            rule = %decision.rule,
+            "Conflict detected"
+        );
 
-            // Compute and set local hash
-            if let Ok(local_hash) = self.local_filesystem.compute_hash(&local_path).await {
-                item.set_local_hash(local_hash);
+        let detected = AuditEntry::new(AuditAction::ConflictDetected, AuditResult::success())
+            .with_item_id(*existing.id())
+            .with_details(serde_json::json!({
+                "path": relative,
+                "conflict_id": conflict.id().to_string(),
+            }));
+        self.state_repository.save_audit(&detected).await?;
+
+        let downloaded = match decision.resolution {
+            Resolution::Manual => {
+                self.state_repository.save_conflict(&conflict).await?;
+                let mut updated = existing.clone();
+                if !matches!(updated.state(), ItemState::Modified) {
+                    updated.mark_modified()?;
+                }
+                updated.mark_conflicted()?;
+                self.state_repository.save_item(&updated).await?;
+                return Ok(DeltaAction::Conflicted);
+            }
+            Resolution::KeepLocal => {
+                // Leave the remote hash stale so the local scan uploads the file
+                let mut updated = existing.clone();
+                if !matches!(updated.state(), ItemState::Modified) {
+                    updated.mark_modified()?;
+                }
+                if let Some(modified) = delta_item.modified {
+                    updated.set_last_modified_remote(modified);
+                }
+                self.state_repository.save_item(&updated).await?;
+                false
             }
+            Resolution::KeepRemote => {
+                self.apply_remote_update(delta_item, existing).await?;
+                true
+            }
+            Resolution::KeepBoth => {
+                let copy_path =
+                    SyncPath::new(conflict_copy_path(local_path.as_path(), Utc::now()))?;
+                self.local_filesystem
+                    .copy_file(local_path, &copy_path)
+                    .await
+                    .context("Failed to write conflicted copy")?;
+                debug!(path = %copy_path, "Preserved local version as conflicted copy");
 
-            self.state_repository
-                .save_item(&item)
-                .await
-                .context("Failed to save new file SyncItem")?;
+                self.apply_remote_update(delta_item, existing).await?;
+                true
+            }
+        };
 
-            Ok(DeltaAction::Downloaded)
-        }
+        let resolved = conflict.resolve(decision.resolution.clone(), ResolutionSource::Policy);
+        self.state_repository.save_conflict(&resolved).await?;
+
+        let entry = AuditEntry::new(AuditAction::ConflictResolved, AuditResult::success())
+            .with_item_id(*existing.id())
+            .with_details(serde_json::json!({
+                "path": relative,
+                "conflict_id": resolved.id().to_string(),
+                "resolution": decision.resolution.to_string(),
+                "resolved_by": ResolutionSource::Policy.to_string(),
+                "rule": decision.rule,
+                "strategy": decision.strategy,
+            }));
+        self.state_repository.save_audit(&entry).await?;
+
+        Ok(DeltaAction::ConflictResolved { downloaded })
     }
 
     // ========================================================================
-    // T155: handle_remote_update()
+    // Conflict quarantine
     // ========================================================================
 
-    /// Handles an updated item in the cloud
+    /// Quarantines the conflicts left unresolved for longer than
+    /// `conflicts.quarantine_after_days`
     ///
-    /// Compares the remote content hash with the stored hash. If they differ,
-    /// downloads the new content and updates the local file and SyncItem.
-    #[tracing::instrument(skip(self))]
-    async fn handle_remote_update(
+    /// Returns the number of conflicts quarantined. One that cannot be
+    /// quarantined (e.g. the remote version fails to download) is reported
+    /// in `errors` and tried again in the next cycle.
+    async fn quarantine_stale_conflicts(
         &self,
-        delta_item: &DeltaItem,
-        existing: &SyncItem,
-        _sync_root: &SyncPath,
-    ) -> Result<DeltaAction> {
-        // For directories, just update metadata
-        if delta_item.is_directory {
-            debug!(
-                path = %existing.local_path(),
-                "Remote directory updated (metadata only)"
-            );
-            let mut updated = existing.clone();
-            if let Some(modified) = delta_item.modified {
-                updated.set_last_modified_remote(modified);
+        sync_root: &SyncPath,
+        errors: &mut Vec<String>,
+    ) -> u32 {
+        let Some(max_age) = self.quarantine_after else {
+            return 0;
+        };
+        let conflicts = match self.state_repository.get_unresolved_conflicts().await {
+            Ok(conflicts) => conflicts,
+            Err(err) => {
+                errors.push(format!("Failed to list unresolved conflicts: {err:#}"));
+                return 0;
             }
-            updated.mark_synced();
-            self.state_repository.save_item(&updated).await?;
-            return Ok(DeltaAction::Skipped);
-        }
-
-        // Compare hashes to determine if content changed
-        let remote_hash_str = delta_item.hash.as_deref();
-        let stored_hash_str = existing.content_hash().map(|h| h.as_str());
-
-        let hashes_differ = match (remote_hash_str, stored_hash_str) {
-            (Some(remote), Some(stored)) => remote != stored,
-            (Some(_), None) => true, // New hash, assume changed
-            (None, _) => false,      // No remote hash, can't compare
         };
 
-        if !hashes_differ {
-            debug!(
-                path = %existing.local_path(),
-                "Remote file unchanged (hash match)"
-            );
-            let mut updated = existing.clone();
-            if let Some(modified) = delta_item.modified {
-                updated.set_last_modified_remote(modified);
+        let now = Utc::now();
+        let mut quarantined = 0;
+        for conflict in conflicts {
+            if now - conflict.detected_at() < max_age {
+                continue;
+            }
+            match self.quarantine_conflict(&conflict, sync_root).await {
+                Ok(true) => quarantined += 1,
+                Ok(false) => {}
+                Err(err) => {
+                    let msg = format!("Failed to quarantine conflict {}: {err:#}", conflict.id());
+                    warn!(%msg);
+                    errors.push(msg);
+                }
             }
-            updated.mark_synced();
-            self.state_repository.save_item(&updated).await?;
-            return Ok(DeltaAction::Skipped);
         }
+        quarantined
+    }
 
-        debug!(
-            path = %existing.local_path(),
-            "Remote file content changed, downloading update"
-        );
-
-        let remote_id = existing
-            .remote_id()
-            .ok_or_else(|| anyhow::anyhow!("Existing item has no remote ID"))?
-            .clone();
-
-        // Download updated content
-        let data = with_retry("download_file_update", || {
-            let rid = remote_id.clone();
-            async move { self.cloud_provider.download_file(&rid).await }
-        })
-        .await
-        .context("Failed to download updated file")?;
-
-        // Write to local filesystem
+    /// Moves both versions of a conflicted file to [`QUARANTINE_DIR`]
+    ///
+    /// The local and remote versions are written to
+    /// `.lnxdrive-conflicts/<conflict id>/local/` and `.../remote/` next to
+    /// a `manifest.json` describing them. The local version then stays in
+    /// place and is uploaded like a `keep_local` resolution, so the file
+    /// syncs again. Returns false for conflicts of another account.
+    async fn quarantine_conflict(&self, conflict: &Conflict, sync_root: &SyncPath) -> Result<bool> {
+        let Some(existing) = self.state_repository.get_item(conflict.item_id()).await? else {
+            // The item is gone, so is the conflict
+            let resolved = conflict
+                .clone()
+                .resolve(Resolution::KeepLocal, ResolutionSource::System);
+            self.state_repository.save_conflict(&resolved).await?;
+            return Ok(false);
+        };
         let local_path = existing.local_path();
-        self.local_filesystem
-            .write_file(local_path, &data)
+        let Ok(relative) = local_path.relative_to(sync_root) else {
+            return Ok(false);
+        };
+        let relative = relative.to_string_lossy().replace('\\', "/");
+        let name = local_path
+            .as_path()
+            .file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_default();
+
+        let local_data = self
+            .local_filesystem
+            .read_file(local_path)
             .await
-            .context("Failed to write updated file")?;
-
-        // Update the SyncItem
-        let mut updated = existing.clone();
-        if let Some(ref hash_str) = delta_item.hash {
-            if let Ok(hash) = FileHash::new(hash_str.clone()) {
-                updated.set_content_hash(hash);
+            .context("Failed to read local version")?;
+        let remote_data = match existing.remote_id() {
+            Some(remote_id) => {
+                let download = with_retry("download_quarantined", || async move {
+                    self.cloud_provider.download_file(remote_id).await
+                })
+                .await;
+                match download {
+                    Ok(data) => Some(data),
+                    // Deleted remotely: there is only the local version
+                    Err(err) if is_remote_item_not_found(&err) => None,
+                    Err(err) => return Err(err.context("Failed to download remote version")),
+                }
             }
-        }
-        if let Some(size) = delta_item.size {
-            updated.set_size_bytes(size);
-        }
-        if let Some(modified) = delta_item.modified {
-            updated.set_last_modified_remote(modified);
-        }
+            None => None,
+        };
 
-        // Compute and set local hash
-        if let Ok(local_hash) = self.local_filesystem.compute_hash(local_path).await {
-            updated.set_local_hash(local_hash);
+        let dir = sync_root
+            .as_path()
+            .join(QUARANTINE_DIR)
+            .join(conflict.id().to_string());
+        let mut versions = serde_json::Map::new();
+        for (side, data, version) in [
+            ("local", Some(&local_data), conflict.local_version()),
+            ("remote", remote_data.as_ref(), conflict.remote_version()),
+        ] {
+            let Some(data) = data else {
+                versions.insert(side.to_string(), serde_json::Value::Null);
+                continue;
+            };
+            let side_dir = SyncPath::new(dir.join(side))?;
+            self.local_filesystem
+                .create_directory(&side_dir)
+                .await
+                .context("Failed to create quarantine folder")?;
+            self.local_filesystem
+                .write_file(&SyncPath::new(side_dir.as_path().join(&name))?, data)
+                .await
+                .with_context(|| format!("Failed to quarantine {side} version"))?;
+            versions.insert(
+                side.to_string(),
+                serde_json::json!({
+                    "file": format!("{side}/{name}"),
+                    "hash": version.hash().map(FileHash::as_str),
+                    "size": data.len(),
+                    "modified_at": version.modified_at(),
+                }),
+            );
         }
+        let manifest = serde_json::json!({
+            "conflict_id": conflict.id().to_string(),
+            "path": relative,
+            "detected_at": conflict.detected_at(),
+            "quarantined_at": Utc::now(),
+            "kept": "local",
+            "local": versions.remove("local"),
+            "remote": versions.remove("remote"),
+        });
+        self.local_filesystem
+            .write_file(
+                &SyncPath::new(dir.join("manifest.json"))?,
+                serde_json::to_string_pretty(&manifest)?.as_bytes(),
+            )
+            .await
+            .context("Failed to write quarantine manifest")?;
 
-        updated.mark_synced();
-        self.state_repository.save_item(&updated).await?;
-
-        Ok(DeltaAction::Updated)
+        // Resolve like keep_local: the next scan uploads the local version
+        if remote_data.is_some() {
+            let mut updated = existing.clone();
+            updated.resolve_conflict()?;
+            updated.mark_modified()?;
+            updated.set_last_modified_remote(conflict.remote_version().modified_at());
+            self.state_repository.save_item(&updated).await?;
+        } else {
+            self.state_repository.delete_item(existing.id()).await?;
+        }
+        let resolved = conflict
+            .clone()
+            .resolve(Resolution::KeepLocal, ResolutionSource::System);
+        self.state_repository.save_conflict(&resolved).await?;
+
+        warn!(
+            path = %relative,
+            conflict_id = %conflict.id(),
+            "Unresolved conflict moved to {}",
+            QUARANTINE_DIR
+        );
+        let entry = AuditEntry::new(AuditAction::ConflictResolved, AuditResult::success())
+            .with_item_id(*existing.id())
+            .with_details(serde_json::json!({
+                "path": relative,
+                "conflict_id": conflict.id().to_string(),
+                "resolution": Resolution::KeepLocal.to_string(),
+                "resolved_by": ResolutionSource::System.to_string(),
+                "quarantined_to": format!("{}/{}", QUARANTINE_DIR, conflict.id()),
+            }));
+        self.state_repository.save_audit(&entry).await?;
+
+        self.notify(Notification::conflict(
+            "Conflict moved to quarantine",
+            format!(
+                "'{}' had an unresolved conflict. Both versions were saved in {}; \
+                 the local version is synced.",
+                relative,
+                dir.display()
+            ),
+        ))
+        .await;
+
+        Ok(true)
     }
 
     // ========================================================================
@@ -914,9 +3758,16 @@ impl SyncEngine {
     /// Handles an item deleted from the cloud
     ///
     /// Finds the local SyncItem by remote ID, deletes the local file/directory,
-    /// and marks the SyncItem as Deleted.
+    /// and drops the SyncItem. A file that changed locally since its last
+    /// sync is a delete conflict instead, see [`Self::handle_delete_conflict`].
+    /// For a folder, pinned or changed files inside it are first moved out
+    /// of the way, see [`Self::recover_local_changes`].
     #[tracing::instrument(skip(self))]
-    async fn handle_remote_delete(&self, delta_item: &DeltaItem) -> Result<DeltaAction> {
+    async fn handle_remote_delete(
+        &self,
+        delta_item: &DeltaItem,
+        sync_root: &SyncPath,
+    ) -> Result<DeltaAction> {
         let remote_id = RemoteId::new(delta_item.id.clone())
             .context("Invalid remote ID in deleted delta item")?;
 
@@ -926,7 +3777,7 @@ impl SyncEngine {
             .await
             .context("Failed to query item for remote delete")?;
 
-        let Some(mut item) = existing else {
+        let Some(item) = existing else {
             debug!(
                 id = %delta_item.id,
                 "Remote delete for unknown item, skipping"
@@ -946,6 +3797,18 @@ impl SyncEngine {
             .await
             .context("Failed to check local file state")?;
 
+        if fs_state.exists && !item.is_directory() && self.has_local_changes(&item).await {
+            return self
+                .handle_delete_conflict(&item, &remote_id, sync_root)
+                .await;
+        }
+
+        let mut recovered = 0;
+        if fs_state.exists && item.is_directory() {
+            recovered = self.recover_local_changes(&item, sync_root).await?;
+            self.forget_items_under(item.local_path()).await?;
+        }
+
         if fs_state.exists {
             self.local_filesystem
                 .delete_file(item.local_path())
@@ -953,14 +3816,112 @@ impl SyncEngine {
                 .context("Failed to delete local file")?;
         }
 
-        // Mark the SyncItem as Deleted
-        item.mark_deleted()?;
+        // Gone on both sides: drop the record. Keeping it as Deleted would
+        // make the next local scan treat it as a pending local deletion.
         self.state_repository
-            .save_item(&item)
+            .delete_item(item.id())
             .await
-            .context("Failed to save deleted SyncItem")?;
+            .context("Failed to remove deleted SyncItem")?;
+
+        if recovered > 0 {
+            Ok(DeltaAction::DeletedWithRecovery { recovered })
+        } else {
+            Ok(DeltaAction::Deleted)
+        }
+    }
+
+    /// Moves the files a remote folder delete would destroy to [`RECOVERED_DIR`]
+    ///
+    /// A file is rescued if it is pinned, changed since its last sync, or
+    /// was never uploaded. It keeps its path relative to the sync root, so
+    /// `Docs/a.txt` ends up in `.lnxdrive-recovered/Docs/a.txt`. Returns
+    /// the number of files moved; the user is notified if any were.
+    async fn recover_local_changes(&self, dir: &SyncItem, sync_root: &SyncPath) -> Result<u32> {
+        let mut at_risk = Vec::new();
+        for path in local_files_under(dir.local_path().as_path()).await? {
+            let path = SyncPath::new(path)?;
+            let keep = match self.state_repository.get_item_by_path(&path).await? {
+                None => true,
+                Some(item) => item.state().is_pinned() || self.has_local_changes(&item).await,
+            };
+            if keep {
+                at_risk.push(path);
+            }
+        }
+        if at_risk.is_empty() {
+            return Ok(0);
+        }
+
+        let recovered_root = sync_root.as_path().join(RECOVERED_DIR);
+        let mut recovered = Vec::with_capacity(at_risk.len());
+        for path in &at_risk {
+            let relative = path.relative_to(sync_root)?;
+            let mut target = recovered_root.join(&relative);
+            if tokio::fs::symlink_metadata(&target).await.is_ok() {
+                target = conflict_copy_path(&target, Utc::now());
+            }
+            if let Some(parent) = target.parent() {
+                self.local_filesystem
+                    .create_directory(&SyncPath::new(parent.to_path_buf())?)
+                    .await
+                    .context("Failed to create recovery folder")?;
+            }
+            self.local_filesystem
+                .rename(path, &SyncPath::new(target.clone())?)
+                .await
+                .context("Failed to move file to recovery folder")?;
+            recovered.push(relative.to_string_lossy().replace('\\', "/"));
+        }
+
+        let dir_relative = dir
+            .local_path()
+            .relative_to(sync_root)?
+            .to_string_lossy()
+            .replace('\\', "/");
+        warn!(
+            path = %dir_relative,
+            count = recovered.len(),
+            "Remote folder deleted; moved local changes to {}",
+            RECOVERED_DIR
+        );
+
+        let entry = AuditEntry::new(AuditAction::ConflictDetected, AuditResult::success())
+            .with_item_id(*dir.id())
+            .with_details(serde_json::json!({
+                "path": dir_relative,
+                "detection": "remote_folder_deleted",
+                "recovered_to": RECOVERED_DIR,
+                "recovered": recovered,
+            }));
+        self.state_repository.save_audit(&entry).await?;
+
+        self.notify(Notification::conflict(
+            "Files recovered from a deleted folder",
+            format!(
+                "'{}' was deleted in the cloud. {} file(s) with local changes were moved to {}.",
+                dir_relative,
+                recovered.len(),
+                recovered_root.display()
+            ),
+        ))
+        .await;
+
+        Ok(recovered.len() as u32)
+    }
 
-        Ok(DeltaAction::Deleted)
+    /// Drops the records of every item below a directory
+    async fn forget_items_under(&self, dir: &SyncPath) -> Result<()> {
+        let items = self
+            .state_repository
+            .query_items(&ItemFilter::new().with_path_prefix(dir.clone()))
+            .await?;
+        for item in items {
+            let path = item.local_path().as_path();
+            if path != dir.as_path() && path.starts_with(dir.as_path()) {
+                self.state_repository.delete_item(item.id()).await?;
+            }
+        }
+        Ok(())
     }
 
     // ========================================================================
@@ -984,10 +3945,12 @@ impl SyncEngine {
         last_sync: Option<DateTime<Utc>>,
     ) -> Result<Vec<LocalChange>> {
         let mut changes = Vec::new();
+        let mut hardlinks = HardlinkGroups::new();
 
         // Walk the sync root directory
-        self.walk_directory(sync_root, &mut changes, last_sync)
+        self.walk_directory(sync_root, &mut changes, &mut hardlinks, last_sync)
             .await?;
+        self.resolve_hardlinks(&mut changes, hardlinks).await;
 
         // Check for deleted items: items in the state repo whose local file is gone
         let all_items = self
@@ -1015,13 +3978,19 @@ impl SyncEngine {
                 continue;
             }
 
+            // Placeholders have no local copy to miss
+            if item.state().is_placeholder() {
+                continue;
+            }
+
             let fs_state = self
                 .local_filesystem
                 .get_state(item.local_path())
                 .await
                 .context("Failed to check local state for deletion scan")?;
 
-            if !fs_state.exists && item.remote_id().is_some() {
+            let is_hardlink = item.metadata().hardlink_of().is_some();
+            if !fs_state.exists && (item.remote_id().is_some() || is_hardlink) {
                 debug!(
                     path = %item.local_path(),
                     "Local file deleted, will remove from cloud"
@@ -1033,15 +4002,44 @@ impl SyncEngine {
         Ok(changes)
     }
 
+    /// Returns why a local entry is left out of sync, or `None` if it is
+    /// synced
+    ///
+    /// Shared by the local scan and [`reconcile()`](Self::reconcile).
+    fn skip_reason(&self, path: &Path, metadata: &std::fs::Metadata) -> Option<&'static str> {
+        let name = path.file_name()?.to_str();
+        if metadata.is_dir() {
+            if name == Some(RECOVERED_DIR) {
+                return Some("recovery folder");
+            }
+            if name == Some(QUARANTINE_DIR) {
+                return Some("conflict quarantine folder");
+            }
+            if self.own_dirs.iter().any(|dir| dir == path) {
+                return Some("LNXDrive mount or cache folder");
+            }
+        }
+        if metadata.is_file() && name.is_some_and(is_lock_file) {
+            return Some("lock file");
+        }
+        if name.is_some_and(|name| self.exclusions.excludes_name(name)) {
+            return Some("excluded entry");
+        }
+        None
+    }
+
     /// Recursively walks a directory, detecting new and modified files
     ///
-    /// T172: When `last_sync` is provided, files whose modification time
-    /// predates that timestamp are skipped (they haven't changed since the
-    /// last successful sync), reducing expensive hash computations.
+    /// When `last_sync` is provided, tracked files whose size and mtime
+    /// still match their SyncItem are skipped without hashing. Items synced
+    /// before mtimes were recorded fall back to T172: they are skipped if
+    /// their mtime predates `last_sync`. Without `last_sync` (first sync or
+    /// reconciliation) every tracked file is hashed.
     fn walk_directory<'a>(
         &'a self,
         dir: &'a SyncPath,
         changes: &'a mut Vec<LocalChange>,
+        hardlinks: &'a mut HardlinkGroups,
         last_sync: Option<DateTime<Utc>>,
     ) -> std::pin::Pin<Box<dyn std::future::Future<Output = Result<()>> + Send + 'a>> {
         Box::pin(async move {
@@ -1062,6 +4060,11 @@ impl SyncEngine {
 
                 let metadata = entry.metadata().await?;
 
+                if let Some(reason) = self.skip_reason(&entry_path, &metadata) {
+                    debug!(path = %sync_path, "Skipping {reason}");
+                    continue;
+                }
+
                 if metadata.is_dir() {
                     // Check if this directory is tracked
                     let existing = self
@@ -1075,8 +4078,16 @@ impl SyncEngine {
                     }
 
                     // Recurse into subdirectory
-                    self.walk_directory(&sync_path, changes, last_sync).await?;
+                    self.walk_directory(&sync_path, changes, hardlinks, last_sync)
+                        .await?;
                 } else if metadata.is_file() {
+                    if metadata.nlink() > 1 {
+                        hardlinks
+                            .entry((metadata.dev(), metadata.ino()))
+                            .or_default()
+                            .push(sync_path.clone());
+                    }
+
                     // T172: Skip files not modified since last_sync for existing items.
                     // New files (not tracked) always need to be checked regardless
                     // of their modification time.
@@ -1091,19 +4102,54 @@ impl SyncEngine {
                             // New file - always report as Created
                             changes.push(LocalChange::Created(sync_path));
                         }
+                        Some(item) if matches!(item.state(), ItemState::Conflicted) => {
+                            debug!(
+                                path = %sync_path,
+                                "Skipping conflicted file until it is resolved"
+                            );
+                        }
+                        Some(item) if item.metadata().hardlink_of().is_some() => {
+                            // The primary uploads the shared content; a link
+                            // that no longer shares its inode is uploaded itself
+                            let primary = item.metadata().hardlink_of().cloned();
+                            let still_linked = match primary {
+                                Some(primary) => {
+                                    tokio::fs::metadata(primary.as_path()).await.is_ok_and(|m| {
+                                        m.dev() == metadata.dev() && m.ino() == metadata.ino()
+                                    })
+                                }
+                                None => false,
+                            };
+                            if !still_linked {
+                                debug!(path = %sync_path, "Hardlink was broken, uploading it");
+                                changes.push(LocalChange::Modified(sync_path, item));
+                            }
+                        }
                         Some(item) => {
-                            // T172: Optimization - skip hash computation for files
-                            // not modified since last sync
-                            if let Some(last_sync_time) = last_sync {
-                                if let Ok(modified_time) = metadata.modified() {
-                                    let modified_dt: DateTime<Utc> = modified_time.into();
-                                    if modified_dt <= last_sync_time {
-                                        debug!(
-                                            path = %sync_path,
-                                            "Skipping unchanged file (modified before last sync)"
-                                        );
-                                        continue;
-                                    }
+                            // Skip hash computation while size and mtime are
+                            // unchanged. Items already marked Modified (e.g. by
+                            // reconciliation) are always checked.
+                            let marked_modified = matches!(item.state(), ItemState::Modified);
+                            let modified_dt: Option<DateTime<Utc>> =
+                                metadata.modified().ok().map(Into::into);
+                            if let (Some(last_sync_time), false) = (last_sync, marked_modified) {
+                                let unchanged = if item.last_modified_local().is_some() {
+                                    self.matches_recorded_metadata(
+                                        &item,
+                                        metadata.len(),
+                                        modified_dt,
+                                    )
+                                } else {
+                                    // T172: no recorded mtime, compare with the last sync
+                                    self.change_detector.mode() == DetectionMode::Quick
+                                        && modified_dt.is_some_and(|m| m <= last_sync_time)
+                                };
+                                if unchanged {
+                                    debug!(
+                                        path = %sync_path,
+                                        "Skipping unchanged file (size and mtime match)"
+                                    );
+                                    continue;
                                 }
                             }
 
@@ -1114,6 +4160,14 @@ impl SyncEngine {
                                 let stored_hash = item.content_hash().map(|h| h.as_str());
                                 if stored_hash != Some(local_hash.as_str()) {
                                     changes.push(LocalChange::Modified(sync_path, item));
+                                } else if let Some(modified) = modified_dt {
+                                    // Only metadata changed; remember the new mtime
+                                    // so the next scan can skip hashing
+                                    if item.last_modified_local() != Some(modified) {
+                                        let mut item = item;
+                                        item.set_last_modified_local(modified);
+                                        self.state_repository.save_item(&item).await?;
+                                    }
                                 }
                             }
                         }
@@ -1125,6 +4179,64 @@ impl SyncEngine {
         }) // end Box::pin(async move { ... })
     }
 
+    /// Turns new paths that are hardlinks of another path into
+    /// [`LocalChange::Hardlinked`] so their content is uploaded only once
+    ///
+    /// The primary of each group is its tracked, non-link path if there is
+    /// one, otherwise the first new path in path order. Link changes are
+    /// moved to the end so the primary is uploaded before them.
+    async fn resolve_hardlinks(&self, changes: &mut Vec<LocalChange>, groups: HardlinkGroups) {
+        let mut links = Vec::new();
+        for (_, mut paths) in groups {
+            if paths.len() < 2 {
+                continue;
+            }
+            paths.sort_by(|a, b| a.as_path().cmp(b.as_path()));
+
+            let is_new = |path: &SyncPath| {
+                changes
+                    .iter()
+                    .any(|c| matches!(c, LocalChange::Created(p) if p == path))
+            };
+            let mut primary = None;
+            for path in &paths {
+                if is_new(path) {
+                    continue;
+                }
+                if let Ok(Some(item)) = self.state_repository.get_item_by_path(path).await {
+                    if item.metadata().hardlink_of().is_none() {
+                        primary = Some(path.clone());
+                        break;
+                    }
+                }
+            }
+            let Some(primary) = primary.or_else(|| paths.iter().find(|p| is_new(p)).cloned())
+            else {
+                continue;
+            };
+
+            for path in paths {
+                if path != primary && is_new(&path) {
+                    links.push(LocalChange::Hardlinked {
+                        path,
+                        primary: primary.clone(),
+                    });
+                }
+            }
+        }
+
+        if links.is_empty() {
+            return;
+        }
+        changes.retain(|change| match change {
+            LocalChange::Created(path) => !links
+                .iter()
+                .any(|link| matches!(link, LocalChange::Hardlinked { path: p, .. } if p == path)),
+            _ => true,
+        });
+        changes.extend(links);
+    }
+
     // ========================================================================
     // T158: handle_local_create()
     // ========================================================================
@@ -1133,79 +4245,98 @@ impl SyncEngine {
     ///
     /// Reads the file, determines the parent remote path, and uploads using
     /// either simple upload or resumable session based on file size.
-    #[tracing::instrument(skip(self))]
-    async fn handle_local_create(&self, path: &SyncPath, sync_root: &SyncPath) -> Result<()> {
+    /// Directories are created as remote folders instead. A file
+    /// that changes meanwhile is uploaded again (see `upload_local_file`).
+    /// Returns the number of bytes uploaded. A file whose name is already
+    /// taken by a remote file is not uploaded over it but reconciled, see
+    /// [`Self::reconcile_name_collision`]. Fails with
+    /// [`SyncError::LimitExceeded`] or [`SyncError::QuotaExceeded`] before
+    /// reading the file if it exceeds a provider limit or does not fit in
+    /// the remaining quota.
+    #[tracing::instrument(skip(self, budget))]
+    async fn handle_local_create(
+        &self,
+        path: &SyncPath,
+        sync_root: &SyncPath,
+        budget: &mut UploadBudget,
+    ) -> Result<LocalCreateAction> {
         let fs_state = self
             .local_filesystem
             .get_state(path)
             .await
             .context("Failed to get state for new local file")?;
+        if fs_state.is_locked {
+            return Err(SyncError::FileLocked(path.as_path().clone()).into());
+        }
 
-        // Compute relative path and derive remote path
-        let relative = path
-            .relative_to(sync_root)
-            .context("Path is not within sync root")?;
-        let remote_path_str = format!("/{}", relative.display()).replace('\\', "/"); // Normalize for Windows-style paths in tests
+        // Two local names in different Unicode forms share one remote name
+        if let Some(other) = self.names.find_collision(path.as_path()) {
+            return Err(SyncError::NameCollision {
+                path: path.as_path().clone(),
+                other,
+            }
+            .into());
+        }
+
+        let remote_path_str = self.remote_path_of(path, sync_root)?;
+
+        self.check_limits(path, &remote_path_str, &fs_state, None)
+            .await?;
 
         if fs_state.is_directory() {
-            // For directories, we don't upload them directly (they're created implicitly)
-            // but we do track them
+            // Folders have no content to upload; an empty one would never
+            // reach the cloud unless created explicitly
+            let (parent, name) = split_remote_path(&remote_path_str)?;
+            let delta_item = with_retry("create_folder", || {
+                let parent = parent.clone();
+                let name = name.clone();
+                async move { self.cloud_provider.create_folder(&parent, &name).await }
+            })
+            .await
+            .context("Failed to create remote folder")?;
+            let remote_id = RemoteId::new(delta_item.id.clone())
+                .context("Invalid remote ID in create folder response")?;
             let remote_path = RemotePath::new(remote_path_str)
                 .context("Failed to construct remote path for directory")?;
 
-            let mut item = SyncItem::new_directory(path.clone(), remote_path)?;
+            let mut item = SyncItem::from_remote(
+                path.clone(),
+                remote_path,
+                remote_id,
+                true, // is_directory
+                0,
+                None,
+                delta_item.modified.unwrap_or_else(Utc::now),
+            )?;
             item.start_hydrating()?;
             item.complete_hydration()?;
             item.mark_synced();
 
             self.state_repository.save_item(&item).await?;
-            return Ok(());
+            return Ok(LocalCreateAction::Uploaded(0));
         }
 
-        // Read file content
-        let data = self
-            .local_filesystem
-            .read_file(path)
-            .await
-            .context("Failed to read local file for upload")?;
+        if let Some(remote) = self.find_remote_file(path, &remote_path_str).await {
+            let action = self
+                .reconcile_name_collision(path, &remote, sync_root)
+                .await?;
+            return Ok(LocalCreateAction::Reconciled(action));
+        }
 
-        // Determine parent path and file name
-        let (parent_remote_path, file_name) = split_remote_path(&remote_path_str)?;
+        self.reserve_quota(budget, path, fs_state.size, None)
+            .await?;
 
-        // Upload based on size
-        let delta_item = if data.len() as u64 > self.large_file_threshold {
-            debug!(
-                path = %path,
-                size = data.len(),
-                "Using resumable upload session (large file)"
-            );
-            with_retry("upload_file_session", || {
-                let parent = parent_remote_path.clone();
-                let name = file_name.clone();
-                let d = data.clone();
-                async move {
-                    self.cloud_provider
-                        .upload_file_session(&parent, &name, &d, None)
-                        .await
-                }
-            })
-            .await
-            .context("Failed to upload large file")?
-        } else {
-            debug!(
-                path = %path,
-                size = data.len(),
-                "Using simple upload"
-            );
-            with_retry("upload_file", || {
-                let parent = parent_remote_path.clone();
-                let name = file_name.clone();
-                let d = data.clone();
-                async move { self.cloud_provider.upload_file(&parent, &name, &d).await }
-            })
-            .await
-            .context("Failed to upload file")?
-        };
+        let (_, file_name) = split_remote_path(&remote_path_str)?;
+        let UploadedFile {
+            mut delta_item,
+            data,
+            fs_state,
+            ..
+        } = self
+            .upload_local_file(path, &remote_path_str, fs_state)
+            .await?;
+        self.send_local_mtime(&mut delta_item, fs_state.modified)
+            .await;
 
         // Create SyncItem from the upload response
         let remote_id =
@@ -1228,18 +4359,42 @@ impl SyncEngine {
             delta_item.modified.unwrap_or_else(Utc::now),
         )?;
 
+        item.metadata_mut()
+            .set_mime_type(Some(detect_mime_type(&file_name, &data)));
         item.start_hydrating()?;
         item.complete_hydration()?;
-        item.mark_synced();
-
-        // Compute and set local hash
-        if let Ok(local_hash) = self.local_filesystem.compute_hash(path).await {
-            item.set_local_hash(local_hash);
-        }
+        item.mark_synced();
+        self.record_local_state(&mut item).await;
 
         self.state_repository.save_item(&item).await?;
 
-        Ok(())
+        Ok(LocalCreateAction::Uploaded(data.len() as u64))
+    }
+
+    /// Looks up a remote file at `remote_path`, where the new local file at
+    /// `path` is about to be uploaded
+    ///
+    /// Only the sync root and tracked folders are looked in; the others are
+    /// new and cannot hold anything yet. A failed lookup is logged and
+    /// treated as no file, as before lookups were made.
+    async fn find_remote_file(&self, path: &SyncPath, remote_path: &str) -> Option<DeltaItem> {
+        let (parent, name) = split_remote_path(remote_path).ok()?;
+        if parent != RemotePath::root() {
+            let local_parent = SyncPath::new(path.as_path().parent()?.to_path_buf()).ok()?;
+            self.state_repository
+                .get_item_by_path(&local_parent)
+                .await
+                .ok()
+                .flatten()?;
+        }
+
+        match self.cloud_provider.find_child(&parent, &name).await {
+            Ok(item) => item.filter(|item| !item.is_directory && !item.is_deleted),
+            Err(err) => {
+                debug!(path = %path, error = %err, "Cannot look up remote file before upload");
+                None
+            }
+        }
     }
 
     // ========================================================================
@@ -1249,71 +4404,84 @@ impl SyncEngine {
     /// Handles a locally modified file that needs to be re-uploaded
     ///
     /// Compares the local hash with the stored content hash. If they differ,
-    /// reads and uploads the file, then updates the SyncItem.
-    #[tracing::instrument(skip(self))]
+    /// reads and uploads the file, then updates the SyncItem. Returns the
+    /// number of bytes uploaded (zero when the content was unchanged). A
+    /// file that grew beyond a provider limit or the remaining quota is not
+    /// uploaded, see [`Self::check_limits`] and [`Self::reserve_quota`].
+    #[tracing::instrument(skip(self, budget))]
     async fn handle_local_update(
         &self,
         path: &SyncPath,
         existing: &SyncItem,
         sync_root: &SyncPath,
-    ) -> Result<()> {
-        // Compute current local hash
+        budget: &mut UploadBudget,
+    ) -> Result<u64> {
+        // Stat before hashing so a write during the hash shows up as a newer mtime
+        let fs_state = self
+            .local_filesystem
+            .get_state(path)
+            .await
+            .context("Failed to get state for modified local file")?;
+        if fs_state.is_locked {
+            return Err(SyncError::FileLocked(path.as_path().clone()).into());
+        }
         let local_hash = self
             .local_filesystem
             .compute_hash(path)
             .await
             .context("Failed to compute local hash")?;
 
-        // Compare with stored content hash
-        let needs_upload = match existing.content_hash() {
-            Some(stored) => local_hash.as_str() != stored.as_str(),
-            None => true, // No stored hash, assume changed
-        };
+        // Compare with stored content hash; a former hardlink was never
+        // uploaded under its own path
+        let was_hardlink = existing.metadata().hardlink_of().is_some();
+        let needs_upload = was_hardlink
+            || match existing.content_hash() {
+                Some(stored) => local_hash.as_str() != stored.as_str(),
+                None => true, // No stored hash, assume changed
+            };
 
         if !needs_upload {
             debug!(path = %path, "Local file unchanged, skipping upload");
-            return Ok(());
+            return Ok(0);
         }
 
+        let remote_path_str = self.remote_path_of(path, sync_root)?;
+        self.check_limits(path, &remote_path_str, &fs_state, Some(existing))
+            .await?;
+
+        // Only growth counts against the quota
+        let growth = fs_state.size.saturating_sub(existing.size_bytes());
+        self.reserve_quota(budget, path, growth, Some(existing))
+            .await?;
+
         debug!(path = %path, "Local file modified, uploading update");
 
-        // Read file content
-        let data = self
-            .local_filesystem
-            .read_file(path)
-            .await
-            .context("Failed to read modified local file")?;
-
-        // Determine parent path and file name
-        let relative = path.relative_to(sync_root)?;
-        let remote_path_str = format!("/{}", relative.display()).replace('\\', "/");
-        let (parent_remote_path, file_name) = split_remote_path(&remote_path_str)?;
-
-        // Upload
-        let delta_item = if data.len() as u64 > self.large_file_threshold {
-            with_retry("upload_file_session_update", || {
-                let parent = parent_remote_path.clone();
-                let name = file_name.clone();
-                let d = data.clone();
-                async move {
-                    self.cloud_provider
-                        .upload_file_session(&parent, &name, &d, None)
-                        .await
-                }
-            })
-            .await?
-        } else {
-            with_retry("upload_file_update", || {
-                let parent = parent_remote_path.clone();
-                let name = file_name.clone();
-                let d = data.clone();
-                async move { self.cloud_provider.upload_file(&parent, &name, &d).await }
-            })
-            .await?
+        let (_, file_name) = split_remote_path(&remote_path_str)?;
+        let uploaded = match self.append_local_file(path, existing, &fs_state).await? {
+            Some(uploaded) => uploaded,
+            None => {
+                self.upload_local_file(path, &remote_path_str, fs_state)
+                    .await?
+            }
         };
-
-        // Update the SyncItem
+        let UploadedFile {
+            mut delta_item,
+            data,
+            fs_state,
+            retries,
+        } = uploaded;
+        self.send_local_mtime(&mut delta_item, fs_state.modified)
+            .await;
+
+        // Update the SyncItem; items rejected by a limit before were never
+        // uploaded and get their remote ID now
         let mut updated = existing.clone();
+        if updated.remote_id().is_none() {
+            updated.set_remote_id(
+                RemoteId::new(delta_item.id.clone())
+                    .context("Invalid remote ID in upload response")?,
+            );
+        }
         if let Some(ref hash_str) = delta_item.hash {
             if let Ok(hash) = FileHash::new(hash_str.clone()) {
                 updated.set_content_hash(hash);
@@ -1322,23 +4490,455 @@ impl SyncEngine {
         if let Some(size) = delta_item.size {
             updated.set_size_bytes(size);
         }
+        if let Some(modified) = delta_item.modified {
+            updated.set_last_modified_remote(modified);
+        }
+        // The hash taken before reading is stale if the file changed since
+        let local_hash = if retries > 0 {
+            self.local_filesystem
+                .compute_hash(path)
+                .await
+                .unwrap_or(local_hash)
+        } else {
+            local_hash
+        };
         updated.set_local_hash(local_hash);
-        updated.set_last_modified_local(Utc::now());
-
-        // If the item was in Modified state, transition to Hydrated
-        if matches!(
-            updated.state(),
-            lnxdrive_core::domain::sync_item::ItemState::Modified
-        ) {
+        if let Some(modified) = fs_state.modified {
+            updated.set_last_modified_local(modified);
+        }
+        updated
+            .metadata_mut()
+            .set_mime_type(Some(detect_mime_type(&file_name, &data)));
+        updated.metadata_mut().set_hardlink_of(None);
+
+        // If the item was in Modified state (or blocked by the quota),
+        // transition to Hydrated
+        if matches!(updated.state(), ItemState::Modified | ItemState::Error(_)) {
             updated.complete_sync()?;
         }
         updated.mark_synced();
 
         self.state_repository.save_item(&updated).await?;
 
+        Ok(data.len() as u64)
+    }
+
+    /// Sends only the bytes added to the end of `existing` since its last
+    /// sync
+    ///
+    /// Applies when the file grew and its first `size_bytes()` bytes still
+    /// hash to the synced content hash. Returns `None` when that is not the
+    /// case, when the file changed while appending, or when the provider
+    /// cannot append (OneDrive cannot, which is known before reading the
+    /// file); the caller uploads the whole file then.
+    async fn append_local_file(
+        &self,
+        path: &SyncPath,
+        existing: &SyncItem,
+        fs_state: &FileSystemState,
+    ) -> Result<Option<UploadedFile>> {
+        let synced_size = existing.size_bytes();
+        let (Some(remote_id), Some(synced_hash)) = (existing.remote_id(), existing.content_hash())
+        else {
+            return Ok(None);
+        };
+        if synced_size == 0
+            || fs_state.size <= synced_size
+            || existing.metadata().hardlink_of().is_some()
+            || !self.cloud_provider.supports_append()
+            || self.append_unsupported.load(Ordering::Relaxed)
+        {
+            return Ok(None);
+        }
+
+        let data = self
+            .local_filesystem
+            .read_file(path)
+            .await
+            .context("Failed to read local file for upload")?;
+        let Some(prefix) = data.get(..synced_size as usize) else {
+            return Ok(None);
+        };
+        let mut hasher = QuickXorHash::new();
+        hasher.update(prefix);
+        if hasher.finalize_hash()?.as_str() != synced_hash.as_str() {
+            return Ok(None);
+        }
+
+        let appended = &data[synced_size as usize..];
+        debug!(
+            path = %path,
+            offset = synced_size,
+            bytes = appended.len(),
+            "File only grew, appending"
+        );
+        let result = self
+            .run_transfer(
+                path,
+                with_retry("append_file", || async move {
+                    self.cloud_provider
+                        .append_file(remote_id, synced_size, appended)
+                        .await
+                }),
+            )
+            .await;
+        let delta_item = match result {
+            Ok(delta_item) => delta_item,
+            Err(err) if is_append_not_supported(&err) => {
+                debug!("Provider cannot append, uploading whole files");
+                self.append_unsupported.store(true, Ordering::Relaxed);
+                return Ok(None);
+            }
+            Err(err) => return Err(err.context("Failed to append to file")),
+        };
+
+        // A write since reading is covered by uploading the whole file
+        let after = self
+            .local_filesystem
+            .get_state(path)
+            .await
+            .context("Failed to get state of uploaded file")?;
+        if data.len() as u64 != after.size || has_changed(fs_state, &after) {
+            warn!(path = %path, "File changed while appending, uploading it again");
+            return Ok(None);
+        }
+        Ok(Some(UploadedFile {
+            delta_item,
+            data,
+            fs_state: after,
+            retries: 0,
+        }))
+    }
+
+    /// Reads a local file and uploads it, starting over if it changes
+    /// meanwhile
+    ///
+    /// `fs_state` is the file's state before reading. After the upload the
+    /// file is checked against it; upload sessions also check it before the
+    /// final chunk, so a changed file is never committed by them. When the
+    /// file changed, the upload result is discarded and the file read and
+    /// uploaded again, up to [`MAX_UPLOAD_DRIFT_RETRIES`] times. After that
+    /// the upload fails with [`SyncError::ChangedDuringUpload`] and the file
+    /// stays unsynced, so the next cycle picks it up again.
+    async fn upload_local_file(
+        &self,
+        path: &SyncPath,
+        remote_path: &str,
+        mut fs_state: FileSystemState,
+    ) -> Result<UploadedFile> {
+        let (parent_remote_path, file_name) = split_remote_path(remote_path)?;
+        let mut retries = 0;
+        loop {
+            let data = self
+                .local_filesystem
+                .read_file(path)
+                .await
+                .context("Failed to read local file for upload")?;
+
+            let delta_item = if data.len() as u64 > self.large_file_threshold {
+                debug!(
+                    path = %path,
+                    size = data.len(),
+                    "Using resumable upload session (large file)"
+                );
+                let reporter =
+                    self.transfer_reporter(path, TransferKind::Upload, data.len() as u64);
+                let upload = self
+                    .run_transfer(
+                        path,
+                        with_retry("upload_file_session", || {
+                            let parent = parent_remote_path.clone();
+                            let name = file_name.clone();
+                            let d = data.clone();
+                            let progress = reporter.as_ref().map(|r| r.callback());
+                            let check = unchanged_check(path, &fs_state);
+                            async move {
+                                self.cloud_provider
+                                    .upload_file_session_checked(
+                                        &parent, &name, &d, progress, check,
+                                    )
+                                    .await
+                            }
+                        }),
+                    )
+                    .await;
+                if let Some(reporter) = &reporter {
+                    reporter.finish(&upload);
+                }
+                upload.context("Failed to upload large file")
+            } else {
+                debug!(
+                    path = %path,
+                    size = data.len(),
+                    "Using simple upload"
+                );
+                self.run_transfer(
+                    path,
+                    with_retry("upload_file", || {
+                        let parent = parent_remote_path.clone();
+                        let name = file_name.clone();
+                        let d = data.clone();
+                        async move { self.cloud_provider.upload_file(&parent, &name, &d).await }
+                    }),
+                )
+                .await
+                .context("Failed to upload file")
+            };
+
+            let changed_during_upload = match delta_item {
+                Ok(_) => false,
+                Err(ref err) => is_changed_during_upload(err),
+            };
+            if !changed_during_upload {
+                let delta_item = delta_item?;
+                let after = self
+                    .local_filesystem
+                    .get_state(path)
+                    .await
+                    .context("Failed to get state of uploaded file")?;
+                if data.len() as u64 == after.size && !has_changed(&fs_state, &after) {
+                    return Ok(UploadedFile {
+                        delta_item,
+                        data,
+                        fs_state: after,
+                        retries,
+                    });
+                }
+                fs_state = after;
+            } else {
+                fs_state = self
+                    .local_filesystem
+                    .get_state(path)
+                    .await
+                    .context("Failed to get state of uploaded file")?;
+            }
+
+            retries += 1;
+            if retries > MAX_UPLOAD_DRIFT_RETRIES {
+                warn!(path = %path, "File keeps changing during upload, leaving it for the next cycle");
+                return Err(SyncError::ChangedDuringUpload(path.as_path().to_path_buf()).into());
+            }
+            warn!(path = %path, retries, "File changed during upload, uploading it again");
+        }
+    }
+
+    /// Records a new local path that is a hardlink to `primary`
+    ///
+    /// Nothing is uploaded: the item shares the primary's content hash and
+    /// remembers the primary in [`ItemMetadata::hardlink_of`]. Returns
+    /// false without recording anything while the primary has not been
+    /// uploaded yet (e.g. it was blocked by the quota); the link is then
+    /// reported again by the next scan.
+    ///
+    /// [`ItemMetadata::hardlink_of`]: lnxdrive_core::domain::sync_item::ItemMetadata::hardlink_of
+    async fn handle_local_hardlink(
+        &self,
+        path: &SyncPath,
+        primary: &SyncPath,
+        sync_root: &SyncPath,
+    ) -> Result<bool> {
+        let primary_item = match self.state_repository.get_item_by_path(primary).await? {
+            Some(item) if item.remote_id().is_some() => item,
+            _ => {
+                debug!(path = %path, primary = %primary, "Hardlink target not uploaded yet");
+                return Ok(false);
+            }
+        };
+
+        let remote_path = RemotePath::new(self.remote_path_of(path, sync_root)?)
+            .context("Failed to construct remote path for hardlink")?;
+
+        let mut item = SyncItem::new_file(
+            path.clone(),
+            remote_path,
+            primary_item.size_bytes(),
+            primary_item.metadata().mime_type().map(str::to_string),
+        )?;
+        if let Some(hash) = primary_item.content_hash() {
+            item.set_content_hash(hash.clone());
+        }
+        item.metadata_mut().set_hardlink_of(Some(primary.clone()));
+        item.start_hydrating()?;
+        item.complete_hydration()?;
+        item.mark_synced();
+        self.record_local_state(&mut item).await;
+        self.state_repository.save_item(&item).await?;
+
+        debug!(path = %path, primary = %primary, "Recorded hardlink without uploading");
+        Ok(true)
+    }
+
+    /// Recreates the hardlinks to `primary` after its file was replaced
+    ///
+    /// Downloads write through a temporary file and rename it over the
+    /// target, which detaches the other paths of a hardlinked file.
+    async fn relink_hardlinks(&self, primary: &SyncItem) -> Result<()> {
+        let links = self
+            .state_repository
+            .query_items(&lnxdrive_core::ports::state_repository::ItemFilter::new())
+            .await
+            .context("Failed to query hardlinks")?
+            .into_iter()
+            .filter(|item| item.metadata().hardlink_of() == Some(primary.local_path()));
+
+        for mut link in links {
+            let link_path = link.local_path().as_path().to_path_buf();
+            let _ = tokio::fs::remove_file(&link_path).await;
+            tokio::fs::hard_link(primary.local_path().as_path(), &link_path)
+                .await
+                .with_context(|| format!("Failed to recreate hardlink {}", link.local_path()))?;
+
+            if let Some(hash) = primary.content_hash() {
+                link.set_content_hash(hash.clone());
+            }
+            link.set_size_bytes(primary.size_bytes());
+            self.record_local_state(&mut link).await;
+            link.mark_synced();
+            self.state_repository.save_item(&link).await?;
+            debug!(path = %link.local_path(), "Recreated hardlink after download");
+        }
         Ok(())
     }
 
+    /// Checks an item against the provider's file size and path length limits
+    ///
+    /// An item over a limit is recorded in the `Error` state with a
+    /// [`ReasonCode`] naming the limit, so `status` and `explain` show it,
+    /// and an audit entry is written. Its size and mtime are recorded too,
+    /// so it is only checked again once it changes (e.g. is shrunk or
+    /// moved to a shorter path).
+    async fn check_limits(
+        &self,
+        path: &SyncPath,
+        remote_path: &str,
+        fs_state: &FileSystemState,
+        existing: Option<&SyncItem>,
+    ) -> Result<()> {
+        let size = fs_state.size;
+        let Err(violation) = self.limits.check(remote_path, size) else {
+            return Ok(());
+        };
+        warn!(path = %path, %violation, "Not uploading item that exceeds a provider limit");
+
+        let mut item = match existing {
+            Some(item) => item.clone(),
+            None => {
+                let remote = RemotePath::new(remote_path.to_string())
+                    .context("Failed to construct remote path")?;
+                if fs_state.is_directory() {
+                    SyncItem::new_directory(path.clone(), remote)?
+                } else {
+                    SyncItem::new_file(path.clone(), remote, size, None)?
+                }
+            }
+        };
+        let message = violation.to_string();
+        item.transition_to_error(ErrorInfo::from_reason(violation.reason(), &message))?;
+        item.set_size_bytes(size);
+        if let Some(modified) = fs_state.modified {
+            item.set_last_modified_local(modified);
+        }
+        // Lets the next scan skip the item while its size and mtime match
+        item.mark_synced();
+        self.state_repository.save_item(&item).await?;
+
+        let entry = AuditEntry::new(
+            AuditAction::FileUpload,
+            AuditResult::failed(violation.reason().as_str(), &message),
+        )
+        .with_item_id(*item.id())
+        .with_details(serde_json::json!({ "path": path.to_string() }));
+        self.state_repository.save_audit(&entry).await?;
+
+        Err(SyncError::LimitExceeded {
+            path: path.as_path().clone(),
+            violation,
+        }
+        .into())
+    }
+
+    /// Checks that an upload of `needed` bytes fits in the remaining quota
+    ///
+    /// The quota is fetched before the first upload of the cycle; if that
+    /// fails the upload is attempted anyway. A file that does not fit is
+    /// reported once: an audit entry with [`ReasonCode::QuotaExceeded`]
+    /// and, for a tracked file, an `Error` state with the same code. It is
+    /// picked up again by every later scan, so it is uploaded as soon as
+    /// space is freed.
+    async fn reserve_quota(
+        &self,
+        budget: &mut UploadBudget,
+        path: &SyncPath,
+        needed: u64,
+        existing: Option<&SyncItem>,
+    ) -> Result<()> {
+        if *budget == UploadBudget::Unknown {
+            *budget = match self.cloud_provider.get_quota().await {
+                Ok(quota) => quota.into(),
+                Err(err) => {
+                    warn!(%err, "Failed to fetch storage quota, uploading without checking it");
+                    UploadBudget::Unlimited
+                }
+            };
+        }
+
+        let remaining = match *budget {
+            UploadBudget::Remaining(remaining) if needed > remaining => remaining,
+            _ => {
+                budget.consume(needed);
+                self.quota_blocked_paths().remove(path.as_path());
+                return Ok(());
+            }
+        };
+
+        let error = SyncError::QuotaExceeded {
+            path: path.as_path().clone(),
+            needed,
+            remaining,
+        };
+        let newly_blocked = self.quota_blocked_paths().insert(path.as_path().clone());
+        if newly_blocked {
+            warn!(path = %path, needed, remaining, "Upload blocked by storage quota");
+            let message = error.to_string();
+            if let Some(item) = existing {
+                let mut blocked = item.clone();
+                blocked.transition_to_error(ErrorInfo::from_reason(
+                    ReasonCode::QuotaExceeded,
+                    &message,
+                ))?;
+                self.state_repository.save_item(&blocked).await?;
+            }
+            let mut entry = AuditEntry::new(
+                AuditAction::FileUpload,
+                AuditResult::failed(ReasonCode::QuotaExceeded.as_str(), &message),
+            )
+            .with_details(serde_json::json!({
+                "path": path.to_string(),
+                "needed": needed,
+                "remaining": remaining,
+            }));
+            if let Some(item) = existing {
+                entry = entry.with_item_id(*item.id());
+            }
+            self.state_repository.save_audit(&entry).await?;
+        } else {
+            debug!(path = %path, needed, remaining, "Upload still blocked by storage quota");
+        }
+        Err(error.into())
+    }
+
+    /// Locks the set of paths blocked by the storage quota
+    fn quota_blocked_paths(&self) -> std::sync::MutexGuard<'_, HashSet<PathBuf>> {
+        self.quota_blocked
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// Number of paths currently blocked by the storage quota
+    fn quota_blocked_count(&self) -> usize {
+        self.quota_blocked_paths().len()
+    }
+
     // ========================================================================
     // T160: handle_local_delete()
     // ========================================================================
@@ -1349,10 +4949,11 @@ impl SyncEngine {
     /// Deletes it from the cloud provider and marks the SyncItem as Deleted.
     #[tracing::instrument(skip(self))]
     async fn handle_local_delete(&self, item: &SyncItem) -> Result<()> {
-        let remote_id = item
-            .remote_id()
-            .ok_or_else(|| anyhow::anyhow!("Cannot delete item without remote ID"))?
-            .clone();
+        let Some(remote_id) = item.remote_id().cloned() else {
+            // Never uploaded (e.g. rejected by a provider limit)
+            self.state_repository.delete_item(item.id()).await?;
+            return Ok(());
+        };
 
         debug!(
             path = %item.local_path(),
@@ -1360,27 +4961,72 @@ impl SyncEngine {
             "Deleting item from cloud (local file deleted)"
         );
 
-        // Delete from cloud with retry
-        with_retry("delete_item", || {
-            let rid = remote_id.clone();
-            async move { self.cloud_provider.delete_item(&rid).await }
-        })
-        .await
-        .context("Failed to delete item from cloud")?;
+        self.delete_remote_item(&remote_id)
+            .await
+            .context("Failed to delete item from cloud")?;
 
-        // Mark as deleted in state repository
-        let mut updated = item.clone();
-        updated.mark_deleted()?;
-        self.state_repository.save_item(&updated).await?;
+        // The deletion is complete on both sides; drop the record so it is
+        // not sent to the cloud again on the next scan
+        self.state_repository.delete_item(item.id()).await?;
 
         Ok(())
     }
+
+    /// Deletes a remote item, with retry
+    ///
+    /// An item that is already gone counts as deleted: it was removed
+    /// remotely after the last delta was fetched.
+    async fn delete_remote_item(&self, remote_id: &RemoteId) -> Result<()> {
+        let result = with_retry("delete_item", || async move {
+            self.cloud_provider.delete_item(remote_id).await
+        })
+        .await;
+        match result {
+            Err(err) if is_remote_item_not_found(&err) => {
+                debug!(remote_id = %remote_id, "Remote item already deleted");
+                Ok(())
+            }
+            other => other,
+        }
+    }
 }
 
 // ============================================================================
 // Helper functions
 // ============================================================================
 
+/// Returns true if an upload failed because the commit check found the
+/// file changed
+fn is_changed_during_upload(err: &anyhow::Error) -> bool {
+    err.chain().any(|cause| {
+        matches!(
+            cause.downcast_ref::<SyncError>(),
+            Some(SyncError::ChangedDuringUpload(_))
+        )
+    })
+}
+
+/// Returns true if a file's size or modification time differ between two
+/// observations
+fn has_changed(before: &FileSystemState, after: &FileSystemState) -> bool {
+    before.size != after.size || before.modified != after.modified
+}
+
+/// Builds the check an upload session runs before its final chunk: the
+/// file must still have the size and modification time of `fs_state`
+fn unchanged_check(path: &SyncPath, fs_state: &FileSystemState) -> CommitCheck {
+    let path = path.as_path().to_path_buf();
+    let (size, modified) = (fs_state.size, fs_state.modified);
+    Box::new(move || {
+        let metadata = std::fs::metadata(&path)?;
+        let now_modified = metadata.modified().ok().and_then(to_utc);
+        if metadata.len() != size || now_modified != modified {
+            return Err(SyncError::ChangedDuringUpload(path.clone()).into());
+        }
+        Ok(())
+    })
+}
+
 /// Splits a remote path like "/Documents/file.txt" into parent ("/Documents")
 /// and file name ("file.txt")
 fn split_remote_path(path: &str) -> Result<(RemotePath, String)> {
@@ -1396,6 +5042,54 @@ fn split_remote_path(path: &str) -> Result<(RemotePath, String)> {
     Ok((parent, file_name))
 }
 
+/// Logs a downloaded file whose local hash differs from the remote hash
+fn warn_on_download_mismatch(item: &SyncItem) {
+    if let (Some(remote), Some(local)) = (item.content_hash(), item.local_hash()) {
+        if remote != local {
+            warn!(
+                path = %item.local_path(),
+                remote = %remote,
+                local = %local,
+                "Downloaded content does not match the remote hash"
+            );
+        }
+    }
+}
+
+/// Lists the regular files below `dir`, at any depth
+async fn local_files_under(dir: &std::path::Path) -> Result<Vec<std::path::PathBuf>> {
+    let mut files = Vec::new();
+    let mut pending = vec![dir.to_path_buf()];
+    while let Some(current) = pending.pop() {
+        let mut entries = tokio::fs::read_dir(&current)
+            .await
+            .with_context(|| format!("Failed to read directory: {}", current.display()))?;
+        while let Some(entry) = entries.next_entry().await? {
+            let file_type = entry.file_type().await?;
+            if file_type.is_dir() {
+                pending.push(entry.path());
+            } else if file_type.is_file() {
+                files.push(entry.path());
+            }
+        }
+    }
+    Ok(files)
+}
+
+/// Returns the nearest existing ancestor of `path` below `sync_root` if it
+/// is a file, as left by an unresolved type conflict
+async fn shadowing_local_file(path: &SyncPath, sync_root: &SyncPath) -> Option<std::path::PathBuf> {
+    for ancestor in path.as_path().ancestors().skip(1) {
+        if ancestor == sync_root.as_path() {
+            break;
+        }
+        if let Ok(metadata) = tokio::fs::metadata(ancestor).await {
+            return metadata.is_file().then(|| ancestor.to_path_buf());
+        }
+    }
+    None
+}
+
 /// Extracts the token parameter from a delta link URL
 ///
 /// Input: `https://graph.microsoft.com/v1.0/me/drive/root/delta?token=abc123`
@@ -1472,6 +5166,24 @@ mod tests {
         assert!(is_transient_error(&err));
     }
 
+    #[tokio::test]
+    async fn test_with_retry_counts_throttled_requests() {
+        let before = throttled_requests();
+        let attempts = AtomicU64::new(0);
+
+        let result = with_retry("throttled", || async {
+            if attempts.fetch_add(1, Ordering::SeqCst) == 0 {
+                Err(anyhow::anyhow!("Too many requests (429)"))
+            } else {
+                Ok(())
+            }
+        })
+        .await;
+
+        assert!(result.is_ok());
+        assert!(throttled_requests() > before);
+    }
+
     #[test]
     fn test_is_transient_error_server() {
         let err = anyhow::anyhow!("Server error: 503 Service Unavailable");
@@ -1490,12 +5202,77 @@ mod tests {
         assert!(!is_transient_error(&err));
     }
 
+    #[test]
+    fn test_error_reason_from_typed_errors() {
+        let locked = anyhow::Error::new(SyncError::FileLocked(PathBuf::from("/a")))
+            .context("Failed to upload");
+        assert_eq!(error_reason(&locked), ReasonCode::Locked);
+
+        let name = anyhow::Error::new(DomainError::InvalidRemotePath("/a|b".into()));
+        assert_eq!(error_reason(&name), ReasonCode::InvalidName);
+
+        let policy = anyhow::Error::new(ConflictError::InvalidStrategy {
+            field: "default_strategy".into(),
+            strategy: "merge".into(),
+        });
+        assert_eq!(error_reason(&policy), ReasonCode::MergeFailed);
+
+        let io = anyhow::Error::new(std::io::Error::from(std::io::ErrorKind::PermissionDenied))
+            .context("Failed to read /a");
+        assert_eq!(error_reason(&io), ReasonCode::PermissionDenied);
+    }
+
+    #[test]
+    fn test_error_reason_from_provider_messages() {
+        let reason = |msg: &str| error_reason(&anyhow::anyhow!("{msg}"));
+        assert_eq!(
+            reason("HTTP status client error (423 Locked) for url (https://graph)"),
+            ReasonCode::Locked
+        );
+        assert_eq!(
+            reason("Chunk upload failed with status 507 Insufficient Storage: {}"),
+            ReasonCode::QuotaExceeded
+        );
+        assert_eq!(
+            reason("HTTP status client error (403 Forbidden) for url (https://graph)"),
+            ReasonCode::PermissionDenied
+        );
+        assert_eq!(
+            reason("HTTP status client error (409 Conflict) for url (https://graph)"),
+            ReasonCode::Conflict
+        );
+        assert_eq!(reason("Too many requests (429)"), ReasonCode::RateLimited);
+        assert_eq!(
+            reason("Token refresh failed: invalid_grant"),
+            ReasonCode::AuthError
+        );
+        assert_eq!(
+            reason("Network error: connection refused"),
+            ReasonCode::NetworkError
+        );
+        assert_eq!(
+            reason("The name contains an invalid name character"),
+            ReasonCode::InvalidName
+        );
+        assert_eq!(reason("File not found: /path/to/file"), ReasonCode::Unknown);
+    }
+
     #[test]
     fn test_sync_result_default() {
         let result = SyncResult {
             files_downloaded: 0,
             files_uploaded: 0,
             files_deleted: 0,
+            bytes_downloaded: 0,
+            bytes_uploaded: 0,
+            conflicts_detected: 0,
+            conflicts_auto_resolved: 0,
+            files_recovered: 0,
+            conflicts_quarantined: 0,
+            uploads_blocked: 0,
+            uploads_deferred: 0,
+            transfers_paused: 0,
+            remote_batches: 0,
             errors: Vec::new(),
             duration_ms: 0,
         };
@@ -1503,6 +5280,51 @@ mod tests {
         assert!(result.errors.is_empty());
     }
 
+    #[test]
+    fn test_sync_result_to_history_entry() {
+        let started = Utc::now();
+        let result = SyncResult {
+            files_downloaded: 2,
+            files_uploaded: 1,
+            files_deleted: 0,
+            bytes_downloaded: 300,
+            bytes_uploaded: 40,
+            conflicts_detected: 1,
+            conflicts_auto_resolved: 1,
+            files_recovered: 0,
+            conflicts_quarantined: 0,
+            uploads_blocked: 0,
+            uploads_deferred: 0,
+            transfers_paused: 0,
+            remote_batches: 0,
+            errors: vec!["oops".to_string()],
+            duration_ms: 25,
+        };
+        let entry = result.to_history_entry(started);
+        assert!(entry.success);
+        assert_eq!(entry.started_at, started);
+        assert_eq!(entry.files_downloaded, 2);
+        assert_eq!(entry.files_uploaded, 1);
+        assert_eq!(entry.bytes_downloaded, 300);
+        assert_eq!(entry.bytes_uploaded, 40);
+        assert_eq!(entry.errors, vec!["oops".to_string()]);
+        assert_eq!(entry.duration_ms, 25);
+    }
+
+    #[test]
+    fn test_reconcile_report_total_changes() {
+        let report = ReconcileReport {
+            files_checked: 10,
+            files_hashed: 3,
+            modified: 2,
+            created: 4,
+            deleted: 1,
+            duration_ms: 5,
+        };
+        assert_eq!(report.total_changes(), 7);
+        assert_eq!(ReconcileReport::default().total_changes(), 0);
+    }
+
     // T168/T170: 410 Gone detection tests
     #[test]
     fn test_410_gone_detected_in_error_string() {
@@ -1511,6 +5333,17 @@ mod tests {
         assert!(err_str.contains("410") || err_str.contains("Gone"));
     }
 
+    #[test]
+    fn test_delta_token_expired_detected_through_context() {
+        use lnxdrive_core::ports::cloud_provider::DeltaTokenExpired;
+
+        let err = anyhow::Error::new(DeltaTokenExpired).context("Delta query returned 410 Gone");
+        assert!(is_delta_token_expired(&err.context("get_delta failed")));
+        assert!(!is_delta_token_expired(&anyhow::anyhow!(
+            "500 Internal Server Error"
+        )));
+    }
+
     #[test]
     fn test_410_gone_not_transient() {
         // 410 Gone should NOT be treated as a transient error
@@ -0,0 +1,129 @@
+//! Built-in sync exclusions
+//!
+//! Besides user patterns, two kinds of entries can be left out of sync by
+//! name alone (see `sync.exclude_hidden` and `sync.exclude_junk`):
+//!
+//! - hidden entries, whose name starts with a dot (`.git`, `.cache`)
+//! - files operating systems leave behind in every folder they show
+//!   ([`JUNK_FILE_NAMES`]), and the `name~` backups editors leave next to
+//!   the files they save
+//!
+//! An excluded folder excludes everything below it. The same rules apply
+//! to local scans, watcher events and remote delta items, so an excluded
+//! entry is neither uploaded nor downloaded.
+
+use std::path::{Component, Path};
+
+use lnxdrive_core::config::SyncConfig;
+
+/// Names of the files desktop environments create on their own, compared
+/// without regard to case
+pub const JUNK_FILE_NAMES: &[&str] = &[".DS_Store", "Thumbs.db", "desktop.ini"];
+
+/// Which built-in exclusions are enabled
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SyncExclusions {
+    /// Leave out entries whose name starts with a dot
+    pub hidden: bool,
+    /// Leave out [`JUNK_FILE_NAMES`] and `name~` backups
+    pub junk: bool,
+}
+
+impl SyncExclusions {
+    /// Reads the enabled exclusions from the sync settings
+    pub fn from_config(config: &SyncConfig) -> Self {
+        Self {
+            hidden: config.exclude_hidden,
+            junk: config.exclude_junk,
+        }
+    }
+
+    /// Returns true if no exclusion is enabled
+    pub fn is_empty(&self) -> bool {
+        !self.hidden && !self.junk
+    }
+
+    /// Returns true if an entry named `name` is excluded
+    pub fn excludes_name(&self, name: &str) -> bool {
+        if self.hidden && name.starts_with('.') && name != "." && name != ".." {
+            return true;
+        }
+        self.junk
+            && (is_backup_name(name)
+                || JUNK_FILE_NAMES
+                    .iter()
+                    .any(|junk| junk.eq_ignore_ascii_case(name)))
+    }
+
+    /// Returns true if `path` or one of its folders below `root` is excluded
+    ///
+    /// Paths outside `root` are checked in full, so relative paths (such as
+    /// the remote path of a delta item) can be checked with an empty root.
+    pub fn excludes(&self, root: &Path, path: &Path) -> bool {
+        if self.is_empty() {
+            return false;
+        }
+        let relative = path.strip_prefix(root).unwrap_or(path);
+        relative.components().any(|component| match component {
+            Component::Normal(name) => name.to_str().is_some_and(|n| self.excludes_name(n)),
+            _ => false,
+        })
+    }
+}
+
+/// Returns true if `name` is a backup left by vim, emacs or gedit
+/// (`report.txt~`)
+fn is_backup_name(name: &str) -> bool {
+    name.len() > 1 && name.ends_with('~')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ALL: SyncExclusions = SyncExclusions {
+        hidden: true,
+        junk: true,
+    };
+
+    #[test]
+    fn test_excludes_name() {
+        assert!(ALL.excludes_name(".git"));
+        assert!(ALL.excludes_name(".bashrc"));
+        assert!(ALL.excludes_name("Thumbs.db"));
+        assert!(ALL.excludes_name("thumbs.db"));
+        assert!(ALL.excludes_name("Desktop.ini"));
+        assert!(ALL.excludes_name("report.txt~"));
+        assert!(!ALL.excludes_name("report.txt"));
+        assert!(!ALL.excludes_name("~"));
+        assert!(!ALL.excludes_name(".."));
+
+        let junk_only = SyncExclusions {
+            hidden: false,
+            junk: true,
+        };
+        assert!(junk_only.excludes_name(".DS_Store"));
+        assert!(!junk_only.excludes_name(".git"));
+        assert!(!SyncExclusions::default().excludes_name("Thumbs.db"));
+        assert!(!SyncExclusions::default().excludes_name("report.txt~"));
+    }
+
+    #[test]
+    fn test_excludes_checks_every_folder_below_root() {
+        let root = Path::new("/home/user/.local/OneDrive");
+        assert!(ALL.excludes(root, &root.join("project/.git/config")));
+        assert!(ALL.excludes(root, &root.join("Photos/Thumbs.db")));
+        assert!(!ALL.excludes(root, &root.join("project/src/main.rs")));
+        assert!(!ALL.excludes(root, root));
+
+        assert!(ALL.excludes(Path::new(""), Path::new("/project/.git/HEAD")));
+        assert!(!ALL.excludes(Path::new(""), Path::new("/project/README.md")));
+    }
+
+    #[test]
+    fn test_from_config_defaults_to_junk_only() {
+        let exclusions = SyncExclusions::from_config(&SyncConfig::default());
+        assert!(!exclusions.hidden);
+        assert!(exclusions.junk);
+    }
+}
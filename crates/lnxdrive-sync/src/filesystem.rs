@@ -5,20 +5,44 @@
 //! ## Design Decisions
 //!
 //! - **Atomic writes**: Uses write-to-temp + rename to avoid partial writes
-//!   on crash or power loss.
-//! - **Lock detection**: Attempts an exclusive open via `spawn_blocking` to
-//!   check whether another process holds the file.
-//! - **quickXorHash**: Implements the OneDrive-compatible hash so local and
-//!   remote hashes can be compared without downloading content.
+//!   on crash or power loss. When the temporary file is on another
+//!   filesystem (`EXDEV`), it is copied next to the destination, synced and
+//!   renamed from there instead.
+//! - **Lock detection**: A file counts as locked while an office suite's
+//!   lock file sits next to it (`.~lock.<name>#`, `~$<name>`) or another
+//!   process holds an advisory lock on it (`flock` or an `fcntl` write
+//!   lock), so it is not uploaded half-written.
+//! - **quickXorHash**: Uses the OneDrive-compatible [`QuickXorHash`] so
+//!   local and remote hashes can be compared without downloading content.
+//! - **Hash cache**: Hashes are cached per path together with the inode,
+//!   size and mtime they were computed from, so unchanged files are not
+//!   read again. Entries whose mtime is too close to the time of hashing
+//!   are not trusted (see [`mtime_is_reliable`]).
+//! - **Streaming hashes**: `read_file` and `write_file` feed the bytes they
+//!   move through a [`StreamingHasher`] and cache the result, so hashing a
+//!   file right after transferring it needs no extra read pass.
 //! - **Watch stub**: Returns a no-op `WatchHandle`; real inotify-based
 //!   watching is planned for Phase 6.
 
-use std::io::ErrorKind;
+use std::{
+    collections::HashMap,
+    fs::File,
+    io::{self, ErrorKind, Read, Write},
+    os::{fd::AsRawFd, unix::fs::MetadataExt},
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+    time::{SystemTime, UNIX_EPOCH},
+};
 
-use base64::Engine;
-use chrono::DateTime;
+use chrono::{DateTime, Utc};
 use lnxdrive_core::{
-    domain::newtypes::{FileHash, SyncPath},
+    domain::{
+        newtypes::{FileHash, SyncPath},
+        QuickXorHash,
+    },
     ports::local_filesystem::{FileSystemState, ILocalFileSystem, WatchHandle},
 };
 use tracing::{debug, instrument};
@@ -29,74 +53,443 @@ use tracing::{debug, instrument};
 
 /// Adapter that bridges the [`ILocalFileSystem`] port to the real filesystem.
 ///
-/// This is a zero-sized struct because all operations derive their context
-/// from the [`SyncPath`] arguments. Configuration (e.g. sync root) lives
-/// at a higher layer.
+/// All operations derive their context from the [`SyncPath`] arguments;
+/// configuration (e.g. sync root) lives at a higher layer. The only state
+/// is the hash cache, which clones share, and an optional directory for
+/// the temporary files of atomic writes.
 #[derive(Debug, Clone, Default)]
-pub struct LocalFileSystemAdapter;
+pub struct LocalFileSystemAdapter {
+    hash_cache: Arc<Mutex<HashMap<PathBuf, CachedHash>>>,
+    temp_dir: Option<PathBuf>,
+}
 
-impl LocalFileSystemAdapter {
-    /// Create a new `LocalFileSystemAdapter`.
+/// A computed hash and the file metadata it was computed from
+#[derive(Debug, Clone)]
+struct CachedHash {
+    inode: u64,
+    size: u64,
+    modified: DateTime<Utc>,
+    hashed_at: DateTime<Utc>,
+    hash: FileHash,
+}
+
+// ============================================================================
+// Streaming hasher
+// ============================================================================
+
+/// Size of the blocks read from or written to disk while hashing
+pub const HASH_CHUNK_SIZE: usize = 64 * 1024;
+
+/// Incremental quickXorHash of a byte stream
+///
+/// Transfers feed each buffer to [`update`](Self::update) as it passes
+/// through, so the hash is ready when the transfer ends. Splitting the
+/// stream into blocks of any size gives the same hash as hashing the
+/// whole content at once.
+#[derive(Debug, Clone, Default)]
+pub struct StreamingHasher {
+    hasher: QuickXorHash,
+    bytes: u64,
+}
+
+impl StreamingHasher {
+    /// Creates a hasher that has seen no bytes
     #[must_use]
     pub fn new() -> Self {
-        Self
+        Self::default()
+    }
+
+    /// Feeds the next block of the stream
+    pub fn update(&mut self, block: &[u8]) {
+        self.hasher.update(block);
+        self.bytes += block.len() as u64;
+    }
+
+    /// Returns how many bytes were fed so far
+    pub fn bytes_hashed(&self) -> u64 {
+        self.bytes
+    }
+
+    /// Returns the hash of everything fed so far
+    pub fn finish(self) -> anyhow::Result<FileHash> {
+        Ok(self.hasher.finalize_hash()?)
+    }
+}
+
+/// Reads a file in [`HASH_CHUNK_SIZE`] blocks, hashing as it goes
+///
+/// The blocks are appended to `sink` when one is given. Returns the hash
+/// and, unless the file changed while it was read, a cache entry for it.
+fn read_and_hash(
+    path: &Path,
+    mut sink: Option<&mut Vec<u8>>,
+) -> anyhow::Result<(FileHash, Option<CachedHash>)> {
+    let mut file = std::fs::File::open(path)?;
+    let before = file.metadata()?;
+    // Taken before reading so a write racing with the read is never trusted
+    let hashed_at = Utc::now();
+
+    if let Some(sink) = sink.as_deref_mut() {
+        sink.reserve(before.len() as usize);
+    }
+    let mut hasher = StreamingHasher::new();
+    let mut buf = vec![0u8; HASH_CHUNK_SIZE];
+    loop {
+        let n = match file.read(&mut buf) {
+            Ok(0) => break,
+            Ok(n) => n,
+            Err(e) if e.kind() == ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e.into()),
+        };
+        hasher.update(&buf[..n]);
+        if let Some(sink) = sink.as_deref_mut() {
+            sink.extend_from_slice(&buf[..n]);
+        }
+    }
+
+    let after = file.metadata()?;
+    let bytes = hasher.bytes_hashed();
+    let hash = hasher.finish()?;
+    let modified = before.modified().ok().and_then(to_utc);
+    let entry = modified
+        .filter(|m| {
+            bytes == before.len()
+                && after.len() == before.len()
+                && after.modified().ok().and_then(to_utc) == Some(*m)
+        })
+        .map(|modified| CachedHash {
+            inode: before.ino(),
+            size: before.len(),
+            modified,
+            hashed_at,
+            hash: hash.clone(),
+        });
+    Ok((hash, entry))
+}
+
+/// Writes `data` to a new file in [`HASH_CHUNK_SIZE`] blocks, hashing as it goes
+///
+/// The file gets the modification time `modified` if one is given. Returns
+/// a cache entry for the written content. `path` must not be visible to
+/// other writers (a temporary file), so the metadata read after writing
+/// belongs to exactly these bytes.
+fn write_and_hash(
+    path: &Path,
+    data: &[u8],
+    modified: Option<SystemTime>,
+) -> anyhow::Result<Option<CachedHash>> {
+    let mut file = std::fs::File::create(path)?;
+    let mut hasher = StreamingHasher::new();
+    for block in data.chunks(HASH_CHUNK_SIZE) {
+        file.write_all(block)?;
+        hasher.update(block);
+    }
+    if let Some(modified) = modified {
+        file.set_modified(modified)?;
     }
+    let metadata = file.metadata()?;
+    let hashed_at = Utc::now();
+    let hash = hasher.finish()?;
+    Ok(metadata
+        .modified()
+        .ok()
+        .and_then(to_utc)
+        .map(|modified| CachedHash {
+            inode: metadata.ino(),
+            size: metadata.len(),
+            modified,
+            hashed_at,
+            hash,
+        }))
 }
 
 // ============================================================================
-// QuickXorHash implementation
+// Atomic writes
 // ============================================================================
 
-/// OneDrive-compatible quickXorHash algorithm.
+/// Keeps temporary files of concurrent writes apart in a shared directory
+static NEXT_TEMP_FILE: AtomicU64 = AtomicU64::new(0);
+
+/// Returns the temporary path next to `target` (`<target>.tmp`)
+fn sibling_temp_path(target: &Path) -> PathBuf {
+    let mut p = target.as_os_str().to_owned();
+    p.push(".tmp");
+    PathBuf::from(p)
+}
+
+/// Returns true if `error` is a rename across filesystems
+fn is_cross_device(error: &std::io::Error) -> bool {
+    error.raw_os_error() == Some(libc::EXDEV)
+}
+
+/// Moves the fully written `tmp` over `target`
 ///
-/// The algorithm works on a 160-bit (20-byte) hash state. For each input
-/// byte, it is XOR-ed into the state at the current *bit* position and the
-/// position advances by 11 bits (mod 160). After processing all input bytes
-/// the total file length (as a little-endian `u64`) is XOR-ed into the
-/// first 8 bytes of the state. The final 20-byte result is base64-encoded.
-struct QuickXorHash {
-    data: [u8; 20],
-    shift: usize,
-    length: u64,
+/// Renames when both are on the same filesystem and falls back to
+/// [`copy_into_place`] otherwise. Returns true if the content was copied.
+fn move_into_place(tmp: &Path, target: &Path) -> std::io::Result<bool> {
+    match std::fs::rename(tmp, target) {
+        Ok(()) => Ok(false),
+        Err(e) if is_cross_device(&e) => {
+            debug!(?tmp, "temporary file is on another filesystem, copying");
+            copy_into_place(tmp, target)?;
+            Ok(true)
+        }
+        Err(e) => Err(e),
+    }
 }
 
-impl QuickXorHash {
-    /// Width of the hash in bits.
-    const WIDTH_BITS: usize = 160;
+/// Replaces `target` with a copy of `tmp` from another filesystem
+///
+/// The copy goes to a staging file next to `target`, keeps the modification
+/// time of `tmp` and is synced to disk before being renamed over `target`,
+/// which is atomic again because both are in the same directory. If any
+/// step fails the staging file is removed, so `target` is never left
+/// partially written.
+fn copy_into_place(tmp: &Path, target: &Path) -> std::io::Result<()> {
+    let staging = sibling_temp_path(target);
+    let copied = std::fs::copy(tmp, &staging)
+        .and_then(|_| {
+            let modified = std::fs::metadata(tmp)?.modified()?;
+            let file = std::fs::OpenOptions::new().write(true).open(&staging)?;
+            file.set_modified(modified)?;
+            file.sync_all()
+        })
+        .and_then(|()| std::fs::rename(&staging, target));
+    if let Err(e) = copied {
+        let _ = std::fs::remove_file(&staging);
+        return Err(e);
+    }
+    let _ = std::fs::remove_file(tmp);
+    Ok(())
+}
+
+// ============================================================================
+// Lock detection
+// ============================================================================
 
-    /// Number of bits the position advances per input byte.
-    const SHIFT_STEP: usize = 11;
+/// Returns true if `name` is the lock file an office suite creates next
+/// to a document it has open: `.~lock.<name>#` (LibreOffice) or
+/// `~$<name>` (Microsoft Office)
+pub fn is_lock_file(name: &str) -> bool {
+    (name.starts_with(".~lock.") && name.ends_with('#')) || name.starts_with("~$")
+}
 
-    fn new() -> Self {
-        Self {
-            data: [0u8; 20],
-            shift: 0,
-            length: 0,
-        }
+/// Returns the lock files an office suite may create for the file at `path`
+///
+/// Microsoft Office drops the first two characters of longer names
+/// (`~$port.docx` for `report.docx`), so both forms are returned.
+fn lock_file_candidates(path: &Path) -> Vec<PathBuf> {
+    let (Some(dir), Some(name)) = (path.parent(), path.file_name().and_then(|n| n.to_str())) else {
+        return Vec::new();
+    };
+    let mut candidates = vec![
+        dir.join(format!(".~lock.{name}#")),
+        dir.join(format!("~${name}")),
+    ];
+    if let Some(short) = name.get(2..).filter(|short| short.contains('.')) {
+        candidates.push(dir.join(format!("~${short}")));
     }
+    candidates
+}
 
-    fn update(&mut self, input: &[u8]) {
-        for &byte in input {
-            let byte_pos = self.shift / 8;
-            let bit_offset = self.shift % 8;
+/// Returns true if another open file description holds an advisory write
+/// lock on `file`: an exclusive `flock` or an `fcntl` write lock
+fn has_advisory_lock(file: &File) -> io::Result<bool> {
+    let fd = file.as_raw_fd();
+    // SAFETY: flock only operates on the descriptor, which `file` keeps open.
+    if unsafe { libc::flock(fd, libc::LOCK_SH | libc::LOCK_NB) } != 0 {
+        let err = io::Error::last_os_error();
+        return if err.raw_os_error() == Some(libc::EWOULDBLOCK) {
+            Ok(true)
+        } else {
+            Err(err)
+        };
+    }
+    // SAFETY: as above; releases the shared lock taken for the check.
+    unsafe { libc::flock(fd, libc::LOCK_UN) };
+
+    // Would a read lock over the whole file conflict with a write lock?
+    // Open file description locks also see the locks of this process.
+    // SAFETY: an all-zero `flock` is a valid value of the plain C struct.
+    let mut lock: libc::flock = unsafe { std::mem::zeroed() };
+    lock.l_type = libc::F_RDLCK as libc::c_short;
+    lock.l_whence = libc::SEEK_SET as libc::c_short;
+    // SAFETY: `lock` is a valid flock buffer for F_OFD_GETLK.
+    if unsafe { libc::fcntl(fd, libc::F_OFD_GETLK, &mut lock) } != 0 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(lock.l_type != libc::F_UNLCK as libc::c_short)
+}
 
-            self.data[byte_pos % 20] ^= byte << bit_offset;
-            if bit_offset > 0 {
-                self.data[(byte_pos + 1) % 20] ^= byte >> (8 - bit_offset);
+/// Returns true if another process holds the file at `path` (see the
+/// module documentation)
+///
+/// Errors (e.g. the file disappeared) count as not locked.
+fn is_held_by_other_process(path: &Path) -> bool {
+    if lock_file_candidates(path)
+        .iter()
+        .any(|candidate| candidate.exists())
+    {
+        return true;
+    }
+    File::open(path)
+        .and_then(|file| has_advisory_lock(&file))
+        .unwrap_or(false)
+}
+
+// ============================================================================
+// Hash cache
+// ============================================================================
+
+/// How long after a whole-second mtime a snapshot must be taken to trust it
+///
+/// FAT stores mtimes with two-second resolution; other coarse filesystems
+/// (ext3, some network mounts) use one second.
+const COARSE_MTIME_WINDOW: chrono::Duration = chrono::Duration::seconds(2);
+
+/// Returns true if a later write to a file would change `mtime`
+///
+/// `observed_at` is when the file's content was last read (hashed or
+/// synced). On filesystems with sub-second timestamps any write after that
+/// moment moves the mtime. Whole-second mtimes suggest a coarse filesystem
+/// where a write in the same tick leaves the mtime untouched, so they are
+/// only trusted once the observation happened [`COARSE_MTIME_WINDOW`] later.
+/// Mtimes at least that far in the future (skewed clocks, extracted
+/// archives) are trusted too: a write would replace them with the current
+/// time.
+pub(crate) fn mtime_is_reliable(mtime: DateTime<Utc>, observed_at: DateTime<Utc>) -> bool {
+    let age = observed_at - mtime;
+    if age <= -COARSE_MTIME_WINDOW {
+        true
+    } else if mtime.timestamp_subsec_nanos() == 0 {
+        age >= COARSE_MTIME_WINDOW
+    } else {
+        age > chrono::Duration::zero()
+    }
+}
+
+/// Converts a filesystem timestamp to `DateTime<Utc>`
+pub(crate) fn to_utc(time: SystemTime) -> Option<DateTime<Utc>> {
+    let dur = time.duration_since(UNIX_EPOCH).ok()?;
+    DateTime::from_timestamp(dur.as_secs() as i64, dur.subsec_nanos())
+}
+
+impl LocalFileSystemAdapter {
+    /// Create a new `LocalFileSystemAdapter`.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Writes the temporary files of atomic writes to `dir` instead of next
+    /// to their destination
+    ///
+    /// If `dir` is on another filesystem than a destination, the write
+    /// falls back to copying the file into place (see [`copy_into_place`]).
+    #[must_use]
+    pub fn with_temp_dir(mut self, dir: PathBuf) -> Self {
+        self.temp_dir = Some(dir);
+        self
+    }
+
+    /// Writes `data` to `path` atomically, with modification time `modified`
+    /// if one is given
+    async fn write_atomic(
+        &self,
+        path: &SyncPath,
+        data: &[u8],
+        modified: Option<SystemTime>,
+    ) -> anyhow::Result<()> {
+        let target = path.as_path();
+
+        // Ensure parent directory exists.
+        if let Some(parent) = target.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+
+        // Write to a temporary file, by default in the same directory so
+        // rename is atomic (same filesystem).
+        if let Some(dir) = &self.temp_dir {
+            tokio::fs::create_dir_all(dir).await?;
+        }
+        let tmp_path = self.temp_path(target);
+
+        debug!(?tmp_path, "writing to temporary file");
+        let entry = {
+            let tmp_path = tmp_path.clone();
+            let data = data.to_vec();
+            tokio::task::spawn_blocking(move || write_and_hash(&tmp_path, &data, modified))
+                .await??
+        };
+
+        // Atomic rename, or copy + sync + rename across filesystems.
+        debug!("moving temporary file to target");
+        let copied = {
+            let (tmp_path, target) = (tmp_path.clone(), target.to_path_buf());
+            tokio::task::spawn_blocking(move || move_into_place(&tmp_path, &target)).await?
+        };
+        let copied = match copied {
+            Ok(copied) => copied,
+            Err(e) => {
+                let _ = tokio::fs::remove_file(&tmp_path).await;
+                return Err(e.into());
             }
+        };
+        // A copy has another inode than the file the hash was cached for
+        match entry {
+            Some(entry) if !copied => self.remember_hash(target, entry),
+            _ => self.forget_hashes(target, false),
+        }
 
-            self.shift = (self.shift + Self::SHIFT_STEP) % Self::WIDTH_BITS;
+        debug!("write complete");
+        Ok(())
+    }
+
+    /// Returns where to write the temporary file for `target`
+    fn temp_path(&self, target: &Path) -> PathBuf {
+        match &self.temp_dir {
+            Some(dir) => dir.join(format!(
+                "lnxdrive-{}-{}.tmp",
+                std::process::id(),
+                NEXT_TEMP_FILE.fetch_add(1, Ordering::Relaxed)
+            )),
+            None => sibling_temp_path(target),
         }
-        self.length += input.len() as u64;
     }
 
-    fn finalize(mut self) -> [u8; 20] {
-        // XOR the total length (little-endian u64) into the first 8 bytes.
-        let length_bytes = self.length.to_le_bytes();
-        for (i, &lb) in length_bytes.iter().enumerate() {
-            self.data[i] ^= lb;
+    /// Returns the cached hash for `path` if the file is unchanged since it was hashed
+    fn cached_hash(
+        &self,
+        path: &Path,
+        metadata: &std::fs::Metadata,
+        modified: DateTime<Utc>,
+    ) -> Option<FileHash> {
+        let cache = self.hash_cache.lock().unwrap();
+        let entry = cache.get(path)?;
+        let unchanged = entry.inode == metadata.ino()
+            && entry.size == metadata.len()
+            && entry.modified == modified
+            && mtime_is_reliable(modified, entry.hashed_at);
+        unchanged.then(|| entry.hash.clone())
+    }
+
+    /// Caches a hash computed while reading or writing `path`
+    fn remember_hash(&self, path: &Path, entry: CachedHash) {
+        self.hash_cache
+            .lock()
+            .unwrap()
+            .insert(path.to_path_buf(), entry);
+    }
+
+    /// Drops the cached hash for `path`, and for everything below it if
+    /// `path` is a directory
+    fn forget_hashes(&self, path: &Path, is_dir: bool) {
+        let mut cache = self.hash_cache.lock().unwrap();
+        if is_dir {
+            cache.retain(|cached, _| !cached.starts_with(path));
+        } else {
+            cache.remove(path);
         }
-        self.data
     }
 }
 
@@ -106,11 +499,19 @@ impl QuickXorHash {
 
 #[async_trait::async_trait]
 impl ILocalFileSystem for LocalFileSystemAdapter {
-    // T145: read_file - async file read
+    // T145: read_file - async file read, hashing the content on the way
     #[instrument(skip(self), fields(path = %path))]
     async fn read_file(&self, path: &SyncPath) -> anyhow::Result<Vec<u8>> {
         debug!("reading file");
-        let data = tokio::fs::read(path.as_path()).await?;
+        let p = path.as_path().to_path_buf();
+        let (data, entry) = tokio::task::spawn_blocking(move || {
+            let mut data = Vec::new();
+            read_and_hash(&p, Some(&mut data)).map(|(_, entry)| (data, entry))
+        })
+        .await??;
+        if let Some(entry) = entry {
+            self.remember_hash(path.as_path(), entry);
+        }
         debug!(bytes = data.len(), "file read complete");
         Ok(data)
     }
@@ -118,30 +519,18 @@ impl ILocalFileSystem for LocalFileSystemAdapter {
     // T146: write_file - atomic write via temp + rename
     #[instrument(skip(self, data), fields(path = %path, bytes = data.len()))]
     async fn write_file(&self, path: &SyncPath, data: &[u8]) -> anyhow::Result<()> {
-        let target = path.as_path();
-
-        // Ensure parent directory exists.
-        if let Some(parent) = target.parent() {
-            tokio::fs::create_dir_all(parent).await?;
-        }
-
-        // Write to a temporary file in the same directory so rename is atomic
-        // (same filesystem).
-        let tmp_path = {
-            let mut p = target.as_os_str().to_owned();
-            p.push(".tmp");
-            std::path::PathBuf::from(p)
-        };
-
-        debug!(?tmp_path, "writing to temporary file");
-        tokio::fs::write(&tmp_path, data).await?;
-
-        // Atomic rename.
-        debug!("renaming temporary file to target");
-        tokio::fs::rename(&tmp_path, target).await?;
+        self.write_atomic(path, data, None).await
+    }
 
-        debug!("write complete");
-        Ok(())
+    // write_file_with_mtime - atomic write that also sets the mtime
+    #[instrument(skip(self, data), fields(path = %path, bytes = data.len()))]
+    async fn write_file_with_mtime(
+        &self,
+        path: &SyncPath,
+        data: &[u8],
+        modified: DateTime<Utc>,
+    ) -> anyhow::Result<()> {
+        self.write_atomic(path, data, Some(modified.into())).await
     }
 
     // T147: delete_file - remove file or directory recursively
@@ -157,11 +546,40 @@ impl ILocalFileSystem for LocalFileSystemAdapter {
             debug!("removing file");
             tokio::fs::remove_file(p).await?;
         }
+        self.forget_hashes(p, metadata.is_dir());
 
         debug!("delete complete");
         Ok(())
     }
 
+    // rename - move an entry without overwriting an existing destination
+    #[instrument(skip(self), fields(from = %from, to = %to))]
+    async fn rename(&self, from: &SyncPath, to: &SyncPath) -> anyhow::Result<()> {
+        if tokio::fs::symlink_metadata(to.as_path()).await.is_ok() {
+            anyhow::bail!("Rename destination already exists: {}", to);
+        }
+        tokio::fs::rename(from.as_path(), to.as_path()).await?;
+        let is_dir = tokio::fs::metadata(to.as_path())
+            .await
+            .is_ok_and(|m| m.is_dir());
+        self.forget_hashes(from.as_path(), is_dir);
+        self.forget_hashes(to.as_path(), is_dir);
+        debug!("rename complete");
+        Ok(())
+    }
+
+    // copy_file - copy in the filesystem, without reading into memory
+    #[instrument(skip(self), fields(from = %from, to = %to))]
+    async fn copy_file(&self, from: &SyncPath, to: &SyncPath) -> anyhow::Result<()> {
+        if let Some(parent) = to.as_path().parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+        let bytes = tokio::fs::copy(from.as_path(), to.as_path()).await?;
+        self.forget_hashes(to.as_path(), false);
+        debug!(bytes, "copy complete");
+        Ok(())
+    }
+
     // T148: get_state - stat file, detect locks
     #[instrument(skip(self), fields(path = %path))]
     async fn get_state(&self, path: &SyncPath) -> anyhow::Result<FileSystemState> {
@@ -179,33 +597,12 @@ impl ILocalFileSystem for LocalFileSystemAdapter {
         let is_file = metadata.is_file();
         let size = metadata.len();
 
-        // Convert system modified time to DateTime<Utc>.
-        let modified = metadata.modified().ok().and_then(|st| {
-            st.duration_since(std::time::UNIX_EPOCH)
-                .ok()
-                .and_then(|dur| DateTime::from_timestamp(dur.as_secs() as i64, dur.subsec_nanos()))
-        });
+        let modified = metadata.modified().ok().and_then(to_utc);
 
-        // Detect lock by attempting an exclusive write-open from a blocking
-        // thread.  If the open fails with WouldBlock or PermissionDenied we
-        // treat the file as locked.
+        // Lock files and advisory locks are checked from a blocking thread
         let is_locked = if is_file {
             let p_owned = p.to_path_buf();
-            tokio::task::spawn_blocking(move || {
-                use std::fs::OpenOptions;
-                match OpenOptions::new().write(true).open(&p_owned) {
-                    Ok(_) => false,
-                    Err(e)
-                        if e.kind() == ErrorKind::WouldBlock
-                            || e.kind() == ErrorKind::PermissionDenied =>
-                    {
-                        true
-                    }
-                    // Any other error (e.g. file disappeared) - not locked.
-                    Err(_) => false,
-                }
-            })
-            .await?
+            tokio::task::spawn_blocking(move || is_held_by_other_process(&p_owned)).await?
         } else {
             false
         };
@@ -221,20 +618,32 @@ impl ILocalFileSystem for LocalFileSystemAdapter {
         })
     }
 
-    // T149: compute_hash - quickXorHash matching OneDrive format
+    // T149: compute_hash - quickXorHash matching OneDrive format, cached by inode/size/mtime
     #[instrument(skip(self), fields(path = %path))]
     async fn compute_hash(&self, path: &SyncPath) -> anyhow::Result<FileHash> {
+        let metadata = tokio::fs::metadata(path.as_path()).await?;
+        let modified = metadata.modified().ok().and_then(to_utc);
+        if let Some(modified) = modified {
+            if let Some(hash) = self.cached_hash(path.as_path(), &metadata, modified) {
+                debug!(hash = %hash, "hash cache hit");
+                return Ok(hash);
+            }
+        }
+
         debug!("computing quickXorHash");
-        let data = tokio::fs::read(path.as_path()).await?;
+        let p = path.as_path().to_path_buf();
+        let (hash, entry) = tokio::task::spawn_blocking(move || read_and_hash(&p, None)).await??;
+        debug!(hash = %hash, "hash computed");
 
-        let mut hasher = QuickXorHash::new();
-        hasher.update(&data);
-        let hash_bytes = hasher.finalize();
+        if let Some(entry) = entry {
+            self.remember_hash(path.as_path(), entry);
+        }
 
-        let encoded = base64::engine::general_purpose::STANDARD.encode(hash_bytes);
-        debug!(hash = %encoded, "hash computed");
+        Ok(hash)
+    }
 
-        Ok(FileHash::new(encoded)?)
+    fn invalidate_hash_cache(&self) {
+        self.hash_cache.lock().unwrap().clear();
     }
 
     // create_directory
@@ -262,6 +671,7 @@ impl ILocalFileSystem for LocalFileSystemAdapter {
 mod tests {
     use std::path::PathBuf;
 
+    use base64::Engine;
     use tempfile::TempDir;
 
     use super::*;
@@ -301,6 +711,93 @@ mod tests {
         assert_eq!(read_back, b"nested content");
     }
 
+    #[tokio::test]
+    async fn test_write_with_temp_dir_on_other_filesystem() {
+        let dir = TempDir::new().unwrap();
+        // /dev/shm is usually a tmpfs, i.e. another filesystem than /tmp
+        let temp = TempDir::new_in("/dev/shm").unwrap_or_else(|_| TempDir::new().unwrap());
+        let fs = LocalFileSystemAdapter::new().with_temp_dir(temp.path().join("staging"));
+        let path = sync_path(&dir, "report.txt");
+
+        fs.write_file(&path, b"first").await.unwrap();
+        fs.write_file(&path, b"second").await.unwrap();
+
+        assert_eq!(fs.read_file(&path).await.unwrap(), b"second");
+        let leftovers = std::fs::read_dir(temp.path().join("staging"))
+            .unwrap()
+            .count();
+        assert_eq!(leftovers, 0);
+        assert!(!dir.path().join("report.txt.tmp").exists());
+
+        // The copy keeps the modification time of the temporary file
+        let mtime = DateTime::parse_from_rfc3339("2024-03-01T12:30:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        fs.write_file_with_mtime(&path, b"third", mtime)
+            .await
+            .unwrap();
+        assert_eq!(fs.get_state(&path).await.unwrap().modified, Some(mtime));
+    }
+
+    #[tokio::test]
+    async fn test_write_file_with_mtime() {
+        let dir = TempDir::new().unwrap();
+        let fs = LocalFileSystemAdapter::new();
+        let path = sync_path(&dir, "dated.txt");
+        let mtime = DateTime::parse_from_rfc3339("2023-11-14T22:13:20.5Z")
+            .unwrap()
+            .with_timezone(&Utc);
+
+        fs.write_file_with_mtime(&path, b"dated content", mtime)
+            .await
+            .unwrap();
+
+        let state = fs.get_state(&path).await.unwrap();
+        assert_eq!(state.modified, Some(mtime));
+        assert_eq!(fs.read_file(&path).await.unwrap(), b"dated content");
+        // The hash cached while writing matches a fresh computation
+        let cached = fs.compute_hash(&path).await.unwrap();
+        fs.invalidate_hash_cache();
+        assert_eq!(fs.compute_hash(&path).await.unwrap(), cached);
+    }
+
+    #[test]
+    fn test_copy_into_place_replaces_target() {
+        let dir = TempDir::new().unwrap();
+        let tmp = dir.path().join("download.part");
+        let target = dir.path().join("file.txt");
+        std::fs::write(&tmp, b"new content").unwrap();
+        std::fs::write(&target, b"old").unwrap();
+
+        copy_into_place(&tmp, &target).unwrap();
+
+        assert_eq!(std::fs::read(&target).unwrap(), b"new content");
+        assert!(!tmp.exists());
+        assert!(!sibling_temp_path(&target).exists());
+    }
+
+    #[test]
+    fn test_copy_into_place_failure_leaves_target_intact() {
+        let dir = TempDir::new().unwrap();
+        let target = dir.path().join("file.txt");
+        std::fs::write(&target, b"old").unwrap();
+
+        // The temporary file vanished, e.g. its filesystem was unmounted
+        let result = copy_into_place(&dir.path().join("missing.part"), &target);
+
+        assert!(result.is_err());
+        assert_eq!(std::fs::read(&target).unwrap(), b"old");
+        assert!(!sibling_temp_path(&target).exists());
+    }
+
+    #[test]
+    fn test_is_cross_device() {
+        assert!(is_cross_device(&std::io::Error::from_raw_os_error(
+            libc::EXDEV
+        )));
+        assert!(!is_cross_device(&std::io::Error::from(ErrorKind::NotFound)));
+    }
+
     #[tokio::test]
     async fn test_write_overwrites_existing() {
         let dir = TempDir::new().unwrap();
@@ -331,6 +828,55 @@ mod tests {
         assert!(!state.exists);
     }
 
+    #[tokio::test]
+    async fn test_rename_directory() {
+        let dir = TempDir::new().unwrap();
+        let fs = LocalFileSystemAdapter::new();
+        let from = sync_path(&dir, "old");
+        let to = sync_path(&dir, "new");
+
+        fs.create_directory(&from).await.unwrap();
+        fs.write_file(&sync_path(&dir, "old/file.txt"), b"data")
+            .await
+            .unwrap();
+        fs.rename(&from, &to).await.unwrap();
+
+        assert!(!fs.get_state(&from).await.unwrap().exists);
+        assert_eq!(
+            fs.read_file(&sync_path(&dir, "new/file.txt"))
+                .await
+                .unwrap(),
+            b"data"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_copy_file() {
+        let dir = TempDir::new().unwrap();
+        let fs = LocalFileSystemAdapter::new();
+        let from = sync_path(&dir, "a.txt");
+        let to = sync_path(&dir, "copies/a (copy).txt");
+
+        fs.write_file(&from, b"content").await.unwrap();
+        fs.copy_file(&from, &to).await.unwrap();
+
+        assert_eq!(fs.read_file(&from).await.unwrap(), b"content");
+        assert_eq!(fs.read_file(&to).await.unwrap(), b"content");
+    }
+
+    #[tokio::test]
+    async fn test_rename_refuses_existing_destination() {
+        let dir = TempDir::new().unwrap();
+        let fs = LocalFileSystemAdapter::new();
+        let from = sync_path(&dir, "a.txt");
+        let to = sync_path(&dir, "b.txt");
+
+        fs.write_file(&from, b"a").await.unwrap();
+        fs.write_file(&to, b"b").await.unwrap();
+        assert!(fs.rename(&from, &to).await.is_err());
+        assert_eq!(fs.read_file(&to).await.unwrap(), b"b");
+    }
+
     #[tokio::test]
     async fn test_delete_directory() {
         let dir = TempDir::new().unwrap();
@@ -366,6 +912,43 @@ mod tests {
         assert!(!state.is_locked);
     }
 
+    #[tokio::test]
+    async fn test_get_state_detects_lock_files() {
+        let dir = TempDir::new().unwrap();
+        let fs = LocalFileSystemAdapter::new();
+        for (name, lock) in [
+            ("notes.odt", ".~lock.notes.odt#"),
+            ("a.xlsx", "~$a.xlsx"),
+            ("report.docx", "~$port.docx"),
+        ] {
+            let path = sync_path(&dir, name);
+            std::fs::write(path.as_path(), b"content").unwrap();
+            assert!(!fs.get_state(&path).await.unwrap().is_locked, "{name}");
+
+            std::fs::write(dir.path().join(lock), b"").unwrap();
+            assert!(fs.get_state(&path).await.unwrap().is_locked, "{name}");
+        }
+        assert!(is_lock_file(".~lock.notes.odt#"));
+        assert!(is_lock_file("~$port.docx"));
+        assert!(!is_lock_file("notes.odt"));
+    }
+
+    #[tokio::test]
+    async fn test_get_state_detects_advisory_locks() {
+        let dir = TempDir::new().unwrap();
+        let fs = LocalFileSystemAdapter::new();
+        let path = sync_path(&dir, "db.sqlite");
+        std::fs::write(path.as_path(), b"content").unwrap();
+
+        let holder = File::open(path.as_path()).unwrap();
+        // SAFETY: flock only operates on the descriptor, which `holder` keeps open.
+        assert_eq!(unsafe { libc::flock(holder.as_raw_fd(), libc::LOCK_EX) }, 0);
+        assert!(fs.get_state(&path).await.unwrap().is_locked);
+
+        drop(holder);
+        assert!(!fs.get_state(&path).await.unwrap().is_locked);
+    }
+
     #[tokio::test]
     async fn test_get_state_existing_directory() {
         let dir = TempDir::new().unwrap();
@@ -456,6 +1039,148 @@ mod tests {
         assert_eq!(decoded.len(), 20);
     }
 
+    // ------------------------------------------------------------------
+    // Streaming hashes
+    // ------------------------------------------------------------------
+
+    /// Sizes around the 160-bit hash width and the I/O block size
+    const STREAM_SIZES: [usize; 12] = [
+        0,
+        1,
+        19,
+        20,
+        21,
+        159,
+        160,
+        161,
+        HASH_CHUNK_SIZE - 1,
+        HASH_CHUNK_SIZE,
+        HASH_CHUNK_SIZE + 1,
+        3 * HASH_CHUNK_SIZE + 7,
+    ];
+
+    fn patterned(size: usize) -> Vec<u8> {
+        (0..size).map(|i| (i * 31 % 251) as u8).collect()
+    }
+
+    fn whole_buffer_hash(data: &[u8]) -> FileHash {
+        let mut hasher = QuickXorHash::new();
+        hasher.update(data);
+        hasher.finalize_hash().unwrap()
+    }
+
+    #[test]
+    fn test_streaming_hasher_matches_whole_buffer_hash() {
+        for size in STREAM_SIZES {
+            let data = patterned(size);
+            let expected = whole_buffer_hash(&data);
+
+            for block in [1, 7, 20, 160, 4096, HASH_CHUNK_SIZE] {
+                let mut hasher = StreamingHasher::new();
+                for chunk in data.chunks(block) {
+                    hasher.update(chunk);
+                }
+                assert_eq!(hasher.bytes_hashed(), size as u64);
+                assert_eq!(
+                    hasher.finish().unwrap(),
+                    expected,
+                    "size {size}, block {block}"
+                );
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_transfers_hash_while_streaming() {
+        let dir = TempDir::new().unwrap();
+        let fs = LocalFileSystemAdapter::new();
+
+        for size in STREAM_SIZES {
+            let path = sync_path(&dir, &format!("stream-{size}.bin"));
+            let data = patterned(size);
+            let expected = whole_buffer_hash(&data);
+
+            fs.write_file(&path, &data).await.unwrap();
+            let written = fs.hash_cache.lock().unwrap()[path.as_path()].hash.clone();
+            assert_eq!(written, expected, "write, size {size}");
+
+            fs.invalidate_hash_cache();
+            assert_eq!(fs.read_file(&path).await.unwrap(), data);
+            let read = fs.hash_cache.lock().unwrap()[path.as_path()].hash.clone();
+            assert_eq!(read, expected, "read, size {size}");
+        }
+    }
+
+    /// Replaces the cached hash for `path` with a marker and makes it trusted
+    fn plant_cached_hash(fs: &LocalFileSystemAdapter, path: &SyncPath) -> FileHash {
+        let marker = FileHash::new("AAAAAAAAAAAAAAAAAAAAAAAAAAA=".to_string()).unwrap();
+        let mut cache = fs.hash_cache.lock().unwrap();
+        let entry = cache.get_mut(path.as_path()).unwrap();
+        entry.hash = marker.clone();
+        entry.hashed_at = entry.modified + chrono::Duration::seconds(10);
+        marker
+    }
+
+    #[tokio::test]
+    async fn test_compute_hash_served_from_cache_while_unchanged() {
+        let dir = TempDir::new().unwrap();
+        let fs = LocalFileSystemAdapter::new();
+        let path = sync_path(&dir, "cached.txt");
+
+        fs.write_file(&path, b"cache me").await.unwrap();
+        let real = fs.compute_hash(&path).await.unwrap();
+        let marker = plant_cached_hash(&fs, &path);
+        assert_eq!(fs.compute_hash(&path).await.unwrap(), marker);
+
+        // An in-place write changes size and mtime, so the file is read again
+        std::fs::write(path.as_path(), b"cache me again").unwrap();
+        let updated = fs.compute_hash(&path).await.unwrap();
+        assert_ne!(updated, marker);
+        assert_ne!(updated, real);
+    }
+
+    #[tokio::test]
+    async fn test_invalidate_hash_cache_forces_rehash() {
+        let dir = TempDir::new().unwrap();
+        let fs = LocalFileSystemAdapter::new();
+        let path = sync_path(&dir, "stale.txt");
+
+        fs.write_file(&path, b"content").await.unwrap();
+        let real = fs.compute_hash(&path).await.unwrap();
+        plant_cached_hash(&fs, &path);
+
+        fs.invalidate_hash_cache();
+        assert_eq!(fs.compute_hash(&path).await.unwrap(), real);
+    }
+
+    #[test]
+    fn test_mtime_is_reliable() {
+        let fine = DateTime::from_timestamp(1_700_000_000, 123_456_789).unwrap();
+        assert!(!mtime_is_reliable(fine, fine));
+        assert!(mtime_is_reliable(
+            fine,
+            fine + chrono::Duration::milliseconds(1)
+        ));
+
+        // Whole-second mtimes need a two-second margin
+        let coarse = DateTime::from_timestamp(1_700_000_000, 0).unwrap();
+        assert!(!mtime_is_reliable(
+            coarse,
+            coarse + chrono::Duration::milliseconds(1500)
+        ));
+        assert!(mtime_is_reliable(
+            coarse,
+            coarse + chrono::Duration::seconds(2)
+        ));
+
+        // Future-dated mtimes are replaced by any later write
+        assert!(!mtime_is_reliable(
+            fine,
+            fine - chrono::Duration::seconds(1)
+        ));
+        assert!(mtime_is_reliable(fine, fine - chrono::Duration::hours(1)));
+    }
+
     // ------------------------------------------------------------------
     // create_directory
     // ------------------------------------------------------------------
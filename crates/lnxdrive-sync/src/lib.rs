@@ -1,7 +1,7 @@
 //! LNXDrive Sync - Delta synchronization engine
 //!
 //! Provides:
-//! - Incremental delta sync with Microsoft Graph
+//! - Incremental delta sync against any cloud provider
 //! - Adaptive rate limiting
 //! - Conflict detection
 //! - Bidirectional synchronization
@@ -9,17 +9,152 @@
 //! ## Modules
 //!
 //! - [`engine`] - Bidirectional sync engine orchestrating pull/push cycles
+//! - [`exclusion`] - Built-in exclusions of hidden and junk files
 //! - [`filesystem`] - Local filesystem adapter (atomic writes, quickXorHash)
+//! - [`local_provider`] - Cloud provider backed by a local directory tree
+//! - [`normalization`] - Unicode normalization of file names (NFC/NFD)
+//! - [`plan`] - Typed change sets planned before a sync cycle applies them
+//! - [`selective`] - Remote folders selected for sync and changes to them
 
 pub mod engine;
+pub mod exclusion;
 pub mod filesystem;
+pub mod local_provider;
+pub mod normalization;
+pub mod plan;
 pub mod scheduler;
+pub mod selective;
 pub mod watcher;
 
-use std::path::PathBuf;
+use std::{fmt, path::PathBuf};
 
+use lnxdrive_core::domain::{
+    newtypes::{RemoteId, UniqueId},
+    ReasonCode, SyncItem,
+};
+use serde::Serialize;
 use thiserror::Error;
 
+/// Step of a sync cycle at which an item failed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SyncStep {
+    /// Applying a change from the cloud (download, rename or delete)
+    ApplyRemoteChange,
+    /// Uploading a file created locally
+    UploadNew,
+    /// Uploading a file modified locally
+    UploadModified,
+    /// Recording a new local hardlink of a synced file
+    RecordHardlink,
+    /// Deleting the cloud copy of an item deleted locally
+    DeleteRemote,
+}
+
+impl fmt::Display for SyncStep {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let step = match self {
+            SyncStep::ApplyRemoteChange => "applying remote change to",
+            SyncStep::UploadNew => "uploading new file",
+            SyncStep::UploadModified => "uploading modified file",
+            SyncStep::RecordHardlink => "recording hardlink",
+            SyncStep::DeleteRemote => "deleting remote item",
+        };
+        f.write_str(step)
+    }
+}
+
+/// The item and step a [`SyncError::Item`] failed at
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ItemContext {
+    /// Step that failed
+    pub step: SyncStep,
+    /// Id of the tracked item, if the item is tracked
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub item_id: Option<UniqueId>,
+    /// Id of the item in the cloud, if known
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub remote_id: Option<String>,
+    /// Path of the item in the cloud, if known
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub remote_path: Option<String>,
+    /// Path of the item below the sync root, if known
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub local_path: Option<PathBuf>,
+}
+
+impl ItemContext {
+    /// A context naming only the step
+    pub fn new(step: SyncStep) -> Self {
+        Self {
+            step,
+            item_id: None,
+            remote_id: None,
+            remote_path: None,
+            local_path: None,
+        }
+    }
+
+    /// A context naming the step and everything known of a tracked item
+    pub fn for_item(step: SyncStep, item: &SyncItem) -> Self {
+        Self::new(step)
+            .with_item_id(*item.id())
+            .with_remote_id(item.remote_id().map(RemoteId::as_str))
+            .with_remote_path(item.remote_path().as_str())
+            .with_local_path(item.local_path().as_path().clone())
+    }
+
+    /// Sets the id of the tracked item
+    pub fn with_item_id(mut self, item_id: UniqueId) -> Self {
+        self.item_id = Some(item_id);
+        self
+    }
+
+    /// Sets the cloud id, if known
+    pub fn with_remote_id(mut self, remote_id: Option<&str>) -> Self {
+        self.remote_id = remote_id.map(str::to_string);
+        self
+    }
+
+    /// Sets the cloud path
+    pub fn with_remote_path(mut self, remote_path: impl Into<String>) -> Self {
+        self.remote_path = Some(remote_path.into());
+        self
+    }
+
+    /// Sets the local path
+    pub fn with_local_path(mut self, local_path: impl Into<PathBuf>) -> Self {
+        self.local_path = Some(local_path.into());
+        self
+    }
+}
+
+impl fmt::Display for ItemContext {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.step)?;
+        match (&self.local_path, &self.remote_path) {
+            (Some(path), _) => write!(f, " '{}'", path.display())?,
+            (None, Some(path)) => write!(f, " '{path}'")?,
+            (None, None) => {}
+        }
+
+        let mut ids = Vec::new();
+        if let Some(item_id) = &self.item_id {
+            ids.push(format!("item {item_id}"));
+        }
+        if let Some(remote_id) = &self.remote_id {
+            ids.push(format!("remote id {remote_id}"));
+        }
+        if let (Some(_), Some(remote_path)) = (&self.local_path, &self.remote_path) {
+            ids.push(format!("remote path {remote_path}"));
+        }
+        if !ids.is_empty() {
+            write!(f, " ({})", ids.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
 /// Errors that can occur during synchronization operations
 #[derive(Debug, Error)]
 pub enum SyncError {
@@ -43,7 +178,189 @@ pub enum SyncError {
     #[error("Path not found: {0}")]
     PathNotFound(PathBuf),
 
+    /// The file does not fit in the remaining storage quota
+    #[error("Not enough storage for {path}: needs {needed} bytes, {remaining} remaining")]
+    QuotaExceeded {
+        path: PathBuf,
+        needed: u64,
+        remaining: u64,
+    },
+
+    /// The item exceeds a file size or path length limit of the provider
+    #[error("Cannot upload {path}: {violation}")]
+    LimitExceeded {
+        path: PathBuf,
+        violation: lnxdrive_core::domain::LimitViolation,
+    },
+
+    /// Another entry of the folder has the same name in another Unicode
+    /// normalization form, so both would be uploaded under one name
+    #[error("Cannot upload {path}: {other} has the same name in another Unicode form")]
+    NameCollision { path: PathBuf, other: PathBuf },
+
+    /// The file kept changing while it was uploaded
+    #[error("File changed during upload: {0}")]
+    ChangedDuringUpload(PathBuf),
+
     /// A domain-level error propagated from lnxdrive-core
     #[error("Domain error: {0}")]
     DomainError(#[from] lnxdrive_core::domain::errors::DomainError),
+
+    /// Processing one item failed; `context` names the item and the step,
+    /// `source` keeps the typed cause (e.g. a `DomainError` or `GraphError`)
+    /// inspectable through the error chain
+    #[error("Error {context}")]
+    Item {
+        context: ItemContext,
+        #[source]
+        source: anyhow::Error,
+    },
+}
+
+impl SyncError {
+    /// Wraps the failure of one item with the item and step it failed at
+    pub fn item(context: ItemContext, source: anyhow::Error) -> anyhow::Error {
+        SyncError::Item { context, source }.into()
+    }
+
+    /// Returns the context of the outermost [`SyncError::Item`] in the
+    /// chain of `err`
+    pub fn context_of(err: &anyhow::Error) -> Option<&ItemContext> {
+        err.chain()
+            .find_map(|cause| match cause.downcast_ref::<SyncError>() {
+                Some(SyncError::Item { context, .. }) => Some(context),
+                _ => None,
+            })
+    }
+
+    /// Returns the [`ReasonCode`] describing this error
+    ///
+    /// [`SyncError::Item`] is described by its source.
+    pub fn reason(&self) -> ReasonCode {
+        match self {
+            SyncError::IoError(e) => ReasonCode::from_io_error(e),
+            SyncError::FileLocked(_) => ReasonCode::Locked,
+            SyncError::PermissionDenied(_) => ReasonCode::PermissionDenied,
+            SyncError::QuotaExceeded { .. } => ReasonCode::QuotaExceeded,
+            SyncError::LimitExceeded { violation, .. } => violation.reason(),
+            SyncError::NameCollision { .. } => ReasonCode::InvalidName,
+            SyncError::DomainError(e) => e.reason(),
+            SyncError::Item { source, .. } => engine::error_reason(source),
+            SyncError::DiskFull
+            | SyncError::PathNotFound(_)
+            | SyncError::ChangedDuringUpload(_) => ReasonCode::Unknown,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use lnxdrive_core::domain::{errors::DomainError, LimitViolation};
+
+    use super::*;
+
+    #[test]
+    fn test_error_reason() {
+        let path = || PathBuf::from("/home/user/OneDrive/a.txt");
+        let cases = [
+            (SyncError::FileLocked(path()), ReasonCode::Locked),
+            (
+                SyncError::PermissionDenied(path()),
+                ReasonCode::PermissionDenied,
+            ),
+            (
+                SyncError::QuotaExceeded {
+                    path: path(),
+                    needed: 10,
+                    remaining: 5,
+                },
+                ReasonCode::QuotaExceeded,
+            ),
+            (
+                SyncError::LimitExceeded {
+                    path: path(),
+                    violation: LimitViolation::PathTooLong {
+                        length: 500,
+                        max: 400,
+                    },
+                },
+                ReasonCode::PathTooLong,
+            ),
+            (
+                SyncError::IoError(std::io::ErrorKind::PermissionDenied.into()),
+                ReasonCode::PermissionDenied,
+            ),
+            (
+                SyncError::DomainError(DomainError::InvalidPath("a\0b".to_string())),
+                ReasonCode::InvalidName,
+            ),
+            (
+                SyncError::NameCollision {
+                    path: path(),
+                    other: PathBuf::from("/home/user/OneDrive/a\u{301}.txt"),
+                },
+                ReasonCode::InvalidName,
+            ),
+            (SyncError::PathNotFound(path()), ReasonCode::Unknown),
+        ];
+        for (error, reason) in cases {
+            assert_eq!(error.reason(), reason, "{error}");
+        }
+    }
+
+    #[test]
+    fn test_item_context_is_propagated() {
+        use anyhow::Context;
+        use lnxdrive_graph::GraphError;
+
+        let item_id = UniqueId::new();
+        let cause = Err::<(), _>(GraphError::Unauthorized("token revoked".to_string()))
+            .context("Failed to upload file")
+            .unwrap_err();
+        let context = ItemContext::new(SyncStep::UploadModified)
+            .with_item_id(item_id)
+            .with_remote_id(Some("01ABC"))
+            .with_remote_path("/Documents/a.txt")
+            .with_local_path("/home/user/OneDrive/Documents/a.txt");
+        let err = SyncError::item(context.clone(), cause).context("Sync cycle failed");
+
+        assert_eq!(SyncError::context_of(&err), Some(&context));
+        let message = format!("{err:#}");
+        assert!(message.contains("uploading modified file '/home/user/OneDrive/Documents/a.txt'"));
+        assert!(message.contains(&format!("item {item_id}")));
+        assert!(message.contains("remote id 01ABC"));
+        assert!(message.contains("remote path /Documents/a.txt"));
+        assert!(message.ends_with("Failed to upload file: Unauthorized: token revoked"));
+
+        // The typed cause stays reachable and classifies the error
+        assert!(matches!(
+            err.chain()
+                .find_map(|cause| cause.downcast_ref::<GraphError>()),
+            Some(GraphError::Unauthorized(_))
+        ));
+        assert_eq!(crate::engine::error_reason(&err), ReasonCode::AuthError);
+
+        let json = serde_json::to_value(&context).unwrap();
+        assert_eq!(json["step"], "upload_modified");
+        assert_eq!(json["remote_id"], "01ABC");
+    }
+
+    #[test]
+    fn test_item_context_keeps_domain_errors() {
+        let err = SyncError::item(
+            ItemContext::new(SyncStep::ApplyRemoteChange).with_remote_path("/Documents/a.txt"),
+            DomainError::InvalidPath("a\0b".to_string()).into(),
+        );
+        assert!(err
+            .chain()
+            .any(|cause| cause.downcast_ref::<DomainError>().is_some()));
+        assert_eq!(
+            err.downcast_ref::<SyncError>().map(SyncError::reason),
+            Some(ReasonCode::InvalidName)
+        );
+        assert_eq!(
+            err.to_string(),
+            "Error applying remote change to '/Documents/a.txt'"
+        );
+    }
 }
@@ -0,0 +1,695 @@
+//! Cloud provider backed by a local directory tree
+//!
+//! [`LocalFolderProvider`] implements [`ICloudProvider`] on top of a plain
+//! directory that plays the role of the cloud drive. It exists to:
+//! - run the sync engine end to end without Microsoft Graph (tests, demos)
+//! - keep the port honest: the engine must work with any backend
+//! - serve as a template for future backends (WebDAV, Nextcloud mounts)
+//!
+//! ## Design Notes
+//!
+//! - **Remote IDs** are derived from the path (`local-<hex of path>`), so an
+//!   item renamed on the "remote" side shows up as a delete plus a create.
+//! - **Delta** is computed by diffing snapshots of the tree. Each delta link
+//!   names a snapshot kept in memory; only the most recent
+//!   [`MAX_SNAPSHOTS`] are retained, and an unknown token fails with
+//!   [`DeltaTokenExpired`] like an expired Graph token.
+//! - **Hashes** use the same quickXorHash as OneDrive and the local
+//!   filesystem adapter, so the engine can compare content without
+//!   downloading it.
+//! - **Uploads** create missing parent folders (like Graph path-based
+//!   uploads) and are written to a temp file that is renamed into place.
+//!   Folders are created with `create_folder`, which accepts existing ones.
+//! - **Appends** extend the stored file in place, unlike OneDrive, so the
+//!   engine's append optimization can be exercised.
+//! - **Modification times** are the files' mtimes; `set_modified_time`
+//!   sets them like Graph's `fileSystemInfo`.
+
+use std::{
+    collections::{BTreeMap, VecDeque},
+    fs,
+    io::ErrorKind,
+    path::{Path, PathBuf},
+    sync::Mutex,
+};
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Duration, Utc};
+use lnxdrive_core::{
+    domain::{
+        newtypes::{DeltaToken, RemoteId, RemotePath},
+        ItemFacets, QuickXorHash,
+    },
+    ports::cloud_provider::{
+        AuthFlow, DeltaItem, DeltaResponse, DeltaTokenExpired, ICloudProvider, ItemPage,
+        RemoteItemNotFound, Tokens, UserInfo,
+    },
+};
+use tracing::{debug, instrument};
+
+/// Number of delta snapshots kept for outstanding tokens
+pub const MAX_SNAPSHOTS: usize = 8;
+
+/// Prefix of every remote ID handed out by this provider
+const ID_PREFIX: &str = "local-";
+
+/// Remote ID of the root folder
+const ROOT_ID: &str = "local-root";
+
+/// Prefix of delta tokens
+const TOKEN_PREFIX: &str = "local-delta-";
+
+/// Prefix of in-progress upload files, hidden from listings
+const UPLOAD_TMP_PREFIX: &str = ".lnxdrive-upload-";
+
+/// State of one entry at snapshot time
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Entry {
+    is_directory: bool,
+    size: u64,
+    modified: DateTime<Utc>,
+    hash: Option<String>,
+}
+
+/// Tree listing keyed by remote path (`/dir/file.txt`)
+type Snapshot = BTreeMap<String, Entry>;
+
+/// Snapshots referenced by outstanding delta tokens
+#[derive(Debug, Default)]
+struct SnapshotLog {
+    next_generation: u64,
+    snapshots: VecDeque<(u64, Snapshot)>,
+}
+
+impl SnapshotLog {
+    fn get(&self, generation: u64) -> Option<&Snapshot> {
+        self.snapshots
+            .iter()
+            .find(|(gen, _)| *gen == generation)
+            .map(|(_, snapshot)| snapshot)
+    }
+
+    fn latest(&self) -> Option<&Snapshot> {
+        self.snapshots.back().map(|(_, snapshot)| snapshot)
+    }
+
+    fn push(&mut self, snapshot: Snapshot) -> u64 {
+        let generation = self.next_generation;
+        self.next_generation += 1;
+        self.snapshots.push_back((generation, snapshot));
+        while self.snapshots.len() > MAX_SNAPSHOTS {
+            self.snapshots.pop_front();
+        }
+        generation
+    }
+}
+
+/// [`ICloudProvider`] that stores the "cloud" in a local directory
+#[derive(Debug)]
+pub struct LocalFolderProvider {
+    root: PathBuf,
+    log: Mutex<SnapshotLog>,
+    /// Storage limit reported as the quota total (0 = unlimited)
+    quota_limit: u64,
+}
+
+impl LocalFolderProvider {
+    /// Creates a provider serving the tree under `root` (created if missing)
+    pub fn new(root: impl Into<PathBuf>) -> Result<Self> {
+        let root = root.into();
+        fs::create_dir_all(&root)
+            .with_context(|| format!("Failed to create provider root {}", root.display()))?;
+        Ok(Self {
+            root,
+            log: Mutex::new(SnapshotLog::default()),
+            quota_limit: 0,
+        })
+    }
+
+    /// Reports a storage quota of `limit` bytes instead of an unlimited one
+    ///
+    /// The limit is only reported, not enforced on upload; it lets the
+    /// engine's quota checks be exercised without a real drive.
+    pub fn with_quota_limit(mut self, limit: u64) -> Self {
+        self.quota_limit = limit;
+        self
+    }
+
+    /// Returns the directory acting as the remote drive
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Returns the remote ID this provider uses for `remote_path`
+    pub fn remote_id_for(remote_path: &str) -> String {
+        let relative = remote_path.trim_matches('/');
+        if relative.is_empty() {
+            return ROOT_ID.to_string();
+        }
+        let hex: String = relative.bytes().map(|b| format!("{b:02x}")).collect();
+        format!("{ID_PREFIX}{hex}")
+    }
+
+    /// Maps a remote ID back to its remote path
+    fn remote_path_for(remote_id: &RemoteId) -> Result<String> {
+        let id = remote_id.as_str();
+        if id == ROOT_ID {
+            return Ok("/".to_string());
+        }
+        let hex = id
+            .strip_prefix(ID_PREFIX)
+            .filter(|hex| hex.len() % 2 == 0)
+            .ok_or_else(|| anyhow::anyhow!("Not a local provider ID: {id}"))?;
+        let bytes = (0..hex.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&hex[i..i + 2], 16))
+            .collect::<Result<Vec<u8>, _>>()
+            .with_context(|| format!("Malformed local provider ID: {id}"))?;
+        let relative = String::from_utf8(bytes)
+            .with_context(|| format!("Malformed local provider ID: {id}"))?;
+        // Reuse RemotePath validation to reject traversal
+        let path = RemotePath::new(format!("/{relative}"))?;
+        Ok(path.as_str().to_string())
+    }
+
+    fn local_path(&self, remote_path: &str) -> PathBuf {
+        self.root.join(remote_path.trim_start_matches('/'))
+    }
+
+    fn delta_item(remote_path: &str, entry: &Entry) -> DeltaItem {
+        let (parent, name) = match remote_path.rsplit_once('/') {
+            Some((parent, name)) => (if parent.is_empty() { "/" } else { parent }, name),
+            None => ("/", remote_path),
+        };
+        DeltaItem {
+            id: Self::remote_id_for(remote_path),
+            name: name.to_string(),
+            path: Some(remote_path.to_string()),
+            size: (!entry.is_directory).then_some(entry.size),
+            hash: entry.hash.clone(),
+            modified: Some(entry.modified),
+            is_deleted: false,
+            is_directory: entry.is_directory,
+            parent_id: Some(Self::remote_id_for(parent)),
+            facets: ItemFacets::default(),
+        }
+    }
+
+    fn deleted_item(remote_path: &str, entry: &Entry) -> DeltaItem {
+        let mut item = Self::delta_item(remote_path, entry);
+        item.path = None;
+        item.size = None;
+        item.hash = None;
+        item.modified = None;
+        item.is_deleted = true;
+        item
+    }
+
+    /// Lists the whole tree, reusing hashes of files unchanged since `previous`
+    async fn scan(&self, previous: Option<Snapshot>) -> Result<Snapshot> {
+        let root = self.root.clone();
+        tokio::task::spawn_blocking(move || {
+            let mut snapshot = Snapshot::new();
+            scan_dir(&root, "", previous.as_ref(), &mut snapshot)?;
+            Ok(snapshot)
+        })
+        .await
+        .context("Snapshot task panicked")?
+    }
+
+    async fn entry_for(&self, remote_path: &str) -> Result<Entry> {
+        let path = self.local_path(remote_path);
+        tokio::task::spawn_blocking(move || read_entry(&path, None))
+            .await
+            .context("Metadata task panicked")?
+    }
+
+    /// Joins `parent_path` and `name` into a remote path
+    fn child_path(parent_path: &RemotePath, name: &str) -> Result<String> {
+        let remote_path = match parent_path.as_str() {
+            "/" => format!("/{name}"),
+            parent => format!("{parent}/{name}"),
+        };
+        Ok(RemotePath::new(remote_path)?.as_str().to_string())
+    }
+
+    async fn write(&self, parent_path: &RemotePath, name: &str, data: &[u8]) -> Result<DeltaItem> {
+        let remote_path = Self::child_path(parent_path, name)?;
+        let target = self.local_path(&remote_path);
+        let parent_dir = target
+            .parent()
+            .ok_or_else(|| anyhow::anyhow!("Upload target has no parent: {remote_path}"))?;
+
+        tokio::fs::create_dir_all(parent_dir).await?;
+        let tmp = parent_dir.join(format!("{UPLOAD_TMP_PREFIX}{name}"));
+        tokio::fs::write(&tmp, data).await?;
+        tokio::fs::rename(&tmp, &target).await?;
+
+        let entry = self.entry_for(&remote_path).await?;
+        debug!(remote_path, bytes = data.len(), "Stored upload");
+        Ok(Self::delta_item(&remote_path, &entry))
+    }
+}
+
+fn scan_dir(
+    dir: &Path,
+    prefix: &str,
+    previous: Option<&Snapshot>,
+    out: &mut Snapshot,
+) -> Result<()> {
+    for dir_entry in
+        fs::read_dir(dir).with_context(|| format!("Failed to list {}", dir.display()))?
+    {
+        let dir_entry = dir_entry?;
+        let Some(name) = dir_entry.file_name().to_str().map(str::to_string) else {
+            continue;
+        };
+        if name.starts_with(UPLOAD_TMP_PREFIX) {
+            continue;
+        }
+        let remote_path = format!("{prefix}/{name}");
+        let entry = read_entry(
+            &dir_entry.path(),
+            previous.and_then(|snapshot| snapshot.get(&remote_path)),
+        )?;
+        let is_directory = entry.is_directory;
+        out.insert(remote_path.clone(), entry);
+        if is_directory {
+            scan_dir(&dir_entry.path(), &remote_path, previous, out)?;
+        }
+    }
+    Ok(())
+}
+
+/// Reads the state of `path`, reusing `previous.hash` if size and mtime match
+fn read_entry(path: &Path, previous: Option<&Entry>) -> Result<Entry> {
+    let metadata =
+        fs::metadata(path).with_context(|| format!("Failed to stat {}", path.display()))?;
+    let modified: DateTime<Utc> = metadata.modified()?.into();
+    let is_directory = metadata.is_dir();
+    let size = if is_directory { 0 } else { metadata.len() };
+
+    let hash = if is_directory {
+        None
+    } else {
+        match previous {
+            Some(prev) if !prev.is_directory && prev.size == size && prev.modified == modified => {
+                prev.hash.clone()
+            }
+            _ => {
+                let mut hasher = QuickXorHash::new();
+                hasher.update(&fs::read(path)?);
+                Some(hasher.finalize_hash()?.as_str().to_string())
+            }
+        }
+    };
+
+    Ok(Entry {
+        is_directory,
+        size,
+        modified,
+        hash,
+    })
+}
+
+fn parse_token(token: &DeltaToken) -> Option<u64> {
+    token.as_str().strip_prefix(TOKEN_PREFIX)?.parse().ok()
+}
+
+#[async_trait::async_trait]
+impl ICloudProvider for LocalFolderProvider {
+    async fn authenticate(&self, _auth_flow: &AuthFlow) -> Result<Tokens> {
+        // Nothing to authenticate against; hand out a long-lived token
+        Ok(Tokens {
+            access_token: "local".to_string(),
+            refresh_token: None,
+            expires_at: Utc::now() + Duration::days(365),
+        })
+    }
+
+    async fn refresh_tokens(&self, _refresh_token: &str) -> Result<Tokens> {
+        self.authenticate(&AuthFlow::AuthorizationCodePKCE {
+            app_id: String::new(),
+            redirect_uri: String::new(),
+            scopes: Vec::new(),
+        })
+        .await
+    }
+
+    #[instrument(skip(self))]
+    async fn get_delta(&self, token: Option<&DeltaToken>) -> Result<DeltaResponse> {
+        let (base, previous) = {
+            let log = self.log.lock().expect("snapshot log poisoned");
+            let base = match token {
+                Some(token) => {
+                    let base = parse_token(token).and_then(|gen| log.get(gen)).cloned();
+                    Some(base.ok_or(DeltaTokenExpired)?)
+                }
+                None => None,
+            };
+            (base, log.latest().cloned())
+        };
+
+        let current = self.scan(previous).await?;
+        let empty = Snapshot::new();
+        let base_ref = base.as_ref().unwrap_or(&empty);
+
+        // Deletions deepest first, then creates/updates parents first
+        let mut items: Vec<DeltaItem> = base_ref
+            .iter()
+            .rev()
+            .filter(|(path, _)| !current.contains_key(*path))
+            .map(|(path, entry)| Self::deleted_item(path, entry))
+            .collect();
+        items.extend(
+            current
+                .iter()
+                .filter(|(path, entry)| base_ref.get(*path) != Some(entry))
+                .map(|(path, entry)| Self::delta_item(path, entry)),
+        );
+
+        let generation = self
+            .log
+            .lock()
+            .expect("snapshot log poisoned")
+            .push(current);
+        debug!(items = items.len(), generation, "Computed local delta");
+
+        Ok(DeltaResponse {
+            items,
+            next_link: None,
+            delta_link: Some(format!("{TOKEN_PREFIX}{generation}")),
+        })
+    }
+
+    async fn download_file(&self, remote_id: &RemoteId) -> Result<Vec<u8>> {
+        let remote_path = Self::remote_path_for(remote_id)?;
+        tokio::fs::read(self.local_path(&remote_path))
+            .await
+            .with_context(|| format!("Failed to read {remote_path}"))
+    }
+
+    async fn upload_file(
+        &self,
+        parent_path: &RemotePath,
+        name: &str,
+        data: &[u8],
+    ) -> Result<DeltaItem> {
+        self.write(parent_path, name, data).await
+    }
+
+    async fn upload_file_session(
+        &self,
+        parent_path: &RemotePath,
+        name: &str,
+        data: &[u8],
+        progress: Option<Box<dyn Fn(u64, u64) + Send>>,
+    ) -> Result<DeltaItem> {
+        let item = self.write(parent_path, name, data).await?;
+        if let Some(progress) = progress {
+            let total = data.len() as u64;
+            progress(total, total);
+        }
+        Ok(item)
+    }
+
+    fn supports_append(&self) -> bool {
+        true
+    }
+
+    async fn append_file(
+        &self,
+        remote_id: &RemoteId,
+        offset: u64,
+        data: &[u8],
+    ) -> Result<DeltaItem> {
+        use tokio::io::AsyncWriteExt;
+
+        let remote_path = Self::remote_path_for(remote_id)?;
+        let path = self.local_path(&remote_path);
+        let size = match tokio::fs::metadata(&path).await {
+            Ok(metadata) => metadata.len(),
+            Err(e) if e.kind() == ErrorKind::NotFound => {
+                return Err(anyhow::Error::new(RemoteItemNotFound)
+                    .context(format!("{remote_path} does not exist")))
+            }
+            Err(e) => return Err(e).with_context(|| format!("Failed to stat {remote_path}")),
+        };
+        if size != offset {
+            anyhow::bail!("Cannot append to {remote_path} at {offset}: it is {size} bytes long");
+        }
+
+        let mut file = tokio::fs::OpenOptions::new()
+            .append(true)
+            .open(&path)
+            .await
+            .with_context(|| format!("Failed to open {remote_path}"))?;
+        file.write_all(data).await?;
+        file.sync_all().await?;
+
+        let entry = self.entry_for(&remote_path).await?;
+        debug!(remote_path, offset, bytes = data.len(), "Appended to file");
+        Ok(Self::delta_item(&remote_path, &entry))
+    }
+
+    async fn create_folder(&self, parent_path: &RemotePath, name: &str) -> Result<DeltaItem> {
+        let remote_path = Self::child_path(parent_path, name)?;
+        tokio::fs::create_dir_all(self.local_path(&remote_path))
+            .await
+            .with_context(|| format!("Failed to create folder {remote_path}"))?;
+        let entry = self.entry_for(&remote_path).await?;
+        debug!(remote_path, "Created folder");
+        Ok(Self::delta_item(&remote_path, &entry))
+    }
+
+    async fn get_metadata(&self, remote_id: &RemoteId) -> Result<DeltaItem> {
+        let remote_path = Self::remote_path_for(remote_id)?;
+        let entry = self.entry_for(&remote_path).await?;
+        Ok(Self::delta_item(&remote_path, &entry))
+    }
+
+    async fn set_modified_time(&self, remote_id: &RemoteId, modified: DateTime<Utc>) -> Result<()> {
+        let remote_path = Self::remote_path_for(remote_id)?;
+        let path = self.local_path(&remote_path);
+        tokio::task::spawn_blocking(move || {
+            fs::OpenOptions::new()
+                .write(true)
+                .open(&path)?
+                .set_modified(modified.into())
+        })
+        .await
+        .context("Set modification time task panicked")?
+        .with_context(|| format!("Failed to set the modification time of {remote_path}"))
+    }
+
+    async fn get_user_info(&self) -> Result<UserInfo> {
+        let snapshot = self.scan(None).await?;
+        Ok(UserInfo {
+            email: "local@localhost.localdomain".to_string(),
+            display_name: format!("Local folder {}", self.root.display()),
+            id: ROOT_ID.to_string(),
+            quota_used: snapshot.values().map(|entry| entry.size).sum(),
+            // A local folder has no quota of its own unless one is set
+            quota_total: self.quota_limit,
+        })
+    }
+
+    /// Lists a folder in a single page
+    async fn list_children(
+        &self,
+        path: &RemotePath,
+        _continuation: Option<&str>,
+    ) -> Result<ItemPage> {
+        let prefix = path.as_str().trim_end_matches('/').to_string();
+        let dir = self.local_path(&prefix);
+        let entries = tokio::task::spawn_blocking(move || -> std::io::Result<_> {
+            let mut entries = Vec::new();
+            for dir_entry in fs::read_dir(&dir)? {
+                let dir_entry = dir_entry?;
+                let Some(name) = dir_entry.file_name().to_str().map(str::to_string) else {
+                    continue;
+                };
+                if !name.starts_with(UPLOAD_TMP_PREFIX) {
+                    entries.push((name, dir_entry.path()));
+                }
+            }
+            Ok(entries)
+        })
+        .await
+        .context("Listing task panicked")?;
+        let entries = match entries {
+            Err(e) if e.kind() == ErrorKind::NotFound => {
+                return Err(anyhow::Error::new(RemoteItemNotFound)
+                    .context(format!("Remote folder {path} not found")));
+            }
+            other => other.with_context(|| format!("Failed to list {path}"))?,
+        };
+
+        let mut items = Vec::with_capacity(entries.len());
+        for (name, local) in entries {
+            let entry = tokio::task::spawn_blocking(move || read_entry(&local, None))
+                .await
+                .context("Metadata task panicked")??;
+            items.push(Self::delta_item(&format!("{prefix}/{name}"), &entry));
+        }
+        Ok(ItemPage {
+            items,
+            continuation: None,
+        })
+    }
+
+    async fn delete_item(&self, remote_id: &RemoteId) -> Result<()> {
+        let remote_path = Self::remote_path_for(remote_id)?;
+        if remote_path == "/" {
+            anyhow::bail!("Refusing to delete the provider root");
+        }
+        let path = self.local_path(&remote_path);
+        let result = match tokio::fs::metadata(&path).await {
+            Ok(metadata) if metadata.is_dir() => tokio::fs::remove_dir_all(&path).await,
+            Ok(_) => tokio::fs::remove_file(&path).await,
+            Err(e) => Err(e),
+        };
+        match result {
+            // Already gone, reported like a 404 from Graph
+            Err(e) if e.kind() == ErrorKind::NotFound => {
+                Err(anyhow::Error::new(RemoteItemNotFound)
+                    .context(format!("{remote_path} does not exist")))
+            }
+            other => other.with_context(|| format!("Failed to delete {remote_path}")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn remote_id(path: &str) -> RemoteId {
+        RemoteId::new(LocalFolderProvider::remote_id_for(path)).unwrap()
+    }
+
+    #[test]
+    fn test_remote_id_roundtrip() {
+        for path in ["/", "/a.txt", "/Docs/Informe año.pdf"] {
+            let id = remote_id(path);
+            assert_eq!(LocalFolderProvider::remote_path_for(&id).unwrap(), path);
+        }
+        let bogus = RemoteId::new("ABC123".to_string()).unwrap();
+        assert!(LocalFolderProvider::remote_path_for(&bogus).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_delta_reports_creates_updates_and_deletes() {
+        let dir = tempfile::tempdir().unwrap();
+        let provider = LocalFolderProvider::new(dir.path()).unwrap();
+        fs::create_dir(dir.path().join("docs")).unwrap();
+        fs::write(dir.path().join("docs/a.txt"), b"alpha").unwrap();
+        fs::write(dir.path().join("b.txt"), b"beta").unwrap();
+
+        let initial = provider.get_delta(None).await.unwrap();
+        let paths: Vec<_> = initial
+            .items
+            .iter()
+            .map(|i| i.path.clone().unwrap())
+            .collect();
+        assert_eq!(paths, ["/b.txt", "/docs", "/docs/a.txt"]);
+        assert!(initial.items[1].is_directory);
+        assert_eq!(initial.items[0].size, Some(4));
+        assert!(initial.items[0].hash.is_some());
+
+        let token = DeltaToken::new(initial.delta_link.unwrap()).unwrap();
+        let unchanged = provider.get_delta(Some(&token)).await.unwrap();
+        assert!(unchanged.items.is_empty());
+
+        fs::write(dir.path().join("docs/a.txt"), b"alpha, edited").unwrap();
+        fs::remove_file(dir.path().join("b.txt")).unwrap();
+        let token = DeltaToken::new(unchanged.delta_link.unwrap()).unwrap();
+        let changes = provider.get_delta(Some(&token)).await.unwrap();
+
+        assert_eq!(changes.items.len(), 2);
+        assert!(changes.items[0].is_deleted);
+        assert_eq!(
+            changes.items[0].id,
+            LocalFolderProvider::remote_id_for("/b.txt")
+        );
+        assert_eq!(changes.items[1].path.as_deref(), Some("/docs/a.txt"));
+        assert_eq!(changes.items[1].size, Some(13));
+    }
+
+    #[tokio::test]
+    async fn test_unknown_token_is_expired() {
+        let dir = tempfile::tempdir().unwrap();
+        let provider = LocalFolderProvider::new(dir.path()).unwrap();
+        let token = DeltaToken::new("local-delta-42".to_string()).unwrap();
+
+        let err = provider.get_delta(Some(&token)).await.unwrap_err();
+
+        assert!(lnxdrive_core::ports::cloud_provider::is_delta_token_expired(&err));
+    }
+
+    #[tokio::test]
+    async fn test_upload_download_delete() {
+        let dir = tempfile::tempdir().unwrap();
+        let provider = LocalFolderProvider::new(dir.path()).unwrap();
+        let parent = RemotePath::new("/new/folder".to_string()).unwrap();
+
+        let item = provider
+            .upload_file(&parent, "c.txt", b"gamma")
+            .await
+            .unwrap();
+
+        assert_eq!(item.path.as_deref(), Some("/new/folder/c.txt"));
+        assert_eq!(
+            item.parent_id,
+            Some(LocalFolderProvider::remote_id_for("/new/folder"))
+        );
+        let id = RemoteId::new(item.id.clone()).unwrap();
+        assert_eq!(provider.download_file(&id).await.unwrap(), b"gamma");
+        assert_eq!(provider.get_metadata(&id).await.unwrap().hash, item.hash);
+
+        provider.delete_item(&id).await.unwrap();
+        assert!(!dir.path().join("new/folder/c.txt").exists());
+        // Deleting twice reports the item as missing, like Graph's 404
+        let err = provider.delete_item(&id).await.unwrap_err();
+        assert!(lnxdrive_core::ports::cloud_provider::is_remote_item_not_found(&err));
+        assert!(provider.delete_item(&remote_id("/")).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_create_folder_accepts_existing_folder() {
+        let dir = tempfile::tempdir().unwrap();
+        let provider = LocalFolderProvider::new(dir.path()).unwrap();
+        let parent = RemotePath::new("/new".to_string()).unwrap();
+
+        let created = provider.create_folder(&parent, "empty").await.unwrap();
+        assert!(created.is_directory);
+        assert_eq!(created.path.as_deref(), Some("/new/empty"));
+        assert!(dir.path().join("new/empty").is_dir());
+
+        let again = provider.create_folder(&parent, "empty").await.unwrap();
+        assert_eq!(again.id, created.id);
+
+        provider.upload_file(&parent, "file", b"").await.unwrap();
+        assert!(provider.create_folder(&parent, "file").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_append_extends_file_at_its_end() {
+        let dir = tempfile::tempdir().unwrap();
+        let provider = LocalFolderProvider::new(dir.path()).unwrap();
+        let root = RemotePath::new("/".to_string()).unwrap();
+        let item = provider
+            .upload_file(&root, "log.txt", b"one\n")
+            .await
+            .unwrap();
+        let id = RemoteId::new(item.id).unwrap();
+
+        let item = provider.append_file(&id, 4, b"two\n").await.unwrap();
+        assert_eq!(item.size, Some(8));
+        assert_eq!(provider.download_file(&id).await.unwrap(), b"one\ntwo\n");
+
+        // The stored file no longer has the length the caller expects
+        assert!(provider.append_file(&id, 4, b"three\n").await.is_err());
+        assert_eq!(provider.download_file(&id).await.unwrap(), b"one\ntwo\n");
+    }
+}
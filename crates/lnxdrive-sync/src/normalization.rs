@@ -0,0 +1,169 @@
+//! Unicode normalization of file names
+//!
+//! The same name can be written in two Unicode forms: composed (NFC, "é"
+//! as one code point), which OneDrive and Windows use, and decomposed
+//! (NFD, "e" followed by a combining accent), which macOS and some Linux
+//! applications write. Both look identical but are different names to a
+//! Linux filesystem, so a folder synced in one form and created again in
+//! the other shows up twice.
+//!
+//! With `sync.normalize_unicode`, names are uploaded in NFC and remote
+//! names are matched to local entries regardless of their form. Two local
+//! entries of one folder whose names only differ in normalization would
+//! be uploaded onto each other; they are reported instead
+//! ([`NameNormalization::find_collision`]).
+
+use std::{
+    borrow::Cow,
+    path::{Component, Path, PathBuf},
+};
+
+use lnxdrive_core::config::SyncConfig;
+use unicode_normalization::{is_nfc, UnicodeNormalization};
+
+/// Whether names are normalized
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct NameNormalization {
+    /// Upload names in NFC and match local names in any form
+    pub enabled: bool,
+}
+
+impl NameNormalization {
+    /// Reads the setting from the sync settings
+    pub fn from_config(config: &SyncConfig) -> Self {
+        Self {
+            enabled: config.normalize_unicode,
+        }
+    }
+
+    /// Returns `name` (or a path) in NFC, unchanged if disabled
+    pub fn normalize<'a>(&self, name: &'a str) -> Cow<'a, str> {
+        if !self.enabled || is_nfc(name) {
+            Cow::Borrowed(name)
+        } else {
+            Cow::Owned(name.nfc().collect())
+        }
+    }
+
+    /// Maps the remote path `remote_path` to a path below `root`
+    ///
+    /// Each folder and file name that does not exist locally as written is
+    /// replaced by an existing local entry whose name only differs in
+    /// normalization, so a remote "café" (NFC) finds a local "café" (NFD).
+    pub fn local_path(&self, root: &Path, remote_path: &str) -> PathBuf {
+        let relative = Path::new(remote_path.trim_start_matches('/'));
+        if !self.enabled {
+            return root.join(relative);
+        }
+
+        let mut path = root.to_path_buf();
+        let mut resolving = true;
+        for component in relative.components() {
+            let Component::Normal(name) = component else {
+                path.push(component);
+                continue;
+            };
+            let exact = path.join(name);
+            if resolving && std::fs::symlink_metadata(&exact).is_err() {
+                match name.to_str().and_then(|n| self.find_entry(&path, n, None)) {
+                    Some(existing) => {
+                        path = existing;
+                        continue;
+                    }
+                    // Nothing below a missing folder exists either
+                    None => resolving = false,
+                }
+            }
+            path = exact;
+        }
+        path
+    }
+
+    /// Returns another entry next to `path` whose name only differs from
+    /// its name in normalization
+    ///
+    /// Both would be uploaded under the same name, so neither can be synced
+    /// until one is renamed.
+    pub fn find_collision(&self, path: &Path) -> Option<PathBuf> {
+        if !self.enabled {
+            return None;
+        }
+        let name = path.file_name()?.to_str()?;
+        self.find_entry(path.parent()?, name, Some(name))
+    }
+
+    /// Looks in `dir` for an entry named like `name`, other than `except`
+    fn find_entry(&self, dir: &Path, name: &str, except: Option<&str>) -> Option<PathBuf> {
+        let wanted = self.normalize(name);
+        std::fs::read_dir(dir)
+            .ok()?
+            .filter_map(Result::ok)
+            .find(|entry| {
+                entry.file_name().to_str().is_some_and(|candidate| {
+                    Some(candidate) != except && self.normalize(candidate) == wanted
+                })
+            })
+            .map(|entry| entry.path())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::TempDir;
+
+    use super::*;
+
+    const NFC: &str = "caf\u{e9}";
+    const NFD: &str = "cafe\u{301}";
+
+    const ENABLED: NameNormalization = NameNormalization { enabled: true };
+    const DISABLED: NameNormalization = NameNormalization { enabled: false };
+
+    #[test]
+    fn test_normalize_composes_names() {
+        assert_ne!(NFC, NFD);
+        assert_eq!(ENABLED.normalize(NFD), NFC);
+        assert_eq!(ENABLED.normalize(NFC), NFC);
+        assert_eq!(
+            ENABLED.normalize(&format!("/{NFD}/menu.txt")),
+            format!("/{NFC}/menu.txt")
+        );
+        assert_eq!(DISABLED.normalize(NFD), NFD);
+    }
+
+    #[test]
+    fn test_local_path_finds_entry_in_other_form() {
+        let root = TempDir::new().unwrap();
+        std::fs::create_dir(root.path().join(NFD)).unwrap();
+
+        let remote = format!("/{NFC}/menu.txt");
+        assert_eq!(
+            ENABLED.local_path(root.path(), &remote),
+            root.path().join(NFD).join("menu.txt")
+        );
+        assert_eq!(
+            DISABLED.local_path(root.path(), &remote),
+            root.path().join(NFC).join("menu.txt")
+        );
+        // An existing exact match wins
+        std::fs::create_dir(root.path().join(NFC)).unwrap();
+        assert_eq!(
+            ENABLED.local_path(root.path(), &remote),
+            root.path().join(NFC).join("menu.txt")
+        );
+    }
+
+    #[test]
+    fn test_find_collision_reports_sibling_in_other_form() {
+        let root = TempDir::new().unwrap();
+        let nfc = root.path().join(NFC);
+        let nfd = root.path().join(NFD);
+        std::fs::write(&nfc, b"1").unwrap();
+        assert_eq!(ENABLED.find_collision(&nfc), None);
+
+        std::fs::write(&nfd, b"2").unwrap();
+        assert_eq!(ENABLED.find_collision(&nfc), Some(nfd.clone()));
+        assert_eq!(ENABLED.find_collision(&nfd), Some(nfc));
+        assert_eq!(DISABLED.find_collision(&nfd), None);
+    }
+}
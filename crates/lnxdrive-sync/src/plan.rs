@@ -0,0 +1,414 @@
+//! Sync plans - the changes a sync cycle is about to apply
+//!
+//! [`SyncEngine::plan()`](crate::engine::SyncEngine::plan) queries the
+//! delta and scans the sync root without changing anything, and returns a
+//! [`SyncPlan`]: one typed [`SyncOperation`] per remote delta item and per
+//! local change. [`SyncEngine::execute()`](crate::engine::SyncEngine::execute)
+//! then applies it; it is the only code path that changes files, the state
+//! database or the cloud.
+//!
+//! A plan holds at most `delta.max_in_flight_items` remote changes. The
+//! pages of a larger delta are read, planned and applied in rounds while
+//! the plan executes, so memory stays bounded however large the drive.
+//!
+//! The operations are a prediction made from the state at planning time.
+//! The conflict policy may still resolve a [`SyncOperation::Conflict`]
+//! automatically, and a locked file is only deferred once its upload is
+//! attempted.
+
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Utc};
+use lnxdrive_core::{
+    domain::{newtypes::SyncPath, Account},
+    ports::cloud_provider::{DeltaItem, DeltaPages},
+};
+use serde::{Deserialize, Serialize};
+
+use crate::engine::LocalChange;
+
+/// Side of the sync on which an operation is applied
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SyncSide {
+    /// The local sync root
+    Local,
+    /// The cloud
+    Remote,
+}
+
+/// Why an entry needs no transfer
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SkipReason {
+    /// Content unchanged; only the recorded metadata is refreshed
+    Unchanged,
+    /// Left out by `sync.exclude_hidden` or `sync.exclude_junk`
+    Excluded,
+    /// Outside the folders selected for sync
+    NotSelected,
+    /// Deleted in the cloud, but never synced here
+    NotTracked,
+    /// Held locked by another process; the upload is deferred
+    Locked,
+    /// Hardlink to a file that is uploaded under its other name
+    Hardlink,
+}
+
+/// One change of a [`SyncPlan`]
+///
+/// Paths are local paths inside the sync root.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub enum SyncOperation {
+    /// Fetch a new or changed remote file
+    Download { path: PathBuf, size: u64 },
+    /// Send a new or changed local file
+    Upload { path: PathBuf, size: u64 },
+    /// Delete an entry that was deleted on the other side
+    Delete { path: PathBuf, side: SyncSide },
+    /// Create a folder that was created on the other side
+    CreateDir { path: PathBuf, side: SyncSide },
+    /// Move a local entry that was renamed or moved in the cloud
+    Move { from: PathBuf, to: PathBuf },
+    /// Entry changed on both sides, resolved by the conflict policy
+    Conflict { path: PathBuf },
+    /// Nothing to transfer
+    Skip { path: PathBuf, reason: SkipReason },
+}
+
+impl SyncOperation {
+    /// Returns the local path the operation applies to (the target of a move)
+    pub fn path(&self) -> &Path {
+        match self {
+            SyncOperation::Download { path, .. }
+            | SyncOperation::Upload { path, .. }
+            | SyncOperation::Delete { path, .. }
+            | SyncOperation::CreateDir { path, .. }
+            | SyncOperation::Conflict { path }
+            | SyncOperation::Skip { path, .. } => path,
+            SyncOperation::Move { to, .. } => to,
+        }
+    }
+
+    /// Returns true for [`SyncOperation::Skip`]
+    pub fn is_skip(&self) -> bool {
+        matches!(self, SyncOperation::Skip { .. })
+    }
+}
+
+/// Number of operations of each kind in a plan
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PlanSummary {
+    pub downloads: u32,
+    pub uploads: u32,
+    /// Bytes to download
+    pub download_bytes: u64,
+    /// Bytes to upload
+    pub upload_bytes: u64,
+    /// Deletes on either side
+    pub deletes: u32,
+    /// Folders to create on either side
+    pub create_dirs: u32,
+    pub moves: u32,
+    pub conflicts: u32,
+    pub skipped: u32,
+}
+
+impl PlanSummary {
+    /// Total number of operations that change something
+    pub fn changes(&self) -> u32 {
+        self.downloads
+            + self.uploads
+            + self.deletes
+            + self.create_dirs
+            + self.moves
+            + self.conflicts
+    }
+}
+
+/// What a plan is about to do, as shown to the user before it runs
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PlanPreview {
+    pub summary: PlanSummary,
+    /// Operations in order, without items skipped as unchanged
+    pub operations: Vec<SyncOperation>,
+    /// Non-fatal problems met while planning
+    pub errors: Vec<String>,
+    /// False if the delta held more remote changes than a plan keeps in
+    /// memory; only the first ones are listed
+    pub complete: bool,
+}
+
+/// A planned remote delta item
+#[derive(Debug, Clone)]
+pub(crate) struct RemoteStep {
+    pub(crate) item: DeltaItem,
+    pub(crate) operation: SyncOperation,
+}
+
+/// A planned local change
+#[derive(Debug, Clone)]
+pub(crate) struct LocalStep {
+    pub(crate) change: LocalChange,
+    pub(crate) operation: SyncOperation,
+}
+
+/// How far the delta of a plan was read
+#[derive(Debug, Default)]
+pub(crate) struct DeltaCursor {
+    /// Number of delta items read, in scope or not
+    pub(crate) items_checked: usize,
+    /// nextLink of the last page read. Once everything read so far is
+    /// applied, a restart can continue the walk from here.
+    pub(crate) next_link: Option<String>,
+    /// Delta link of the last page, from which the next token is taken
+    pub(crate) delta_link: Option<String>,
+    /// Pages not read yet because the plan reached
+    /// `delta.max_in_flight_items` changes
+    pub(crate) pages: Option<DeltaPages>,
+}
+
+/// The changes one sync cycle applies, in order: remote ones first
+#[derive(Debug)]
+pub struct SyncPlan {
+    /// Account the plan was made for
+    pub(crate) account: Account,
+    /// Restricts the plan to one path, see
+    /// [`SyncEngine::plan_path()`](crate::engine::SyncEngine::plan_path).
+    /// Such a plan neither records a session nor advances the delta token.
+    pub(crate) scope: Option<SyncPath>,
+    /// Whether the stored delta token had expired, so the delta lists
+    /// every remote item
+    pub(crate) full_resync: bool,
+    /// Whether the local scan ignored the mtime shortcut for a pending
+    /// reconciliation, which executing the plan completes
+    pub(crate) reconciliation: bool,
+    /// Whether the delta continues a page walk an earlier cycle left
+    /// unfinished, rather than starting from the delta token
+    pub(crate) resumed: bool,
+    /// How far the delta was read
+    pub(crate) delta: DeltaCursor,
+    /// Files modified before this were not hashed by the local scan
+    pub(crate) last_sync: Option<DateTime<Utc>>,
+    pub(crate) remote: Vec<RemoteStep>,
+    pub(crate) local: Vec<LocalStep>,
+    /// Non-fatal problems met while planning
+    pub(crate) errors: Vec<String>,
+}
+
+impl SyncPlan {
+    /// Iterates over all operations, remote ones first
+    pub fn operations(&self) -> impl Iterator<Item = &SyncOperation> {
+        self.remote
+            .iter()
+            .map(|step| &step.operation)
+            .chain(self.local.iter().map(|step| &step.operation))
+    }
+
+    /// Counts the operations by kind
+    pub fn summary(&self) -> PlanSummary {
+        let mut summary = PlanSummary::default();
+        for operation in self.operations() {
+            match operation {
+                SyncOperation::Download { size, .. } => {
+                    summary.downloads += 1;
+                    summary.download_bytes += size;
+                }
+                SyncOperation::Upload { size, .. } => {
+                    summary.uploads += 1;
+                    summary.upload_bytes += size;
+                }
+                SyncOperation::Delete { .. } => summary.deletes += 1,
+                SyncOperation::CreateDir { .. } => summary.create_dirs += 1,
+                SyncOperation::Move { .. } => summary.moves += 1,
+                SyncOperation::Conflict { .. } => summary.conflicts += 1,
+                SyncOperation::Skip { .. } => summary.skipped += 1,
+            }
+        }
+        summary
+    }
+
+    /// Returns true if executing the plan would change nothing
+    pub fn is_empty(&self) -> bool {
+        self.is_complete() && self.operations().all(SyncOperation::is_skip)
+    }
+
+    /// Non-fatal problems met while planning, e.g. a failed local scan
+    pub fn errors(&self) -> &[String] {
+        &self.errors
+    }
+
+    /// Returns false if the delta held more remote changes than the plan
+    /// keeps in memory; the rest are planned while it executes
+    pub fn is_complete(&self) -> bool {
+        self.delta.pages.is_none()
+    }
+
+    /// Returns the preview of the plan
+    pub fn preview(&self) -> PlanPreview {
+        PlanPreview {
+            summary: self.summary(),
+            operations: self
+                .operations()
+                .filter(|op| {
+                    !matches!(
+                        op,
+                        SyncOperation::Skip {
+                            reason: SkipReason::Unchanged,
+                            ..
+                        }
+                    )
+                })
+                .cloned()
+                .collect(),
+            errors: self.errors.clone(),
+            complete: self.is_complete(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use lnxdrive_core::domain::newtypes::Email;
+
+    use super::*;
+
+    #[test]
+    fn test_operation_serializes_with_tag() {
+        let op = SyncOperation::Skip {
+            path: PathBuf::from("/od/.git"),
+            reason: SkipReason::Excluded,
+        };
+        assert_eq!(
+            serde_json::to_value(&op).unwrap(),
+            serde_json::json!({"op": "skip", "path": "/od/.git", "reason": "excluded"})
+        );
+
+        let op = SyncOperation::CreateDir {
+            path: PathBuf::from("/od/new"),
+            side: SyncSide::Remote,
+        };
+        assert_eq!(
+            serde_json::to_value(&op).unwrap(),
+            serde_json::json!({"op": "create_dir", "path": "/od/new", "side": "remote"})
+        );
+    }
+
+    fn plan_with(remote: Vec<SyncOperation>, local: Vec<SyncOperation>) -> SyncPlan {
+        let email = Email::new("user@example.com".to_string()).unwrap();
+        let root = SyncPath::new(PathBuf::from("/od")).unwrap();
+        let item = |name: &str| DeltaItem {
+            id: name.to_string(),
+            name: name.to_string(),
+            path: Some(format!("/{name}")),
+            size: Some(1),
+            hash: None,
+            modified: None,
+            is_deleted: false,
+            is_directory: false,
+            parent_id: None,
+            facets: Default::default(),
+        };
+        SyncPlan {
+            account: Account::new(email, "User", "root", root),
+            scope: None,
+            full_resync: false,
+            reconciliation: false,
+            resumed: false,
+            delta: DeltaCursor {
+                items_checked: remote.len(),
+                ..DeltaCursor::default()
+            },
+            last_sync: None,
+            remote: remote
+                .into_iter()
+                .map(|operation| RemoteStep {
+                    item: item(&operation.path().to_string_lossy()),
+                    operation,
+                })
+                .collect(),
+            local: local
+                .into_iter()
+                .map(|operation| LocalStep {
+                    change: LocalChange::Created(SyncPath::new(operation.path().into()).unwrap()),
+                    operation,
+                })
+                .collect(),
+            errors: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_preview_json_shape() {
+        let plan = plan_with(
+            vec![
+                SyncOperation::Download {
+                    path: PathBuf::from("/od/a.txt"),
+                    size: 10,
+                },
+                SyncOperation::Skip {
+                    path: PathBuf::from("/od/same.txt"),
+                    reason: SkipReason::Unchanged,
+                },
+                SyncOperation::Conflict {
+                    path: PathBuf::from("/od/both.txt"),
+                },
+            ],
+            vec![SyncOperation::Upload {
+                path: PathBuf::from("/od/b.txt"),
+                size: 20,
+            }],
+        );
+        assert!(!plan.is_empty());
+
+        let json = serde_json::to_value(plan.preview()).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "summary": {
+                    "downloads": 1,
+                    "uploads": 1,
+                    "download_bytes": 10,
+                    "upload_bytes": 20,
+                    "deletes": 0,
+                    "create_dirs": 0,
+                    "moves": 0,
+                    "conflicts": 1,
+                    "skipped": 1,
+                },
+                "operations": [
+                    {"op": "download", "path": "/od/a.txt", "size": 10},
+                    {"op": "conflict", "path": "/od/both.txt"},
+                    {"op": "upload", "path": "/od/b.txt", "size": 20},
+                ],
+                "errors": [],
+                "complete": true,
+            })
+        );
+        assert_eq!(plan.summary().changes(), 3);
+    }
+
+    #[test]
+    fn test_plan_of_skips_only_is_empty() {
+        let plan = plan_with(
+            vec![SyncOperation::Skip {
+                path: PathBuf::from("/od/.git"),
+                reason: SkipReason::Excluded,
+            }],
+            Vec::new(),
+        );
+        assert!(plan.is_empty());
+        assert_eq!(plan.preview().operations.len(), 1);
+    }
+
+    #[test]
+    fn test_operation_path_is_move_target() {
+        let op = SyncOperation::Move {
+            from: PathBuf::from("/od/a"),
+            to: PathBuf::from("/od/b"),
+        };
+        assert_eq!(op.path(), Path::new("/od/b"));
+        assert!(!op.is_skip());
+    }
+}
@@ -17,6 +17,7 @@
 //! the debounce window entirely, useful for "sync now" commands.
 
 use std::{
+    path::PathBuf,
     sync::{
         atomic::{AtomicBool, Ordering},
         Arc,
@@ -27,7 +28,10 @@ use std::{
 use tokio::sync::mpsc;
 use tracing::{debug, info};
 
-use crate::watcher::{ChangeEvent, DebouncedChangeQueue};
+use crate::{
+    exclusion::SyncExclusions,
+    watcher::{ChangeEvent, DebouncedChangeQueue},
+};
 
 // ============================================================================
 // T183: SyncScheduler struct
@@ -51,6 +55,8 @@ pub struct SyncScheduler {
     queue: DebouncedChangeQueue,
     /// Shared flag indicating that a sync cycle should start
     sync_requested: Arc<AtomicBool>,
+    /// Shared flag indicating that the next sync must rescan the whole tree
+    reconcile_requested: Arc<AtomicBool>,
     /// How often the scheduler polls the debounced queue for settled events
     poll_interval: Duration,
 }
@@ -88,12 +94,27 @@ impl SyncScheduler {
             change_rx,
             queue: DebouncedChangeQueue::new(debounce_delay),
             sync_requested,
+            reconcile_requested: Arc::new(AtomicBool::new(false)),
             poll_interval,
         };
 
         (scheduler, flag)
     }
 
+    /// Ignores the events of paths below `root` that `exclusions` leave
+    /// out of sync
+    pub fn with_exclusions(mut self, root: PathBuf, exclusions: SyncExclusions) -> Self {
+        self.queue = self.queue.with_exclusions(root, exclusions);
+        self
+    }
+
+    /// Ignores the events of paths inside `dirs`, such as the FUSE mount
+    /// point and cache directory, so syncing cannot trigger itself
+    pub fn with_ignored_dirs(mut self, dirs: impl IntoIterator<Item = PathBuf>) -> Self {
+        self.queue = self.queue.with_ignored_dirs(dirs);
+        self
+    }
+
     // ========================================================================
     // T184: SyncScheduler::enqueue()
     // ========================================================================
@@ -159,6 +180,7 @@ impl SyncScheduler {
                                     count = settled.len(),
                                     "Flushing remaining settled events before shutdown"
                                 );
+                                self.flag_reconciliation(&settled);
                                 self.sync_requested.store(true, Ordering::Release);
                             }
 
@@ -178,6 +200,7 @@ impl SyncScheduler {
                         for event in &settled {
                             debug!(path = %event.path().display(), event = ?event, "Settled");
                         }
+                        self.flag_reconciliation(&settled);
                         self.sync_requested.store(true, Ordering::Release);
                     }
                 }
@@ -201,6 +224,31 @@ impl SyncScheduler {
     pub fn clear_sync_request(&self) {
         self.sync_requested.store(false, Ordering::Release);
     }
+
+    /// Returns the shared flag set when a reconciliation scan is needed
+    ///
+    /// The flag is raised when the watcher reports lost events
+    /// ([`ChangeEvent::Rescan`]). The consumer should run a full scan via
+    /// [`SyncEngine::request_reconciliation`](crate::engine::SyncEngine::request_reconciliation)
+    /// and reset the flag.
+    pub fn reconcile_flag(&self) -> Arc<AtomicBool> {
+        Arc::clone(&self.reconcile_requested)
+    }
+
+    /// Returns whether a reconciliation scan has been scheduled
+    pub fn is_reconcile_requested(&self) -> bool {
+        self.reconcile_requested.load(Ordering::Acquire)
+    }
+
+    /// Raises the reconciliation flag if any settled event is a rescan
+    fn flag_reconciliation(&self, settled: &[ChangeEvent]) {
+        for event in settled {
+            if let ChangeEvent::Rescan(path) = event {
+                info!(path = %path.display(), "Reconciliation scan scheduled");
+                self.reconcile_requested.store(true, Ordering::Release);
+            }
+        }
+    }
 }
 
 // ============================================================================
@@ -298,6 +346,60 @@ mod tests {
             .expect("Scheduler should exit when channel closes");
     }
 
+    #[tokio::test]
+    async fn test_run_overflow_schedules_reconciliation() {
+        let (tx, rx) = mpsc::channel(16);
+        let (mut scheduler, flag) =
+            SyncScheduler::new(rx, Duration::from_millis(0), Duration::from_millis(10));
+        let reconcile = scheduler.reconcile_flag();
+
+        tx.send(ChangeEvent::Rescan(PathBuf::from("/home/user/OneDrive")))
+            .await
+            .unwrap();
+        drop(tx);
+
+        scheduler.run().await;
+
+        assert!(flag.load(Ordering::Acquire));
+        assert!(reconcile.load(Ordering::Acquire));
+        assert!(scheduler.is_reconcile_requested());
+    }
+
+    #[tokio::test]
+    async fn test_run_regular_events_do_not_reconcile() {
+        let (tx, rx) = mpsc::channel(16);
+        let (mut scheduler, _flag) =
+            SyncScheduler::new(rx, Duration::from_millis(0), Duration::from_millis(10));
+
+        tx.send(ChangeEvent::Modified(PathBuf::from("/a.txt")))
+            .await
+            .unwrap();
+        drop(tx);
+
+        scheduler.run().await;
+
+        assert!(!scheduler.is_reconcile_requested());
+    }
+
+    #[tokio::test]
+    async fn test_run_events_inside_mount_do_not_request_sync() {
+        let (tx, rx) = mpsc::channel(16);
+        let (scheduler, flag) =
+            SyncScheduler::new(rx, Duration::from_millis(0), Duration::from_millis(10));
+        let mut scheduler = scheduler.with_ignored_dirs([PathBuf::from("/home/user/OneDrive/mnt")]);
+
+        tx.send(ChangeEvent::Modified(PathBuf::from(
+            "/home/user/OneDrive/mnt/a.txt",
+        )))
+        .await
+        .unwrap();
+        drop(tx);
+
+        scheduler.run().await;
+
+        assert!(!flag.load(Ordering::Acquire));
+    }
+
     #[tokio::test]
     async fn test_run_multiple_events_coalesced() {
         let (tx, rx) = mpsc::channel(16);
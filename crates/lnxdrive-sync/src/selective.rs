@@ -0,0 +1,391 @@
+//! Selective sync
+//!
+//! A [`FolderSelection`] names the remote folders the user chose to sync;
+//! an empty selection syncs the whole drive. Items outside the selection
+//! are neither downloaded nor tracked, except the folders leading to a
+//! selected one, so it can be reached from the sync root.
+//!
+//! Changing the selection is planned here and applied by
+//! [`SyncEngine::apply_folder_selection`](crate::engine::SyncEngine::apply_folder_selection):
+//!
+//! 1. Newly selected folders are listed in the cloud and every item in
+//!    them becomes a placeholder (an `Online` SyncItem, no content).
+//! 2. Items of deselected folders are collected for removal. Nothing is
+//!    deleted in the cloud. Items with changes that were not uploaded yet
+//!    block the change, so no local work is lost.
+//! 3. The new selection, the placeholders and the removals are written in
+//!    one transaction. A failure in steps 1-3 leaves the previous
+//!    selection fully in place.
+
+use std::{
+    collections::{HashSet, VecDeque},
+    path::Path,
+};
+
+use anyhow::{Context, Result};
+use chrono::Utc;
+use lnxdrive_core::{
+    domain::{
+        newtypes::{FileHash, RemoteId, RemotePath, SyncPath},
+        ItemState, SyncItem,
+    },
+    ports::{
+        cloud_provider::{DeltaItem, ICloudProvider},
+        IStateRepository, ItemFilter,
+    },
+};
+use tracing::debug;
+
+use crate::exclusion::SyncExclusions;
+
+/// Remote folders selected for sync
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FolderSelection {
+    /// Normalized remote paths, none inside another (empty = everything)
+    folders: Vec<String>,
+}
+
+impl FolderSelection {
+    /// Selection of the whole drive
+    pub fn everything() -> Self {
+        Self::default()
+    }
+
+    /// Selection of `folders`, remote paths such as `/Documents/Work`
+    ///
+    /// A missing leading `/` is added and trailing slashes are dropped.
+    /// Folders inside another selected folder are left out, and selecting
+    /// `/` selects everything.
+    pub fn new<I, S>(folders: I) -> Result<Self>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let mut normalized = Vec::new();
+        for folder in folders {
+            let trimmed = folder.as_ref().trim().trim_matches('/');
+            if trimmed.is_empty() {
+                return Ok(Self::everything());
+            }
+            let path = RemotePath::new(format!("/{trimmed}"))
+                .with_context(|| format!("Invalid folder: {}", folder.as_ref()))?;
+            normalized.push(path.as_str().to_string());
+        }
+
+        // Shortest first, so a folder is seen before the folders inside it
+        normalized.sort_by(|a, b| a.len().cmp(&b.len()).then_with(|| a.cmp(b)));
+        let mut folders: Vec<String> = Vec::with_capacity(normalized.len());
+        for path in normalized {
+            if !folders.iter().any(|folder| is_within(&path, folder)) {
+                folders.push(path);
+            }
+        }
+        folders.sort();
+        Ok(Self { folders })
+    }
+
+    /// Returns true if the whole drive is selected
+    pub fn is_everything(&self) -> bool {
+        self.folders.is_empty()
+    }
+
+    /// Returns the selected folders (empty if everything is selected)
+    pub fn folders(&self) -> &[String] {
+        &self.folders
+    }
+
+    /// Returns true if `remote_path` is a selected folder or inside one
+    pub fn covers(&self, remote_path: &str) -> bool {
+        self.is_everything()
+            || self
+                .folders
+                .iter()
+                .any(|folder| is_within(remote_path, folder))
+    }
+
+    /// Returns true if the item at `remote_path` is synced: it is covered
+    /// by the selection, or it is a folder leading to a selected folder
+    pub fn includes(&self, remote_path: &str, is_directory: bool) -> bool {
+        self.covers(remote_path)
+            || (is_directory
+                && self
+                    .folders
+                    .iter()
+                    .any(|folder| is_within(folder, remote_path)))
+    }
+}
+
+/// Returns true if `path` is `folder` or inside it
+fn is_within(path: &str, folder: &str) -> bool {
+    path.strip_prefix(folder)
+        .is_some_and(|rest| rest.is_empty() || rest.starts_with('/'))
+}
+
+/// Progress of applying a folder selection
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SelectionProgress {
+    /// Listing the newly selected `folder`; `items` placeholders were
+    /// found so far in all folders
+    Listing { folder: String, items: u64 },
+    /// Removing the items of deselected folders; `done` of `total`
+    Removing { done: u64, total: u64 },
+}
+
+/// Result of applying a folder selection
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SelectionOutcome {
+    /// Placeholders created for newly selected folders
+    pub added: u64,
+    /// Items removed with deselected folders
+    pub removed: u64,
+}
+
+/// Changes needed to go from the tracked items to a new selection
+#[derive(Debug, Default)]
+pub(crate) struct SelectionPlan {
+    /// Placeholders to create, parents before children
+    pub saved: Vec<SyncItem>,
+    /// Tracked items to drop, parents before children
+    pub removed: Vec<SyncItem>,
+}
+
+/// Plans the change from the selection `previous` to `next`
+///
+/// Lists the cloud for the folders `next` adds and collects the tracked
+/// items it leaves out. Nothing is written.
+pub(crate) async fn plan_selection(
+    provider: &dyn ICloudProvider,
+    repository: &dyn IStateRepository,
+    exclusions: &SyncExclusions,
+    sync_root: &SyncPath,
+    previous: &FolderSelection,
+    next: &FolderSelection,
+    progress: &(dyn Fn(&SelectionProgress) + Send + Sync),
+) -> Result<SelectionPlan> {
+    let tracked = repository
+        .query_items(&ItemFilter::new())
+        .await
+        .context("Failed to query tracked items")?;
+
+    let mut removed: Vec<SyncItem> = Vec::new();
+    let mut known: HashSet<String> = HashSet::new();
+    for item in tracked {
+        let remote_path = item.remote_path().as_str().to_string();
+        if next.includes(&remote_path, item.is_directory()) {
+            known.insert(remote_path);
+            continue;
+        }
+        if item.remote_id().is_none()
+            || matches!(item.state(), ItemState::Modified | ItemState::Conflicted)
+        {
+            anyhow::bail!(
+                "{} has changes that are not uploaded yet; sync it before deselecting its folder",
+                item.local_path()
+            );
+        }
+        removed.push(item);
+    }
+    removed.sort_by(|a, b| a.remote_path().as_str().cmp(b.remote_path().as_str()));
+
+    let mut lister = Lister {
+        provider,
+        exclusions,
+        sync_root,
+        progress,
+        known,
+        saved: Vec::new(),
+    };
+    let roots = if next.is_everything() {
+        vec!["/".to_string()]
+    } else {
+        next.folders().to_vec()
+    };
+    for folder in roots.iter().filter(|folder| !previous.covers(folder)) {
+        lister.add_folder(folder).await?;
+    }
+
+    debug!(
+        added = lister.saved.len(),
+        removed = removed.len(),
+        "Planned folder selection"
+    );
+    Ok(SelectionPlan {
+        saved: lister.saved,
+        removed,
+    })
+}
+
+/// Creates placeholders for the untracked items of selected folders
+struct Lister<'a> {
+    provider: &'a dyn ICloudProvider,
+    exclusions: &'a SyncExclusions,
+    sync_root: &'a SyncPath,
+    progress: &'a (dyn Fn(&SelectionProgress) + Send + Sync),
+    /// Remote paths that are tracked or already planned
+    known: HashSet<String>,
+    saved: Vec<SyncItem>,
+}
+
+impl Lister<'_> {
+    /// Adds the selected `folder`, the folders leading to it and every
+    /// item inside it
+    async fn add_folder(&mut self, folder: &str) -> Result<()> {
+        if folder != "/" {
+            // Each folder on the way down, ending with `folder` itself
+            let mut path = String::new();
+            for name in folder.trim_start_matches('/').split('/') {
+                let parent = if path.is_empty() { "/" } else { path.as_str() };
+                let child = format!("{}/{name}", parent.trim_end_matches('/'));
+                if !self.known.contains(&child) {
+                    let item = self
+                        .find_child(parent, &child)
+                        .await?
+                        .filter(|item| item.is_directory)
+                        .ok_or_else(|| anyhow::anyhow!("{folder} is not a folder in the cloud"))?;
+                    self.add_placeholder(&item)?;
+                }
+                path = child;
+            }
+        }
+
+        let mut pending = VecDeque::from([folder.to_string()]);
+        while let Some(dir) = pending.pop_front() {
+            for item in self.list(&dir).await? {
+                let Some(path) = item.path.clone() else {
+                    continue;
+                };
+                if self.exclusions.excludes(Path::new(""), Path::new(&path)) {
+                    continue;
+                }
+                if item.is_directory {
+                    pending.push_back(path.clone());
+                }
+                if !self.known.contains(&path) {
+                    self.add_placeholder(&item)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Lists every page of the folder at `dir`
+    async fn list(&self, dir: &str) -> Result<Vec<DeltaItem>> {
+        let path = RemotePath::new(dir.to_string())?;
+        let mut items = Vec::new();
+        let mut continuation: Option<String> = None;
+        loop {
+            let page = self
+                .provider
+                .list_children(&path, continuation.as_deref())
+                .await
+                .with_context(|| format!("Failed to list {dir}"))?;
+            items.extend(page.items);
+            (self.progress)(&SelectionProgress::Listing {
+                folder: dir.to_string(),
+                items: self.saved.len() as u64,
+            });
+            match page.continuation {
+                Some(next) => continuation = Some(next),
+                None => return Ok(items),
+            }
+        }
+    }
+
+    /// Finds the item at `path` in the folder `parent`
+    async fn find_child(&self, parent: &str, path: &str) -> Result<Option<DeltaItem>> {
+        Ok(self
+            .list(parent)
+            .await?
+            .into_iter()
+            .find(|item| item.path.as_deref() == Some(path)))
+    }
+
+    fn add_placeholder(&mut self, item: &DeltaItem) -> Result<()> {
+        let placeholder = placeholder_item(item, self.sync_root)?;
+        self.known
+            .insert(placeholder.remote_path().as_str().to_string());
+        self.saved.push(placeholder);
+        Ok(())
+    }
+}
+
+/// Builds a placeholder SyncItem (no local content) for a remote item
+fn placeholder_item(item: &DeltaItem, sync_root: &SyncPath) -> Result<SyncItem> {
+    let remote_path = item
+        .path
+        .as_deref()
+        .ok_or_else(|| anyhow::anyhow!("Remote item has no path: {}", item.id))?;
+    let local_path = SyncPath::new(
+        sync_root
+            .as_path()
+            .join(remote_path.trim_start_matches('/')),
+    )
+    .context("Failed to construct local path")?;
+
+    let mut placeholder = SyncItem::from_remote(
+        local_path,
+        RemotePath::new(remote_path.to_string())?,
+        RemoteId::new(item.id.clone()).context("Invalid remote ID")?,
+        item.is_directory,
+        item.size.unwrap_or(0),
+        item.hash.clone().and_then(|hash| FileHash::new(hash).ok()),
+        item.modified.unwrap_or_else(Utc::now),
+    )?;
+    placeholder.metadata_mut().set_facets(item.facets.clone());
+    placeholder.mark_synced();
+    Ok(placeholder)
+}
+
+/// Returns the removed items that are not inside another removed folder
+///
+/// `removed` must be sorted parents first, as planned.
+pub(crate) fn topmost(removed: &[SyncItem]) -> Vec<&SyncItem> {
+    let mut top: Vec<&SyncItem> = Vec::new();
+    for item in removed {
+        let path = item.remote_path().as_str();
+        let inside_removed = top
+            .iter()
+            .any(|dir| dir.is_directory() && is_within(path, dir.remote_path().as_str()));
+        if !inside_removed {
+            top.push(item);
+        }
+    }
+    top
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_selection_is_normalized() {
+        let selection =
+            FolderSelection::new(["Docs/", "/Docs/Work", "/Photos/2024", "/Photos/2024/"]).unwrap();
+        assert_eq!(selection.folders(), ["/Docs", "/Photos/2024"]);
+
+        assert!(FolderSelection::new(["/Docs", "/"])
+            .unwrap()
+            .is_everything());
+        assert!(FolderSelection::new(Vec::<String>::new())
+            .unwrap()
+            .is_everything());
+        assert!(FolderSelection::new(["/Docs/../etc"]).is_err());
+    }
+
+    #[test]
+    fn test_selection_includes_selected_folders_and_their_parents() {
+        let selection = FolderSelection::new(["/Photos/2024"]).unwrap();
+
+        assert!(selection.includes("/Photos/2024", true));
+        assert!(selection.includes("/Photos/2024/beach.jpg", false));
+        // The folder leading to the selection, but not its other content
+        assert!(selection.includes("/Photos", true));
+        assert!(!selection.includes("/Photos/2023", true));
+        assert!(!selection.includes("/Photos/cover.jpg", false));
+        // A sibling sharing the prefix
+        assert!(!selection.includes("/Photos/2024-old", true));
+        assert!(!selection.includes("/Docs", true));
+
+        assert!(FolderSelection::everything().includes("/Docs/a.txt", false));
+    }
+}
@@ -15,21 +15,31 @@
 //!       ▼
 //!  FileWatcher  ──→  mpsc::channel  ──→  DebouncedChangeQueue  ──→  SyncScheduler
 //! ```
+//!
+//! notify delivers raw events on its own thread. They are translated on a
+//! separate thread, so walking a new directory or waiting for room in the
+//! channel never holds up notify's event loop.
 
 use std::{
-    collections::HashMap,
+    collections::{HashMap, HashSet},
     path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering},
+        Arc,
+    },
     time::{Duration, Instant},
 };
 
 use anyhow::{Context, Result};
 use notify::{
-    event::{ModifyKind, RenameMode},
+    event::{CreateKind, ModifyKind, RemoveKind, RenameMode},
     EventKind, RecommendedWatcher, RecursiveMode, Watcher,
 };
 use tokio::sync::mpsc;
 use tracing::{debug, error, info, warn};
 
+use crate::exclusion::SyncExclusions;
+
 // ============================================================================
 // T178: ChangeEvent enum
 // ============================================================================
@@ -53,6 +63,9 @@ pub enum ChangeEvent {
         /// The new path after the rename
         new: PathBuf,
     },
+    /// Events under the given subtree may have been lost (e.g. inotify
+    /// queue overflow) and it must be rescanned to reconcile state
+    Rescan(PathBuf),
 }
 
 impl ChangeEvent {
@@ -65,6 +78,7 @@ impl ChangeEvent {
             ChangeEvent::Modified(p) => p,
             ChangeEvent::Deleted(p) => p,
             ChangeEvent::Renamed { new, .. } => new,
+            ChangeEvent::Rescan(p) => p,
         }
     }
 }
@@ -91,6 +105,12 @@ pub struct FileWatcher {
     watcher: RecommendedWatcher,
     /// Sender half of the channel used to emit ChangeEvents
     event_tx: mpsc::Sender<ChangeEvent>,
+    /// Number of watched directories, compared against the inotify limit
+    watch_budget: Arc<WatchBudget>,
+    /// Root paths passed to [`watch`](FileWatcher::watch), rescanned on overflow
+    roots: Arc<std::sync::Mutex<Vec<PathBuf>>>,
+    /// Number of event queue overflows observed since creation
+    overflow_count: Arc<AtomicU64>,
 }
 
 impl FileWatcher {
@@ -114,33 +134,40 @@ impl FileWatcher {
     pub fn new(debounce_ms: u64) -> Result<(Self, mpsc::Receiver<ChangeEvent>)> {
         let (event_tx, event_rx) = mpsc::channel::<ChangeEvent>(1024);
         let tx = event_tx.clone();
+        let watch_budget = Arc::new(WatchBudget::new(inotify_watch_limit()));
+        let budget = Arc::clone(&watch_budget);
+        let roots: Arc<std::sync::Mutex<Vec<PathBuf>>> = Arc::default();
+        let overflow_roots = Arc::clone(&roots);
+        let overflow_count = Arc::new(AtomicU64::new(0));
+        let overflows = Arc::clone(&overflow_count);
 
         info!(debounce_ms, "Initializing file watcher");
 
         // Note: notify 6.x uses an event handler callback rather than a
-        // built-in debounce. We configure the watcher with a callback that
-        // converts events and sends them through the channel. Debouncing is
-        // handled externally by DebouncedChangeQueue.
+        // built-in debounce. The watcher hands raw events to a translation
+        // thread that converts them and sends them through the channel.
+        // Debouncing is handled externally by DebouncedChangeQueue.
         let _ = debounce_ms; // Debouncing is handled by DebouncedChangeQueue
 
-        let watcher = RecommendedWatcher::new(
-            move |res: std::result::Result<notify::Event, notify::Error>| match res {
-                Ok(event) => {
-                    if let Some(change) = map_notify_event(&event) {
-                        if let Err(e) = tx.blocking_send(change) {
-                            warn!(error = %e, "Failed to send change event (receiver dropped)");
-                        }
-                    }
-                }
-                Err(err) => {
-                    error!(error = %err, "File watcher error");
-                }
+        let (raw_tx, raw_rx) = std::sync::mpsc::channel::<RawEvent>();
+        std::thread::Builder::new()
+            .name("lnxdrive-watcher".to_string())
+            .spawn(move || translate_events(raw_rx, tx, budget, overflow_roots, overflows))
+            .context("Failed to start the file watcher thread")?;
+
+        let watcher = RecommendedWatcher::new(raw_tx, notify::Config::default())
+            .context("Failed to create file watcher")?;
+
+        Ok((
+            Self {
+                watcher,
+                event_tx,
+                watch_budget,
+                roots,
+                overflow_count,
             },
-            notify::Config::default(),
-        )
-        .context("Failed to create file watcher")?;
-
-        Ok((Self { watcher, event_tx }, event_rx))
+            event_rx,
+        ))
     }
 
     // ========================================================================
@@ -149,8 +176,9 @@ impl FileWatcher {
 
     /// Starts watching a directory recursively for filesystem changes
     ///
-    /// All subdirectories under the given path will be monitored. Returns a
-    /// [`WatchHandle`] that, when dropped, stops watching the path.
+    /// All subdirectories under the given path will be monitored, including
+    /// directories created after the watch starts. Returns a [`WatchHandle`]
+    /// that, when dropped, stops watching the path.
     ///
     /// # Arguments
     /// * `path` - The directory path to watch
@@ -161,9 +189,22 @@ impl FileWatcher {
     pub fn watch(&mut self, path: &Path) -> Result<WatchHandle> {
         info!(path = %path.display(), "Starting recursive watch");
 
-        self.watcher
-            .watch(path, RecursiveMode::Recursive)
-            .with_context(|| format!("Failed to watch path: {}", path.display()))?;
+        if let Err(err) = self.watcher.watch(path, RecursiveMode::Recursive) {
+            if matches!(err.kind, notify::ErrorKind::MaxFilesWatch) {
+                let hint = watch_limit_hint(self.watch_budget.limit);
+                warn!("{}", hint);
+                return Err(anyhow::Error::new(err)
+                    .context(hint)
+                    .context(format!("Failed to watch path: {}", path.display())));
+            }
+            return Err(anyhow::Error::new(err))
+                .with_context(|| format!("Failed to watch path: {}", path.display()));
+        }
+
+        self.watch_budget.add(count_directories(path));
+        if let Ok(mut roots) = self.roots.lock() {
+            roots.push(path.to_path_buf());
+        }
 
         let watched_path = path.to_path_buf();
         let tx = self.event_tx.clone();
@@ -194,8 +235,275 @@ impl FileWatcher {
             .unwatch(path)
             .with_context(|| format!("Failed to unwatch path: {}", path.display()))?;
 
+        self.watch_budget.remove(count_directories(path));
+        if let Ok(mut roots) = self.roots.lock() {
+            roots.retain(|r| r != path);
+        }
+
         Ok(())
     }
+
+    /// Returns how many times the OS event queue overflowed
+    ///
+    /// Each overflow schedules a [`ChangeEvent::Rescan`] for every watched root.
+    pub fn overflow_count(&self) -> u64 {
+        self.overflow_count.load(Ordering::Relaxed)
+    }
+
+    /// Returns the counter behind [`overflow_count`](Self::overflow_count)
+    ///
+    /// Lets a metric read the count without keeping the watcher alive.
+    pub fn overflow_counter(&self) -> Arc<AtomicU64> {
+        Arc::clone(&self.overflow_count)
+    }
+
+    /// Returns the number of directories currently being watched
+    pub fn watched_directories(&self) -> usize {
+        self.watch_budget.count.load(Ordering::Relaxed)
+    }
+}
+
+// ============================================================================
+// Watch limit tracking
+// ============================================================================
+
+/// Sysctl file holding the per-user inotify watch limit
+const INOTIFY_MAX_WATCHES_PATH: &str = "/proc/sys/fs/inotify/max_user_watches";
+
+/// Tracks how many directories are watched relative to the inotify limit
+///
+/// inotify needs one watch per directory. notify adds watches for new
+/// subdirectories internally but silently ignores failures, so the count is
+/// kept here to warn the user before changes start going unnoticed.
+struct WatchBudget {
+    /// Directories currently watched
+    count: AtomicUsize,
+    /// Value of `fs.inotify.max_user_watches`, if known
+    limit: Option<usize>,
+    /// Whether the limit warning has already been logged
+    warned: AtomicBool,
+}
+
+impl WatchBudget {
+    fn new(limit: Option<usize>) -> Self {
+        Self {
+            count: AtomicUsize::new(0),
+            limit,
+            warned: AtomicBool::new(false),
+        }
+    }
+
+    /// Records `n` newly watched directories, warning once if the limit is reached
+    fn add(&self, n: usize) {
+        let total = self.count.fetch_add(n, Ordering::Relaxed) + n;
+        if let Some(limit) = self.limit {
+            if total >= limit && !self.warned.swap(true, Ordering::Relaxed) {
+                warn!(watched = total, "{}", watch_limit_hint(Some(limit)));
+            }
+        }
+    }
+
+    /// Records `n` directories that are no longer watched
+    fn remove(&self, n: usize) {
+        let mut current = self.count.load(Ordering::Relaxed);
+        while let Err(actual) = self.count.compare_exchange_weak(
+            current,
+            current.saturating_sub(n),
+            Ordering::Relaxed,
+            Ordering::Relaxed,
+        ) {
+            current = actual;
+        }
+    }
+}
+
+/// Reads the per-user inotify watch limit from procfs
+pub fn inotify_watch_limit() -> Option<usize> {
+    std::fs::read_to_string(INOTIFY_MAX_WATCHES_PATH)
+        .ok()
+        .and_then(|s| s.trim().parse().ok())
+}
+
+/// Builds the warning shown when the inotify watch limit is reached
+fn watch_limit_hint(limit: Option<usize>) -> String {
+    let current = limit
+        .map(|l| format!(" (currently {l})"))
+        .unwrap_or_default();
+    format!(
+        "inotify watch limit reached{current}; changes in some directories will not be detected. \
+         Raise fs.inotify.max_user_watches, e.g. \
+         `sudo sysctl fs.inotify.max_user_watches=524288`, and persist it in /etc/sysctl.d/"
+    )
+}
+
+/// Counts `path` and all directories below it (symlinks are not followed)
+///
+/// This is the number of inotify watches a recursive watch of `path` needs.
+pub fn count_directories(path: &Path) -> usize {
+    let mut count = 0;
+    let mut stack = vec![path.to_path_buf()];
+
+    while let Some(dir) = stack.pop() {
+        count += 1;
+        let Ok(entries) = std::fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            if entry.file_type().map(|t| t.is_dir()).unwrap_or(false) {
+                stack.push(entry.path());
+            }
+        }
+    }
+
+    count
+}
+
+/// Lists every entry below `dir`, parents before children
+fn list_tree(dir: &Path) -> Vec<(PathBuf, bool)> {
+    let mut entries = Vec::new();
+    let mut stack = vec![dir.to_path_buf()];
+
+    while let Some(current) = stack.pop() {
+        let Ok(read_dir) = std::fs::read_dir(&current) else {
+            continue;
+        };
+        for entry in read_dir.flatten() {
+            let is_dir = entry.file_type().map(|t| t.is_dir()).unwrap_or(false);
+            let path = entry.path();
+            if is_dir {
+                stack.push(path.clone());
+            }
+            entries.push((path, is_dir));
+        }
+    }
+
+    entries
+}
+
+// ============================================================================
+// Event translation
+// ============================================================================
+
+/// A raw result from notify, as delivered on its event loop thread
+type RawEvent = std::result::Result<notify::Event, notify::Error>;
+
+/// Translates raw notify results into change events and sends them to `tx`
+///
+/// Runs on its own thread until notify stops (the watcher is dropped) or
+/// the receiver of `tx` is dropped.
+fn translate_events(
+    raw_rx: std::sync::mpsc::Receiver<RawEvent>,
+    tx: mpsc::Sender<ChangeEvent>,
+    budget: Arc<WatchBudget>,
+    roots: Arc<std::sync::Mutex<Vec<PathBuf>>>,
+    overflows: Arc<AtomicU64>,
+) {
+    for res in raw_rx {
+        match res {
+            Ok(event) => {
+                let changes = overflow_rescan_events(&event, &roots, &overflows)
+                    .unwrap_or_else(|| expand_notify_event(&event, &budget));
+                for change in changes {
+                    if let Err(e) = tx.blocking_send(change) {
+                        warn!(error = %e, "Failed to send change event (receiver dropped)");
+                        return;
+                    }
+                }
+            }
+            Err(err) => {
+                if matches!(err.kind, notify::ErrorKind::MaxFilesWatch) {
+                    warn!("{}", watch_limit_hint(budget.limit));
+                } else {
+                    error!(error = %err, "File watcher error");
+                }
+            }
+        }
+    }
+    debug!("File watcher thread stopped");
+}
+
+// ============================================================================
+// Event queue overflow
+// ============================================================================
+
+/// Handles an event queue overflow notice from notify
+///
+/// inotify reports `IN_Q_OVERFLOW` when its kernel queue fills up and
+/// events are dropped. notify forwards it as an event flagged `Rescan`
+/// without any path, so every watched root is scheduled for a rescan.
+///
+/// Returns `None` if `event` is not an overflow notice.
+fn overflow_rescan_events(
+    event: &notify::Event,
+    roots: &std::sync::Mutex<Vec<PathBuf>>,
+    overflows: &AtomicU64,
+) -> Option<Vec<ChangeEvent>> {
+    if !event.need_rescan() {
+        return None;
+    }
+
+    let total = overflows.fetch_add(1, Ordering::Relaxed) + 1;
+    let roots = roots.lock().map(|r| r.clone()).unwrap_or_default();
+
+    let affected: Vec<PathBuf> = if event.paths.is_empty() {
+        roots
+    } else {
+        event.paths.clone()
+    };
+
+    warn!(
+        overflows = total,
+        roots = affected.len(),
+        "File watcher event queue overflowed; scheduling reconciliation scan"
+    );
+
+    Some(affected.into_iter().map(ChangeEvent::Rescan).collect())
+}
+
+// ============================================================================
+// New directory expansion
+// ============================================================================
+
+/// Maps a notify event to change events, expanding newly appeared directories
+///
+/// When a directory is created or moved into the watched tree, its contents
+/// may already exist before notify has added a watch for it (e.g. `mkdir -p`
+/// followed by a quick write, or `mv` of a whole tree). Those entries never
+/// produce events of their own, so they are reported here as `Created`.
+fn expand_notify_event(event: &notify::Event, budget: &WatchBudget) -> Vec<ChangeEvent> {
+    let Some(change) = map_notify_event(event) else {
+        return Vec::new();
+    };
+
+    let appeared_dir = match (&event.kind, &change) {
+        (EventKind::Create(CreateKind::Folder), ChangeEvent::Created(p)) => Some(p.clone()),
+        (EventKind::Create(_), ChangeEvent::Created(p)) if p.is_dir() => Some(p.clone()),
+        (_, ChangeEvent::Renamed { new, .. }) if new.is_dir() => Some(new.clone()),
+        _ => None,
+    };
+
+    if matches!(event.kind, EventKind::Remove(RemoveKind::Folder)) {
+        budget.remove(1);
+    }
+
+    let mut changes = vec![change];
+
+    if let Some(dir) = appeared_dir {
+        let tree = list_tree(&dir);
+        let new_dirs = tree.iter().filter(|(_, is_dir)| *is_dir).count();
+        budget.add(new_dirs + 1);
+
+        if !tree.is_empty() {
+            debug!(
+                path = %dir.display(),
+                entries = tree.len(),
+                "Reporting contents of new directory"
+            );
+        }
+        changes.extend(tree.into_iter().map(|(p, _)| ChangeEvent::Created(p)));
+    }
+
+    changes
 }
 
 // ============================================================================
@@ -346,6 +654,41 @@ pub async fn is_file_stable(path: &Path, check_interval_ms: u64) -> bool {
     stable
 }
 
+// ============================================================================
+// Editor temporary file detection
+// ============================================================================
+
+/// Returns true if `path` looks like a temporary file written by an editor
+/// during a save
+///
+/// Editors rarely write a file in place. Instead they write a temporary file
+/// and rename it over the original, or move the original aside as a backup.
+/// Recognised patterns:
+/// - `.goutputstream-*` (GIO atomic save, used by gedit and Nautilus)
+/// - `#*#` (emacs auto-save files)
+/// - `.#*` (emacs lock files)
+/// - `.*.swp`, `.*.swo`, `.*.swx` (vim swap files)
+///
+/// These names are never used for real files. `*~` backups are left to the
+/// junk exclusion (see [`SyncExclusions`]), and vim's `4913` probe is only
+/// dropped when it is deleted again within the debounce window.
+pub fn is_editor_temp_file(path: &Path) -> bool {
+    let Some(name) = path.file_name().and_then(|n| n.to_str()) else {
+        return false;
+    };
+
+    if name.starts_with(".goutputstream-") || name.starts_with(".#") {
+        return true;
+    }
+
+    if name.len() > 2 && name.starts_with('#') && name.ends_with('#') {
+        return true;
+    }
+
+    name.starts_with('.')
+        && (name.ends_with(".swp") || name.ends_with(".swo") || name.ends_with(".swx"))
+}
+
 // ============================================================================
 // T180: DebouncedChangeQueue struct
 // ============================================================================
@@ -362,11 +705,22 @@ pub async fn is_file_stable(path: &Path, check_interval_ms: u64) -> bool {
 /// This prevents the sync engine from reacting to every intermediate save
 /// of a file being edited, or to rapid create/modify sequences that happen
 /// when applications write files.
+///
+/// Events inside the FUSE mount point or cache directory are dropped (see
+/// [`with_ignored_dirs`](Self::with_ignored_dirs)): if either lies inside
+/// the watched tree, syncing would write there and wake the watcher again,
+/// looping forever.
 pub struct DebouncedChangeQueue {
     /// Pending changes keyed by path, storing the latest event and its timestamp
     pending: HashMap<PathBuf, (ChangeEvent, Instant)>,
     /// Minimum quiet period before a change is considered settled
     debounce_delay: Duration,
+    /// Sync root and the built-in exclusions applied below it
+    exclusions: Option<(PathBuf, SyncExclusions)>,
+    /// Directories whose events are never synced (mount point, cache)
+    ignored_dirs: Vec<PathBuf>,
+    /// Ignored directories that events were already seen in
+    reported_dirs: HashSet<PathBuf>,
 }
 
 impl DebouncedChangeQueue {
@@ -378,7 +732,49 @@ impl DebouncedChangeQueue {
         Self {
             pending: HashMap::new(),
             debounce_delay,
+            exclusions: None,
+            ignored_dirs: Vec::new(),
+            reported_dirs: HashSet::new(),
+        }
+    }
+
+    /// Drops the events of paths below `root` that `exclusions` leave out
+    /// of sync
+    pub fn with_exclusions(mut self, root: PathBuf, exclusions: SyncExclusions) -> Self {
+        self.exclusions = Some((root, exclusions));
+        self
+    }
+
+    /// Drops the events of paths inside `dirs`, typically the FUSE mount
+    /// point and cache directory
+    ///
+    /// The configuration already refuses to nest them inside the sync root;
+    /// this guards against the watcher seeing the daemon's own writes if
+    /// that happens anyway (e.g. through a bind mount or symlink).
+    pub fn with_ignored_dirs(mut self, dirs: impl IntoIterator<Item = PathBuf>) -> Self {
+        self.ignored_dirs.extend(dirs);
+        self
+    }
+
+    /// Returns true if `path` is left out of sync, as an editor temporary
+    /// file, by the configured exclusions or inside an ignored directory
+    fn is_ignored(&mut self, path: &Path) -> bool {
+        if let Some(dir) = self.ignored_dirs.iter().find(|dir| path.starts_with(dir)) {
+            if self.reported_dirs.insert(dir.clone()) {
+                warn!(
+                    dir = %dir.display(),
+                    path = %path.display(),
+                    "Ignoring changes inside the FUSE mount or cache directory, which is \
+                     inside the watched sync root; move it out to avoid sync loops"
+                );
+            }
+            return true;
         }
+        is_editor_temp_file(path)
+            || self
+                .exclusions
+                .as_ref()
+                .is_some_and(|(root, exclusions)| exclusions.excludes(root, path))
     }
 
     // ========================================================================
@@ -392,9 +788,56 @@ impl DebouncedChangeQueue {
     /// This means rapid changes to the same file will keep extending
     /// the debounce window until the changes stop.
     ///
+    /// Editor save sequences are collapsed into a single logical change:
+    /// - Events on editor temporary files (see [`is_editor_temp_file`]), on
+    ///   excluded paths (see [`with_exclusions`](Self::with_exclusions)) and
+    ///   inside ignored directories are dropped
+    /// - A temporary file renamed over a real file becomes `Modified(real)`
+    /// - A real file renamed to an excluded backup name becomes `Deleted(real)`
+    /// - `Deleted` followed by `Created` for the same path becomes `Modified`
+    /// - `Created` followed by `Deleted` for the same path is dropped, which
+    ///   covers probes such as vim's `4913`
+    ///
     /// # Arguments
     /// * `event` - The change event to enqueue
     pub fn push(&mut self, event: ChangeEvent) {
+        let event = match event {
+            ChangeEvent::Renamed { old, new } => {
+                match (self.is_ignored(&old), self.is_ignored(&new)) {
+                    (true, true) => {
+                        self.pending.remove(&old);
+                        return;
+                    }
+                    (true, false) => {
+                        self.pending.remove(&old);
+                        ChangeEvent::Modified(new)
+                    }
+                    (false, true) => ChangeEvent::Deleted(old),
+                    (false, false) => ChangeEvent::Renamed { old, new },
+                }
+            }
+            other if self.is_ignored(other.path()) => {
+                debug!(path = %other.path().display(), "Ignoring excluded or temporary file");
+                self.pending.remove(other.path());
+                return;
+            }
+            other => other,
+        };
+
+        // A file replaced within the debounce window is a modification,
+        // and one created and deleted again never existed for sync
+        let event = match (self.pending.get(event.path()), event) {
+            (Some((ChangeEvent::Deleted(_), _)), ChangeEvent::Created(path)) => {
+                ChangeEvent::Modified(path)
+            }
+            (Some((ChangeEvent::Created(_), _)), ChangeEvent::Deleted(path)) => {
+                debug!(path = %path.display(), "Dropping file created and deleted before settling");
+                self.pending.remove(&path);
+                return;
+            }
+            (_, event) => event,
+        };
+
         let path = event.path().to_path_buf();
         debug!(
             path = %path.display(),
@@ -635,6 +1078,198 @@ mod tests {
         assert_eq!(settled[0], ChangeEvent::Modified(PathBuf::from("/a.txt")));
     }
 
+    // ------------------------------------------------------------------
+    // Editor save sequence tests
+    // ------------------------------------------------------------------
+
+    #[test]
+    fn test_is_editor_temp_file() {
+        assert!(is_editor_temp_file(Path::new("/d/.goutputstream-XYZ123")));
+        assert!(is_editor_temp_file(Path::new("/d/#report.txt#")));
+        assert!(is_editor_temp_file(Path::new("/d/.#report.txt")));
+        assert!(is_editor_temp_file(Path::new("/d/.report.txt.swp")));
+
+        assert!(!is_editor_temp_file(Path::new("/d/report.txt")));
+        assert!(!is_editor_temp_file(Path::new("/d/.bashrc")));
+        assert!(!is_editor_temp_file(Path::new("/d/~")));
+        assert!(!is_editor_temp_file(Path::new("/d/#")));
+        assert!(!is_editor_temp_file(Path::new("/d/notes.swp")));
+        assert!(!is_editor_temp_file(Path::new("/d/report.txt~")));
+        assert!(!is_editor_temp_file(Path::new("/d/4913")));
+    }
+
+    /// Queue with the default exclusions, which leave out `name~` backups
+    fn default_queue() -> DebouncedChangeQueue {
+        DebouncedChangeQueue::new(Duration::from_millis(0)).with_exclusions(
+            PathBuf::from("/d"),
+            SyncExclusions {
+                hidden: false,
+                junk: true,
+            },
+        )
+    }
+
+    #[test]
+    fn test_gedit_atomic_save_yields_one_change() {
+        let mut queue = DebouncedChangeQueue::new(Duration::from_millis(0));
+        let tmp = PathBuf::from("/d/.goutputstream-ABC123");
+        let doc = PathBuf::from("/d/report.txt");
+
+        queue.push(ChangeEvent::Created(tmp.clone()));
+        queue.push(ChangeEvent::Modified(tmp.clone()));
+        queue.push(ChangeEvent::Modified(tmp.clone()));
+        queue.push(ChangeEvent::Renamed {
+            old: tmp,
+            new: doc.clone(),
+        });
+
+        std::thread::sleep(Duration::from_millis(10));
+        let settled = queue.poll();
+        assert_eq!(settled, vec![ChangeEvent::Modified(doc)]);
+    }
+
+    #[test]
+    fn test_vim_backup_save_yields_one_change() {
+        let mut queue = default_queue();
+        let doc = PathBuf::from("/d/report.txt");
+        let backup = PathBuf::from("/d/report.txt~");
+
+        queue.push(ChangeEvent::Created(PathBuf::from("/d/4913")));
+        queue.push(ChangeEvent::Deleted(PathBuf::from("/d/4913")));
+        queue.push(ChangeEvent::Renamed {
+            old: doc.clone(),
+            new: backup.clone(),
+        });
+        queue.push(ChangeEvent::Created(doc.clone()));
+        queue.push(ChangeEvent::Modified(doc.clone()));
+        queue.push(ChangeEvent::Deleted(backup));
+
+        std::thread::sleep(Duration::from_millis(10));
+        let settled = queue.poll();
+        assert_eq!(settled, vec![ChangeEvent::Modified(doc)]);
+    }
+
+    #[test]
+    fn test_emacs_save_yields_one_change() {
+        let mut queue = default_queue();
+        let doc = PathBuf::from("/d/report.txt");
+
+        queue.push(ChangeEvent::Created(PathBuf::from("/d/.#report.txt")));
+        queue.push(ChangeEvent::Modified(PathBuf::from("/d/#report.txt#")));
+        queue.push(ChangeEvent::Renamed {
+            old: doc.clone(),
+            new: PathBuf::from("/d/report.txt~"),
+        });
+        queue.push(ChangeEvent::Created(doc.clone()));
+        queue.push(ChangeEvent::Deleted(PathBuf::from("/d/#report.txt#")));
+        queue.push(ChangeEvent::Deleted(PathBuf::from("/d/.#report.txt")));
+
+        std::thread::sleep(Duration::from_millis(10));
+        let settled = queue.poll();
+        assert_eq!(settled, vec![ChangeEvent::Modified(doc)]);
+    }
+
+    #[test]
+    fn test_backups_are_synced_without_junk_exclusion() {
+        let mut queue = DebouncedChangeQueue::new(Duration::from_millis(0));
+        let backup = PathBuf::from("/d/report.txt~");
+
+        queue.push(ChangeEvent::Created(backup.clone()));
+
+        std::thread::sleep(Duration::from_millis(10));
+        assert_eq!(queue.poll(), vec![ChangeEvent::Created(backup)]);
+    }
+
+    #[test]
+    fn test_file_named_4913_is_synced_unless_deleted_before_settling() {
+        let mut queue = DebouncedChangeQueue::new(Duration::from_millis(0));
+        let probe = PathBuf::from("/d/4913");
+
+        queue.push(ChangeEvent::Created(probe.clone()));
+        queue.push(ChangeEvent::Deleted(probe.clone()));
+        assert!(queue.is_empty());
+
+        queue.push(ChangeEvent::Created(probe.clone()));
+        std::thread::sleep(Duration::from_millis(10));
+        assert_eq!(queue.poll(), vec![ChangeEvent::Created(probe)]);
+    }
+
+    #[test]
+    fn test_delete_then_create_becomes_modified() {
+        let mut queue = DebouncedChangeQueue::new(Duration::from_millis(0));
+        queue.push(ChangeEvent::Deleted(PathBuf::from("/a.txt")));
+        queue.push(ChangeEvent::Created(PathBuf::from("/a.txt")));
+
+        std::thread::sleep(Duration::from_millis(10));
+        let settled = queue.poll();
+        assert_eq!(
+            settled,
+            vec![ChangeEvent::Modified(PathBuf::from("/a.txt"))]
+        );
+    }
+
+    #[test]
+    fn test_excluded_paths_are_dropped() {
+        let exclusions = SyncExclusions {
+            hidden: true,
+            junk: true,
+        };
+        let mut queue = DebouncedChangeQueue::new(Duration::from_millis(0))
+            .with_exclusions(PathBuf::from("/h/.od"), exclusions);
+        queue.push(ChangeEvent::Modified(PathBuf::from("/h/.od/.git/index")));
+        queue.push(ChangeEvent::Created(PathBuf::from("/h/.od/Thumbs.db")));
+        queue.push(ChangeEvent::Renamed {
+            old: PathBuf::from("/h/.od/.draft"),
+            new: PathBuf::from("/h/.od/draft"),
+        });
+        queue.push(ChangeEvent::Created(PathBuf::from("/h/.od/main.rs")));
+
+        std::thread::sleep(Duration::from_millis(10));
+        let mut settled = queue.poll();
+        settled.sort_by(|a, b| a.path().cmp(b.path()));
+        assert_eq!(
+            settled,
+            vec![
+                ChangeEvent::Modified(PathBuf::from("/h/.od/draft")),
+                ChangeEvent::Created(PathBuf::from("/h/.od/main.rs")),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_events_inside_mount_and_cache_are_dropped() {
+        let mut queue = DebouncedChangeQueue::new(Duration::from_millis(0)).with_ignored_dirs([
+            PathBuf::from("/h/OneDrive/.mount"),
+            PathBuf::from("/h/OneDrive/.cache"),
+        ]);
+        queue.push(ChangeEvent::Created(PathBuf::from(
+            "/h/OneDrive/.mount/a.txt",
+        )));
+        queue.push(ChangeEvent::Modified(PathBuf::from(
+            "/h/OneDrive/.cache/content/ab/cd",
+        )));
+        queue.push(ChangeEvent::Deleted(PathBuf::from(
+            "/h/OneDrive/.mount/b.txt",
+        )));
+        queue.push(ChangeEvent::Renamed {
+            old: PathBuf::from("/h/OneDrive/.cache/tmp/x"),
+            new: PathBuf::from("/h/OneDrive/.cache/content/x"),
+        });
+        // Only a name prefix, not inside the mount
+        queue.push(ChangeEvent::Created(PathBuf::from(
+            "/h/OneDrive/.mount2/c.txt",
+        )));
+
+        std::thread::sleep(Duration::from_millis(10));
+        assert_eq!(
+            queue.poll(),
+            vec![ChangeEvent::Created(PathBuf::from(
+                "/h/OneDrive/.mount2/c.txt"
+            ))]
+        );
+        assert_eq!(queue.reported_dirs.len(), 2);
+    }
+
     #[test]
     fn test_empty_queue() {
         let mut queue = DebouncedChangeQueue::new(Duration::from_millis(100));
@@ -732,6 +1367,144 @@ mod tests {
         assert!(mapped.is_none());
     }
 
+    // ------------------------------------------------------------------
+    // New directory handling tests
+    // ------------------------------------------------------------------
+
+    #[test]
+    fn test_expand_new_directory_reports_contents() {
+        let dir = tempfile::tempdir().unwrap();
+        let nested = dir.path().join("a");
+        std::fs::create_dir_all(nested.join("b")).unwrap();
+        std::fs::write(nested.join("b").join("file.txt"), b"x").unwrap();
+
+        let event = notify::Event {
+            kind: EventKind::Create(CreateKind::Folder),
+            paths: vec![nested.clone()],
+            attrs: Default::default(),
+        };
+        let budget = WatchBudget::new(None);
+        let changes = expand_notify_event(&event, &budget);
+
+        assert_eq!(changes[0], ChangeEvent::Created(nested.clone()));
+        assert!(changes.contains(&ChangeEvent::Created(nested.join("b"))));
+        assert!(changes.contains(&ChangeEvent::Created(nested.join("b").join("file.txt"))));
+        assert_eq!(budget.count.load(Ordering::Relaxed), 2);
+    }
+
+    #[test]
+    fn test_translation_thread_expands_new_directory() {
+        let dir = tempfile::tempdir().unwrap();
+        let nested = dir.path().join("a");
+        std::fs::create_dir(&nested).unwrap();
+        std::fs::write(nested.join("file.txt"), b"x").unwrap();
+
+        let (raw_tx, raw_rx) = std::sync::mpsc::channel();
+        let (tx, mut rx) = mpsc::channel(16);
+        let thread = std::thread::spawn(move || {
+            translate_events(
+                raw_rx,
+                tx,
+                Arc::new(WatchBudget::new(None)),
+                Arc::default(),
+                Arc::default(),
+            )
+        });
+
+        // notify's callback only queues the event and returns
+        raw_tx
+            .send(Ok(notify::Event {
+                kind: EventKind::Create(CreateKind::Folder),
+                paths: vec![nested.clone()],
+                attrs: Default::default(),
+            }))
+            .unwrap();
+        drop(raw_tx);
+        thread.join().unwrap();
+
+        assert_eq!(rx.try_recv().unwrap(), ChangeEvent::Created(nested.clone()));
+        assert_eq!(
+            rx.try_recv().unwrap(),
+            ChangeEvent::Created(nested.join("file.txt"))
+        );
+        assert!(rx.try_recv().is_err());
+    }
+
+    // ------------------------------------------------------------------
+    // Overflow tests
+    // ------------------------------------------------------------------
+
+    #[test]
+    fn test_overflow_schedules_rescan_of_roots() {
+        let roots = std::sync::Mutex::new(vec![PathBuf::from("/home/user/OneDrive")]);
+        let overflows = AtomicU64::new(0);
+        let event = notify::Event::new(EventKind::Other).set_flag(notify::event::Flag::Rescan);
+
+        let changes = overflow_rescan_events(&event, &roots, &overflows).unwrap();
+
+        assert_eq!(
+            changes,
+            vec![ChangeEvent::Rescan(PathBuf::from("/home/user/OneDrive"))]
+        );
+        assert_eq!(overflows.load(Ordering::Relaxed), 1);
+    }
+
+    #[test]
+    fn test_non_overflow_event_not_rescanned() {
+        let roots = std::sync::Mutex::new(vec![PathBuf::from("/root")]);
+        let overflows = AtomicU64::new(0);
+        let event = notify::Event {
+            kind: EventKind::Create(CreateKind::File),
+            paths: vec![PathBuf::from("/root/a.txt")],
+            attrs: Default::default(),
+        };
+
+        assert!(overflow_rescan_events(&event, &roots, &overflows).is_none());
+        assert_eq!(overflows.load(Ordering::Relaxed), 0);
+    }
+
+    #[test]
+    fn test_watch_budget_saturates() {
+        let budget = WatchBudget::new(Some(2));
+        budget.add(3);
+        assert!(budget.warned.load(Ordering::Relaxed));
+        budget.remove(10);
+        assert_eq!(budget.count.load(Ordering::Relaxed), 0);
+    }
+
+    #[test]
+    fn test_watch_limit_hint_mentions_sysctl() {
+        let hint = watch_limit_hint(Some(8192));
+        assert!(hint.contains("fs.inotify.max_user_watches"));
+        assert!(hint.contains("8192"));
+    }
+
+    #[tokio::test]
+    async fn test_nested_directory_created_after_start_is_watched() {
+        let dir = tempfile::tempdir().unwrap();
+        let (mut watcher, mut rx) = FileWatcher::new(0).unwrap();
+        let _handle = watcher.watch(dir.path()).unwrap();
+
+        let nested = dir.path().join("x").join("y").join("z");
+        std::fs::create_dir_all(&nested).unwrap();
+        let file = nested.join("deep.txt");
+        std::fs::write(&file, b"hello").unwrap();
+
+        let found = tokio::time::timeout(Duration::from_secs(5), async {
+            while let Some(event) = rx.recv().await {
+                if event.path() == file {
+                    return true;
+                }
+            }
+            false
+        })
+        .await
+        .unwrap_or(false);
+
+        assert!(found, "file in new nested directory was not detected");
+        assert!(watcher.watched_directories() >= 4);
+    }
+
     #[test]
     fn test_map_event_no_paths() {
         let event = notify::Event {
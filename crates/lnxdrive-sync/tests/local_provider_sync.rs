@@ -0,0 +1,2753 @@
+//! End-to-end sync between two local directories
+//!
+//! Two engines, each with its own sync root and state database, sync
+//! against the same "cloud" directory through [`LocalFolderProvider`].
+//! This exercises the full engine without Microsoft Graph.
+
+use std::{
+    collections::{HashMap, VecDeque},
+    fs,
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
+};
+
+use chrono::{DateTime, Utc};
+use lnxdrive_cache::{DatabasePool, SqliteStateRepository};
+use lnxdrive_core::{
+    config::Config,
+    domain::{
+        audit::AuditAction,
+        newtypes::{DeltaToken, Email, FileHash, RemoteId, RemotePath, SyncPath, UniqueId},
+        Account, Conflict, ItemState, ReasonCode,
+    },
+    ports::{
+        cloud_provider::{
+            AppendNotSupported, AuthFlow, CommitCheck, DeltaItem, DeltaPageSource, DeltaPages,
+            DeltaResponse, ICloudProvider, ItemPage, Tokens, UserInfo,
+        },
+        local_filesystem::{FileSystemState, ILocalFileSystem, WatchHandle},
+        IItemObserver, INotificationService, IStateRepository, Notification, TransferControl,
+    },
+};
+use lnxdrive_sync::{
+    engine::{SyncEngine, QUARANTINE_DIR, RECOVERED_DIR},
+    filesystem::LocalFileSystemAdapter,
+    local_provider::LocalFolderProvider,
+    plan::{SkipReason, SyncOperation, SyncSide},
+    selective::FolderSelection,
+};
+use tempfile::TempDir;
+
+// ============================================================================
+// Test helpers
+// ============================================================================
+
+/// Real filesystem adapter that counts how often file contents are hashed
+#[derive(Default)]
+struct CountingFs {
+    inner: LocalFileSystemAdapter,
+    hashes: AtomicUsize,
+}
+
+#[async_trait::async_trait]
+impl ILocalFileSystem for CountingFs {
+    async fn read_file(&self, path: &SyncPath) -> anyhow::Result<Vec<u8>> {
+        self.inner.read_file(path).await
+    }
+
+    async fn write_file(&self, path: &SyncPath, data: &[u8]) -> anyhow::Result<()> {
+        self.inner.write_file(path, data).await
+    }
+
+    async fn write_file_with_mtime(
+        &self,
+        path: &SyncPath,
+        data: &[u8],
+        modified: DateTime<Utc>,
+    ) -> anyhow::Result<()> {
+        self.inner.write_file_with_mtime(path, data, modified).await
+    }
+
+    async fn delete_file(&self, path: &SyncPath) -> anyhow::Result<()> {
+        self.inner.delete_file(path).await
+    }
+
+    async fn rename(&self, from: &SyncPath, to: &SyncPath) -> anyhow::Result<()> {
+        self.inner.rename(from, to).await
+    }
+
+    async fn copy_file(&self, from: &SyncPath, to: &SyncPath) -> anyhow::Result<()> {
+        self.inner.copy_file(from, to).await
+    }
+
+    async fn get_state(&self, path: &SyncPath) -> anyhow::Result<FileSystemState> {
+        self.inner.get_state(path).await
+    }
+
+    async fn compute_hash(&self, path: &SyncPath) -> anyhow::Result<FileHash> {
+        self.hashes.fetch_add(1, Ordering::SeqCst);
+        self.inner.compute_hash(path).await
+    }
+
+    fn invalidate_hash_cache(&self) {
+        self.inner.invalidate_hash_cache();
+    }
+
+    async fn create_directory(&self, path: &SyncPath) -> anyhow::Result<()> {
+        self.inner.create_directory(path).await
+    }
+
+    async fn watch(&self, path: &SyncPath) -> anyhow::Result<WatchHandle> {
+        self.inner.watch(path).await
+    }
+}
+
+/// Local folder provider that can delete or create a cloud file right
+/// after answering a delta query, as if another client changed it mid-sync
+struct RacingProvider {
+    inner: LocalFolderProvider,
+    delete_after_delta: Mutex<Option<PathBuf>>,
+    create_after_delta: Mutex<Option<(PathBuf, Vec<u8>)>>,
+    /// Leaves out the content hashes, as OneDrive does for some files
+    without_hashes: AtomicBool,
+}
+
+impl RacingProvider {
+    fn new(cloud: &Path) -> Self {
+        Self {
+            inner: LocalFolderProvider::new(cloud).unwrap(),
+            delete_after_delta: Mutex::new(None),
+            create_after_delta: Mutex::new(None),
+            without_hashes: AtomicBool::new(false),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl ICloudProvider for RacingProvider {
+    async fn authenticate(&self, auth_flow: &AuthFlow) -> anyhow::Result<Tokens> {
+        self.inner.authenticate(auth_flow).await
+    }
+
+    async fn refresh_tokens(&self, refresh_token: &str) -> anyhow::Result<Tokens> {
+        self.inner.refresh_tokens(refresh_token).await
+    }
+
+    async fn get_delta(&self, token: Option<&DeltaToken>) -> anyhow::Result<DeltaResponse> {
+        let mut delta = self.inner.get_delta(token).await?;
+        if self.without_hashes.load(Ordering::SeqCst) {
+            for item in &mut delta.items {
+                item.hash = None;
+            }
+        }
+        if let Some(path) = self.delete_after_delta.lock().unwrap().take() {
+            fs::remove_file(path).unwrap();
+        }
+        if let Some((path, content)) = self.create_after_delta.lock().unwrap().take() {
+            fs::write(path, content).unwrap();
+        }
+        Ok(delta)
+    }
+
+    async fn download_file(&self, remote_id: &RemoteId) -> anyhow::Result<Vec<u8>> {
+        self.inner.download_file(remote_id).await
+    }
+
+    async fn upload_file(
+        &self,
+        parent_path: &RemotePath,
+        name: &str,
+        data: &[u8],
+    ) -> anyhow::Result<DeltaItem> {
+        self.inner.upload_file(parent_path, name, data).await
+    }
+
+    async fn upload_file_session(
+        &self,
+        parent_path: &RemotePath,
+        name: &str,
+        data: &[u8],
+        progress: Option<Box<dyn Fn(u64, u64) + Send>>,
+    ) -> anyhow::Result<DeltaItem> {
+        self.inner
+            .upload_file_session(parent_path, name, data, progress)
+            .await
+    }
+
+    async fn create_folder(
+        &self,
+        parent_path: &RemotePath,
+        name: &str,
+    ) -> anyhow::Result<DeltaItem> {
+        self.inner.create_folder(parent_path, name).await
+    }
+
+    async fn get_metadata(&self, remote_id: &RemoteId) -> anyhow::Result<DeltaItem> {
+        self.inner.get_metadata(remote_id).await
+    }
+
+    async fn get_user_info(&self) -> anyhow::Result<UserInfo> {
+        self.inner.get_user_info().await
+    }
+
+    async fn list_children(
+        &self,
+        path: &RemotePath,
+        continuation: Option<&str>,
+    ) -> anyhow::Result<ItemPage> {
+        self.inner.list_children(path, continuation).await
+    }
+
+    async fn delete_item(&self, remote_id: &RemoteId) -> anyhow::Result<()> {
+        self.inner.delete_item(remote_id).await
+    }
+}
+
+/// Local folder provider whose item IDs survive renames, as on OneDrive
+///
+/// A path renamed through [`StableIdProvider::rename`] keeps the ID it had
+/// before, so the delta reports it as an update under a new path instead
+/// of a delete plus a create.
+struct StableIdProvider {
+    inner: LocalFolderProvider,
+    /// Path-derived ID of the current path -> ID the item was created with
+    aliases: Mutex<HashMap<String, String>>,
+    downloads: AtomicUsize,
+}
+
+impl StableIdProvider {
+    fn new(cloud: &Path) -> Self {
+        Self {
+            inner: LocalFolderProvider::new(cloud).unwrap(),
+            aliases: Mutex::new(HashMap::new()),
+            downloads: AtomicUsize::new(0),
+        }
+    }
+
+    /// Renames `from` to `to` in the cloud, including everything below it
+    fn rename(&self, from: &str, to: &str) {
+        let root = self.inner.root();
+        fs::rename(root.join(from), root.join(to)).unwrap();
+
+        let mut relatives = vec![String::new()];
+        let mut pending = vec![root.join(to)];
+        while let Some(dir) = pending.pop() {
+            let Ok(entries) = fs::read_dir(&dir) else {
+                continue;
+            };
+            for entry in entries {
+                let path = entry.unwrap().path();
+                let relative = path.strip_prefix(root.join(to)).unwrap();
+                relatives.push(format!("/{}", relative.display()));
+                pending.push(path);
+            }
+        }
+
+        let mut aliases = self.aliases.lock().unwrap();
+        for relative in relatives {
+            let old = LocalFolderProvider::remote_id_for(&format!("/{from}{relative}"));
+            let stable = aliases.remove(&old).unwrap_or(old);
+            let new = LocalFolderProvider::remote_id_for(&format!("/{to}{relative}"));
+            aliases.insert(new, stable);
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl ICloudProvider for StableIdProvider {
+    async fn authenticate(&self, auth_flow: &AuthFlow) -> anyhow::Result<Tokens> {
+        self.inner.authenticate(auth_flow).await
+    }
+
+    async fn refresh_tokens(&self, refresh_token: &str) -> anyhow::Result<Tokens> {
+        self.inner.refresh_tokens(refresh_token).await
+    }
+
+    async fn get_delta(&self, token: Option<&DeltaToken>) -> anyhow::Result<DeltaResponse> {
+        let mut delta = self.inner.get_delta(token).await?;
+        let aliases = self.aliases.lock().unwrap();
+        delta
+            .items
+            .retain(|item| !(item.is_deleted && aliases.values().any(|id| *id == item.id)));
+        for item in &mut delta.items {
+            if let Some(stable) = aliases.get(&item.id) {
+                item.id = stable.clone();
+            }
+        }
+        Ok(delta)
+    }
+
+    async fn download_file(&self, remote_id: &RemoteId) -> anyhow::Result<Vec<u8>> {
+        self.downloads.fetch_add(1, Ordering::SeqCst);
+        let current = self
+            .aliases
+            .lock()
+            .unwrap()
+            .iter()
+            .find(|(_, stable)| stable.as_str() == remote_id.as_str())
+            .map(|(current, _)| RemoteId::new(current.clone()).unwrap());
+        self.inner
+            .download_file(current.as_ref().unwrap_or(remote_id))
+            .await
+    }
+
+    async fn upload_file(
+        &self,
+        parent_path: &RemotePath,
+        name: &str,
+        data: &[u8],
+    ) -> anyhow::Result<DeltaItem> {
+        self.inner.upload_file(parent_path, name, data).await
+    }
+
+    async fn upload_file_session(
+        &self,
+        parent_path: &RemotePath,
+        name: &str,
+        data: &[u8],
+        progress: Option<Box<dyn Fn(u64, u64) + Send>>,
+    ) -> anyhow::Result<DeltaItem> {
+        self.inner
+            .upload_file_session(parent_path, name, data, progress)
+            .await
+    }
+
+    async fn create_folder(
+        &self,
+        parent_path: &RemotePath,
+        name: &str,
+    ) -> anyhow::Result<DeltaItem> {
+        self.inner.create_folder(parent_path, name).await
+    }
+
+    async fn get_metadata(&self, remote_id: &RemoteId) -> anyhow::Result<DeltaItem> {
+        self.inner.get_metadata(remote_id).await
+    }
+
+    async fn get_user_info(&self) -> anyhow::Result<UserInfo> {
+        self.inner.get_user_info().await
+    }
+
+    async fn delete_item(&self, remote_id: &RemoteId) -> anyhow::Result<()> {
+        self.inner.delete_item(remote_id).await
+    }
+}
+
+/// Local folder provider that hands out its delta one item per page and
+/// can fail one page, as if the connection dropped mid-enumeration
+struct PagedProvider {
+    inner: LocalFolderProvider,
+    /// Index of the page to fail on the next query
+    fail_page: Mutex<Option<usize>>,
+    /// Pages handed out so far
+    pages_read: Arc<AtomicUsize>,
+    downloads: AtomicUsize,
+    /// Most pages handed out but not downloaded yet when a download starts
+    max_in_flight: AtomicUsize,
+    /// Pages of the last query, before any failure was injected
+    last_pages: Mutex<Vec<DeltaResponse>>,
+    /// nextLinks the delta was resumed from
+    resumed_from: Mutex<Vec<String>>,
+}
+
+impl PagedProvider {
+    fn new(cloud: &Path) -> Self {
+        Self {
+            inner: LocalFolderProvider::new(cloud).unwrap(),
+            fail_page: Mutex::new(None),
+            pages_read: Arc::new(AtomicUsize::new(0)),
+            downloads: AtomicUsize::new(0),
+            max_in_flight: AtomicUsize::new(0),
+            last_pages: Mutex::new(Vec::new()),
+            resumed_from: Mutex::new(Vec::new()),
+        }
+    }
+}
+
+/// Pages prepared up front, counting the ones handed out
+struct QueuedPages(VecDeque<anyhow::Result<DeltaResponse>>, Arc<AtomicUsize>);
+
+#[async_trait::async_trait]
+impl DeltaPageSource for QueuedPages {
+    async fn next_page(&mut self) -> Option<anyhow::Result<DeltaResponse>> {
+        let page = self.0.pop_front()?;
+        self.1.fetch_add(1, Ordering::SeqCst);
+        Some(page)
+    }
+}
+
+#[async_trait::async_trait]
+impl ICloudProvider for PagedProvider {
+    async fn authenticate(&self, auth_flow: &AuthFlow) -> anyhow::Result<Tokens> {
+        self.inner.authenticate(auth_flow).await
+    }
+
+    async fn refresh_tokens(&self, refresh_token: &str) -> anyhow::Result<Tokens> {
+        self.inner.refresh_tokens(refresh_token).await
+    }
+
+    async fn get_delta(&self, token: Option<&DeltaToken>) -> anyhow::Result<DeltaResponse> {
+        self.inner.get_delta(token).await
+    }
+
+    async fn get_delta_pages(&self, token: Option<&DeltaToken>) -> anyhow::Result<DeltaPages> {
+        let delta = self.inner.get_delta(token).await?;
+        let count = delta.items.len();
+        let mut pages: VecDeque<anyhow::Result<DeltaResponse>> = delta
+            .items
+            .into_iter()
+            .enumerate()
+            .map(|(index, item)| {
+                let last = index + 1 == count;
+                Ok(DeltaResponse {
+                    items: vec![item],
+                    next_link: (!last).then(|| format!("page-{}", index + 1)),
+                    delta_link: if last { delta.delta_link.clone() } else { None },
+                })
+            })
+            .collect();
+        *self.last_pages.lock().unwrap() = pages
+            .iter()
+            .map(|page| page.as_ref().unwrap().clone())
+            .collect();
+        if let Some(fail) = self.fail_page.lock().unwrap().take() {
+            pages.truncate(fail);
+            pages.push_back(Err(anyhow::anyhow!("connection reset")));
+        }
+        match pages.pop_front() {
+            Some(first) => {
+                self.pages_read.fetch_add(1, Ordering::SeqCst);
+                let rest = QueuedPages(pages, Arc::clone(&self.pages_read));
+                Ok(DeltaPages::new(first?, Box::new(rest)))
+            }
+            None => Ok(DeltaPages::single(DeltaResponse {
+                items: Vec::new(),
+                next_link: None,
+                delta_link: delta.delta_link,
+            })),
+        }
+    }
+
+    async fn resume_delta_pages(&self, next_link: &str) -> anyhow::Result<DeltaPages> {
+        self.resumed_from
+            .lock()
+            .unwrap()
+            .push(next_link.to_string());
+        let index: usize = next_link.strip_prefix("page-").unwrap().parse()?;
+        let mut pages: VecDeque<anyhow::Result<DeltaResponse>> = self.last_pages.lock().unwrap()
+            [index..]
+            .iter()
+            .cloned()
+            .map(Ok)
+            .collect();
+        let first = pages.pop_front().unwrap()?;
+        self.pages_read.fetch_add(1, Ordering::SeqCst);
+        let rest = QueuedPages(pages, Arc::clone(&self.pages_read));
+        Ok(DeltaPages::new(first, Box::new(rest)))
+    }
+
+    async fn download_file(&self, remote_id: &RemoteId) -> anyhow::Result<Vec<u8>> {
+        let done = self.downloads.fetch_add(1, Ordering::SeqCst);
+        let in_flight = self.pages_read.load(Ordering::SeqCst) - done;
+        self.max_in_flight.fetch_max(in_flight, Ordering::SeqCst);
+        self.inner.download_file(remote_id).await
+    }
+
+    async fn upload_file(
+        &self,
+        parent_path: &RemotePath,
+        name: &str,
+        data: &[u8],
+    ) -> anyhow::Result<DeltaItem> {
+        self.inner.upload_file(parent_path, name, data).await
+    }
+
+    async fn upload_file_session(
+        &self,
+        parent_path: &RemotePath,
+        name: &str,
+        data: &[u8],
+        progress: Option<Box<dyn Fn(u64, u64) + Send>>,
+    ) -> anyhow::Result<DeltaItem> {
+        self.inner
+            .upload_file_session(parent_path, name, data, progress)
+            .await
+    }
+
+    async fn create_folder(
+        &self,
+        parent_path: &RemotePath,
+        name: &str,
+    ) -> anyhow::Result<DeltaItem> {
+        self.inner.create_folder(parent_path, name).await
+    }
+
+    async fn get_metadata(&self, remote_id: &RemoteId) -> anyhow::Result<DeltaItem> {
+        self.inner.get_metadata(remote_id).await
+    }
+
+    async fn get_user_info(&self) -> anyhow::Result<UserInfo> {
+        self.inner.get_user_info().await
+    }
+
+    async fn delete_item(&self, remote_id: &RemoteId) -> anyhow::Result<()> {
+        self.inner.delete_item(remote_id).await
+    }
+}
+
+/// Local folder provider whose upload sessions are resumable and slow
+///
+/// Sessions send [`ChunkedProvider::CHUNK`] bytes at a time. A session
+/// dropped midway keeps the offset of its last accepted chunk, and the
+/// next session for the same name continues from there, like a Graph
+/// upload session with a saved checkpoint.
+struct ChunkedProvider {
+    inner: LocalFolderProvider,
+    /// Name -> bytes accepted by the unfinished session
+    committed: Mutex<HashMap<String, usize>>,
+    /// Offset each session started from, in call order
+    starts: Mutex<Vec<usize>>,
+}
+
+impl ChunkedProvider {
+    const CHUNK: usize = 4;
+    const CHUNK_DELAY: std::time::Duration = std::time::Duration::from_millis(50);
+
+    fn new(cloud: &Path) -> Self {
+        Self {
+            inner: LocalFolderProvider::new(cloud).unwrap(),
+            committed: Mutex::new(HashMap::new()),
+            starts: Mutex::new(Vec::new()),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl ICloudProvider for ChunkedProvider {
+    async fn authenticate(&self, auth_flow: &AuthFlow) -> anyhow::Result<Tokens> {
+        self.inner.authenticate(auth_flow).await
+    }
+
+    async fn refresh_tokens(&self, refresh_token: &str) -> anyhow::Result<Tokens> {
+        self.inner.refresh_tokens(refresh_token).await
+    }
+
+    async fn get_delta(&self, token: Option<&DeltaToken>) -> anyhow::Result<DeltaResponse> {
+        self.inner.get_delta(token).await
+    }
+
+    async fn download_file(&self, remote_id: &RemoteId) -> anyhow::Result<Vec<u8>> {
+        self.inner.download_file(remote_id).await
+    }
+
+    async fn upload_file(
+        &self,
+        parent_path: &RemotePath,
+        name: &str,
+        data: &[u8],
+    ) -> anyhow::Result<DeltaItem> {
+        self.inner.upload_file(parent_path, name, data).await
+    }
+
+    async fn upload_file_session(
+        &self,
+        parent_path: &RemotePath,
+        name: &str,
+        data: &[u8],
+        _progress: Option<Box<dyn Fn(u64, u64) + Send>>,
+    ) -> anyhow::Result<DeltaItem> {
+        let mut offset = self
+            .committed
+            .lock()
+            .unwrap()
+            .get(name)
+            .copied()
+            .unwrap_or(0);
+        self.starts.lock().unwrap().push(offset);
+        while offset < data.len() {
+            tokio::time::sleep(Self::CHUNK_DELAY).await;
+            offset = (offset + Self::CHUNK).min(data.len());
+            self.committed
+                .lock()
+                .unwrap()
+                .insert(name.to_string(), offset);
+        }
+        self.committed.lock().unwrap().remove(name);
+        self.inner.upload_file(parent_path, name, data).await
+    }
+
+    async fn create_folder(
+        &self,
+        parent_path: &RemotePath,
+        name: &str,
+    ) -> anyhow::Result<DeltaItem> {
+        self.inner.create_folder(parent_path, name).await
+    }
+
+    async fn get_metadata(&self, remote_id: &RemoteId) -> anyhow::Result<DeltaItem> {
+        self.inner.get_metadata(remote_id).await
+    }
+
+    async fn get_user_info(&self) -> anyhow::Result<UserInfo> {
+        self.inner.get_user_info().await
+    }
+
+    async fn delete_item(&self, remote_id: &RemoteId) -> anyhow::Result<()> {
+        self.inner.delete_item(remote_id).await
+    }
+}
+
+/// Local folder provider that rewrites a local file while its first
+/// upload is under way, as if an application kept saving it
+///
+/// Upload sessions rewrite the file before running their commit check,
+/// so the check is expected to refuse the commit.
+struct MutatingProvider {
+    inner: LocalFolderProvider,
+    /// Local file and the content written to it during the next upload
+    rewrite: Mutex<Option<(PathBuf, Vec<u8>)>>,
+    uploads: AtomicUsize,
+}
+
+impl MutatingProvider {
+    fn new(cloud: &Path) -> Self {
+        Self {
+            inner: LocalFolderProvider::new(cloud).unwrap(),
+            rewrite: Mutex::new(None),
+            uploads: AtomicUsize::new(0),
+        }
+    }
+
+    fn rewrite_during_upload(&self, path: PathBuf, content: &[u8]) {
+        *self.rewrite.lock().unwrap() = Some((path, content.to_vec()));
+    }
+
+    fn apply_rewrite(&self) {
+        self.uploads.fetch_add(1, Ordering::SeqCst);
+        if let Some((path, content)) = self.rewrite.lock().unwrap().take() {
+            fs::write(path, content).unwrap();
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl ICloudProvider for MutatingProvider {
+    async fn authenticate(&self, auth_flow: &AuthFlow) -> anyhow::Result<Tokens> {
+        self.inner.authenticate(auth_flow).await
+    }
+
+    async fn refresh_tokens(&self, refresh_token: &str) -> anyhow::Result<Tokens> {
+        self.inner.refresh_tokens(refresh_token).await
+    }
+
+    async fn get_delta(&self, token: Option<&DeltaToken>) -> anyhow::Result<DeltaResponse> {
+        self.inner.get_delta(token).await
+    }
+
+    async fn download_file(&self, remote_id: &RemoteId) -> anyhow::Result<Vec<u8>> {
+        self.inner.download_file(remote_id).await
+    }
+
+    async fn upload_file(
+        &self,
+        parent_path: &RemotePath,
+        name: &str,
+        data: &[u8],
+    ) -> anyhow::Result<DeltaItem> {
+        self.apply_rewrite();
+        self.inner.upload_file(parent_path, name, data).await
+    }
+
+    async fn upload_file_session(
+        &self,
+        parent_path: &RemotePath,
+        name: &str,
+        data: &[u8],
+        _progress: Option<Box<dyn Fn(u64, u64) + Send>>,
+    ) -> anyhow::Result<DeltaItem> {
+        self.inner.upload_file(parent_path, name, data).await
+    }
+
+    async fn upload_file_session_checked(
+        &self,
+        parent_path: &RemotePath,
+        name: &str,
+        data: &[u8],
+        progress: Option<Box<dyn Fn(u64, u64) + Send>>,
+        commit_check: CommitCheck,
+    ) -> anyhow::Result<DeltaItem> {
+        self.apply_rewrite();
+        commit_check()?;
+        self.upload_file_session(parent_path, name, data, progress)
+            .await
+    }
+
+    async fn create_folder(
+        &self,
+        parent_path: &RemotePath,
+        name: &str,
+    ) -> anyhow::Result<DeltaItem> {
+        self.inner.create_folder(parent_path, name).await
+    }
+
+    async fn get_metadata(&self, remote_id: &RemoteId) -> anyhow::Result<DeltaItem> {
+        self.inner.get_metadata(remote_id).await
+    }
+
+    async fn get_user_info(&self) -> anyhow::Result<UserInfo> {
+        self.inner.get_user_info().await
+    }
+
+    async fn delete_item(&self, remote_id: &RemoteId) -> anyhow::Result<()> {
+        self.inner.delete_item(remote_id).await
+    }
+}
+
+/// Local folder provider that counts the bytes sent by uploads and appends
+struct UploadCountingProvider {
+    inner: LocalFolderProvider,
+    /// Whether appends are passed on or refused like on OneDrive
+    can_append: bool,
+    uploaded: AtomicUsize,
+    appended: AtomicUsize,
+    append_calls: AtomicUsize,
+}
+
+impl UploadCountingProvider {
+    fn new(cloud: &Path, can_append: bool) -> Self {
+        Self {
+            inner: LocalFolderProvider::new(cloud).unwrap(),
+            can_append,
+            uploaded: AtomicUsize::new(0),
+            appended: AtomicUsize::new(0),
+            append_calls: AtomicUsize::new(0),
+        }
+    }
+
+    /// Returns and resets the (uploaded, appended) byte counts
+    fn take_counts(&self) -> (usize, usize) {
+        (
+            self.uploaded.swap(0, Ordering::SeqCst),
+            self.appended.swap(0, Ordering::SeqCst),
+        )
+    }
+}
+
+#[async_trait::async_trait]
+impl ICloudProvider for UploadCountingProvider {
+    async fn authenticate(&self, auth_flow: &AuthFlow) -> anyhow::Result<Tokens> {
+        self.inner.authenticate(auth_flow).await
+    }
+
+    async fn refresh_tokens(&self, refresh_token: &str) -> anyhow::Result<Tokens> {
+        self.inner.refresh_tokens(refresh_token).await
+    }
+
+    async fn get_delta(&self, token: Option<&DeltaToken>) -> anyhow::Result<DeltaResponse> {
+        self.inner.get_delta(token).await
+    }
+
+    async fn download_file(&self, remote_id: &RemoteId) -> anyhow::Result<Vec<u8>> {
+        self.inner.download_file(remote_id).await
+    }
+
+    async fn upload_file(
+        &self,
+        parent_path: &RemotePath,
+        name: &str,
+        data: &[u8],
+    ) -> anyhow::Result<DeltaItem> {
+        self.uploaded.fetch_add(data.len(), Ordering::SeqCst);
+        self.inner.upload_file(parent_path, name, data).await
+    }
+
+    async fn upload_file_session(
+        &self,
+        parent_path: &RemotePath,
+        name: &str,
+        data: &[u8],
+        progress: Option<Box<dyn Fn(u64, u64) + Send>>,
+    ) -> anyhow::Result<DeltaItem> {
+        self.uploaded.fetch_add(data.len(), Ordering::SeqCst);
+        self.inner
+            .upload_file_session(parent_path, name, data, progress)
+            .await
+    }
+
+    fn supports_append(&self) -> bool {
+        self.can_append
+    }
+
+    async fn append_file(
+        &self,
+        remote_id: &RemoteId,
+        offset: u64,
+        data: &[u8],
+    ) -> anyhow::Result<DeltaItem> {
+        self.append_calls.fetch_add(1, Ordering::SeqCst);
+        if !self.can_append {
+            return Err(AppendNotSupported.into());
+        }
+        self.appended.fetch_add(data.len(), Ordering::SeqCst);
+        self.inner.append_file(remote_id, offset, data).await
+    }
+
+    async fn create_folder(
+        &self,
+        parent_path: &RemotePath,
+        name: &str,
+    ) -> anyhow::Result<DeltaItem> {
+        self.inner.create_folder(parent_path, name).await
+    }
+
+    async fn get_metadata(&self, remote_id: &RemoteId) -> anyhow::Result<DeltaItem> {
+        self.inner.get_metadata(remote_id).await
+    }
+
+    async fn get_user_info(&self) -> anyhow::Result<UserInfo> {
+        self.inner.get_user_info().await
+    }
+
+    async fn delete_item(&self, remote_id: &RemoteId) -> anyhow::Result<()> {
+        self.inner.delete_item(remote_id).await
+    }
+}
+
+/// Local folder provider whose first download waits until it is released
+///
+/// Lets a test act while a transfer is in flight.
+struct BlockingDownloadProvider {
+    inner: LocalFolderProvider,
+    blocked: AtomicBool,
+    started: tokio::sync::Notify,
+    release: tokio::sync::Notify,
+    downloads: AtomicUsize,
+}
+
+impl BlockingDownloadProvider {
+    fn new(cloud: &Path) -> Self {
+        Self {
+            inner: LocalFolderProvider::new(cloud).unwrap(),
+            blocked: AtomicBool::new(false),
+            started: tokio::sync::Notify::new(),
+            release: tokio::sync::Notify::new(),
+            downloads: AtomicUsize::new(0),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl ICloudProvider for BlockingDownloadProvider {
+    async fn authenticate(&self, auth_flow: &AuthFlow) -> anyhow::Result<Tokens> {
+        self.inner.authenticate(auth_flow).await
+    }
+
+    async fn refresh_tokens(&self, refresh_token: &str) -> anyhow::Result<Tokens> {
+        self.inner.refresh_tokens(refresh_token).await
+    }
+
+    async fn get_delta(&self, token: Option<&DeltaToken>) -> anyhow::Result<DeltaResponse> {
+        self.inner.get_delta(token).await
+    }
+
+    async fn download_file(&self, remote_id: &RemoteId) -> anyhow::Result<Vec<u8>> {
+        if !self.blocked.swap(true, Ordering::SeqCst) {
+            self.started.notify_one();
+            self.release.notified().await;
+        }
+        self.downloads.fetch_add(1, Ordering::SeqCst);
+        self.inner.download_file(remote_id).await
+    }
+
+    async fn upload_file(
+        &self,
+        parent_path: &RemotePath,
+        name: &str,
+        data: &[u8],
+    ) -> anyhow::Result<DeltaItem> {
+        self.inner.upload_file(parent_path, name, data).await
+    }
+
+    async fn upload_file_session(
+        &self,
+        parent_path: &RemotePath,
+        name: &str,
+        data: &[u8],
+        progress: Option<Box<dyn Fn(u64, u64) + Send>>,
+    ) -> anyhow::Result<DeltaItem> {
+        self.inner
+            .upload_file_session(parent_path, name, data, progress)
+            .await
+    }
+
+    async fn create_folder(
+        &self,
+        parent_path: &RemotePath,
+        name: &str,
+    ) -> anyhow::Result<DeltaItem> {
+        self.inner.create_folder(parent_path, name).await
+    }
+
+    async fn get_metadata(&self, remote_id: &RemoteId) -> anyhow::Result<DeltaItem> {
+        self.inner.get_metadata(remote_id).await
+    }
+
+    async fn get_user_info(&self) -> anyhow::Result<UserInfo> {
+        self.inner.get_user_info().await
+    }
+
+    async fn delete_item(&self, remote_id: &RemoteId) -> anyhow::Result<()> {
+        self.inner.delete_item(remote_id).await
+    }
+}
+
+/// Item observer that records every move and removal it is told about
+#[derive(Default)]
+struct RecordingObserver {
+    moves: Mutex<Vec<(UniqueId, Option<UniqueId>, String)>>,
+    removed: Mutex<Vec<UniqueId>>,
+}
+
+impl IItemObserver for RecordingObserver {
+    fn item_moved(&self, item_id: &UniqueId, new_parent: Option<&UniqueId>, new_name: &str) {
+        self.moves
+            .lock()
+            .unwrap()
+            .push((*item_id, new_parent.copied(), new_name.to_string()));
+    }
+
+    fn item_removed(&self, item_id: &UniqueId) {
+        self.removed.lock().unwrap().push(*item_id);
+    }
+}
+
+/// Notifier that records every notification it is asked to show
+#[derive(Default)]
+struct RecordingNotifier {
+    sent: Mutex<Vec<Notification>>,
+}
+
+#[async_trait::async_trait]
+impl INotificationService for RecordingNotifier {
+    async fn notify(&self, notification: &Notification) -> anyhow::Result<()> {
+        self.sent.lock().unwrap().push(notification.clone());
+        Ok(())
+    }
+
+    async fn show_progress(&self, _id: &str, _title: &str, _percent: f64) -> anyhow::Result<()> {
+        Ok(())
+    }
+
+    async fn clear_progress(&self, _id: &str) -> anyhow::Result<()> {
+        Ok(())
+    }
+}
+
+/// One "machine": a sync root, its state database and an engine
+struct Replica {
+    root: TempDir,
+    fs: Arc<CountingFs>,
+    repo: Arc<SqliteStateRepository>,
+    engine: SyncEngine,
+}
+
+impl Replica {
+    async fn new(cloud: &Path) -> Self {
+        let provider = Arc::new(LocalFolderProvider::new(cloud).unwrap());
+        Self::build(provider, &Config::default()).await
+    }
+
+    async fn build(provider: Arc<dyn ICloudProvider>, config: &Config) -> Self {
+        let root = TempDir::new().unwrap();
+        let pool = DatabasePool::in_memory().await.unwrap();
+        let repo = Arc::new(SqliteStateRepository::new(pool.pool().clone()));
+
+        let email = Email::new("local@localhost.localdomain".to_string()).unwrap();
+        let sync_root = SyncPath::new(root.path().to_path_buf()).unwrap();
+        let account = Account::new(email, "Local", "local-root", sync_root);
+        repo.save_account(&account).await.unwrap();
+
+        let fs = Arc::new(CountingFs::default());
+        let engine = SyncEngine::new(
+            provider,
+            Arc::clone(&repo) as Arc<dyn IStateRepository + Send + Sync>,
+            Arc::clone(&fs) as Arc<dyn ILocalFileSystem + Send + Sync>,
+            config,
+        );
+        Self {
+            root,
+            fs,
+            repo,
+            engine,
+        }
+    }
+
+    fn take_hash_count(&self) -> usize {
+        self.fs.hashes.swap(0, Ordering::SeqCst)
+    }
+
+    fn path(&self, relative: &str) -> std::path::PathBuf {
+        self.root.path().join(relative)
+    }
+
+    async fn sync(&self) {
+        let result = self.engine.sync().await.unwrap();
+        assert!(result.errors.is_empty(), "sync errors: {:?}", result.errors);
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[tokio::test]
+async fn test_sync_between_two_local_directories() {
+    let cloud = TempDir::new().unwrap();
+    let a = Replica::new(cloud.path()).await;
+    let b = Replica::new(cloud.path()).await;
+
+    // A creates files, B receives them
+    fs::create_dir_all(a.path("docs")).unwrap();
+    fs::write(a.path("docs/report.txt"), b"first draft").unwrap();
+    fs::write(a.path("notes.md"), b"# notes").unwrap();
+    a.sync().await;
+    assert_eq!(
+        fs::read(cloud.path().join("docs/report.txt")).unwrap(),
+        b"first draft"
+    );
+
+    b.sync().await;
+    assert_eq!(fs::read(b.path("docs/report.txt")).unwrap(), b"first draft");
+    assert_eq!(fs::read(b.path("notes.md")).unwrap(), b"# notes");
+
+    // B edits a file, A receives the edit
+    fs::write(b.path("docs/report.txt"), b"second draft, longer").unwrap();
+    b.sync().await;
+    a.sync().await;
+    assert_eq!(
+        fs::read(a.path("docs/report.txt")).unwrap(),
+        b"second draft, longer"
+    );
+
+    // A deletes a file, B loses it too
+    fs::remove_file(a.path("notes.md")).unwrap();
+    a.sync().await;
+    assert!(!cloud.path().join("notes.md").exists());
+
+    b.sync().await;
+    assert!(!b.path("notes.md").exists());
+    assert!(b.path("docs/report.txt").exists());
+}
+
+#[tokio::test]
+async fn test_empty_files_and_folders_round_trip() {
+    let cloud = TempDir::new().unwrap();
+    let a = Replica::new(cloud.path()).await;
+    let b = Replica::new(cloud.path()).await;
+
+    fs::create_dir_all(a.path("empty/nested")).unwrap();
+    fs::write(a.path("empty.txt"), b"").unwrap();
+    a.sync().await;
+    assert!(cloud.path().join("empty/nested").is_dir());
+    assert_eq!(fs::read(cloud.path().join("empty.txt")).unwrap(), b"");
+    let folder = a
+        .repo
+        .get_item_by_path(&SyncPath::new(a.path("empty/nested")).unwrap())
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(folder.remote_id().is_some());
+
+    b.sync().await;
+    assert!(b.path("empty/nested").is_dir());
+    assert_eq!(fs::read(b.path("empty.txt")).unwrap(), b"");
+
+    // Created on the other side, they come back the same way
+    fs::create_dir_all(b.path("from-b/inner")).unwrap();
+    fs::write(b.path("from-b/blank"), b"").unwrap();
+    b.sync().await;
+    a.sync().await;
+    assert!(a.path("from-b/inner").is_dir());
+    assert_eq!(fs::read(a.path("from-b/blank")).unwrap(), b"");
+
+    // Nothing is uploaded again
+    a.sync().await;
+    assert_eq!(fs::read_dir(a.path("empty/nested")).unwrap().count(), 0);
+}
+
+#[tokio::test]
+async fn test_modification_times_survive_a_sync_cycle() {
+    use std::time::{Duration, SystemTime};
+
+    let cloud = TempDir::new().unwrap();
+    let a = Replica::new(cloud.path()).await;
+    let b = Replica::new(cloud.path()).await;
+    let mtime = |path: PathBuf| fs::metadata(path).unwrap().modified().unwrap();
+
+    // A uploads a file last edited long ago
+    let edited = SystemTime::UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+    fs::write(a.path("old.txt"), b"old content").unwrap();
+    fs::File::options()
+        .write(true)
+        .open(a.path("old.txt"))
+        .unwrap()
+        .set_modified(edited)
+        .unwrap();
+    a.sync().await;
+    assert_eq!(mtime(cloud.path().join("old.txt")), edited);
+
+    // B downloads it with the same timestamp
+    b.sync().await;
+    assert_eq!(fs::read(b.path("old.txt")).unwrap(), b"old content");
+    assert_eq!(mtime(b.path("old.txt")), edited);
+
+    // An edit on B travels back to A with its timestamp too
+    let edited = edited + Duration::from_secs(3600);
+    fs::write(b.path("old.txt"), b"edited on B").unwrap();
+    fs::File::options()
+        .write(true)
+        .open(b.path("old.txt"))
+        .unwrap()
+        .set_modified(edited)
+        .unwrap();
+    b.sync().await;
+    a.sync().await;
+    assert_eq!(fs::read(a.path("old.txt")).unwrap(), b"edited on B");
+    assert_eq!(mtime(a.path("old.txt")), edited);
+}
+
+#[tokio::test]
+async fn test_unchanged_trees_sync_nothing() {
+    let cloud = TempDir::new().unwrap();
+    fs::write(cloud.path().join("existing.txt"), b"already in the cloud").unwrap();
+    let a = Replica::new(cloud.path()).await;
+
+    a.sync().await;
+    assert_eq!(
+        fs::read(a.path("existing.txt")).unwrap(),
+        b"already in the cloud"
+    );
+
+    let second = a.engine.sync().await.unwrap();
+    assert_eq!(second.files_downloaded, 0);
+    assert_eq!(second.files_uploaded, 0);
+    assert_eq!(second.files_deleted, 0);
+}
+
+#[tokio::test]
+async fn test_unchanged_files_are_not_hashed() {
+    let cloud = TempDir::new().unwrap();
+    for name in ["one.txt", "two.txt", "three.txt"] {
+        fs::write(cloud.path().join(name), name.as_bytes()).unwrap();
+    }
+    let a = Replica::new(cloud.path()).await;
+    a.sync().await;
+    a.take_hash_count();
+
+    a.sync().await;
+    assert_eq!(a.take_hash_count(), 0);
+
+    // Same size, new mtime: hashed once, then skipped again
+    fs::write(a.path("two.txt"), b"TWO.txt").unwrap();
+    let result = a.engine.sync().await.unwrap();
+    assert_eq!(result.files_uploaded, 1);
+    assert_eq!(fs::read(cloud.path().join("two.txt")).unwrap(), b"TWO.txt");
+    a.take_hash_count();
+
+    a.sync().await;
+    assert_eq!(a.take_hash_count(), 0);
+}
+
+/// Rewrites `path` with same-size content and restores its mtime
+fn edit_keeping_size_and_mtime(path: PathBuf, content: &[u8]) {
+    let modified = fs::metadata(&path).unwrap().modified().unwrap();
+    assert_eq!(fs::metadata(&path).unwrap().len(), content.len() as u64);
+    fs::write(&path, content).unwrap();
+    fs::File::options()
+        .write(true)
+        .open(&path)
+        .unwrap()
+        .set_modified(modified)
+        .unwrap();
+}
+
+#[tokio::test]
+async fn test_quick_detection_misses_edits_keeping_size_and_mtime() {
+    let cloud = TempDir::new().unwrap();
+    fs::write(cloud.path().join("big.bin"), b"original").unwrap();
+    let a = Replica::new(cloud.path()).await;
+    a.sync().await;
+
+    edit_keeping_size_and_mtime(a.path("big.bin"), b"ORIGINAL");
+    a.take_hash_count();
+    let result = a.engine.sync().await.unwrap();
+
+    // The documented trade-off: nothing is hashed, so the edit stays local
+    assert_eq!(a.take_hash_count(), 0);
+    assert_eq!(result.files_uploaded, 0);
+    assert_eq!(fs::read(cloud.path().join("big.bin")).unwrap(), b"original");
+}
+
+#[tokio::test]
+async fn test_full_hash_detection_hashes_every_file() {
+    let cloud = TempDir::new().unwrap();
+    fs::write(cloud.path().join("big.bin"), b"original").unwrap();
+    fs::write(cloud.path().join("other.txt"), b"untouched").unwrap();
+    let mut config = Config::default();
+    config.conflicts.change_detection = "full_hash".to_string();
+    let a = Replica::build(
+        Arc::new(LocalFolderProvider::new(cloud.path()).unwrap()),
+        &config,
+    )
+    .await;
+    a.sync().await;
+
+    edit_keeping_size_and_mtime(a.path("big.bin"), b"ORIGINAL");
+    a.take_hash_count();
+    let result = a.engine.sync().await.unwrap();
+
+    assert!(a.take_hash_count() >= 2);
+    assert_eq!(result.files_uploaded, 1);
+    assert_eq!(fs::read(cloud.path().join("big.bin")).unwrap(), b"ORIGINAL");
+}
+
+#[tokio::test]
+async fn test_reconcile_finds_changes_made_while_stopped() {
+    let cloud = TempDir::new().unwrap();
+    for name in ["edited.txt", "removed.txt", "untouched.txt"] {
+        fs::write(cloud.path().join(name), name.as_bytes()).unwrap();
+    }
+    let a = Replica::new(cloud.path()).await;
+    a.sync().await;
+
+    fs::write(a.path("edited.txt"), b"edited while stopped").unwrap();
+    fs::remove_file(a.path("removed.txt")).unwrap();
+    a.take_hash_count();
+
+    let report = a.engine.reconcile().await.unwrap();
+
+    assert_eq!(report.files_checked, 3);
+    assert_eq!(report.modified, 1);
+    assert_eq!(report.deleted, 1);
+    assert_eq!(report.created, 0);
+    // Only the edited file is hashed
+    assert_eq!(report.files_hashed, 1);
+    assert_eq!(a.take_hash_count(), 1);
+    assert_eq!(
+        item_state(&a, "edited.txt").await,
+        Some(ItemState::Modified)
+    );
+    assert_eq!(
+        item_state(&a, "untouched.txt").await,
+        Some(ItemState::Hydrated)
+    );
+
+    // The next sync applies what reconciliation found
+    let result = a.engine.sync().await.unwrap();
+    assert!(result.errors.is_empty(), "sync errors: {:?}", result.errors);
+    assert_eq!(
+        fs::read(cloud.path().join("edited.txt")).unwrap(),
+        b"edited while stopped"
+    );
+    assert!(!cloud.path().join("removed.txt").exists());
+}
+
+#[tokio::test]
+async fn test_reconcile_counts_only_new_files_the_scan_would_upload() {
+    let cloud = TempDir::new().unwrap();
+    let mut config = Config::default();
+    config.sync.exclude_hidden = true;
+    let a = Replica::build(
+        Arc::new(LocalFolderProvider::new(cloud.path()).unwrap()),
+        &config,
+    )
+    .await;
+    a.sync().await;
+
+    fs::create_dir_all(a.path("new/nested")).unwrap();
+    fs::write(a.path("new/nested/one.txt"), b"one").unwrap();
+    fs::write(a.path("two.txt"), b"two").unwrap();
+    for skipped in [RECOVERED_DIR, QUARANTINE_DIR, ".hidden"] {
+        fs::create_dir_all(a.path(skipped).join("sub")).unwrap();
+        fs::write(a.path(skipped).join("sub/file.txt"), b"skipped").unwrap();
+    }
+    fs::write(a.path(".hidden-file"), b"skipped").unwrap();
+
+    let report = a.engine.reconcile().await.unwrap();
+
+    // Folders are not counted, skipped folders are not entered
+    assert_eq!(report.created, 2);
+}
+
+#[tokio::test]
+async fn test_delete_on_both_sides_is_not_an_error() {
+    let cloud = TempDir::new().unwrap();
+    fs::write(cloud.path().join("shared.txt"), b"both delete me").unwrap();
+    let provider = Arc::new(RacingProvider::new(cloud.path()));
+    let a = Replica::build(
+        Arc::clone(&provider) as Arc<dyn ICloudProvider>,
+        &Config::default(),
+    )
+    .await;
+    a.sync().await;
+
+    // Deleted locally, and remotely after the delta was fetched: the
+    // remote delete then finds nothing to delete
+    fs::remove_file(a.path("shared.txt")).unwrap();
+    *provider.delete_after_delta.lock().unwrap() = Some(cloud.path().join("shared.txt"));
+    let result = a.engine.sync().await.unwrap();
+    assert!(result.errors.is_empty(), "sync errors: {:?}", result.errors);
+    assert_eq!(result.files_deleted, 1);
+
+    // The record is gone, so nothing is deleted again
+    let second = a.engine.sync().await.unwrap();
+    assert!(second.errors.is_empty(), "sync errors: {:?}", second.errors);
+    assert_eq!(second.files_deleted, 0);
+}
+
+#[tokio::test]
+async fn test_lost_database_adopts_identical_local_files() {
+    let cloud = TempDir::new().unwrap();
+    fs::write(cloud.path().join("same.txt"), b"synced before").unwrap();
+    fs::write(cloud.path().join("changed.txt"), b"edited elsewhere").unwrap();
+
+    // The files are still on disk, but the database that tracked them is new
+    let b = Replica::new(cloud.path()).await;
+    fs::write(b.path("same.txt"), b"synced before").unwrap();
+    fs::write(b.path("changed.txt"), b"edited here").unwrap();
+
+    let result = b.engine.sync().await.unwrap();
+
+    assert!(result.errors.is_empty(), "sync errors: {:?}", result.errors);
+    assert_eq!(result.files_downloaded, 0);
+    assert_eq!(result.files_uploaded, 0);
+    assert_eq!(result.conflicts_detected, 1);
+    let same = b
+        .repo
+        .get_item_by_path(&SyncPath::new(b.path("same.txt")).unwrap())
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(same.remote_id().is_some());
+    assert_eq!(same.state(), &ItemState::Hydrated);
+
+    // Different content is a conflict: neither side is overwritten
+    assert_eq!(fs::read(b.path("changed.txt")).unwrap(), b"edited here");
+    assert_eq!(
+        fs::read(cloud.path().join("changed.txt")).unwrap(),
+        b"edited elsewhere"
+    );
+    let conflicts = b.repo.get_unresolved_conflicts().await.unwrap();
+    assert_eq!(conflicts.len(), 1);
+}
+
+#[tokio::test]
+async fn test_lost_database_collision_keep_both() {
+    let cloud = TempDir::new().unwrap();
+    fs::write(cloud.path().join("notes.txt"), b"remote notes").unwrap();
+    let mut config = Config::default();
+    config.conflicts.default_strategy = "keep_both".to_string();
+    let b = Replica::build(
+        Arc::new(LocalFolderProvider::new(cloud.path()).unwrap()),
+        &config,
+    )
+    .await;
+    fs::write(b.path("notes.txt"), b"local notes").unwrap();
+
+    let result = b.engine.sync().await.unwrap();
+
+    assert!(result.errors.is_empty(), "sync errors: {:?}", result.errors);
+    assert_eq!(result.conflicts_auto_resolved, 1);
+    assert_eq!(fs::read(b.path("notes.txt")).unwrap(), b"remote notes");
+    let copies: Vec<_> = fs::read_dir(b.root.path())
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path != &b.path("notes.txt"))
+        .collect();
+    assert_eq!(copies.len(), 1, "{copies:?}");
+    assert_eq!(fs::read(&copies[0]).unwrap(), b"local notes");
+}
+
+#[tokio::test]
+async fn test_lost_database_without_remote_hashes_compares_size_and_mtime() {
+    let cloud = TempDir::new().unwrap();
+    fs::write(cloud.path().join("same.txt"), b"synced before").unwrap();
+    fs::write(cloud.path().join("changed.txt"), b"edited elsewhere").unwrap();
+    let provider = Arc::new(RacingProvider::new(cloud.path()));
+    provider.without_hashes.store(true, Ordering::SeqCst);
+
+    let b = Replica::build(
+        Arc::clone(&provider) as Arc<dyn ICloudProvider>,
+        &Config::default(),
+    )
+    .await;
+    fs::write(b.path("same.txt"), b"synced before").unwrap();
+    let synced_mtime = fs::metadata(cloud.path().join("same.txt"))
+        .unwrap()
+        .modified()
+        .unwrap();
+    fs::File::options()
+        .write(true)
+        .open(b.path("same.txt"))
+        .unwrap()
+        .set_modified(synced_mtime)
+        .unwrap();
+    fs::write(b.path("changed.txt"), b"edited here").unwrap();
+
+    let result = b.engine.sync().await.unwrap();
+
+    assert!(result.errors.is_empty(), "sync errors: {:?}", result.errors);
+    assert_eq!(result.files_downloaded, 0);
+    assert_eq!(result.files_uploaded, 0);
+    assert_eq!(result.conflicts_detected, 1);
+    let conflicts = b.repo.get_unresolved_conflicts().await.unwrap();
+    assert_eq!(conflicts.len(), 1);
+    assert!(conflicts[0].remote_version().hash().is_none());
+    assert_eq!(fs::read(b.path("changed.txt")).unwrap(), b"edited here");
+}
+
+#[tokio::test]
+async fn test_upload_adopts_remote_file_with_same_content() {
+    let cloud = TempDir::new().unwrap();
+    let provider = Arc::new(RacingProvider::new(cloud.path()));
+    let a = Replica::build(
+        Arc::clone(&provider) as Arc<dyn ICloudProvider>,
+        &Config::default(),
+    )
+    .await;
+    a.sync().await;
+
+    // Another client uploads the same file after the delta was fetched
+    fs::write(a.path("report.txt"), b"final report").unwrap();
+    *provider.create_after_delta.lock().unwrap() =
+        Some((cloud.path().join("report.txt"), b"final report".to_vec()));
+    let result = a.engine.sync().await.unwrap();
+
+    assert!(result.errors.is_empty(), "sync errors: {:?}", result.errors);
+    assert_eq!(result.files_uploaded, 0);
+    assert_eq!(result.conflicts_detected, 0);
+    let item = a
+        .repo
+        .get_item_by_path(&SyncPath::new(a.path("report.txt")).unwrap())
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(item.remote_id().is_some());
+
+    // Adopted as synced: nothing to do next time
+    let second = a.engine.sync().await.unwrap();
+    assert_eq!(second.files_uploaded, 0);
+    assert_eq!(second.files_downloaded, 0);
+}
+
+#[tokio::test]
+async fn test_upload_does_not_overwrite_different_remote_file() {
+    let cloud = TempDir::new().unwrap();
+    let provider = Arc::new(RacingProvider::new(cloud.path()));
+    let a = Replica::build(
+        Arc::clone(&provider) as Arc<dyn ICloudProvider>,
+        &Config::default(),
+    )
+    .await;
+    a.sync().await;
+
+    fs::write(a.path("report.txt"), b"my report").unwrap();
+    *provider.create_after_delta.lock().unwrap() =
+        Some((cloud.path().join("report.txt"), b"their report".to_vec()));
+    let result = a.engine.sync().await.unwrap();
+
+    assert!(result.errors.is_empty(), "sync errors: {:?}", result.errors);
+    assert_eq!(result.files_uploaded, 0);
+    assert_eq!(result.conflicts_detected, 1);
+    assert_eq!(
+        fs::read(cloud.path().join("report.txt")).unwrap(),
+        b"their report"
+    );
+    assert_eq!(fs::read(a.path("report.txt")).unwrap(), b"my report");
+    assert_eq!(a.repo.get_unresolved_conflicts().await.unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn test_upload_collides_with_remote_name_in_other_case() {
+    let cloud = TempDir::new().unwrap();
+    let provider = Arc::new(RacingProvider::new(cloud.path()));
+    let a = Replica::build(
+        Arc::clone(&provider) as Arc<dyn ICloudProvider>,
+        &Config::default(),
+    )
+    .await;
+    a.sync().await;
+
+    // OneDrive names are case-insensitive: both would be the same file
+    fs::write(a.path("report.txt"), b"my report").unwrap();
+    *provider.create_after_delta.lock().unwrap() =
+        Some((cloud.path().join("Report.TXT"), b"their report".to_vec()));
+    let result = a.engine.sync().await.unwrap();
+
+    assert!(result.errors.is_empty(), "sync errors: {:?}", result.errors);
+    assert_eq!(result.files_uploaded, 0);
+    assert_eq!(result.conflicts_detected, 1);
+    assert!(!cloud.path().join("report.txt").exists());
+}
+
+#[tokio::test]
+async fn test_remote_delete_of_unchanged_file_deletes_it() {
+    let cloud = TempDir::new().unwrap();
+    fs::write(cloud.path().join("old.txt"), b"nobody edits this").unwrap();
+    let a = Replica::new(cloud.path()).await;
+    let b = Replica::new(cloud.path()).await;
+    a.sync().await;
+    b.sync().await;
+
+    fs::remove_file(a.path("old.txt")).unwrap();
+    a.sync().await;
+    b.sync().await;
+
+    assert!(!b.path("old.txt").exists());
+}
+
+/// A and B share `draft.txt`; B edits it while A deletes it
+async fn delete_edit_conflict(config: &Config) -> (TempDir, Replica) {
+    let cloud = TempDir::new().unwrap();
+    fs::write(cloud.path().join("draft.txt"), b"v1").unwrap();
+    let a = Replica::new(cloud.path()).await;
+    let b = Replica::build(
+        Arc::new(LocalFolderProvider::new(cloud.path()).unwrap()),
+        config,
+    )
+    .await;
+    a.sync().await;
+    b.sync().await;
+
+    fs::write(b.path("draft.txt"), b"v2, edited on B").unwrap();
+    fs::remove_file(a.path("draft.txt")).unwrap();
+    a.sync().await;
+    assert!(!cloud.path().join("draft.txt").exists());
+
+    (cloud, b)
+}
+
+#[tokio::test]
+async fn test_remote_delete_of_edited_file_is_a_conflict() {
+    let (cloud, b) = delete_edit_conflict(&Config::default()).await;
+
+    let result = b.engine.sync().await.unwrap();
+
+    assert!(result.errors.is_empty(), "sync errors: {:?}", result.errors);
+    assert_eq!(result.conflicts_detected, 1);
+    assert_eq!(result.conflicts_auto_resolved, 0);
+    assert_eq!(fs::read(b.path("draft.txt")).unwrap(), b"v2, edited on B");
+    assert!(!cloud.path().join("draft.txt").exists());
+}
+
+#[tokio::test]
+async fn test_delete_edit_conflict_keep_local_reuploads() {
+    let mut config = Config::default();
+    config.conflicts.default_strategy = "keep_local".to_string();
+    let (cloud, b) = delete_edit_conflict(&config).await;
+
+    let result = b.engine.sync().await.unwrap();
+
+    assert!(result.errors.is_empty(), "sync errors: {:?}", result.errors);
+    assert_eq!(result.conflicts_auto_resolved, 1);
+    assert_eq!(result.files_uploaded, 1);
+    assert_eq!(
+        fs::read(cloud.path().join("draft.txt")).unwrap(),
+        b"v2, edited on B"
+    );
+}
+
+#[tokio::test]
+async fn test_delete_edit_conflict_keep_remote_deletes_local() {
+    let mut config = Config::default();
+    config.conflicts.default_strategy = "keep_remote".to_string();
+    let (cloud, b) = delete_edit_conflict(&config).await;
+
+    let result = b.engine.sync().await.unwrap();
+
+    assert!(result.errors.is_empty(), "sync errors: {:?}", result.errors);
+    assert_eq!(result.conflicts_auto_resolved, 1);
+    assert!(!b.path("draft.txt").exists());
+    assert!(!cloud.path().join("draft.txt").exists());
+}
+
+/// A uploads the folder `report`; B has an unsynced file at that path
+async fn type_conflict(config: &Config) -> (TempDir, Replica, Replica) {
+    let cloud = TempDir::new().unwrap();
+    let a = Replica::new(cloud.path()).await;
+    let b = Replica::build(
+        Arc::new(LocalFolderProvider::new(cloud.path()).unwrap()),
+        config,
+    )
+    .await;
+    a.sync().await;
+    b.sync().await;
+
+    fs::create_dir_all(a.path("report")).unwrap();
+    fs::write(a.path("report/summary.txt"), b"from A").unwrap();
+    fs::write(a.path("notes.txt"), b"notes").unwrap();
+    a.sync().await;
+    fs::write(b.path("report"), b"file on B").unwrap();
+
+    (cloud, a, b)
+}
+
+#[tokio::test]
+async fn test_local_file_shadowing_remote_folder_is_a_conflict() {
+    let (cloud, a, b) = type_conflict(&Config::default()).await;
+
+    let result = b.engine.sync().await.unwrap();
+
+    assert!(result.errors.is_empty(), "sync errors: {:?}", result.errors);
+    assert_eq!(result.conflicts_detected, 1);
+    assert_eq!(fs::read(b.path("report")).unwrap(), b"file on B");
+    assert!(cloud.path().join("report").is_dir());
+    // The rest of the delta is applied
+    assert_eq!(fs::read(b.path("notes.txt")).unwrap(), b"notes");
+    assert_eq!(item_state(&b, "report").await, Some(ItemState::Conflicted));
+    let conflicts = b.repo.get_unresolved_conflicts().await.unwrap();
+    assert_eq!(conflicts.len(), 1);
+
+    // The token moved on: later changes arrive, the conflict is not
+    // detected again
+    fs::write(a.path("later.txt"), b"later").unwrap();
+    a.sync().await;
+    let result = b.engine.sync().await.unwrap();
+    assert!(result.errors.is_empty(), "sync errors: {:?}", result.errors);
+    assert_eq!(result.conflicts_detected, 0);
+    assert_eq!(fs::read(b.path("later.txt")).unwrap(), b"later");
+    assert_eq!(b.repo.get_unresolved_conflicts().await.unwrap().len(), 1);
+    assert_eq!(
+        fs::read(cloud.path().join("report/summary.txt")).unwrap(),
+        b"from A"
+    );
+}
+
+#[tokio::test]
+async fn test_local_file_shadowing_remote_folder_keep_both() {
+    let mut config = Config::default();
+    config.conflicts.default_strategy = "keep_both".to_string();
+    let (cloud, _a, b) = type_conflict(&config).await;
+
+    let result = b.engine.sync().await.unwrap();
+
+    assert!(result.errors.is_empty(), "sync errors: {:?}", result.errors);
+    assert_eq!(result.conflicts_auto_resolved, 1);
+    assert_eq!(fs::read(b.path("report/summary.txt")).unwrap(), b"from A");
+    let aside: Vec<_> = fs::read_dir(b.path(""))
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.is_file() && fs::read(path).unwrap() == b"file on B")
+        .collect();
+    assert_eq!(aside.len(), 1, "local file moved aside");
+    assert!(b.repo.get_unresolved_conflicts().await.unwrap().is_empty());
+    // The moved file is uploaded under its new name
+    let name = aside[0].file_name().unwrap();
+    assert_eq!(fs::read(cloud.path().join(name)).unwrap(), b"file on B");
+}
+
+#[tokio::test]
+async fn test_remote_folder_delete_recovers_modified_files() {
+    let cloud = TempDir::new().unwrap();
+    fs::create_dir_all(cloud.path().join("docs/nested")).unwrap();
+    fs::write(cloud.path().join("docs/a.txt"), b"v1").unwrap();
+    fs::write(cloud.path().join("docs/nested/b.txt"), b"untouched").unwrap();
+    let mut b = Replica::new(cloud.path()).await;
+    let notifier = Arc::new(RecordingNotifier::default());
+    b.engine
+        .set_notifier(Arc::clone(&notifier) as Arc<dyn INotificationService>);
+    b.sync().await;
+
+    fs::write(b.path("docs/a.txt"), b"v2, edited on B").unwrap();
+    fs::remove_dir_all(cloud.path().join("docs")).unwrap();
+    let result = b.engine.sync().await.unwrap();
+
+    assert!(result.errors.is_empty(), "sync errors: {:?}", result.errors);
+    assert_eq!(result.files_recovered, 1);
+    assert!(!b.path("docs").exists());
+    let recovered = b.path(RECOVERED_DIR);
+    assert_eq!(
+        fs::read(recovered.join("docs/a.txt")).unwrap(),
+        b"v2, edited on B"
+    );
+    assert!(!recovered.join("docs/nested/b.txt").exists());
+    assert_eq!(notifier.sent.lock().unwrap().len(), 1);
+
+    // The recovery folder stays local and the folder is not recreated
+    let second = b.engine.sync().await.unwrap();
+    assert!(second.errors.is_empty(), "sync errors: {:?}", second.errors);
+    assert_eq!(second.files_uploaded, 0);
+    assert!(!cloud.path().join(RECOVERED_DIR).exists());
+    assert!(!cloud.path().join("docs").exists());
+}
+
+#[tokio::test]
+async fn test_upload_blocked_until_quota_frees_up() {
+    let cloud = TempDir::new().unwrap();
+    fs::write(cloud.path().join("existing.bin"), vec![0u8; 600]).unwrap();
+    let provider = LocalFolderProvider::new(cloud.path())
+        .unwrap()
+        .with_quota_limit(1000);
+    let mut b = Replica::build(Arc::new(provider), &Config::default()).await;
+    let notifier = Arc::new(RecordingNotifier::default());
+    b.engine
+        .set_notifier(Arc::clone(&notifier) as Arc<dyn INotificationService>);
+    b.sync().await;
+
+    // 800 bytes do not fit in the 400 left
+    fs::write(b.path("large.bin"), vec![1u8; 800]).unwrap();
+    let result = b.engine.sync().await.unwrap();
+    assert!(result.errors.is_empty(), "sync errors: {:?}", result.errors);
+    assert_eq!(result.uploads_blocked, 1);
+    assert_eq!(result.files_uploaded, 0);
+    assert!(!cloud.path().join("large.bin").exists());
+    let sent = notifier.sent.lock().unwrap().clone();
+    assert_eq!(sent.len(), 1);
+    assert_eq!(sent[0].category, "quota");
+
+    // Still blocked, but not reported again
+    let result = b.engine.sync().await.unwrap();
+    assert_eq!(result.uploads_blocked, 1);
+    assert_eq!(notifier.sent.lock().unwrap().len(), 1);
+
+    // Freeing space lets the upload through
+    fs::remove_file(b.path("existing.bin")).unwrap();
+    b.sync().await;
+    b.sync().await;
+    assert_eq!(fs::read(cloud.path().join("large.bin")).unwrap().len(), 800);
+    assert!(!cloud.path().join("existing.bin").exists());
+}
+
+#[tokio::test]
+async fn test_upload_of_locked_file_is_deferred_until_unlocked() {
+    let cloud = TempDir::new().unwrap();
+    let b = Replica::new(cloud.path()).await;
+    b.sync().await;
+
+    // LibreOffice has the document open
+    fs::write(b.path("report.odt"), b"half written").unwrap();
+    fs::write(b.path(".~lock.report.odt#"), b"user,host").unwrap();
+    let result = b.engine.sync().await.unwrap();
+    assert!(result.errors.is_empty(), "sync errors: {:?}", result.errors);
+    assert_eq!(result.uploads_deferred, 1);
+    assert_eq!(result.files_uploaded, 0);
+    assert!(!cloud.path().join("report.odt").exists());
+    assert!(!cloud.path().join(".~lock.report.odt#").exists());
+
+    let audit = b
+        .repo
+        .get_audit_since(chrono::DateTime::<chrono::Utc>::MIN_UTC, 100)
+        .await
+        .unwrap();
+    let deferred = audit
+        .iter()
+        .find(|e| e.action() == &AuditAction::FileUpload)
+        .expect("deferral audited");
+    assert_eq!(deferred.result().code(), Some(ReasonCode::Locked.as_str()));
+
+    // Closing the document removes the lock file
+    fs::write(b.path("report.odt"), b"final").unwrap();
+    fs::remove_file(b.path(".~lock.report.odt#")).unwrap();
+    let result = b.engine.sync().await.unwrap();
+    assert_eq!(result.uploads_deferred, 0);
+    assert_eq!(result.files_uploaded, 1);
+    assert_eq!(fs::read(cloud.path().join("report.odt")).unwrap(), b"final");
+}
+
+#[tokio::test]
+async fn test_hidden_folders_are_skipped_when_excluded() {
+    let cloud = TempDir::new().unwrap();
+    let mut config = Config::default();
+    config.sync.exclude_hidden = true;
+    let provider = Arc::new(LocalFolderProvider::new(cloud.path()).unwrap());
+    let a = Replica::build(provider, &config).await;
+
+    fs::create_dir_all(a.path("project/.git/objects")).unwrap();
+    fs::write(a.path("project/.git/HEAD"), b"ref: refs/heads/main").unwrap();
+    fs::write(a.path("project/.git/objects/ab"), b"blob").unwrap();
+    fs::write(a.path("project/main.rs"), b"fn main() {}").unwrap();
+    fs::write(a.path("project/Thumbs.db"), b"junk").unwrap();
+    let result = a.engine.sync().await.unwrap();
+    assert!(result.errors.is_empty(), "sync errors: {:?}", result.errors);
+    assert!(cloud.path().join("project/main.rs").exists());
+    assert!(!cloud.path().join("project/.git").exists());
+    assert!(!cloud.path().join("project/Thumbs.db").exists());
+
+    // A .git folder already in the cloud is not downloaded either
+    fs::create_dir_all(cloud.path().join("other/.git")).unwrap();
+    fs::write(cloud.path().join("other/.git/HEAD"), b"ref").unwrap();
+    fs::write(cloud.path().join("other/notes.txt"), b"notes").unwrap();
+    let provider = Arc::new(LocalFolderProvider::new(cloud.path()).unwrap());
+    let b = Replica::build(provider, &config).await;
+    b.sync().await;
+    assert!(b.path("other/notes.txt").exists());
+    assert!(!b.path("other/.git").exists());
+    assert!(b.path("project/main.rs").exists());
+}
+
+#[tokio::test]
+async fn test_unicode_names_are_uploaded_in_nfc() {
+    const NFC: &str = "Caf\u{e9}";
+    const NFD: &str = "Cafe\u{301}";
+
+    let cloud = TempDir::new().unwrap();
+    let a = Replica::new(cloud.path()).await;
+    let b = Replica::new(cloud.path()).await;
+
+    // Written by a macOS application
+    fs::create_dir(a.path(NFD)).unwrap();
+    fs::write(a.path(&format!("{NFD}/menu.txt")), b"espresso").unwrap();
+    a.sync().await;
+    assert!(cloud.path().join(NFC).join("menu.txt").exists());
+    assert!(!cloud.path().join(NFD).exists());
+
+    // The NFC name coming back does not create a second folder
+    a.sync().await;
+    assert!(!a.path(NFC).exists());
+    fs::write(cloud.path().join(NFC).join("hours.txt"), b"8-18").unwrap();
+    a.sync().await;
+    assert_eq!(
+        fs::read(a.path(&format!("{NFD}/hours.txt"))).unwrap(),
+        b"8-18"
+    );
+    assert!(!a.path(NFC).exists());
+
+    b.sync().await;
+    assert!(b.path(&format!("{NFC}/menu.txt")).exists());
+
+    // The same name in both forms cannot be uploaded
+    fs::write(b.path("te\u{301}.txt"), b"nfd").unwrap();
+    fs::write(b.path("t\u{e9}.txt"), b"nfc").unwrap();
+    let result = b.engine.sync().await.unwrap();
+    assert_eq!(result.errors.len(), 2, "sync errors: {:?}", result.errors);
+    assert!(!cloud.path().join("t\u{e9}.txt").exists());
+
+    // Once one is renamed, both are uploaded
+    fs::rename(b.path("te\u{301}.txt"), b.path("tea.txt")).unwrap();
+    b.sync().await;
+    assert_eq!(fs::read(cloud.path().join("t\u{e9}.txt")).unwrap(), b"nfc");
+    assert_eq!(fs::read(cloud.path().join("tea.txt")).unwrap(), b"nfd");
+}
+
+#[tokio::test]
+async fn test_unicode_names_are_kept_when_normalization_is_disabled() {
+    let cloud = TempDir::new().unwrap();
+    let mut config = Config::default();
+    config.sync.normalize_unicode = false;
+    let provider = Arc::new(LocalFolderProvider::new(cloud.path()).unwrap());
+    let a = Replica::build(provider, &config).await;
+
+    fs::write(a.path("cafe\u{301}.txt"), b"nfd").unwrap();
+    a.sync().await;
+    assert!(cloud.path().join("cafe\u{301}.txt").exists());
+    assert!(!cloud.path().join("caf\u{e9}.txt").exists());
+}
+
+#[tokio::test]
+async fn test_items_over_provider_limits_are_not_uploaded() {
+    let cloud = TempDir::new().unwrap();
+    let mut config = Config::default();
+    config.limits.max_file_size = 100;
+    config.limits.max_path_length = 30;
+    let provider = Arc::new(LocalFolderProvider::new(cloud.path()).unwrap());
+    let b = Replica::build(provider, &config).await;
+
+    fs::write(b.path("big.bin"), vec![0u8; 200]).unwrap();
+    let long_name = format!("{}.txt", "n".repeat(40));
+    fs::write(b.path(&long_name), b"short").unwrap();
+    fs::write(b.path("ok.txt"), b"fits").unwrap();
+    let result = b.engine.sync().await.unwrap();
+
+    assert!(result.errors.is_empty(), "sync errors: {:?}", result.errors);
+    assert_eq!(result.uploads_blocked, 2);
+    assert_eq!(result.files_uploaded, 1);
+    assert!(!cloud.path().join("big.bin").exists());
+    assert!(!cloud.path().join(&long_name).exists());
+
+    let reason_of = |name: &str| {
+        let path = SyncPath::new(b.path(name)).unwrap();
+        let repo = Arc::clone(&b.repo);
+        async move {
+            let item = repo.get_item_by_path(&path).await.unwrap().unwrap();
+            assert!(matches!(item.state(), ItemState::Error(_)));
+            item.error_info().and_then(|e| e.reason())
+        }
+    };
+    assert_eq!(reason_of("big.bin").await, Some(ReasonCode::FileTooLarge));
+    assert_eq!(reason_of(&long_name).await, Some(ReasonCode::PathTooLong));
+
+    // Unchanged items are not checked again
+    let result = b.engine.sync().await.unwrap();
+    assert!(result.errors.is_empty(), "sync errors: {:?}", result.errors);
+    assert_eq!(result.uploads_blocked, 0);
+
+    // Once the file fits, it is uploaded
+    fs::write(b.path("big.bin"), vec![0u8; 50]).unwrap();
+    b.sync().await;
+    assert_eq!(fs::read(cloud.path().join("big.bin")).unwrap().len(), 50);
+    let path = SyncPath::new(b.path("big.bin")).unwrap();
+    let item = b.repo.get_item_by_path(&path).await.unwrap().unwrap();
+    assert_eq!(item.state(), &ItemState::Hydrated);
+    assert!(item.remote_id().is_some());
+}
+
+#[tokio::test]
+async fn test_hardlinks_are_uploaded_once() {
+    use std::os::unix::fs::MetadataExt;
+
+    let cloud = TempDir::new().unwrap();
+    let a = Replica::new(cloud.path()).await;
+    let b = Replica::new(cloud.path()).await;
+
+    fs::write(a.path("data.bin"), vec![7u8; 300]).unwrap();
+    fs::hard_link(a.path("data.bin"), a.path("link.bin")).unwrap();
+    let result = a.engine.sync().await.unwrap();
+    assert!(result.errors.is_empty(), "sync errors: {:?}", result.errors);
+    assert_eq!(result.files_uploaded, 1);
+    assert_eq!(result.bytes_uploaded, 300);
+    assert!(cloud.path().join("data.bin").exists());
+    assert!(!cloud.path().join("link.bin").exists());
+
+    let link_path = SyncPath::new(a.path("link.bin")).unwrap();
+    let link = a.repo.get_item_by_path(&link_path).await.unwrap().unwrap();
+    assert_eq!(
+        link.metadata().hardlink_of(),
+        Some(&SyncPath::new(a.path("data.bin")).unwrap())
+    );
+
+    // Nothing left to upload
+    let result = a.engine.sync().await.unwrap();
+    assert_eq!(result.files_uploaded, 0);
+
+    // A remote edit of the primary keeps both paths linked
+    b.sync().await;
+    fs::write(b.path("data.bin"), b"edited elsewhere").unwrap();
+    b.sync().await;
+    a.sync().await;
+    assert_eq!(fs::read(a.path("link.bin")).unwrap(), b"edited elsewhere");
+    let primary_ino = fs::metadata(a.path("data.bin")).unwrap().ino();
+    assert_eq!(fs::metadata(a.path("link.bin")).unwrap().ino(), primary_ino);
+    let result = a.engine.sync().await.unwrap();
+    assert_eq!(result.files_uploaded, 0);
+
+    // Breaking the link uploads the path on its own
+    fs::remove_file(a.path("link.bin")).unwrap();
+    fs::write(a.path("link.bin"), b"edited elsewhere").unwrap();
+    a.sync().await;
+    assert_eq!(
+        fs::read(cloud.path().join("link.bin")).unwrap(),
+        b"edited elsewhere"
+    );
+    let link = a.repo.get_item_by_path(&link_path).await.unwrap().unwrap();
+    assert_eq!(link.metadata().hardlink_of(), None);
+    assert!(link.remote_id().is_some());
+}
+
+/// Sets up B with an edit of `draft.txt` that conflicts with a newer
+/// cloud version (or an older one, if `local_is_newer`)
+async fn edit_edit_conflict(local_is_newer: bool) -> (TempDir, Replica) {
+    use std::time::{Duration, SystemTime};
+
+    let cloud = TempDir::new().unwrap();
+    fs::write(cloud.path().join("draft.txt"), b"v1").unwrap();
+    let mut config = Config::default();
+    config.conflicts.default_strategy = "keep_newer".to_string();
+    let a = Replica::new(cloud.path()).await;
+    let b = Replica::build(
+        Arc::new(LocalFolderProvider::new(cloud.path()).unwrap()),
+        &config,
+    )
+    .await;
+    a.sync().await;
+    b.sync().await;
+
+    let set_mtime = |path: PathBuf, age_secs: u64| {
+        let file = fs::File::options().write(true).open(path).unwrap();
+        file.set_modified(SystemTime::now() - Duration::from_secs(age_secs))
+            .unwrap();
+    };
+    let (local_age, remote_age) = if local_is_newer {
+        (60, 3600)
+    } else {
+        (3600, 60)
+    };
+
+    fs::write(a.path("draft.txt"), b"edited on A").unwrap();
+    a.sync().await;
+    set_mtime(cloud.path().join("draft.txt"), remote_age);
+    fs::write(b.path("draft.txt"), b"edited on B").unwrap();
+    set_mtime(b.path("draft.txt"), local_age);
+
+    (cloud, b)
+}
+
+#[tokio::test]
+async fn test_stale_conflict_is_quarantined() {
+    let cloud = TempDir::new().unwrap();
+    fs::write(cloud.path().join("draft.txt"), b"v1").unwrap();
+    let mut config = Config::default();
+    config.conflicts.quarantine_after_days = 7;
+    let a = Replica::new(cloud.path()).await;
+    let b = Replica::build(
+        Arc::new(LocalFolderProvider::new(cloud.path()).unwrap()),
+        &config,
+    )
+    .await;
+    a.sync().await;
+    b.sync().await;
+    fs::write(a.path("draft.txt"), b"edited on A").unwrap();
+    a.sync().await;
+    fs::write(b.path("draft.txt"), b"edited on B").unwrap();
+
+    let result = b.engine.sync().await.unwrap();
+    assert_eq!(result.conflicts_detected, 1);
+    // A fresh conflict waits for the user
+    let result = b.engine.sync().await.unwrap();
+    assert_eq!(result.conflicts_quarantined, 0);
+    let conflicts = b.repo.get_unresolved_conflicts().await.unwrap();
+    assert_eq!(conflicts.len(), 1);
+
+    // Age the conflict past the threshold
+    let mut json = serde_json::to_value(&conflicts[0]).unwrap();
+    json["detected_at"] = serde_json::to_value(Utc::now() - chrono::Duration::days(8)).unwrap();
+    let aged: Conflict = serde_json::from_value(json).unwrap();
+    b.repo.save_conflict(&aged).await.unwrap();
+
+    let result = b.engine.sync().await.unwrap();
+
+    assert!(result.errors.is_empty(), "sync errors: {:?}", result.errors);
+    assert_eq!(result.conflicts_quarantined, 1);
+    assert_eq!(result.files_uploaded, 1);
+    assert!(b.repo.get_unresolved_conflicts().await.unwrap().is_empty());
+    let quarantine = b.path(QUARANTINE_DIR).join(aged.id().to_string());
+    assert_eq!(
+        fs::read(quarantine.join("local/draft.txt")).unwrap(),
+        b"edited on B"
+    );
+    assert_eq!(
+        fs::read(quarantine.join("remote/draft.txt")).unwrap(),
+        b"edited on A"
+    );
+    let manifest: serde_json::Value =
+        serde_json::from_slice(&fs::read(quarantine.join("manifest.json")).unwrap()).unwrap();
+    assert_eq!(manifest["path"], "draft.txt");
+    assert_eq!(manifest["remote"]["file"], "remote/draft.txt");
+    assert_eq!(
+        fs::read(cloud.path().join("draft.txt")).unwrap(),
+        b"edited on B"
+    );
+    assert!(!cloud.path().join(QUARANTINE_DIR).exists());
+
+    let audit = b.repo.get_audit_trail(aged.item_id()).await.unwrap();
+    assert!(audit.iter().any(|entry| {
+        *entry.action() == AuditAction::ConflictResolved
+            && entry.details()["quarantined_to"].is_string()
+    }));
+}
+
+#[tokio::test]
+async fn test_keep_newer_keeps_newer_local_edit() {
+    let (cloud, b) = edit_edit_conflict(true).await;
+
+    let result = b.engine.sync().await.unwrap();
+
+    assert!(result.errors.is_empty(), "sync errors: {:?}", result.errors);
+    assert_eq!(result.conflicts_auto_resolved, 1);
+    assert_eq!(result.files_uploaded, 1);
+    assert_eq!(fs::read(b.path("draft.txt")).unwrap(), b"edited on B");
+    assert_eq!(
+        fs::read(cloud.path().join("draft.txt")).unwrap(),
+        b"edited on B"
+    );
+}
+
+#[tokio::test]
+async fn test_keep_newer_keeps_newer_remote_edit() {
+    let (cloud, b) = edit_edit_conflict(false).await;
+
+    let result = b.engine.sync().await.unwrap();
+
+    assert!(result.errors.is_empty(), "sync errors: {:?}", result.errors);
+    assert_eq!(result.conflicts_auto_resolved, 1);
+    assert_eq!(result.files_uploaded, 0);
+    assert_eq!(fs::read(b.path("draft.txt")).unwrap(), b"edited on A");
+    assert_eq!(
+        fs::read(cloud.path().join("draft.txt")).unwrap(),
+        b"edited on A"
+    );
+
+    // The automatic resolution is audited with the strategy that chose it
+    let audit = b
+        .repo
+        .get_audit_since(chrono::DateTime::<chrono::Utc>::MIN_UTC, 100)
+        .await
+        .unwrap();
+    let resolved = audit
+        .iter()
+        .find(|e| e.action() == &AuditAction::ConflictResolved)
+        .unwrap();
+    assert_eq!(resolved.details()["strategy"], "keep_newer");
+    assert_eq!(resolved.details()["resolution"], "keep_remote");
+}
+
+#[tokio::test]
+async fn test_remote_rename_is_applied_in_place() {
+    let cloud = TempDir::new().unwrap();
+    fs::create_dir_all(cloud.path().join("docs")).unwrap();
+    fs::write(cloud.path().join("docs/report.txt"), b"report").unwrap();
+    fs::write(cloud.path().join("notes.txt"), b"notes").unwrap();
+    let provider = Arc::new(StableIdProvider::new(cloud.path()));
+    let mut a = Replica::build(Arc::clone(&provider) as _, &Config::default()).await;
+    let observer = Arc::new(RecordingObserver::default());
+    a.engine.set_item_observer(Arc::clone(&observer) as _);
+    a.sync().await;
+    assert_eq!(provider.downloads.swap(0, Ordering::SeqCst), 2);
+
+    let notes_path = SyncPath::new(a.path("notes.txt")).unwrap();
+    let mut notes = a.repo.get_item_by_path(&notes_path).await.unwrap().unwrap();
+    notes.pin().unwrap();
+    a.repo.save_item(&notes).await.unwrap();
+    let docs_path = SyncPath::new(a.path("docs")).unwrap();
+    let docs = a.repo.get_item_by_path(&docs_path).await.unwrap().unwrap();
+
+    // Move the file into the folder, then rename the folder
+    provider.rename("notes.txt", "docs/notes.txt");
+    provider.rename("docs", "archive");
+    let result = a.engine.sync().await.unwrap();
+
+    assert!(result.errors.is_empty(), "sync errors: {:?}", result.errors);
+    assert_eq!(provider.downloads.load(Ordering::SeqCst), 0);
+    assert_eq!(result.files_downloaded, 0);
+    assert_eq!(result.files_deleted, 0);
+    assert_eq!(result.files_uploaded, 0);
+    assert!(!a.path("notes.txt").exists());
+    assert!(!a.path("docs").exists());
+    assert_eq!(fs::read(a.path("archive/notes.txt")).unwrap(), b"notes");
+    assert_eq!(fs::read(a.path("archive/report.txt")).unwrap(), b"report");
+
+    // Same SyncItem, still pinned, now under the new path
+    let moved = a.repo.get_item(notes.id()).await.unwrap().unwrap();
+    assert_eq!(moved.local_path().as_path(), &a.path("archive/notes.txt"));
+    assert_eq!(moved.remote_path().as_str(), "/archive/notes.txt");
+    assert_eq!(*moved.state(), ItemState::Pinned);
+    let report_path = SyncPath::new(a.path("archive/report.txt")).unwrap();
+    let report = a
+        .repo
+        .get_item_by_path(&report_path)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(report.remote_path().as_str(), "/archive/report.txt");
+
+    let moves = observer.moves.lock().unwrap().clone();
+    assert!(moves.contains(&(*docs.id(), None, "archive".to_string())));
+    assert!(moves.contains(&(*notes.id(), Some(*docs.id()), "notes.txt".to_string())));
+
+    // Nothing left to do
+    let result = a.engine.sync().await.unwrap();
+    assert_eq!(result.files_downloaded + result.files_uploaded, 0);
+}
+
+#[tokio::test]
+async fn test_paged_delta_keeps_token_until_last_page() {
+    let cloud = TempDir::new().unwrap();
+    for name in ["a.txt", "b.txt", "c.txt", "d.txt"] {
+        fs::write(cloud.path().join(name), name.as_bytes()).unwrap();
+    }
+    let provider = Arc::new(PagedProvider::new(cloud.path()));
+    *provider.fail_page.lock().unwrap() = Some(2);
+    let a = Replica::build(Arc::clone(&provider) as _, &Config::default()).await;
+
+    // The plan fails before anything is applied, and the token is not advanced
+    assert!(a.engine.sync().await.is_err());
+    assert!(!a.path("a.txt").exists());
+    assert!(!a.path("d.txt").exists());
+    let account = a.repo.get_default_account().await.unwrap().unwrap();
+    assert!(account.delta_token().is_none());
+
+    // The retry starts over and completes
+    let result = a.engine.sync().await.unwrap();
+    assert!(result.errors.is_empty(), "sync errors: {:?}", result.errors);
+    for name in ["a.txt", "b.txt", "c.txt", "d.txt"] {
+        assert_eq!(fs::read(a.path(name)).unwrap(), name.as_bytes());
+    }
+    assert_eq!(result.files_downloaded, 4);
+    let account = a.repo.get_default_account().await.unwrap().unwrap();
+    assert!(account.delta_token().is_some());
+}
+
+#[tokio::test]
+async fn test_drain_finishes_transfer_in_flight_and_keeps_token() {
+    let cloud = TempDir::new().unwrap();
+    let provider = Arc::new(BlockingDownloadProvider::new(cloud.path()));
+    let a = Replica::build(Arc::clone(&provider) as _, &Config::default()).await;
+    a.sync().await;
+    let token = a
+        .repo
+        .get_default_account()
+        .await
+        .unwrap()
+        .unwrap()
+        .delta_token()
+        .cloned();
+    assert!(token.is_some());
+
+    for name in ["a.txt", "b.txt", "c.txt"] {
+        fs::write(cloud.path().join(name), name.as_bytes()).unwrap();
+    }
+    fs::write(a.path("local.txt"), b"not uploaded yet").unwrap();
+
+    // Shutdown arrives while the first download is in flight
+    let drain = async {
+        provider.started.notified().await;
+        a.engine.begin_drain();
+        provider.release.notify_one();
+    };
+    let (result, ()) = tokio::join!(a.engine.sync(), drain);
+    let result = result.unwrap();
+
+    // The in-flight download completes, nothing new is started
+    assert_eq!(provider.downloads.load(Ordering::SeqCst), 1);
+    assert_eq!(result.files_downloaded, 1);
+    let downloaded = ["a.txt", "b.txt", "c.txt"]
+        .into_iter()
+        .filter(|name| a.path(name).exists())
+        .count();
+    assert_eq!(downloaded, 1);
+    assert!(!cloud.path().join("local.txt").exists());
+
+    // The old token is kept, so the next start fetches the skipped items
+    let account = a.repo.get_default_account().await.unwrap().unwrap();
+    assert_eq!(account.delta_token().cloned(), token);
+
+    let restarted = SyncEngine::new(
+        Arc::clone(&provider) as _,
+        Arc::clone(&a.repo) as Arc<dyn IStateRepository + Send + Sync>,
+        Arc::clone(&a.fs) as Arc<dyn ILocalFileSystem + Send + Sync>,
+        &Config::default(),
+    );
+    let result = restarted.sync().await.unwrap();
+    assert!(result.errors.is_empty(), "sync errors: {:?}", result.errors);
+    for name in ["a.txt", "b.txt", "c.txt"] {
+        assert_eq!(fs::read(a.path(name)).unwrap(), name.as_bytes());
+    }
+    assert_eq!(
+        fs::read(cloud.path().join("local.txt")).unwrap(),
+        b"not uploaded yet"
+    );
+}
+
+#[tokio::test]
+async fn test_large_delta_is_applied_in_bounded_batches() {
+    let cloud = TempDir::new().unwrap();
+    for i in 0..40 {
+        fs::write(cloud.path().join(format!("f{i:02}.txt")), b"x").unwrap();
+    }
+    let provider = Arc::new(PagedProvider::new(cloud.path()));
+    let config = lnxdrive_core::config::ConfigBuilder::new()
+        .delta_max_in_flight_items(8)
+        .build();
+    let a = Replica::build(Arc::clone(&provider) as _, &config).await;
+
+    let plan = a.engine.plan().await.unwrap();
+    assert!(!plan.is_complete());
+    assert_eq!(plan.summary().downloads, 8);
+    assert!(!plan.preview().complete);
+
+    let result = a.engine.execute(plan).await.unwrap();
+    assert!(result.errors.is_empty(), "sync errors: {:?}", result.errors);
+    assert_eq!(result.files_downloaded, 40);
+    assert_eq!(result.remote_batches, 5);
+    assert!(a.path("f39.txt").exists());
+
+    // One item per page, so no more pages than the limit were read ahead
+    // of the downloads
+    let in_flight = provider.max_in_flight.load(Ordering::SeqCst);
+    assert!(in_flight <= 8, "{in_flight} items held in memory");
+    let account = a.repo.get_default_account().await.unwrap().unwrap();
+    assert!(account.delta_token().is_some());
+}
+
+#[tokio::test]
+async fn test_interrupted_delta_resumes_after_restart() {
+    let cloud = TempDir::new().unwrap();
+    let names: Vec<String> = (0..6).map(|i| format!("f{i}.txt")).collect();
+    for name in &names {
+        fs::write(cloud.path().join(name), name.as_bytes()).unwrap();
+    }
+    let provider = Arc::new(PagedProvider::new(cloud.path()));
+    *provider.fail_page.lock().unwrap() = Some(2);
+    let config = lnxdrive_core::config::ConfigBuilder::new()
+        .delta_max_in_flight_items(2)
+        .build();
+    let mut a = Replica::build(Arc::clone(&provider) as _, &config).await;
+
+    // The first two pages are applied, then the connection drops
+    let result = a.engine.sync().await.unwrap();
+    assert_eq!(result.files_downloaded, 2);
+    assert!(result.errors.iter().any(|e| e.contains("connection reset")));
+    let account = a.repo.get_default_account().await.unwrap().unwrap();
+    assert!(account.delta_token().is_none());
+    assert_eq!(account.delta_next_link(), Some("page-2"));
+
+    // A restarted daemon continues the page walk where it was applied
+    a.engine = SyncEngine::new(
+        Arc::clone(&provider) as _,
+        Arc::clone(&a.repo) as Arc<dyn IStateRepository + Send + Sync>,
+        Arc::clone(&a.fs) as Arc<dyn ILocalFileSystem + Send + Sync>,
+        &config,
+    );
+    let result = a.engine.sync().await.unwrap();
+    assert!(result.errors.is_empty(), "sync errors: {:?}", result.errors);
+    assert_eq!(*provider.resumed_from.lock().unwrap(), vec!["page-2"]);
+    assert_eq!(result.files_downloaded, 4);
+    for name in &names {
+        assert_eq!(fs::read(a.path(name)).unwrap(), name.as_bytes());
+    }
+    assert_eq!(provider.downloads.load(Ordering::SeqCst), 6);
+
+    let account = a.repo.get_default_account().await.unwrap().unwrap();
+    assert!(account.delta_token().is_some());
+    assert!(account.delta_next_link().is_none());
+}
+
+#[tokio::test]
+async fn test_plan_of_added_files_changes_nothing() {
+    let cloud = TempDir::new().unwrap();
+    let a = Replica::new(cloud.path()).await;
+    let b = Replica::new(cloud.path()).await;
+    fs::write(a.path("new.txt"), b"hello").unwrap();
+
+    let plan = a.engine.plan().await.unwrap();
+    let ops: Vec<_> = plan.operations().cloned().collect();
+    assert_eq!(
+        ops,
+        vec![SyncOperation::Upload {
+            path: a.path("new.txt"),
+            size: 5
+        }]
+    );
+    assert!(!cloud.path().join("new.txt").exists());
+    assert_eq!(a.engine.plan().await.unwrap().summary(), plan.summary());
+
+    let result = a.engine.execute(plan).await.unwrap();
+    assert_eq!(result.files_uploaded, 1);
+    assert!(cloud.path().join("new.txt").exists());
+
+    let plan = b.engine.plan().await.unwrap();
+    assert!(plan.operations().any(|op| op
+        == &SyncOperation::Download {
+            path: b.path("new.txt"),
+            size: 5
+        }));
+    assert!(!b.path("new.txt").exists());
+    assert_eq!(plan.summary().downloads, 1);
+    assert!(a.engine.plan().await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_plan_of_modified_and_deleted_files() {
+    let cloud = TempDir::new().unwrap();
+    let a = Replica::new(cloud.path()).await;
+    let b = Replica::new(cloud.path()).await;
+    fs::create_dir_all(a.path("docs")).unwrap();
+    fs::write(a.path("docs/edit.txt"), b"v1").unwrap();
+    fs::write(a.path("gone.txt"), b"bye").unwrap();
+    a.sync().await;
+    b.sync().await;
+
+    fs::write(a.path("docs/edit.txt"), b"version 2").unwrap();
+    fs::remove_file(a.path("gone.txt")).unwrap();
+    fs::create_dir_all(a.path("photos")).unwrap();
+    let plan = a.engine.plan().await.unwrap();
+    let local: Vec<_> = plan.operations().filter(|op| !op.is_skip()).collect();
+    assert!(local.contains(&&SyncOperation::Upload {
+        path: a.path("docs/edit.txt"),
+        size: 9
+    }));
+    assert!(local.contains(&&SyncOperation::Delete {
+        path: a.path("gone.txt"),
+        side: SyncSide::Remote
+    }));
+    assert!(local.contains(&&SyncOperation::CreateDir {
+        path: a.path("photos"),
+        side: SyncSide::Remote
+    }));
+    assert_eq!(local.len(), 3);
+    assert!(cloud.path().join("gone.txt").exists());
+    a.engine.execute(plan).await.unwrap();
+
+    let plan = b.engine.plan().await.unwrap();
+    let summary = plan.summary();
+    assert_eq!(summary.downloads, 1);
+    assert_eq!(summary.deletes, 1);
+    assert!(plan.operations().any(|op| op
+        == &SyncOperation::Delete {
+            path: b.path("gone.txt"),
+            side: SyncSide::Local
+        }));
+    assert!(plan.operations().any(|op| op
+        == &SyncOperation::Skip {
+            path: b.path("docs"),
+            reason: SkipReason::Unchanged
+        }));
+    assert!(b.path("gone.txt").exists());
+    assert_eq!(fs::read(b.path("docs/edit.txt")).unwrap(), b"v1");
+
+    b.engine.execute(plan).await.unwrap();
+    assert!(!b.path("gone.txt").exists());
+    assert_eq!(fs::read(b.path("docs/edit.txt")).unwrap(), b"version 2");
+}
+
+#[tokio::test]
+async fn test_sync_path_only_syncs_that_subtree() {
+    let cloud = TempDir::new().unwrap();
+    let a = Replica::new(cloud.path()).await;
+    let b = Replica::new(cloud.path()).await;
+    fs::create_dir_all(a.path("docs")).unwrap();
+    fs::create_dir_all(a.path("other")).unwrap();
+    fs::write(a.path("docs/plan.txt"), b"plan v1").unwrap();
+    fs::write(a.path("other/list.txt"), b"list v1").unwrap();
+    a.sync().await;
+    b.sync().await;
+    let token = a
+        .repo
+        .get_default_account()
+        .await
+        .unwrap()
+        .unwrap()
+        .delta_token()
+        .cloned();
+
+    // Remote edits from B, local edits on A, inside and outside docs/
+    fs::write(b.path("docs/plan.txt"), b"plan v2 from b").unwrap();
+    fs::write(b.path("other/list.txt"), b"list v2 from b").unwrap();
+    b.sync().await;
+    fs::write(a.path("docs/new.txt"), b"new on a").unwrap();
+    fs::write(a.path("other/new.txt"), b"new on a").unwrap();
+
+    let result = a.engine.sync_path(&a.path("docs")).await.unwrap();
+    assert!(result.errors.is_empty(), "sync errors: {:?}", result.errors);
+    assert_eq!(result.files_uploaded, 1);
+    assert_eq!(
+        fs::read(a.path("docs/plan.txt")).unwrap(),
+        b"plan v2 from b"
+    );
+    assert_eq!(
+        fs::read(cloud.path().join("docs/new.txt")).unwrap(),
+        b"new on a"
+    );
+    assert_eq!(fs::read(a.path("other/list.txt")).unwrap(), b"list v1");
+    assert!(!cloud.path().join("other/new.txt").exists());
+
+    // The rest is left for the regular cycle
+    let account = a.repo.get_default_account().await.unwrap().unwrap();
+    assert_eq!(account.delta_token().cloned(), token);
+    let result = a.engine.sync().await.unwrap();
+    assert!(result.errors.is_empty(), "sync errors: {:?}", result.errors);
+    assert_eq!(result.files_uploaded, 1);
+    assert_eq!(
+        fs::read(a.path("other/list.txt")).unwrap(),
+        b"list v2 from b"
+    );
+}
+
+#[tokio::test]
+async fn test_sync_path_outside_sync_root_is_rejected() {
+    let cloud = TempDir::new().unwrap();
+    let a = Replica::new(cloud.path()).await;
+    let err = a
+        .engine
+        .sync_path(Path::new("/etc/passwd"))
+        .await
+        .unwrap_err();
+    assert!(
+        err.to_string().contains("not within sync root"),
+        "unexpected error: {err}"
+    );
+}
+
+#[tokio::test]
+async fn test_paused_upload_resumes_from_last_chunk() {
+    let cloud = TempDir::new().unwrap();
+    let provider = Arc::new(ChunkedProvider::new(cloud.path()));
+    let mut config = Config::default();
+    // Every non-empty file goes through an upload session
+    config.large_files.threshold_mb = 0;
+    let mut a = Replica::build(Arc::clone(&provider) as _, &config).await;
+    let control = Arc::new(TransferControl::new());
+    a.engine.set_transfer_control(Arc::clone(&control));
+
+    let data: Vec<u8> = (0..40).collect();
+    fs::write(a.path("video.bin"), &data).unwrap();
+
+    // Ten chunks take 500ms; pause after the first few
+    let pauser = {
+        let control = Arc::clone(&control);
+        tokio::spawn(async move {
+            tokio::time::sleep(std::time::Duration::from_millis(120)).await;
+            control.pause_all();
+        })
+    };
+    let result = a.engine.sync().await.unwrap();
+    pauser.await.unwrap();
+
+    assert!(result.errors.is_empty(), "sync errors: {:?}", result.errors);
+    assert_eq!(result.transfers_paused, 1);
+    assert_eq!(result.files_uploaded, 0);
+    assert!(!cloud.path().join("video.bin").exists());
+    let checkpoint = provider.committed.lock().unwrap()["video.bin"];
+    assert!(
+        checkpoint > 0 && checkpoint < data.len(),
+        "checkpoint {checkpoint}"
+    );
+
+    // While paused, nothing is started
+    let result = a.engine.sync().await.unwrap();
+    assert_eq!(result.transfers_paused, 1);
+    assert_eq!(provider.starts.lock().unwrap().len(), 1);
+
+    control.resume_all();
+    let result = a.engine.sync().await.unwrap();
+    assert!(result.errors.is_empty(), "sync errors: {:?}", result.errors);
+    assert_eq!(result.transfers_paused, 0);
+    assert_eq!(result.files_uploaded, 1);
+    assert_eq!(*provider.starts.lock().unwrap(), vec![0, checkpoint]);
+    assert_eq!(fs::read(cloud.path().join("video.bin")).unwrap(), data);
+}
+
+async fn upload_of_changing_file(config: &Config) {
+    let cloud = TempDir::new().unwrap();
+    let provider = Arc::new(MutatingProvider::new(cloud.path()));
+    let a = Replica::build(Arc::clone(&provider) as _, config).await;
+
+    fs::write(a.path("notes.txt"), b"first draft").unwrap();
+    provider.rewrite_during_upload(a.path("notes.txt"), b"second, longer draft");
+    a.sync().await;
+
+    assert_eq!(provider.uploads.load(Ordering::SeqCst), 2);
+    assert_eq!(
+        fs::read(cloud.path().join("notes.txt")).unwrap(),
+        b"second, longer draft"
+    );
+    let item = a
+        .repo
+        .get_item_by_path(&SyncPath::new(a.path("notes.txt")).unwrap())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(item.size_bytes(), 20);
+
+    // The state recorded matches the file, so nothing is uploaded again
+    let result = a.engine.sync().await.unwrap();
+    assert_eq!(result.files_uploaded, 0);
+    assert_eq!(provider.uploads.load(Ordering::SeqCst), 2);
+}
+
+#[tokio::test]
+async fn test_file_changed_during_simple_upload_is_uploaded_again() {
+    upload_of_changing_file(&Config::default()).await;
+}
+
+#[tokio::test]
+async fn test_file_changed_during_upload_session_is_not_committed() {
+    let mut config = Config::default();
+    config.large_files.threshold_mb = 0;
+    upload_of_changing_file(&config).await;
+}
+
+/// Cloud with two top-level folders, `Docs` holding a subfolder
+fn selection_cloud() -> TempDir {
+    let cloud = TempDir::new().unwrap();
+    fs::create_dir_all(cloud.path().join("Docs/Sub")).unwrap();
+    fs::create_dir_all(cloud.path().join("Photos")).unwrap();
+    fs::write(cloud.path().join("Docs/a.txt"), b"a").unwrap();
+    fs::write(cloud.path().join("Docs/Sub/b.txt"), b"b").unwrap();
+    fs::write(cloud.path().join("Photos/p.jpg"), b"jpeg").unwrap();
+    cloud
+}
+
+async fn item_state(replica: &Replica, relative: &str) -> Option<ItemState> {
+    let path = SyncPath::new(replica.path(relative)).unwrap();
+    replica
+        .repo
+        .get_item_by_path(&path)
+        .await
+        .unwrap()
+        .map(|item| item.state().clone())
+}
+
+#[tokio::test]
+async fn test_selected_folder_is_added_as_placeholders() {
+    let cloud = selection_cloud();
+    let a = Replica::new(cloud.path()).await;
+    a.engine
+        .set_folder_selection(FolderSelection::new(["/Photos"]).unwrap());
+    a.sync().await;
+    assert!(a.path("Photos/p.jpg").exists());
+    assert!(!a.path("Docs").exists());
+    assert_eq!(item_state(&a, "Docs/a.txt").await, None);
+
+    let selection = FolderSelection::new(["/Photos", "/Docs"]).unwrap();
+    let outcome = a
+        .engine
+        .apply_folder_selection(selection, &|_| {})
+        .await
+        .unwrap();
+    assert_eq!(outcome.added, 4);
+    assert_eq!(outcome.removed, 0);
+
+    // Tracked without downloading anything
+    for relative in ["Docs", "Docs/a.txt", "Docs/Sub", "Docs/Sub/b.txt"] {
+        assert_eq!(
+            item_state(&a, relative).await,
+            Some(ItemState::Online),
+            "{relative}"
+        );
+    }
+    assert!(!a.path("Docs/a.txt").exists());
+    assert_eq!(
+        a.repo.get_selected_folders().await.unwrap(),
+        vec!["/Docs".to_string(), "/Photos".to_string()]
+    );
+}
+
+#[tokio::test]
+async fn test_deselected_folder_is_removed_locally_only() {
+    let cloud = selection_cloud();
+    let mut a = Replica::new(cloud.path()).await;
+    let observer = Arc::new(RecordingObserver::default());
+    a.engine
+        .set_item_observer(Arc::clone(&observer) as Arc<dyn IItemObserver>);
+    a.sync().await;
+    assert!(a.path("Docs/Sub/b.txt").exists());
+
+    let selection = FolderSelection::new(["/Photos"]).unwrap();
+    let outcome = a
+        .engine
+        .apply_folder_selection(selection, &|_| {})
+        .await
+        .unwrap();
+    assert_eq!(outcome.added, 0);
+    assert_eq!(outcome.removed, 4);
+
+    assert!(!a.path("Docs").exists());
+    assert_eq!(item_state(&a, "Docs/Sub/b.txt").await, None);
+    assert_eq!(observer.removed.lock().unwrap().len(), 4);
+    assert!(a.path("Photos/p.jpg").exists());
+    assert!(cloud.path().join("Docs/Sub/b.txt").exists());
+    assert_eq!(
+        a.repo.get_selected_folders().await.unwrap(),
+        vec!["/Photos".to_string()]
+    );
+
+    // Later cycles neither delete the folder remotely nor bring it back
+    a.sync().await;
+    assert!(cloud.path().join("Docs/a.txt").exists());
+    assert!(!a.path("Docs").exists());
+}
+
+#[tokio::test]
+async fn test_unsynced_edit_blocks_deselection() {
+    let cloud = selection_cloud();
+    let a = Replica::new(cloud.path()).await;
+    a.sync().await;
+    // An edit the engine noticed but did not upload yet
+    fs::write(a.path("Docs/a.txt"), b"edited, not uploaded").unwrap();
+    let path = SyncPath::new(a.path("Docs/a.txt")).unwrap();
+    let mut item = a.repo.get_item_by_path(&path).await.unwrap().unwrap();
+    item.mark_modified().unwrap();
+    a.repo.save_item(&item).await.unwrap();
+
+    let selection = FolderSelection::new(["/Photos"]).unwrap();
+    let err = a
+        .engine
+        .apply_folder_selection(selection, &|_| {})
+        .await
+        .unwrap_err();
+    assert!(
+        err.to_string().contains("Docs/a.txt"),
+        "unexpected error: {err}"
+    );
+
+    // Nothing changed
+    assert!(a.engine.folder_selection().is_everything());
+    assert!(a.repo.get_selected_folders().await.unwrap().is_empty());
+    assert_eq!(
+        fs::read(a.path("Docs/a.txt")).unwrap(),
+        b"edited, not uploaded"
+    );
+    assert!(item_state(&a, "Docs/Sub/b.txt").await.is_some());
+}
+
+/// Contents of a large log file, `lines` lines long
+fn log_lines(lines: usize) -> Vec<u8> {
+    (0..lines)
+        .flat_map(|i| format!("{i:08} log line\n").into_bytes())
+        .collect()
+}
+
+#[tokio::test]
+async fn test_appended_file_sends_only_new_bytes() {
+    let cloud = TempDir::new().unwrap();
+    let provider = Arc::new(UploadCountingProvider::new(cloud.path(), true));
+    let a = Replica::build(
+        Arc::clone(&provider) as Arc<dyn ICloudProvider>,
+        &Config::default(),
+    )
+    .await;
+    let log = log_lines(10_000);
+    fs::write(a.path("app.log"), &log).unwrap();
+    a.sync().await;
+    assert_eq!(provider.take_counts(), (log.len(), 0));
+
+    // Growing at the end sends just the new lines
+    let mut grown = log.clone();
+    grown.extend_from_slice(b"appended line\n");
+    fs::write(a.path("app.log"), &grown).unwrap();
+    a.sync().await;
+    assert_eq!(provider.take_counts(), (0, 14));
+    assert_eq!(fs::read(cloud.path().join("app.log")).unwrap(), grown);
+
+    // The stored hash matches, so the next cycle has nothing to do
+    a.sync().await;
+    assert_eq!(provider.take_counts(), (0, 0));
+
+    // An edit before the end needs the whole file
+    let mut edited = grown.clone();
+    edited[0] = b'X';
+    edited.extend_from_slice(b"one more\n");
+    fs::write(a.path("app.log"), &edited).unwrap();
+    a.sync().await;
+    assert_eq!(provider.take_counts(), (edited.len(), 0));
+    assert_eq!(fs::read(cloud.path().join("app.log")).unwrap(), edited);
+
+    let b = Replica::new(cloud.path()).await;
+    b.sync().await;
+    assert_eq!(fs::read(b.path("app.log")).unwrap(), edited);
+}
+
+#[tokio::test]
+async fn test_append_falls_back_to_full_upload() {
+    let cloud = TempDir::new().unwrap();
+    let provider = Arc::new(UploadCountingProvider::new(cloud.path(), false));
+    let a = Replica::build(
+        Arc::clone(&provider) as Arc<dyn ICloudProvider>,
+        &Config::default(),
+    )
+    .await;
+    fs::write(a.path("app.log"), log_lines(100)).unwrap();
+    a.sync().await;
+    provider.take_counts();
+
+    let grown = log_lines(200);
+    fs::write(a.path("app.log"), &grown).unwrap();
+    a.sync().await;
+    assert_eq!(provider.take_counts(), (grown.len(), 0));
+    assert_eq!(fs::read(cloud.path().join("app.log")).unwrap(), grown);
+    // The provider says it cannot append, so the file is not read for it
+    assert_eq!(provider.append_calls.load(Ordering::SeqCst), 0);
+}
@@ -0,0 +1,258 @@
+//! Liveness and readiness checks
+//!
+//! `/healthz` only answers whether the process is alive and serving HTTP.
+//! `/readyz` additionally checks that the daemon can do useful work:
+//! - an account is authenticated
+//! - the last successful sync is recent enough
+//! - the state database answers queries
+//!
+//! The daemon supplies the raw facts through [`HealthSource`]; the
+//! thresholds that turn them into pass/fail come from [`ReadinessThresholds`].
+
+use std::{collections::BTreeMap, time::Duration};
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+/// Supplies the facts behind the readiness checks
+#[async_trait]
+pub trait HealthSource: Send + Sync {
+    /// Returns true if an account with usable tokens is loaded
+    async fn is_authenticated(&self) -> bool;
+
+    /// Returns when the last sync cycle finished successfully, if ever
+    async fn last_successful_sync(&self) -> Option<DateTime<Utc>>;
+
+    /// Runs a trivial query against the state database
+    async fn ping_database(&self) -> Result<(), String>;
+}
+
+/// Limits used to decide whether the daemon is ready
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReadinessThresholds {
+    /// Maximum age of the last successful sync
+    pub max_sync_age: Duration,
+    /// Maximum time the database ping may take
+    pub db_timeout: Duration,
+}
+
+impl Default for ReadinessThresholds {
+    fn default() -> Self {
+        Self {
+            max_sync_age: Duration::from_secs(300),
+            db_timeout: Duration::from_secs(2),
+        }
+    }
+}
+
+/// Outcome of a single check
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct CheckResult {
+    pub ok: bool,
+    pub detail: String,
+}
+
+impl CheckResult {
+    fn pass(detail: impl Into<String>) -> Self {
+        Self {
+            ok: true,
+            detail: detail.into(),
+        }
+    }
+
+    fn fail(detail: impl Into<String>) -> Self {
+        Self {
+            ok: false,
+            detail: detail.into(),
+        }
+    }
+}
+
+/// JSON body returned by the health endpoints
+///
+/// ```json
+/// {"status":"fail","checks":{"authenticated":{"ok":true,"detail":"..."}, ...}}
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct HealthReport {
+    /// `"ok"` if every check passed, `"fail"` otherwise
+    pub status: &'static str,
+    /// Individual checks by name
+    pub checks: BTreeMap<&'static str, CheckResult>,
+}
+
+impl HealthReport {
+    fn from_checks(checks: BTreeMap<&'static str, CheckResult>) -> Self {
+        let status = if checks.values().all(|check| check.ok) {
+            "ok"
+        } else {
+            "fail"
+        };
+        Self { status, checks }
+    }
+
+    /// Returns true if every check passed
+    pub fn is_ok(&self) -> bool {
+        self.status == "ok"
+    }
+}
+
+/// Builds the `/healthz` report (the process is alive if it can answer)
+pub fn liveness() -> HealthReport {
+    let mut checks = BTreeMap::new();
+    checks.insert(
+        "process",
+        CheckResult::pass(format!("pid {}", std::process::id())),
+    );
+    HealthReport::from_checks(checks)
+}
+
+/// Builds the `/readyz` report
+pub async fn readiness(
+    source: &dyn HealthSource,
+    thresholds: &ReadinessThresholds,
+    now: DateTime<Utc>,
+) -> HealthReport {
+    let mut checks = BTreeMap::new();
+
+    let authenticated = if source.is_authenticated().await {
+        CheckResult::pass("account authenticated")
+    } else {
+        CheckResult::fail("no authenticated account")
+    };
+    checks.insert("authenticated", authenticated);
+
+    let max_age = thresholds.max_sync_age.as_secs();
+    let last_sync = match source.last_successful_sync().await {
+        Some(at) => {
+            let age = (now - at).num_seconds().max(0) as u64;
+            if age <= max_age {
+                CheckResult::pass(format!("last sync {age}s ago (max {max_age}s)"))
+            } else {
+                CheckResult::fail(format!("last sync {age}s ago (max {max_age}s)"))
+            }
+        }
+        None => CheckResult::fail("no successful sync yet"),
+    };
+    checks.insert("last_sync", last_sync);
+
+    let database = match tokio::time::timeout(thresholds.db_timeout, source.ping_database()).await {
+        Ok(Ok(())) => CheckResult::pass("reachable"),
+        Ok(Err(e)) => CheckResult::fail(e),
+        Err(_) => CheckResult::fail(format!(
+            "no answer within {}ms",
+            thresholds.db_timeout.as_millis()
+        )),
+    };
+    checks.insert("database", database);
+
+    HealthReport::from_checks(checks)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct FakeSource {
+        authenticated: bool,
+        last_sync: Option<DateTime<Utc>>,
+        db: Result<(), String>,
+        db_delay: Duration,
+    }
+
+    impl FakeSource {
+        fn healthy(now: DateTime<Utc>) -> Self {
+            Self {
+                authenticated: true,
+                last_sync: Some(now - chrono::Duration::seconds(10)),
+                db: Ok(()),
+                db_delay: Duration::ZERO,
+            }
+        }
+    }
+
+    #[async_trait]
+    impl HealthSource for FakeSource {
+        async fn is_authenticated(&self) -> bool {
+            self.authenticated
+        }
+
+        async fn last_successful_sync(&self) -> Option<DateTime<Utc>> {
+            self.last_sync
+        }
+
+        async fn ping_database(&self) -> Result<(), String> {
+            tokio::time::sleep(self.db_delay).await;
+            self.db.clone()
+        }
+    }
+
+    #[test]
+    fn test_liveness_is_ok() {
+        let report = liveness();
+        assert!(report.is_ok());
+        assert!(report.checks["process"].ok);
+    }
+
+    #[tokio::test]
+    async fn test_readiness_ok_when_all_checks_pass() {
+        let now = Utc::now();
+        let report = readiness(
+            &FakeSource::healthy(now),
+            &ReadinessThresholds::default(),
+            now,
+        )
+        .await;
+
+        assert!(report.is_ok(), "{report:?}");
+        assert_eq!(report.checks.len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_readiness_fails_each_check() {
+        let now = Utc::now();
+        let thresholds = ReadinessThresholds::default();
+
+        let mut source = FakeSource::healthy(now);
+        source.authenticated = false;
+        let report = readiness(&source, &thresholds, now).await;
+        assert!(!report.is_ok());
+        assert!(!report.checks["authenticated"].ok);
+
+        let mut source = FakeSource::healthy(now);
+        source.last_sync = Some(now - chrono::Duration::seconds(301));
+        let report = readiness(&source, &thresholds, now).await;
+        assert!(!report.checks["last_sync"].ok);
+        assert!(report.checks["last_sync"].detail.contains("max 300s"));
+
+        let mut source = FakeSource::healthy(now);
+        source.last_sync = None;
+        let report = readiness(&source, &thresholds, now).await;
+        assert!(!report.checks["last_sync"].ok);
+
+        let mut source = FakeSource::healthy(now);
+        source.db = Err("database is locked".into());
+        let report = readiness(&source, &thresholds, now).await;
+        assert_eq!(
+            report.checks["database"],
+            CheckResult::fail("database is locked")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_readiness_times_out_slow_database() {
+        let now = Utc::now();
+        let mut source = FakeSource::healthy(now);
+        source.db_delay = Duration::from_millis(200);
+        let thresholds = ReadinessThresholds {
+            db_timeout: Duration::from_millis(10),
+            ..ReadinessThresholds::default()
+        };
+
+        let report = readiness(&source, &thresholds, now).await;
+
+        assert!(!report.checks["database"].ok);
+        assert!(report.checks["database"].detail.contains("10ms"));
+    }
+}
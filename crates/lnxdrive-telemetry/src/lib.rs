@@ -5,3 +5,18 @@
 //! - Prometheus metrics export
 //! - Opt-in data collection
 //! - Privacy-preserving aggregation
+//!
+//! The [`MetricsServer`] exposes Prometheus metrics along with `/healthz`
+//! and `/readyz` probes for orchestrators and monitoring tools. The
+//! [`MetricsRegistry`] keeps the session counters that `Sync.GetStats`
+//! reports over D-Bus.
+
+pub mod health;
+pub mod metrics;
+pub mod server;
+pub mod stats;
+
+pub use health::{HealthReport, HealthSource, ReadinessThresholds};
+pub use metrics::GaugeFn;
+pub use server::MetricsServer;
+pub use stats::{MetricsRegistry, StatsSnapshot};
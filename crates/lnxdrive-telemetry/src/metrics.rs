@@ -0,0 +1,111 @@
+//! Gauges computed on demand
+//!
+//! Some values, such as the size of an in-memory table, already live in
+//! another component. Rather than updating a gauge on every change, a
+//! [`GaugeFn`] reads the value when Prometheus scrapes `/metrics`.
+
+use prometheus::{
+    core::{Collector, Desc},
+    proto::MetricFamily,
+    Gauge, Opts, Registry,
+};
+
+/// A gauge whose value is read from a closure at collection time
+pub struct GaugeFn {
+    gauge: Gauge,
+    value: Box<dyn Fn() -> f64 + Send + Sync>,
+}
+
+impl GaugeFn {
+    /// Creates a gauge named `name` that reports `value()` when collected
+    pub fn new<F>(name: &str, help: &str, value: F) -> prometheus::Result<Self>
+    where
+        F: Fn() -> f64 + Send + Sync + 'static,
+    {
+        Ok(Self {
+            gauge: Gauge::with_opts(Opts::new(name, help))?,
+            value: Box::new(value),
+        })
+    }
+
+    /// Registers the gauge in the default registry, which the
+    /// [`MetricsServer`](crate::MetricsServer) exports
+    ///
+    /// See [`register_in`](Self::register_in).
+    pub fn register(self) -> prometheus::Result<()> {
+        self.register_in(prometheus::default_registry())
+    }
+
+    /// Registers the gauge in `registry`, replacing a gauge of the same name
+    ///
+    /// Components that can be started again (e.g. a remount) register their
+    /// gauges again, which must then read from the new instance.
+    pub fn register_in(self, registry: &Registry) -> prometheus::Result<()> {
+        let desc = self.gauge.desc()[0];
+        // Unregistering matches on the description, so a fresh gauge will do
+        if let Ok(previous) = Gauge::with_opts(Opts::new(&desc.fq_name, &desc.help)) {
+            let _ = registry.unregister(Box::new(previous));
+        }
+        registry.register(Box::new(self))
+    }
+}
+
+impl Collector for GaugeFn {
+    fn desc(&self) -> Vec<&Desc> {
+        self.gauge.desc()
+    }
+
+    fn collect(&self) -> Vec<MetricFamily> {
+        self.gauge.set((self.value)());
+        self.gauge.collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    };
+
+    use prometheus::{Encoder, TextEncoder};
+
+    use super::*;
+
+    fn scrape(registry: &Registry) -> String {
+        let mut buffer = Vec::new();
+        TextEncoder::new()
+            .encode(&registry.gather(), &mut buffer)
+            .unwrap();
+        String::from_utf8(buffer).unwrap()
+    }
+
+    #[test]
+    fn test_gauge_fn_reads_value_when_collected() {
+        let registry = Registry::new();
+        let value = Arc::new(AtomicU64::new(3));
+        let source = Arc::clone(&value);
+        let gauge = GaugeFn::new("test_items", "Items", move || {
+            source.load(Ordering::Relaxed) as f64
+        })
+        .unwrap();
+        gauge.register_in(&registry).unwrap();
+
+        assert!(scrape(&registry).contains("test_items 3"));
+        value.store(7, Ordering::Relaxed);
+        assert!(scrape(&registry).contains("test_items 7"));
+    }
+
+    #[test]
+    fn test_register_replaces_previous() {
+        let registry = Registry::new();
+        let first = GaugeFn::new("test_items", "Items", || 1.0).unwrap();
+        first.register_in(&registry).unwrap();
+        let second = GaugeFn::new("test_items", "Items", || 2.0).unwrap();
+        second.register_in(&registry).unwrap();
+
+        let text = scrape(&registry);
+        assert!(text.contains("test_items 2"));
+        assert!(!text.contains("test_items 1"));
+    }
+}
@@ -0,0 +1,229 @@
+//! HTTP server for Prometheus metrics and health probes
+//!
+//! Endpoints:
+//! - `GET /metrics`: metrics in the Prometheus text exposition format
+//! - `GET /healthz`: liveness, always `200` while the process serves HTTP
+//! - `GET /readyz`: readiness, `200` when every check passes, `503` otherwise
+//!
+//! Both health endpoints return a small JSON body describing each check
+//! (see [`HealthReport`]), so a failing probe also tells why.
+
+use std::{convert::Infallible, io, net::SocketAddr, sync::Arc};
+
+use chrono::Utc;
+use http_body_util::Full;
+use hyper::{
+    body::{Bytes, Incoming},
+    server::conn::http1,
+    service::service_fn,
+    Request, Response, StatusCode,
+};
+use hyper_util::rt::TokioIo;
+use prometheus::{Encoder, Registry, TextEncoder};
+use tokio::{net::TcpListener, task::JoinHandle};
+use tokio_util::sync::CancellationToken;
+use tracing::{debug, info, warn};
+
+use crate::health::{self, HealthReport, HealthSource, ReadinessThresholds};
+
+/// Serves `/metrics`, `/healthz` and `/readyz`
+pub struct MetricsServer {
+    registry: Registry,
+    health: Arc<dyn HealthSource>,
+    thresholds: ReadinessThresholds,
+}
+
+impl MetricsServer {
+    /// Creates a server exporting the default Prometheus registry
+    pub fn new(health: Arc<dyn HealthSource>, thresholds: ReadinessThresholds) -> Self {
+        Self {
+            registry: prometheus::default_registry().clone(),
+            health,
+            thresholds,
+        }
+    }
+
+    /// Exports `registry` instead of the default registry
+    pub fn with_registry(mut self, registry: Registry) -> Self {
+        self.registry = registry;
+        self
+    }
+
+    /// Binds `addr` and serves requests until `shutdown` is cancelled
+    ///
+    /// Returns the bound address (useful with port `0`) and the server task.
+    pub async fn spawn(
+        self,
+        addr: SocketAddr,
+        shutdown: CancellationToken,
+    ) -> io::Result<(SocketAddr, JoinHandle<()>)> {
+        let listener = TcpListener::bind(addr).await?;
+        let local_addr = listener.local_addr()?;
+        info!(address = %local_addr, "Metrics server listening");
+
+        let server = Arc::new(self);
+        let handle = tokio::spawn(async move { server.serve(listener, shutdown).await });
+        Ok((local_addr, handle))
+    }
+
+    async fn serve(self: Arc<Self>, listener: TcpListener, shutdown: CancellationToken) {
+        loop {
+            let stream = tokio::select! {
+                accepted = listener.accept() => match accepted {
+                    Ok((stream, _)) => stream,
+                    Err(e) => {
+                        warn!(error = %e, "Metrics server failed to accept connection");
+                        continue;
+                    }
+                },
+                _ = shutdown.cancelled() => break,
+            };
+
+            let server = Arc::clone(&self);
+            tokio::spawn(async move {
+                let service = service_fn(move |req: Request<Incoming>| {
+                    let server = Arc::clone(&server);
+                    async move { Ok::<_, Infallible>(server.handle(req.uri().path()).await) }
+                });
+                if let Err(e) = http1::Builder::new()
+                    .serve_connection(TokioIo::new(stream), service)
+                    .await
+                {
+                    debug!(error = %e, "Metrics server connection error");
+                }
+            });
+        }
+        debug!("Metrics server stopped");
+    }
+
+    async fn handle(&self, path: &str) -> Response<Full<Bytes>> {
+        match path {
+            "/metrics" => self.metrics(),
+            "/healthz" => json_response(&health::liveness()),
+            "/readyz" => {
+                let report =
+                    health::readiness(self.health.as_ref(), &self.thresholds, Utc::now()).await;
+                json_response(&report)
+            }
+            _ => response(StatusCode::NOT_FOUND, "text/plain", "not found\n".into()),
+        }
+    }
+
+    fn metrics(&self) -> Response<Full<Bytes>> {
+        let encoder = TextEncoder::new();
+        let mut buffer = Vec::new();
+        match encoder.encode(&self.registry.gather(), &mut buffer) {
+            Ok(()) => response(StatusCode::OK, encoder.format_type(), buffer),
+            Err(e) => response(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "text/plain",
+                format!("failed to encode metrics: {e}\n").into_bytes(),
+            ),
+        }
+    }
+}
+
+fn json_response(report: &HealthReport) -> Response<Full<Bytes>> {
+    let status = if report.is_ok() {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+    let body = serde_json::to_vec(report).unwrap_or_default();
+    response(status, "application/json", body)
+}
+
+fn response(status: StatusCode, content_type: &str, body: Vec<u8>) -> Response<Full<Bytes>> {
+    Response::builder()
+        .status(status)
+        .header("Content-Type", content_type)
+        .body(Full::new(Bytes::from(body)))
+        .expect("static response parts are valid")
+}
+
+#[cfg(test)]
+mod tests {
+    use async_trait::async_trait;
+    use chrono::DateTime;
+    use prometheus::IntCounter;
+
+    use super::*;
+
+    struct StaticSource {
+        authenticated: bool,
+    }
+
+    #[async_trait]
+    impl HealthSource for StaticSource {
+        async fn is_authenticated(&self) -> bool {
+            self.authenticated
+        }
+
+        async fn last_successful_sync(&self) -> Option<DateTime<Utc>> {
+            Some(Utc::now())
+        }
+
+        async fn ping_database(&self) -> Result<(), String> {
+            Ok(())
+        }
+    }
+
+    async fn start(authenticated: bool) -> (String, CancellationToken) {
+        let registry = Registry::new();
+        let counter = IntCounter::new("lnxdrive_test_total", "Test counter").unwrap();
+        counter.inc_by(3);
+        registry.register(Box::new(counter)).unwrap();
+
+        let server = MetricsServer::new(
+            Arc::new(StaticSource { authenticated }),
+            ReadinessThresholds::default(),
+        )
+        .with_registry(registry);
+        let shutdown = CancellationToken::new();
+        let (addr, _handle) = server
+            .spawn("127.0.0.1:0".parse().unwrap(), shutdown.clone())
+            .await
+            .unwrap();
+        (format!("http://{addr}"), shutdown)
+    }
+
+    #[tokio::test]
+    async fn test_health_endpoints_report_status_codes() {
+        let (base, shutdown) = start(false).await;
+
+        let healthz = reqwest::get(format!("{base}/healthz")).await.unwrap();
+        assert_eq!(healthz.status(), 200);
+        let body: serde_json::Value = healthz.json().await.unwrap();
+        assert_eq!(body["status"], "ok");
+
+        let readyz = reqwest::get(format!("{base}/readyz")).await.unwrap();
+        assert_eq!(readyz.status(), 503);
+        let body: serde_json::Value = readyz.json().await.unwrap();
+        assert_eq!(body["status"], "fail");
+        assert_eq!(body["checks"]["authenticated"]["ok"], false);
+        assert_eq!(body["checks"]["database"]["ok"], true);
+
+        shutdown.cancel();
+    }
+
+    #[tokio::test]
+    async fn test_ready_and_metrics_endpoints() {
+        let (base, shutdown) = start(true).await;
+
+        let readyz = reqwest::get(format!("{base}/readyz")).await.unwrap();
+        assert_eq!(readyz.status(), 200);
+
+        let metrics = reqwest::get(format!("{base}/metrics")).await.unwrap();
+        assert_eq!(metrics.status(), 200);
+        assert!(metrics
+            .text()
+            .await
+            .unwrap()
+            .contains("lnxdrive_test_total 3"));
+
+        let missing = reqwest::get(format!("{base}/nope")).await.unwrap();
+        assert_eq!(missing.status(), 404);
+
+        shutdown.cancel();
+    }
+}
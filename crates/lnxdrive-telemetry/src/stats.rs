@@ -0,0 +1,321 @@
+//! Session statistics of the daemon
+//!
+//! [`MetricsRegistry`] owns the transfer counters of the daemon and reads
+//! them, together with gauges registered by other components (see the
+//! name constants below), into a [`StatsSnapshot`]. The same values are
+//! exported to Prometheus, and `lnxdrive status --stats` shows the
+//! snapshot without requiring a scraper.
+
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use lnxdrive_core::ports::{ITransferObserver, TransferEvent, TransferKind};
+use prometheus::{IntCounterVec, IntGauge, Opts, Registry};
+use serde::{Deserialize, Serialize};
+
+/// Gauge with the number of Graph requests rejected with HTTP 429
+pub const THROTTLED_REQUESTS: &str = "lnxdrive_http_throttled_requests";
+
+/// Gauge with the number of file opens served from the content cache
+pub const CACHE_HITS: &str = "lnxdrive_fuse_cache_hits";
+
+/// Gauge with the number of file opens that had to download the content
+pub const CACHE_MISSES: &str = "lnxdrive_fuse_cache_misses";
+
+/// Gauge with the number of damaged cache files healed by the scrubber
+pub const CACHE_HEALED: &str = "lnxdrive_cache_healed_total";
+
+/// Period over which the current throughput is averaged
+pub const THROUGHPUT_WINDOW: Duration = Duration::from_secs(10);
+
+/// Counters of one daemon session, as reported by `Sync.GetStats`
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct StatsSnapshot {
+    /// Seconds since the daemon started
+    pub uptime_secs: u64,
+    /// Bytes sent to the cloud
+    pub bytes_uploaded: u64,
+    /// Bytes received from the cloud
+    pub bytes_downloaded: u64,
+    /// Files uploaded successfully
+    pub files_uploaded: u64,
+    /// Files downloaded successfully
+    pub files_downloaded: u64,
+    /// Upload throughput over the last [`THROUGHPUT_WINDOW`], in bytes/s
+    pub upload_rate: u64,
+    /// Download throughput over the last [`THROUGHPUT_WINDOW`], in bytes/s
+    pub download_rate: u64,
+    /// Requests the cloud rejected with HTTP 429
+    pub throttled_requests: u64,
+    /// File opens served from the content cache
+    pub cache_hits: u64,
+    /// File opens that had to download the content first
+    pub cache_misses: u64,
+    /// Transfers under way
+    pub active_transfers: u64,
+    /// Sync-by-path requests waiting to run
+    pub queued_sync_paths: u64,
+}
+
+impl StatsSnapshot {
+    /// Share of file opens served from the cache, if there were any
+    pub fn cache_hit_ratio(&self) -> Option<f64> {
+        let total = self.cache_hits + self.cache_misses;
+        (total > 0).then(|| self.cache_hits as f64 / total as f64)
+    }
+}
+
+/// Bytes transferred recently, for the current throughput
+#[derive(Debug, Default)]
+struct RateWindow {
+    samples: VecDeque<(Instant, u64)>,
+}
+
+impl RateWindow {
+    fn record(&mut self, now: Instant, bytes: u64) {
+        self.samples.push_back((now, bytes));
+        self.prune(now);
+    }
+
+    fn prune(&mut self, now: Instant) {
+        while let Some((at, _)) = self.samples.front() {
+            if now.duration_since(*at) <= THROUGHPUT_WINDOW {
+                break;
+            }
+            self.samples.pop_front();
+        }
+    }
+
+    fn rate(&mut self, now: Instant) -> u64 {
+        self.prune(now);
+        let bytes: u64 = self.samples.iter().map(|(_, b)| b).sum();
+        bytes / THROUGHPUT_WINDOW.as_secs().max(1)
+    }
+}
+
+/// Transfers seen so far: bytes done of the ones under way, throughput
+#[derive(Debug, Default)]
+struct TransferTracker {
+    in_progress: HashMap<(TransferKind, String), u64>,
+    upload: RateWindow,
+    download: RateWindow,
+}
+
+/// Session counters of the daemon, exported to Prometheus
+pub struct MetricsRegistry {
+    registry: Registry,
+    started: Instant,
+    bytes: IntCounterVec,
+    files: IntCounterVec,
+    active_transfers: IntGauge,
+    queued_sync_paths: IntGauge,
+    transfers: Mutex<TransferTracker>,
+}
+
+impl MetricsRegistry {
+    /// Creates the counters and registers them in `registry`
+    ///
+    /// Gauges registered in the same registry under [`THROTTLED_REQUESTS`],
+    /// [`CACHE_HITS`] or [`CACHE_MISSES`] are included in the snapshot.
+    pub fn new(registry: &Registry) -> prometheus::Result<Self> {
+        let bytes = IntCounterVec::new(
+            Opts::new(
+                "lnxdrive_transfer_bytes_total",
+                "Bytes transferred, by direction",
+            ),
+            &["direction"],
+        )?;
+        let files = IntCounterVec::new(
+            Opts::new(
+                "lnxdrive_files_transferred_total",
+                "Files transferred successfully, by direction",
+            ),
+            &["direction"],
+        )?;
+        let active_transfers =
+            IntGauge::new("lnxdrive_transfers_active", "File transfers under way")?;
+        let queued_sync_paths = IntGauge::new(
+            "lnxdrive_sync_path_queue",
+            "Sync-by-path requests waiting to run",
+        )?;
+        registry.register(Box::new(bytes.clone()))?;
+        registry.register(Box::new(files.clone()))?;
+        registry.register(Box::new(active_transfers.clone()))?;
+        registry.register(Box::new(queued_sync_paths.clone()))?;
+
+        Ok(Self {
+            registry: registry.clone(),
+            started: Instant::now(),
+            bytes,
+            files,
+            active_transfers,
+            queued_sync_paths,
+            transfers: Mutex::new(TransferTracker::default()),
+        })
+    }
+
+    /// Creates the counters in the default registry, which the
+    /// [`MetricsServer`](crate::MetricsServer) exports
+    pub fn register_default() -> prometheus::Result<Self> {
+        Self::new(prometheus::default_registry())
+    }
+
+    /// Sets the number of sync-by-path requests waiting to run
+    pub fn set_queued_sync_paths(&self, queued: usize) {
+        self.queued_sync_paths.set(queued as i64);
+    }
+
+    /// Reads the current values
+    pub fn snapshot(&self) -> StatsSnapshot {
+        let now = Instant::now();
+        let (upload_rate, download_rate) = match self.transfers.lock() {
+            Ok(mut transfers) => (transfers.upload.rate(now), transfers.download.rate(now)),
+            Err(_) => (0, 0),
+        };
+        let families = self.registry.gather();
+        let gauge = |name: &str| {
+            families
+                .iter()
+                .find(|family| family.get_name() == name)
+                .and_then(|family| family.get_metric().first())
+                .map(|metric| metric.get_gauge().get_value() as u64)
+                .unwrap_or(0)
+        };
+
+        StatsSnapshot {
+            uptime_secs: now.duration_since(self.started).as_secs(),
+            bytes_uploaded: self.bytes.with_label_values(&["upload"]).get(),
+            bytes_downloaded: self.bytes.with_label_values(&["download"]).get(),
+            files_uploaded: self.files.with_label_values(&["upload"]).get(),
+            files_downloaded: self.files.with_label_values(&["download"]).get(),
+            upload_rate,
+            download_rate,
+            throttled_requests: gauge(THROTTLED_REQUESTS),
+            cache_hits: gauge(CACHE_HITS),
+            cache_misses: gauge(CACHE_MISSES),
+            active_transfers: self.active_transfers.get().max(0) as u64,
+            queued_sync_paths: self.queued_sync_paths.get().max(0) as u64,
+        }
+    }
+}
+
+/// Counts the bytes and files of the transfers reported to it
+///
+/// Progress events carry the bytes done so far, so the counters advance
+/// by the difference to the previous event of the same transfer.
+impl ITransferObserver for MetricsRegistry {
+    fn on_transfer_event(&self, event: TransferEvent) {
+        let Ok(mut transfers) = self.transfers.lock() else {
+            return;
+        };
+        let kind = event.kind();
+        let direction = kind.to_string();
+        match event {
+            TransferEvent::Progress {
+                path, bytes_done, ..
+            } => {
+                let done = transfers.in_progress.entry((kind, path)).or_insert(0);
+                let delta = bytes_done.saturating_sub(*done);
+                *done = (*done).max(bytes_done);
+                if delta > 0 {
+                    self.bytes.with_label_values(&[&direction]).inc_by(delta);
+                    let window = match kind {
+                        TransferKind::Upload => &mut transfers.upload,
+                        TransferKind::Download => &mut transfers.download,
+                    };
+                    window.record(Instant::now(), delta);
+                }
+            }
+            TransferEvent::Complete { path, error, .. } => {
+                transfers.in_progress.remove(&(kind, path));
+                if error.is_none() {
+                    self.files.with_label_values(&[&direction]).inc();
+                }
+            }
+        }
+        self.active_transfers
+            .set(transfers.in_progress.len() as i64);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::GaugeFn;
+
+    fn progress(path: &str, kind: TransferKind, bytes_done: u64) -> TransferEvent {
+        TransferEvent::Progress {
+            path: path.to_string(),
+            kind,
+            bytes_done,
+            bytes_total: 1000,
+            eta_secs: None,
+        }
+    }
+
+    fn complete(path: &str, kind: TransferKind, error: Option<&str>) -> TransferEvent {
+        TransferEvent::Complete {
+            path: path.to_string(),
+            kind,
+            error: error.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn test_transfer_events_are_counted() {
+        let metrics = MetricsRegistry::new(&Registry::new()).unwrap();
+
+        metrics.on_transfer_event(progress("/a", TransferKind::Upload, 0));
+        metrics.on_transfer_event(progress("/a", TransferKind::Upload, 400));
+        metrics.on_transfer_event(progress("/b", TransferKind::Download, 300));
+        assert_eq!(metrics.snapshot().active_transfers, 2);
+
+        metrics.on_transfer_event(progress("/a", TransferKind::Upload, 1000));
+        metrics.on_transfer_event(complete("/a", TransferKind::Upload, None));
+        metrics.on_transfer_event(complete("/b", TransferKind::Download, Some("failed")));
+
+        let stats = metrics.snapshot();
+        assert_eq!(stats.bytes_uploaded, 1000);
+        assert_eq!(stats.bytes_downloaded, 300);
+        assert_eq!(stats.files_uploaded, 1);
+        assert_eq!(stats.files_downloaded, 0);
+        assert_eq!(stats.active_transfers, 0);
+        assert_eq!(stats.upload_rate, 1000 / THROUGHPUT_WINDOW.as_secs());
+    }
+
+    #[test]
+    fn test_snapshot_reads_registered_gauges() {
+        let registry = Registry::new();
+        let metrics = MetricsRegistry::new(&registry).unwrap();
+        GaugeFn::new(CACHE_HITS, "Hits", || 3.0)
+            .unwrap()
+            .register_in(&registry)
+            .unwrap();
+        GaugeFn::new(CACHE_MISSES, "Misses", || 1.0)
+            .unwrap()
+            .register_in(&registry)
+            .unwrap();
+        metrics.set_queued_sync_paths(2);
+
+        let stats = metrics.snapshot();
+        assert_eq!(stats.cache_hits, 3);
+        assert_eq!(stats.cache_misses, 1);
+        assert_eq!(stats.cache_hit_ratio(), Some(0.75));
+        assert_eq!(stats.throttled_requests, 0);
+        assert_eq!(stats.queued_sync_paths, 2);
+    }
+
+    #[test]
+    fn test_rate_window_forgets_old_samples() {
+        let mut window = RateWindow::default();
+        let start = Instant::now();
+        window.record(start, 5000);
+        window.record(start + Duration::from_secs(5), 1000);
+        assert_eq!(window.rate(start + Duration::from_secs(5)), 600);
+        assert_eq!(window.rate(start + Duration::from_secs(12)), 100);
+        assert_eq!(window.rate(start + Duration::from_secs(30)), 0);
+    }
+}